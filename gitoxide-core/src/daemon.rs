@@ -0,0 +1,179 @@
+//! A read-only server for the `git://` protocol, similar to [`git daemon`](https://git-scm.com/docs/git-daemon).
+//!
+//! Only the `upload-pack` service is supported, see [`gix::serve::upload_pack`] for details.
+use std::{
+    io::{BufWriter, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Component, PathBuf},
+    sync::{atomic::Ordering, mpsc},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context as _};
+use gix::{
+    bstr::{BStr, ByteSlice},
+    protocol::transport::packetline::{encode, PacketLineRef, StreamingPeekableIter},
+    serve::upload_pack,
+};
+
+/// The name of the file in a git directory that marks it as allowed to be served.
+pub const EXPORT_OK_FILE: &str = "git-daemon-export-ok";
+
+pub struct Options {
+    /// The address to listen on.
+    pub listen: SocketAddr,
+    /// If set, all requested paths are relative to this directory.
+    pub base_path: Option<PathBuf>,
+    /// If set, only serve repositories within these directories.
+    pub directories: Vec<PathBuf>,
+    /// If `true`, serve all repositories, not only those containing the [`EXPORT_OK_FILE`].
+    pub export_all: bool,
+    /// If set, close connections that didn't read or write anything for this long.
+    pub timeout: Option<Duration>,
+    /// The maximum amount of connections to serve at the same time. Additional connections are closed right away.
+    pub max_connections: usize,
+}
+
+/// Serve repositories according to `options` until interrupted, and write information about each connection to `err`.
+pub fn serve(options: Options, mut err: impl Write) -> anyhow::Result<()> {
+    let listener =
+        TcpListener::bind(options.listen).with_context(|| format!("Could not listen on {}", options.listen))?;
+    listener.set_nonblocking(true)?;
+    writeln!(err, "Listening on {}", listener.local_addr()?)?;
+
+    let options = std::sync::Arc::new(options);
+    let (send_outcome, outcomes) = mpsc::channel::<(SocketAddr, anyhow::Result<PathBuf>)>();
+    let mut num_connections = 0;
+    while !gix::interrupt::IS_INTERRUPTED.load(Ordering::Relaxed) {
+        for (peer, outcome) in outcomes.try_iter() {
+            num_connections -= 1;
+            match outcome {
+                Ok(path) => writeln!(err, "{peer}: served '{}'", path.display())?,
+                Err(e) => writeln!(err, "{peer}: {e:#}")?,
+            }
+        }
+        let (stream, peer) = match listener.accept() {
+            Ok(connection) => connection,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if num_connections >= options.max_connections {
+            writeln!(err, "{peer}: too many connections")?;
+            continue;
+        }
+        num_connections += 1;
+        std::thread::spawn({
+            let options = options.clone();
+            let send_outcome = send_outcome.clone();
+            move || {
+                send_outcome.send((peer, handle_connection(stream, &options))).ok();
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, options: &Options) -> anyhow::Result<PathBuf> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(options.timeout)?;
+    stream.set_write_timeout(options.timeout)?;
+    let mut reader = StreamingPeekableIter::new(stream.try_clone()?, &[PacketLineRef::Flush], false);
+    let mut out = BufWriter::new(stream);
+
+    let request = reader
+        .read_line()
+        .ok_or_else(|| anyhow!("Connection closed before receiving a request"))???
+        .as_slice()
+        .ok_or_else(|| anyhow!("Expected a request, got a special packet line"))?
+        .to_owned();
+    let (service, path) = parse_request(request.as_bstr())?;
+    if service != "git-upload-pack" {
+        encode::error_to_write(format!("service not enabled: '{service}'").as_bytes(), &mut out)?;
+        out.flush()?;
+        bail!("Refused to run service '{service}'");
+    }
+    let repo = match open_repository(path, options) {
+        Ok(repo) => repo,
+        Err(e) => {
+            encode::error_to_write(
+                format!("access denied or repository not exported: {path}").as_bytes(),
+                &mut out,
+            )?;
+            out.flush()?;
+            return Err(e);
+        }
+    };
+    let advertisement = upload_pack::advertise_refs(&repo, &mut out)?;
+    out.flush()?;
+    upload_pack::run(
+        &repo,
+        &advertisement,
+        reader.into_inner(),
+        &mut out,
+        upload_pack::Options::default(),
+        &gix::interrupt::IS_INTERRUPTED,
+    )?;
+    Ok(repo.path().to_owned())
+}
+
+/// Parse a request like `git-upload-pack /path/to/repo\0host=example.com\0` into the service and the path.
+fn parse_request(request: &BStr) -> anyhow::Result<(&BStr, &BStr)> {
+    let (service, rest) = request
+        .split_once_str(" ")
+        .ok_or_else(|| anyhow!("Invalid request: {request:?}"))?;
+    let path = rest.split_str("\0").next().unwrap_or_default();
+    Ok((service.as_bstr(), path.as_bstr()))
+}
+
+/// Translate the requested `path` into a repository, just like `git daemon` would, and open it if it may be served.
+///
+/// Whether it may be served is determined before opening it, so repositories that aren't allowed are never read.
+fn open_repository(path: &BStr, options: &Options) -> anyhow::Result<gix::Repository> {
+    let path = gix::path::try_from_bstr(path)?;
+    if !path.is_absolute() {
+        bail!("Refusing relative path '{}'", path.display());
+    }
+    if path.components().any(|c| c == Component::ParentDir) {
+        bail!("Refusing path with '..' in it: '{}'", path.display());
+    }
+    let path = match &options.base_path {
+        Some(base) => base.join(path.strip_prefix("/").expect("absolute")),
+        None => path.into_owned(),
+    };
+
+    let is_allowed = |git_dir: &std::path::Path| {
+        options.directories.is_empty()
+            || options
+                .directories
+                .iter()
+                .any(|dir| matches!(gix::path::realpath(dir), Ok(dir) if git_dir.starts_with(&dir)))
+    };
+    let git_dir = ["/.git", "", ".git/.git", ".git"]
+        .iter()
+        .find_map(|suffix| {
+            let mut candidate = path.clone().into_os_string();
+            candidate.push(suffix);
+            let candidate = PathBuf::from(candidate);
+            let git_dir = if candidate.is_file() {
+                gix::discover::path::from_gitdir_file(&candidate).ok()?
+            } else {
+                candidate
+            };
+            let git_dir = gix::path::realpath(git_dir).ok()?;
+            (is_allowed(&git_dir) && gix::discover::is_git(&git_dir).is_ok()).then_some(git_dir)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "No repository at '{}' in any of the allowed directories",
+                path.display()
+            )
+        })?;
+
+    if !options.export_all && !git_dir.join(EXPORT_OK_FILE).is_file() {
+        bail!("'{}' isn't exported", git_dir.display());
+    }
+    Ok(gix::open_opts(&git_dir, gix::open::Options::isolated())?)
+}
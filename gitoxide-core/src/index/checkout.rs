@@ -81,6 +81,7 @@ pub fn checkout_exclusive(
     let checkout::Outcome {
         errors,
         collisions,
+        path_collisions: _,
         files_updated,
         bytes_written,
         delayed_paths_unknown,
@@ -196,6 +197,20 @@ where
     }
 }
 
+impl<Find> gix::objs::FindHeader for EmptyOrDb<Find>
+where
+    Find: gix::objs::FindHeader,
+{
+    fn try_header(&self, id: &gix::oid) -> Result<Option<gix::objs::Header>, Error> {
+        let header = self.db.try_header(id)?;
+        Ok(if self.empty_files {
+            header.map(|header| gix::objs::Header { size: 0, ..header })
+        } else {
+            header
+        })
+    }
+}
+
 #[derive(Clone)]
 struct Empty;
 
@@ -208,3 +223,12 @@ impl gix::objs::Find for Empty {
         }))
     }
 }
+
+impl gix::objs::FindHeader for Empty {
+    fn try_header(&self, _id: &gix::oid) -> Result<Option<gix::objs::Header>, Error> {
+        Ok(Some(gix::objs::Header {
+            kind: gix::object::Kind::Blob,
+            size: 0,
+        }))
+    }
+}
@@ -66,6 +66,8 @@ impl FromStr for OutputFormat {
 pub mod commitgraph;
 #[cfg(feature = "corpus")]
 pub mod corpus;
+#[cfg(feature = "blocking-client")]
+pub mod daemon;
 pub mod net;
 
 #[cfg(feature = "estimate-hours")]
@@ -77,7 +79,11 @@ pub mod organize;
 pub mod pack;
 #[cfg(feature = "query")]
 pub mod query;
+#[cfg(feature = "repo-size")]
+pub mod repo_size;
 pub mod repository;
+#[cfg(feature = "rewrite-history")]
+pub mod rewrite_history;
 
 mod discover;
 pub use discover::discover;
@@ -80,10 +80,25 @@ pub struct Context<W> {
     /// This is a total, shared among all threads if `thread_limit` permits.
     /// Only used when known to be effective, namely when `expansion == ObjectExpansion::TreeDiff`.
     pub object_cache_size_in_bytes: usize,
+    /// If set, keep at most this amount of bytes worth of counted objects in memory, and write them to disk otherwise.
+    /// This allows to create packs for repositories with more objects than would fit into memory.
+    pub counts_memory_limit_in_bytes: Option<usize>,
+    /// If set, ignore all deltas in existing packs and search for new ones instead, which is slower but can produce
+    /// smaller packs.
+    pub delta_search: Option<DeltaSearch>,
     /// The output stream for use of additional information
     pub out: W,
 }
 
+/// Configure the search for new deltas, with all unset values obtained from the repository configuration.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DeltaSearch {
+    /// The amount of objects to try as delta base for each object, or `pack.window` if unset.
+    pub window: Option<usize>,
+    /// The maximum length of delta chains, or `pack.depth` if unset.
+    pub depth: Option<usize>,
+}
+
 pub fn create<W, P>(
     repository_path: impl AsRef<Path>,
     tips: impl IntoIterator<Item = impl AsRef<OsStr>>,
@@ -98,6 +113,8 @@ pub fn create<W, P>(
         statistics,
         pack_cache_size_in_bytes,
         object_cache_size_in_bytes,
+        counts_memory_limit_in_bytes,
+        delta_search,
         mut out,
     }: Context<W>,
 ) -> anyhow::Result<()>
@@ -109,9 +126,11 @@ where
     type ObjectIdIter = dyn Iterator<Item = Result<ObjectId, Box<dyn std::error::Error + Send + Sync>>> + Send;
 
     let repo = gix::discover(repository_path)?.into_sync();
+    let delta_search = delta_search
+        .map(|delta_search| delta_search_options(&repo.to_thread_local(), delta_search, thread_limit))
+        .transpose()?;
     progress.init(Some(2), progress::steps());
     let tips = tips.into_iter();
-    let make_cancellation_err = || anyhow!("Cancelled by user");
     let (mut handle, mut input): (_, Box<ObjectIdIter>) = match input {
         None => {
             let mut progress = progress.add_child("traversing");
@@ -193,50 +212,172 @@ where
         let input_object_expansion = expansion.into();
         handle.prevent_pack_unload();
         handle.ignore_replacements = true;
-        let (mut counts, count_stats) = if may_use_multiple_threads {
-            pack::data::output::count::objects(
-                handle.clone(),
-                input,
-                &progress,
-                &interrupt::IS_INTERRUPTED,
-                pack::data::output::count::objects::Options {
-                    thread_limit,
-                    chunk_size,
-                    input_object_expansion,
+        if let Some(memory_limit_in_bytes) = counts_memory_limit_in_bytes {
+            let mut counts = pack::data::output::count::spill::Counts::new(
+                handle.store_ref().object_hash(),
+                pack::data::output::count::spill::Options {
+                    memory_limit_in_bytes,
+                    directory: output_directory.as_ref().map(|dir| dir.as_ref().to_owned()),
                 },
-            )?
+            );
+            stats.counts = if may_use_multiple_threads {
+                pack::data::output::count::objects_into(
+                    handle.clone(),
+                    input,
+                    &progress,
+                    &interrupt::IS_INTERRUPTED,
+                    pack::data::output::count::objects::Options {
+                        thread_limit,
+                        chunk_size,
+                        input_object_expansion,
+                    },
+                    &mut counts,
+                )?
+            } else {
+                pack::data::output::count::objects_unthreaded_into(
+                    &handle,
+                    &mut input,
+                    &progress,
+                    &interrupt::IS_INTERRUPTED,
+                    input_object_expansion,
+                    &mut counts,
+                )?
+            };
+            Counts::Spilled(counts.finish()?)
         } else {
-            pack::data::output::count::objects_unthreaded(
-                &handle,
-                &mut input,
-                &progress,
-                &interrupt::IS_INTERRUPTED,
-                input_object_expansion,
-            )?
-        };
-        stats.counts = count_stats;
-        counts.shrink_to_fit();
-        counts
+            let (mut counts, count_stats) = if may_use_multiple_threads {
+                pack::data::output::count::objects(
+                    handle.clone(),
+                    input,
+                    &progress,
+                    &interrupt::IS_INTERRUPTED,
+                    pack::data::output::count::objects::Options {
+                        thread_limit,
+                        chunk_size,
+                        input_object_expansion,
+                    },
+                )?
+            } else {
+                pack::data::output::count::objects_unthreaded(
+                    &handle,
+                    &mut input,
+                    &progress,
+                    &interrupt::IS_INTERRUPTED,
+                    input_object_expansion,
+                )?
+            };
+            stats.counts = count_stats;
+            counts.shrink_to_fit();
+            Counts::Memory(counts)
+        }
     };
 
     progress.inc();
-    let num_objects = counts.len();
-    let mut in_order_entries = {
-        let progress = progress.add_child("creating entries");
-        InOrderIter::from(pack::data::output::entry::iter_from_counts(
-            counts,
-            handle,
-            Box::new(progress),
-            pack::data::output::entry::iter_from_counts::Options {
-                thread_limit,
-                mode: pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
-                allow_thin_pack: thin,
-                chunk_size,
-                version: Default::default(),
-            },
-        ))
+    if let Some(options) = delta_search {
+        let ids: Vec<_> = match counts {
+            Counts::Memory(counts) => counts.into_iter().map(|count| count.id).collect(),
+            Counts::Spilled(counts) => (0..counts.len()).map(|idx| counts.get(idx).id).collect(),
+        };
+        let (entries, outcome) = {
+            let mut progress = progress.add_child("searching deltas");
+            pack::data::output::delta::search(handle, ids, &mut progress, &interrupt::IS_INTERRUPTED, options)?
+        };
+        let num_objects = entries.len();
+        write_pack(
+            std::iter::once(Ok::<_, pack::data::output::delta::Error>(entries)),
+            num_objects,
+            output_directory,
+            &mut progress,
+            &mut out,
+        )?;
+        stats.deltas = Some(outcome);
+    } else {
+        let entries_progress = progress.add_child("creating entries");
+        let options = pack::data::output::entry::iter_from_counts::Options {
+            thread_limit,
+            mode: pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+            allow_thin_pack: thin,
+            chunk_size,
+            version: Default::default(),
+        };
+        stats.entries = match counts {
+            Counts::Memory(counts) => {
+                let num_objects = counts.len();
+                let mut entries = InOrderIter::from(pack::data::output::entry::iter_from_counts(
+                    counts,
+                    handle,
+                    Box::new(entries_progress),
+                    options,
+                ));
+                write_pack(entries.by_ref(), num_objects, output_directory, &mut progress, &mut out)?;
+                entries.inner.finalize()?
+            }
+            Counts::Spilled(counts) => {
+                let num_objects = counts.len();
+                let mut entries = InOrderIter::from(pack::data::output::entry::iter_from_sorted_counts(
+                    counts,
+                    handle,
+                    |_| true,
+                    Box::new(entries_progress),
+                    options,
+                ));
+                write_pack(entries.by_ref(), num_objects, output_directory, &mut progress, &mut out)?;
+                entries.inner.finalize()?
+            }
+        };
+    }
+
+    if let Some(format) = statistics {
+        print(stats, format, out)?;
+    }
+    progress.inc();
+    Ok(())
+}
+
+enum Counts {
+    Memory(Vec<pack::data::output::Count>),
+    Spilled(pack::data::output::count::spill::Sorted),
+}
+
+fn delta_search_options(
+    repo: &gix::Repository,
+    DeltaSearch { window, depth }: DeltaSearch,
+    thread_limit: Option<usize>,
+) -> anyhow::Result<pack::data::output::delta::Options> {
+    use gix::config::tree::{Key, Pack};
+    let config = repo.config_snapshot();
+    let defaults = pack::data::output::delta::Options::default();
+    let from_config = |key: &'static gix::config::tree::keys::UnsignedInteger| -> anyhow::Result<Option<usize>> {
+        Ok(config
+            .integer(key.logical_name().as_str())
+            .map(|value| key.try_into_usize(Ok(value)))
+            .transpose()?)
     };
+    Ok(pack::data::output::delta::Options {
+        window: window
+            .map_or_else(|| from_config(&Pack::WINDOW), |window| Ok(Some(window)))?
+            .unwrap_or(defaults.window),
+        depth: depth
+            .map_or_else(|| from_config(&Pack::DEPTH), |depth| Ok(Some(depth)))?
+            .unwrap_or(defaults.depth),
+        thread_limit: match thread_limit {
+            Some(limit) => Some(limit),
+            None => from_config(&Pack::THREADS)?.filter(|threads| *threads != 0),
+        },
+    })
+}
 
+fn write_pack<E>(
+    entries: impl Iterator<Item = Result<Vec<pack::data::output::Entry>, E>>,
+    num_objects: usize,
+    output_directory: Option<impl AsRef<Path>>,
+    progress: &mut impl NestedProgress,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let make_cancellation_err = || anyhow!("Cancelled by user");
     let mut entries_progress = progress.add_child("consuming");
     entries_progress.init(Some(num_objects), progress::count("entries"));
     let mut write_progress = progress.add_child("writing");
@@ -257,7 +398,7 @@ where
     };
     let mut interruptible_output_iter = interrupt::Iter::new(
         pack::data::output::bytes::FromEntriesIter::new(
-            in_order_entries.by_ref().inspect(|e| {
+            entries.inspect(|e| {
                 if let Ok(entries) = e {
                     entries_progress.inc_by(entries.len())
                 }
@@ -284,15 +425,9 @@ where
     } else {
         writeln!(out, "{pack_name}")?;
     }
-    stats.entries = in_order_entries.inner.finalize()?;
 
     write_progress.show_throughput(start);
     entries_progress.show_throughput(start);
-
-    if let Some(format) = statistics {
-        print(stats, format, out)?;
-    }
-    progress.inc();
     Ok(())
 }
 
@@ -320,6 +455,7 @@ fn human_output(
                 objects_copied_from_pack,
                 ref_delta_objects,
             },
+        deltas,
     }: Statistics,
     mut out: impl std::io::Write,
 ) -> std::io::Result<()> {
@@ -335,6 +471,24 @@ fn human_output(
         "total objects", total_objects,
         width = width
     )?;
+    if let Some(pack::data::output::delta::Outcome {
+        base_objects,
+        delta_objects,
+        max_chain_length,
+    }) = deltas
+    {
+        writeln!(out, "delta search phase")?;
+        #[rustfmt::skip]
+        writeln!(
+            out,
+            "\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}",
+            "base objects", base_objects,
+            "delta objects", delta_objects,
+            "longest delta chain", max_chain_length,
+            width = width
+        )?;
+        return Ok(());
+    }
     writeln!(out, "generation phase")?;
     #[rustfmt::skip]
     writeln!(
@@ -354,6 +508,7 @@ fn human_output(
 struct Statistics {
     counts: pack::data::output::count::objects::Outcome,
     entries: pack::data::output::entry::iter_from_counts::Outcome,
+    deltas: Option<pack::data::output::delta::Outcome>,
 }
 
 pub mod input_iteration {
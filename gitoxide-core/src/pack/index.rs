@@ -51,6 +51,8 @@ pub struct Context<'a, W: io::Write> {
     pub should_interrupt: &'a AtomicBool,
     pub out: W,
     pub object_hash: gix::hash::Kind,
+    /// If set, the path to a repository to obtain base objects from if the pack is thin.
+    pub fix_thin: Option<PathBuf>,
 }
 
 pub fn stream_len(mut s: impl io::Seek) -> io::Result<u64> {
@@ -82,7 +84,22 @@ pub fn from_pack(
         iteration_mode: ctx.iteration_mode.into(),
         index_version: pack::index::Version::default(),
         object_hash: ctx.object_hash,
+        fsync_pack: None,
+        fsync_index: None,
     };
+    let thin_pack_base_objects = ctx
+        .fix_thin
+        .map(|repo| {
+            gix::open(&repo)
+                .map(|repo| repo.into_sync().objects.into_shared_arc().to_cache_arc())
+                .with_context(|| {
+                    format!(
+                        "Could not open repository at '{}' to resolve thin packs",
+                        repo.display()
+                    )
+                })
+        })
+        .transpose()?;
     let out = ctx.out;
     let format = ctx.format;
     let res = match pack {
@@ -95,7 +112,7 @@ pub fn from_pack(
                 directory,
                 &mut progress,
                 ctx.should_interrupt,
-                None::<gix::objs::find::Never>,
+                thin_pack_base_objects,
                 options,
             )
         }
@@ -105,7 +122,7 @@ pub fn from_pack(
             directory,
             &mut progress,
             ctx.should_interrupt,
-            None::<gix::objs::find::Never>,
+            thin_pack_base_objects,
             options,
         ),
     }
@@ -2,7 +2,7 @@ use anyhow::Context;
 use rusqlite::{params, OptionalExtension};
 
 /// A version to be incremented whenever the database layout is changed, to refresh it automatically.
-const VERSION: usize = 1;
+const VERSION: usize = 2;
 
 pub fn create(path: impl AsRef<std::path::Path>) -> anyhow::Result<rusqlite::Connection> {
     let path = path.as_ref();
@@ -32,7 +32,10 @@ pub fn create(path: impl AsRef<std::path::Path>) -> anyhow::Result<rusqlite::Con
     con.execute_batch(
         r#"
         CREATE TABLE if not exists commits(
-            hash blob(20) NOT NULL PRIMARY KEY
+            hash blob(20) NOT NULL PRIMARY KEY,
+            author_name text,
+            author_email text,
+            author_time integer
         )
         "#,
     )?;
@@ -49,7 +52,7 @@ pub fn create(path: impl AsRef<std::path::Path>) -> anyhow::Result<rusqlite::Con
         r#"
         CREATE TABLE if not exists commit_file(
             hash blob(20),
-            file_id text,
+            file_id integer,
             has_diff boolean NOT NULL,
             lines_added integer NOT NULL,
             lines_removed integer NOT NULL,
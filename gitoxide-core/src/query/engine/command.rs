@@ -123,6 +123,15 @@ impl query::Engine {
                 }
                 Ok(())
             }
+            Command::FileChurn { window, limit } => self.file_churn(window, limit, out),
+            Command::AuthorActivity { window, interval } => self.author_activity(window, interval, out),
+            Command::BusFactor { window, depth } => self.bus_factor(window, depth, out),
+            Command::CoChange {
+                window,
+                min_shared_commits,
+                max_files_per_commit,
+                limit,
+            } => self.co_change(window, min_shared_commits, max_files_per_commit, limit, out),
         }
     }
 }
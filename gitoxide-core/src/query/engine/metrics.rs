@@ -0,0 +1,300 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use rusqlite::params;
+
+use crate::query::{self, Interval, Window};
+
+/// A commit reachable from `HEAD` within a time window, along with the files it changed.
+struct Commit {
+    /// The author with the mailmap applied, formatted as `Name <email>`.
+    author: String,
+    /// The time at which the commit was authored.
+    time: gix::date::SecondsSinceUnixEpoch,
+    changes: Vec<Change>,
+}
+
+struct Change {
+    file_id: usize,
+    lines_added: usize,
+    lines_removed: usize,
+}
+
+impl query::Engine {
+    pub(super) fn file_churn(&self, window: Window, limit: usize, mut out: impl std::io::Write) -> anyhow::Result<()> {
+        #[derive(Default)]
+        struct Churn<'a> {
+            commits: usize,
+            lines_added: usize,
+            lines_removed: usize,
+            authors: HashSet<&'a str>,
+        }
+        let commits = self.commits_in(window)?;
+        let mut by_file_id = HashMap::<usize, Churn<'_>>::new();
+        for commit in &commits {
+            for change in &commit.changes {
+                let churn = by_file_id.entry(change.file_id).or_default();
+                churn.commits += 1;
+                churn.lines_added += change.lines_added;
+                churn.lines_removed += change.lines_removed;
+                churn.authors.insert(&commit.author);
+            }
+        }
+
+        let paths = self.paths()?;
+        let mut churn: Vec<_> = by_file_id.into_iter().collect();
+        churn.sort_by(|(a_id, a), (b_id, b)| {
+            (b.lines_added + b.lines_removed)
+                .cmp(&(a.lines_added + a.lines_removed))
+                .then_with(|| paths[a_id].cmp(&paths[b_id]))
+        });
+        writeln!(
+            out,
+            "{:>8} {:>8} {:>7} {:>7}  path",
+            "added", "removed", "commits", "authors"
+        )?;
+        for (file_id, churn) in churn.into_iter().take(limit) {
+            writeln!(
+                out,
+                "{:>8} {:>8} {:>7} {:>7}  {}",
+                churn.lines_added,
+                churn.lines_removed,
+                churn.commits,
+                churn.authors.len(),
+                paths[&file_id]
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn author_activity(
+        &self,
+        window: Window,
+        interval: Interval,
+        mut out: impl std::io::Write,
+    ) -> anyhow::Result<()> {
+        const MAX_BAR_WIDTH: usize = 40;
+        let commits = self.commits_in(window)?;
+        let mut by_author = HashMap::<&str, BTreeMap<String, usize>>::new();
+        for commit in &commits {
+            *by_author
+                .entry(&commit.author)
+                .or_default()
+                .entry(interval.bucket(commit.time))
+                .or_default() += 1;
+        }
+
+        let max_count = by_author
+            .values()
+            .flat_map(|buckets| buckets.values().copied())
+            .max()
+            .unwrap_or_default();
+        let mut by_author: Vec<_> = by_author
+            .into_iter()
+            .map(|(author, buckets)| (author, buckets.values().sum::<usize>(), buckets))
+            .collect();
+        by_author.sort_by(|(a, a_commits, _), (b, b_commits, _)| b_commits.cmp(a_commits).then_with(|| a.cmp(b)));
+        for (author, num_commits, buckets) in by_author {
+            writeln!(out, "{num_commits:>6} {author}")?;
+            for (bucket, count) in buckets {
+                let width = (count * MAX_BAR_WIDTH + max_count - 1) / max_count;
+                writeln!(out, "       {bucket:<10} {:<MAX_BAR_WIDTH$} {count}", "*".repeat(width))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn bus_factor(&self, window: Window, depth: usize, mut out: impl std::io::Write) -> anyhow::Result<()> {
+        let commits = self.commits_in(window)?;
+        let paths = self.paths()?;
+        let mut lines_by_dir = HashMap::<&str, HashMap<&str, usize>>::new();
+        for commit in &commits {
+            for change in &commit.changes {
+                let lines = change.lines_added + change.lines_removed;
+                if lines == 0 {
+                    continue;
+                }
+                *lines_by_dir
+                    .entry(directory(&paths[&change.file_id], depth))
+                    .or_default()
+                    .entry(&commit.author)
+                    .or_default() += lines;
+            }
+        }
+
+        let mut dirs: Vec<_> = lines_by_dir
+            .into_iter()
+            .map(|(dir, lines_by_author)| {
+                let mut lines_by_author: Vec<_> = lines_by_author.into_iter().collect();
+                lines_by_author.sort_by(|(a, a_lines), (b, b_lines)| b_lines.cmp(a_lines).then_with(|| a.cmp(b)));
+                let total: usize = lines_by_author.iter().map(|(_, lines)| lines).sum();
+                let mut covered = 0;
+                let bus_factor = lines_by_author
+                    .iter()
+                    .take_while(|(_, lines)| {
+                        let is_below_half = covered * 2 < total;
+                        covered += lines;
+                        is_below_half
+                    })
+                    .count();
+                let (top_author, top_lines) = lines_by_author[0];
+                (dir, bus_factor, total, top_author, top_lines * 100 / total)
+            })
+            .collect();
+        dirs.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| b.2.cmp(&a.2)).then_with(|| a.0.cmp(b.0)));
+        writeln!(out, "{:>3} {:>8}  directory (top author)", "bus", "lines")?;
+        for (dir, bus_factor, total, top_author, top_percent) in dirs {
+            writeln!(out, "{bus_factor:>3} {total:>8}  {dir} ({top_author} {top_percent}%)")?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn co_change(
+        &self,
+        window: Window,
+        min_shared_commits: usize,
+        max_files_per_commit: usize,
+        limit: usize,
+        mut out: impl std::io::Write,
+    ) -> anyhow::Result<()> {
+        let commits = self.commits_in(window)?;
+        let mut revisions = HashMap::<usize, usize>::new();
+        let mut shared_commits = HashMap::<(usize, usize), usize>::new();
+        let mut file_ids = Vec::new();
+        for commit in &commits {
+            file_ids.clear();
+            file_ids.extend(commit.changes.iter().map(|change| change.file_id));
+            file_ids.sort_unstable();
+            file_ids.dedup();
+            if file_ids.len() > max_files_per_commit {
+                continue;
+            }
+            for (idx, a) in file_ids.iter().enumerate() {
+                *revisions.entry(*a).or_default() += 1;
+                for b in &file_ids[idx + 1..] {
+                    *shared_commits.entry((*a, *b)).or_default() += 1;
+                }
+            }
+        }
+
+        let paths = self.paths()?;
+        let mut pairs: Vec<_> = shared_commits
+            .into_iter()
+            .filter(|(_, shared)| *shared >= min_shared_commits)
+            .map(|((a, b), shared)| {
+                // The degree of coupling is the percentage of shared commits relative to the average amount of commits.
+                let degree = shared * 200 / (revisions[&a] + revisions[&b]);
+                (&paths[&a], &paths[&b], shared, degree)
+            })
+            .collect();
+        pairs.sort_by(|a, b| {
+            b.2.cmp(&a.2)
+                .then_with(|| b.3.cmp(&a.3))
+                .then_with(|| a.0.cmp(b.0))
+                .then_with(|| a.1.cmp(b.1))
+        });
+        writeln!(out, "{:>6} {:>7}  paths", "shared", "degree")?;
+        for (a, b, shared, degree) in pairs.into_iter().take(limit) {
+            writeln!(out, "{shared:>6} {degree:>6}%  {a} {b}")?;
+        }
+        Ok(())
+    }
+
+    /// Return all commits reachable from `HEAD` that were authored within `window`, with the files they changed.
+    /// Note that merge commits are included, but don't have changes.
+    fn commits_in(&self, window: Window) -> anyhow::Result<Vec<Commit>> {
+        let since = window
+            .since
+            .map_or(gix::date::SecondsSinceUnixEpoch::MIN, |t| t.seconds);
+        let until = window
+            .until
+            .map_or(gix::date::SecondsSinceUnixEpoch::MAX, |t| t.seconds);
+        let reachable: HashSet<_> = self.commits.iter().collect();
+        let mailmap = self.repo.open_mailmap();
+
+        let mut commits = Vec::new();
+        let mut idx_by_id = HashMap::new();
+        let mut by_time = self.con.prepare(
+            "SELECT hash, author_name, author_email, author_time FROM commits WHERE author_time BETWEEN ? AND ?",
+        )?;
+        let rows = by_time.query_map(params![since, until], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?))
+        })?;
+        for row in rows {
+            let (hash, name, email, time): ([u8; 20], String, String, gix::date::SecondsSinceUnixEpoch) = row?;
+            let id = gix::ObjectId::from(hash);
+            if !reachable.contains(&id) {
+                continue;
+            }
+            let author = mailmap.resolve(gix::actor::SignatureRef {
+                name: name.as_str().into(),
+                email: email.as_str().into(),
+                time: Default::default(),
+            });
+            idx_by_id.insert(id, commits.len());
+            commits.push(Commit {
+                author: format!("{} <{}>", author.name, author.email),
+                time,
+                changes: Vec::new(),
+            });
+        }
+
+        let mut changes_by_time = self.con.prepare(
+            r#"SELECT commit_file.hash, file_id, lines_added, lines_removed
+               FROM commit_file JOIN commits ON commits.hash = commit_file.hash
+               WHERE author_time BETWEEN ? AND ?"#,
+        )?;
+        let rows = changes_by_time.query_map(params![since, until], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?))
+        })?;
+        for row in rows {
+            let (hash, file_id, lines_added, lines_removed): ([u8; 20], usize, usize, usize) = row?;
+            if let Some(idx) = idx_by_id.get(&gix::ObjectId::from(hash)) {
+                commits[*idx].changes.push(Change {
+                    file_id,
+                    lines_added,
+                    lines_removed,
+                });
+            }
+        }
+        Ok(commits)
+    }
+
+    fn paths(&self) -> anyhow::Result<HashMap<usize, String>> {
+        let mut all_paths = self.con.prepare("SELECT file_id, file_path FROM files")?;
+        let paths = all_paths
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        Ok(paths)
+    }
+}
+
+impl Interval {
+    /// Return the name of the bucket that `time` falls into, which sorts in chronological order.
+    fn bucket(&self, time: gix::date::SecondsSinceUnixEpoch) -> String {
+        const DAY: gix::date::SecondsSinceUnixEpoch = 24 * 60 * 60;
+        let day = |seconds| gix::date::Time::new(seconds, 0).format(gix::date::time::format::SHORT);
+        match self {
+            Interval::Day => day(time),
+            Interval::Week => {
+                // The unix epoch is on a Thursday, three days after the start of its week.
+                let days = time.div_euclid(DAY);
+                day((days - (days + 3).rem_euclid(7)) * DAY)
+            }
+            Interval::Month => day(time)[.."YYYY-MM".len()].to_owned(),
+            Interval::Year => day(time)[.."YYYY".len()].to_owned(),
+        }
+    }
+}
+
+/// Return the directory made up of the first `depth` components of the directory containing `path`, or `.` if there is none.
+fn directory(path: &str, depth: usize) -> &str {
+    let dir_end = path.rfind('/').unwrap_or(0);
+    let end = path[..dir_end]
+        .match_indices('/')
+        .nth(depth.saturating_sub(1))
+        .map_or(dir_end, |(idx, _)| idx);
+    match &path[..if depth == 0 { 0 } else { end }] {
+        "" => ".",
+        dir => dir,
+    }
+}
@@ -3,6 +3,50 @@ pub enum Command {
         /// The repo-relative path to the file to trace
         spec: gix::pathspec::Pattern,
     },
+    /// List the paths with the most changed lines, along with the amount of commits and authors that changed them.
+    FileChurn {
+        window: Window,
+        /// The maximum amount of paths to list.
+        limit: usize,
+    },
+    /// Show a histogram of commits per author, bucketed by `interval`.
+    AuthorActivity { window: Window, interval: Interval },
+    /// For each directory, show the smallest amount of authors who together changed at least half of its lines.
+    BusFactor {
+        window: Window,
+        /// The amount of leading path components that make up a directory, with files in shallower directories
+        /// being counted towards their parent directory.
+        depth: usize,
+    },
+    /// List pairs of paths that are frequently changed in the same commit.
+    CoChange {
+        window: Window,
+        /// The minimum amount of commits a pair of paths has to share to be listed.
+        min_shared_commits: usize,
+        /// Ignore commits changing more than this amount of paths, as they would couple unrelated paths.
+        max_files_per_commit: usize,
+        /// The maximum amount of pairs to list.
+        limit: usize,
+    },
+}
+
+/// The time window of commits to consider, based on the time at which they were authored.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Window {
+    /// If set, ignore commits authored before this time.
+    pub since: Option<gix::date::Time>,
+    /// If set, ignore commits authored after this time.
+    pub until: Option<gix::date::Time>,
+}
+
+/// The size of the buckets of an activity histogram.
+#[derive(Debug, Copy, Clone)]
+pub enum Interval {
+    Day,
+    /// Weeks start on Monday.
+    Week,
+    Month,
+    Year,
 }
 
 pub(crate) mod update;
@@ -10,3 +54,4 @@ pub(crate) mod update;
 pub use update::update;
 
 mod command;
+mod metrics;
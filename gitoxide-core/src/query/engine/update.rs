@@ -68,6 +68,8 @@ pub fn update(
         struct CommitDiffStats {
             /// The id of the commit which was diffed with its predecessor
             id: gix::hash::ObjectId,
+            /// The author of the commit, if it could be decoded.
+            author: Option<Author>,
             changes: Vec<FileChange>,
         }
         let start = Instant::now();
@@ -98,8 +100,13 @@ pub fn update(
                         mut insert_file_path,
                     } = Updates::new(&trans)?;
                     for stats in InOrderIter::from(rx_stats.into_iter()) {
-                        for CommitDiffStats { id, changes } in stats.expect("infallible") {
-                            new_commit.execute(params![id.as_bytes()])?;
+                        for CommitDiffStats { id, author, changes } in stats.expect("infallible") {
+                            new_commit.execute(params![
+                                id.as_bytes(),
+                                author.as_ref().map(|a| a.name.to_str_lossy()),
+                                author.as_ref().map(|a| a.email.to_str_lossy()),
+                                author.as_ref().map(|a| a.time),
+                            ])?;
                             for change in changes {
                                 insert_file_path.execute(params![change.relpath.to_str_lossy()])?;
                                 let (has_diff, lines) = change.lines.map(|l| (true, l)).unwrap_or_default();
@@ -189,6 +196,17 @@ pub fn update(
                                         return Ok(());
                                     }
                                     let mut out = Vec::new();
+                                    let author = repo
+                                        .find_object(commit)
+                                        .ok()
+                                        .and_then(|obj| obj.try_into_commit().ok())
+                                        .and_then(|commit| {
+                                            commit.author().ok().map(|author| Author {
+                                                name: author.name.to_owned(),
+                                                email: author.email.to_owned(),
+                                                time: author.time.seconds,
+                                            })
+                                        });
                                     if compute_stats {
                                         let from = match parent_commit {
                                             Some(id) => {
@@ -309,11 +327,13 @@ pub fn update(
                                             })?;
                                         out_chunk.push(CommitDiffStats {
                                             id: commit,
+                                            author,
                                             changes: out,
                                         });
                                     } else {
                                         out_chunk.push(CommitDiffStats {
                                             id: commit,
+                                            author,
                                             changes: Vec::new(),
                                         })
                                     }
@@ -546,6 +566,15 @@ struct FileChange {
     lines: Option<LineStats>,
 }
 
+/// The author of a commit as it is recorded, without applying the mailmap.
+#[derive(Debug)]
+struct Author {
+    name: BString,
+    email: BString,
+    /// The time at which the commit was authored.
+    time: gix::date::SecondsSinceUnixEpoch,
+}
+
 /// Line statistics for a particular commit.
 #[derive(Debug, Default, Copy, Clone)]
 struct LineStats {
@@ -570,8 +599,8 @@ impl<'a> Updates<'a> {
     fn new(trans: &'a Transaction<'_>) -> rusqlite::Result<Self> {
         let new_commit = trans.prepare(
             r#"INSERT INTO
-               commits(hash)
-               VALUES(?)"#,
+               commits(hash, author_name, author_email, author_time)
+               VALUES(?, ?, ?, ?)"#,
         )?;
         let insert_commit_file = trans.prepare(
             r#"
@@ -13,7 +13,7 @@ pub struct Options {
 mod db;
 
 mod engine;
-pub use engine::Command;
+pub use engine::{Command, Interval, Window};
 
 pub fn prepare(
     repo_dir: &std::path::Path,
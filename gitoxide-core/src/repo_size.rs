@@ -0,0 +1,616 @@
+//! Analyze what takes up space in a repository, similar to [git-sizer](https://github.com/github/git-sizer).
+//!
+//! All sizes are the sizes of objects after decompression and delta-resolution, as that's what is relevant when checking out
+//! or processing the objects, even though their representation in packs is typically much smaller.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    io,
+    sync::atomic::Ordering,
+};
+
+use anyhow::bail;
+use bytesize::ByteSize;
+use gix::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    odb::HeaderExt,
+    prelude::ObjectIdExt,
+    Count, NestedProgress, ObjectId, Progress,
+};
+
+use crate::OutputFormat;
+
+/// Additional configuration for the repository size analysis.
+pub struct Options {
+    /// The way to display the report.
+    pub format: OutputFormat,
+    /// The amount of threads to use when looking up object headers. If unset, use all cores, if 0 use all physical cores.
+    pub threads: Option<usize>,
+    /// The maximum amount of entries in each of the lists of largest objects, paths and extensions.
+    pub limit: usize,
+    /// If `true`, also determine how much data is reachable from each reference, and how much of it is only
+    /// reachable from it.
+    ///
+    /// This is expensive as the history of each reference is traversed individually.
+    pub refs: bool,
+}
+
+/// Statistics about all objects of a single kind.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KindStatistics {
+    /// The amount of objects of this kind.
+    pub count: usize,
+    /// The sum of the sizes of all objects of this kind.
+    pub size: u64,
+    /// The size of the largest object of this kind.
+    pub max_size: u64,
+}
+
+/// Statistics about all objects in the object database, whether they are reachable or not.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ObjectStatistics {
+    /// All commits.
+    pub commits: KindStatistics,
+    /// All trees.
+    pub trees: KindStatistics,
+    /// All blobs.
+    pub blobs: KindStatistics,
+    /// All annotated tags.
+    pub tags: KindStatistics,
+}
+
+/// One of the largest objects of its kind.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct LargestObject {
+    /// The id of the object.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "as_str::id"))]
+    pub id: ObjectId,
+    /// The size of the object in bytes.
+    pub size: u64,
+    /// The first path at which the object was encountered in history, or `None` if it's unreachable
+    /// or the root tree of a commit.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "as_str::optional"))]
+    pub path: Option<BString>,
+}
+
+/// The sizes of all versions of a path in history.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct PathStatistics {
+    /// The path relative to the repository root.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "as_str::display"))]
+    pub path: BString,
+    /// The amount of distinct blobs that were stored at this path.
+    pub versions: usize,
+    /// The sum of the sizes of all versions, which is how much the path contributed to the growth of the repository.
+    pub total_size: u64,
+    /// The size of the largest version.
+    pub max_size: u64,
+}
+
+/// The accumulated sizes of all paths with a particular file extension.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct ExtensionStatistics {
+    /// The extension without leading dot, or empty if paths didn't have an extension.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "as_str::display"))]
+    pub extension: BString,
+    /// The amount of distinct blobs stored at paths with this extension.
+    pub versions: usize,
+    /// The sum of the sizes of all these blobs.
+    pub total_size: u64,
+}
+
+/// How much data is attributed to a reference.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct RefStatistics {
+    /// The full name of the reference.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "as_str::display"))]
+    pub name: BString,
+    /// The amount of objects reachable from the reference.
+    pub reachable_objects: usize,
+    /// The sum of the sizes of all objects reachable from the reference.
+    pub reachable_size: u64,
+    /// The amount of objects that are reachable only from this reference, and which would be removed along with it.
+    pub unique_objects: usize,
+    /// The sum of sizes of all objects that are only reachable from this reference.
+    pub unique_size: u64,
+}
+
+/// The result of [`report()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Statistics about all objects in the object database.
+    pub objects: ObjectStatistics,
+    /// The largest blobs, largest first.
+    pub largest_blobs: Vec<LargestObject>,
+    /// The largest trees, largest first.
+    pub largest_trees: Vec<LargestObject>,
+    /// The paths with the largest accumulated size of all of their versions, largest first.
+    pub paths: Vec<PathStatistics>,
+    /// File extensions with the largest accumulated size, largest first.
+    pub extensions: Vec<ExtensionStatistics>,
+    /// The data attributed to each reference, with the most unique data first, or empty if [`Options::refs`] wasn't set.
+    pub refs: Vec<RefStatistics>,
+}
+
+/// Serialize ids and paths as strings instead of arrays of bytes.
+#[cfg(feature = "serde")]
+mod as_str {
+    use gix::bstr::{BString, ByteSlice};
+
+    pub fn display<S: serde::Serializer>(value: &impl std::fmt::Display, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn id<S: serde::Serializer>(id: &gix::ObjectId, serializer: S) -> Result<S::Ok, S::Error> {
+        display(id, serializer)
+    }
+
+    pub fn optional<S: serde::Serializer>(value: &Option<BString>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&value.to_str_lossy()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Analyze the objects and history of `repo` and write a report in the desired format to `out`.
+pub fn show(
+    repo: gix::Repository,
+    progress: impl NestedProgress,
+    mut out: impl io::Write,
+    options: Options,
+) -> anyhow::Result<()> {
+    let format = options.format;
+    let report = report(repo, progress, options)?;
+    match format {
+        OutputFormat::Human => write_human(&report, &mut out)?,
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => serde_json::to_writer_pretty(out, &report)?,
+    }
+    Ok(())
+}
+
+/// Analyze the objects and history of `repo` and return the [`Report`].
+///
+/// Object headers are looked up in parallel, and history is traversed starting at all references, which are peeled
+/// to the object they point to.
+///
+/// ### Shortcomings
+///
+/// * Trees are only traversed once, so if the same tree is present at multiple paths, only the first path
+///   receives the size of its blobs.
+pub fn report(repo: gix::Repository, mut progress: impl NestedProgress, options: Options) -> anyhow::Result<Report> {
+    let start = std::time::Instant::now();
+    let scan = {
+        let mut progress = progress.add_child("counting objects");
+        let scan = scan_objects(&repo, &mut progress, options.threads, options.limit)?;
+        progress.show_throughput(start);
+        scan
+    };
+
+    let wanted_paths: HashSet<ObjectId> = scan
+        .largest_blobs
+        .iter()
+        .chain(scan.largest_trees.iter())
+        .map(|Reverse((_, id))| *id)
+        .collect();
+    let mut first_path = HashMap::<ObjectId, BString>::new();
+    let mut paths = HashMap::<BString, HashSet<ObjectId>>::new();
+    let tips = ref_tips(&repo)?;
+
+    {
+        let mut progress = progress.add_child("traversing history");
+        progress.init(None, gix::progress::count("objects"));
+        let start = std::time::Instant::now();
+        traverse(&repo, tips.iter().map(|(_, id)| *id), |id, kind, path| {
+            progress.inc();
+            let Some(path) = path else { return };
+            if wanted_paths.contains(&id) {
+                first_path.entry(id).or_insert_with(|| path.to_owned());
+            }
+            if kind != gix::object::Kind::Blob {
+                return;
+            }
+            if let Some(blobs) = paths.get_mut(path) {
+                blobs.insert(id);
+            } else {
+                paths.entry(path.to_owned()).or_default().insert(id);
+            }
+        })?;
+        progress.show_throughput(start);
+    }
+
+    let size_of = |id: &ObjectId| scan.sizes.get(id).copied().unwrap_or_default();
+    let mut extensions = HashMap::<BString, (usize, u64)>::new();
+    let mut paths: Vec<_> = paths
+        .into_iter()
+        .map(|(path, blobs)| {
+            let (total_size, max_size) = blobs
+                .iter()
+                .map(size_of)
+                .fold((0, 0), |(total, max), size| (total + size, max.max(size)));
+            let ext = extensions.entry(extension(path.as_ref()).into()).or_default();
+            ext.0 += blobs.len();
+            ext.1 += total_size;
+            PathStatistics {
+                path,
+                versions: blobs.len(),
+                total_size,
+                max_size,
+            }
+        })
+        .collect();
+    paths.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.path.cmp(&b.path)));
+    paths.truncate(options.limit);
+
+    let mut extensions: Vec<_> = extensions
+        .into_iter()
+        .map(|(extension, (versions, total_size))| ExtensionStatistics {
+            extension,
+            versions,
+            total_size,
+        })
+        .collect();
+    extensions.sort_by(|a, b| {
+        b.total_size
+            .cmp(&a.total_size)
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+    extensions.truncate(options.limit);
+
+    let refs = if options.refs {
+        let mut progress = progress.add_child("attributing objects to references");
+        progress.init(Some(tips.len()), gix::progress::count("references"));
+        let reachable_by_ref = tips
+            .iter()
+            .map(|(_, tip)| -> anyhow::Result<_> {
+                let mut reachable = HashSet::new();
+                traverse(&repo, Some(*tip), |id, _kind, _path| {
+                    reachable.insert(id);
+                })?;
+                progress.inc();
+                Ok(reachable)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut reachable_count = HashMap::<ObjectId, usize>::new();
+        for id in reachable_by_ref.iter().flatten() {
+            *reachable_count.entry(*id).or_default() += 1;
+        }
+        let mut refs: Vec<_> = tips
+            .into_iter()
+            .zip(reachable_by_ref)
+            .map(|((name, _), reachable)| {
+                let mut stats = RefStatistics {
+                    name,
+                    reachable_objects: reachable.len(),
+                    reachable_size: 0,
+                    unique_objects: 0,
+                    unique_size: 0,
+                };
+                for id in &reachable {
+                    let size = size_of(id);
+                    stats.reachable_size += size;
+                    if reachable_count[id] == 1 {
+                        stats.unique_objects += 1;
+                        stats.unique_size += size;
+                    }
+                }
+                stats
+            })
+            .collect();
+        refs.sort_by(|a, b| b.unique_size.cmp(&a.unique_size).then_with(|| a.name.cmp(&b.name)));
+        refs
+    } else {
+        Vec::new()
+    };
+
+    let into_largest = |heap: BinaryHeap<Reverse<(u64, ObjectId)>>, first_path: &mut HashMap<ObjectId, BString>| {
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((size, id))| LargestObject {
+                id,
+                size,
+                path: first_path.remove(&id),
+            })
+            .collect()
+    };
+    Ok(Report {
+        objects: scan.objects,
+        largest_blobs: into_largest(scan.largest_blobs, &mut first_path),
+        largest_trees: into_largest(scan.largest_trees, &mut first_path),
+        paths,
+        extensions,
+        refs,
+    })
+}
+
+struct Scan {
+    objects: ObjectStatistics,
+    sizes: HashMap<ObjectId, u64>,
+    largest_blobs: BinaryHeap<Reverse<(u64, ObjectId)>>,
+    largest_trees: BinaryHeap<Reverse<(u64, ObjectId)>>,
+}
+
+impl Scan {
+    fn new() -> Self {
+        Scan {
+            objects: Default::default(),
+            sizes: Default::default(),
+            largest_blobs: Default::default(),
+            largest_trees: Default::default(),
+        }
+    }
+
+    fn consume(&mut self, id: ObjectId, kind: gix::object::Kind, size: u64, limit: usize) {
+        use gix::object::Kind::*;
+        let (stats, largest) = match kind {
+            Commit => (&mut self.objects.commits, None),
+            Tree => (&mut self.objects.trees, Some(&mut self.largest_trees)),
+            Blob => (&mut self.objects.blobs, Some(&mut self.largest_blobs)),
+            Tag => (&mut self.objects.tags, None),
+        };
+        stats.count += 1;
+        stats.size += size;
+        stats.max_size = stats.max_size.max(size);
+        if let Some(largest) = largest.filter(|_| limit != 0) {
+            largest.push(Reverse((size, id)));
+            if largest.len() > limit {
+                largest.pop();
+            }
+        }
+        self.sizes.insert(id, size);
+    }
+}
+
+struct Reduce {
+    scan: Scan,
+    limit: usize,
+}
+
+impl gix::parallel::Reduce for Reduce {
+    type Input = Result<Vec<(ObjectId, gix::object::Kind, u64)>, anyhow::Error>;
+    type FeedProduce = ();
+    type Output = Scan;
+    type Error = anyhow::Error;
+
+    fn feed(&mut self, items: Self::Input) -> Result<Self::FeedProduce, Self::Error> {
+        for (id, kind, size) in items? {
+            self.scan.consume(id, kind, size, self.limit);
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.scan)
+    }
+}
+
+fn scan_objects(
+    repo: &gix::Repository,
+    progress: &mut impl Progress,
+    threads: Option<usize>,
+    limit: usize,
+) -> anyhow::Result<Scan> {
+    progress.init(None, gix::progress::count("objects"));
+    let counter = progress.counter();
+    let cancelled = || anyhow::anyhow!("Cancelled by user");
+    let object_ids = repo.objects.iter()?;
+    gix::parallel::in_parallel(
+        gix::interrupt::Iter::new(
+            gix::features::iter::Chunks {
+                inner: object_ids,
+                size: 1_000,
+            },
+            cancelled,
+        ),
+        threads,
+        {
+            let objects = repo.objects.clone();
+            move |_| (objects.clone().into_inner(), counter)
+        },
+        |ids, (handle, counter)| {
+            let ids = ids?;
+            counter.fetch_add(ids.len(), Ordering::Relaxed);
+            ids.into_iter()
+                .map(|id| {
+                    let id = id?;
+                    let header = handle.header(id)?;
+                    Ok((id, header.kind(), header.size()))
+                })
+                .collect()
+        },
+        Reduce {
+            scan: Scan::new(),
+            limit,
+        },
+    )
+}
+
+/// Return the names of all references along with the id of the object they point to, with annotated tags not peeled.
+fn ref_tips(repo: &gix::Repository) -> anyhow::Result<Vec<(BString, ObjectId)>> {
+    let mut out = Vec::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(|err| anyhow::anyhow!("{err}"))?;
+        // Symbolic references point to other references, which are already part of the list.
+        let Some(id) = reference.try_id() else {
+            continue;
+        };
+        out.push((reference.name().as_bstr().to_owned(), id.detach()));
+    }
+    Ok(out)
+}
+
+/// Traverse all objects reachable from `tips` and call `visit` for each of them, along with their path if they are
+/// a blob or a tree that isn't the root tree of a commit.
+///
+/// Each tree is only visited once, while blobs are visited each time they are encountered in a tree.
+fn traverse(
+    repo: &gix::Repository,
+    tips: impl IntoIterator<Item = ObjectId>,
+    mut visit: impl FnMut(ObjectId, gix::object::Kind, Option<&BStr>),
+) -> anyhow::Result<()> {
+    let mut seen_trees = HashSet::new();
+    let mut commits = Vec::new();
+    let mut trees = Vec::<(ObjectId, BString)>::new();
+    for tip in tips {
+        let mut object = tip.attach(repo).object()?;
+        while object.kind == gix::object::Kind::Tag {
+            visit(object.id, object.kind, None);
+            let target = object.to_tag_ref().target();
+            object = target.attach(repo).object()?;
+        }
+        match object.kind {
+            gix::object::Kind::Commit => commits.push(object.id),
+            gix::object::Kind::Tree => trees.push((object.id, BString::default())),
+            gix::object::Kind::Blob => visit(object.id, object.kind, None),
+            gix::object::Kind::Tag => unreachable!("peeled"),
+        }
+    }
+
+    if !commits.is_empty() {
+        for info in repo.rev_walk(commits).all()? {
+            if gix::interrupt::is_triggered() {
+                bail!("Cancelled by user");
+            }
+            let commit = info?.object()?;
+            visit(commit.id, gix::object::Kind::Commit, None);
+            trees.push((commit.tree_id()?.detach(), BString::default()));
+            traverse_trees(repo, &mut trees, &mut seen_trees, &mut visit)?;
+        }
+    }
+    traverse_trees(repo, &mut trees, &mut seen_trees, &mut visit)
+}
+
+fn traverse_trees(
+    repo: &gix::Repository,
+    trees: &mut Vec<(ObjectId, BString)>,
+    seen_trees: &mut HashSet<ObjectId>,
+    visit: &mut impl FnMut(ObjectId, gix::object::Kind, Option<&BStr>),
+) -> anyhow::Result<()> {
+    let mut path = BString::default();
+    while let Some((tree_id, prefix)) = trees.pop() {
+        if !seen_trees.insert(tree_id) {
+            continue;
+        }
+        visit(
+            tree_id,
+            gix::object::Kind::Tree,
+            (!prefix.is_empty()).then_some(prefix.as_ref()),
+        );
+        let tree = repo.find_object(tree_id)?.try_into_tree()?;
+        for entry in tree.iter() {
+            let entry = entry?;
+            path.clone_from(&prefix);
+            if !path.is_empty() {
+                path.push_byte(b'/');
+            }
+            path.push_str(entry.filename());
+            let mode = entry.mode();
+            if mode.is_tree() {
+                trees.push((entry.oid().to_owned(), path.clone()));
+            } else if mode.is_blob_or_symlink() {
+                visit(entry.oid().to_owned(), gix::object::Kind::Blob, Some(path.as_ref()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn extension(path: &BStr) -> &BStr {
+    let file_name = path.rsplit_str("/").next().unwrap_or(path);
+    match file_name.rfind_byte(b'.') {
+        Some(pos) if pos > 0 => file_name[pos + 1..].as_bstr(),
+        _ => "".into(),
+    }
+}
+
+fn write_human(report: &Report, mut out: impl io::Write) -> io::Result<()> {
+    writeln!(out, "objects")?;
+    for (name, stats) in [
+        ("commits", report.objects.commits),
+        ("trees", report.objects.trees),
+        ("blobs", report.objects.blobs),
+        ("tags", report.objects.tags),
+    ] {
+        writeln!(
+            out,
+            "  {name: <8} {count: >10} {size: >12} (largest {max})",
+            count = stats.count,
+            size = ByteSize(stats.size).to_string(),
+            max = ByteSize(stats.max_size)
+        )?;
+    }
+
+    for (title, objects) in [
+        ("largest blobs", &report.largest_blobs),
+        ("largest trees", &report.largest_trees),
+    ] {
+        if objects.is_empty() {
+            continue;
+        }
+        writeln!(out, "\n{title}")?;
+        for object in objects {
+            writeln!(
+                out,
+                "  {size: >12} {id} {path}",
+                size = ByteSize(object.size).to_string(),
+                id = object.id,
+                path = object.path.as_ref().map_or("".into(), |p| p.as_bstr())
+            )?;
+        }
+    }
+
+    if !report.paths.is_empty() {
+        writeln!(out, "\npaths by total size of all versions")?;
+        for path in &report.paths {
+            writeln!(
+                out,
+                "  {size: >12} {versions: >6} versions (largest {max}) {path}",
+                size = ByteSize(path.total_size).to_string(),
+                versions = path.versions,
+                max = ByteSize(path.max_size),
+                path = path.path
+            )?;
+        }
+    }
+
+    if !report.extensions.is_empty() {
+        writeln!(out, "\nextensions by total size of all versions")?;
+        for ext in &report.extensions {
+            writeln!(
+                out,
+                "  {size: >12} {versions: >6} versions {extension}",
+                size = ByteSize(ext.total_size).to_string(),
+                versions = ext.versions,
+                extension = if ext.extension.is_empty() {
+                    "<none>".into()
+                } else {
+                    ext.extension.as_bstr()
+                }
+            )?;
+        }
+    }
+
+    if !report.refs.is_empty() {
+        writeln!(out, "\nreferences by size of uniquely reachable objects")?;
+        for r in &report.refs {
+            writeln!(
+                out,
+                "  {unique: >12} {unique_objects: >8} unique objects, {reachable} in {reachable_objects} reachable objects {name}",
+                unique = ByteSize(r.unique_size).to_string(),
+                unique_objects = r.unique_objects,
+                reachable = ByteSize(r.reachable_size),
+                reachable_objects = r.reachable_objects,
+                name = r.name
+            )?;
+        }
+    }
+    Ok(())
+}
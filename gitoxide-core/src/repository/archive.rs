@@ -8,6 +8,7 @@ pub struct Options {
     pub files: Vec<(String, String)>,
     pub prefix: Option<String>,
     pub add_paths: Vec<PathBuf>,
+    pub recurse_submodules: bool,
 }
 
 pub fn stream(
@@ -20,6 +21,7 @@ pub fn stream(
         prefix,
         add_paths,
         files,
+        recurse_submodules,
     }: Options,
 ) -> anyhow::Result<()> {
     let format = format.map_or_else(|| format_from_ext(destination_path), Ok)?;
@@ -27,7 +29,11 @@ pub fn stream(
     let (modification_date, tree) = fetch_rev_info(object)?;
 
     let start = std::time::Instant::now();
-    let (mut stream, index) = repo.worktree_stream(tree)?;
+    let (mut stream, index) = if recurse_submodules {
+        repo.worktree_stream_with_submodules(tree)?
+    } else {
+        repo.worktree_stream(tree)?
+    };
     if !add_paths.is_empty() {
         let root = gix::path::realpath(
             repo.work_dir()
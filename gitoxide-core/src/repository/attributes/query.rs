@@ -10,7 +10,6 @@ pub struct Options {
 pub(crate) mod function {
     use std::{borrow::Cow, io, path::Path};
 
-    use anyhow::bail;
     use gix::bstr::BStr;
 
     use crate::{
@@ -29,12 +28,10 @@ pub(crate) mod function {
         mut err: impl io::Write,
         Options { format, statistics }: Options,
     ) -> anyhow::Result<()> {
-        if format != OutputFormat::Human {
-            bail!("JSON output isn't implemented yet");
-        }
-
         let (mut cache, index) = attributes_cache(&repo)?;
         let mut matches = cache.attribute_matches();
+        #[cfg(feature = "serde")]
+        let mut json_matches = Vec::new();
 
         match input {
             PathsOrPatterns::Paths(paths) => {
@@ -48,7 +45,14 @@ pub(crate) mod function {
                     if !entry.matching_attributes(&mut matches) {
                         continue;
                     }
-                    print_match(&matches, path.as_ref(), &mut out)?;
+                    print_match(
+                        &matches,
+                        path.as_ref(),
+                        format,
+                        &mut out,
+                        #[cfg(feature = "serde")]
+                        &mut json_matches,
+                    )?;
                 }
             }
             PathsOrPatterns::Patterns(patterns) => {
@@ -68,7 +72,14 @@ pub(crate) mod function {
                         if !entry.matching_attributes(&mut matches) {
                             continue;
                         }
-                        print_match(&matches, path, &mut out)?;
+                        print_match(
+                            &matches,
+                            path,
+                            format,
+                            &mut out,
+                            #[cfg(feature = "serde")]
+                            &mut json_matches,
+                        )?;
                     }
                 }
 
@@ -96,12 +107,23 @@ pub(crate) mod function {
                         if !entry.matching_attributes(&mut matches) {
                             continue;
                         }
-                        print_match(&matches, path, &mut out)?;
+                        print_match(
+                            &matches,
+                            path,
+                            format,
+                            &mut out,
+                            #[cfg(feature = "serde")]
+                            &mut json_matches,
+                        )?;
                     }
                 }
             }
         }
 
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut out, &json_matches)?;
+        }
         if let Some(stats) = statistics.then(|| cache.take_statistics()) {
             out.flush()?;
             writeln!(err, "{stats:#?}").ok();
@@ -109,11 +131,41 @@ pub(crate) mod function {
         Ok(())
     }
 
+    /// An attribute assignment matching a path, as printed with `--format json`.
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize)]
+    struct JsonMatch {
+        /// The queried path.
+        path: String,
+        /// The file the matching pattern was read from, or `null` if it wasn't read from a file.
+        source: Option<String>,
+        /// The line number of the pattern in its `source`.
+        sequence_number: usize,
+        /// The pattern that matched `path`.
+        pattern: String,
+        /// The attribute assignment of the pattern, like `text`, `-diff` or `eol=lf`.
+        assignment: String,
+    }
+
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
     fn print_match(
         matches: &gix::attrs::search::Outcome,
         path: &BStr,
+        format: OutputFormat,
         mut out: impl std::io::Write,
+        #[cfg(feature = "serde")] json_matches: &mut Vec<JsonMatch>,
     ) -> std::io::Result<()> {
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            json_matches.extend(matches.iter().map(|m| JsonMatch {
+                path: path.to_string(),
+                source: m.location.source.map(|p| p.to_string_lossy().into_owned()),
+                sequence_number: m.location.sequence_number,
+                pattern: m.pattern.to_string(),
+                assignment: m.assignment.to_string(),
+            }));
+            return Ok(());
+        }
         for m in matches.iter() {
             writeln!(
                 out,
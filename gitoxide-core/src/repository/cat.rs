@@ -0,0 +1,38 @@
+use anyhow::Context;
+
+pub struct Options {
+    /// The header format, or `None` to use the default of `git cat-file --batch`.
+    pub format: Option<String>,
+    /// Print object contents after each header.
+    pub contents: bool,
+    /// Only flush the output at the end.
+    pub buffer: bool,
+}
+
+pub fn batch(
+    mut repo: gix::Repository,
+    input: impl std::io::BufRead,
+    mut out: impl std::io::Write,
+    Options {
+        format,
+        contents,
+        buffer,
+    }: Options,
+) -> anyhow::Result<()> {
+    repo.object_cache_size_if_unset(4 * 1024 * 1024);
+    let format = format
+        .map(|format| gix::object::batch::Format::from_bytes(format.as_bytes()))
+        .transpose()
+        .context("Invalid batch format")?
+        .unwrap_or_default();
+    repo.cat_file_batch(
+        input,
+        &mut out,
+        &gix::object::batch::Options {
+            format,
+            contents,
+            buffer,
+        },
+    )?;
+    Ok(())
+}
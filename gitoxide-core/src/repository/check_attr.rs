@@ -0,0 +1,138 @@
+use std::{borrow::Cow, io};
+
+use anyhow::bail;
+use gix::bstr::{BStr, BString};
+
+use crate::{is_dir_to_mode, OutputFormat};
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// Print all attributes that are set, unset or have a value instead of only the selected `attributes`.
+    pub all: bool,
+    /// The names of the attributes to print for each path, in order.
+    pub attributes: Vec<String>,
+    /// Only read attributes from the index, and not from `.gitattributes` files in the worktree.
+    pub cached: bool,
+    /// Terminate each field with a NUL byte instead of printing a line per attribute.
+    pub nul_terminated: bool,
+}
+
+/// Print the `attributes` of each of the worktree-relative `paths` like `git check-attr` does.
+///
+/// Note that paths aren't C-quoted as `git` would do when `nul_terminated` is unset.
+pub fn check_attr(
+    repo: gix::Repository,
+    paths: impl Iterator<Item = io::Result<BString>>,
+    mut out: impl io::Write,
+    Options {
+        format,
+        all,
+        attributes,
+        cached,
+        nul_terminated,
+    }: Options,
+) -> anyhow::Result<()> {
+    if !all && attributes.is_empty() {
+        bail!("At least one attribute must be given unless all attributes should be printed");
+    }
+
+    let index = if repo.is_bare() {
+        repo.index_or_load_from_head()?
+    } else {
+        gix::worktree::IndexPersistedOrInMemory::Persisted(repo.index_or_empty()?)
+    };
+    let mut cache = repo.attributes(
+        &index,
+        if cached || repo.is_bare() {
+            gix::worktree::stack::state::attributes::Source::IdMapping
+        } else {
+            gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping
+        },
+        gix::worktree::stack::state::ignore::Source::IdMapping,
+        None,
+    )?;
+    let mut matches = if all {
+        cache.attribute_matches()
+    } else {
+        cache.selected_attribute_matches(attributes.iter().map(String::as_str))
+    };
+
+    #[cfg(feature = "serde")]
+    let mut json_assignments = Vec::new();
+    for path in paths {
+        let path = path?;
+        let mode = gix::path::from_bstr(Cow::Borrowed(path.as_ref()))
+            .metadata()
+            .ok()
+            .map(|m| is_dir_to_mode(m.is_dir()));
+        let platform = cache.at_entry(path.as_slice(), mode)?;
+        platform.matching_attributes(&mut matches);
+
+        let assignments: Box<dyn Iterator<Item = gix::attrs::search::Match<'_>>> = if all {
+            Box::new(
+                matches
+                    .iter()
+                    .filter(|m| m.assignment.state != gix::attrs::StateRef::Unspecified),
+            )
+        } else {
+            Box::new(matches.iter_selected())
+        };
+        for m in assignments {
+            match format {
+                OutputFormat::Human => print_assignment(path.as_ref(), m.assignment, nul_terminated, &mut out)?,
+                #[cfg(feature = "serde")]
+                OutputFormat::Json => json_assignments.push(JsonAssignment {
+                    path: path.to_string(),
+                    attribute: m.assignment.name.as_str().to_owned(),
+                    info: info(&m.assignment.state).to_string(),
+                }),
+            }
+        }
+        out.flush()?;
+    }
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_assignments)?;
+    }
+    Ok(())
+}
+
+/// The state of an attribute for a path, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonAssignment {
+    /// The queried path.
+    path: String,
+    /// The name of the attribute.
+    attribute: String,
+    /// Either `unspecified`, `set`, `unset` or the value of the attribute.
+    info: String,
+}
+
+fn info<'a>(state: &'a gix::attrs::StateRef<'_>) -> &'a BStr {
+    use gix::attrs::StateRef;
+    match state {
+        StateRef::Unspecified => "unspecified".into(),
+        StateRef::Set => "set".into(),
+        StateRef::Unset => "unset".into(),
+        StateRef::Value(value) => value.as_bstr(),
+    }
+}
+
+fn print_assignment(
+    path: &BStr,
+    assignment: gix::attrs::AssignmentRef<'_>,
+    nul_terminated: bool,
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    let info = info(&assignment.state);
+    if nul_terminated {
+        for field in [path, assignment.name.as_str().into(), info] {
+            out.write_all(field)?;
+            out.write_all(b"\0")?;
+        }
+        Ok(())
+    } else {
+        writeln!(out, "{path}: {}: {info}", assignment.name.as_str())
+    }
+}
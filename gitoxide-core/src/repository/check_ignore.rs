@@ -0,0 +1,157 @@
+use std::{borrow::Cow, io};
+
+use anyhow::bail;
+use gix::bstr::{BStr, BString};
+
+use crate::{is_dir_to_mode, OutputFormat};
+
+pub struct Options {
+    pub format: OutputFormat,
+    /// Print the pattern that matched each path along with its source, even if it is negated.
+    pub verbose: bool,
+    /// Also print paths that didn't match any pattern, only useful with `verbose`.
+    pub non_matching: bool,
+    /// Also check tracked paths, which are otherwise never considered ignored.
+    pub no_index: bool,
+    /// Terminate each field with a NUL byte instead of printing a line per path.
+    pub nul_terminated: bool,
+}
+
+/// Print which of the worktree-relative `paths` are ignored like `git check-ignore` does, and fail if none of them is.
+///
+/// Note that paths aren't C-quoted as `git` would do when `nul_terminated` is unset.
+#[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+pub fn check_ignore(
+    repo: gix::Repository,
+    paths: impl Iterator<Item = io::Result<BString>>,
+    mut out: impl io::Write,
+    Options {
+        format,
+        verbose,
+        non_matching,
+        no_index,
+        nul_terminated,
+    }: Options,
+) -> anyhow::Result<()> {
+    let Some(workdir) = repo.work_dir().map(ToOwned::to_owned) else {
+        bail!("Cannot check ignored paths in a bare repository");
+    };
+
+    let index = repo.index_or_empty()?;
+    let mut cache = repo.excludes(&index, None, Default::default())?;
+    let mut num_ignored = 0;
+    #[cfg(feature = "serde")]
+    let mut json_paths = Vec::new();
+    for path in paths {
+        let path = path?;
+        let is_tracked = !no_index && index.entry_by_path(path.as_ref()).is_some();
+        let platform;
+        let match_ = if is_tracked {
+            None
+        } else {
+            let mode = gix::path::from_bstr(Cow::Borrowed(path.as_ref()))
+                .metadata()
+                .ok()
+                .map(|m| is_dir_to_mode(m.is_dir()));
+            platform = cache.at_entry(path.as_slice(), mode)?;
+            platform.matching_exclude_pattern()
+        };
+
+        let is_ignored = matches!(&match_, Some(m) if !m.pattern.is_negative());
+        num_ignored += usize::from(is_ignored);
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            if is_ignored || verbose && (match_.is_some() || non_matching) {
+                json_paths.push(JsonPath {
+                    path: path.to_string(),
+                    is_ignored,
+                    pattern: match_.filter(|_| verbose).map(|m| JsonPattern {
+                        source: m.source.map(|source| {
+                            source
+                                .strip_prefix(&workdir)
+                                .unwrap_or(source)
+                                .to_string_lossy()
+                                .into_owned()
+                        }),
+                        sequence_number: m.sequence_number,
+                        pattern: m.pattern.to_string(),
+                    }),
+                });
+            }
+            continue;
+        }
+        match (verbose, match_) {
+            (true, Some(m)) => {
+                let source = m
+                    .source
+                    .map(|source| gix::path::into_bstr(source.strip_prefix(&workdir).unwrap_or(source)).into_owned())
+                    .unwrap_or_default();
+                let line = m.sequence_number.to_string();
+                let pattern = m.pattern.to_string();
+                print_fields(
+                    [source.as_ref(), line.as_str().into(), pattern.as_str().into()],
+                    path.as_ref(),
+                    nul_terminated,
+                    &mut out,
+                )?;
+            }
+            (true, None) if non_matching => print_fields(["".into(); 3], path.as_ref(), nul_terminated, &mut out)?,
+            (false, _) if is_ignored => {
+                out.write_all(&path)?;
+                out.write_all(if nul_terminated { b"\0" } else { b"\n" })?;
+            }
+            _ => {}
+        }
+        out.flush()?;
+    }
+
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_paths)?;
+    }
+    if num_ignored == 0 {
+        bail!("No path is ignored");
+    }
+    Ok(())
+}
+
+/// A path and whether it's ignored, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonPath {
+    /// The queried path.
+    path: String,
+    /// `true` if the path is ignored, which is never the case for tracked paths unless the index is disregarded.
+    is_ignored: bool,
+    /// The pattern that matched `path` last in verbose mode, even if it is negated, or `null` otherwise.
+    pattern: Option<JsonPattern>,
+}
+
+/// An exclude pattern and where it is defined.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonPattern {
+    /// The file the pattern was read from relative to the worktree if it is inside of it, or `null` if it has no source.
+    source: Option<String>,
+    /// The line number of the pattern in its `source`.
+    sequence_number: usize,
+    /// The pattern itself, as it would be written in an exclude file.
+    pattern: String,
+}
+
+fn print_fields(
+    [source, line, pattern]: [&BStr; 3],
+    path: &BStr,
+    nul_terminated: bool,
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    if nul_terminated {
+        for field in [source, line, pattern, path] {
+            out.write_all(field)?;
+            out.write_all(b"\0")?;
+        }
+        Ok(())
+    } else {
+        writeln!(out, "{source}:{line}:{pattern}\t{path}")
+    }
+}
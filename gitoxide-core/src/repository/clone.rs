@@ -3,8 +3,18 @@ use crate::OutputFormat;
 pub struct Options {
     pub format: OutputFormat,
     pub bare: bool,
+    /// If `true`, mirror all references of the remote in a bare repository.
+    pub mirror: bool,
     pub handshake_info: bool,
     pub no_tags: bool,
+    /// If `true`, only fetch the branch to check out, now and in future.
+    pub single_branch: bool,
+    /// How to obtain objects if the remote is on the local filesystem.
+    pub local_objects: gix::clone::LocalObjects,
+    /// Repositories to borrow objects from.
+    pub reference_repositories: Vec<std::path::PathBuf>,
+    /// If `true`, copy all borrowed objects into the new repository.
+    pub dissociate: bool,
     pub shallow: gix::remote::fetch::Shallow,
     pub ref_name: Option<gix::refs::PartialName>,
 }
@@ -31,7 +41,12 @@ pub(crate) mod function {
             format,
             handshake_info,
             bare,
+            mirror,
             no_tags,
+            single_branch,
+            local_objects,
+            reference_repositories,
+            dissociate,
             ref_name,
             shallow,
         }: Options,
@@ -44,6 +59,7 @@ pub(crate) mod function {
             bail!("JSON output isn't yet supported for fetching.");
         }
 
+        let bare = bare || mirror;
         let url: gix::Url = url.as_ref().try_into()?;
         let directory = directory.map_or_else(
             || {
@@ -73,9 +89,16 @@ pub(crate) mod function {
             },
         )?;
         if no_tags {
-            prepare = prepare.configure_remote(|r| Ok(r.with_fetch_tags(gix::remote::fetch::Tags::None)));
+            prepare = prepare.with_fetch_tags(gix::remote::fetch::Tags::None);
+        }
+        for path in reference_repositories {
+            prepare = prepare.with_reference_repository(path);
         }
         let (mut checkout, fetch_outcome) = prepare
+            .with_mirror(mirror)
+            .with_single_branch(single_branch)
+            .with_local_objects(local_objects)
+            .with_dissociate(dissociate)
             .with_shallow(shallow)
             .with_ref_name(ref_name.as_ref())?
             .fetch_then_checkout(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
@@ -92,29 +115,47 @@ pub(crate) mod function {
             writeln!(out, "\t{:?}", fetch_outcome.ref_map.handshake)?;
         }
 
-        match fetch_outcome.status {
-            Status::NoPackReceived { dry_run, .. } => {
+        let (update_refs, negotiate) = match fetch_outcome.status {
+            Status::NoPackReceived { dry_run, .. }
+                if fetch_outcome
+                    .ref_map
+                    .remote_refs
+                    .iter()
+                    .all(|r| matches!(r, gix::protocol::handshake::Ref::Unborn { .. })) =>
+            {
                 assert!(!dry_run, "dry-run unsupported");
                 writeln!(err, "The cloned repository appears to be empty")?;
+                (None, None)
             }
-            Status::Change {
-                update_refs, negotiate, ..
+            Status::NoPackReceived {
+                dry_run,
+                update_refs,
+                negotiate,
             } => {
-                let remote = repo
-                    .find_default_remote(gix::remote::Direction::Fetch)
-                    .expect("one origin remote")?;
-                let ref_specs = remote.refspecs(gix::remote::Direction::Fetch);
-                print_updates(
-                    &repo,
-                    &negotiate,
-                    update_refs,
-                    ref_specs,
-                    fetch_outcome.ref_map,
-                    &mut out,
-                    &mut err,
-                )?;
+                // Objects were copied or shared with `--local` or `--shared`, so there was nothing to receive.
+                assert!(!dry_run, "dry-run unsupported");
+                (Some(update_refs), negotiate)
             }
+            Status::Change {
+                update_refs, negotiate, ..
+            } => (Some(update_refs), Some(negotiate)),
         };
+        if let Some(update_refs) = update_refs {
+            let remote = repo
+                .find_default_remote(gix::remote::Direction::Fetch)
+                .expect("one origin remote")?;
+            let ref_specs = remote.refspecs(gix::remote::Direction::Fetch);
+            let negotiate_default = Default::default();
+            print_updates(
+                &repo,
+                negotiate.as_ref().unwrap_or(&negotiate_default),
+                update_refs,
+                ref_specs,
+                fetch_outcome.ref_map,
+                &mut out,
+                &mut err,
+            )?;
+        }
 
         if let Some(gix::worktree::state::checkout::Outcome { collisions, errors, .. }) = outcome {
             if !(collisions.is_empty() && errors.is_empty()) {
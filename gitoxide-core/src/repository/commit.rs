@@ -88,6 +88,65 @@ pub fn describe(
     Ok(())
 }
 
+pub fn name_rev(
+    mut repo: gix::Repository,
+    rev_specs: &[String],
+    annotate: Option<impl std::io::BufRead>,
+    mut out: impl std::io::Write,
+    name_rev::Options {
+        tags_only,
+        refs,
+        exclude,
+        name_only,
+    }: name_rev::Options,
+) -> Result<()> {
+    repo.object_cache_size_if_unset(4 * 1024 * 1024);
+    let mut platform = repo.name_rev().tags_only(tags_only);
+    for pattern in refs {
+        platform = platform.include_refs(pattern);
+    }
+    for pattern in exclude {
+        platform = platform.exclude_refs(pattern);
+    }
+
+    if let Some(mut input) = annotate {
+        let names = platform.all_names()?;
+        let mut line = Vec::new();
+        while input.read_until(b'\n', &mut line)? != 0 {
+            out.write_all(&names.annotate(line.as_slice().into(), name_only))?;
+            line.clear();
+        }
+        return Ok(());
+    }
+
+    let commits = rev_specs
+        .iter()
+        .map(|spec| Ok(repo.rev_parse_single(format!("{spec}^{{commit}}").as_str())?.detach()))
+        .collect::<Result<Vec<_>>>()?;
+    let names = platform.names(commits.iter().copied())?;
+    for (spec, id) in rev_specs.iter().zip(commits) {
+        let name = names
+            .get(&id)
+            .map_or_else(|| "undefined".to_string(), ToString::to_string);
+        if name_only {
+            writeln!(out, "{name}")?;
+        } else {
+            writeln!(out, "{spec} {name}")?;
+        }
+    }
+    Ok(())
+}
+
+pub mod name_rev {
+    #[derive(Debug, Clone)]
+    pub struct Options {
+        pub tags_only: bool,
+        pub refs: Vec<String>,
+        pub exclude: Vec<String>,
+        pub name_only: bool,
+    }
+}
+
 pub mod describe {
     #[derive(Debug, Clone)]
     pub struct Options {
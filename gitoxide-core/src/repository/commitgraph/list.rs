@@ -1,7 +1,7 @@
 pub(crate) mod function {
     use std::{borrow::Cow, ffi::OsString};
 
-    use anyhow::{bail, Context};
+    use anyhow::Context;
     use gix::{prelude::ObjectIdExt, traverse::commit::simple::Sorting};
 
     use crate::OutputFormat;
@@ -12,9 +12,6 @@ pub(crate) mod function {
         mut out: impl std::io::Write,
         format: OutputFormat,
     ) -> anyhow::Result<()> {
-        if format != OutputFormat::Human {
-            bail!("Only human output is currently supported");
-        }
         let graph = repo
             .commit_graph()
             .context("a commitgraph is required, but none was found")?;
@@ -32,24 +29,67 @@ pub(crate) mod function {
             .ancestors()
             .sorting(Sorting::ByCommitTimeNewestFirst)
             .all()?;
+        #[cfg(feature = "serde")]
+        let mut json_commits = Vec::new();
         for commit in commits {
             let commit = commit?;
-            writeln!(
-                out,
-                "{} {} {} {}",
-                commit.id().shorten_or_id(),
-                commit.commit_time.expect("traversal with date"),
-                commit.parent_ids.len(),
-                graph.commit_by_id(commit.id).map_or_else(
-                    || Cow::Borrowed("<NOT IN GRAPH-CACHE>"),
-                    |c| Cow::Owned(format!(
-                        "{} {}",
-                        c.root_tree_id().to_owned().attach(&repo).shorten_or_id(),
-                        c.generation()
-                    ))
-                )
-            )?;
+            let graph_commit = graph.commit_by_id(commit.id);
+            match format {
+                OutputFormat::Human => writeln!(
+                    out,
+                    "{} {} {} {}",
+                    commit.id().shorten_or_id(),
+                    commit.commit_time.expect("traversal with date"),
+                    commit.parent_ids.len(),
+                    graph_commit.map_or_else(
+                        || Cow::Borrowed("<NOT IN GRAPH-CACHE>"),
+                        |c| Cow::Owned(format!(
+                            "{} {}",
+                            c.root_tree_id().to_owned().attach(&repo).shorten_or_id(),
+                            c.generation()
+                        ))
+                    )
+                )?,
+                #[cfg(feature = "serde")]
+                OutputFormat::Json => json_commits.push(JsonCommit {
+                    id: commit.id.to_string(),
+                    commit_time: commit.commit_time.expect("traversal with date"),
+                    num_parents: commit.parent_ids.len(),
+                    graph: graph_commit.map(|c| JsonGraphEntry {
+                        root_tree_id: c.root_tree_id().to_string(),
+                        generation: c.generation(),
+                    }),
+                }),
+            }
+        }
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut out, &json_commits)?;
         }
         Ok(())
     }
+
+    /// A commit reachable from the starting point, as printed with `--format json`.
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize)]
+    struct JsonCommit {
+        /// The hexadecimal id of the commit.
+        id: String,
+        /// The commit time in seconds since the unix epoch.
+        commit_time: gix::date::SecondsSinceUnixEpoch,
+        /// The amount of parents of the commit.
+        num_parents: usize,
+        /// Information stored in the commit-graph, or `null` if the commit isn't contained in it.
+        graph: Option<JsonGraphEntry>,
+    }
+
+    /// Information about a commit as stored in the commit-graph.
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize)]
+    struct JsonGraphEntry {
+        /// The hexadecimal id of the commit.s tree.
+        root_tree_id: String,
+        /// The generation number of the commit.
+        generation: u32,
+    }
 }
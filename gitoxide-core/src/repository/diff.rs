@@ -0,0 +1,358 @@
+use anyhow::{bail, Context};
+use gix::bstr::{BStr, BString, ByteVec};
+
+use crate::OutputFormat;
+
+/// How to display the changes between two trees.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Print the kind of change along with the path of each changed file, like `git diff --name-status`.
+    NameStatus,
+    /// Print a histogram of insertions and deletions per file and a summary line, like `git diff --stat`.
+    Stat,
+    /// Print the amount of insertions and deletions per file in a machine-readable format, like `git diff --numstat`.
+    NumStat,
+    /// Print only the summary line, like `git diff --shortstat`.
+    ShortStat,
+}
+
+/// The kind of change of a [`FileStat`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Addition,
+    Deletion,
+    Modification,
+    Rename,
+    Copy,
+}
+
+impl ChangeKind {
+    fn as_char(&self) -> char {
+        match self {
+            ChangeKind::Addition => 'A',
+            ChangeKind::Deletion => 'D',
+            ChangeKind::Modification => 'M',
+            ChangeKind::Rename => 'R',
+            ChangeKind::Copy => 'C',
+        }
+    }
+}
+
+/// What changed in a file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Lines {
+    /// The amount of lines that were added and removed.
+    Text { insertions: u32, deletions: u32 },
+    /// One of the versions is binary, so only the sizes in bytes are known.
+    Binary { old_size: u64, new_size: u64 },
+}
+
+/// The statistics of a single changed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub kind: ChangeKind,
+    /// The path of the file in the new tree, or in the old tree if it was deleted.
+    pub path: BString,
+    /// The path of the file in the old tree if it was renamed or copied.
+    pub source_path: Option<BString>,
+    pub lines: Lines,
+}
+
+impl FileStat {
+    /// The path to display, which shows renames and copies as `old => new`, with common leading and trailing
+    /// directories factored out like `dir/{old => new}/file`.
+    pub fn display_path(&self) -> BString {
+        match &self.source_path {
+            Some(source_path) => rename_path(source_path.as_ref(), self.path.as_ref()),
+            None => self.path.clone(),
+        }
+    }
+
+    fn changed_lines(&self) -> u32 {
+        match self.lines {
+            Lines::Text { insertions, deletions } => insertions + deletions,
+            Lines::Binary { .. } => 0,
+        }
+    }
+}
+
+/// Compute the statistics of all files that changed between the trees of `old_treeish` and `new_treeish`, sorted by path,
+/// with renames tracked as configured.
+pub fn tree_stats(repo: &gix::Repository, old_treeish: &BStr, new_treeish: &BStr) -> anyhow::Result<Vec<FileStat>> {
+    let old_tree = treeish_to_tree(repo, old_treeish)?;
+    let new_tree = treeish_to_tree(repo, new_treeish)?;
+    let mut cache = repo.diff_resource_cache(gix::diff::blob::pipeline::Mode::ToGit, Default::default())?;
+    let size_of =
+        |id: gix::ObjectId| -> anyhow::Result<u64> { Ok(if id.is_null() { 0 } else { repo.find_header(id)?.size() }) };
+
+    let mut stats = Vec::new();
+    old_tree
+        .changes()?
+        .track_path()
+        .for_each_to_obtain_tree(&new_tree, |change| -> anyhow::Result<_> {
+            use gix::object::tree::diff::change::Event;
+            if change.event.entry_mode().is_tree() {
+                return Ok(gix::object::tree::diff::Action::Continue);
+            }
+            let (kind, source_path, old_id, new_id) = match change.event {
+                Event::Addition { id, .. } => (ChangeKind::Addition, None, repo.object_hash().null(), id.detach()),
+                Event::Deletion { id, .. } => (ChangeKind::Deletion, None, id.detach(), repo.object_hash().null()),
+                Event::Modification { previous_id, id, .. } => {
+                    (ChangeKind::Modification, None, previous_id.detach(), id.detach())
+                }
+                Event::Rewrite {
+                    source_location,
+                    source_id,
+                    id,
+                    copy,
+                    ..
+                } => (
+                    if copy { ChangeKind::Copy } else { ChangeKind::Rename },
+                    Some(source_location.to_owned()),
+                    source_id.detach(),
+                    id.detach(),
+                ),
+            };
+            let lines = match change.diff(&mut cache)?.line_counts()? {
+                Some(counts) => Lines::Text {
+                    insertions: counts.insertions,
+                    deletions: counts.removals,
+                },
+                None => Lines::Binary {
+                    old_size: size_of(old_id)?,
+                    new_size: size_of(new_id)?,
+                },
+            };
+            cache.clear_resource_cache();
+            stats.push(FileStat {
+                kind,
+                path: change.location.to_owned(),
+                source_path,
+                lines,
+            });
+            Ok(gix::object::tree::diff::Action::Continue)
+        })?;
+    stats.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(stats)
+}
+
+/// Write the changes between the trees of `old_treeish` and `new_treeish` to `out` in the given `format`.
+pub fn tree(
+    repo: gix::Repository,
+    out: &mut dyn std::io::Write,
+    old_treeish: BString,
+    new_treeish: BString,
+    format: Format,
+    output_format: OutputFormat,
+) -> anyhow::Result<()> {
+    if output_format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+    let stats = tree_stats(&repo, old_treeish.as_ref(), new_treeish.as_ref())?;
+    match format {
+        Format::NameStatus => {
+            for stat in &stats {
+                match &stat.source_path {
+                    Some(source_path) => writeln!(out, "{}\t{source_path}\t{}", stat.kind.as_char(), stat.path)?,
+                    None => writeln!(out, "{}\t{}", stat.kind.as_char(), stat.path)?,
+                }
+            }
+        }
+        Format::NumStat => {
+            for stat in &stats {
+                match stat.lines {
+                    Lines::Text { insertions, deletions } => {
+                        writeln!(out, "{insertions}\t{deletions}\t{}", stat.display_path())?
+                    }
+                    Lines::Binary { .. } => writeln!(out, "-\t-\t{}", stat.display_path())?,
+                }
+            }
+        }
+        Format::Stat => {
+            write_stat(out, &stats)?;
+            write_summary(out, &stats)?;
+        }
+        Format::ShortStat => write_summary(out, &stats)?,
+    }
+    Ok(())
+}
+
+/// The width of the `--stat` output if `COLUMNS` isn't set, which is what `git` uses when not writing to a terminal.
+const DEFAULT_STAT_WIDTH: usize = 80;
+
+/// Write a line per file with its path and a histogram of its changed lines, sized to fit into `COLUMNS` just like `git` does it.
+fn write_stat(out: &mut dyn std::io::Write, stats: &[FileStat]) -> std::io::Result<()> {
+    let paths: Vec<_> = stats.iter().map(|stat| stat.display_path().to_string()).collect();
+    let max_path_width = paths.iter().map(|path| path.chars().count()).max().unwrap_or_default();
+    let max_change = stats.iter().map(FileStat::changed_lines).max().unwrap_or_default() as usize;
+    let bin_width = stats
+        .iter()
+        .filter_map(|stat| match stat.lines {
+            Lines::Binary { old_size, new_size } => {
+                Some("Bin  -> bytes".len() + 1 + old_size.to_string().len() + new_size.to_string().len())
+            }
+            Lines::Text { .. } => None,
+        })
+        .max();
+    let count_width = max_change
+        .to_string()
+        .len()
+        .max(if bin_width.is_some() { 3 } else { 0 });
+
+    let width = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_STAT_WIDTH)
+        .max(16 + 6 + count_width);
+    let mut graph_width = match bin_width {
+        Some(bin_width) if max_change + 4 <= bin_width => bin_width - 4,
+        _ => max_change,
+    };
+    let mut path_width = max_path_width;
+    if path_width + count_width + 6 + graph_width > width {
+        let max_graph_width = (width * 3 / 8).saturating_sub(count_width + 6);
+        if graph_width > max_graph_width {
+            graph_width = max_graph_width.max(6);
+        }
+        if path_width > width - count_width - 6 - graph_width {
+            path_width = width - count_width - 6 - graph_width;
+        } else {
+            graph_width = width - count_width - 6 - path_width;
+        }
+    }
+
+    for (stat, path) in stats.iter().zip(paths) {
+        let (prefix, path) = shorten_path(&path, path_width);
+        let padding = path_width.saturating_sub(prefix.len() + path.chars().count());
+        write!(out, " {prefix}{path}{:padding$} | ", "")?;
+        match stat.lines {
+            Lines::Text { insertions, deletions } => {
+                let (mut insertions_width, mut deletions_width) = (insertions as usize, deletions as usize);
+                if graph_width <= max_change {
+                    let mut total = scale(insertions_width + deletions_width, graph_width, max_change);
+                    if total < 2 && insertions_width > 0 && deletions_width > 0 {
+                        total = 2;
+                    }
+                    if insertions_width < deletions_width {
+                        insertions_width = scale(insertions_width, graph_width, max_change);
+                        deletions_width = total - insertions_width;
+                    } else {
+                        deletions_width = scale(deletions_width, graph_width, max_change);
+                        insertions_width = total - deletions_width;
+                    }
+                }
+                write!(out, "{:>count_width$}", insertions + deletions)?;
+                if insertions + deletions > 0 {
+                    write!(out, " {}{}", "+".repeat(insertions_width), "-".repeat(deletions_width))?;
+                }
+                writeln!(out)?;
+            }
+            Lines::Binary { old_size, new_size } => {
+                writeln!(out, "{:>count_width$} {old_size} -> {new_size} bytes", "Bin")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shorten `path` so it fits into `width` by removing leading characters up to the next slash, and return the `...`
+/// prefix to show in front of it if it was shortened.
+fn shorten_path(path: &str, width: usize) -> (&'static str, &str) {
+    let len = path.chars().count();
+    if len <= width {
+        return ("", path);
+    }
+    let skip = len - width.saturating_sub(3);
+    let shortened = path.char_indices().nth(skip).map_or("", |(pos, _)| &path[pos..]);
+    let shortened = shortened.find('/').map_or(shortened, |pos| &shortened[pos..]);
+    ("...", shortened)
+}
+
+/// Scale `value` linearly so that `max` maps to `width`, while keeping non-zero values visible.
+fn scale(value: usize, width: usize, max: usize) -> usize {
+    if value == 0 {
+        0
+    } else {
+        1 + value * (width - 1) / max
+    }
+}
+
+fn write_summary(out: &mut dyn std::io::Write, stats: &[FileStat]) -> std::io::Result<()> {
+    let (insertions, deletions) = stats
+        .iter()
+        .fold((0, 0), |(insertions, deletions), stat| match stat.lines {
+            Lines::Text {
+                insertions: i,
+                deletions: d,
+            } => (insertions + i, deletions + d),
+            Lines::Binary { .. } => (insertions, deletions),
+        });
+    let plural = |count: u32, singular: &'static str, plural: &'static str| if count == 1 { singular } else { plural };
+    let files = stats.len() as u32;
+    write!(out, " {files} {} changed", plural(files, "file", "files"))?;
+    if files == 0 {
+        return writeln!(out);
+    }
+    if insertions != 0 || deletions == 0 {
+        write!(
+            out,
+            ", {insertions} {}(+)",
+            plural(insertions, "insertion", "insertions")
+        )?;
+    }
+    if deletions != 0 || insertions == 0 {
+        write!(out, ", {deletions} {}(-)", plural(deletions, "deletion", "deletions"))?;
+    }
+    writeln!(out)
+}
+
+/// Display a rename from `old` to `new` like `git` does, with common leading and trailing directories outside of braces.
+fn rename_path(old: &BStr, new: &BStr) -> BString {
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter_map(|(idx, (byte, _))| (*byte == b'/').then_some(idx + 1))
+        .last()
+        .unwrap_or_default();
+
+    // If there is a common prefix, it ends in a slash which may also be the start of the common suffix.
+    let min_pos = prefix_len.saturating_sub(1);
+    let mut suffix_len = 0;
+    let (mut old_pos, mut new_pos) = (old.len(), new.len());
+    while old_pos > min_pos && new_pos > min_pos && old[old_pos - 1] == new[new_pos - 1] {
+        old_pos -= 1;
+        new_pos -= 1;
+        if old[old_pos] == b'/' {
+            suffix_len = old.len() - old_pos;
+        }
+    }
+
+    if prefix_len + suffix_len == 0 {
+        let mut out = BString::from(old);
+        out.push_str(" => ");
+        out.push_str(new);
+        return out;
+    }
+    let old_mid = &old[prefix_len..old.len().saturating_sub(suffix_len).max(prefix_len)];
+    let new_mid = &new[prefix_len..new.len().saturating_sub(suffix_len).max(prefix_len)];
+    let mut out = BString::from(&old[..prefix_len]);
+    out.push_byte(b'{');
+    out.push_str(old_mid);
+    out.push_str(" => ");
+    out.push_str(new_mid);
+    out.push_byte(b'}');
+    out.push_str(&old[old.len() - suffix_len..]);
+    out
+}
+
+fn treeish_to_tree<'repo>(repo: &'repo gix::Repository, treeish: &BStr) -> anyhow::Result<gix::Tree<'repo>> {
+    let spec = format!("{treeish}^{{tree}}");
+    Ok(repo
+        .rev_parse_single(spec.as_str())
+        .with_context(|| format!("Could not resolve '{treeish}' to a tree"))?
+        .object()?
+        .into_tree())
+}
@@ -12,9 +12,6 @@ pub fn check(
     out: &mut dyn std::io::Write,
     format: OutputFormat,
 ) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("JSON output isn't implemented yet");
-    }
     let is_dirty = repo.is_dirty()?;
     let res = match (is_dirty, mode) {
         (false, Mode::IsClean) => Ok("The repository is clean"),
@@ -24,9 +21,25 @@ pub fn check(
     };
 
     let suffix = "(not counting untracked files)";
-    match res {
-        Ok(msg) => writeln!(out, "{msg} {suffix}")?,
-        Err(msg) => bail!("{msg} {suffix}"),
+    match format {
+        OutputFormat::Human => {
+            if let Ok(msg) = res {
+                writeln!(out, "{msg} {suffix}")?;
+            }
+        }
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => {
+            /// The state of the repository, as printed with `--format json`.
+            #[derive(serde::Serialize)]
+            struct JsonState {
+                /// `true` if tracked files or the index have changes, untracked files are not considered.
+                is_dirty: bool,
+            }
+            serde_json::to_writer_pretty(&mut *out, &JsonState { is_dirty })?;
+        }
+    }
+    if let Err(msg) = res {
+        bail!("{msg} {suffix}");
     }
     Ok(())
 }
@@ -1,6 +1,5 @@
 use std::{borrow::Cow, io};
 
-use anyhow::bail;
 use gix::bstr::BStr;
 
 use crate::{is_dir_to_mode, repository::PathsOrPatterns, OutputFormat};
@@ -30,10 +29,6 @@ pub fn query(
         statistics,
     }: query::Options,
 ) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("JSON output isn't implemented yet");
-    }
-
     let index = repo.index()?;
     let mut cache = repo.excludes(
         &index,
@@ -41,6 +36,8 @@ pub fn query(
         Default::default(),
     )?;
 
+    #[cfg(feature = "serde")]
+    let mut json_matches = Vec::new();
     match input {
         PathsOrPatterns::Paths(paths) => {
             for path in paths {
@@ -52,7 +49,14 @@ pub fn query(
                 let match_ = entry
                     .matching_exclude_pattern()
                     .and_then(|m| (show_ignore_patterns || !m.pattern.is_negative()).then_some(m));
-                print_match(match_, path.as_ref(), &mut out)?;
+                print_match(
+                    match_,
+                    path.as_ref(),
+                    format,
+                    &mut out,
+                    #[cfg(feature = "serde")]
+                    &mut json_matches,
+                )?;
             }
         }
         PathsOrPatterns::Patterns(patterns) => {
@@ -72,7 +76,14 @@ pub fn query(
                     let match_ = entry
                         .matching_exclude_pattern()
                         .and_then(|m| (show_ignore_patterns || !m.pattern.is_negative()).then_some(m));
-                    print_match(match_, path, &mut out)?;
+                    print_match(
+                        match_,
+                        path,
+                        format,
+                        &mut out,
+                        #[cfg(feature = "serde")]
+                        &mut json_matches,
+                    )?;
                 }
             }
 
@@ -100,12 +111,23 @@ pub fn query(
                     let match_ = entry
                         .matching_exclude_pattern()
                         .and_then(|m| (show_ignore_patterns || !m.pattern.is_negative()).then_some(m));
-                    print_match(match_, path, &mut out)?;
+                    print_match(
+                        match_,
+                        path,
+                        format,
+                        &mut out,
+                        #[cfg(feature = "serde")]
+                        &mut json_matches,
+                    )?;
                 }
             }
         }
     }
 
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_matches)?;
+    }
     if let Some(stats) = statistics.then(|| cache.take_statistics()) {
         out.flush()?;
         writeln!(err, "{stats:#?}").ok();
@@ -113,11 +135,48 @@ pub fn query(
     Ok(())
 }
 
+/// A path and the exclude pattern matching it, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonMatch {
+    /// The queried path.
+    path: String,
+    /// The pattern that matched `path` last, or `null` if no pattern matched.
+    pattern: Option<JsonPattern>,
+}
+
+/// An exclude pattern and where it is defined.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonPattern {
+    /// The file the pattern was read from, or `null` if it was provided on the command-line.
+    source: Option<String>,
+    /// The line number of the pattern in its `source`.
+    sequence_number: usize,
+    /// The pattern itself, as it would be written in an exclude file.
+    pattern: String,
+}
+
+#[cfg_attr(not(feature = "serde"), allow(unused_variables))]
 fn print_match(
     m: Option<gix::ignore::search::Match<'_>>,
     path: &BStr,
+    format: OutputFormat,
     mut out: impl std::io::Write,
+    #[cfg(feature = "serde")] json_matches: &mut Vec<JsonMatch>,
 ) -> std::io::Result<()> {
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        json_matches.push(JsonMatch {
+            path: path.to_string(),
+            pattern: m.map(|m| JsonPattern {
+                source: m.source.map(|p| p.to_string_lossy().into_owned()),
+                sequence_number: m.sequence_number,
+                pattern: m.pattern.to_string(),
+            }),
+        });
+        return Ok(());
+    }
     match m {
         Some(m) => writeln!(
             out,
@@ -0,0 +1,43 @@
+use std::{io::BufReader, path::PathBuf};
+
+use anyhow::Context;
+use gix::bstr::BString;
+
+pub fn export(repo: gix::Repository, refs: Vec<BString>, mut out: impl std::io::Write) -> anyhow::Result<()> {
+    let outcome = repo.fast_export(&refs, &mut out, &gix::interrupt::IS_INTERRUPTED)?;
+    out.flush()?;
+    if outcome.commits == 0 {
+        anyhow::bail!("Didn't find any commits to export");
+    }
+    Ok(())
+}
+
+pub fn import(
+    repo: gix::Repository,
+    input: impl std::io::Read,
+    export_marks: Option<PathBuf>,
+    mut out: impl std::io::Write,
+) -> anyhow::Result<()> {
+    let outcome = repo.fast_import(gix::fast_import::Reader::new(BufReader::new(input)))?;
+    for edit in &outcome.edits {
+        match edit.change.new_value() {
+            Some(new) => writeln!(out, "{} {}", new.id(), edit.name)?,
+            None => writeln!(out, "deleted {}", edit.name)?,
+        }
+    }
+    writeln!(
+        out,
+        "created {} commits, {} blobs and {} tags",
+        outcome.commits, outcome.blobs, outcome.tags
+    )?;
+
+    if let Some(path) = export_marks {
+        let mut marks = Vec::new();
+        for (mark, id) in &outcome.marks {
+            use std::io::Write;
+            writeln!(marks, ":{mark} {id}")?;
+        }
+        std::fs::write(&path, marks).with_context(|| format!("Could not write marks to '{}'", path.display()))?;
+    }
+    Ok(())
+}
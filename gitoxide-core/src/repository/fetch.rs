@@ -6,9 +6,17 @@ pub struct Options {
     pub format: OutputFormat,
     pub dry_run: bool,
     pub remote: Option<String>,
+    /// If `true`, fetch from all remotes, or from the remotes in the group named by `remote`.
+    pub all: bool,
+    /// The amount of remotes to fetch from in parallel if `all` is set, or `None` to use the configured value.
+    pub jobs: Option<usize>,
     /// If non-empty, override all ref-specs otherwise configured in the remote
     pub ref_specs: Vec<BString>,
     pub shallow: gix::remote::fetch::Shallow,
+    /// If `true`, delete local references whose counterpart doesn't exist on the remote anymore.
+    pub prune: bool,
+    /// If `true`, also delete local tags that don't exist on the remote anymore, which implies `prune`.
+    pub prune_tags: bool,
     pub handshake_info: bool,
     pub negotiation_info: bool,
     pub open_negotiation_graph: Option<std::path::PathBuf>,
@@ -18,6 +26,7 @@ pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
 
 pub(crate) mod function {
     use anyhow::bail;
+    use gix::bstr::ByteSlice;
     use gix::{
         prelude::ObjectIdExt,
         refspec::match_group::validate::Fix,
@@ -41,10 +50,14 @@ pub(crate) mod function {
             format,
             dry_run,
             remote,
+            all,
+            jobs,
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
             shallow,
+            prune,
+            prune_tags,
             ref_specs,
         }: Options,
     ) -> anyhow::Result<()>
@@ -55,12 +68,24 @@ pub(crate) mod function {
         if format != OutputFormat::Human {
             bail!("JSON output isn't yet supported for fetching.");
         }
+        if all {
+            if !ref_specs.is_empty() {
+                bail!("Ref-specs can't be overridden when fetching from multiple remotes");
+            }
+            return fetch_all(&repo, progress, out, err, remote, jobs, dry_run);
+        }
 
         let mut remote = crate::repository::remote::by_name_or_url(&repo, remote.as_deref())?;
         if !ref_specs.is_empty() {
             remote.replace_refspecs(ref_specs.iter(), gix::remote::Direction::Fetch)?;
             remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
         }
+        if prune || prune_tags {
+            remote = remote.with_prune(true);
+        }
+        if prune_tags {
+            remote = remote.with_prune_tags(true);
+        }
         let res: gix::remote::fetch::Outcome = remote
             .connect(gix::remote::Direction::Fetch)?
             .prepare_fetch(&mut progress, Default::default())?
@@ -103,14 +128,17 @@ pub(crate) mod function {
             Status::Change {
                 update_refs,
                 write_pack_bundle,
+                write_packfile_uri_bundles,
                 negotiate,
             } => {
                 print_updates(&repo, &negotiate, update_refs, ref_specs, res.ref_map, &mut out, err)?;
-                if let Some(data_path) = write_pack_bundle.data_path {
-                    writeln!(out, "pack  file: \"{}\"", data_path.display()).ok();
-                }
-                if let Some(index_path) = write_pack_bundle.index_path {
-                    writeln!(out, "index file: \"{}\"", index_path.display()).ok();
+                for bundle in std::iter::once(write_pack_bundle).chain(write_packfile_uri_bundles) {
+                    if let Some(data_path) = bundle.data_path {
+                        writeln!(out, "pack  file: \"{}\"", data_path.display()).ok();
+                    }
+                    if let Some(index_path) = bundle.index_path {
+                        writeln!(out, "index file: \"{}\"", index_path.display()).ok();
+                    }
                 }
                 if negotiation_info {
                     print_negotiate_info(&mut out, Some(&negotiate))?;
@@ -127,6 +155,68 @@ pub(crate) mod function {
         Ok(())
     }
 
+    fn fetch_all<P>(
+        repo: &gix::Repository,
+        mut progress: P,
+        mut out: impl std::io::Write,
+        mut err: impl std::io::Write,
+        group: Option<String>,
+        jobs: Option<usize>,
+        dry_run: bool,
+    ) -> anyhow::Result<()>
+    where
+        P: gix::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let outcome = repo.fetch_all(
+            gix::remote::fetch_all::Options {
+                group: group.map(Into::into),
+                jobs,
+                dry_run,
+            },
+            &mut progress,
+            &gix::interrupt::IS_INTERRUPTED,
+        )?;
+        let is_success = outcome.is_success();
+        for fetched in outcome.remotes {
+            writeln!(out, "{}", fetched.name)?;
+            let res = match fetched.result {
+                Ok(res) => res,
+                Err(e) => {
+                    writeln!(err, "\t{e}")?;
+                    continue;
+                }
+            };
+            let remote = repo.find_remote(fetched.name.as_bstr())?;
+            let ref_specs = remote.refspecs(gix::remote::Direction::Fetch);
+            let negotiate_default = Default::default();
+            let (update_refs, negotiate) = match res.status {
+                Status::NoPackReceived {
+                    update_refs, negotiate, ..
+                } => (update_refs, negotiate),
+                Status::Change {
+                    update_refs, negotiate, ..
+                } => (update_refs, Some(negotiate)),
+            };
+            print_updates(
+                repo,
+                negotiate.as_ref().unwrap_or(&negotiate_default),
+                update_refs,
+                ref_specs,
+                res.ref_map,
+                &mut out,
+                &mut err,
+            )?;
+        }
+        if dry_run {
+            writeln!(out, "DRY-RUN: No ref was updated and no pack was received.").ok();
+        }
+        if !is_success {
+            bail!("Could not fetch from all remotes");
+        }
+        Ok(())
+    }
+
     fn render_graph(
         repo: &gix::Repository,
         graph: &gix::negotiate::IdMap,
@@ -290,6 +380,9 @@ pub(crate) mod function {
             }?;
         }
         consume_skipped_tags(&mut skipped_due_to_implicit_tag, &mut out)?;
+        for edit in &update_refs.pruned {
+            writeln!(out, "\t{} [pruned]", edit.name)?;
+        }
         if !map.fixes.is_empty() {
             writeln!(
                 err,
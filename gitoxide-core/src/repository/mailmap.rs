@@ -57,9 +57,6 @@ pub fn check(
     mut out: impl io::Write,
     mut err: impl io::Write,
 ) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("Only human output is supported right now");
-    }
     if contacts.is_empty() {
         bail!("specify at least one contact to run through the mailmap")
     }
@@ -69,6 +66,8 @@ pub fn check(
         bail!(err);
     }
 
+    #[cfg(feature = "serde")]
+    let mut json_contacts = Vec::new();
     let mut buf = Vec::new();
     for contact in contacts {
         let actor = match gix::actor::IdentityRef::from_bytes::<()>(&contact) {
@@ -97,11 +96,37 @@ pub fn check(
             name: resolved.name.as_ref(),
             email: resolved.email.as_ref(),
         };
-        buf.clear();
-        resolved.write_to(&mut buf)?;
+        match format {
+            OutputFormat::Human => {
+                buf.clear();
+                resolved.write_to(&mut buf)?;
 
-        out.write_all(&buf)?;
-        out.write_all(b"\n")?;
+                out.write_all(&buf)?;
+                out.write_all(b"\n")?;
+            }
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => json_contacts.push(JsonContact {
+                contact: contact.to_str_lossy().into_owned(),
+                name: resolved.name.to_str_lossy().into_owned(),
+                email: resolved.email.to_str_lossy().into_owned(),
+            }),
+        }
+    }
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_contacts)?;
     }
     Ok(())
 }
+
+/// A contact and its identity after mapping it through the mailmap, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonContact {
+    /// The contact as passed on the command-line.
+    contact: String,
+    /// The mapped name, which is empty if the contact was only an email.
+    name: String,
+    /// The mapped email.
+    email: String,
+}
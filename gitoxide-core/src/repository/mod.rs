@@ -19,6 +19,11 @@ pub enum PathsOrPatterns {
 
 #[cfg(feature = "archive")]
 pub mod archive;
+pub mod cat;
+pub mod check_attr;
+pub use check_attr::check_attr;
+pub mod check_ignore;
+pub use check_ignore::check_ignore;
 pub mod commit;
 pub mod config;
 mod credential;
@@ -26,12 +31,14 @@ pub use credential::function as credential;
 pub mod attributes;
 #[cfg(feature = "clean")]
 pub mod clean;
+pub mod diff;
 pub mod dirty;
 #[cfg(feature = "clean")]
 pub use clean::function::clean;
 #[cfg(feature = "blocking-client")]
 pub mod clone;
 pub mod exclude;
+pub mod fast_import;
 #[cfg(feature = "blocking-client")]
 pub mod fetch;
 #[cfg(feature = "blocking-client")]
@@ -100,6 +100,39 @@ pub fn statistics(
         commits_size: ByteSize,
         blobs: usize,
         blobs_size: ByteSize,
+        /// Information about the object database itself, obtained after counting.
+        database: Option<Database>,
+    }
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    struct Database {
+        packs: usize,
+        multi_pack_indices: usize,
+        loose_objects: usize,
+        alternates: Vec<std::path::PathBuf>,
+        has_bitmaps: bool,
+        pack_cache_hits: usize,
+        pack_cache_misses: usize,
+        object_cache_hits: usize,
+        object_cache_misses: usize,
+        metrics: gix::odb::store::Metrics,
+    }
+
+    impl From<gix::repository::ObjectDatabaseStats> for Database {
+        fn from(stats: gix::repository::ObjectDatabaseStats) -> Self {
+            Database {
+                packs: stats.packs,
+                multi_pack_indices: stats.multi_pack_indices,
+                loose_objects: stats.loose_objects,
+                alternates: stats.alternates,
+                has_bitmaps: stats.has_bitmaps,
+                pack_cache_hits: stats.pack_cache.hits,
+                pack_cache_misses: stats.pack_cache.misses,
+                object_cache_hits: stats.object_cache.hits,
+                object_cache_misses: stats.object_cache.misses,
+                metrics: stats.metrics,
+            }
+        }
     }
 
     impl Statistics {
@@ -217,6 +250,7 @@ pub fn statistics(
     };
 
     progress.show_throughput(start);
+    stats.database = Some(repo.object_database_stats()?.into());
 
     if let Some(mut ids) = stats.ids.take() {
         // Critical to re-open the repo to assure we don't have any ODB state and start fresh.
@@ -260,13 +294,19 @@ pub fn statistics(
 }
 
 pub fn entries(repo: gix::Repository, format: OutputFormat, mut out: impl io::Write) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("Only human output format is supported at the moment");
-    }
-
+    #[cfg(feature = "serde")]
+    let mut ids = Vec::new();
     for object in repo.objects.iter()? {
         let object = object?;
-        writeln!(out, "{object}")?;
+        match format {
+            OutputFormat::Human => writeln!(out, "{object}")?,
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => ids.push(object.to_string()),
+        }
+    }
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &ids)?;
     }
 
     Ok(())
@@ -18,7 +18,9 @@ mod refs_impl {
         pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
 
         pub enum Kind {
-            Remote,
+            Remote {
+                patterns: Vec<BString>,
+            },
             Tracking {
                 ref_specs: Vec<BString>,
                 show_unmapped_remote_refs: bool,
@@ -72,13 +74,32 @@ mod refs_impl {
                 .context("Remote didn't have a URL to connect to")?
                 .to_bstring()
         ));
+        if let refs::Kind::Remote { patterns } = &kind {
+            let outcome = remote
+                .connect(gix::remote::Direction::Fetch)
+                .await?
+                .ls_remote(&mut progress, patterns)
+                .await?;
+            if handshake_info {
+                writeln!(out, "Handshake Information")?;
+                writeln!(out, "\t{:?}", outcome.handshake)?;
+            }
+            match format {
+                OutputFormat::Human => drop(print(out, &outcome.refs)),
+                #[cfg(feature = "serde")]
+                OutputFormat::Json => {
+                    serde_json::to_writer_pretty(out, &outcome.refs.into_iter().map(JsonRef::from).collect::<Vec<_>>())?
+                }
+            };
+            return Ok(());
+        }
         let map = remote
             .connect(gix::remote::Direction::Fetch)
             .await?
             .ref_map(
                 &mut progress,
                 gix::remote::ref_map::Options {
-                    prefix_from_spec_as_filter_on_remote: !matches!(kind, refs::Kind::Remote),
+                    prefix_from_spec_as_filter_on_remote: true,
                     ..Default::default()
                 },
             )
@@ -88,27 +109,14 @@ mod refs_impl {
             writeln!(out, "Handshake Information")?;
             writeln!(out, "\t{:?}", map.handshake)?;
         }
-        match kind {
-            refs::Kind::Tracking { .. } => print_refmap(
-                &repo,
-                remote.refspecs(gix::remote::Direction::Fetch),
-                map,
-                show_unmapped,
-                out,
-                err,
-            ),
-            refs::Kind::Remote => {
-                match format {
-                    OutputFormat::Human => drop(print(out, &map.remote_refs)),
-                    #[cfg(feature = "serde")]
-                    OutputFormat::Json => serde_json::to_writer_pretty(
-                        out,
-                        &map.remote_refs.into_iter().map(JsonRef::from).collect::<Vec<_>>(),
-                    )?,
-                };
-                Ok(())
-            }
-        }
+        print_refmap(
+            &repo,
+            remote.refspecs(gix::remote::Direction::Fetch),
+            map,
+            show_unmapped,
+            out,
+            err,
+        )
     }
 
     pub(crate) fn print_refmap(
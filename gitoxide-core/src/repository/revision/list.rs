@@ -37,8 +37,8 @@ pub(crate) mod function {
             limit,
         }: super::Context,
     ) -> anyhow::Result<()> {
-        if format != OutputFormat::Human {
-            bail!("Only human output is currently supported");
+        if format != OutputFormat::Human && matches!(text, Format::Svg { .. }) {
+            bail!("JSON output is not supported when writing an SVG graph");
         }
         repo.object_cache_size_if_unset(4 * 1024 * 1024);
 
@@ -67,6 +67,8 @@ pub(crate) mod function {
         progress.init(None, gix::progress::count("commits"));
         progress.set_name("traverse".into());
 
+        #[cfg(feature = "serde")]
+        let mut json_commits = Vec::new();
         let start = std::time::Instant::now();
         for commit in commits {
             if gix::interrupt::is_triggered() {
@@ -97,15 +99,21 @@ pub(crate) mod function {
                         vg.add_edge(arrow, source, dest);
                     }
                 }
-                None => {
-                    writeln!(
+                None => match format {
+                    OutputFormat::Human => writeln!(
                         out,
                         "{} {} {}",
                         commit.id().shorten_or_id(),
                         commit.commit_time.expect("traversal with date"),
                         commit.parent_ids.len()
-                    )?;
-                }
+                    )?,
+                    #[cfg(feature = "serde")]
+                    OutputFormat::Json => json_commits.push(JsonCommit {
+                        id: commit.id.to_string(),
+                        commit_time: commit.commit_time.expect("traversal with date"),
+                        parent_ids: commit.parent_ids.iter().map(ToString::to_string).collect(),
+                    }),
+                },
             }
             progress.inc();
             if limit.map_or(false, |limit| limit == progress.step()) {
@@ -114,6 +122,10 @@ pub(crate) mod function {
         }
 
         progress.show_throughput(start);
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut out, &json_commits)?;
+        }
         if let Some((mut vg, path, _)) = vg {
             let start = std::time::Instant::now();
             progress.set_name("layout graph".into());
@@ -134,4 +146,16 @@ pub(crate) mod function {
             Element::create(shape, style, Orientation::LeftToRight, pt)
         }
     }
+
+    /// A commit reachable from the starting point, as printed with `--format json`.
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize)]
+    struct JsonCommit {
+        /// The hexadecimal id of the commit.
+        id: String,
+        /// The commit time in seconds since the unix epoch.
+        commit_time: gix::date::SecondsSinceUnixEpoch,
+        /// The hexadecimal ids of the parents of the commit, in order.
+        parent_ids: Vec<String>,
+    }
 }
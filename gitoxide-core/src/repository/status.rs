@@ -2,6 +2,7 @@ use anyhow::bail;
 use gix::bstr::{BStr, BString};
 use gix::status::index_worktree::iter::Item;
 use gix_status::index_as_worktree::{Change, Conflict, EntryStatus};
+use std::borrow::Cow;
 use std::path::Path;
 
 use crate::OutputFormat;
@@ -21,6 +22,8 @@ pub enum Submodules {
 pub enum Ignored {
     Collapsed,
     Matching,
+    /// Collapsed, unless all untracked files are shown in which case it's like `Matching`.
+    Traditional,
 }
 
 #[derive(Copy, Clone)]
@@ -31,6 +34,8 @@ pub enum Format {
 
 pub struct Options {
     pub ignored: Option<Ignored>,
+    /// If `None`, the git configuration decides how untracked files are shown.
+    pub untracked_files: Option<gix::status::UntrackedFiles>,
     pub format: Format,
     pub output_format: OutputFormat,
     pub submodules: Option<Submodules>,
@@ -38,6 +43,47 @@ pub struct Options {
     pub statistics: bool,
     pub allow_write: bool,
     pub index_worktree_renames: Option<f32>,
+    /// If `true` and `index_worktree_renames` is unset, don't track renames even if `status.renames` or `diff.renames` are configured.
+    pub no_renames: bool,
+    /// The maximum amount of rename candidates, with 0 meaning no limit, whenever renames are tracked.
+    ///
+    /// If `None`, `status.renameLimit` or `diff.renameLimit` are used if renames are enabled by configuration,
+    /// and there is no limit if renames are enabled with `index_worktree_renames`.
+    pub rename_limit: Option<usize>,
+}
+
+/// Read rename tracking from `status.renames` and `status.renameLimit`, falling back to their `diff.*` counterparts.
+///
+/// Unlike `git`, renames are not tracked if none of these is configured.
+fn configured_rewrites(repo: &gix::Repository) -> anyhow::Result<Option<gix::diff::Rewrites>> {
+    use gix::config::tree::{Diff, Status};
+    use gix::diff::rename::Tracking;
+
+    let config = repo.config_snapshot();
+    let Some(renames) = config.plumbing().boolean("status.renames") else {
+        return Ok(gix::diff::new_rewrites(config.plumbing(), true)?);
+    };
+    let copies = match Status::RENAMES.try_into_renames(renames)? {
+        Tracking::Disabled => return Ok(None),
+        Tracking::Renames => None,
+        Tracking::RenamesAndCopies => Some(Default::default()),
+    };
+    let limit = config
+        .plumbing()
+        .integer("status.renameLimit")
+        .map(|limit| Status::RENAME_LIMIT.try_into_usize(limit))
+        .or_else(|| {
+            config
+                .plumbing()
+                .integer("diff.renameLimit")
+                .map(|limit| Diff::RENAME_LIMIT.try_into_usize(limit))
+        })
+        .transpose()?;
+    Ok(Some(gix::diff::Rewrites {
+        copies,
+        limit: limit.unwrap_or_else(|| gix::diff::Rewrites::default().limit),
+        ..Default::default()
+    }))
 }
 
 pub fn show(
@@ -48,6 +94,7 @@ pub fn show(
     mut progress: impl gix::NestedProgress + 'static,
     Options {
         ignored,
+        untracked_files,
         format,
         output_format,
         submodules,
@@ -55,33 +102,50 @@ pub fn show(
         allow_write,
         statistics,
         index_worktree_renames,
+        no_renames,
+        rename_limit,
     }: Options,
 ) -> anyhow::Result<()> {
-    if output_format != OutputFormat::Human {
-        bail!("Only human format is supported right now");
-    }
     if !matches!(format, Format::Simplified) {
         bail!("Only the simplified format is currently implemented");
     }
 
     let start = std::time::Instant::now();
+    let rewrites = match index_worktree_renames {
+        Some(percentage) => Some(gix::diff::Rewrites {
+            copies: None,
+            percentage: Some(percentage),
+            limit: rename_limit.unwrap_or_default(),
+        }),
+        None if no_renames => None,
+        None => configured_rewrites(&repo)?.map(|rewrites| gix::diff::Rewrites {
+            limit: rename_limit.unwrap_or(rewrites.limit),
+            ..rewrites
+        }),
+    };
     let prefix = repo.prefix()?.unwrap_or(Path::new(""));
     let index_progress = progress.add_child("traverse index");
-    let mut iter = repo
+    // Without a directory walk there are no ignored files either, so keep it and filter untracked files instead.
+    let hide_untracked = matches!(untracked_files, Some(gix::status::UntrackedFiles::None)) && ignored.is_some();
+    let mut status = repo
         .status(index_progress)?
-        .should_interrupt_shared(&gix::interrupt::IS_INTERRUPTED)
+        .should_interrupt_shared(&gix::interrupt::IS_INTERRUPTED);
+    if let Some(untracked_files) = untracked_files.filter(|_| !hide_untracked) {
+        status = status.untracked_files(untracked_files);
+    }
+    if let Some(ignored) = ignored {
+        status = status.ignored_files(match ignored {
+            Ignored::Collapsed => gix::status::IgnoredFiles::Collapsed,
+            Ignored::Matching => gix::status::IgnoredFiles::Matching,
+            Ignored::Traditional => match untracked_files {
+                Some(gix::status::UntrackedFiles::Files) => gix::status::IgnoredFiles::Matching,
+                _ => gix::status::IgnoredFiles::Collapsed,
+            },
+        });
+    }
+    let mut iter = status
         .index_worktree_options_mut(|opts| {
-            if let Some((opts, ignored)) = opts.dirwalk_options.as_mut().zip(ignored) {
-                opts.set_emit_ignored(Some(match ignored {
-                    Ignored::Collapsed => gix::dir::walk::EmissionMode::CollapseDirectory,
-                    Ignored::Matching => gix::dir::walk::EmissionMode::Matching,
-                }));
-            }
-            opts.rewrites = index_worktree_renames.map(|percentage| gix::diff::Rewrites {
-                copies: None,
-                percentage: Some(percentage),
-                limit: 0,
-            });
+            opts.rewrites = rewrites;
             if opts.rewrites.is_some() {
                 if let Some(opts) = opts.dirwalk_options.as_mut() {
                     opts.set_emit_untracked(gix::dir::walk::EmissionMode::Matching);
@@ -110,32 +174,52 @@ pub fn show(
         })
         .into_index_worktree_iter(pathspecs)?;
 
+    let display_path = |rela_path: &BStr| {
+        gix::path::relativize_with_prefix(&gix::path::from_bstr(rela_path), prefix)
+            .to_string_lossy()
+            .into_owned()
+    };
+    #[cfg(feature = "serde")]
+    let mut json_items = Vec::new();
     for item in iter.by_ref() {
         let item = item?;
-        match item {
+        let item = match item {
+            Item::Conflict { rela_path, kind, .. } => JsonItem {
+                status: as_str(kind).into(),
+                path: display_path(rela_path.as_ref()),
+                source_path: None,
+            },
             Item::Modification {
                 entry: _,
                 entry_index: _,
                 rela_path,
                 status,
-            } => print_index_entry_status(&mut out, prefix, rela_path.as_ref(), status)?,
+            } => {
+                let Some(status) = index_entry_status(status) else {
+                    continue;
+                };
+                JsonItem {
+                    status,
+                    path: display_path(rela_path.as_ref()),
+                    source_path: None,
+                }
+            }
             Item::DirectoryContents {
                 entry,
                 collapsed_directory_status,
             } => {
-                if collapsed_directory_status.is_none() {
-                    writeln!(
-                        out,
-                        "{status: >3} {rela_path}{slash}",
-                        status = "?",
-                        rela_path =
-                            gix::path::relativize_with_prefix(&gix::path::from_bstr(entry.rela_path), prefix).display(),
-                        slash = if entry.disk_kind.unwrap_or(gix::dir::entry::Kind::File).is_dir() {
-                            "/"
-                        } else {
-                            ""
-                        }
-                    )?;
+                let is_ignored = matches!(entry.status, gix::dir::entry::Status::Ignored(_));
+                if hide_untracked && !is_ignored || collapsed_directory_status.is_some() {
+                    continue;
+                }
+                let mut path = display_path(entry.rela_path.as_ref());
+                if entry.disk_kind.unwrap_or(gix::dir::entry::Kind::File).is_dir() {
+                    path.push('/');
+                }
+                JsonItem {
+                    status: if is_ignored { "!" } else { "?" }.into(),
+                    path,
+                    source_path: None,
                 }
             }
             Item::Rewrite {
@@ -145,21 +229,26 @@ pub fn show(
                 ..
             } => {
                 // TODO: handle multi-status characters, there can also be modifications at the same time as determined by their ID and potentially diffstats.
-                writeln!(
-                    out,
-                    "{status: >3} {source_rela_path} → {dest_rela_path}",
-                    status = "R",
-                    source_rela_path =
-                        gix::path::relativize_with_prefix(&gix::path::from_bstr(source.rela_path()), prefix).display(),
-                    dest_rela_path = gix::path::relativize_with_prefix(
-                        &gix::path::from_bstr(dirwalk_entry.rela_path.as_ref()),
-                        prefix
-                    )
-                    .display(),
-                )?;
+                JsonItem {
+                    status: "R".into(),
+                    path: display_path(dirwalk_entry.rela_path.as_ref()),
+                    source_path: Some(display_path(source.rela_path())),
+                }
             }
+        };
+        match output_format {
+            OutputFormat::Human => match &item.source_path {
+                Some(source_path) => writeln!(out, "{: >3} {source_path} → {}", item.status, item.path)?,
+                None => writeln!(out, "{: >3} {}", item.status, item.path)?,
+            },
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => json_items.push(item),
         }
     }
+    #[cfg(feature = "serde")]
+    if output_format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_items)?;
+    }
     if gix::interrupt::is_triggered() {
         bail!("interrupted by user");
     }
@@ -181,28 +270,25 @@ pub fn show(
     Ok(())
 }
 
-fn print_index_entry_status(
-    out: &mut dyn std::io::Write,
-    prefix: &Path,
-    rela_path: &BStr,
-    status: EntryStatus<(), gix::submodule::Status>,
-) -> std::io::Result<()> {
-    let char_storage;
-    let status = match status {
-        EntryStatus::Conflict(conflict) => as_str(conflict),
-        EntryStatus::Change(change) => {
-            char_storage = change_to_char(&change);
-            std::str::from_utf8(std::slice::from_ref(&char_storage)).expect("valid ASCII")
-        }
-        EntryStatus::NeedsUpdate(_stat) => {
-            return Ok(());
-        }
-        EntryStatus::IntentToAdd => "A",
-    };
+/// A change in the worktree compared to the index, as printed with `--format json`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct JsonItem {
+    /// The status like `git status --short` shows it, like `M` for modifications, `?` for untracked files or `UU` for conflicts.
+    status: Cow<'static, str>,
+    /// The path relative to the current working directory, with a trailing slash if it is a directory.
+    path: String,
+    /// The path the item was renamed from, relative to the current working directory.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    source_path: Option<String>,
+}
 
-    let rela_path = gix::path::from_bstr(rela_path);
-    let display_path = gix::path::relativize_with_prefix(&rela_path, prefix);
-    writeln!(out, "{status: >3} {}", display_path.display())
+fn index_entry_status(status: EntryStatus<(), gix::submodule::Status>) -> Option<Cow<'static, str>> {
+    Some(match status {
+        EntryStatus::Conflict(conflict) => as_str(conflict).into(),
+        EntryStatus::Change(change) => char::from(change_to_char(&change)).to_string().into(),
+        EntryStatus::NeedsUpdate(_stat) => return None,
+        EntryStatus::IntentToAdd => "A".into(),
+    })
 }
 
 fn as_str(c: Conflict) -> &'static str {
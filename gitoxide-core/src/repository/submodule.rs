@@ -1,4 +1,3 @@
-use anyhow::bail;
 use gix::{commit::describe::SelectRef, prelude::ObjectIdExt, Repository, Submodule};
 
 use crate::OutputFormat;
@@ -9,15 +8,25 @@ pub fn list(
     format: OutputFormat,
     dirty_suffix: Option<String>,
 ) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("Only human output is supported for now")
-    }
-
     let Some(submodules) = repo.submodules()? else {
+        #[cfg(feature = "serde")]
+        if format == OutputFormat::Json {
+            serde_json::to_writer_pretty(&mut out, &Vec::<JsonSubmodule>::new())?;
+        }
         return Ok(());
     };
+    #[cfg(feature = "serde")]
+    let mut json_submodules = Vec::new();
     for sm in submodules {
-        print_sm(sm, dirty_suffix.as_deref(), &mut out)?;
+        match format {
+            OutputFormat::Human => print_sm(sm, dirty_suffix.as_deref(), &mut out)?,
+            #[cfg(feature = "serde")]
+            OutputFormat::Json => json_submodules.push(JsonSubmodule::new(sm, dirty_suffix.as_deref())?),
+        }
+    }
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_submodules)?;
     }
     Ok(())
 }
@@ -25,10 +34,7 @@ pub fn list(
 fn print_sm(sm: Submodule<'_>, dirty_suffix: Option<&str>, out: &mut impl std::io::Write) -> anyhow::Result<()> {
     let _span = gix::trace::coarse!("print_sm", path = ?sm.path());
     let state = sm.state()?;
-    let mut sm_repo = sm.open()?;
-    if let Some(repo) = sm_repo.as_mut() {
-        repo.object_cache_size_if_unset(4 * 1024 * 1024);
-    };
+    let sm_repo = open(&sm)?;
     writeln!(
         out,
         " {is_active} {path} {config} head:{head_id} index:{index_id} ({worktree}) [{url}]",
@@ -46,19 +52,7 @@ fn print_sm(sm: Submodule<'_>, dirty_suffix: Option<&str>, out: &mut impl std::i
         head_id = submodule_short_hash(sm.head_id()?, sm_repo.as_ref()),
         index_id = submodule_short_hash(sm.index_id()?, sm_repo.as_ref()),
         worktree = match sm_repo {
-            Some(repo) => {
-                // TODO(name-revision): this is the simple version, `git` gives it
-                // multiple tries https://github.com/git/git/blob/fac96dfbb1c24369ba7d37a5affd8adfe6c650fd/builtin/submodule--helper.c#L161
-                // and even uses `git name-rev`/`git describe --contains` which we can't do yet.
-                repo.head_commit()?
-                    .describe()
-                    .names(SelectRef::AllRefs)
-                    .id_as_fallback(true)
-                    .try_resolve()?
-                    .expect("resolution present if ID can be used as fallback")
-                    .format_with_dirty_suffix(dirty_suffix.map(ToOwned::to_owned))?
-                    .to_string()
-            }
+            Some(repo) => describe_head(&repo, dirty_suffix)?,
             None => {
                 "no worktree".to_string()
             }
@@ -68,6 +62,70 @@ fn print_sm(sm: Submodule<'_>, dirty_suffix: Option<&str>, out: &mut impl std::i
     Ok(())
 }
 
+/// A submodule, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonSubmodule {
+    /// The name of the submodule as configured in `.gitmodules`.
+    name: String,
+    /// The path of the submodule relative to the worktree of the superproject.
+    path: String,
+    /// The URL to fetch the submodule from.
+    url: String,
+    /// `true` if the submodule is active and its repository exists.
+    is_active: bool,
+    /// `true` if the submodule is configured in the configuration of the superproject.
+    superproject_configuration: bool,
+    /// The commit checked out in the submodule repository, or `null` if there is none.
+    head_id: Option<String>,
+    /// The commit of the submodule as recorded in the index of the superproject, or `null` if there is none.
+    index_id: Option<String>,
+    /// A description of the checked out commit like `git describe` produces, or `null` if there is no worktree.
+    worktree: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl JsonSubmodule {
+    fn new(sm: Submodule<'_>, dirty_suffix: Option<&str>) -> anyhow::Result<Self> {
+        let _span = gix::trace::coarse!("JsonSubmodule::new", path = ?sm.path());
+        let state = sm.state()?;
+        let sm_repo = open(&sm)?;
+        Ok(JsonSubmodule {
+            name: sm.name().to_string(),
+            path: sm.path()?.to_string(),
+            url: sm.url()?.to_bstring().to_string(),
+            is_active: sm.is_active()? && state.repository_exists,
+            superproject_configuration: state.superproject_configuration,
+            head_id: sm.head_id()?.map(|id| id.to_string()),
+            index_id: sm.index_id()?.map(|id| id.to_string()),
+            worktree: sm_repo.map(|repo| describe_head(&repo, dirty_suffix)).transpose()?,
+        })
+    }
+}
+
+fn open(sm: &Submodule<'_>) -> anyhow::Result<Option<Repository>> {
+    let mut sm_repo = sm.open()?;
+    if let Some(repo) = sm_repo.as_mut() {
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+    };
+    Ok(sm_repo)
+}
+
+fn describe_head(repo: &Repository, dirty_suffix: Option<&str>) -> anyhow::Result<String> {
+    // TODO(name-revision): this is the simple version, `git` gives it
+    // multiple tries https://github.com/git/git/blob/fac96dfbb1c24369ba7d37a5affd8adfe6c650fd/builtin/submodule--helper.c#L161
+    // and even uses `git name-rev`/`git describe --contains` which we can't do yet.
+    Ok(repo
+        .head_commit()?
+        .describe()
+        .names(SelectRef::AllRefs)
+        .id_as_fallback(true)
+        .try_resolve()?
+        .expect("resolution present if ID can be used as fallback")
+        .format_with_dirty_suffix(dirty_suffix.map(ToOwned::to_owned))?
+        .to_string())
+}
+
 fn submodule_short_hash(id: Option<gix::ObjectId>, repo: Option<&Repository>) -> String {
     id.map_or_else(
         || "none".to_string(),
@@ -1,6 +1,5 @@
 use std::{borrow::Cow, io};
 
-use anyhow::bail;
 use gix::Tree;
 
 use crate::OutputFormat;
@@ -36,6 +35,9 @@ mod entries {
         out: Option<&'a mut dyn std::io::Write>,
         path: BString,
         path_deque: VecDeque<BString>,
+        /// If set, entries are collected here instead of being written to `out`.
+        #[cfg(feature = "serde")]
+        pub entries: Option<Vec<super::JsonEntry>>,
     }
 
     impl<'repo, 'a> Traverse<'repo, 'a> {
@@ -46,6 +48,8 @@ mod entries {
                 out,
                 path: BString::default(),
                 path_deque: VecDeque::new(),
+                #[cfg(feature = "serde")]
+                entries: None,
             }
         }
 
@@ -92,6 +96,10 @@ mod entries {
             let size = self
                 .repo
                 .and_then(|repo| repo.find_object(entry.oid).map(|o| o.data.len()).ok());
+            #[cfg(feature = "serde")]
+            if let Some(entries) = &mut self.entries {
+                entries.push(super::JsonEntry::new(entry, self.path.as_bstr(), size));
+            }
             if let Some(out) = &mut self.out {
                 format_entry(out, entry, self.path.as_bstr(), size).ok();
             }
@@ -147,32 +155,75 @@ pub fn entries(
     format: OutputFormat,
     mut out: impl io::Write,
 ) -> anyhow::Result<()> {
-    if format != OutputFormat::Human {
-        bail!("Only human output format is supported at the moment");
-    }
-
     let tree = treeish_to_tree(treeish, &repo)?;
 
+    #[cfg(feature = "serde")]
+    let mut json_entries = Vec::new();
     if recursive {
-        let mut delegate = entries::Traverse::new(extended.then_some(&repo), Some(&mut out));
+        let is_human = format == OutputFormat::Human;
+        let mut delegate = entries::Traverse::new(extended.then_some(&repo), is_human.then_some(&mut out));
+        #[cfg(feature = "serde")]
+        if !is_human {
+            delegate.entries = Some(Vec::new());
+        }
         tree.traverse().breadthfirst(&mut delegate)?;
+        #[cfg(feature = "serde")]
+        json_entries.extend(delegate.entries.into_iter().flatten());
     } else {
         for entry in tree.iter() {
             let entry = entry?;
-            format_entry(
-                &mut out,
-                &entry.inner,
-                entry.inner.filename,
-                extended
-                    .then(|| entry.id().object().map(|o| o.data.len()))
-                    .transpose()?,
-            )?;
+            let size = extended
+                .then(|| entry.id().object().map(|o| o.data.len()))
+                .transpose()?;
+            match format {
+                OutputFormat::Human => format_entry(&mut out, &entry.inner, entry.inner.filename, size)?,
+                #[cfg(feature = "serde")]
+                OutputFormat::Json => json_entries.push(JsonEntry::new(&entry.inner, entry.inner.filename, size)),
+            }
         }
     }
+    #[cfg(feature = "serde")]
+    if format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &json_entries)?;
+    }
 
     Ok(())
 }
 
+/// A tree entry, as printed with `--format json`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+pub(crate) struct JsonEntry {
+    /// The kind of entry, one of `tree`, `blob`, `blob-executable`, `link` or `commit` for submodules.
+    kind: &'static str,
+    /// The hexadecimal id of the object the entry points to.
+    id: String,
+    /// The size of the object in bytes, only set in extended mode for non-tree entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<usize>,
+    /// The path of the entry, relative to the root of the tree when listing recursively.
+    path: String,
+}
+
+#[cfg(feature = "serde")]
+impl JsonEntry {
+    fn new(entry: &gix::objs::tree::EntryRef<'_>, path: &gix::bstr::BStr, size: Option<usize>) -> Self {
+        use gix::objs::tree::EntryKind::*;
+        JsonEntry {
+            kind: match entry.mode.kind() {
+                Tree => "tree",
+                Blob => "blob",
+                BlobExecutable => "blob-executable",
+                Link => "link",
+                Commit => "commit",
+            },
+            id: entry.oid.to_string(),
+            size,
+            path: path.to_string(),
+        }
+    }
+}
+
 fn treeish_to_tree<'repo>(treeish: Option<&str>, repo: &'repo gix::Repository) -> anyhow::Result<Tree<'repo>> {
     let spec = treeish.map_or_else(|| "@^{tree}".into(), |spec| format!("{spec}^{{tree}}"));
     Ok(repo.rev_parse_single(spec.as_str())?.object()?.into_tree())
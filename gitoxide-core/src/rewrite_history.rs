@@ -0,0 +1,543 @@
+//! Rewrite the history of a repository to keep, drop or rename paths, similar to
+//! [git-filter-repo](https://github.com/newren/git-filter-repo).
+//!
+//! A typical use is extracting a subdirectory into its own repository, which is the same as keeping only that
+//! directory and renaming it to be the root of the tree.
+//!
+//! New objects are written to the object database of the repository, while references are only changed
+//! if [`Options::update_refs`] is set. This makes it possible to look at the outcome before making it permanent.
+//! If the checked-out commit is rewritten, the index and worktree are changed to match it.
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use anyhow::{bail, Context};
+use gix::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    objs::tree::EntryMode,
+    refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Count, NestedProgress, ObjectId, Progress,
+};
+
+/// Define which paths to keep and how to rename them.
+#[derive(Debug, Default, Clone)]
+pub struct PathRules {
+    /// Paths of files or directories to keep, relative to the repository root and without trailing slash.
+    ///
+    /// If empty, all paths are kept.
+    pub paths: Vec<BString>,
+    /// If `true`, drop all [`paths`](Self::paths) and keep everything else instead.
+    pub invert_paths: bool,
+    /// Pairs of `(from, to)` paths of files or directories to rename, applied after filtering.
+    ///
+    /// The first matching rename is used, and an empty `to` moves the contents of `from` to the root of the tree.
+    pub renames: Vec<(BString, BString)>,
+}
+
+/// Additional configuration for rewriting history.
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    /// The rules to apply to the tree of each commit.
+    pub rules: PathRules,
+    /// If `true`, keep commits whose tree didn't change compared to their parent after rewriting.
+    ///
+    /// Otherwise these are dropped, which is typically desired as many commits won't touch the kept paths at all.
+    pub keep_empty_commits: bool,
+    /// If `true`, update all references to point to the rewritten history, and delete references to commits
+    /// that were dropped entirely.
+    ///
+    /// If `HEAD` is affected, the worktree must not have any changes as it's updated to the rewritten commit.
+    pub update_refs: bool,
+}
+
+/// The way a reference was changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefMapping {
+    /// The full name of the reference, or `HEAD` if it's detached.
+    pub name: BString,
+    /// The object the reference pointed to before the rewrite.
+    pub old: ObjectId,
+    /// The object the reference points to after the rewrite, or `None` if all commits reachable from it were dropped.
+    pub new: Option<ObjectId>,
+}
+
+/// The result of [`rewrite()`].
+#[derive(Debug, Default, Clone)]
+pub struct Outcome {
+    /// A mapping of each original commit to its rewritten version, or to `None` if it was dropped without replacement.
+    ///
+    /// Dropped commits that have a rewritten ancestor are mapped to that ancestor instead.
+    pub commits: HashMap<ObjectId, Option<ObjectId>>,
+    /// All references which are affected by the rewrite, sorted by name.
+    pub refs: Vec<RefMapping>,
+}
+
+/// Rewrite all commits reachable from all references in `repo` according to `options` and return a mapping
+/// of old to new commits and references.
+///
+/// Commits are rewritten with their original author, committer and message, but signatures are removed as they
+/// wouldn't be valid anymore. The same is true for annotated tags, which are rewritten to point to the new commit.
+pub fn rewrite(repo: &gix::Repository, mut progress: impl NestedProgress, options: Options) -> anyhow::Result<Outcome> {
+    let mut refs = Vec::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(|err| anyhow::anyhow!("{err}"))?;
+        if let Some(id) = reference.target().try_id() {
+            refs.push((reference.name().to_owned(), id.to_owned()));
+        }
+    }
+    let head = repo.head()?;
+    let head_name = match head.referent_name() {
+        Some(name) => name.to_owned(),
+        None => "HEAD".try_into().expect("valid"),
+    };
+    if head.is_detached() {
+        if let Some(id) = head.id() {
+            refs.insert(0, (head_name.clone(), id.detach()));
+        }
+    }
+
+    let mut rewriter = Rewriter {
+        repo,
+        rules: &options.rules,
+        keep_empty_commits: options.keep_empty_commits,
+        commits: HashMap::new(),
+        trees: HashMap::new(),
+    };
+    let mut commit_progress = progress.add_child("rewriting commits");
+    commit_progress.init(None, gix::progress::count("commits"));
+    let mut ref_map = Vec::new();
+    for (name, old) in refs {
+        let new = rewriter.rewrite_object(old, &mut commit_progress)?;
+        if new != Some(old) {
+            ref_map.push((name, old, new));
+        }
+    }
+
+    if options.update_refs {
+        let head_change = ref_map
+            .iter()
+            .find(|(name, _, _)| *name == head_name)
+            .map(|(_, old, new)| (*old, *new));
+        let checkout = match head_change {
+            Some((_, None)) => bail!(
+                "Refusing to drop all commits reachable from the checked-out '{}'",
+                head_name.as_bstr()
+            ),
+            Some((old, Some(new))) => match repo.work_dir() {
+                Some(workdir) => {
+                    if repo.is_dirty()? {
+                        bail!("Refusing to rewrite the checked-out commit as the worktree has changes")
+                    }
+                    Some((workdir, old, new))
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        let edits: Vec<_> = ref_map
+            .iter()
+            .map(|(name, old, new)| RefEdit {
+                change: match new {
+                    Some(new) => Change::Update {
+                        log: LogChange {
+                            mode: RefLog::AndReference,
+                            force_create_reflog: false,
+                            message: "rewrite-history".into(),
+                        },
+                        expected: PreviousValue::MustExistAndMatch((*old).into()),
+                        new: (*new).into(),
+                    },
+                    None => Change::Delete {
+                        expected: PreviousValue::MustExistAndMatch((*old).into()),
+                        log: RefLog::AndReference,
+                    },
+                },
+                name: name.clone(),
+                deref: false,
+            })
+            .collect();
+        repo.edit_references(edits)?;
+
+        if let Some((workdir, old, new)) = checkout {
+            checkout_rewritten_head(repo, workdir, old, new, &mut progress)?;
+        }
+    }
+
+    Ok(Outcome {
+        commits: rewriter.commits,
+        refs: ref_map
+            .into_iter()
+            .map(|(name, old, new)| RefMapping {
+                name: name.as_bstr().to_owned(),
+                old,
+                new,
+            })
+            .collect(),
+    })
+}
+
+/// Change the index and the files in `workdir` from the tree of commit `old` to the tree of commit `new`,
+/// assuming they are unchanged compared to `old`.
+fn checkout_rewritten_head(
+    repo: &gix::Repository,
+    workdir: &Path,
+    old: ObjectId,
+    new: ObjectId,
+    progress: &mut impl NestedProgress,
+) -> anyhow::Result<()> {
+    let tree_of = |id: ObjectId| -> anyhow::Result<ObjectId> { Ok(repo.find_object(id)?.peel_to_tree()?.id) };
+    let (old_tree, new_tree) = (tree_of(old)?, tree_of(new)?);
+    if old_tree == new_tree {
+        return Ok(());
+    }
+    let old_index = repo.index_from_tree(&old_tree)?;
+    let mut index = repo.index_from_tree(&new_tree)?;
+    for entry in old_index.entries() {
+        let rela_path = entry.path(&old_index);
+        if index.entry_by_path(rela_path).is_some() {
+            continue;
+        }
+        let path = workdir.join(gix::path::from_bstr(rela_path));
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("Could not remove '{}'", path.display())),
+        }
+        let mut dir = path.parent();
+        while let Some(parent) = dir.filter(|dir| *dir != workdir) {
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+    }
+
+    let options = gix::worktree::state::checkout::Options {
+        fs: gix::fs::Capabilities::probe(workdir),
+        overwrite_existing: true,
+        filters: repo.filter_pipeline(None)?.0.into_parts().0,
+        ..Default::default()
+    };
+    let files = progress.add_child("checkout");
+    let bytes = progress.add_child("writing");
+    let outcome = gix::worktree::state::checkout(
+        &mut index,
+        workdir,
+        repo.objects.clone().into_arc()?,
+        &files,
+        &bytes,
+        &gix::interrupt::IS_INTERRUPTED,
+        options,
+    )?;
+    if let Some(err) = outcome.errors.first() {
+        bail!(
+            "Could not check out {} file(s) of the rewritten commit, the first being '{}': {}",
+            outcome.errors.len(),
+            err.path,
+            err.error
+        );
+    }
+    index.write(Default::default())?;
+    Ok(())
+}
+
+struct Rewriter<'repo> {
+    repo: &'repo gix::Repository,
+    rules: &'repo PathRules,
+    keep_empty_commits: bool,
+    commits: HashMap<ObjectId, Option<ObjectId>>,
+    /// Maps original root trees to their rewritten version.
+    trees: HashMap<ObjectId, ObjectId>,
+}
+
+impl Rewriter<'_> {
+    /// Rewrite the object `id` points to, returning `None` if it's a commit or tag pointing to a commit that was dropped.
+    fn rewrite_object(&mut self, id: ObjectId, progress: &mut impl Progress) -> anyhow::Result<Option<ObjectId>> {
+        let object = self.repo.find_object(id)?;
+        Ok(match object.kind {
+            gix::object::Kind::Commit => self.rewrite_commit(id, progress)?,
+            gix::object::Kind::Tag => {
+                let mut tag: gix::objs::Tag = object.try_to_tag_ref()?.into();
+                match self.rewrite_object(tag.target, progress)? {
+                    Some(target) if target == tag.target => Some(id),
+                    Some(target) => {
+                        tag.target = target;
+                        tag.pgp_signature = None;
+                        Some(self.repo.write_object(&tag)?.detach())
+                    }
+                    None => None,
+                }
+            }
+            gix::object::Kind::Tree | gix::object::Kind::Blob => Some(id),
+        })
+    }
+
+    /// Rewrite `tip` and all of its ancestors, parents first, without recursion to support deep histories.
+    fn rewrite_commit(&mut self, tip: ObjectId, progress: &mut impl Progress) -> anyhow::Result<Option<ObjectId>> {
+        let mut stack = vec![(tip, false)];
+        while let Some((id, parents_done)) = stack.pop() {
+            if self.commits.contains_key(&id) {
+                continue;
+            }
+            let commit = self.repo.find_object(id)?.try_into_commit()?;
+            if !parents_done {
+                stack.push((id, true));
+                stack.extend(
+                    commit
+                        .parent_ids()
+                        .map(|id| (id.detach(), false))
+                        .filter(|(id, _)| !self.commits.contains_key(id)),
+                );
+                continue;
+            }
+            if gix::interrupt::is_triggered() {
+                bail!("Cancelled by user");
+            }
+
+            let mut commit: gix::objs::Commit = commit.decode()?.into();
+            let mut parents = Vec::with_capacity(commit.parents.len());
+            for parent in &commit.parents {
+                if let Some(parent) = self.commits[parent] {
+                    if !parents.contains(&parent) {
+                        parents.push(parent);
+                    }
+                }
+            }
+            let tree = self.rewrite_root_tree(commit.tree)?;
+            let is_empty = match parents.as_slice() {
+                [] => tree == ObjectId::empty_tree(self.repo.object_hash()),
+                [parent] => tree == self.repo.find_object(*parent)?.try_into_commit()?.tree_id()?,
+                _ => false,
+            };
+            let new = if is_empty && !self.keep_empty_commits {
+                parents.first().copied()
+            } else if tree == commit.tree && parents.as_slice() == commit.parents.as_slice() {
+                Some(id)
+            } else {
+                commit.tree = tree;
+                commit.parents = parents.into();
+                commit.extra_headers.retain(|(name, _)| name != "gpgsig");
+                Some(self.repo.write_object(&commit)?.detach())
+            };
+            self.commits.insert(id, new);
+            progress.inc();
+        }
+        Ok(self.commits[&tip])
+    }
+
+    fn rewrite_root_tree(&mut self, tree: ObjectId) -> anyhow::Result<ObjectId> {
+        if let Some(new) = self.trees.get(&tree) {
+            return Ok(*new);
+        }
+        let new = if self.rules.paths.is_empty() && self.rules.renames.is_empty() {
+            tree
+        } else {
+            let mut root = Node::default();
+            self.collect(tree, BString::default(), &mut root)?;
+            root.write(self.repo)?
+                .unwrap_or_else(|| ObjectId::empty_tree(self.repo.object_hash()))
+        };
+        self.trees.insert(tree, new);
+        Ok(new)
+    }
+
+    /// Add all entries of `tree` at `prefix` that pass our rules to `root`.
+    fn collect(&self, tree: ObjectId, prefix: BString, root: &mut Node) -> anyhow::Result<()> {
+        let tree = self.repo.find_object(tree)?.try_into_tree()?;
+        let mut path = prefix.clone();
+        for entry in tree.iter() {
+            let entry = entry?;
+            path.clone_from(&prefix);
+            if !path.is_empty() {
+                path.push_byte(b'/');
+            }
+            path.push_str(entry.filename());
+            let mode = entry.mode();
+            let oid = entry.oid().to_owned();
+            match self.rules.selection(path.as_ref(), mode.is_tree()) {
+                Selection::Drop => {}
+                Selection::Keep => {
+                    let new_path = self.rules.rename(path.as_ref());
+                    if new_path.is_empty() && !mode.is_tree() {
+                        bail!("Cannot move '{path}' to the root of the tree as only directories can be renamed to ''");
+                    }
+                    root.insert(self.repo, new_path.as_ref(), mode, oid)?
+                }
+                Selection::Descend => self.collect(oid, path.clone(), root)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Selection {
+    /// Drop the path entirely, including all of its contents.
+    Drop,
+    /// Keep the path along with all of its contents, if it's a directory.
+    Keep,
+    /// Rules apply to some of the contents of the directory at path, so its contents have to be looked at individually.
+    Descend,
+}
+
+impl PathRules {
+    fn selection(&self, path: &BStr, is_dir: bool) -> Selection {
+        let selected = self.paths.is_empty() || self.paths.iter().any(|p| is_same_or_below(path, p.as_ref()));
+        let is_partial = is_dir
+            && self
+                .paths
+                .iter()
+                .any(|p| is_same_or_below(p.as_ref(), path) && p != path);
+        let keep = if is_partial {
+            return Selection::Descend;
+        } else {
+            selected != self.invert_paths
+        };
+        if !keep {
+            return Selection::Drop;
+        }
+        if is_dir
+            && self
+                .renames
+                .iter()
+                .any(|(from, _)| is_same_or_below(from.as_ref(), path) && from != path)
+        {
+            return Selection::Descend;
+        }
+        Selection::Keep
+    }
+
+    fn rename(&self, path: &BStr) -> BString {
+        match self
+            .renames
+            .iter()
+            .find(|(from, _)| is_same_or_below(path, from.as_ref()))
+        {
+            Some((from, to)) => {
+                let mut out = to.clone();
+                let rest = &path[from.len()..];
+                if out.is_empty() {
+                    out.push_str(rest.strip_prefix(b"/").unwrap_or(rest));
+                } else {
+                    out.push_str(rest);
+                }
+                out
+            }
+            None => path.to_owned(),
+        }
+    }
+}
+
+/// Return `true` if `path` is `dir` or inside of it.
+fn is_same_or_below(path: &BStr, dir: &BStr) -> bool {
+    matches!(path.strip_prefix(dir.as_bytes()), Some(rest) if rest.is_empty() || rest[0] == b'/')
+}
+
+/// A tree under construction, with unchanged subtrees kept as is until something has to be inserted into them.
+#[derive(Default)]
+struct Node {
+    children: BTreeMap<BString, Child>,
+}
+
+enum Child {
+    Existing { mode: EntryMode, oid: ObjectId },
+    Tree(Node),
+}
+
+impl Node {
+    fn insert(&mut self, repo: &gix::Repository, path: &BStr, mode: EntryMode, oid: ObjectId) -> anyhow::Result<()> {
+        if path.is_empty() {
+            // Renaming a directory to the root merges its contents with the root tree.
+            let tree = repo.find_object(oid)?.try_into_tree()?;
+            for entry in tree.iter() {
+                let entry = entry?;
+                self.insert(repo, entry.filename(), entry.mode(), entry.oid().to_owned())?;
+            }
+            return Ok(());
+        }
+        let (name, rest) = match path.find_byte(b'/') {
+            Some(pos) => (&path[..pos], Some(path[pos + 1..].as_bstr())),
+            None => (path, None),
+        };
+        let Some(rest) = rest else {
+            self.children.insert(name.into(), Child::Existing { mode, oid });
+            return Ok(());
+        };
+        let child = self
+            .children
+            .entry(name.into())
+            .or_insert_with(|| Child::Tree(Node::default()));
+        if let Child::Existing { mode, oid } = child {
+            let mut expanded = Node::default();
+            if mode.is_tree() {
+                let tree = repo.find_object(*oid)?.try_into_tree()?;
+                for entry in tree.iter() {
+                    let entry = entry?;
+                    expanded.children.insert(
+                        entry.filename().to_owned(),
+                        Child::Existing {
+                            mode: entry.mode(),
+                            oid: entry.oid().to_owned(),
+                        },
+                    );
+                }
+            }
+            *child = Child::Tree(expanded);
+        }
+        match child {
+            Child::Tree(node) => node.insert(repo, rest, mode, oid),
+            Child::Existing { .. } => unreachable!("expanded above"),
+        }
+    }
+
+    /// Write this tree and all of its subtrees, and return its id, or `None` if it's empty.
+    fn write(self, repo: &gix::Repository) -> anyhow::Result<Option<ObjectId>> {
+        let mut entries = Vec::with_capacity(self.children.len());
+        for (filename, child) in self.children {
+            let (mode, oid) = match child {
+                Child::Existing { mode, oid } => (mode, oid),
+                Child::Tree(node) => match node.write(repo)? {
+                    Some(oid) => (gix::objs::tree::EntryKind::Tree.into(), oid),
+                    None => continue,
+                },
+            };
+            entries.push(gix::objs::tree::Entry { mode, filename, oid });
+        }
+        if entries.is_empty() {
+            return Ok(None);
+        }
+        entries.sort();
+        Ok(Some(
+            repo.write_object(&gix::objs::Tree { entries })
+                .context("failed to write rewritten tree")?
+                .detach(),
+        ))
+    }
+}
+
+/// Write the mapping of rewritten references as `<old> <new> <name>` to `out`, with `new` being the null id for
+/// references that were deleted.
+pub fn write_ref_map(outcome: &Outcome, hash: gix::hash::Kind, mut out: impl std::io::Write) -> std::io::Result<()> {
+    for mapping in &outcome.refs {
+        writeln!(
+            out,
+            "{old} {new} {name}",
+            old = mapping.old,
+            new = mapping.new.unwrap_or_else(|| ObjectId::null(hash)),
+            name = mapping.name
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the mapping of rewritten commits as `<old> <new>` lines to `out`, with `new` being the null id for
+/// commits that were dropped without replacement.
+pub fn write_commit_map(outcome: &Outcome, hash: gix::hash::Kind, mut out: impl std::io::Write) -> std::io::Result<()> {
+    let mut commits: Vec<_> = outcome.commits.iter().collect();
+    commits.sort();
+    for (old, new) in commits {
+        writeln!(out, "{old} {new}", new = new.unwrap_or_else(|| ObjectId::null(hash)))?;
+    }
+    Ok(())
+}
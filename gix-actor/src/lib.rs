@@ -21,6 +21,8 @@ pub use gix_date as date;
 use gix_date::Time;
 
 mod identity;
+/// Resolve the identity of authors and committers from configuration and the environment, like `git` does.
+pub mod resolve;
 ///
 #[allow(clippy::empty_docs)]
 pub mod signature;
@@ -74,3 +76,25 @@ pub struct SignatureRef<'a> {
     /// The time stamp at which the signature was performed.
     pub time: gix_date::Time,
 }
+
+/// A signature as found in the headers of commits and tags, which was parsed leniently and retains the bytes
+/// it was parsed from, malformed or not.
+///
+/// This is useful for tools that rewrite history and need to write signatures back exactly as they were found,
+/// even if they are malformed, while still having access to all information that could be extracted from them.
+/// Use [`validate()`](LenientSignatureRef::validate()) to obtain a [`SignatureRef`] if the signature is well-formed.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LenientSignatureRef<'a> {
+    /// The bytes this signature was parsed from, which are written back unchanged.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub raw: &'a BStr,
+    /// The actor's name, or all of `raw` if no email could be found.
+    pub name: &'a BStr,
+    /// The actor's email, which is empty if no email could be found.
+    pub email: &'a BStr,
+    /// The time stamp at which the signature was performed, or `None` if none could be found.
+    ///
+    /// A missing or malformed timezone is assumed to be UTC.
+    pub time: Option<gix_date::Time>,
+}
@@ -0,0 +1,173 @@
+use bstr::{BString, ByteSlice, ByteVec};
+
+use crate::Identity;
+
+/// The role of the identity to resolve, as it determines which values are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The author of a commit or tag, configured by `author.name|email` and `GIT_AUTHOR_(NAME|EMAIL)`.
+    Author,
+    /// The committer of a commit, configured by `committer.name|email` and `GIT_COMMITTER_(NAME|EMAIL)`.
+    Committer,
+}
+
+impl Role {
+    /// Return the name of the environment variable that sets the name of this role.
+    pub fn name_environment_variable(&self) -> &'static str {
+        match self {
+            Role::Author => "GIT_AUTHOR_NAME",
+            Role::Committer => "GIT_COMMITTER_NAME",
+        }
+    }
+
+    /// Return the name of the environment variable that sets the email of this role.
+    pub fn email_environment_variable(&self) -> &'static str {
+        match self {
+            Role::Author => "GIT_AUTHOR_EMAIL",
+            Role::Committer => "GIT_COMMITTER_EMAIL",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Role::Author => "author",
+            Role::Committer => "committer",
+        })
+    }
+}
+
+/// All values that are considered when resolving the identity of a [role](Role), listed in order of precedence
+/// for names and emails respectively.
+///
+/// Empty values are treated as if they were unset, with the exception of the role-specific environment variables.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Sources {
+    /// The value of `GIT_AUTHOR_NAME` or `GIT_COMMITTER_NAME`.
+    pub env_name: Option<BString>,
+    /// The value of `GIT_AUTHOR_EMAIL` or `GIT_COMMITTER_EMAIL`.
+    pub env_email: Option<BString>,
+    /// The value of `author.name` or `committer.name`.
+    pub config_name: Option<BString>,
+    /// The value of `author.email` or `committer.email`.
+    pub config_email: Option<BString>,
+    /// The value of `user.name`.
+    pub user_name: Option<BString>,
+    /// The value of `user.email`.
+    pub user_email: Option<BString>,
+    /// The value of `user.useConfigOnly`, which disables the automatic detection of names and emails from
+    /// the system if `true`.
+    pub use_config_only: bool,
+    /// The value of the `EMAIL` environment variable, the first automatically detected email.
+    pub email: Option<BString>,
+    /// The full name of the user running the program, like the one in the password database, which is used as name
+    /// in place of `username` if set.
+    pub full_name: Option<BString>,
+    /// The login name of the user running the program, which is used as name and as local part of the email.
+    pub username: Option<BString>,
+    /// The name of the host the program runs on, which is used as domain of the email.
+    ///
+    /// If it doesn't contain a `.`, it's not considered a proper domain and the email is rejected.
+    pub hostname: Option<BString>,
+}
+
+impl Sources {
+    /// Create an instance with all values that can be obtained from the environment of the process for `role`,
+    /// leaving all configuration values unset.
+    ///
+    /// The `username` is read from `USER` or `USERNAME`, and the `hostname` from `HOSTNAME` or `COMPUTERNAME`.
+    pub fn from_env(role: Role) -> Self {
+        fn var(name: &str) -> Option<BString> {
+            std::env::var_os(name).and_then(|value| Vec::from_os_string(value).ok().map(Into::into))
+        }
+        Sources {
+            env_name: var(role.name_environment_variable()),
+            env_email: var(role.email_environment_variable()),
+            email: var("EMAIL"),
+            username: var("USER").or_else(|| var("USERNAME")),
+            hostname: var("HOSTNAME").or_else(|| var("COMPUTERNAME")),
+            ..Default::default()
+        }
+    }
+}
+
+/// The error returned by [`identity()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("No {role} name was given and it couldn't be detected automatically, set it with `git config user.name \"Your Name\"` or `{}`", .role.name_environment_variable())]
+    NameUnset { role: Role },
+    #[error("No {role} name was given and auto-detection is disabled by `user.useConfigOnly`, set it with `git config user.name \"Your Name\"` or `{}`", .role.name_environment_variable())]
+    NameAutoDetectionDisabled { role: Role },
+    #[error("The {role} name must not be empty, set it with `git config user.name \"Your Name\"` or `{}`", .role.name_environment_variable())]
+    EmptyName { role: Role },
+    #[error("No {role} email was given and it couldn't be detected automatically, set it with `git config user.email \"you@example.com\"` or `{}`", .role.email_environment_variable())]
+    EmailUnset { role: Role },
+    #[error("No {role} email was given and auto-detection is disabled by `user.useConfigOnly`, set it with `git config user.email \"you@example.com\"` or `{}`", .role.email_environment_variable())]
+    EmailAutoDetectionDisabled { role: Role },
+    #[error("Unable to auto-detect the {role} email address (got '{email}'), set it with `git config user.email \"you@example.com\"` or `{}`", .role.email_environment_variable())]
+    BogusEmail { role: Role, email: BString },
+}
+
+/// Resolve the identity of `role` from `sources` just like `git` does, or return an error that explains what needs to be configured.
+///
+/// Names and emails are taken from the first of these that is set:
+///
+/// * the `GIT_AUTHOR_*` or `GIT_COMMITTER_*` environment variables
+/// * the `author.*` or `committer.*` configuration
+/// * the `user.*` configuration
+/// * unless `user.useConfigOnly` is set, values detected from the system, which is the full name or the username as name,
+///   and the `EMAIL` environment variable or `<username>@<hostname>` as email.
+pub fn identity(role: Role, sources: &Sources) -> Result<Identity, Error> {
+    fn non_empty(value: &Option<BString>) -> Option<&BString> {
+        value.as_ref().filter(|value| !value.is_empty())
+    }
+
+    let name = match sources
+        .env_name
+        .as_ref()
+        .or_else(|| non_empty(&sources.config_name))
+        .or_else(|| non_empty(&sources.user_name))
+    {
+        Some(name) => name.clone(),
+        None if sources.use_config_only => return Err(Error::NameAutoDetectionDisabled { role }),
+        None => non_empty(&sources.full_name)
+            .or_else(|| non_empty(&sources.username))
+            .cloned()
+            .ok_or(Error::NameUnset { role })?,
+    };
+    if name.trim().is_empty() {
+        return Err(Error::EmptyName { role });
+    }
+
+    let email = match sources
+        .env_email
+        .as_ref()
+        .or_else(|| non_empty(&sources.config_email))
+        .or_else(|| non_empty(&sources.user_email))
+    {
+        Some(email) => email.clone(),
+        None if sources.use_config_only => return Err(Error::EmailAutoDetectionDisabled { role }),
+        None => match non_empty(&sources.email) {
+            Some(email) => email.clone(),
+            None => {
+                let username = non_empty(&sources.username).ok_or(Error::EmailUnset { role })?;
+                let mut email = username.clone();
+                email.push_byte(b'@');
+                match non_empty(&sources.hostname) {
+                    Some(hostname) if hostname.contains(&b'.') => email.push_str(hostname),
+                    hostname => {
+                        if let Some(hostname) = hostname {
+                            email.push_str(hostname);
+                        }
+                        email.push_str(".(none)");
+                        return Err(Error::BogusEmail { role, email });
+                    }
+                }
+                email
+            }
+        },
+    };
+    Ok(Identity { name, email })
+}
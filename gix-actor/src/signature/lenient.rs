@@ -0,0 +1,102 @@
+use bstr::ByteSlice;
+use gix_date::{time::Sign, SecondsSinceUnixEpoch, Time};
+use winnow::prelude::*;
+
+use crate::{signature, LenientSignatureRef, SignatureRef};
+
+impl<'a> LenientSignatureRef<'a> {
+    /// Parse `data` as signature, which never fails as whatever can't be parsed is left empty, or is `None`.
+    ///
+    /// Signatures which can be [decoded](signature::decode()) are parsed just like that, otherwise name and email
+    /// are assumed to be delimited by the first `<` and the first `>` thereafter, while the time is expected after the
+    /// last `>`, similar to what `git` does when reading signatures.
+    pub fn from_bytes(data: &'a [u8]) -> Self {
+        let raw = data.as_bstr();
+        let mut input = data;
+        if let Ok(signature) = signature::decode::<()>.parse_next(&mut input) {
+            if input.is_empty() {
+                return LenientSignatureRef {
+                    raw,
+                    name: signature.name,
+                    email: signature.email,
+                    time: Some(signature.time),
+                };
+            }
+        }
+
+        let Some(email_start) = data.find_byte(b'<') else {
+            return LenientSignatureRef {
+                raw,
+                name: data.trim().as_bstr(),
+                email: Default::default(),
+                time: None,
+            };
+        };
+        let name = data[..email_start].trim().as_bstr();
+        let rest = &data[email_start + 1..];
+        let Some(email_end) = rest.find_byte(b'>') else {
+            return LenientSignatureRef {
+                raw,
+                name,
+                email: rest.trim().as_bstr(),
+                time: None,
+            };
+        };
+        let after_email = data.rfind_byte(b'>').expect("present") + 1;
+        LenientSignatureRef {
+            raw,
+            name,
+            email: rest[..email_end].as_bstr(),
+            time: parse_time(&data[after_email..]),
+        }
+    }
+
+    /// Return this signature as [`SignatureRef`] if its [`raw`](Self::raw) bytes are valid according to `git fsck`,
+    /// or an error that describes what's wrong with them.
+    pub fn validate(&self) -> Result<SignatureRef<'a>, signature::strict::Error> {
+        signature::strict::decode(self.raw)
+    }
+
+    /// Return the parts of this signature as [`SignatureRef`] if a time could be parsed,
+    /// whether the signature is well-formed or not.
+    ///
+    /// Note that the returned signature might not be writable as it may contain illegal characters,
+    /// and that writing it won't reproduce the original bytes in any case.
+    pub fn to_signature_ref(&self) -> Option<SignatureRef<'a>> {
+        Some(SignatureRef {
+            name: self.name,
+            email: self.email,
+            time: self.time?,
+        })
+    }
+
+    /// Serialize this instance to `out`, which writes the bytes it was parsed from unchanged.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        out.write_all(self.raw)
+    }
+
+    /// Computes the number of bytes necessary to serialize this signature.
+    pub fn size(&self) -> usize {
+        self.raw.len()
+    }
+}
+
+/// Parse `<timestamp> <+|-><HHMM>` from `input`, which may be surrounded by whitespace, and assume UTC if
+/// the timezone is missing or malformed.
+fn parse_time(input: &[u8]) -> Option<Time> {
+    let mut tokens = input.fields();
+    let seconds: SecondsSinceUnixEpoch = gix_utils::btoi::to_signed(tokens.next()?).ok()?;
+    let mut time = Time {
+        seconds,
+        offset: 0,
+        sign: Sign::Plus,
+    };
+    let Some(timezone) = tokens.next() else {
+        return Some(time);
+    };
+    if let Some((sign, offset)) = signature::strict::parse_timezone(timezone) {
+        time.sign = sign;
+        time.offset = offset;
+    }
+    Some(time)
+}
@@ -13,6 +13,14 @@ mod _ref {
             decode.parse_next(&mut data)
         }
 
+        /// Deserialize a signature from the given `data`, which must be exactly as `git fsck` expects it,
+        /// or return an error that describes what is wrong with it.
+        ///
+        /// Use [`from_bytes()`](Self::from_bytes()) to also accept signatures which `git` itself would tolerate.
+        pub fn from_bytes_strict(data: &'a [u8]) -> Result<SignatureRef<'a>, crate::signature::strict::Error> {
+            crate::signature::strict::decode(data)
+        }
+
         /// Create an owned instance from this shared one.
         pub fn to_owned(&self) -> Signature {
             Signature {
@@ -132,3 +140,9 @@ pub(crate) mod write {
 #[allow(clippy::empty_docs)]
 pub mod decode;
 pub use decode::function::decode;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod strict;
+
+mod lenient;
@@ -0,0 +1,111 @@
+//! Parse signatures with the same rules `git fsck` uses to validate them.
+use bstr::{BString, ByteSlice};
+use gix_date::{time::Sign, OffsetInSeconds, SecondsSinceUnixEpoch, Time};
+
+use crate::SignatureRef;
+
+/// The error returned by [`decode()`] and [`SignatureRef::from_bytes_strict()`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(
+        "Could not find the start of the email, signatures must look like '<name> <<email>> <timestamp> <+|-><HHMM>'"
+    )]
+    MissingEmail,
+    #[error("There is no name before the email")]
+    MissingNameBeforeEmail,
+    #[error("The name must be separated from the email by a space")]
+    MissingSpaceBeforeEmail,
+    #[error("The name must not contain '>' or newlines")]
+    BadName,
+    #[error("The email must be terminated by '>' and must not contain '<' or newlines")]
+    BadEmail,
+    #[error("The email must be separated from the timestamp by a space")]
+    MissingSpaceBeforeDate,
+    #[error("The timestamp {date:?} must be a positive number of seconds")]
+    BadDate { date: BString },
+    #[error("The timestamp {date:?} must not be padded with zeroes")]
+    ZeroPaddedDate { date: BString },
+    #[error("The timestamp {date:?} is too large")]
+    DateOverflow { date: BString },
+    #[error("The timestamp must be followed by a space and a timezone")]
+    MissingTimezone,
+    #[error("The timezone {timezone:?} must look like '<+|-><HHMM>'")]
+    BadTimezone { timezone: BString },
+}
+
+/// Decode `data` as signature like `name <email> 1234567890 +0100`, and fail with an error describing the first problem
+/// that prevents it from being valid according to `git fsck`.
+///
+/// Note that in contrast to [`decode()`](super::decode()), negative timestamps, timezones which aren't exactly
+/// four digits and any trailing bytes are rejected, as well as names and emails that couldn't be written back unchanged.
+pub fn decode(data: &[u8]) -> Result<SignatureRef<'_>, Error> {
+    let email_start = data.find_byte(b'<').ok_or(Error::MissingEmail)?;
+    let name = &data[..email_start];
+    if name.is_empty() {
+        return Err(Error::MissingNameBeforeEmail);
+    }
+    if name.find_byteset(b">\n").is_some() {
+        return Err(Error::BadName);
+    }
+    let name = name.strip_suffix(b" ").ok_or(Error::MissingSpaceBeforeEmail)?;
+
+    let rest = &data[email_start + 1..];
+    let email_end = rest.find_byteset(b"<>\n").ok_or(Error::BadEmail)?;
+    if rest[email_end] != b'>' {
+        return Err(Error::BadEmail);
+    }
+    let email = &rest[..email_end];
+
+    let rest = rest[email_end + 1..]
+        .strip_prefix(b" ")
+        .ok_or(Error::MissingSpaceBeforeDate)?;
+    let date_end = rest.iter().position(|b| !b.is_ascii_digit()).unwrap_or(rest.len());
+    let date = &rest[..date_end];
+    if date.is_empty() {
+        return Err(Error::BadDate {
+            date: rest.split_str(" ").next().unwrap_or_default().into(),
+        });
+    }
+    if date.len() > 1 && date[0] == b'0' {
+        return Err(Error::ZeroPaddedDate { date: date.into() });
+    }
+    let seconds: SecondsSinceUnixEpoch =
+        gix_utils::btoi::to_signed(date).map_err(|_| Error::DateOverflow { date: date.into() })?;
+
+    let rest = &rest[date_end..];
+    if rest.is_empty() {
+        return Err(Error::MissingTimezone);
+    }
+    let timezone = rest.strip_prefix(b" ").ok_or_else(|| Error::BadDate {
+        date: [date, rest.split_str(" ").next().unwrap_or_default()].concat().into(),
+    })?;
+    let (sign, offset) = parse_timezone(timezone).ok_or_else(|| Error::BadTimezone {
+        timezone: timezone.into(),
+    })?;
+
+    Ok(SignatureRef {
+        name: name.as_bstr(),
+        email: email.as_bstr(),
+        time: Time { seconds, offset, sign },
+    })
+}
+
+/// Parse `timezone` as `<+|-><HHMM>` and return its sign and offset.
+pub(crate) fn parse_timezone(timezone: &[u8]) -> Option<(Sign, OffsetInSeconds)> {
+    let (sign, hhmm) = match timezone.split_first()? {
+        (b'+', hhmm) => (Sign::Plus, hhmm),
+        (b'-', hhmm) => (Sign::Minus, hhmm),
+        _ => return None,
+    };
+    if hhmm.len() != 4 || !hhmm.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let digits = |range: std::ops::Range<usize>| -> OffsetInSeconds {
+        hhmm[range]
+            .iter()
+            .fold(0, |acc, b| acc * 10 + OffsetInSeconds::from(b - b'0'))
+    };
+    let offset = (digits(0..2) * 3600 + digits(2..4) * 60) * if sign == Sign::Minus { -1 } else { 1 };
+    Some((sign, offset))
+}
@@ -5,4 +5,5 @@ pub fn fixture(path: &str) -> PathBuf {
 }
 
 mod identity;
+mod resolve;
 mod signature;
@@ -0,0 +1,141 @@
+use gix_actor::{
+    resolve::{identity, Error, Role, Sources},
+    Identity,
+};
+
+fn configured() -> Sources {
+    Sources {
+        config_name: Some("author config".into()),
+        config_email: Some("author@config".into()),
+        user_name: Some("user".into()),
+        user_email: Some("user@config".into()),
+        email: Some("email@env".into()),
+        username: Some("login".into()),
+        hostname: Some("host.example.com".into()),
+        ..Default::default()
+    }
+}
+
+fn id(name: &str, email: &str) -> Identity {
+    Identity {
+        name: name.into(),
+        email: email.into(),
+    }
+}
+
+#[test]
+fn precedence() -> gix_testtools::Result {
+    let mut sources = configured();
+    sources.env_name = Some("author env".into());
+    sources.env_email = Some("author@env".into());
+    assert_eq!(
+        identity(Role::Author, &sources)?,
+        id("author env", "author@env"),
+        "environment variables come first"
+    );
+
+    sources.env_name = None;
+    sources.env_email = None;
+    assert_eq!(identity(Role::Author, &sources)?, id("author config", "author@config"));
+
+    sources.config_name = None;
+    sources.config_email = Some("".into());
+    assert_eq!(
+        identity(Role::Author, &sources)?,
+        id("user", "user@config"),
+        "empty values are skipped"
+    );
+
+    sources.user_name = None;
+    sources.user_email = None;
+    assert_eq!(
+        identity(Role::Author, &sources)?,
+        id("login", "email@env"),
+        "the system is the last resort"
+    );
+
+    sources.full_name = Some("Full Name".into());
+    sources.email = None;
+    assert_eq!(
+        identity(Role::Author, &sources)?,
+        id("Full Name", "login@host.example.com")
+    );
+    Ok(())
+}
+
+#[test]
+fn use_config_only_disables_auto_detection() -> gix_testtools::Result {
+    let mut sources = configured();
+    sources.use_config_only = true;
+    assert_eq!(
+        identity(Role::Committer, &sources)?,
+        id("author config", "author@config"),
+        "configured values are used as before"
+    );
+
+    sources.config_name = None;
+    sources.user_name = None;
+    assert!(matches!(
+        identity(Role::Committer, &sources),
+        Err(Error::NameAutoDetectionDisabled { role: Role::Committer })
+    ));
+
+    sources.config_name = Some("name".into());
+    sources.config_email = None;
+    sources.user_email = None;
+    let err = identity(Role::Committer, &sources).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::EmailAutoDetectionDisabled { role: Role::Committer }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "No committer email was given and auto-detection is disabled by `user.useConfigOnly`, set it with `git config user.email \"you@example.com\"` or `GIT_COMMITTER_EMAIL`"
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let sources = Sources {
+        env_name: Some(" ".into()),
+        ..Default::default()
+    };
+    assert!(
+        matches!(
+            identity(Role::Author, &sources),
+            Err(Error::EmptyName { role: Role::Author })
+        ),
+        "environment variables can't be empty"
+    );
+
+    assert!(matches!(
+        identity(Role::Author, &Sources::default()),
+        Err(Error::NameUnset { role: Role::Author })
+    ));
+
+    let sources = Sources {
+        username: Some("login".into()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        identity(Role::Author, &Sources{ hostname: None, ..sources.clone() }),
+        Err(Error::BogusEmail { email, .. }) if email == "login@.(none)"
+    ));
+    assert!(
+        matches!(
+            identity(Role::Author, &Sources { hostname: Some("localhost".into()), ..sources }),
+            Err(Error::BogusEmail { email, .. }) if email == "login@localhost.(none)"
+        ),
+        "hosts without domain don't yield valid emails"
+    );
+
+    let sources = Sources {
+        full_name: Some("name".into()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        identity(Role::Committer, &sources),
+        Err(Error::EmailUnset { role: Role::Committer })
+    ));
+}
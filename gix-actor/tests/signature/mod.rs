@@ -105,3 +105,170 @@ fn parse_timestamp_with_trailing_digits() {
         }
     );
 }
+
+mod lenient {
+    use bstr::ByteSlice;
+    use gix_actor::{
+        date::{time::Sign, Time},
+        LenientSignatureRef, SignatureRef,
+    };
+
+    #[test]
+    fn malformed_signatures_round_trip_and_retain_what_could_be_parsed() {
+        for (input, name, email, time) in [
+            (
+                &b"Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230"[..],
+                "Sebastian Thiel",
+                "byronimo@gmail.com",
+                Some(Time::new(1528473343, 9000)),
+            ),
+            (
+                b"name <broken@email 1234 +0100",
+                "name",
+                "broken@email 1234 +0100",
+                None,
+            ),
+            (b"name <email> 1234", "name", "email", Some(Time::new(1234, 0))),
+            (b"name <email>1234 +01", "name", "email", Some(Time::new(1234, 0))),
+            (
+                b"name <name@example.com> <other@example.com> 1234 -0100",
+                "name",
+                "name@example.com",
+                Some(Time {
+                    seconds: 1234,
+                    offset: -3600,
+                    sign: Sign::Minus,
+                }),
+            ),
+            (b"name <email> not-a-date +0100", "name", "email", None),
+            (b"  just a name  ", "just a name", "", None),
+            (b"", "", "", None),
+        ] {
+            let signature = LenientSignatureRef::from_bytes(input);
+            assert_eq!(signature.raw, input.as_bstr());
+            assert_eq!(signature.name, name, "{input:?}");
+            assert_eq!(signature.email, email, "{input:?}");
+            assert_eq!(signature.time, time, "{input:?}");
+
+            let mut output = Vec::new();
+            signature.write_to(&mut output).expect("writing to memory works");
+            assert_eq!(output.as_bstr(), input.as_bstr(), "the original bytes are written back");
+            assert_eq!(signature.size(), output.len());
+        }
+    }
+
+    #[test]
+    fn validation_and_conversion() {
+        let signature = LenientSignatureRef::from_bytes(b"name <email> 1234 +0100");
+        let expected = SignatureRef {
+            name: "name".into(),
+            email: "email".into(),
+            time: Time::new(1234, 3600),
+        };
+        assert_eq!(signature.validate(), Ok(expected));
+        assert_eq!(signature.to_signature_ref(), Some(expected));
+
+        let signature = LenientSignatureRef::from_bytes(b"name <email> 1234");
+        assert_eq!(
+            signature.validate(),
+            Err(gix_actor::signature::strict::Error::MissingTimezone)
+        );
+        assert_eq!(
+            signature.to_signature_ref(),
+            Some(SignatureRef {
+                time: Time::new(1234, 0),
+                ..expected
+            })
+        );
+        assert_eq!(
+            LenientSignatureRef::from_bytes(b"name <email>").to_signature_ref(),
+            None
+        );
+    }
+}
+
+mod strict {
+    use gix_actor::{
+        date::Time,
+        signature::strict::{decode, Error},
+        SignatureRef,
+    };
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            SignatureRef::from_bytes_strict(b"Sebastian Thiel <byronimo@gmail.com> 1528473343 -0230"),
+            Ok(SignatureRef {
+                name: "Sebastian Thiel".into(),
+                email: "byronimo@gmail.com".into(),
+                time: Time {
+                    seconds: 1528473343,
+                    offset: -9000,
+                    sign: gix_actor::date::time::Sign::Minus,
+                },
+            })
+        );
+        assert_eq!(
+            decode(b" <> 0 +0000"),
+            Ok(SignatureRef {
+                name: "".into(),
+                email: "".into(),
+                time: Time::new(0, 0),
+            }),
+            "empty names and emails are fine as long as the spacing is right"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for (input, expected) in [
+            (&b"name email 1234 +0000"[..], Error::MissingEmail),
+            (b"<email> 1234 +0000", Error::MissingNameBeforeEmail),
+            (b"name<email> 1234 +0000", Error::MissingSpaceBeforeEmail),
+            (b"na>me <email> 1234 +0000", Error::BadName),
+            (b"na\nme <email> 1234 +0000", Error::BadName),
+            (b"name <em<ail> 1234 +0000", Error::BadEmail),
+            (b"name <email 1234 +0000", Error::BadEmail),
+            (b"name <email>1234 +0000", Error::MissingSpaceBeforeDate),
+            (b"name <email> -1234 +0000", Error::BadDate { date: "-1234".into() }),
+            (b"name <email> 12ab +0000", Error::BadDate { date: "12ab".into() }),
+            (
+                b"name <email> 01234 +0000",
+                Error::ZeroPaddedDate { date: "01234".into() },
+            ),
+            (
+                b"name <email> 99999999999999999999 +0000",
+                Error::DateOverflow {
+                    date: "99999999999999999999".into(),
+                },
+            ),
+            (b"name <email> 1234", Error::MissingTimezone),
+            (
+                b"name <email> 1234 0000",
+                Error::BadTimezone {
+                    timezone: "0000".into(),
+                },
+            ),
+            (
+                b"name <email> 1234 --700",
+                Error::BadTimezone {
+                    timezone: "--700".into(),
+                },
+            ),
+            (
+                b"name <email> 1234 +051800",
+                Error::BadTimezone {
+                    timezone: "+051800".into(),
+                },
+            ),
+            (
+                b"name <email> 1234 +0000 ",
+                Error::BadTimezone {
+                    timezone: "+0000 ".into(),
+                },
+            ),
+        ] {
+            assert_eq!(decode(input), Err(expected), "{input:?}");
+        }
+    }
+}
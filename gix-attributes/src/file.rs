@@ -0,0 +1,227 @@
+use std::{borrow::Cow, io::Write};
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+use crate::{parse, Assignment, AssignmentRef, File, NameRef};
+
+/// The error returned by [`File::push()`] and [`File::set()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A pattern must not be empty")]
+    EmptyPattern,
+    #[error("The pattern {pattern:?} would not be read back as is, for instance because it starts a comment")]
+    InvalidPattern { pattern: BString },
+    #[error(transparent)]
+    Parse(#[from] parse::Error),
+}
+
+/// A line of a [`File`].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub(crate) struct Line {
+    /// The line as it was read, without its line ending.
+    raw: BString,
+    /// The unquoted pattern and its assignments, or `None` if it's a comment, blank or couldn't be parsed.
+    parsed: Option<(BString, Vec<Assignment>)>,
+}
+
+impl Line {
+    fn parse(raw: &BStr) -> Self {
+        Line {
+            raw: raw.to_owned(),
+            parsed: Self::parse_pattern_and_assignments(raw),
+        }
+    }
+
+    fn parse_pattern_and_assignments(raw: &BStr) -> Option<(BString, Vec<Assignment>)> {
+        let line = parse::skip_blanks(raw);
+        if line.first() == Some(&b'#') {
+            return None;
+        }
+        // Only keep lines that are understood so all others are left untouched.
+        parse::Lines::new(raw).next()?.ok()?;
+        let (pattern, attrs) = parse::split_pattern(line).ok()?;
+        let assignments = parse::Iter::new(attrs)
+            .map(|res| res.map(AssignmentRef::to_owned))
+            .collect::<Result<_, _>>()
+            .ok()?;
+        Some((pattern.into_owned(), assignments))
+    }
+
+    fn pattern(&self) -> Option<&BStr> {
+        self.parsed.as_ref().map(|(pattern, _)| pattern.as_bstr())
+    }
+
+    /// Recreate the raw line from its pattern and assignments.
+    fn update_raw(&mut self) {
+        let (pattern, assignments) = self.parsed.as_ref().expect("only parsed lines are changed");
+        let mut raw = quote(pattern.as_bstr()).into_owned();
+        for assignment in assignments {
+            raw.push_byte(b' ');
+            raw.push_str(assignment.as_ref().to_string());
+        }
+        self.raw = raw;
+    }
+}
+
+/// Quote `pattern` if it contains characters that would otherwise end it or change its meaning.
+fn quote(pattern: &BStr) -> Cow<'_, BStr> {
+    if !pattern.starts_with(b"\"") && !pattern.contains(&b'\n') && pattern.find_byteset(parse::BLANKS).is_none() {
+        return pattern.into();
+    }
+    let mut out = BString::from("\"");
+    for b in pattern.iter().copied() {
+        match b {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\t' => out.push_str("\\t"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b => out.push_byte(b),
+        }
+    }
+    out.push_byte(b'"');
+    out.into()
+}
+
+/// Instantiation
+impl File {
+    /// Read all lines of the `.gitattributes` file in `input`, which never fails.
+    ///
+    /// Comments, blank lines and lines that can't be parsed are kept as is, but can't be edited.
+    pub fn from_bytes(input: &[u8]) -> Self {
+        let bom = unicode_bom::Bom::from(input);
+        let (bom, input) = input.split_at(bom.len());
+        File {
+            bom: bom.into(),
+            lines: input.lines().map(|line| Line::parse(line.as_bstr())).collect(),
+            crlf: input.find(b"\r\n").is_some(),
+            trailing_newline: input.is_empty() || input.ends_with(b"\n"),
+        }
+    }
+}
+
+/// Access
+impl File {
+    /// Return the patterns of all lines with a pattern, in order and possibly repeated.
+    ///
+    /// Patterns are returned as written in the file after unquoting, which also is what other methods expect.
+    pub fn patterns(&self) -> impl Iterator<Item = &BStr> {
+        self.lines.iter().filter_map(Line::pattern)
+    }
+
+    /// Return the assignments of all lines with `pattern`, in order.
+    ///
+    /// Note that later assignments of the same attribute override earlier ones.
+    pub fn assignments<'a>(&'a self, pattern: &'a BStr) -> impl Iterator<Item = AssignmentRef<'a>> + 'a {
+        self.lines
+            .iter()
+            .filter_map(|line| line.parsed.as_ref())
+            .filter(move |(line_pattern, _)| line_pattern == pattern)
+            .flat_map(|(_, assignments)| assignments.iter().map(Assignment::as_ref))
+    }
+}
+
+/// Mutation
+impl File {
+    /// Add a line with `pattern` and `assignments` to the end of the file.
+    ///
+    /// `pattern` is as it would be written into the file, so literal leading `!` must be escaped, and it's quoted as needed.
+    pub fn push(
+        &mut self,
+        pattern: &BStr,
+        assignments: impl IntoIterator<Item = Assignment>,
+    ) -> Result<&mut Self, Error> {
+        if pattern.is_empty() {
+            return Err(Error::EmptyPattern);
+        }
+        let mut line = Line {
+            raw: Default::default(),
+            parsed: Some((pattern.to_owned(), assignments.into_iter().collect())),
+        };
+        line.update_raw();
+        if let Some(Err(err)) = parse::Lines::new(&line.raw).next() {
+            return Err(err.into());
+        }
+        if Line::parse(line.raw.as_bstr()) != line {
+            return Err(Error::InvalidPattern {
+                pattern: pattern.to_owned(),
+            });
+        }
+        self.lines.push(line);
+        self.trailing_newline = true;
+        Ok(self)
+    }
+
+    /// Set `assignment` for `pattern`, replacing the state of an attribute of the same name, or adding it to the last line
+    /// with `pattern`. If there is no such line, it's [pushed](Self::push()).
+    pub fn set(&mut self, pattern: &BStr, assignment: Assignment) -> Result<&mut Self, Error> {
+        let Some(line) = self.lines.iter_mut().rev().find(|line| line.pattern() == Some(pattern)) else {
+            return self.push(pattern, Some(assignment));
+        };
+        let (_, assignments) = line.parsed.as_mut().expect("has a pattern");
+        match assignments.iter_mut().rev().find(|a| a.name == assignment.name) {
+            Some(existing) => existing.state = assignment.state,
+            None => assignments.push(assignment),
+        }
+        line.update_raw();
+        Ok(self)
+    }
+
+    /// Remove the attribute `name` from all lines with `pattern`, and return `true` if it was present.
+    ///
+    /// Lines without any assignment afterwards are removed.
+    pub fn remove(&mut self, pattern: &BStr, name: NameRef<'_>) -> bool {
+        let mut removed = false;
+        self.lines.retain_mut(|line| {
+            let Some((line_pattern, assignments)) = line.parsed.as_mut() else {
+                return true;
+            };
+            if line_pattern != pattern {
+                return true;
+            }
+            let len = assignments.len();
+            assignments.retain(|a| a.name.as_ref() != name);
+            if assignments.len() == len {
+                return true;
+            }
+            removed = true;
+            if assignments.is_empty() {
+                return false;
+            }
+            line.update_raw();
+            true
+        });
+        removed
+    }
+
+    /// Remove all lines with `pattern` and return the amount of removed lines.
+    pub fn remove_pattern(&mut self, pattern: &BStr) -> usize {
+        let len = self.lines.len();
+        self.lines.retain(|line| line.pattern() != Some(pattern));
+        len - self.lines.len()
+    }
+}
+
+/// Serialization
+impl File {
+    /// Write this file to `out`, with lines that weren't changed written exactly as they were read.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        out.write_all(&self.bom)?;
+        let newline: &[u8] = if self.crlf { b"\r\n" } else { b"\n" };
+        for (index, line) in self.lines.iter().enumerate() {
+            out.write_all(&line.raw)?;
+            if index + 1 < self.lines.len() || self.trailing_newline {
+                out.write_all(newline)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return this file as byte string, see [`write_to()`](Self::write_to()).
+    pub fn to_bstring(&self) -> BString {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf).expect("io error impossible");
+        buf.into()
+    }
+}
@@ -27,6 +27,10 @@ pub mod search;
 #[allow(clippy::empty_docs)]
 pub mod parse;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod file;
+
 /// Parse attribute assignments line by line from `bytes`, and fail the operation on error.
 ///
 /// For leniency, ignore errors using `filter_map(Result::ok)` for example.
@@ -109,6 +113,19 @@ pub struct Search {
     patterns: Vec<gix_glob::search::pattern::List<search::Attributes>>,
 }
 
+/// The lines of a `.gitattributes` file, which can be edited while preserving comments, blank lines and their order,
+/// similar to how `gix-config` edits configuration files.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+pub struct File {
+    /// The byte order mark the file started with, if any.
+    bom: Vec<u8>,
+    lines: Vec<file::Line>,
+    /// If `true`, lines are separated by `\r\n`.
+    crlf: bool,
+    /// If `true`, the last line ends with a newline as well.
+    trailing_newline: bool,
+}
+
 /// A list of known global sources for git attribute files in order of ascending precedence.
 ///
 /// This means that values from the first variant will be returned first.
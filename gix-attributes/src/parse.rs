@@ -105,9 +105,6 @@ impl<'a> Iterator for Lines<'a> {
     type Item = Result<(Kind, Iter<'a>, usize), Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        fn skip_blanks(line: &BStr) -> &BStr {
-            line.find_not_byteset(BLANKS).map_or(line, |pos| &line[pos..])
-        }
         for line in self.lines.by_ref() {
             self.line_no += 1;
             let line = skip_blanks(line.into());
@@ -128,16 +125,9 @@ fn parse_line(line: &BStr, line_number: usize) -> Option<Result<(Kind, Iter<'_>,
         return None;
     }
 
-    let (line, attrs): (Cow<'_, _>, _) = if line.starts_with(b"\"") {
-        let (unquoted, consumed) = match gix_quote::ansi_c::undo(line) {
-            Ok(res) => res,
-            Err(err) => return Some(Err(err.into())),
-        };
-        (unquoted, &line[consumed..])
-    } else {
-        line.find_byteset(BLANKS)
-            .map(|pos| (line[..pos].as_bstr().into(), line[pos..].as_bstr()))
-            .unwrap_or((line.into(), [].as_bstr()))
+    let (line, attrs) = match split_pattern(line) {
+        Ok(res) => res,
+        Err(err) => return Some(Err(err)),
     };
 
     let kind_res = match line.strip_prefix(b"[attr]") {
@@ -166,4 +156,21 @@ fn parse_line(line: &BStr, line_number: usize) -> Option<Result<(Kind, Iter<'_>,
     Ok((kind, Iter::new(attrs), line_number)).into()
 }
 
-const BLANKS: &[u8] = b" \t\r";
+/// Split `line`, which must not start with blanks, into its possibly quoted pattern and the remainder with attributes.
+pub(crate) fn split_pattern(line: &BStr) -> Result<(Cow<'_, BStr>, &BStr), Error> {
+    Ok(if line.starts_with(b"\"") {
+        let (unquoted, consumed) = gix_quote::ansi_c::undo(line)?;
+        (unquoted, &line[consumed..])
+    } else {
+        line.find_byteset(BLANKS)
+            .map(|pos| (line[..pos].as_bstr().into(), line[pos..].as_bstr()))
+            .unwrap_or((line.into(), [].as_bstr()))
+    })
+}
+
+/// Return `line` without leading blanks.
+pub(crate) fn skip_blanks(line: &BStr) -> &BStr {
+    line.find_not_byteset(BLANKS).map_or(line, |pos| &line[pos..])
+}
+
+pub(crate) const BLANKS: &[u8] = b" \t\r";
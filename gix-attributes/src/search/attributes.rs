@@ -63,7 +63,8 @@ impl Search {
             let last = self.patterns.last_mut().expect("just added");
             if !allow_macros {
                 last.patterns
-                    .retain(|p| !matches!(p.value, Value::MacroAssignments { .. }))
+                    .retain(|p| !matches!(p.value, Value::MacroAssignments { .. }));
+                last.compile();
             }
             collection.update_from_list(last);
         }
@@ -84,7 +85,8 @@ impl Search {
         let last = self.patterns.last_mut().expect("just added");
         if !allow_macros {
             last.patterns
-                .retain(|p| !matches!(p.value, Value::MacroAssignments { .. }))
+                .retain(|p| !matches!(p.value, Value::MacroAssignments { .. }));
+            last.compile();
         }
         collection.update_from_list(last);
     }
@@ -212,15 +214,16 @@ fn pattern_matching_relative_path(
             None => return false,
         };
     let cur_len = out.remaining();
-    'outer: for pattern::Mapping {
-        pattern,
-        value,
-        sequence_number,
-    } in list
-        .patterns
-        .iter()
-        .rev()
-        .filter(|pm| Attributes::may_use_glob_pattern(&pm.pattern))
+    'outer: for (
+        _idx,
+        pattern::Mapping {
+            pattern,
+            value,
+            sequence_number,
+        },
+    ) in list
+        .candidates(relative_path, basename_start_pos)
+        .filter(|(_idx, pm)| Attributes::may_use_glob_pattern(&pm.pattern))
     {
         let value: &Value = value;
         let attrs = match value {
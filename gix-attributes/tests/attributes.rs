@@ -1,5 +1,6 @@
 pub use gix_testtools::Result;
 mod assignment;
+mod file;
 mod parse;
 mod search;
 mod state;
@@ -0,0 +1,115 @@
+use bstr::ByteSlice;
+use gix_attributes::{parse, Assignment, File, NameRef};
+
+const INPUT: &str = "# binary files\n*.png binary\n\n*.txt text eol=lf\n*.png -diff\n";
+
+#[test]
+fn unchanged_files_are_written_verbatim() {
+    for input in [
+        INPUT,
+        "",
+        "*.txt text",
+        "\u{feff}*.txt\ttext \r\n# comment\r\n",
+        "\"a b\"   x\n!invalid\n",
+    ] {
+        assert_eq!(File::from_bytes(input.as_bytes()).to_bstring(), input);
+    }
+}
+
+#[test]
+fn access() {
+    let file = File::from_bytes(INPUT.as_bytes());
+    assert_eq!(
+        file.patterns().collect::<Vec<_>>(),
+        ["*.png", "*.txt", "*.png"],
+        "comments and blank lines aren't patterns"
+    );
+    assert_eq!(
+        file.assignments("*.png".into())
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>(),
+        ["binary", "-diff"]
+    );
+    assert_eq!(file.assignments("*.jpg".into()).count(), 0);
+}
+
+#[test]
+fn set_changes_the_last_line_with_the_pattern_or_adds_one() -> crate::Result {
+    let mut file = File::from_bytes(INPUT.as_bytes());
+    file.set("*.txt".into(), assignment("eol=crlf"))?
+        .set("*.png".into(), assignment("diff"))?
+        .set("*.png".into(), assignment("filter=lfs"))?
+        .set("*.bin".into(), assignment("filter=lfs"))?;
+    assert_eq!(
+        file.to_bstring(),
+        "# binary files\n*.png binary\n\n*.txt text eol=crlf\n*.png diff filter=lfs\n*.bin filter=lfs\n"
+    );
+
+    let mut file = File::from_bytes(b"*.txt text");
+    file.set("with space".into(), assignment("-text"))?;
+    assert_eq!(
+        file.to_bstring(),
+        "*.txt text\n\"with space\" -text\n",
+        "a missing trailing newline is added, and patterns are quoted as needed"
+    );
+    assert_eq!(
+        File::from_bytes(file.to_bstring().as_bytes())
+            .assignments("with space".into())
+            .count(),
+        1
+    );
+
+    let mut file = File::from_bytes(b"a x\r\n");
+    file.set("b".into(), assignment("y"))?;
+    assert_eq!(file.to_bstring(), "a x\r\nb y\r\n", "line endings are preserved");
+    Ok(())
+}
+
+#[test]
+fn push_validates_patterns() -> crate::Result {
+    let mut file = File::default();
+    assert!(matches!(
+        file.push("".into(), Some(assignment("a"))),
+        Err(gix_attributes::file::Error::EmptyPattern)
+    ));
+    assert!(matches!(
+        file.push("!negated".into(), Some(assignment("a"))),
+        Err(gix_attributes::file::Error::Parse(_))
+    ));
+    assert!(matches!(
+        file.push("#comment".into(), Some(assignment("a"))),
+        Err(gix_attributes::file::Error::InvalidPattern { .. })
+    ));
+    file.push("[attr]binary".into(), ["-diff", "-merge", "-text"].map(assignment))?;
+    assert_eq!(file.to_bstring(), "[attr]binary -diff -merge -text\n");
+    Ok(())
+}
+
+#[test]
+fn remove() {
+    let mut file = File::from_bytes(INPUT.as_bytes());
+    assert!(!file.remove("*.png".into(), name("text")));
+    assert!(file.remove("*.png".into(), name("binary")));
+    assert!(file.remove("*.txt".into(), name("eol")));
+    assert_eq!(
+        file.to_bstring(),
+        "# binary files\n\n*.txt text\n*.png -diff\n",
+        "lines without assignments are removed"
+    );
+
+    assert_eq!(file.remove_pattern("*.png".into()), 1);
+    assert_eq!(file.remove_pattern("*.png".into()), 0);
+    assert_eq!(file.to_bstring(), "# binary files\n\n*.txt text\n");
+}
+
+fn assignment(input: &str) -> Assignment {
+    parse::Iter::new(input.into())
+        .next()
+        .expect("one assignment")
+        .expect("valid")
+        .to_owned()
+}
+
+fn name(name: &str) -> NameRef<'_> {
+    NameRef::try_from(name.as_bytes().as_bstr()).expect("valid")
+}
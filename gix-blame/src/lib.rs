@@ -0,0 +1,336 @@
+//! Attribute each line of a file to the commit that introduced it, similar to what `git blame` does.
+//!
+//! Use [`file()`] to blame a file, optionally limited to some [line ranges](range::Spec) and to the commits after a cutoff.
+#![deny(missing_docs, rust_2018_idioms)]
+#![forbid(unsafe_code)]
+
+use std::{collections::BinaryHeap, ops::Range};
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_hash::ObjectId;
+use gix_hashtable::{hash_map, HashMap, HashSet};
+use gix_object::FindExt;
+use imara_diff::{intern::InternedInput, sources::byte_lines_with_terminator, Algorithm};
+
+pub mod range;
+
+/// The error returned by [`file()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindObject(#[from] gix_object::find::existing_object::Error),
+    #[error("The file {file_path:?} does not exist in commit {commit_id}")]
+    FileMissing { file_path: BString, commit_id: ObjectId },
+    #[error(transparent)]
+    Range(#[from] range::Error),
+}
+
+/// Options for use in [`file()`].
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+    /// The ranges of lines to blame, like `-L`, or all lines if empty.
+    ///
+    /// Overlapping ranges are merged.
+    pub ranges: Vec<range::Spec>,
+    /// If set, lines aren't attributed past commits whose committer time is before this time, like `--since`.
+    ///
+    /// The lines that reach such a commit are attributed to it as [boundary](Entry::boundary).
+    pub since: Option<gix_date::Time>,
+    /// Commits whose ancestry, including themselves, isn't looked at, like `^<commit>` or `<commit>..`.
+    ///
+    /// The lines that reach one of these commits are attributed to it as [boundary](Entry::boundary).
+    pub hidden: Vec<ObjectId>,
+}
+
+/// Lines of the blamed file that were attributed to a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The zero-based lines in the blamed file.
+    pub range_in_blamed_file: Range<u32>,
+    /// The zero-based lines in the version of the file in `commit_id`.
+    pub range_in_original_file: Range<u32>,
+    /// The commit the lines are attributed to.
+    pub commit_id: ObjectId,
+    /// If `true`, `commit_id` is a root commit or at the cutoff, and the lines may be older than it.
+    ///
+    /// These are the commits that `git blame` marks with `^`.
+    pub boundary: bool,
+}
+
+/// The result of [`file()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The entries for all blamed lines, ordered by their position in the blamed file.
+    pub entries: Vec<Entry>,
+    /// The content of the blamed file.
+    pub blob: Vec<u8>,
+}
+
+/// Lines of the blamed file which still have to be attributed to a commit.
+#[derive(Debug, Clone)]
+struct UnblamedHunk {
+    /// The lines in the version of the file of the commit that is currently suspected to have introduced them.
+    suspect: Range<u32>,
+    /// The first of the lines in the blamed file.
+    blamed_start: u32,
+}
+
+/// Blame the lines of the file at `file_path` in the tree of `commit_id`, with all objects looked up in `odb`, and return
+/// which commits introduced them as configured by `options`.
+///
+/// Lines are passed on from a commit to the first of its parents in which they are unchanged, and are attributed to
+/// the commit if none of its parents has them, while unchanged files are passed on as a whole.
+/// Commits are visited from the most recent to the oldest, by committer time.
+///
+/// Note that renames aren't followed, which makes the commit that moved the file the one that introduced all of its lines.
+pub fn file(
+    odb: &impl gix_object::Find,
+    commit_id: ObjectId,
+    file_path: &BStr,
+    options: Options,
+) -> Result<Outcome, Error> {
+    let mut buf = Vec::new();
+    let blob_id = find_file(odb, commit_id, file_path, &mut buf)?.ok_or_else(|| Error::FileMissing {
+        file_path: file_path.to_owned(),
+        commit_id,
+    })?;
+    let blob = odb.find_blob(&blob_id, &mut buf)?.data.to_vec();
+
+    let mut ranges = if options.ranges.is_empty() {
+        std::iter::once(0..blob.lines_with_terminator().count() as u32).collect()
+    } else {
+        options
+            .ranges
+            .iter()
+            .map(|spec| spec.resolve(&blob))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    ranges.sort_by_key(|range| range.start);
+    let mut hunks = Vec::<UnblamedHunk>::new();
+    for range in ranges.into_iter().filter(|range| !range.is_empty()) {
+        match hunks.last_mut() {
+            Some(last) if last.suspect.end >= range.start => last.suspect.end = last.suspect.end.max(range.end),
+            _ => hunks.push(UnblamedHunk {
+                blamed_start: range.start,
+                suspect: range,
+            }),
+        }
+    }
+
+    let hidden = ancestors(odb, &options.hidden)?;
+    let mut suspects = HashMap::default();
+    let mut queue = BinaryHeap::new();
+    pass_blame(odb, &mut suspects, &mut queue, commit_id, blob_id, hunks, &mut buf)?;
+
+    let mut entries = Vec::new();
+    let mut parent_buf = Vec::new();
+    while let Some((_time, suspect)) = queue.pop() {
+        let Some((blob_id, mut hunks)) = suspects.remove(&suspect) else {
+            continue;
+        };
+        let (is_cutoff, parents) = {
+            let commit = odb.find_commit(&suspect, &mut buf)?;
+            let is_cutoff = hidden.contains(&suspect)
+                || options
+                    .since
+                    .map_or(false, |since| commit.committer.time.seconds < since.seconds);
+            (is_cutoff, commit.parents().collect::<Vec<_>>())
+        };
+        if is_cutoff || parents.is_empty() {
+            attribute(&mut entries, hunks, suspect, true);
+            continue;
+        }
+
+        let mut parent_blobs = Vec::new();
+        for parent in parents {
+            if let Some(parent_blob_id) = find_file(odb, parent, file_path, &mut buf)? {
+                parent_blobs.push((parent, parent_blob_id));
+            }
+        }
+        if let Some((parent, parent_blob_id)) = parent_blobs
+            .iter()
+            .find(|(_, parent_blob_id)| *parent_blob_id == blob_id)
+        {
+            pass_blame(
+                odb,
+                &mut suspects,
+                &mut queue,
+                *parent,
+                *parent_blob_id,
+                hunks,
+                &mut buf,
+            )?;
+            continue;
+        }
+
+        let suspect_blob = odb.find_blob(&blob_id, &mut buf)?.data.to_vec();
+        for (parent, parent_blob_id) in parent_blobs {
+            if hunks.is_empty() {
+                break;
+            }
+            let parent_blob = odb.find_blob(&parent_blob_id, &mut parent_buf)?.data;
+            let (passed, remaining) = split_hunks(hunks, &unchanged_lines(parent_blob, &suspect_blob));
+            hunks = remaining;
+            pass_blame(odb, &mut suspects, &mut queue, parent, parent_blob_id, passed, &mut buf)?;
+        }
+        attribute(&mut entries, hunks, suspect, false);
+    }
+
+    entries.sort_by_key(|entry| entry.range_in_blamed_file.start);
+    let mut coalesced = Vec::<Entry>::with_capacity(entries.len());
+    for entry in entries {
+        match coalesced.last_mut() {
+            Some(last)
+                if last.commit_id == entry.commit_id
+                    && last.range_in_blamed_file.end == entry.range_in_blamed_file.start
+                    && last.range_in_original_file.end == entry.range_in_original_file.start =>
+            {
+                last.range_in_blamed_file.end = entry.range_in_blamed_file.end;
+                last.range_in_original_file.end = entry.range_in_original_file.end;
+            }
+            _ => coalesced.push(entry),
+        }
+    }
+    Ok(Outcome {
+        entries: coalesced,
+        blob,
+    })
+}
+
+/// Return the id of the blob at `file_path` in the tree of `commit_id`, or `None` if there is no such blob.
+fn find_file(
+    odb: &impl gix_object::Find,
+    commit_id: ObjectId,
+    file_path: &BStr,
+    buf: &mut Vec<u8>,
+) -> Result<Option<ObjectId>, Error> {
+    let mut tree_id = odb.find_commit(&commit_id, buf)?.tree();
+    let mut components = file_path.split(|b| *b == b'/').peekable();
+    while let Some(component) = components.next() {
+        let tree = odb.find_tree(&tree_id, buf)?;
+        let Some(entry) = tree.entries.iter().find(|entry| entry.filename == component) else {
+            return Ok(None);
+        };
+        if components.peek().is_none() {
+            return Ok(entry.mode.is_blob().then(|| entry.oid.to_owned()));
+        }
+        if !entry.mode.is_tree() {
+            return Ok(None);
+        }
+        tree_id = entry.oid.to_owned();
+    }
+    Ok(None)
+}
+
+/// Return all `tips` along with all of their ancestors.
+fn ancestors(odb: &impl gix_object::Find, tips: &[ObjectId]) -> Result<HashSet<ObjectId>, Error> {
+    let mut buf = Vec::new();
+    let mut seen = HashSet::default();
+    let mut stack = tips.to_vec();
+    while let Some(id) = stack.pop() {
+        if seen.insert(id) {
+            stack.extend(odb.find_commit(&id, &mut buf)?.parents());
+        }
+    }
+    Ok(seen)
+}
+
+/// Make `commit_id` with the file at `blob_id` the suspect for `hunks`, and queue it if it isn't yet.
+fn pass_blame(
+    odb: &impl gix_object::Find,
+    suspects: &mut HashMap<ObjectId, (ObjectId, Vec<UnblamedHunk>)>,
+    queue: &mut BinaryHeap<(gix_date::SecondsSinceUnixEpoch, ObjectId)>,
+    commit_id: ObjectId,
+    blob_id: ObjectId,
+    hunks: Vec<UnblamedHunk>,
+    buf: &mut Vec<u8>,
+) -> Result<(), Error> {
+    if hunks.is_empty() {
+        return Ok(());
+    }
+    match suspects.entry(commit_id) {
+        hash_map::Entry::Occupied(mut entry) => entry.get_mut().1.extend(hunks),
+        hash_map::Entry::Vacant(entry) => {
+            let time = odb.find_commit(&commit_id, buf)?.committer.time.seconds;
+            entry.insert((blob_id, hunks));
+            queue.push((time, commit_id));
+        }
+    }
+    Ok(())
+}
+
+fn attribute(entries: &mut Vec<Entry>, hunks: Vec<UnblamedHunk>, commit_id: ObjectId, boundary: bool) {
+    entries.extend(hunks.into_iter().map(|hunk| Entry {
+        range_in_blamed_file: hunk.blamed_start..hunk.blamed_start + hunk.suspect.len() as u32,
+        range_in_original_file: hunk.suspect,
+        commit_id,
+        boundary,
+    }));
+}
+
+/// Lines which are the same in both versions of a file.
+struct UnchangedLines {
+    /// The lines in the newer version.
+    after: Range<u32>,
+    /// The first of the lines in the older version.
+    before_start: u32,
+}
+
+fn unchanged_lines(before: &[u8], after: &[u8]) -> Vec<UnchangedLines> {
+    let input = InternedInput::new(byte_lines_with_terminator(before), byte_lines_with_terminator(after));
+    let mut out = Vec::new();
+    let (mut before_pos, mut after_pos) = (0, 0);
+    imara_diff::diff(Algorithm::Myers, &input, |before: Range<u32>, after: Range<u32>| {
+        if after_pos < after.start {
+            out.push(UnchangedLines {
+                after: after_pos..after.start,
+                before_start: before_pos,
+            });
+        }
+        (before_pos, after_pos) = (before.end, after.end);
+    });
+    let num_after_lines = input.after.len() as u32;
+    if after_pos < num_after_lines {
+        out.push(UnchangedLines {
+            after: after_pos..num_after_lines,
+            before_start: before_pos,
+        });
+    }
+    out
+}
+
+/// Split `hunks` into the ones that are `unchanged` and thus passed on to the older version with their lines
+/// translated, and the ones that remain.
+fn split_hunks(hunks: Vec<UnblamedHunk>, unchanged: &[UnchangedLines]) -> (Vec<UnblamedHunk>, Vec<UnblamedHunk>) {
+    let (mut passed, mut remaining) = (Vec::new(), Vec::new());
+    for hunk in hunks {
+        let blamed_start = |suspect_line: u32| hunk.blamed_start + (suspect_line - hunk.suspect.start);
+        let mut pos = hunk.suspect.start;
+        for lines in unchanged {
+            let overlap = lines.after.start.max(hunk.suspect.start)..lines.after.end.min(hunk.suspect.end);
+            if overlap.is_empty() {
+                continue;
+            }
+            if pos < overlap.start {
+                remaining.push(UnblamedHunk {
+                    suspect: pos..overlap.start,
+                    blamed_start: blamed_start(pos),
+                });
+            }
+            let before_start = lines.before_start + (overlap.start - lines.after.start);
+            passed.push(UnblamedHunk {
+                suspect: before_start..before_start + overlap.len() as u32,
+                blamed_start: blamed_start(overlap.start),
+            });
+            pos = overlap.end;
+        }
+        if pos < hunk.suspect.end {
+            remaining.push(UnblamedHunk {
+                suspect: pos..hunk.suspect.end,
+                blamed_start: blamed_start(pos),
+            });
+        }
+    }
+    (passed, remaining)
+}
@@ -0,0 +1,187 @@
+//! Parse line ranges in the format of `git blame -L` and resolve them against the lines of a file.
+use std::ops::Range;
+
+use bstr::{BStr, BString, ByteSlice};
+use regex::bytes::Regex;
+
+/// The error returned by [`Spec::parse()`] and [`Spec::resolve()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("{spec:?} is not a valid line range")]
+    Syntax { spec: BString },
+    #[error(transparent)]
+    Regex(#[from] regex::Error),
+    #[error("Pattern {pattern:?} did not match any line starting at line {line}")]
+    NoMatch { pattern: String, line: u32 },
+    #[error("Line {line} is out of range as the file has only {lines} lines")]
+    OutOfRange { line: u32, lines: u32 },
+}
+
+/// A range of lines as passed to `git blame -L`, which is resolved against the lines of the blamed file.
+///
+/// The supported forms are `<start>,<end>` and `:<funcname>`, where `<start>` is a line number or `/<regex>/`
+/// and `<end>` is a line number, `/<regex>/`, `+<count>` or `-<count>`. Omitting `<start>` starts at the first line,
+/// and omitting `<end>` ends at the last line.
+/// Regular expressions use the syntax of the `regex` crate, and `:<funcname>` matches only lines that git would
+/// consider function headers by default, which is all lines starting with a letter, `$` or `_`.
+#[derive(Debug, Clone)]
+pub struct Spec(Kind);
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Lines { start: Start, end: End },
+    Function(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Start {
+    /// The one-based line number.
+    Line(u32),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum End {
+    /// The one-based and inclusive line number.
+    Line(u32),
+    Regex(Regex),
+    /// The amount of lines starting at `start`.
+    Forward(u32),
+    /// The amount of lines ending at `start`.
+    Backward(u32),
+    /// The last line of the file.
+    Last,
+}
+
+impl Spec {
+    /// Parse `spec` in the format of `git blame -L`.
+    pub fn parse(spec: &BStr) -> Result<Self, Error> {
+        let syntax_error = || Error::Syntax { spec: spec.to_owned() };
+        if let Some(funcname) = spec.strip_prefix(b":") {
+            return Ok(Spec(Kind::Function(regex(funcname)?)));
+        }
+
+        let mut input: &[u8] = spec;
+        let start = match input.first() {
+            Some(b'/') => Start::Regex(parse_regex(&mut input).ok_or_else(syntax_error)??),
+            Some(b',') => Start::Line(1),
+            _ => Start::Line(parse_number(&mut input).filter(|n| *n > 0).ok_or_else(syntax_error)?),
+        };
+        let end = match input {
+            [] | [b','] => End::Last,
+            [b',', rest @ ..] => {
+                input = rest;
+                let end = match input.first() {
+                    Some(b'/') => End::Regex(parse_regex(&mut input).ok_or_else(syntax_error)??),
+                    Some(b'+') => {
+                        input = &input[1..];
+                        End::Forward(parse_number(&mut input).filter(|n| *n > 0).ok_or_else(syntax_error)?)
+                    }
+                    Some(b'-') => {
+                        input = &input[1..];
+                        End::Backward(parse_number(&mut input).filter(|n| *n > 0).ok_or_else(syntax_error)?)
+                    }
+                    _ => End::Line(parse_number(&mut input).filter(|n| *n > 0).ok_or_else(syntax_error)?),
+                };
+                if !input.is_empty() {
+                    return Err(syntax_error());
+                }
+                end
+            }
+            _ => return Err(syntax_error()),
+        };
+        Ok(Spec(Kind::Lines { start, end }))
+    }
+
+    /// Resolve this instance against the lines of `blob`, returning the zero-based range of lines it refers to.
+    ///
+    /// Note that ranges reaching past the end of the file are cut off, but it's an error if they start after it.
+    pub fn resolve(&self, blob: &[u8]) -> Result<Range<u32>, Error> {
+        let lines: Vec<_> = blob.lines().collect();
+        let num_lines = lines.len() as u32;
+        let find = |re: &Regex, from: u32, is_candidate: &dyn Fn(&[u8]) -> bool| {
+            lines
+                .iter()
+                .enumerate()
+                .skip(from as usize)
+                .find(|(_, line)| is_candidate(line) && re.is_match(line))
+                .map(|(idx, _)| idx as u32)
+                .ok_or_else(|| Error::NoMatch {
+                    pattern: re.as_str().into(),
+                    line: from + 1,
+                })
+        };
+
+        let range = match &self.0 {
+            Kind::Function(re) => {
+                let start = find(re, 0, &is_function_header)?;
+                let end = lines[start as usize + 1..]
+                    .iter()
+                    .position(|line| is_function_header(line))
+                    .map_or(num_lines, |pos| start + 1 + pos as u32);
+                start..end
+            }
+            Kind::Lines { start, end } => {
+                let start = match start {
+                    Start::Line(line) => line - 1,
+                    Start::Regex(re) => find(re, 0, &|_| true)?,
+                };
+                match end {
+                    End::Line(line) if *line > start => start..*line,
+                    End::Line(line) => line - 1..start + 1,
+                    End::Regex(re) => start..find(re, start + 1, &|_| true)? + 1,
+                    End::Forward(count) => start..start.saturating_add(*count),
+                    End::Backward(count) => (start + 1).saturating_sub(*count)..start + 1,
+                    End::Last => start..num_lines,
+                }
+            }
+        };
+        if range.start >= num_lines {
+            return Err(Error::OutOfRange {
+                line: range.start + 1,
+                lines: num_lines,
+            });
+        }
+        Ok(range.start..range.end.min(num_lines))
+    }
+}
+
+fn is_function_header(line: &[u8]) -> bool {
+    line.first()
+        .map_or(false, |b| b.is_ascii_alphabetic() || *b == b'$' || *b == b'_')
+}
+
+fn regex(pattern: &[u8]) -> Result<Regex, Error> {
+    let pattern = std::str::from_utf8(pattern).map_err(|_| Error::Syntax { spec: pattern.into() })?;
+    Ok(Regex::new(pattern)?)
+}
+
+/// Parse `/<regex>/` from the beginning of `input`, allowing `\/` within the regex, and advance `input` past it.
+fn parse_regex(input: &mut &[u8]) -> Option<Result<Regex, Error>> {
+    let data = *input;
+    let mut pattern = Vec::new();
+    let mut bytes = data.iter().enumerate().skip(1);
+    while let Some((idx, byte)) = bytes.next() {
+        match byte {
+            b'\\' if data.get(idx + 1) == Some(&b'/') => {
+                pattern.push(b'/');
+                bytes.next();
+            }
+            b'/' => {
+                *input = &data[idx + 1..];
+                return Some(regex(&pattern));
+            }
+            _ => pattern.push(*byte),
+        }
+    }
+    None
+}
+
+/// Parse a decimal number from the beginning of `input` and advance `input` past it.
+fn parse_number(input: &mut &[u8]) -> Option<u32> {
+    let len = input.iter().take_while(|b| b.is_ascii_digit()).count();
+    let number = std::str::from_utf8(&input[..len]).ok()?.parse().ok()?;
+    *input = &input[len..];
+    Some(number)
+}
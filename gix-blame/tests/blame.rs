@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+use gix_blame::{range, Options};
+use gix_hash::ObjectId;
+use gix_testtools::Result;
+
+/// A blamed line as `(commit_id, line_in_original_file, line_in_blamed_file, boundary)`, with one-based line numbers.
+type Line = (ObjectId, u32, u32, bool);
+
+fn fixture() -> Result<PathBuf> {
+    gix_testtools::scripted_fixture_read_only("make_blame_repo.sh")
+}
+
+fn id(dir: &Path, name: &str) -> Result<ObjectId> {
+    Ok(ObjectId::from_hex(
+        std::fs::read_to_string(dir.join(name))?.trim().as_bytes(),
+    )?)
+}
+
+/// Parse the output of `git blame --line-porcelain`.
+fn baseline(dir: &Path, name: &str) -> Result<Vec<Line>> {
+    let mut lines = Vec::new();
+    for line in std::fs::read_to_string(dir.join(format!("{name}.baseline")))?.lines() {
+        let mut tokens = line.split(' ');
+        match tokens.next() {
+            Some(hex) if hex.len() == 40 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                let original = tokens.next().expect("original line").parse()?;
+                let blamed = tokens.next().expect("final line").parse()?;
+                lines.push((ObjectId::from_hex(hex.as_bytes())?, original, blamed, false));
+            }
+            Some("boundary") => lines.last_mut().expect("header line before").3 = true,
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
+
+fn blame(dir: &Path, options: Options) -> Result<Vec<Line>> {
+    let odb = gix_odb::at(dir.join(".git").join("objects"))?;
+    let outcome = gix_blame::file(&odb, id(dir, "head.id")?, "src/f.rs".into(), options)?;
+    Ok(outcome
+        .entries
+        .into_iter()
+        .flat_map(|entry| {
+            entry
+                .range_in_original_file
+                .zip(entry.range_in_blamed_file)
+                .map(move |(original, blamed)| (entry.commit_id, original + 1, blamed + 1, entry.boundary))
+        })
+        .collect())
+}
+
+fn ranges(specs: &[&str]) -> Result<Vec<range::Spec>> {
+    Ok(specs
+        .iter()
+        .map(|spec| range::Spec::parse((*spec).into()))
+        .collect::<std::result::Result<_, _>>()?)
+}
+
+fn time(seconds: gix_date::SecondsSinceUnixEpoch) -> Option<gix_date::Time> {
+    Some(gix_date::Time::new(seconds, 0))
+}
+
+#[test]
+fn all_lines_through_merges() -> Result {
+    let dir = fixture()?;
+    let actual = blame(&dir, Options::default())?;
+    assert_eq!(actual, baseline(&dir, "all")?);
+    assert_eq!(actual.len(), 11, "all lines are blamed");
+    Ok(())
+}
+
+#[test]
+fn line_ranges() -> Result {
+    let dir = fixture()?;
+    let options = Options {
+        ranges: ranges(&["3,4", ":b", "4,+2"])?,
+        ..Default::default()
+    };
+    assert_eq!(
+        blame(&dir, options)?,
+        baseline(&dir, "ranges")?,
+        "overlapping ranges are merged"
+    );
+    Ok(())
+}
+
+#[test]
+fn since_cutoff_reports_boundary_commits() -> Result {
+    let dir = fixture()?;
+    let options = Options {
+        // 2001-06-01
+        since: time(991353600),
+        ..Default::default()
+    };
+    assert_eq!(blame(&dir, options)?, baseline(&dir, "since")?);
+
+    let options = Options {
+        ranges: ranges(&[":a"])?,
+        // 2002-03-01
+        since: time(1014940800),
+        ..Default::default()
+    };
+    assert_eq!(blame(&dir, options)?, baseline(&dir, "function-since")?);
+    Ok(())
+}
+
+#[test]
+fn hidden_commits_are_boundaries() -> Result {
+    let dir = fixture()?;
+    let options = Options {
+        hidden: vec![id(&dir, "extend-a.id")?],
+        ..Default::default()
+    };
+    assert_eq!(blame(&dir, options)?, baseline(&dir, "hidden")?);
+    Ok(())
+}
+
+mod range_spec {
+    use gix_blame::range::{Error, Spec};
+
+    const BLOB: &[u8] = b"fn a() {\n  1\n}\n\nfn b() {\n  2\n}\nfn c() {}";
+
+    fn resolve(spec: &str) -> Result<std::ops::Range<u32>, Error> {
+        Spec::parse(spec.into())?.resolve(BLOB)
+    }
+
+    #[test]
+    fn forms_like_git() {
+        for (spec, expected) in [
+            ("2,3", 1..3),
+            ("3", 2..8),
+            (",2", 0..2),
+            ("2,", 1..8),
+            ("4,2", 1..4),
+            ("2,+2", 1..3),
+            ("5,-2", 3..5),
+            ("3,20", 2..8),
+            ("/fn b/,+2", 4..6),
+            ("/fn/,/fn/", 0..5),
+            (r"/\/x|2/", 5..8),
+            (":b", 4..7),
+            (":a", 0..4),
+            (":c", 7..8),
+        ] {
+            assert_eq!(resolve(spec).expect("valid"), expected, "{spec}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        for spec in ["", "0", "x", "1,+0", "1,-0", "2,3x", "/unterminated", "1 2"] {
+            assert!(matches!(Spec::parse(spec.into()), Err(Error::Syntax { .. })), "{spec}");
+        }
+        assert!(matches!(Spec::parse("/(/".into()), Err(Error::Regex(_))));
+        assert!(matches!(resolve("9"), Err(Error::OutOfRange { line: 9, lines: 8 })));
+        assert!(matches!(resolve(":  2"), Err(Error::NoMatch { line: 1, .. })));
+        assert!(matches!(resolve("5,/fn a/"), Err(Error::NoMatch { line: 6, .. })));
+    }
+}
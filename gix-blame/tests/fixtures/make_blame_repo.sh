@@ -0,0 +1,53 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit_at() {
+  local date="$1 +0000"
+  shift
+  GIT_AUTHOR_DATE="$date" GIT_COMMITTER_DATE="$date" git commit -q "$@"
+}
+
+function baseline() {
+  local name=${1:?First argument is the name of the baseline}
+  shift
+  git blame --line-porcelain "$@" -- src/f.rs > "$name.baseline"
+}
+
+git init -q
+git checkout -q -b main
+
+mkdir src
+printf 'fn a() {\n  1\n}\n\nfn b() {\n  2\n}\n' > src/f.rs
+git add src/f.rs
+commit_at "2000-01-01 00:00:00" -m "add f"
+
+printf 'fn a() {\n  1\n  one\n}\n\nfn b() {\n  2\n}\n' > src/f.rs
+commit_at "2001-01-01 00:00:00" -am "extend a"
+git tag extend-a
+
+git checkout -q -b side
+printf '// header\nfn a() {\n  1\n  one\n}\n\nfn b() {\n  2\n}\n' > src/f.rs
+commit_at "2002-06-01 00:00:00" -am "add header"
+
+git checkout -q main
+printf 'fn a() {\n  1\n  one\n}\n\nfn b() {\n  2\n  two\n}\nfn c() {}\n' > src/f.rs
+commit_at "2002-01-01 00:00:00" -am "extend b and add c"
+
+GIT_AUTHOR_DATE="2003-01-01 00:00:00 +0000" GIT_COMMITTER_DATE="2003-01-01 00:00:00 +0000" git merge -q --no-edit side
+
+printf 'fn a() {\n  1 changed\n  one\n}\n' > other
+sed -i.bak 's/^  1$/  1 changed/' src/f.rs && rm src/f.rs.bak
+git add other
+commit_at "2004-01-01 00:00:00" -am "change a"
+
+echo "unrelated" > other
+commit_at "2005-01-01 00:00:00" -am "change other"
+
+git rev-parse HEAD > head.id
+git rev-parse extend-a > extend-a.id
+
+baseline all
+baseline ranges -L 3,4 -L :b -L 4,+2
+baseline since --since="2001-06-01 00:00:00 +0000"
+baseline hidden extend-a..HEAD
+baseline function-since -L :a --since="2002-03-01 00:00:00 +0000"
@@ -26,6 +26,8 @@ pub struct Prepare {
     pub args: Vec<OsString>,
     /// environment variables to set in the spawned process.
     pub env: Vec<(OsString, OsString)>,
+    /// Control which environment variables of the current process are inherited by the spawned process.
+    pub environment: Environment,
     /// If `true`, we will use `sh` to execute the `command`.
     pub use_shell: bool,
     /// If `true` (default `true` on windows and `false` everywhere else)
@@ -56,6 +58,8 @@ pub struct Context {
     pub git_dir: Option<PathBuf>,
     /// Set the `GIT_WORK_TREE` environment variable with the given path.
     pub worktree_dir: Option<PathBuf>,
+    /// Set the `GIT_INDEX_FILE` environment variable with the given path.
+    pub index_file: Option<PathBuf>,
     /// If `true`, set `GIT_NO_REPLACE_OBJECTS` to `1`, which turns off object replacements, or `0` otherwise.
     /// If `None`, the variable won't be set.
     pub no_replace_objects: Option<bool>,
@@ -78,6 +82,65 @@ pub struct Context {
     pub stderr: Option<bool>,
 }
 
+/// Describe which environment variables of the current process are passed on to spawned processes,
+/// before any variables of the [`Context`] or those [set explicitly](Prepare::env()) are added.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Environment {
+    /// Inherit all environment variables, which is the default when spawning processes.
+    #[default]
+    InheritAll,
+    /// Inherit all environment variables except for those that tell `git` which repository to operate on and how,
+    /// like `GIT_DIR` or `GIT_INDEX_FILE`, just like `git` does before running commands in another repository.
+    ///
+    /// This assures that spawned processes only see the repository information of the [`Context`],
+    /// and not the one that happened to be set for the current process.
+    /// See [`Environment::GIT_REPOSITORY_VARIABLES`] for the variables that are removed.
+    GitClean,
+    /// Don't inherit any environment variable except for the ones with the given names, if they are set.
+    Allowlist(Vec<OsString>),
+}
+
+impl Environment {
+    /// The names of all variables that refer to a repository and its configuration, which is the same as `local_repo_env` in `git`.
+    pub const GIT_REPOSITORY_VARIABLES: &'static [&'static str] = &[
+        "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+        "GIT_CONFIG",
+        "GIT_CONFIG_PARAMETERS",
+        "GIT_CONFIG_COUNT",
+        "GIT_OBJECT_DIRECTORY",
+        "GIT_DIR",
+        "GIT_WORK_TREE",
+        "GIT_IMPLICIT_WORK_TREE",
+        "GIT_GRAFT_FILE",
+        "GIT_INDEX_FILE",
+        "GIT_NO_REPLACE_OBJECTS",
+        "GIT_REPLACE_REF_BASE",
+        "GIT_PREFIX",
+        "GIT_SHALLOW_FILE",
+        "GIT_COMMON_DIR",
+    ];
+
+    /// Apply this environment profile to `cmd`.
+    pub fn apply(&self, cmd: &mut std::process::Command) {
+        match self {
+            Environment::InheritAll => {}
+            Environment::GitClean => {
+                for name in Self::GIT_REPOSITORY_VARIABLES {
+                    cmd.env_remove(name);
+                }
+            }
+            Environment::Allowlist(names) => {
+                cmd.env_clear();
+                for name in names {
+                    if let Some(value) = std::env::var_os(name) {
+                        cmd.env(name, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
 mod prepare {
     use std::borrow::Cow;
     use std::{
@@ -87,7 +150,7 @@ mod prepare {
 
     use bstr::ByteSlice;
 
-    use crate::{extract_interpreter, win_path_lookup, Context, Prepare};
+    use crate::{extract_interpreter, win_path_lookup, Context, Environment, Prepare};
 
     /// Builder
     impl Prepare {
@@ -120,6 +183,15 @@ mod prepare {
             self
         }
 
+        /// Control which variables of the environment of the current process are inherited by the spawned process
+        /// with the given `environment` profile.
+        ///
+        /// Variables of the [context](Self::with_context()) and those [set explicitly](Self::env()) are always added.
+        pub fn with_environment(mut self, environment: Environment) -> Self {
+            self.environment = environment;
+            self
+        }
+
         /// Use a shell, but try to split arguments by hand if this can be safely done without a shell.
         ///
         /// If that's not the case, use a shell instead.
@@ -237,6 +309,7 @@ mod prepare {
             } else {
                 Command::new(prep.command)
             };
+            prep.environment.apply(&mut cmd);
             cmd.stdin(prep.stdin)
                 .stdout(prep.stdout)
                 .stderr(prep.stderr)
@@ -249,6 +322,9 @@ mod prepare {
                 if let Some(worktree_dir) = ctx.worktree_dir {
                     cmd.env("GIT_WORK_TREE", worktree_dir);
                 }
+                if let Some(index_file) = ctx.index_file {
+                    cmd.env("GIT_INDEX_FILE", index_file);
+                }
                 if let Some(value) = ctx.no_replace_objects {
                     cmd.env("GIT_NO_REPLACE_OBJECTS", usize::from(value).to_string());
                 }
@@ -415,6 +491,7 @@ pub fn prepare(cmd: impl Into<OsString>) -> Prepare {
         stderr: std::process::Stdio::inherit(),
         args: Vec::new(),
         env: Vec::new(),
+        environment: Environment::InheritAll,
         use_shell: false,
         allow_manual_arg_splitting: cfg!(windows),
     }
@@ -147,6 +147,16 @@ mod context {
         assert_eq!(format!("{cmd:?}"), winfix(r#"GIT_WORK_TREE="." """#));
     }
 
+    #[test]
+    fn index_file_sets_env_only() {
+        let ctx = Context {
+            index_file: Some("index".into()),
+            ..Default::default()
+        };
+        let cmd = std::process::Command::from(gix_command::prepare("").with_context(ctx));
+        assert_eq!(format!("{cmd:?}"), winfix(r#"GIT_INDEX_FILE="index" """#));
+    }
+
     #[test]
     fn no_replace_objects_sets_env_only() {
         for value in [false, true] {
@@ -221,6 +231,60 @@ mod context {
     }
 }
 
+mod environment {
+    use std::ffi::OsStr;
+
+    use gix_command::{Context, Environment};
+
+    #[test]
+    fn git_clean_removes_repository_variables_but_keeps_context() {
+        let cmd = std::process::Command::from(
+            gix_command::prepare("")
+                .with_environment(Environment::GitClean)
+                .with_context(Context {
+                    git_dir: Some("repo".into()),
+                    ..Default::default()
+                }),
+        );
+        let envs: Vec<_> = cmd.get_envs().collect();
+        for name in Environment::GIT_REPOSITORY_VARIABLES
+            .iter()
+            .filter(|name| **name != "GIT_DIR")
+        {
+            assert!(
+                envs.contains(&(OsStr::new(name), None)),
+                "{name} is removed from the environment"
+            );
+        }
+        assert!(
+            envs.contains(&(OsStr::new("GIT_DIR"), Some(OsStr::new("repo")))),
+            "the context is applied afterwards"
+        );
+    }
+
+    #[test]
+    fn inherit_all_is_the_default_and_changes_nothing() {
+        let cmd = std::process::Command::from(gix_command::prepare(""));
+        assert_eq!(cmd.get_envs().count(), 0);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn allowlist_only_inherits_listed_variables() -> crate::Result {
+        let out = gix_command::prepare("echo \"$HOME:$EXPLICIT\"")
+            .with_environment(Environment::Allowlist(vec!["PATH".into()]))
+            .env("EXPLICIT", "set")
+            .with_shell()
+            .spawn()?
+            .wait_with_output()?;
+        assert_eq!(
+            out.stdout, b":set\n",
+            "HOME isn't inherited, but explicit variables are set"
+        );
+        Ok(())
+    }
+}
+
 mod prepare {
     #[cfg(windows)]
     const SH: &str = "sh";
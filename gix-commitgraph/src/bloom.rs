@@ -0,0 +1,151 @@
+//! Changed-path Bloom filters as stored in commit-graph files.
+//!
+//! Each commit may have a [`Filter`] that contains all paths that changed compared to its first parent, along with all
+//! of their leading directories. A filter can tell for sure that a path did *not* change, which allows to skip the
+//! comparison of trees for most commits when looking for the history of a path.
+//!
+//! See the [`git` documentation](https://git-scm.com/docs/gitformat-commit-graph) for details on the format.
+use bstr::{BStr, ByteSlice};
+
+/// The parameters used to create the filters of a commit-graph file, which are needed to compute matching [keys](Key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Settings {
+    /// The version of the hash algorithm, `1` or `2`, which only differ in how bytes with the high bit set are hashed.
+    pub hash_version: u32,
+    /// The number of hashes computed for each path, i.e. the amount of bits set in a filter for each path.
+    pub num_hashes: u32,
+    /// The amount of bits used per changed path when creating a filter.
+    pub bits_per_entry: u32,
+}
+
+/// The hashes of a path, ready to be looked up in a [`Filter`] which was created with the same [`Settings`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    hashes: Vec<u32>,
+}
+
+impl Key {
+    /// Compute the key of `path`, a slash-separated path relative to the root of the repository, for use with filters
+    /// created with `settings`.
+    pub fn new(path: &[u8], settings: &Settings) -> Self {
+        const SEED0: u32 = 0x293a_e76f;
+        const SEED1: u32 = 0x7e64_6e2c;
+        let hash = |seed| murmur3(seed, path, settings.hash_version >= 2);
+        let (hash0, hash1) = (hash(SEED0), hash(SEED1));
+        Key {
+            hashes: (0..settings.num_hashes)
+                .map(|idx| hash0.wrapping_add(idx.wrapping_mul(hash1)))
+                .collect(),
+        }
+    }
+
+    /// Compute the keys of `path` and each of its leading directories, which is what is needed to query a filter
+    /// with [`Filter::may_contain_all()`] with fewer false positives.
+    ///
+    /// For `a/b/c`, the keys of `a/b/c`, `a/b` and `a` are returned.
+    pub fn with_leading_dirs(path: &BStr, settings: &Settings) -> Vec<Self> {
+        let path = path.trim_end_with(|c| c == '/');
+        std::iter::once(path.len())
+            .chain(path.rfind_iter("/").filter(|pos| *pos > 0))
+            .map(|end| Key::new(&path[..end], settings))
+            .collect()
+    }
+}
+
+/// A changed-path Bloom filter of a single commit.
+#[derive(Debug, Clone, Copy)]
+pub struct Filter<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) settings: Settings,
+}
+
+/// Access
+impl<'a> Filter<'a> {
+    /// Return the settings with which this filter was created, and with which [keys](Key) have to be created.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Return `false` if the path represented by `key` definitely did not change in this commit, or `true` if it may have.
+    ///
+    /// Note that filters of commits with too many changes, or with filters that couldn't be computed, may contain everything.
+    pub fn may_contain(&self, key: &Key) -> bool {
+        let num_bits = self.data.len() as u64 * 8;
+        if num_bits == 0 {
+            return true;
+        }
+        key.hashes.iter().all(|hash| {
+            let bit = u64::from(*hash) % num_bits;
+            self.data[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+        })
+    }
+
+    /// Return `false` if at least one of `keys` definitely is not contained in this filter, or `true` if all of them may be.
+    ///
+    /// This is useful with keys produced by [`Key::with_leading_dirs()`].
+    pub fn may_contain_all(&self, keys: &[Key]) -> bool {
+        keys.iter().all(|key| self.may_contain(key))
+    }
+}
+
+/// The 32 bit murmur3 hash, as implemented by `git`.
+///
+/// Version 1 of the changed-path filters is computed with bytes sign-extended, which is a bug in `git` on platforms with
+/// signed `char` that we reproduce unless `unsigned_bytes` is set.
+fn murmur3(mut seed: u32, data: &[u8], unsigned_bytes: bool) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+    let byte = |b: u8| {
+        if unsigned_bytes {
+            u32::from(b)
+        } else {
+            b as i8 as i32 as u32
+        }
+    };
+    let mix = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in chunks.by_ref() {
+        let k = byte(chunk[0]) | byte(chunk[1]) << 8 | byte(chunk[2]) << 16 | byte(chunk[3]) << 24;
+        seed ^= mix(k);
+        seed = seed.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .enumerate()
+            .fold(0, |k, (idx, b)| k ^ byte(*b) << (8 * idx as u32));
+        seed ^= mix(k);
+    }
+
+    seed ^= data.len() as u32;
+    seed ^= seed >> 16;
+    seed = seed.wrapping_mul(0x85eb_ca6b);
+    seed ^= seed >> 13;
+    seed = seed.wrapping_mul(0xc2b2_ae35);
+    seed ^= seed >> 16;
+    seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::murmur3;
+
+    #[test]
+    fn murmur3_matches_git() {
+        // Values from `t0095-bloom.sh` in `git`.
+        assert_eq!(murmur3(0, b"", true), 0x0000_0000);
+        assert_eq!(murmur3(0, b"Hello world!", true), 0x627b_0c2c);
+        assert_eq!(
+            murmur3(0, b"The quick brown fox jumps over the lazy dog", true),
+            0x2e4f_f723
+        );
+        assert_eq!(murmur3(0, b"\x99\xaa\xbb\xcc\xdd\xee\xff", true), 0xa183_ccfd);
+        assert_eq!(
+            murmur3(0, b"\x99\xaa\xbb\xcc\xdd\xee\xff", false),
+            0xdd92_776e,
+            "version 1 sign-extends bytes, and thus differs"
+        );
+    }
+}
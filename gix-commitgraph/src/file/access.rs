@@ -4,6 +4,7 @@ use std::{
 };
 
 use crate::{
+    bloom,
     file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH},
     File,
 };
@@ -15,6 +16,39 @@ impl File {
         self.base_graph_count
     }
 
+    /// Returns the changed-path Bloom filter of the commit at the given lexicographical position, or `None` if this
+    /// file has no such filters or if they were created with an unsupported version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    pub fn bloom_filter_at(&self, pos: file::Position) -> Option<bloom::Filter<'_>> {
+        assert!(
+            pos.0 < self.num_commits(),
+            "expected lexicographical position less than {}, got {}",
+            self.num_commits(),
+            pos.0
+        );
+        let filters = self.bloom_filters.as_ref()?;
+        let end_of = |pos: usize| {
+            let ofs = filters.index_offset + pos * 4;
+            u32::from_be_bytes(self.data[ofs..][..4].try_into().expect("4 bytes")) as usize
+        };
+        let pos = pos.0 as usize;
+        let start = if pos == 0 { 0 } else { end_of(pos - 1) };
+        let data = &self.data[filters.data.clone()];
+        Some(bloom::Filter {
+            // Corrupt offsets lead to empty filters, which are always matching.
+            data: data.get(start..end_of(pos)).unwrap_or_default(),
+            settings: filters.settings,
+        })
+    }
+
+    /// Returns the settings of the changed-path Bloom filters in this file, or `None` if there are no such filters.
+    pub fn bloom_filter_settings(&self) -> Option<&bloom::Settings> {
+        self.bloom_filters.as_ref().map(|filters| &filters.settings)
+    }
+
     /// Returns the commit data for the commit located at the given lexicographical position.
     ///
     /// `pos` must range from 0 to `self.num_commits()`.
@@ -100,6 +100,12 @@ impl<'a> Commit<'a> {
         self.pos
     }
 
+    /// Return the changed-path Bloom filter of this commit, which contains all paths that changed compared to its first
+    /// parent, if the owning file has such filters.
+    pub fn bloom_filter(&self) -> Option<crate::bloom::Filter<'a>> {
+        self.file.bloom_filter_at(self.pos)
+    }
+
     /// Return the hash of the tree this commit points to.
     pub fn root_tree_id(&self) -> &gix_hash::oid {
         self.root_tree_id
@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 use crate::{
     file::{
-        ChunkId, BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
+        BloomFilters, ChunkId, BASE_GRAPHS_LIST_CHUNK_ID, BLOOM_FILTER_DATA_CHUNK_ID, BLOOM_FILTER_DATA_HEADER_LEN,
+        BLOOM_FILTER_INDEX_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
         EXTENDED_EDGES_LIST_CHUNK_ID, FAN_LEN, HEADER_LEN, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
     },
     File,
@@ -183,6 +184,7 @@ impl File {
             })??;
 
         let extra_edges_list_range = chunks.usize_offset_by_id(EXTENDED_EDGES_LIST_CHUNK_ID).ok();
+        let bloom_filters = bloom_filters(&data, &chunks, commit_data_count)?;
 
         let trailer = &data[chunks.highest_offset() as usize..];
         if trailer.len() != object_hash.len_in_bytes() {
@@ -220,6 +222,7 @@ impl File {
         Ok(File {
             base_graph_count,
             base_graphs_list_offset,
+            bloom_filters,
             commit_data_offset,
             data,
             extra_edges_list_range,
@@ -252,6 +255,52 @@ impl TryFrom<&Path> for File {
     }
 }
 
+/// Return the location of the changed-path Bloom filters if both of their chunks are present and if we understand
+/// their hash version. Otherwise, the filters are ignored just like `git` does.
+fn bloom_filters(
+    data: &[u8],
+    chunks: &gix_chunk::file::Index,
+    num_commits: u32,
+) -> Result<Option<BloomFilters>, Error> {
+    let (Ok(index), Ok(filters)) = (
+        chunks.usize_offset_by_id(BLOOM_FILTER_INDEX_CHUNK_ID),
+        chunks.usize_offset_by_id(BLOOM_FILTER_DATA_CHUNK_ID),
+    ) else {
+        return Ok(None);
+    };
+    let expected_size = num_commits as usize * 4;
+    if index.len() != expected_size {
+        return Err(Error::InvalidChunkSize {
+            id: BLOOM_FILTER_INDEX_CHUNK_ID,
+            msg: format!("expected chunk length {expected_size}, got {}", index.len()),
+        });
+    }
+    if filters.len() < BLOOM_FILTER_DATA_HEADER_LEN {
+        return Err(Error::InvalidChunkSize {
+            id: BLOOM_FILTER_DATA_CHUNK_ID,
+            msg: format!(
+                "chunk length {} is too small to hold the header of {BLOOM_FILTER_DATA_HEADER_LEN} bytes",
+                filters.len()
+            ),
+        });
+    }
+    let header = &data[filters.start..][..BLOOM_FILTER_DATA_HEADER_LEN];
+    let read_u32 = |ofs: usize| u32::from_be_bytes(header[ofs..][..4].try_into().expect("4 bytes"));
+    let settings = crate::bloom::Settings {
+        hash_version: read_u32(0),
+        num_hashes: read_u32(4),
+        bits_per_entry: read_u32(8),
+    };
+    if !matches!(settings.hash_version, 1 | 2) {
+        return Ok(None);
+    }
+    Ok(Some(BloomFilters {
+        index_offset: index.start,
+        data: filters.start + BLOOM_FILTER_DATA_HEADER_LEN..filters.end,
+        settings,
+    }))
+}
+
 // Copied from gix-odb/pack/index/init.rs
 fn read_fan(d: &[u8]) -> ([u32; FAN_LEN], usize) {
     assert!(d.len() >= FAN_LEN * 4);
@@ -17,6 +17,9 @@ const SIGNATURE: &[u8] = b"CGPH";
 
 type ChunkId = gix_chunk::Id;
 const BASE_GRAPHS_LIST_CHUNK_ID: ChunkId = *b"BASE";
+const BLOOM_FILTER_INDEX_CHUNK_ID: ChunkId = *b"BIDX";
+const BLOOM_FILTER_DATA_CHUNK_ID: ChunkId = *b"BDAT";
+const BLOOM_FILTER_DATA_HEADER_LEN: usize = 12;
 const COMMIT_DATA_CHUNK_ID: ChunkId = *b"CDAT";
 const EXTENDED_EDGES_LIST_CHUNK_ID: ChunkId = *b"EDGE";
 const OID_FAN_CHUNK_ID: ChunkId = *b"OIDF";
@@ -28,6 +31,16 @@ const NO_PARENT: u32 = 0x7000_0000;
 const EXTENDED_EDGES_MASK: u32 = 0x8000_0000;
 const LAST_EXTENDED_EDGE_MASK: u32 = 0x8000_0000;
 
+/// The location of the changed-path Bloom filters within a commit-graph file.
+#[derive(Debug, Clone)]
+pub(crate) struct BloomFilters {
+    /// The offset of the index into `data`, with one entry per commit.
+    index_offset: usize,
+    /// The filter data, sans header.
+    data: std::ops::Range<usize>,
+    settings: crate::bloom::Settings,
+}
+
 /// The position of a given commit within a graph file, starting at 0.
 ///
 /// Commits within a graph file are sorted in lexicographical order by OID; a commit's lexicographical position
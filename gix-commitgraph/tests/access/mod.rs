@@ -1,3 +1,5 @@
+use gix_commitgraph::bloom::Key;
+
 use crate::{check_common, graph_and_expected, graph_and_expected_named};
 
 #[test]
@@ -111,3 +113,37 @@ fn two_parents() {
     assert_eq!(cg.commit_at(refs["parent2"].pos()).generation(), 1);
     assert_eq!(cg.commit_at(refs["child"].pos()).generation(), 2);
 }
+
+#[test]
+fn changed_path_bloom_filters() {
+    let (cg, refs) = graph_and_expected("changed_paths.sh", &["first", "second", "third"]);
+    check_common(&cg, &refs);
+
+    let settings = *cg
+        .commit_at(refs["first"].pos())
+        .bloom_filter()
+        .expect("filters were written")
+        .settings();
+    assert_eq!(settings.num_hashes, 7, "the default used by git");
+    let keys = |path: &str| Key::with_leading_dirs(path.into(), &settings);
+    let filter = |name: &str| cg.commit_at(refs[name].pos()).bloom_filter().expect("present");
+
+    for path in ["a/b/file", "top", "a/b", "a"] {
+        assert!(filter("first").may_contain_all(&keys(path)), "{path}");
+    }
+    assert!(!filter("first").may_contain_all(&keys("c/file")));
+
+    assert!(filter("second").may_contain_all(&keys("a/b/file")));
+    assert!(!filter("second").may_contain_all(&keys("top")));
+    assert!(!filter("second").may_contain_all(&keys("c/file")));
+
+    assert!(filter("third").may_contain_all(&keys("c/file")));
+    assert!(!filter("third").may_contain_all(&keys("a/b/file")));
+    assert!(!filter("third").may_contain(&Key::new(b"a", &settings)));
+}
+
+#[test]
+fn graphs_without_changed_path_bloom_filters() {
+    let (cg, refs) = graph_and_expected("single_parent.sh", &["parent", "child"]);
+    assert!(cg.commit_at(refs["child"].pos()).bloom_filter().is_none());
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+mkdir -p a/b c
+echo 1 > a/b/file && echo 1 > top
+git add . && git commit -q -m first && git branch first
+
+echo 2 > a/b/file
+git commit -q -am second && git branch second
+
+echo 1 > c/file
+git add . && git commit -q -m third && git branch third
+
+git commit-graph write --no-progress --reachable --changed-paths
@@ -37,6 +37,46 @@ impl File<'static> {
     }
 }
 
+impl<'event> File<'event> {
+    /// Return the paths of all files that `include` and `includeIf` directives found in this instance refer to according to
+    /// `options`, whether these files exist or not. Conditions of `includeIf` directives are evaluated, and paths
+    /// that can't be interpolated or made absolute are skipped unless `options` demand an error.
+    ///
+    /// This is useful to learn which files would affect this configuration if it was loaded again. Note that this instance
+    /// should have its [includes resolved](File::resolve_includes()) so that the directives of included files are seen as well.
+    /// If `options` don't allow following includes, no path is returned.
+    pub fn include_paths(&self, options: includes::Options<'_>) -> Result<Vec<PathBuf>, Error> {
+        let mut out = Vec::new();
+        if options.max_depth == 0 {
+            return Ok(out);
+        }
+        for section in self.section_order.iter().map(|id| &self.sections[id]) {
+            let header = &section.header;
+            let header_name = header.name.as_ref();
+            let target_config_path = section.meta.path.as_deref();
+            let is_included = if header_name == "include" {
+                header.subsection_name.is_none()
+            } else if header_name == "includeIf" {
+                match &header.subsection_name {
+                    Some(condition) => include_condition_match(condition.as_ref(), target_config_path, options)?,
+                    None => false,
+                }
+            } else {
+                false
+            };
+            if !is_included {
+                continue;
+            }
+            for path in section.body.values("path") {
+                if let Some(path) = resolve_path(crate::Path::from(path), target_config_path, options)? {
+                    out.push(path);
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
 pub(crate) fn resolve(config: &mut File<'static>, buf: &mut Vec<u8>, options: init::Options<'_>) -> Result<(), Error> {
     resolve_includes_recursive(config, 0, buf, options)
 }
@@ -24,6 +24,11 @@ pub mod includes;
 mod meta;
 mod util;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod transaction;
+pub use transaction::Transaction;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod section;
@@ -22,6 +22,7 @@ use crate::{
 pub struct SectionMut<'a, 'event> {
     section: &'a mut Section<'event>,
     implicit_newline: bool,
+    align_separators: bool,
     whitespace: Whitespace<'event>,
     newline: SmallVec<[u8; 2]>,
 }
@@ -49,38 +50,109 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         self
     }
 
-    fn push_with_comment_inner(&mut self, value_name: ValueName<'event>, value: Option<&BStr>, comment: Option<&BStr>) {
+    /// Adds an entry with `value_name` and `value` right after the line of the last value named `anchor`, or to the end of this
+    /// section if there is no such value. If `value` is `None`, no equal sign will be written leaving just the key.
+    ///
+    /// This is useful to keep related values together, like multiple `fetch` refspecs of a remote, which keeps diffs
+    /// of hand-maintained configuration files small.
+    pub fn push_after<'b>(
+        &mut self,
+        anchor: &str,
+        value_name: ValueName<'event>,
+        value: Option<&'b BStr>,
+    ) -> &mut Self {
+        let anchor = ValueName::from_str_unchecked(anchor);
+        let Some((anchor_range, _)) = self.key_and_value_range_by(&anchor) else {
+            return self.push(value_name, value);
+        };
+        let events = self.value_events(value_name, value, None);
+        let newline = Event::Newline(BString::from(self.newline.to_vec()).into());
         let body = &mut self.section.body.0;
+        match body[anchor_range.end..]
+            .iter()
+            .position(|e| matches!(e, Event::Newline(_)))
+        {
+            Some(newline_pos) => {
+                let insert_pos = anchor_range.end + newline_pos + 1;
+                body.splice(insert_pos..insert_pos, events.into_iter().chain(Some(newline)));
+            }
+            None => {
+                body.push(newline);
+                body.extend(events);
+                if self.implicit_newline {
+                    self.push_newline();
+                }
+            }
+        }
+        self
+    }
+
+    fn push_with_comment_inner(&mut self, value_name: ValueName<'event>, value: Option<&BStr>, comment: Option<&BStr>) {
+        let events = self.value_events(value_name, value, comment);
+        self.section.body.0.extend(events);
+        if self.implicit_newline {
+            self.push_newline();
+        }
+    }
+
+    /// Return all events for a line with `value_name`, `value` and `comment`, without the trailing newline.
+    fn value_events(
+        &self,
+        value_name: ValueName<'event>,
+        value: Option<&BStr>,
+        comment: Option<&BStr>,
+    ) -> Vec<Event<'event>> {
+        let mut out = Vec::new();
         if let Some(ws) = &self.whitespace.pre_key {
-            body.push(Event::Whitespace(ws.clone()));
+            out.push(Event::Whitespace(ws.clone()));
         }
 
-        body.push(Event::SectionValueName(value_name));
+        let separators = value.is_some().then(|| self.key_value_separators(&value_name));
+        out.push(Event::SectionValueName(value_name));
         match value {
             Some(value) => {
-                body.extend(self.whitespace.key_value_separators());
-                body.push(Event::Value(escape_value(value).into()));
+                out.extend(separators.expect("set if there is a value"));
+                out.push(Event::Value(escape_value(value).into()));
             }
-            None => body.push(Event::Value(Cow::Borrowed("".into()))),
+            None => out.push(Event::Value(Cow::Borrowed("".into()))),
         }
         if let Some(comment) = comment {
-            body.push(Event::Whitespace(Cow::Borrowed(" ".into())));
-            body.push(Event::Comment(parse::Comment {
-                tag: b'#',
-                text: Cow::Owned({
-                    let mut c = Vec::with_capacity(comment.len());
-                    let mut bytes = comment.iter().peekable();
-                    if !bytes.peek().map_or(true, |b| b.is_ascii_whitespace()) {
-                        c.insert(0, b' ');
-                    }
-                    c.extend(bytes.map(|b| if *b == b'\n' { b' ' } else { *b }));
-                    c.into()
-                }),
-            }));
+            out.extend(comment_events(comment));
         }
-        if self.implicit_newline {
-            body.push(Event::Newline(BString::from(self.newline.to_vec()).into()));
+        out
+    }
+
+    /// Return the separator events to use for a new `value_name`, possibly aligned with the other values in this section.
+    fn key_value_separators(&self, value_name: &ValueName<'_>) -> Vec<Event<'event>> {
+        let mut out = self.whitespace.key_value_separators();
+        if self.align_separators {
+            let body = &self.section.body.0;
+            let separator_column = body
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, e)| match (e, body.get(idx + 1), body.get(idx + 2)) {
+                    (Event::SectionValueName(name), Some(Event::KeyValueSeparator), _) => Some(name.len()),
+                    (Event::SectionValueName(name), Some(Event::Whitespace(ws)), Some(Event::KeyValueSeparator)) => {
+                        Some(name.len() + ws.len())
+                    }
+                    _ => None,
+                })
+                .max();
+            if let Some(column) = separator_column {
+                let has_pre_sep = matches!(out.first(), Some(Event::Whitespace(_)));
+                let padding = match column.saturating_sub(value_name.len()) {
+                    0 => usize::from(has_pre_sep),
+                    padding => padding,
+                };
+                if has_pre_sep {
+                    out.remove(0);
+                }
+                if padding > 0 {
+                    out.insert(0, Event::Whitespace(Cow::Owned(" ".repeat(padding).into())));
+                }
+            }
         }
+        out
     }
 
     /// Removes all events until a key value pair is removed. This will also
@@ -142,10 +214,42 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         }
     }
 
+    /// Sets the last key value pair if it exists, or adds the new value, just like [`set()`](Self::set()).
+    /// Additionally, `comment` replaces the comment behind the value, or is attached to it if there was none.
+    /// Note that newlines in `comment` are silently transformed into spaces.
+    ///
+    /// Use [`set()`](Self::set()) to keep the existing comment instead.
+    pub fn set_with_comment<'c>(
+        &mut self,
+        value_name: ValueName<'event>,
+        value: &BStr,
+        comment: impl Into<&'c BStr>,
+    ) -> Option<Cow<'event, BStr>> {
+        let comment = comment.into();
+        if !self.contains_value_name(value_name.as_ref()) {
+            self.push_with_comment_inner(value_name, Some(value), Some(comment));
+            return None;
+        }
+        let previous = self.set(value_name.clone(), value);
+        let (key_range, _) = self.key_and_value_range_by(&value_name).expect("value was just set");
+        let comment_range = self.trailing_comment_range(key_range.end);
+        let body = &mut self.section.body.0;
+        body.splice(
+            key_range.end..comment_range.map_or(key_range.end, |range| range.end),
+            comment_events(comment),
+        );
+        previous
+    }
+
     /// Removes the latest value by key and returns it, if it exists.
+    ///
+    /// A comment behind the value is removed along with it, while comments on their own lines are preserved.
     pub fn remove(&mut self, value_name: &str) -> Option<Cow<'event, BStr>> {
         let key = ValueName::from_str_unchecked(value_name);
-        let (key_range, _value_range) = self.key_and_value_range_by(&key)?;
+        let (mut key_range, _value_range) = self.key_and_value_range_by(&key)?;
+        if let Some(comment_range) = self.trailing_comment_range(key_range.end) {
+            key_range.end = comment_range.end;
+        }
         Some(self.remove_internal(key_range, true))
     }
 
@@ -192,6 +296,42 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         self
     }
 
+    /// Sets the exact whitespace to use before and after the `=` between the key and the value of newly
+    /// created key-value pairs, with only whitespace characters being permissible.
+    ///
+    /// The default is derived from the first value in the section, or a single space on each side.
+    /// Set either to `None` to not use any whitespace.
+    ///
+    /// # Panics
+    ///
+    /// If non-whitespace characters are used. This makes the method only suitable for validated
+    /// or known input.
+    pub fn set_separator_whitespace(
+        &mut self,
+        before: Option<Cow<'event, BStr>>,
+        after: Option<Cow<'event, BStr>>,
+    ) -> &mut Self {
+        assert!(
+            [&before, &after]
+                .iter()
+                .all(|ws| ws.as_deref().map_or(true, |ws| ws.iter().all(u8::is_ascii_whitespace))),
+            "input whitespace must only contain whitespace characters."
+        );
+        self.whitespace.pre_sep = before;
+        self.whitespace.post_sep = after;
+        self
+    }
+
+    /// If enabled, the `=` of newly created key-value pairs is aligned with the right-most `=` of the existing values
+    /// in this section by padding the key with spaces, which is useful if these are aligned already.
+    /// Keys that are too long to be aligned are followed by a single space instead, if there is
+    /// [separator whitespace](Self::separator_whitespace()) before the `=`.
+    /// This is _disabled by default_.
+    pub fn set_align_separators(&mut self, on: bool) -> &mut Self {
+        self.align_separators = on;
+        self
+    }
+
     /// Returns the whitespace this section will insert before the
     /// beginning of a key, if any.
     #[must_use]
@@ -217,6 +357,7 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         Self {
             section,
             implicit_newline: true,
+            align_separators: false,
             whitespace,
             newline,
         }
@@ -271,6 +412,16 @@ impl<'a, 'event> SectionMut<'a, 'event> {
         Size(size)
     }
 
+    /// Return the range of the whitespace and comment following the value ending at `value_end`, if there is a comment.
+    fn trailing_comment_range(&self, value_end: usize) -> Option<Range<usize>> {
+        let body = &self.section.body.0;
+        let comment_pos = value_end
+            + body[value_end..]
+                .iter()
+                .position(|e| !matches!(e, Event::Whitespace(_)))?;
+        matches!(body[comment_pos], Event::Comment(_)).then(|| value_end..comment_pos + 1)
+    }
+
     /// Performs the removal, assuming the range is valid.
     fn remove_internal(&mut self, range: Range<usize>, fix_whitespace: bool) -> Cow<'event, BStr> {
         let events = &mut self.section.body.0;
@@ -315,3 +466,22 @@ impl<'event> file::section::Body<'event> {
         &mut self.0
     }
 }
+
+/// Return the events to place `comment` right behind a value.
+fn comment_events<'event>(comment: &BStr) -> [Event<'event>; 2] {
+    [
+        Event::Whitespace(Cow::Borrowed(" ".into())),
+        Event::Comment(parse::Comment {
+            tag: b'#',
+            text: Cow::Owned({
+                let mut c = Vec::with_capacity(comment.len());
+                let mut bytes = comment.iter().peekable();
+                if !bytes.peek().map_or(true, |b| b.is_ascii_whitespace()) {
+                    c.insert(0, b' ');
+                }
+                c.extend(bytes.map(|b| if *b == b'\n' { b' ' } else { *b }));
+                c.into()
+            }),
+        }),
+    ]
+}
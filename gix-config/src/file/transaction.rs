@@ -0,0 +1,188 @@
+use std::{io::Write, path::PathBuf};
+
+use crate::{file::Metadata, File, Source};
+
+/// A transaction to change multiple configuration files at once, like the `Local` and the `Worktree` configuration,
+/// so that either all of them are changed or none of them.
+///
+/// All files are locked when the transaction is [prepared](Transaction::prepare()) and their current content is loaded,
+/// so that they can be edited without interference from other writers which respect `git` lock files.
+/// Changes are then written with [`commit()`](Transaction::commit()), while dropping the transaction or calling
+/// [`rollback()`](Transaction::rollback()) releases all locks without changing any file.
+pub struct Transaction {
+    files: Vec<Staged>,
+}
+
+struct Staged {
+    lock: gix_lock::File,
+    /// The content of the file before the transaction, or `None` if it didn't exist.
+    original: Option<Vec<u8>>,
+    config: File<'static>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod prepare {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Transaction::prepare()`][super::Transaction::prepare()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The configuration file of kind {kind:?} has no path and can't be part of a transaction")]
+        MissingPath { kind: crate::Source },
+        #[error("The configuration file at \"{}\" was added to the transaction more than once", path.display())]
+        Duplicate { path: PathBuf },
+        #[error(transparent)]
+        Lock(#[from] gix_lock::acquire::Error),
+        #[error("The configuration file at \"{}\" could not be read", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        Init(#[from] crate::file::init::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod commit {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Transaction::commit()`][super::Transaction::commit()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The edited configuration file at \"{}\" would not be valid anymore", path.display())]
+        Validate { source: crate::parse::Error, path: PathBuf },
+        #[error("The configuration file at \"{}\" could not be written", path.display())]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error("The configuration file at \"{}\" could not be committed, and previous changes were {} rolled back", path.display(), if *rolled_back { "successfully" } else { "not entirely" })]
+        Commit {
+            source: std::io::Error,
+            path: PathBuf,
+            rolled_back: bool,
+        },
+    }
+}
+
+/// Lifecycle
+impl Transaction {
+    /// Lock the configuration files described by `files`, whose [path](Metadata::path) must be set, and load their
+    /// current content without resolving includes, or start with an empty file if it doesn't exist yet.
+    /// Use `lock_mode` to determine how to deal with files that are already locked.
+    ///
+    /// If a single file can't be locked, all previously acquired locks are released.
+    pub fn prepare(
+        files: impl IntoIterator<Item = Metadata>,
+        lock_mode: gix_lock::acquire::Fail,
+    ) -> Result<Self, prepare::Error> {
+        let mut staged = Vec::<Staged>::new();
+        for meta in files {
+            let path = meta
+                .path
+                .clone()
+                .ok_or(prepare::Error::MissingPath { kind: meta.source })?;
+            if staged.iter().any(|file| file.lock.resource_path() == path) {
+                return Err(prepare::Error::Duplicate { path });
+            }
+            let lock = gix_lock::File::acquire_to_update_resource(&path, lock_mode, None)?;
+            let original = match std::fs::read(&path) {
+                Ok(buf) => Some(buf),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(source) => return Err(prepare::Error::Io { source, path }),
+            };
+            let config = match &original {
+                Some(buf) => File::from_bytes_owned(&mut buf.clone(), meta, Default::default())?,
+                None => File::new(meta),
+            };
+            staged.push(Staged { lock, original, config });
+        }
+        Ok(Transaction { files: staged })
+    }
+
+    /// Write all changed files and commit them, releasing all locks.
+    ///
+    /// Before anything is written, each changed file is validated to still parse after serialization.
+    /// Should committing one of the files fail, the files that were already committed are restored to their original
+    /// content on a best-effort basis.
+    pub fn commit(self) -> Result<(), commit::Error> {
+        let mut to_commit = Vec::with_capacity(self.files.len());
+        for Staged {
+            mut lock,
+            original,
+            config,
+        } in self.files
+        {
+            let path = lock.resource_path();
+            let buf = config.to_bstring();
+            if original.as_deref() == Some(buf.as_slice()) || (original.is_none() && buf.is_empty()) {
+                continue;
+            }
+            if let Err(source) = crate::parse::Events::from_bytes(&buf, None) {
+                return Err(commit::Error::Validate { source, path });
+            }
+            lock.write_all(&buf)
+                .and_then(|_| lock.flush())
+                .map_err(|source| commit::Error::Io { source, path })?;
+            to_commit.push((lock, original));
+        }
+
+        let mut committed = Vec::<(PathBuf, Option<Vec<u8>>)>::new();
+        for (lock, original) in to_commit {
+            match lock.commit() {
+                Ok((path, _)) => committed.push((path, original)),
+                Err(err) => {
+                    let mut rolled_back = true;
+                    for (path, original) in committed {
+                        rolled_back &= restore(path, original).is_ok();
+                    }
+                    return Err(commit::Error::Commit {
+                        path: err.instance.resource_path(),
+                        source: err.error,
+                        rolled_back,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Release all locks without changing any file. This is equivalent to dropping the transaction.
+    pub fn rollback(self) {}
+}
+
+/// Access
+impl Transaction {
+    /// Return all files in the order they were passed to [`prepare()`](Self::prepare()).
+    pub fn files(&self) -> impl Iterator<Item = &File<'static>> + '_ {
+        self.files.iter().map(|file| &file.config)
+    }
+
+    /// Return all files mutably, in the order they were passed to [`prepare()`](Self::prepare()), for editing them.
+    pub fn files_mut(&mut self) -> impl Iterator<Item = &mut File<'static>> + '_ {
+        self.files.iter_mut().map(|file| &mut file.config)
+    }
+
+    /// Return the first file whose metadata indicates `source`, if present.
+    pub fn file(&self, source: Source) -> Option<&File<'static>> {
+        self.files().find(|file| file.meta().source == source)
+    }
+
+    /// Return the first file whose metadata indicates `source` mutably, if present.
+    pub fn file_mut(&mut self, source: Source) -> Option<&mut File<'static>> {
+        self.files_mut().find(|file| file.meta().source == source)
+    }
+}
+
+/// Restore the file at `path` to `original`, or remove it if it didn't exist.
+fn restore(path: PathBuf, original: Option<Vec<u8>>) -> std::io::Result<()> {
+    match original {
+        Some(original) => {
+            let mut lock =
+                gix_lock::File::acquire_to_update_resource(&path, gix_lock::acquire::Fail::Immediately, None)
+                    .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+            lock.write_all(&original)?;
+            lock.commit().map(|_| ()).map_err(|err| err.error)
+        }
+        None => std::fs::remove_file(path),
+    }
+}
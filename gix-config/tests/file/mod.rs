@@ -48,4 +48,5 @@ mod impls;
 mod init;
 mod mutable;
 mod resolve_includes;
+mod transaction;
 mod write;
@@ -61,6 +61,19 @@ mod remove {
         assert_eq!(config.to_string(), "\n        [a]\n");
         Ok(())
     }
+
+    #[test]
+    fn trailing_comments_are_removed_with_the_value() -> crate::Result {
+        let mut config: gix_config::File = "[a]\n\t# about a\n\ta = 1 # one\n\tb = 2\n".parse()?;
+        let mut section = config.section_mut("a", None)?;
+        assert_eq!(section.remove("a").as_deref(), Some("1".into()));
+        assert_eq!(
+            config.to_string(),
+            "[a]\n\t# about a\n\tb = 2\n",
+            "comments on their own line are kept"
+        );
+        Ok(())
+    }
 }
 
 mod pop {
@@ -118,6 +131,110 @@ mod set {
     }
 }
 
+mod set_with_comment {
+    #[test]
+    fn comments_are_replaced_or_attached() -> crate::Result {
+        let mut config: gix_config::File = "[a]\n\ta = 1 # one\n\tb = 2\n".parse()?;
+        let mut section = config.section_mut("a", None)?;
+        assert_eq!(
+            section.set_with_comment("a".try_into()?, "10".into(), "ten").as_deref(),
+            Some("1".into())
+        );
+        assert_eq!(
+            section
+                .set_with_comment("b".try_into()?, "20".into(), "twenty")
+                .as_deref(),
+            Some("2".into())
+        );
+        assert_eq!(section.set_with_comment("c".try_into()?, "3".into(), "new"), None);
+        assert_eq!(
+            config.to_string(),
+            "[a]\n\ta = 10 # ten\n\tb = 20 # twenty\n\tc = 3 # new\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_keeps_comments() -> crate::Result {
+        let mut config: gix_config::File = "[a]\n\ta = 1 # one\n".parse()?;
+        config.section_mut("a", None)?.set("a".try_into()?, "2".into());
+        assert_eq!(config.to_string(), "[a]\n\ta = 2 # one\n");
+        Ok(())
+    }
+}
+
+mod push_after {
+    #[test]
+    fn values_are_placed_after_the_last_anchor() -> crate::Result {
+        let mut config: gix_config::File =
+            "[remote \"origin\"]\n\turl = https://example.com\n\tfetch = +refs/heads/*:refs/remotes/origin/*\n\tpushurl = x\n"
+                .parse()?;
+        let mut section = config.section_mut("remote", Some("origin".into()))?;
+        section.push_after("fetch", "fetch".try_into()?, Some("+refs/tags/*:refs/tags/*".into()));
+        section.push_after("missing", "tagOpt".try_into()?, Some("--no-tags".into()));
+        assert_eq!(
+            config.to_string(),
+            "[remote \"origin\"]\n\turl = https://example.com\n\tfetch = +refs/heads/*:refs/remotes/origin/*\n\tfetch = +refs/tags/*:refs/tags/*\n\tpushurl = x\n\ttagOpt = --no-tags\n",
+            "without anchor, values are pushed to the end"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn anchor_on_last_line_without_newline() -> crate::Result {
+        let mut config: gix_config::File = "[a]\n\tb = 1 # comment".parse()?;
+        config
+            .section_mut("a", None)?
+            .push_after("b", "b".try_into()?, Some("2".into()));
+        assert_eq!(config.to_string(), "[a]\n\tb = 1 # comment\n\tb = 2\n");
+        Ok(())
+    }
+}
+
+mod set_separator_whitespace {
+    use std::borrow::Cow;
+
+    use crate::file::cow_str;
+
+    #[test]
+    fn affects_new_values() -> crate::Result {
+        let mut config = gix_config::File::default();
+        let mut section = config.new_section("a", None)?;
+        section.set_separator_whitespace(None, Some(Cow::Borrowed("\t".into())));
+        section.push("b".try_into()?, Some("1".into()));
+        section.set_separator_whitespace(None, None);
+        section.push("c".try_into()?, Some("2".into()));
+        let nl = section.newline().to_owned();
+        assert_eq!(config.to_string(), format!("[a]{nl}\tb=\t1{nl}\tc=2{nl}"));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_if_non_whitespace_is_used() {
+        let mut config = gix_config::File::default();
+        let mut section = config.new_section("core", None).unwrap();
+        section.set_separator_whitespace(None, cow_str("=").into());
+    }
+}
+
+mod set_align_separators {
+    #[test]
+    fn new_values_are_aligned_with_the_longest_key() -> crate::Result {
+        let mut config: gix_config::File = "[user]\n\tname       = a\n\tsigningkey = b\n".parse()?;
+        let mut section = config.section_mut("user", None)?;
+        section.set_align_separators(true);
+        section.push("email".try_into()?, Some("a@example.com".into()));
+        section.push("useConfigOnly".try_into()?, Some("true".into()));
+        assert_eq!(
+            config.to_string(),
+            "[user]\n\tname       = a\n\tsigningkey = b\n\temail      = a@example.com\n\tuseConfigOnly = true\n",
+            "keys too long to be aligned use the usual separator whitespace"
+        );
+        Ok(())
+    }
+}
+
 mod push {
     use gix_config::parse::section::ValueName;
 
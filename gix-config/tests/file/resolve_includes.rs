@@ -36,3 +36,45 @@ fn missing_includes_are_ignored_by_default() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn include_paths_are_listed_whether_they_exist_or_not() -> crate::Result {
+    let input = r#"
+        [include]
+            path = /etc/absolute/missing.config
+            path = relative.config
+            path = %(prefix)/no-install.config
+        [includeIf "onbranch:main"]
+            path = on-main.config
+        [includeIf "onbranch:other"]
+            path = on-other.config
+    "#;
+    let config = gix_config::File::from_bytes_no_includes(
+        input.as_bytes(),
+        file::Metadata::from(gix_config::Source::Local).at("/repo/.git/config"),
+        Default::default(),
+    )?;
+
+    let branch_name: gix_ref::FullName = "refs/heads/main".try_into()?;
+    let options = file::includes::Options::follow(
+        Default::default(),
+        file::includes::conditional::Context {
+            branch_name: Some(branch_name.as_ref()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        config.include_paths(options)?,
+        [
+            std::path::PathBuf::from("/etc/absolute/missing.config"),
+            "/repo/.git/relative.config".into(),
+            "/repo/.git/on-main.config".into(),
+        ],
+        "paths that can't be interpolated and unmatched conditions are skipped"
+    );
+    assert!(
+        config.include_paths(options.strict()).is_err(),
+        "strict mode fails if something couldn't be interpolated"
+    );
+    Ok(())
+}
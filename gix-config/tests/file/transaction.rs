@@ -0,0 +1,100 @@
+use gix_config::{file::Metadata, file::Transaction, Source};
+use gix_lock::acquire::Fail;
+
+fn metadata(dir: &std::path::Path) -> [Metadata; 2] {
+    [
+        Metadata::from(Source::Local).at(dir.join("config")),
+        Metadata::from(Source::Worktree).at(dir.join("config.worktree")),
+    ]
+}
+
+#[test]
+fn commit_changes_all_files() -> crate::Result {
+    let dir = gix_testtools::tempfile::tempdir()?;
+    std::fs::write(dir.path().join("config"), "[core]\n\tbare = false\n")?;
+
+    let mut tx = Transaction::prepare(metadata(dir.path()), Fail::Immediately)?;
+    assert_eq!(
+        tx.file(Source::Local).expect("present").boolean("core.bare"),
+        Some(Ok(false)),
+        "existing files are loaded"
+    );
+    assert_eq!(
+        tx.file(Source::Worktree).expect("present").sections().count(),
+        0,
+        "non-existing files start out empty"
+    );
+    tx.file_mut(Source::Local)
+        .expect("present")
+        .set_raw_value(&"branch.main.remote", "origin")?;
+    tx.file_mut(Source::Worktree)
+        .expect("present")
+        .set_raw_value(&"remote.origin.url", "https://example.com")?;
+    assert!(
+        dir.path().join("config.lock").is_file() && dir.path().join("config.worktree.lock").is_file(),
+        "all files are locked"
+    );
+    tx.commit()?;
+
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("config"))?,
+        "[core]\n\tbare = false\n[branch \"main\"]\n\tremote = origin\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("config.worktree"))?,
+        "[remote \"origin\"]\n\turl = https://example.com\n"
+    );
+    assert_no_locks(dir.path())?;
+    Ok(())
+}
+
+#[test]
+fn rollback_leaves_files_untouched() -> crate::Result {
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let original = "[core]\n\tbare = false\n";
+    std::fs::write(dir.path().join("config"), original)?;
+
+    let mut tx = Transaction::prepare(metadata(dir.path()), Fail::Immediately)?;
+    for file in tx.files_mut() {
+        file.set_raw_value(&"core.bare", "true")?;
+    }
+    tx.rollback();
+
+    assert_eq!(std::fs::read_to_string(dir.path().join("config"))?, original);
+    assert!(!dir.path().join("config.worktree").exists(), "nothing was created");
+    assert_no_locks(dir.path())?;
+    Ok(())
+}
+
+#[test]
+fn a_single_locked_file_fails_the_entire_transaction() -> crate::Result {
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let _lock =
+        gix_lock::Marker::acquire_to_hold_resource(dir.path().join("config.worktree"), Fail::Immediately, None)?;
+
+    assert!(matches!(
+        Transaction::prepare(metadata(dir.path()), Fail::Immediately),
+        Err(gix_config::file::transaction::prepare::Error::Lock(_))
+    ));
+    assert!(
+        !dir.path().join("config.lock").exists(),
+        "locks that were already acquired are released"
+    );
+    Ok(())
+}
+
+#[test]
+fn files_need_a_path() {
+    assert!(matches!(
+        Transaction::prepare(Some(Metadata::from(Source::Local)), Fail::Immediately),
+        Err(gix_config::file::transaction::prepare::Error::MissingPath { kind: Source::Local })
+    ));
+}
+
+fn assert_no_locks(dir: &std::path::Path) -> crate::Result {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        assert_ne!(path.extension().and_then(|ext| ext.to_str()), Some("lock"), "{path:?}");
+    }
+    Ok(())
+}
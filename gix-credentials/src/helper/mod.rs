@@ -132,11 +132,28 @@ impl Action {
 }
 
 /// A handle to [store][NextAction::store()] or [erase][NextAction::erase()] the outcome of the initial action.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// Note that its `Debug` implementation redacts the username and password.
+#[derive(Clone, Eq, PartialEq)]
 pub struct NextAction {
     previous_output: BString,
 }
 
+impl std::fmt::Debug for NextAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("NextAction");
+        match Context::try_from(self) {
+            Ok(mut ctx) => {
+                for secret in [&mut ctx.username, &mut ctx.password].into_iter().flatten() {
+                    *secret = "<redacted>".into();
+                }
+                s.field("previous_output", &ctx).finish()
+            }
+            Err(_) => s.finish_non_exhaustive(),
+        }
+    }
+}
+
 impl TryFrom<&NextAction> for Context {
     type Error = protocol::context::decode::Error;
 
@@ -16,6 +16,8 @@ pub struct Program {
     pub kind: program::Kind,
     /// If true, stderr is enabled, which is the default.
     pub stderr: bool,
+    /// The environment profile to use when spawning the program, which removes variables that select a repository by default.
+    pub environment: gix_command::Environment,
     /// `Some(…)` if the process is running.
     child: Option<std::process::Child>,
 }
@@ -34,6 +34,7 @@ impl Program {
             kind,
             child: None,
             stderr: true,
+            environment: gix_command::Environment::GitClean,
         }
     }
 
@@ -61,6 +62,7 @@ impl Program {
                 kind,
                 child: None,
                 stderr: true,
+                environment: gix_command::Environment::GitClean,
             }
         }
         from_custom_definition_inner(input.into())
@@ -69,12 +71,10 @@ impl Program {
     /// Convert the program into the respective command, suitable to invoke `action`.
     pub fn to_command(&self, action: &helper::Action) -> std::process::Command {
         let git_program = gix_path::env::exe_invocation();
-        let mut cmd = match &self.kind {
-            Kind::Builtin => {
-                let mut cmd = Command::new(git_program);
-                cmd.arg("credential").arg(action.as_arg(false));
-                cmd
-            }
+        let prepare = match &self.kind {
+            Kind::Builtin => gix_command::prepare(git_program)
+                .arg("credential")
+                .arg(action.as_arg(false)),
             Kind::ExternalName { name_and_args } => {
                 let mut args = name_and_args.clone();
                 args.insert_str(0, "credential-");
@@ -83,16 +83,15 @@ impl Program {
                 gix_command::prepare(gix_path::from_bstr(args.as_ref()).into_owned())
                     .arg(action.as_arg(true))
                     .with_shell_allow_argument_splitting()
-                    .into()
             }
             Kind::ExternalShellScript(for_shell)
             | Kind::ExternalPath {
                 path_and_args: for_shell,
             } => gix_command::prepare(gix_path::from_bstr(for_shell.as_bstr()).as_ref())
                 .with_shell()
-                .arg(action.as_arg(true))
-                .into(),
+                .arg(action.as_arg(true)),
         };
+        let mut cmd: Command = prepare.with_environment(self.environment.clone()).into();
         cmd.stdin(Stdio::piped())
             .stdout(if action.expects_output() {
                 Stdio::piped()
@@ -111,6 +110,12 @@ impl Program {
         self.stderr = false;
         self
     }
+
+    /// Spawn the program with the given `environment` profile, instead of [`GitClean`](gix_command::Environment::GitClean).
+    pub fn with_environment(mut self, environment: gix_command::Environment) -> Self {
+        self.environment = environment;
+        self
+    }
 }
 
 impl Program {
@@ -11,6 +11,17 @@ pub struct Outcome {
     pub next: helper::NextAction,
 }
 
+impl Outcome {
+    /// Return the action to erase the [`identity`](Self::identity) from all credential helpers after it wasn't accepted by
+    /// the remote, so it's not provided again when credentials are obtained next time.
+    ///
+    /// The action contains the entire context the identity was obtained with, including the username and password that were
+    /// tried, so helpers can erase exactly the credentials that failed.
+    pub fn reject(self) -> helper::Action {
+        self.next.erase()
+    }
+}
+
 /// The Result type used in credentials top-level functions to obtain a complete identity.
 pub type Result = std::result::Result<Option<Outcome>, Error>;
 
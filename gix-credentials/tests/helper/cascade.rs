@@ -136,6 +136,28 @@ mod invoke {
         assert_eq!(actual.identity, identity("user", ""));
     }
 
+    #[test]
+    fn rejected_credentials_are_erased_with_the_context_they_were_obtained_with() {
+        let outcome = invoke_cascade(["username", "password"], action_get())
+            .unwrap()
+            .expect("credentials");
+        let action = outcome.reject();
+        assert_eq!(action.as_arg(true), "erase");
+        let ctx = Context::from_bytes(action.payload().expect("erase has a payload")).unwrap();
+        assert_eq!(
+            ctx.username.as_deref(),
+            Some("user"),
+            "the username that was tried is passed so helpers can erase exactly the failing credentials"
+        );
+        assert_eq!(ctx.password.as_deref(), Some("pass"));
+        assert_eq!(ctx.path.as_ref().map(|p| p.as_bstr()), Some("does/not/matter".into()));
+
+        assert!(
+            invoke_cascade(["username", "password"], action).unwrap().is_none(),
+            "erasing has no outcome"
+        );
+    }
+
     fn action_get() -> Action {
         Action::get_for_url("does/not/matter")
     }
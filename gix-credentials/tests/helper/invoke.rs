@@ -17,6 +17,11 @@ fn get() {
             password: "pass".into()
         }
     );
+    let debug = format!("{:?}", outcome.next);
+    assert!(
+        !debug.contains("\"user\"") && !debug.contains("\"pass\""),
+        "credentials are redacted: {debug}"
+    );
     assert_eq!(
         outcome.next.store().payload().unwrap(),
         "username=user\npassword=pass\nquit=1\n"
@@ -1,3 +1,4 @@
+use gix_command::Environment;
 use gix_credentials::{helper, program::Kind, Program};
 
 static GIT: once_cell::sync::Lazy<&'static str> =
@@ -10,7 +11,7 @@ const SH: &str = "/bin/sh";
 
 #[test]
 fn empty() {
-    let prog = Program::from_custom_definition("");
+    let prog = Program::from_custom_definition("").with_environment(Environment::InheritAll);
     let git = *GIT;
     assert!(matches!(&prog.kind, Kind::ExternalName { name_and_args } if name_and_args == ""));
     assert_eq!(
@@ -22,7 +23,7 @@ fn empty() {
 
 #[test]
 fn simple_script_in_path() {
-    let prog = Program::from_custom_definition("!exe");
+    let prog = Program::from_custom_definition("!exe").with_environment(Environment::InheritAll);
     assert!(matches!(&prog.kind, Kind::ExternalShellScript(script) if script == "exe"));
     assert_eq!(
         format!("{:?}", prog.to_command(&helper::Action::Store("egal".into()))),
@@ -34,7 +35,7 @@ fn simple_script_in_path() {
 #[test]
 fn name_with_args() {
     let input = "name --arg --bar=\"a b\"";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     let git = *GIT;
     assert!(matches!(&prog.kind, Kind::ExternalName{name_and_args} if name_and_args == input));
     assert_eq!(
@@ -46,7 +47,7 @@ fn name_with_args() {
 #[test]
 fn name_with_special_args() {
     let input = "name --arg --bar=~/folder/in/home";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     let git = *GIT;
     assert!(matches!(&prog.kind, Kind::ExternalName{name_and_args} if name_and_args == input));
     assert_eq!(
@@ -58,7 +59,7 @@ fn name_with_special_args() {
 #[test]
 fn name() {
     let input = "name";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     let git = *GIT;
     assert!(matches!(&prog.kind, Kind::ExternalName{name_and_args} if name_and_args == input));
     assert_eq!(
@@ -71,7 +72,7 @@ fn name() {
 #[test]
 fn path_with_args_that_definitely_need_shell() {
     let input = "/abs/name --arg --bar=\"a b\"";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     assert!(matches!(&prog.kind, Kind::ExternalPath{path_and_args} if path_and_args == input));
     assert_eq!(
         format!("{:?}", prog.to_command(&helper::Action::Store("egal".into()))),
@@ -86,7 +87,7 @@ fn path_with_args_that_definitely_need_shell() {
 #[test]
 fn path_without_args() {
     let input = "/abs/name";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     assert!(matches!(&prog.kind, Kind::ExternalPath{path_and_args} if path_and_args == input));
     assert_eq!(
         format!("{:?}", prog.to_command(&helper::Action::Store("egal".into()))),
@@ -98,7 +99,7 @@ fn path_without_args() {
 #[test]
 fn path_with_simple_args() {
     let input = "/abs/name a b";
-    let prog = Program::from_custom_definition(input);
+    let prog = Program::from_custom_definition(input).with_environment(Environment::InheritAll);
     assert!(matches!(&prog.kind, Kind::ExternalPath{path_and_args} if path_and_args == input));
     assert_eq!(
         format!("{:?}", prog.to_command(&helper::Action::Store("egal".into()))),
@@ -110,3 +111,19 @@ fn path_with_simple_args() {
         "a shell is used as there are arguments, and it's generally more flexible, but on windows we split ourselves"
     );
 }
+
+#[test]
+fn repository_variables_are_removed_by_default() {
+    let prog = Program::from_custom_definition("name");
+    assert_eq!(prog.environment, Environment::GitClean);
+    let cmd = prog.to_command(&helper::Action::Store("egal".into()));
+    let removed: Vec<_> = cmd
+        .get_envs()
+        .filter(|(_, value)| value.is_none())
+        .map(|(name, _)| name)
+        .collect();
+    assert!(
+        removed.iter().any(|name| *name == "GIT_DIR") && removed.iter().any(|name| *name == "GIT_INDEX_FILE"),
+        "helpers run like in `git` and must not see the repository of the current process"
+    );
+}
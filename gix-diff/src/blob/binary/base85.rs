@@ -0,0 +1,65 @@
+//! The base85 encoding as used by `git` for binary patches, which is different from the one specified in RFC 1924.
+
+const ALPHABET: &[u8; 85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Append the encoding of `data` to `out`, producing 5 characters for every 4 bytes of input.
+///
+/// The last group is padded with zeroes, so the amount of encoded bytes has to be stored separately.
+pub(crate) fn encode(data: &[u8], out: &mut Vec<u8>) {
+    for chunk in data.chunks(4) {
+        let mut acc = chunk
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(4)
+            .fold(0_u32, |acc, byte| (acc << 8) | u32::from(*byte));
+        let mut group = [0_u8; 5];
+        for char in group.iter_mut().rev() {
+            *char = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&group);
+    }
+}
+
+/// Append `len` bytes decoded from `encoded` to `out`, or return `None` if `encoded` isn't valid or too short.
+pub(crate) fn decode(encoded: &[u8], len: usize, out: &mut Vec<u8>) -> Option<()> {
+    if encoded.len() != (len + 3) / 4 * 5 {
+        return None;
+    }
+    let mut remaining = len;
+    for group in encoded.chunks(5) {
+        let mut acc = 0_u32;
+        for char in group {
+            let value = ALPHABET.iter().position(|c| c == char)? as u32;
+            acc = acc.checked_mul(85)?.checked_add(value)?;
+        }
+        let bytes = acc.to_be_bytes();
+        let take = remaining.min(4);
+        out.extend_from_slice(&bytes[..take]);
+        remaining -= take;
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn round_trip() {
+        for input in [&b""[..], b"a", b"ab", b"abc", b"abcd", b"abcde", &[0xff; 9], &[0; 7]] {
+            let mut encoded = Vec::new();
+            super::encode(input, &mut encoded);
+            assert_eq!(encoded.len(), (input.len() + 3) / 4 * 5);
+            let mut decoded = Vec::new();
+            super::decode(&encoded, input.len(), &mut decoded).expect("valid");
+            assert_eq!(decoded, input);
+        }
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        let mut out = Vec::new();
+        assert!(super::decode(b"0000", 1, &mut out).is_none(), "too short");
+        assert!(super::decode(b"0000\"", 1, &mut out).is_none(), "invalid character");
+        assert!(super::decode(b"|||||", 4, &mut out).is_none(), "overflow");
+    }
+}
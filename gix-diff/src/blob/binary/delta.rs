@@ -0,0 +1,182 @@
+//! Create and apply deltas in the format `git` uses in packs and binary patches.
+use std::collections::HashMap;
+
+/// The size of the blocks of the source that are indexed to find matches.
+const BLOCK_SIZE: usize = 16;
+/// The maximum amount of bytes a single copy instruction can copy, for compatibility with older versions of `git`.
+const MAX_COPY_SIZE: usize = 0x10000;
+/// The maximum amount of bytes that can be inserted with a single instruction.
+const MAX_INSERT_SIZE: usize = 0x7f;
+
+/// The error returned by [`apply()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The delta header is truncated or malformed")]
+    Header,
+    #[error("The delta expects a base of {expected} bytes, but it has {actual} bytes")]
+    BaseSize { expected: u64, actual: u64 },
+    #[error("The delta contains an instruction that is truncated, invalid, or exceeds the base")]
+    Instruction,
+    #[error("The delta produced {actual} bytes, but should have produced {expected} bytes")]
+    ResultSize { expected: u64, actual: u64 },
+}
+
+/// Append a delta to `out` which transforms `base` into `target` when [applied](apply()).
+pub fn create(base: &[u8], target: &[u8], out: &mut Vec<u8>) {
+    encode_size(base.len() as u64, out);
+    encode_size(target.len() as u64, out);
+
+    let mut index = HashMap::<&[u8], usize>::new();
+    for (block_index, block) in base.chunks_exact(BLOCK_SIZE).enumerate() {
+        index.entry(block).or_insert(block_index * BLOCK_SIZE);
+    }
+
+    let mut insert_start = 0;
+    let mut pos = 0;
+    while pos + BLOCK_SIZE <= target.len() {
+        let Some(&base_start) = index.get(&target[pos..][..BLOCK_SIZE]) else {
+            pos += 1;
+            continue;
+        };
+        let mut len = BLOCK_SIZE
+            + base[base_start + BLOCK_SIZE..]
+                .iter()
+                .zip(&target[pos + BLOCK_SIZE..])
+                .take_while(|(a, b)| a == b)
+                .count();
+        // Also match backwards into what would otherwise have to be inserted.
+        let backwards = base[..base_start]
+            .iter()
+            .rev()
+            .zip(target[insert_start..pos].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let (base_start, target_start) = (base_start - backwards, pos - backwards);
+        len += backwards;
+
+        push_insert(&target[insert_start..target_start], out);
+        push_copy(base_start, len, out);
+        pos = target_start + len;
+        insert_start = pos;
+    }
+    push_insert(&target[insert_start..], out);
+}
+
+/// Append the result of applying `delta` to `base` to `out`.
+pub fn apply(base: &[u8], mut delta: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+    let base_size = decode_size(&mut delta).ok_or(Error::Header)?;
+    if base_size != base.len() as u64 {
+        return Err(Error::BaseSize {
+            expected: base_size,
+            actual: base.len() as u64,
+        });
+    }
+    let result_size = decode_size(&mut delta).ok_or(Error::Header)?;
+    let start = out.len();
+    while let Some((&cmd, rest)) = delta.split_first() {
+        delta = rest;
+        if cmd & 0x80 != 0 {
+            let mut offset = 0_usize;
+            for (bit, shift) in [(0x01, 0), (0x02, 8), (0x04, 16), (0x08, 24)] {
+                if cmd & bit != 0 {
+                    let (&byte, rest) = delta.split_first().ok_or(Error::Instruction)?;
+                    offset |= usize::from(byte) << shift;
+                    delta = rest;
+                }
+            }
+            let mut size = 0_usize;
+            for (bit, shift) in [(0x10, 0), (0x20, 8), (0x40, 16)] {
+                if cmd & bit != 0 {
+                    let (&byte, rest) = delta.split_first().ok_or(Error::Instruction)?;
+                    size |= usize::from(byte) << shift;
+                    delta = rest;
+                }
+            }
+            if size == 0 {
+                size = MAX_COPY_SIZE;
+            }
+            let data = offset
+                .checked_add(size)
+                .and_then(|end| base.get(offset..end))
+                .ok_or(Error::Instruction)?;
+            out.extend_from_slice(data);
+        } else if cmd != 0 {
+            let size = usize::from(cmd);
+            let data = delta.get(..size).ok_or(Error::Instruction)?;
+            out.extend_from_slice(data);
+            delta = &delta[size..];
+        } else {
+            return Err(Error::Instruction);
+        }
+    }
+    let actual = (out.len() - start) as u64;
+    if actual != result_size {
+        return Err(Error::ResultSize {
+            expected: result_size,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn push_insert(mut data: &[u8], out: &mut Vec<u8>) {
+    while !data.is_empty() {
+        let (chunk, rest) = data.split_at(data.len().min(MAX_INSERT_SIZE));
+        out.push(chunk.len() as u8);
+        out.extend_from_slice(chunk);
+        data = rest;
+    }
+}
+
+fn push_copy(mut offset: usize, mut len: usize, out: &mut Vec<u8>) {
+    while len != 0 {
+        let size = len.min(MAX_COPY_SIZE);
+        let cmd_pos = out.len();
+        out.push(0x80);
+        for (bit, shift) in [(0x01, 0), (0x02, 8), (0x04, 16), (0x08, 24)] {
+            let byte = (offset >> shift) as u8;
+            if byte != 0 {
+                out[cmd_pos] |= bit;
+                out.push(byte);
+            }
+        }
+        // A size of 0x10000 is encoded as 0.
+        let encoded_size = size & 0xffff;
+        for (bit, shift) in [(0x10, 0), (0x20, 8)] {
+            let byte = (encoded_size >> shift) as u8;
+            if byte != 0 {
+                out[cmd_pos] |= bit;
+                out.push(byte);
+            }
+        }
+        offset += size;
+        len -= size;
+    }
+}
+
+fn encode_size(mut size: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (size & 0x7f) as u8;
+        size >>= 7;
+        if size == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn decode_size(data: &mut &[u8]) -> Option<u64> {
+    let mut size = 0_u64;
+    let mut shift = 0;
+    loop {
+        let (&byte, rest) = data.split_first()?;
+        *data = rest;
+        size |= u64::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(size);
+        }
+        shift += 7;
+    }
+}
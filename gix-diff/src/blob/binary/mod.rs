@@ -0,0 +1,277 @@
+//! Create, write, parse and apply binary patches in the format `git diff --binary` produces, and `git apply` understands.
+//!
+//! Such a patch consists of a forward hunk to turn the old version into the new version, and a reverse hunk to go
+//! back from the new version to the old one. Each hunk is either the zlib-compressed new content, or the zlib-compressed
+//! delta to produce it from the other version, whichever is smaller.
+use bstr::ByteSlice;
+
+mod base85;
+pub mod delta;
+
+/// The header line introducing a binary patch.
+pub const HEADER: &[u8] = b"GIT binary patch\n";
+
+/// The maximum amount of bytes encoded in a single line.
+const MAX_BYTES_PER_LINE: usize = 52;
+
+/// The way data is stored in a [`Hunk`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum HunkKind {
+    /// The hunk contains the whole resulting content.
+    Literal,
+    /// The hunk contains a [delta](delta::apply()) to apply to the original content.
+    Delta,
+}
+
+/// One direction of a binary patch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Hunk {
+    /// How to interpret `data`.
+    pub kind: HunkKind,
+    /// The decompressed data of the hunk, i.e. the resulting content or the delta instructions to produce it.
+    pub data: Vec<u8>,
+}
+
+/// A binary patch with hunks to produce the new version from the old one, and vice versa.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Patch {
+    /// The hunk to turn the old version into the new version.
+    pub forward: Hunk,
+    /// The hunk to turn the new version into the old version.
+    ///
+    /// It's `None` only for parsed patches that don't contain it, as `git` accepts those as well.
+    pub reverse: Option<Hunk>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse {
+    /// The error returned by [`Patch::from_bytes()`](super::Patch::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Binary patches must start with 'GIT binary patch'")]
+        MissingHeader,
+        #[error("Expected a hunk header like 'literal <size>' or 'delta <size>', got {line:?}")]
+        HunkHeader { line: bstr::BString },
+        #[error("Line {line:?} of a binary hunk is malformed")]
+        Line { line: bstr::BString },
+        #[error("The binary hunk isn't terminated by an empty line")]
+        Unterminated,
+        #[error("Could not decompress the data of a binary hunk")]
+        Inflate(#[from] gix_features::zlib::inflate::Error),
+        #[error("The hunk data decompressed to {actual} bytes, but {expected} bytes were expected")]
+        Size { expected: u64, actual: u64 },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    /// The error returned by [`Patch::apply()`](super::Patch::apply()) and [`Patch::apply_reverse()`](super::Patch::apply_reverse()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Delta(#[from] super::delta::Error),
+        #[error("The patch has no reverse hunk")]
+        MissingReverseHunk,
+    }
+}
+
+/// Lifecycle
+impl Patch {
+    /// Create a patch to turn `old` into `new` and back.
+    pub fn new(old: &[u8], new: &[u8]) -> Self {
+        Patch {
+            forward: Hunk::new(old, new),
+            reverse: Some(Hunk::new(new, old)),
+        }
+    }
+
+    /// Parse a patch from `input`, which is expected to start with the [`HEADER`] line, and return it along with
+    /// the amount of bytes consumed from `input`.
+    pub fn from_bytes(input: &[u8]) -> Result<(Self, usize), parse::Error> {
+        let mut lines = Lines { input, pos: 0 };
+        if lines.next() != Some(&HEADER[..HEADER.len() - 1]) {
+            return Err(parse::Error::MissingHeader);
+        }
+        let forward = Hunk::parse(&mut lines)?.ok_or_else(|| parse::Error::HunkHeader {
+            line: lines.peek().unwrap_or_default().into(),
+        })?;
+        let reverse = Hunk::parse(&mut lines)?;
+        Ok((Patch { forward, reverse }, lines.pos))
+    }
+}
+
+/// Access
+impl Patch {
+    /// Write this patch, including the [`HEADER`], to `out`.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        out.write_all(HEADER)?;
+        self.forward.write_to(out)?;
+        if let Some(reverse) = &self.reverse {
+            reverse.write_to(out)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the forward hunk to `old` and return the new version.
+    pub fn apply(&self, old: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        self.forward.apply(old)
+    }
+
+    /// Apply the reverse hunk to `new` and return the old version.
+    pub fn apply_reverse(&self, new: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        self.reverse
+            .as_ref()
+            .ok_or(apply::Error::MissingReverseHunk)?
+            .apply(new)
+    }
+}
+
+impl Hunk {
+    /// Create a hunk which turns `base` into `target`, using a delta if it compresses better than `target` itself.
+    pub fn new(base: &[u8], target: &[u8]) -> Self {
+        let literal = Hunk {
+            kind: HunkKind::Literal,
+            data: target.to_owned(),
+        };
+        if base.is_empty() || target.is_empty() {
+            return literal;
+        }
+        let mut data = Vec::new();
+        delta::create(base, target, &mut data);
+        let delta = Hunk {
+            kind: HunkKind::Delta,
+            data,
+        };
+        if delta.data.len() < literal.data.len() && compress(&delta.data).len() < compress(&literal.data).len() {
+            delta
+        } else {
+            literal
+        }
+    }
+
+    /// Apply this hunk to `base` and return the result.
+    pub fn apply(&self, base: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        Ok(match self.kind {
+            HunkKind::Literal => self.data.clone(),
+            HunkKind::Delta => {
+                let mut out = Vec::new();
+                delta::apply(base, &self.data, &mut out)?;
+                out
+            }
+        })
+    }
+
+    /// Write this hunk to `out`, including the empty line that terminates it.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let kind = match self.kind {
+            HunkKind::Literal => "literal",
+            HunkKind::Delta => "delta",
+        };
+        writeln!(out, "{kind} {}", self.data.len())?;
+        let compressed = compress(&self.data);
+        let mut line = Vec::with_capacity(1 + MAX_BYTES_PER_LINE / 4 * 5 + 1);
+        for chunk in compressed.chunks(MAX_BYTES_PER_LINE) {
+            line.clear();
+            let len = chunk.len() as u8;
+            line.push(if len <= 26 { b'A' + len - 1 } else { b'a' + len - 27 });
+            base85::encode(chunk, &mut line);
+            line.push(b'\n');
+            out.write_all(&line)?;
+        }
+        out.write_all(b"\n")
+    }
+
+    /// Parse a hunk from `lines`, or return `None` if there is no hunk header.
+    fn parse(lines: &mut Lines<'_>) -> Result<Option<Self>, parse::Error> {
+        let Some(header) = lines.peek() else {
+            return Ok(None);
+        };
+        let (kind, size) = if let Some(size) = header.strip_prefix(b"literal ") {
+            (HunkKind::Literal, size)
+        } else if let Some(size) = header.strip_prefix(b"delta ") {
+            (HunkKind::Delta, size)
+        } else {
+            return Ok(None);
+        };
+        let size: u64 = size
+            .to_str()
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(|| parse::Error::HunkHeader { line: header.into() })?;
+        lines.next();
+
+        let mut compressed = Vec::new();
+        loop {
+            let line = lines.next().ok_or(parse::Error::Unterminated)?;
+            let Some((&len, encoded)) = line.split_first() else {
+                break;
+            };
+            let len = match len {
+                b'A'..=b'Z' => len - b'A' + 1,
+                b'a'..=b'z' => len - b'a' + 27,
+                _ => return Err(parse::Error::Line { line: line.into() }),
+            };
+            base85::decode(encoded, len as usize, &mut compressed)
+                .ok_or_else(|| parse::Error::Line { line: line.into() })?;
+        }
+
+        // Deflate can't compress better than about 1032:1, so don't trust sizes that are larger than that
+        // as they are likely malicious and would make us allocate up-front.
+        let max_size = (compressed.len() as u64).saturating_mul(1032);
+        let mut data = vec![
+            0;
+            size.min(max_size).try_into().map_err(|_| parse::Error::Size {
+                expected: size,
+                actual: 0
+            })?
+        ];
+        let mut inflate = gix_features::zlib::Inflate::default();
+        let (status, _consumed_in, consumed_out) = inflate.once(&compressed, &mut data)?;
+        if status != gix_features::zlib::Status::StreamEnd || consumed_out as u64 != size {
+            return Err(parse::Error::Size {
+                expected: size,
+                actual: consumed_out as u64,
+            });
+        }
+        Ok(Some(Hunk { kind, data }))
+    }
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut out = gix_features::zlib::stream::deflate::Write::with_compression_level(Vec::new(), 9);
+    out.write_all(data)
+        .and_then(|_| out.flush())
+        .expect("writing to memory never fails");
+    out.into_inner()
+}
+
+/// An iterator over lines without their line feed, which keeps track of the amount of consumed bytes.
+struct Lines<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn peek(&self) -> Option<&'a [u8]> {
+        let rest = &self.input[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        Some(rest.find_byte(b'\n').map_or(rest, |end| &rest[..end]))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.peek()?;
+        self.pos = (self.pos + line.len() + 1).min(self.input.len());
+        Some(line)
+    }
+}
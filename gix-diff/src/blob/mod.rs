@@ -5,6 +5,9 @@ use std::{collections::HashMap, path::PathBuf};
 use bstr::BString;
 pub use imara_diff::*;
 
+pub mod binary;
+pub mod moved;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod pipeline;
@@ -0,0 +1,295 @@
+//! Detect lines that were moved within a patch, just like `git diff --color-moved` does, to be able to display them
+//! differently from lines that were actually added or removed.
+//!
+//! Lines of a patch are collected in display order, for instance with [`Collect`], and then [analyzed](detect()) together
+//! to obtain a [`Moved`] annotation for each line that was moved. Note that lines may be moved between files as well,
+//! which is why lines of multiple files can be analyzed at once.
+use std::{collections::HashMap, ops::Range};
+
+use bstr::{BStr, ByteSlice};
+use imara_diff::intern::InternedInput;
+
+/// Blocks of moved lines with less than this amount of alphanumeric characters are not considered moved,
+/// unless in [`Mode::Plain`].
+const MIN_ALNUM_COUNT: usize = 20;
+
+/// The way moved lines are detected and annotated, like `diff.colorMoved`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Mode {
+    /// Annotate each line that was added somewhere and removed somewhere else, without regard to blocks of lines.
+    Plain,
+    /// Annotate blocks of at least 20 alphanumeric characters that were moved, and ignore the rest.
+    Blocks,
+    /// Like [`Blocks`](Mode::Blocks), but mark the second of two adjacent blocks as [alternative](Moved::alternative)
+    /// to be able to tell them apart.
+    #[default]
+    Zebra,
+    /// Like [`Zebra`](Mode::Zebra), but additionally mark the lines of a block as [dimmed](Moved::dimmed) if they aren't
+    /// at the border to another block.
+    DimmedZebra,
+}
+
+/// A line of a patch, in display order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Line<'a> {
+    /// Anything that isn't a removed or added line and thus separates blocks of moved lines, like context lines,
+    /// hunk headers or the start of another file.
+    Context,
+    /// A removed line with the given content.
+    Removed(&'a BStr),
+    /// An added line with the given content.
+    Added(&'a BStr),
+}
+
+/// The annotation of a line that was moved.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Moved {
+    /// If `true`, the line is part of a block that directly follows another moved block, and should be displayed
+    /// differently from it.
+    ///
+    /// Only set in [`Mode::Zebra`] and [`Mode::DimmedZebra`].
+    pub alternative: bool,
+    /// If `true`, the line is within a block of moved lines and not at its border to another moved block, so it's
+    /// not interesting and should be displayed less prominently.
+    ///
+    /// Only set in [`Mode::DimmedZebra`].
+    pub dimmed: bool,
+}
+
+/// A [`Sink`](imara_diff::Sink) to collect the [lines](Line) of a diff into `lines`, in the order in which they would be
+/// displayed in a patch, with each hunk being separated by a [context line](Line::Context).
+pub struct Collect<'a, 'data> {
+    input: &'a InternedInput<&'data [u8]>,
+    lines: &'a mut Vec<Line<'data>>,
+}
+
+impl<'a, 'data> Collect<'a, 'data> {
+    /// Create a new instance to collect the lines of the diff of `input` into `lines`.
+    ///
+    /// Use it multiple times with the same `lines` to detect lines moved between files.
+    pub fn new(input: &'a InternedInput<&'data [u8]>, lines: &'a mut Vec<Line<'data>>) -> Self {
+        Collect { input, lines }
+    }
+}
+
+impl imara_diff::Sink for Collect<'_, '_> {
+    type Out = ();
+
+    fn process_change(&mut self, before: Range<u32>, after: Range<u32>) {
+        let Collect { input, lines } = self;
+        lines.push(Line::Context);
+        lines.extend(
+            input.before[before.start as usize..before.end as usize]
+                .iter()
+                .map(|&token| Line::Removed(input.interner[token].as_bstr())),
+        );
+        lines.extend(
+            input.after[after.start as usize..after.end as usize]
+                .iter()
+                .map(|&token| Line::Added(input.interner[token].as_bstr())),
+        );
+    }
+
+    fn finish(self) -> Self::Out {}
+}
+
+impl Line<'_> {
+    fn is_change(&self) -> bool {
+        !matches!(self, Line::Context)
+    }
+
+    fn has_same_kind(&self, other: &Line<'_>) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+struct Flags {
+    moved: bool,
+    alternative: bool,
+    dimmed: bool,
+}
+
+impl Flags {
+    fn zebra(&self) -> (bool, bool) {
+        (self.moved, self.alternative)
+    }
+}
+
+/// Detect which of `lines` were moved according to `mode`, and return an annotation for each of them, which is `None`
+/// if the line wasn't moved.
+///
+/// A removed line is moved if the same line was added elsewhere, and vice versa. Unless in [`Mode::Plain`], only
+/// blocks of moved lines that are long enough are considered moved. This follows the algorithm of `git diff --color-moved`,
+/// without any of the options for ignoring whitespace.
+pub fn detect(lines: &[Line<'_>], mode: Mode) -> Vec<Option<Moved>> {
+    let mut id_by_content = HashMap::<&[u8], usize>::new();
+    let mut ids = Vec::with_capacity(lines.len());
+    let (mut removed, mut added) = (Vec::<Vec<usize>>::new(), Vec::<Vec<usize>>::new());
+    for (idx, line) in lines.iter().enumerate() {
+        let content = match line {
+            Line::Context => {
+                ids.push(None);
+                continue;
+            }
+            Line::Removed(content) | Line::Added(content) => content.as_bytes(),
+        };
+        let next_id = id_by_content.len();
+        let id = *id_by_content.entry(content).or_insert(next_id);
+        if id == removed.len() {
+            removed.push(Vec::new());
+            added.push(Vec::new());
+        }
+        match line {
+            Line::Removed(_) => removed[id].push(idx),
+            Line::Added(_) => added[id].push(idx),
+            Line::Context => unreachable!("handled above"),
+        }
+        ids.push(Some(id));
+    }
+
+    let mut flags = vec![Flags::default(); lines.len()];
+    let next_line = |idx: usize| {
+        let next = idx + 1;
+        (next < lines.len() && lines[idx].is_change() && lines[idx].has_same_kind(&lines[next])).then_some(next)
+    };
+
+    // The lines on the other side that the current block could have been moved from or to.
+    let mut potential_blocks = Vec::<usize>::new();
+    let mut block_kind = Line::Context;
+    let (mut block_len, mut alternative) = (0, false);
+    let mut n = 0;
+    while n < lines.len() {
+        let line = lines[n];
+        let mut matches = match (line, ids[n]) {
+            (Line::Added(_), Some(id)) => Some(&removed[id]),
+            (Line::Removed(_), Some(id)) => Some(&added[id]),
+            _ => {
+                alternative = false;
+                None
+            }
+        }
+        .filter(|matches| !matches.is_empty());
+
+        if !potential_blocks.is_empty() && (matches.is_none() || !line.has_same_kind(&block_kind)) {
+            if !adjust_last_block(lines, &mut flags, mode, n, block_len) && block_len > 1 {
+                // Retry in case another block starts at the second line of the block.
+                matches = None;
+                n -= block_len;
+            }
+            potential_blocks.clear();
+            block_len = 0;
+            alternative = false;
+        }
+
+        let Some(matches) = matches else {
+            block_kind = Line::Context;
+            n += 1;
+            continue;
+        };
+
+        if mode == Mode::Plain {
+            flags[n].moved = true;
+            n += 1;
+            continue;
+        }
+
+        potential_blocks.retain_mut(|idx| match next_line(*idx) {
+            Some(next) if ids[next] == ids[n] => {
+                *idx = next;
+                true
+            }
+            _ => false,
+        });
+
+        if potential_blocks.is_empty() {
+            let contiguous = adjust_last_block(lines, &mut flags, mode, n, block_len);
+            if !contiguous && block_len > 1 {
+                // Retry in case another block starts at the second line of the block.
+                n -= block_len;
+            } else {
+                potential_blocks.extend_from_slice(matches);
+            }
+
+            alternative = contiguous && !potential_blocks.is_empty() && line.has_same_kind(&block_kind) && !alternative;
+            block_kind = if potential_blocks.is_empty() {
+                Line::Context
+            } else {
+                line
+            };
+            block_len = 0;
+        }
+
+        if !potential_blocks.is_empty() {
+            block_len += 1;
+            flags[n].moved = true;
+            flags[n].alternative = alternative && mode != Mode::Blocks;
+        }
+        n += 1;
+    }
+    adjust_last_block(lines, &mut flags, mode, n, block_len);
+
+    if mode == Mode::DimmedZebra {
+        dim_lines_within_blocks(lines, &mut flags);
+    }
+
+    flags
+        .into_iter()
+        .map(|flags| {
+            flags.moved.then_some(Moved {
+                alternative: flags.alternative,
+                dimmed: flags.dimmed,
+            })
+        })
+        .collect()
+}
+
+/// Unmark the block of `block_len` lines before line `n` if it has too few alphanumeric characters,
+/// and return `true` if it is still marked as moved.
+fn adjust_last_block(lines: &[Line<'_>], flags: &mut [Flags], mode: Mode, n: usize, block_len: usize) -> bool {
+    if mode == Mode::Plain {
+        return block_len != 0;
+    }
+    let block = n - block_len..n;
+    let alnum_count = lines[block.clone()]
+        .iter()
+        .filter_map(|line| match line {
+            Line::Removed(content) | Line::Added(content) => Some(content),
+            Line::Context => None,
+        })
+        .flat_map(|content| content.iter())
+        .filter(|b| b.is_ascii_alphanumeric())
+        .take(MIN_ALNUM_COUNT)
+        .count();
+    if alnum_count >= MIN_ALNUM_COUNT {
+        return true;
+    }
+    for flags in &mut flags[block] {
+        flags.moved = false;
+        flags.alternative = false;
+    }
+    false
+}
+
+/// Dim all moved lines that are surrounded by lines of the same block, or which don't border another block.
+fn dim_lines_within_blocks(lines: &[Line<'_>], flags: &mut [Flags]) {
+    for n in 0..lines.len() {
+        if !lines[n].is_change() || !flags[n].moved {
+            continue;
+        }
+        let current = flags[n];
+        let prev = n
+            .checked_sub(1)
+            .filter(|&idx| lines[idx].is_change())
+            .map(|idx| flags[idx]);
+        let next = Some(n + 1)
+            .filter(|&idx| idx < lines.len() && lines[idx].is_change())
+            .map(|idx| flags[idx]);
+
+        let is_within_block = matches!((prev, next), (Some(prev), Some(next)) if prev.zebra() == current.zebra() && next.zebra() == current.zebra());
+        let borders_other_block = |other: Option<Flags>| matches!(other, Some(other) if other.moved && other.alternative != current.alternative);
+        if is_within_block || !(borders_other_block(prev) || borders_other_block(next)) {
+            flags[n].dimmed = true;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use gix_diff::blob::binary::{delta, HunkKind, Patch};
+
+fn old_and_new() -> (Vec<u8>, Vec<u8>) {
+    let old: Vec<u8> = (0..3000_usize).map(|i| (i * 7 % 251) as u8).collect();
+    let mut new = old.clone();
+    new[100..110].fill(0);
+    new.extend_from_slice(b"tail");
+    (old, new)
+}
+
+#[test]
+fn patches_produced_by_git_can_be_parsed_and_applied() -> gix_testtools::Result {
+    let (old, new) = old_and_new();
+    let input = b"GIT binary patch\ndelta 36\nkcmdlXzDImQ3Ks($Y<A=<XWIOL`3C#s2h6`&N)j`30H>u2s{jB1\n\ndelta 16\nYcmdlZzC(OM%H{x;4ED_rnBOo1069Yjk^lez\n\ndiff --git a/b.bin b/b.bin\n";
+    let (patch, consumed) = Patch::from_bytes(input)?;
+    assert_eq!(
+        &input[consumed..],
+        b"diff --git a/b.bin b/b.bin\n",
+        "parsing stops after the reverse hunk"
+    );
+    assert_eq!(patch.forward.kind, HunkKind::Delta);
+    assert_eq!(patch.apply(&old)?, new);
+    assert_eq!(patch.apply_reverse(&new)?, old);
+
+    let input = b"GIT binary patch\nliteral 3\nKcmZQ(_y+(13IPHD\n\nliteral 4\nLcmZQzWcm*P0SW;F\n\n";
+    let (patch, consumed) = Patch::from_bytes(input)?;
+    assert_eq!(consumed, input.len());
+    assert_eq!(patch.forward.kind, HunkKind::Literal);
+    assert_eq!(patch.apply(&[0, 1, 2, 0xff])?, [3, 0, 0xfe]);
+    assert_eq!(patch.apply_reverse(&[3, 0, 0xfe])?, [0, 1, 2, 0xff]);
+    Ok(())
+}
+
+#[test]
+fn round_trip() -> gix_testtools::Result {
+    let (old, new) = old_and_new();
+    for (old, new, expected_kind) in [
+        (&old[..], &new[..], HunkKind::Delta),
+        (&new[..], &old[..], HunkKind::Delta),
+        (&[][..], &new[..], HunkKind::Literal),
+        (&old[..], &[][..], HunkKind::Literal),
+        (&[1, 2, 3][..], &[4][..], HunkKind::Literal),
+    ] {
+        let patch = Patch::new(old, new);
+        assert_eq!(patch.forward.kind, expected_kind);
+        let mut buf = Vec::new();
+        patch.write_to(&mut buf)?;
+        assert!(buf.starts_with(b"GIT binary patch\n"));
+
+        let (parsed, consumed) = Patch::from_bytes(&buf)?;
+        assert_eq!(consumed, buf.len());
+        assert_eq!(parsed, patch);
+        assert_eq!(parsed.apply(old)?, new);
+        assert_eq!(parsed.apply_reverse(new)?, old);
+    }
+    Ok(())
+}
+
+#[test]
+fn patches_without_reverse_hunk_are_accepted() -> gix_testtools::Result {
+    let (patch, _) = Patch::from_bytes(b"GIT binary patch\nliteral 3\nKcmZQ(_y+(13IPHD\n\n")?;
+    assert!(patch.reverse.is_none());
+    assert_eq!(patch.apply(b"ignored")?, [3, 0, 0xfe]);
+    assert!(patch.apply_reverse(&[3, 0, 0xfe]).is_err());
+    Ok(())
+}
+
+#[test]
+fn malformed_patches_are_rejected() {
+    for input in [
+        &b"literal 3\nKcmZQ(_y+(13IPHD\n\n"[..],
+        b"GIT binary patch\nliteral x\n\n",
+        b"GIT binary patch\nliteral 3\nKcmZQ(_y+(13IPHD\n",
+        b"GIT binary patch\nliteral 3\n!cmZQ(_y+(13IPHD\n\n",
+        b"GIT binary patch\nliteral 4\nKcmZQ(_y+(13IPHD\n\n",
+        b"GIT binary patch\nliteral 18446744073709551615\nKcmZQ(_y+(13IPHD\n\n",
+    ] {
+        assert!(
+            Patch::from_bytes(input).is_err(),
+            "{:?}",
+            gix_object::bstr::BStr::new(input)
+        );
+    }
+}
+
+#[test]
+fn deltas_apply_only_to_their_base() {
+    let (old, new) = old_and_new();
+    let mut delta = Vec::new();
+    delta::create(&old, &new, &mut delta);
+    assert!(
+        delta.len() < new.len() / 10,
+        "the delta is much smaller than the content"
+    );
+
+    let mut out = Vec::new();
+    delta::apply(&old, &delta, &mut out).expect("valid");
+    assert_eq!(out, new);
+    assert!(matches!(
+        delta::apply(&old[1..], &delta, &mut out),
+        Err(delta::Error::BaseSize { .. })
+    ));
+}
@@ -1,2 +1,4 @@
+mod binary;
+mod moved;
 pub(crate) mod pipeline;
 mod platform;
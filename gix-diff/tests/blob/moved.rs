@@ -0,0 +1,161 @@
+use gix_diff::blob::{
+    intern::InternedInput,
+    moved::{self, Line, Mode, Moved},
+    sources, Algorithm,
+};
+
+const OLD: &str = "A line with enough characters 1
+A line with enough characters 2
+X
+B line with enough characters 1
+B line with enough characters 2
+B line with enough characters 3
+Y
+P
+Q
+R
+Z
+";
+
+const NEW: &str = "X
+Y
+P
+Q
+R
+B line with enough characters 1
+B line with enough characters 2
+B line with enough characters 3
+A line with enough characters 1
+A line with enough characters 2
+Z
+";
+
+fn moved_lines(old: &str, new: &str, mode: Mode) -> Vec<(String, Option<Moved>)> {
+    let input = InternedInput::new(sources::byte_lines(old.as_bytes()), sources::byte_lines(new.as_bytes()));
+    let mut lines = Vec::new();
+    gix_diff::blob::diff(Algorithm::Myers, &input, moved::Collect::new(&input, &mut lines));
+    annotated(&lines, mode)
+}
+
+fn annotated(lines: &[Line<'_>], mode: Mode) -> Vec<(String, Option<Moved>)> {
+    lines
+        .iter()
+        .zip(moved::detect(lines, mode))
+        .filter_map(|(line, moved)| match line {
+            Line::Context => None,
+            Line::Removed(content) => Some((format!("-{}", content.to_string().trim_end()), moved)),
+            Line::Added(content) => Some((format!("+{}", content.to_string().trim_end()), moved)),
+        })
+        .collect()
+}
+
+const MOVED: Option<Moved> = Some(Moved {
+    alternative: false,
+    dimmed: false,
+});
+const MOVED_DIMMED: Option<Moved> = Some(Moved {
+    alternative: false,
+    dimmed: true,
+});
+const ALTERNATIVE: Option<Moved> = Some(Moved {
+    alternative: true,
+    dimmed: false,
+});
+const ALTERNATIVE_DIMMED: Option<Moved> = Some(Moved {
+    alternative: true,
+    dimmed: true,
+});
+
+fn expected(annotations: [Option<Moved>; 10]) -> Vec<(String, Option<Moved>)> {
+    [
+        "-A line with enough characters 1",
+        "-A line with enough characters 2",
+        "-B line with enough characters 1",
+        "-B line with enough characters 2",
+        "-B line with enough characters 3",
+        "+B line with enough characters 1",
+        "+B line with enough characters 2",
+        "+B line with enough characters 3",
+        "+A line with enough characters 1",
+        "+A line with enough characters 2",
+    ]
+    .into_iter()
+    .map(ToOwned::to_owned)
+    .zip(annotations)
+    .collect()
+}
+
+#[test]
+fn adjacent_blocks_are_told_apart_in_zebra_modes() {
+    assert_eq!(moved_lines(OLD, NEW, Mode::Plain), expected([MOVED; 10]));
+    assert_eq!(moved_lines(OLD, NEW, Mode::Blocks), expected([MOVED; 10]));
+    assert_eq!(
+        moved_lines(OLD, NEW, Mode::Zebra),
+        expected([
+            MOVED,
+            MOVED,
+            MOVED,
+            MOVED,
+            MOVED,
+            MOVED,
+            MOVED,
+            MOVED,
+            ALTERNATIVE,
+            ALTERNATIVE
+        ]),
+        "the second of two adjacent blocks is the alternative"
+    );
+    assert_eq!(
+        moved_lines(OLD, NEW, Mode::DimmedZebra),
+        expected([
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED_DIMMED,
+            MOVED,
+            ALTERNATIVE,
+            ALTERNATIVE_DIMMED
+        ]),
+        "only the lines at the border of adjacent blocks stay prominent"
+    );
+}
+
+#[test]
+fn short_blocks_are_only_moved_in_plain_mode() {
+    let long = "long line with enough characters\n".into();
+    let lines = [
+        Line::Context,
+        Line::Removed(long),
+        Line::Context,
+        Line::Added("short\n".into()),
+        Line::Context,
+        Line::Removed("short\n".into()),
+        Line::Added(long),
+        Line::Context,
+        Line::Added("unique\n".into()),
+    ];
+    assert_eq!(
+        annotated(&lines, Mode::Plain),
+        [
+            ("-long line with enough characters".into(), MOVED),
+            ("+short".into(), MOVED),
+            ("-short".into(), MOVED),
+            ("+long line with enough characters".into(), MOVED),
+            ("+unique".into(), None),
+        ]
+    );
+    assert_eq!(
+        annotated(&lines, Mode::Blocks),
+        [
+            ("-long line with enough characters".into(), MOVED),
+            ("+short".into(), None),
+            ("-short".into(), None),
+            ("+long line with enough characters".into(), MOVED),
+            ("+unique".into(), None),
+        ],
+        "blocks with less than 20 alphanumeric characters are ignored"
+    );
+}
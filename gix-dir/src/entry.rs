@@ -20,6 +20,9 @@ pub enum Property {
     ///
     /// Note that evne if the directory is empty, it will only have this state, not `EmptyDirectory`.
     TrackedExcluded,
+    /// The entry is a [nested repository](Kind::Repository), and it was emitted because
+    /// [NestedRepositoryMode::Emit](crate::walk::NestedRepositoryMode::Emit) was configured.
+    NestedRepository,
 }
 
 /// The kind of the entry, seated in their kinds available on disk.
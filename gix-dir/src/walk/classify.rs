@@ -2,7 +2,7 @@ use crate::{entry, Entry, EntryRef};
 use std::borrow::Cow;
 
 use crate::entry::PathspecMatch;
-use crate::walk::{Context, Error, ForDeletionMode, Options};
+use crate::walk::{Context, Error, ForDeletionMode, NestedRepositoryMode, Options};
 use bstr::{BStr, BString, ByteSlice};
 use std::path::{Path, PathBuf};
 
@@ -85,6 +85,17 @@ impl Outcome {
         self.index_kind = index_kind;
         self
     }
+
+    /// Mark this instance as nested repository if it is one and if `mode` asks for it.
+    fn with_nested_repository_property(mut self, mode: NestedRepositoryMode) -> Self {
+        if mode == NestedRepositoryMode::Emit
+            && self.disk_kind == Some(entry::Kind::Repository)
+            && self.property.is_none()
+        {
+            self.property = entry::Property::NestedRepository.into();
+        }
+        self
+    }
 }
 
 impl From<&Entry> for Outcome {
@@ -130,7 +141,7 @@ pub fn path(
     on_demand_disk_kind: impl FnOnce() -> Option<entry::Kind>,
     Options {
         ignore_case,
-        recurse_repositories,
+        nested_repositories,
         emit_ignored,
         for_deletion,
         classify_untracked_bare_repositories,
@@ -213,7 +224,7 @@ pub fn path(
     };
 
     let mut maybe_upgrade_to_repository = |current_kind, find_harder: bool| {
-        if recurse_repositories {
+        if nested_repositories == NestedRepositoryMode::Recurse {
             return current_kind;
         }
         if find_harder {
@@ -249,7 +260,10 @@ pub fn path(
         if kind == Some(entry::Kind::Directory) && index_kind == Some(entry::Kind::Repository) {
             kind = maybe_upgrade_to_repository(kind, false);
         }
-        return Ok(out.with_status(status).with_kind(kind, index_kind));
+        return Ok(out
+            .with_status(status)
+            .with_kind(kind, index_kind)
+            .with_nested_repository_property(nested_repositories));
     }
 
     debug_assert!(maybe_status.is_none(), "It only communicates a single state right now");
@@ -286,7 +300,8 @@ pub fn path(
         }
         return Ok(out
             .with_status(entry::Status::Ignored(excluded))
-            .with_kind(kind, index_kind));
+            .with_kind(kind, index_kind)
+            .with_nested_repository_property(nested_repositories));
     }
 
     debug_assert!(maybe_status.is_none());
@@ -297,7 +312,10 @@ pub fn path(
     } else if out.pathspec_match.is_none() {
         status = entry::Status::Pruned;
     }
-    Ok(out.with_status(status).with_kind(kind, index_kind))
+    Ok(out
+        .with_status(status)
+        .with_kind(kind, index_kind)
+        .with_nested_repository_property(nested_repositories))
 }
 
 /// Note that `rela_path` is used as buffer for convenience, but will be left as is when this function returns.
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use bstr::{BStr, BString, ByteSlice};
 
-use crate::walk::{classify, readdir, Action, Context, Delegate, Error, ForDeletionMode, Options, Outcome};
+use crate::walk::{classify, readdir, Action, Context, Delegate, Error, ForDeletionMode, Options, Outcome, SkipReason};
 use crate::{entry, EntryRef};
 
 /// A function to perform a git-style, unsorted, directory walk.
@@ -100,7 +100,12 @@ pub fn walk(
         return Ok((out, root.to_owned()));
     }
 
-    let mut state = readdir::State::new(worktree_root, ctx.current_dir, options.for_deletion.is_some());
+    let mut state = readdir::State::new(
+        worktree_root,
+        ctx.current_dir,
+        options.for_deletion.is_some(),
+        options.one_file_system,
+    );
     let may_collapse = root != worktree_root && state.may_collapse(&current);
     let (action, _) = readdir::recursive(
         may_collapse,
@@ -189,7 +194,9 @@ pub(super) fn emit_entry(
     out.seen_entries += 1;
 
     if (!emit_empty_directories && info.property == Some(entry::Property::EmptyDirectory)
-        || !emit_tracked && info.status == entry::Status::Tracked)
+        || !emit_tracked
+            && info.status == entry::Status::Tracked
+            && info.property != Some(entry::Property::NestedRepository))
         || emit_ignored.is_none() && matches!(info.status, entry::Status::Ignored(_))
         || !emit_pruned
             && (info.status.is_pruned()
@@ -203,3 +210,15 @@ pub(super) fn emit_entry(
     out.returned_entries += 1;
     delegate.emit(EntryRef::from_outcome(rela_path, info), dir_status)
 }
+
+/// Pass an entry that is neither emitted nor traversed for the given `reason` to the delegate.
+pub(super) fn skip_entry(
+    rela_path: &BStr,
+    info: classify::Outcome,
+    reason: SkipReason,
+    out: &mut Outcome,
+    delegate: &mut dyn Delegate,
+) -> Action {
+    out.seen_entries += 1;
+    delegate.skipped(EntryRef::from_outcome(Cow::Borrowed(rela_path), info), reason)
+}
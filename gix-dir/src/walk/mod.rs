@@ -27,6 +27,8 @@ pub mod delegate {
     pub struct Collect {
         /// All collected entries, in any order.
         pub unorded_entries: Entries,
+        /// All entries that were [skipped](walk::Delegate::skipped()), along with the reason for it, in any order.
+        pub skipped_entries: Vec<(Entry, walk::SkipReason)>,
     }
 
     impl Collect {
@@ -42,6 +44,11 @@ pub mod delegate {
             self.unorded_entries.push((entry.to_owned(), dir_status));
             walk::Action::Continue
         }
+
+        fn skipped(&mut self, entry: EntryRef<'_>, reason: walk::SkipReason) -> Action {
+            self.skipped_entries.push((entry.to_owned(), reason));
+            walk::Action::Continue
+        }
     }
 }
 
@@ -89,6 +96,30 @@ pub trait Delegate {
             worktree_root_is_repository,
         )
     }
+
+    /// Called for each `entry` that was neither emitted nor traversed because of `reason`, which allows to account
+    /// for all entries in the worktree. Note that directories containing skipped entries are never collapsed.
+    ///
+    /// The base implementation ignores skipped entries.
+    fn skipped(&mut self, entry: EntryRef<'_>, reason: SkipReason) -> Action {
+        let _ = (entry, reason);
+        Action::Continue
+    }
+}
+
+/// The reason for an entry to be passed to [`Delegate::skipped()`] instead of being emitted or traversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkipReason {
+    /// The directory is on another filesystem than the worktree root, and [`Options::one_file_system`] is set.
+    OtherFileSystem,
+    /// The directory is too deep to be entered according to [`Options::max_depth`].
+    TooDeep,
+    /// The worktree-relative path of the entry is longer than [`Options::max_path_len`].
+    PathTooLong,
+    /// The directory could not be read for the given reason, like missing permissions, and [`Options::skip_inaccessible`] is set.
+    Inaccessible(std::io::ErrorKind),
+    /// The entry is a symlink that can't be resolved as it's part of a loop of symlinks, and [`Options::skip_inaccessible`] is set.
+    SymlinkLoop,
 }
 
 /// The way entries are emitted using the [Delegate].
@@ -141,6 +172,26 @@ pub enum ForDeletionMode {
     FindRepositoriesInIgnoredDirectories,
 }
 
+/// Determine what to do when a directory turns out to be a nested repository, i.e. contains a `.git` directory
+/// or is a submodule.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum NestedRepositoryMode {
+    /// Do not enter nested repositories, and classify them as [Repository](crate::entry::Kind::Repository) instead,
+    /// which is what Git does. This way they appear like any other untracked or tracked directory.
+    #[default]
+    Untracked,
+    /// Don't check directories for being repositories at all, which saves time but leads to recurse into them
+    /// as if they were ordinary directories.
+    Recurse,
+    /// Like [`Untracked`](Self::Untracked), but also emit each nested repository as dedicated entry
+    /// with the [NestedRepository](crate::entry::Property::NestedRepository) property.
+    ///
+    /// This happens even if its status would otherwise prevent it from being emitted, like it's the case for submodules
+    /// which are tracked, and such entries are never collapsed into their parent directory.
+    /// Their `HEAD` can then be read by the caller, which is useful for backup tools or to display them specifically.
+    Emit,
+}
+
 /// Options for use in [`walk()`](function::walk()) function.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Options {
@@ -155,9 +206,8 @@ pub struct Options {
     /// Note that [pathspecs](Context::pathspec) must also be using the same defaults, which makes them match case-insensitive
     /// automatically.
     pub ignore_case: bool,
-    /// If `true`, we will stop figuring out if any directory that is a candidate for recursion is also a nested repository,
-    /// which saves time but leads to recurse into it. If `false`, nested repositories will not be traversed.
-    pub recurse_repositories: bool,
+    /// Control what happens when encountering nested repositories, which by default are not traversed.
+    pub nested_repositories: NestedRepositoryMode,
     /// If `true`, entries that are pruned and whose [Kind](crate::entry::Kind) is known will be emitted.
     pub emit_pruned: bool,
     /// If `Some(mode)`, entries that are ignored will be emitted according to the given `mode`.
@@ -192,6 +242,21 @@ pub struct Options {
     ///
     /// In other words, for Git compatibility this flag should be `false`, the default, for `git2` compatibility it should be `true`.
     pub symlinks_to_directories_are_ignored_like_directories: bool,
+    /// If `true`, directories on another filesystem than the one of the worktree root aren't entered, similar to
+    /// `--one-file-system` of backup tools, and are passed to [`Delegate::skipped()`] instead.
+    ///
+    /// This is only supported on Unix and has no effect on other platforms.
+    pub one_file_system: bool,
+    /// If `Some(depth)`, directories whose worktree-relative path consists of `depth` or more components aren't entered,
+    /// and are passed to [`Delegate::skipped()`] instead. This way, no entry will have more than `depth` components.
+    pub max_depth: Option<usize>,
+    /// If `Some(len)`, entries whose worktree-relative path is longer than `len` bytes are neither emitted nor entered,
+    /// and are passed to [`Delegate::skipped()`] instead.
+    pub max_path_len: Option<usize>,
+    /// If `true`, directories that can't be read, for instance due to missing permissions, are passed to [`Delegate::skipped()`]
+    /// instead of failing the walk, and so are symlinks that can't be resolved as they are part of a loop.
+    /// If `false`, such directories cause an error and such symlinks are emitted like any other.
+    pub skip_inaccessible: bool,
 }
 
 /// All information that is required to perform a dirwalk, and classify paths properly.
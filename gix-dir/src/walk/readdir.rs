@@ -4,9 +4,11 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 
 use crate::entry::{PathspecMatch, Status};
-use crate::walk::function::{can_recurse, emit_entry};
+use crate::walk::function::{can_recurse, emit_entry, skip_entry};
 use crate::walk::EmissionMode::CollapseDirectory;
-use crate::walk::{classify, Action, CollapsedEntriesEmissionMode, Context, Delegate, Error, Options, Outcome};
+use crate::walk::{
+    classify, Action, CollapsedEntriesEmissionMode, Context, Delegate, Error, Options, Outcome, SkipReason,
+};
 use crate::{entry, walk, Entry, EntryRef};
 
 /// ### Deviation
@@ -28,10 +30,20 @@ pub(super) fn recursive(
         return Err(Error::Interrupted);
     }
     out.read_dir_calls += 1;
-    let entries = gix_fs::read_dir(current, opts.precompose_unicode).map_err(|err| Error::ReadDir {
-        path: current.to_owned(),
-        source: err,
-    })?;
+    let entries = match gix_fs::read_dir(current, opts.precompose_unicode) {
+        Ok(entries) => entries,
+        Err(err) if opts.skip_inaccessible => {
+            let reason = SkipReason::Inaccessible(err.kind());
+            let action = skip_entry(current_bstr.as_bstr(), current_info, reason, out, delegate);
+            return Ok((action, true));
+        }
+        Err(err) => {
+            return Err(Error::ReadDir {
+                path: current.to_owned(),
+                source: err,
+            })
+        }
+    };
 
     let mut num_entries = 0;
     let mark = state.mark(may_collapse);
@@ -67,13 +79,29 @@ pub(super) fn recursive(
             ctx,
         )?;
 
-        if can_recurse(
+        if let Some(reason) = skip_reason(current, current_bstr.as_bstr(), info, &opts) {
+            prevent_collapse = true;
+            let action = skip_entry(current_bstr.as_bstr(), info, reason, out, delegate);
+            if action != Action::Continue {
+                return Ok((action, prevent_collapse));
+            }
+        } else if can_recurse(
             current_bstr.as_bstr(),
             info,
             opts.for_deletion,
             false, /* is root */
             delegate,
         ) {
+            if let Some(reason) = state.directory_skip_reason(current, current_bstr.as_bstr(), &opts) {
+                prevent_collapse = true;
+                let action = skip_entry(current_bstr.as_bstr(), info, reason, out, delegate);
+                if action != Action::Continue {
+                    return Ok((action, prevent_collapse));
+                }
+                current_bstr.truncate(prev_len);
+                current.pop();
+                continue;
+            }
             let subdir_may_collapse = state.may_collapse(current);
             let (action, subdir_prevent_collapse) = recursive(
                 subdir_may_collapse,
@@ -120,6 +148,37 @@ pub(super) struct State {
     pub on_hold: Vec<Entry>,
     /// The path the user is currently in, as seen from the workdir root.
     worktree_relative_current_dir: Option<PathBuf>,
+    /// The device of the worktree root if directories on other devices must not be entered.
+    root_device: Option<u64>,
+}
+
+/// Return the reason for skipping the entry at `path` with worktree-relative `rela_path`, if it has to be skipped
+/// independently of its kind.
+fn skip_reason(path: &Path, rela_path: &BStr, info: classify::Outcome, opts: &Options) -> Option<SkipReason> {
+    if opts.max_path_len.map_or(false, |max| rela_path.len() > max) {
+        Some(SkipReason::PathTooLong)
+    } else if opts.skip_inaccessible
+        && info.disk_kind == Some(entry::Kind::Symlink)
+        && matches!(
+            gix_path::realpath(path),
+            Err(gix_path::realpath::Error::MaxSymlinksExceeded { .. })
+        )
+    {
+        Some(SkipReason::SymlinkLoop)
+    } else {
+        None
+    }
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    path.metadata().ok().map(|meta| meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device_of(_path: &Path) -> Option<u64> {
+    None
 }
 
 impl State {
@@ -143,7 +202,23 @@ impl State {
         }
     }
 
-    pub(super) fn new(worktree_root: &Path, current_dir: &Path, is_delete_mode: bool) -> Self {
+    /// Return the reason for not entering the directory at `path` with worktree-relative `rela_path`, if it must not be entered.
+    fn directory_skip_reason(&self, path: &Path, rela_path: &BStr, opts: &Options) -> Option<SkipReason> {
+        if opts
+            .max_depth
+            .map_or(false, |max| rela_path.split(|b| *b == b'/').count() >= max)
+        {
+            Some(SkipReason::TooDeep)
+        } else if self.root_device.map_or(false, |root_device| {
+            device_of(path).map_or(false, |dev| dev != root_device)
+        }) {
+            Some(SkipReason::OtherFileSystem)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn new(worktree_root: &Path, current_dir: &Path, is_delete_mode: bool, one_file_system: bool) -> Self {
         let worktree_relative_current_dir = if is_delete_mode {
             gix_path::realpath_opts(worktree_root, current_dir, gix_path::realpath::MAX_SYMLINKS)
                 .ok()
@@ -155,6 +230,7 @@ impl State {
         Self {
             on_hold: Vec::new(),
             worktree_relative_current_dir,
+            root_device: one_file_system.then(|| device_of(worktree_root)).flatten(),
         }
     }
 
@@ -42,4 +42,11 @@ EOF
   ln -s src/file file2
 )
 
-ln -s excluded-symlinks-to-dir worktree-root-is-symlink
\ No newline at end of file
+ln -s excluded-symlinks-to-dir worktree-root-is-symlink
+
+git init symlink-loop
+(cd symlink-loop
+  ln -s loop loop
+  ln -s does-not-exist dangling
+  touch file
+)
@@ -351,3 +351,9 @@ git clone submodule multiple-submodules
   git submodule add ../submodule a/b
   git commit -m "add modules"
 )
+
+git init deep-and-long
+(cd deep-and-long
+  mkdir -p a/b/c
+  touch top a/long-file-name a/b/c/file
+)
@@ -5,7 +5,7 @@ use std::sync::atomic::AtomicBool;
 use crate::walk_utils::{
     collect, collect_filtered, collect_filtered_with_cwd, entry, entry_dirstat, entry_nokind, entry_nomatch, entryps,
     entryps_dirstat, fixture, fixture_in, options, options_emit_all, try_collect, try_collect_filtered_opts,
-    try_collect_filtered_opts_collect, try_collect_filtered_opts_collect_with_root, EntryExt, Options,
+    try_collect_filtered_opts_collect, try_collect_filtered_opts_collect_with_root, Entries, EntryExt, Options,
 };
 use gix_dir::entry;
 use gix_dir::entry::Kind::*;
@@ -2986,7 +2986,7 @@ fn root_may_not_go_through_nested_repository_unless_enabled() -> crate::Result {
                 &root,
                 ctx,
                 walk::Options {
-                    recurse_repositories: true,
+                    nested_repositories: walk::NestedRepositoryMode::Recurse,
                     ..options()
                 },
                 keep,
@@ -3079,6 +3079,59 @@ fn walk_with_submodule() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn nested_repositories_can_be_emitted_as_dedicated_entries() -> crate::Result {
+    let emit_nested = walk::Options {
+        nested_repositories: walk::NestedRepositoryMode::Emit,
+        ..options()
+    };
+    let root = fixture("with-submodule");
+    let (_out, entries) = collect(&root, None, |keep, ctx| walk(&root, ctx, emit_nested, keep));
+    assert_eq!(
+        entries,
+        [entry("submodule", Tracked, Repository)
+            .with_index_kind(Repository)
+            .with_property(NestedRepository)],
+        "submodules are tracked, but are emitted nonetheless"
+    );
+
+    let root = fixture("nested-repository");
+    let (_out, entries) = collect(&root, None, |keep, ctx| {
+        walk(
+            &root,
+            ctx,
+            walk::Options {
+                emit_untracked: CollapseDirectory,
+                ..emit_nested
+            },
+            keep,
+        )
+    });
+    assert_eq!(
+        entries,
+        [entry("nested", Untracked, Repository).with_property(NestedRepository)],
+        "untracked nested repositories are marked as well"
+    );
+
+    let (_out, entries) = collect(&root, None, |keep, ctx| {
+        walk(
+            &root,
+            ctx,
+            walk::Options {
+                nested_repositories: walk::NestedRepositoryMode::Recurse,
+                ..options()
+            },
+            keep,
+        )
+    });
+    assert_eq!(
+        entries,
+        [entry("nested/file", Untracked, File)],
+        "with recursion, nested repositories are just directories"
+    );
+    Ok(())
+}
+
 #[test]
 fn root_that_is_tracked_file_is_returned() -> crate::Result {
     let root = fixture("dir-with-tracked-file");
@@ -4276,3 +4329,109 @@ fn type_mismatch_ignore_case_clash_file_is_dir() {
          If there was no special handling for this, it would have found the file (`d` in the index, icase), which would have been wrong."
     );
 }
+
+type Skipped = Vec<(gix_dir::Entry, walk::SkipReason)>;
+
+fn collect_with_skipped(root: &std::path::Path, options: walk::Options) -> crate::Result<(Entries, Skipped)> {
+    let mut dlg = walk::delegate::Collect::default();
+    try_collect_filtered_opts(
+        root,
+        None,
+        None,
+        None,
+        |keep, ctx| walk(root, ctx, options, keep),
+        None::<&str>,
+        &mut dlg,
+        Default::default(),
+    )?;
+    let mut skipped = std::mem::take(&mut dlg.skipped_entries);
+    skipped.sort_by(|a, b| a.0.rela_path.cmp(&b.0.rela_path));
+    Ok((dlg.into_entries_by_path(), skipped))
+}
+
+#[test]
+fn max_depth_reports_directories_that_are_not_entered() -> crate::Result {
+    let root = fixture("deep-and-long");
+    let (entries, skipped) = collect_with_skipped(
+        &root,
+        walk::Options {
+            max_depth: Some(2),
+            ..options()
+        },
+    )?;
+    assert_eq!(
+        entries,
+        [
+            entry("a/long-file-name", Untracked, File),
+            entry("top", Untracked, File),
+        ],
+        "no emitted entry has more than 2 components"
+    );
+    assert_eq!(
+        skipped,
+        [(entry("a/b", Untracked, Directory).0, walk::SkipReason::TooDeep)],
+        "the directory that wasn't entered is reported"
+    );
+    Ok(())
+}
+
+#[test]
+fn max_path_len_reports_entries_with_long_paths() -> crate::Result {
+    let root = fixture("deep-and-long");
+    let (entries, skipped) = collect_with_skipped(
+        &root,
+        walk::Options {
+            max_path_len: Some(10),
+            emit_untracked: CollapseDirectory,
+            ..options()
+        },
+    )?;
+    assert_eq!(
+        entries,
+        [entry("a/b", Untracked, Directory), entry("top", Untracked, File)],
+        "`a` can't be collapsed as it contains a skipped entry"
+    );
+    assert_eq!(
+        skipped,
+        [(
+            entry("a/long-file-name", Untracked, File).0,
+            walk::SkipReason::PathTooLong
+        )]
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore = "symlinks the way they are organized don't yet work on windows")]
+fn symlink_loops_are_reported_if_inaccessible_entries_are_skipped() -> crate::Result {
+    let root = fixture_in("many-symlinks", "symlink-loop");
+    let (entries, skipped) = collect_with_skipped(&root, options())?;
+    assert_eq!(
+        entries,
+        [
+            entry("dangling", Untracked, Symlink),
+            entry("file", Untracked, File),
+            entry("loop", Untracked, Symlink),
+        ],
+        "by default, symlink loops are emitted like any other symlink"
+    );
+    assert!(skipped.is_empty());
+
+    let (entries, skipped) = collect_with_skipped(
+        &root,
+        walk::Options {
+            skip_inaccessible: true,
+            ..options()
+        },
+    )?;
+    assert_eq!(
+        entries,
+        [entry("dangling", Untracked, Symlink), entry("file", Untracked, File),],
+        "dangling symlinks are still emitted"
+    );
+    assert_eq!(
+        skipped,
+        [(entry("loop", Untracked, Symlink).0, walk::SkipReason::SymlinkLoop)]
+    );
+    Ok(())
+}
@@ -23,7 +23,7 @@ pub fn options_emit_all() -> walk::Options {
     walk::Options {
         precompose_unicode: false,
         ignore_case: false,
-        recurse_repositories: false,
+        nested_repositories: Default::default(),
         for_deletion: None,
         classify_untracked_bare_repositories: false,
         emit_pruned: true,
@@ -33,6 +33,10 @@ pub fn options_emit_all() -> walk::Options {
         emit_empty_directories: true,
         emit_collapsed: None,
         symlinks_to_directories_are_ignored_like_directories: false,
+        one_file_system: false,
+        max_depth: None,
+        max_path_len: None,
+        skip_inaccessible: false,
     }
 }
 
@@ -378,4 +382,4 @@ impl<'a> Default for Options<'a> {
     }
 }
 
-type Entries = Vec<(Entry, Option<entry::Status>)>;
+pub type Entries = Vec<(Entry, Option<entry::Status>)>;
@@ -0,0 +1,204 @@
+//! A [`Progress`] implementation which produces a stream of machine-readable [`Event`]s.
+//!
+//! It's meant for applications like GUIs which want to render progress of long-running operations themselves,
+//! without having to parse human-readable messages. Each operation identifies itself with a stable [`Id`],
+//! like `*b"FERP"` for remote progress during a fetch, which applications can match on.
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::progress::{Count, Id, MessageLevel, NestedProgress, Progress, Step, StepShared, Unit, UNKNOWN};
+
+static NEXT_TASK: AtomicUsize = AtomicUsize::new(0);
+
+/// A task-specific event produced by a [`Events`] progress instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// A process-wide unique number identifying the task that produced this event, stable for its lifetime.
+    pub task: usize,
+    /// The task number of the parent task, or `None` if this is a top-level task.
+    pub parent: Option<usize>,
+    /// The stable identifier of the operation performed by the task, or [`UNKNOWN`] if it wasn't set.
+    pub id: Id,
+    /// What happened.
+    pub kind: Kind,
+}
+
+/// The kind of [`Event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// The task was created with the given `name`.
+    Added {
+        /// The name of the task.
+        name: String,
+    },
+    /// The task was initialized, typically right before work is started.
+    Init {
+        /// The amount of steps to be done, or `None` if unknown.
+        max: Option<Step>,
+        /// The unit of steps, like `objects` or `B` for bytes, or `None` if there is no unit.
+        unit: Option<String>,
+    },
+    /// The task was renamed.
+    Renamed {
+        /// The new name of the task.
+        name: String,
+    },
+    /// The amount of steps to be done changed.
+    Max(Option<Step>),
+    /// The current step changed.
+    ///
+    /// Note that steps advanced by means of the [shared counter](Count::counter()) don't cause events, but are
+    /// picked up by the next event of this kind or once the task is [done](Kind::Done).
+    Step(Step),
+    /// A message was emitted.
+    Message {
+        /// The severity of the message.
+        level: MessageLevel,
+        /// The human-readable message itself.
+        message: String,
+    },
+    /// The task was dropped, indicating that no more events will follow.
+    Done {
+        /// The final step of the task.
+        step: Step,
+    },
+}
+
+/// A function receiving all events of a tree of [`Events`] progress instances.
+pub type Sink = Arc<dyn Fn(Event) + Send + Sync>;
+
+/// A [`Progress`] implementation that forwards all state changes as [`Event`] to a [sink](Sink).
+///
+/// Children created with [`NestedProgress::add_child()`] share the same sink, and are associated with their parent.
+pub struct Events {
+    task: usize,
+    parent: Option<usize>,
+    id: Id,
+    name: String,
+    max: Option<Step>,
+    unit: Option<Unit>,
+    step: StepShared,
+    sink: Sink,
+}
+
+impl Events {
+    /// Create a new top-level task called `name` which passes all of its events and those of its children to `sink`.
+    pub fn new(name: impl Into<String>, sink: impl Fn(Event) + Send + Sync + 'static) -> Self {
+        Self::new_inner(name.into(), UNKNOWN, None, Arc::new(sink))
+    }
+
+    fn new_inner(name: String, id: Id, parent: Option<usize>, sink: Sink) -> Self {
+        let progress = Events {
+            task: NEXT_TASK.fetch_add(1, Ordering::Relaxed),
+            parent,
+            id,
+            name,
+            max: None,
+            unit: None,
+            step: Default::default(),
+            sink,
+        };
+        progress.emit(Kind::Added {
+            name: progress.name.clone(),
+        });
+        progress
+    }
+
+    /// Return the process-wide unique number of this task, as used in [`Event::task`].
+    pub fn task(&self) -> usize {
+        self.task
+    }
+
+    fn emit(&self, kind: Kind) {
+        (self.sink)(Event {
+            task: self.task,
+            parent: self.parent,
+            id: self.id,
+            kind,
+        })
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.emit(Kind::Done { step: self.step() });
+    }
+}
+
+impl Count for Events {
+    fn set(&self, step: Step) {
+        self.step.store(step, Ordering::Relaxed);
+        self.emit(Kind::Step(step));
+    }
+
+    fn step(&self) -> Step {
+        self.step.load(Ordering::Relaxed)
+    }
+
+    fn inc_by(&self, step: Step) {
+        let step = self.step.fetch_add(step, Ordering::Relaxed) + step;
+        self.emit(Kind::Step(step));
+    }
+
+    fn counter(&self) -> StepShared {
+        self.step.clone()
+    }
+}
+
+impl Progress for Events {
+    fn init(&mut self, max: Option<Step>, unit: Option<Unit>) {
+        self.max = max;
+        let unit_label = unit.as_ref().and_then(|unit| {
+            let mut buf = String::new();
+            unit.as_display_value().display_unit(&mut buf, max.unwrap_or(0)).ok()?;
+            (!buf.is_empty()).then_some(buf)
+        });
+        self.unit = unit;
+        self.emit(Kind::Init { max, unit: unit_label });
+    }
+
+    fn unit(&self) -> Option<Unit> {
+        self.unit.clone()
+    }
+
+    fn max(&self) -> Option<Step> {
+        self.max
+    }
+
+    fn set_max(&mut self, max: Option<Step>) -> Option<Step> {
+        let previous = std::mem::replace(&mut self.max, max);
+        self.emit(Kind::Max(max));
+        previous
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name.clone_from(&name);
+        self.emit(Kind::Renamed { name });
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn message(&self, level: MessageLevel, message: String) {
+        self.emit(Kind::Message { level, message });
+    }
+}
+
+impl NestedProgress for Events {
+    type SubProgress = Events;
+
+    fn add_child(&mut self, name: impl Into<String>) -> Self::SubProgress {
+        self.add_child_with_id(name, UNKNOWN)
+    }
+
+    fn add_child_with_id(&mut self, name: impl Into<String>, id: Id) -> Self::SubProgress {
+        Events::new_inner(name.into(), id, Some(self.task), self.sink.clone())
+    }
+}
@@ -12,6 +12,12 @@ pub use prodash::{
     unit, BoxedDynNestedProgress, Count, DynNestedProgress, DynNestedProgressToNestedProgress, NestedProgress,
     Progress, Unit,
 };
+
+///
+#[allow(clippy::empty_docs)]
+pub mod events;
+pub use events::Events;
+
 /// A stub for the portions of the `bytesize` crate that we use internally in `gitoxide`.
 #[cfg(not(feature = "progress-unit-bytes"))]
 pub mod bytesize {
@@ -7,6 +7,7 @@ const BUF_SIZE: usize = 4096 * 8;
 /// Be sure to call `flush()` when done to finalize the deflate stream.
 pub struct Write<W> {
     compressor: Compress,
+    level: flate2::Compression,
     inner: W,
     buf: [u8; BUF_SIZE],
 }
@@ -17,7 +18,8 @@ where
 {
     fn clone(&self) -> Self {
         Write {
-            compressor: impls::new_compress(),
+            compressor: impls::new_compress(self.level),
+            level: self.level,
             inner: self.inner.clone(),
             buf: self.buf,
         }
@@ -31,18 +33,26 @@ mod impls {
 
     use crate::zlib::stream::deflate;
 
-    pub(crate) fn new_compress() -> Compress {
-        Compress::new(Compression::fast(), true)
+    pub(crate) fn new_compress(level: Compression) -> Compress {
+        Compress::new(level, true)
     }
 
     impl<W> deflate::Write<W>
     where
         W: io::Write,
     {
-        /// Create a new instance writing compressed bytes to `inner`.
+        /// Create a new instance writing compressed bytes to `inner`, favoring speed over compression ratio.
         pub fn new(inner: W) -> deflate::Write<W> {
+            Self::with_compression_level(inner, Compression::fast().level())
+        }
+
+        /// Create a new instance writing compressed bytes to `inner` with the given compression `level` from 0 to 9,
+        /// where 0 means no compression and 9 means the best compression.
+        pub fn with_compression_level(inner: W, level: u32) -> deflate::Write<W> {
+            let level = Compression::new(level);
             deflate::Write {
-                compressor: new_compress(),
+                compressor: new_compress(level),
+                level,
                 inner,
                 buf: [0; deflate::BUF_SIZE],
             }
@@ -1 +1,3 @@
+#[cfg(feature = "progress")]
+mod progress;
 mod trace;
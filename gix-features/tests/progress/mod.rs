@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+
+use gix_features::progress::{
+    events::{Event, Kind},
+    Count, Events, MessageLevel, NestedProgress, Progress,
+};
+
+#[test]
+fn events_are_produced_for_each_state_change_of_tasks_and_their_children() {
+    let events = Arc::new(Mutex::new(Vec::<Event>::new()));
+    let mut root = Events::new("root", {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    });
+    let mut child = root.add_child_with_id("child", *b"TEST");
+    child.init(Some(10), gix_features::progress::count("objects"));
+    child.inc_by(2);
+    child.counter().fetch_add(3, std::sync::atomic::Ordering::Relaxed);
+    child.info("hello".into());
+    let child_task = child.task();
+    drop(child);
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    let kinds: Vec<_> = events
+        .iter()
+        .map(|e| (e.task, e.parent, e.id, e.kind.clone()))
+        .collect();
+    let root_task = root.task();
+    assert_eq!(
+        kinds,
+        vec![
+            (root_task, None, *b"\0\0\0\0", Kind::Added { name: "root".into() }),
+            (
+                child_task,
+                Some(root_task),
+                *b"TEST",
+                Kind::Added { name: "child".into() }
+            ),
+            (
+                child_task,
+                Some(root_task),
+                *b"TEST",
+                Kind::Init {
+                    max: Some(10),
+                    unit: Some("objects".into())
+                }
+            ),
+            (child_task, Some(root_task), *b"TEST", Kind::Step(2)),
+            (
+                child_task,
+                Some(root_task),
+                *b"TEST",
+                Kind::Message {
+                    level: MessageLevel::Info,
+                    message: "hello".into()
+                }
+            ),
+            (child_task, Some(root_task), *b"TEST", Kind::Done { step: 5 },),
+        ],
+        "updates of the shared counter are visible once the task is done"
+    );
+}
@@ -1,2 +1,77 @@
-#![deny(rust_2018_idioms)]
+//! Read and write `.git/FETCH_HEAD`, the file in which `git fetch` records the tips of all references it fetched.
+//!
+//! Use [`parse()`] to read [entries](Entry) back, and [`Entry::write_to()`] to produce lines `git` can read.
+#![deny(missing_docs, rust_2018_idioms)]
 #![forbid(unsafe_code)]
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_hash::ObjectId;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse;
+
+/// Parse the given `buf` with the contents of a `FETCH_HEAD` file line by line into [entries](Entry).
+///
+/// Errors may occur per line, but it's up to the caller to stop iteration when one is encountered.
+pub fn parse(buf: &[u8]) -> parse::Lines<'_> {
+    parse::Lines::new(buf)
+}
+
+/// A line in a `FETCH_HEAD` file, representing the tip of a single fetched reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The object the fetched reference pointed to.
+    pub id: ObjectId,
+    /// If `true`, the object should be merged by `git pull`, or it's `not-for-merge` otherwise.
+    pub for_merge: bool,
+    /// A description of where the object came from, like `branch 'main' of https://example.com/repo`,
+    /// typically created with [`description()`].
+    pub description: BString,
+}
+
+impl Entry {
+    /// Write this entry as line to `out`, the way `git` does it.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        write!(out, "{}\t", self.id)?;
+        if !self.for_merge {
+            out.write_all(b"not-for-merge")?;
+        }
+        out.write_all(b"\t")?;
+        out.write_all(&self.description)?;
+        out.write_all(b"\n")
+    }
+}
+
+/// Produce the description of an [entry](Entry) for the fetched reference `name` on the remote at `url` like `git` does,
+/// for example `branch 'main' of https://example.com/repo` for `refs/heads/main`.
+///
+/// `name` may also be a hexadecimal object id if an object was fetched by id, and `url` should not contain credentials.
+/// Trailing slashes and a trailing `.git` are removed from `url`.
+pub fn description(name: &BStr, url: &BStr) -> BString {
+    let (kind, what) = if name == "HEAD" {
+        (None, None)
+    } else if let Some(branch) = name.strip_prefix(b"refs/heads/") {
+        (Some("branch"), Some(branch))
+    } else if let Some(tag) = name.strip_prefix(b"refs/tags/") {
+        (Some("tag"), Some(tag))
+    } else if let Some(remote_branch) = name.strip_prefix(b"refs/remotes/") {
+        (Some("remote-tracking branch"), Some(remote_branch))
+    } else {
+        (None, Some(name.as_bytes()))
+    };
+
+    let mut out = BString::default();
+    if let Some(kind) = kind {
+        out.extend_from_slice(kind.as_bytes());
+        out.push(b' ');
+    }
+    if let Some(what) = what {
+        out.push(b'\'');
+        out.extend_from_slice(what);
+        out.extend_from_slice(b"' of ");
+    }
+    let url = url.trim_end_with(|c| c == '/');
+    out.extend_from_slice(url.strip_suffix(b".git").unwrap_or(url));
+    out
+}
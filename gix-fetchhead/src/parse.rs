@@ -0,0 +1,80 @@
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`parse()`][crate::parse()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number} is not of the form '<object-id>\\t[not-for-merge]\\t<description>': {line:?}")]
+        Malformed { line_number: usize, line: BString },
+        #[error("Line {line_number} does not start with a valid object id: {line:?}")]
+        ObjectId {
+            line_number: usize,
+            line: BString,
+            source: gix_hash::decode::Error,
+        },
+    }
+}
+
+use bstr::{BStr, ByteSlice};
+pub use error::Error;
+use gix_hash::ObjectId;
+
+use crate::Entry;
+
+/// An iterator to parse the lines of a `FETCH_HEAD` file on-demand.
+pub struct Lines<'a> {
+    lines: bstr::Lines<'a>,
+    line_no: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Lines {
+            lines: input.as_bstr().lines(),
+            line_no: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in self.lines.by_ref() {
+            self.line_no += 1;
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return Some(parse_line(line.as_bstr(), self.line_no));
+        }
+        None
+    }
+}
+
+fn parse_line(line: &BStr, line_number: usize) -> Result<Entry, Error> {
+    let malformed = || Error::Malformed {
+        line_number,
+        line: line.to_owned(),
+    };
+    let mut tokens = line.splitn_str(3, b"\t");
+    let (id, marker, description) = match (tokens.next(), tokens.next(), tokens.next()) {
+        (Some(id), Some(marker), Some(description)) => (id, marker, description),
+        _ => return Err(malformed()),
+    };
+    let id = ObjectId::from_hex(id).map_err(|err| Error::ObjectId {
+        line_number,
+        line: line.to_owned(),
+        source: err,
+    })?;
+    let for_merge = match marker {
+        b"" => true,
+        b"not-for-merge" => false,
+        _ => return Err(malformed()),
+    };
+    Ok(Entry {
+        id,
+        for_merge,
+        description: description.into(),
+    })
+}
@@ -0,0 +1,83 @@
+use gix_fetchhead::{parse, Entry};
+use gix_hash::ObjectId;
+
+fn id(hex: &str) -> ObjectId {
+    ObjectId::from_hex(hex.as_bytes()).expect("valid hex")
+}
+
+const TYPICAL: &str = "\
+a2a6f3fcfd1e4e0f2d4f3b5c6d7e8f9a0b1c2d3e\t\tbranch 'main' of https://example.com/repo
+1111111111111111111111111111111111111111\tnot-for-merge\tbranch 'feature' of https://example.com/repo
+2222222222222222222222222222222222222222\tnot-for-merge\ttag 'v1.0' of https://example.com/repo
+";
+
+#[test]
+fn a_typical_file() {
+    let actual = parse(TYPICAL.as_bytes()).map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(
+        actual,
+        vec![
+            Entry {
+                id: id("a2a6f3fcfd1e4e0f2d4f3b5c6d7e8f9a0b1c2d3e"),
+                for_merge: true,
+                description: "branch 'main' of https://example.com/repo".into(),
+            },
+            Entry {
+                id: id("1111111111111111111111111111111111111111"),
+                for_merge: false,
+                description: "branch 'feature' of https://example.com/repo".into(),
+            },
+            Entry {
+                id: id("2222222222222222222222222222222222222222"),
+                for_merge: false,
+                description: "tag 'v1.0' of https://example.com/repo".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn entries_write_the_lines_they_were_parsed_from() {
+    let mut out = Vec::new();
+    for entry in parse(TYPICAL.as_bytes()) {
+        entry.unwrap().write_to(&mut out).unwrap();
+    }
+    assert_eq!(out, TYPICAL.as_bytes());
+}
+
+#[test]
+fn empty_lines_are_ignored_and_errors_carry_line_numbers() {
+    let input =
+        b"\n1111111111111111111111111111111111111111\tmaybe\tsomething\nnot-a-hash\t\tdescription\nincomplete\n";
+    let mut actual = parse(input).collect::<Vec<_>>().into_iter();
+    assert_eq!(actual.len(), 3);
+    assert!(matches!(
+        actual.next().unwrap().unwrap_err(),
+        gix_fetchhead::parse::Error::Malformed { line_number: 2, .. }
+    ));
+    assert!(matches!(
+        actual.next().unwrap().unwrap_err(),
+        gix_fetchhead::parse::Error::ObjectId { line_number: 3, .. }
+    ));
+    assert!(matches!(
+        actual.next().unwrap().unwrap_err(),
+        gix_fetchhead::parse::Error::Malformed { line_number: 4, .. }
+    ));
+}
+
+#[test]
+fn description() {
+    let url = "https://example.com/repo.git/".into();
+    for (name, expected) in [
+        ("refs/heads/main", "branch 'main' of https://example.com/repo"),
+        ("refs/tags/v1.0", "tag 'v1.0' of https://example.com/repo"),
+        (
+            "refs/remotes/origin/main",
+            "remote-tracking branch 'origin/main' of https://example.com/repo",
+        ),
+        ("refs/changes/1", "'refs/changes/1' of https://example.com/repo"),
+        ("HEAD", "https://example.com/repo"),
+    ] {
+        assert_eq!(gix_fetchhead::description(name.into(), url), expected, "{name}");
+    }
+}
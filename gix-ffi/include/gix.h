@@ -0,0 +1,143 @@
+/*
+ * The C ABI of gitoxide, as exported by the `gix-ffi` crate.
+ *
+ * Fallible functions return GIX_OK on success or a negative error code, with
+ * gix_error_last() describing the failure. Functions taking a callback stop as
+ * soon as it returns non-zero, and return that value.
+ *
+ * Strings are NUL-terminated and, unless stated otherwise, only valid for the
+ * duration of a callback or until the owning handle is freed.
+ */
+#ifndef GIX_H
+#define GIX_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Error codes */
+
+#define GIX_OK 0
+#define GIX_ERROR -1
+#define GIX_EINVALID -2
+#define GIX_ENOTFOUND -3
+#define GIX_EUSER -4
+#define GIX_EAMBIGUOUS -5
+
+/* The message of the most recent error on the calling thread, or NULL if the last call succeeded. */
+const char *gix_error_last(void);
+
+/* Object ids */
+
+typedef struct gix_oid {
+    uint8_t id[20];
+} gix_oid;
+
+int gix_oid_fromstr(gix_oid *out, const char *hex);
+/* Write at most `size - 1` hex characters of `oid` followed by NUL into `out`. */
+int gix_oid_tostr(char *out, size_t size, const gix_oid *oid);
+
+/* Repositories */
+
+typedef struct gix_repository gix_repository;
+
+int gix_repository_open(gix_repository **out, const char *path);
+int gix_repository_discover(gix_repository **out, const char *path);
+void gix_repository_free(gix_repository *repo);
+const char *gix_repository_git_dir(const gix_repository *repo);
+/* NULL for bare repositories. */
+const char *gix_repository_workdir(const gix_repository *repo);
+
+/* References and revisions */
+
+/* `target` is NULL if the reference can't be resolved, `symbolic_target` is NULL unless the reference is symbolic. */
+typedef int (*gix_reference_foreach_cb)(const char *name, const gix_oid *target, const char *symbolic_target,
+                                        void *payload);
+
+/* `prefix` may be NULL to visit all references. */
+int gix_reference_foreach(const gix_repository *repo, const char *prefix, gix_reference_foreach_cb cb, void *payload);
+int gix_reference_name_to_id(gix_oid *out, const gix_repository *repo, const char *name);
+int gix_revparse_single(gix_oid *out, const gix_repository *repo, const char *spec);
+
+/* History */
+
+typedef struct gix_signature {
+    const char *name;
+    const char *email;
+    int64_t time;
+    int offset;
+} gix_signature;
+
+typedef struct gix_commit {
+    gix_oid id;
+    gix_oid tree_id;
+    const gix_oid *parent_ids;
+    size_t parent_count;
+    gix_signature author;
+    gix_signature committer;
+    const char *message;
+} gix_commit;
+
+typedef int (*gix_log_foreach_cb)(const gix_commit *commit, void *payload);
+
+/* `spec` may be NULL to start at HEAD. */
+int gix_log_foreach(const gix_repository *repo, const char *spec, gix_log_foreach_cb cb, void *payload);
+
+/* Diffs */
+
+typedef enum gix_delta_t {
+    GIX_DELTA_ADDED = 1,
+    GIX_DELTA_DELETED = 2,
+    GIX_DELTA_MODIFIED = 3,
+    GIX_DELTA_RENAMED = 4,
+    GIX_DELTA_COPIED = 5,
+} gix_delta_t;
+
+typedef struct gix_diff_delta {
+    gix_delta_t status;
+    const char *old_path;
+    const char *new_path;
+    gix_oid old_id;
+    gix_oid new_id;
+    uint32_t old_mode;
+    uint32_t new_mode;
+} gix_diff_delta;
+
+typedef int (*gix_diff_delta_cb)(const gix_diff_delta *delta, void *payload);
+
+/* `old` and `new` may be commits or trees, or NULL for the empty tree. */
+int gix_diff_tree_to_tree(const gix_repository *repo, const gix_oid *old, const gix_oid *new, gix_diff_delta_cb cb,
+                          void *payload);
+
+/* Fetching, only available with the `blocking-network-client` feature. All callbacks run on the calling thread. */
+
+#define GIX_FETCH_OPTIONS_VERSION 1
+
+typedef struct gix_credentials gix_credentials;
+
+/* Copies `username` and `password`. Only valid on the `out` argument of a gix_credentials_cb. */
+int gix_credentials_set(gix_credentials *cred, const char *username, const char *password);
+
+/* `done` is 0 if unknown. */
+typedef int (*gix_fetch_progress_cb)(const char *name, size_t step, size_t done, void *payload);
+/* `username` is NULL if unknown. Call gix_credentials_set() on `out`. */
+typedef int (*gix_credentials_cb)(gix_credentials *out, const char *url, const char *username, void *payload);
+
+typedef struct gix_fetch_options {
+    uint32_t version;
+    gix_fetch_progress_cb progress_cb;
+    gix_credentials_cb credentials_cb;
+    void *payload;
+} gix_fetch_options;
+
+/* `opts` may be NULL. */
+int gix_remote_fetch(const gix_repository *repo, const char *remote, const gix_fetch_options *opts);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GIX_H */
@@ -0,0 +1,146 @@
+//! Diffing trees.
+use std::ffi::{c_char, c_int, c_void};
+
+use crate::{
+    error::{c_string, guard, Error, GIX_EINVALID, GIX_OK},
+    repository::repo_arg,
+    Oid, Repository,
+};
+
+/// The kind of change of a [`DiffDelta`], `gix_delta_t` in C.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeltaStatus {
+    /// The entry was added.
+    Added = 1,
+    /// The entry was deleted.
+    Deleted = 2,
+    /// The content or mode of the entry changed.
+    Modified = 3,
+    /// The entry was renamed, possibly with changes to its content.
+    Renamed = 4,
+    /// The entry was copied, possibly with changes to its content.
+    Copied = 5,
+}
+
+/// A change to a single file, as passed to the callback of [`gix_diff_tree_to_tree()`], `gix_diff_delta` in C.
+///
+/// For additions, `old_id` is all zeroes and `old_mode` is `0`, and the same is true for `new_id` and `new_mode` of deletions.
+/// The paths are the same unless the entry was renamed or copied.
+#[repr(C)]
+pub struct DiffDelta {
+    /// The kind of change.
+    pub status: DeltaStatus,
+    /// The path before the change.
+    pub old_path: *const c_char,
+    /// The path after the change.
+    pub new_path: *const c_char,
+    /// The id of the blob before the change.
+    pub old_id: Oid,
+    /// The id of the blob after the change.
+    pub new_id: Oid,
+    /// The mode of the entry before the change, like `0o100644`.
+    pub old_mode: u32,
+    /// The mode of the entry after the change.
+    pub new_mode: u32,
+}
+
+/// The callback for [`gix_diff_tree_to_tree()`], `gix_diff_delta_cb` in C.
+pub type DiffDeltaCb = Option<extern "C" fn(delta: *const DiffDelta, payload: *mut c_void) -> c_int>;
+
+/// Call `cb` with each file that changed between the trees of `old` and `new`, which may be commits or trees,
+/// or null for the empty tree. Renames and copies are detected as configured with `diff.renames`.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle and `old` and `new` must each be null or point to a valid `Oid`.
+#[no_mangle]
+pub unsafe extern "C" fn gix_diff_tree_to_tree(
+    repo: *const Repository,
+    old: *const Oid,
+    new: *const Oid,
+    cb: DiffDeltaCb,
+    payload: *mut c_void,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (repo, old, new) = unsafe { (repo_arg(repo)?, old.as_ref(), new.as_ref()) };
+        let cb = cb.ok_or_else(|| Error::new(GIX_EINVALID, "argument 'cb' must not be null"))?;
+        let tree = |id: Option<&Oid>| -> Result<gix::Tree<'_>, Error> {
+            Ok(match id {
+                Some(id) => repo
+                    .find_object(gix::ObjectId::from(*id))?
+                    .peel_to_kind(gix::object::Kind::Tree)
+                    .map_err(|err| Error::from(err).with_code(GIX_EINVALID))?
+                    .into_tree(),
+                None => repo.empty_tree(),
+            })
+        };
+        let (old, new) = (tree(old)?, tree(new)?);
+
+        let mut res = GIX_OK;
+        let outcome = old.changes()?.track_path().for_each_to_obtain_tree(&new, |change| {
+            use gix::object::tree::diff::change::Event;
+            let none = (gix::object::tree::EntryMode(0), Oid::default());
+            let (status, old_path, (old_mode, old_id), (new_mode, new_id)) = match change.event {
+                Event::Addition { entry_mode, id } => (DeltaStatus::Added, None, none, (entry_mode, id.into())),
+                Event::Deletion { entry_mode, id } => (DeltaStatus::Deleted, None, (entry_mode, id.into()), none),
+                Event::Modification {
+                    previous_entry_mode,
+                    previous_id,
+                    entry_mode,
+                    id,
+                } => (
+                    DeltaStatus::Modified,
+                    None,
+                    (previous_entry_mode, previous_id.into()),
+                    (entry_mode, id.into()),
+                ),
+                Event::Rewrite {
+                    source_location,
+                    source_entry_mode,
+                    source_id,
+                    entry_mode,
+                    id,
+                    copy,
+                    ..
+                } => (
+                    if copy {
+                        DeltaStatus::Copied
+                    } else {
+                        DeltaStatus::Renamed
+                    },
+                    Some(source_location),
+                    (source_entry_mode, source_id.into()),
+                    (entry_mode, id.into()),
+                ),
+            };
+            if old_mode.is_tree() || new_mode.is_tree() {
+                return Ok::<_, std::convert::Infallible>(gix::object::tree::diff::Action::Continue);
+            }
+            let new_path = c_string(change.location.to_vec());
+            let old_path = old_path.map(|path| c_string(path.to_vec()));
+            let delta = DiffDelta {
+                status,
+                old_path: old_path.as_ref().unwrap_or(&new_path).as_ptr(),
+                new_path: new_path.as_ptr(),
+                old_id,
+                new_id,
+                old_mode: old_mode.0.into(),
+                new_mode: new_mode.0.into(),
+            };
+            res = cb(&delta, payload);
+            Ok(if res == 0 {
+                gix::object::tree::diff::Action::Continue
+            } else {
+                gix::object::tree::diff::Action::Cancel
+            })
+        });
+        match outcome {
+            // The callback asked to stop, which surfaces as cancellation.
+            Err(_) if res != 0 => Ok(res),
+            Err(err) => Err(err.into()),
+            Ok(_) => Ok(GIX_OK),
+        }
+    })
+}
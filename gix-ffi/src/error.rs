@@ -0,0 +1,133 @@
+//! Error codes and access to the message of the last error.
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr, CString},
+    panic::AssertUnwindSafe,
+};
+
+use gix::bstr::BStr;
+
+/// The operation succeeded.
+pub const GIX_OK: c_int = 0;
+/// The operation failed for reasons not covered by a more specific code.
+pub const GIX_ERROR: c_int = -1;
+/// An argument was invalid, like a null pointer where a value is required, or a malformed revision specification.
+pub const GIX_EINVALID: c_int = -2;
+/// The requested repository, object, reference or remote doesn't exist.
+pub const GIX_ENOTFOUND: c_int = -3;
+/// A value for callbacks to return to abort an operation, which then returns it.
+pub const GIX_EUSER: c_int = -4;
+/// A revision specification or short id matched more than one object.
+pub const GIX_EAMBIGUOUS: c_int = -5;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Return the message of the error produced by the most recent call on the calling thread,
+/// or null if that call succeeded.
+///
+/// The string remains valid until the next call into the library on the same thread.
+#[no_mangle]
+pub extern "C" fn gix_error_last() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// An error along with the code to return for it.
+pub(crate) struct Error {
+    code: c_int,
+    message: String,
+}
+
+impl Error {
+    pub(crate) fn new(code: c_int, message: impl Into<String>) -> Self {
+        Error {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn with_code(mut self, code: c_int) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+impl<E: std::error::Error> From<E> for Error {
+    fn from(err: E) -> Self {
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(err) = source {
+            message.push_str(": ");
+            message.push_str(&err.to_string());
+            source = err.source();
+        }
+        Error {
+            code: GIX_ERROR,
+            message,
+        }
+    }
+}
+
+/// Run `f` and turn its result into a code to return, keeping the error message around for [`gix_error_last()`].
+/// Panics are caught as they must not unwind into foreign code.
+pub(crate) fn guard(f: impl FnOnce() -> Result<c_int, Error>) -> c_int {
+    let res = std::panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err(Error::new(GIX_ERROR, "a panic occurred, which is a bug")));
+    let (code, message) = match res {
+        Ok(code) => (code, None),
+        Err(err) => (err.code, Some(c_string(err.message))),
+    };
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    code
+}
+
+/// Convert `bytes` into a C string, truncating it at the first NUL byte.
+pub(crate) fn c_string(bytes: impl Into<Vec<u8>>) -> CString {
+    let mut bytes = bytes.into();
+    if let Some(pos) = bytes.iter().position(|b| *b == 0) {
+        bytes.truncate(pos);
+    }
+    CString::new(bytes).expect("no interior NUL")
+}
+
+/// Obtain the string at `ptr`, or fail if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string that outlives `'a`.
+pub(crate) unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a BStr, Error> {
+    if ptr.is_null() {
+        return Err(null_arg(name));
+    }
+    // SAFETY: non-null, and valid as guaranteed by the caller.
+    Ok(unsafe { CStr::from_ptr(ptr) }.to_bytes().into())
+}
+
+/// Obtain the value at `ptr`, or fail if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid instance that outlives `'a`.
+pub(crate) unsafe fn ref_arg<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, Error> {
+    // SAFETY: aligned and valid if non-null, as guaranteed by the caller.
+    unsafe { ptr.as_ref() }.ok_or_else(|| null_arg(name))
+}
+
+/// Obtain the value at `ptr` for writing, or fail if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid instance that isn't aliased and outlives `'a`.
+pub(crate) unsafe fn mut_arg<'a, T>(ptr: *mut T, name: &str) -> Result<&'a mut T, Error> {
+    // SAFETY: aligned, valid and unaliased if non-null, as guaranteed by the caller.
+    unsafe { ptr.as_mut() }.ok_or_else(|| null_arg(name))
+}
+
+fn null_arg(name: &str) -> Error {
+    Error::new(GIX_EINVALID, format!("argument '{name}' must not be null"))
+}
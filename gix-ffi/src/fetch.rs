@@ -0,0 +1,282 @@
+//! Fetching from remotes, with callbacks for progress and credentials.
+//!
+//! The fetch itself runs on a separate thread, but all callbacks are invoked on the calling thread.
+use std::{
+    collections::HashMap,
+    ffi::{c_char, c_int, c_void},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::Duration,
+};
+
+use gix::{
+    bstr::{BStr, ByteSlice},
+    progress::prodash::{progress::Key, tree},
+};
+
+use crate::{
+    error::{c_string, guard, mut_arg, str_arg, Error, GIX_EINVALID, GIX_ENOTFOUND, GIX_OK},
+    repository::repo_arg,
+    Repository,
+};
+
+/// The version of [`FetchOptions`] understood by this library.
+pub const GIX_FETCH_OPTIONS_VERSION: u32 = 1;
+
+/// How often progress is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Credentials to fill in by the [`CredentialsCb`], `gix_credentials` in C.
+#[derive(Default)]
+pub struct Credentials {
+    username: Option<String>,
+    password: Option<String>,
+}
+
+/// Set the `username` and `password` to authenticate with. Both strings are copied.
+///
+/// # Safety
+///
+/// `cred` must be the pointer passed to a [`CredentialsCb`], and `username` and `password` must point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn gix_credentials_set(
+    cred: *mut Credentials,
+    username: *const c_char,
+    password: *const c_char,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (cred, username, password) = unsafe {
+            (
+                mut_arg(cred, "cred")?,
+                str_arg(username, "username")?,
+                str_arg(password, "password")?,
+            )
+        };
+        cred.username = Some(utf8(username, "username")?);
+        cred.password = Some(utf8(password, "password")?);
+        Ok(GIX_OK)
+    })
+}
+
+/// The callback for progress during [`gix_remote_fetch()`], `gix_fetch_progress_cb` in C.
+///
+/// It receives the `name` of a task, like `read pack`, the `step` it is currently at and the step at which it will be `done`,
+/// which is `0` if unknown. Returning non-zero interrupts the fetch.
+pub type ProgressCb =
+    Option<extern "C" fn(name: *const c_char, step: usize, done: usize, payload: *mut c_void) -> c_int>;
+
+/// The callback to obtain credentials during [`gix_remote_fetch()`], `gix_credentials_cb` in C.
+///
+/// It receives the `url` to authenticate with and the `username` if it is already known or null otherwise,
+/// and should call [`gix_credentials_set()`] on `out`. Returning non-zero aborts the fetch.
+pub type CredentialsCb = Option<
+    extern "C" fn(out: *mut Credentials, url: *const c_char, username: *const c_char, payload: *mut c_void) -> c_int,
+>;
+
+/// Options for [`gix_remote_fetch()`], `gix_fetch_options` in C.
+#[repr(C)]
+pub struct FetchOptions {
+    /// Must be [`GIX_FETCH_OPTIONS_VERSION`].
+    pub version: u32,
+    /// Called with progress information, or null.
+    pub progress_cb: ProgressCb,
+    /// Called when the remote requires authentication, or null to use the configured credential helpers.
+    pub credentials_cb: CredentialsCb,
+    /// Passed to all callbacks.
+    pub payload: *mut c_void,
+}
+
+/// A request from the thread performing the fetch to the calling thread.
+enum Request {
+    Credentials {
+        url: String,
+        username: Option<String>,
+        reply: mpsc::Sender<Result<Credentials, c_int>>,
+    },
+}
+
+/// Fetch from the remote named `remote` as configured and update its tracking references.
+/// Returns `GIX_ENOTFOUND` if there is no such remote.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle, `remote` must point to a NUL-terminated string and
+/// `opts` must be null or point to valid options.
+#[no_mangle]
+pub unsafe extern "C" fn gix_remote_fetch(
+    repo: *const Repository,
+    remote: *const c_char,
+    opts: *const FetchOptions,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (repo, remote, opts) = unsafe { (repo_arg(repo)?, str_arg(remote, "remote")?, opts.as_ref()) };
+        let (progress_cb, credentials_cb, payload) = match opts {
+            Some(opts) if opts.version != GIX_FETCH_OPTIONS_VERSION => {
+                return Err(Error::new(
+                    GIX_EINVALID,
+                    format!(
+                        "fetch options have version {}, but only version {GIX_FETCH_OPTIONS_VERSION} is supported",
+                        opts.version
+                    ),
+                ))
+            }
+            Some(opts) => (opts.progress_cb, opts.credentials_cb, opts.payload),
+            None => (None, None, std::ptr::null_mut()),
+        };
+
+        let root = tree::Root::new();
+        let should_interrupt = AtomicBool::new(false);
+        let (requests_tx, requests) = mpsc::channel();
+        let sync_repo = repo.clone().into_sync();
+        std::thread::scope(|scope| {
+            let worker = scope.spawn({
+                let progress = root.add_child("fetch");
+                let should_interrupt = &should_interrupt;
+                move || {
+                    let credentials = credentials_cb.is_some().then(|| requests_tx.clone());
+                    let res = fetch(
+                        sync_repo.to_thread_local(),
+                        remote,
+                        progress,
+                        should_interrupt,
+                        credentials,
+                    );
+                    // Disconnecting the channel is what stops the calling thread from waiting for requests.
+                    drop(requests_tx);
+                    res
+                }
+            });
+
+            let mut aborted = None;
+            let mut reported = HashMap::new();
+            let mut tasks = Vec::new();
+            loop {
+                let request = requests.recv_timeout(PROGRESS_INTERVAL);
+                if let (Some(cb), None) = (progress_cb, aborted) {
+                    let res = report_progress(&root, &mut tasks, &mut reported, cb, payload);
+                    if res != 0 {
+                        aborted = Some(res);
+                        should_interrupt.store(true, Ordering::SeqCst);
+                    }
+                }
+                match request {
+                    Ok(Request::Credentials { url, username, reply }) => {
+                        let cb = credentials_cb.expect("only requested if set");
+                        let mut credentials = Credentials::default();
+                        let url = c_string(url);
+                        let username = username.map(c_string);
+                        let res = cb(
+                            &mut credentials,
+                            url.as_ptr(),
+                            username.as_ref().map_or(std::ptr::null(), |name| name.as_ptr()),
+                            payload,
+                        );
+                        if res != 0 {
+                            aborted.get_or_insert(res);
+                        }
+                        reply.send(if res == 0 { Ok(credentials) } else { Err(res) }).ok();
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            let res = worker
+                .join()
+                .unwrap_or_else(|_| Err(Error::new(crate::GIX_ERROR, "the fetch panicked, which is a bug")));
+            match (res, aborted) {
+                (Err(_), Some(code)) => Ok(code),
+                (res, _) => res.map(|()| GIX_OK),
+            }
+        })
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn fetch(
+    repo: gix::Repository,
+    name: &BStr,
+    mut progress: tree::Item,
+    should_interrupt: &AtomicBool,
+    requests: Option<mpsc::Sender<Request>>,
+) -> Result<(), Error> {
+    let remote = repo.find_remote(name).map_err(|err| {
+        let code = match err {
+            gix::remote::find::existing::Error::NotFound { .. } => GIX_ENOTFOUND,
+            _ => crate::GIX_ERROR,
+        };
+        Error::from(err).with_code(code)
+    })?;
+    let mut connection = remote.connect(gix::remote::Direction::Fetch)?;
+    if let Some(requests) = requests {
+        connection = connection.with_credentials(move |action| {
+            use gix::credentials::{helper::Action, protocol};
+            let Action::Get(ctx) = action else {
+                return Ok(None);
+            };
+            let (reply, response) = mpsc::channel();
+            requests
+                .send(Request::Credentials {
+                    url: ctx
+                        .url
+                        .as_ref()
+                        .map(|url| url.to_str_lossy().into_owned())
+                        .unwrap_or_default(),
+                    username: ctx.username.clone(),
+                    reply,
+                })
+                .map_err(|_| protocol::Error::Quit)?;
+            match response.recv() {
+                Ok(Ok(Credentials {
+                    username: Some(username),
+                    password: Some(password),
+                })) => Ok(Some(protocol::Outcome {
+                    identity: gix::sec::identity::Account { username, password },
+                    next: ctx.into(),
+                })),
+                Ok(Ok(_)) => Ok(None),
+                Ok(Err(_)) | Err(_) => Err(protocol::Error::Quit),
+            }
+        });
+    }
+    connection
+        .prepare_fetch(&mut progress, Default::default())?
+        .receive(&mut progress, should_interrupt)?;
+    Ok(())
+}
+
+/// Call `cb` for each task whose progress changed since the last call, and return the first non-zero result.
+fn report_progress(
+    root: &tree::Root,
+    tasks: &mut Vec<(Key, gix::progress::prodash::progress::Task)>,
+    reported: &mut HashMap<Key, (usize, Option<usize>)>,
+    cb: extern "C" fn(*const c_char, usize, usize, *mut c_void) -> c_int,
+    payload: *mut c_void,
+) -> c_int {
+    root.sorted_snapshot(tasks);
+    for (key, task) in tasks.iter() {
+        let Some(value) = task.progress.as_ref() else {
+            continue;
+        };
+        let current = (value.step.load(Ordering::Relaxed), value.done_at);
+        if reported.insert(*key, current) == Some(current) {
+            continue;
+        }
+        let name = c_string(task.name.as_str());
+        let res = cb(name.as_ptr(), current.0, current.1.unwrap_or(0), payload);
+        if res != 0 {
+            return res;
+        }
+    }
+    GIX_OK
+}
+
+fn utf8(s: &BStr, name: &str) -> Result<String, Error> {
+    s.to_str()
+        .map(ToOwned::to_owned)
+        .map_err(|_| Error::new(GIX_EINVALID, format!("argument '{name}' must be valid UTF-8")))
+}
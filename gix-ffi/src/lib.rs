@@ -0,0 +1,37 @@
+//! Export core operations of `gix` through a C ABI, to allow applications written in other languages to embed gitoxide
+//! instead of spawning `git` or `gix` processes.
+//!
+//! All exported functions are declared in `include/gix.h`, which is the authoritative description of the ABI.
+//!
+//! ### Conventions
+//!
+//! * Fallible functions return [`GIX_OK`] on success or a negative error code, and [`gix_error_last()`](error::gix_error_last())
+//!   describes the most recent failure on the calling thread.
+//! * Handles created by the library must be released with the matching `*_free()` function.
+//! * Strings are NUL-terminated. Strings and pointers passed to callbacks are only valid for the duration of the call.
+//! * Functions taking a callback stop once it returns a non-zero value, and return that value.
+//! * A repository handle may be sent to other threads, but must not be used by more than one thread at a time.
+//! * Panics never cross the ABI boundary, but are reported as [`GIX_ERROR`].
+//!
+//! ### Feature Flags
+//!
+//! * `blocking-network-client` - export `gix_remote_fetch()` to fetch from remotes through the `file`, `git` and `ssh` transports.
+#![deny(missing_docs, rust_2018_idioms, unsafe_op_in_unsafe_fn)]
+
+pub mod error;
+pub use error::{GIX_EAMBIGUOUS, GIX_EINVALID, GIX_ENOTFOUND, GIX_ERROR, GIX_EUSER, GIX_OK};
+
+pub mod oid;
+pub use oid::Oid;
+
+pub mod repository;
+pub use repository::Repository;
+
+pub mod reference;
+
+pub mod log;
+
+pub mod diff;
+
+#[cfg(feature = "blocking-network-client")]
+pub mod fetch;
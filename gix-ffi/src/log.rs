@@ -0,0 +1,116 @@
+//! Walking the commit history.
+use std::ffi::{c_char, c_int, c_void, CString};
+
+use crate::{
+    error::{c_string, guard, str_arg, Error, GIX_EINVALID, GIX_OK},
+    reference::rev_parse_single,
+    repository::repo_arg,
+    Oid, Repository,
+};
+
+/// The author or committer of a commit, `gix_signature` in C.
+#[repr(C)]
+pub struct Signature {
+    /// The name.
+    pub name: *const c_char,
+    /// The email address, without angle brackets.
+    pub email: *const c_char,
+    /// The time in seconds since the unix epoch.
+    pub time: i64,
+    /// The offset of the timezone from UTC in seconds, positive for timezones east of UTC.
+    pub offset: c_int,
+}
+
+/// A commit as passed to the callback of [`gix_log_foreach()`], `gix_commit` in C.
+#[repr(C)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: Oid,
+    /// The id of the tree of the commit.
+    pub tree_id: Oid,
+    /// The ids of all parents, in order.
+    pub parent_ids: *const Oid,
+    /// The amount of ids in `parent_ids`.
+    pub parent_count: usize,
+    /// The author.
+    pub author: Signature,
+    /// The committer.
+    pub committer: Signature,
+    /// The complete commit message.
+    pub message: *const c_char,
+}
+
+/// The callback for [`gix_log_foreach()`], `gix_log_foreach_cb` in C.
+pub type LogForeachCb = Option<extern "C" fn(commit: *const Commit, payload: *mut c_void) -> c_int>;
+
+/// Call `cb` with each commit reachable from the commit that `spec` resolves to, or from `HEAD` if `spec` is null,
+/// newest commits first.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle and `spec` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_log_foreach(
+    repo: *const Repository,
+    spec: *const c_char,
+    cb: LogForeachCb,
+    payload: *mut c_void,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let repo = unsafe { repo_arg(repo)? };
+        let spec = if spec.is_null() {
+            "HEAD".into()
+        } else {
+            // SAFETY: non-null and valid as guaranteed by the caller.
+            unsafe { str_arg(spec, "spec")? }
+        };
+        let cb = cb.ok_or_else(|| Error::new(GIX_EINVALID, "argument 'cb' must not be null"))?;
+
+        let tip = rev_parse_single(repo, spec)?;
+        let walk = repo
+            .rev_walk(Some(gix::ObjectId::from(tip)))
+            .sorting(gix::traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+            .all()?;
+        let mut parent_ids = Vec::new();
+        for info in walk {
+            let info = info?;
+            let commit = info.object()?;
+            let commit = commit.decode()?;
+            parent_ids.clear();
+            parent_ids.extend(commit.parents().map(Oid::from));
+            let (author_name, author_email) = (
+                c_string(commit.author.name.to_vec()),
+                c_string(commit.author.email.to_vec()),
+            );
+            let (committer_name, committer_email) = (
+                c_string(commit.committer.name.to_vec()),
+                c_string(commit.committer.email.to_vec()),
+            );
+            let message = c_string(commit.message.to_vec());
+            let commit = Commit {
+                id: info.id.into(),
+                tree_id: commit.tree().into(),
+                parent_ids: parent_ids.as_ptr(),
+                parent_count: parent_ids.len(),
+                author: signature(&author_name, &author_email, commit.author.time),
+                committer: signature(&committer_name, &committer_email, commit.committer.time),
+                message: message.as_ptr(),
+            };
+            let res = cb(&commit, payload);
+            if res != 0 {
+                return Ok(res);
+            }
+        }
+        Ok(GIX_OK)
+    })
+}
+
+fn signature(name: &CString, email: &CString, time: gix::date::Time) -> Signature {
+    Signature {
+        name: name.as_ptr(),
+        email: email.as_ptr(),
+        time: time.seconds,
+        offset: time.offset,
+    }
+}
@@ -0,0 +1,79 @@
+//! Object ids, which are passed by value.
+use std::ffi::{c_char, c_int};
+
+use crate::error::{guard, mut_arg, ref_arg, str_arg, Error, GIX_EINVALID, GIX_OK};
+
+/// The binary SHA-1 id of an object, `gix_oid` in C.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Oid {
+    /// The raw bytes of the id.
+    pub id: [u8; 20],
+}
+
+impl From<&gix::oid> for Oid {
+    fn from(id: &gix::oid) -> Self {
+        let mut out = Oid::default();
+        out.id.copy_from_slice(id.as_bytes());
+        out
+    }
+}
+
+impl From<gix::ObjectId> for Oid {
+    fn from(id: gix::ObjectId) -> Self {
+        id.as_ref().into()
+    }
+}
+
+impl From<gix::Id<'_>> for Oid {
+    fn from(id: gix::Id<'_>) -> Self {
+        id.detach().into()
+    }
+}
+
+impl From<Oid> for gix::ObjectId {
+    fn from(id: Oid) -> Self {
+        gix::ObjectId::Sha1(id.id)
+    }
+}
+
+/// Parse the 40 hexadecimal characters in `hex` into `out`.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for an `Oid`, and `hex` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_oid_fromstr(out: *mut Oid, hex: *const c_char) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (out, hex) = unsafe { (mut_arg(out, "out")?, str_arg(hex, "hex")?) };
+        let id = gix::ObjectId::from_hex(hex).map_err(|err| Error::from(err).with_code(GIX_EINVALID))?;
+        *out = id.into();
+        Ok(GIX_OK)
+    })
+}
+
+/// Write the hexadecimal form of `oid` into the buffer `out` of `size` bytes, truncating it to `size - 1` characters
+/// if needed, and terminate it with NUL. A buffer of 41 bytes fits the entire id.
+///
+/// # Safety
+///
+/// `out` must point to `size` bytes of writable memory, and `oid` must point to a valid `Oid`.
+#[no_mangle]
+pub unsafe extern "C" fn gix_oid_tostr(out: *mut c_char, size: usize, oid: *const Oid) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let oid = unsafe { ref_arg(oid, "oid")? };
+        if out.is_null() || size == 0 {
+            return Err(Error::new(GIX_EINVALID, "argument 'out' must not be null or empty"));
+        }
+        let hex = gix::ObjectId::from(*oid).to_hex().to_string();
+        let len = hex.len().min(size - 1);
+        // SAFETY: `out` has room for `size` bytes, and we write at most `size - 1` bytes plus NUL.
+        unsafe {
+            std::ptr::copy_nonoverlapping(hex.as_ptr().cast::<c_char>(), out, len);
+            *out.add(len) = 0;
+        }
+        Ok(GIX_OK)
+    })
+}
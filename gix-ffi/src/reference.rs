@@ -0,0 +1,151 @@
+//! Listing references and resolving names and revision specifications to objects.
+use std::ffi::{c_char, c_int, c_void};
+
+use crate::{
+    error::{c_string, guard, mut_arg, str_arg, Error, GIX_EAMBIGUOUS, GIX_EINVALID, GIX_ENOTFOUND, GIX_ERROR, GIX_OK},
+    repository::repo_arg,
+    Oid, Repository,
+};
+
+/// The callback for [`gix_reference_foreach()`], `gix_reference_foreach_cb` in C.
+///
+/// It receives the full reference `name`, the `target` object after following symbolic references or null if that failed,
+/// the `symbolic_target` name if the reference is symbolic or null otherwise, and the user-provided `payload`.
+pub type ReferenceForeachCb = Option<
+    extern "C" fn(
+        name: *const c_char,
+        target: *const Oid,
+        symbolic_target: *const c_char,
+        payload: *mut c_void,
+    ) -> c_int,
+>;
+
+/// Call `cb` with each reference of `repo` whose name starts with `prefix`, like `refs/heads/`, or with all references
+/// if `prefix` is null. References are visited in order of their name.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle and `prefix` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_reference_foreach(
+    repo: *const Repository,
+    prefix: *const c_char,
+    cb: ReferenceForeachCb,
+    payload: *mut c_void,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let repo = unsafe { repo_arg(repo)? };
+        let prefix = if prefix.is_null() {
+            None
+        } else {
+            // SAFETY: non-null and valid as guaranteed by the caller.
+            Some(
+                gix::path::try_from_bstr(unsafe { str_arg(prefix, "prefix")? })
+                    .map_err(|err| Error::from(err).with_code(GIX_EINVALID))?,
+            )
+        };
+        let cb = cb.ok_or_else(|| Error::new(GIX_EINVALID, "argument 'cb' must not be null"))?;
+
+        let platform = repo.references()?;
+        let iter = match prefix {
+            Some(prefix) => platform.prefixed(prefix.as_ref())?,
+            None => platform.all()?,
+        };
+        for reference in iter {
+            let reference = reference.map_err(|err| Error::new(GIX_ERROR, err.to_string()))?;
+            let name = c_string(reference.name().as_bstr().to_vec());
+            let symbolic_target = match reference.target() {
+                gix::refs::TargetRef::Symbolic(target) => Some(c_string(target.as_bstr().to_vec())),
+                gix::refs::TargetRef::Peeled(_) => None,
+            };
+            let target = resolve(reference).map(Oid::from);
+            let res = cb(
+                name.as_ptr(),
+                target.as_ref().map_or(std::ptr::null(), |target| target as *const Oid),
+                symbolic_target
+                    .as_ref()
+                    .map_or(std::ptr::null(), |target| target.as_ptr()),
+                payload,
+            );
+            if res != 0 {
+                return Ok(res);
+            }
+        }
+        Ok(GIX_OK)
+    })
+}
+
+/// Store the object that the reference with the full `name` points to in `out`, following symbolic references.
+/// Returns `GIX_ENOTFOUND` if there is no such reference.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for an `Oid`, `repo` must be a valid repository handle and
+/// `name` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_reference_name_to_id(
+    out: *mut Oid,
+    repo: *const Repository,
+    name: *const c_char,
+) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (out, repo, name) = unsafe { (mut_arg(out, "out")?, repo_arg(repo)?, str_arg(name, "name")?) };
+        let reference = repo
+            .try_find_reference(name)
+            .map_err(|err| Error::from(err).with_code(GIX_EINVALID))?
+            .ok_or_else(|| Error::new(GIX_ENOTFOUND, format!("reference '{name}' not found")))?;
+        *out = resolve(reference)
+            .ok_or_else(|| {
+                Error::new(
+                    GIX_ENOTFOUND,
+                    format!("reference '{name}' could not be resolved to an object"),
+                )
+            })?
+            .into();
+        Ok(GIX_OK)
+    })
+}
+
+/// Resolve the revision specification `spec`, like `HEAD~1` or `main^{tree}`, to a single object and store its id in `out`.
+/// Returns `GIX_EINVALID` for malformed specifications or ranges, `GIX_EAMBIGUOUS` if a short id matches more than one object,
+/// and `GIX_ENOTFOUND` if a part of the specification doesn't exist.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for an `Oid`, `repo` must be a valid repository handle and
+/// `spec` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_revparse_single(out: *mut Oid, repo: *const Repository, spec: *const c_char) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (out, repo, spec) = unsafe { (mut_arg(out, "out")?, repo_arg(repo)?, str_arg(spec, "spec")?) };
+        *out = rev_parse_single(repo, spec)?;
+        Ok(GIX_OK)
+    })
+}
+
+pub(crate) fn rev_parse_single(repo: &gix::Repository, spec: &gix::bstr::BStr) -> Result<Oid, Error> {
+    use gix::revision::spec::parse::{single, Error as ParseError};
+    repo.rev_parse_single(spec).map(Oid::from).map_err(|err| {
+        let code = match &err {
+            single::Error::RangedRev { .. } | single::Error::Parse(ParseError::Parse(_)) => GIX_EINVALID,
+            single::Error::Parse(ParseError::AmbiguousPrefix { .. } | ParseError::AmbiguousRefAndObject { .. }) => {
+                GIX_EAMBIGUOUS
+            }
+            single::Error::Parse(_) => GIX_ENOTFOUND,
+        };
+        Error::from(err).with_code(code)
+    })
+}
+
+/// Follow symbolic references until an object id is found, or return `None` if that isn't possible.
+fn resolve(mut reference: gix::Reference<'_>) -> Option<gix::ObjectId> {
+    loop {
+        if let Some(id) = reference.try_id() {
+            return Some(id.detach());
+        }
+        reference = reference.follow()?.ok()?;
+    }
+}
@@ -0,0 +1,125 @@
+//! Opening repositories and accessing their locations.
+use std::{
+    ffi::{c_char, c_int, CString},
+    path::Path,
+};
+
+use crate::error::{c_string, guard, mut_arg, ref_arg, str_arg, Error, GIX_EINVALID, GIX_ENOTFOUND, GIX_OK};
+
+/// An open repository, `gix_repository` in C.
+pub struct Repository {
+    pub(crate) inner: gix::Repository,
+    git_dir: CString,
+    workdir: Option<CString>,
+}
+
+impl Repository {
+    fn new(inner: gix::Repository) -> Box<Self> {
+        let git_dir = c_string(gix::path::into_bstr(inner.git_dir()).into_owned());
+        let workdir = inner
+            .work_dir()
+            .map(|dir| c_string(gix::path::into_bstr(dir).into_owned()));
+        Box::new(Repository {
+            inner,
+            git_dir,
+            workdir,
+        })
+    }
+}
+
+/// Open the repository at `path`, which is either a `.git` directory or the worktree containing it, and store
+/// a handle to it in `out`. Returns `GIX_ENOTFOUND` if there is no repository at `path`.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for a pointer, and `path` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_repository_open(out: *mut *mut Repository, path: *const c_char) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (out, path) = unsafe { (mut_arg(out, "out")?, str_arg(path, "path")?) };
+        let repo = gix::open(to_path(path)?.as_ref()).map_err(|err| {
+            let code = match err {
+                gix::open::Error::NotARepository { .. } => GIX_ENOTFOUND,
+                _ => crate::GIX_ERROR,
+            };
+            Error::from(err).with_code(code)
+        })?;
+        *out = Box::into_raw(Repository::new(repo));
+        Ok(GIX_OK)
+    })
+}
+
+/// Open the repository containing `path` by searching `path` and its parent directories, and store a handle to it in `out`.
+/// Returns `GIX_ENOTFOUND` if no repository was found.
+///
+/// # Safety
+///
+/// `out` must point to writable memory for a pointer, and `path` must point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gix_repository_discover(out: *mut *mut Repository, path: *const c_char) -> c_int {
+    guard(|| {
+        // SAFETY: valid as guaranteed by the caller.
+        let (out, path) = unsafe { (mut_arg(out, "out")?, str_arg(path, "path")?) };
+        let repo = gix::discover(to_path(path)?.as_ref()).map_err(|err| {
+            let code = match err {
+                gix::discover::Error::Discover(_) => GIX_ENOTFOUND,
+                gix::discover::Error::Open(_) => crate::GIX_ERROR,
+            };
+            Error::from(err).with_code(code)
+        })?;
+        *out = Box::into_raw(Repository::new(repo));
+        Ok(GIX_OK)
+    })
+}
+
+/// Release `repo`, which may be null.
+///
+/// # Safety
+///
+/// `repo` must be null or a handle obtained from this library which wasn't released yet.
+#[no_mangle]
+pub unsafe extern "C" fn gix_repository_free(repo: *mut Repository) {
+    if !repo.is_null() {
+        // SAFETY: created by `Box::into_raw()` and not freed yet, as guaranteed by the caller.
+        drop(unsafe { Box::from_raw(repo) });
+    }
+}
+
+/// Return the path to the `.git` directory of `repo`, valid for as long as `repo` is.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle.
+#[no_mangle]
+pub unsafe extern "C" fn gix_repository_git_dir(repo: *const Repository) -> *const c_char {
+    // SAFETY: valid as guaranteed by the caller.
+    unsafe { repo.as_ref() }.map_or(std::ptr::null(), |repo| repo.git_dir.as_ptr())
+}
+
+/// Return the path to the worktree of `repo`, valid for as long as `repo` is, or null if the repository is bare.
+///
+/// # Safety
+///
+/// `repo` must be a valid repository handle.
+#[no_mangle]
+pub unsafe extern "C" fn gix_repository_workdir(repo: *const Repository) -> *const c_char {
+    // SAFETY: valid as guaranteed by the caller.
+    unsafe { repo.as_ref() }
+        .and_then(|repo| repo.workdir.as_ref())
+        .map_or(std::ptr::null(), |dir| dir.as_ptr())
+}
+
+/// Obtain the repository at `ptr`, or fail if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or a valid repository handle that outlives `'a`.
+pub(crate) unsafe fn repo_arg<'a>(ptr: *const Repository) -> Result<&'a gix::Repository, Error> {
+    // SAFETY: valid if non-null, as guaranteed by the caller.
+    Ok(&unsafe { ref_arg(ptr, "repo")? }.inner)
+}
+
+fn to_path(path: &gix::bstr::BStr) -> Result<std::borrow::Cow<'_, Path>, Error> {
+    gix::path::try_from_bstr(path).map_err(|err| Error::from(err).with_code(GIX_EINVALID))
+}
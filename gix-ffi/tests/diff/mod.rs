@@ -0,0 +1,70 @@
+use std::ffi::{c_int, c_void};
+
+use gix_ffi::{
+    diff::{gix_diff_tree_to_tree, DeltaStatus, DiffDelta},
+    GIX_OK,
+};
+
+use crate::{last_error, repo, rev_parse, string};
+
+extern "C" fn collect(delta: *const DiffDelta, payload: *mut c_void) -> c_int {
+    let (delta, out) = unsafe { (&*delta, &mut *(payload as *mut Vec<(DeltaStatus, String, String, u32)>)) };
+    out.push((
+        delta.status,
+        string(delta.old_path),
+        string(delta.new_path),
+        delta.new_mode,
+    ));
+    GIX_OK
+}
+
+extern "C" fn stop(_: *const DiffDelta, _: *mut c_void) -> c_int {
+    7
+}
+
+#[test]
+fn tree_to_tree() -> gix_testtools::Result {
+    let repo = repo("base")?;
+    let (initial, second) = (rev_parse(&repo, "HEAD~2"), rev_parse(&repo, "HEAD~1^{tree}"));
+    let mut deltas = Vec::<(DeltaStatus, String, String, u32)>::new();
+    let res = unsafe {
+        gix_diff_tree_to_tree(
+            repo.0,
+            &initial,
+            &second,
+            Some(collect),
+            &mut deltas as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    deltas.sort_by(|a, b| a.2.cmp(&b.2));
+    assert_eq!(
+        deltas,
+        [
+            (DeltaStatus::Added, "b".into(), "b".into(), 0o100644),
+            (DeltaStatus::Modified, "dir/c".into(), "dir/c".into(), 0o100644),
+            (DeltaStatus::Renamed, "a".into(), "renamed".into(), 0o100644),
+        ]
+    );
+
+    deltas.clear();
+    let res = unsafe {
+        gix_diff_tree_to_tree(
+            repo.0,
+            std::ptr::null(),
+            &initial,
+            Some(collect),
+            &mut deltas as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK);
+    assert!(
+        deltas.iter().all(|delta| delta.0 == DeltaStatus::Added),
+        "null is the empty tree"
+    );
+    assert_eq!(deltas.len(), 2, "trees aren't reported");
+
+    let res = unsafe { gix_diff_tree_to_tree(repo.0, &initial, &second, Some(stop), std::ptr::null_mut()) };
+    assert_eq!(res, 7, "the value of the callback is returned");
+    Ok(())
+}
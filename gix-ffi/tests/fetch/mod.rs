@@ -0,0 +1,51 @@
+use std::ffi::{c_char, c_int, c_void};
+
+use gix_ffi::{
+    fetch::{gix_remote_fetch, FetchOptions, GIX_FETCH_OPTIONS_VERSION},
+    GIX_EINVALID, GIX_ENOTFOUND, GIX_OK,
+};
+
+use crate::{c, last_error, open, rev_parse, string};
+
+extern "C" fn progress(name: *const c_char, _step: usize, _done: usize, payload: *mut c_void) -> c_int {
+    let names = unsafe { &mut *(payload as *mut Vec<String>) };
+    names.push(string(name));
+    GIX_OK
+}
+
+#[test]
+fn updates_tracking_references() -> gix_testtools::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_repos.sh")?;
+    let repo = open(&tmp.path().join("clone"))?;
+    let base = open(&tmp.path().join("base"))?;
+    assert_ne!(rev_parse(&repo, "origin/main"), rev_parse(&base, "main"));
+
+    let mut names = Vec::<String>::new();
+    let opts = FetchOptions {
+        version: GIX_FETCH_OPTIONS_VERSION,
+        progress_cb: Some(progress),
+        credentials_cb: None,
+        payload: &mut names as *mut _ as *mut c_void,
+    };
+    let res = unsafe { gix_remote_fetch(repo.0, c("origin").as_ptr(), &opts) };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    assert_eq!(rev_parse(&repo, "origin/main"), rev_parse(&base, "main"));
+    Ok(())
+}
+
+#[test]
+fn errors() -> gix_testtools::Result {
+    let repo = crate::repo("clone")?;
+    let res = unsafe { gix_remote_fetch(repo.0, c("missing").as_ptr(), std::ptr::null()) };
+    assert_eq!(res, GIX_ENOTFOUND, "{}", last_error());
+
+    let opts = FetchOptions {
+        version: 2,
+        progress_cb: None,
+        credentials_cb: None,
+        payload: std::ptr::null_mut(),
+    };
+    let res = unsafe { gix_remote_fetch(repo.0, c("origin").as_ptr(), &opts) };
+    assert_eq!(res, GIX_EINVALID);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use std::ffi::{c_char, CStr, CString};
+
+use gix_ffi::{repository::gix_repository_free, Oid, Repository};
+
+mod diff;
+#[cfg(feature = "blocking-network-client")]
+mod fetch;
+mod log;
+mod reference;
+mod repository;
+
+/// A repository handle that is freed on drop.
+struct Repo(*mut Repository);
+
+impl Drop for Repo {
+    fn drop(&mut self) {
+        unsafe { gix_repository_free(self.0) }
+    }
+}
+
+fn fixture(name: &str) -> gix_testtools::Result<std::path::PathBuf> {
+    Ok(gix_testtools::scripted_fixture_read_only("make_repos.sh")?.join(name))
+}
+
+fn repo(name: &str) -> gix_testtools::Result<Repo> {
+    open(&fixture(name)?)
+}
+
+fn open(path: &std::path::Path) -> gix_testtools::Result<Repo> {
+    let mut out = std::ptr::null_mut();
+    let res =
+        unsafe { gix_ffi::repository::gix_repository_open(&mut out, c(path.to_str().expect("valid UTF-8")).as_ptr()) };
+    assert_eq!(res, gix_ffi::GIX_OK, "{}", last_error());
+    Ok(Repo(out))
+}
+
+fn c(s: &str) -> CString {
+    CString::new(s).expect("no NUL")
+}
+
+fn string(s: *const c_char) -> String {
+    assert!(!s.is_null());
+    unsafe { CStr::from_ptr(s) }.to_str().expect("valid UTF-8").to_owned()
+}
+
+fn last_error() -> String {
+    let message = gix_ffi::error::gix_error_last();
+    if message.is_null() {
+        String::new()
+    } else {
+        string(message)
+    }
+}
+
+fn rev_parse(repo: &Repo, spec: &str) -> Oid {
+    let mut out = Oid::default();
+    let res = unsafe { gix_ffi::reference::gix_revparse_single(&mut out, repo.0, c(spec).as_ptr()) };
+    assert_eq!(res, gix_ffi::GIX_OK, "{}", last_error());
+    out
+}
+
+fn hex(oid: &Oid) -> String {
+    let mut buf = [0 as c_char; 41];
+    let res = unsafe { gix_ffi::oid::gix_oid_tostr(buf.as_mut_ptr(), buf.len(), oid) };
+    assert_eq!(res, gix_ffi::GIX_OK);
+    string(buf.as_ptr())
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q base
+(cd base
+  echo a > a
+  mkdir dir && echo c > dir/c
+  git add . && git commit -q -m "initial"
+  git mv a renamed
+  echo b > b && echo c2 >> dir/c
+  git add . && git commit -q -m "second"
+  git branch other HEAD~1
+)
+
+git clone -q base clone
+(cd clone
+  git config user.name committer
+  git config user.email committer@example.com
+)
+
+(cd base
+  echo b2 >> b && git commit -q -am "third"
+)
+
+git init -q --bare bare
@@ -0,0 +1,61 @@
+use std::ffi::{c_int, c_void};
+
+use gix_ffi::{
+    log::{gix_log_foreach, Commit},
+    GIX_ENOTFOUND, GIX_OK,
+};
+
+use crate::{c, last_error, repo, rev_parse, string};
+
+extern "C" fn collect(commit: *const Commit, payload: *mut c_void) -> c_int {
+    let (commit, out) = unsafe { (&*commit, &mut *(payload as *mut Vec<(String, usize, String)>)) };
+    out.push((string(commit.message), commit.parent_count, string(commit.author.name)));
+    GIX_OK
+}
+
+#[test]
+fn foreach() -> gix_testtools::Result {
+    let repo = repo("base")?;
+    let mut commits = Vec::<(String, usize, String)>::new();
+    let res = unsafe {
+        gix_log_foreach(
+            repo.0,
+            std::ptr::null(),
+            Some(collect),
+            &mut commits as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    assert_eq!(
+        commits,
+        [
+            ("third\n".into(), 1, "author".into()),
+            ("second\n".into(), 1, "author".into()),
+            ("initial\n".into(), 0, "author".into()),
+        ]
+    );
+
+    commits.clear();
+    let res = unsafe {
+        gix_log_foreach(
+            repo.0,
+            c("other").as_ptr(),
+            Some(collect),
+            &mut commits as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK);
+    assert_eq!(commits.len(), 1, "starts at the given revision");
+    assert_eq!(rev_parse(&repo, "other"), rev_parse(&repo, "HEAD~2"));
+
+    let res = unsafe {
+        gix_log_foreach(
+            repo.0,
+            c("missing").as_ptr(),
+            Some(collect),
+            &mut commits as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_ENOTFOUND);
+    Ok(())
+}
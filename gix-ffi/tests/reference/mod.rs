@@ -0,0 +1,97 @@
+use std::ffi::{c_char, c_int, c_void};
+
+use gix_ffi::{
+    reference::{gix_reference_foreach, gix_reference_name_to_id, gix_revparse_single},
+    Oid, GIX_EINVALID, GIX_ENOTFOUND, GIX_OK,
+};
+
+use crate::{c, hex, last_error, repo, rev_parse, string};
+
+extern "C" fn collect(name: *const c_char, target: *const Oid, symbolic: *const c_char, payload: *mut c_void) -> c_int {
+    let out = unsafe { &mut *(payload as *mut Vec<(String, bool, Option<String>)>) };
+    out.push((
+        string(name),
+        !target.is_null(),
+        (!symbolic.is_null()).then(|| string(symbolic)),
+    ));
+    GIX_OK
+}
+
+extern "C" fn stop(_: *const c_char, _: *const Oid, _: *const c_char, _: *mut c_void) -> c_int {
+    42
+}
+
+#[test]
+fn foreach() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let mut refs = Vec::<(String, bool, Option<String>)>::new();
+    let res = unsafe {
+        gix_reference_foreach(
+            repo.0,
+            std::ptr::null(),
+            Some(collect),
+            &mut refs as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    assert_eq!(
+        refs,
+        [
+            ("refs/heads/main".into(), true, None),
+            (
+                "refs/remotes/origin/HEAD".into(),
+                true,
+                Some("refs/remotes/origin/main".into())
+            ),
+            ("refs/remotes/origin/main".into(), true, None),
+            ("refs/remotes/origin/other".into(), true, None),
+        ]
+    );
+
+    refs.clear();
+    let res = unsafe {
+        gix_reference_foreach(
+            repo.0,
+            c("refs/heads/").as_ptr(),
+            Some(collect),
+            &mut refs as *mut _ as *mut c_void,
+        )
+    };
+    assert_eq!(res, GIX_OK);
+    assert_eq!(refs.len(), 1);
+
+    let res = unsafe { gix_reference_foreach(repo.0, std::ptr::null(), Some(stop), std::ptr::null_mut()) };
+    assert_eq!(res, 42, "the value of the callback is returned");
+    let res = unsafe { gix_reference_foreach(repo.0, std::ptr::null(), None, std::ptr::null_mut()) };
+    assert_eq!(res, GIX_EINVALID);
+    Ok(())
+}
+
+#[test]
+fn name_to_id() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let mut out = Oid::default();
+    let res = unsafe { gix_reference_name_to_id(&mut out, repo.0, c("refs/remotes/origin/HEAD").as_ptr()) };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    assert_eq!(out, rev_parse(&repo, "main"));
+
+    let res = unsafe { gix_reference_name_to_id(&mut out, repo.0, c("refs/heads/missing").as_ptr()) };
+    assert_eq!(res, GIX_ENOTFOUND);
+    assert_eq!(last_error(), "reference 'refs/heads/missing' not found");
+    Ok(())
+}
+
+#[test]
+fn revparse_single() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let head = rev_parse(&repo, "HEAD");
+    assert_eq!(hex(&rev_parse(&repo, "HEAD~1")), hex(&rev_parse(&repo, "origin/other")));
+    assert_ne!(head, rev_parse(&repo, "HEAD^{tree}"));
+
+    let mut out = Oid::default();
+    let res = unsafe { gix_revparse_single(&mut out, repo.0, c("missing").as_ptr()) };
+    assert_eq!(res, GIX_ENOTFOUND);
+    let res = unsafe { gix_revparse_single(&mut out, repo.0, c("HEAD~1..HEAD").as_ptr()) };
+    assert_eq!(res, GIX_EINVALID, "ranges aren't single objects");
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use gix_ffi::{
+    repository::{gix_repository_discover, gix_repository_git_dir, gix_repository_open, gix_repository_workdir},
+    GIX_EINVALID, GIX_ENOTFOUND, GIX_OK,
+};
+
+use crate::{c, fixture, last_error, repo, string, Repo};
+
+#[test]
+fn open_and_paths() -> gix_testtools::Result {
+    let repo = repo("base")?;
+    assert!(string(unsafe { gix_repository_git_dir(repo.0) }).ends_with(".git"));
+    assert!(string(unsafe { gix_repository_workdir(repo.0) }).ends_with("base"));
+
+    let bare = crate::repo("bare")?;
+    assert!(
+        unsafe { gix_repository_workdir(bare.0) }.is_null(),
+        "bare repositories have no work dir"
+    );
+    Ok(())
+}
+
+#[test]
+fn discover_from_subdirectory() -> gix_testtools::Result {
+    let mut out = std::ptr::null_mut();
+    let dir = fixture("base")?.join("dir");
+    let res = unsafe { gix_repository_discover(&mut out, c(dir.to_str().expect("UTF-8")).as_ptr()) };
+    assert_eq!(res, GIX_OK, "{}", last_error());
+    let repo = Repo(out);
+    assert!(string(unsafe { gix_repository_workdir(repo.0) }).ends_with("base"));
+    Ok(())
+}
+
+#[test]
+fn errors() -> gix_testtools::Result {
+    let mut out = std::ptr::null_mut();
+    let dir = fixture("base")?.join("dir");
+    let res = unsafe { gix_repository_open(&mut out, c(dir.to_str().expect("UTF-8")).as_ptr()) };
+    assert_eq!(res, GIX_ENOTFOUND, "subdirectories aren't repositories");
+    assert!(out.is_null());
+    assert!(!last_error().is_empty());
+
+    let res = unsafe { gix_repository_open(&mut out, std::ptr::null()) };
+    assert_eq!(res, GIX_EINVALID);
+    assert_eq!(last_error(), "argument 'path' must not be null");
+    Ok(())
+}
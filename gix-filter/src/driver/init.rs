@@ -93,6 +93,7 @@ fn spawn_driver(
 ) -> Result<(std::process::Child, std::process::Command), Error> {
     let mut cmd: std::process::Command = gix_command::prepare(gix_path::from_bstr(cmd).into_owned())
         .with_shell()
+        .with_environment(gix_command::Environment::GitClean)
         .with_context(context.clone())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -24,14 +24,54 @@ pub enum RoundTripCheck<'a> {
         /// The repository-relative path of the file to check. Used in case of error.
         rela_path: &'a Path,
     },
+    /// Store the issue in `issue` to allow the caller to handle it, but don't fail.
+    ///
+    /// This is useful to present the issue to the user in a structured manner, instead of using a trace event.
+    Record {
+        /// Set to the issue that was detected, or left untouched if the conversion will round-trip.
+        issue: &'a std::cell::Cell<Option<RoundTripIssue>>,
+    },
+}
+
+/// A line-ending conversion to `git` that wouldn't survive a round-trip, i.e. a checkout would produce different line endings.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum RoundTripIssue {
+    /// The worktree file has `CRLF` line endings that would become `LF` after checkout.
+    CrlfReplacedByLf,
+    /// The worktree file has `LF` line endings that would become `CRLF` after checkout.
+    LfReplacedByCrlf,
+}
+
+impl RoundTripIssue {
+    /// Emit a warning about this issue with `gix_trace::warn!`, using the same message as Git.
+    #[allow(unused_variables)]
+    pub(crate) fn trace_warning(&self, rela_path: &Path) {
+        let replacement = match self {
+            RoundTripIssue::CrlfReplacedByLf => "CRLF will be replaced by LF",
+            RoundTripIssue::LfReplacedByCrlf => "LF will be replaced by CRLF",
+        };
+        gix_trace::warn!(
+            "in the working copy of '{}', {replacement} next time git touches it",
+            rela_path.display()
+        )
+    }
+}
+
+impl std::fmt::Display for RoundTripIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RoundTripIssue::CrlfReplacedByLf => "CRLF would be replaced by LF",
+            RoundTripIssue::LfReplacedByCrlf => "LF would be replaced by CRLF",
+        })
+    }
 }
 
 /// The error returned by [convert_to_git()][super::convert_to_git()].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
-    #[error("{msg} in '{}'", path.display())]
-    RoundTrip { msg: &'static str, path: PathBuf },
+    #[error("{issue} in '{}'", path.display())]
+    RoundTrip { issue: RoundTripIssue, path: PathBuf },
     #[error("Could not obtain index object to check line endings for")]
     FetchObjectFromIndex(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("Could not allocate buffer")]
@@ -48,7 +88,7 @@ pub(crate) mod function {
     use crate::{
         clear_and_set_capacity,
         eol::{
-            convert_to_git::{Error, IndexObjectFn, Options, RoundTripCheck},
+            convert_to_git::{Error, IndexObjectFn, Options, RoundTripCheck, RoundTripIssue},
             AttributesDigest, Stats,
         },
     };
@@ -106,39 +146,25 @@ pub(crate) mod function {
                 new_stats.crlf += new_stats.lone_lf;
                 new_stats.lone_lf = 0;
             }
-            if stats.crlf > 0 && new_stats.crlf == 0 {
+            let issue = if stats.crlf > 0 && new_stats.crlf == 0 {
                 // CRLF would not be restored by checkout
-                match round_trip_check {
-                    RoundTripCheck::Fail { rela_path } => {
-                        return Err(Error::RoundTrip {
-                            msg: "CRLF would be replaced by LF",
-                            path: rela_path.to_owned(),
-                        })
-                    }
-                    #[allow(unused_variables)]
-                    RoundTripCheck::Warn { rela_path } => {
-                        gix_trace::warn!(
-                            "in the working copy of '{}', CRLF will be replaced by LF next time git touches it",
-                            rela_path.display()
-                        )
-                    }
-                }
+                Some(RoundTripIssue::CrlfReplacedByLf)
             } else if stats.lone_lf > 0 && new_stats.lone_lf == 0 {
                 // CRLF would be added by checkout
+                Some(RoundTripIssue::LfReplacedByCrlf)
+            } else {
+                None
+            };
+            if let Some(issue) = issue {
                 match round_trip_check {
                     RoundTripCheck::Fail { rela_path } => {
                         return Err(Error::RoundTrip {
-                            msg: "LF would be replaced by CRLF",
+                            issue,
                             path: rela_path.to_owned(),
                         })
                     }
-                    #[allow(unused_variables)]
-                    RoundTripCheck::Warn { rela_path } => {
-                        gix_trace::warn!(
-                            "in the working copy of '{}', LF will be replaced by CRLF next time git touches it",
-                            rela_path.display()
-                        )
-                    }
+                    RoundTripCheck::Warn { rela_path } => issue.trace_warning(rela_path),
+                    RoundTripCheck::Record { issue: out } => out.set(Some(issue)),
                 }
             }
         }
@@ -46,6 +46,7 @@ impl Stats {
     ///
     /// Note that the entire buffer will be scanned.
     pub fn from_bytes(bytes: &[u8]) -> Self {
+        let last_byte = bytes.last().copied();
         let mut bytes = bytes.iter().peekable();
         let mut null = 0;
         let mut lone_cr = 0;
@@ -84,6 +85,11 @@ impl Stats {
             }
         }
 
+        // Like Git, don't count a trailing DOS end-of-file marker as non-printable.
+        if last_byte == Some(0x1a) {
+            non_printable -= 1;
+        }
+
         Self {
             null,
             lone_cr,
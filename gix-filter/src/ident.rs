@@ -40,23 +40,45 @@ pub fn undo(src: &[u8], buf: &mut Vec<u8>) -> Result<bool, std::collections::Try
     Ok(initialized)
 }
 
-/// Substitute all occurrences of `$Id$` with `$Id: <hexsha-of-input>$` if present in `src` and write all changes to `buf`,
+/// Substitute all occurrences of `$Id$` with `$Id: <hexsha-of-input> $` if present in `src` and write all changes to `buf`,
 /// with `object_hash` being used accordingly. Return `true` if `buf` was written to or `false` if no change was made
 /// (as there was nothing to do).
 ///
-/// ### Deviation
-///
-/// `Git` also tries to cleanup 'stray' substituted `$Id: <hex>$`, but we don't do that, sticking exactly to what ought to be done.
-/// The respective code is up to 16 years old and one might assume that `git` by now handles checking and checkout filters correctly.
+/// Like `git`, stray substitutions like `$Id: <hex>$` are substituted as well, unless they contain spaces in unexpected places
+/// or a newline, as these are assumed to be identifiers of another version control system.
 pub fn apply(
     src: &[u8],
     object_hash: gix_hash::Kind,
     buf: &mut Vec<u8>,
 ) -> Result<bool, std::collections::TryReserveError> {
-    const HASH_LEN: usize = ": ".len() + gix_hash::Kind::longest().len_in_hex();
+    /// Find the next `$Id$` or substituted `$Id: <anything>$` in `input`, the way `ident_to_worktree()` in `git` does.
+    fn find_range(input: &[u8]) -> Option<Range<usize>> {
+        let mut ofs = 0;
+        loop {
+            let start = ofs + input[ofs..].find_byte(b'$')?;
+            ofs = start + 1;
+            let cursor = &input[ofs..];
+            if cursor.starts_with(b"Id$") {
+                return Some(start..(ofs + 3));
+            } else if cursor.starts_with(b"Id:") {
+                let end = ofs + 3 + cursor[3..].find_byte(b'$')?;
+                let value = &input[ofs + 3..end];
+                if value.contains(&b'\n') {
+                    continue;
+                }
+                // Spaces are expected only after the colon and before the closing dollar.
+                let is_foreign = value.len() > 2 && value[1..value.len() - 1].contains(&b' ');
+                if !is_foreign {
+                    return Some(start..(end + 1));
+                }
+            }
+        }
+    }
+
+    const HASH_LEN: usize = ": ".len() + gix_hash::Kind::longest().len_in_hex() + " ".len();
     let mut id = None;
     let mut ofs = 0;
-    while let Some(pos) = src[ofs..].find(b"$Id$") {
+    while let Some(range) = find_range(&src[ofs..]) {
         let id = match id {
             None => {
                 let new_id = gix_object::compute_hash(object_hash, gix_object::Kind::Blob, src);
@@ -67,12 +89,12 @@ pub fn apply(
             Some(id) => id.to_owned(),
         };
 
-        buf.push_str(&src[ofs..][..pos + 3]);
-        buf.push_str(b": ");
+        buf.push_str(&src[ofs..][..range.start]);
+        buf.push_str(b"$Id: ");
         id.write_hex_to(&mut *buf).expect("writes to memory always work");
-        buf.push(b'$');
+        buf.push_str(b" $");
 
-        ofs += pos + 4;
+        ofs += range.end;
     }
     if id.is_some() {
         buf.push_str(&src[ofs..]);
@@ -47,6 +47,8 @@ pub struct Pipeline {
     processes: driver::State,
     /// A utility to handle multiple buffers to keep results of various filters.
     bufs: gix_utils::Buffers,
+    /// The CRLF round-trip issues encountered when converting to git with [`pipeline::CrlfRoundTripCheck::Warn`].
+    round_trip_warnings: Vec<(BString, eol::convert_to_git::RoundTripIssue)>,
 }
 
 /// A declaration of a driver program.
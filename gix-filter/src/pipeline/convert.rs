@@ -145,18 +145,26 @@ impl Pipeline {
             self.bufs.swap();
         }
 
+        let round_trip_issue = std::cell::Cell::new(None);
         if eol::convert_to_git(
             &self.bufs.src,
             digest,
             &mut self.bufs.dest,
             &mut |buf| index_object(buf),
             eol::convert_to_git::Options {
-                round_trip_check: self.options.crlf_roundtrip_check.to_eol_roundtrip_check(rela_path),
+                round_trip_check: self
+                    .options
+                    .crlf_roundtrip_check
+                    .to_eol_roundtrip_check(rela_path, &round_trip_issue),
                 config: self.options.eol_config,
             },
         )? {
             self.bufs.swap();
         }
+        if let Some(issue) = round_trip_issue.get() {
+            issue.trace_warning(rela_path);
+            self.round_trip_warnings.push((bstr_path.into_owned(), issue));
+        }
 
         if apply_ident_filter && ident::undo(&self.bufs.src, &mut self.bufs.dest)? {
             self.bufs.swap();
@@ -231,6 +239,36 @@ impl Pipeline {
             None => ToWorktreeOutcome::Buffer(bufs.src),
         })
     }
+
+    /// Return `true` if [`convert_to_worktree()`](Self::convert_to_worktree()) may change the content of a file at `rela_path`
+    /// based on the `attributes` at `rela_path` which is passed as first argument, or `false` if it would surely be
+    /// [unchanged](ToWorktreeOutcome::Unchanged).
+    ///
+    /// This allows to write objects into the worktree as stream if they would not be converted, without having to
+    /// hold them in memory.
+    pub fn may_convert_to_worktree(
+        &mut self,
+        rela_path: &BStr,
+        attributes: &mut dyn FnMut(&BStr, &mut gix_attributes::search::Outcome),
+    ) -> Result<bool, configuration::Error> {
+        let Configuration {
+            driver,
+            digest,
+            _attr_digest: _,
+            encoding,
+            apply_ident_filter,
+        } = Configuration::at_path(
+            rela_path,
+            &self.options.drivers,
+            &mut self.attrs,
+            attributes,
+            self.options.eol_config,
+        )?;
+        Ok(driver.is_some()
+            || encoding.is_some()
+            || apply_ident_filter
+            || digest.to_eol(self.options.eol_config) == Some(eol::Mode::CrLf))
+    }
 }
 
 /// The result of a conversion with zero or more filters to be stored in git.
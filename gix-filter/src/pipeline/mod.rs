@@ -10,6 +10,7 @@ pub enum CrlfRoundTripCheck {
     /// Emit a warning using `gix_trace::warn!`, but don't fail.
     ///
     /// Note that the parent application has to setup tracing to make these events visible, along with a parent `span!`.
+    /// Alternatively, the warnings can be obtained with [`Pipeline::take_round_trip_warnings()`].
     #[default]
     Warn,
     /// Do nothing, do not perform round-trip check at all.
@@ -26,7 +27,7 @@ pub struct Options {
     /// How to perform round-trip checks during end-of-line conversions to git.
     pub crlf_roundtrip_check: CrlfRoundTripCheck,
     /// All worktree encodings for round-trip checks should be performed.
-    pub encodings_with_roundtrip_check: Vec<&'static encoding_rs::Encoding>,
+    pub encodings_with_roundtrip_check: Vec<crate::worktree::Encoding>,
     /// The object hash to use when applying the `ident` filter.
     pub object_hash: gix_hash::Kind,
 }
@@ -65,6 +66,7 @@ impl Pipeline {
             processes: driver::State::new(context),
             options,
             bufs: Default::default(),
+            round_trip_warnings: Vec::new(),
         }
     }
 
@@ -107,6 +109,13 @@ impl Pipeline {
     pub fn buffers_mut(&mut self) -> &mut gix_utils::Buffers {
         &mut self.bufs
     }
+    /// Return all CRLF round-trip issues as `(rela_path, issue)` that were encountered since the last call
+    /// when converting files to `git` with [`CrlfRoundTripCheck::Warn`].
+    ///
+    /// This allows to present them to the user in a structured manner, similar to the warnings emitted by `git add`.
+    pub fn take_round_trip_warnings(&mut self) -> Vec<(BString, eol::convert_to_git::RoundTripIssue)> {
+        std::mem::take(&mut self.round_trip_warnings)
+    }
 }
 
 ///
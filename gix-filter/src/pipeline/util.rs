@@ -8,7 +8,7 @@ use crate::{
     driver, eol,
     eol::AttributesDigest,
     pipeline::{convert::configuration, Context, CrlfRoundTripCheck},
-    Driver,
+    worktree, Driver,
 };
 
 pub(crate) struct Configuration<'a> {
@@ -17,7 +17,7 @@ pub(crate) struct Configuration<'a> {
     pub(crate) _attr_digest: Option<eol::AttributesDigest>,
     /// The final digest that includes configuration values
     pub(crate) digest: eol::AttributesDigest,
-    pub(crate) encoding: Option<&'static encoding_rs::Encoding>,
+    pub(crate) encoding: Option<worktree::Encoding>,
     /// Whether or not to apply the `ident` filter
     pub(crate) apply_ident_filter: bool,
 }
@@ -40,17 +40,17 @@ impl<'driver> Configuration<'driver> {
 
         fn extract_encoding(
             attr: &gix_attributes::search::Match<'_>,
-        ) -> Result<Option<&'static encoding_rs::Encoding>, configuration::Error> {
+        ) -> Result<Option<worktree::Encoding>, configuration::Error> {
             match attr.assignment.state {
                 StateRef::Set | StateRef::Unset => Err(configuration::Error::InvalidEncoding),
-                StateRef::Value(name) => encoding_rs::Encoding::for_label(name.as_bstr())
-                    .ok_or(configuration::Error::UnknownEncoding {
+                StateRef::Value(name) => worktree::encoding::for_label(name.as_bstr())
+                    .map_err(|_| configuration::Error::UnknownEncoding {
                         name: name.as_bstr().to_owned(),
                     })
                     .map(|encoding| {
                         // The working-tree-encoding is the encoding we have to expect in the working tree.
                         // If the specified one is the default encoding, there is nothing to do.
-                        if encoding == encoding_rs::UTF_8 {
+                        if encoding.is_utf8() {
                             None
                         } else {
                             Some(encoding)
@@ -148,10 +148,15 @@ impl Context {
 }
 
 impl CrlfRoundTripCheck {
-    pub(crate) fn to_eol_roundtrip_check(self, rela_path: &Path) -> Option<eol::convert_to_git::RoundTripCheck<'_>> {
+    /// Note that warnings are recorded in `issue` to be handled by the caller.
+    pub(crate) fn to_eol_roundtrip_check<'a>(
+        self,
+        rela_path: &'a Path,
+        issue: &'a std::cell::Cell<Option<eol::convert_to_git::RoundTripIssue>>,
+    ) -> Option<eol::convert_to_git::RoundTripCheck<'a>> {
         match self {
             CrlfRoundTripCheck::Fail => Some(eol::convert_to_git::RoundTripCheck::Fail { rela_path }),
-            CrlfRoundTripCheck::Warn => Some(eol::convert_to_git::RoundTripCheck::Warn { rela_path }),
+            CrlfRoundTripCheck::Warn => Some(eol::convert_to_git::RoundTripCheck::Record { issue }),
             CrlfRoundTripCheck::Skip => None,
         }
     }
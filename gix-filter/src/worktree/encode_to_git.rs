@@ -15,6 +15,10 @@ pub enum Error {
     Overflow { input_len: usize },
     #[error("The input was malformed and could not be decoded as '{encoding}'")]
     Malformed { encoding: &'static str },
+    #[error("A byte order mark (BOM) is prohibited if encoded as {encoding}, use {} as working-tree-encoding instead", &encoding[..encoding.len() - 2])]
+    BomProhibited { encoding: &'static str },
+    #[error("A byte order mark (BOM) is required if encoded as {encoding}, use {encoding}BE or {encoding}LE (depending on the byte order) as working-tree-encoding instead")]
+    BomRequired { encoding: &'static str },
     #[error("Encoding from '{src_encoding}' to '{dest_encoding}' and back is not the same")]
     RoundTrip {
         src_encoding: &'static str,
@@ -26,10 +30,84 @@ pub(crate) mod function {
     use encoding_rs::DecoderResult;
 
     use super::{Error, RoundTripCheck};
+    use crate::worktree::{
+        encoding::{bom_is_big_endian, ByteOrder, Kind},
+        Encoding,
+    };
 
     /// Decode `src` according to `src_encoding` to `UTF-8` for storage in git and place it in `buf`.
     /// Note that the encoding is always applied, there is no conditional even if `src_encoding` already is `UTF-8`.
+    ///
+    /// Like `git`, `UTF-16` and `UTF-32` encodings fail if a byte order mark (BOM) is present even though the byte order is
+    /// explicit, or if it is missing even though the byte order isn't.
     pub fn encode_to_git(
+        src: &[u8],
+        src_encoding: impl Into<Encoding>,
+        buf: &mut Vec<u8>,
+        round_trip: RoundTripCheck,
+    ) -> Result<(), Error> {
+        let src_encoding = src_encoding.into();
+        match src_encoding.kind() {
+            Kind::Unicode { unit_len, order } => decode_unicode(src, unit_len, order, src_encoding.name(), buf),
+            Kind::Other(src_encoding) => decode_other(src, src_encoding, buf, round_trip),
+        }
+    }
+
+    /// Decoding is strict, so unicode encodings always round-trip and don't need to be checked.
+    fn decode_unicode(
+        src: &[u8],
+        unit_len: usize,
+        order: ByteOrder,
+        encoding: &'static str,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        let bom = bom_is_big_endian(src, unit_len);
+        let (is_big_endian, data) = match (order, bom) {
+            (ByteOrder::BigEndian | ByteOrder::LittleEndian, Some(_)) => return Err(Error::BomProhibited { encoding }),
+            (ByteOrder::Bom, None) => return Err(Error::BomRequired { encoding }),
+            (ByteOrder::Bom | ByteOrder::LittleEndianWithBom, Some(is_big_endian)) => (is_big_endian, &src[unit_len..]),
+            (ByteOrder::BigEndian, None) => (true, src),
+            (ByteOrder::LittleEndian | ByteOrder::LittleEndianWithBom, None) => (false, src),
+        };
+        if data.len() % unit_len != 0 {
+            return Err(Error::Malformed { encoding });
+        }
+
+        buf.clear();
+        buf.reserve(data.len());
+        let mut char_buf = [0u8; 4];
+        let mut push = |c: Option<char>| -> Result<(), Error> {
+            let c = c.ok_or(Error::Malformed { encoding })?;
+            buf.extend_from_slice(c.encode_utf8(&mut char_buf).as_bytes());
+            Ok(())
+        };
+        if unit_len == 2 {
+            let units = data.chunks_exact(2).map(|unit| {
+                let unit = [unit[0], unit[1]];
+                if is_big_endian {
+                    u16::from_be_bytes(unit)
+                } else {
+                    u16::from_le_bytes(unit)
+                }
+            });
+            for c in char::decode_utf16(units) {
+                push(c.ok())?;
+            }
+        } else {
+            for unit in data.chunks_exact(4) {
+                let unit = [unit[0], unit[1], unit[2], unit[3]];
+                let scalar = if is_big_endian {
+                    u32::from_be_bytes(unit)
+                } else {
+                    u32::from_le_bytes(unit)
+                };
+                push(char::from_u32(scalar))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_other(
         src: &[u8],
         src_encoding: &'static encoding_rs::Encoding,
         buf: &mut Vec<u8>,
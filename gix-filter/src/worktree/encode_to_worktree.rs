@@ -17,11 +17,61 @@ pub(crate) mod function {
     use encoding_rs::EncoderResult;
 
     use super::Error;
+    use crate::worktree::{
+        encoding::{ByteOrder, Kind},
+        Encoding,
+    };
 
     /// Encode `src_utf8`, which is assumed to be UTF-8 encoded, according to `worktree_encoding` for placement in the working directory,
     /// and write it to `buf`, possibly resizing it.
     /// Note that the encoding is always applied, there is no conditional even if `worktree_encoding` and the `src` encoding are the same.
     pub fn encode_to_worktree(
+        src_utf8: &[u8],
+        worktree_encoding: impl Into<Encoding>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        match worktree_encoding.into().kind() {
+            Kind::Unicode { unit_len, order } => encode_unicode(src_utf8, unit_len, order, buf),
+            Kind::Other(worktree_encoding) => encode_other(src_utf8, worktree_encoding, buf),
+        }
+    }
+
+    fn encode_unicode(src_utf8: &[u8], unit_len: usize, order: ByteOrder, buf: &mut Vec<u8>) -> Result<(), Error> {
+        let src = std::str::from_utf8(src_utf8)?;
+        let is_big_endian = match order {
+            ByteOrder::BigEndian | ByteOrder::Bom => true,
+            ByteOrder::LittleEndian | ByteOrder::LittleEndianWithBom => false,
+        };
+        buf.clear();
+        buf.reserve(src.len() * unit_len + unit_len);
+        let mut push = |scalar: u32| {
+            if unit_len == 2 {
+                let unit = scalar as u16;
+                buf.extend_from_slice(&if is_big_endian {
+                    unit.to_be_bytes()
+                } else {
+                    unit.to_le_bytes()
+                });
+            } else {
+                buf.extend_from_slice(&if is_big_endian {
+                    scalar.to_be_bytes()
+                } else {
+                    scalar.to_le_bytes()
+                });
+            }
+        };
+        if matches!(order, ByteOrder::Bom | ByteOrder::LittleEndianWithBom) {
+            push(0xfeff);
+        }
+        if unit_len == 2 {
+            src.encode_utf16().map(u32::from).for_each(push);
+        } else {
+            src.chars().map(u32::from).for_each(push);
+        }
+        Ok(())
+    }
+
+    fn encode_other(
         src_utf8: &[u8],
         worktree_encoding: &'static encoding_rs::Encoding,
         buf: &mut Vec<u8>,
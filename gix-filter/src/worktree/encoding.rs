@@ -1,5 +1,94 @@
-use bstr::BStr;
-use encoding_rs::Encoding;
+use bstr::{BStr, ByteSlice};
+
+/// The encoding of files in the working tree, as configured with the `working-tree-encoding` attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    /// An encoding implemented by the `encoding_rs` crate.
+    ///
+    /// Note that `UTF-16` encodings are always represented by [`Utf16`](Encoding::Utf16), as `encoding_rs` can only decode them.
+    Other(&'static encoding_rs::Encoding),
+    /// `UTF-16`, with two bytes per code unit and the given handling of the byte order.
+    Utf16(ByteOrder),
+    /// `UTF-32`, with four bytes per code unit and the given handling of the byte order.
+    Utf32(ByteOrder),
+}
+
+/// Determine the byte order of [`Utf16`](Encoding::Utf16) and [`Utf32`](Encoding::Utf32) encodings, and how byte order
+/// marks (BOM) are handled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// The data is big-endian and must not start with a BOM, like `UTF-16BE`.
+    BigEndian,
+    /// The data is little-endian and must not start with a BOM, like `UTF-16LE`.
+    LittleEndian,
+    /// The data must start with a BOM which determines its byte order, like `UTF-16`.
+    ///
+    /// When writing, the data is big-endian and starts with a BOM.
+    Bom,
+    /// The data is little-endian and starts with a BOM when written, like `UTF-16LE-BOM`.
+    ///
+    /// When reading, a BOM is optional, but determines the byte order if present.
+    LittleEndianWithBom,
+}
+
+impl From<&'static encoding_rs::Encoding> for Encoding {
+    fn from(encoding: &'static encoding_rs::Encoding) -> Self {
+        if encoding == encoding_rs::UTF_16BE {
+            Encoding::Utf16(ByteOrder::BigEndian)
+        } else if encoding == encoding_rs::UTF_16LE {
+            Encoding::Utf16(ByteOrder::LittleEndian)
+        } else {
+            Encoding::Other(encoding)
+        }
+    }
+}
+
+/// Access
+impl Encoding {
+    /// Return the canonical name of this encoding.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Other(encoding) => encoding.name(),
+            Encoding::Utf16(order) => match order {
+                ByteOrder::BigEndian => "UTF-16BE",
+                ByteOrder::LittleEndian => "UTF-16LE",
+                ByteOrder::Bom => "UTF-16",
+                ByteOrder::LittleEndianWithBom => "UTF-16LE-BOM",
+            },
+            Encoding::Utf32(order) => match order {
+                ByteOrder::BigEndian => "UTF-32BE",
+                ByteOrder::LittleEndian => "UTF-32LE",
+                ByteOrder::Bom => "UTF-32",
+                ByteOrder::LittleEndianWithBom => "UTF-32LE-BOM",
+            },
+        }
+    }
+
+    /// Return `true` if this is `UTF-8`, which is the encoding used for storage in git.
+    pub fn is_utf8(&self) -> bool {
+        *self == Encoding::Other(encoding_rs::UTF_8)
+    }
+
+    /// Return how data in this encoding has to be converted.
+    pub(crate) fn kind(&self) -> Kind {
+        match *self {
+            Encoding::Other(encoding) => match Encoding::from(encoding) {
+                Encoding::Other(encoding) => Kind::Other(encoding),
+                encoding => encoding.kind(),
+            },
+            Encoding::Utf16(order) => Kind::Unicode { unit_len: 2, order },
+            Encoding::Utf32(order) => Kind::Unicode { unit_len: 4, order },
+        }
+    }
+}
+
+/// The way data is converted from and to an [`Encoding`].
+pub(crate) enum Kind {
+    /// `UTF-16` or `UTF-32`, implemented by us, with `unit_len` bytes per code unit.
+    Unicode { unit_len: usize, order: ByteOrder },
+    /// Everything else, implemented by `encoding_rs`.
+    Other(&'static encoding_rs::Encoding),
+}
 
 ///
 #[allow(clippy::empty_docs)]
@@ -17,16 +106,54 @@ pub mod for_label {
 
 /// Try to produce a new `Encoding` for `label` or report an error if it is not known.
 ///
-/// ### Deviation
-///
-/// * There is no special handling of UTF-16LE/BE with checks if data contains a BOM or not, like `git` as we don't expect to have
-///   data available here.
-/// * Special `-BOM` suffixed versions of `UTF-16` encodings are not supported.
-pub fn for_label<'a>(label: impl Into<&'a BStr>) -> Result<&'static Encoding, for_label::Error> {
+/// Like `git`, the `UTF-16` and `UTF-32` families are matched case-insensitively with an optional dash, like `utf16le`,
+/// and `UTF-16LE-BOM` is supported to write little-endian data with a byte order mark.
+pub fn for_label<'a>(label: impl Into<&'a BStr>) -> Result<Encoding, for_label::Error> {
     let mut label = label.into();
+    if let Some(encoding) = unicode_for_label(label.trim()) {
+        return Ok(encoding);
+    }
     if label == "latin-1" {
         label = "ISO-8859-1".into();
     }
-    let enc = Encoding::for_label(label.as_ref()).ok_or_else(|| for_label::Error::Unknown { name: label.into() })?;
-    Ok(enc)
+    let enc = encoding_rs::Encoding::for_label(label.as_ref())
+        .ok_or_else(|| for_label::Error::Unknown { name: label.into() })?;
+    Ok(enc.into())
+}
+
+/// This is modelled after `same_utf_encoding()` in `git`.
+fn unicode_for_label(label: &[u8]) -> Option<Encoding> {
+    let rest = label
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case(b"UTF"))
+        .map(|_| &label[3..])?;
+    let rest = rest.strip_prefix(b"-").unwrap_or(rest);
+    let (is_utf16, rest) = match rest.strip_prefix(b"16") {
+        Some(rest) => (true, rest),
+        None => (false, rest.strip_prefix(b"32")?),
+    };
+    let order = match rest.to_ascii_uppercase().as_slice() {
+        b"" => ByteOrder::Bom,
+        b"BE" => ByteOrder::BigEndian,
+        b"LE" => ByteOrder::LittleEndian,
+        b"LE-BOM" if is_utf16 => ByteOrder::LittleEndianWithBom,
+        _ => return None,
+    };
+    Some(if is_utf16 {
+        Encoding::Utf16(order)
+    } else {
+        Encoding::Utf32(order)
+    })
+}
+
+/// Return `Some(true)` if `data` starts with a big-endian BOM and `Some(false)` if it starts with a little-endian BOM,
+/// for code units of `unit_len` bytes.
+pub(crate) fn bom_is_big_endian(data: &[u8], unit_len: usize) -> Option<bool> {
+    match unit_len {
+        2 if data.starts_with(&[0xfe, 0xff]) => Some(true),
+        2 if data.starts_with(&[0xff, 0xfe]) => Some(false),
+        4 if data.starts_with(&[0, 0, 0xfe, 0xff]) => Some(true),
+        4 if data.starts_with(&[0xff, 0xfe, 0, 0]) => Some(false),
+        _ => None,
+    }
 }
@@ -1,5 +1,6 @@
 //! Worktree encodings are powered by the `encoding_rs` crate, which has a narrower focus than the `iconv` library. Thus this implementation
 //! is inherently more limited but will handle the common cases.
+//! `UTF-16` and `UTF-32` are implemented here as `encoding_rs` can't encode the former and doesn't know the latter.
 //!  
 //! Note that for encoding to legacy formats, [additional normalization steps](https://docs.rs/encoding_rs/0.8.32/encoding_rs/#preparing-text-for-the-encoders)
 //! can be taken, which we do not yet take unless there is specific examples or problems to solve.
@@ -7,6 +8,7 @@
 ///
 #[allow(clippy::empty_docs)]
 pub mod encoding;
+pub use encoding::Encoding;
 
 ///
 #[allow(clippy::empty_docs)]
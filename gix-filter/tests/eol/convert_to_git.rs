@@ -159,11 +159,69 @@ fn round_trip_check() -> crate::Result {
         assert!(
             changed,
             "in warn mode, we will get a result even though it won't round-trip"
-        )
+        );
+
+        let issue = std::cell::Cell::new(None);
+        eol::convert_to_git(
+            input,
+            AttributesDigest::TextCrlf,
+            &mut buf,
+            &mut no_call,
+            eol::convert_to_git::Options {
+                round_trip_check: Some(gix_filter::eol::convert_to_git::RoundTripCheck::Record { issue: &issue }),
+                config: Default::default(),
+            },
+        )?;
+        assert_eq!(
+            issue.get(),
+            Some(eol::convert_to_git::RoundTripIssue::LfReplacedByCrlf),
+            "issues can be recorded for handling them in a structured manner"
+        );
     }
     Ok(())
 }
 
+#[test]
+fn round_trip_check_crlf_to_lf() -> crate::Result {
+    let mut buf = Vec::new();
+    let err = eol::convert_to_git(
+        b"crlf\r\nonly\r\n",
+        AttributesDigest::TextInput,
+        &mut buf,
+        &mut no_call,
+        eol::convert_to_git::Options {
+            round_trip_check: Some(gix_filter::eol::convert_to_git::RoundTripCheck::Fail {
+                rela_path: Path::new("hello.txt"),
+            }),
+            config: Default::default(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "CRLF would be replaced by LF in 'hello.txt'");
+    assert!(matches!(
+        err,
+        eol::convert_to_git::Error::RoundTrip {
+            issue: eol::convert_to_git::RoundTripIssue::CrlfReplacedByLf,
+            ..
+        }
+    ));
+
+    let issue = std::cell::Cell::new(None);
+    let changed = eol::convert_to_git(
+        b"crlf\r\nonly\r\n",
+        AttributesDigest::TextCrlf,
+        &mut buf,
+        &mut no_call,
+        eol::convert_to_git::Options {
+            round_trip_check: Some(gix_filter::eol::convert_to_git::RoundTripCheck::Record { issue: &issue }),
+            config: Default::default(),
+        },
+    )?;
+    assert!(changed);
+    assert_eq!(issue.get(), None, "CRLF is restored on checkout, so there is no issue");
+    Ok(())
+}
+
 #[allow(clippy::ptr_arg)]
 fn no_call(_buf: &mut Vec<u8>) -> Result<Option<()>, Box<dyn std::error::Error + Send + Sync>> {
     unreachable!("index function will not be called")
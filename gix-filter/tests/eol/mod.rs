@@ -18,6 +18,16 @@ mod stats {
             );
             assert!(stats.is_binary());
         }
+
+        #[test]
+        fn trailing_dos_eof_marker_is_not_counted_as_non_printable() {
+            let stats = eol::Stats::from_bytes(b"hi\r\n\x1a");
+            assert_eq!(stats.non_printable, 0, "this is what Git does as well");
+            assert!(!stats.is_binary());
+
+            let stats = eol::Stats::from_bytes(b"\x1ahi\r\n");
+            assert_eq!(stats.non_printable, 1, "only a trailing marker is special");
+        }
     }
 }
 
@@ -77,6 +77,9 @@ mod apply {
             "nothing",
             "$ID$ case sensitive matching",
             "$Id: expanded is ignored$",
+            "$Id: some other $",
+            "$Id: \n$",
+            "$Id: no closing dollar",
         ] {
             let changed = ident::apply(input_no_match.as_bytes(), gix_hash::Kind::Sha1, &mut buf)?;
             assert!(!changed, "no substitution happens, nothing to do");
@@ -92,12 +95,28 @@ mod apply {
             ident::apply(B("$Id$"), gix_hash::Kind::Sha1, &mut buf)?,
             "a change happens"
         );
-        assert_eq!(buf.as_bstr(), "$Id: b3f5ebfb5843bc43ceecff6d4f26bb37c615beb1$");
+        assert_eq!(buf.as_bstr(), "$Id: b3f5ebfb5843bc43ceecff6d4f26bb37c615beb1 $");
 
         assert!(ident::apply(B("$Id$ $Id$ foo"), gix_hash::Kind::Sha1, &mut buf)?);
         assert_eq!(
             buf.as_bstr(),
-            "$Id: e230cff7a9624f59eaa28bfb97602c3a03651a49$ $Id: e230cff7a9624f59eaa28bfb97602c3a03651a49$ foo"
+            "$Id: e230cff7a9624f59eaa28bfb97602c3a03651a49 $ $Id: e230cff7a9624f59eaa28bfb97602c3a03651a49 $ foo"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn stray_substitutions_are_substituted_again() -> crate::Result {
+        let mut buf = Vec::new();
+        assert!(ident::apply(
+            B("a $Id: stale $ b $Id:stale$ c $Id: other vcs $ d $Id$"),
+            gix_hash::Kind::Sha1,
+            &mut buf
+        )?);
+        assert_eq!(
+            buf.as_bstr(),
+            "a $Id: 3343bb18e5e5e2f68094d7633d2a60f4f302166c $ b $Id: 3343bb18e5e5e2f68094d7633d2a60f4f302166c $ c $Id: other vcs $ d $Id: 3343bb18e5e5e2f68094d7633d2a60f4f302166c $",
+            "identifiers with unexpected spaces are left alone, just like git does"
         );
         Ok(())
     }
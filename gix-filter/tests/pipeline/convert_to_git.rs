@@ -37,6 +37,44 @@ fn no_driver_but_filter_with_autocrlf() -> gix_testtools::Result {
     Ok(())
 }
 
+#[test]
+fn round_trip_warnings_are_collected() -> gix_testtools::Result {
+    let (_cache, mut pipe) = pipeline("no-filter", || {
+        (
+            vec![],
+            Vec::new(),
+            CrlfRoundTripCheck::Warn,
+            eol::Configuration {
+                auto_crlf: eol::AutoCrlf::Input,
+                eol: None,
+            },
+        )
+    })?;
+
+    for path in ["a.txt", "b.txt"] {
+        let out = pipe.convert_to_git(
+            "hi\r\n".as_bytes(),
+            Path::new(path),
+            &mut |_path, _attrs| {},
+            &mut no_object_in_index,
+        )?;
+        assert_eq!(
+            out.as_bytes().expect("converted").as_bstr(),
+            "hi\n",
+            "warnings don't prevent the conversion"
+        );
+    }
+    assert_eq!(
+        pipe.take_round_trip_warnings(),
+        [
+            ("a.txt".into(), eol::convert_to_git::RoundTripIssue::CrlfReplacedByLf),
+            ("b.txt".into(), eol::convert_to_git::RoundTripIssue::CrlfReplacedByLf)
+        ]
+    );
+    assert_eq!(pipe.take_round_trip_warnings(), [], "warnings are taken");
+    Ok(())
+}
+
 #[test]
 fn all_stages_mean_streaming_is_impossible() -> gix_testtools::Result {
     let (mut cache, mut pipe) = pipeline("all-filters", || {
@@ -49,7 +87,7 @@ fn all_stages_mean_streaming_is_impossible() -> gix_testtools::Result {
     })?;
 
     let mut out = pipe.convert_to_git(
-        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$".as_bytes(),
+        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $".as_bytes(),
         Path::new("any.txt"),
         &mut |path, attrs| {
             cache
@@ -78,7 +116,7 @@ fn only_driver_means_streaming_is_possible() -> gix_testtools::Result {
     })?;
 
     let mut out = pipe.convert_to_git(
-        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$".as_bytes(),
+        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $".as_bytes(),
         Path::new("subdir/doesnot/matter/any.txt"),
         &mut |path, attrs| {
             cache
@@ -94,7 +132,7 @@ fn only_driver_means_streaming_is_possible() -> gix_testtools::Result {
     out.read_to_end(&mut buf)?;
     assert_eq!(
         buf.as_bstr(),
-        "a\r\nb\r\n$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$",
+        "a\r\nb\r\n$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $",
         "one filter was reversed"
     );
     Ok(())
@@ -106,7 +144,7 @@ fn no_filter_means_reader_is_returned_unchanged() -> gix_testtools::Result {
         (vec![], Vec::new(), CrlfRoundTripCheck::Fail, Default::default())
     })?;
 
-    let input = "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$";
+    let input = "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $";
     let mut out = pipe.convert_to_git(
         input.as_bytes(),
         Path::new("other.txt"),
@@ -37,7 +37,7 @@ fn all_stages() -> gix_testtools::Result {
     out.read_to_end(&mut buf)?;
     assert_eq!(
         buf.as_bstr(),
-        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$",
+        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $",
         "the buffer shows that a lot of transformations were applied"
     );
     Ok(())
@@ -68,7 +68,7 @@ fn all_stages_no_filter() -> gix_testtools::Result {
     let buf = out.as_bytes().expect("no filter process");
     assert_eq!(
         buf.as_bstr(),
-        "$Id: a77d7acbc809ac8df987a769221c83137ba1b9f9$a\r\nb\r\n",
+        "$Id: a77d7acbc809ac8df987a769221c83137ba1b9f9 $a\r\nb\r\n",
         "the buffer shows that a lot of transformations were applied"
     );
     Ok(())
@@ -98,3 +98,29 @@ fn no_filter() -> gix_testtools::Result {
     assert_eq!(actual.as_ptr(), input.as_ptr(), "…which means it's exactly the same");
     Ok(())
 }
+
+#[test]
+fn may_convert_to_worktree() -> gix_testtools::Result {
+    for (name, path, expected) in [
+        ("all-filters", "any.txt", true),
+        ("all-filters", "other.txt", true),
+        ("no-filters", "other.txt", false),
+    ] {
+        let (mut cache, mut pipe) = pipeline(name, || {
+            (
+                vec![driver_with_process()],
+                Vec::new(),
+                CrlfRoundTripCheck::Skip,
+                Default::default(),
+            )
+        })?;
+        let actual = pipe.may_convert_to_worktree(path.into(), &mut |path, attrs| {
+            cache
+                .at_entry(path, None, &gix_object::find::Never)
+                .expect("cannot fail")
+                .matching_attributes(attrs);
+        })?;
+        assert_eq!(actual, expected, "{name}: {path}");
+    }
+    Ok(())
+}
@@ -50,7 +50,7 @@ fn pipeline(
     name: &str,
     init: impl FnOnce() -> (
         Vec<gix_filter::Driver>,
-        Vec<&'static encoding_rs::Encoding>,
+        Vec<gix_filter::worktree::Encoding>,
         gix_filter::pipeline::CrlfRoundTripCheck,
         eol::Configuration,
     ),
@@ -1,6 +1,9 @@
 mod encoding {
     mod for_label {
-        use gix_filter::worktree;
+        use gix_filter::{
+            worktree,
+            worktree::{encoding::ByteOrder, Encoding},
+        };
 
         #[test]
         fn unknown() {
@@ -11,16 +14,26 @@ mod encoding {
         }
 
         #[test]
-        fn utf32_is_not_supported() {
-            for enc in ["UTF-32BE", "UTF-32LE", "UTF-32", "UTF-32LE-BOM", "UTF-32BE-BOM"] {
-                assert!(
-                    matches!(
-                        worktree::encoding::for_label(enc).unwrap_err(),
-                        worktree::encoding::for_label::Error::Unknown { .. }
-                    ),
-                    "it's not needed for the web and this crate is meant for use in firefox"
+        fn utf32_is_supported() {
+            for (label, expected) in [
+                ("UTF-32BE", ByteOrder::BigEndian),
+                ("utf32le", ByteOrder::LittleEndian),
+                ("UTF-32", ByteOrder::Bom),
+            ] {
+                let enc = worktree::encoding::for_label(label).unwrap();
+                assert_eq!(enc, Encoding::Utf32(expected));
+                assert_eq!(
+                    enc.name().to_ascii_lowercase(),
+                    label.to_ascii_lowercase().replace("f32", "f-32")
                 );
             }
+            assert!(
+                matches!(
+                    worktree::encoding::for_label("UTF-32LE-BOM").unwrap_err(),
+                    worktree::encoding::for_label::Error::Unknown { .. }
+                ),
+                "git only knows this variant for UTF-16"
+            );
         }
 
         #[test]
@@ -33,23 +46,30 @@ mod encoding {
 
         #[test]
         fn various_utf_16_without_bom_suffix_are_supported() {
-            for label in ["UTF-16BE", "UTF-16LE"] {
+            for (label, expected) in [
+                ("UTF-16BE", ByteOrder::BigEndian),
+                ("UTF-16LE", ByteOrder::LittleEndian),
+                ("utf16", ByteOrder::Bom),
+                ("UTF-16", ByteOrder::Bom),
+            ] {
                 let enc = worktree::encoding::for_label(label).unwrap();
-                assert_eq!(enc.name(), label);
+                assert_eq!(enc, Encoding::Utf16(expected));
             }
         }
 
         #[test]
-        fn various_utf_16_with_bom_suffix_are_unsupported() {
-            for label in ["UTF-16BE-BOM", "UTF-16LE-BOM"] {
-                assert!(
-                    matches!(
-                        worktree::encoding::for_label(label).unwrap_err(),
-                        worktree::encoding::for_label::Error::Unknown { .. }
-                    ),
-                    "git supports these and has special handling, but we have not for now. Git has no tests for that either."
-                );
-            }
+        fn utf_16_with_bom_suffix_is_only_supported_for_little_endian() {
+            assert_eq!(
+                worktree::encoding::for_label("UTF-16LE-BOM").unwrap(),
+                Encoding::Utf16(ByteOrder::LittleEndianWithBom)
+            );
+            assert!(
+                matches!(
+                    worktree::encoding::for_label("UTF-16BE-BOM").unwrap_err(),
+                    worktree::encoding::for_label::Error::Unknown { .. }
+                ),
+                "git only knows the little-endian version"
+            );
         }
 
         #[test]
@@ -66,7 +86,10 @@ mod encoding {
 
 mod encode_to_git {
     use bstr::ByteSlice;
-    use gix_filter::{worktree, worktree::encode_to_git::RoundTripCheck};
+    use gix_filter::{
+        worktree,
+        worktree::{encode_to_git::RoundTripCheck, encoding::ByteOrder, Encoding},
+    };
 
     #[test]
     fn simple() -> crate::Result {
@@ -78,11 +101,90 @@ mod encode_to_git {
         }
         Ok(())
     }
+
+    #[test]
+    fn utf16_and_utf32() -> crate::Result {
+        let mut buf = Vec::new();
+        for (input, encoding) in [
+            (&b"h\0\xe4\0"[..], Encoding::Utf16(ByteOrder::LittleEndian)),
+            (b"\0h\0\xe4", Encoding::Utf16(ByteOrder::BigEndian)),
+            (b"\xff\xfeh\0\xe4\0", Encoding::Utf16(ByteOrder::Bom)),
+            (b"\xfe\xff\0h\0\xe4", Encoding::Utf16(ByteOrder::Bom)),
+            (b"\xfe\xff\0h\0\xe4", Encoding::Utf16(ByteOrder::LittleEndianWithBom)),
+            (b"h\0\xe4\0", Encoding::Utf16(ByteOrder::LittleEndianWithBom)),
+            (b"h\0\0\0\xe4\0\0\0", Encoding::Utf32(ByteOrder::LittleEndian)),
+            (b"\0\0\xfe\xff\0\0\0h\0\0\0\xe4", Encoding::Utf32(ByteOrder::Bom)),
+        ] {
+            worktree::encode_to_git(input, encoding, &mut buf, RoundTripCheck::Fail)?;
+            assert_eq!(buf.as_bstr(), "hä", "{encoding:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn utf16_surrogate_pairs_and_malformed_input() -> crate::Result {
+        let mut buf = Vec::new();
+        let encoding = Encoding::Utf16(ByteOrder::BigEndian);
+        worktree::encode_to_git(b"\xd8\x3d\xde\x00", encoding, &mut buf, RoundTripCheck::Skip)?;
+        assert_eq!(buf.as_bstr(), "😀");
+
+        for input in [&b"\xd8\x3d"[..], b"\0h\0"] {
+            assert_eq!(
+                worktree::encode_to_git(input, encoding, &mut buf, RoundTripCheck::Skip)
+                    .unwrap_err()
+                    .to_string(),
+                "The input was malformed and could not be decoded as 'UTF-16BE'",
+                "lone surrogates and incomplete code units are rejected"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bom_is_prohibited_if_byte_order_is_explicit() {
+        let mut buf = Vec::new();
+        for (input, encoding, expected) in [
+            (
+                &b"\xff\xfeh\0"[..],
+                Encoding::Utf16(ByteOrder::LittleEndian),
+                "UTF-16LE",
+            ),
+            (b"\xfe\xff\0h", Encoding::Utf16(ByteOrder::BigEndian), "UTF-16BE"),
+            (
+                b"\0\0\xfe\xff\0\0\0h",
+                Encoding::Utf32(ByteOrder::BigEndian),
+                "UTF-32BE",
+            ),
+        ] {
+            let err = worktree::encode_to_git(input, encoding, &mut buf, RoundTripCheck::Skip).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "A byte order mark (BOM) is prohibited if encoded as {expected}, use {} as working-tree-encoding instead",
+                    &expected[..6]
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn bom_is_required_if_byte_order_is_implicit() {
+        let mut buf = Vec::new();
+        let err = worktree::encode_to_git(b"h\0", Encoding::Utf16(ByteOrder::Bom), &mut buf, RoundTripCheck::Skip)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "A byte order mark (BOM) is required if encoded as UTF-16, use UTF-16BE or UTF-16LE (depending on the byte order) as working-tree-encoding instead"
+        );
+    }
 }
 
 mod encode_to_worktree {
     use bstr::ByteSlice;
-    use gix_filter::{worktree, worktree::encode_to_git::RoundTripCheck};
+    use gix_filter::{
+        worktree,
+        worktree::{encode_to_git::RoundTripCheck, encoding::ByteOrder, Encoding},
+    };
 
     #[test]
     fn shift_jis() -> crate::Result {
@@ -96,4 +198,27 @@ mod encode_to_worktree {
         assert_eq!(re_encoded.as_bstr(), input, "this should be round-trippable too");
         Ok(())
     }
+
+    #[test]
+    fn unicode_round_trips() -> crate::Result {
+        let input = "hä 😀\n";
+        for (encoding, bom) in [
+            (Encoding::Utf16(ByteOrder::LittleEndian), &[][..]),
+            (Encoding::Utf16(ByteOrder::BigEndian), &[]),
+            (Encoding::Utf16(ByteOrder::Bom), &[0xfe, 0xff]),
+            (Encoding::Utf16(ByteOrder::LittleEndianWithBom), &[0xff, 0xfe]),
+            (Encoding::Utf32(ByteOrder::LittleEndian), &[]),
+            (Encoding::Utf32(ByteOrder::Bom), &[0, 0, 0xfe, 0xff]),
+        ] {
+            let mut buf = Vec::new();
+            worktree::encode_to_worktree(input.as_bytes(), encoding, &mut buf)?;
+            assert!(buf.starts_with(bom), "{encoding:?} writes a BOM only if it is required");
+            assert_ne!(buf.as_bstr(), input, "{encoding:?} is actually applied");
+
+            let mut re_encoded = Vec::new();
+            worktree::encode_to_git(&buf, encoding, &mut re_encoded, RoundTripCheck::Fail)?;
+            assert_eq!(re_encoded.as_bstr(), input, "{encoding:?} round-trips");
+        }
+        Ok(())
+    }
 }
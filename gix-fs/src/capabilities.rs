@@ -1,8 +1,180 @@
-// TODO: tests
-use std::path::Path;
+use std::{path::Path, sync::Mutex};
 
 use crate::Capabilities;
 
+/// How a filesystem treats the precomposed and decomposed forms of unicode characters in file names,
+/// like `ä` and `"a\u{308}"`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub enum UnicodeNormalization {
+    /// File names are just bytes, so both forms refer to different files.
+    None,
+    /// Both forms refer to the same file, and file names are stored in the form they were created with.
+    Insensitive,
+    /// Both forms refer to the same file, and file names are stored in their decomposed form, like on HFS+.
+    Decompose,
+}
+
+/// A detailed report about the capabilities of a filesystem, as returned by [`Capabilities::probe_extended()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub struct Report {
+    /// If `true`, the filesystem ignores the case of file names, which makes `A` the same file as `a`.
+    pub ignore_case: bool,
+    /// How precomposed and decomposed unicode in file names is treated.
+    pub unicode_normalization: UnicodeNormalization,
+    /// If `true`, the executable bit of files is honored.
+    pub executable_bit: bool,
+    /// If `true`, symbolic links can be created.
+    pub symlink: bool,
+    /// The maximum length of a single file name in bytes, or `None` if it couldn't be determined, which is also the case
+    /// if there is no limit below [`MAX_PROBED_FILENAME_LEN`].
+    pub max_filename_len: Option<usize>,
+}
+
+/// The longest file name that is tried when probing for [`Report::max_filename_len`].
+pub const MAX_PROBED_FILENAME_LEN: usize = 4096;
+
+impl From<Report> for Capabilities {
+    fn from(report: Report) -> Self {
+        Capabilities {
+            precompose_unicode: report.unicode_normalization != UnicodeNormalization::None,
+            ignore_case: report.ignore_case,
+            executable_bit: report.executable_bit,
+            symlink: report.symlink,
+        }
+    }
+}
+
+impl Report {
+    /// Probe all capabilities of the filesystem that `dir` is located on, by creating and removing a temporary directory
+    /// with test files in it, without using or updating the cache used by [`Capabilities::probe_extended()`].
+    ///
+    /// All errors are ignored and interpreted on top of the default for the platform the binary is compiled for.
+    pub fn probe(dir: &Path) -> Self {
+        Self::try_probe(dir).unwrap_or_else(Self::fallback)
+    }
+
+    /// The report based on the defaults for the platform the binary is compiled for.
+    fn fallback() -> Self {
+        let ctx = Capabilities::default();
+        Report {
+            ignore_case: ctx.ignore_case,
+            unicode_normalization: default_normalization(&ctx),
+            executable_bit: ctx.executable_bit,
+            symlink: ctx.symlink,
+            max_filename_len: None,
+        }
+    }
+
+    /// Like [`probe()`](Self::probe()), but return `None` if the temporary directory couldn't be created in `dir`,
+    /// so nothing could be probed.
+    fn try_probe(dir: &Path) -> Option<Self> {
+        let ctx = Capabilities::default();
+        let probe_dir = dir.join(format!("_gix_probe{}", fastrand::usize(..)));
+        std::fs::create_dir(&probe_dir).ok()?;
+        let report = Report {
+            ignore_case: probe_case_folding(&probe_dir).unwrap_or(ctx.ignore_case),
+            unicode_normalization: probe_unicode_normalization(&probe_dir).unwrap_or(default_normalization(&ctx)),
+            executable_bit: Capabilities::probe_file_mode(&probe_dir).unwrap_or(ctx.executable_bit),
+            symlink: Capabilities::probe_symlink(&probe_dir).unwrap_or(ctx.symlink),
+            max_filename_len: probe_max_filename_len(&probe_dir),
+        };
+        std::fs::remove_dir_all(probe_dir).ok();
+        Some(report)
+    }
+}
+
+fn default_normalization(ctx: &Capabilities) -> UnicodeNormalization {
+    if ctx.precompose_unicode {
+        UnicodeNormalization::Insensitive
+    } else {
+        UnicodeNormalization::None
+    }
+}
+
+/// Reports of filesystems that were probed already, keyed by the mount they belong to.
+static REPORTS_BY_MOUNT: Mutex<Vec<(MountId, Report)>> = Mutex::new(Vec::new());
+
+#[derive(PartialEq, Eq, Clone)]
+enum MountId {
+    #[cfg(unix)]
+    Device(u64),
+    #[cfg(not(unix))]
+    Root(std::ffi::OsString),
+}
+
+#[cfg(unix)]
+fn mount_id(dir: &Path) -> Option<MountId> {
+    use std::os::unix::fs::MetadataExt;
+    dir.metadata().ok().map(|meta| MountId::Device(meta.dev()))
+}
+
+#[cfg(not(unix))]
+fn mount_id(dir: &Path) -> Option<MountId> {
+    let dir = std::fs::canonicalize(dir).ok()?;
+    let root = dir.components().next()?;
+    Some(MountId::Root(root.as_os_str().to_owned()))
+}
+
+fn probe_case_folding(root: &Path) -> std::io::Result<bool> {
+    let rand = fastrand::usize(..);
+    let path = root.join(format!("_test_CaSe{rand}"));
+    std::fs::OpenOptions::new().create_new(true).write(true).open(&path)?;
+    let res = root.join(format!("_test_case{rand}")).symlink_metadata().map(|_| true);
+    std::fs::remove_file(path)?;
+    Ok(res.is_ok())
+}
+
+fn probe_unicode_normalization(root: &Path) -> std::io::Result<UnicodeNormalization> {
+    let rand = fastrand::usize(..);
+    let precomposed = format!("ä{rand}");
+    let decomposed = format!("a\u{308}{rand}");
+
+    let precomposed_path = root.join(&precomposed);
+    std::fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&precomposed_path)?;
+    let res = if root.join(&decomposed).symlink_metadata().is_err() {
+        UnicodeNormalization::None
+    } else if std::fs::read_dir(root)?
+        .filter_map(Result::ok)
+        .any(|entry| entry.file_name().to_str() == Some(decomposed.as_str()))
+    {
+        UnicodeNormalization::Decompose
+    } else {
+        UnicodeNormalization::Insensitive
+    };
+    std::fs::remove_file(precomposed_path)?;
+    Ok(res)
+}
+
+fn probe_max_filename_len(root: &Path) -> Option<usize> {
+    let can_create = |len: usize| {
+        let path = root.join("f".repeat(len));
+        let created = std::fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .is_ok();
+        created && std::fs::remove_file(path).is_ok()
+    };
+    if !can_create(1) || can_create(MAX_PROBED_FILENAME_LEN) {
+        return None;
+    }
+    let (mut good, mut bad) = (1, MAX_PROBED_FILENAME_LEN);
+    while bad - good > 1 {
+        let mid = good + (bad - good) / 2;
+        if can_create(mid) {
+            good = mid;
+        } else {
+            bad = mid;
+        }
+    }
+    Some(good)
+}
+
 #[cfg(windows)]
 impl Default for Capabilities {
     fn default() -> Self {
@@ -55,6 +227,37 @@ impl Capabilities {
         }
     }
 
+    /// Like [`probe()`](Self::probe()), but return a more detailed [`Report`] about the filesystem that `dir` is located on,
+    /// by creating and removing a temporary directory with test files in it. Thus, `dir` must be writable, but doesn't have
+    /// to be a git directory.
+    ///
+    /// The report is cached per mounted filesystem, or per volume on platforms other than Unix, so probing happens only once
+    /// per filesystem and process, no matter how many repositories are located on it.
+    /// If nothing could be probed in `dir`, the defaults are returned without caching them.
+    /// Use [`Report::probe()`] to bypass the cache.
+    pub fn probe_extended(dir: &Path) -> Report {
+        let Some(mount) = mount_id(dir) else {
+            return Report::probe(dir);
+        };
+        let cached = REPORTS_BY_MOUNT
+            .lock()
+            .ok()
+            .and_then(|reports| reports.iter().find(|(id, _)| *id == mount).map(|(_, report)| *report));
+        if let Some(report) = cached {
+            return report;
+        }
+        let Some(report) = Report::try_probe(dir) else {
+            // Don't let the fallback stand in for the filesystem, which may well be probed in another directory.
+            return Report::fallback();
+        };
+        if let Ok(mut reports) = REPORTS_BY_MOUNT.lock() {
+            if !reports.iter().any(|(id, _)| *id == mount) {
+                reports.push((mount, report));
+            }
+        }
+        report
+    }
+
     #[cfg(unix)]
     fn probe_file_mode(root: &Path) -> std::io::Result<bool> {
         use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
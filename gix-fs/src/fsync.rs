@@ -0,0 +1,131 @@
+//! Flushing written files to permanent storage so they survive a system crash, as configured by `core.fsync` and
+//! `core.fsyncMethod`.
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+/// Determine how written files are flushed to permanent storage, similar to `core.fsyncMethod`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Method {
+    /// Flush the data and metadata of each file before it is moved into place, and wait until the storage device
+    /// has persisted them.
+    #[default]
+    Fsync,
+    /// Only flush the data of each file before it is moved into place, which is faster but may not be safe
+    /// on all filesystems.
+    WriteoutOnly,
+    /// Like [`Method::Fsync`], but files that are typically written in large numbers, like loose objects, are
+    /// added to a [`Batch`] instead, to be flushed together once the batch is complete.
+    ///
+    /// Until then, the operating system can write them out in the background, which makes flushing them much cheaper.
+    Batch,
+}
+
+/// The kinds of files that should be flushed to permanent storage when written, similar to `core.fsync`.
+///
+/// The default matches the one of `git`, which flushes packs, their metadata and the commit-graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Components {
+    /// Loose objects, `loose-object` in `git`.
+    pub loose_objects: bool,
+    /// Pack data files, `pack` in `git`.
+    pub packs: bool,
+    /// Pack indices and other files that describe packs, `pack-metadata` in `git`.
+    pub pack_metadata: bool,
+    /// The commit-graph, `commit-graph` in `git`.
+    pub commit_graph: bool,
+    /// The index file, `index` in `git`.
+    pub index: bool,
+    /// Loose references and the `packed-refs` file, `reference` in `git`.
+    pub references: bool,
+}
+
+impl Default for Components {
+    fn default() -> Self {
+        Components {
+            packs: true,
+            pack_metadata: true,
+            commit_graph: true,
+            ..Components::none()
+        }
+    }
+}
+
+impl Components {
+    /// Return an instance that doesn't flush any file.
+    pub fn none() -> Self {
+        Components {
+            loose_objects: false,
+            packs: false,
+            pack_metadata: false,
+            commit_graph: false,
+            index: false,
+            references: false,
+        }
+    }
+
+    /// Return an instance that flushes all files.
+    pub fn all() -> Self {
+        Components {
+            loose_objects: true,
+            packs: true,
+            pack_metadata: true,
+            commit_graph: true,
+            index: true,
+            references: true,
+        }
+    }
+}
+
+/// Flush `file` to permanent storage according to `method`.
+pub fn file(file: &fs::File, method: Method) -> io::Result<()> {
+    match method {
+        Method::Fsync | Method::Batch => file.sync_all(),
+        Method::WriteoutOnly => file.sync_data(),
+    }
+}
+
+/// A list of files that were written in [batch mode](Method::Batch) and need to be flushed before anything refers to them.
+#[derive(Default, Debug)]
+pub struct Batch {
+    paths: Mutex<Vec<PathBuf>>,
+}
+
+impl Batch {
+    /// Remember that the file at `path` was written and needs to be flushed when the batch is complete.
+    pub fn push(&self, path: PathBuf) {
+        self.paths.lock().unwrap_or_else(PoisonError::into_inner).push(path);
+    }
+
+    /// Return `true` if there is no file left to flush.
+    pub fn is_empty(&self) -> bool {
+        self.paths.lock().unwrap_or_else(PoisonError::into_inner).is_empty()
+    }
+
+    /// Flush all files added since the last call to permanent storage, and return their amount.
+    ///
+    /// Files that couldn't be flushed yet remain in the batch.
+    pub fn flush(&self) -> io::Result<usize> {
+        let paths = std::mem::take(&mut *self.paths.lock().unwrap_or_else(PoisonError::into_inner));
+        for (num_flushed, path) in paths.iter().enumerate() {
+            // Windows can only flush files that are open for writing.
+            let res = fs::OpenOptions::new()
+                .read(true)
+                .write(cfg!(windows))
+                .open(path)
+                .and_then(|f| f.sync_all());
+            if let Err(err) = res {
+                self.paths
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .extend(paths.into_iter().skip(num_flushed));
+                return Err(err);
+            }
+        }
+        Ok(paths.len())
+    }
+}
@@ -30,7 +30,10 @@ pub struct Capabilities {
     /// out as files which contain the link as text.
     pub symlink: bool,
 }
-mod capabilities;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod capabilities;
 
 mod snapshot;
 pub use snapshot::{FileSnapshot, SharedFileSnapshot, SharedFileSnapshotMut};
@@ -48,6 +51,10 @@ pub use read_dir::function::read_dir;
 #[allow(clippy::empty_docs)]
 pub mod dir;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod fsync;
+
 /// Like [`std::env::current_dir()`], but it will `precompose_unicode` if that value is true, if the current directory
 /// is valid unicode and if there are decomposed unicode codepoints.
 ///
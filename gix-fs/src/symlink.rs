@@ -36,9 +36,20 @@ pub fn remove(path: &Path) -> io::Result<()> {
 /// Note that if a symlink target (the `original`) isn't present on disk, it's assumed to be a
 /// file, creating a dangling file symlink. This is similar to a dangling symlink on Unix,
 /// which doesn't have to care about the target type though.
+///
+/// As `original` is typically obtained from a Git repository, it may use slashes as separators which are
+/// converted into backslashes as Windows won't resolve them otherwise. This is what Git for Windows does as well.
 #[cfg(windows)]
 pub fn create(original: &Path, link: &Path) -> io::Result<()> {
     use std::os::windows::fs::{symlink_dir, symlink_file};
+    let original_with_backslashes;
+    let original = match original.to_str() {
+        Some(original_str) if original_str.contains('/') => {
+            original_with_backslashes = std::path::PathBuf::from(original_str.replace('/', "\\"));
+            original_with_backslashes.as_path()
+        }
+        _ => original,
+    };
     // TODO: figure out if links to links count as files or whatever they point at
     let orig_abs = link.parent().expect("dir for link").join(original);
     if orig_abs.is_dir() {
@@ -65,3 +76,19 @@ pub fn is_collision_error(err: &std::io::Error) -> bool {
 pub fn is_collision_error(err: &std::io::Error) -> bool {
     err.kind() == AlreadyExists || err.kind() == std::io::ErrorKind::PermissionDenied
 }
+
+/// Return true if `err` indicates that symlinks can't be created on the filesystem, or by the current user,
+/// in which case the symlink should be written as ordinary file which contains the symlink target instead.
+#[cfg(not(windows))]
+pub fn is_unsupported_error(err: &std::io::Error) -> bool {
+    // EPERM is what Linux returns for filesystems that don't support symlinks, like FAT.
+    err.kind() == std::io::ErrorKind::Unsupported || err.raw_os_error() == Some(1)
+}
+
+/// Return true if `err` indicates that symlinks can't be created on the filesystem, or by the current user,
+/// in which case the symlink should be written as ordinary file which contains the symlink target instead.
+#[cfg(windows)]
+pub fn is_unsupported_error(err: &std::io::Error) -> bool {
+    // ERROR_PRIVILEGE_NOT_HELD is returned if developer mode is off and the user isn't an administrator.
+    err.kind() == std::io::ErrorKind::Unsupported || err.raw_os_error() == Some(1314)
+}
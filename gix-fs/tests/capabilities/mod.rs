@@ -46,3 +46,62 @@ fn parallel_probe() {
         thread.join().expect("no panic");
     }
 }
+
+#[test]
+fn probe_extended() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::File::create(dir.path().join("config")).unwrap();
+    let report = gix_fs::capabilities::Report::probe(dir.path());
+
+    let entries: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_name().to_str() != Some("config"))
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(
+        entries.len(),
+        0,
+        "there should be no left-over files after probing, found {entries:?}"
+    );
+
+    let caps = gix_fs::Capabilities::probe(dir.path());
+    assert_eq!(
+        gix_fs::Capabilities::from(report),
+        caps,
+        "both probes agree on what they have in common"
+    );
+    let max_filename_len = report.max_filename_len.expect("all common filesystems have a limit");
+    assert!(
+        max_filename_len >= 143,
+        "even encrypted filesystems allow reasonably long names, got {max_filename_len}"
+    );
+    if cfg!(target_os = "linux") {
+        assert_eq!(
+            report.unicode_normalization,
+            gix_fs::capabilities::UnicodeNormalization::None,
+            "Linux filesystems see bytes"
+        );
+    }
+
+    assert_eq!(
+        gix_fs::Capabilities::probe_extended(dir.path()),
+        gix_fs::Capabilities::probe_extended(dir.path()),
+        "the cached report is returned the second time"
+    );
+}
+
+#[test]
+fn probe_extended_does_not_cache_the_fallback() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("file");
+    std::fs::File::create(&file).unwrap();
+    // Nothing can be probed inside of a file, so the defaults are used.
+    gix_fs::Capabilities::probe_extended(&file);
+
+    assert_eq!(
+        gix_fs::Capabilities::probe_extended(dir.path()),
+        gix_fs::capabilities::Report::probe(dir.path()),
+        "the directory on the same filesystem can still be probed"
+    );
+}
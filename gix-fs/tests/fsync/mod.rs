@@ -0,0 +1,38 @@
+use gix_fs::fsync::{Batch, Components, Method};
+
+#[test]
+fn components_default_to_what_git_flushes() {
+    let components = Components::default();
+    assert!(components.packs && components.pack_metadata && components.commit_graph);
+    assert!(!components.loose_objects && !components.references && !components.index);
+    assert_eq!(Method::default(), Method::Fsync);
+}
+
+#[test]
+fn batch_flushes_all_files_once() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let batch = Batch::default();
+    for name in ["a", "b"] {
+        let path = dir.path().join(name);
+        std::fs::write(&path, name)?;
+        batch.push(path);
+    }
+    assert!(!batch.is_empty());
+    assert_eq!(batch.flush()?, 2);
+    assert!(batch.is_empty());
+    assert_eq!(batch.flush()?, 0, "files are only flushed once");
+    Ok(())
+}
+
+#[test]
+fn batch_keeps_files_it_could_not_flush() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let batch = Batch::default();
+    batch.push(dir.path().join("missing"));
+    assert!(batch.flush().is_err());
+    assert!(!batch.is_empty(), "it can be retried");
+
+    std::fs::write(dir.path().join("missing"), b"")?;
+    assert_eq!(batch.flush()?, 1);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+/// The error returned by all fallible operations, with a [code](ErrorCode) and [class](ErrorClass) like `git2::Error`.
+#[derive(Debug)]
+pub struct Error {
+    code: ErrorCode,
+    class: ErrorClass,
+    message: String,
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+}
+
+/// A classification of errors to allow reacting to specific conditions, like `git2::ErrorCode`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// A generic error without more specific classification.
+    GenericError,
+    /// The requested object, reference or remote could not be found.
+    NotFound,
+    /// The object or reference to create already exists.
+    Exists,
+    /// A revision specification matched more than one object.
+    Ambiguous,
+    /// The operation isn't possible in a bare repository.
+    BareRepo,
+    /// `HEAD` refers to a branch without commits.
+    UnbornBranch,
+    /// A revision specification or reference name is invalid.
+    InvalidSpec,
+    /// Authentication with a remote failed.
+    Auth,
+    /// A user-provided callback requested the operation to be aborted.
+    User,
+}
+
+/// The subsystem an error originated in, like `git2::ErrorClass`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// No particular subsystem.
+    None,
+    /// Operating system operations, like I/O.
+    Os,
+    /// Invalid input.
+    Invalid,
+    /// Reference handling.
+    Reference,
+    /// Object decoding and lookup.
+    Object,
+    /// Network communication.
+    Net,
+    /// Opening or creating repositories.
+    Repository,
+    /// The git configuration.
+    Config,
+    /// The object database.
+    Odb,
+    /// The index.
+    Index,
+    /// User-provided callbacks.
+    Callback,
+    /// Computing the status of the worktree.
+    Filesystem,
+}
+
+/// Initialization
+impl Error {
+    /// Create a new generic error with the given `message`, like `git2::Error::from_str()`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(message: &str) -> Self {
+        Error {
+            code: ErrorCode::GenericError,
+            class: ErrorClass::None,
+            message: message.to_owned(),
+            source: None,
+        }
+    }
+
+    /// Create a new error with the given `code`, `class` and `message`, like `git2::Error::new()`.
+    pub fn new(code: ErrorCode, class: ErrorClass, message: impl fmt::Display) -> Self {
+        Error {
+            code,
+            class,
+            message: message.to_string(),
+            source: None,
+        }
+    }
+
+    pub(crate) fn from_gix(class: ErrorClass, err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Error {
+            code: ErrorCode::GenericError,
+            class,
+            message: err.to_string(),
+            source: Some(Box::new(err)),
+        }
+    }
+
+    pub(crate) fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+/// Access
+impl Error {
+    /// Return the code of this error, for reacting to specific conditions.
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Return the subsystem this error originated in.
+    pub fn class(&self) -> ErrorClass {
+        self.class
+    }
+
+    /// Return the error message, without the code and class.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}; class={:?}; code={:?}", self.message, self.class, self.code)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|err| err as _)
+    }
+}
+
+/// Convert `gix` errors into [`Error`] with the given class.
+pub(crate) trait ResultExt<T> {
+    fn or_class(self, class: ErrorClass) -> Result<T, Error>;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn or_class(self, class: ErrorClass) -> Result<T, Error> {
+        self.map_err(|err| Error::from_gix(class, err))
+    }
+}
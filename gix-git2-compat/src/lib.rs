@@ -0,0 +1,41 @@
+//! Provide the most commonly used types and methods of the [`git2`](https://docs.rs/git2) crate, implemented with `gix`,
+//! to make migrating existing applications a matter of changing imports, one call-site at a time.
+//!
+//! Types are named like their `git2` counterparts and their methods have the same names and similar signatures, but
+//! may return owned values where `git2` borrows data owned by `libgit2`.
+//! Whenever something isn't available here, use [`Repository::as_gix()`] to drop down to `gix` directly.
+//!
+//! ### Differences
+//!
+//! * [`Repository::statuses()`] never detects renames.
+//! * [`Remote::fetch()`] calls the `transfer_progress` callback only once, after the pack was received, and only supports
+//!   plaintext credentials. SSH transports authenticate through the `ssh` program instead.
+//!
+//! ### Feature Flags
+//!
+//! * `blocking-network-client` - make [`Remote::fetch()`] available, along with [`FetchOptions`] and [`RemoteCallbacks`].
+#![deny(missing_docs, rust_2018_idioms)]
+#![forbid(unsafe_code)]
+
+mod error;
+pub use error::{Error, ErrorClass, ErrorCode};
+
+mod oid;
+pub use oid::{ObjectType, Oid};
+
+mod object;
+pub use object::{Commit, Object, Signature, Time};
+
+mod reference;
+pub use reference::{Branch, BranchType, Branches, Reference};
+
+mod remote;
+pub use remote::Remote;
+#[cfg(feature = "blocking-network-client")]
+pub use remote::{Cred, CredentialType, FetchOptions, FetchPrune, Progress, RemoteCallbacks};
+
+mod repository;
+pub use repository::Repository;
+
+mod status;
+pub use status::{Status, StatusEntry, StatusOptions, Statuses};
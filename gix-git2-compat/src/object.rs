@@ -0,0 +1,229 @@
+use gix::bstr::ByteSlice;
+
+use crate::{error::ResultExt, Error, ErrorClass, ErrorCode, ObjectType, Oid};
+
+/// An object of any kind in the object database, like `git2::Object`.
+#[derive(Clone)]
+pub struct Object<'repo> {
+    inner: gix::Object<'repo>,
+}
+
+impl<'repo> Object<'repo> {
+    pub(crate) fn new(inner: gix::Object<'repo>) -> Self {
+        Object { inner }
+    }
+
+    /// Return the id of this object.
+    pub fn id(&self) -> Oid {
+        self.inner.id.into()
+    }
+
+    /// Return the kind of this object.
+    pub fn kind(&self) -> Option<ObjectType> {
+        Some(self.inner.kind.into())
+    }
+
+    /// Return the shortest unambiguous hexadecimal id of this object.
+    pub fn short_id(&self) -> Result<String, Error> {
+        Ok(self.inner.id().shorten().or_class(ErrorClass::Odb)?.to_string())
+    }
+
+    /// Follow tags and commits until an object of `kind` is found.
+    pub fn peel(&self, kind: ObjectType) -> Result<Object<'repo>, Error> {
+        let Some(kind) = kind.to_kind() else {
+            return Ok(self.clone());
+        };
+        self.inner
+            .clone()
+            .peel_to_kind(kind)
+            .map(Object::new)
+            .map_err(|err| Error::from_gix(ErrorClass::Object, err).with_code(ErrorCode::InvalidSpec))
+    }
+
+    /// Follow tags until a commit is found.
+    pub fn peel_to_commit(&self) -> Result<Commit<'repo>, Error> {
+        self.peel(ObjectType::Commit)?.into_commit().map_err(|_| {
+            Error::new(
+                ErrorCode::InvalidSpec,
+                ErrorClass::Object,
+                "object could not be peeled to a commit",
+            )
+        })
+    }
+
+    /// Turn this instance into a commit, or return it unchanged if it isn't one.
+    #[allow(clippy::result_large_err)]
+    pub fn into_commit(self) -> Result<Commit<'repo>, Object<'repo>> {
+        if self.inner.kind != gix::object::Kind::Commit {
+            return Err(self);
+        }
+        let commit = self.inner.clone().into_commit();
+        Commit::new(commit).map_err(|_| self)
+    }
+
+    /// Return the data of this object.
+    pub fn data(&self) -> &[u8] {
+        &self.inner.data
+    }
+}
+
+/// A commit, like `git2::Commit`.
+#[derive(Clone)]
+pub struct Commit<'repo> {
+    inner: gix::Commit<'repo>,
+    author: gix::actor::Signature,
+    committer: gix::actor::Signature,
+}
+
+impl<'repo> Commit<'repo> {
+    pub(crate) fn new(inner: gix::Commit<'repo>) -> Result<Self, Error> {
+        let author = inner.author().or_class(ErrorClass::Object)?.to_owned();
+        let committer = inner.committer().or_class(ErrorClass::Object)?.to_owned();
+        Ok(Commit {
+            inner,
+            author,
+            committer,
+        })
+    }
+
+    /// Return the id of this commit.
+    pub fn id(&self) -> Oid {
+        self.inner.id.into()
+    }
+
+    /// Return the full commit message, or `None` if it isn't valid UTF-8.
+    pub fn message(&self) -> Option<&str> {
+        self.message_bytes().to_str().ok()
+    }
+
+    /// Return the full commit message.
+    pub fn message_bytes(&self) -> &[u8] {
+        self.inner.message_raw_sloppy()
+    }
+
+    /// Return the author of this commit.
+    pub fn author(&self) -> Signature<'_> {
+        Signature(self.author.to_ref())
+    }
+
+    /// Return the committer of this commit.
+    pub fn committer(&self) -> Signature<'_> {
+        Signature(self.committer.to_ref())
+    }
+
+    /// Return the time at which the commit was committed.
+    pub fn time(&self) -> Time {
+        Time(self.committer.time)
+    }
+
+    /// Return the id of the tree of this commit.
+    pub fn tree_id(&self) -> Oid {
+        self.inner
+            .tree_id()
+            .expect("the commit was decoded successfully before")
+            .into()
+    }
+
+    /// Return the amount of parents of this commit.
+    pub fn parent_count(&self) -> usize {
+        self.inner.parent_ids().count()
+    }
+
+    /// Return the ids of all parents of this commit, in order.
+    pub fn parent_ids(&self) -> impl Iterator<Item = Oid> + '_ {
+        self.inner.parent_ids().map(Oid::from)
+    }
+
+    /// Return the id of the parent at index `i`.
+    pub fn parent_id(&self, i: usize) -> Result<Oid, Error> {
+        self.inner.parent_ids().nth(i).map(Oid::from).ok_or_else(|| {
+            Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Invalid,
+                format!("parent {i} does not exist"),
+            )
+        })
+    }
+
+    /// Return the parent at index `i`.
+    pub fn parent(&self, i: usize) -> Result<Commit<'repo>, Error> {
+        let id = self.inner.parent_ids().nth(i).ok_or_else(|| {
+            Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Invalid,
+                format!("parent {i} does not exist"),
+            )
+        })?;
+        let commit = id
+            .object()
+            .or_class(ErrorClass::Odb)?
+            .try_into_commit()
+            .or_class(ErrorClass::Object)?;
+        Commit::new(commit)
+    }
+
+    /// Return this commit as generic object.
+    pub fn as_object(&self) -> Object<'repo> {
+        Object::new(self.inner.clone().into())
+    }
+}
+
+/// The identity and time of an author or committer, like `git2::Signature`.
+#[derive(Debug, Copy, Clone)]
+pub struct Signature<'a>(gix::actor::SignatureRef<'a>);
+
+impl<'a> Signature<'a> {
+    /// Return the name, or `None` if it isn't valid UTF-8.
+    pub fn name(&self) -> Option<&'a str> {
+        self.0.name.to_str().ok()
+    }
+
+    /// Return the name.
+    pub fn name_bytes(&self) -> &'a [u8] {
+        self.0.name
+    }
+
+    /// Return the email, or `None` if it isn't valid UTF-8.
+    pub fn email(&self) -> Option<&'a str> {
+        self.0.email.to_str().ok()
+    }
+
+    /// Return the email.
+    pub fn email_bytes(&self) -> &'a [u8] {
+        self.0.email
+    }
+
+    /// Return the time of the signature.
+    pub fn when(&self) -> Time {
+        Time(self.0.time)
+    }
+}
+
+/// A point in time along with the timezone it was recorded in, like `git2::Time`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Time(gix::date::Time);
+
+impl Time {
+    /// Create a new instance from `seconds` since the unix epoch, in a timezone `offset` minutes away from UTC.
+    pub fn new(seconds: i64, offset: i32) -> Time {
+        Time(gix::date::Time::new(seconds, offset * 60))
+    }
+
+    /// Return the seconds since the unix epoch.
+    pub fn seconds(&self) -> i64 {
+        self.0.seconds
+    }
+
+    /// Return the offset of the timezone from UTC in minutes.
+    pub fn offset_minutes(&self) -> i32 {
+        self.0.offset / 60
+    }
+
+    /// Return the sign of the timezone offset, either `+` or `-`.
+    pub fn sign(&self) -> char {
+        match self.0.sign {
+            gix::date::time::Sign::Plus => '+',
+            gix::date::time::Sign::Minus => '-',
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+
+use crate::{Error, ErrorClass, ErrorCode};
+
+/// The id of an object, like `git2::Oid`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Oid(gix::ObjectId);
+
+/// Initialization
+impl Oid {
+    /// Parse a full hexadecimal object id.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(hex: &str) -> Result<Oid, Error> {
+        gix::ObjectId::from_hex(hex.as_bytes())
+            .map(Oid)
+            .map_err(|err| Error::from_gix(ErrorClass::Invalid, err).with_code(ErrorCode::InvalidSpec))
+    }
+
+    /// Create an id from its raw `bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Oid, Error> {
+        gix::ObjectId::try_from(bytes)
+            .map(Oid)
+            .map_err(|err| Error::from_gix(ErrorClass::Invalid, err))
+    }
+
+    /// Return an id with all bytes set to zero, which is used to indicate the absence of an object.
+    pub fn zero() -> Oid {
+        Oid(gix::ObjectId::null(gix::hash::Kind::Sha1))
+    }
+}
+
+/// Access
+impl Oid {
+    /// Return `true` if all bytes of this id are zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Return the raw bytes of this id.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl std::str::FromStr for Oid {
+    type Err = Error;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Oid::from_str(hex)
+    }
+}
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<gix::ObjectId> for Oid {
+    fn from(id: gix::ObjectId) -> Self {
+        Oid(id)
+    }
+}
+
+impl From<gix::Id<'_>> for Oid {
+    fn from(id: gix::Id<'_>) -> Self {
+        Oid(id.detach())
+    }
+}
+
+impl From<Oid> for gix::ObjectId {
+    fn from(id: Oid) -> Self {
+        id.0
+    }
+}
+
+/// The kind of an object, like `git2::ObjectType`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ObjectType {
+    /// Any kind of object, for use in lookups that shouldn't be restricted to a specific kind.
+    Any,
+    /// A commit.
+    Commit,
+    /// A tree.
+    Tree,
+    /// A blob.
+    Blob,
+    /// An annotated tag.
+    Tag,
+}
+
+impl ObjectType {
+    /// Return the name of the object type as `git` would print it.
+    pub fn str(&self) -> &'static str {
+        match self {
+            ObjectType::Any => "any",
+            ObjectType::Commit => "commit",
+            ObjectType::Tree => "tree",
+            ObjectType::Blob => "blob",
+            ObjectType::Tag => "tag",
+        }
+    }
+
+    pub(crate) fn to_kind(self) -> Option<gix::object::Kind> {
+        Some(match self {
+            ObjectType::Any => return None,
+            ObjectType::Commit => gix::object::Kind::Commit,
+            ObjectType::Tree => gix::object::Kind::Tree,
+            ObjectType::Blob => gix::object::Kind::Blob,
+            ObjectType::Tag => gix::object::Kind::Tag,
+        })
+    }
+}
+
+impl From<gix::object::Kind> for ObjectType {
+    fn from(kind: gix::object::Kind) -> Self {
+        match kind {
+            gix::object::Kind::Commit => ObjectType::Commit,
+            gix::object::Kind::Tree => ObjectType::Tree,
+            gix::object::Kind::Blob => ObjectType::Blob,
+            gix::object::Kind::Tag => ObjectType::Tag,
+        }
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.str())
+    }
+}
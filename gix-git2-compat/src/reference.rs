@@ -0,0 +1,205 @@
+use gix::{bstr::ByteSlice, prelude::ReferenceExt};
+
+use crate::{error::ResultExt, Commit, Error, ErrorClass, ErrorCode, Object, Oid};
+
+/// A reference, like `git2::Reference`.
+#[derive(Clone)]
+pub struct Reference<'repo> {
+    inner: gix::Reference<'repo>,
+}
+
+impl<'repo> Reference<'repo> {
+    pub(crate) fn new(inner: gix::Reference<'repo>) -> Self {
+        Reference { inner }
+    }
+
+    /// Return the full name of this reference, like `refs/heads/main`, or `None` if it isn't valid UTF-8.
+    pub fn name(&self) -> Option<&str> {
+        self.name_bytes().to_str().ok()
+    }
+
+    /// Return the full name of this reference.
+    pub fn name_bytes(&self) -> &[u8] {
+        self.inner.name().as_bstr()
+    }
+
+    /// Return the name of this reference without its well-known prefix, like `main` for `refs/heads/main`,
+    /// or `None` if it isn't valid UTF-8.
+    pub fn shorthand(&self) -> Option<&str> {
+        self.shorthand_bytes().to_str().ok()
+    }
+
+    /// Return the name of this reference without its well-known prefix.
+    pub fn shorthand_bytes(&self) -> &[u8] {
+        self.inner.name().shorten()
+    }
+
+    /// Return `true` if this is a local branch.
+    pub fn is_branch(&self) -> bool {
+        self.name_bytes().starts_with(b"refs/heads/")
+    }
+
+    /// Return `true` if this is a remote tracking branch.
+    pub fn is_remote(&self) -> bool {
+        self.name_bytes().starts_with(b"refs/remotes/")
+    }
+
+    /// Return `true` if this is a tag.
+    pub fn is_tag(&self) -> bool {
+        self.name_bytes().starts_with(b"refs/tags/")
+    }
+
+    /// Return the object this reference points to, or `None` if it is a symbolic reference.
+    pub fn target(&self) -> Option<Oid> {
+        self.inner.try_id().map(Oid::from)
+    }
+
+    /// Return the name of the reference this symbolic reference points to, or `None` if it points to an object.
+    pub fn symbolic_target(&self) -> Option<&str> {
+        match self.inner.target() {
+            gix::refs::TargetRef::Symbolic(name) => name.as_bstr().to_str().ok(),
+            gix::refs::TargetRef::Peeled(_) => None,
+        }
+    }
+
+    /// Follow symbolic references until a reference pointing to an object is found.
+    pub fn resolve(&self) -> Result<Reference<'repo>, Error> {
+        let mut reference = self.inner.clone();
+        while let Some(next) = reference.follow() {
+            reference = next.or_class(ErrorClass::Reference)?;
+        }
+        Ok(Reference::new(reference))
+    }
+
+    /// Follow this reference and tags until an object is found, and return it.
+    pub fn peel_to_object(&self) -> Result<Object<'repo>, Error> {
+        let id = self
+            .inner
+            .clone()
+            .into_fully_peeled_id()
+            .or_class(ErrorClass::Reference)?;
+        Ok(Object::new(id.object().or_class(ErrorClass::Odb)?))
+    }
+
+    /// Follow this reference and tags until a commit is found, and return it.
+    pub fn peel_to_commit(&self) -> Result<Commit<'repo>, Error> {
+        self.peel_to_object()?.peel_to_commit()
+    }
+
+    pub(crate) fn as_gix(&self) -> &gix::Reference<'repo> {
+        &self.inner
+    }
+}
+
+/// The kind of a branch, like `git2::BranchType`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BranchType {
+    /// A branch in `refs/heads/`.
+    Local,
+    /// A remote tracking branch in `refs/remotes/`.
+    Remote,
+}
+
+/// A local or remote tracking branch, like `git2::Branch`.
+#[derive(Clone)]
+pub struct Branch<'repo> {
+    inner: Reference<'repo>,
+    repo: &'repo gix::Repository,
+}
+
+impl<'repo> Branch<'repo> {
+    pub(crate) fn new(inner: gix::Reference<'repo>, repo: &'repo gix::Repository) -> Self {
+        Branch {
+            inner: Reference::new(inner),
+            repo,
+        }
+    }
+
+    /// Return the name of the branch without its prefix, like `main` or `origin/main`, or `None` if it isn't valid UTF-8.
+    pub fn name(&self) -> Result<Option<&str>, Error> {
+        Ok(self.inner.shorthand())
+    }
+
+    /// Return the name of the branch without its prefix.
+    pub fn name_bytes(&self) -> Result<&[u8], Error> {
+        Ok(self.inner.shorthand_bytes())
+    }
+
+    /// Return `true` if `HEAD` points to this branch.
+    pub fn is_head(&self) -> bool {
+        matches!(self.repo.head_name(), Ok(Some(name)) if name.as_ref() == self.inner.as_gix().name())
+    }
+
+    /// Return the reference of this branch.
+    pub fn get(&self) -> &Reference<'repo> {
+        &self.inner
+    }
+
+    /// Turn this instance into the reference of this branch.
+    pub fn into_reference(self) -> Reference<'repo> {
+        self.inner
+    }
+
+    /// Return the remote tracking branch this local branch is configured to merge from.
+    pub fn upstream(&self) -> Result<Branch<'repo>, Error> {
+        let not_found = || {
+            Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Reference,
+                format!(
+                    "branch '{}' does not have an upstream",
+                    self.inner.shorthand_bytes().as_bstr()
+                ),
+            )
+        };
+        let name = self
+            .repo
+            .branch_remote_tracking_ref_name(self.inner.as_gix().name(), gix::remote::Direction::Fetch)
+            .ok_or_else(not_found)?
+            .or_class(ErrorClass::Config)?;
+        let reference = self
+            .repo
+            .try_find_reference(name.as_ref())
+            .or_class(ErrorClass::Reference)?
+            .ok_or_else(not_found)?;
+        Ok(Branch::new(reference, self.repo))
+    }
+}
+
+/// An iterator over branches, like `git2::Branches`.
+pub struct Branches<'repo> {
+    inner: std::vec::IntoIter<Result<(Branch<'repo>, BranchType), Error>>,
+}
+
+impl<'repo> Branches<'repo> {
+    pub(crate) fn new(repo: &'repo gix::Repository, filter: Option<BranchType>) -> Result<Self, Error> {
+        let platform = repo.references().or_class(ErrorClass::Reference)?;
+        let mut branches = Vec::new();
+        for branch_type in [BranchType::Local, BranchType::Remote] {
+            if matches!(filter, Some(filter) if filter != branch_type) {
+                continue;
+            }
+            let iter = match branch_type {
+                BranchType::Local => platform.local_branches(),
+                BranchType::Remote => platform.remote_branches(),
+            }
+            .or_class(ErrorClass::Reference)?;
+            branches.extend(iter.map(|reference| {
+                reference
+                    .map(|reference| (Branch::new(reference.detach().attach(repo), repo), branch_type))
+                    .map_err(|err| Error::new(ErrorCode::GenericError, ErrorClass::Reference, err))
+            }));
+        }
+        Ok(Branches {
+            inner: branches.into_iter(),
+        })
+    }
+}
+
+impl<'repo> Iterator for Branches<'repo> {
+    type Item = Result<(Branch<'repo>, BranchType), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
@@ -0,0 +1,367 @@
+use gix::bstr::ByteSlice;
+
+/// A configured remote, like `git2::Remote`.
+pub struct Remote<'repo> {
+    inner: gix::Remote<'repo>,
+    name: Option<String>,
+    url: Option<String>,
+    pushurl: Option<String>,
+}
+
+impl<'repo> Remote<'repo> {
+    pub(crate) fn new(inner: gix::Remote<'repo>) -> Self {
+        let name = inner
+            .name()
+            .and_then(|name| name.as_bstr().to_str().ok().map(ToOwned::to_owned));
+        let url = inner
+            .url(gix::remote::Direction::Fetch)
+            .map(|url| url.to_bstring().to_string());
+        let pushurl = inner
+            .url(gix::remote::Direction::Push)
+            .filter(|url| Some(*url) != inner.url(gix::remote::Direction::Fetch))
+            .map(|url| url.to_bstring().to_string());
+        Remote {
+            inner,
+            name,
+            url,
+            pushurl,
+        }
+    }
+
+    /// Return the name of the remote, or `None` if it is anonymous or the name isn't valid UTF-8.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Return the url to fetch from, or `None` if it isn't set.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Return the url to push to, or `None` if it isn't set explicitly.
+    pub fn pushurl(&self) -> Option<&str> {
+        self.pushurl.as_deref()
+    }
+
+    /// Return the refspecs used when fetching, as configured.
+    pub fn fetch_refspecs(&self) -> Vec<String> {
+        self.inner
+            .refspecs(gix::remote::Direction::Fetch)
+            .iter()
+            .map(|spec| spec.to_ref().to_bstring().to_string())
+            .collect()
+    }
+}
+
+#[cfg(feature = "blocking-network-client")]
+mod fetch {
+    use std::{cell::RefCell, sync::atomic::AtomicBool};
+
+    use gix::{
+        bstr::{BString, ByteSlice},
+        remote::fetch::refs::update::Mode,
+    };
+
+    use super::Remote;
+    use crate::{error::ResultExt, Error, ErrorClass, ErrorCode, Oid};
+
+    bitflags::bitflags! {
+        /// The kinds of credentials a remote may accept, like `git2::CredentialType`.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct CredentialType: u32 {
+            /// A username and a password in plain text.
+            const USER_PASS_PLAINTEXT = 1 << 0;
+        }
+    }
+
+    /// Credentials to authenticate with, like `git2::Cred`.
+    #[derive(Clone)]
+    pub struct Cred {
+        username: String,
+        password: String,
+    }
+
+    impl Cred {
+        /// Create credentials from a `username` and `password`.
+        pub fn userpass_plaintext(username: &str, password: &str) -> Result<Cred, Error> {
+            Ok(Cred {
+                username: username.into(),
+                password: password.into(),
+            })
+        }
+    }
+
+    impl std::fmt::Debug for Cred {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Cred")
+                .field("username", &self.username)
+                .field("password", &"<redacted>")
+                .finish()
+        }
+    }
+
+    /// Statistics about a received pack, like `git2::Progress`.
+    #[derive(Debug, Copy, Clone)]
+    pub struct Progress {
+        total_objects: usize,
+        received_bytes: usize,
+    }
+
+    impl Progress {
+        /// Return the amount of objects in the pack.
+        pub fn total_objects(&self) -> usize {
+            self.total_objects
+        }
+
+        /// Return the amount of received objects.
+        pub fn received_objects(&self) -> usize {
+            self.total_objects
+        }
+
+        /// Return the amount of indexed objects.
+        pub fn indexed_objects(&self) -> usize {
+            self.total_objects
+        }
+
+        /// Return the size of the received pack in bytes.
+        pub fn received_bytes(&self) -> usize {
+            self.received_bytes
+        }
+    }
+
+    type CredentialsFn<'a> = dyn FnMut(&str, Option<&str>, CredentialType) -> Result<Cred, Error> + 'a;
+    type TransferProgressFn<'a> = dyn FnMut(Progress) -> bool + 'a;
+    type UpdateTipsFn<'a> = dyn FnMut(&str, Oid, Oid) -> bool + 'a;
+
+    /// Callbacks to be called during [`Remote::fetch()`], like `git2::RemoteCallbacks`.
+    #[derive(Default)]
+    pub struct RemoteCallbacks<'a> {
+        credentials: Option<Box<CredentialsFn<'a>>>,
+        transfer_progress: Option<Box<TransferProgressFn<'a>>>,
+        update_tips: Option<Box<UpdateTipsFn<'a>>>,
+    }
+
+    impl<'a> RemoteCallbacks<'a> {
+        /// Create a new instance without any callback.
+        pub fn new() -> Self {
+            RemoteCallbacks::default()
+        }
+
+        /// Call `cb` with the url, the username if known and the allowed credential types when the remote
+        /// requires authentication.
+        pub fn credentials<F>(&mut self, cb: F) -> &mut Self
+        where
+            F: FnMut(&str, Option<&str>, CredentialType) -> Result<Cred, Error> + 'a,
+        {
+            self.credentials = Some(Box::new(cb));
+            self
+        }
+
+        /// Call `cb` with statistics about the received pack, and abort the fetch if it returns `false`.
+        pub fn transfer_progress<F>(&mut self, cb: F) -> &mut Self
+        where
+            F: FnMut(Progress) -> bool + 'a,
+        {
+            self.transfer_progress = Some(Box::new(cb));
+            self
+        }
+
+        /// Call `cb` with the name, the previous and the new id of each updated reference, and abort the fetch
+        /// if it returns `false`. The previous id is zero for new references.
+        pub fn update_tips<F>(&mut self, cb: F) -> &mut Self
+        where
+            F: FnMut(&str, Oid, Oid) -> bool + 'a,
+        {
+            self.update_tips = Some(Box::new(cb));
+            self
+        }
+    }
+
+    /// Whether references that don't exist on the remote anymore should be deleted, like `git2::FetchPrune`.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+    pub enum FetchPrune {
+        /// Use the configuration of the remote.
+        #[default]
+        Unspecified,
+        /// Delete tracking references that don't exist on the remote anymore.
+        On,
+        /// Keep all tracking references.
+        Off,
+    }
+
+    /// Options for [`Remote::fetch()`], like `git2::FetchOptions`.
+    #[derive(Default)]
+    pub struct FetchOptions<'a> {
+        callbacks: Option<RemoteCallbacks<'a>>,
+        prune: FetchPrune,
+        depth: Option<std::num::NonZeroU32>,
+    }
+
+    impl<'a> FetchOptions<'a> {
+        /// Create new options that fetch as configured.
+        pub fn new() -> Self {
+            FetchOptions::default()
+        }
+
+        /// Set the `callbacks` to call during the fetch.
+        pub fn remote_callbacks(&mut self, callbacks: RemoteCallbacks<'a>) -> &mut Self {
+            self.callbacks = Some(callbacks);
+            self
+        }
+
+        /// Control whether references that don't exist on the remote anymore should be deleted.
+        pub fn prune(&mut self, prune: FetchPrune) -> &mut Self {
+            self.prune = prune;
+            self
+        }
+
+        /// Limit the history to fetch to `depth` commits, or fetch everything if `depth` is `0`.
+        pub fn depth(&mut self, depth: i32) -> &mut Self {
+            self.depth = u32::try_from(depth).ok().and_then(std::num::NonZeroU32::new);
+            self
+        }
+    }
+
+    impl<'repo> Remote<'repo> {
+        /// Fetch `refspecs`, or the configured refspecs if empty, from this remote and update the local tracking
+        /// references, calling the callbacks in `opts` if given. `reflog_msg` is used verbatim in reflog entries if set.
+        #[allow(clippy::result_large_err)]
+        pub fn fetch<Str: AsRef<str>>(
+            &mut self,
+            refspecs: &[Str],
+            opts: Option<&mut FetchOptions<'_>>,
+            reflog_msg: Option<&str>,
+        ) -> Result<(), Error> {
+            let mut default_opts = FetchOptions::default();
+            let opts = opts.unwrap_or(&mut default_opts);
+            let callbacks = opts.callbacks.get_or_insert_with(RemoteCallbacks::default);
+
+            let mut remote = self.inner.clone();
+            if !refspecs.is_empty() {
+                remote
+                    .replace_refspecs(refspecs.iter().map(AsRef::<str>::as_ref), gix::remote::Direction::Fetch)
+                    .map_err(|err| Error::from_gix(ErrorClass::Invalid, err).with_code(ErrorCode::InvalidSpec))?;
+            }
+            match opts.prune {
+                FetchPrune::Unspecified => {}
+                FetchPrune::On => remote = remote.with_prune(true),
+                FetchPrune::Off => remote = remote.with_prune(false),
+            }
+
+            let callback_error = RefCell::new(None);
+            let mut connection = remote
+                .connect(gix::remote::Direction::Fetch)
+                .or_class(ErrorClass::Net)?;
+            if let Some(credentials) = callbacks.credentials.as_mut() {
+                connection = connection.with_credentials(|action| {
+                    use gix::credentials::{helper::Action, protocol};
+                    let Action::Get(ctx) = action else {
+                        return Ok(None);
+                    };
+                    let url = ctx
+                        .url
+                        .as_ref()
+                        .map(|url| url.to_str_lossy().into_owned())
+                        .unwrap_or_default();
+                    match credentials(&url, ctx.username.as_deref(), CredentialType::USER_PASS_PLAINTEXT) {
+                        Ok(cred) => Ok(Some(protocol::Outcome {
+                            identity: gix::sec::identity::Account {
+                                username: cred.username,
+                                password: cred.password,
+                            },
+                            next: ctx.into(),
+                        })),
+                        Err(err) => {
+                            *callback_error.borrow_mut() = Some(err);
+                            Err(protocol::Error::Quit)
+                        }
+                    }
+                });
+            }
+
+            let reflog_message = match reflog_msg {
+                Some(message) => gix::remote::fetch::RefLogMessage::Override {
+                    message: BString::from(message),
+                },
+                None => gix::remote::fetch::RefLogMessage::Prefixed { action: "fetch".into() },
+            };
+            let mut prepare = connection
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .map_err(|err| {
+                    callback_error
+                        .borrow_mut()
+                        .take()
+                        .unwrap_or_else(|| Error::from_gix(ErrorClass::Net, err))
+                })?
+                .with_reflog_message(reflog_message);
+            if let Some(depth) = opts.depth {
+                prepare = prepare.with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(depth));
+            }
+            let outcome = prepare
+                .receive(gix::progress::Discard, &AtomicBool::default())
+                .or_class(ErrorClass::Net)?;
+
+            let update_refs = match outcome.status {
+                gix::remote::fetch::Status::NoPackReceived { update_refs, .. } => update_refs,
+                gix::remote::fetch::Status::Change {
+                    write_pack_bundle,
+                    update_refs,
+                    ..
+                } => {
+                    if let Some(transfer_progress) = callbacks.transfer_progress.as_mut() {
+                        let received_bytes = write_pack_bundle
+                            .data_path
+                            .as_ref()
+                            .and_then(|path| std::fs::metadata(path).ok())
+                            .map_or(0, |meta| meta.len() as usize);
+                        let progress = Progress {
+                            total_objects: write_pack_bundle.index.num_objects as usize,
+                            received_bytes,
+                        };
+                        if !transfer_progress(progress) {
+                            return Err(aborted_by_callback("transfer_progress"));
+                        }
+                    }
+                    update_refs
+                }
+            };
+
+            if let Some(update_tips) = callbacks.update_tips.as_mut() {
+                for update in &update_refs.updates {
+                    if !matches!(update.mode, Mode::New | Mode::FastForward | Mode::Forced) {
+                        continue;
+                    }
+                    let Some(edit) = update.edit_index.and_then(|idx| update_refs.edits.get(idx)) else {
+                        continue;
+                    };
+                    let Some(new) = edit
+                        .change
+                        .new_value()
+                        .and_then(|target| target.try_id().map(ToOwned::to_owned))
+                    else {
+                        continue;
+                    };
+                    let previous = edit
+                        .change
+                        .previous_value()
+                        .and_then(|target| target.try_id().map(ToOwned::to_owned))
+                        .map_or_else(Oid::zero, Oid::from);
+                    if !update_tips(&edit.name.as_bstr().to_str_lossy(), previous, new.into()) {
+                        return Err(aborted_by_callback("update_tips"));
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn aborted_by_callback(name: &str) -> Error {
+        Error::new(
+            ErrorCode::User,
+            ErrorClass::Callback,
+            format!("the '{name}' callback aborted the fetch"),
+        )
+    }
+}
+#[cfg(feature = "blocking-network-client")]
+pub use fetch::{Cred, CredentialType, FetchOptions, FetchPrune, Progress, RemoteCallbacks};
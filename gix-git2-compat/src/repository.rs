@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use gix::bstr::ByteSlice;
+
+use crate::{
+    error::ResultExt, Branch, BranchType, Branches, Commit, Error, ErrorClass, ErrorCode, Object, ObjectType, Oid,
+    Reference, Remote, StatusOptions, Statuses,
+};
+
+/// A git repository, like `git2::Repository`.
+pub struct Repository {
+    inner: gix::Repository,
+}
+
+/// Initialization
+impl Repository {
+    /// Open the repository at `path`, which is either the `.git` directory or the worktree containing it.
+    pub fn open(path: impl AsRef<Path>) -> Result<Repository, Error> {
+        gix::open(path.as_ref())
+            .map(Repository::from)
+            .map_err(|err| Error::from_gix(ErrorClass::Repository, err).with_code(ErrorCode::NotFound))
+    }
+
+    /// Find the repository containing `path` by searching `path` and all of its parent directories.
+    pub fn discover(path: impl AsRef<Path>) -> Result<Repository, Error> {
+        gix::discover(path.as_ref())
+            .map(Repository::from)
+            .map_err(|err| Error::from_gix(ErrorClass::Repository, err).with_code(ErrorCode::NotFound))
+    }
+
+    /// Create a new repository with a worktree at `path`.
+    pub fn init(path: impl AsRef<Path>) -> Result<Repository, Error> {
+        gix::init(path.as_ref())
+            .map(Repository::from)
+            .or_class(ErrorClass::Repository)
+    }
+
+    /// Create a new bare repository at `path`.
+    pub fn init_bare(path: impl AsRef<Path>) -> Result<Repository, Error> {
+        gix::init_bare(path.as_ref())
+            .map(Repository::from)
+            .or_class(ErrorClass::Repository)
+    }
+}
+
+/// Access
+impl Repository {
+    /// Return the path to the `.git` directory.
+    pub fn path(&self) -> &Path {
+        self.inner.git_dir()
+    }
+
+    /// Return the path to the worktree, or `None` if the repository is bare.
+    pub fn workdir(&self) -> Option<&Path> {
+        self.inner.work_dir()
+    }
+
+    /// Return `true` if the repository doesn't have a worktree.
+    pub fn is_bare(&self) -> bool {
+        self.inner.is_bare()
+    }
+
+    /// Return `true` if `HEAD` points to a branch without commits.
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.inner.head().or_class(ErrorClass::Reference)?.is_unborn())
+    }
+
+    /// Return the reference `HEAD` points to, or `HEAD` itself if it is detached.
+    pub fn head(&self) -> Result<Reference<'_>, Error> {
+        let head = self.inner.head().or_class(ErrorClass::Reference)?;
+        let name = head.referent_name().map(ToOwned::to_owned);
+        match head.try_into_referent() {
+            Some(reference) => Ok(Reference::new(reference)),
+            None => match name {
+                Some(name) => Err(Error::new(
+                    ErrorCode::UnbornBranch,
+                    ErrorClass::Reference,
+                    format!("reference '{}' not found", name.as_bstr()),
+                )),
+                None => self.find_reference("HEAD"),
+            },
+        }
+    }
+
+    /// Return the reference with the full `name`, like `refs/heads/main`.
+    pub fn find_reference(&self, name: &str) -> Result<Reference<'_>, Error> {
+        match self.inner.try_find_reference(name) {
+            Ok(Some(reference)) => Ok(Reference::new(reference)),
+            Ok(None) => Err(Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Reference,
+                format!("reference '{name}' not found"),
+            )),
+            Err(err) => Err(Error::from_gix(ErrorClass::Reference, err).with_code(ErrorCode::InvalidSpec)),
+        }
+    }
+
+    /// Resolve the revision specification `spec`, like `HEAD~1` or `main^{tree}`, to a single object.
+    pub fn revparse_single(&self, spec: &str) -> Result<Object<'_>, Error> {
+        use gix::revision::spec::parse::{single, Error as ParseError};
+        let id = self.inner.rev_parse_single(spec).map_err(|err| {
+            let code = match &err {
+                single::Error::RangedRev { .. } | single::Error::Parse(ParseError::Parse(_)) => ErrorCode::InvalidSpec,
+                single::Error::Parse(ParseError::AmbiguousPrefix { .. } | ParseError::AmbiguousRefAndObject { .. }) => {
+                    ErrorCode::Ambiguous
+                }
+                single::Error::Parse(_) => ErrorCode::NotFound,
+            };
+            Error::from_gix(ErrorClass::Reference, err).with_code(code)
+        })?;
+        Ok(Object::new(id.object().or_class(ErrorClass::Odb)?))
+    }
+
+    /// Return the object with `oid`, which must be of `kind` unless it is `None` or [`ObjectType::Any`].
+    pub fn find_object(&self, oid: Oid, kind: Option<ObjectType>) -> Result<Object<'_>, Error> {
+        let object = match self.inner.try_find_object(oid).or_class(ErrorClass::Odb)? {
+            Some(object) => Object::new(object),
+            None => {
+                return Err(Error::new(
+                    ErrorCode::NotFound,
+                    ErrorClass::Odb,
+                    format!("object {oid} not found"),
+                ))
+            }
+        };
+        match kind.and_then(ObjectType::to_kind) {
+            Some(expected) if object.kind() != Some(expected.into()) => Err(Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Object,
+                format!("object {oid} is not a {expected}"),
+            )),
+            _ => Ok(object),
+        }
+    }
+
+    /// Return the commit with `oid`.
+    pub fn find_commit(&self, oid: Oid) -> Result<Commit<'_>, Error> {
+        self.find_object(oid, Some(ObjectType::Commit))?
+            .into_commit()
+            .map_err(|_| {
+                Error::new(
+                    ErrorCode::NotFound,
+                    ErrorClass::Object,
+                    format!("{oid} is not a commit"),
+                )
+            })
+    }
+
+    /// Return the status of all changed paths, or of the paths selected by `options`.
+    ///
+    /// Without `options`, untracked and ignored files are listed with untracked directories being recursed into.
+    pub fn statuses(&self, options: Option<&mut StatusOptions>) -> Result<Statuses<'_>, Error> {
+        Statuses::new(&self.inner, options.map(|options| &*options))
+    }
+
+    /// Return all branches, or only the ones of the given `filter` type.
+    pub fn branches(&self, filter: Option<BranchType>) -> Result<Branches<'_>, Error> {
+        Branches::new(&self.inner, filter)
+    }
+
+    /// Return the branch with the short `name`, like `main` or `origin/main`, of the given `branch_type`.
+    pub fn find_branch(&self, name: &str, branch_type: BranchType) -> Result<Branch<'_>, Error> {
+        let full_name = match branch_type {
+            BranchType::Local => format!("refs/heads/{name}"),
+            BranchType::Remote => format!("refs/remotes/{name}"),
+        };
+        match self.inner.try_find_reference(full_name.as_str()) {
+            Ok(Some(reference)) => Ok(Branch::new(reference, &self.inner)),
+            Ok(None) => Err(Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Reference,
+                format!(
+                    "cannot locate {} branch '{name}'",
+                    match branch_type {
+                        BranchType::Local => "local",
+                        BranchType::Remote => "remote-tracking",
+                    }
+                ),
+            )),
+            Err(err) => Err(Error::from_gix(ErrorClass::Reference, err).with_code(ErrorCode::InvalidSpec)),
+        }
+    }
+
+    /// Return the remote with the given `name`.
+    pub fn find_remote(&self, name: &str) -> Result<Remote<'_>, Error> {
+        match self.inner.try_find_remote(name) {
+            Some(Ok(remote)) => Ok(Remote::new(remote)),
+            Some(Err(err)) => Err(Error::from_gix(ErrorClass::Config, err)),
+            None => Err(Error::new(
+                ErrorCode::NotFound,
+                ErrorClass::Config,
+                format!("remote '{name}' does not exist"),
+            )),
+        }
+    }
+
+    /// Return the names of all configured remotes, sorted, skipping names that aren't valid UTF-8.
+    pub fn remotes(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .inner
+            .remote_names()
+            .into_iter()
+            .filter_map(|name| name.to_str().ok().map(ToOwned::to_owned))
+            .collect())
+    }
+
+    /// Return the underlying `gix` repository, for everything not provided by this crate.
+    pub fn as_gix(&self) -> &gix::Repository {
+        &self.inner
+    }
+}
+
+impl From<gix::Repository> for Repository {
+    fn from(inner: gix::Repository) -> Self {
+        Repository { inner }
+    }
+}
+
+impl From<Repository> for gix::Repository {
+    fn from(repo: Repository) -> Self {
+        repo.inner
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+
+use gix::bstr::{BStr, BString, ByteSlice};
+
+use crate::{error::ResultExt, Error, ErrorClass, ErrorCode};
+
+bitflags::bitflags! {
+    /// The status of a single path, with the same values as `git2::Status`.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Status: u32 {
+        /// The path is unchanged.
+        const CURRENT = 0;
+        /// The path was added to the index.
+        const INDEX_NEW = 1 << 0;
+        /// The path was modified in the index compared to `HEAD`.
+        const INDEX_MODIFIED = 1 << 1;
+        /// The path was removed from the index.
+        const INDEX_DELETED = 1 << 2;
+        /// The path was renamed in the index. Never set as renames aren't detected.
+        const INDEX_RENAMED = 1 << 3;
+        /// The type of the path changed in the index compared to `HEAD`, for instance from file to symlink.
+        const INDEX_TYPECHANGE = 1 << 4;
+        /// The path is untracked.
+        const WT_NEW = 1 << 7;
+        /// The path was modified in the worktree compared to the index.
+        const WT_MODIFIED = 1 << 8;
+        /// The path was removed from the worktree.
+        const WT_DELETED = 1 << 9;
+        /// The type of the path changed in the worktree compared to the index.
+        const WT_TYPECHANGE = 1 << 10;
+        /// The path was renamed in the worktree. Never set as renames aren't detected.
+        const WT_RENAMED = 1 << 11;
+        /// The path couldn't be read in the worktree.
+        const WT_UNREADABLE = 1 << 12;
+        /// The path is ignored.
+        const IGNORED = 1 << 14;
+        /// The path is conflicted in the index.
+        const CONFLICTED = 1 << 15;
+    }
+}
+
+impl Status {
+    /// Return `true` if the path was added to the index.
+    pub fn is_index_new(&self) -> bool {
+        self.contains(Status::INDEX_NEW)
+    }
+
+    /// Return `true` if the path was modified in the index.
+    pub fn is_index_modified(&self) -> bool {
+        self.contains(Status::INDEX_MODIFIED)
+    }
+
+    /// Return `true` if the path was removed from the index.
+    pub fn is_index_deleted(&self) -> bool {
+        self.contains(Status::INDEX_DELETED)
+    }
+
+    /// Return `true` if the type of the path changed in the index.
+    pub fn is_index_typechange(&self) -> bool {
+        self.contains(Status::INDEX_TYPECHANGE)
+    }
+
+    /// Return `true` if the path is untracked.
+    pub fn is_wt_new(&self) -> bool {
+        self.contains(Status::WT_NEW)
+    }
+
+    /// Return `true` if the path was modified in the worktree.
+    pub fn is_wt_modified(&self) -> bool {
+        self.contains(Status::WT_MODIFIED)
+    }
+
+    /// Return `true` if the path was removed from the worktree.
+    pub fn is_wt_deleted(&self) -> bool {
+        self.contains(Status::WT_DELETED)
+    }
+
+    /// Return `true` if the type of the path changed in the worktree.
+    pub fn is_wt_typechange(&self) -> bool {
+        self.contains(Status::WT_TYPECHANGE)
+    }
+
+    /// Return `true` if the path is ignored.
+    pub fn is_ignored(&self) -> bool {
+        self.contains(Status::IGNORED)
+    }
+
+    /// Return `true` if the path is conflicted.
+    pub fn is_conflicted(&self) -> bool {
+        self.contains(Status::CONFLICTED)
+    }
+}
+
+/// Options to control which paths are returned by [`Repository::statuses()`](crate::Repository::statuses()),
+/// like `git2::StatusOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct StatusOptions {
+    include_untracked: bool,
+    include_ignored: bool,
+    recurse_untracked_dirs: bool,
+    pathspecs: Vec<BString>,
+}
+
+impl StatusOptions {
+    /// Create new options which only show changes to tracked files.
+    pub fn new() -> Self {
+        StatusOptions::default()
+    }
+
+    /// If `include` is `true`, list untracked files.
+    pub fn include_untracked(&mut self, include: bool) -> &mut Self {
+        self.include_untracked = include;
+        self
+    }
+
+    /// If `include` is `true`, list ignored files.
+    pub fn include_ignored(&mut self, include: bool) -> &mut Self {
+        self.include_ignored = include;
+        self
+    }
+
+    /// If `recurse` is `true`, list all files in untracked directories instead of just the directory.
+    pub fn recurse_untracked_dirs(&mut self, recurse: bool) -> &mut Self {
+        self.recurse_untracked_dirs = recurse;
+        self
+    }
+
+    /// Add `pathspec` to limit the paths to consider. All paths are considered if no pathspec is given.
+    pub fn pathspec(&mut self, pathspec: impl AsRef<[u8]>) -> &mut Self {
+        self.pathspecs.push(pathspec.as_ref().into());
+        self
+    }
+}
+
+/// The status of all changed paths in a repository, like `git2::Statuses`, sorted by path.
+pub struct Statuses<'repo> {
+    entries: Vec<(BString, Status)>,
+    _repo: std::marker::PhantomData<&'repo gix::Repository>,
+}
+
+impl<'repo> Statuses<'repo> {
+    pub(crate) fn new(repo: &'repo gix::Repository, options: Option<&StatusOptions>) -> Result<Self, Error> {
+        let default_options;
+        let options = match options {
+            Some(options) => options,
+            None => {
+                default_options = StatusOptions {
+                    include_untracked: true,
+                    include_ignored: true,
+                    recurse_untracked_dirs: true,
+                    pathspecs: Vec::new(),
+                };
+                &default_options
+            }
+        };
+        if repo.is_bare() {
+            return Err(Error::new(
+                ErrorCode::BareRepo,
+                ErrorClass::Repository,
+                "cannot obtain the status of a bare repository",
+            ));
+        }
+
+        let mut statuses = BTreeMap::<BString, Status>::new();
+        head_to_index(repo, options, &mut statuses)?;
+        index_to_worktree(repo, options, &mut statuses)?;
+        Ok(Statuses {
+            entries: statuses.into_iter().collect(),
+            _repo: std::marker::PhantomData,
+        })
+    }
+
+    /// Return the amount of changed paths.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if there are no changed paths.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the entry at `index`, if present.
+    pub fn get(&self, index: usize) -> Option<StatusEntry<'_>> {
+        self.entries.get(index).map(StatusEntry::new)
+    }
+
+    /// Return an iterator over all entries.
+    pub fn iter(&self) -> impl Iterator<Item = StatusEntry<'_>> + '_ {
+        self.entries.iter().map(StatusEntry::new)
+    }
+}
+
+/// The status of a single path, like `git2::StatusEntry`.
+#[derive(Debug, Copy, Clone)]
+pub struct StatusEntry<'a> {
+    path: &'a BStr,
+    status: Status,
+}
+
+impl<'a> StatusEntry<'a> {
+    fn new((path, status): &'a (BString, Status)) -> Self {
+        StatusEntry {
+            path: path.as_ref(),
+            status: *status,
+        }
+    }
+
+    /// Return the repository-relative path, or `None` if it isn't valid UTF-8.
+    ///
+    /// Untracked or ignored directories end with a slash.
+    pub fn path(&self) -> Option<&'a str> {
+        self.path.to_str().ok()
+    }
+
+    /// Return the repository-relative path.
+    pub fn path_bytes(&self) -> &'a [u8] {
+        self.path
+    }
+
+    /// Return the status of the path.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+}
+
+fn head_to_index(
+    repo: &gix::Repository,
+    options: &StatusOptions,
+    out: &mut BTreeMap<BString, Status>,
+) -> Result<(), Error> {
+    let index = repo.index_or_empty().or_class(ErrorClass::Index)?;
+    let mut pathspec = repo
+        .pathspec(
+            false,
+            &options.pathspecs,
+            true,
+            &index,
+            gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+        )
+        .or_class(ErrorClass::Invalid)?;
+
+    let mut head_entries = BTreeMap::new();
+    if let Some(head_id) = repo.head().or_class(ErrorClass::Reference)?.id() {
+        let tree = head_id
+            .object()
+            .or_class(ErrorClass::Odb)?
+            .peel_to_kind(gix::object::Kind::Tree)
+            .or_class(ErrorClass::Object)?
+            .into_tree();
+        let mut recorder = gix::traverse::tree::Recorder::default();
+        tree.traverse()
+            .breadthfirst(&mut recorder)
+            .or_class(ErrorClass::Object)?;
+        head_entries.extend(
+            recorder
+                .records
+                .into_iter()
+                .filter(|entry| !entry.mode.is_tree())
+                .map(|entry| (entry.filepath, (entry.mode, entry.oid))),
+        );
+    }
+
+    for entry in index.entries() {
+        let path = entry.path(&index);
+        let head_entry = head_entries.remove(path);
+        if !pathspec.is_included(path, Some(false)) {
+            continue;
+        }
+        let status = if entry.stage_raw() != 0 {
+            Status::CONFLICTED
+        } else if entry.flags.contains(gix::index::entry::Flags::INTENT_TO_ADD) {
+            continue;
+        } else {
+            match (head_entry, entry.mode.to_tree_entry_mode()) {
+                (None, _) => Status::INDEX_NEW,
+                (Some((head_mode, _)), Some(mode)) if !is_same_type(head_mode, mode) => Status::INDEX_TYPECHANGE,
+                (Some((head_mode, head_id)), mode) => {
+                    if head_id == entry.id && Some(head_mode) == mode {
+                        continue;
+                    }
+                    Status::INDEX_MODIFIED
+                }
+            }
+        };
+        *out.entry(path.to_owned()).or_insert(Status::CURRENT) |= status;
+    }
+
+    for path in head_entries.into_keys() {
+        if pathspec.is_included(path.as_bstr(), Some(false)) {
+            *out.entry(path).or_insert(Status::CURRENT) |= Status::INDEX_DELETED;
+        }
+    }
+    Ok(())
+}
+
+fn index_to_worktree(
+    repo: &gix::Repository,
+    options: &StatusOptions,
+    out: &mut BTreeMap<BString, Status>,
+) -> Result<(), Error> {
+    use gix::status::{
+        index_worktree::iter::Item,
+        plumbing::index_as_worktree::{Change, EntryStatus},
+        IgnoredFiles, UntrackedFiles,
+    };
+
+    let untracked = match (options.include_untracked, options.recurse_untracked_dirs) {
+        (false, _) => UntrackedFiles::None,
+        (true, false) => UntrackedFiles::Collapsed,
+        (true, true) => UntrackedFiles::Files,
+    };
+    let ignored = if options.include_ignored {
+        IgnoredFiles::Collapsed
+    } else {
+        IgnoredFiles::None
+    };
+    let iter = repo
+        .status(gix::progress::Discard)
+        .or_class(ErrorClass::Filesystem)?
+        .untracked_files(untracked)
+        .ignored_files(ignored)
+        .index_worktree_rewrites(None)
+        .into_index_worktree_iter(options.pathspecs.iter().cloned())
+        .or_class(ErrorClass::Filesystem)?;
+    for item in iter {
+        let item = item.or_class(ErrorClass::Filesystem)?;
+        let (path, status) = match item {
+            Item::Conflict { rela_path, .. } => (rela_path, Status::CONFLICTED),
+            Item::Modification { rela_path, status, .. } => {
+                let status = match status {
+                    EntryStatus::Conflict(_) => Status::CONFLICTED,
+                    EntryStatus::Change(Change::Removed) => Status::WT_DELETED,
+                    EntryStatus::Change(Change::Type) => Status::WT_TYPECHANGE,
+                    EntryStatus::Change(Change::Modification { .. } | Change::SubmoduleModification(_)) => {
+                        Status::WT_MODIFIED
+                    }
+                    EntryStatus::IntentToAdd => Status::WT_NEW,
+                    EntryStatus::NeedsUpdate(_) => continue,
+                };
+                (rela_path, status)
+            }
+            Item::DirectoryContents {
+                entry,
+                collapsed_directory_status: None,
+            } => {
+                let status = match entry.status {
+                    gix::dir::entry::Status::Untracked => Status::WT_NEW,
+                    gix::dir::entry::Status::Ignored(_) => Status::IGNORED,
+                    gix::dir::entry::Status::Tracked | gix::dir::entry::Status::Pruned => continue,
+                };
+                let mut path = entry.rela_path;
+                if matches!(entry.disk_kind, Some(kind) if kind.is_dir()) {
+                    path.push(b'/');
+                }
+                (path, status)
+            }
+            Item::DirectoryContents { .. } | Item::Rewrite { .. } => continue,
+        };
+        *out.entry(path).or_insert(Status::CURRENT) |= status;
+    }
+    Ok(())
+}
+
+fn is_same_type(a: gix::object::tree::EntryMode, b: gix::object::tree::EntryMode) -> bool {
+    (a.is_blob(), a.is_link(), a.is_commit()) == (b.is_blob(), b.is_link(), b.is_commit())
+}
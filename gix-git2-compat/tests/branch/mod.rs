@@ -0,0 +1,57 @@
+use gix_git2_compat::{BranchType, ErrorCode};
+
+use crate::repo;
+
+#[test]
+fn list_and_filter() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let names = |filter| -> gix_testtools::Result<Vec<(String, BranchType)>> {
+        let mut out = Vec::new();
+        for branch in repo.branches(filter)? {
+            let (branch, kind) = branch?;
+            out.push((branch.name()?.expect("valid UTF-8").to_owned(), kind));
+        }
+        Ok(out)
+    };
+    assert_eq!(
+        names(None)?,
+        [
+            ("local-other".into(), BranchType::Local),
+            ("main".into(), BranchType::Local),
+            ("no-upstream".into(), BranchType::Local),
+            ("origin/HEAD".into(), BranchType::Remote),
+            ("origin/main".into(), BranchType::Remote),
+            ("origin/other".into(), BranchType::Remote),
+        ]
+    );
+    assert_eq!(names(Some(BranchType::Local))?.len(), 3);
+    assert_eq!(names(Some(BranchType::Remote))?.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn head_and_upstream() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let main = repo.find_branch("main", BranchType::Local)?;
+    assert!(main.is_head());
+    assert_eq!(main.upstream()?.name()?, Some("origin/main"));
+    assert_eq!(main.get().target(), repo.head()?.target());
+
+    let other = repo.find_branch("local-other", BranchType::Local)?;
+    assert!(!other.is_head());
+    assert_eq!(other.upstream()?.get().name(), Some("refs/remotes/origin/other"));
+
+    let no_upstream = repo.find_branch("no-upstream", BranchType::Local)?;
+    assert_eq!(no_upstream.upstream().err().expect("none").code(), ErrorCode::NotFound);
+
+    let remote = repo.find_branch("origin/main", BranchType::Remote)?;
+    assert!(remote.get().is_remote());
+    assert_eq!(
+        repo.find_branch("origin/main", BranchType::Local)
+            .err()
+            .expect("not local")
+            .code(),
+        ErrorCode::NotFound
+    );
+    Ok(())
+}
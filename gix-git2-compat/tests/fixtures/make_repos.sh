@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q base
+(cd base
+  echo a > a
+  echo b > b
+  mkdir dir && echo c > dir/c
+  git add . && git commit -q -m "initial"
+  echo a2 >> a && git commit -q -am "second"
+  git branch other HEAD~1
+  git tag -a -m "tag message" v1
+)
+
+git clone -q base clone
+(cd clone
+  git config user.name committer
+  git config user.email committer@example.com
+  git branch --track local-other origin/other
+  git branch no-upstream HEAD~1
+)
+
+(cd base
+  echo a3 >> a && git commit -q -am "third"
+)
+
+cp -R clone changed
+(cd changed
+  echo "*.log" >> .git/info/exclude
+  echo change >> a
+  rm b
+  echo c2 >> dir/c && git add dir/c
+  echo new > staged && git add staged
+  mkdir untracked && echo u > untracked/file
+  echo log > debug.log
+)
+
+git init -q --bare bare
@@ -0,0 +1,12 @@
+use gix_git2_compat::Repository;
+
+mod branch;
+#[cfg(feature = "blocking-network-client")]
+mod remote;
+mod repository;
+mod status;
+
+fn repo(name: &str) -> gix_testtools::Result<Repository> {
+    let dir = gix_testtools::scripted_fixture_read_only("make_repos.sh")?;
+    Ok(Repository::open(dir.join(name))?)
+}
@@ -0,0 +1,67 @@
+use gix_git2_compat::{BranchType, FetchOptions, Oid, RemoteCallbacks, Repository};
+
+#[test]
+fn access() -> gix_testtools::Result {
+    let repo = crate::repo("clone")?;
+    let remote = repo.find_remote("origin")?;
+    assert_eq!(remote.name(), Some("origin"));
+    assert!(remote.url().expect("set").ends_with("base"));
+    assert_eq!(remote.pushurl(), None, "no push url is configured");
+    assert_eq!(remote.fetch_refspecs(), ["+refs/heads/*:refs/remotes/origin/*"]);
+    Ok(())
+}
+
+#[test]
+fn fetch_calls_callbacks_and_updates_tracking_branches() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_writable("make_repos.sh")?;
+    let repo = Repository::open(dir.path().join("clone"))?;
+    let previous = repo.find_branch("origin/main", BranchType::Remote)?.get().target();
+
+    let mut tips = Vec::<(String, Oid, Oid)>::new();
+    let mut received_objects = 0;
+    {
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks
+            .update_tips(|name, old, new| {
+                tips.push((name.into(), old, new));
+                true
+            })
+            .transfer_progress(|progress| {
+                received_objects = progress.received_objects();
+                true
+            });
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(callbacks);
+        repo.find_remote("origin")?
+            .fetch(&[] as &[&str], Some(&mut options), None)?;
+    }
+
+    let current = repo.find_branch("origin/main", BranchType::Remote)?.get().target();
+    assert_ne!(current, previous, "the tracking branch was updated");
+    assert_eq!(
+        tips,
+        [(
+            "refs/remotes/origin/main".into(),
+            previous.expect("set"),
+            current.expect("set")
+        )]
+    );
+    assert_eq!(received_objects, 3, "a commit, a tree and a blob");
+    Ok(())
+}
+
+#[test]
+fn fetch_aborted_by_callback() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_writable("make_repos.sh")?;
+    let repo = Repository::open(dir.path().join("clone"))?;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.update_tips(|_, _, _| false);
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(callbacks);
+    let err = repo
+        .find_remote("origin")?
+        .fetch(&["refs/heads/main:refs/remotes/origin/main"], Some(&mut options), None)
+        .expect_err("aborted");
+    assert_eq!(err.code(), gix_git2_compat::ErrorCode::User);
+    Ok(())
+}
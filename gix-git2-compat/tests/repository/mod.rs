@@ -0,0 +1,88 @@
+use gix_git2_compat::{ErrorCode, ObjectType, Oid, Repository};
+
+use crate::repo;
+
+#[test]
+fn open_and_access() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    assert!(!repo.is_bare());
+    assert!(!repo.is_empty()?);
+    assert!(repo.path().ends_with(".git"));
+    assert!(repo.workdir().expect("non-bare").ends_with("clone"));
+
+    let head = repo.head()?;
+    assert_eq!(head.name(), Some("refs/heads/main"));
+    assert_eq!(head.shorthand(), Some("main"));
+    assert!(head.is_branch());
+    assert_eq!(repo.remotes()?, ["origin"]);
+    Ok(())
+}
+
+#[test]
+fn open_nonexisting_path_is_not_found() {
+    let err = Repository::open("this-path-does-not-exist").err().expect("cannot open");
+    assert_eq!(err.code(), ErrorCode::NotFound);
+}
+
+#[test]
+fn unborn_head() -> gix_testtools::Result {
+    let repo = repo("bare")?;
+    assert!(repo.is_bare());
+    assert!(repo.is_empty()?);
+    assert_eq!(repo.head().err().expect("unborn").code(), ErrorCode::UnbornBranch);
+    assert_eq!(
+        repo.statuses(None).err().expect("bare").code(),
+        ErrorCode::BareRepo,
+        "there is no worktree to obtain the status for"
+    );
+    Ok(())
+}
+
+#[test]
+fn revparse_single_and_commits() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    let head = repo.revparse_single("HEAD")?;
+    assert_eq!(head.kind(), Some(ObjectType::Commit));
+
+    let commit = head.peel_to_commit()?;
+    assert_eq!(commit.message(), Some("second\n"));
+    assert_eq!(commit.author().name(), Some("author"));
+    assert_eq!(commit.parent_count(), 1);
+    assert_eq!(commit.parent(0)?.id(), repo.revparse_single("HEAD~1")?.id());
+    assert_eq!(commit.tree_id(), repo.revparse_single("HEAD^{tree}")?.id());
+    assert_eq!(repo.find_commit(commit.id())?.id(), commit.id());
+
+    let tag = repo.revparse_single("v1")?;
+    assert_eq!(tag.kind(), Some(ObjectType::Tag));
+    assert_eq!(tag.peel(ObjectType::Commit)?.id(), commit.id());
+    assert_eq!(tag.peel(ObjectType::Tree)?.id(), commit.tree_id());
+
+    let tree = repo.find_object(commit.tree_id(), Some(ObjectType::Tree))?;
+    assert_eq!(tree.id().to_string(), commit.tree_id().to_string());
+    assert_eq!(
+        repo.find_object(commit.tree_id(), Some(ObjectType::Blob))
+            .err()
+            .expect("kind mismatch")
+            .code(),
+        ErrorCode::NotFound
+    );
+    Ok(())
+}
+
+#[test]
+fn revparse_single_errors() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    assert_eq!(
+        repo.revparse_single("does-not-exist").err().expect("unknown").code(),
+        ErrorCode::NotFound
+    );
+    assert_eq!(
+        repo.revparse_single("HEAD..main").err().expect("range").code(),
+        ErrorCode::InvalidSpec
+    );
+    assert_eq!(
+        repo.find_commit(Oid::zero()).err().expect("missing").code(),
+        ErrorCode::NotFound
+    );
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use gix_git2_compat::{Status, StatusOptions};
+
+use crate::repo;
+
+fn statuses(
+    repo: &gix_git2_compat::Repository,
+    options: Option<&mut StatusOptions>,
+) -> gix_testtools::Result<Vec<(String, Status)>> {
+    Ok(repo
+        .statuses(options)?
+        .iter()
+        .map(|entry| (entry.path().expect("valid UTF-8").to_owned(), entry.status()))
+        .collect())
+}
+
+#[test]
+fn clean_worktree() -> gix_testtools::Result {
+    let repo = repo("clone")?;
+    assert!(repo.statuses(None)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn defaults_include_untracked_and_ignored_files() -> gix_testtools::Result {
+    let repo = repo("changed")?;
+    assert_eq!(
+        statuses(&repo, None)?,
+        [
+            ("a".into(), Status::WT_MODIFIED),
+            ("b".into(), Status::WT_DELETED),
+            ("debug.log".into(), Status::IGNORED),
+            ("dir/c".into(), Status::INDEX_MODIFIED),
+            ("staged".into(), Status::INDEX_NEW),
+            ("untracked/file".into(), Status::WT_NEW),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn options() -> gix_testtools::Result {
+    let repo = repo("changed")?;
+    assert_eq!(
+        statuses(&repo, Some(&mut StatusOptions::new()))?,
+        [
+            ("a".into(), Status::WT_MODIFIED),
+            ("b".into(), Status::WT_DELETED),
+            ("dir/c".into(), Status::INDEX_MODIFIED),
+            ("staged".into(), Status::INDEX_NEW),
+        ],
+        "only tracked files are listed by default"
+    );
+    assert_eq!(
+        statuses(&repo, Some(StatusOptions::new().include_untracked(true)))?
+            .into_iter()
+            .filter(|(_, status)| status.is_wt_new())
+            .collect::<Vec<_>>(),
+        [("untracked/".into(), Status::WT_NEW)],
+        "untracked directories are collapsed unless recursion is enabled"
+    );
+    assert_eq!(
+        statuses(&repo, Some(StatusOptions::new().pathspec("dir").pathspec("b")))?,
+        [
+            ("b".into(), Status::WT_DELETED),
+            ("dir/c".into(), Status::INDEX_MODIFIED),
+        ]
+    );
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
 use bitflags::bitflags;
-use bstr::{BStr, ByteSlice};
+use bstr::{BStr, BString, ByteSlice};
 
 use crate::{pattern, wildmatch, Pattern};
 
@@ -164,18 +164,33 @@ impl Pattern {
     }
 }
 
-impl fmt::Display for Pattern {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// Serialization
+impl Pattern {
+    /// Return this pattern as it would be written into a file, so that parsing it with [`from_bytes()`](Self::from_bytes())
+    /// yields the same pattern again.
+    ///
+    /// A leading `!` or `#` of a pattern that isn't negative is escaped so it's not mistaken for a negation or comment.
+    /// Note that patterns which can't be the result of parsing, like those with a leading slash in their `text`, don't round-trip.
+    pub fn to_bstring(&self) -> BString {
+        let mut out = BString::default();
         if self.mode.contains(Mode::NEGATIVE) {
-            "!".fmt(f)?;
+            out.push(b'!');
+        } else if !self.mode.contains(Mode::ABSOLUTE) && matches!(self.text.first(), Some(b'!' | b'#')) {
+            out.push(b'\\');
         }
         if self.mode.contains(Mode::ABSOLUTE) {
-            "/".fmt(f)?;
+            out.push(b'/');
         }
-        self.text.fmt(f)?;
+        out.extend_from_slice(&self.text);
         if self.mode.contains(Mode::MUST_BE_DIR) {
-            "/".fmt(f)?;
+            out.push(b'/');
         }
-        Ok(())
+        out
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_bstring().fmt(f)
     }
 }
@@ -7,11 +7,14 @@ use bstr::{BStr, BString, ByteSlice, ByteVec};
 
 use crate::{pattern::Case, search::Pattern};
 
+mod index;
+pub use index::{Candidates, Index};
+
 /// A list of patterns which optionally know where they were loaded from and what their base is.
 ///
 /// Knowing their base which is relative to a source directory, it will ignore all path to match against
 /// that don't also start with said base.
-#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct List<T: Pattern> {
     /// Patterns and their associated data in the order they were loaded in or specified,
     /// the line number in its source file or its sequence number (_`(pattern, value, line_number)`_).
@@ -26,6 +29,47 @@ pub struct List<T: Pattern> {
     /// The parent directory of source, or `None` if the patterns are _global_ to match against the repository root.
     /// It's processed to contain slashes only and to end with a trailing slash, and is relative to the repository root.
     pub base: Option<BString>,
+
+    /// An index over `patterns` to accelerate matching, which is created automatically for lists with many patterns
+    /// and is ignored once the amount of `patterns` changes.
+    /// Use [`compile()`](Self::compile()) to recreate it after `patterns` were altered.
+    ///
+    /// Note that it's never considered when comparing or hashing lists.
+    pub index: Option<Index>,
+}
+
+impl<T: Pattern> List<T> {
+    /// Return all fields that identify this list, which excludes the index as it's derived from the patterns.
+    #[allow(clippy::type_complexity)]
+    fn key(&self) -> (&[Mapping<T::Value>], &Option<PathBuf>, &Option<BString>) {
+        (&self.patterns, &self.source, &self.base)
+    }
+}
+
+impl<T: Pattern> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T: Pattern> Eq for List<T> {}
+
+impl<T: Pattern> std::hash::Hash for List<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl<T: Pattern> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Pattern> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
 /// An association of a pattern with its value, along with a sequence number providing a sort order in relation to its peers.
@@ -92,11 +136,14 @@ where
                     base
                 })
             });
-        List {
+        let mut list = List {
             patterns,
             source: Some(source_file),
             base,
-        }
+            index: None,
+        };
+        list.compile();
+        list
     }
 
     /// Create a pattern list from the `source` file, which may be located underneath `root`, while optionally
@@ -112,11 +159,56 @@ where
     }
 }
 
+/// The minimal amount of patterns for which an [`Index`] is created by [`List::compile()`].
+const MIN_PATTERNS_FOR_INDEX: usize = 32;
+
+/// Mutation
+impl<T> List<T>
+where
+    T: Pattern,
+{
+    /// Create an [index](List::index) over our patterns if there are enough of them to make it worthwhile,
+    /// or remove it otherwise.
+    ///
+    /// This has to be called after altering `patterns` to benefit from accelerated matching.
+    pub fn compile(&mut self) {
+        self.index = (self.patterns.len() >= MIN_PATTERNS_FOR_INDEX).then(|| Index::new(&self.patterns));
+    }
+}
+
 /// Utilities
 impl<T> List<T>
 where
     T: Pattern,
 {
+    /// Return the patterns that may match `relative_path` along with their index, in the order in which they should be
+    /// matched, i.e. the reverse of the order they were specified in.
+    /// `relative_path` and `basename_start_pos` are expected to be relative to our base already, as returned by
+    /// [`strip_base_handle_recompute_basename_pos()`](Self::strip_base_handle_recompute_basename_pos()).
+    ///
+    /// If there is a valid [index](List::index), only a pre-selection of patterns is returned, otherwise all of them.
+    /// Either way, each pattern still has to be matched against `relative_path`.
+    pub fn candidates<'a>(
+        &'a self,
+        relative_path: &BStr,
+        basename_start_pos: Option<usize>,
+    ) -> impl Iterator<Item = (usize, &'a Mapping<T::Value>)> + 'a {
+        let candidates = self
+            .index
+            .as_ref()
+            .filter(|index| index.is_valid_for(self.patterns.len()))
+            .map(|index| index.candidates(relative_path, basename_start_pos));
+        let (indexed, all) = match candidates {
+            Some(candidates) => (Some(candidates), None),
+            None => (None, Some((0..self.patterns.len()).rev())),
+        };
+        indexed
+            .into_iter()
+            .flatten()
+            .chain(all.into_iter().flatten())
+            .map(move |idx| (idx, &self.patterns[idx]))
+    }
+
     /// If this list is anchored to a base path, return `relative_path` as being relative to our base and return
     /// an updated `basename_pos` as well if it was set.
     /// `case` is respected for the comparison.
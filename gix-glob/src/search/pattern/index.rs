@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use bstr::BStr;
+
+use crate::{pattern::Mode, search::pattern::Mapping};
+
+/// A compiled representation of the patterns of a [`List`](super::List) to quickly find the few patterns that may
+/// match a path, which matters if there are thousands of patterns, as is common in large monorepos.
+///
+/// Patterns are sorted into buckets: literal basenames like `Cargo.lock`, literal paths like `/target` or `docs/index.md`,
+/// and basename patterns that end with an extension like `*.o`. These are looked up by the respective portion of the path
+/// to match, ignoring the case so the same index serves case-sensitive and case-insensitive matches.
+/// All other patterns are always considered.
+///
+/// Candidates are only a pre-selection and each one still has to be matched against the path.
+#[derive(Debug, Clone, Default)]
+pub struct Index {
+    by_basename: HashMap<u64, Vec<usize>>,
+    by_path: HashMap<u64, Vec<usize>>,
+    by_extension: HashMap<u64, Vec<usize>>,
+    /// Patterns that can't be put into any bucket.
+    other: Vec<usize>,
+    /// The amount of patterns the index was created from, to prevent using it for a list that changed since.
+    num_patterns: usize,
+}
+
+/// Lifecycle
+impl Index {
+    /// Create a new index from `patterns`.
+    pub fn new<T>(patterns: &[Mapping<T>]) -> Self {
+        let mut index = Index {
+            num_patterns: patterns.len(),
+            ..Default::default()
+        };
+        for (idx, mapping) in patterns.iter().enumerate() {
+            let pattern = &mapping.pattern;
+            let matches_basename = pattern.mode.contains(Mode::NO_SUB_DIR) && !pattern.mode.contains(Mode::ABSOLUTE);
+            let bucket = match pattern.first_wildcard_pos {
+                None if matches_basename => Some((&mut index.by_basename, pattern.text.as_ref())),
+                None => Some((&mut index.by_path, pattern.text.as_ref())),
+                Some(pos) if matches_basename && pattern.mode.contains(Mode::ENDS_WITH) => {
+                    extension(&pattern.text[pos + 1..]).map(|ext| (&mut index.by_extension, ext))
+                }
+                Some(_) => None,
+            };
+            match bucket {
+                Some((map, key)) => map.entry(folded_hash(key)).or_default().push(idx),
+                None => index.other.push(idx),
+            }
+        }
+        index
+    }
+}
+
+/// Access
+impl Index {
+    /// Return `true` if this index was created for exactly `num_patterns` patterns.
+    pub fn is_valid_for(&self, num_patterns: usize) -> bool {
+        self.num_patterns == num_patterns
+    }
+
+    /// Return the indices of all patterns that may match `relative_path` whose basename starts at `basename_start_pos`,
+    /// in descending order, which is the order in which patterns are matched.
+    pub fn candidates(&self, relative_path: &BStr, basename_start_pos: Option<usize>) -> Candidates<'_> {
+        let basename = &relative_path[basename_start_pos.unwrap_or_default()..];
+        Candidates {
+            lists: [
+                lookup(&self.by_basename, basename),
+                lookup(&self.by_path, relative_path),
+                extension(basename).map_or(&[], |ext| lookup(&self.by_extension, ext)),
+                &self.other,
+            ],
+        }
+    }
+}
+
+/// An iterator over pattern indices in descending order, as returned by [`Index::candidates()`].
+pub struct Candidates<'a> {
+    /// Lists of indices in ascending order, which are consumed from the back.
+    lists: [&'a [usize]; 4],
+}
+
+impl Iterator for Candidates<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let list = self
+            .lists
+            .iter_mut()
+            .filter(|list| !list.is_empty())
+            .max_by_key(|list| list[list.len() - 1])?;
+        let (last, rest) = list.split_last().expect("non-empty");
+        *list = rest;
+        Some(*last)
+    }
+}
+
+fn lookup<'a>(map: &'a HashMap<u64, Vec<usize>>, key: &[u8]) -> &'a [usize] {
+    map.get(&folded_hash(key)).map_or(&[], Vec::as_slice)
+}
+
+/// Return the portion of `name` past its last dot, if there is one, which is also the extension of every name that ends
+/// with `name`.
+fn extension(name: &[u8]) -> Option<&[u8]> {
+    let pos = name.iter().rposition(|b| *b == b'.')?;
+    Some(&name[pos + 1..])
+}
+
+/// Hash `bytes` while ignoring their ASCII case. Collisions are harmless as candidates are verified.
+fn folded_hash(bytes: &[u8]) -> u64 {
+    // FNV-1a
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b.to_ascii_lowercase())).wrapping_mul(0x0100_0000_01b3)
+    })
+}
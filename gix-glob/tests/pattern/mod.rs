@@ -15,4 +15,46 @@ fn display() {
     assert_eq!(pat("a", Mode::NEGATIVE), "!a");
     assert_eq!(pat("a", Mode::ABSOLUTE | Mode::NEGATIVE | Mode::MUST_BE_DIR), "!/a/");
 }
+
+#[test]
+fn to_bstring_round_trips() {
+    for input in [
+        "a",
+        "!a",
+        "/a",
+        "a/",
+        "!/a/b/",
+        "*.log",
+        "a/**/b",
+        r"\!important",
+        r"\#not-a-comment",
+        "!!a",
+        "!#a",
+        "/!a",
+        "/#a",
+        r"f\[o\]",
+    ] {
+        let pattern = Pattern::from_bytes(input.as_bytes()).expect("valid");
+        assert_eq!(pattern.to_bstring(), input, "{input:?} is already canonical");
+        assert_eq!(Pattern::from_bytes(&pattern.to_bstring()), Some(pattern));
+    }
+}
+
+#[test]
+fn to_bstring_escapes_what_would_otherwise_be_parsed_differently() {
+    let pattern = Pattern::from_bytes_without_negation(b"!a").expect("valid");
+    assert_eq!(
+        pattern.to_bstring(),
+        r"\!a",
+        "it's not negative and must not become negative"
+    );
+    assert_eq!(
+        Pattern::from_bytes(&pattern.to_bstring()).expect("valid").text,
+        pattern.text,
+        "the escape is removed when parsing"
+    );
+
+    let pattern = Pattern::from_bytes(b"#a").expect("valid");
+    assert_eq!(pattern.to_bstring(), r"\#a", "otherwise it would be read as comment");
+}
 mod matching;
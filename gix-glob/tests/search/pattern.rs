@@ -1,6 +1,7 @@
 mod list {
     use std::path::Path;
 
+    use bstr::ByteSlice;
     use gix_glob::{
         pattern::Case,
         search::{
@@ -107,4 +108,84 @@ mod list {
 
         Ok(())
     }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Default)]
+    struct Globs;
+
+    impl Pattern for Globs {
+        type Value = ();
+
+        fn bytes_to_patterns(bytes: &[u8], _source: &Path) -> Vec<Mapping<Self::Value>> {
+            bytes
+                .lines()
+                .filter_map(gix_glob::parse)
+                .enumerate()
+                .map(|(idx, pattern)| Mapping {
+                    pattern,
+                    value: (),
+                    sequence_number: idx + 1,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn candidates_with_index_yield_the_same_matches_as_without() {
+        let mut patterns = Vec::<u8>::new();
+        for idx in 0..100 {
+            patterns.extend_from_slice(format!("file{idx}\n/root{idx}\ndir/path{idx}\n*.ext{idx}\n").as_bytes());
+        }
+        patterns.extend_from_slice(b"*\n*.RS\nCargo.*\n**/nested/**\n*suffix\nend/\n");
+        let list = List::<Globs>::from_bytes(&patterns, "a/source".into(), None);
+        assert!(list.index.is_some(), "large lists are indexed automatically");
+        let mut unindexed = list.clone();
+        unindexed.index = None;
+        assert_eq!(list, unindexed, "the index isn't considered in comparisons");
+        assert_eq!(
+            list.candidates("file7".into(), None).count(),
+            5,
+            "only the literal and all patterns that can't be indexed are candidates"
+        );
+
+        for path in [
+            "file7",
+            "a/b/FILE7",
+            "root3",
+            "a/root3",
+            "dir/path42",
+            "x/dir/path42",
+            "main.ext99",
+            "a/main.EXT1",
+            "lib.rs",
+            "Cargo.toml",
+            "a/nested/b",
+            "has-suffix",
+            "end",
+            "",
+        ] {
+            let path = path.as_bytes().as_bstr();
+            let basename_pos = path.rfind_byte(b'/').map(|pos| pos + 1);
+            for case in [Case::Sensitive, Case::Fold] {
+                for is_dir in [None, Some(true)] {
+                    let matches = |list: &List<Globs>| {
+                        list.candidates(path, basename_pos)
+                            .filter(|(_idx, mapping)| {
+                                mapping.pattern.matches_repo_relative_path(
+                                    path,
+                                    basename_pos,
+                                    is_dir,
+                                    case,
+                                    gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+                                )
+                            })
+                            .map(|(idx, _mapping)| idx)
+                            .collect::<Vec<_>>()
+                    };
+                    let expected = matches(&unindexed);
+                    assert!(!expected.is_empty(), "the catch-all pattern always matches");
+                    assert_eq!(matches(&list), expected, "{path:?} {case:?} {is_dir:?}");
+                }
+            }
+        }
+    }
 }
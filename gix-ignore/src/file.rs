@@ -0,0 +1,173 @@
+use std::io::Write;
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_glob::pattern::Mode;
+
+use crate::{parse, File, Kind};
+
+/// A line of a [`File`].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub(crate) struct Line {
+    /// The line as it was read, without its line ending.
+    raw: BString,
+    /// The pattern of the line, or `None` if it's a comment, blank or not a valid pattern.
+    parsed: Option<(gix_glob::Pattern, Kind)>,
+}
+
+impl Line {
+    fn parse(raw: &BStr) -> Self {
+        Line {
+            raw: raw.to_owned(),
+            parsed: parse_pattern(raw),
+        }
+    }
+}
+
+fn parse_pattern(line: &[u8]) -> Option<(gix_glob::Pattern, Kind)> {
+    parse::Lines::new(line)
+        .next()
+        .map(|(pattern, _line_number, kind)| (pattern, kind))
+}
+
+/// Instantiation
+impl File {
+    /// Read all lines of the `.gitignore` file in `input`, which never fails.
+    ///
+    /// Comments, blank lines and invalid patterns are kept as is.
+    pub fn from_bytes(input: &[u8]) -> Self {
+        let bom = unicode_bom::Bom::from(input);
+        let (bom, input) = input.split_at(bom.len());
+        File {
+            bom: bom.into(),
+            lines: input.lines().map(|line| Line::parse(line.as_bstr())).collect(),
+            crlf: input.find(b"\r\n").is_some(),
+            trailing_newline: input.is_empty() || input.ends_with(b"\n"),
+        }
+    }
+}
+
+/// Access
+impl File {
+    /// Return all lines that contain a pattern, as written in the file.
+    pub fn patterns(&self) -> impl Iterator<Item = &BStr> {
+        self.lines
+            .iter()
+            .filter(|line| line.parsed.is_some())
+            .map(|line| line.raw.as_bstr())
+    }
+
+    /// Return `true` if `pattern`, as it would be written into the file, is already covered by the patterns of this file,
+    /// so adding it wouldn't change which paths are ignored.
+    ///
+    /// This is the case if the same pattern is present and not overridden by a later pattern, or if `pattern` is a path
+    /// without wildcards that is ignored by another pattern, possibly because one of its parent directories is ignored.
+    /// Note that this check is conservative, so patterns with wildcards are only considered covered if they are present.
+    pub fn covers(&self, pattern: &BStr) -> bool {
+        matches!(parse_pattern(pattern), Some((pattern, kind)) if self.covers_parsed(&pattern, kind))
+    }
+
+    fn covers_parsed(&self, new: &gix_glob::Pattern, new_kind: Kind) -> bool {
+        let is_literal_path = new.first_wildcard_pos.is_none() && !new.is_negative();
+        if is_literal_path {
+            let path = new.text.as_bstr();
+            let ignored_parent = path
+                .char_indices()
+                .filter(|(_, _, c)| *c == '/')
+                .any(|(pos, _, _)| self.decide(path[..pos].as_bstr(), true) == Some((false, new_kind)));
+            if ignored_parent {
+                return true;
+            }
+        }
+        let matches_anywhere = |mode: Mode| mode.contains(Mode::NO_SUB_DIR) && !mode.contains(Mode::ABSOLUTE);
+        for (existing, kind) in self.lines.iter().rev().filter_map(|line| line.parsed.as_ref()) {
+            if existing == new && *kind == new_kind {
+                return true;
+            }
+            if is_literal_path {
+                if pattern_matches(existing, new.text.as_bstr(), new.mode.contains(Mode::MUST_BE_DIR)) {
+                    return !existing.is_negative()
+                        && *kind == new_kind
+                        && (!matches_anywhere(new.mode) || matches_anywhere(existing.mode));
+                }
+            } else if existing.is_negative() != new.is_negative() {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Return `(is_negative, kind)` of the last pattern matching `path`, if there is one.
+    fn decide(&self, path: &BStr, is_dir: bool) -> Option<(bool, Kind)> {
+        self.lines
+            .iter()
+            .rev()
+            .filter_map(|line| line.parsed.as_ref())
+            .find(|(pattern, _)| pattern_matches(pattern, path, is_dir))
+            .map(|(pattern, kind)| (pattern.is_negative(), *kind))
+    }
+}
+
+fn pattern_matches(pattern: &gix_glob::Pattern, path: &BStr, is_dir: bool) -> bool {
+    pattern.matches_repo_relative_path(
+        path,
+        path.rfind_byte(b'/').map(|pos| pos + 1),
+        Some(is_dir),
+        gix_glob::pattern::Case::Sensitive,
+        gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+    )
+}
+
+/// Mutation
+impl File {
+    /// Add `pattern`, as it would be written into the file, to the end of the file unless it's already
+    /// [covered](Self::covers()) or isn't a pattern at all, and return `true` if it was added.
+    pub fn add(&mut self, pattern: &BStr) -> bool {
+        let Some((parsed, kind)) = parse_pattern(pattern) else {
+            return false;
+        };
+        if pattern.contains(&b'\n') || self.covers_parsed(&parsed, kind) {
+            return false;
+        }
+        self.lines.push(Line {
+            raw: pattern.to_owned(),
+            parsed: Some((parsed, kind)),
+        });
+        self.trailing_newline = true;
+        true
+    }
+
+    /// Remove all lines with `pattern`, as it would be written into the file, and return the amount of removed lines.
+    ///
+    /// Lines are removed if their pattern is the same, even if they are written differently, like with trailing spaces.
+    pub fn remove(&mut self, pattern: &BStr) -> usize {
+        let Some(pattern) = parse_pattern(pattern) else {
+            return 0;
+        };
+        let len = self.lines.len();
+        self.lines.retain(|line| line.parsed.as_ref() != Some(&pattern));
+        len - self.lines.len()
+    }
+}
+
+/// Serialization
+impl File {
+    /// Write this file to `out`, with all lines that were read written exactly as they were.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        out.write_all(&self.bom)?;
+        let newline: &[u8] = if self.crlf { b"\r\n" } else { b"\n" };
+        for (index, line) in self.lines.iter().enumerate() {
+            out.write_all(&line.raw)?;
+            if index + 1 < self.lines.len() || self.trailing_newline {
+                out.write_all(newline)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return this file as byte string, see [`write_to()`](Self::write_to()).
+    pub fn to_bstring(&self) -> BString {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf).expect("io error impossible");
+        buf.into()
+    }
+}
@@ -26,6 +26,22 @@ pub struct Search {
     pub patterns: Vec<gix_glob::search::pattern::List<search::Ignore>>,
 }
 
+/// The lines of a `.gitignore` file, which can be edited while preserving comments, blank lines and their order.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+pub struct File {
+    /// The byte order mark the file started with, if any.
+    bom: Vec<u8>,
+    lines: Vec<file::Line>,
+    /// If `true`, lines are separated by `\r\n`.
+    crlf: bool,
+    /// If `true`, the last line ends with a newline as well.
+    trailing_newline: bool,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod file;
+
 /// The kind of *ignored* item.
 ///
 /// This classification is obtained when checking if a path matches an ignore pattern.
@@ -69,25 +69,26 @@ impl Search {
     }
 
     fn from_overrides_inner(patterns: &mut dyn Iterator<Item = OsString>) -> Self {
-        Search {
-            patterns: vec![pattern::List {
-                patterns: patterns
-                    .enumerate()
-                    .filter_map(|(seq_id, pattern)| {
-                        let pattern = gix_path::try_into_bstr(PathBuf::from(pattern)).ok()?;
-                        crate::parse(pattern.as_ref())
-                            .next()
-                            .map(|(p, _seq_id, kind)| pattern::Mapping {
-                                pattern: p,
-                                value: kind,
-                                sequence_number: seq_id + 1,
-                            })
-                    })
-                    .collect(),
-                source: None,
-                base: None,
-            }],
-        }
+        let mut list = pattern::List {
+            patterns: patterns
+                .enumerate()
+                .filter_map(|(seq_id, pattern)| {
+                    let pattern = gix_path::try_into_bstr(PathBuf::from(pattern)).ok()?;
+                    crate::parse(pattern.as_ref())
+                        .next()
+                        .map(|(p, _seq_id, kind)| pattern::Mapping {
+                            pattern: p,
+                            value: kind,
+                            sequence_number: seq_id + 1,
+                        })
+                })
+                .collect(),
+            source: None,
+            base: None,
+            index: None,
+        };
+        list.compile();
+        Search { patterns: vec![list] }
     }
 }
 
@@ -113,12 +114,15 @@ pub fn pattern_matching_relative_path<'a>(
 ) -> Option<Match<'a>> {
     let (relative_path, basename_start_pos) =
         list.strip_base_handle_recompute_basename_pos(relative_path, basename_pos, case)?;
-    list.patterns.iter().rev().find_map(
-        |pattern::Mapping {
-             pattern,
-             value: kind,
-             sequence_number,
-         }| {
+    list.candidates(relative_path, basename_start_pos).find_map(
+        |(
+            _idx,
+            pattern::Mapping {
+                pattern,
+                value: kind,
+                sequence_number,
+            },
+        )| {
             pattern
                 .matches_repo_relative_path(
                     relative_path,
@@ -148,17 +152,18 @@ pub fn pattern_idx_matching_relative_path(
 ) -> Option<usize> {
     let (relative_path, basename_start_pos) =
         list.strip_base_handle_recompute_basename_pos(relative_path, basename_pos, case)?;
-    list.patterns.iter().enumerate().rev().find_map(|(idx, pm)| {
-        pm.pattern
-            .matches_repo_relative_path(
-                relative_path,
-                basename_start_pos,
-                is_dir,
-                case,
-                gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
-            )
-            .then_some(idx)
-    })
+    list.candidates(relative_path, basename_start_pos)
+        .find_map(|(idx, pm)| {
+            pm.pattern
+                .matches_repo_relative_path(
+                    relative_path,
+                    basename_start_pos,
+                    is_dir,
+                    case,
+                    gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+                )
+                .then_some(idx)
+        })
 }
 
 /// Matching of ignore patterns.
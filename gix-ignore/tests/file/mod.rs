@@ -0,0 +1,80 @@
+use gix_ignore::File;
+
+const INPUT: &str = "# build output\ntarget/\n\n*.log\n!keep.log\n";
+
+#[test]
+fn unchanged_files_are_written_verbatim() {
+    for input in [INPUT, "", "*.o", "\u{feff}a  \r\n# comment\r\n", "!$invalid\n"] {
+        assert_eq!(File::from_bytes(input.as_bytes()).to_bstring(), input);
+    }
+}
+
+#[test]
+fn patterns() {
+    let file = File::from_bytes(INPUT.as_bytes());
+    assert_eq!(file.patterns().collect::<Vec<_>>(), ["target/", "*.log", "!keep.log"]);
+}
+
+#[test]
+fn covered_patterns_are_not_added() {
+    let file = File::from_bytes(INPUT.as_bytes());
+    for (pattern, expected) in [
+        ("target/", true),
+        ("target/debug/app", true),
+        ("/target/", true),
+        ("debug.log", true),
+        ("dir/debug.log", true),
+        ("keep.log", false),
+        ("!keep.log", true),
+        ("target", false),
+        ("*.tmp", false),
+        ("$debug.log", false),
+        ("", false),
+        ("# comment", false),
+    ] {
+        assert_eq!(file.covers(pattern.into()), expected, "{pattern}");
+    }
+    assert!(
+        !file.covers("*.log".into()),
+        "adding it again would ignore 'keep.log' as well, as it comes after the negation"
+    );
+
+    let mut file = File::from_bytes(b"/debug.log\nsub/*.o\n*.a\n!*.a\n");
+    assert!(file.covers("/debug.log".into()));
+    assert!(
+        !file.covers("debug.log".into()),
+        "the pattern matches in every directory, but the existing one only at the root"
+    );
+    assert!(
+        !file.covers("*.a".into()),
+        "a later negation overrides it, and wildcards are compared conservatively"
+    );
+    assert!(file.covers("sub/x.o".into()));
+
+    assert!(!file.add("/debug.log".into()));
+    assert!(file.add("debug.log".into()));
+    assert!(!file.add("debug.log".into()), "it's covered by itself now");
+    assert!(file.add("*.a".into()));
+    assert_eq!(file.to_bstring(), "/debug.log\nsub/*.o\n*.a\n!*.a\ndebug.log\n*.a\n");
+
+    let mut file = File::from_bytes(b"a\r\nb");
+    assert!(file.add("c/".into()));
+    assert_eq!(
+        file.to_bstring(),
+        "a\r\nb\r\nc/\r\n",
+        "line endings are preserved, and a missing trailing newline is added"
+    );
+}
+
+#[test]
+fn remove() {
+    let mut file = File::from_bytes(b"# comment\ntarget/\n*.log  \n*.log\nkeep\n");
+    assert_eq!(
+        file.remove("*.log".into()),
+        2,
+        "the pattern is compared, not how it is written"
+    );
+    assert_eq!(file.remove("*.log".into()), 0);
+    assert_eq!(file.remove("# comment".into()), 0, "comments aren't patterns");
+    assert_eq!(file.to_bstring(), "# comment\ntarget/\nkeep\n");
+}
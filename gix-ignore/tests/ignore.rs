@@ -1,3 +1,4 @@
 pub use gix_testtools::Result;
+mod file;
 mod parse;
 mod search;
@@ -364,6 +364,14 @@ impl State {
         self.is_sparse
     }
 
+    /// Returns `true` if only the entries below a path prefix were loaded, for instance with
+    /// [`File::at_with_prefix()`][crate::File::at_with_prefix()].
+    ///
+    /// Partial indices can be queried like any other, but refuse to be written as that would lose all other entries.
+    pub fn is_partial(&self) -> bool {
+        self.is_partial
+    }
+
     /// Return the range of entries that exactly match the given `path`, in all available stages, or `None` if no entry with such
     /// path exists.
     ///
@@ -534,6 +542,32 @@ impl State {
             res
         });
     }
+
+    /// Mark the trees of the `tree` extension leading to the entry at `path` as invalid, which is required after
+    /// adding, changing or removing the entry so they are recomputed by the next consumer of the index.
+    pub fn invalidate_tree_cache(&mut self, path: &BStr) {
+        let Some(mut tree) = self.tree.as_mut() else {
+            return;
+        };
+        tree.num_entries = None;
+        let mut components = path.split(|b| *b == b'/').peekable();
+        while let Some(component) = components.next() {
+            if components.peek().is_none() {
+                break;
+            }
+            match tree
+                .children
+                .iter_mut()
+                .find(|child| child.name.as_slice() == component)
+            {
+                Some(child) => {
+                    child.num_entries = None;
+                    tree = child;
+                }
+                None => break,
+            }
+        }
+    }
 }
 
 /// Extensions
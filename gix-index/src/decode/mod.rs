@@ -6,6 +6,7 @@ mod entries;
 ///
 #[allow(clippy::empty_docs)]
 pub mod header;
+mod prefix;
 
 mod error {
 
@@ -208,23 +209,7 @@ impl State {
             }
         };
 
-        if data.len() != object_hash.len_in_bytes() {
-            return Err(Error::UnexpectedTrailerLength {
-                expected: object_hash.len_in_bytes(),
-                actual: data.len(),
-            });
-        }
-
-        let checksum = gix_hash::ObjectId::from_bytes_or_panic(data);
-        let checksum = (!checksum.is_null()).then_some(checksum);
-        if let Some((expected_checksum, actual_checksum)) = expected_checksum.zip(checksum) {
-            if actual_checksum != expected_checksum {
-                return Err(Error::ChecksumMismatch {
-                    actual_checksum,
-                    expected_checksum,
-                });
-            }
-        }
+        let checksum = checksum(data, object_hash, expected_checksum)?;
         let EntriesOutcome {
             entries,
             path_backing,
@@ -250,6 +235,7 @@ impl State {
                 entries,
                 path_backing,
                 is_sparse,
+                is_partial: false,
 
                 end_of_index_at_decode_time: end_of_index,
                 offset_table_at_decode_time: offset_table,
@@ -264,6 +250,32 @@ impl State {
     }
 }
 
+/// Return the checksum stored in the `trailer` of an index, or `None` if it was skipped, and assure it matches `expected_checksum`.
+fn checksum(
+    trailer: &[u8],
+    object_hash: gix_hash::Kind,
+    expected_checksum: Option<gix_hash::ObjectId>,
+) -> Result<Option<gix_hash::ObjectId>, Error> {
+    if trailer.len() != object_hash.len_in_bytes() {
+        return Err(Error::UnexpectedTrailerLength {
+            expected: object_hash.len_in_bytes(),
+            actual: trailer.len(),
+        });
+    }
+
+    let checksum = gix_hash::ObjectId::from_bytes_or_panic(trailer);
+    let checksum = (!checksum.is_null()).then_some(checksum);
+    if let Some((expected_checksum, actual_checksum)) = expected_checksum.zip(checksum) {
+        if actual_checksum != expected_checksum {
+            return Err(Error::ChecksumMismatch {
+                actual_checksum,
+                expected_checksum,
+            });
+        }
+    }
+    Ok(checksum)
+}
+
 struct EntriesOutcome {
     pub entries: Vec<Entry>,
     pub path_backing: Vec<u8>,
@@ -0,0 +1,155 @@
+use bstr::{BStr, ByteSlice};
+use filetime::FileTime;
+
+use crate::{
+    decode::{self, entries, header, Error, Options},
+    extension, State,
+};
+
+impl State {
+    /// Like [`from_bytes()`][State::from_bytes()], but only keep the entries whose path starts with `prefix`, which yields
+    /// a [partial](State::is_partial()) state that can't be written.
+    ///
+    /// Note that `prefix` is compared byte-wise, so `dir/` should be used to only obtain the entries of the directory `dir`.
+    /// Pathspecs can be used by passing their common prefix.
+    ///
+    /// If the index has an `IEOT` extension, blocks of entries that can't contain paths with `prefix` are skipped entirely,
+    /// which is what makes this faster than a full decode for large indices. Otherwise, all entries are decoded and
+    /// filtered afterwards.
+    /// The `tree` extension is dropped as it describes all entries, while all other extensions are kept.
+    ///
+    /// ### The `link` extension
+    ///
+    /// Entries of a split index refer to the shared index by position, so they can't be filtered before the
+    /// `link` extension is dissolved. If it is present, the returned state contains all entries. Use
+    /// [`File::at_with_prefix()`][crate::File::at_with_prefix()] to handle this case transparently.
+    pub fn from_bytes_with_prefix(
+        data: &[u8],
+        timestamp: FileTime,
+        object_hash: gix_hash::Kind,
+        prefix: &BStr,
+        options: Options,
+    ) -> Result<(Self, Option<gix_hash::ObjectId>), Error> {
+        let _span = gix_features::trace::detail!("gix_index::State::from_bytes_with_prefix()", prefix = ?prefix);
+        let blocks = extension::end_of_index_entry::decode(data, object_hash).and_then(|start_of_extensions| {
+            extension::index_entry_offset_table::find(&data[start_of_extensions..], object_hash)
+                .map(|blocks| (start_of_extensions, blocks))
+        });
+        let Some((start_of_extensions, blocks)) = blocks.filter(|_| !prefix.is_empty()) else {
+            return Self::from_bytes_and_retain_prefix(data, timestamp, object_hash, prefix, options);
+        };
+        let (version, _num_entries, _post_header_data) = header::decode(data, object_hash)?;
+        let (ext, trailer) = extension::decode::all(&data[start_of_extensions..], object_hash)?;
+        if ext.link.is_some() {
+            return Self::from_bytes_and_retain_prefix(data, timestamp, object_hash, prefix, options);
+        }
+        let checksum = decode::checksum(trailer, object_hash, options.expected_checksum)?;
+
+        // The first path of each block tells us which blocks may contain entries with `prefix`, as entries are sorted.
+        // Note that each block is self-contained, even V4 path compression starts over at its beginning.
+        let mut first_paths = Vec::with_capacity(blocks.len());
+        let (mut scratch_entries, mut scratch_paths) = (Vec::with_capacity(1), Vec::new());
+        for block in &blocks {
+            scratch_entries.clear();
+            scratch_paths.clear();
+            entries::chunk(
+                &data[block.from_beginning_of_file as usize..],
+                &mut scratch_entries,
+                &mut scratch_paths,
+                block.num_entries.min(1),
+                object_hash,
+                version,
+            )?;
+            first_paths.push(
+                scratch_entries
+                    .first()
+                    .map(|entry| scratch_paths[entry.path.clone()].to_owned()),
+            );
+        }
+
+        let mut entries = Vec::new();
+        let mut path_backing = Vec::new();
+        let mut is_sparse = ext.is_sparse;
+        for (idx, block) in blocks.iter().enumerate() {
+            let Some(first_path) = first_paths[idx].as_deref() else {
+                continue;
+            };
+            let ends_before_prefix = matches!(
+                first_paths.get(idx + 1).and_then(Option::as_deref),
+                Some(next_first_path) if next_first_path < prefix.as_bytes()
+            );
+            let starts_after_prefix = first_path > prefix.as_bytes() && !first_path.starts_with(prefix);
+            if ends_before_prefix || starts_after_prefix {
+                continue;
+            }
+            let (
+                entries::Outcome {
+                    is_sparse: block_is_sparse,
+                },
+                _data,
+            ) = entries::chunk(
+                &data[block.from_beginning_of_file as usize..],
+                &mut entries,
+                &mut path_backing,
+                block.num_entries,
+                object_hash,
+                version,
+            )?;
+            is_sparse |= block_is_sparse;
+        }
+
+        let extension::decode::Outcome {
+            tree: _,
+            link,
+            resolve_undo,
+            untracked,
+            fs_monitor,
+            is_sparse: _,
+            end_of_index,
+            offset_table,
+        } = ext;
+        let mut state = State {
+            object_hash,
+            timestamp,
+            version,
+            entries,
+            path_backing,
+            is_sparse,
+            is_partial: false,
+
+            end_of_index_at_decode_time: end_of_index,
+            offset_table_at_decode_time: offset_table,
+            tree: None,
+            link,
+            resolve_undo,
+            untracked,
+            fs_monitor,
+        };
+        state.retain_prefix(prefix);
+        Ok((state, checksum))
+    }
+
+    fn from_bytes_and_retain_prefix(
+        data: &[u8],
+        timestamp: FileTime,
+        object_hash: gix_hash::Kind,
+        prefix: &BStr,
+        options: Options,
+    ) -> Result<(Self, Option<gix_hash::ObjectId>), Error> {
+        let (mut state, checksum) = Self::from_bytes(data, timestamp, object_hash, options)?;
+        if state.link.is_none() {
+            state.retain_prefix(prefix);
+        }
+        Ok((state, checksum))
+    }
+
+    /// Remove all entries whose path doesn't start with `prefix` and mark this state as partial, unless `prefix` is empty.
+    pub(crate) fn retain_prefix(&mut self, prefix: &BStr) {
+        if prefix.is_empty() {
+            return;
+        }
+        self.remove_entries(|_, path, _| !path.starts_with(prefix));
+        self.tree = None;
+        self.is_partial = true;
+    }
+}
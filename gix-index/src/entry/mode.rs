@@ -33,6 +33,9 @@ impl Mode {
     /// executable). That measn that unstaging and staging files can be a lossy
     /// operation on such file systems.
     ///
+    /// Note that if `has_symlinks` is false, symlinks on disk are still acceptable
+    /// for symlink entries, just like Git does it.
+    ///
     /// If a directory replaced a normal file/symlink we assume that the
     /// directory is a submodule. Normal (non-submodule) directories would
     /// cause a file to be deleted from the index and should be handled before
@@ -50,7 +53,7 @@ impl Mode {
         match self {
             Mode::FILE if !stat.is_file() => (),
             Mode::SYMLINK if has_symlinks && !stat.is_symlink() => (),
-            Mode::SYMLINK if !has_symlinks && !stat.is_file() => (),
+            Mode::SYMLINK if !has_symlinks && !stat.is_file() && !stat.is_symlink() => (),
             Mode::COMMIT | Mode::DIR if !stat.is_dir() => (),
             Mode::FILE if executable_bit && stat.is_executable() => return Some(Change::ExecutableBit),
             Mode::FILE_EXECUTABLE if executable_bit && !stat.is_executable() => return Some(Change::ExecutableBit),
@@ -2,6 +2,8 @@
 
 use std::path::{Path, PathBuf};
 
+use bstr::BStr;
+
 use crate::{decode, extension, File, State};
 
 mod error {
@@ -57,7 +59,31 @@ impl File {
         options: decode::Options,
     ) -> Result<Self, Error> {
         let _span = gix_features::trace::detail!("gix_index::File::at()");
-        let path = path.into();
+        Self::at_inner(path.into(), object_hash, skip_hash, "".into(), options)
+    }
+
+    /// Like [`at()`](Self::at()), but only load the entries whose path starts with `prefix` to obtain a
+    /// [partial](State::is_partial()) index, which is much faster for large indices with an `IEOT` extension.
+    ///
+    /// See [`State::from_bytes_with_prefix()`] for details. Unlike it, indices with a `link` extension are filtered as well.
+    pub fn at_with_prefix(
+        path: impl Into<PathBuf>,
+        object_hash: gix_hash::Kind,
+        skip_hash: bool,
+        prefix: &BStr,
+        options: decode::Options,
+    ) -> Result<Self, Error> {
+        let _span = gix_features::trace::detail!("gix_index::File::at_with_prefix()", prefix = ?prefix);
+        Self::at_inner(path.into(), object_hash, skip_hash, prefix, options)
+    }
+
+    fn at_inner(
+        path: PathBuf,
+        object_hash: gix_hash::Kind,
+        skip_hash: bool,
+        prefix: &BStr,
+        options: decode::Options,
+    ) -> Result<Self, Error> {
         let (data, mtime) = {
             let mut file = std::fs::File::open(&path)?;
             // SAFETY: we have to take the risk of somebody changing the file underneath. Git never writes into the same file.
@@ -95,10 +121,15 @@ impl File {
             (data, filetime::FileTime::from_last_modification_time(&file.metadata()?))
         };
 
-        let (state, checksum) = State::from_bytes(&data, mtime, object_hash, options)?;
+        let (state, checksum) = if prefix.is_empty() {
+            State::from_bytes(&data, mtime, object_hash, options)?
+        } else {
+            State::from_bytes_with_prefix(&data, mtime, object_hash, prefix, options)?
+        };
         let mut file = File { state, path, checksum };
         if let Some(mut link) = file.link.take() {
             link.dissolve_into(&mut file, object_hash, skip_hash, options)?;
+            file.state.retain_prefix(prefix);
         }
 
         Ok(file)
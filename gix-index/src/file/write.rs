@@ -47,10 +47,11 @@ impl File {
             gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?,
         );
         let (version, digest) = self.write_to(&mut lock, options)?;
-        match lock.into_inner() {
-            Ok(lock) => lock.commit()?,
-            Err(err) => return Err(err.into_error().into()),
-        };
+        let mut lock = lock.into_inner().map_err(std::io::IntoInnerError::into_error)?;
+        if let Some(method) = options.fsync {
+            lock.with_mut(|file| gix_fs::fsync::file(file, method))?;
+        }
+        lock.commit()?;
         self.state.version = version;
         self.checksum = Some(digest);
         Ok(())
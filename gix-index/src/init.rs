@@ -36,6 +36,7 @@ pub mod from_tree {
                 entries: vec![],
                 path_backing: vec![],
                 is_sparse: false,
+                is_partial: false,
                 tree: None,
                 link: None,
                 resolve_undo: None,
@@ -94,6 +95,7 @@ pub mod from_tree {
                 entries,
                 path_backing,
                 is_sparse: false,
+                is_partial: false,
                 tree: None,
                 link: None,
                 resolve_undo: None,
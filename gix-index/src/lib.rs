@@ -45,6 +45,10 @@ pub mod verify;
 #[allow(clippy::empty_docs)]
 pub mod write;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod sparse;
+
 pub mod fs;
 
 /// All known versions of a git index file.
@@ -152,6 +156,8 @@ pub struct State {
     path_backing: PathStorage,
     /// True if one entry in the index has a special marker mode
     is_sparse: bool,
+    /// True if only the entries below a path prefix were loaded, which makes this state unsuitable for writing.
+    is_partial: bool,
 
     // Extensions
     end_of_index_at_decode_time: bool,
@@ -0,0 +1,208 @@
+use std::ops::Range;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_object::FindExt;
+
+use crate::{
+    entry::{Flags, Mode, Stage, Stat},
+    extension, Entry, State,
+};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod expand {
+    /// The error returned by [State::expand_sparse_directory()](crate::State::expand_sparse_directory()) and its relatives.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not find the tree of the sparse directory at '{path}'")]
+        FindTree {
+            path: bstr::BString,
+            source: gix_object::find::existing_iter::Error,
+        },
+        #[error("Could not decode the tree of the sparse directory at '{path}'")]
+        DecodeTree {
+            path: bstr::BString,
+            source: gix_object::decode::Error,
+        },
+    }
+}
+
+/// Sparse directories
+impl State {
+    /// Replace the [sparse directory entry](Mode::DIR) at `idx` with the entries of its tree, which are looked up in `objects`,
+    /// and return the amount of entries it was replaced with.
+    ///
+    /// Only one level is expanded, so sub-directories are added as sparse directory entries themselves, which allows to expand
+    /// only the parts of the index that are needed.
+    /// All new entries inherit the [`SKIP_WORKTREE`](Flags::SKIP_WORKTREE) flag, and nothing is done if the entry at `idx`
+    /// isn't a sparse directory.
+    ///
+    /// ### Panics
+    ///
+    /// If `idx` is out of bounds.
+    pub fn expand_sparse_directory(
+        &mut self,
+        idx: usize,
+        objects: &impl gix_object::Find,
+    ) -> Result<usize, expand::Error> {
+        let dir = self.entries[idx].clone();
+        if !dir.mode.is_sparse() {
+            return Ok(0);
+        }
+        let dir_path: BString = dir.path(self).to_owned();
+        let mut buf = Vec::new();
+        let tree = objects
+            .find_tree_iter(&dir.id, &mut buf)
+            .map_err(|source| expand::Error::FindTree {
+                path: dir_path.clone(),
+                source,
+            })?;
+
+        let mut children = Vec::new();
+        for entry in tree {
+            let entry = entry.map_err(|source| expand::Error::DecodeTree {
+                path: dir_path.clone(),
+                source,
+            })?;
+            let start = self.path_backing.len();
+            self.path_backing.extend_from_slice(&dir_path);
+            self.path_backing.extend_from_slice(entry.filename);
+            if entry.mode.is_tree() {
+                self.path_backing.push(b'/');
+            }
+            children.push(Entry {
+                stat: Stat::default(),
+                id: entry.oid.to_owned(),
+                flags: Flags::EXTENDED | Flags::SKIP_WORKTREE,
+                mode: entry.mode.into(),
+                path: start..self.path_backing.len(),
+            });
+        }
+        let path_backing = &self.path_backing;
+        children.sort_by(|a, b| Entry::cmp_filepaths(a.path_in(path_backing), b.path_in(path_backing)));
+
+        let num_children = children.len();
+        self.entries.splice(idx..idx + 1, children);
+        self.is_sparse = self.entries.iter().any(|entry| entry.mode.is_sparse());
+        Ok(num_children)
+    }
+
+    /// Expand all [sparse directory entries](Mode::DIR) that contain `path`, looking up their trees in `objects`, so that
+    /// `path` can be found in the index if it is tracked, and return the amount of directories that were expanded.
+    ///
+    /// This should be called before accessing or changing entries inside of collapsed directories.
+    pub fn expand_sparse_directories_to_path(
+        &mut self,
+        path: &BStr,
+        objects: &impl gix_object::Find,
+    ) -> Result<usize, expand::Error> {
+        let mut num_expanded = 0;
+        if !self.is_sparse {
+            return Ok(num_expanded);
+        }
+        let mut prefix = BString::default();
+        for component in path.split_str("/") {
+            prefix.push_str(component);
+            prefix.push_byte(b'/');
+            if let Some(idx) = self
+                .entry_index_by_path_and_stage(prefix.as_bstr(), Stage::Unconflicted)
+                .filter(|idx| self.entries[*idx].mode.is_sparse())
+            {
+                self.expand_sparse_directory(idx, objects)?;
+                num_expanded += 1;
+            }
+        }
+        Ok(num_expanded)
+    }
+
+    /// Expand all [sparse directory entries](Mode::DIR) recursively, looking up their trees in `objects`, so that
+    /// the index contains all tracked files, and return the amount of directories that were expanded.
+    pub fn expand_all_sparse_directories(&mut self, objects: &impl gix_object::Find) -> Result<usize, expand::Error> {
+        let mut num_expanded = 0;
+        let mut idx = 0;
+        while idx < self.entries.len() {
+            if self.entries[idx].mode.is_sparse() {
+                self.expand_sparse_directory(idx, objects)?;
+                num_expanded += 1;
+            } else {
+                idx += 1;
+            }
+        }
+        Ok(num_expanded)
+    }
+
+    /// Replace all entries of directories which are entirely excluded from the worktree with a single
+    /// [sparse directory entry](Mode::DIR) each, and return the amount of directories that were collapsed.
+    ///
+    /// A directory is collapsed if all entries below it have the [`SKIP_WORKTREE`](Flags::SKIP_WORKTREE) flag and aren't
+    /// conflicted, and if it is valid in the [`tree` extension](Self::tree()) which provides the id of its tree.
+    /// This is typically done before writing the index if `index.sparse` is enabled in cone mode, to undo expansions
+    /// made with [`expand_sparse_directory()`](Self::expand_sparse_directory()) and its relatives.
+    pub fn collapse_sparse_directories(&mut self) -> usize {
+        let Some(tree) = self.tree.as_ref() else {
+            return 0;
+        };
+        let mut collapsible = Vec::new();
+        let mut prefix = BString::default();
+        for child in &tree.children {
+            find_collapsible_directories(self, child, &mut prefix, &mut collapsible);
+        }
+        if collapsible.is_empty() {
+            return 0;
+        }
+        collapsible.sort_by_key(|(range, _, _)| range.start);
+
+        let num_collapsed = collapsible.len();
+        let mut entries = Vec::with_capacity(self.entries.len());
+        let mut previous_end = 0;
+        for (range, path, id) in collapsible {
+            entries.extend_from_slice(&self.entries[previous_end..range.start]);
+            previous_end = range.end;
+            let start = self.path_backing.len();
+            self.path_backing.extend_from_slice(&path);
+            entries.push(Entry {
+                stat: Stat::default(),
+                id,
+                flags: Flags::EXTENDED | Flags::SKIP_WORKTREE,
+                mode: Mode::DIR,
+                path: start..self.path_backing.len(),
+            });
+        }
+        entries.extend_from_slice(&self.entries[previous_end..]);
+        self.entries = entries;
+        self.is_sparse = true;
+        num_collapsed
+    }
+}
+
+/// Add the entry range, path and tree id of `tree` to `out` if its entries can be collapsed, or try its children otherwise.
+/// `prefix` is the path of the parent directory of `tree`, with a trailing slash if it isn't empty.
+fn find_collapsible_directories(
+    state: &State,
+    tree: &extension::Tree,
+    prefix: &mut BString,
+    out: &mut Vec<(Range<usize>, BString, gix_hash::ObjectId)>,
+) {
+    if tree.num_entries.is_none() {
+        return;
+    }
+    let prefix_len = prefix.len();
+    prefix.extend_from_slice(&tree.name);
+    prefix.push_byte(b'/');
+    if let Some(range) = state.prefixed_entries_range(prefix.as_bstr()) {
+        let is_collapsible = state.entries[range.clone()].iter().all(|entry| {
+            entry.flags.contains(Flags::SKIP_WORKTREE)
+                && entry.stage() == Stage::Unconflicted
+                && !(entry.mode.is_sparse() && entry.path(state) == prefix.as_bstr())
+        });
+        if is_collapsible {
+            out.push((range, prefix.clone(), tree.id));
+        } else {
+            for child in &tree.children {
+                find_collapsible_directories(state, child, prefix, out);
+            }
+        }
+    }
+    prefix.truncate(prefix_len);
+}
@@ -56,19 +56,31 @@ pub struct Options {
     /// via [`File::write()`](crate::File::write()) and [`File::write_to()`](crate::File::write_to()).
     /// Note that
     pub skip_hash: bool,
+    /// How to flush the index file to permanent storage before moving it into place when it is written with
+    /// [`File::write()`](crate::File::write()), or `None` to not flush it.
+    pub fsync: Option<gix_fs::fsync::Method>,
 }
 
 impl State {
     /// Serialize this instance to `out` with [`options`][Options].
+    ///
+    /// Fails if this state [is partial](State::is_partial()).
     pub fn write_to(
         &self,
         out: impl std::io::Write,
         Options {
             extensions,
             skip_hash: _,
+            fsync: _,
         }: Options,
     ) -> std::io::Result<Version> {
         let _span = gix_features::trace::detail!("gix_index::State::write()");
+        if self.is_partial {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Refusing to write a partially loaded index as it would lose all entries that weren't loaded",
+            ));
+        }
         let version = self.detect_required_version();
 
         let mut write = CountBytes::new(out);
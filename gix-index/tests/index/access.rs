@@ -214,6 +214,26 @@ fn remove_entries() {
     file.remove_entries(|_, _, _| unreachable!("should not be called"));
 }
 
+#[test]
+fn invalidate_tree_cache() {
+    let mut file = Fixture::Generated("v2_more_files").open();
+    let is_valid = |file: &gix_index::File| {
+        let tree = file.tree().expect("present");
+        (tree.num_entries.is_some(), tree.children[0].num_entries.is_some())
+    };
+    assert_eq!(is_valid(&file), (true, true));
+
+    file.invalidate_tree_cache("a".into());
+    assert_eq!(is_valid(&file), (false, true), "only the root tree contains 'a'");
+
+    file.invalidate_tree_cache("d/new/file".into());
+    assert_eq!(
+        is_valid(&file),
+        (false, false),
+        "all existing trees leading to the path are invalidated"
+    );
+}
+
 #[test]
 fn sort_entries() {
     let mut file = Fixture::Generated("v4_more_files_IEOT").open();
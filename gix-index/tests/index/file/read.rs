@@ -375,3 +375,88 @@ fn split_index_and_regular_index_of_same_content_are_indeed_the_same() {
         assert_eq!(s.path_in(split.path_backing()), r.path_in(regular.path_backing()));
     })
 }
+
+mod at_with_prefix {
+    use bstr::{BStr, ByteSlice};
+
+    use crate::index::file::read::verify;
+
+    fn file_with_prefix(name: &str, prefix: &str) -> gix_index::File {
+        let file = gix_index::File::at_with_prefix(
+            crate::fixture_index_path(name),
+            gix_hash::Kind::Sha1,
+            false,
+            prefix.into(),
+            Default::default(),
+        )
+        .unwrap();
+        verify(file)
+    }
+
+    fn paths(file: &gix_index::File) -> Vec<&BStr> {
+        file.entries().iter().map(|e| e.path(file)).collect()
+    }
+
+    #[test]
+    fn v4_with_ieot_ext() {
+        for (prefix, expected) in [
+            ("d/", &["d/a", "d/b", "d/c", "d/last/123", "d/last/34", "d/last/6"][..]),
+            ("d/last/", &["d/last/123", "d/last/34", "d/last/6"]),
+            ("d/last/3", &["d/last/34"]),
+            ("a", &["a"]),
+            ("x", &["x"]),
+            ("e", &[]),
+            ("z", &[]),
+        ] {
+            let file = file_with_prefix("v4_more_files_IEOT", prefix);
+            assert_eq!(paths(&file), expected, "{prefix:?}");
+            assert!(file.is_partial());
+            assert!(
+                file.tree().is_none(),
+                "the tree extension describes all entries and is dropped"
+            );
+        }
+
+        let file = file_with_prefix("v4_more_files_IEOT", "");
+        assert!(!file.is_partial(), "an empty prefix loads everything");
+        assert_eq!(file.entries().len(), 10);
+    }
+
+    #[test]
+    fn partial_indices_match_filtered_full_indices() {
+        for name in [
+            "v4_more_files_IEOT",
+            "v2_more_files",
+            "v2_deeper_tree",
+            "v2_split_index",
+            "v3_sparse_index",
+        ] {
+            let full = crate::index::file::read::file(name);
+            for entry in full.entries() {
+                let path = entry.path(&full);
+                for prefix_len in 1..=path.len() {
+                    let prefix = path[..prefix_len].to_str().expect("valid UTF-8");
+                    let partial = file_with_prefix(name, prefix);
+                    let expected: Vec<_> = paths(&full)
+                        .into_iter()
+                        .filter(|path| path.starts_with(prefix.as_bytes()))
+                        .collect();
+                    assert_eq!(paths(&partial), expected, "{name}: {prefix:?}");
+                    assert!(
+                        partial.link().is_none(),
+                        "{name}: split indices are dissolved before filtering"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partial_indices_cannot_be_written() {
+        let file = file_with_prefix("v2_more_files", "d/");
+        let err = file
+            .write_to(std::io::sink(), Default::default())
+            .expect_err("partial indices would lose entries");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -48,6 +48,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: false,
+        fsync: None,
     })?;
 
     let actual = gix_index::File::at(
@@ -65,6 +66,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: true,
+        fsync: None,
     })?;
 
     let actual = gix_index::File::at(
@@ -288,6 +290,36 @@ fn compare_raw_bytes(generated: &[u8], expected: &[u8], fixture: &str) {
     }
 }
 
+#[test]
+fn fsync() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let path = tmp.path().join("index");
+    let mut expected = Loose("conflicting-file").open();
+    expected.set_path(&path);
+    for method in [
+        gix_fs::fsync::Method::Fsync,
+        gix_fs::fsync::Method::WriteoutOnly,
+        gix_fs::fsync::Method::Batch,
+    ] {
+        expected.write(Options {
+            fsync: Some(method),
+            ..Default::default()
+        })?;
+        let actual = gix_index::File::at(
+            &path,
+            expected.checksum().expect("present").kind(),
+            false,
+            Default::default(),
+        )?;
+        assert_eq!(
+            actual.checksum(),
+            expected.checksum(),
+            "flushing doesn't affect the content"
+        );
+    }
+    Ok(())
+}
+
 fn only_tree_ext() -> Options {
     Options {
         extensions: write::Extensions::Given {
@@ -295,6 +327,7 @@ fn only_tree_ext() -> Options {
             tree_cache: true,
         },
         skip_hash: false,
+        fsync: None,
     }
 }
 
@@ -302,5 +335,6 @@ fn options_with(extensions: write::Extensions) -> Options {
     Options {
         extensions,
         skip_hash: false,
+        fsync: None,
     }
 }
@@ -0,0 +1,77 @@
+use bstr::{BString, ByteSlice};
+use filetime::FileTime;
+use gix_index::{entry::Flags, State};
+
+use crate::index::Fixture;
+
+fn sparse_index_and_odb() -> crate::Result<(gix_index::File, gix_odb::Handle)> {
+    let fixture = Fixture::Generated("v3_sparse_index");
+    let index = fixture.open();
+    let objects = gix_odb::at(fixture.to_path().parent().expect("in .git").join("objects"))?;
+    Ok((index, objects))
+}
+
+fn paths(state: &State) -> Vec<BString> {
+    state.entries().iter().map(|e| e.path(state).to_owned()).collect()
+}
+
+#[test]
+fn expand_to_path_only_expands_directories_leading_to_it() -> crate::Result {
+    let (mut index, objects) = sparse_index_and_odb()?;
+    assert!(index.entry_by_path("d/c4/c5".into()).is_none(), "collapsed");
+
+    assert_eq!(index.expand_sparse_directories_to_path("d/c4/c5".into(), &objects)?, 2);
+    assert_eq!(
+        paths(&index),
+        ["a", "b", "c1/a", "c1/b", "c1/c2/a", "c1/c2/b", "c1/c3/", "d/a", "d/b", "d/c4/a", "d/c4/b", "d/c4/c5"]
+    );
+    let entry = index.entry_by_path("d/c4/c5".into()).expect("expanded");
+    assert!(
+        entry.flags.contains(Flags::SKIP_WORKTREE),
+        "expanded entries stay excluded"
+    );
+    assert!(index.is_sparse(), "'c1/c3/' is still collapsed");
+
+    assert_eq!(
+        index.expand_sparse_directories_to_path("d/c4/c5".into(), &objects)?,
+        0,
+        "nothing left to expand"
+    );
+    Ok(())
+}
+
+#[test]
+fn expand_all_and_collapse_again() -> crate::Result {
+    let (mut index, objects) = sparse_index_and_odb()?;
+    let expected = paths(&index);
+
+    assert_eq!(index.expand_all_sparse_directories(&objects)?, 3);
+    assert!(!index.is_sparse());
+    assert_eq!(index.entries().len(), 13);
+    assert!(
+        index.entries().iter().all(|e| !e.mode.is_sparse()),
+        "there are no sparse directories left"
+    );
+
+    assert_eq!(index.collapse_sparse_directories(), 2, "'c1/c3' and 'd' are excluded");
+    assert!(index.is_sparse());
+    assert_eq!(paths(&index), expected);
+
+    let mut buf = Vec::new();
+    index.write_to(&mut buf, Default::default())?;
+    let (actual, _) = State::from_bytes(&buf, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    assert!(actual.is_sparse());
+    assert_eq!(paths(&actual), expected, "sparse directories are written and read back");
+    for (actual, expected) in actual
+        .entries()
+        .iter()
+        .zip(Fixture::Generated("v3_sparse_index").open().entries())
+    {
+        assert_eq!(actual.id, expected.id, "collapsed directories point to their trees");
+        assert_eq!(actual.flags, expected.flags);
+    }
+
+    assert_eq!(index.collapse_sparse_directories(), 0, "nothing else can be collapsed");
+    assert_eq!(paths(&index).iter().filter(|p| p.ends_with_str("/")).count(), 2);
+    Ok(())
+}
@@ -17,19 +17,79 @@ pub enum Fail {
     /// Retry after failure with exponentially longer sleep times to block the current thread.
     /// Fail once the given duration is exceeded, similar to [Fail::Immediately]
     AfterDurationWithBackoff(Duration),
+    /// Like [Fail::AfterDurationWithBackoff], but with full control over the backoff curve.
+    AfterBackoff(Backoff),
+}
+
+impl Fail {
+    /// Return the backoff configuration to use when retrying, or `None` if there should be no retries.
+    pub fn backoff(&self) -> Option<Backoff> {
+        match self {
+            Fail::Immediately => None,
+            Fail::AfterDurationWithBackoff(duration) => Some(Backoff::new(*duration)),
+            Fail::AfterBackoff(backoff) => Some(*backoff),
+        }
+    }
 }
 
 impl fmt::Display for Fail {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Fail::Immediately => f.write_str("immediately"),
-            Fail::AfterDurationWithBackoff(duration) => {
-                write!(f, "after {:.02}s", duration.as_secs_f32())
+        match self.backoff() {
+            None => f.write_str("immediately"),
+            Some(backoff) => {
+                write!(f, "after {:.02}s", backoff.max_total_wait.as_secs_f32())
             }
         }
     }
 }
 
+/// Configure the retry curve used while waiting for a lock that is held elsewhere.
+///
+/// Sleep times grow exponentially until they reach `max_step`, and each of them is randomized by `jitter_percent`
+/// to prevent contending processes from retrying in lockstep.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Backoff {
+    /// The total amount of time to wait for the lock, after which acquisition fails.
+    pub max_total_wait: Duration,
+    /// The longest time to sleep between two attempts.
+    pub max_step: Duration,
+    /// By how many percent each sleep time may randomly deviate from its nominal value, clamped to 100.
+    pub jitter_percent: u8,
+}
+
+impl Backoff {
+    /// Create a new instance which waits no longer than `max_total_wait`, using the same curve as [Fail::AfterDurationWithBackoff].
+    pub fn new(max_total_wait: Duration) -> Self {
+        Backoff {
+            max_total_wait,
+            max_step: Duration::from_secs(1),
+            jitter_percent: 25,
+        }
+    }
+
+    /// Set the longest time to sleep between two attempts to `max_step`.
+    pub fn with_max_step(mut self, max_step: Duration) -> Self {
+        self.max_step = max_step;
+        self
+    }
+
+    /// Set the amount of random deviation of each sleep time to `jitter_percent`.
+    pub fn with_jitter_percent(mut self, jitter_percent: u8) -> Self {
+        self.jitter_percent = jitter_percent;
+        self
+    }
+
+    fn steps(&self) -> backoff::Exponential<impl Fn(usize) -> usize + Send + Sync> {
+        backoff::Exponential::new(self.max_step, backoff::jitter(self.jitter_percent))
+    }
+}
+
+impl From<Backoff> for Fail {
+    fn from(value: Backoff) -> Self {
+        Fail::AfterBackoff(value)
+    }
+}
+
 impl From<Duration> for Fail {
     fn from(value: Duration) -> Self {
         if value.is_zero() {
@@ -102,6 +162,40 @@ impl File {
     }
 }
 
+/// Async acquisition
+impl File {
+    /// Like [`acquire_to_update_resource()`](File::acquire_to_update_resource()), but suitable for use in async contexts
+    /// as waiting for the lock to become available is done by awaiting the future returned by `sleep(duration)`
+    /// instead of blocking the current thread.
+    ///
+    /// Use the timer of the runtime of your choice to implement `sleep`, e.g. `|d| tokio::time::sleep(d)`.
+    /// Note that creating the lock file itself is still performed using blocking IO, which is assumed to be fast.
+    pub async fn acquire_to_update_resource_async<Sleep, Fut>(
+        at_path: impl AsRef<Path>,
+        mode: Fail,
+        boundary_directory: Option<PathBuf>,
+        sleep: Sleep,
+    ) -> Result<File, Error>
+    where
+        Sleep: FnMut(Duration) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        let (lock_path, handle) =
+            lock_with_mode_async(at_path.as_ref(), mode, boundary_directory, sleep, &|p, d, c| {
+                if let Some(permissions) = default_permissions() {
+                    gix_tempfile::writable_at_with_permissions(p, d, c, permissions)
+                } else {
+                    gix_tempfile::writable_at(p, d, c)
+                }
+            })
+            .await?;
+        Ok(File {
+            inner: handle,
+            lock_path,
+        })
+    }
+}
+
 impl Marker {
     /// Like [`acquire_to_update_resource()`](File::acquire_to_update_resource()) but _without_ the possibility to make changes
     /// and commit them.
@@ -153,6 +247,39 @@ impl Marker {
     }
 }
 
+/// Async acquisition
+impl Marker {
+    /// Like [`acquire_to_hold_resource()`](Marker::acquire_to_hold_resource()), but awaits the future returned by
+    /// `sleep(duration)` while waiting for the lock instead of blocking the current thread.
+    ///
+    /// See [`File::acquire_to_update_resource_async()`] for details.
+    pub async fn acquire_to_hold_resource_async<Sleep, Fut>(
+        at_path: impl AsRef<Path>,
+        mode: Fail,
+        boundary_directory: Option<PathBuf>,
+        sleep: Sleep,
+    ) -> Result<Marker, Error>
+    where
+        Sleep: FnMut(Duration) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        let (lock_path, handle) =
+            lock_with_mode_async(at_path.as_ref(), mode, boundary_directory, sleep, &|p, d, c| {
+                if let Some(permissions) = default_permissions() {
+                    gix_tempfile::mark_at_with_permissions(p, d, c, permissions)
+                } else {
+                    gix_tempfile::mark_at(p, d, c)
+                }
+            })
+            .await?;
+        Ok(Marker {
+            created_from_file: false,
+            inner: handle,
+            lock_path,
+        })
+    }
+}
+
 fn dir_cleanup(boundary: Option<PathBuf>) -> (ContainingDirectory, AutoRemove) {
     match boundary {
         None => (ContainingDirectory::Exists, AutoRemove::Tempfile),
@@ -169,25 +296,53 @@ fn lock_with_mode<T>(
     boundary_directory: Option<PathBuf>,
     try_lock: &dyn Fn(&Path, ContainingDirectory, AutoRemove) -> std::io::Result<T>,
 ) -> Result<(PathBuf, T), Error> {
-    use std::io::ErrorKind::*;
     let (directory, cleanup) = dir_cleanup(boundary_directory);
     let lock_path = add_lock_suffix(resource);
     let mut attempts = 1;
-    match mode {
-        Fail::Immediately => try_lock(&lock_path, directory, cleanup),
-        Fail::AfterDurationWithBackoff(time) => {
-            for wait in backoff::Exponential::default_with_random().until_no_remaining(time) {
+    match mode.backoff() {
+        None => try_lock(&lock_path, directory, cleanup),
+        Some(backoff) => {
+            for wait in backoff.steps().until_no_remaining(backoff.max_total_wait) {
                 attempts += 1;
                 match try_lock(&lock_path, directory, cleanup.clone()) {
                     Ok(v) => return Ok((lock_path, v)),
-                    #[cfg(windows)]
-                    Err(err) if err.kind() == AlreadyExists || err.kind() == PermissionDenied => {
+                    Err(err) if is_held_elsewhere(&err) => {
                         std::thread::sleep(wait);
                         continue;
                     }
-                    #[cfg(not(windows))]
-                    Err(err) if err.kind() == AlreadyExists => {
-                        std::thread::sleep(wait);
+                    Err(err) => return Err(Error::from(err)),
+                }
+            }
+            try_lock(&lock_path, directory, cleanup)
+        }
+    }
+    .map(|v| (lock_path, v))
+    .map_err(|err| into_error(err, resource, mode, attempts))
+}
+
+async fn lock_with_mode_async<T, Sleep, Fut>(
+    resource: &Path,
+    mode: Fail,
+    boundary_directory: Option<PathBuf>,
+    mut sleep: Sleep,
+    try_lock: &(dyn Fn(&Path, ContainingDirectory, AutoRemove) -> std::io::Result<T> + Sync),
+) -> Result<(PathBuf, T), Error>
+where
+    Sleep: FnMut(Duration) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let (directory, cleanup) = dir_cleanup(boundary_directory);
+    let lock_path = add_lock_suffix(resource);
+    let mut attempts = 1;
+    match mode.backoff() {
+        None => try_lock(&lock_path, directory, cleanup),
+        Some(backoff) => {
+            for wait in backoff.steps().until_no_remaining(backoff.max_total_wait) {
+                attempts += 1;
+                match try_lock(&lock_path, directory, cleanup.clone()) {
+                    Ok(v) => return Ok((lock_path, v)),
+                    Err(err) if is_held_elsewhere(&err) => {
+                        sleep(wait).await;
                         continue;
                     }
                     Err(err) => return Err(Error::from(err)),
@@ -197,14 +352,30 @@ fn lock_with_mode<T>(
         }
     }
     .map(|v| (lock_path, v))
-    .map_err(|err| match err.kind() {
-        AlreadyExists => Error::PermanentlyLocked {
+    .map_err(|err| into_error(err, resource, mode, attempts))
+}
+
+fn is_held_elsewhere(err: &std::io::Error) -> bool {
+    use std::io::ErrorKind::*;
+    #[cfg(windows)]
+    {
+        err.kind() == AlreadyExists || err.kind() == PermissionDenied
+    }
+    #[cfg(not(windows))]
+    {
+        err.kind() == AlreadyExists
+    }
+}
+
+fn into_error(err: std::io::Error, resource: &Path, mode: Fail, attempts: usize) -> Error {
+    match err.kind() {
+        std::io::ErrorKind::AlreadyExists => Error::PermanentlyLocked {
             resource_path: resource.into(),
             mode,
             attempts,
         },
         _ => Error::Io(err),
-    })
+    }
 }
 
 fn add_lock_suffix(resource_path: &Path) -> PathBuf {
@@ -1,7 +1,7 @@
 mod acquire {
     use std::time::{Duration, Instant};
 
-    use gix_lock::acquire::Fail;
+    use gix_lock::acquire::{Backoff, Fail};
 
     #[test]
     fn fail_mode_immediately_produces_a_descriptive_error() -> crate::Result {
@@ -41,6 +41,62 @@ mod acquire {
         assert!(err_str.contains("the-resource.lock"), "it mentions the lockfile itself");
         Ok(())
     }
+
+    #[test]
+    fn fail_mode_after_custom_backoff_respects_the_total_wait_time() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("the-resource");
+        let _guard = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)?;
+        let start = Instant::now();
+        let backoff = Backoff::new(Duration::from_millis(50))
+            .with_max_step(Duration::from_millis(5))
+            .with_jitter_percent(0);
+        let err = gix_lock::Marker::acquire_to_hold_resource(resource, backoff.into(), None)
+            .expect_err("the lock is taken and can't be obtained");
+        assert!(start.elapsed() >= backoff.max_total_wait);
+        match err {
+            gix_lock::acquire::Error::PermanentlyLocked { attempts, .. } => assert!(
+                attempts > 10,
+                "small steps lead to more attempts than with the default curve, got {attempts}"
+            ),
+            err => unreachable!("unexpected error: {err}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn async_acquisition_uses_the_provided_sleep_function() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("the-resource");
+        let guard = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)?;
+
+        let mut sleeps = Vec::new();
+        let err = futures_lite::future::block_on(gix_lock::Marker::acquire_to_hold_resource_async(
+            &resource,
+            Fail::AfterDurationWithBackoff(Duration::from_millis(20)),
+            None,
+            |wait| {
+                sleeps.push(wait);
+                futures_lite::future::ready(())
+            },
+        ))
+        .expect_err("the lock is still held");
+        assert!(err.to_string().contains("could not be obtained after 0.02s"));
+        assert!(
+            sleeps.iter().sum::<Duration>() >= Duration::from_millis(20),
+            "we 'slept' through the provided function only"
+        );
+
+        drop(guard);
+        let file = futures_lite::future::block_on(gix_lock::File::acquire_to_update_resource_async(
+            &resource,
+            Fail::Immediately,
+            None,
+            |_| async { unreachable!("no need to wait") },
+        ))?;
+        assert!(file.lock_path().is_file());
+        Ok(())
+    }
 }
 mod commit {
     use gix_lock::acquire::Fail;
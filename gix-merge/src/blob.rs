@@ -0,0 +1,240 @@
+//! Merge the content of blobs line by line, similar to what `git merge-file` does.
+use std::ops::Range;
+
+use bstr::BStr;
+use imara_diff::{
+    intern::{Interner, Token},
+    Algorithm,
+};
+
+/// Determine how conflicts are presented in the merged result.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictStyle {
+    /// Show our and their version of the conflicting lines, similar to `merge.conflictStyle=merge`.
+    ///
+    /// Lines that both sides have in common at the beginning or end of a conflict are moved out of it.
+    #[default]
+    Merge,
+    /// Show our and their version of the conflicting lines along with the lines of the common ancestor,
+    /// similar to `merge.conflictStyle=diff3`.
+    Diff3,
+}
+
+/// Options for use in [`merge()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// How to present conflicts.
+    pub conflict_style: ConflictStyle,
+    /// The amount of characters to use for conflict markers, like `<<<<<<<` when set to 7, which is the default.
+    pub marker_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            conflict_style: Default::default(),
+            marker_size: 7,
+        }
+    }
+}
+
+/// The names to display next to the conflict markers, typically the names of branches or files.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Labels<'a> {
+    /// The name of the common ancestor, shown after `|||||||` in the [`Diff3`](ConflictStyle::Diff3) style.
+    pub ancestor: Option<&'a BStr>,
+    /// The name of our side, shown after `<<<<<<<`.
+    pub current: Option<&'a BStr>,
+    /// The name of their side, shown after `>>>>>>>`.
+    pub other: Option<&'a BStr>,
+}
+
+/// The way a [`merge()`] was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// All changes could be merged without conflict.
+    Complete,
+    /// At least one conflict was encountered, which was marked in the output.
+    Conflict,
+}
+
+/// Merge the lines of `ours` and `theirs` which have `base` as common ancestor into `out`, and return whether or not there
+/// were conflicts. Conflicts are written to `out` enclosed by markers, with `labels` written next to them as configured in `options`.
+///
+/// Changes made by both sides to the same or adjacent lines conflict unless both sides made the same change.
+/// If any of the inputs [is binary](is_binary()), they aren't merged, and `ours` is written to `out` instead, along with
+/// a conflict being reported.
+pub fn merge(
+    base: &[u8],
+    ours: &[u8],
+    theirs: &[u8],
+    labels: Labels<'_>,
+    options: Options,
+    out: &mut Vec<u8>,
+) -> Resolution {
+    if is_binary(base) || is_binary(ours) || is_binary(theirs) {
+        out.extend_from_slice(ours);
+        return Resolution::Conflict;
+    }
+
+    let mut interner = Interner::new((base.len() + ours.len() + theirs.len()) / 32);
+    let base = tokenize(base, &mut interner);
+    let ours = tokenize(ours, &mut interner);
+    let theirs = tokenize(theirs, &mut interner);
+    let ours_hunks = hunks(&base, &ours, interner.num_tokens());
+    let theirs_hunks = hunks(&base, &theirs, interner.num_tokens());
+
+    let mut out = Output {
+        interner: &interner,
+        out,
+        marker_size: options.marker_size,
+    };
+    let mut resolution = Resolution::Complete;
+    let (mut ours_idx, mut theirs_idx) = (0, 0);
+    let (mut ours_delta, mut theirs_delta) = (0, 0);
+    let mut base_pos = 0;
+    loop {
+        let start = match (ours_hunks.get(ours_idx), theirs_hunks.get(theirs_idx)) {
+            (None, None) => break,
+            (Some(ours), Some(theirs)) => ours.base.start.min(theirs.base.start),
+            (Some(hunk), None) | (None, Some(hunk)) => hunk.base.start,
+        };
+
+        // Collect all hunks of both sides that overlap or touch each other.
+        let mut group = start..start;
+        let (ours_first, theirs_first) = (ours_idx, theirs_idx);
+        loop {
+            let mut extended = false;
+            for (hunks, idx) in [(&ours_hunks, &mut ours_idx), (&theirs_hunks, &mut theirs_idx)] {
+                if let Some(hunk) = hunks.get(*idx).filter(|hunk| hunk.base.start <= group.end) {
+                    group.end = group.end.max(hunk.base.end);
+                    *idx += 1;
+                    extended = true;
+                }
+            }
+            if !extended {
+                break;
+            }
+        }
+
+        out.lines(&base[base_pos as usize..group.start as usize]);
+        let ours_range = side_range(&group, &ours_hunks[ours_first..ours_idx], &mut ours_delta);
+        let theirs_range = side_range(&group, &theirs_hunks[theirs_first..theirs_idx], &mut theirs_delta);
+        let (ours_lines, theirs_lines) = (&ours[ours_range], &theirs[theirs_range]);
+        let (ours_changed, theirs_changed) = (ours_idx > ours_first, theirs_idx > theirs_first);
+        if !theirs_changed || ours_lines == theirs_lines {
+            out.lines(ours_lines);
+        } else if !ours_changed {
+            out.lines(theirs_lines);
+        } else {
+            resolution = Resolution::Conflict;
+            match options.conflict_style {
+                ConflictStyle::Merge => {
+                    let common_prefix = ours_lines.iter().zip(theirs_lines).take_while(|(a, b)| a == b).count();
+                    out.lines(&ours_lines[..common_prefix]);
+                    let (ours_lines, theirs_lines) = (&ours_lines[common_prefix..], &theirs_lines[common_prefix..]);
+                    let common_suffix = ours_lines
+                        .iter()
+                        .rev()
+                        .zip(theirs_lines.iter().rev())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    out.conflict(
+                        &ours_lines[..ours_lines.len() - common_suffix],
+                        None,
+                        &theirs_lines[..theirs_lines.len() - common_suffix],
+                        labels,
+                    );
+                    out.lines(&ours_lines[ours_lines.len() - common_suffix..]);
+                }
+                ConflictStyle::Diff3 => {
+                    let base_lines = &base[group.start as usize..group.end as usize];
+                    out.conflict(ours_lines, Some(base_lines), theirs_lines, labels);
+                }
+            }
+        }
+        base_pos = group.end;
+    }
+    out.lines(&base[base_pos as usize..]);
+    resolution
+}
+
+/// Return `true` if `data` is considered binary, which is the case if there is a null-byte in the first 8000 bytes,
+/// just like `git` does it.
+pub fn is_binary(data: &[u8]) -> bool {
+    data.iter().take(8000).any(|b| *b == 0)
+}
+
+/// A change of `side` compared to `base`, with both ranges being line-numbers.
+struct Hunk {
+    base: Range<u32>,
+    side: Range<u32>,
+}
+
+fn tokenize<'a>(data: &'a [u8], interner: &mut Interner<&'a [u8]>) -> Vec<Token> {
+    imara_diff::sources::byte_lines_with_terminator(data)
+        .map(|line| interner.intern(line))
+        .collect()
+}
+
+fn hunks(base: &[Token], side: &[Token], num_tokens: u32) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    imara_diff::diff_with_tokens(
+        Algorithm::Histogram,
+        base,
+        side,
+        num_tokens,
+        |base: Range<u32>, side: Range<u32>| hunks.push(Hunk { base, side }),
+    );
+    hunks
+}
+
+/// Return the lines of a side that correspond to the lines of `group` in the base, after applying `hunks` that fall into it.
+/// `delta` is the difference in line numbers between the side and the base just before `group`, and it is updated to the
+/// difference just after it.
+fn side_range(group: &Range<u32>, hunks: &[Hunk], delta: &mut i64) -> Range<usize> {
+    let start = group.start as i64 + *delta;
+    for hunk in hunks {
+        *delta += hunk.side.len() as i64 - hunk.base.len() as i64;
+    }
+    let end = group.end as i64 + *delta;
+    start as usize..end as usize
+}
+
+struct Output<'a, 'data> {
+    interner: &'a Interner<&'data [u8]>,
+    out: &'a mut Vec<u8>,
+    marker_size: usize,
+}
+
+impl Output<'_, '_> {
+    fn lines(&mut self, lines: &[Token]) {
+        for token in lines {
+            self.out.extend_from_slice(self.interner[*token]);
+        }
+    }
+
+    fn conflict(&mut self, ours: &[Token], base: Option<&[Token]>, theirs: &[Token], labels: Labels<'_>) {
+        self.marker(b'<', labels.current);
+        self.lines(ours);
+        if let Some(base) = base {
+            self.marker(b'|', labels.ancestor);
+            self.lines(base);
+        }
+        self.marker(b'=', None);
+        self.lines(theirs);
+        self.marker(b'>', labels.other);
+    }
+
+    fn marker(&mut self, marker: u8, label: Option<&BStr>) {
+        if matches!(self.out.last(), Some(b) if *b != b'\n') {
+            self.out.push(b'\n');
+        }
+        self.out.extend(std::iter::repeat(marker).take(self.marker_size));
+        if let Some(label) = label {
+            self.out.push(b' ');
+            self.out.extend_from_slice(label);
+        }
+        self.out.push(b'\n');
+    }
+}
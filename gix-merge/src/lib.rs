@@ -0,0 +1,9 @@
+//! Provide algorithms to merge blobs line by line and trees into an index, without the need for a worktree.
+//!
+//! Use [`tree::merge()`] to merge trees, which is what servers need to test whether or not two branches can be merged,
+//! and [`blob::merge()`] to merge the content of files.
+#![deny(missing_docs, rust_2018_idioms)]
+#![forbid(unsafe_code)]
+
+pub mod blob;
+pub mod tree;
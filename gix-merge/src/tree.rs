@@ -0,0 +1,358 @@
+//! Merge trees into an index, similar to what `git merge-tree --write-tree` does, but without writing a tree.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_features::progress::{Count, DynNestedProgress, Progress};
+use gix_hash::ObjectId;
+use gix_index::entry::{Flags, Mode, Stat};
+use gix_object::FindExt;
+
+use crate::blob;
+
+/// The error returned by [`merge()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read the entries of tree {id}")]
+    IndexFromTree {
+        id: ObjectId,
+        source: gix_index::init::from_tree::Error,
+    },
+    #[error(transparent)]
+    FindBlob(#[from] gix_object::find::existing_object::Error),
+    #[error(transparent)]
+    WriteBlob(#[from] gix_odb::write::Error),
+    #[error("The merge was interrupted")]
+    Interrupted,
+}
+
+/// The progress ids used in [`merge()`].
+///
+/// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+#[derive(Debug, Copy, Clone)]
+pub enum ProgressId {
+    /// The amount of paths that were merged so far, out of all paths of the three trees.
+    MergedPaths,
+    /// The amount of files whose content was changed by both sides and had to be merged line by line.
+    MergedBlobs,
+}
+
+impl From<ProgressId> for gix_features::progress::Id {
+    fn from(v: ProgressId) -> Self {
+        match v {
+            ProgressId::MergedPaths => *b"MTMP",
+            ProgressId::MergedBlobs => *b"MTMB",
+        }
+    }
+}
+
+/// Options for use in [`merge()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// Options for merging the content of files that were changed on both sides.
+    pub blob: blob::Options,
+    /// Options to validate the path components of all tree entries with.
+    pub validate: gix_validate::path::component::Options,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod conflict {
+    /// The side of a merge.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Side {
+        /// Our side, whose entries are kept in stage 2.
+        Ours,
+        /// Their side, whose entries are kept in stage 3.
+        Theirs,
+    }
+
+    /// The kind of [conflict](super::Conflict).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Kind {
+        /// Both sides changed the same lines of a file, or at least one of the versions is binary.
+        Content,
+        /// Both sides changed the executable bit of a file in different ways, for instance by adding the file with different modes.
+        Mode,
+        /// Both sides changed an entry in different ways but at least one of them isn't a file, like a symbolic link or a submodule.
+        Unmergeable,
+        /// One side modified an entry that was deleted by the other side.
+        ModifyDelete {
+            /// The side that deleted the entry.
+            deleted_by: Side,
+        },
+        /// One side has a file where the other side has a directory.
+        ///
+        /// The directory is kept, and the file is placed into the stage of its side.
+        FileDirectory {
+            /// The side that has the file.
+            file_by: Side,
+        },
+    }
+}
+
+/// A path that could not be merged automatically, and whose versions are kept in stage 1 for the common ancestor, stage 2
+/// for our side and stage 3 for their side, as far as they exist.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The path of the conflicting entry.
+    pub path: BString,
+    /// The kind of conflict.
+    pub kind: conflict::Kind,
+    /// The id of the blob which contains the merged content of both sides along with conflict markers,
+    /// if this is a [content conflict](conflict::Kind::Content).
+    ///
+    /// If the content was considered binary, it's the id of our version instead.
+    pub merged_blob: Option<ObjectId>,
+}
+
+/// The outcome of [`merge()`].
+#[derive(Clone)]
+pub struct Outcome {
+    /// The index with all merged entries in stage 0, along with the entries of each [conflict](Self::conflicts) in their respective stages.
+    ///
+    /// Note that entries don't have any stat information, and the index has no extensions.
+    pub index: gix_index::State,
+    /// All conflicts, sorted by path.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Outcome {
+    /// Return `true` if at least one path couldn't be merged automatically.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Merge the trees `ours` and `theirs` which have the tree `base` as common ancestor, and return an index with the result.
+///
+/// `objects` are used to read all trees and blobs, and merged blobs are written to it, so an in-memory object database
+/// can be used to avoid persisting any objects. `labels` are shown next to conflict markers, and `options` are used to
+/// further configure the merge.
+///
+/// Each path is merged individually: changes of only one side are taken as they are, and files changed by both sides
+/// are merged line by line. Paths that can't be merged this way are recorded as [`Conflict`], with their versions placed
+/// in stages 1, 2 and 3 of the index, which is what `git` needs to resolve them later, while the worktree isn't touched at all.
+/// Note that renames are not detected.
+///
+/// `progress` is informed about the amount of merged paths and blobs, see [`ProgressId`], and the merge stops with
+/// [`Error::Interrupted`] once `should_interrupt` is `true`, which allows to bound the time spent on very large merges.
+#[allow(clippy::too_many_arguments)]
+pub fn merge(
+    base: &gix_hash::oid,
+    ours: &gix_hash::oid,
+    theirs: &gix_hash::oid,
+    objects: &(impl gix_object::Find + gix_odb::Write),
+    labels: blob::Labels<'_>,
+    options: Options,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Outcome, Error> {
+    let _span = gix_trace::coarse!("gix_merge::tree::merge()");
+    let flatten = |tree: &gix_hash::oid| {
+        gix_index::State::from_tree(tree, objects, options.validate).map_err(|err| Error::IndexFromTree {
+            id: tree.to_owned(),
+            source: err,
+        })
+    };
+    let trees = [flatten(base)?, flatten(ours)?, flatten(theirs)?];
+    if should_interrupt.load(Ordering::Relaxed) {
+        return Err(Error::Interrupted);
+    }
+    let mut versions_by_path = BTreeMap::<&BStr, [Option<Version>; 3]>::new();
+    for (side, tree) in trees.iter().enumerate() {
+        for entry in tree.entries() {
+            versions_by_path.entry(entry.path(tree)).or_default()[side] = Some(Version {
+                mode: entry.mode,
+                id: entry.id,
+            });
+        }
+    }
+
+    let mut index = gix_index::State::new(base.kind());
+    let mut conflicts = Vec::new();
+    let mut merged = Vec::new();
+    let mut bufs = Buffers::default();
+    let mut paths = progress.add_child_with_id("merge paths".into(), ProgressId::MergedPaths.into());
+    paths.init(Some(versions_by_path.len()), gix_features::progress::count("paths"));
+    let mut blobs = progress.add_child_with_id("merge blobs".into(), ProgressId::MergedBlobs.into());
+    blobs.init(None, gix_features::progress::count("blobs"));
+    let merged_blobs = blobs.counter();
+    for (path, versions) in &versions_by_path {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        paths.inc();
+        let [base, ours, theirs] = *versions;
+        if ours == theirs {
+            merged.extend(ours.map(|ours| (*path, ours, None)));
+            continue;
+        } else if base == ours {
+            merged.extend(theirs.map(|theirs| (*path, theirs, Some(conflict::Side::Theirs))));
+            continue;
+        } else if base == theirs {
+            merged.extend(ours.map(|ours| (*path, ours, Some(conflict::Side::Ours))));
+            continue;
+        }
+
+        let (kind, merged_blob) = match (ours, theirs) {
+            (Some(ours), Some(theirs)) if ours.is_file() && theirs.is_file() => {
+                let (id, is_conflict) = merge_file_content(
+                    objects,
+                    base,
+                    ours,
+                    theirs,
+                    labels,
+                    options.blob,
+                    &mut bufs,
+                    &merged_blobs,
+                )?;
+                let mode = if ours.mode == theirs.mode {
+                    Some(ours.mode)
+                } else {
+                    match base.map(|base| base.mode) {
+                        Some(mode) if mode == ours.mode => Some(theirs.mode),
+                        Some(mode) if mode == theirs.mode => Some(ours.mode),
+                        _ => None,
+                    }
+                };
+                match mode {
+                    Some(mode) if !is_conflict => {
+                        merged.push((path, Version { mode, id }, None));
+                        continue;
+                    }
+                    _ if is_conflict => (conflict::Kind::Content, Some(id)),
+                    _ => (conflict::Kind::Mode, None),
+                }
+            }
+            (Some(_), Some(_)) => (conflict::Kind::Unmergeable, None),
+            (None, _) => (
+                conflict::Kind::ModifyDelete {
+                    deleted_by: conflict::Side::Ours,
+                },
+                None,
+            ),
+            (_, None) => (
+                conflict::Kind::ModifyDelete {
+                    deleted_by: conflict::Side::Theirs,
+                },
+                None,
+            ),
+        };
+        push_stages(&mut index, path, *versions);
+        conflicts.push(Conflict {
+            path: (*path).to_owned(),
+            kind,
+            merged_blob,
+        });
+    }
+
+    let directories: BTreeSet<&BStr> = merged
+        .iter()
+        .map(|(path, _, _)| *path)
+        .chain(conflicts.iter().map(|c| c.path.as_bstr()))
+        .flat_map(|path| {
+            std::iter::successors(Some(path), |path| {
+                path.rfind_byte(b'/').map(|pos| path[..pos].as_bstr())
+            })
+            .skip(1)
+        })
+        .collect();
+    let mut file_directory_conflicts = Vec::new();
+    for (path, version, side) in merged {
+        if directories.contains(path) {
+            let file_by = side.unwrap_or(conflict::Side::Ours);
+            let mut versions = [None; 3];
+            versions[match file_by {
+                conflict::Side::Ours => 1,
+                conflict::Side::Theirs => 2,
+            }] = Some(version);
+            push_stages(&mut index, path, versions);
+            file_directory_conflicts.push(Conflict {
+                path: path.to_owned(),
+                kind: conflict::Kind::FileDirectory { file_by },
+                merged_blob: None,
+            });
+        } else {
+            index.dangerously_push_entry(Stat::default(), version.id, Flags::empty(), version.mode, path);
+        }
+    }
+    conflicts.extend(file_directory_conflicts);
+    index.sort_entries();
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(Outcome { index, conflicts })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Version {
+    mode: Mode,
+    id: ObjectId,
+}
+
+impl Version {
+    fn is_file(&self) -> bool {
+        self.mode == Mode::FILE || self.mode == Mode::FILE_EXECUTABLE
+    }
+}
+
+#[derive(Default)]
+struct Buffers {
+    base: Vec<u8>,
+    ours: Vec<u8>,
+    theirs: Vec<u8>,
+    out: Vec<u8>,
+}
+
+/// Push all available `versions` of the entry at `path` to `index` into stage 1, 2 and 3 respectively.
+fn push_stages(index: &mut gix_index::State, path: &BStr, versions: [Option<Version>; 3]) {
+    for (stage, version) in (1u32..).zip(versions) {
+        if let Some(version) = version {
+            index.dangerously_push_entry(
+                Stat::default(),
+                version.id,
+                Flags::from_bits_retain(stage << 12),
+                version.mode,
+                path,
+            );
+        }
+    }
+}
+
+/// Merge the content of `ours` and `theirs` and return the id of the result, along with `true` if there were conflicts.
+/// The content of `base` is only used if it is a file, otherwise both sides are merged as if they were added.
+/// Increment `merged_blobs` if the content had to be merged line by line.
+#[allow(clippy::too_many_arguments)]
+fn merge_file_content(
+    objects: &(impl gix_object::Find + gix_odb::Write),
+    base: Option<Version>,
+    ours: Version,
+    theirs: Version,
+    labels: blob::Labels<'_>,
+    options: blob::Options,
+    bufs: &mut Buffers,
+    merged_blobs: &gix_features::progress::StepShared,
+) -> Result<(ObjectId, bool), Error> {
+    let base = base.filter(Version::is_file);
+    if ours.id == theirs.id {
+        return Ok((ours.id, false));
+    } else if base.map(|base| base.id) == Some(ours.id) {
+        return Ok((theirs.id, false));
+    } else if base.map(|base| base.id) == Some(theirs.id) {
+        return Ok((ours.id, false));
+    }
+
+    let base = match base {
+        Some(base) => objects.find_blob(&base.id, &mut bufs.base)?.data,
+        None => &[],
+    };
+    let ours = objects.find_blob(&ours.id, &mut bufs.ours)?.data;
+    let theirs = objects.find_blob(&theirs.id, &mut bufs.theirs)?.data;
+    bufs.out.clear();
+    let resolution = blob::merge(base, ours, theirs, labels, options, &mut bufs.out);
+    merged_blobs.fetch_add(1, Ordering::Relaxed);
+    let id = objects.write_buf(gix_object::Kind::Blob, &bufs.out)?;
+    Ok((id, resolution == blob::Resolution::Conflict))
+}
@@ -0,0 +1,101 @@
+use gix_merge::blob::{merge, ConflictStyle, Labels, Options, Resolution};
+
+fn labels() -> Labels<'static> {
+    Labels {
+        ancestor: Some("base".into()),
+        current: Some("ours".into()),
+        other: Some("theirs".into()),
+    }
+}
+
+fn merged(base: &str, ours: &str, theirs: &str, options: Options) -> (String, Resolution) {
+    let mut out = Vec::new();
+    let resolution = merge(
+        base.as_bytes(),
+        ours.as_bytes(),
+        theirs.as_bytes(),
+        labels(),
+        options,
+        &mut out,
+    );
+    (String::from_utf8(out).expect("valid UTF-8"), resolution)
+}
+
+#[test]
+fn changes_to_different_lines_are_merged() {
+    let (out, resolution) = merged(
+        "1\n2\n3\n4\n5\n6\n7\n8\n9\n",
+        "1\nours\n3\n4\n5\n6\n7\n8\n9\n",
+        "1\n2\n3\n4\n5\n6\n7\ntheirs\n9\n",
+        Options::default(),
+    );
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, "1\nours\n3\n4\n5\n6\n7\ntheirs\n9\n");
+}
+
+#[test]
+fn identical_changes_are_merged() {
+    let (out, resolution) = merged("1\n2\n3\n", "1\nsame\n3\n", "1\nsame\n3\n", Options::default());
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, "1\nsame\n3\n");
+
+    let (out, resolution) = merged("1\n2\n3\n", "1\n3\n", "1\n2\n3\n", Options::default());
+    assert_eq!(resolution, Resolution::Complete);
+    assert_eq!(out, "1\n3\n", "a deletion of one side is taken as well");
+}
+
+#[test]
+fn conflicts_in_merge_style_exclude_common_lines() {
+    let (out, resolution) = merged(
+        "1\n2\n3\n4\n5\n",
+        "1\nX\nours\nY\n4\n5\n",
+        "1\nX\ntheirs\nY\n4\n5\n",
+        Options::default(),
+    );
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(
+        out, "1\nX\n<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\nY\n4\n5\n",
+        "this is what `git merge-file` produces"
+    );
+}
+
+#[test]
+fn conflicts_in_diff3_style_show_the_base() {
+    let (out, resolution) = merged(
+        "1\n2\n3\n4\n5\n",
+        "1\nX\nours\nY\n4\n5\n",
+        "1\nX\ntheirs\nY\n4\n5\n",
+        Options {
+            conflict_style: ConflictStyle::Diff3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(
+        out, "1\n<<<<<<< ours\nX\nours\nY\n||||||| base\n2\n3\n=======\nX\ntheirs\nY\n>>>>>>> theirs\n4\n5\n",
+        "this is what `git merge-file --diff3` produces"
+    );
+}
+
+#[test]
+fn conflict_markers_are_placed_on_their_own_line_and_can_be_resized() {
+    let (out, resolution) = merged(
+        "1\n2",
+        "1\nours",
+        "1\ntheirs",
+        Options {
+            marker_size: 3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(out, "1\n<<< ours\nours\n===\ntheirs\n>>> theirs\n");
+}
+
+#[test]
+fn binary_content_is_not_merged() {
+    let mut out = Vec::new();
+    let resolution = merge(b"a\0", b"ours\0", b"theirs\0", labels(), Options::default(), &mut out);
+    assert_eq!(resolution, Resolution::Conflict);
+    assert_eq!(out, b"ours\0", "our version is chosen in case of conflict");
+}
@@ -0,0 +1,2 @@
+mod blob;
+mod tree;
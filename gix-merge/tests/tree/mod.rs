@@ -0,0 +1,87 @@
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
+
+use gix_features::progress::{self, events};
+use gix_merge::tree::{merge, Error, Options, ProgressId};
+use gix_object::tree::{Entry, EntryKind};
+use gix_odb::Write;
+
+type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+fn write_tree(odb: &gix_odb::Handle, files: &[(&str, &str)]) -> Result<gix_hash::ObjectId> {
+    let mut entries = Vec::new();
+    for (name, content) in files {
+        entries.push(Entry {
+            mode: EntryKind::Blob.into(),
+            filename: (*name).into(),
+            oid: odb.write_buf(gix_object::Kind::Blob, content.as_bytes())?,
+        });
+    }
+    entries.sort();
+    odb.write(&gix_object::Tree { entries })
+}
+
+fn trees(odb: &gix_odb::Handle) -> Result<[gix_hash::ObjectId; 3]> {
+    Ok([
+        write_tree(odb, &[("a", "1\n2\n3\n"), ("b", "b\n"), ("c", "c\n")])?,
+        write_tree(odb, &[("a", "one\n2\n3\n"), ("b", "ours\n"), ("c", "c\n")])?,
+        write_tree(odb, &[("a", "1\n2\nthree\n"), ("b", "b\n"), ("c", "c\n"), ("d", "d\n")])?,
+    ])
+}
+
+#[test]
+fn progress_counts_merged_paths_and_blobs() -> Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let odb = gix_odb::at(tmp.path())?;
+    let [base, ours, theirs] = trees(&odb)?;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut progress = progress::Events::new("merge", {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    });
+    let outcome = merge(
+        &base,
+        &ours,
+        &theirs,
+        &odb,
+        Default::default(),
+        Options::default(),
+        &mut progress,
+        &AtomicBool::default(),
+    )?;
+    assert!(!outcome.has_conflicts(), "changes to different lines are merged");
+
+    let final_step = |id: ProgressId| {
+        let id: progress::Id = id.into();
+        events.lock().unwrap().iter().find_map(|event| match event.kind {
+            events::Kind::Done { step } if event.id == id => Some(step),
+            _ => None,
+        })
+    };
+    assert_eq!(final_step(ProgressId::MergedPaths), Some(4), "all paths of all trees");
+    assert_eq!(
+        final_step(ProgressId::MergedBlobs),
+        Some(1),
+        "only 'a' was changed by both sides"
+    );
+    Ok(())
+}
+
+#[test]
+fn interruption_stops_the_merge() -> Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let odb = gix_odb::at(tmp.path())?;
+    let [base, ours, theirs] = trees(&odb)?;
+    let res = merge(
+        &base,
+        &ours,
+        &theirs,
+        &odb,
+        Default::default(),
+        Options::default(),
+        &mut progress::Discard,
+        &AtomicBool::new(true),
+    );
+    assert!(matches!(res, Err(Error::Interrupted)));
+    Ok(())
+}
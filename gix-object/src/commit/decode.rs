@@ -43,9 +43,9 @@ pub fn commit<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
             .context(StrContext::Expected(
                 "zero or more 'parent <40 lowercase hex char>'".into(),
             )),
-        (|i: &mut _| parse::header_field(i, b"author", parse::signature))
+        (|i: &mut _| parse::header_field(i, b"author", parse::lenient_signature))
             .context(StrContext::Expected("author <signature>".into())),
-        (|i: &mut _| parse::header_field(i, b"committer", parse::signature))
+        (|i: &mut _| parse::header_field(i, b"committer", parse::lenient_signature))
             .context(StrContext::Expected("committer <signature>".into())),
         opt(|i: &mut _| parse::header_field(i, b"encoding", take_till(1.., NL)))
             .context(StrContext::Expected("encoding <encoding>".into())),
@@ -63,11 +63,21 @@ pub fn commit<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
         terminated(message, eof),
     )
         .map(
-            |(tree, parents, author, committer, encoding, extra_headers, message)| CommitRef {
+            |(
+                tree,
+                parents,
+                (author, malformed_author),
+                (committer, malformed_committer),
+                encoding,
+                extra_headers,
+                message,
+            )| CommitRef {
                 tree,
                 parents: SmallVec::from(parents),
                 author,
                 committer,
+                malformed_author,
+                malformed_committer,
                 encoding: encoding.map(ByteSlice::as_bstr),
                 message,
                 extra_headers,
@@ -10,6 +10,9 @@ use crate::{
 #[allow(clippy::empty_docs)]
 pub mod body;
 mod decode;
+///
+#[allow(clippy::empty_docs)]
+pub mod trailers;
 
 impl<'a> CommitRef<'a> {
     /// Return exactly the same message as [`MessageRef::summary()`].
@@ -50,6 +53,14 @@ impl<'a> MessageRef<'a> {
     pub fn body(&self) -> Option<BodyRef<'a>> {
         self.body.map(|b| BodyRef::from_bytes(b))
     }
+
+    /// Return an iterator over the trailers in the last paragraph of the body, following the rules of `git interpret-trailers`.
+    ///
+    /// In contrast to [`BodyRef::trailers()`], the paragraph is only considered to contain trailers if most of its lines
+    /// are trailers, and values spanning multiple lines are supported.
+    pub fn trailers(&self) -> trailers::Iter<'a> {
+        trailers::parse_body(self.body.map_or(&[][..], |body| body.as_bytes()))
+    }
 }
 
 pub(crate) fn summary(message: &BStr) -> Cow<'_, BStr> {
@@ -0,0 +1,318 @@
+//! Read and edit the trailers at the end of commit messages, like `Signed-off-by: Name <email>`,
+//! following the rules of [git-interpret-trailers](https://git-scm.com/docs/git-interpret-trailers).
+use std::{borrow::Cow, ops::Range};
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// A trailer as parsed from the trailer block of a commit message.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub struct Trailer<'a> {
+    /// The name of the trailer, like `Signed-off-by`, without the separator.
+    pub token: &'a BStr,
+    /// The value after the separator with surrounding whitespace trimmed.
+    ///
+    /// Values spanning multiple lines are unfolded into a single line, with lines separated by a single space.
+    pub value: Cow<'a, BStr>,
+}
+
+/// Where to place trailers that are added with [`add()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// Add new trailers after all existing ones.
+    #[default]
+    End,
+    /// Add new trailers before all existing ones.
+    Start,
+}
+
+/// What to do if a trailer with the same token already exists when adding one with [`add()`].
+///
+/// Tokens are compared case-insensitively, as are values.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IfExists {
+    /// Add the trailer unless the trailer it would be placed next to has the same token and value.
+    #[default]
+    AddIfDifferentNeighbor,
+    /// Add the trailer unless any trailer has the same token and value.
+    AddIfDifferent,
+    /// Add the trailer, even if it already exists.
+    Add,
+    /// Remove the existing trailer with the same token that is closest to where it would be placed, and add the new one
+    /// according to its [placement](Placement).
+    Replace,
+    /// Don't add the trailer.
+    DoNothing,
+}
+
+/// What to do if no trailer with the same token exists when adding one with [`add()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IfMissing {
+    /// Add the trailer.
+    #[default]
+    Add,
+    /// Don't add the trailer.
+    DoNothing,
+}
+
+/// Options for use in [`add()`] and [`normalize()`], with defaults matching those of `git interpret-trailers`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Where to place new trailers.
+    pub placement: Placement,
+    /// What to do if a trailer with the same token exists.
+    pub if_exists: IfExists,
+    /// What to do if no trailer with the same token exists.
+    pub if_missing: IfMissing,
+    /// If `true`, remove all trailers with empty values, like `git interpret-trailers --trim-empty`.
+    pub trim_empty: bool,
+}
+
+/// An iterator over the [trailers](Trailer) of a commit message, as returned by [`parse()`].
+pub struct Iter<'a> {
+    lines: bstr::LinesWithTerminator<'a>,
+    peeked: Option<&'a [u8]>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Trailer<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.peeked.take().or_else(|| self.lines.next())?;
+            let Some((token, value)) = parse_line(line) else {
+                continue;
+            };
+            let mut value = Cow::Borrowed(value);
+            for line in self.lines.by_ref() {
+                if !is_continuation(line) {
+                    self.peeked = Some(line);
+                    break;
+                }
+                let value = value.to_mut();
+                if !value.is_empty() {
+                    value.push_byte(b' ');
+                }
+                value.push_str(line.trim());
+            }
+            return Some(Trailer { token, value });
+        }
+    }
+}
+
+/// Return an iterator over the trailers in the last paragraph of `message`, which must contain only trailers,
+/// or at least 25% trailers along with one that looks like it was generated by `git`.
+///
+/// The first paragraph is the commit title and never contains trailers.
+pub fn parse(message: &[u8]) -> Iter<'_> {
+    let block = block(message, false).map_or(&[][..], |range| &message[range]);
+    Iter {
+        lines: block.lines_with_terminator(),
+        peeked: None,
+    }
+}
+
+/// Like [`parse()`], but assumes that `body` is the part of the commit message following its title.
+pub(crate) fn parse_body(body: &[u8]) -> Iter<'_> {
+    let block = block(body, true).map_or(&[][..], |range| &body[range]);
+    Iter {
+        lines: block.lines_with_terminator(),
+        peeked: None,
+    }
+}
+
+/// Add `trailers` made of tokens and values to `message` according to `options`, and return the edited message.
+///
+/// Note that existing trailers are [normalized](normalize()), and that a trailer block is created if there is none yet.
+/// Tokens of new trailers may end with `:`, which is ignored.
+pub fn add<'b>(message: &[u8], trailers: impl IntoIterator<Item = (&'b BStr, &'b BStr)>, options: Options) -> BString {
+    let (head, mut items, had_block) = match block(message, false) {
+        Some(range) => (&message[..range.start], parse_items(&message[range]), true),
+        None => (message.trim_end(), Vec::new(), false),
+    };
+
+    for (token, value) in trailers {
+        let token = token.trim().trim_end_with(|c| c == ':').as_bstr();
+        let value = value.trim().as_bstr();
+        let same = |item: &Item| item.has_token(token) && item.has_value(value);
+        let insert_at = match options.placement {
+            Placement::End => items.len(),
+            Placement::Start => 0,
+        };
+        let closest_with_same_token = match options.placement {
+            Placement::End => items.iter().rposition(|item| item.has_token(token)),
+            Placement::Start => items.iter().position(|item| item.has_token(token)),
+        };
+        let new = Item::Trailer {
+            token: token.to_owned(),
+            value: value.to_owned(),
+        };
+        match closest_with_same_token {
+            None => match options.if_missing {
+                IfMissing::Add => items.insert(insert_at, new),
+                IfMissing::DoNothing => {}
+            },
+            Some(pos) => match options.if_exists {
+                IfExists::AddIfDifferentNeighbor => {
+                    let neighbor = match options.placement {
+                        Placement::End => items.last(),
+                        Placement::Start => items.first(),
+                    };
+                    if !matches!(neighbor, Some(item) if same(item)) {
+                        items.insert(insert_at, new);
+                    }
+                }
+                IfExists::AddIfDifferent => {
+                    if !items.iter().any(same) {
+                        items.insert(insert_at, new);
+                    }
+                }
+                IfExists::Add => items.insert(insert_at, new),
+                IfExists::Replace => {
+                    items.remove(pos);
+                    let insert_at = match options.placement {
+                        Placement::End => items.len(),
+                        Placement::Start => 0,
+                    };
+                    items.insert(insert_at, new);
+                }
+                IfExists::DoNothing => {}
+            },
+        }
+    }
+
+    if options.trim_empty {
+        items.retain(|item| !matches!(item, Item::Trailer { value, .. } if value.is_empty()));
+    }
+
+    if items.is_empty() {
+        if !had_block {
+            return message.into();
+        }
+        let mut out = BString::from(head.trim_end());
+        out.push_byte(b'\n');
+        return out;
+    }
+    let mut out = BString::from(head);
+    if !had_block && !head.is_empty() {
+        out.push_str("\n\n");
+    }
+    for item in items {
+        match item {
+            Item::Trailer { token, value } => {
+                out.push_str(token);
+                out.push_str(b": ");
+                out.push_str(value);
+            }
+            Item::Other(line) => out.push_str(line),
+        }
+        out.push_byte(b'\n');
+    }
+    out
+}
+
+/// Rewrite the trailers of `message` so each is written as `<token>: <value>` on a single line, and return the edited message.
+///
+/// Lines in the trailer block that aren't trailers are kept, and trailers with empty values are removed
+/// if [`Options::trim_empty`] is set.
+pub fn normalize(message: &[u8], options: Options) -> BString {
+    add(message, None, options)
+}
+
+/// A line in the trailer block.
+enum Item {
+    Trailer { token: BString, value: BString },
+    Other(BString),
+}
+
+impl Item {
+    fn has_token(&self, other: &BStr) -> bool {
+        matches!(self, Item::Trailer { token, .. } if token.eq_ignore_ascii_case(other))
+    }
+
+    fn has_value(&self, other: &BStr) -> bool {
+        matches!(self, Item::Trailer { value, .. } if value.eq_ignore_ascii_case(other))
+    }
+}
+
+fn parse_items(block: &[u8]) -> Vec<Item> {
+    let mut items = Vec::new();
+    for line in block.lines_with_terminator() {
+        if is_continuation(line) {
+            if let Some(Item::Trailer { value, .. }) = items.last_mut() {
+                if !value.is_empty() {
+                    value.push_byte(b' ');
+                }
+                value.push_str(line.trim());
+                continue;
+            }
+        }
+        items.push(match parse_line(line) {
+            Some((token, value)) => Item::Trailer {
+                token: token.to_owned(),
+                value: value.to_owned(),
+            },
+            None => Item::Other(line.trim_end().as_bstr().to_owned()),
+        });
+    }
+    items
+}
+
+/// Prefixes of lines that `git` generates itself, which make a paragraph a trailer block more easily.
+const GIT_GENERATED_PREFIXES: &[&[u8]] = &[b"Signed-off-by: ", b"(cherry picked from commit "];
+
+/// Return the range of the trailer block in `message`, without trailing empty lines.
+///
+/// If `first_paragraph_may_be_block` is `false`, the first paragraph is treated as title.
+fn block(message: &[u8], first_paragraph_may_be_block: bool) -> Option<Range<usize>> {
+    let (mut start, mut end, mut offset, mut after_empty_line) = (None, 0, 0, None);
+    for line in message.lines_with_terminator() {
+        offset += line.len();
+        if line.trim().is_empty() {
+            if end != 0 {
+                after_empty_line = Some(offset);
+            }
+        } else {
+            if let Some(paragraph_start) = after_empty_line.take() {
+                start = Some(paragraph_start);
+            }
+            end = offset;
+        }
+    }
+    let start = match start {
+        Some(start) => start,
+        None if first_paragraph_may_be_block => 0,
+        None => return None,
+    };
+
+    let (mut trailers, mut non_trailers, mut git_generated) = (0, 0, false);
+    let mut in_trailer = false;
+    for line in message[start..end].lines_with_terminator() {
+        if line.trim().is_empty() || (in_trailer && is_continuation(line)) {
+            continue;
+        }
+        git_generated |= GIT_GENERATED_PREFIXES.iter().any(|prefix| line.starts_with(prefix));
+        in_trailer = parse_line(line).is_some();
+        if in_trailer {
+            trailers += 1;
+        } else {
+            non_trailers += 1;
+        }
+    }
+    (trailers > 0 && (non_trailers == 0 || (git_generated && trailers * 3 >= non_trailers))).then_some(start..end)
+}
+
+fn is_continuation(line: &[u8]) -> bool {
+    matches!(line.first(), Some(b' ' | b'\t')) && !line.trim().is_empty()
+}
+
+/// Parse `line` as `<token>: <value>`, where the token consists of alphanumeric characters or `-`,
+/// and may be followed by whitespace before the separator.
+fn parse_line(line: &[u8]) -> Option<(&BStr, &BStr)> {
+    let token_end = line.iter().position(|b| !(b.is_ascii_alphanumeric() || *b == b'-'))?;
+    if token_end == 0 {
+        return None;
+    }
+    let rest = line[token_end..].trim_start_with(|c| c == ' ' || c == '\t');
+    let value = rest.strip_prefix(b":")?;
+    Some((line[..token_end].as_bstr(), value.trim().as_bstr()))
+}
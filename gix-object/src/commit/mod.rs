@@ -120,6 +120,18 @@ impl Commit {
     pub fn extra_headers(&self) -> ExtraHeaders<impl Iterator<Item = (&BStr, &BStr)>> {
         ExtraHeaders::new(self.extra_headers.iter().map(|(k, v)| (k.as_bstr(), v.as_bstr())))
     }
+
+    /// Add `trailers` made of tokens and values to the message of this commit according to `options`,
+    /// like `git interpret-trailers` would.
+    ///
+    /// See [`message::trailers::add()`] for details.
+    pub fn add_trailers<'b>(
+        &mut self,
+        trailers: impl IntoIterator<Item = (&'b BStr, &'b BStr)>,
+        options: message::trailers::Options,
+    ) {
+        self.message = message::trailers::add(&self.message, trailers, options);
+    }
 }
 
 /// An iterator over extra headers in [owned][crate::Commit] and [borrowed][crate::CommitRef] commits.
@@ -205,7 +205,7 @@ impl<'a> CommitRefIter<'a> {
                         (&b"committer"[..], "committer <signature>")
                     }
                 };
-                let signature = (|i: &mut _| parse::header_field(i, field_name, parse::signature))
+                let (signature, _) = (|i: &mut _| parse::header_field(i, field_name, parse::lenient_signature))
                     .context(StrContext::Expected(err_msg.into()))
                     .parse_next(input)?;
                 match who {
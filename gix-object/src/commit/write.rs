@@ -57,8 +57,14 @@ impl<'a> crate::WriteTo for CommitRef<'a> {
         for parent in self.parents() {
             encode::trusted_header_id(b"parent", &parent, &mut out)?;
         }
-        encode::trusted_header_signature(b"author", &self.author, &mut out)?;
-        encode::trusted_header_signature(b"committer", &self.committer, &mut out)?;
+        match &self.malformed_author {
+            Some(malformed) => encode::trusted_header_field(b"author", malformed.raw, &mut out)?,
+            None => encode::trusted_header_signature(b"author", &self.author, &mut out)?,
+        }
+        match &self.malformed_committer {
+            Some(malformed) => encode::trusted_header_field(b"committer", malformed.raw, &mut out)?,
+            None => encode::trusted_header_signature(b"committer", &self.committer, &mut out)?,
+        }
         if let Some(encoding) = self.encoding.as_ref() {
             encode::header_field(b"encoding", encoding, &mut out)?;
         }
@@ -77,8 +83,8 @@ impl<'a> crate::WriteTo for CommitRef<'a> {
         let hash_in_hex = self.tree().kind().len_in_hex();
         (b"tree".len() + 1 /* space */ + hash_in_hex + 1 /* nl */
             + self.parents.iter().count() * (b"parent".len() + 1 /* space */ + hash_in_hex + 1 /* nl */)
-            + b"author".len() + 1 /* space */ + self.author.size() + 1 /* nl */
-            + b"committer".len() + 1 /* space */ + self.committer.size() + 1 /* nl */
+            + b"author".len() + 1 /* space */ + self.malformed_author.map_or_else(|| self.author.size(), |s| s.size()) + 1 /* nl */
+            + b"committer".len() + 1 /* space */ + self.malformed_committer.map_or_else(|| self.committer.size(), |s| s.size()) + 1 /* nl */
             + self
                 .encoding
                 .as_ref()
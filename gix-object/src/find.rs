@@ -1,5 +1,8 @@
 /// The error type returned by the [`Find`](crate::Find) trait.
 pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The header of an object along with a reader for its decoded data, as returned by [`Find::try_find_stream()`](crate::Find::try_find_stream()).
+pub type Stream<'a> = (crate::Header, Box<dyn std::io::Read + 'a>);
 ///
 #[allow(clippy::empty_docs)]
 pub mod existing {
@@ -99,6 +99,15 @@ pub struct CommitRef<'a> {
     /// This may be different from the `author` in case the author couldn't write to the repository themselves and
     /// is commonly encountered with contributed commits.
     pub committer: gix_actor::SignatureRef<'a>,
+    /// The author as found in the commit if it is malformed, in which case `author` only contains what could be extracted from it.
+    ///
+    /// It is written back unchanged to allow rewriting commits with malformed signatures without altering them.
+    pub malformed_author: Option<gix_actor::LenientSignatureRef<'a>>,
+    /// The committer as found in the commit if it is malformed, in which case `committer` only contains what could be
+    /// extracted from it.
+    ///
+    /// It is written back unchanged to allow rewriting commits with malformed signatures without altering them.
+    pub malformed_committer: Option<gix_actor::LenientSignatureRef<'a>>,
     /// The name of the message encoding, otherwise [UTF-8 should be assumed](https://github.com/git/git/blob/e67fbf927dfdf13d0b21dc6ea15dc3c7ef448ea0/commit.c#L1493:L1493).
     pub encoding: Option<&'a BStr>,
     /// The commit message documenting the change.
@@ -152,6 +161,10 @@ pub struct TagRef<'a> {
     pub name: &'a BStr,
     /// The author of the tag.
     pub tagger: Option<gix_actor::SignatureRef<'a>>,
+    /// The tagger as found in the tag if it is malformed, in which case `tagger` only contains what could be extracted from it.
+    ///
+    /// It is written back unchanged to allow rewriting tags with malformed signatures without altering them.
+    pub malformed_tagger: Option<gix_actor::LenientSignatureRef<'a>>,
     /// The message describing this release.
     pub message: &'a BStr,
     /// A cryptographic signature over the entire content of the serialized tag object thus far.
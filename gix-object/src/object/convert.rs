@@ -8,6 +8,7 @@ impl From<TagRef<'_>> for Tag {
             target_kind,
             message,
             tagger: signature,
+            malformed_tagger: _,
             pgp_signature,
         } = other;
         Tag {
@@ -28,6 +29,8 @@ impl From<CommitRef<'_>> for Commit {
             parents,
             author,
             committer,
+            malformed_author: _,
+            malformed_committer: _,
             encoding,
             message,
             extra_headers,
@@ -67,8 +67,23 @@ pub fn hex_hash<'a, E: ParserError<&'a [u8]>>(i: &mut &'a [u8]) -> PResult<&'a B
     .parse_next(i)
 }
 
-pub(crate) fn signature<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
+/// Parse a signature up to the end of the line, and if it's malformed, parse it leniently like `git` does
+/// and return the [lenient signature](gix_actor::LenientSignatureRef) as well, which retains its bytes.
+pub(crate) fn lenient_signature<'a, E: ParserError<&'a [u8]>>(
     i: &mut &'a [u8],
-) -> PResult<gix_actor::SignatureRef<'a>, E> {
-    gix_actor::signature::decode(i)
+) -> PResult<(gix_actor::SignatureRef<'a>, Option<gix_actor::LenientSignatureRef<'a>>), E> {
+    let line = take_till(0.., NL).parse_next(i)?;
+    let mut input = line;
+    if let Ok(signature) = gix_actor::signature::decode::<()>.parse_next(&mut input) {
+        if input.is_empty() {
+            return Ok((signature, None));
+        }
+    }
+    let lenient = gix_actor::LenientSignatureRef::from_bytes(line);
+    let signature = gix_actor::SignatureRef {
+        name: lenient.name,
+        email: lenient.email,
+        time: lenient.time.unwrap_or_default(),
+    };
+    Ok((signature, Some(lenient)))
 }
@@ -19,7 +19,7 @@ pub fn git_tag<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
             .context(StrContext::Expected("type <object kind>".into())),
         (|i: &mut _| parse::header_field(i, b"tag", take_while(1.., |b| b != NL[0])))
             .context(StrContext::Expected("tag <version>".into())),
-        opt(|i: &mut _| parse::header_field(i, b"tagger", parse::signature))
+        opt(|i: &mut _| parse::header_field(i, b"tagger", parse::lenient_signature))
             .context(StrContext::Expected("tagger <signature>".into())),
         terminated(message, eof),
     )
@@ -29,7 +29,8 @@ pub fn git_tag<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
                 name: tag_version.as_bstr(),
                 target_kind: kind,
                 message,
-                tagger: signature,
+                tagger: signature.map(|(signature, _)| signature),
+                malformed_tagger: signature.and_then(|(_, malformed)| malformed),
                 pgp_signature,
             },
         )
@@ -99,11 +99,11 @@ impl<'a> TagRefIter<'a> {
                 Token::Name(tag_version.as_bstr())
             }
             Tagger => {
-                let signature = opt(|i: &mut _| parse::header_field(i, b"tagger", parse::signature))
+                let signature = opt(|i: &mut _| parse::header_field(i, b"tagger", parse::lenient_signature))
                     .context(StrContext::Expected("tagger <signature>".into()))
                     .parse_next(input)?;
                 *state = Message;
-                Token::Tagger(signature)
+                Token::Tagger(signature.map(|(signature, _)| signature))
             }
             Message => {
                 let (message, pgp_signature) = terminated(decode::message, eof).parse_next(input)?;
@@ -62,7 +62,9 @@ impl<'a> crate::WriteTo for TagRef<'a> {
         encode::trusted_header_field(b"object", self.target, &mut out)?;
         encode::trusted_header_field(b"type", self.target_kind.as_bytes(), &mut out)?;
         encode::header_field(b"tag", validated_name(self.name)?, &mut out)?;
-        if let Some(tagger) = &self.tagger {
+        if let Some(malformed) = &self.malformed_tagger {
+            encode::trusted_header_field(b"tagger", malformed.raw, &mut out)?;
+        } else if let Some(tagger) = &self.tagger {
             encode::trusted_header_signature(b"tagger", tagger, &mut out)?;
         }
 
@@ -86,9 +88,10 @@ impl<'a> crate::WriteTo for TagRef<'a> {
             + b"type".len() + 1 /* space */ + self.target_kind.as_bytes().len() + 1 /* nl */
             + b"tag".len() + 1 /* space */ + self.name.len() + 1 /* nl */
             + self
-                .tagger
-                .as_ref()
-                .map_or(0, |t| b"tagger".len() + 1 /* space */ + t.size() + 1 /* nl */)
+                .malformed_tagger
+                .map(|t| t.size())
+                .or_else(|| self.tagger.map(|t| t.size()))
+                .map_or(0, |size| b"tagger".len() + 1 /* space */ + size + 1 /* nl */)
             + 1 /* nl */ + self.message.len()
             + self.pgp_signature.as_ref().map_or(0, |m| 1 /* nl */ + m.len())) as u64
     }
@@ -69,6 +69,27 @@ mod find {
             id: &gix_hash::oid,
             buffer: &'a mut Vec<u8>,
         ) -> Result<Option<crate::Data<'a>>, find::Error>;
+
+        /// Find an object matching `id` in the database and return its header along with a reader for its decoded data,
+        /// which is useful for objects that are too large to be held in memory.
+        ///
+        /// Returns `Some` if it was present in the database, or the error that occurred during lookup or object retrieval.
+        ///
+        /// The default implementation reads the whole object into memory using [`try_find()`](Self::try_find()),
+        /// and implementors should override it if they can do better.
+        fn try_find_stream(&self, id: &gix_hash::oid) -> Result<Option<find::Stream<'_>>, find::Error> {
+            let mut buf = Vec::new();
+            let Some(kind) = self.try_find(id, &mut buf)?.map(|data| data.kind) else {
+                return Ok(None);
+            };
+            Ok(Some((
+                crate::Header {
+                    kind,
+                    size: buf.len() as u64,
+                },
+                Box::new(std::io::Cursor::new(buf)),
+            )))
+        }
     }
 
     /// Find the header of an object in the object store.
@@ -107,6 +128,10 @@ mod find {
             fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, crate::find::Error> {
                 (*self).try_find(id, buffer)
             }
+
+            fn try_find_stream(&self, id: &oid) -> Result<Option<crate::find::Stream<'_>>, crate::find::Error> {
+                (*self).try_find_stream(id)
+            }
         }
 
         impl<T> crate::FindHeader for &T
@@ -143,6 +168,10 @@ mod find {
             fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, crate::find::Error> {
                 self.deref().try_find(id, buffer)
             }
+
+            fn try_find_stream(&self, id: &oid) -> Result<Option<crate::find::Stream<'_>>, crate::find::Error> {
+                self.deref().try_find_stream(id)
+            }
         }
 
         impl<T> crate::FindHeader for Rc<T>
@@ -161,6 +190,10 @@ mod find {
             fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, crate::find::Error> {
                 self.deref().try_find(id, buffer)
             }
+
+            fn try_find_stream(&self, id: &oid) -> Result<Option<crate::find::Stream<'_>>, crate::find::Error> {
+                self.deref().try_find_stream(id)
+            }
         }
 
         impl<T> crate::FindHeader for Box<T>
@@ -188,6 +221,10 @@ mod find {
             fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, crate::find::Error> {
                 self.deref().try_find(id, buffer)
             }
+
+            fn try_find_stream(&self, id: &oid) -> Result<Option<crate::find::Stream<'_>>, crate::find::Error> {
+                self.deref().try_find_stream(id)
+            }
         }
 
         impl<T> crate::FindHeader for Arc<T>
@@ -27,6 +27,8 @@ fn invalid_timestsamp() {
             parents: [b"8845ae683e2688bc619baade49510c17e978518f".as_bstr()].into(),
             author: actor,
             committer: actor,
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"edit changelog to mention about x_sendfile_header default change".as_bstr(),
             extra_headers: vec![]
@@ -44,6 +46,8 @@ fn unsigned() -> crate::Result {
             parents: SmallVec::default(),
             author: signature(1592437401),
             committer: signature(1592437401),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"without sig".as_bstr(),
             extra_headers: vec![]
@@ -61,6 +65,8 @@ fn whitespace() -> crate::Result {
             parents: SmallVec::from(vec![b"26b4df046d1776c123ac69d918f5aec247b58cc6".as_bstr()]),
             author: signature(1592448450),
             committer: signature(1592448450),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b" nl".as_bstr(), // this one had a \n trailing it, but git seems to trim that
             extra_headers: vec![]
@@ -78,6 +84,8 @@ fn signed_singleline() -> crate::Result {
             parents: SmallVec::from(vec![b"09d8d3a12e161a7f6afb522dbe8900a9c09bce06".as_bstr()]),
             author: signature(1592391367),
             committer: signature(1592391367),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"update tasks\n".as_bstr(),
             extra_headers: vec![(b"gpgsig".as_bstr(), b"magic:signature".as_bstr().into())]
@@ -97,6 +105,8 @@ fn mergetag() -> crate::Result {
         ]),
         author: linus_signature(1591996221),
         committer: linus_signature(1591996221),
+        malformed_author: None,
+        malformed_committer: None,
         encoding: None,
         message: LONG_MESSAGE.as_bytes().as_bstr(),
         extra_headers: vec![(
@@ -119,6 +129,8 @@ fn signed() -> crate::Result {
             parents: SmallVec::from(vec![b"09d8d3a12e161a7f6afb522dbe8900a9c09bce06".as_bstr()]),
             author: signature(1592391367),
             committer: signature(1592391367),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"update tasks\n".as_bstr(),
             extra_headers: vec![(b"gpgsig".as_bstr(), b"-----BEGIN PGP SIGNATURE-----\n\niQEzBAABCAAdFiEEdjYp/sh4j8NRKLX27gKdHl60AwAFAl7p9tgACgkQ7gKdHl60\nAwBpegf+KQciv9AOIN7+yPmowecGxBnSfpKWTDzFxnyGR8dq63SpWT8WEKG5mf3a\nG6iUqpsDWaMHlzihaMKRvgRpZxFRbjnNPFBj6F4RRqfE+5R7k6DRSLUV5PqnsdSH\nuccfIDWi1imhsm7AaP5trwl1t+83U2JhHqPcPVFLMODYwWeO6NLR/JCzGSTQRa8t\nRgaVMKI19O/fge5OT5Ua8D47VKEhsJX0LfmkP5RfZQ8JJvNd40TupqKRdlv0sAzP\nya7NXkSHXCavHNR6kA+KpWxn900UoGK8/IDlwU6MeOkpPVawb3NFMqnc7KJDaC2p\nSMzpuEG8LTrCx2YSpHNLqHyzvQ1CZA==\n=5ITV\n-----END PGP SIGNATURE-----".as_bstr().into())]
@@ -136,6 +148,8 @@ fn signed_with_encoding() -> crate::Result {
             parents: SmallVec::from(vec![b"79c51cc86923e2b8ca0ee5c4eb75e48027133f9a".as_bstr()]),
             author: signature(1592448995),
             committer: signature(1592449083),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: Some(b"ISO-8859-1".as_bstr()),
             message: b"encoding & sig".as_bstr(),
             extra_headers: vec![(b"gpgsig".as_bstr(), SIGNATURE.as_bstr().into())]
@@ -153,6 +167,8 @@ fn with_encoding() -> crate::Result {
             parents: SmallVec::from(vec![b"7ca98aad461a5c302cb4c9e3acaaa6053cc67a62".as_bstr()]),
             author: signature(1592438199),
             committer: signature(1592438199),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: Some("ISO-8859-1".into()),
             message: b"commit with encoding".as_bstr(),
             extra_headers: vec![]
@@ -179,6 +195,8 @@ fn pre_epoch() -> crate::Result {
             parents: SmallVec::default(),
             author: signature(),
             committer: signature(),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: "Version consolidée au 14 mars 1803\n".into(),
             extra_headers: vec![]
@@ -205,6 +223,8 @@ fn double_dash_special_time_offset() -> crate::Result {
             parents: SmallVec::from(vec![b"31350f4f0f459485eff2131517e3450cf251f6fa".as_bstr()]),
             author: signature(),
             committer: signature(),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: "msg\n".into(),
             extra_headers: vec![]
@@ -233,6 +253,8 @@ fn with_trailer() -> crate::Result {
             parents: SmallVec::from(vec![b"699ae71105dddfcbb9711ed3a92df09e91a04e90".as_bstr()]),
             author: kim,
             committer: kim,
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"test: use gitoxide for link-git-protocol tests
 
@@ -313,6 +335,8 @@ fn merge() -> crate::Result {
             ]),
             author: signature(1592454703),
             committer: signature(1592454738),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: Some("ISO-8859-1".into()),
             message: b"Merge branch 'branch'".as_bstr(),
             extra_headers: vec![]
@@ -332,3 +356,36 @@ fn newline_right_after_signature_multiline_header() -> crate::Result {
     assert!(commit.message.starts_with(b"Rollup"));
     Ok(())
 }
+
+#[test]
+fn malformed_signatures_are_parsed_leniently_and_written_back_unchanged() -> crate::Result {
+    use gix_object::WriteTo;
+    let data = b"tree 7989dfb2ec2f41914611a22fb30bbc2b3849df9a\n\
+author Name <name@example.com> 1312735823 +0000\n\
+committer Name name@example.com 1312735823 +0000\n\
+\n\
+message";
+    let commit = CommitRef::from_bytes(data)?;
+    assert_eq!(commit.malformed_author, None, "the author is well-formed");
+    let malformed = commit.malformed_committer.expect("the committer lacks an email");
+    assert_eq!(malformed.raw, "Name name@example.com 1312735823 +0000");
+    assert_eq!(
+        commit.committer,
+        SignatureRef {
+            name: "Name name@example.com 1312735823 +0000".into(),
+            email: "".into(),
+            time: Time::default(),
+        },
+        "what could be extracted is available as usual"
+    );
+
+    let mut buf = Vec::new();
+    commit.write_to(&mut buf)?;
+    assert_eq!(
+        buf.as_bstr(),
+        data.as_bstr(),
+        "malformed signatures are written back as they were found"
+    );
+    assert_eq!(commit.size() as usize, buf.len());
+    Ok(())
+}
@@ -248,6 +248,8 @@ mod summary {
                 parents: Default::default(),
                 author: actor,
                 committer: actor,
+                malformed_author: None,
+                malformed_committer: None,
                 encoding: None,
                 message: input.as_bstr(),
                 extra_headers: vec![]
@@ -295,3 +297,206 @@ mod summary {
         assert_eq!(summary(input), Cow::Borrowed(b"hello world foo".as_bstr()));
     }
 }
+
+mod trailers {
+    use bstr::{BStr, ByteSlice};
+    use gix_object::commit::{
+        message::trailers::{self, IfExists, IfMissing, Options, Placement},
+        MessageRef,
+    };
+
+    fn parse(message: &str) -> Vec<(String, String)> {
+        trailers::parse(message.as_bytes())
+            .map(|t| (t.token.to_string(), t.value.to_string()))
+            .collect()
+    }
+
+    fn add(message: &str, trailers: &[(&str, &str)], options: Options) -> String {
+        trailers::add(
+            message.as_bytes(),
+            trailers.iter().map(|(t, v)| (<&BStr>::from(*t), <&BStr>::from(*v))),
+            options,
+        )
+        .to_string()
+    }
+
+    fn pairs(input: &[(&str, &str)]) -> Vec<(String, String)> {
+        input.iter().map(|(t, v)| (t.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn parse_last_paragraph_if_it_only_contains_trailers() {
+        assert_eq!(
+            parse("title\n\nbody\n\nSigned-off-by: A <a@example.com>\nCo-authored-by : B <b@example.com>\n\n"),
+            pairs(&[
+                ("Signed-off-by", "A <a@example.com>"),
+                ("Co-authored-by", "B <b@example.com>")
+            ])
+        );
+        assert_eq!(
+            parse("title\r\n\r\nReviewed-by: A\r\n"),
+            pairs(&[("Reviewed-by", "A")]),
+            "windows line endings are supported"
+        );
+        assert_eq!(parse("Key: value"), vec![], "the title is never a trailer");
+        assert_eq!(
+            parse("title\n\nKey: value\nnot a trailer\n"),
+            vec![],
+            "non-trailer lines disqualify the paragraph unless there are git-generated trailers"
+        );
+        assert_eq!(
+            parse("title\n\nnot a trailer\nSigned-off-by: A\nmore\nlines\n"),
+            pairs(&[("Signed-off-by", "A")]),
+            "with git-generated trailers, 25% of lines must be trailers"
+        );
+        assert_eq!(
+            parse("title\n\nnot a trailer\nSigned-off-by: A\nmore\nlines\nthan allowed\n"),
+            vec![]
+        );
+        assert_eq!(
+            parse("title\n\nKey: multi\n  line\n\tvalue\nOther:\n"),
+            pairs(&[("Key", "multi line value"), ("Other", "")]),
+            "multi-line values are unfolded"
+        );
+    }
+
+    #[test]
+    fn message_ref_trailers_use_the_body() {
+        let message = MessageRef::from_bytes(b"title\n\nKey: value\nOther: value\n");
+        assert_eq!(
+            message
+                .trailers()
+                .map(|t| (t.token.to_string(), t.value.to_string()))
+                .collect::<Vec<_>>(),
+            pairs(&[("Key", "value"), ("Other", "value")])
+        );
+        assert_eq!(MessageRef::from_bytes(b"Key: value").trailers().count(), 0);
+    }
+
+    #[test]
+    fn add_creates_a_trailer_block_if_needed() {
+        let sob = [("Signed-off-by", "A <a@example.com>")];
+        assert_eq!(
+            add("title\n\nbody\n", &sob, Options::default()),
+            "title\n\nbody\n\nSigned-off-by: A <a@example.com>\n"
+        );
+        assert_eq!(
+            add("title", &sob, Options::default()),
+            "title\n\nSigned-off-by: A <a@example.com>\n"
+        );
+        assert_eq!(add("", &sob, Options::default()), "Signed-off-by: A <a@example.com>\n");
+        assert_eq!(
+            add(
+                "title\n\nKey:  value \n\n\n",
+                &[("Signed-off-by:", "A")],
+                Options::default()
+            ),
+            "title\n\nKey: value\nSigned-off-by: A\n",
+            "existing blocks are extended and normalized, and separators in tokens are ignored"
+        );
+        assert_eq!(
+            add(
+                "title\n\nKey: value\n",
+                &sob,
+                Options {
+                    placement: Placement::Start,
+                    ..Default::default()
+                }
+            ),
+            "title\n\nSigned-off-by: A <a@example.com>\nKey: value\n"
+        );
+        assert_eq!(
+            add(
+                "title\n",
+                &sob,
+                Options {
+                    if_missing: IfMissing::DoNothing,
+                    ..Default::default()
+                }
+            ),
+            "title\n",
+            "nothing changes if nothing is added"
+        );
+    }
+
+    #[test]
+    fn add_if_exists() {
+        let message = "title\n\nKey: a\nOther: b\n";
+        let with = |if_exists, trailers: &[(&str, &str)]| {
+            add(
+                message,
+                trailers,
+                Options {
+                    if_exists,
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(
+            with(IfExists::AddIfDifferentNeighbor, &[("other", "B"), ("key", "A")]),
+            "title\n\nKey: a\nOther: b\nkey: A\n",
+            "only the neighbor is compared, case-insensitively"
+        );
+        assert_eq!(
+            with(IfExists::AddIfDifferent, &[("key", "A"), ("key", "c")]),
+            "title\n\nKey: a\nOther: b\nkey: c\n"
+        );
+        assert_eq!(
+            with(IfExists::Add, &[("Other", "b")]),
+            "title\n\nKey: a\nOther: b\nOther: b\n"
+        );
+        assert_eq!(
+            with(IfExists::Replace, &[("key", "c")]),
+            "title\n\nOther: b\nkey: c\n",
+            "the existing trailer is removed, and the new one is placed like any other"
+        );
+        assert_eq!(
+            add(
+                "title\n\nOther: b\nKey: a\n",
+                &[("key", "c")],
+                Options {
+                    if_exists: IfExists::Replace,
+                    placement: Placement::Start,
+                    ..Default::default()
+                },
+            ),
+            "title\n\nkey: c\nOther: b\n"
+        );
+        assert_eq!(with(IfExists::DoNothing, &[("Key", "c")]), message);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            trailers::normalize(
+                b"title\n\nKey :value\n  continued\nEmpty:\nSigned-off-by: A\n(cherry picked from commit abc)\n\n",
+                Options {
+                    trim_empty: true,
+                    ..Default::default()
+                }
+            )
+            .as_bstr(),
+            "title\n\nKey: value continued\nSigned-off-by: A\n(cherry picked from commit abc)\n"
+        );
+        assert_eq!(
+            trailers::normalize(b"title\n\nbody\n\n", Options::default()).as_bstr(),
+            "title\n\nbody\n\n",
+            "messages without trailers are left unchanged"
+        );
+    }
+
+    #[test]
+    fn commit_add_trailers() {
+        let mut commit = gix_object::Commit {
+            tree: gix_hash::Kind::Sha1.null(),
+            parents: Default::default(),
+            author: Default::default(),
+            committer: Default::default(),
+            encoding: None,
+            message: "title\n".into(),
+            extra_headers: vec![],
+        };
+        commit.add_trailers(Some(("Change-Id".into(), "I123".into())), Options::default());
+        assert_eq!(commit.message, "title\n\nChange-Id: I123\n");
+    }
+}
@@ -160,6 +160,7 @@ mod from_bytes {
                 target_kind: Kind::Commit,
                 message: b"".as_bstr(),
                 tagger: Some(signature(1592381636)),
+                malformed_tagger: None,
                 pgp_signature: None
             }
         );
@@ -176,6 +177,7 @@ mod from_bytes {
                 target_kind: Kind::Commit,
                 message: b"hello\n\nworld".as_bstr(),
                 tagger: Some(signature(1592311808)),
+                malformed_tagger: None,
                 pgp_signature: None
             }
         );
@@ -198,6 +200,7 @@ object up to a real commit. In the meantime, this acts as an anchor point for
 doing diffs etc under git."
                     .as_bstr(),
                 tagger: None,
+                malformed_tagger: None,
                 pgp_signature: Some(
                     b"-----BEGIN PGP SIGNATURE-----
 Version: GnuPG v1.2.4 (GNU/Linux)
@@ -214,6 +217,33 @@ KLMHist5yj0sw1E4hDTyQa0=
         Ok(())
     }
 
+    #[test]
+    fn malformed_tagger_is_parsed_leniently_and_written_back_unchanged() -> crate::Result {
+        use gix_object::WriteTo;
+        let data = b"object 01dd4e2a978a9f5bd773dae6da7aa4a5ac1cdbbc\n\
+type commit\n\
+tag malformed\n\
+tagger Name <name@example.com>\n\
+\n\
+message";
+        let tag = TagRef::from_bytes(data)?;
+        let malformed = tag.malformed_tagger.expect("the time is missing");
+        assert_eq!(malformed.raw, "Name <name@example.com>");
+        let tagger = tag.tagger.expect("present");
+        assert_eq!(tagger.name, "Name");
+        assert_eq!(tagger.email, "name@example.com");
+
+        let mut buf = Vec::new();
+        tag.write_to(&mut buf)?;
+        assert_eq!(
+            buf.as_bstr(),
+            data.as_bstr(),
+            "malformed signatures are written back as they were found"
+        );
+        assert_eq!(tag.size() as usize, buf.len());
+        Ok(())
+    }
+
     #[test]
     fn whitespace() -> crate::Result {
         assert_eq!(
@@ -224,6 +254,7 @@ KLMHist5yj0sw1E4hDTyQa0=
                 target_kind: Kind::Commit,
                 message: b" \ttab\nnewline\n\nlast-with-trailer\n".as_bstr(),
                 tagger: Some(signature(1592382888)),
+                malformed_tagger: None,
                 pgp_signature: None
             }
         );
@@ -237,6 +268,7 @@ fn tag_fixture(offset: i32) -> TagRef<'static> {
         name: b"1.0.0".as_bstr(),
         target_kind: Kind::Commit,
         message: b"for the signature".as_bstr(),
+        malformed_tagger: None,
         pgp_signature: Some(
             b"-----BEGIN PGP SIGNATURE-----
 Comment: GPGTools - https://gpgtools.org
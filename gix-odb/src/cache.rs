@@ -157,6 +157,13 @@ mod impls {
         fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, gix_object::find::Error> {
             gix_pack::Find::try_find(self, id, buffer).map(|t| t.map(|t| t.0))
         }
+
+        fn try_find_stream(&self, id: &oid) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            match self.pack_cache.as_ref().map(RefCell::borrow_mut) {
+                Some(mut pack_cache) => self.inner.try_find_stream_cached(id, pack_cache.deref_mut()),
+                None => self.inner.try_find_stream_cached(id, &mut gix_pack::cache::Never),
+            }
+        }
     }
 
     impl<S> gix_object::Exists for Cache<S>
@@ -225,6 +232,14 @@ mod impls {
             Ok(possibly_obj)
         }
 
+        fn try_find_stream_cached(
+            &self,
+            id: &oid,
+            pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            self.inner.try_find_stream_cached(id, pack_cache)
+        }
+
         fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<gix_pack::data::entry::Location> {
             self.inner.location_by_oid(id, buf)
         }
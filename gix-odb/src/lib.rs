@@ -7,6 +7,8 @@
 //! * loose object reading and writing
 //! * access to packed objects
 //! * multiple loose objects and pack locations as gathered from `alternates` files.
+//!
+//! The [`memory::Proxy`] keeps written objects in memory, optionally on top of any other object database.
 //! ## Feature Flags
 #![cfg_attr(
     all(doc, feature = "document-features"),
@@ -69,10 +71,21 @@ pub fn sink(object_hash: gix_hash::Kind) -> Sink {
 
 mod sink;
 
+/// Create a new [`memory::Proxy`] which isn't backed by another object database, keeping all objects of the
+/// `object_hash` kind in memory.
+pub fn memory(object_hash: gix_hash::Kind) -> memory::Proxy<gix_object::find::Never> {
+    memory::Proxy::new(gix_object::find::Never, object_hash)
+}
+
+/// An object database that keeps written objects in memory, optionally layered over another object database.
+pub mod memory;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod find;
 
+pub mod stream;
+
 /// An object database equivalent to `/dev/null`, dropping all objects stored into it.
 mod traits;
 
@@ -117,6 +130,9 @@ pub struct Store {
     /// Keeping it here helps to assure consistency even while a process changes its CWD.
     pub(crate) current_dir: PathBuf,
 
+    /// Object directories to use in addition to the ones listed in `info/alternates`, along with their own alternates.
+    pub(crate) extra_alternates: Vec<PathBuf>,
+
     /// A set of replacements that given a source OID return a destination OID. The vector is sorted.
     pub(crate) replacements: Vec<(gix_hash::ObjectId, gix_hash::ObjectId)>,
 
@@ -140,6 +156,10 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// How to flush loose objects to permanent storage when writing them, if at all.
+    pub(crate) fsync_loose_objects: Option<gix_fs::fsync::Method>,
+    /// The loose objects written in batch mode which still have to be flushed.
+    pub(crate) loose_batch: std::sync::Arc<gix_fs::fsync::Batch>,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
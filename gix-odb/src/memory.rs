@@ -0,0 +1,177 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::Read,
+    ops::{Deref, DerefMut},
+};
+
+use gix_hash::{oid, ObjectId};
+use gix_object::{Data, Kind};
+
+use crate::find::Header;
+
+/// The storage of objects kept in memory, keyed by their id.
+pub type Storage = BTreeMap<ObjectId, (Kind, Vec<u8>)>;
+
+/// An object database which keeps all objects written to it in memory, and which looks up objects in memory first
+/// before delegating to the `inner` object database.
+///
+/// Objects are never written to `inner`, which makes it possible to create throwaway objects without touching the disk,
+/// and to decide later if and where they should be persisted, for instance by writing all objects obtained with
+/// [`Proxy::take_object_memory()`] into another database.
+///
+/// Use [`memory()`](crate::memory()) to create an instance that isn't backed by another object database.
+pub struct Proxy<T> {
+    inner: T,
+    memory: RefCell<Storage>,
+    object_hash: gix_hash::Kind,
+}
+
+impl<T> Proxy<T> {
+    /// Create a new instance which writes objects of the `object_hash` kind into memory while reading them from memory
+    /// or from `inner`.
+    pub fn new(inner: T, object_hash: gix_hash::Kind) -> Self {
+        Proxy {
+            inner,
+            memory: Default::default(),
+            object_hash,
+        }
+    }
+
+    /// Return the kind of hash used to compute the ids of written objects.
+    pub fn object_hash(&self) -> gix_hash::Kind {
+        self.object_hash
+    }
+
+    /// Return the amount of objects currently held in memory.
+    pub fn num_objects_in_memory(&self) -> usize {
+        self.memory.borrow().len()
+    }
+
+    /// Remove all objects from memory and return them.
+    pub fn take_object_memory(&self) -> Storage {
+        self.memory.take()
+    }
+
+    /// Replace the objects held in memory with `new`, and return the previous ones.
+    pub fn set_object_memory(&self, new: Storage) -> Storage {
+        self.memory.replace(new)
+    }
+
+    /// Dissolve this instance into the object database it's layered over, dropping all objects held in memory.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Clone> Clone for Proxy<T> {
+    fn clone(&self) -> Self {
+        Proxy {
+            inner: self.inner.clone(),
+            memory: self.memory.clone(),
+            object_hash: self.object_hash,
+        }
+    }
+}
+
+impl<T> Deref for Proxy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Proxy<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T> crate::Write for Proxy<T> {
+    fn write_stream(&self, kind: Kind, size: u64, from: &mut dyn Read) -> Result<ObjectId, crate::write::Error> {
+        let mut buf = Vec::with_capacity(size as usize);
+        from.take(size).read_to_end(&mut buf)?;
+        if buf.len() as u64 != size {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("expected {size} bytes of object data, but got only {}", buf.len()),
+            )));
+        }
+        let id = gix_object::compute_hash(self.object_hash, kind, &buf);
+        self.memory.borrow_mut().insert(id, (kind, buf));
+        Ok(id)
+    }
+}
+
+impl<T> gix_object::Find for Proxy<T>
+where
+    T: gix_object::Find,
+{
+    fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, gix_object::find::Error> {
+        if let Some((kind, data)) = self.memory.borrow().get(id) {
+            buffer.clear();
+            buffer.extend_from_slice(data);
+            return Ok(Some(Data::new(*kind, buffer)));
+        }
+        self.inner.try_find(id, buffer)
+    }
+
+    fn try_find_stream(&self, id: &oid) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+        if let Some((kind, data)) = self.memory.borrow().get(id) {
+            return Ok(Some((
+                gix_object::Header {
+                    kind: *kind,
+                    size: data.len() as u64,
+                },
+                Box::new(std::io::Cursor::new(data.clone())),
+            )));
+        }
+        self.inner.try_find_stream(id)
+    }
+}
+
+impl<T> gix_object::Exists for Proxy<T>
+where
+    T: gix_object::Exists,
+{
+    fn exists(&self, id: &oid) -> bool {
+        self.memory.borrow().contains_key(id) || self.inner.exists(id)
+    }
+}
+
+impl<T> gix_object::FindHeader for Proxy<T>
+where
+    T: gix_object::FindHeader,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<gix_object::Header>, gix_object::find::Error> {
+        if let Some((kind, data)) = self.memory.borrow().get(id) {
+            return Ok(Some(gix_object::Header {
+                kind: *kind,
+                size: data.len() as u64,
+            }));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl<T> crate::Header for Proxy<T>
+where
+    T: crate::Header,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<Header>, gix_object::find::Error> {
+        if let Some((kind, data)) = self.memory.borrow().get(id) {
+            return Ok(Some(Header::Loose {
+                kind: *kind,
+                size: data.len() as u64,
+            }));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl crate::Header for gix_object::find::Never {
+    fn try_header(&self, _id: &oid) -> Result<Option<Header>, gix_object::find::Error> {
+        Ok(None)
+    }
+}
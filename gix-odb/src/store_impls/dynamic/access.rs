@@ -21,4 +21,12 @@ impl Store {
     pub fn replacements(&self) -> impl Iterator<Item = (gix_hash::ObjectId, gix_hash::ObjectId)> + '_ {
         self.replacements.iter().copied()
     }
+
+    /// Flush all loose objects that were written in [batch mode](gix_fs::fsync::Method::Batch) since the last call
+    /// to permanent storage, and return their amount.
+    ///
+    /// This should be done before anything, like references, starts to point to them.
+    pub fn flush_batch(&self) -> std::io::Result<usize> {
+        self.loose_batch.flush()
+    }
 }
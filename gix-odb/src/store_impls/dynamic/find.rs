@@ -85,7 +85,7 @@ impl<S> super::Handle<S>
 where
     S: Deref<Target = super::Store> + Clone,
 {
-    fn try_find_cached_inner<'a, 'b>(
+    pub(crate) fn try_find_cached_inner<'a, 'b>(
         &'b self,
         mut id: &'b gix_hash::oid,
         buffer: &'a mut Vec<u8>,
@@ -357,6 +357,22 @@ where
             .map_err(|err| Box::new(err) as _)
     }
 
+    fn try_find_stream_cached(
+        &self,
+        id: &gix_hash::oid,
+        pack_cache: &mut dyn DecodeEntry,
+    ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+        Ok(self.try_stream_cached(id, pack_cache)?.map(|reader| {
+            (
+                gix_object::Header {
+                    kind: reader.kind(),
+                    size: reader.size(),
+                },
+                Box::new(reader) as _,
+            )
+        }))
+    }
+
     fn location_by_oid(&self, id: &gix_hash::oid, buf: &mut Vec<u8>) -> Option<gix_pack::data::entry::Location> {
         assert!(
             matches!(self.token.as_ref(), Some(handle::Mode::KeepDeletedPacksAvailable)),
@@ -514,6 +530,21 @@ where
     ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
         gix_pack::Find::try_find(self, id, buffer).map(|t| t.map(|t| t.0))
     }
+
+    fn try_find_stream(
+        &self,
+        id: &gix_hash::oid,
+    ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+        Ok(self.try_stream(id)?.map(|reader| {
+            (
+                gix_object::Header {
+                    kind: reader.kind(),
+                    size: reader.size(),
+                },
+                Box::new(reader) as _,
+            )
+        }))
+    }
 }
 
 impl<S> gix_object::FindHeader for super::Handle<S>
@@ -344,7 +344,7 @@ impl TryFrom<&super::Store> for super::Store {
     type Error = std::io::Error;
 
     fn try_from(s: &super::Store) -> Result<Self, Self::Error> {
-        super::Store::at_opts(
+        let mut store = super::Store::at_opts(
             s.path().into(),
             &mut s.replacements(),
             crate::store::init::Options {
@@ -352,8 +352,13 @@ impl TryFrom<&super::Store> for super::Store {
                 object_hash: Default::default(),
                 use_multi_pack_index: false,
                 current_dir: s.current_dir.clone().into(),
+                fsync_loose_objects: s.fsync_loose_objects,
+                alternates: s.extra_alternates.clone(),
             },
-        )
+        )?;
+        // Objects written through either store are flushed together.
+        store.loose_batch = Arc::clone(&s.loose_batch);
+        Ok(store)
     }
 }
 
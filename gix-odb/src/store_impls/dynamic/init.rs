@@ -19,6 +19,15 @@ pub struct Options {
     /// The current directory of the process at the time of instantiation.
     /// If unset, it will be retrieved using `gix_fs::current_dir(false)`.
     pub current_dir: Option<std::path::PathBuf>,
+    /// How to flush loose objects to permanent storage when writing them, or `None` to not flush them, which is the default.
+    ///
+    /// With [`Batch`](gix_fs::fsync::Method::Batch), they have to be flushed with [`Store::flush_batch()`].
+    pub fsync_loose_objects: Option<gix_fs::fsync::Method>,
+    /// Object directories to use in addition to the ones listed in `info/alternates`, similar to
+    /// `GIT_ALTERNATE_OBJECT_DIRECTORIES`. Their own alternates are used as well.
+    ///
+    /// Relative paths are relative to `current_dir`.
+    pub alternates: Vec<PathBuf>,
 }
 
 impl Default for Options {
@@ -28,6 +37,8 @@ impl Default for Options {
             object_hash: Default::default(),
             use_multi_pack_index: true,
             current_dir: None,
+            fsync_loose_objects: None,
+            alternates: Vec::new(),
         }
     }
 }
@@ -77,6 +88,8 @@ impl Store {
             object_hash,
             use_multi_pack_index,
             current_dir,
+            fsync_loose_objects,
+            alternates,
         }: Options,
     ) -> std::io::Result<Self> {
         let _span = gix_features::trace::detail!("gix_odb::Store::at()");
@@ -87,6 +100,7 @@ impl Store {
             },
             Ok,
         )?;
+        let alternates: Vec<_> = alternates.into_iter().map(|dir| current_dir.join(dir)).collect();
         if !objects_dir.is_dir() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::Other, // TODO: use NotADirectory when stabilized
@@ -96,9 +110,8 @@ impl Store {
         let slot_count = match slots {
             Slots::Given(n) => n as usize,
             Slots::AsNeededByDiskState { multiplier, minimum } => {
-                let mut db_paths = crate::alternate::resolve(objects_dir.clone(), &current_dir)
+                let db_paths = super::db_paths(&objects_dir, &alternates, &current_dir)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-                db_paths.insert(0, objects_dir.clone());
                 let num_slots = super::Store::collect_indices_and_mtime_sorted_by_size(db_paths, None, None)
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
                     .len();
@@ -116,6 +129,7 @@ impl Store {
         replacements.sort_by(|a, b| a.0.cmp(&b.0));
 
         Ok(Store {
+            extra_alternates: alternates,
             current_dir,
             write: Default::default(),
             replacements,
@@ -127,6 +141,8 @@ impl Store {
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
+            fsync_loose_objects,
+            loose_batch: Default::default(),
         })
     }
 }
@@ -67,6 +67,65 @@ impl AllObjects {
         self.order = order;
         self
     }
+
+    /// Only yield objects whose kind is one of `kinds`, using `db` to look up their headers.
+    ///
+    /// Note that object data isn't decoded, and that it's best to use
+    /// [`Ordering::PackAscendingOffsetThenLooseLexicographical`] to make header lookups efficient.
+    pub fn filter_kinds<H: crate::Header>(self, db: H, kinds: &[gix_object::Kind]) -> OfKinds<H> {
+        OfKinds {
+            inner: self,
+            db,
+            kinds: kinds.to_owned(),
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod of_kinds {
+    /// The error returned by the [`OfKinds`][super::OfKinds] iterator.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Iter(#[from] crate::loose::iter::Error),
+        #[error(transparent)]
+        Header(#[from] gix_object::find::Error),
+    }
+}
+
+/// An iterator over all, _possibly duplicate_, objects of an object store whose kind matches any of the chosen kinds,
+/// along with their header information.
+///
+/// Create it with [`AllObjects::filter_kinds()`] or [`Handle::iter_kinds()`][super::Handle::iter_kinds()].
+pub struct OfKinds<H> {
+    inner: AllObjects,
+    db: H,
+    kinds: Vec<gix_object::Kind>,
+}
+
+impl<H: crate::Header> Iterator for OfKinds<H> {
+    type Item = Result<(ObjectId, crate::find::Header), of_kinds::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let id = match self.inner.next()? {
+                Ok(id) => id,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match self.db.try_header(&id) {
+                Ok(Some(header)) if self.kinds.contains(&header.kind()) => return Some(Ok((id, header))),
+                // Objects that vanished in the mean time are skipped just like those of other kinds.
+                Ok(_) => continue,
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
 }
 
 impl AllObjects {
@@ -221,6 +280,16 @@ where
     pub fn iter(&self) -> Result<AllObjects, dynamic::load_index::Error> {
         AllObjects::new(self.store_ref())
     }
+
+    /// Return an iterator over all, _possibly duplicate_, objects whose kind is one of `kinds`, along with their header.
+    ///
+    /// Objects are traversed in pack order to make header lookups efficient, and object data is never decoded.
+    pub fn iter_kinds(&self, kinds: &[gix_object::Kind]) -> Result<OfKinds<&Self>, dynamic::load_index::Error> {
+        Ok(self
+            .iter()?
+            .with_ordering(Ordering::PackAscendingOffsetThenLooseLexicographical)
+            .filter_kinds(self, kinds))
+    }
 }
 
 impl dynamic::Store {
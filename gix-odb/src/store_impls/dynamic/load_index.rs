@@ -214,9 +214,7 @@ impl super::Store {
         }
         self.num_disk_state_consolidation.fetch_add(1, Ordering::Relaxed);
 
-        let db_paths: Vec<_> = std::iter::once(objects_directory.to_owned())
-            .chain(crate::alternate::resolve(objects_directory.clone(), &self.current_dir)?)
-            .collect();
+        let db_paths = super::db_paths(objects_directory, &self.extra_alternates, &self.current_dir)?;
 
         // turn db paths into loose object databases. Reuse what's there, but only if it is in the right order.
         let loose_dbs = if was_uninitialized
@@ -229,7 +227,12 @@ impl super::Store {
             Arc::new(
                 db_paths
                     .iter()
-                    .map(|path| crate::loose::Store::at(path, self.object_hash))
+                    .map(|path| {
+                        let mut store =
+                            crate::loose::Store::at(path, self.object_hash).with_fsync(self.fsync_loose_objects);
+                        store.batch = Arc::clone(&self.loose_batch);
+                        store
+                    })
                     .collect::<Vec<_>>(),
             )
         } else {
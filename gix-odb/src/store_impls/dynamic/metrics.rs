@@ -77,4 +77,30 @@ impl super::Store {
             unreachable_packs,
         }
     }
+
+    /// Return the total amount of bytes of all memory-mapped index and pack data files, whether they are reachable or not,
+    /// collected in a racy fashion.
+    ///
+    /// Note that the operating system decides how much of it is actually resident in memory.
+    pub fn mapped_bytes(&self) -> u64 {
+        let mut bytes = 0;
+        for slot in &self.files {
+            bytes += match slot.files.load().as_ref() {
+                None => 0,
+                Some(IndexAndPacks::Index(single)) => {
+                    single.index.loaded().map_or(0, |f| f.data_len() as u64)
+                        + single.data.loaded().map_or(0, |f| f.data_len() as u64)
+                }
+                Some(IndexAndPacks::MultiIndex(multi)) => {
+                    multi.multi_index.loaded().map_or(0, |f| f.data_len() as u64)
+                        + multi
+                            .data
+                            .iter()
+                            .filter_map(|p| p.loaded().map(|f| f.data_len() as u64))
+                            .sum::<u64>()
+                }
+            };
+        }
+        bytes
+    }
 }
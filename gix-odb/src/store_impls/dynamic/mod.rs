@@ -5,6 +5,21 @@ use gix_features::zlib;
 
 use crate::Store;
 
+/// Return `objects_dir` followed by all of its alternates, and all `extra_alternates` along with their alternates.
+fn db_paths(
+    objects_dir: &std::path::Path,
+    extra_alternates: &[std::path::PathBuf],
+    current_dir: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>, crate::alternate::Error> {
+    let mut out = vec![objects_dir.to_owned()];
+    out.extend(crate::alternate::resolve(objects_dir.to_owned(), current_dir)?);
+    for dir in extra_alternates {
+        out.push(dir.clone());
+        out.extend(crate::alternate::resolve(dir.clone(), current_dir)?);
+    }
+    Ok(out)
+}
+
 /// This effectively acts like a handle but exists to be usable from the actual `crate::Handle` implementation which adds caches on top.
 /// Each store is quickly cloned and contains thread-local state for shared packs.
 pub struct Handle<S>
@@ -60,6 +75,8 @@ pub mod prefix;
 
 mod header;
 
+mod stream;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod iter;
@@ -94,3 +111,7 @@ mod access;
 ///
 #[allow(clippy::empty_docs)]
 pub mod structure;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod statistics;
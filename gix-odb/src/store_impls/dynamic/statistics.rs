@@ -0,0 +1,313 @@
+use std::{
+    collections::BTreeMap,
+    ops::Deref,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::{progress::DynNestedProgress, zlib};
+
+use crate::{pack, types::IndexAndPacks};
+
+/// Returned by [`Store::statistics()`][crate::Store::statistics()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    IndexOpen(#[from] pack::index::init::Error),
+    #[error(transparent)]
+    MultiIndexOpen(#[from] pack::multi_index::init::Error),
+    #[error(transparent)]
+    PackOpen(#[from] pack::data::init::Error),
+    #[error(transparent)]
+    PackEntry(#[from] pack::data::entry::decode::Error),
+    #[error(transparent)]
+    PackEntryHeader(#[from] pack::data::decode::Error),
+    #[error(transparent)]
+    LooseIter(#[from] crate::loose::iter::Error),
+    #[error(transparent)]
+    LooseHeader(#[from] crate::loose::find::Error),
+    #[error(transparent)]
+    InitializeODB(#[from] crate::store::load_index::Error),
+    #[error("The disk on state changed while performing the operation, and we observed the change.")]
+    NeedsRetryDueToChangeOnDisk,
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// The amount of objects per kind.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Counts {
+    /// The amount of commits.
+    pub commits: usize,
+    /// The amount of trees.
+    pub trees: usize,
+    /// The amount of blobs.
+    pub blobs: usize,
+    /// The amount of tags.
+    pub tags: usize,
+}
+
+impl Counts {
+    /// Return the amount of objects of all kinds.
+    pub fn total(&self) -> usize {
+        self.commits + self.trees + self.blobs + self.tags
+    }
+
+    /// Return the amount of objects of `kind`.
+    pub fn of_kind(&self, kind: gix_object::Kind) -> usize {
+        match kind {
+            gix_object::Kind::Commit => self.commits,
+            gix_object::Kind::Tree => self.trees,
+            gix_object::Kind::Blob => self.blobs,
+            gix_object::Kind::Tag => self.tags,
+        }
+    }
+
+    fn add(&mut self, kind: gix_object::Kind) {
+        *match kind {
+            gix_object::Kind::Commit => &mut self.commits,
+            gix_object::Kind::Tree => &mut self.trees,
+            gix_object::Kind::Blob => &mut self.blobs,
+            gix_object::Kind::Tag => &mut self.tags,
+        } += 1;
+    }
+}
+
+impl std::ops::AddAssign for Counts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.commits += rhs.commits;
+        self.trees += rhs.trees;
+        self.blobs += rhs.blobs;
+        self.tags += rhs.tags;
+    }
+}
+
+/// Statistics about a single pack data file.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pack {
+    /// The path to the pack data file.
+    pub path: PathBuf,
+    /// The amount of objects in the pack, by kind.
+    pub counts: Counts,
+    /// A mapping of the length of the delta chain to the amount of objects with a chain of that length.
+    ///
+    /// A length of 0 indicates base objects, everything above that are objects stored as delta.
+    pub objects_per_chain_length: BTreeMap<u32, usize>,
+    /// The amount of bytes all objects would occupy if they were undeltified and decompressed.
+    pub total_object_size: u64,
+    /// The size of the pack data file in bytes.
+    pub pack_size: u64,
+}
+
+impl Pack {
+    /// Return the amount of objects that are stored as delta.
+    pub fn num_deltas(&self) -> usize {
+        self.objects_per_chain_length
+            .iter()
+            .filter_map(|(len, count)| (*len > 0).then_some(*count))
+            .sum()
+    }
+}
+
+/// Statistics about a single loose object database.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Loose {
+    /// The path to the root directory of the loose object database.
+    pub path: PathBuf,
+    /// The amount of loose objects, by kind.
+    pub counts: Counts,
+    /// The amount of bytes all objects occupy when decompressed.
+    pub total_object_size: u64,
+}
+
+/// Returned by [`Store::statistics()`][crate::Store::statistics()].
+#[derive(Debug, Default, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// Statistics for each pack data file, including packs referred to by multi-pack indices.
+    pub packs: Vec<Pack>,
+    /// Statistics for each loose object database.
+    pub loose: Vec<Loose>,
+}
+
+impl Outcome {
+    /// Return the amount of objects by kind in all packs and loose object databases.
+    ///
+    /// Note that objects may be counted more than once if they are stored in more than one location.
+    pub fn counts(&self) -> Counts {
+        let mut out = Counts::default();
+        for counts in self
+            .packs
+            .iter()
+            .map(|p| p.counts)
+            .chain(self.loose.iter().map(|l| l.counts))
+        {
+            out += counts;
+        }
+        out
+    }
+}
+
+impl super::Store {
+    /// Count all objects by kind and gather statistics about each pack, like a histogram of delta-chain lengths,
+    /// similar to what `git count-objects -v` and `git verify-pack -v` provide.
+    ///
+    /// Only object headers are read, which is cheap for base objects, and requires decompressing only the beginning of
+    /// each delta in a chain to learn about the kind and size of delta objects.
+    /// `progress` is incremented for each inspected object, and `should_interrupt` allows stopping early.
+    ///
+    /// Note that this will not force loading all indices or packs permanently, as we will only use the momentarily loaded disk state.
+    /// This does, however, include all alternates.
+    pub fn statistics(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_features::trace::coarse!("gix_odb:Store::statistics()");
+        let mut index = self.index.load();
+        if !index.is_initialized() {
+            self.consolidate_with_disk_state(true, false)?;
+            index = self.index.load();
+            assert!(
+                index.is_initialized(),
+                "BUG: after consolidating successfully, we have an initialized index"
+            )
+        }
+
+        progress.init(None, gix_features::progress::count("objects"));
+        let mut inflate = zlib::Inflate::default();
+        let mut packs = Vec::new();
+        for slot_index in &index.slot_indices {
+            let slot = &self.files[*slot_index];
+            if slot.generation.load(Ordering::SeqCst) != index.generation {
+                return Err(Error::NeedsRetryDueToChangeOnDisk);
+            }
+            let files = slot.files.load();
+            let files = Option::as_ref(&files).ok_or(Error::NeedsRetryDueToChangeOnDisk)?;
+            match files {
+                IndexAndPacks::Index(bundle) => {
+                    let index;
+                    let index = match bundle.index.loaded() {
+                        Some(index) => index.deref(),
+                        None => {
+                            index = pack::index::File::at(bundle.index.path(), self.object_hash)?;
+                            &index
+                        }
+                    };
+                    let pack;
+                    let data = match bundle.data.loaded() {
+                        Some(pack) => pack.deref(),
+                        None => {
+                            pack = pack::data::File::at(bundle.data.path(), self.object_hash)?;
+                            &pack
+                        }
+                    };
+                    packs.push(pack_statistics(
+                        data,
+                        index.sorted_offsets(),
+                        &|id| index.lookup(id).map(|idx| index.pack_offset_at_index(idx)),
+                        &mut inflate,
+                        progress,
+                        should_interrupt,
+                    )?);
+                }
+                IndexAndPacks::MultiIndex(bundle) => {
+                    let index;
+                    let index = match bundle.multi_index.loaded() {
+                        Some(index) => index.deref(),
+                        None => {
+                            index = pack::multi_index::File::at(bundle.multi_index.path())?;
+                            &index
+                        }
+                    };
+                    let mut offsets_by_pack = vec![Vec::new(); index.num_indices() as usize];
+                    for entry in index.iter() {
+                        offsets_by_pack[entry.pack_index as usize].push(entry.pack_offset);
+                    }
+                    for (pack_index, (mut offsets, on_disk_pack)) in
+                        offsets_by_pack.into_iter().zip(bundle.data.iter()).enumerate()
+                    {
+                        let pack;
+                        let data = match on_disk_pack.loaded() {
+                            Some(pack) => pack.deref(),
+                            None => {
+                                pack = pack::data::File::at(on_disk_pack.path(), self.object_hash)?;
+                                &pack
+                            }
+                        };
+                        offsets.sort_unstable();
+                        packs.push(pack_statistics(
+                            data,
+                            offsets,
+                            &|id| {
+                                index.lookup(id).and_then(|idx| {
+                                    let (base_pack_index, pack_offset) = index.pack_id_and_pack_offset_at_index(idx);
+                                    (base_pack_index as usize == pack_index).then_some(pack_offset)
+                                })
+                            },
+                            &mut inflate,
+                            progress,
+                            should_interrupt,
+                        )?);
+                    }
+                }
+            }
+        }
+
+        let mut loose = Vec::new();
+        for loose_db in &*index.loose_dbs {
+            let mut stats = Loose {
+                path: loose_db.path().to_owned(),
+                ..Default::default()
+            };
+            for id in loose_db.iter() {
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+                // Objects that vanished in the mean time are ignored.
+                if let Some((size, kind)) = loose_db.try_header(&id?)? {
+                    stats.counts.add(kind);
+                    stats.total_object_size += size;
+                }
+                progress.inc();
+            }
+            loose.push(stats);
+        }
+        Ok(Outcome { packs, loose })
+    }
+}
+
+fn pack_statistics(
+    pack: &pack::data::File,
+    sorted_offsets: Vec<pack::data::Offset>,
+    pack_offset_by_id: &dyn Fn(&gix_hash::oid) -> Option<pack::data::Offset>,
+    inflate: &mut zlib::Inflate,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Pack, Error> {
+    let mut stats = Pack {
+        path: pack.path().to_owned(),
+        pack_size: pack.data_len() as u64,
+        ..Default::default()
+    };
+    let resolve = |id: &gix_hash::oid| {
+        pack_offset_by_id(id)
+            .and_then(|offset| pack.entry(offset).ok())
+            .map(pack::data::decode::header::ResolvedBase::InPack)
+    };
+    for offset in sorted_offsets {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        let header = pack.decode_header(pack.entry(offset)?, inflate, &resolve)?;
+        stats.counts.add(header.kind);
+        *stats.objects_per_chain_length.entry(header.num_deltas).or_insert(0) += 1;
+        stats.total_object_size += header.object_size;
+        progress.inc();
+    }
+    Ok(stats)
+}
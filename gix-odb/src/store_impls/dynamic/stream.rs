@@ -0,0 +1,126 @@
+use std::ops::Deref;
+
+use super::find::Error;
+use crate::{
+    store::{handle, load_index},
+    stream::Reader,
+};
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return a reader for the object identified by `id` which decompresses its data on the fly, or `None` if it doesn't exist.
+    ///
+    /// Loose objects and base objects in packs are streamed, so that objects larger than memory can be hashed or scanned.
+    /// Objects stored as delta are decoded into memory first as their delta-chain has to be resolved,
+    /// which [`Reader::is_streaming()`] indicates. This is typically acceptable as `git` doesn't deltify objects
+    /// larger than `core.bigFileThreshold`.
+    pub fn try_stream(&self, id: &gix_hash::oid) -> Result<Option<Reader>, Error> {
+        self.try_stream_cached(id, &mut gix_pack::cache::Never)
+    }
+
+    /// Like [`try_stream()`](Self::try_stream()), but use `pack_cache` to accelerate decoding objects stored as delta.
+    pub fn try_stream_cached(
+        &self,
+        id: &gix_hash::oid,
+        pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
+    ) -> Result<Option<Reader>, Error> {
+        let original_id = id;
+        let mut id = id;
+        if !self.ignore_replacements {
+            if let Ok(pos) = self
+                .store
+                .replacements
+                .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
+            {
+                id = self.store.replacements[pos].1.as_ref();
+            }
+        }
+
+        let mut snapshot = self.snapshot.borrow_mut();
+        match self.try_stream_inner(id, &mut snapshot)? {
+            Stream::Base(reader) => Ok(Some(reader)),
+            Stream::Missing => Ok(None),
+            Stream::Delta => {
+                let mut inflate = self.inflate.borrow_mut();
+                let mut buf = Vec::new();
+                let kind = match self.try_find_cached_inner(
+                    original_id,
+                    &mut buf,
+                    &mut inflate,
+                    pack_cache,
+                    &mut snapshot,
+                    None,
+                )? {
+                    Some((data, _location)) => data.kind,
+                    None => return Ok(None),
+                };
+                Ok(Some(Reader::from_buffer(kind, buf)))
+            }
+        }
+    }
+
+    fn try_stream_inner(&self, id: &gix_hash::oid, snapshot: &mut load_index::Snapshot) -> Result<Stream, Error> {
+        'outer: loop {
+            {
+                let marker = snapshot.marker;
+                for (idx, index) in snapshot.indices.iter_mut().enumerate() {
+                    if let Some(handle::index_lookup::Outcome {
+                        object_index: handle::IndexForObjectInPack { pack_id, pack_offset },
+                        index_file: _,
+                        pack: possibly_pack,
+                    }) = index.lookup(id)
+                    {
+                        let pack = match possibly_pack {
+                            Some(pack) => pack.clone(),
+                            None => match self.store.load_pack(pack_id, marker)? {
+                                Some(pack) => {
+                                    *possibly_pack = Some(pack.clone());
+                                    pack
+                                }
+                                None => match self.store.load_one_index(self.refresh, snapshot.marker)? {
+                                    Some(new_snapshot) => {
+                                        *snapshot = new_snapshot;
+                                        self.clear_cache();
+                                        continue 'outer;
+                                    }
+                                    None => return Ok(Stream::Missing),
+                                },
+                            },
+                        };
+                        let entry = pack.entry(pack_offset)?;
+                        if idx != 0 {
+                            snapshot.indices.swap(0, idx);
+                        }
+                        return Ok(if entry.header.is_base() {
+                            Stream::Base(Reader::from_pack(pack, &entry))
+                        } else {
+                            Stream::Delta
+                        });
+                    }
+                }
+            }
+
+            for lodb in snapshot.loose_dbs.iter() {
+                if let Some(reader) = lodb.try_stream(id)? {
+                    return Ok(Stream::Base(reader));
+                }
+            }
+
+            match self.store.load_one_index(self.refresh, snapshot.marker)? {
+                Some(new_snapshot) => {
+                    *snapshot = new_snapshot;
+                    self.clear_cache();
+                }
+                None => return Ok(Stream::Missing),
+            }
+        }
+    }
+}
+
+enum Stream {
+    Base(Reader),
+    Delta,
+    Missing,
+}
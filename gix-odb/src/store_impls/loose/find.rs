@@ -179,6 +179,42 @@ impl Store {
         Ok(Some((size, kind)))
     }
 
+    /// Return a reader for the object identified by `id` which decompresses its data on the fly, or `None` if it doesn't exist.
+    ///
+    /// This is useful for objects that are too large to be held in memory at once.
+    pub fn try_stream(&self, id: &gix_hash::oid) -> Result<Option<crate::stream::Reader>, Error> {
+        debug_assert_eq!(self.object_hash, id.kind());
+        let path = hash_path(id, self.path.clone());
+        let mut file = match fs::File::open(&path) {
+            Ok(f) => std::io::BufReader::new(f),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::Io {
+                    source: err,
+                    action: Self::OPEN_ACTION,
+                    path,
+                })
+            }
+        };
+
+        let mut inflate = zlib::Inflate::default();
+        let mut header_buf = [0_u8; HEADER_MAX_SIZE];
+        let bytes_read =
+            zlib::stream::inflate::read(&mut file, &mut inflate.state, &mut header_buf).map_err(|e| Error::Io {
+                source: e,
+                action: "deflate",
+                path: path.to_owned(),
+            })?;
+        let (kind, size, header_size) = gix_object::decode::loose_header(&header_buf[..bytes_read])?;
+        Ok(Some(crate::stream::Reader::from_loose(
+            kind,
+            size,
+            file,
+            inflate,
+            header_buf[header_size..bytes_read].to_vec(),
+        )))
+    }
+
     fn find_inner<'a>(&self, id: &gix_hash::oid, buf: &'a mut Vec<u8>) -> Result<gix_object::Data<'a>, Error> {
         let path = hash_path(id, self.path.clone());
 
@@ -1,19 +1,34 @@
 //! An object database storing each object in a zlib compressed file with its hash in the path
 /// The maximum size that an object header can have. `git2` says 64, and `git` says 32 but also mentions it can be larger.
 const HEADER_MAX_SIZE: usize = 64;
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use gix_features::fs;
 
 /// A database for reading and writing objects to disk, one file per object.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct Store {
     /// The directory in which objects are stored, containing 256 folders representing the hashes first byte.
     pub(crate) path: PathBuf,
     /// The kind of hash we should assume during iteration and when writing new objects.
     pub(crate) object_hash: gix_hash::Kind,
+    /// How to flush newly written objects to permanent storage, or `None` to not flush them.
+    pub(crate) fsync: Option<gix_fs::fsync::Method>,
+    /// The objects written in batch mode which still have to be flushed, possibly shared with other instances.
+    pub(crate) batch: Arc<gix_fs::fsync::Batch>,
 }
 
+impl PartialEq for Store {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.object_hash == other.object_hash && self.fsync == other.fsync
+    }
+}
+
+impl Eq for Store {}
+
 /// Initialization
 impl Store {
     /// Initialize the Db with the `objects_directory` containing the hexadecimal first byte subdirectories, which in turn
@@ -26,9 +41,20 @@ impl Store {
         Store {
             path: objects_directory.into(),
             object_hash,
+            fsync: None,
+            batch: Default::default(),
         }
     }
 
+    /// Flush newly written objects to permanent storage using `method` before moving them into place, or don't flush them
+    /// if `None`, which is the default.
+    ///
+    /// With [`Batch`](gix_fs::fsync::Method::Batch), objects are only flushed with [`flush_batch()`](Self::flush_batch()).
+    pub fn with_fsync(mut self, method: Option<gix_fs::fsync::Method>) -> Self {
+        self.fsync = method;
+        self
+    }
+
     /// Return the path to our `objects` directory.
     pub fn path(&self) -> &Path {
         &self.path
@@ -38,6 +64,14 @@ impl Store {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+
+    /// Flush all objects that were written in [batch mode](gix_fs::fsync::Method::Batch) since the last call to permanent
+    /// storage, and return their amount.
+    ///
+    /// This should be done before anything, like references, starts to point to them.
+    pub fn flush_batch(&self) -> std::io::Result<usize> {
+        self.batch.flush()
+    }
 }
 
 fn hash_path(id: &gix_hash::oid, mut root: PathBuf) -> PathBuf {
@@ -141,6 +141,14 @@ impl Store {
             }
         }
         let file = file.into_inner();
+        match self.fsync {
+            Some(gix_fs::fsync::Method::Batch) | None => {}
+            Some(method) => gix_fs::fsync::file(file.as_file(), method).map_err(|err| Error::Io {
+                source: err,
+                message: "flush object to storage in",
+                path: self.path.to_owned(),
+            })?,
+        }
         let res = file.persist(&object_path);
         // On windows, we assume that such errors are due to its special filesystem semantics,
         // on any other platform that would be a legitimate error though.
@@ -154,8 +162,11 @@ impl Store {
         }
         res.map_err(|err| Error::Persist {
             source: err,
-            target: object_path,
+            target: object_path.clone(),
         })?;
+        if self.fsync == Some(gix_fs::fsync::Method::Batch) {
+            self.batch.push(object_path);
+        }
         Ok(id)
     }
 }
@@ -0,0 +1,141 @@
+//! Read the data of objects without loading them into memory at once.
+use std::{
+    io::{BufRead, Read},
+    sync::Arc,
+};
+
+use gix_features::zlib;
+
+/// A reader for the data of a single object, which decompresses it on the fly instead of keeping all of it in memory.
+///
+/// Obtain it with [`Handle::try_stream()`](crate::store::Handle::try_stream()) or [`loose::Store::try_stream()`](crate::loose::Store::try_stream()).
+///
+/// Objects stored as delta in a pack can't be streamed as their delta-chain has to be resolved first, which is why
+/// they are fully decoded into memory. As `git` never stores objects larger than `core.bigFileThreshold` as delta, large objects
+/// will usually be streamed.
+pub struct Reader {
+    kind: gix_object::Kind,
+    size: u64,
+    /// The amount of bytes that are yet to be read.
+    remaining: u64,
+    inner: Inner,
+}
+
+pub(crate) enum Inner {
+    Loose {
+        file: std::io::BufReader<std::fs::File>,
+        inflate: zlib::Inflate,
+        /// Decompressed bytes that were read along with the header, and have yet to be returned.
+        pending: std::io::Cursor<Vec<u8>>,
+    },
+    Packed {
+        pack: Arc<gix_pack::data::File>,
+        /// The position of the next compressed byte to read in the pack.
+        data_offset: u64,
+        inflate: zlib::Inflate,
+    },
+    Buffered(std::io::Cursor<Vec<u8>>),
+}
+
+/// Access
+impl Reader {
+    /// The kind of the object we are reading.
+    pub fn kind(&self) -> gix_object::Kind {
+        self.kind
+    }
+
+    /// The size of the object in bytes, which is the amount of bytes that can be read.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Return `true` if the object is actually streamed, or `false` if it was fully decoded into memory before.
+    pub fn is_streaming(&self) -> bool {
+        !matches!(self.inner, Inner::Buffered(_))
+    }
+}
+
+/// Initialization
+impl Reader {
+    pub(crate) fn from_loose(
+        kind: gix_object::Kind,
+        size: u64,
+        file: std::io::BufReader<std::fs::File>,
+        inflate: zlib::Inflate,
+        pending: Vec<u8>,
+    ) -> Self {
+        Reader {
+            kind,
+            size,
+            remaining: size,
+            inner: Inner::Loose {
+                file,
+                inflate,
+                pending: std::io::Cursor::new(pending),
+            },
+        }
+    }
+
+    pub(crate) fn from_pack(pack: Arc<gix_pack::data::File>, entry: &gix_pack::data::Entry) -> Self {
+        Reader {
+            kind: entry.header.as_kind().expect("BUG: only base objects can be streamed"),
+            size: entry.decompressed_size,
+            remaining: entry.decompressed_size,
+            inner: Inner::Packed {
+                pack,
+                data_offset: entry.data_offset,
+                inflate: zlib::Inflate::default(),
+            },
+        }
+    }
+
+    pub(crate) fn from_buffer(kind: gix_object::Kind, data: Vec<u8>) -> Self {
+        Reader {
+            kind,
+            size: data.len() as u64,
+            remaining: data.len() as u64,
+            inner: Inner::Buffered(std::io::Cursor::new(data)),
+        }
+    }
+}
+
+impl Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let buf_len = buf.len().min(self.remaining.try_into().unwrap_or(usize::MAX));
+        let buf = &mut buf[..buf_len];
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let bytes_read = match &mut self.inner {
+            Inner::Loose { file, inflate, pending } => {
+                if pending.fill_buf()?.is_empty() {
+                    zlib::stream::inflate::read(file, &mut inflate.state, buf)?
+                } else {
+                    pending.read(buf)?
+                }
+            }
+            Inner::Packed {
+                pack,
+                data_offset,
+                inflate,
+            } => {
+                let mut input = pack.entry_slice(*data_offset..pack.pack_end() as u64).ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "pack entry out of bounds")
+                })?;
+                let before = input.len();
+                let written = zlib::stream::inflate::read(&mut input, &mut inflate.state, buf)?;
+                *data_offset += (before - input.len()) as u64;
+                written
+            }
+            Inner::Buffered(data) => data.read(buf)?,
+        };
+        if bytes_read == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("object data ended {} bytes early", self.remaining),
+            ));
+        }
+        self.remaining -= bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
@@ -0,0 +1,74 @@
+use gix_object::{Exists, Find, FindExt, FindHeader};
+use gix_odb::{HeaderExt, Write};
+
+use crate::{
+    hex_to_id,
+    store::loose::{locate_oid, object_ids},
+};
+
+#[test]
+fn write_and_read_without_backing_store() -> crate::Result {
+    let db = gix_odb::memory(gix_hash::Kind::Sha1);
+    let mut buf = Vec::new();
+    let mut buf2 = Vec::new();
+    for oid in object_ids() {
+        let obj = locate_oid(oid, &mut buf);
+        let actual = db.write(&obj.decode()?)?;
+        assert_eq!(actual, oid, "ids are the same as for all other object databases");
+        assert!(db.exists(&oid));
+
+        let found = db.find(&oid, &mut buf2)?;
+        assert_eq!(found.kind, obj.kind);
+        assert_eq!(found.data, obj.data);
+        assert_eq!(
+            FindHeader::try_header(&db, &oid)?.map(|h| h.size),
+            Some(obj.data.len() as u64)
+        );
+        assert_eq!(db.header(oid)?.kind(), obj.kind);
+    }
+    assert_eq!(db.num_objects_in_memory(), object_ids().len());
+
+    let missing = hex_to_id("0000000000000000000000000000000000000001");
+    assert!(!db.exists(&missing));
+    assert!(db.try_find(&missing, &mut buf)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn layered_over_disk_store_is_copy_on_write() -> crate::Result {
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let disk = gix_odb::at(dir.path())?;
+    let blob_on_disk = disk.write_buf(gix_object::Kind::Blob, b"on disk")?;
+
+    let db = gix_odb::memory::Proxy::new(disk, gix_hash::Kind::Sha1);
+    let mut buf = Vec::new();
+    assert_eq!(
+        db.find_blob(&blob_on_disk, &mut buf)?.data,
+        b"on disk",
+        "objects are read from the backing store"
+    );
+    assert_eq!(db.header(blob_on_disk)?.size(), 7);
+
+    let blob_in_memory = db.write_buf(gix_object::Kind::Blob, b"in memory")?;
+    assert!(db.exists(&blob_in_memory));
+    assert_eq!(db.find_blob(&blob_in_memory, &mut buf)?.data, b"in memory");
+    assert!(
+        !std::ops::Deref::deref(&db).exists(&blob_in_memory),
+        "the backing store is never written to"
+    );
+
+    let objects = db.take_object_memory();
+    assert_eq!(objects.len(), 1);
+    assert!(!db.exists(&blob_in_memory), "taken objects are gone");
+
+    for (kind, data) in objects.values() {
+        db.write_buf(*kind, data)?;
+    }
+    assert_eq!(db.num_objects_in_memory(), 1, "objects can be restored");
+    let disk = db.into_inner();
+    assert!(
+        !disk.exists(&blob_in_memory),
+        "objects are dropped along with the proxy"
+    );
+    Ok(())
+}
@@ -59,6 +59,7 @@ fn multi_index_access() -> crate::Result {
         },
         "it starts out knowing nothing, it's completely lazy"
     );
+    assert_eq!(handle.store_ref().mapped_bytes(), 0, "nothing is mapped yet");
 
     for order in all_orderings() {
         let mut count = 0;
@@ -91,6 +92,12 @@ fn multi_index_access() -> crate::Result {
         },
         "it opened only a single multi-index and its pack - hard to see it's actually a multi-index as it's just one index anyway…"
     );
+    let mapped_bytes = handle.store_ref().mapped_bytes();
+    let multi_index_len = std::fs::metadata(handle.store_ref().path().join("pack/multi-pack-index"))?.len();
+    assert!(
+        mapped_bytes > multi_index_len,
+        "the multi-index and all of its packs are mapped"
+    );
 
     let non_existing_to_trigger_refresh = hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
     handle.exists(&non_existing_to_trigger_refresh);
@@ -221,6 +228,30 @@ fn write() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn write_in_batch_mode_defers_flushing_to_the_store() -> crate::Result {
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let handle = gix_odb::at_opts(
+        dir.path(),
+        None,
+        store::init::Options {
+            fsync_loose_objects: Some(gix_fs::fsync::Method::Batch),
+            ..Default::default()
+        },
+    )?;
+    handle.write_buf(gix_object::Kind::Blob, b"hello world")?;
+
+    let arc_handle = handle.clone().into_arc()?;
+    arc_handle.write_buf(gix_object::Kind::Blob, b"hello")?;
+    assert_eq!(
+        handle.store_ref().flush_batch()?,
+        2,
+        "thread-safe handles share the batch with the store they were created from"
+    );
+    assert_eq!(arc_handle.store_ref().flush_batch()?, 0);
+    Ok(())
+}
+
 #[test]
 fn alternate_dbs_query() -> crate::Result {
     let dir = gix_testtools::scripted_fixture_read_only_standalone("make_alternates_odb.sh")?;
@@ -241,6 +272,29 @@ fn alternate_dbs_query() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn extra_alternates_are_used_along_with_their_own_alternates() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_alternates_odb.sh")?;
+    let objects_dir = gix_testtools::tempfile::tempdir()?;
+    let handle = gix_odb::at_opts(
+        objects_dir.path(),
+        None,
+        store::init::Options {
+            alternates: vec![dir.join(".git/objects")],
+            ..Default::default()
+        },
+    )?;
+
+    let alternates = handle.store_ref().alternate_db_paths()?;
+    assert_eq!(alternates.len(), 2, "the extra alternate and the one it refers to");
+    assert!(alternates[0].ends_with(dir.join(".git/objects")));
+    assert!(alternates[1].ends_with("object_source/.git/objects"));
+
+    let head = ObjectId::from_hex(&std::fs::read(dir.join(".git/refs/heads/main"))?[..40])?;
+    assert!(handle.exists(&head), "objects of the extra alternate are available");
+    Ok(())
+}
+
 #[test]
 fn object_replacement() -> crate::Result {
     let dir = gix_testtools::scripted_fixture_read_only_standalone("make_replaced_history.sh")?;
@@ -651,6 +705,119 @@ mod iter {
         }
         Ok(())
     }
+
+    #[test]
+    fn kinds_can_be_filtered() -> crate::Result {
+        use gix_object::{FindExt, Kind};
+        for (handle, _tmp) in [db_with_all_object_sources().map(|(a, b)| (a, Some(b)))?, (db(), None)] {
+            let all = handle.iter()?.count();
+            let mut total = 0;
+            for kind in [Kind::Commit, Kind::Tree, Kind::Blob, Kind::Tag] {
+                for res in handle.iter_kinds(&[kind])? {
+                    let (id, header) = res?;
+                    assert_eq!(header.kind(), kind);
+                    assert_eq!(handle.find(&id, &mut Vec::new())?.kind, kind);
+                    total += 1;
+                }
+            }
+            assert_eq!(total, all, "each object has exactly one kind");
+            assert_eq!(
+                handle.iter_kinds(&[Kind::Commit, Kind::Tag])?.count(),
+                handle.iter_kinds(&[Kind::Commit])?.count() + handle.iter_kinds(&[Kind::Tag])?.count(),
+                "multiple kinds can be selected at once"
+            );
+        }
+        Ok(())
+    }
+}
+
+mod statistics {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_object::Kind;
+
+    use crate::odb::{db, store::dynamic::db_with_all_object_sources};
+
+    #[test]
+    fn counts_match_iteration_and_packs_have_chain_histograms() -> crate::Result {
+        for (handle, _tmp) in [db_with_all_object_sources().map(|(a, b)| (a, Some(b)))?, (db(), None)] {
+            let stats = handle
+                .store_ref()
+                .statistics(&mut gix_features::progress::Discard, &AtomicBool::default())?;
+            let counts = stats.counts();
+            assert_eq!(counts.total(), handle.iter()?.count(), "all objects are counted");
+            for kind in [Kind::Commit, Kind::Tree, Kind::Blob, Kind::Tag] {
+                assert_eq!(counts.of_kind(kind), handle.iter_kinds(&[kind])?.count());
+            }
+            assert!(!stats.packs.is_empty() && !stats.loose.is_empty());
+            for pack in &stats.packs {
+                assert_eq!(
+                    pack.objects_per_chain_length.values().sum::<usize>(),
+                    pack.counts.total(),
+                    "each object has a chain length"
+                );
+                assert!(pack.pack_size > 0);
+                assert!(pack.total_object_size > 0);
+            }
+            assert!(
+                stats.packs.iter().any(|p| p.num_deltas() > 0),
+                "our packs contain delta objects"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_interrupted() -> crate::Result {
+        let stats = db()
+            .store_ref()
+            .statistics(&mut gix_features::progress::Discard, &AtomicBool::new(true));
+        assert!(matches!(stats, Err(gix_odb::store::statistics::Error::Interrupted)));
+        Ok(())
+    }
+}
+
+mod stream {
+    use std::io::Read;
+
+    use gix_object::FindExt;
+
+    use crate::odb::{db, hex_to_id, store::dynamic::db_with_all_object_sources};
+
+    #[test]
+    fn all_objects_can_be_streamed_with_the_same_data_as_when_found() -> crate::Result {
+        for (handle, _tmp) in [db_with_all_object_sources().map(|(a, b)| (a, Some(b)))?, (db(), None)] {
+            let (mut buf, mut streamed) = (Vec::new(), Vec::new());
+            let (mut num_streaming, mut num_buffered) = (0, 0);
+            for id in handle.iter()? {
+                let id = id?;
+                let mut reader = handle.try_stream(&id)?.expect("object exists");
+                let obj = handle.find(&id, &mut buf)?;
+                assert_eq!(reader.kind(), obj.kind);
+                assert_eq!(reader.size(), obj.data.len() as u64);
+
+                streamed.clear();
+                reader.read_to_end(&mut streamed)?;
+                assert_eq!(streamed, obj.data, "{id}: streamed data matches");
+                if reader.is_streaming() {
+                    num_streaming += 1;
+                } else {
+                    num_buffered += 1;
+                }
+            }
+            assert!(num_streaming > 0, "loose objects and packed base objects are streamed");
+            assert!(num_buffered > 0, "deltified objects are decoded into memory");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn missing_objects_yield_none() -> crate::Result {
+        assert!(db()
+            .try_stream(&hex_to_id("0000000000000000000000000000000000000001"))?
+            .is_none());
+        Ok(())
+    }
 }
 
 mod lookup_prefix {
@@ -74,6 +74,27 @@ mod write {
         Ok(())
     }
 
+    #[test]
+    fn objects_can_be_flushed_individually_or_in_batches() -> crate::Result {
+        use gix_fs::fsync::Method;
+
+        let dir = gix_testtools::tempfile::tempdir()?;
+        for method in [Method::Fsync, Method::WriteoutOnly] {
+            let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1).with_fsync(Some(method));
+            let id = db.write_buf(gix_object::Kind::Blob, b"flushed right away")?;
+            assert!(db.contains(&id));
+            assert_eq!(db.flush_batch()?, 0, "nothing is deferred");
+        }
+
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1).with_fsync(Some(Method::Batch));
+        let a = db.write_buf(gix_object::Kind::Blob, b"a")?;
+        db.write_buf(gix_object::Kind::Blob, b"b")?;
+        assert!(db.contains(&a), "objects are available right away");
+        assert_eq!(db.flush_batch()?, 2, "objects are flushed once the batch is complete");
+        assert_eq!(db.flush_batch()?, 0);
+        Ok(())
+    }
+
     #[test]
     #[cfg(unix)]
     fn it_writes_objects_with_similar_permissions() -> crate::Result {
@@ -263,6 +284,7 @@ mod find {
             name: b"1.0.0".as_bstr(),
             target_kind: Kind::Commit,
             message: b"for the signature".as_bstr(),
+            malformed_tagger: None,
             pgp_signature: Some(
                 b"-----BEGIN PGP SIGNATURE-----
 Comment: GPGTools - https://gpgtools.org
@@ -301,6 +323,8 @@ cjHJZXWmV4CcRfmLsXzU8s2cR9A0DBvOxhPD1TlKC2JhBFXigjuL9U4Rbq9tdegB
             parents: vec![].into(),
             author: signature(1528473303),
             committer: signature(1528473303),
+            malformed_author: None,
+            malformed_committer: None,
             encoding: None,
             message: b"initial commit\n".as_bstr(),
             extra_headers: vec![(b"gpgsig".as_bstr(), b"-----BEGIN PGP SIGNATURE-----\nComment: GPGTools - https://gpgtools.org\n\niQIzBAABCgAdFiEEw7xSvXbiwjusbsBqZl+Z+p2ZlmwFAlsaptwACgkQZl+Z+p2Z\nlmxXSQ//fj6t7aWoEKeMdFigfj6OXWPUyrRbS0N9kpJeOfA0BIOea/6Jbn8J5qh1\nYRfrySOzHPXR5Y+w4GwLiVas66qyhAbk4yeqZM0JxBjHDyPyRGhjUd3y7WjEa6bj\nP0ACAIkYZQ/Q/LDE3eubmhAwEobBH3nZbwE+/zDIG0i265bD5C0iDumVOiKkSelw\ncr6FZVw1HH+GcabFkeLRZLNGmPqGdbeBwYERqb0U1aRCzV1xLYteoKwyWcYaH8E3\n97z1rwhUO/L7o8WUEJtP3CLB0zuocslMxskf6bCeubBnRNJ0YrRmxGarxCP3vn4D\n3a/MwECnl6mnUU9t+OnfvrzLDN73rlq8iasUq6hGe7Sje7waX6b2UGpxHqwykmXg\nVimD6Ah7svJanHryfJn38DvJW/wOMqmAnSUAp+Y8W9EIe0xVntCmtMyoKuqBoY7T\nJlZ1kHJte6ELIM5JOY9Gx7D0ZCSKZJQqyjoqtl36dsomT0I78/+7QS1DP4S6XB7d\nc3BYH0JkW81p7AAFbE543ttN0Z4wKXErMFqUKnPZUIEuybtlNYV+krRdfDBWQysT\n3MBebjguVQ60oGs06PzeYBosKGQrHggAcwduLFuqXhLTJqN4UQ18RkE0vbtG3YA0\n+XtZQM13vURdfwFI5qitAGgw4EzPVrkWWzApzLCrRPEMbvP+b9A=\n=2qqN\n-----END PGP SIGNATURE-----".as_bstr().into())]
@@ -0,0 +1,163 @@
+use gix_features::zlib;
+use gix_hash::ObjectId;
+
+use crate::{data, data::decode::header::ResolvedBase, Bundle};
+
+/// The error returned by the [`Iter`] iterator.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Entry(#[from] data::entry::decode::Error),
+    #[error(transparent)]
+    Header(#[from] data::decode::Error),
+}
+
+/// The base object of a delta entry.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Base {
+    /// The base is referred to by its offset in the same pack, as with `OFS_DELTA` entries.
+    Offset {
+        /// The offset of the base entry in the pack.
+        pack_offset: data::Offset,
+        /// The id of the base object, as found in the index.
+        id: ObjectId,
+    },
+    /// The base is referred to by its id, as with `REF_DELTA` entries.
+    Ref {
+        /// The id of the base object.
+        id: ObjectId,
+        /// The offset of the base entry if it is contained in the same pack.
+        pack_offset: Option<data::Offset>,
+    },
+}
+
+impl Base {
+    /// Return the id of the base object.
+    pub fn id(&self) -> &gix_hash::oid {
+        match self {
+            Base::Offset { id, .. } | Base::Ref { id, .. } => id,
+        }
+    }
+}
+
+/// Information about a single entry in a pack, similar to what `git verify-pack -v` displays.
+#[derive(Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// The id of the object stored in the entry.
+    pub id: ObjectId,
+    /// The offset at which the entry starts in the pack.
+    pub pack_offset: data::Offset,
+    /// The kind of the object, after resolving all deltas.
+    pub kind: gix_object::Kind,
+    /// The size of the object in bytes, after resolving all deltas.
+    pub object_size: u64,
+    /// The size of the decompressed data of the entry, which is the delta itself for delta entries.
+    pub decompressed_size: u64,
+    /// The amount of bytes the entry occupies in the pack, including its header.
+    pub compressed_size: u64,
+    /// The base this entry is a delta against, or `None` if it is a base object.
+    pub base: Option<Base>,
+    /// The amount of deltas that have to be applied to obtain the object, or 0 if it is a base object.
+    pub chain_length: u32,
+}
+
+/// An iterator over all entries of a pack in the order they appear in the pack, created with [`Bundle::inspect()`].
+pub struct Iter<'a> {
+    bundle: &'a Bundle,
+    /// `(pack_offset, index_entry)` tuples sorted by offset.
+    entries: Vec<(data::Offset, u32)>,
+    pos: usize,
+    inflate: zlib::Inflate,
+}
+
+impl Bundle {
+    /// Return an iterator over information about each entry in our pack in the order they are stored, along with their
+    /// delta-chain information, similar to `git verify-pack -v`.
+    ///
+    /// Only entry headers are read, and objects are never fully decoded.
+    pub fn inspect(&self) -> Iter<'_> {
+        let mut entries: Vec<_> = (0..self.index.num_objects())
+            .map(|idx| (self.index.pack_offset_at_index(idx), idx))
+            .collect();
+        entries.sort_unstable_by_key(|(offset, _)| *offset);
+        Iter {
+            bundle: self,
+            entries,
+            pos: 0,
+            inflate: zlib::Inflate::default(),
+        }
+    }
+}
+
+impl<'a> Iter<'a> {
+    fn entry_at(&mut self, pos: usize) -> Result<Entry, Error> {
+        let (pack_offset, index_entry) = self.entries[pos];
+        let (index, pack) = (&self.bundle.index, &self.bundle.pack);
+        let entry = pack.entry(pack_offset)?;
+        let end = self
+            .entries
+            .get(pos + 1)
+            .map_or(pack.pack_end() as u64, |(next_offset, _)| *next_offset);
+        let base = match entry.header {
+            data::entry::Header::OfsDelta { base_distance } => {
+                let base_offset = entry.base_pack_offset(base_distance);
+                let id = self
+                    .entries
+                    .binary_search_by_key(&base_offset, |(offset, _)| *offset)
+                    .map(|base_pos| index.oid_at_index(self.entries[base_pos].1).to_owned())
+                    .map_err(|_| {
+                        data::decode::Error::DeltaBaseUnresolved(index.oid_at_index(index_entry).to_owned())
+                    })?;
+                Some(Base::Offset {
+                    pack_offset: base_offset,
+                    id,
+                })
+            }
+            data::entry::Header::RefDelta { base_id } => Some(Base::Ref {
+                id: base_id,
+                pack_offset: index.lookup(base_id).map(|idx| index.pack_offset_at_index(idx)),
+            }),
+            _ => None,
+        };
+        let decompressed_size = entry.decompressed_size;
+        let header = pack.decode_header(entry, &mut self.inflate, &|id| {
+            index
+                .lookup(id)
+                .and_then(|idx| pack.entry(index.pack_offset_at_index(idx)).ok())
+                .map(ResolvedBase::InPack)
+        })?;
+        Ok(Entry {
+            id: index.oid_at_index(index_entry).to_owned(),
+            pack_offset,
+            kind: header.kind,
+            object_size: header.object_size,
+            decompressed_size,
+            compressed_size: end - pack_offset,
+            base,
+            chain_length: header.num_deltas,
+        })
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.entries.len() {
+            return None;
+        }
+        let res = self.entry_at(self.pos);
+        self.pos += 1;
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.entries.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
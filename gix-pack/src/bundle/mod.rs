@@ -4,6 +4,9 @@ pub mod init;
 
 mod find;
 ///
+#[allow(clippy::empty_docs)]
+pub mod inspect;
+///
 #[cfg(all(not(feature = "wasm"), feature = "streaming-input"))]
 pub mod write;
 
@@ -211,6 +211,14 @@ impl crate::Bundle {
                     thin_pack_lookup,
                 );
                 let pack_kind = pack_entries_iter.inner.version();
+                let pack_entries_iter = data::input::EntriesToBytesIter::new(
+                    pack_entries_iter,
+                    LockWriter {
+                        writer: data_file.clone(),
+                    },
+                    pack_kind,
+                    gix_hash::Kind::Sha1, // Thin packs imply a pack being transported, and there we only ever know SHA1 at the moment.
+                );
                 (Box::new(pack_entries_iter), pack_kind)
             }
             None => {
@@ -269,6 +277,8 @@ impl crate::Bundle {
             iteration_mode: _,
             index_version: index_kind,
             object_hash,
+            fsync_pack,
+            fsync_index,
         }: Options,
         data_file: SharedTempFile,
         mut pack_entries_iter: Box<dyn Iterator<Item = Result<data::input::Entry, data::input::Error>> + 'a>,
@@ -315,18 +325,23 @@ impl crate::Bundle {
                         // and this is likely to fail on Windows as negotiation opened the pack.
                         None
                     } else {
-                        let keep_path = data_path.with_extension("keep");
-
-                        std::fs::write(&keep_path, b"")?;
-                        Arc::try_unwrap(data_file)
+                        let keep_path = crate::keep::create(&data_path, b"")?;
+                        let mut data_file = Arc::try_unwrap(data_file)
                             .expect("only one handle left after pack was consumed")
                             .into_inner()
                             .into_inner()
-                            .map_err(|err| Error::from(err.into_error()))?
-                            .persist(&data_path)?;
-                        Some(keep_path)
+                            .map_err(|err| Error::from(err.into_error()))?;
+                        if let Some(method) = fsync_pack {
+                            data_file.with_mut(|file| gix_fs::fsync::file(file.as_file(), method))??;
+                        }
+                        data_file.persist(&data_path)?;
+                        keep_path
                     };
                     if !index_path.is_file() {
+                        if let Some(method) = fsync_index {
+                            index_file.flush()?;
+                            index_file.with_mut(|file| gix_fs::fsync::file(file.as_file(), method))??;
+                        }
                         index_file
                             .persist(&index_path)
                             .map_err(|err| {
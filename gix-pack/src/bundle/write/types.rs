@@ -14,6 +14,10 @@ pub struct Options {
     pub index_version: crate::index::Version,
     /// The kind of hash to use when writing the bundle.
     pub object_hash: gix_hash::Kind,
+    /// How to flush the pack data file to permanent storage before moving it into place, or `None` to not flush it.
+    pub fsync_pack: Option<gix_fs::fsync::Method>,
+    /// How to flush the pack index file to permanent storage before moving it into place, or `None` to not flush it.
+    pub fsync_index: Option<gix_fs::fsync::Method>,
 }
 
 impl Default for Options {
@@ -24,6 +28,8 @@ impl Default for Options {
             iteration_mode: crate::data::input::Mode::Verify,
             index_version: Default::default(),
             object_hash: Default::default(),
+            fsync_pack: None,
+            fsync_index: None,
         }
     }
 }
@@ -46,7 +52,7 @@ pub struct Outcome {
     pub data_path: Option<PathBuf>,
     /// The path to the `.keep` file to prevent collection of the newly written pack until refs are pointing to it.
     /// It might be `None` if the file at `data_path` already existed, indicating that we have received a pack that
-    /// was already present locally.
+    /// was already present locally, or if the `.keep` file already existed and thus must not be removed by the caller.
     ///
     /// The file is created right before moving the pack data and index data into place (i.e. `data_path` and `index_path`)
     /// and is expected to be removed by the caller when ready.
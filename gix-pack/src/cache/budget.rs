@@ -0,0 +1,289 @@
+//! Account for the memory used by any amount of caches, possibly across threads, and keep it below a soft limit.
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::cache;
+
+#[derive(Debug, Default)]
+struct Shared {
+    used: AtomicUsize,
+    soft_limit: AtomicUsize,
+    evictions: AtomicUsize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+/// A tally of the bytes held by all [tracked](Tracked) caches sharing it, along with an optional soft limit.
+///
+/// Clones share the same tally, so it can be handed to cache instances created on different threads.
+/// The limit is _soft_ as it's only enforced after an entry was added, by clearing the cache that added it.
+#[derive(Debug, Default, Clone)]
+pub struct Budget {
+    shared: Arc<Shared>,
+}
+
+/// Lifecycle
+impl Budget {
+    /// Create a new instance which makes caches evict their entries once all of them together use more than `soft_limit` bytes,
+    /// or which never causes eviction if `None`.
+    pub fn new(soft_limit: impl Into<Option<usize>>) -> Self {
+        let budget = Budget::default();
+        budget.set_soft_limit(soft_limit);
+        budget
+    }
+}
+
+/// Access
+impl Budget {
+    /// Return the amount of bytes currently held by all caches using this budget.
+    pub fn used(&self) -> usize {
+        self.shared.used.load(Ordering::Relaxed)
+    }
+
+    /// Return the soft limit in bytes, or `None` if there is none.
+    pub fn soft_limit(&self) -> Option<usize> {
+        match self.shared.soft_limit.load(Ordering::Relaxed) {
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Set the soft limit to `limit` bytes, or remove it with `None`. A limit of `0` is the same as `None`.
+    ///
+    /// Note that it takes effect the next time an entry is added to a cache.
+    pub fn set_soft_limit(&self, limit: impl Into<Option<usize>>) {
+        self.shared
+            .soft_limit
+            .store(limit.into().unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    /// Return `true` if the soft limit is set and exceeded.
+    pub fn is_exceeded(&self) -> bool {
+        match self.soft_limit() {
+            Some(limit) => self.used() > limit,
+            None => false,
+        }
+    }
+
+    /// Return how often a cache was cleared because the soft limit was exceeded.
+    pub fn evictions(&self) -> usize {
+        self.shared.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Return how often an entry was found in one of the caches.
+    pub fn hits(&self) -> usize {
+        self.shared.hits.load(Ordering::Relaxed)
+    }
+
+    /// Return how often an entry was looked up in one of the caches, but wasn't found.
+    pub fn misses(&self) -> usize {
+        self.shared.misses.load(Ordering::Relaxed)
+    }
+
+    /// Record that `bytes` were allocated by a cache.
+    pub fn allocate(&self, bytes: usize) {
+        self.shared.used.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Record that `bytes` previously [allocated](Self::allocate()) were released by a cache.
+    pub fn release(&self, bytes: usize) {
+        self.shared.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+/// A cache that reports its [memory usage](cache::DecodeEntry::memory_usage()) to a [`Budget`], and clears itself
+/// after adding an entry if the budget is [exceeded](Budget::is_exceeded()).
+///
+/// It can wrap implementations of [`cache::DecodeEntry`] as well as [`cache::Object`].
+pub struct Tracked<T> {
+    inner: T,
+    budget: Budget,
+    /// The amount of bytes we last reported to `budget`.
+    held: usize,
+}
+
+/// Lifecycle
+impl<T> Tracked<T> {
+    /// Wrap `inner` so it reports its memory usage to `budget`.
+    pub fn new(inner: T, budget: Budget) -> Self {
+        Tracked { inner, budget, held: 0 }
+    }
+}
+
+/// Access
+impl<T> Tracked<T> {
+    /// Return the budget we report to.
+    pub fn budget(&self) -> &Budget {
+        &self.budget
+    }
+
+    /// Return the wrapped cache.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> Tracked<T> {
+    fn update(&mut self, now_held: usize, clear: impl FnOnce(&mut T) -> usize) {
+        self.account(now_held);
+        if self.held != 0 && self.budget.is_exceeded() {
+            let now_held = clear(&mut self.inner);
+            self.account(now_held);
+            self.budget.shared.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn record_lookup<V>(&self, res: Option<V>) -> Option<V> {
+        let counter = if res.is_some() {
+            &self.budget.shared.hits
+        } else {
+            &self.budget.shared.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        res
+    }
+
+    fn account(&mut self, now_held: usize) {
+        if now_held > self.held {
+            self.budget.allocate(now_held - self.held);
+        } else {
+            self.budget.release(self.held - now_held);
+        }
+        self.held = now_held;
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.budget.release(self.held);
+    }
+}
+
+impl<T: cache::DecodeEntry> cache::DecodeEntry for Tracked<T> {
+    fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: gix_object::Kind, compressed_size: usize) {
+        self.inner.put(pack_id, offset, data, kind, compressed_size);
+        self.update(self.inner.memory_usage(), |inner| {
+            cache::DecodeEntry::clear(inner);
+            cache::DecodeEntry::memory_usage(inner)
+        });
+    }
+
+    fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(gix_object::Kind, usize)> {
+        let res = self.inner.get(pack_id, offset, out);
+        self.record_lookup(res)
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.account(self.inner.memory_usage());
+    }
+}
+
+impl<T: cache::Object> cache::Object for Tracked<T> {
+    fn put(&mut self, id: gix_hash::ObjectId, kind: gix_object::Kind, data: &[u8]) {
+        self.inner.put(id, kind, data);
+        self.update(self.inner.memory_usage(), |inner| {
+            cache::Object::clear(inner);
+            cache::Object::memory_usage(inner)
+        });
+    }
+
+    fn get(&mut self, id: &gix_hash::ObjectId, out: &mut Vec<u8>) -> Option<gix_object::Kind> {
+        let res = self.inner.get(id, out);
+        self.record_lookup(res)
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.account(self.inner.memory_usage());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::DecodeEntry;
+
+    #[derive(Default)]
+    struct Unbounded(Vec<Vec<u8>>);
+
+    impl DecodeEntry for Unbounded {
+        fn put(&mut self, _pack_id: u32, _offset: u64, data: &[u8], _kind: gix_object::Kind, _compressed_size: usize) {
+            self.0.push(data.to_owned());
+        }
+
+        fn get(&mut self, _pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(gix_object::Kind, usize)> {
+            let data = self.0.get(offset as usize)?;
+            out.clone_from(data);
+            Some((gix_object::Kind::Blob, 1))
+        }
+
+        fn memory_usage(&self) -> usize {
+            self.0.iter().map(Vec::len).sum()
+        }
+
+        fn clear(&mut self) {
+            self.0.clear();
+        }
+    }
+
+    #[test]
+    fn usage_is_shared_and_released_on_drop() {
+        let budget = Budget::new(None);
+        let mut a = Tracked::new(Unbounded::default(), budget.clone());
+        let mut b = Tracked::new(Unbounded::default(), budget.clone());
+        a.put(0, 0, &[0; 10], gix_object::Kind::Blob, 1);
+        b.put(0, 0, &[0; 5], gix_object::Kind::Blob, 1);
+        assert_eq!(budget.used(), 15);
+        assert!(!budget.is_exceeded(), "there is no limit");
+
+        a.clear();
+        assert_eq!(budget.used(), 5, "clearing is accounted for");
+        drop(b);
+        assert_eq!(budget.used(), 0, "dropping releases everything");
+        assert_eq!(budget.evictions(), 0);
+    }
+
+    #[test]
+    fn exceeding_the_soft_limit_clears_the_cache_that_added_the_entry() {
+        let budget = Budget::new(20);
+        let mut a = Tracked::new(Unbounded::default(), budget.clone());
+        let mut b = Tracked::new(Unbounded::default(), budget.clone());
+        a.put(0, 0, &[0; 15], gix_object::Kind::Blob, 1);
+        b.put(0, 0, &[0; 5], gix_object::Kind::Blob, 1);
+        assert_eq!(budget.used(), 20, "reaching the limit is fine");
+
+        b.put(0, 1, &[0; 1], gix_object::Kind::Blob, 1);
+        assert_eq!(budget.used(), 15, "b was cleared even though a holds more");
+        assert_eq!(b.memory_usage(), 0);
+        assert_eq!(budget.evictions(), 1);
+
+        budget.set_soft_limit(None);
+        b.put(0, 0, &[0; 50], gix_object::Kind::Blob, 1);
+        assert_eq!(budget.used(), 65, "without limit, there is no eviction");
+        assert_eq!(budget.evictions(), 1);
+    }
+
+    #[test]
+    fn lookups_are_counted_as_hits_or_misses() {
+        let budget = Budget::new(None);
+        let mut a = Tracked::new(Unbounded::default(), budget.clone());
+        let mut out = Vec::new();
+        assert!(a.get(0, 0, &mut out).is_none());
+        a.put(0, 0, &[1, 2, 3], gix_object::Kind::Blob, 1);
+        assert!(a.get(0, 0, &mut out).is_some());
+        assert!(a.get(0, 1, &mut out).is_none());
+        assert_eq!(budget.hits(), 1);
+        assert_eq!(budget.misses(), 2);
+    }
+}
@@ -77,6 +77,15 @@ mod memory {
             }
             res
         }
+
+        fn memory_usage(&self) -> usize {
+            self.inner.weight() + self.free_list.iter().map(Vec::capacity).sum::<usize>()
+        }
+
+        fn clear(&mut self) {
+            self.inner.clear();
+            self.free_list = Vec::new();
+        }
     }
 }
 
@@ -182,6 +191,16 @@ mod _static {
             }
             res
         }
+
+        fn memory_usage(&self) -> usize {
+            self.mem_used
+        }
+
+        fn clear(&mut self) {
+            self.inner.clear();
+            self.last_evicted = Vec::new();
+            self.mem_used = 0;
+        }
     }
 
     #[cfg(test)]
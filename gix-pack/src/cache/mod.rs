@@ -1,4 +1,4 @@
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 
 use gix_object::Kind;
 
@@ -13,6 +13,14 @@ pub trait DecodeEntry {
     /// Attempt to fetch the object at `offset` and store its decoded bytes in `out`, as previously stored with [`DecodeEntry::put()`], and return
     /// its (object `kind`, `decompressed_size`)
     fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(gix_object::Kind, usize)>;
+    /// Return the approximate amount of bytes held by this cache, including allocations kept for reuse.
+    ///
+    /// Implementations that don't track their memory usage report `0`.
+    fn memory_usage(&self) -> usize {
+        0
+    }
+    /// Remove all entries and release the memory held by them.
+    fn clear(&mut self) {}
 }
 
 /// A cache that stores nothing and retrieves nothing, thus it _never_ caches.
@@ -34,6 +42,14 @@ impl<T: DecodeEntry + ?Sized> DecodeEntry for Box<T> {
     fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(Kind, usize)> {
         self.deref_mut().get(pack_id, offset, out)
     }
+
+    fn memory_usage(&self) -> usize {
+        self.deref().memory_usage()
+    }
+
+    fn clear(&mut self) {
+        self.deref_mut().clear()
+    }
 }
 
 /// A way of storing and retrieving entire objects to and from a cache.
@@ -43,8 +59,20 @@ pub trait Object {
 
     /// Try to retrieve the object named `id` and place its data into `out` if available and return `Some(kind)` if found.
     fn get(&mut self, id: &gix_hash::ObjectId, out: &mut Vec<u8>) -> Option<gix_object::Kind>;
+
+    /// Return the approximate amount of bytes held by this cache, including allocations kept for reuse.
+    ///
+    /// Implementations that don't track their memory usage report `0`.
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    /// Remove all entries and release the memory held by them.
+    fn clear(&mut self) {}
 }
 
+pub mod budget;
+
 /// Various implementations of [`DecodeEntry`] using least-recently-used algorithms.
 #[cfg(any(feature = "pack-cache-lru-dynamic", feature = "pack-cache-lru-static"))]
 pub mod lru;
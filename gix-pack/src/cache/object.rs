@@ -78,6 +78,15 @@ mod memory {
             }
             res
         }
+
+        fn memory_usage(&self) -> usize {
+            self.inner.weight() + self.free_list.iter().map(Vec::capacity).sum::<usize>()
+        }
+
+        fn clear(&mut self) {
+            self.inner.clear();
+            self.free_list = Vec::new();
+        }
     }
 }
 #[cfg(feature = "object-cache-dynamic")]
@@ -106,4 +115,14 @@ impl<T: cache::Object + ?Sized> cache::Object for Box<T> {
         use std::ops::DerefMut;
         self.deref_mut().get(id, out)
     }
+
+    fn memory_usage(&self) -> usize {
+        use std::ops::Deref;
+        self.deref().memory_usage()
+    }
+
+    fn clear(&mut self) {
+        use std::ops::DerefMut;
+        self.deref_mut().clear()
+    }
 }
@@ -29,6 +29,21 @@ impl PackLocation {
     }
 }
 
+/// Order `lhs` and `rhs` by their location in packs, placing counts without location first, followed by all counts
+/// of the same pack in order of their offset.
+pub(crate) fn by_pack_location(lhs: &Count, rhs: &Count) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (lhs.entry_pack_location.as_ref(), rhs.entry_pack_location.as_ref()) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (Some(lhs), Some(rhs)) => lhs
+            .pack_id
+            .cmp(&rhs.pack_id)
+            .then(lhs.pack_offset.cmp(&rhs.pack_offset)),
+    }
+}
+
 impl Count {
     /// Create a new instance from the given `oid` and its corresponding location.
     pub fn from_data(oid: impl Into<ObjectId>, location: Option<crate::data::entry::Location>) -> Self {
@@ -42,9 +57,16 @@ impl Count {
 #[path = "objects/mod.rs"]
 mod objects_impl;
 pub use objects_impl::{objects, objects_unthreaded};
+#[cfg(not(target_arch = "wasm32"))]
+pub use objects_impl::{objects_into, objects_unthreaded_into};
 
 ///
 #[allow(clippy::empty_docs)]
 pub mod objects {
     pub use super::objects_impl::{Error, ObjectExpansion, Options, Outcome};
 }
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(not(target_arch = "wasm32"))]
+pub mod spill;
@@ -43,6 +43,7 @@ pub fn objects<Find>(
 where
     Find: crate::Find + Send + Clone,
 {
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::count::objects()");
     let lower_bound = objects_ids.size_hint().0;
     let (chunk_size, thread_limit, _) = parallel::optimize_chunk_size_and_thread_limit(
         chunk_size,
@@ -97,6 +98,7 @@ pub fn objects_unthreaded(
     should_interrupt: &AtomicBool,
     input_object_expansion: ObjectExpansion,
 ) -> Result<(Vec<output::Count>, Outcome), Error> {
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::count::objects_unthreaded()");
     let seen_objs = RefCell::new(gix_hashtable::HashSet::default());
 
     let (mut buf1, mut buf2) = (Vec::new(), Vec::new());
@@ -113,6 +115,113 @@ pub fn objects_unthreaded(
     )
 }
 
+/// Like [`objects()`], but instead of collecting all counts in memory, they are pushed into `out` as soon as they are
+/// available, which keeps them on disk once they exceed its memory limit.
+///
+/// Note that the set of ids of all seen objects is still kept in memory to avoid duplicates.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn objects_into<Find>(
+    db: Find,
+    objects_ids: Box<dyn Iterator<Item = Result<ObjectId, Box<dyn std::error::Error + Send + Sync + 'static>>> + Send>,
+    objects: &dyn gix_features::progress::Count,
+    should_interrupt: &AtomicBool,
+    Options {
+        thread_limit,
+        input_object_expansion,
+        chunk_size,
+    }: Options,
+    out: &mut output::count::spill::Counts,
+) -> Result<Outcome, Error>
+where
+    Find: crate::Find + Send + Clone,
+{
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::count::objects_into()");
+    let lower_bound = objects_ids.size_hint().0;
+    let (chunk_size, thread_limit, _) = parallel::optimize_chunk_size_and_thread_limit(
+        chunk_size,
+        if lower_bound == 0 { None } else { Some(lower_bound) },
+        thread_limit,
+        None,
+    );
+    let chunks = gix_features::iter::Chunks {
+        inner: objects_ids,
+        size: chunk_size,
+    };
+    let seen_objs = gix_hashtable::sync::ObjectIdMap::default();
+    let objects = objects.counter();
+
+    parallel::in_parallel(
+        chunks,
+        thread_limit,
+        {
+            move |_| {
+                (
+                    Vec::new(), // object data buffer
+                    Vec::new(), // object data buffer 2 to hold two objects at a time
+                    objects.clone(),
+                )
+            }
+        },
+        {
+            let seen_objs = &seen_objs;
+            move |oids: Vec<_>, (buf1, buf2, objects)| {
+                expand::this(
+                    &db,
+                    input_object_expansion,
+                    seen_objs,
+                    &mut oids.into_iter(),
+                    buf1,
+                    buf2,
+                    objects,
+                    should_interrupt,
+                    true, /*allow pack lookups*/
+                )
+            }
+        },
+        reduce::Spill::new(out),
+    )
+}
+
+/// Like [`objects_unthreaded()`], but pushes all counts into `out` like [`objects_into()`] does.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn objects_unthreaded_into(
+    db: &dyn crate::Find,
+    object_ids: &mut dyn Iterator<Item = Result<ObjectId, Box<dyn std::error::Error + Send + Sync + 'static>>>,
+    objects: &dyn gix_features::progress::Count,
+    should_interrupt: &AtomicBool,
+    input_object_expansion: ObjectExpansion,
+    out: &mut output::count::spill::Counts,
+) -> Result<Outcome, Error> {
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::count::objects_unthreaded_into()");
+    let seen_objs = RefCell::new(gix_hashtable::HashSet::default());
+    let objects = objects.counter();
+
+    let (mut buf1, mut buf2) = (Vec::new(), Vec::new());
+    let mut outcome = Outcome::default();
+    for id in object_ids {
+        let (counts, stats) = expand::this(
+            db,
+            input_object_expansion,
+            &seen_objs,
+            &mut std::iter::once(id),
+            &mut buf1,
+            &mut buf2,
+            &objects,
+            should_interrupt,
+            false, /*allow pack lookups*/
+        )?;
+        outcome.aggregate(stats);
+        for mut count in counts {
+            if let output::count::PackLocation::NotLookedUp = count.entry_pack_location {
+                count.entry_pack_location =
+                    output::count::PackLocation::LookedUp(db.location_by_oid(&count.id, &mut buf1));
+            }
+            out.push(count).map_err(Error::WriteCounts)?;
+        }
+    }
+    Ok(outcome)
+}
+
 mod expand {
     use std::{
         cell::RefCell,
@@ -38,3 +38,41 @@ impl<E> parallel::Reduce for Statistics<E> {
         Ok((self.counts, self.total))
     }
 }
+
+/// A reducer which pushes all counts into a [`spill::Counts`](output::count::spill::Counts) instance.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Spill<'a> {
+    total: Outcome,
+    out: &'a mut output::count::spill::Counts,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> Spill<'a> {
+    pub fn new(out: &'a mut output::count::spill::Counts) -> Self {
+        Spill {
+            total: Default::default(),
+            out,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl parallel::Reduce for Spill<'_> {
+    type Input = Result<(Vec<output::Count>, Outcome), super::Error>;
+    type FeedProduce = ();
+    type Output = Outcome;
+    type Error = super::Error;
+
+    fn feed(&mut self, item: Self::Input) -> Result<Self::FeedProduce, Self::Error> {
+        let (counts, stats) = item?;
+        self.total.aggregate(stats);
+        for count in counts {
+            self.out.push(count).map_err(super::Error::WriteCounts)?;
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.total)
+    }
+}
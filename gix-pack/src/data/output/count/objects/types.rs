@@ -93,4 +93,6 @@ pub enum Error {
     TreeChanges(gix_diff::tree::changes::Error),
     #[error("Operation interrupted")]
     Interrupted,
+    #[error("Could not write counts to disk")]
+    WriteCounts(#[source] std::io::Error),
 }
@@ -0,0 +1,332 @@
+//! Keep the list of counted objects on disk once it exceeds a memory limit, to be able to generate packs for repositories
+//! with more objects than would fit into memory.
+//!
+//! [`Counts`] receives counts in any order, and [`Counts::finish()`] turns them into [`Sorted`] counts which are ordered
+//! by their location in existing packs, just like [`iter_from_counts()`](crate::data::output::entry::iter_from_counts())
+//! would order them. If counts had to be written to disk, sorted runs of them are merged into a single file which is
+//! memory-mapped, leaving it to the operating system to keep only the parts in memory that are currently needed.
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    io::{BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+
+use gix_hash::ObjectId;
+use gix_tempfile::{handle::Writable, AutoRemove, ContainingDirectory};
+
+use crate::data::output::{self, count::PackLocation};
+
+/// Configuration for [`Counts`].
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The amount of bytes that counts may occupy in memory before they are sorted and written to disk.
+    pub memory_limit_in_bytes: usize,
+    /// The directory to place temporary files into, or the system's temporary directory if `None`.
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            memory_limit_in_bytes: 256 * 1024 * 1024,
+            directory: None,
+        }
+    }
+}
+
+/// A list of counts which is kept in memory until it exceeds [`Options::memory_limit_in_bytes`], after which sorted runs of
+/// counts are written to temporary files.
+pub struct Counts {
+    object_hash: gix_hash::Kind,
+    options: Options,
+    buf: Vec<output::Count>,
+    runs: Vec<Run>,
+    len: usize,
+}
+
+struct Run {
+    file: gix_tempfile::Handle<Writable>,
+    len: usize,
+}
+
+impl Counts {
+    /// Create a new instance to hold counts of objects hashed with `object_hash`, configured by `options`.
+    pub fn new(object_hash: gix_hash::Kind, options: Options) -> Self {
+        Counts {
+            object_hash,
+            options,
+            buf: Vec::new(),
+            runs: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Add `count`, which must have been looked up already, i.e. its location must not be
+    /// [`NotLookedUp`](PackLocation::NotLookedUp).
+    pub fn push(&mut self, count: output::Count) -> std::io::Result<()> {
+        assert!(
+            matches!(count.entry_pack_location, PackLocation::LookedUp(_)),
+            "counts must have been looked up before they can be spilled"
+        );
+        self.buf.push(count);
+        self.len += 1;
+        if self.buf.len() * std::mem::size_of::<output::Count>() >= self.options.memory_limit_in_bytes.max(1) {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// Return the amount of counts added so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if no count was added yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the amount of sorted runs that were written to disk so far.
+    pub fn num_spilled_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Sort all counts by their location in existing packs, merging all runs that were written to disk if necessary.
+    pub fn finish(mut self) -> std::io::Result<Sorted> {
+        if self.runs.is_empty() {
+            self.buf.sort_by(output::count::by_pack_location);
+            return Ok(Sorted {
+                object_hash: self.object_hash,
+                len: self.len,
+                storage: Storage::Memory(self.buf),
+            });
+        }
+        if !self.buf.is_empty() {
+            self.spill()?;
+        }
+
+        let record_len = record_len(self.object_hash);
+        let mut runs = Vec::with_capacity(self.runs.len());
+        for run in &mut self.runs {
+            let file = run.file.with_mut(|tempfile| tempfile.reopen())??;
+            runs.push((BufReader::with_capacity(64 * 1024, file), run.len));
+        }
+        let mut heap = BinaryHeap::with_capacity(runs.len());
+        let mut heads = vec![vec![0; record_len]; runs.len()];
+        for (run_idx, ((reader, remaining), head)) in runs.iter_mut().zip(heads.iter_mut()).enumerate() {
+            if *remaining > 0 {
+                reader.read_exact(head)?;
+                *remaining -= 1;
+                heap.push(Reverse((sort_key(head, self.object_hash), run_idx)));
+            }
+        }
+
+        let mut file = self.new_tempfile()?;
+        {
+            let mut out = BufWriter::with_capacity(64 * 1024, &mut file);
+            while let Some(Reverse((_, run_idx))) = heap.pop() {
+                out.write_all(&heads[run_idx])?;
+                let (reader, remaining) = &mut runs[run_idx];
+                if *remaining > 0 {
+                    reader.read_exact(&mut heads[run_idx])?;
+                    *remaining -= 1;
+                    heap.push(Reverse((sort_key(&heads[run_idx], self.object_hash), run_idx)));
+                }
+            }
+            out.flush()?;
+        }
+        drop(runs);
+        self.runs.clear();
+
+        // SAFETY: the file is a temporary file we own and don't change anymore.
+        #[allow(unsafe_code)]
+        let map = file.with_mut(|tempfile| unsafe { memmap2::Mmap::map(tempfile.as_file()) })??;
+        Ok(Sorted {
+            object_hash: self.object_hash,
+            len: self.len,
+            storage: Storage::Mapped { map, _file: file },
+        })
+    }
+
+    fn new_tempfile(&self) -> std::io::Result<gix_tempfile::Handle<Writable>> {
+        let directory = self.options.directory.clone().unwrap_or_else(std::env::temp_dir);
+        gix_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)
+    }
+
+    fn spill(&mut self) -> std::io::Result<()> {
+        self.buf.sort_by(output::count::by_pack_location);
+        let mut file = self.new_tempfile()?;
+        {
+            let mut out = BufWriter::with_capacity(64 * 1024, &mut file);
+            let mut record = Vec::with_capacity(record_len(self.object_hash));
+            for count in &self.buf {
+                record.clear();
+                encode(count, &mut record);
+                out.write_all(&record)?;
+            }
+            out.flush()?;
+        }
+        self.runs.push(Run {
+            file,
+            len: self.buf.len(),
+        });
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+/// Counts sorted by their location in existing packs, either in memory or memory-mapped from disk.
+pub struct Sorted {
+    object_hash: gix_hash::Kind,
+    len: usize,
+    storage: Storage,
+}
+
+enum Storage {
+    Memory(Vec<output::Count>),
+    Mapped {
+        map: memmap2::Mmap,
+        _file: gix_tempfile::Handle<Writable>,
+    },
+}
+
+impl Sorted {
+    /// Return the amount of counts.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if there is no count.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return `true` if the counts are stored on disk.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.storage, Storage::Mapped { .. })
+    }
+
+    /// Return the count at `index`, or _panic_ if it is out of bounds.
+    pub fn get(&self, index: usize) -> output::Count {
+        match &self.storage {
+            Storage::Memory(counts) => counts[index].clone(),
+            Storage::Mapped { map, .. } => {
+                let record_len = record_len(self.object_hash);
+                decode(&map[index * record_len..][..record_len], self.object_hash)
+            }
+        }
+    }
+
+    fn pack_location(&self, index: usize) -> Option<(u32, crate::data::Offset)> {
+        match &self.storage {
+            Storage::Memory(counts) => counts[index]
+                .entry_pack_location
+                .as_ref()
+                .map(|l| (l.pack_id, l.pack_offset)),
+            Storage::Mapped { map, .. } => {
+                let record_len = record_len(self.object_hash);
+                let key = sort_key(&map[index * record_len..][..record_len], self.object_hash);
+                key.0.then_some((key.1, key.2))
+            }
+        }
+    }
+
+    /// Return the ranges of counts that are located in the same pack, along with the id of the pack.
+    pub(crate) fn ranges_by_pack_id(&self) -> Vec<(u32, std::ops::Range<usize>)> {
+        let mut out = Vec::new();
+        let mut start = partition_point(0..self.len, |idx| self.pack_location(idx).is_none());
+        while start < self.len {
+            let pack_id = self.pack_location(start).expect("packed").0;
+            let end = partition_point(start..self.len, |idx| {
+                self.pack_location(idx).map(|l| l.0) == Some(pack_id)
+            });
+            out.push((pack_id, start..end));
+            start = end;
+        }
+        out
+    }
+
+    /// Find the index of the count located at `pack_offset` within `range`, which must contain counts of a single pack.
+    pub(crate) fn find_by_pack_offset(
+        &self,
+        range: std::ops::Range<usize>,
+        pack_offset: crate::data::Offset,
+    ) -> Option<usize> {
+        match &self.storage {
+            Storage::Memory(counts) => counts[range.clone()]
+                .binary_search_by(|e| {
+                    e.entry_pack_location
+                        .as_ref()
+                        .expect("packed")
+                        .pack_offset
+                        .cmp(&pack_offset)
+                })
+                .ok()
+                .map(|idx| idx + range.start),
+            Storage::Mapped { .. } => {
+                let idx = partition_point(range.clone(), |idx| {
+                    self.pack_location(idx).expect("packed").1 < pack_offset
+                });
+                (idx < range.end && self.pack_location(idx).expect("packed").1 == pack_offset).then_some(idx)
+            }
+        }
+    }
+}
+
+/// Like `slice::partition_point()`, but for any indexable sequence.
+fn partition_point(range: std::ops::Range<usize>, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (range.start, range.end);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// The size of a count on disk: the object id, a flag to indicate if it has a location, the pack id,
+/// the pack offset and the entry size.
+fn record_len(object_hash: gix_hash::Kind) -> usize {
+    object_hash.len_in_bytes() + 1 + 4 + 8 + 8
+}
+
+fn encode(count: &output::Count, out: &mut Vec<u8>) {
+    out.extend_from_slice(count.id.as_slice());
+    match count.entry_pack_location.as_ref() {
+        Some(location) => {
+            out.push(1);
+            out.extend_from_slice(&location.pack_id.to_be_bytes());
+            out.extend_from_slice(&location.pack_offset.to_be_bytes());
+            out.extend_from_slice(&(location.entry_size as u64).to_be_bytes());
+        }
+        None => out.extend_from_slice(&[0; 1 + 4 + 8 + 8]),
+    }
+}
+
+fn decode(record: &[u8], object_hash: gix_hash::Kind) -> output::Count {
+    let hash_len = object_hash.len_in_bytes();
+    let (has_location, pack_id, pack_offset) = sort_key(record, object_hash);
+    let entry_size = u64::from_be_bytes(record[hash_len + 13..][..8].try_into().expect("8 bytes"));
+    output::Count {
+        id: ObjectId::from_bytes_or_panic(&record[..hash_len]),
+        entry_pack_location: PackLocation::LookedUp(has_location.then_some(crate::data::entry::Location {
+            pack_id,
+            pack_offset,
+            entry_size: entry_size as usize,
+        })),
+    }
+}
+
+/// Return the fields of the record that determine its order.
+fn sort_key(record: &[u8], object_hash: gix_hash::Kind) -> (bool, u32, u64) {
+    let hash_len = object_hash.len_in_bytes();
+    (
+        record[hash_len] == 1,
+        u32::from_be_bytes(record[hash_len + 1..][..4].try_into().expect("4 bytes")),
+        u64::from_be_bytes(record[hash_len + 5..][..8].try_into().expect("8 bytes")),
+    )
+}
@@ -0,0 +1,184 @@
+/// The amount of bytes that are hashed to find matching regions between base and target.
+const BLOCK: usize = 16;
+/// The maximum amount of base positions to remember per hash bucket, to bound the time spent on pathological inputs.
+const MAX_POSITIONS_PER_BUCKET: usize = 64;
+/// The largest amount of bytes that can be copied from the base with a single instruction, see `data::delta::apply()`.
+const MAX_COPY: usize = 0x10000;
+/// The largest amount of bytes that can be inserted with a single instruction.
+const MAX_INSERT: usize = 0x7f;
+/// The multiplier of the rolling hash.
+const PRIME: u32 = 0x0100_0193;
+
+/// An index over the blocks of a base object to quickly find regions that a target object has in common with it.
+pub(crate) struct Index {
+    shift: u32,
+    /// For each bucket, the start of its positions in `positions`, with one more entry to mark the end of the last bucket.
+    bucket_starts: Vec<u32>,
+    positions: Vec<u32>,
+    base_len: usize,
+}
+
+impl Index {
+    /// Index `base`, or return `None` if it can't be used as base as it is too small or too large.
+    pub(crate) fn new(base: &[u8]) -> Option<Self> {
+        if base.len() < BLOCK || u32::try_from(base.len()).is_err() {
+            return None;
+        }
+        let num_blocks = base.len() / BLOCK;
+        let bits = (usize::BITS - num_blocks.leading_zeros()).clamp(4, 24);
+        let shift = u32::BITS - bits;
+        let num_buckets = 1usize << bits;
+
+        let block_offsets = (0..num_blocks).map(|block| block * BLOCK);
+        let mut bucket_starts = vec![0u32; num_buckets + 1];
+        for offset in block_offsets.clone() {
+            let bucket = bucket(hash(&base[offset..][..BLOCK]), shift);
+            bucket_starts[bucket + 1] = (bucket_starts[bucket + 1] + 1).min(MAX_POSITIONS_PER_BUCKET as u32);
+        }
+        for idx in 1..bucket_starts.len() {
+            bucket_starts[idx] += bucket_starts[idx - 1];
+        }
+        let mut positions = vec![0u32; bucket_starts[num_buckets] as usize];
+        let mut fill = bucket_starts.clone();
+        for offset in block_offsets {
+            let bucket = bucket(hash(&base[offset..][..BLOCK]), shift);
+            if fill[bucket] < bucket_starts[bucket + 1] {
+                positions[fill[bucket] as usize] = offset as u32;
+                fill[bucket] += 1;
+            }
+        }
+        Some(Index {
+            shift,
+            bucket_starts,
+            positions,
+            base_len: base.len(),
+        })
+    }
+
+    /// Create a delta which turns `base`, which must be the data this index was created from, into `target`,
+    /// or return `None` if the delta would be larger than `max_size` bytes.
+    pub(crate) fn create_delta(&self, base: &[u8], target: &[u8], max_size: usize) -> Option<Vec<u8>> {
+        debug_assert_eq!(base.len(), self.base_len, "the index must belong to the base");
+        let mut out = Vec::with_capacity(max_size.min(target.len() / 2 + 32));
+        encode_size(base.len(), &mut out);
+        encode_size(target.len(), &mut out);
+
+        let mut pending_insert_start = 0;
+        let mut pos = 0;
+        let mut rolling = (target.len() >= BLOCK).then(|| hash(&target[..BLOCK]));
+        while let Some(hash) = rolling {
+            match self.longest_match(base, target, pos, hash) {
+                Some((mut base_pos, mut len)) => {
+                    while pos > pending_insert_start && base_pos > 0 && base[base_pos - 1] == target[pos - 1] {
+                        base_pos -= 1;
+                        pos -= 1;
+                        len += 1;
+                    }
+                    encode_insert(&target[pending_insert_start..pos], &mut out);
+                    encode_copy(base_pos, len, &mut out);
+                    pos += len;
+                    pending_insert_start = pos;
+                    rolling = (pos + BLOCK <= target.len()).then(|| self::hash(&target[pos..][..BLOCK]));
+                }
+                None => {
+                    rolling = (pos + BLOCK < target.len()).then(|| roll(hash, target[pos], target[pos + BLOCK]));
+                    pos += 1;
+                }
+            }
+            if out.len() > max_size {
+                return None;
+            }
+        }
+        encode_insert(&target[pending_insert_start..], &mut out);
+        (out.len() <= max_size).then_some(out)
+    }
+
+    /// Return the position in `base` and the length of the longest region matching `target` at `pos`, whose first
+    /// block hashes to `hash`.
+    fn longest_match(&self, base: &[u8], target: &[u8], pos: usize, hash: u32) -> Option<(usize, usize)> {
+        let bucket = bucket(hash, self.shift);
+        let candidates = &self.positions[self.bucket_starts[bucket] as usize..self.bucket_starts[bucket + 1] as usize];
+        let mut best: Option<(usize, usize)> = None;
+        for &base_pos in candidates {
+            let base_pos = base_pos as usize;
+            let len = base[base_pos..]
+                .iter()
+                .zip(&target[pos..])
+                .take_while(|(lhs, rhs)| lhs == rhs)
+                .count();
+            if len >= BLOCK && best.map_or(true, |(_, best_len)| len > best_len) {
+                best = Some((base_pos, len));
+            }
+        }
+        best
+    }
+}
+
+fn hash(block: &[u8]) -> u32 {
+    block.iter().fold(0u32, |hash, byte| {
+        hash.wrapping_mul(PRIME).wrapping_add(u32::from(*byte))
+    })
+}
+
+/// Remove `outgoing` from the front of the block hashed to `hash`, and add `incoming` to its back.
+fn roll(hash: u32, outgoing: u8, incoming: u8) -> u32 {
+    const OUTGOING_FACTOR: u32 = {
+        let mut factor = 1u32;
+        let mut round = 1;
+        while round < BLOCK {
+            factor = factor.wrapping_mul(PRIME);
+            round += 1;
+        }
+        factor
+    };
+    hash.wrapping_sub(u32::from(outgoing).wrapping_mul(OUTGOING_FACTOR))
+        .wrapping_mul(PRIME)
+        .wrapping_add(u32::from(incoming))
+}
+
+fn bucket(hash: u32, shift: u32) -> usize {
+    (hash.wrapping_mul(0x9e37_79b1) >> shift) as usize
+}
+
+fn encode_size(mut size: usize, out: &mut Vec<u8>) {
+    loop {
+        let byte = (size & 0x7f) as u8;
+        size >>= 7;
+        if size == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn encode_insert(data: &[u8], out: &mut Vec<u8>) {
+    for chunk in data.chunks(MAX_INSERT) {
+        out.push(chunk.len() as u8);
+        out.extend_from_slice(chunk);
+    }
+}
+
+fn encode_copy(mut offset: usize, mut len: usize, out: &mut Vec<u8>) {
+    while len > 0 {
+        let size = len.min(MAX_COPY);
+        let cmd_pos = out.len();
+        let mut cmd = 0b1000_0000u8;
+        out.push(cmd);
+        for (bit, byte) in (offset as u32).to_le_bytes().into_iter().enumerate() {
+            if byte != 0 {
+                cmd |= 1 << bit;
+                out.push(byte);
+            }
+        }
+        for (bit, byte) in (size as u32).to_le_bytes().into_iter().take(3).enumerate() {
+            if byte != 0 {
+                cmd |= 1 << (bit + 4);
+                out.push(byte);
+            }
+        }
+        out[cmd_pos] = cmd;
+        offset += size;
+        len -= size;
+    }
+}
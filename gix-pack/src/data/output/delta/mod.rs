@@ -0,0 +1,416 @@
+//! Find good delta bases among the objects to put into a pack, instead of reusing deltas of existing packs.
+//!
+//! This is what `git repack -f` does, and it works by sorting all objects so that similar objects are close to
+//! each other, and then trying each object against a sliding window of its predecessors to find the smallest delta.
+use std::{
+    collections::VecDeque,
+    io::Write,
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::{
+    parallel,
+    progress::{Count, DynNestedProgress, Progress},
+};
+use gix_hash::ObjectId;
+
+use crate::data::output;
+
+mod create;
+
+/// The error returned by [`search()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Find(#[from] gix_object::find::Error),
+    #[error("The object {id} could not be found")]
+    NotFound { id: ObjectId },
+    #[error(transparent)]
+    DecodeTree(#[from] gix_object::decode::Error),
+    #[error("Failed to compress an entry")]
+    ZlibDeflate(#[from] std::io::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// The progress ids used in [`search()`].
+///
+/// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+#[derive(Debug, Copy, Clone)]
+pub enum ProgressId {
+    /// The amount of objects whose size and type were obtained to find good delta candidates.
+    PreparedObjects,
+    /// The amount of objects that were tried against the objects in their window.
+    SearchedObjects,
+}
+
+impl From<ProgressId> for gix_features::progress::Id {
+    fn from(v: ProgressId) -> Self {
+        match v {
+            ProgressId::PreparedObjects => *b"PDPO",
+            ProgressId::SearchedObjects => *b"PDSO",
+        }
+    }
+}
+
+/// Configuration for [`search()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The amount of preceding objects to try as delta base for each object, like `pack.window`.
+    ///
+    /// Larger windows yield smaller packs, but make the search take longer. If `0`, no deltas will be created.
+    pub window: usize,
+    /// The maximum length of delta chains, like `pack.depth`.
+    ///
+    /// Longer chains yield smaller packs, but make accessing objects at the end of the chain slower.
+    pub depth: usize,
+    /// The amount of threads to use, like `pack.threads`, or `None` to use all logical cores.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    /// The defaults used by git.
+    fn default() -> Self {
+        Options {
+            window: 10,
+            depth: 50,
+            thread_limit: None,
+        }
+    }
+}
+
+/// Information about the delta search.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The amount of objects that are stored as a whole.
+    pub base_objects: usize,
+    /// The amount of objects that are stored as delta against another object in the pack.
+    pub delta_objects: usize,
+    /// The length of the longest delta chain.
+    pub max_chain_length: usize,
+}
+
+/// An object as input to the delta search.
+struct Object {
+    id: ObjectId,
+    kind: gix_object::Kind,
+    size: usize,
+    /// A hash of the name the object was last seen under, to group objects of the same name together.
+    name_hash: u32,
+}
+
+/// Search deltas among all objects identified by `ids` in `db` and return pack entries for them along with information
+/// about the search.
+///
+/// This ignores all deltas in existing packs and is significantly slower than copying existing pack entries as done by
+/// [`iter_from_counts()`](output::entry::iter_from_counts()), but is also able to produce smaller packs.
+/// The returned entries are ordered so that each delta base is written before the objects that refer to it.
+///
+/// * `ids` - the objects to put into the pack, typically obtained by [counting](output::count::objects()). Objects
+///   are assumed to be ordered by recency, which is used as tie-breaker when sorting them.
+/// * `progress` - receives progress information for each phase of the search.
+/// * `should_interrupt` - if set to `true`, the search will stop with an error.
+pub fn search<Find>(
+    db: Find,
+    ids: impl IntoIterator<Item = ObjectId>,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+    Options {
+        window,
+        depth,
+        thread_limit,
+    }: Options,
+) -> Result<(Vec<output::Entry>, Outcome), Error>
+where
+    Find: crate::Find + Send + Clone,
+{
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::delta::search()");
+    let ids: Vec<_> = ids.into_iter().collect();
+    let num_threads = parallel::num_threads(thread_limit).min(ids.len()).max(1);
+
+    let mut objects = {
+        let mut progress = progress.add_child_with_id("preparing objects".into(), ProgressId::PreparedObjects.into());
+        progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+        let counter = progress.counter();
+        let prepared = in_threads(&db, split_evenly(ids.len(), num_threads), |db, range| {
+            prepare(&db, &ids[range], &counter, should_interrupt)
+        })?;
+        let mut name_hashes = gix_hashtable::HashMap::<ObjectId, u32>::default();
+        for (_, names) in &prepared {
+            for (id, name_hash) in names {
+                name_hashes.entry(*id).or_insert(*name_hash);
+            }
+        }
+        prepared
+            .into_iter()
+            .flat_map(|(objects, _)| objects)
+            .map(|mut object| {
+                object.name_hash = name_hashes.get(&object.id).copied().unwrap_or_default();
+                object
+            })
+            .collect::<Vec<_>>()
+    };
+    drop(ids);
+    // Sorting is stable, so recency is maintained among objects that are otherwise the same.
+    objects.sort_by(|a, b| {
+        kind_rank(a.kind)
+            .cmp(&kind_rank(b.kind))
+            .then_with(|| b.name_hash.cmp(&a.name_hash))
+            .then_with(|| b.size.cmp(&a.size))
+    });
+
+    let mut progress = progress.add_child_with_id("searching deltas".into(), ProgressId::SearchedObjects.into());
+    progress.init(Some(objects.len()), gix_features::progress::count("objects"));
+    let start = std::time::Instant::now();
+    let counter = progress.counter();
+    let segments = split_at_group_boundaries(&objects, num_threads);
+    let options = Options {
+        window,
+        depth,
+        thread_limit,
+    };
+    let mut out = Vec::with_capacity(objects.len());
+    let mut outcome = Outcome::default();
+    for (entries, segment_outcome) in in_threads(&db, segments, |db, range| {
+        search_segment(&db, &objects, range, options, &counter, should_interrupt)
+    })? {
+        out.extend(entries);
+        outcome.base_objects += segment_outcome.base_objects;
+        outcome.delta_objects += segment_outcome.delta_objects;
+        outcome.max_chain_length = outcome.max_chain_length.max(segment_outcome.max_chain_length);
+    }
+    progress.show_throughput(start);
+    Ok((out, outcome))
+}
+
+/// The ids of tree entries along with the hash of their name.
+type NameHashes = Vec<(ObjectId, u32)>;
+
+/// Obtain type and size of all objects identified by `ids`, along with the name hashes of all entries of the trees among them.
+fn prepare(
+    db: &dyn crate::Find,
+    ids: &[ObjectId],
+    counter: &gix_features::progress::StepShared,
+    should_interrupt: &AtomicBool,
+) -> Result<(Vec<Object>, NameHashes), Error> {
+    let mut buf = Vec::new();
+    let mut objects = Vec::with_capacity(ids.len());
+    let mut names = Vec::new();
+    for id in ids {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        let (data, _location) = db.try_find(id, &mut buf)?.ok_or(Error::NotFound { id: *id })?;
+        if data.kind == gix_object::Kind::Tree {
+            for entry in gix_object::TreeRefIter::from_bytes(data.data) {
+                let entry = entry?;
+                names.push((entry.oid.to_owned(), name_hash(entry.filename)));
+            }
+        }
+        objects.push(Object {
+            id: *id,
+            kind: data.kind,
+            size: data.data.len(),
+            name_hash: 0,
+        });
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok((objects, names))
+}
+
+/// A delta base candidate in the window.
+struct Candidate {
+    /// The index of the object in the sorted list of all objects, which is also its index in the produced pack.
+    object_index: usize,
+    kind: gix_object::Kind,
+    data: Vec<u8>,
+    /// The index over `data`, created on first use, and `None` if `data` can't be used as base.
+    index: Option<Option<create::Index>>,
+    depth: usize,
+}
+
+fn search_segment(
+    db: &dyn crate::Find,
+    objects: &[Object],
+    range: Range<usize>,
+    Options { window, depth, .. }: Options,
+    counter: &gix_features::progress::StepShared,
+    should_interrupt: &AtomicBool,
+) -> Result<(Vec<output::Entry>, Outcome), Error> {
+    let hash_len = objects.first().map_or(0, |object| object.id.as_slice().len());
+    let mut out = Vec::with_capacity(range.len());
+    let mut outcome = Outcome::default();
+    let mut candidates = VecDeque::<Candidate>::with_capacity(window);
+    for object_index in range {
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+        let object = &objects[object_index];
+        let mut data = Vec::new();
+        let obj = db
+            .try_find(&object.id, &mut data)?
+            .ok_or(Error::NotFound { id: object.id })?
+            .0;
+        let kind = obj.kind;
+
+        // Prefer the delta that is the smallest, but weigh it against the depth of the chain it creates, just like git.
+        let mut best: Option<(Vec<u8>, usize, usize)> = None;
+        for candidate in candidates.iter_mut().rev() {
+            if candidate.kind != kind || candidate.depth >= depth {
+                continue;
+            }
+            let (max_size, ref_depth) = match &best {
+                Some((delta, _, best_depth)) => (delta.len().saturating_sub(1), *best_depth),
+                None => ((data.len() / 2).saturating_sub(hash_len), 1),
+            };
+            let max_size = max_size * (depth - candidate.depth) / (depth - ref_depth + 1);
+            if max_size == 0
+                || data.len().saturating_sub(candidate.data.len()) >= max_size
+                || data.len() < candidate.data.len() / 32
+            {
+                continue;
+            }
+            let Some(index) = candidate
+                .index
+                .get_or_insert_with(|| create::Index::new(&candidate.data))
+            else {
+                continue;
+            };
+            if let Some(delta) = index.create_delta(&candidate.data, &data, max_size) {
+                best = Some((delta, candidate.object_index, candidate.depth + 1));
+            }
+        }
+
+        let entry_depth = match best {
+            Some((delta, base_index, delta_depth)) => {
+                out.push(output::Entry {
+                    id: object.id,
+                    kind: output::entry::Kind::DeltaRef {
+                        object_index: base_index,
+                    },
+                    decompressed_size: delta.len(),
+                    compressed_data: deflate(&delta)?,
+                });
+                outcome.delta_objects += 1;
+                outcome.max_chain_length = outcome.max_chain_length.max(delta_depth);
+                delta_depth
+            }
+            None => {
+                out.push(output::Entry {
+                    id: object.id,
+                    kind: output::entry::Kind::Base(kind),
+                    decompressed_size: data.len(),
+                    compressed_data: deflate(&data)?,
+                });
+                outcome.base_objects += 1;
+                0
+            }
+        };
+
+        if window != 0 {
+            if candidates.len() == window {
+                candidates.pop_front();
+            }
+            candidates.push_back(Candidate {
+                object_index,
+                kind,
+                data,
+                index: None,
+                depth: entry_depth,
+            });
+        }
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok((out, outcome))
+}
+
+/// Run `f` on each of `ranges` in its own thread with its own clone of `db`, and return the results in order.
+fn in_threads<Find, T>(
+    db: &Find,
+    ranges: Vec<Range<usize>>,
+    f: impl Fn(Find, Range<usize>) -> Result<T, Error> + Send + Sync,
+) -> Result<Vec<T>, Error>
+where
+    Find: crate::Find + Send + Clone,
+    T: Send,
+{
+    parallel::threads(|s| {
+        let f = &f;
+        let threads = ranges
+            .into_iter()
+            .enumerate()
+            .map(|(tid, range)| {
+                let db = db.clone();
+                parallel::build_thread()
+                    .name(format!("gix-pack.delta.search.{tid}"))
+                    .spawn_scoped(s, move || f(db, range))
+                    .expect("valid name")
+            })
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().expect("no panic"))
+            .collect()
+    })
+}
+
+fn split_evenly(len: usize, parts: usize) -> Vec<Range<usize>> {
+    let parts = parts.max(1);
+    let chunk_size = ((len + parts - 1) / parts).max(1);
+    (0..len)
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(len))
+        .collect()
+}
+
+/// Like [`split_evenly()`], but move the boundaries so that objects of the same kind and name stay in the same range,
+/// as these are the most likely to yield good deltas.
+fn split_at_group_boundaries(objects: &[Object], parts: usize) -> Vec<Range<usize>> {
+    let mut out = Vec::<Range<usize>>::new();
+    for range in split_evenly(objects.len(), parts) {
+        let start = out.last().map_or(range.start, |previous| previous.end);
+        if start >= range.end {
+            continue;
+        }
+        let mut end = range.end;
+        while end < objects.len()
+            && objects[end].kind == objects[end - 1].kind
+            && objects[end].name_hash == objects[end - 1].name_hash
+        {
+            end += 1;
+        }
+        out.push(start..end);
+    }
+    out
+}
+
+/// Commits come first as they are needed first when traversing history, while blobs are needed last.
+fn kind_rank(kind: gix_object::Kind) -> u8 {
+    use gix_object::Kind::*;
+    match kind {
+        Commit => 0,
+        Tag => 1,
+        Tree => 2,
+        Blob => 3,
+    }
+}
+
+/// Hash `name` so that names with the same ending produce similar values, which makes files of the same type sort
+/// close to each other. This is the same hash that git uses.
+fn name_hash(name: &[u8]) -> u32 {
+    name.iter()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .fold(0u32, |hash, byte| (hash >> 2).wrapping_add(u32::from(*byte) << 24))
+}
+
+/// Compress `data` with the default compression level of zlib, just like git, as the time it takes is small compared
+/// to the delta search.
+fn deflate(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = gix_features::zlib::stream::deflate::Write::with_compression_level(Vec::new(), 6);
+    out.write_all(data)?;
+    out.flush()?;
+    Ok(out.into_inner())
+}
@@ -1,5 +1,5 @@
 pub(crate) mod function {
-    use std::{cmp::Ordering, sync::Arc};
+    use std::{borrow::Cow, sync::Arc};
 
     use gix_features::{
         parallel,
@@ -41,29 +41,46 @@ pub(crate) mod function {
     /// * ~~currently there is no way to easily write the pack index, even though the state here is uniquely positioned to do
     ///   so with minimal overhead (especially compared to `gix index-from-pack`)~~ Probably works now by chaining Iterators
     ///   or keeping enough state to write a pack and then generate an index with recorded data.
+    /// * All `counts` are held in memory, use [`iter_from_sorted_counts()`] to keep them on disk instead.
     ///
     pub fn iter_from_counts<Find>(
+        counts: Vec<output::Count>,
+        db: Find,
+        progress: Box<dyn DynNestedProgress + 'static>,
+        options: Options,
+    ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
+           + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
+    where
+        Find: crate::Find + Send + Clone + 'static,
+    {
+        iter_from_counts_with_thin_bases(counts, db, |_| true, progress, options)
+    }
+
+    /// Like [`iter_from_counts()`], but if [`Options::allow_thin_pack`] is set, delta objects may only refer to bases
+    /// outside of the pack if `is_thin_base(base_id)` returns `true`, which is typically the case if the receiving side is known
+    /// to have the object.
+    ///
+    /// All other deltas with bases outside of the pack are decoded and recompressed as base objects.
+    /// This allows producing thin packs for push or fetch responses that the other side is guaranteed to be able to resolve.
+    pub fn iter_from_counts_with_thin_bases<Find, IsThinBase>(
         mut counts: Vec<output::Count>,
         db: Find,
+        is_thin_base: IsThinBase,
         mut progress: Box<dyn DynNestedProgress + 'static>,
-        Options {
-            version,
-            mode,
-            allow_thin_pack,
-            thread_limit,
-            chunk_size,
-        }: Options,
+        options: Options,
     ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
            + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
     where
         Find: crate::Find + Send + Clone + 'static,
+        IsThinBase: Fn(&gix_hash::oid) -> bool + Send + Clone + 'static,
     {
-        assert!(
-            matches!(version, crate::data::Version::V2),
-            "currently we can only write version 2"
+        let _span = gix_features::trace::coarse!("gix_pack::data::output::iter_from_counts()");
+        let (chunk_size, thread_limit, _) = parallel::optimize_chunk_size_and_thread_limit(
+            options.chunk_size,
+            Some(counts.len()),
+            options.thread_limit,
+            None,
         );
-        let (chunk_size, thread_limit, _) =
-            parallel::optimize_chunk_size_and_thread_limit(chunk_size, Some(counts.len()), thread_limit, None);
         {
             let progress = Arc::new(parking_lot::Mutex::new(
                 progress.add_child_with_id("resolving".into(), ProgressId::ResolveCounts.into()),
@@ -97,23 +114,13 @@ pub(crate) mod function {
             .expect("infallible - we ignore none-existing objects");
             progress.lock().show_throughput(start);
         }
-        let counts_range_by_pack_id = match mode {
+        let counts_range_by_pack_id = match options.mode {
             Mode::PackCopyAndBaseObjects => {
                 let mut progress = progress.add_child_with_id("sorting".into(), ProgressId::SortEntries.into());
                 progress.init(Some(counts.len()), gix_features::progress::count("counts"));
                 let start = std::time::Instant::now();
 
-                use crate::data::output::count::PackLocation::*;
-                counts.sort_by(|lhs, rhs| match (&lhs.entry_pack_location, &rhs.entry_pack_location) {
-                    (LookedUp(None), LookedUp(None)) => Ordering::Equal,
-                    (LookedUp(Some(_)), LookedUp(None)) => Ordering::Greater,
-                    (LookedUp(None), LookedUp(Some(_))) => Ordering::Less,
-                    (LookedUp(Some(lhs)), LookedUp(Some(rhs))) => lhs
-                        .pack_id
-                        .cmp(&rhs.pack_id)
-                        .then(lhs.pack_offset.cmp(&rhs.pack_offset)),
-                    (_, _) => unreachable!("counts were resolved beforehand"),
-                });
+                counts.sort_by(output::count::by_pack_location);
 
                 let mut index: Vec<(u32, std::ops::Range<usize>)> = Vec::new();
                 let mut chunks_pack_start = counts.partition_point(|e| e.entry_pack_location.is_none());
@@ -135,7 +142,138 @@ pub(crate) mod function {
             }
         };
 
-        let counts = Arc::new(counts);
+        iter_from_sorted(
+            Arc::new(counts),
+            counts_range_by_pack_id,
+            db,
+            is_thin_base,
+            progress,
+            Options {
+                chunk_size,
+                thread_limit,
+                ..options
+            },
+        )
+    }
+
+    /// Like [`iter_from_counts_with_thin_bases()`], but uses `counts` that were previously sorted by their pack location,
+    /// possibly while keeping them on disk to [bound memory usage](output::count::spill).
+    ///
+    /// Use `|_| true` as `is_thin_base` to allow all bases outside of the pack if [`Options::allow_thin_pack`] is set.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn iter_from_sorted_counts<Find, IsThinBase>(
+        counts: output::count::spill::Sorted,
+        db: Find,
+        is_thin_base: IsThinBase,
+        progress: Box<dyn DynNestedProgress + 'static>,
+        options: Options,
+    ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
+           + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
+    where
+        Find: crate::Find + Send + Clone + 'static,
+        IsThinBase: Fn(&gix_hash::oid) -> bool + Send + Clone + 'static,
+    {
+        let _span = gix_features::trace::coarse!("gix_pack::data::output::iter_from_sorted_counts()");
+        let (chunk_size, thread_limit, _) = parallel::optimize_chunk_size_and_thread_limit(
+            options.chunk_size,
+            Some(counts.len()),
+            options.thread_limit,
+            None,
+        );
+        let counts_range_by_pack_id = counts.ranges_by_pack_id();
+        iter_from_sorted(
+            Arc::new(counts),
+            counts_range_by_pack_id,
+            db,
+            is_thin_base,
+            progress,
+            Options {
+                chunk_size,
+                thread_limit,
+                ..options
+            },
+        )
+    }
+
+    /// Counts that are sorted by their location in packs, with those without location first.
+    pub(crate) trait SortedCounts: Send + Sync + 'static {
+        fn len(&self) -> usize;
+        fn get(&self, index: usize) -> Cow<'_, output::Count>;
+        /// Find the index of the count at `pack_offset` within `range`, which contains counts of a single pack.
+        fn find_by_pack_offset(&self, range: std::ops::Range<usize>, pack_offset: crate::data::Offset)
+            -> Option<usize>;
+    }
+
+    impl SortedCounts for Vec<output::Count> {
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn get(&self, index: usize) -> Cow<'_, output::Count> {
+            Cow::Borrowed(&self[index])
+        }
+
+        fn find_by_pack_offset(
+            &self,
+            range: std::ops::Range<usize>,
+            pack_offset: crate::data::Offset,
+        ) -> Option<usize> {
+            self[range.clone()]
+                .binary_search_by(|e| {
+                    e.entry_pack_location
+                        .as_ref()
+                        .expect("packed")
+                        .pack_offset
+                        .cmp(&pack_offset)
+                })
+                .ok()
+                .map(|idx| idx + range.start)
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    impl SortedCounts for output::count::spill::Sorted {
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn get(&self, index: usize) -> Cow<'_, output::Count> {
+            Cow::Owned(self.get(index))
+        }
+
+        fn find_by_pack_offset(
+            &self,
+            range: std::ops::Range<usize>,
+            pack_offset: crate::data::Offset,
+        ) -> Option<usize> {
+            self.find_by_pack_offset(range, pack_offset)
+        }
+    }
+
+    fn iter_from_sorted<Find, IsThinBase, Counts>(
+        counts: Arc<Counts>,
+        counts_range_by_pack_id: Vec<(u32, std::ops::Range<usize>)>,
+        db: Find,
+        is_thin_base: IsThinBase,
+        progress: Box<dyn DynNestedProgress + 'static>,
+        Options {
+            version,
+            mode: _,
+            allow_thin_pack,
+            thread_limit,
+            chunk_size,
+        }: Options,
+    ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
+           + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
+    where
+        Find: crate::Find + Send + Clone + 'static,
+        IsThinBase: Fn(&gix_hash::oid) -> bool + Send + Clone + 'static,
+        Counts: SortedCounts,
+    {
+        assert!(
+            matches!(version, crate::data::Version::V2),
+            "currently we can only write version 2"
+        );
         let progress = Arc::new(parking_lot::Mutex::new(progress));
         let chunks = util::ChunkRanges::new(chunk_size, counts.len());
 
@@ -154,15 +292,15 @@ pub(crate) mod function {
                 }
             },
             {
-                let counts = Arc::clone(&counts);
                 move |(chunk_id, chunk_range): (SequenceId, std::ops::Range<usize>), (buf, progress)| {
                     let mut out = Vec::new();
-                    let chunk = &counts[chunk_range];
                     let mut stats = Outcome::default();
                     let mut pack_offsets_to_id = None;
-                    progress.init(Some(chunk.len()), gix_features::progress::count("objects"));
+                    progress.init(Some(chunk_range.len()), gix_features::progress::count("objects"));
 
-                    for count in chunk.iter() {
+                    for index in chunk_range {
+                        let count = counts.get(index);
+                        let count = count.as_ref();
                         out.push(match count
                             .entry_pack_location
                             .as_ref()
@@ -179,13 +317,10 @@ pub(crate) mod function {
                                     .expect("pack-id always present")]
                                 .1
                                 .clone();
-                                let base_index_offset = pack_range.start;
-                                let counts_in_pack = &counts[pack_range];
-                                let entry = output::Entry::from_pack_entry(
+                                let entry = output::Entry::from_pack_entry_with_base_lookup(
                                     pack_entry,
                                     count,
-                                    counts_in_pack,
-                                    base_index_offset,
+                                    |base_offset| counts.find_by_pack_offset(pack_range, base_offset),
                                     allow_thin_pack.then_some({
                                         |pack_id, base_offset| {
                                             let (cached_pack_id, cache) = pack_offsets_to_id.get_or_insert_with(|| {
@@ -197,11 +332,13 @@ pub(crate) mod function {
                                                     .expect("pack used for counts is still available")
                                             });
                                             debug_assert_eq!(*cached_pack_id, pack_id);
-                                            stats.ref_delta_objects += 1;
-                                            cache
+                                            let base_id = cache
                                                 .binary_search_by_key(&base_offset, |e| e.0)
                                                 .ok()
                                                 .map(|idx| cache[idx].1)
+                                                .filter(|id| is_thin_base(id));
+                                            stats.ref_delta_objects += usize::from(base_id.is_some());
+                                            base_id
                                         }
                                     }),
                                     version,
@@ -379,6 +516,8 @@ mod types {
         /// commit graph. Please note that thin packs are not valid packs at rest, thus they are only valid for packs in transit.
         ///
         /// If set to false, delta objects will be decompressed and recompressed as base objects.
+        /// Use [`iter_from_counts_with_thin_bases()`][crate::data::output::entry::iter_from_counts_with_thin_bases()] to control
+        /// which objects may serve as base outside of the pack.
         pub allow_thin_pack: bool,
         /// The amount of objects per chunk or unit of work to be sent to threads for processing
         /// TODO: could this become the window size?
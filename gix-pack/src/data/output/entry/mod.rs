@@ -7,7 +7,9 @@ use crate::{data, data::output, find};
 ///
 #[allow(clippy::empty_docs)]
 pub mod iter_from_counts;
-pub use iter_from_counts::function::iter_from_counts;
+#[cfg(not(target_arch = "wasm32"))]
+pub use iter_from_counts::function::iter_from_sorted_counts;
+pub use iter_from_counts::function::{iter_from_counts, iter_from_counts_with_thin_bases};
 
 /// The kind of pack entry to be written
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
@@ -64,12 +66,41 @@ impl output::Entry {
     /// Create an Entry from a previously counted object which is located in a pack. It's `entry` is provided here.
     /// The `version` specifies what kind of target `Entry` version the caller desires.
     pub fn from_pack_entry(
-        mut entry: find::Entry,
+        entry: find::Entry,
         count: &output::Count,
         potential_bases: &[output::Count],
         bases_index_offset: usize,
         pack_offset_to_oid: Option<impl FnMut(u32, u64) -> Option<ObjectId>>,
         target_version: data::Version,
+    ) -> Option<Result<Self, Error>> {
+        Self::from_pack_entry_with_base_lookup(
+            entry,
+            count,
+            |base_offset| {
+                potential_bases
+                    .binary_search_by(|e| {
+                        e.entry_pack_location
+                            .as_ref()
+                            .expect("packed")
+                            .pack_offset
+                            .cmp(&base_offset)
+                    })
+                    .ok()
+                    .map(|idx| idx + bases_index_offset)
+            },
+            pack_offset_to_oid,
+            target_version,
+        )
+    }
+
+    /// Like [`from_pack_entry()`](Self::from_pack_entry()), but uses `base_index_by_pack_offset(base_offset)` to find
+    /// the absolute index of the base object of an offset delta among all objects to be written.
+    pub(crate) fn from_pack_entry_with_base_lookup(
+        mut entry: find::Entry,
+        count: &output::Count,
+        base_index_by_pack_offset: impl FnOnce(u64) -> Option<usize>,
+        pack_offset_to_oid: Option<impl FnMut(u32, u64) -> Option<ObjectId>>,
+        target_version: data::Version,
     ) -> Option<Result<Self, Error>> {
         if entry.version != target_version {
             return None;
@@ -94,18 +125,8 @@ impl output::Entry {
                     .pack_offset
                     .checked_sub(base_distance)
                     .expect("pack-offset - distance is firmly within the pack");
-                potential_bases
-                    .binary_search_by(|e| {
-                        e.entry_pack_location
-                            .as_ref()
-                            .expect("packed")
-                            .pack_offset
-                            .cmp(&base_offset)
-                    })
-                    .ok()
-                    .map(|idx| output::entry::Kind::DeltaRef {
-                        object_index: idx + bases_index_offset,
-                    })
+                base_index_by_pack_offset(base_offset)
+                    .map(|object_index| output::entry::Kind::DeltaRef { object_index })
                     .or_else(|| {
                         pack_offset_to_oid
                             .and_then(|mut f| f(pack_location.pack_id, base_offset))
@@ -42,3 +42,7 @@ pub mod entry;
 ///
 #[allow(clippy::empty_docs)]
 pub mod bytes;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod delta;
@@ -42,6 +42,33 @@ pub trait Find {
         pack_cache: &mut dyn crate::cache::DecodeEntry,
     ) -> Result<Option<(gix_object::Data<'a>, Option<data::entry::Location>)>, gix_object::find::Error>;
 
+    /// Like [`Find::try_find_cached()`], but return the header of the object along with a reader for its decoded data,
+    /// which is useful for objects that are too large to be held in memory.
+    /// The `pack_cache` is used for objects which have to be decoded into memory nonetheless, like those stored as delta.
+    ///
+    /// The default implementation reads the whole object into memory using [`Find::try_find_cached()`],
+    /// and implementors should override it if they can do better.
+    fn try_find_stream_cached(
+        &self,
+        id: &gix_hash::oid,
+        pack_cache: &mut dyn crate::cache::DecodeEntry,
+    ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+        let mut buf = Vec::new();
+        let Some(kind) = self
+            .try_find_cached(id, &mut buf, pack_cache)?
+            .map(|(data, _location)| data.kind)
+        else {
+            return Ok(None);
+        };
+        Ok(Some((
+            gix_object::Header {
+                kind,
+                size: buf.len() as u64,
+            },
+            Box::new(std::io::Cursor::new(buf)),
+        )))
+    }
+
     /// Find the packs location where an object with `id` can be found in the database, or `None` if there is no pack
     /// holding the object.
     ///
@@ -183,6 +210,14 @@ mod find_impls {
             (*self).try_find_cached(id, buffer, pack_cache)
         }
 
+        fn try_find_stream_cached(
+            &self,
+            id: &oid,
+            pack_cache: &mut dyn crate::cache::DecodeEntry,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            (*self).try_find_stream_cached(id, pack_cache)
+        }
+
         fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<data::entry::Location> {
             (*self).location_by_oid(id, buf)
         }
@@ -213,6 +248,14 @@ mod find_impls {
             self.deref().try_find_cached(id, buffer, pack_cache)
         }
 
+        fn try_find_stream_cached(
+            &self,
+            id: &oid,
+            pack_cache: &mut dyn crate::cache::DecodeEntry,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            self.deref().try_find_stream_cached(id, pack_cache)
+        }
+
         fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<data::entry::Location> {
             self.deref().location_by_oid(id, buf)
         }
@@ -243,6 +286,14 @@ mod find_impls {
             self.deref().try_find_cached(id, buffer, pack_cache)
         }
 
+        fn try_find_stream_cached(
+            &self,
+            id: &oid,
+            pack_cache: &mut dyn crate::cache::DecodeEntry,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            self.deref().try_find_stream_cached(id, pack_cache)
+        }
+
         fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<data::entry::Location> {
             self.deref().location_by_oid(id, buf)
         }
@@ -273,6 +324,14 @@ mod find_impls {
             self.deref().try_find_cached(id, buffer, pack_cache)
         }
 
+        fn try_find_stream_cached(
+            &self,
+            id: &oid,
+            pack_cache: &mut dyn crate::cache::DecodeEntry,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            self.deref().try_find_stream_cached(id, pack_cache)
+        }
+
         fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<data::entry::Location> {
             self.deref().location_by_oid(id, buf)
         }
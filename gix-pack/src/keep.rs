@@ -0,0 +1,48 @@
+//! `.keep` files, which mark packs that must not be removed or combined with other packs during maintenance like repacks.
+//!
+//! Writers of packs create them right before moving a new pack into place and remove them once references point to its
+//! objects, so a concurrent repack doesn't consider its objects unreachable. Users may also create them to keep a pack forever.
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Return the path to the `.keep` file of the pack whose data or index file is at `pack_path`.
+pub fn path(pack_path: &Path) -> PathBuf {
+    pack_path.with_extension("keep")
+}
+
+/// Return `true` if the pack whose data or index file is at `pack_path` has a `.keep` file.
+pub fn is_kept(pack_path: &Path) -> bool {
+    path(pack_path).is_file()
+}
+
+/// Create the `.keep` file for the pack whose data or index file is at `pack_path` with `reason` as content, and return
+/// its path, or `None` if it already existed.
+///
+/// The file is created exclusively, so of multiple concurrent callers only one will succeed, and an existing `.keep` file
+/// is left untouched as it belongs to someone else.
+pub fn create(pack_path: &Path, reason: &[u8]) -> std::io::Result<Option<PathBuf>> {
+    let keep_path = path(pack_path);
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&keep_path)
+    {
+        Ok(mut file) => {
+            file.write_all(reason)?;
+            Ok(Some(keep_path))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Remove the `.keep` file of the pack whose data or index file is at `pack_path`, and return `true` if it existed.
+pub fn remove(pack_path: &Path) -> std::io::Result<bool> {
+    match std::fs::remove_file(path(pack_path)) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
@@ -46,6 +46,7 @@ pub use find_traits::{Find, FindExt};
 ///
 #[allow(clippy::empty_docs)]
 pub mod index;
+pub mod keep;
 ///
 #[allow(clippy::empty_docs)]
 pub mod multi_index;
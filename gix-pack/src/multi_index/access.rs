@@ -47,6 +47,10 @@ impl File {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+    /// Returns the length of all mapped data, including the header and the trailing checksum.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
     /// Returns the checksum over the entire content of the file (excluding the checksum itself).
     ///
     /// It can be used to validate it didn't change after creation.
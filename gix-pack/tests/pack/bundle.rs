@@ -176,8 +176,77 @@ mod write_to_directory {
                 iteration_mode: pack::data::input::Mode::Verify,
                 index_version: pack::index::Version::V2,
                 object_hash: gix_hash::Kind::Sha1,
+                fsync_pack: None,
+                fsync_index: None,
             },
         )
         .map_err(Into::into)
     }
 }
+
+mod inspect {
+    use gix_odb::pack;
+
+    use crate::{fixture_path, pack::V2_PACKS_AND_INDICES};
+
+    #[test]
+    fn entries_are_ordered_by_offset_and_describe_their_deltas() -> crate::Result {
+        for (index_path, _data_path) in V2_PACKS_AND_INDICES {
+            let bundle = pack::Bundle::at(fixture_path(index_path), gix_hash::Kind::Sha1)?;
+            let entries = bundle.inspect().collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(entries.len(), bundle.index.num_objects() as usize);
+            assert_eq!(entries[0].pack_offset, 12, "the first entry follows the pack header");
+
+            let mut num_deltas = 0;
+            for (entry, next) in entries.iter().zip(entries.iter().skip(1)) {
+                assert!(entry.pack_offset < next.pack_offset);
+                assert_eq!(entry.pack_offset + entry.compressed_size, next.pack_offset);
+            }
+            let last = entries.last().expect("non-empty");
+            assert_eq!(
+                last.pack_offset + last.compressed_size,
+                bundle.pack.pack_end() as u64,
+                "the last entry ends where the trailer starts"
+            );
+
+            for entry in &entries {
+                assert_eq!(
+                    bundle
+                        .index
+                        .lookup(entry.id)
+                        .map(|idx| bundle.index.pack_offset_at_index(idx)),
+                    Some(entry.pack_offset)
+                );
+                match entry.base {
+                    None => {
+                        assert_eq!(entry.chain_length, 0);
+                        assert_eq!(
+                            entry.object_size, entry.decompressed_size,
+                            "base objects are stored as is"
+                        );
+                    }
+                    Some(base) => {
+                        num_deltas += 1;
+                        let base = match base {
+                            pack::bundle::inspect::Base::Offset { pack_offset, id } => {
+                                let base = entries
+                                    .iter()
+                                    .find(|e| e.pack_offset == pack_offset)
+                                    .expect("base is in pack");
+                                assert_eq!(base.id, id);
+                                base
+                            }
+                            pack::bundle::inspect::Base::Ref { id, .. } => {
+                                entries.iter().find(|e| e.id == id).expect("base is in pack")
+                            }
+                        };
+                        assert_eq!(entry.chain_length, base.chain_length + 1);
+                        assert_eq!(entry.kind, base.kind, "deltas have the kind of their base");
+                    }
+                }
+            }
+            assert!(num_deltas > 0, "there are deltas in our packs");
+        }
+        Ok(())
+    }
+}
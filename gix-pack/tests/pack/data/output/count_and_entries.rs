@@ -320,6 +320,112 @@ fn traversals() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn thin_pack_bases_can_be_limited_to_known_objects() -> crate::Result {
+    let db = db(DbKind::DeterministicGeneratedContent)?;
+    let head = hex_to_id("dfcb5e39ac6eb30179808bbab721e8a28ce1b52e");
+    let (counts, _) = output::count::objects(
+        db.clone(),
+        Box::new(std::iter::once(Ok(head))),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::Options {
+            input_object_expansion: count::objects::ObjectExpansion::TreeAdditionsComparedToAncestor,
+            thread_limit: Some(1),
+            ..Default::default()
+        },
+    )?;
+    let thin = output::entry::iter_from_counts::Options {
+        allow_thin_pack: true,
+        ..Default::default()
+    };
+    let entries_with_bases = |is_thin_base: fn(&gix_hash::oid) -> bool| -> crate::Result<_> {
+        let mut iter = output::entry::iter_from_counts_with_thin_bases(
+            counts.clone(),
+            db.clone(),
+            is_thin_base,
+            Box::new(progress::Discard),
+            thin,
+        );
+        let entries: Vec<_> = InOrderIter::from(iter.by_ref())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        Ok((entries, iter.finalize()?))
+    };
+
+    let (entries, stats) = entries_with_bases(|_| true)?;
+    let thin_bases: Vec<_> = entries
+        .iter()
+        .filter_map(|e| match e.kind {
+            entry::Kind::DeltaOid { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        thin_bases.len(),
+        74,
+        "all bases outside of the pack are referred to by id"
+    );
+    assert_eq!(stats.ref_delta_objects, thin_bases.len());
+    write_and_verify(db.clone(), entries.clone(), head, None)?;
+
+    let mut pack = Vec::new();
+    let num_entries = entries.len() as u32;
+    for res in output::bytes::FromEntriesIter::new(
+        std::iter::once(Ok::<_, entry::iter_from_counts::Error>(entries)),
+        &mut pack,
+        num_entries,
+        pack::data::Version::V2,
+        gix_hash::Kind::Sha1,
+    ) {
+        res?;
+    }
+    static SHOULD_INTERRUPT: AtomicBool = AtomicBool::new(false);
+    let tmp_dir = gix_testtools::tempfile::TempDir::new()?;
+    let outcome = pack::Bundle::write_to_directory_eagerly(
+        Box::new(std::io::Cursor::new(pack)),
+        None,
+        Some(tmp_dir.path()),
+        &mut progress::Discard,
+        &SHOULD_INTERRUPT,
+        Some(db.clone()),
+        pack::bundle::write::Options::default(),
+    )?;
+    assert_eq!(
+        outcome.index.num_objects as usize,
+        counts.len() + thin_bases.iter().collect::<std::collections::BTreeSet<_>>().len(),
+        "the thin pack is completed with its base objects, just like with `--fix-thin`"
+    );
+    outcome.to_bundle().expect("written to directory")?.verify_integrity(
+        &mut progress::Discard,
+        &SHOULD_INTERRUPT,
+        gix_pack::index::verify::integrity::Options::default(),
+    )?;
+
+    let (entries, stats) = entries_with_bases(|_| false)?;
+    assert!(
+        entries.iter().all(|e| !matches!(e.kind, entry::Kind::DeltaOid { .. })),
+        "no base is known to the other side, so the pack isn't thin"
+    );
+    assert_eq!(stats.ref_delta_objects, 0);
+    assert_eq!(
+        stats.decoded_and_recompressed_objects, 74,
+        "deltas against unknown bases are recompressed as base objects"
+    );
+    write_and_verify(db.clone(), entries, head, None)?;
+
+    let (entries, stats) = entries_with_bases(|id| id.as_bytes()[0] < 0x80)?;
+    let expected_thin = thin_bases.iter().filter(|id| id.as_bytes()[0] < 0x80).count();
+    assert_ne!(expected_thin, 0, "the fixture must allow some, but not all bases");
+    assert_ne!(expected_thin, thin_bases.len());
+    assert_eq!(stats.ref_delta_objects, expected_thin);
+    assert_eq!(stats.decoded_and_recompressed_objects, 74 - expected_thin);
+    write_and_verify(db, entries, head, None)?;
+    Ok(())
+}
+
 #[test]
 fn empty_pack_is_allowed() {
     assert_eq!(
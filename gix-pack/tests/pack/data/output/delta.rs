@@ -0,0 +1,123 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_features::{progress, zlib};
+use gix_odb::{pack, pack::FindExt};
+use gix_pack::data::output::{self, count, delta};
+
+use crate::pack::{
+    data::output::{db, DbKind},
+    hex_to_id,
+};
+
+#[test]
+fn fresh_deltas_roundtrip_and_yield_smaller_packs() -> crate::Result {
+    let db = db(DbKind::DeterministicGeneratedContent)?;
+    let head = hex_to_id("dfcb5e39ac6eb30179808bbab721e8a28ce1b52e");
+    let (counts, _) = output::count::objects(
+        db.clone(),
+        Box::new(std::iter::once(Ok(head))),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::Options {
+            input_object_expansion: count::objects::ObjectExpansion::TreeContents,
+            thread_limit: Some(1),
+            ..Default::default()
+        },
+    )?;
+    let ids: Vec<_> = counts.iter().map(|count| count.id).collect();
+
+    let search = |options: delta::Options| {
+        delta::search(
+            db.clone(),
+            ids.iter().copied(),
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            options,
+        )
+    };
+    let (entries, outcome) = search(delta::Options {
+        thread_limit: Some(2),
+        ..Default::default()
+    })?;
+    assert_eq!(outcome.base_objects + outcome.delta_objects, ids.len());
+    assert!(
+        outcome.delta_objects > outcome.base_objects,
+        "most objects are similar to one another: {outcome:?}"
+    );
+    let packed_size = write_and_verify(&db, entries, &ids)?;
+
+    let (entries, outcome) = search(delta::Options {
+        window: 0,
+        ..Default::default()
+    })?;
+    assert_eq!(outcome.delta_objects, 0, "without window, there are no delta bases");
+    let unpacked_size = write_and_verify(&db, entries, &ids)?;
+    assert!(
+        packed_size * 3 < unpacked_size * 2,
+        "deltas make a difference: {packed_size} vs {unpacked_size}"
+    );
+
+    let (entries, outcome) = search(delta::Options {
+        depth: 1,
+        thread_limit: Some(1),
+        ..Default::default()
+    })?;
+    assert_eq!(
+        outcome.max_chain_length, 1,
+        "the depth limits the length of delta chains"
+    );
+    write_and_verify(&db, entries, &ids)?;
+    Ok(())
+}
+
+/// Write `entries` into a pack, index it and assure all objects identified by `ids` can be read from it,
+/// returning the size of the pack.
+fn write_and_verify(
+    db: &gix_odb::HandleArc,
+    entries: Vec<output::Entry>,
+    ids: &[gix_hash::ObjectId],
+) -> crate::Result<u64> {
+    let mut pack = Vec::new();
+    let num_entries = entries.len() as u32;
+    for res in output::bytes::FromEntriesIter::new(
+        std::iter::once(Ok::<_, delta::Error>(entries)),
+        &mut pack,
+        num_entries,
+        pack::data::Version::V2,
+        gix_hash::Kind::Sha1,
+    ) {
+        res?;
+    }
+    let pack_size = pack.len() as u64;
+
+    let should_interrupt = AtomicBool::new(false);
+    let tmp_dir = gix_testtools::tempfile::TempDir::new()?;
+    let outcome = pack::Bundle::write_to_directory(
+        &mut std::io::Cursor::new(pack),
+        Some(tmp_dir.path()),
+        &mut progress::Discard,
+        &should_interrupt,
+        None::<gix_odb::HandleArc>,
+        pack::bundle::write::Options::default(),
+    )?;
+    assert_eq!(outcome.index.num_objects as usize, ids.len());
+    let bundle = outcome.to_bundle().expect("written to directory")?;
+    bundle.verify_integrity(
+        &mut progress::Discard,
+        &should_interrupt,
+        gix_pack::index::verify::integrity::Options::default(),
+    )?;
+
+    let (mut expected, mut actual) = (Vec::new(), Vec::new());
+    let mut inflate = zlib::Inflate::default();
+    for id in ids {
+        let expected = db.find(id, &mut expected)?.0;
+        let actual = bundle
+            .find(id, &mut actual, &mut inflate, &mut pack::cache::Never)?
+            .expect("every object is in the pack")
+            .0;
+        assert_eq!(actual.kind, expected.kind);
+        assert_eq!(actual.data, expected.data, "{id} decodes to the original");
+    }
+    Ok(pack_size)
+}
@@ -42,3 +42,5 @@ fn db(kind: DbKind) -> crate::Result<gix_odb::HandleArc> {
 }
 
 mod count_and_entries;
+mod delta;
+mod spill;
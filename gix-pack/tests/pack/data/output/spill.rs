@@ -0,0 +1,101 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_features::{
+    parallel::{reduce::Finalize, InOrderIter},
+    progress,
+};
+use gix_pack::data::output::{self, count};
+
+use crate::pack::{
+    data::output::{db, DbKind},
+    hex_to_id,
+};
+
+fn entries(
+    iter: impl Iterator<Item = Result<(usize, Vec<output::Entry>), output::entry::iter_from_counts::Error>>
+        + Finalize<Reduce = impl gix_features::parallel::Reduce<Output = output::entry::iter_from_counts::Outcome>>,
+) -> crate::Result<(Vec<output::Entry>, output::entry::iter_from_counts::Outcome)> {
+    let mut iter = iter;
+    let entries = InOrderIter::from(iter.by_ref())
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    let stats = iter.finalize().map_err(|_| "finalize failed")?;
+    Ok((entries, stats))
+}
+
+#[test]
+fn spilled_counts_produce_the_same_entries_as_in_memory_counts() -> crate::Result {
+    let db = db(DbKind::DeterministicGeneratedContentMultiIndex)?;
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let head = hex_to_id("dfcb5e39ac6eb30179808bbab721e8a28ce1b52e");
+    let commits: Vec<_> = gix_traverse::commit::Simple::new(Some(head), db.clone())
+        .map(|c| c.map(|c| c.id))
+        .collect::<Result<_, _>>()?;
+
+    let (counts, expected_counts_outcome) = count::objects_unthreaded(
+        &db,
+        &mut commits.iter().copied().map(Ok),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::ObjectExpansion::TreeContents,
+    )?;
+
+    let mut spilled = count::spill::Counts::new(
+        gix_hash::Kind::Sha1,
+        count::spill::Options {
+            memory_limit_in_bytes: 100 * std::mem::size_of::<output::Count>(),
+            directory: Some(tmp.path().into()),
+        },
+    );
+    let counts_outcome = count::objects_unthreaded_into(
+        &db,
+        &mut commits.iter().copied().map(Ok),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::ObjectExpansion::TreeContents,
+        &mut spilled,
+    )?;
+    assert_eq!(counts_outcome, expected_counts_outcome);
+    assert_eq!(spilled.len(), counts.len());
+    assert!(spilled.num_spilled_runs() > 1, "multiple runs are needed to merge them");
+    let sorted = spilled.finish()?;
+    assert!(sorted.is_spilled());
+    assert_eq!(sorted.len(), counts.len());
+
+    let options = output::entry::iter_from_counts::Options {
+        thread_limit: Some(1),
+        ..Default::default()
+    };
+    let (expected_entries, expected_stats) = entries(output::entry::iter_from_counts(
+        counts,
+        db.clone(),
+        Box::new(progress::Discard),
+        options,
+    ))?;
+    let (actual_entries, actual_stats) = entries(output::entry::iter_from_sorted_counts(
+        sorted,
+        db.clone(),
+        |_| true,
+        Box::new(progress::Discard),
+        options,
+    ))?;
+    assert_eq!(actual_stats, expected_stats);
+    assert_eq!(
+        actual_entries, expected_entries,
+        "entries, including the position of their delta bases, are exactly the same"
+    );
+    Ok(())
+}
+
+#[test]
+fn counts_stay_in_memory_below_the_limit() -> crate::Result {
+    let mut counts = count::spill::Counts::new(gix_hash::Kind::Sha1, Default::default());
+    counts.push(output::Count::from_data(gix_hash::Kind::Sha1.null(), None))?;
+    assert_eq!(counts.num_spilled_runs(), 0);
+    let sorted = counts.finish()?;
+    assert!(!sorted.is_spilled());
+    assert_eq!(sorted.get(0).id, gix_hash::Kind::Sha1.null());
+    Ok(())
+}
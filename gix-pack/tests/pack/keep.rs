@@ -0,0 +1,28 @@
+use gix_pack::keep;
+use gix_testtools::tempfile::TempDir;
+
+#[test]
+fn create_is_exclusive_and_remove_is_idempotent() -> crate::Result {
+    let dir = TempDir::new()?;
+    let pack_path = dir.path().join("pack-abc.pack");
+    let index_path = pack_path.with_extension("idx");
+    assert!(!keep::is_kept(&pack_path));
+
+    let keep_path = keep::create(&pack_path, b"fetch in progress")?.expect("created");
+    assert_eq!(keep_path, dir.path().join("pack-abc.keep"));
+    assert!(
+        keep::is_kept(&index_path),
+        "the keep file is shared by data and index file"
+    );
+    assert_eq!(
+        keep::create(&index_path, b"other")?,
+        None,
+        "an existing keep file belongs to someone else"
+    );
+    assert_eq!(std::fs::read(&keep_path)?, b"fetch in progress", "it wasn't touched");
+
+    assert!(keep::remove(&index_path)?);
+    assert!(!keep::is_kept(&pack_path));
+    assert!(!keep::remove(&pack_path)?, "nothing to remove anymore");
+    Ok(())
+}
@@ -46,7 +46,7 @@ fn prefixed_and_suffixed_data_to_write(
     prefix: &[u8],
     data: &[u8],
     suffix: &[u8],
-    mut out: impl io::Write,
+    out: impl io::Write,
 ) -> io::Result<usize> {
     let data_len = prefix.len() + data.len() + suffix.len();
     if data_len > MAX_DATA_LEN {
@@ -64,13 +64,30 @@ fn prefixed_and_suffixed_data_to_write(
     let data_len = data_len + 4;
     let buf = u16_to_hex(data_len as u16);
 
-    out.write_all(&buf)?;
-    if !prefix.is_empty() {
-        out.write_all(prefix)?;
-    }
-    out.write_all(data)?;
-    if !suffix.is_empty() {
-        out.write_all(suffix)?;
-    }
+    write_all_vectored(out, [&buf, prefix, data, suffix])?;
     Ok(data_len)
 }
+
+/// Write all `parts` to `out` using as few calls to [`write_vectored()`][io::Write::write_vectored()] as possible,
+/// so a packet line can be written at once without copying it into a contiguous buffer first.
+fn write_all_vectored(mut out: impl io::Write, mut parts: [&[u8]; 4]) -> io::Result<()> {
+    while parts.iter().any(|part| !part.is_empty()) {
+        let mut written = match out.write_vectored(&parts.map(io::IoSlice::new)) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole packet line",
+                ))
+            }
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        for part in parts.iter_mut() {
+            let skip = written.min(part.len());
+            *part = &part[skip..];
+            written -= skip;
+        }
+    }
+    Ok(())
+}
@@ -8,6 +8,14 @@ use crate::{
     PacketLineRef, StreamingPeekableIter, MAX_LINE_LEN, U16_HEX_BYTES,
 };
 
+/// The result of [`StreamingPeekableIter::read_line_or_data_into()`].
+pub(crate) enum LineOrData<'a> {
+    /// A line that was read into the internal buffer, including the band if there is one.
+    Line(PacketLineRef<'a>),
+    /// The amount of data bytes that were read into the buffer provided by the caller, without the band.
+    Data(usize),
+}
+
 /// Non-IO methods
 impl<T> StreamingPeekableIter<T>
 where
@@ -158,6 +166,82 @@ where
         }
     }
 
+    /// Like [`read_line()`][StreamingPeekableIter::read_line()], but read the data of the next line directly into `out`
+    /// if it fits and is data for the caller, which is every data line, or only those of the data band if `with_band` is `true`.
+    ///
+    /// This avoids copying data out of the internal buffer, and falls back to reading the line into the internal buffer
+    /// if that isn't possible, or if the next line was peeked or may be an ERR line which has to be detected.
+    pub(crate) fn read_line_or_data_into(
+        &mut self,
+        with_band: bool,
+        out: &mut [u8],
+    ) -> Option<io::Result<Result<LineOrData<'_>, decode::Error>>> {
+        if self.is_done {
+            return None;
+        }
+        if !self.peek_buf.is_empty() || self.fail_on_err_lines {
+            return self.read_line().map(|res| res.map(|res| res.map(LineOrData::Line)));
+        }
+        if self.buf.len() != MAX_LINE_LEN {
+            self.buf.resize(MAX_LINE_LEN, 0);
+        }
+        if let Err(err) = self.read.read_exact(&mut self.buf[..U16_HEX_BYTES]) {
+            return Some(Err(err));
+        }
+        let num_data_bytes = match decode::hex_prefix(&self.buf[..U16_HEX_BYTES]) {
+            Ok(decode::PacketLineOrWantedSize::Wanted(additional_bytes)) => additional_bytes as usize,
+            Ok(decode::PacketLineOrWantedSize::Line(line)) => {
+                let (line, _name) = match line {
+                    PacketLineRef::Flush => (PacketLineRef::Flush, "FLUSH"),
+                    PacketLineRef::Delimiter => (PacketLineRef::Delimiter, "DELIM"),
+                    PacketLineRef::ResponseEnd => (PacketLineRef::ResponseEnd, "RESPONSE_END"),
+                    PacketLineRef::Data(_) => unreachable!("hex prefixes never decode to data lines"),
+                };
+                if self.trace {
+                    gix_trace::trace!("<< {}", _name);
+                }
+                if self.delimiters.contains(&line) {
+                    self.is_done = true;
+                    self.stopped_at = Some(line);
+                    return None;
+                }
+                return Some(Ok(Ok(LineOrData::Line(line))));
+            }
+            Err(err) => return Some(Ok(Err(err))),
+        };
+        if U16_HEX_BYTES + num_data_bytes > MAX_LINE_LEN {
+            return Some(Ok(Err(decode::Error::DataLengthLimitExceeded {
+                length_in_bytes: num_data_bytes,
+            })));
+        }
+
+        let band_len = usize::from(with_band);
+        let data_start = U16_HEX_BYTES + band_len;
+        if let Err(err) = self.read.read_exact(&mut self.buf[U16_HEX_BYTES..data_start]) {
+            return Some(Err(err));
+        }
+        let band = with_band.then(|| self.buf[U16_HEX_BYTES]);
+        let num_data_bytes = num_data_bytes - band_len;
+        let (data, is_direct) = if matches!(band, None | Some(1)) && num_data_bytes <= out.len() {
+            (&mut out[..num_data_bytes], true)
+        } else {
+            (&mut self.buf[data_start..][..num_data_bytes], false)
+        };
+        if let Err(err) = self.read.read_exact(data) {
+            return Some(Err(err));
+        }
+        if self.trace {
+            gix_trace::trace!("<< {}", data.as_bstr().trim().as_bstr());
+        }
+        Some(Ok(Ok(if is_direct {
+            LineOrData::Data(num_data_bytes)
+        } else {
+            LineOrData::Line(PacketLineRef::Data(
+                &self.buf[U16_HEX_BYTES..data_start + num_data_bytes],
+            ))
+        })))
+    }
+
     /// Return this instance as implementor of [`Read`][io::Read] assuming side bands to be used in all received packet lines.
     /// Each invocation of [`read_line()`][io::BufRead::read_line()] returns a packet line.
     ///
@@ -1,6 +1,9 @@
 use std::{io, io::BufRead};
 
-use crate::{read::ProgressAction, BandRef, PacketLineRef, StreamingPeekableIter, TextRef, U16_HEX_BYTES};
+use crate::{
+    read::{blocking_io::LineOrData, ProgressAction},
+    BandRef, PacketLineRef, StreamingPeekableIter, TextRef, U16_HEX_BYTES,
+};
 
 /// An implementor of [`BufRead`][io::BufRead] yielding packet lines on each call to [`read_line()`][io::BufRead::read_line()].
 /// It's also possible to hide the underlying packet lines using the [`Read`][io::Read] implementation which is useful
@@ -127,6 +130,55 @@ where
     }
 }
 
+/// Process a `line` that was read while reading data, and return the offset and length of its data in the line buffer,
+/// or `None` if it only contained progress information that was passed to `handle_progress`.
+fn data_range_of<F>(line: PacketLineRef<'_>, handle_progress: Option<&mut F>) -> io::Result<Option<(usize, usize)>>
+where
+    F: FnMut(bool, &[u8]) -> ProgressAction,
+{
+    match handle_progress {
+        Some(handle_progress) => {
+            let band = line
+                .decode_band()
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+            const ENCODED_BAND: usize = 1;
+            match band {
+                BandRef::Data(d) => {
+                    if d.is_empty() {
+                        return Ok(None);
+                    }
+                    Ok(Some((U16_HEX_BYTES + ENCODED_BAND, d.len())))
+                }
+                BandRef::Progress(d) => {
+                    let text = TextRef::from(d).0;
+                    match handle_progress(false, text) {
+                        ProgressAction::Continue => Ok(None),
+                        ProgressAction::Interrupt => {
+                            Err(std::io::Error::new(std::io::ErrorKind::Other, "interrupted by user"))
+                        }
+                    }
+                }
+                BandRef::Error(d) => {
+                    let text = TextRef::from(d).0;
+                    match handle_progress(true, text) {
+                        ProgressAction::Continue => Ok(None),
+                        ProgressAction::Interrupt => {
+                            Err(std::io::Error::new(std::io::ErrorKind::Other, "interrupted by user"))
+                        }
+                    }
+                }
+            }
+        }
+        None => match line.as_slice() {
+            Some(d) => Ok(Some((U16_HEX_BYTES, d.len()))),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "encountered non-data line in a data-line only context",
+            )),
+        },
+    }
+}
+
 impl<'a, T, F> BufRead for WithSidebands<'a, T, F>
 where
     T: io::Read,
@@ -139,56 +191,8 @@ where
                     Some(line) => line?.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
                     None => break (0, 0),
                 };
-                match self.handle_progress.as_mut() {
-                    Some(handle_progress) => {
-                        let band = line
-                            .decode_band()
-                            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-                        const ENCODED_BAND: usize = 1;
-                        match band {
-                            BandRef::Data(d) => {
-                                if d.is_empty() {
-                                    continue;
-                                }
-                                break (U16_HEX_BYTES + ENCODED_BAND, d.len());
-                            }
-                            BandRef::Progress(d) => {
-                                let text = TextRef::from(d).0;
-                                match handle_progress(false, text) {
-                                    ProgressAction::Continue => {}
-                                    ProgressAction::Interrupt => {
-                                        return Err(std::io::Error::new(
-                                            std::io::ErrorKind::Other,
-                                            "interrupted by user",
-                                        ))
-                                    }
-                                };
-                            }
-                            BandRef::Error(d) => {
-                                let text = TextRef::from(d).0;
-                                match handle_progress(true, text) {
-                                    ProgressAction::Continue => {}
-                                    ProgressAction::Interrupt => {
-                                        return Err(std::io::Error::new(
-                                            std::io::ErrorKind::Other,
-                                            "interrupted by user",
-                                        ))
-                                    }
-                                };
-                            }
-                        };
-                    }
-                    None => {
-                        break match line.as_slice() {
-                            Some(d) => (U16_HEX_BYTES, d.len()),
-                            None => {
-                                return Err(io::Error::new(
-                                    io::ErrorKind::UnexpectedEof,
-                                    "encountered non-data line in a data-line only context",
-                                ))
-                            }
-                        }
-                    }
+                if let Some(range) = data_range_of(line, self.handle_progress.as_mut())? {
+                    break range;
                 }
             };
             self.cap = cap + ofs;
@@ -207,7 +211,28 @@ where
     T: io::Read,
     F: FnMut(bool, &[u8]) -> ProgressAction,
 {
+    /// Read data into `buf`, which receives the data of packet lines directly if they fit into it to avoid copying them
+    /// out of the line buffer.
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.cap && !buf.is_empty() {
+            let (ofs, cap) = loop {
+                let line = match self.parent.read_line_or_data_into(self.handle_progress.is_some(), buf) {
+                    Some(line) => line?.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
+                    None => return Ok(0),
+                };
+                match line {
+                    LineOrData::Data(0) => continue,
+                    LineOrData::Data(nread) => return Ok(nread),
+                    LineOrData::Line(line) => {
+                        if let Some(range) = data_range_of(line, self.handle_progress.as_mut())? {
+                            break range;
+                        }
+                    }
+                }
+            };
+            self.cap = cap + ofs;
+            self.pos = ofs;
+        }
         let mut rem = self.fill_buf()?;
         let nread = rem.read(buf)?;
         self.consume(nread);
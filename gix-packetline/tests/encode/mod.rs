@@ -100,3 +100,71 @@ mod flush_delim_response_end {
         Ok(())
     }
 }
+
+#[cfg(feature = "blocking-io")]
+mod band_to_write {
+    use std::io;
+
+    use bstr::ByteSlice;
+    use gix_packetline::{encode::band_to_write, Channel};
+
+    /// A writer which records the amount of calls and accepts at most `limit` bytes per call.
+    struct Chunked {
+        out: Vec<u8>,
+        calls: usize,
+        limit: usize,
+    }
+
+    impl io::Write for Chunked {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_vectored(&[io::IoSlice::new(buf)])
+        }
+
+        fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+            self.calls += 1;
+            let mut written = 0;
+            for buf in bufs {
+                let n = buf.len().min(self.limit - written);
+                self.out.extend_from_slice(&buf[..n]);
+                written += n;
+                if written == self.limit {
+                    break;
+                }
+            }
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_a_whole_line_with_a_single_vectored_write_if_possible() -> crate::Result {
+        let mut out = Chunked {
+            out: Vec::new(),
+            calls: 0,
+            limit: usize::MAX,
+        };
+        let res = band_to_write(Channel::Data, b"hello", &mut out)?;
+        assert_eq!(res, 10);
+        assert_eq!(out.out.as_bstr(), b"000a\x01hello".as_bstr());
+        assert_eq!(out.calls, 1, "header, band and data are written at once");
+        Ok(())
+    }
+
+    #[test]
+    fn partial_vectored_writes_are_continued() -> crate::Result {
+        for limit in 1..=10 {
+            let mut out = Chunked {
+                out: Vec::new(),
+                calls: 0,
+                limit,
+            };
+            let res = band_to_write(Channel::Progress, b"hello", &mut out)?;
+            assert_eq!(res, 10);
+            assert_eq!(out.out.as_bstr(), b"000a\x02hello".as_bstr(), "limit = {limit}");
+        }
+        Ok(())
+    }
+}
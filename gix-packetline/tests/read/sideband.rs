@@ -272,3 +272,60 @@ async fn handling_of_err_lines() {
         "An error can also be the reason, which is not distinguishable from an EOF"
     );
 }
+
+#[cfg(feature = "blocking-io")]
+#[test]
+fn reading_with_any_buffer_size_yields_the_same_data_and_progress() -> crate::Result {
+    use std::io::BufRead;
+
+    fn read_pack(
+        buf: &[u8],
+        read_all: impl FnOnce(&mut dyn BufRead) -> std::io::Result<Vec<u8>>,
+    ) -> std::io::Result<(Vec<u8>, Vec<BString>)> {
+        let mut rd = gix_packetline::StreamingPeekableIter::new(buf, &[PacketLineRef::Flush], false);
+        rd.as_read().read_to_end(&mut Vec::new())?;
+        rd.reset();
+        rd.read_line().expect("NAK")?.expect("valid line");
+        let mut texts = Vec::<BString>::new();
+        let data = read_all(&mut rd.as_read_with_sidebands(|_, text: &[u8]| {
+            texts.push(text.into());
+            ProgressAction::Continue
+        }))?;
+        Ok((data, texts))
+    }
+
+    let buf = fixture_bytes("v1/01-clone.combined-output");
+    let expected = read_pack(&buf, |rd| {
+        let mut out = Vec::new();
+        loop {
+            let data = rd.fill_buf()?;
+            if data.is_empty() {
+                break Ok(out);
+            }
+            out.extend_from_slice(data);
+            let len = data.len();
+            rd.consume(len);
+        }
+    })?;
+    assert!(expected.0.starts_with(b"PACK"), "buffered reads see the pack");
+    assert_eq!(expected.1.len(), 6, "all progress was seen");
+
+    for buf_size in [1, 7, 1024, 65536] {
+        let actual = read_pack(&buf, |rd| {
+            let mut out = Vec::new();
+            let mut chunk = vec![0; buf_size];
+            loop {
+                let nread = rd.read(&mut chunk)?;
+                if nread == 0 {
+                    break Ok(out);
+                }
+                out.extend_from_slice(&chunk[..nread]);
+            }
+        })?;
+        assert_eq!(
+            actual, expected,
+            "reading into a buffer of size {buf_size} directly or not doesn't matter"
+        );
+    }
+    Ok(())
+}
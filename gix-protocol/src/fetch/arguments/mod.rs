@@ -2,6 +2,27 @@ use std::fmt;
 
 use bstr::{BStr, BString, ByteSlice, ByteVec};
 
+/// The error returned by the fallible methods of [`Arguments`], like [`Arguments::try_deepen_since()`] and [`Arguments::validate()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(
+        "The server doesn't advertise the '{capability}' capability which is needed for the '{argument}' argument"
+    )]
+    MissingCapability {
+        capability: &'static str,
+        argument: &'static str,
+    },
+    #[error("'deepen <depth>' can't be used together with 'deepen-since' or 'deepen-not'")]
+    DepthWithSinceOrNot,
+    #[error("'deepen-relative' needs a depth set with 'deepen <depth>'")]
+    DeepenRelativeWithoutDepth,
+    #[error("Filter specifications must not be empty")]
+    EmptyFilter,
+    #[error("Only a single filter can be set, use 'combine:<spec>+<spec>' to combine filters")]
+    MultipleFilters,
+}
+
 /// The arguments passed to a server command.
 #[derive(Debug)]
 pub struct Arguments {
@@ -18,6 +39,7 @@ pub struct Arguments {
     deepen_not: bool,
     deepen_relative: bool,
     ref_in_want: bool,
+    packfile_uris: bool,
     supports_include_tag: bool,
 
     features_for_first_want: Option<Vec<String>>,
@@ -76,6 +98,12 @@ impl Arguments {
     pub fn can_use_ref_in_want(&self) -> bool {
         self.ref_in_want
     }
+    /// Return true if the 'packfile-uris' capability is supported.
+    ///
+    /// This allows servers to offload parts of the pack to separate downloads, typically from a CDN.
+    pub fn can_use_packfile_uris(&self) -> bool {
+        self.packfile_uris
+    }
     /// Return true if the 'include-tag' capability is supported.
     pub fn can_use_include_tag(&self) -> bool {
         self.supports_include_tag
@@ -161,6 +189,74 @@ impl Arguments {
             self.prefixed("filter ", spec);
         }
     }
+    /// Like [`shallow()`](Self::shallow()), but fails if the server doesn't support the 'shallow' capability.
+    pub fn try_shallow(&mut self, id: impl AsRef<gix_hash::oid>) -> Result<(), Error> {
+        self.require(self.shallow, "shallow", "shallow")?;
+        self.shallow(id);
+        Ok(())
+    }
+    /// Like [`deepen()`](Self::deepen()), but fails if the server doesn't support the 'shallow' capability.
+    pub fn try_deepen(&mut self, depth: usize) -> Result<(), Error> {
+        self.require(self.shallow, "shallow", "deepen")?;
+        self.deepen(depth);
+        Ok(())
+    }
+    /// Like [`deepen_since()`](Self::deepen_since()), but fails if the server doesn't support the 'deepen-since' capability.
+    pub fn try_deepen_since(&mut self, seconds: gix_date::SecondsSinceUnixEpoch) -> Result<(), Error> {
+        self.require(self.deepen_since, "deepen-since", "deepen-since")?;
+        self.deepen_since(seconds);
+        Ok(())
+    }
+    /// Like [`deepen_relative()`](Self::deepen_relative()), but fails if the server doesn't support the 'deepen-relative' capability.
+    pub fn try_deepen_relative(&mut self) -> Result<(), Error> {
+        self.require(self.deepen_relative, "deepen-relative", "deepen-relative")?;
+        self.deepen_relative();
+        Ok(())
+    }
+    /// Like [`deepen_not()`](Self::deepen_not()), but fails if the server doesn't support the 'deepen-not' capability.
+    pub fn try_deepen_not(&mut self, ref_path: &BStr) -> Result<(), Error> {
+        self.require(self.deepen_not, "deepen-not", "deepen-not")?;
+        self.deepen_not(ref_path);
+        Ok(())
+    }
+    /// Like [`filter()`](Self::filter()), but fails if the server doesn't support the 'filter' capability or if `spec` is empty.
+    pub fn try_filter(&mut self, spec: &str) -> Result<(), Error> {
+        self.require(self.filter, "filter", "filter")?;
+        if spec.trim().is_empty() {
+            return Err(Error::EmptyFilter);
+        }
+        self.filter(spec);
+        Ok(())
+    }
+    /// Check that the arguments added so far can be used together, as the server would reject the request otherwise.
+    ///
+    /// Shallow arguments and filters can be combined freely, but `deepen <depth>` can't be used together with
+    /// `deepen-since` or `deepen-not`, and `deepen-relative` only works with `deepen <depth>`.
+    pub fn validate(&self) -> Result<(), Error> {
+        let has = |prefix: &str| self.args.iter().any(|arg| arg.starts_with_str(prefix));
+        let has_depth = has("deepen ");
+        if has_depth && (has("deepen-since ") || has("deepen-not ")) {
+            return Err(Error::DepthWithSinceOrNot);
+        }
+        if !has_depth && self.args.iter().any(|arg| arg == "deepen-relative") {
+            return Err(Error::DeepenRelativeWithoutDepth);
+        }
+        if self.args.iter().filter(|arg| arg.starts_with_str("filter ")).count() > 1 {
+            return Err(Error::MultipleFilters);
+        }
+        Ok(())
+    }
+
+    /// Allow the server to send parts of the pack as URIs using any of the given `protocols`, like `https`,
+    /// which have to be downloaded separately and are listed in [`Response::packfile_uris()`][crate::fetch::Response::packfile_uris()].
+    ///
+    /// Needs to only be called once.
+    pub fn use_packfile_uris<'a>(&mut self, protocols: impl IntoIterator<Item = &'a str>) {
+        debug_assert!(self.packfile_uris, "'packfile-uris' feature required");
+        if self.packfile_uris {
+            self.prefixed("packfile-uris ", protocols.into_iter().collect::<Vec<_>>().join(","));
+        }
+    }
     /// Permanently allow the server to include tags that point to commits or objects it would return.
     ///
     /// Needs to only be called once.
@@ -191,6 +287,14 @@ impl Arguments {
         }
     }
 
+    fn require(&self, is_supported: bool, capability: &'static str, argument: &'static str) -> Result<(), Error> {
+        if is_supported {
+            Ok(())
+        } else {
+            Err(Error::MissingCapability { capability, argument })
+        }
+    }
+
     fn prefixed(&mut self, prefix: &str, value: impl fmt::Display) {
         self.args.push(format!("{prefix}{value}").into());
     }
@@ -204,6 +308,7 @@ impl Arguments {
         let filter = has("filter");
         let shallow = has("shallow");
         let ref_in_want = has("ref-in-want");
+        let packfile_uris = version == gix_transport::Protocol::V2 && has("packfile-uris");
         let mut deepen_since = shallow;
         let mut deepen_not = shallow;
         let mut deepen_relative = shallow;
@@ -243,6 +348,7 @@ impl Arguments {
             deepen_not,
             deepen_relative,
             ref_in_want,
+            packfile_uris,
             deepen_since,
             features_for_first_want,
             trace,
@@ -1,4 +1,6 @@
-mod arguments;
+///
+#[allow(clippy::empty_docs)]
+pub mod arguments;
 pub use arguments::Arguments;
 
 ///
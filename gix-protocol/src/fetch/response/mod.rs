@@ -79,6 +79,17 @@ pub struct WantedRef {
     pub path: BString,
 }
 
+/// A packfile-uri line received from the server, pointing to a pack that has to be downloaded separately as it's not part
+/// of the pack sent by the server.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackfileUri {
+    /// The checksum of the pack as found in its trailer, which must be verified after downloading it.
+    pub hash: gix_hash::ObjectId,
+    /// The URI to download the pack from.
+    pub uri: BString,
+}
+
 impl ShallowUpdate {
     /// Parse a `ShallowUpdate` from a `line` as received to the server.
     pub fn from_line(line: &str) -> Result<ShallowUpdate, Error> {
@@ -148,12 +159,27 @@ impl WantedRef {
     }
 }
 
+impl PackfileUri {
+    /// Parse a `PackfileUri` from a `line` as received from the server.
+    pub fn from_line(line: &str) -> Result<PackfileUri, Error> {
+        match line.trim_end().split_once(' ') {
+            Some((hash, uri)) if !uri.is_empty() => {
+                let hash = gix_hash::ObjectId::from_hex(hash.as_bytes())
+                    .map_err(|_| Error::UnknownLineType { line: line.to_owned() })?;
+                Ok(PackfileUri { hash, uri: uri.into() })
+            }
+            _ => Err(Error::UnknownLineType { line: line.to_owned() }),
+        }
+    }
+}
+
 /// A representation of a complete fetch response
 #[derive(Debug)]
 pub struct Response {
     acks: Vec<Acknowledgement>,
     shallows: Vec<ShallowUpdate>,
     wanted_refs: Vec<WantedRef>,
+    packfile_uris: Vec<PackfileUri>,
     has_pack: bool,
 }
 
@@ -207,6 +233,14 @@ impl Response {
     pub fn wanted_refs(&self) -> &[WantedRef] {
         &self.wanted_refs
     }
+
+    /// Return all packfile-uris [parsed previously][Response::from_line_reader()], which point to packs that have to be
+    /// downloaded in addition to the pack sent by the server.
+    ///
+    /// These are only sent if the client asked for them with [`Arguments::use_packfile_uris()`][crate::fetch::Arguments::use_packfile_uris()].
+    pub fn packfile_uris(&self) -> &[PackfileUri] {
+        &self.packfile_uris
+    }
 }
 
 #[cfg(any(feature = "async-client", feature = "blocking-client"))]
@@ -412,5 +412,144 @@ mod arguments {
                     .as_bstr()
             )
         }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn packfile_uris() {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v2(["packfile-uris"].iter().copied());
+            assert!(arguments.can_use_packfile_uris());
+
+            arguments.use_packfile_uris(["https", "http"]);
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+001dpackfile-uris https,http
+0032want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907
+0009done
+0000"
+                    .as_bstr()
+            )
+        }
+    }
+
+    mod validation {
+        use bstr::ByteSlice;
+
+        use super::{arguments_v1, arguments_v2, id, transport};
+        use crate::fetch::arguments::Error;
+
+        #[test]
+        fn missing_capabilities_are_errors() {
+            let mut arguments = arguments_v1(["shallow", "deepen-relative"].iter().copied());
+            arguments.try_deepen(1).expect("supported");
+            arguments.try_deepen_relative().expect("supported");
+            assert!(matches!(
+                arguments.try_deepen_since(12345),
+                Err(Error::MissingCapability {
+                    capability: "deepen-since",
+                    argument: "deepen-since"
+                })
+            ));
+            assert!(matches!(
+                arguments.try_deepen_not("refs/heads/main".into()),
+                Err(Error::MissingCapability {
+                    capability: "deepen-not",
+                    ..
+                })
+            ));
+            assert!(matches!(
+                arguments.try_filter("blob:none"),
+                Err(Error::MissingCapability {
+                    capability: "filter",
+                    ..
+                })
+            ));
+            arguments.validate().expect("nothing was added that isn't supported");
+
+            let mut arguments = arguments_v2(Some("filter"));
+            assert!(matches!(
+                arguments.try_shallow(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c9ff")),
+                Err(Error::MissingCapability {
+                    capability: "shallow",
+                    argument: "shallow"
+                })
+            ));
+            assert!(matches!(arguments.try_filter(" "), Err(Error::EmptyFilter)));
+        }
+
+        #[test]
+        fn incompatible_shallow_arguments() {
+            let mut arguments = arguments_v2(Some("shallow"));
+            arguments.try_deepen(1).unwrap();
+            arguments.try_deepen_since(12345).unwrap();
+            assert!(matches!(arguments.validate(), Err(Error::DepthWithSinceOrNot)));
+
+            let mut arguments = arguments_v2(Some("shallow"));
+            arguments.try_deepen_not("refs/heads/main".into()).unwrap();
+            arguments.try_deepen(1).unwrap();
+            assert!(matches!(arguments.validate(), Err(Error::DepthWithSinceOrNot)));
+
+            let mut arguments = arguments_v2(Some("shallow"));
+            arguments.try_deepen_relative().unwrap();
+            assert!(matches!(arguments.validate(), Err(Error::DeepenRelativeWithoutDepth)));
+
+            let mut arguments = arguments_v2(Some("filter"));
+            arguments.try_filter("blob:none").unwrap();
+            arguments.try_filter("tree:0").unwrap();
+            assert!(matches!(arguments.validate(), Err(Error::MultipleFilters)));
+        }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn shallow_arguments_can_be_combined_with_a_filter() -> Result<(), Error> {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v1(["shallow", "deepen-since", "deepen-not", "filter"].iter().copied());
+
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.try_deepen_since(12345)?;
+            arguments.try_deepen_not("refs/heads/main".into())?;
+            arguments.try_filter("blob:none")?;
+            arguments.validate()?;
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0059want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907 shallow deepen-since deepen-not filter
+0017deepen-since 12345
+001fdeepen-not refs/heads/main
+0015filter blob:none
+00000009done
+"
+                .as_bstr()
+            );
+
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v2(["shallow", "filter"].iter().copied());
+            arguments.try_deepen(2)?;
+            arguments.try_deepen_relative()?;
+            arguments.try_filter("tree:0")?;
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.validate()?;
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+000ddeepen 2
+0014deepen-relative
+0012filter tree:0
+0032want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907
+0009done
+0000"
+                    .as_bstr()
+            );
+            Ok(())
+        }
     }
 }
@@ -72,6 +72,7 @@ where
         server_protocol_version: protocol_version,
         refs,
         capabilities,
+        credentials: _,
     } = crate::fetch::handshake(
         &mut transport,
         authenticate,
@@ -23,7 +23,7 @@ where
     T: client::Transport,
 {
     let _span = gix_features::trace::detail!("gix_protocol::handshake()", service = ?service, extra_parameters = ?extra_parameters);
-    let (server_protocol_version, refs, capabilities) = {
+    let (server_protocol_version, refs, capabilities, credentials) = {
         progress.init(None, progress::steps());
         progress.set_name("handshake".into());
         progress.step();
@@ -35,6 +35,7 @@ where
         let supported_versions: Vec<_> = transport.supported_protocol_versions().into();
 
         let result = transport.handshake(service, &extra_parameters).await;
+        let mut credentials = None;
         let SetServiceResponse {
             actual_protocol,
             capabilities,
@@ -53,7 +54,8 @@ where
                 progress.set_name("handshake (authenticated)".into());
                 match transport.handshake(service, &extra_parameters).await {
                     Ok(v) => {
-                        authenticate(next.store())?;
+                        authenticate(next.clone().store())?;
+                        credentials = Some(next);
                         Ok(v)
                     }
                     // Still no permission? Reject the credentials.
@@ -92,12 +94,13 @@ where
             }
             None => None,
         };
-        (actual_protocol, parsed_refs, capabilities)
+        (actual_protocol, parsed_refs, capabilities, credentials)
     }; // this scope is needed, see https://github.com/rust-lang/rust/issues/76149
 
     Ok(Outcome {
         server_protocol_version,
         refs,
         capabilities,
+        credentials,
     })
 }
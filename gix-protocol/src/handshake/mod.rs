@@ -58,6 +58,12 @@ pub struct Outcome {
     pub refs: Option<Vec<Ref>>,
     /// The server capabilities.
     pub capabilities: Capabilities,
+    /// If authentication was required, this is the handle to the credentials that were accepted by the remote during the handshake.
+    ///
+    /// Use it to [erase](crate::credentials::helper::NextAction::erase()) these credentials if the remote rejects them in
+    /// subsequent requests of the same operation.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub credentials: Option<crate::credentials::helper::NextAction>,
 }
 
 mod error {
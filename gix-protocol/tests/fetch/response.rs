@@ -209,7 +209,7 @@ mod v2 {
         use gix_packetline::read::ProgressAction;
         use gix_protocol::fetch::{
             self,
-            response::{Acknowledgement, ShallowUpdate},
+            response::{Acknowledgement, PackfileUri, ShallowUpdate},
         };
         use gix_transport::Protocol;
 
@@ -237,6 +237,28 @@ mod v2 {
             Ok(())
         }
 
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn clone_with_packfile_uris() -> crate::Result {
+            let mut provider = mock_reader("v2/clone-packfile-uris.response");
+            let mut reader = provider.as_read_without_sidebands();
+            let r = fetch::Response::from_line_reader(Protocol::V2, &mut reader, true, true).await?;
+            assert_eq!(
+                r.packfile_uris(),
+                &[PackfileUri {
+                    hash: id("a1d3c8e2b0f4956e7d3c1b2a0f9e8d7c6b5a4f3e"),
+                    uri: "https://cdn.example.com/packs/pack-a1d3c8e2b0f4956e7d3c1b2a0f9e8d7c6b5a4f3e.pack".into()
+                }]
+            );
+            assert!(r.has_pack(), "the remainder of the pack follows");
+            reader.set_progress_handler(Some(Box::new(|_is_err, _text| {
+                gix_transport::packetline::read::ProgressAction::Continue
+            })));
+            let mut buf = Vec::new();
+            let bytes_read = reader.read_to_end(&mut buf).await?;
+            assert_eq!(bytes_read, 876, "should be able to read the whole pack");
+            Ok(())
+        }
+
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn shallow_clone() -> crate::Result {
             let mut provider = mock_reader("v2/clone-deepen-1.response");
@@ -54,6 +54,7 @@ mod init {
                 common_dir: None,
                 write_reflog,
                 namespace: None,
+                fsync: None,
                 prohibit_windows_device_names,
                 packed: gix_fs::SharedFileSnapshotMut::new().into(),
                 object_hash,
@@ -82,6 +83,7 @@ mod init {
                 common_dir: Some(common_dir),
                 write_reflog,
                 namespace: None,
+                fsync: None,
                 prohibit_windows_device_names,
                 packed: gix_fs::SharedFileSnapshotMut::new().into(),
                 object_hash,
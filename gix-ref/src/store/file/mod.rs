@@ -27,6 +27,9 @@ pub struct Store {
     pub write_reflog: WriteReflog,
     /// The namespace to use for edits and reads
     pub namespace: Option<Namespace>,
+    /// How to flush loose references and the `packed-refs` file to permanent storage before moving them into place,
+    /// or `None` to not flush them.
+    pub fsync: Option<gix_fs::fsync::Method>,
     /// This is only needed on Windows, where some device names are reserved at any level of a path, so that
     /// reading or writing `refs/heads/CON` for example would read from the console, or write to it.
     pub prohibit_windows_device_names: bool,
@@ -90,6 +93,7 @@ pub struct Transaction<'s, 'p> {
     packed_transaction: Option<crate::store_impl::packed::Transaction>,
     updates: Option<Vec<transaction::Edit>>,
     packed_refs: transaction::PackedRefs<'p>,
+    observer: Option<transaction::Observer<'p>>,
 }
 
 pub(in crate::store_impl::file) fn path_to_name<'a>(path: impl Into<Cow<'a, Path>>) -> Cow<'a, BStr> {
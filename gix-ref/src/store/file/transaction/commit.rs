@@ -1,5 +1,8 @@
 use crate::{
-    store_impl::file::{transaction::PackedRefs, Transaction},
+    store_impl::file::{
+        transaction::{notify_observer, PackedRefs, State},
+        Transaction,
+    },
     transaction::{Change, LogChange, RefEdit, RefLog},
     Target,
 };
@@ -29,7 +32,7 @@ impl<'s, 'p> Transaction<'s, 'p> {
         self.commit_inner(committer.into())
     }
 
-    fn commit_inner(self, committer: Option<gix_actor::SignatureRef<'_>>) -> Result<Vec<RefEdit>, Error> {
+    fn commit_inner(mut self, committer: Option<gix_actor::SignatureRef<'_>>) -> Result<Vec<RefEdit>, Error> {
         let mut updates = self.updates.expect("BUG: must call prepare before commit");
         let delete_loose_refs = matches!(
             self.packed_refs,
@@ -173,6 +176,8 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 drop(lock)
             }
         }
+        // Like `git`, the outcome of notifying about changes that were already made is ignored.
+        notify_observer(self.observer.as_mut(), State::Committed, &updates).ok();
         Ok(updates.into_iter().map(|edit| edit.update).collect())
     }
 }
@@ -5,7 +5,7 @@ use gix_object::bstr::BString;
 
 use crate::{
     store_impl::{file, file::Transaction},
-    transaction::RefEdit,
+    transaction::{Change, RefEdit, RefLog},
 };
 
 /// How to handle packed refs during a transaction
@@ -27,8 +27,52 @@ pub enum PackedRefs<'a> {
     /// Thus, this is similar to `DeletionsAndNonSymbolicUpdates`, but removes the loose reference after the update, leaving only their copy
     /// in `packed-refs`.
     DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(Box<dyn gix_object::Find + 'a>),
+    /// Behave like `DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference` if the transaction updates at least `threshold`
+    /// references which are peeled and contain an object id, or like `DeletionsOnly` otherwise.
+    ///
+    /// This writes large amounts of updates, like the ones of mirror fetches, in one pass into `packed-refs` instead of
+    /// creating a loose file for each of them, while small transactions keep writing loose references.
+    DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast {
+        /// The least amount of non-symbolic updates which causes them to be written into `packed-refs`.
+        threshold: usize,
+        /// The object database to peel annotated tags with.
+        objects: Box<dyn gix_object::Find + 'a>,
+    },
+}
+
+/// The state of a transaction as passed to its [observer](Transaction::with_observer()), which corresponds to the argument
+/// passed to the `reference-transaction` hook.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum State {
+    /// All references are locked and the transaction is ready to be committed.
+    ///
+    /// Observers may reject the transaction at this stage by returning an error, which aborts it.
+    Prepared,
+    /// All changes were written.
+    Committed,
+    /// The prepared transaction was rolled back, or rejected by an observer.
+    Aborted,
+}
+
+impl State {
+    /// Return the name of the state as passed to the `reference-transaction` hook.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            State::Prepared => "prepared",
+            State::Committed => "committed",
+            State::Aborted => "aborted",
+        }
+    }
 }
 
+/// A function to be informed about the [`State`] of a transaction along with the edits it affects, which can reject the transaction
+/// by returning an error while it's [prepared](State::Prepared).
+///
+/// The edits passed to it are those which change references, with their `expected` value set to the previous value of the reference
+/// if it existed, while edits that only affect reflogs are omitted.
+pub type Observer<'a> =
+    Box<dyn FnMut(State, &[RefEdit]) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> + 'a>;
+
 #[derive(Debug)]
 pub(in crate::store_impl::file) struct Edit {
     update: RefEdit,
@@ -73,6 +117,7 @@ impl file::Store {
             packed_transaction: None,
             updates: None,
             packed_refs: PackedRefs::default(),
+            observer: None,
         }
     }
 }
@@ -83,6 +128,32 @@ impl<'s, 'p> Transaction<'s, 'p> {
         self.packed_refs = packed_refs;
         self
     }
+
+    /// Call `observer` once the transaction was prepared, committed or aborted, similar to the `reference-transaction` hook.
+    pub fn with_observer(mut self, observer: Observer<'p>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+}
+
+/// Inform `observer`, if present, that the transaction with `updates` reached `state`, and return its result.
+fn notify_observer(
+    observer: Option<&mut Observer<'_>>,
+    state: State,
+    updates: &[Edit],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let Some(observer) = observer else {
+        return Ok(());
+    };
+    let edits: Vec<_> = updates
+        .iter()
+        .filter(|edit| match &edit.update.change {
+            Change::Update { log, .. } => log.mode == RefLog::AndReference,
+            Change::Delete { log, .. } => *log == RefLog::AndReference,
+        })
+        .map(|edit| edit.update.clone())
+        .collect();
+    observer(state, &edits)
 }
 
 impl std::fmt::Debug for Transaction<'_, '_> {
@@ -5,7 +5,7 @@ use crate::{
         file,
         file::{
             loose,
-            transaction::{Edit, PackedRefs},
+            transaction::{notify_observer, Edit, PackedRefs, State},
             Transaction,
         },
     },
@@ -185,6 +185,9 @@ impl<'s, 'p> Transaction<'s, 'p> {
                         Target::Peeled(oid) => write!(file, "{oid}"),
                         Target::Symbolic(name) => writeln!(file, "ref: {}", name.0),
                     })?;
+                    if let Some(method) = store.fsync {
+                        lock.with_mut(|file| gix_fs::fsync::file(file, method))?;
+                    }
                     Some(lock.close()?)
                 } else {
                     None
@@ -249,10 +252,40 @@ impl<'s, 'p> Transaction<'s, 'p> {
             )
             .map_err(Error::PreprocessingFailed)?;
 
+        if let PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast { threshold, .. } =
+            self.packed_refs
+        {
+            let num_non_symbolic_updates = updates
+                .iter()
+                .filter(|edit| {
+                    matches!(
+                        edit.update.change,
+                        Change::Update {
+                            log: LogChange {
+                                mode: RefLog::AndReference,
+                                ..
+                            },
+                            new: Target::Peeled(_),
+                            ..
+                        }
+                    )
+                })
+                .count();
+            self.packed_refs = match std::mem::take(&mut self.packed_refs) {
+                PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast { objects, .. }
+                    if num_non_symbolic_updates >= threshold =>
+                {
+                    PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(objects)
+                }
+                _ => PackedRefs::DeletionsOnly,
+            };
+        }
+
         let mut maybe_updates_for_packed_refs = match self.packed_refs {
             PackedRefs::DeletionsAndNonSymbolicUpdates(_)
             | PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(_) => Some(0_usize),
-            PackedRefs::DeletionsOnly => None,
+            PackedRefs::DeletionsOnly
+            | PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast { .. } => None,
         };
         if maybe_updates_for_packed_refs.is_some()
             || self.store.packed_refs_path().is_file()
@@ -332,6 +365,7 @@ impl<'s, 'p> Transaction<'s, 'p> {
                                     packed_refs_lock_fail_mode,
                                     self.store.precompose_unicode,
                                     self.store.namespace.clone(),
+                                    self.store.fsync,
                                 )
                                 .map_err(Error::PackedTransactionAcquire)
                             })
@@ -343,8 +377,11 @@ impl<'s, 'p> Transaction<'s, 'p> {
                         | PackedRefs::DeletionsAndNonSymbolicUpdates(f) => {
                             transaction.prepare(&mut edits_for_packed_transaction.into_iter(), &**f)?
                         }
-                        PackedRefs::DeletionsOnly => transaction
-                            .prepare(&mut edits_for_packed_transaction.into_iter(), &gix_object::find::Never)?,
+                        PackedRefs::DeletionsOnly
+                        | PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast { .. } => {
+                            transaction
+                                .prepare(&mut edits_for_packed_transaction.into_iter(), &gix_object::find::Never)?
+                        }
                     });
                 }
             }
@@ -401,6 +438,10 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 }
             }
         }
+        if let Err(err) = notify_observer(self.observer.as_mut(), State::Prepared, &updates) {
+            notify_observer(self.observer.as_mut(), State::Aborted, &updates).ok();
+            return Err(Error::Rejected(err));
+        }
         self.updates = Some(updates);
         Ok(self)
     }
@@ -413,10 +454,12 @@ impl<'s, 'p> Transaction<'s, 'p> {
     /// # Note
     ///
     /// A rollback happens automatically as this instance is dropped as well.
-    pub fn rollback(self) -> Vec<RefEdit> {
-        self.updates
-            .map(|updates| updates.into_iter().map(|u| u.update).collect())
-            .unwrap_or_default()
+    pub fn rollback(mut self) -> Vec<RefEdit> {
+        let Some(updates) = self.updates.take() else {
+            return Vec::new();
+        };
+        notify_observer(self.observer.as_mut(), State::Aborted, &updates).ok();
+        updates.into_iter().map(|u| u.update).collect()
     }
 }
 
@@ -486,6 +529,8 @@ mod error {
         },
         #[error("Could not read reference")]
         ReferenceDecode(#[from] file::loose::reference::decode::Error),
+        #[error("The transaction was rejected by its observer")]
+        Rejected(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 
@@ -41,6 +41,8 @@ pub(crate) struct Transaction {
     precompose_unicode: bool,
     /// The namespace to use when preparing or writing refs
     namespace: Option<Namespace>,
+    /// How to flush the `packed-refs` file to permanent storage before moving it into place, if at all.
+    fsync: Option<gix_fs::fsync::Method>,
 }
 
 /// A reference as parsed from the `packed-refs` file
@@ -17,6 +17,7 @@ impl packed::Transaction {
         lock: gix_lock::File,
         precompose_unicode: bool,
         namespace: Option<Namespace>,
+        fsync: Option<gix_fs::fsync::Method>,
     ) -> Self {
         packed::Transaction {
             buffer,
@@ -25,6 +26,7 @@ impl packed::Transaction {
             closed_lock: None,
             precompose_unicode,
             namespace,
+            fsync,
         }
     }
 }
@@ -214,6 +216,9 @@ impl packed::Transaction {
         if num_written_lines == 0 {
             std::fs::remove_file(file.resource_path())?;
         } else {
+            if let Some(method) = self.fsync {
+                file.with_mut(|f| gix_fs::fsync::file(f, method))?;
+            }
             file.commit()?;
         }
         drop(refs_sorted);
@@ -260,6 +265,7 @@ pub(crate) fn buffer_into_transaction(
     lock_mode: gix_lock::acquire::Fail,
     precompose_unicode: bool,
     namespace: Option<Namespace>,
+    fsync: Option<gix_fs::fsync::Method>,
 ) -> Result<packed::Transaction, gix_lock::acquire::Error> {
     let lock = gix_lock::File::acquire_to_update_resource(&buffer.path, lock_mode, None)?;
     Ok(packed::Transaction {
@@ -269,6 +275,7 @@ pub(crate) fn buffer_into_transaction(
         edits: None,
         precompose_unicode,
         namespace,
+        fsync,
     })
 }
 
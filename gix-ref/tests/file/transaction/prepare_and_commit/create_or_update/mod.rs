@@ -813,6 +813,67 @@ fn packed_refs_creation_with_packed_refs_mode_prune_removes_original_loose_refs(
     Ok(())
 }
 
+#[test]
+fn packed_refs_creation_with_threshold_only_packs_if_there_are_enough_updates() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    let edits = || -> crate::Result<Vec<RefEdit>> {
+        Ok(store
+            .loose_iter()?
+            .filter_map(|r| r.ok().filter(|r| r.kind() == gix_ref::Kind::Peeled))
+            .map(|r| RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustExistAndMatch(r.target.clone()),
+                    new: r.target,
+                },
+                name: r.name,
+                deref: false,
+            })
+            .collect())
+    };
+
+    let edits_below_threshold = store
+        .transaction()
+        .packed_refs(
+            PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast {
+                threshold: 9,
+                objects: Box::new(odb.clone()),
+            },
+        )
+        .prepare(edits()?, Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits_below_threshold.len(), 8);
+    assert!(
+        store.open_packed_buffer()?.is_none(),
+        "below the threshold, updates go into loose refs as usual"
+    );
+
+    store
+        .transaction()
+        .packed_refs(
+            PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast {
+                threshold: 8,
+                objects: Box::new(odb),
+            },
+        )
+        .prepare(edits()?, Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        store.open_packed_buffer()?.expect("created").iter()?.count(),
+        8,
+        "at the threshold, all updates are written into packed-refs"
+    );
+    assert!(
+        store
+            .loose_iter()?
+            .filter_map(Result::ok)
+            .all(|r| r.kind() == gix_ref::Kind::Symbolic),
+        "and the loose refs are removed"
+    );
+    Ok(())
+}
+
 #[test]
 fn packed_refs_creation_with_packed_refs_mode_leave_keeps_original_loose_refs() -> crate::Result {
     let (_keep, store) = store_writable("make_packed_ref_repository_for_overlay.sh")?;
@@ -907,3 +968,40 @@ fn packed_refs_deletion_in_deletions_and_updates_mode() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn loose_and_packed_refs_can_be_flushed_to_storage() -> crate::Result {
+    let (_keep, mut store) = empty_store()?;
+    store.fsync = Some(gix_fs::fsync::Method::Fsync);
+    let id = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    let update = |name: &str| RefEdit {
+        change: Change::Update {
+            log: LogChange::default(),
+            expected: PreviousValue::MustNotExist,
+            new: Target::Peeled(id),
+        },
+        name: name.try_into().expect("valid"),
+        deref: false,
+    };
+
+    store
+        .transaction()
+        .prepare(Some(update("refs/heads/loose")), Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert_eq!(store.find_loose("refs/heads/loose")?.target.into_id(), id);
+
+    store
+        .transaction()
+        .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdates(Box::new(EmptyCommit)))
+        .prepare(Some(update("refs/heads/packed")), Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        store
+            .open_packed_buffer()?
+            .expect("written")
+            .find("refs/heads/packed")?
+            .target(),
+        id
+    );
+    Ok(())
+}
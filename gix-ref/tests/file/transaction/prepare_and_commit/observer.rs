@@ -0,0 +1,158 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gix_lock::acquire::Fail;
+use gix_ref::{
+    file::transaction::{prepare, State},
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    file::transaction::prepare_and_commit::{committer, create_at, delete_at, empty_store},
+    hex_to_id,
+};
+
+type Calls = Rc<RefCell<Vec<(State, Vec<(String, PreviousValue, Option<Target>)>)>>>;
+
+fn observer(calls: &Calls, reject: bool) -> gix_ref::file::transaction::Observer<'static> {
+    let calls = calls.clone();
+    Box::new(move |state, edits| {
+        calls.borrow_mut().push((
+            state,
+            edits
+                .iter()
+                .map(|edit| match &edit.change {
+                    Change::Update { expected, new, .. } => {
+                        (edit.name.to_string(), expected.clone(), Some(new.clone()))
+                    }
+                    Change::Delete { expected, .. } => (edit.name.to_string(), expected.clone(), None),
+                })
+                .collect(),
+        ));
+        if reject && state == State::Prepared {
+            return Err("rejected".into());
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn prepared_and_committed_transactions_are_observed_without_log_only_edits() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    let calls = Calls::default();
+    let id = hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+    store
+        .transaction()
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustNotExist,
+                    new: Target::Symbolic("refs/heads/main".try_into()?),
+                },
+                name: "HEAD".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let new_id = hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbee4904");
+    store
+        .transaction()
+        .with_observer(observer(&calls, false))
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "update through HEAD".into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(new_id),
+                },
+                name: "HEAD".try_into()?,
+                deref: true,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    let expected_edits = vec![(
+        "refs/heads/main".to_string(),
+        PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+        Some(Target::Peeled(new_id)),
+    )];
+    assert_eq!(
+        *calls.borrow(),
+        [
+            (State::Prepared, expected_edits.clone()),
+            (State::Committed, expected_edits)
+        ],
+        "only the referent is changed, while HEAD only receives a reflog entry"
+    );
+    Ok(())
+}
+
+#[test]
+fn rejecting_prepared_transactions_aborts_them() -> crate::Result {
+    let (dir, store) = empty_store()?;
+    let calls = Calls::default();
+    let err = store
+        .transaction()
+        .with_observer(observer(&calls, true))
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)
+        .unwrap_err();
+    assert!(matches!(err, prepare::Error::Rejected(_)));
+    assert_eq!(
+        calls.borrow().iter().map(|(state, _)| *state).collect::<Vec<_>>(),
+        [State::Prepared, State::Aborted]
+    );
+    assert!(
+        !dir.path().join("refs").exists(),
+        "the transaction was rolled back, leaving no lock files"
+    );
+    Ok(())
+}
+
+#[test]
+fn rollbacks_are_observed() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+    store
+        .transaction()
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+
+    let calls = Calls::default();
+    store
+        .transaction()
+        .with_observer(observer(&calls, false))
+        .prepare([delete_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .rollback();
+    let calls = calls.borrow();
+    assert_eq!(
+        calls.iter().map(|(state, _)| *state).collect::<Vec<_>>(),
+        [State::Prepared, State::Aborted]
+    );
+    assert_eq!(
+        calls[1].1,
+        [(
+            "refs/heads/main".to_string(),
+            PreviousValue::MustExistAndMatch(Target::Peeled(hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"))),
+            None
+        )],
+        "deletions have no new value"
+    );
+    assert!(
+        store.try_find_loose("refs/heads/main")?.is_some(),
+        "nothing was deleted"
+    );
+    Ok(())
+}
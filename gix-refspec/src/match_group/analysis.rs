@@ -0,0 +1,226 @@
+use std::borrow::Cow;
+
+use bstr::{BStr, BString, ByteVec};
+
+use crate::{
+    match_group::{
+        util::{Matcher, Needle},
+        Item, Mapping, Outcome, SourceRef,
+    },
+    types::Mode,
+    MatchGroup,
+};
+
+/// Two specs of a [`MatchGroup`] that can write the same destination, as returned by [`MatchGroup::overlaps()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    /// The indices of the two specs in the match group, with the first one being the smaller one.
+    pub spec_indices: (usize, usize),
+    /// A destination that both specs write to, which is a glob pattern if it represents many destinations.
+    pub destination: BString,
+    /// The sources that the first and the second spec map to `destination`, which is a glob pattern if `destination` is one.
+    pub sources: (BString, BString),
+}
+
+impl Overlap {
+    /// Return `true` if the specs map different sources to the same destination, which is a conflict that will fail the
+    /// [validation](Outcome::validated()) of mappings as soon as both sources exist.
+    ///
+    /// If `false`, one spec is merely redundant for the destination.
+    pub fn is_conflict(&self) -> bool {
+        self.sources.0 != self.sources.1
+    }
+}
+
+/// Information about which items and specs participated in the mappings of an [`Outcome`], as returned by [`Outcome::coverage()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// The indices of all items that aren't part of any mapping, in ascending order.
+    pub unmatched_items: Vec<usize>,
+    /// The indices of all non-negative specs that didn't produce any mapping, in ascending order.
+    pub unused_specs: Vec<usize>,
+}
+
+/// Analysis
+impl<'a> MatchGroup<'a> {
+    /// Find all pairs of specs that can write the same destination, which is possible without knowing the actual references.
+    ///
+    /// Only non-negative specs that have a source and a destination are considered, and the returned overlaps are ordered
+    /// by the indices of their specs.
+    pub fn overlaps(&self) -> Vec<Overlap> {
+        let null = gix_hash::Kind::Sha1.null();
+        let invert = |matcher: &Matcher<'_>, destination: &BStr| -> Option<BString> {
+            matcher
+                .matches_rhs(Item {
+                    full_ref_name: destination,
+                    target: &null,
+                    object: None,
+                })
+                .1
+                .map(Cow::into_owned)
+        };
+        let candidates: Vec<_> = self
+            .specs
+            .iter()
+            .enumerate()
+            .filter(|(_, spec)| spec.mode != Mode::Negative)
+            .map(|(idx, spec)| (idx, Matcher::from(*spec)))
+            .filter(|(_, matcher)| {
+                matches!(
+                    (matcher.lhs, matcher.rhs),
+                    (Some(lhs), Some(rhs)) if !matches!(lhs, Needle::Object(_)) && !matches!(rhs, Needle::Object(_))
+                )
+            })
+            .collect();
+
+        let mut out = Vec::new();
+        for (pos, (a_idx, a)) in candidates.iter().enumerate() {
+            for (b_idx, b) in &candidates[pos + 1..] {
+                let Some(destination) = intersect(a.rhs.expect("filtered"), b.rhs.expect("filtered")) else {
+                    continue;
+                };
+                if let (Some(a_source), Some(b_source)) =
+                    (invert(a, destination.as_ref()), invert(b, destination.as_ref()))
+                {
+                    out.push(Overlap {
+                        spec_indices: (*a_idx, *b_idx),
+                        destination,
+                        sources: (a_source, b_source),
+                    });
+                }
+            }
+        }
+        out
+    }
+
+    /// Match all `items` against the *destinations* of the specs in this group to learn which sources they are written from,
+    /// which inverts [`match_remotes()`](Self::match_remotes()).
+    ///
+    /// In the returned mappings, `lhs` is the name of the matched item, and `rhs` is the source that is mapped to it.
+    /// For fetch specs, this maps local references to the remote references they track, and for push specs it maps
+    /// remote references to the local references that are pushed to them.
+    ///
+    /// Specs with object ids as source can't be inverted and are ignored, while negative specs remove mappings
+    /// with matching sources.
+    pub fn match_rhs<'item>(self, items: impl Iterator<Item = Item<'item>> + Clone) -> Outcome<'a, 'item> {
+        let null = gix_hash::Kind::Sha1.null();
+        let mut out: Vec<Mapping<'item, 'a>> = Vec::new();
+        let matchers: Vec<_> = self.specs.iter().map(|spec| Matcher::from(*spec)).collect();
+        for (spec_index, (spec, matcher)) in self.specs.iter().zip(matchers.iter()).enumerate() {
+            if spec.mode == Mode::Negative || matches!(matcher.lhs, Some(Needle::Object(_))) {
+                continue;
+            }
+            for (item_index, item) in items.clone().enumerate() {
+                let (matched, source) = matcher.matches_rhs(item);
+                if !matched {
+                    continue;
+                }
+                let lhs = SourceRef::FullName(item.full_ref_name);
+                if !out.iter().any(|m| m.lhs == lhs && m.rhs == source) {
+                    out.push(Mapping {
+                        item_index: Some(item_index),
+                        lhs,
+                        rhs: source,
+                        spec_index,
+                    });
+                }
+            }
+        }
+
+        for (matcher, _) in matchers
+            .iter()
+            .zip(self.specs.iter())
+            .filter(|(_, spec)| spec.mode == Mode::Negative)
+        {
+            out.retain(|m| match m.rhs.as_ref() {
+                Some(source) => {
+                    !matcher
+                        .matches_lhs(Item {
+                            full_ref_name: source.as_ref(),
+                            target: &null,
+                            object: None,
+                        })
+                        .0
+                }
+                None => true,
+            });
+        }
+        Outcome {
+            group: self,
+            mappings: out,
+        }
+    }
+}
+
+/// Analysis
+impl Outcome<'_, '_> {
+    /// Determine which of the `num_items` items that were matched didn't end up in any mapping, and which specs didn't
+    /// produce any mapping, for instance to find references that a set of specs doesn't cover, or specs that are useless.
+    pub fn coverage(&self, num_items: usize) -> Coverage {
+        let mut item_matched = vec![false; num_items];
+        let mut spec_used = vec![false; self.group.specs.len()];
+        for mapping in &self.mappings {
+            if let Some(item_index) = mapping.item_index {
+                item_matched[item_index] = true;
+            }
+            spec_used[mapping.spec_index] = true;
+        }
+        Coverage {
+            unmatched_items: item_matched
+                .into_iter()
+                .enumerate()
+                .filter_map(|(idx, matched)| (!matched).then_some(idx))
+                .collect(),
+            unused_specs: spec_used
+                .into_iter()
+                .zip(self.group.specs.iter())
+                .enumerate()
+                .filter_map(|(idx, (used, spec))| (!used && spec.mode != Mode::Negative).then_some(idx))
+                .collect(),
+        }
+    }
+}
+
+/// Return a name or glob pattern that both `a` and `b` match, or `None` if there is none.
+fn intersect(a: Needle<'_>, b: Needle<'_>) -> Option<BString> {
+    match (a, b) {
+        (
+            Needle::Glob {
+                name: a,
+                asterisk_pos: a_pos,
+            },
+            Needle::Glob {
+                name: b,
+                asterisk_pos: b_pos,
+            },
+        ) => {
+            let (a_prefix, a_suffix) = (&a[..a_pos], &a[a_pos + 1..]);
+            let (b_prefix, b_suffix) = (&b[..b_pos], &b[b_pos + 1..]);
+            let prefix = longer_if_compatible(a_prefix, b_prefix, <[u8]>::starts_with)?;
+            let suffix = longer_if_compatible(a_suffix, b_suffix, <[u8]>::ends_with)?;
+            let mut out = BString::from(prefix);
+            out.push_byte(b'*');
+            out.push_str(suffix);
+            Some(out)
+        }
+        (glob @ Needle::Glob { .. }, other) | (other, glob @ Needle::Glob { .. }) => {
+            let name = other.to_bstr();
+            glob.matches(Item {
+                full_ref_name: name.as_ref(),
+                target: &gix_hash::Kind::Sha1.null(),
+                object: None,
+            })
+            .is_match()
+            .then(|| name.into_owned())
+        }
+        (a, b) => {
+            let (a, b) = (a.to_bstr(), b.to_bstr());
+            (a == b).then(|| a.into_owned())
+        }
+    }
+}
+
+fn longer_if_compatible<'a>(a: &'a [u8], b: &'a [u8], contains: impl Fn(&[u8], &[u8]) -> bool) -> Option<&'a [u8]> {
+    let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    contains(longer, shorter).then_some(longer)
+}
@@ -9,6 +9,10 @@ pub use types::{Item, Mapping, Outcome, Source, SourceRef};
 #[allow(clippy::empty_docs)]
 pub mod validate;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod analysis;
+
 /// Initialization
 impl<'a> MatchGroup<'a> {
     /// Take all the fetch ref specs from `specs` get a match group ready.
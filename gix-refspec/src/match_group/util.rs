@@ -23,6 +23,15 @@ impl<'a> Matcher<'a> {
             (None, _) => (false, None),
         }
     }
+
+    /// Like [`matches_lhs()`](Self::matches_lhs()), but match `item` against the destination of the spec to obtain the
+    /// source that would be written to it, or return `(false, None)` if there is no source or destination.
+    pub fn matches_rhs(&self, item: Item<'_>) -> (bool, Option<Cow<'a, BStr>>) {
+        match (self.lhs, self.rhs) {
+            (Some(lhs), Some(rhs)) => rhs.matches(item).into_match_outcome(lhs, item),
+            _ => (false, None),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -33,7 +42,7 @@ pub(crate) enum Needle<'a> {
     Object(ObjectId),
 }
 
-enum Match {
+pub(crate) enum Match {
     /// There was no match.
     None,
     /// No additional data is provided as part of the match.
@@ -43,7 +52,7 @@ enum Match {
 }
 
 impl Match {
-    fn is_match(&self) -> bool {
+    pub(crate) fn is_match(&self) -> bool {
         !matches!(self, Match::None)
     }
     fn into_match_outcome<'a>(self, destination: Needle<'a>, item: Item<'_>) -> (bool, Option<Cow<'a, BStr>>) {
@@ -58,7 +67,7 @@ impl Match {
 
 impl<'a> Needle<'a> {
     #[inline]
-    fn matches(&self, item: Item<'_>) -> Match {
+    pub(crate) fn matches(&self, item: Item<'_>) -> Match {
         match self {
             Needle::FullName(name) => {
                 if *name == item.full_ref_name {
@@ -159,7 +159,9 @@ impl<'a> RefSpecRef<'a> {
                 };
                 if let Some(rest) = source.strip_prefix(b"refs/") {
                     if !rest.contains(&b'/') {
-                        out.push(source.into());
+                        // `refs/*` can only be represented by everything that is a ref.
+                        let literal_len = source.find_byte(b'*').unwrap_or(source.len());
+                        out.push(source[..literal_len].into());
                     }
                     return;
                 } else if gix_hash::ObjectId::from_hex(source).is_ok() {
@@ -184,3 +184,145 @@ mod multiple {
         )
     }
 }
+
+mod analysis {
+    use gix_refspec::{
+        match_group::{analysis::Overlap, Item, SourceRef},
+        parse::Operation,
+        MatchGroup, RefSpec,
+    };
+
+    fn specs(specs: &[&str]) -> Vec<RefSpec> {
+        specs
+            .iter()
+            .map(|spec| gix_refspec::parse((*spec).into(), Operation::Fetch).unwrap().to_owned())
+            .collect()
+    }
+
+    fn items<'a>(names: &'a [&'a str], id: &'a gix_hash::oid) -> impl Iterator<Item = Item<'a>> + Clone + 'a {
+        names.iter().map(move |name| Item {
+            full_ref_name: (*name).into(),
+            target: id,
+            object: None,
+        })
+    }
+
+    #[test]
+    fn overlaps_of_globs_and_names() {
+        let specs = specs(&[
+            "+refs/heads/*:refs/remotes/origin/*",
+            "refs/heads/main:refs/remotes/origin/main",
+            "refs/tags/*:refs/remotes/origin/tags/*",
+            "refs/pull/*/head:refs/remotes/origin/*/head",
+            "^refs/heads/main",
+            "refs/heads/other:refs/heads/other",
+        ]);
+        let group = MatchGroup::from_fetch_specs(specs.iter().map(RefSpec::to_ref));
+        let overlaps = group.overlaps();
+        assert_eq!(
+            overlaps,
+            [
+                Overlap {
+                    spec_indices: (0, 1),
+                    destination: "refs/remotes/origin/main".into(),
+                    sources: ("refs/heads/main".into(), "refs/heads/main".into()),
+                },
+                Overlap {
+                    spec_indices: (0, 2),
+                    destination: "refs/remotes/origin/tags/*".into(),
+                    sources: ("refs/heads/tags/*".into(), "refs/tags/*".into()),
+                },
+                Overlap {
+                    spec_indices: (0, 3),
+                    destination: "refs/remotes/origin/*/head".into(),
+                    sources: ("refs/heads/*/head".into(), "refs/pull/*/head".into()),
+                },
+                Overlap {
+                    spec_indices: (2, 3),
+                    destination: "refs/remotes/origin/tags/*/head".into(),
+                    sources: ("refs/tags/*/head".into(), "refs/pull/tags/*/head".into()),
+                },
+            ]
+        );
+        assert_eq!(
+            overlaps.iter().map(Overlap::is_conflict).collect::<Vec<_>>(),
+            [false, true, true, true],
+            "the same source written by multiple specs is merely redundant"
+        );
+    }
+
+    #[test]
+    fn no_overlaps_with_disjoint_destinations() {
+        let specs = specs(&[
+            "refs/heads/*:refs/remotes/origin/*",
+            "refs/heads/*:refs/remotes/upstream/*",
+            "refs/tags/v*:refs/tags/v*",
+            "refs/tags/x*:refs/tags/x*",
+            "main",
+        ]);
+        let group = MatchGroup::from_fetch_specs(specs.iter().map(RefSpec::to_ref));
+        assert_eq!(group.overlaps(), []);
+    }
+
+    #[test]
+    fn match_rhs_inverts_mappings() {
+        let specs = specs(&[
+            "+refs/heads/*:refs/remotes/origin/*",
+            "refs/tags/v1:refs/tags/v1",
+            "^refs/heads/private",
+            "78b1c1be9421b33a49a7a8176d93eeeafa112da1:refs/heads/special",
+        ]);
+        let id = gix_hash::Kind::Sha1.null();
+        let names = [
+            "refs/remotes/origin/main",
+            "refs/remotes/origin/private",
+            "refs/tags/v1",
+            "refs/heads/main",
+            "refs/heads/special",
+        ];
+        let group = MatchGroup::from_fetch_specs(specs.iter().map(RefSpec::to_ref));
+        let outcome = group.match_rhs(items(&names, &id));
+        assert_eq!(
+            outcome
+                .mappings
+                .iter()
+                .map(|m| (
+                    m.lhs,
+                    m.rhs.as_ref().expect("always set").to_string(),
+                    m.item_index,
+                    m.spec_index
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    SourceRef::FullName("refs/remotes/origin/main".into()),
+                    "refs/heads/main".into(),
+                    Some(0),
+                    0
+                ),
+                (
+                    SourceRef::FullName("refs/tags/v1".into()),
+                    "refs/tags/v1".into(),
+                    Some(2),
+                    1
+                ),
+            ],
+            "negative specs exclude sources, and object ids can't be inverted"
+        );
+
+        let coverage = outcome.coverage(names.len());
+        assert_eq!(coverage.unmatched_items, [1, 3, 4]);
+        assert_eq!(coverage.unused_specs, [3], "negative specs are never considered unused");
+    }
+
+    #[test]
+    fn coverage_of_fetch_mappings() {
+        let specs = specs(&["refs/heads/*:refs/remotes/origin/*", "refs/heads/gone:refs/heads/gone"]);
+        let id = gix_hash::Kind::Sha1.null();
+        let names = ["refs/heads/main", "refs/tags/v1", "HEAD"];
+        let outcome = MatchGroup::from_fetch_specs(specs.iter().map(RefSpec::to_ref)).match_remotes(items(&names, &id));
+        let coverage = outcome.coverage(names.len());
+        assert_eq!(coverage.unmatched_items, [1, 2]);
+        assert_eq!(coverage.unused_specs, [1]);
+    }
+}
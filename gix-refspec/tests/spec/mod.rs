@@ -100,6 +100,12 @@ mod expand_prefixes {
         assert_eq!(parse("refs/heads/*:refs/remotes/origin/*"), ["refs/heads/"]);
     }
 
+    #[test]
+    fn globs_of_all_refs_expand_to_the_refs_prefix() {
+        assert_eq!(parse("+refs/*:refs/*"), ["refs/"]);
+        assert_eq!(parse("refs/h*:refs/remotes/origin/h*"), ["refs/h"]);
+    }
+
     #[test]
     fn push_specs_use_the_destination() {
         let mut out = Vec::new();
@@ -15,6 +15,20 @@ pub mod describe;
 #[cfg(feature = "describe")]
 pub use describe::function::describe;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "merge_base")]
+pub mod merge_base;
+#[cfg(feature = "merge_base")]
+pub use merge_base::function::merge_base;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "name_rev")]
+pub mod name_rev;
+#[cfg(feature = "name_rev")]
+pub use name_rev::function::name_rev;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod spec;
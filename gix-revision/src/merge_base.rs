@@ -0,0 +1,267 @@
+bitflags::bitflags! {
+    /// The flags used in the graph for finding [merge bases](crate::merge_base()).
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    pub struct Flags: u8 {
+        /// The commit belongs to the ancestry of the first commit.
+        const COMMIT1 = 1 << 0;
+        /// The commit belongs to the ancestry of one of the other commits.
+        const COMMIT2 = 1 << 1;
+        /// The commit is reachable from a merge base, and thus can't be a merge base itself.
+        const STALE = 1 << 2;
+        /// The commit was added to the result.
+        const RESULT = 1 << 3;
+    }
+}
+
+/// The error returned by the [`merge_base()`][function::merge_base()] function.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] crate::graph::try_lookup_or_insert_default::Error),
+    #[error("The commit {id} to find merge bases for could not be found")]
+    NotFound { id: gix_hash::ObjectId },
+}
+
+pub use function::{independent, is_ancestor, octopus};
+
+pub(crate) mod function {
+    use gix_hash::ObjectId;
+
+    use super::{Error, Flags};
+    use crate::{graph, Graph, PriorityQueue};
+
+    /// Given a commit at `first` id, traverse the commit `graph` and return all possible merge-base between it and `others`,
+    /// sorted from best to worst. Returns `None` if there is no merge-base as `first` and `others` don't share history.
+    /// If `others` is empty, `Some(first)` is returned.
+    ///
+    /// Note that this function doesn't do any work if `first` is contained in `others`, which is when `first` will be returned
+    /// as only merge-base right away. This is even the case if some commits of `others` are disjoint.
+    ///
+    /// Like `git merge-base --all`, all merge bases are returned, of which there is usually only one. If there are more,
+    /// none of them is an ancestor of any of the others, which happens with criss-cross merges.
+    pub fn merge_base(
+        first: ObjectId,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Option<Vec<ObjectId>>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base()", ?first, ?others);
+        if others.is_empty() || others.contains(&first) {
+            return Ok(Some(vec![first]));
+        }
+
+        graph.clear();
+        let bases = paint_down_to_common(first, others, graph)?;
+        graph.clear();
+        let bases = remove_redundant(&bases, graph)?;
+        Ok((!bases.is_empty()).then_some(bases))
+    }
+
+    /// Return all merge-bases of all `commits`, i.e. the best common ancestors of all of them, sorted from best to worst,
+    /// similar to `git merge-base --octopus --all`. Returns `None` if they don't share any history, or if `commits` is empty.
+    ///
+    /// Unlike with [`merge_base()`], the result may contain merge-bases which are ancestors of other merge-bases,
+    /// just like in `git`.
+    pub fn octopus(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Option<Vec<ObjectId>>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::octopus()", ?commits);
+        let Some((first, rest)) = commits.split_first() else {
+            return Ok(None);
+        };
+        let mut bases = vec![*first];
+        for commit in rest {
+            let mut next = Vec::new();
+            for base in &bases {
+                for id in merge_base(*base, &[*commit], graph)?.unwrap_or_default() {
+                    if !next.contains(&id) {
+                        next.push(id);
+                    }
+                }
+            }
+            bases = next;
+            if bases.is_empty() {
+                return Ok(None);
+            }
+        }
+        let mut bases = bases
+            .into_iter()
+            .map(|id| Ok((id, commit_time(id, graph)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        bases.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(Some(bases.into_iter().map(|(id, _)| id).collect()))
+    }
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, or if both are the same, similar to
+    /// `git merge-base --is-ancestor`.
+    ///
+    /// If generation numbers are available in the `graph`, they are used to avoid walking commits that are older than `ancestor`.
+    pub fn is_ancestor(
+        ancestor: ObjectId,
+        descendant: ObjectId,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<bool, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::is_ancestor()", ?ancestor, ?descendant);
+        if ancestor == descendant {
+            return Ok(true);
+        }
+        graph.clear();
+        let ancestor_generation = graph
+            .try_lookup_or_insert_commit(ancestor, |_| {})?
+            .ok_or(Error::NotFound { id: ancestor })?
+            .generation;
+        let mut queue = vec![descendant];
+        graph
+            .try_lookup_or_insert_commit(descendant, |flags| *flags |= Flags::COMMIT1)?
+            .ok_or(Error::NotFound { id: descendant })?;
+        while let Some(id) = queue.pop() {
+            let commit = graph.get(&id).expect("everything queued is in the graph");
+            if let (Some(generation), Some(ancestor_generation)) = (commit.generation, ancestor_generation) {
+                // All ancestors of commits with a lower generation have an even lower generation.
+                if generation <= ancestor_generation {
+                    continue;
+                }
+            }
+            for parent_id in commit.parents.clone() {
+                if parent_id == ancestor {
+                    return Ok(true);
+                }
+                let mut is_new = false;
+                // Missing parents are skipped as they are typical for shallow repositories.
+                graph.try_lookup_or_insert_commit(parent_id, |flags| {
+                    if !flags.contains(Flags::COMMIT1) {
+                        *flags |= Flags::COMMIT1;
+                        is_new = true;
+                    }
+                })?;
+                if is_new {
+                    queue.push(parent_id);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Return the commits of `commits` which are not reachable by any of the other commits, sorted by commit time
+    /// with the newest commits first, similar to `git merge-base --independent`.
+    pub fn independent(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::independent()", ?commits);
+        let mut unique = Vec::with_capacity(commits.len());
+        for id in commits {
+            if !unique.iter().any(|(existing, _)| existing == id) {
+                unique.push((*id, commit_time(*id, graph)?));
+            }
+        }
+        graph.clear();
+        remove_redundant(&unique, graph)
+    }
+
+    fn commit_time(
+        id: ObjectId,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<gix_date::SecondsSinceUnixEpoch, Error> {
+        Ok(graph
+            .try_lookup_or_insert_commit(id, |_| {})?
+            .ok_or(Error::NotFound { id })?
+            .commit_time)
+    }
+
+    /// Return the commits of `commits` which are not reachable by any of the other commits of `commits`,
+    /// sorted by commit time with the newest commits first.
+    fn remove_redundant(
+        commits: &[(ObjectId, gix_date::SecondsSinceUnixEpoch)],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        if commits.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut redundant = vec![false; commits.len()];
+        for (idx, (commit, _)) in commits.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            let others: Vec<_> = commits
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx && !redundant[*other_idx])
+                .map(|(_, (id, _))| *id)
+                .collect();
+            if others.is_empty() {
+                continue;
+            }
+            graph.clear();
+            paint_down_to_common(*commit, &others, graph)?;
+            if graph.get(commit).map_or(false, |c| c.data.contains(Flags::COMMIT2)) {
+                redundant[idx] = true;
+            }
+            for (other_idx, (other, _)) in commits.iter().enumerate() {
+                if other_idx != idx && graph.get(other).map_or(false, |c| c.data.contains(Flags::COMMIT1)) {
+                    redundant[other_idx] = true;
+                }
+            }
+        }
+        let mut out: Vec<_> = commits
+            .iter()
+            .zip(redundant)
+            .filter_map(|(commit, is_redundant)| (!is_redundant).then_some(*commit))
+            .collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(out.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Walk the ancestry of `first` and `others` by commit time, and return all commits that are reachable by both,
+    /// but which are not reachable by a common commit that was found earlier, along with their commit time.
+    fn paint_down_to_common(
+        first: ObjectId,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<(ObjectId, gix_date::SecondsSinceUnixEpoch)>, Error> {
+        let mut queue = PriorityQueue::<gix_date::SecondsSinceUnixEpoch, ObjectId>::new();
+        for (id, flags) in std::iter::once((first, Flags::COMMIT1)).chain(others.iter().map(|id| (*id, Flags::COMMIT2)))
+        {
+            let commit = graph
+                .try_lookup_or_insert_commit(id, |existing| *existing |= flags)?
+                .ok_or(Error::NotFound { id })?;
+            queue.insert(commit.commit_time, id);
+        }
+
+        let mut out = Vec::new();
+        while queue
+            .iter_unordered()
+            .any(|id| graph.get(id).map_or(false, |c| !c.data.contains(Flags::STALE)))
+        {
+            let (commit_time, id) = queue.pop().expect("we have non-stale commits left");
+            let commit = graph.get_mut(&id).expect("everything queued is in the graph");
+            let mut flags = commit.data & (Flags::COMMIT1 | Flags::COMMIT2 | Flags::STALE);
+            if flags == (Flags::COMMIT1 | Flags::COMMIT2) {
+                if !commit.data.contains(Flags::RESULT) {
+                    commit.data |= Flags::RESULT;
+                    out.push((id, commit_time));
+                }
+                flags |= Flags::STALE;
+            }
+            for parent_id in commit.parents.clone() {
+                // Missing parents are skipped as they are typical for shallow repositories.
+                let mut was_updated = false;
+                if let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |existing| {
+                    if !existing.contains(flags) {
+                        *existing |= flags;
+                        was_updated = true;
+                    }
+                })? {
+                    if was_updated {
+                        queue.insert(parent.commit_time, parent_id);
+                    }
+                }
+            }
+        }
+        Ok(out
+            .into_iter()
+            .filter(|(id, _)| graph.get(id).map_or(false, |c| !c.data.contains(Flags::STALE)))
+            .collect())
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+};
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_date::SecondsSinceUnixEpoch;
+use gix_hash::ObjectId;
+
+/// A reference pointing to a commit, whose name is used to name the commit and its ancestors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tip<'name> {
+    /// The name to use for the commit at `id`, typically a shortened reference name like `main` or `tags/v1.0`.
+    pub name: Cow<'name, BStr>,
+    /// The commit the reference points to, after peeling tags.
+    pub id: ObjectId,
+    /// If true, the reference is a tag, and names derived from it are preferred over names derived from other references.
+    pub from_tag: bool,
+    /// The time of the annotated tag the reference points to, or `None` to use the time of the commit at `id`.
+    ///
+    /// Names derived from older tips are preferred.
+    pub tagger_date: Option<SecondsSinceUnixEpoch>,
+    /// If true, the reference points to an annotated tag which was peeled to obtain `id`, which is indicated
+    /// by naming the commit at `id` itself `<name>^0`.
+    pub peeled: bool,
+}
+
+/// The name of a commit relative to a [`Tip`], as produced by [`name_rev()`](function::name_rev()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Name {
+    /// The name of the tip along with the path taken through merge commits to get to the first-parent chain
+    /// which contains the named commit, like `main` or `main~2^2`.
+    pub tip_name: BString,
+    /// The amount of first-parent hops from `tip_name` to the named commit.
+    pub generation: u32,
+    /// The amount of hops from the tip to the named commit, where each hop to a parent that isn't the first parent
+    /// is weighted so that names following first parents are preferred.
+    pub distance: u32,
+    /// The time of the tag or the tip commit the name is derived from.
+    pub tagger_date: SecondsSinceUnixEpoch,
+    /// If true, the name is derived from a tag.
+    pub from_tag: bool,
+}
+
+impl Name {
+    /// Return `true` if a name with the given properties should replace this one, following the rules of `git name-rev`.
+    fn is_worse_than(&self, tagger_date: SecondsSinceUnixEpoch, distance: u32, from_tag: bool) -> bool {
+        if self.from_tag && from_tag {
+            // Prefer names based on the older tag, even if it is farther away.
+            return self.tagger_date > tagger_date || (self.tagger_date == tagger_date && self.distance > distance);
+        }
+        if self.from_tag != from_tag {
+            return from_tag;
+        }
+        if self.distance != distance {
+            return self.distance > distance;
+        }
+        self.tagger_date > tagger_date
+    }
+
+    /// Return the name of the tip without the `^0` suffix used for peeled tags.
+    fn tip_name_without_peel_suffix(&self) -> &BStr {
+        self.tip_name
+            .strip_suffix(b"^0")
+            .unwrap_or(self.tip_name.as_slice())
+            .as_bstr()
+    }
+
+    /// The tip name to use for the `parent_number`th parent of the commit with this name.
+    fn parent_tip_name(&self, parent_number: usize) -> BString {
+        let tip_name = self.tip_name_without_peel_suffix();
+        let mut name = BString::from(tip_name);
+        if self.generation > 0 {
+            name.extend_from_slice(format!("~{}", self.generation).as_bytes());
+        }
+        name.extend_from_slice(format!("^{parent_number}").as_bytes());
+        name
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.generation == 0 {
+            self.tip_name.fmt(f)
+        } else {
+            write!(f, "{}~{}", self.tip_name_without_peel_suffix(), self.generation)
+        }
+    }
+}
+
+/// The names of commits as produced by [`name_rev()`](function::name_rev()).
+pub type Names = gix_hashtable::HashMap<ObjectId, Name>;
+
+/// The error returned by the [`name_rev()`](function::name_rev()) function.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] crate::graph::try_lookup_or_insert_default::Error),
+    #[error("The commit {id} to find a name for could not be found")]
+    NotFound { id: ObjectId },
+}
+
+/// Names of commits further away from their tip than this are considered worse than names of commits
+/// reached through first parents.
+const MERGE_TRAVERSAL_WEIGHT: u32 = 65535;
+
+/// Commits older than the commits to name by this amount of seconds are still traversed, to be resilient to clock skew.
+const CUTOFF_DATE_SLOP: SecondsSinceUnixEpoch = 86400;
+
+pub(crate) mod function {
+    use gix_date::SecondsSinceUnixEpoch;
+    use gix_hash::ObjectId;
+
+    use super::{Error, Name, Names, Tip, CUTOFF_DATE_SLOP, MERGE_TRAVERSAL_WEIGHT};
+    use crate::{graph, Graph};
+
+    /// Find a name for `commits` and their ancestors that describes their relation to one of the given `tips`,
+    /// like `main~2` or `tags/v1.0~1^2`, traversing the commit `graph` similar to `git name-rev`.
+    ///
+    /// Names derived from tags are preferred, and among those the ones derived from the oldest tag. Otherwise,
+    /// names whose path to their tip is shortest are preferred, while hops through merge commits to parents
+    /// other than the first one count as much longer paths.
+    ///
+    /// Commits which are much older than the oldest of `commits` are not traversed for performance, which means that only
+    /// the names of `commits` are guaranteed to be correct in the returned names. If `commits` is empty, all commits
+    /// reachable from `tips` are named, which is useful to name arbitrary commits later.
+    ///
+    /// `tips` that don't point to commits are ignored. If tips are equally suitable, the one that comes first wins.
+    pub fn name_rev<'name>(
+        commits: &[ObjectId],
+        tips: impl IntoIterator<Item = Tip<'name>>,
+        graph: &mut Graph<'_, graph::Commit<()>>,
+    ) -> Result<Names, Error> {
+        let _span = gix_trace::coarse!("gix_revision::name_rev()", ?commits);
+        let mut cutoff = Cutoff::default();
+        for id in commits {
+            let commit = graph
+                .try_lookup_or_insert_commit(*id, |_| {})?
+                .ok_or(Error::NotFound { id: *id })?;
+            cutoff.include(commit);
+        }
+        cutoff.time = cutoff.time.map(|time| time.saturating_sub(CUTOFF_DATE_SLOP));
+
+        let mut tips: Vec<_> = {
+            let mut out = Vec::new();
+            for tip in tips {
+                let Some(commit) = graph.try_lookup_or_insert_commit(tip.id, |_| {})? else {
+                    continue;
+                };
+                let tagger_date = tip.tagger_date.unwrap_or(commit.commit_time);
+                out.push((tip, tagger_date));
+            }
+            out
+        };
+        // Tags come first, then older tips.
+        tips.sort_by(|(a, a_date), (b, b_date)| b.from_tag.cmp(&a.from_tag).then(a_date.cmp(b_date)));
+
+        let mut names = Names::default();
+        let mut stack = Vec::new();
+        let mut parents_to_push = Vec::new();
+        for (tip, tagger_date) in tips {
+            let commit = graph.try_lookup_or_insert_commit(tip.id, |_| {})?.expect("present");
+            if cutoff.excludes(commit) {
+                continue;
+            }
+            let mut tip_name = tip.name.into_owned();
+            if tip.peeled {
+                tip_name.extend_from_slice(b"^0");
+            }
+            if !update_name(&mut names, tip.id, tagger_date, 0, 0, tip.from_tag, || tip_name) {
+                continue;
+            }
+
+            stack.push(tip.id);
+            while let Some(id) = stack.pop() {
+                let name = names[&id].clone();
+                let parents = graph
+                    .try_lookup_or_insert_commit(id, |_| {})?
+                    .expect("present")
+                    .parents
+                    .clone();
+                for (parent_number, parent_id) in parents.into_iter().enumerate().map(|(idx, id)| (idx + 1, id)) {
+                    // Parents may be missing in shallow repositories.
+                    let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |_| {})? else {
+                        continue;
+                    };
+                    if cutoff.excludes(parent) {
+                        continue;
+                    }
+                    let (generation, distance) = if parent_number > 1 {
+                        (0, name.distance.saturating_add(MERGE_TRAVERSAL_WEIGHT))
+                    } else {
+                        (name.generation + 1, name.distance.saturating_add(1))
+                    };
+                    if update_name(
+                        &mut names,
+                        parent_id,
+                        name.tagger_date,
+                        generation,
+                        distance,
+                        name.from_tag,
+                        || {
+                            if parent_number > 1 {
+                                name.parent_tip_name(parent_number)
+                            } else {
+                                name.tip_name.clone()
+                            }
+                        },
+                    ) {
+                        parents_to_push.push(parent_id);
+                    }
+                }
+                // The first parent must be processed first.
+                stack.extend(parents_to_push.drain(..).rev());
+            }
+        }
+        Ok(names)
+    }
+
+    /// Set the name of the commit `id` if it has none yet or if the given name is better than the existing one,
+    /// and return `true` in that case.
+    fn update_name(
+        names: &mut Names,
+        id: ObjectId,
+        tagger_date: SecondsSinceUnixEpoch,
+        generation: u32,
+        distance: u32,
+        from_tag: bool,
+        tip_name: impl FnOnce() -> bstr::BString,
+    ) -> bool {
+        if let Some(existing) = names.get(&id) {
+            if !existing.is_worse_than(tagger_date, distance, from_tag) {
+                return false;
+            }
+        }
+        names.insert(
+            id,
+            Name {
+                tip_name: tip_name(),
+                generation,
+                distance,
+                tagger_date,
+                from_tag,
+            },
+        );
+        true
+    }
+
+    /// Commits older than those to name can't be their ancestors, so there is no need to traverse them.
+    struct Cutoff {
+        time: Option<SecondsSinceUnixEpoch>,
+        generation: Option<u32>,
+        /// If false, one of the commits to name has no generation number, so only `time` can be used.
+        use_generation: bool,
+    }
+
+    impl Default for Cutoff {
+        fn default() -> Self {
+            Cutoff {
+                time: None,
+                generation: None,
+                use_generation: true,
+            }
+        }
+    }
+
+    impl Cutoff {
+        fn include(&mut self, commit: &graph::Commit<()>) {
+            self.time = Some(
+                self.time
+                    .map_or(commit.commit_time, |time| time.min(commit.commit_time)),
+            );
+            match commit.generation {
+                Some(generation) if self.use_generation => {
+                    self.generation = Some(self.generation.map_or(generation, |g| g.min(generation)));
+                }
+                _ => {
+                    self.generation = None;
+                    self.use_generation = false;
+                }
+            }
+        }
+
+        fn excludes(&self, commit: &graph::Commit<()>) -> bool {
+            match (self.generation, commit.generation) {
+                (Some(cutoff), Some(generation)) => generation < cutoff,
+                _ => matches!(self.time, Some(cutoff) if commit.commit_time < cutoff),
+            }
+        }
+    }
+}
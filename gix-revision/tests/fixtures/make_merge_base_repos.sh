@@ -0,0 +1,75 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit() {
+  git commit -q --allow-empty -m "$1"
+  git tag "$1"
+}
+
+function baseline() {
+  echo "$(git rev-parse "$@" | tr '\n' ' ')-> $( (git merge-base --all "$@" || true) | tr '\n' ' ')" >> baseline.git
+}
+
+git init -q
+git checkout -q -b main
+commit c1
+commit c2
+commit c3
+
+git checkout -q -b branch c2
+commit b1
+commit b2
+
+git checkout -q -b criss c3
+commit x1
+git checkout -q -b cross c3
+commit y1
+git merge -q --no-ff -m x-into-y x1 && git tag y2
+git checkout -q criss
+git merge -q --no-ff -m y-into-x y1 && git tag x2
+
+git checkout -q --orphan unrelated
+commit u1
+
+baseline c3 b2
+baseline b2 c3
+baseline c1 c3
+baseline c3 c1
+baseline c3 c3
+baseline x2 y2
+baseline x2 b2 u1
+baseline c3 u1
+baseline b2 x2 y2
+
+function baseline_octopus() {
+  echo "$(git rev-parse "$@" | tr '\n' ' ')-> $( (git merge-base --octopus --all "$@" || true) | tr '\n' ' ')" >> octopus.git
+}
+
+function baseline_independent() {
+  echo "$(git rev-parse "$@" | tr '\n' ' ')-> $(git merge-base --independent "$@" | tr '\n' ' ')" >> independent.git
+}
+
+function baseline_is_ancestor() {
+  echo "$(git rev-parse "$@" | tr '\n' ' ')-> $(git merge-base --is-ancestor "$@" && echo true || echo false)" >> is-ancestor.git
+}
+
+baseline_octopus c3 b2
+baseline_octopus x2 y2 b2
+baseline_octopus x2 y2 c3
+baseline_octopus x2 b2 u1
+
+baseline_independent c1 c2 c3
+baseline_independent b2 c3 c1
+baseline_independent x2 y2 x1 y1
+baseline_independent c3 u1 c3
+
+baseline_is_ancestor c1 c3
+baseline_is_ancestor c3 c1
+baseline_is_ancestor c3 c3
+baseline_is_ancestor c2 b2
+baseline_is_ancestor c3 b2
+baseline_is_ancestor x1 y2
+baseline_is_ancestor y1 x2
+baseline_is_ancestor u1 x2
+
+git commit-graph write --no-progress --reachable
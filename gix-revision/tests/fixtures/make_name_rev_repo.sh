@@ -0,0 +1,62 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  tick
+  git commit -q --allow-empty -m "$1"
+}
+
+function merge() {
+  tick
+  git merge -q --no-ff -m "$1" "$2"
+}
+
+git init -q
+git checkout -q -b main
+commit c1
+tick
+git tag -a -m "first release" v1
+commit c2
+commit c3
+
+git checkout -q -b feature main~1
+commit f1
+git checkout -q -b nested
+commit n1
+commit n2
+git checkout -q feature
+commit f2
+merge "merge nested" nested
+commit f3
+
+git checkout -q main
+commit c4
+merge "merge feature" feature
+tick
+git tag -a -m "second release" v2
+commit c5
+git tag lightweight
+commit c6
+
+git checkout -q --orphan unrelated
+commit u1
+git checkout -q main
+
+git for-each-ref --format='%(objectname) %(objecttype) %(*objectname) %(creatordate:unix) %(refname)' > tips
+git rev-list --all -- > commits
+git name-rev --annotate-stdin --name-only < commits > baseline.all
+git name-rev --no-undefined $(git rev-parse nested~1 feature~2 main~3) > baseline.some
+
+git commit-graph write --no-progress --reachable
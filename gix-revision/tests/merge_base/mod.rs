@@ -0,0 +1,97 @@
+use gix_revision::merge_base;
+
+use crate::hex_to_id;
+
+#[test]
+fn validate() -> crate::Result {
+    let root = gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh").unwrap();
+    let store = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&store, None);
+    let baseline = std::fs::read_to_string(root.join("baseline.git"))?;
+    let mut num_cases = 0;
+    for case in baseline.lines() {
+        let (commits, bases) = case.split_once("->").expect("commits and merge-bases are separated");
+        let mut commits = commits.split_whitespace().map(hex_to_id);
+        let first = commits.next().expect("at least one commit");
+        let others: Vec<_> = commits.collect();
+        let mut expected: Vec<_> = bases.split_whitespace().map(hex_to_id).collect();
+
+        let mut actual = merge_base(first, &others, &mut graph)?.unwrap_or_default();
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected, "{first} with {others:?}");
+        num_cases += 1;
+    }
+    assert_eq!(num_cases, 9, "all cases were checked");
+    Ok(())
+}
+
+/// Call `f` with the commits and expected outcome of each case in the baseline file `name`, once without and once with commit-graph.
+fn for_each_case(
+    name: &str,
+    mut f: impl FnMut(
+        &mut gix_revision::Graph<'_, gix_revision::graph::Commit<merge_base::Flags>>,
+        Vec<gix_hash::ObjectId>,
+        &str,
+    ) -> crate::Result,
+) -> crate::Result<usize> {
+    let root = gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh").unwrap();
+    let store = gix_odb::at(root.join(".git/objects"))?;
+    let baseline = std::fs::read_to_string(root.join(name))?;
+    let mut num_cases = 0;
+    for use_commit_graph in [false, true] {
+        let cache = use_commit_graph
+            .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+            .transpose()?;
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for case in baseline.lines() {
+            let (commits, expected) = case.split_once("->").expect("commits and outcome are separated");
+            f(
+                &mut graph,
+                commits.split_whitespace().map(hex_to_id).collect(),
+                expected.trim(),
+            )?;
+            num_cases += 1;
+        }
+    }
+    Ok(num_cases)
+}
+
+#[test]
+fn octopus() -> crate::Result {
+    let num_cases = for_each_case("octopus.git", |graph, commits, expected| {
+        let mut expected: Vec<_> = expected.split_whitespace().map(hex_to_id).collect();
+        let mut actual = merge_base::octopus(&commits, graph)?.unwrap_or_default();
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected, "{commits:?}");
+        Ok(())
+    })?;
+    assert_eq!(num_cases, 4 * 2, "all cases were checked");
+    Ok(())
+}
+
+#[test]
+fn independent() -> crate::Result {
+    let num_cases = for_each_case("independent.git", |graph, commits, expected| {
+        let mut expected: Vec<_> = expected.split_whitespace().map(hex_to_id).collect();
+        let mut actual = merge_base::independent(&commits, graph)?;
+        actual.sort();
+        expected.sort();
+        assert_eq!(actual, expected, "{commits:?}");
+        Ok(())
+    })?;
+    assert_eq!(num_cases, 4 * 2, "all cases were checked");
+    Ok(())
+}
+
+#[test]
+fn is_ancestor() -> crate::Result {
+    let num_cases = for_each_case("is-ancestor.git", |graph, commits, expected| {
+        let actual = merge_base::is_ancestor(commits[0], commits[1], graph)?;
+        assert_eq!(actual.to_string(), expected, "{commits:?}");
+        Ok(())
+    })?;
+    assert_eq!(num_cases, 8 * 2, "all cases were checked");
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::{borrow::Cow, path::PathBuf};
+
+use gix_revision::name_rev::{Names, Tip};
+
+use crate::hex_to_id;
+
+fn fixture() -> PathBuf {
+    gix_testtools::scripted_fixture_read_only("make_name_rev_repo.sh").unwrap()
+}
+
+/// Parse the tips as written by `git for-each-ref` and name them like `git name-rev` does.
+fn tips(root: &std::path::Path) -> crate::Result<Vec<Tip<'static>>> {
+    Ok(std::fs::read_to_string(root.join("tips"))?
+        .lines()
+        .map(|line| {
+            let mut tokens = line.split(' ');
+            let mut id = hex_to_id(tokens.next().expect("id"));
+            let peeled = tokens.next() == Some("tag");
+            if let Some(peeled_id) = tokens.next().filter(|id| !id.is_empty()) {
+                id = hex_to_id(peeled_id);
+            }
+            let date: i64 = tokens.next().expect("date").parse().expect("valid");
+            let ref_name = tokens.next().expect("ref name");
+            let name = ref_name
+                .strip_prefix("refs/heads/")
+                .or_else(|| ref_name.strip_prefix("refs/"))
+                .expect("known prefix");
+            Tip {
+                name: Cow::Owned(name.into()),
+                id,
+                from_tag: ref_name.starts_with("refs/tags/"),
+                tagger_date: peeled.then_some(date),
+                peeled,
+            }
+        })
+        .collect())
+}
+
+/// Name `commits` once without and once with commit-graph, and call `f` with the outcome.
+fn name_rev(commits: &[gix_hash::ObjectId], mut f: impl FnMut(Names)) -> crate::Result {
+    let root = fixture();
+    let store = gix_odb::at(root.join(".git/objects"))?;
+    for use_commit_graph in [false, true] {
+        let cache = use_commit_graph
+            .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+            .transpose()?;
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        f(gix_revision::name_rev(commits, tips(&root)?, &mut graph)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn all_commits_without_cutoff() -> crate::Result {
+    let root = fixture();
+    let commits: Vec<_> = std::fs::read_to_string(root.join("commits"))?
+        .lines()
+        .map(hex_to_id)
+        .collect();
+    let expected = std::fs::read_to_string(root.join("baseline.all"))?;
+    let mut num_cases = 0;
+    name_rev(&[], |names| {
+        for (id, expected) in commits.iter().zip(expected.lines()) {
+            assert_eq!(names[id].to_string(), expected, "{id}");
+            num_cases += 1;
+        }
+        assert_eq!(names.len(), commits.len(), "every commit is reachable from a tip");
+    })?;
+    assert_eq!(num_cases, 2 * 14, "all commits were checked");
+    Ok(())
+}
+
+#[test]
+fn some_commits_with_cutoff() -> crate::Result {
+    let root = fixture();
+    let baseline = std::fs::read_to_string(root.join("baseline.some"))?;
+    let expected: Vec<_> = baseline
+        .lines()
+        .map(|line| {
+            let (id, name) = line.split_once(' ').expect("id and name");
+            (hex_to_id(id), name)
+        })
+        .collect();
+    let commits: Vec<_> = expected.iter().map(|(id, _)| *id).collect();
+    name_rev(&commits, |names| {
+        for (id, expected) in &expected {
+            assert_eq!(names[id].to_string(), *expected, "{id}");
+        }
+    })
+}
+
+#[test]
+fn missing_commits_are_an_error() -> crate::Result {
+    let root = fixture();
+    let store = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&store, None);
+    let missing = hex_to_id("0000000000000000000000000000000000000001");
+    assert!(matches!(
+        gix_revision::name_rev(&[missing], tips(&root)?, &mut graph),
+        Err(gix_revision::name_rev::Error::NotFound { id }) if id == missing
+    ));
+    Ok(())
+}
@@ -72,6 +72,7 @@ where
     E: std::error::Error + Send + Sync + 'static,
     Find: gix_object::Find + Send + Clone,
 {
+    let _span = gix_features::trace::coarse!("gix_status::index_as_worktree()");
     // the order is absolutely critical here we use the old timestamp to detect racy index entries
     // (modified at or after the last index update) during the index update we then set those
     // entries size to 0 (see below) to ensure they keep showing up as racy and reset the timestamp.
@@ -527,11 +528,20 @@ where
         // normal files with their content equal to the linked path (so can be read normally)
         //
         let is_symlink = self.entry.mode == gix_index::entry::Mode::SYMLINK;
+        // Even without symlink support, there may be actual symlinks on disk, for instance if the capability
+        // was configured after checkout, and like Git, we read their target in that case.
+        let symlink_path = if is_symlink && self.options.fs.symlink {
+            Some(std::fs::read_link(self.path)?)
+        } else if is_symlink {
+            std::fs::read_link(self.path).ok()
+        } else {
+            None
+        };
         // TODO: what to do about precompose unicode and ignore_case for symlinks
-        let out = if is_symlink && self.options.fs.symlink {
+        let out = if let Some(symlink_path) = symlink_path {
             // conversion to bstr can never fail because symlinks are only used
             // on unix (by git) so no reason to use the try version here
-            let symlink_path = gix_path::into_bstr(std::fs::read_link(self.path)?);
+            let symlink_path = gix_path::into_bstr(symlink_path);
             self.buf.extend_from_slice(&symlink_path);
             self.worktree_bytes.fetch_add(self.buf.len() as u64, Ordering::Relaxed);
             Stream {
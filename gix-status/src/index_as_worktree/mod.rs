@@ -7,6 +7,9 @@ pub use types::{Change, Conflict, Context, EntryStatus, Error, Options, Outcome,
 mod recorder;
 pub use recorder::{Record, Recorder};
 
+mod refresh;
+pub use refresh::{StatUpdate, StatUpdates};
+
 pub(super) mod function;
 ///
 #[allow(clippy::empty_docs)]
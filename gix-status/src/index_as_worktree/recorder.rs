@@ -1,7 +1,7 @@
 use bstr::BStr;
 use gix_index as index;
 
-use crate::index_as_worktree::{EntryStatus, VisitEntry};
+use crate::index_as_worktree::{EntryStatus, StatUpdates, VisitEntry};
 
 /// A record of a change.
 ///
@@ -25,6 +25,17 @@ pub struct Recorder<'index, T = (), U = ()> {
     pub records: Vec<Record<'index, T, U>>,
 }
 
+impl<'index, T, U> Recorder<'index, T, U> {
+    /// Return the stat updates of all recorded entries, to be able to write them back to the index.
+    pub fn stat_updates(&self) -> StatUpdates {
+        let mut updates = StatUpdates::default();
+        for record in &self.records {
+            updates.record(record.entry_index, &record.status);
+        }
+        updates
+    }
+}
+
 impl<'index, T: Send, U: Send> VisitEntry<'index> for Recorder<'index, T, U> {
     type ContentChange = T;
     type SubmoduleStatus = U;
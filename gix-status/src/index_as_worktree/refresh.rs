@@ -0,0 +1,89 @@
+use crate::index_as_worktree::{Change, EntryStatus};
+
+/// A change to the stat information of an index entry which allows the next status computation to avoid work,
+/// similar to what `git status` writes back to the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatUpdate {
+    /// The entry was racily clean, but its content changed, so its size is set to `0` to have it detected as modified
+    /// without reading it again.
+    SetSizeToZero,
+    /// The entry is unchanged, and its stat information is replaced with the one of the file in the worktree
+    /// so it won't have to be read and hashed again.
+    NewStat(gix_index::entry::Stat),
+}
+
+impl StatUpdate {
+    /// Return the update to apply to the entry whose `status` was computed, or `None` if it doesn't need an update.
+    pub fn from_status<T, U>(status: &EntryStatus<T, U>) -> Option<Self> {
+        match status {
+            EntryStatus::NeedsUpdate(stat) => Some(StatUpdate::NewStat(*stat)),
+            EntryStatus::Change(Change::Modification {
+                set_entry_stat_size_zero: true,
+                ..
+            }) => Some(StatUpdate::SetSizeToZero),
+            _ => None,
+        }
+    }
+
+    /// Apply this update to `entry`.
+    pub fn apply_to(&self, entry: &mut gix_index::Entry) {
+        match self {
+            StatUpdate::SetSizeToZero => entry.stat.size = 0,
+            StatUpdate::NewStat(stat) => entry.stat = *stat,
+        }
+    }
+}
+
+/// A list of [stat updates](StatUpdate) to write back to the index the status was computed for, to make subsequent
+/// status computations faster.
+///
+/// Note that racily clean entries that turned out to be unchanged are updated as well, which makes them non-racy once
+/// the index is written as the index will be newer than the file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatUpdates {
+    /// The index of the entry in the index along with the update to apply to it.
+    pub updates: Vec<(usize, StatUpdate)>,
+}
+
+impl StatUpdates {
+    /// Record the update for the entry at `entry_index` with `status`, if it needs one, and return `true` in that case.
+    pub fn record<T, U>(&mut self, entry_index: usize, status: &EntryStatus<T, U>) -> bool {
+        match StatUpdate::from_status(status) {
+            Some(update) => {
+                self.updates.push((entry_index, update));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return `true` if there is no update.
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Apply all updates to the entries of `index`, which must be the index the status was computed for.
+    pub fn apply_to(&self, index: &mut gix_index::State) {
+        let entries = index.entries_mut();
+        for (entry_index, update) in &self.updates {
+            update.apply_to(&mut entries[*entry_index]);
+        }
+    }
+
+    /// [Apply](Self::apply_to()) all updates to `index` and write it back to disk with `options`, or do nothing if
+    /// there is no update. Return `true` if the index was written.
+    ///
+    /// Note that `index` must be the index the status was computed for, and that the write fails if the index is locked.
+    pub fn write_back(
+        &self,
+        index: &mut gix_index::File,
+        options: gix_index::write::Options,
+    ) -> Result<bool, gix_index::file::write::Error> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        self.apply_to(index);
+        index.write(options)?;
+        Ok(true)
+    }
+}
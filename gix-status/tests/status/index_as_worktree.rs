@@ -11,7 +11,7 @@ use gix_status::index_as_worktree::Context;
 use gix_status::{
     index_as_worktree,
     index_as_worktree::{
-        traits::{CompareBlobs, FastEq, ReadData, SubmoduleStatus},
+        traits::{CompareBlobs, FastEq, HashEq, ReadData, SubmoduleStatus},
         Change as WorktreeChange, Conflict, EntryStatus as WorktreeEntryStatus, Options, Outcome, Record, Recorder,
     },
 };
@@ -411,6 +411,57 @@ fn unchanged() {
     fixture("status_unchanged", &[]);
 }
 
+#[test]
+fn unchanged_symlinks_without_symlink_support() {
+    let worktree = fixture_path("status_unchanged");
+    let git_dir = worktree.join(".git");
+    let mut index =
+        gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default()).unwrap();
+    // Force the worktree content to be compared.
+    index.entries_mut().iter_mut().for_each(|e| e.stat = Default::default());
+    let stack = gix_worktree::Stack::from_state_and_ignore_case(
+        worktree.clone(),
+        false,
+        gix_worktree::stack::State::AttributesStack(Default::default()),
+        &index,
+        index.path_backing(),
+    );
+    let mut recorder = Recorder { records: Vec::new() };
+    index_as_worktree(
+        &index,
+        &worktree,
+        &mut recorder,
+        HashEq,
+        SubmoduleStatusMock { dirty: false },
+        gix_object::find::Never,
+        &mut gix_features::progress::Discard,
+        Context {
+            pathspec: default_pathspec(),
+            stack,
+            filter: Default::default(),
+            should_interrupt: &AtomicBool::default(),
+        },
+        Options {
+            fs: gix_fs::Capabilities {
+                symlink: false,
+                ..gix_fs::Capabilities::probe(&git_dir)
+            },
+            stat: TEST_OPTIONS,
+            ..Options::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        recorder
+            .records
+            .iter()
+            .filter(|r| !matches!(r.status, WorktreeEntryStatus::NeedsUpdate(_)))
+            .count(),
+        0,
+        "actual symlinks are compared by their target, even if symlinks aren't supported"
+    );
+}
+
 #[test]
 fn refresh() {
     let expected_outcome = Outcome {
@@ -589,23 +640,6 @@ fn racy_git() {
     let mut index =
         gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default()).unwrap();
 
-    #[derive(Clone)]
-    struct CountCalls(Arc<AtomicUsize>, FastEq);
-    impl CompareBlobs for CountCalls {
-        type Output = ();
-
-        fn compare_blobs<'a, 'b>(
-            &mut self,
-            entry: &Entry,
-            worktree_file_size: u64,
-            data: impl ReadData<'a>,
-            buf: &mut Vec<u8>,
-        ) -> Result<Option<Self::Output>, gix_status::index_as_worktree::Error> {
-            self.0.fetch_add(1, Ordering::Relaxed);
-            self.1.compare_blobs(entry, worktree_file_size, data, buf)
-        }
-    }
-
     // We artificially mess with the entry's `mtime` so that it's before the timestamp saved by git.
     // This would usually mean an invalid fs/invalid index file and as a result the racy git
     // mitigation doesn't work and the worktree shows up as unchanged even tough the file did
@@ -718,6 +752,97 @@ fn racy_git() {
     );
 }
 
+#[test]
+fn stat_updates_are_written_back_to_avoid_reading_files_again() {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("racy_git.sh").expect("script works");
+    let worktree = dir.path();
+    let git_dir = worktree.join(".git");
+    let fs = gix_fs::Capabilities::probe(&git_dir);
+    let index_path = git_dir.join("index");
+
+    // Restore the original content, but with a different mtime than recorded in the index, so it has to be read.
+    std::fs::write(worktree.join("content"), b"foo").unwrap();
+    set_file_mtime(worktree.join("content"), FileTime::from_unix_time(940040400, 0)).expect("changing filetime works");
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let status = |index: &gix_index::File| {
+        let mut recorder = Recorder::default();
+        let stack = gix_worktree::Stack::from_state_and_ignore_case(
+            worktree,
+            false,
+            gix_worktree::stack::State::AttributesStack(Default::default()),
+            index,
+            index.path_backing(),
+        );
+        index_as_worktree(
+            index,
+            worktree,
+            &mut recorder,
+            CountCalls(count.clone(), FastEq),
+            SubmoduleStatusMock { dirty: false },
+            gix_object::find::Never,
+            &mut gix_features::progress::Discard,
+            Context {
+                pathspec: default_pathspec(),
+                stack,
+                filter: Default::default(),
+                should_interrupt: &AtomicBool::default(),
+            },
+            Options {
+                fs,
+                stat: TEST_OPTIONS,
+                ..Options::default()
+            },
+        )
+        .unwrap();
+        recorder.stat_updates()
+    };
+
+    let mut index = gix_index::File::at(&index_path, gix_hash::Kind::Sha1, false, Default::default()).unwrap();
+    let updates = status(&index);
+    assert_eq!(count.load(Ordering::Relaxed), 1, "the file had to be read once");
+    assert_eq!(
+        updates.updates.len(),
+        1,
+        "the entry is unchanged, but needs a stat update"
+    );
+    assert!(matches!(
+        updates.updates[0],
+        (0, index_as_worktree::StatUpdate::NewStat(_))
+    ));
+    assert!(updates.write_back(&mut index, Default::default()).unwrap());
+
+    let mut index = gix_index::File::at(&index_path, gix_hash::Kind::Sha1, false, Default::default()).unwrap();
+    let updates = status(&index);
+    assert_eq!(
+        count.load(Ordering::Relaxed),
+        1,
+        "with the refreshed stat information the file doesn't have to be read again"
+    );
+    assert!(updates.is_empty());
+    assert!(
+        !updates.write_back(&mut index, Default::default()).unwrap(),
+        "without updates, the index isn't written"
+    );
+}
+
+#[derive(Clone)]
+struct CountCalls(Arc<AtomicUsize>, FastEq);
+impl CompareBlobs for CountCalls {
+    type Output = ();
+
+    fn compare_blobs<'a, 'b>(
+        &mut self,
+        entry: &Entry,
+        worktree_file_size: u64,
+        data: impl ReadData<'a>,
+        buf: &mut Vec<u8>,
+    ) -> Result<Option<Self::Output>, gix_status::index_as_worktree::Error> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        self.1.compare_blobs(entry, worktree_file_size, data, buf)
+    }
+}
+
 fn default_pathspec() -> gix_pathspec::Search {
     gix_pathspec::Search::from_specs(to_pathspecs(&[]), None, std::path::Path::new("")).expect("empty is always valid")
 }
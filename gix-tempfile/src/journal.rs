@@ -0,0 +1,322 @@
+//! An on-disk record of tempfiles and locks owned by processes, to allow reclaiming those left behind by crashed processes.
+//!
+//! The in-memory [registry](crate::registry) can only clean up after the current process, and it won't get a chance to do so
+//! if the process is killed with `SIGKILL` or crashes hard. Long-lived processes then keep running into stale `.lock` files
+//! which block all further operations until removed by hand.
+//!
+//! A [`Journal`] is a directory shared by all cooperating processes. Each process writes its own journal file into it,
+//! named after its process id and the [boot id](boot_id()) of the machine, listing all paths it currently owns.
+//! As only the owning process ever writes its journal file, no inter-process locking is needed.
+//!
+//! Other processes can then [query the holders](Journal::holders()) of paths, and opt-in to
+//! [reclaim](Journal::reclaim_stale()) all paths of holders that are known to be dead.
+//!
+//! ### Limitations
+//!
+//! * Process liveness can only be determined on unix-like systems, on all other systems holders are never considered dead
+//!   unless they were recorded during a different boot of the machine.
+//! * Process ids may be reused, in which case a dead holder is considered alive and its paths remain until the process
+//!   with the reused id exits.
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// Serializes writes to the journal file of the current process, as it's shared by all threads.
+static JOURNAL_WRITE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// A directory holding one journal file per process which owns tempfiles or locks.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Journal {
+    directory: PathBuf,
+}
+
+/// Describes whether or not a holder process is still running.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Liveness {
+    /// The process is running, or is the current process.
+    Alive,
+    /// The process is known not to be running anymore, making all of its paths stale.
+    Dead,
+    /// It's unknown if the process is running, which happens on platforms where we can't check.
+    Unknown,
+}
+
+/// A process which recorded paths it owns in a [`Journal`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Holder {
+    /// The id of the process that recorded the paths.
+    pub process_id: u32,
+    /// The boot id of the machine at the time the paths were recorded, if it could be determined.
+    pub boot_id: Option<String>,
+    /// Whether or not the process is still running.
+    pub liveness: Liveness,
+    /// The paths the process owns, in the order they were recorded.
+    pub paths: Vec<PathBuf>,
+    /// The location of the journal file of the process.
+    pub journal_path: PathBuf,
+}
+
+impl Holder {
+    /// Return `true` if this holder is known to be dead, making all of its paths safe to remove.
+    pub fn is_stale(&self) -> bool {
+        self.liveness == Liveness::Dead
+    }
+}
+
+/// Lifecycle
+impl Journal {
+    /// Use `directory` to store journal files, which doesn't have to exist yet.
+    ///
+    /// All processes that want to see each other's paths must use the same directory.
+    pub fn at(directory: impl Into<PathBuf>) -> Self {
+        Journal {
+            directory: directory.into(),
+        }
+    }
+
+    /// Return the directory containing all journal files.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+}
+
+/// Recording
+impl Journal {
+    /// Record that the current process owns `path`, which typically is a tempfile or lock that was just created.
+    ///
+    /// Note that `path` should be absolute to be meaningful to other processes.
+    pub fn record(&self, path: &Path) -> io::Result<()> {
+        let line = path_to_line(path)?;
+        let _guard = JOURNAL_WRITE_LOCK.lock();
+        let journal_path = self.current_process_journal_path();
+        let mut paths = read_lines(&journal_path)?;
+        if !paths.contains(&line) {
+            paths.push(line);
+        }
+        self.write_lines(&journal_path, &paths)
+    }
+
+    /// Record that the current process doesn't own `path` anymore, typically because it was persisted or removed.
+    ///
+    /// It's not an error if `path` wasn't recorded.
+    pub fn release(&self, path: &Path) -> io::Result<()> {
+        let line = path_to_line(path)?;
+        let _guard = JOURNAL_WRITE_LOCK.lock();
+        let journal_path = self.current_process_journal_path();
+        let mut paths = read_lines(&journal_path)?;
+        let num_paths = paths.len();
+        paths.retain(|p| *p != line);
+        if paths.len() == num_paths {
+            return Ok(());
+        }
+        if paths.is_empty() {
+            return remove_if_present(&journal_path);
+        }
+        self.write_lines(&journal_path, &paths)
+    }
+
+    fn current_process_journal_path(&self) -> PathBuf {
+        self.directory
+            .join(journal_file_name(std::process::id(), boot_id().as_deref()))
+    }
+
+    fn write_lines(&self, journal_path: &Path, lines: &[Vec<u8>]) -> io::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        let mut file = tempfile::NamedTempFile::new_in(&self.directory)?;
+        for line in lines {
+            file.write_all(line)?;
+            file.write_all(b"\n")?;
+        }
+        file.persist(journal_path).map_err(|err| err.error)?;
+        Ok(())
+    }
+}
+
+/// Querying and reclamation
+impl Journal {
+    /// Return all processes which currently have paths recorded, including the current one.
+    ///
+    /// A journal directory that doesn't exist yields no holders.
+    pub fn holders(&self) -> io::Result<Vec<Holder>> {
+        let entries = match std::fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let current_boot_id = boot_id();
+        let mut out = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let Some((process_id, recorded_boot_id)) = entry.file_name().to_str().and_then(parse_journal_file_name)
+            else {
+                continue;
+            };
+            let journal_path = entry.path();
+            let paths = read_lines(&journal_path)?
+                .into_iter()
+                .filter_map(line_to_path)
+                .collect();
+            out.push(Holder {
+                process_id,
+                liveness: liveness(process_id, recorded_boot_id.as_deref(), current_boot_id.as_deref()),
+                boot_id: recorded_boot_id,
+                paths,
+                journal_path,
+            });
+        }
+        out.sort_by(|a, b| a.journal_path.cmp(&b.journal_path));
+        Ok(out)
+    }
+
+    /// Return the holder of `path`, if any process recorded it.
+    pub fn holder_of(&self, path: &Path) -> io::Result<Option<Holder>> {
+        Ok(self.holders()?.into_iter().find(|h| h.paths.iter().any(|p| p == path)))
+    }
+
+    /// Remove all paths recorded by [dead](Liveness::Dead) holders along with their journal files, and return the paths
+    /// that were actually removed.
+    ///
+    /// Paths that don't exist anymore are skipped silently, and holders that are alive or whose liveness is unknown
+    /// are never touched.
+    pub fn reclaim_stale(&self) -> io::Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        for holder in self.holders()?.into_iter().filter(Holder::is_stale) {
+            for path in holder.paths {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => removed.push(path),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            remove_if_present(&holder.journal_path)?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Return the id of the current boot of the machine, if it can be determined on this platform.
+///
+/// It's used to detect holders which were recorded before a reboot, and which thus can't possibly be alive anymore.
+pub fn boot_id() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/sys/kernel/random/boot_id")
+            .ok()
+            .map(|id| id.trim().to_owned())
+            .filter(|id| !id.is_empty())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+fn liveness(process_id: u32, recorded_boot_id: Option<&str>, current_boot_id: Option<&str>) -> Liveness {
+    if let (Some(recorded), Some(current)) = (recorded_boot_id, current_boot_id) {
+        if recorded != current {
+            return Liveness::Dead;
+        }
+    }
+    if process_id == std::process::id() {
+        return Liveness::Alive;
+    }
+    process_liveness(process_id)
+}
+
+#[cfg(target_os = "linux")]
+fn process_liveness(process_id: u32) -> Liveness {
+    if Path::new("/proc").join(process_id.to_string()).exists() {
+        Liveness::Alive
+    } else {
+        Liveness::Dead
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_liveness(process_id: u32) -> Liveness {
+    let Ok(pid) = libc::pid_t::try_from(process_id) else {
+        return Liveness::Unknown;
+    };
+    // SAFETY: signal 0 performs error checking only and doesn't affect the target process.
+    #[allow(unsafe_code)]
+    let res = unsafe { libc::kill(pid, 0) };
+    if res == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM) {
+        Liveness::Alive
+    } else {
+        Liveness::Dead
+    }
+}
+
+#[cfg(not(unix))]
+fn process_liveness(_process_id: u32) -> Liveness {
+    Liveness::Unknown
+}
+
+fn journal_file_name(process_id: u32, boot_id: Option<&str>) -> String {
+    match boot_id {
+        Some(boot_id) => format!("{process_id}@{boot_id}"),
+        None => process_id.to_string(),
+    }
+}
+
+fn parse_journal_file_name(name: &str) -> Option<(u32, Option<String>)> {
+    match name.split_once('@') {
+        Some((pid, boot_id)) => Some((pid.parse().ok()?, Some(boot_id.to_owned()))),
+        None => Some((name.parse().ok()?, None)),
+    }
+}
+
+fn read_lines(path: &Path) -> io::Result<Vec<Vec<u8>>> {
+    match std::fs::read(path) {
+        Ok(buf) => Ok(buf
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(ToOwned::to_owned)
+            .collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn remove_if_present(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn path_to_line(path: &Path) -> io::Result<Vec<u8>> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "journal paths must be valid UTF-8"))?
+        .as_bytes();
+    if bytes.contains(&b'\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("journal paths must not contain newlines: {path:?}"),
+        ));
+    }
+    Ok(bytes.to_owned())
+}
+
+fn line_to_path(line: Vec<u8>) -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        Some(std::ffi::OsString::from_vec(line).into())
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(line).ok().map(Into::into)
+    }
+}
@@ -24,6 +24,9 @@
 //! * The application is performing a write operation on the tempfile when a signal arrives, preventing this tempfile to be removed,
 //!   but not others. Any other operation dealing with the tempfile suffers from the same issue.
 //!
+//! Use a [`journal::Journal`] to record owned paths on disk so that later invocations can reclaim them once their
+//! owning process is known to be dead.
+//!
 //! [`signal-hook`]: https://docs.rs/signal-hook
 //!
 //! ## Feature Flags
@@ -111,6 +114,8 @@ use crate::handle::{Closed, Writable};
 #[allow(clippy::empty_docs)]
 pub mod registry;
 
+pub mod journal;
+
 static NEXT_MAP_INDEX: AtomicUsize = AtomicUsize::new(0);
 static REGISTRY: Lazy<HashMap<usize, Option<ForksafeTempfile>>> = Lazy::new(|| {
     #[cfg(feature = "signals")]
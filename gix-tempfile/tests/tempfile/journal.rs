@@ -0,0 +1,60 @@
+use gix_tempfile::journal::{Journal, Liveness};
+
+#[test]
+fn record_and_release_roundtrip_for_the_current_process() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let journal = Journal::at(dir.path().join("journal"));
+    assert!(
+        journal.holders()?.is_empty(),
+        "a missing journal directory has no holders"
+    );
+
+    let lock = dir.path().join("index.lock");
+    std::fs::write(&lock, b"")?;
+    journal.record(&lock)?;
+    journal.record(&lock)?;
+
+    let holders = journal.holders()?;
+    assert_eq!(holders.len(), 1);
+    let holder = &holders[0];
+    assert_eq!(holder.process_id, std::process::id());
+    assert_eq!(holder.boot_id, gix_tempfile::journal::boot_id());
+    assert_eq!(holder.liveness, Liveness::Alive, "we are alive");
+    assert_eq!(holder.paths, vec![lock.clone()], "paths are recorded only once");
+    assert_eq!(journal.holder_of(&lock)?.as_ref(), Some(holder));
+
+    assert!(journal.reclaim_stale()?.is_empty(), "live holders are never reclaimed");
+    assert!(lock.is_file());
+
+    journal.release(&lock)?;
+    assert!(journal.holders()?.is_empty(), "empty journal files are removed");
+    journal.release(&lock)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn holders_of_a_previous_boot_are_reclaimed() -> crate::Result {
+    let dir = tempfile::tempdir()?;
+    let journal = Journal::at(dir.path());
+    let lock = dir.path().join("HEAD.lock");
+    let missing = dir.path().join("missing.lock");
+    std::fs::write(&lock, b"")?;
+    std::fs::write(
+        dir.path().join(format!("{}@previous-boot", std::process::id())),
+        format!("{}\n{}\n", lock.display(), missing.display()),
+    )?;
+
+    let holders = journal.holders()?;
+    assert_eq!(holders.len(), 1);
+    assert!(holders[0].is_stale(), "a different boot id means the process is gone");
+
+    assert_eq!(
+        journal.reclaim_stale()?,
+        vec![lock.clone()],
+        "only existing paths are reported"
+    );
+    assert!(!lock.exists());
+    assert!(journal.holders()?.is_empty(), "the journal file was removed as well");
+    Ok(())
+}
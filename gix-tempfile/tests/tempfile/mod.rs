@@ -1,4 +1,5 @@
 mod handle;
+mod journal;
 
 #[cfg(feature = "signals")]
 mod setup {
@@ -1,4 +1,4 @@
-pub use crate::client::non_io_types::connect::{Error, Options};
+pub use crate::client::non_io_types::connect::{Certificates, Error, Options};
 
 #[cfg(feature = "async-std")]
 pub(crate) mod function {
@@ -0,0 +1,387 @@
+//! An HTTP transport for the async client which leaves performing requests to an implementation of the [`Http`] trait.
+//!
+//! As requests and responses are plain values and nothing is required to be `Send`, any HTTP client can be plugged in,
+//! including the `fetch()` API of web browsers when compiling to `wasm32-unknown-unknown`.
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::RefCell,
+    future::Future,
+    io,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use base64::Engine;
+use bstr::BStr;
+use futures_io::{AsyncRead, AsyncWrite};
+use futures_lite::AsyncReadExt;
+use gix_packetline::PacketLineRef;
+
+use crate::{
+    client::{self, capabilities, Capabilities, MessageKind, RequestWriter},
+    Protocol, Service,
+};
+
+/// The error returned by implementations of the [`Http`] trait.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The HTTP request failed")]
+    Request {
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("{description}")]
+    Detail { description: String },
+}
+
+impl crate::IsSpuriousError for Error {}
+
+/// The HTTP method of a [`Request`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Method {
+    /// A `GET` request, without body.
+    Get,
+    /// A `POST` request, with body.
+    Post,
+}
+
+/// A request for the [`Http`] implementation to perform.
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// The kind of request.
+    pub method: Method,
+    /// The URL to send the request to.
+    pub url: String,
+    /// The URL of the repository, of which `url` is a sub-path, which is useful to decide which redirects to follow.
+    pub base_url: String,
+    /// The headers to send, like `Name: value`, without newlines.
+    pub headers: Vec<String>,
+    /// The complete body to send with a [`Method::Post`] request, empty otherwise.
+    pub body: Vec<u8>,
+}
+
+/// The response to a [`Request`].
+pub struct Response {
+    /// The response headers, like `Name: value`.
+    pub headers: Vec<String>,
+    /// The response body.
+    pub body: Box<dyn AsyncRead + Unpin>,
+}
+
+/// The future returned by [`Http::request()`].
+pub type ResponseFuture = Pin<Box<dyn Future<Output = Result<Response, Error>>>>;
+
+/// A trait to abstract over the HTTP client performing the requests of the [`Transport`].
+///
+/// Implementations must fail with an [`Error`] if the server responds with a status code other than `2xx`,
+/// after following redirects as they see fit.
+/// Note that the returned future must not borrow from `self`, and is not required to be `Send`.
+pub trait Http {
+    /// Perform `request` and return a future resolving to its response.
+    fn request(&self, request: Request) -> ResponseFuture;
+
+    /// Pass `config` which can deliver implementation-specific configuration at any time.
+    fn configure(&mut self, _config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
+
+impl<F> Http for F
+where
+    F: Fn(Request) -> ResponseFuture,
+{
+    fn request(&self, request: Request) -> ResponseFuture {
+        self(request)
+    }
+}
+
+/// A transport for the `http` and `https` protocols, using `H` to perform the actual requests.
+pub struct Transport<H: Http> {
+    url: String,
+    user_agent_header: &'static str,
+    desired_version: Protocol,
+    actual_version: Protocol,
+    http: Rc<H>,
+    service: Option<Service>,
+    line_provider: Option<gix_packetline::StreamingPeekableIter<Body>>,
+    identity: Option<gix_sec::identity::Account>,
+    trace: bool,
+}
+
+impl<H: Http> Transport<H> {
+    /// Create a new instance with `http` as implementation to communicate to `url` using the given `desired_version`.
+    /// Note that we will always fallback to other versions as supported by the server.
+    /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    pub fn new_http(http: H, url: gix_url::Url, desired_version: Protocol, trace: bool) -> Self {
+        let identity = url
+            .user()
+            .zip(url.password())
+            .map(|(user, pass)| gix_sec::identity::Account {
+                username: user.to_string(),
+                password: pass.to_string(),
+            });
+        Transport {
+            url: url.to_bstring().to_string(),
+            user_agent_header: concat!("User-Agent: git/oxide-", env!("CARGO_PKG_VERSION")),
+            desired_version,
+            actual_version: Default::default(),
+            http: Rc::new(http),
+            service: None,
+            line_provider: None,
+            identity,
+            trace,
+        }
+    }
+
+    /// Returns the identity that the transport uses when connecting to the remote.
+    pub fn identity(&self) -> Option<&gix_sec::identity::Account> {
+        self.identity.as_ref()
+    }
+
+    fn basic_auth_header(&self) -> Result<Option<String>, client::Error> {
+        let Some(gix_sec::identity::Account { username, password }) = &self.identity else {
+            return Ok(None);
+        };
+        #[cfg(not(debug_assertions))]
+        if self.url.starts_with("http://") {
+            return Err(client::Error::AuthenticationRefused(
+                "Will not send credentials in clear text over http",
+            ));
+        }
+        Ok(Some(format!(
+            "Authorization: Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"))
+        )))
+    }
+}
+
+fn check_content_type(service: Service, kind: &str, headers: &[String]) -> Result<(), Error> {
+    let wanted_content_type = format!("application/x-{}-{}", service.as_str(), kind);
+    if !headers.iter().any(|line| {
+        let mut tokens = line.split(':');
+        matches!(tokens.next().zip(tokens.next()), Some((name, value)) if name.eq_ignore_ascii_case("content-type") && value.trim() == wanted_content_type)
+    }) {
+        return Err(Error::Detail {
+            description: format!(
+                "Didn't find '{wanted_content_type}' header to indicate 'smart' protocol, and 'dumb' protocol is not supported."
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn append_url(base: &str, suffix: &str) -> String {
+    let mut buf = base.to_owned();
+    if base.as_bytes().last() != Some(&b'/') {
+        buf.push('/');
+    }
+    buf.push_str(suffix);
+    buf
+}
+
+impl<H: Http + 'static> client::TransportWithoutIO for Transport<H> {
+    fn set_identity(&mut self, identity: gix_sec::identity::Account) -> Result<(), client::Error> {
+        self.identity = Some(identity);
+        Ok(())
+    }
+
+    fn request(
+        &mut self,
+        write_mode: client::WriteMode,
+        on_into_read: MessageKind,
+        trace: bool,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        let service = self.service.expect("handshake() must have been called first");
+        let mut headers = vec![
+            self.user_agent_header.to_owned(),
+            format!("Content-Type: application/x-{}-request", service.as_str()),
+            format!("Accept: application/x-{}-result", service.as_str()),
+        ];
+        headers.extend(self.basic_auth_header()?);
+        if self.actual_version != Protocol::V1 {
+            headers.push(format!("Git-Protocol: version={}", self.actual_version as usize));
+        }
+
+        // The request is only sent once its body is complete, which is when the response is first read.
+        let post_body = Rc::new(RefCell::new(Vec::new()));
+        let send = {
+            let http = Rc::clone(&self.http);
+            let post_body = Rc::clone(&post_body);
+            let request = Request {
+                method: Method::Post,
+                url: append_url(&self.url, service.as_str()),
+                base_url: self.url.clone(),
+                headers,
+                body: Vec::new(),
+            };
+            move || -> ResponseFuture {
+                let response = http.request(Request {
+                    body: post_body.take(),
+                    ..request
+                });
+                Box::pin(async move {
+                    let response = response.await?;
+                    check_content_type(service, "result", &response.headers)?;
+                    Ok(response)
+                })
+            }
+        };
+        let line_provider = self
+            .line_provider
+            .as_mut()
+            .expect("handshake to have been called first");
+        line_provider.replace(Body::Unsent(Some(Box::new(send))));
+        Ok(RequestWriter::new_from_bufread(
+            PostBody(post_body),
+            Box::new(line_provider.as_read_without_sidebands()),
+            write_mode,
+            on_into_read,
+            trace,
+        ))
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        Cow::Borrowed(self.url.as_str().into())
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        false
+    }
+
+    fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        match Rc::get_mut(&mut self.http) {
+            Some(http) => http.configure(config),
+            None => Err("cannot configure the HTTP implementation while a request is in progress".into()),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<H: Http + 'static> client::Transport for Transport<H> {
+    async fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<client::SetServiceResponse<'_>, client::Error> {
+        let mut headers = vec![self.user_agent_header.to_owned()];
+        if self.desired_version != Protocol::V1 || !extra_parameters.is_empty() {
+            let mut parameters = Vec::new();
+            if self.desired_version != Protocol::V1 {
+                parameters.push(format!("version={}", self.desired_version as usize));
+            }
+            parameters.extend(extra_parameters.iter().map(|(key, value)| match value {
+                Some(value) => format!("{key}={value}"),
+                None => key.to_string(),
+            }));
+            headers.push(format!("Git-Protocol: {}", parameters.join(":")));
+        }
+        headers.extend(self.basic_auth_header()?);
+        let response = self
+            .http
+            .request(Request {
+                method: Method::Get,
+                url: append_url(&self.url, &format!("info/refs?service={}", service.as_str())),
+                base_url: self.url.clone(),
+                headers,
+                body: Vec::new(),
+            })
+            .await?;
+        check_content_type(service, "advertisement", &response.headers)?;
+
+        let line_reader = self.line_provider.insert(gix_packetline::StreamingPeekableIter::new(
+            Body::Received(response.body),
+            &[PacketLineRef::Flush],
+            self.trace,
+        ));
+
+        // the service announcement is only sent sometimes depending on the exact server/protocol version/used protocol (http?)
+        // eat the announcement when its there to avoid errors later (and check that the correct service was announced).
+        // Ignore the announcement otherwise.
+        let line_ = line_reader
+            .peek_line()
+            .await
+            .ok_or(client::Error::ExpectedLine("capabilities, version or service"))???;
+        let line = line_.as_text().ok_or(client::Error::ExpectedLine("text"))?;
+
+        if let Some(announced_service) = line.as_bstr().strip_prefix(b"# service=") {
+            if announced_service != service.as_str().as_bytes() {
+                return Err(client::Error::Http(Error::Detail {
+                    description: format!(
+                        "Expected to see service {:?}, but got {:?}",
+                        service.as_str(),
+                        announced_service
+                    ),
+                }));
+            }
+
+            line_reader.as_read().read_to_end(&mut Vec::new()).await?;
+        }
+
+        let capabilities::recv::Outcome {
+            capabilities,
+            refs,
+            protocol: actual_protocol,
+        } = Capabilities::from_lines_with_version_detection(line_reader).await?;
+        self.actual_version = actual_protocol;
+        self.service = Some(service);
+        Ok(client::SetServiceResponse {
+            actual_protocol,
+            capabilities,
+            refs,
+        })
+    }
+}
+
+/// Collects the body of a `POST` request until it is sent.
+struct PostBody(Rc<RefCell<Vec<u8>>>);
+
+impl AsyncWrite for PostBody {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The body of a response, which sends its request when it is first read.
+enum Body {
+    Unsent(Option<Box<dyn FnOnce() -> ResponseFuture>>),
+    Sent(ResponseFuture),
+    Received(Box<dyn AsyncRead + Unpin>),
+}
+
+impl AsyncRead for Body {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            match this {
+                Body::Unsent(send) => {
+                    let send = send.take().expect("only taken once");
+                    *this = Body::Sent(send());
+                }
+                Body::Sent(response) => match response.as_mut().poll(cx) {
+                    Poll::Ready(Ok(response)) => *this = Body::Received(response.body),
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, err))),
+                    Poll::Pending => return Poll::Pending,
+                },
+                Body::Received(body) => return Pin::new(body).poll_read(cx, buf),
+            }
+        }
+    }
+}
+
+/// Connect to the given `url` via HTTP/S using the `desired_version` of the `git` protocol, with `http` as implementation.
+/// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+pub fn connect_http<H: Http>(http: H, url: gix_url::Url, desired_version: Protocol, trace: bool) -> Transport<H> {
+    Transport::new_http(http, url, desired_version, trace)
+}
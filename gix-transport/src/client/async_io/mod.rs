@@ -1,6 +1,10 @@
 mod bufread_ext;
 pub use bufread_ext::{ExtendedBufRead, HandleProgress, ReadlineBufRead};
 
+///
+#[cfg(feature = "async-http-client")]
+pub mod http;
+
 mod request;
 pub use request::RequestWriter;
 
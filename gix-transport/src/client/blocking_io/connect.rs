@@ -1,4 +1,4 @@
-pub use crate::client::non_io_types::connect::{Error, Options};
+pub use crate::client::non_io_types::connect::{Certificates, Error, Options};
 
 pub(crate) mod function {
     use crate::client::{non_io_types::connect::Error, Transport};
@@ -58,9 +58,10 @@ pub(crate) mod function {
             #[cfg(not(any(feature = "http-client-curl", feature = "http-client-reqwest")))]
             gix_url::Scheme::Https | gix_url::Scheme::Http => return Err(Error::CompiledWithoutHttp(url.scheme)),
             #[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
-            gix_url::Scheme::Https | gix_url::Scheme::Http => {
-                Box::new(crate::client::http::connect(url, options.version, options.trace))
-            }
+            gix_url::Scheme::Https | gix_url::Scheme::Http => Box::new(
+                crate::client::http::connect(url, options.version, options.trace)
+                    .with_certificates(options.certificates),
+            ),
         })
     }
 }
@@ -45,6 +45,7 @@ pub struct SpawnProcessOnDemand {
     /// The environment variables to set in the invoked command.
     envs: Vec<(&'static str, String)>,
     ssh_disallow_shell: bool,
+    ssh_multiplex: Option<ssh::connect::Multiplex>,
     connection: Option<git::Connection<Box<dyn std::io::Read + Send>, process::ChildStdin>>,
     child: Option<process::Child>,
     trace: bool,
@@ -66,12 +67,17 @@ impl SpawnProcessOnDemand {
             ssh_cmd: Some((program.into(), ssh_kind)),
             envs: Default::default(),
             ssh_disallow_shell,
+            ssh_multiplex: None,
             child: None,
             connection: None,
             desired_version: version,
             trace,
         }
     }
+    pub(crate) fn with_ssh_multiplex(mut self, multiplex: Option<ssh::connect::Multiplex>) -> Self {
+        self.ssh_multiplex = multiplex;
+        self
+    }
     fn new_local(path: BString, version: Protocol, trace: bool) -> SpawnProcessOnDemand {
         SpawnProcessOnDemand {
             url: gix_url::Url::from_parts(gix_url::Scheme::File, None, None, None, None, path.clone(), true)
@@ -82,6 +88,7 @@ impl SpawnProcessOnDemand {
                 .then(|| vec![("GIT_PROTOCOL", format!("version={}", version as usize))])
                 .unwrap_or_default(),
             ssh_disallow_shell: false,
+            ssh_multiplex: None,
             child: None,
             connection: None,
             desired_version: version,
@@ -215,9 +222,15 @@ impl client::Transport for SpawnProcessOnDemand {
     ) -> Result<SetServiceResponse<'_>, client::Error> {
         let (mut cmd, ssh_kind, cmd_name) = match &self.ssh_cmd {
             Some((command, kind)) => (
-                kind.prepare_invocation(command, &self.url, self.desired_version, self.ssh_disallow_shell)
-                    .map_err(client::Error::SshInvocation)?
-                    .stderr(Stdio::piped()),
+                kind.prepare_invocation(
+                    command,
+                    &self.url,
+                    self.desired_version,
+                    self.ssh_disallow_shell,
+                    self.ssh_multiplex.as_ref(),
+                )
+                .map_err(client::Error::SshInvocation)?
+                .stderr(Stdio::piped()),
                 Some(*kind),
                 Cow::Owned(command.to_owned()),
             ),
@@ -321,6 +334,7 @@ mod tests {
                         command: Some("unrecognized".into()),
                         disallow_shell: false,
                         kind: None,
+                        multiplex: None,
                     };
                     assert!(matches!(
                         ssh::connect(url, Protocol::V1, options, false),
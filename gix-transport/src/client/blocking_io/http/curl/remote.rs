@@ -158,7 +158,11 @@ pub fn new() -> (
                     ssl_ca_info,
                     ssl_version,
                     ssl_verify,
+                    ssl_cert,
+                    ssl_key,
+                    certificates,
                     http_version,
+                    empty_auth,
                     backend,
                 },
         } in req_recv
@@ -174,9 +178,23 @@ pub fn new() -> (
             headers.append("Expect:")?;
             handle.verbose(verbose)?;
 
-            if let Some(ca_info) = ssl_ca_info {
+            if let Some(ca_certificates) = certificates.ca_certificates {
+                handle.ssl_cainfo_blob(&ca_certificates)?;
+            } else if let Some(ca_info) = ssl_ca_info {
                 handle.cainfo(ca_info)?;
             }
+            if let Some(client_certificate) = certificates.client_certificate {
+                handle.ssl_cert_blob(&client_certificate)?;
+                handle.ssl_cert_type("PEM")?;
+            } else if let Some(cert) = ssl_cert {
+                handle.ssl_cert(cert)?;
+            }
+            if let Some(client_key) = certificates.client_key {
+                handle.ssl_key_blob(&client_key)?;
+                handle.ssl_key_type("PEM")?;
+            } else if let Some(key) = ssl_key {
+                handle.ssl_key(key)?;
+            }
 
             if let Some(ref mut curl_options) = backend.as_ref().and_then(|backend| backend.lock().ok()) {
                 if let Some(opts) = curl_options.downcast_mut::<super::Options>() {
@@ -256,11 +274,26 @@ pub fn new() -> (
                         .aws_sigv4(true),
                     ProxyAuthMethod::Basic => auth.basic(true),
                     ProxyAuthMethod::Digest => auth.digest(true),
-                    ProxyAuthMethod::Negotiate => auth.digest_ie(true),
+                    ProxyAuthMethod::Negotiate => auth.gssnegotiate(true),
                     ProxyAuthMethod::Ntlm => auth.ntlm(true),
                 };
                 handle.proxy_auth(&auth)?;
             }
+            if empty_auth {
+                // Like `git`, allow curl to pick any method offered by the server, and provide empty credentials
+                // so it will try the ones which don't need any, like `Negotiate`.
+                // Credentials that are already known are sent with a header, which curl won't override.
+                handle.http_auth(
+                    Auth::new()
+                        .basic(true)
+                        .digest(true)
+                        .digest_ie(true)
+                        .gssnegotiate(true)
+                        .ntlm(true),
+                )?;
+                handle.username("")?;
+                handle.password("")?;
+            }
             handle.tcp_keepalive(true)?;
 
             if low_speed_time_seconds > 0 && low_speed_limit_bytes_per_second > 0 {
@@ -185,8 +185,26 @@ pub struct Options {
     /// various security risks. An example where this may be needed is when an internal git server uses a self-signed
     /// certificate and the user accepts the associated security risks.
     pub ssl_verify: bool,
+    /// If set, use this path to point to a file with the client certificate to present to servers that require
+    /// clients to authenticate with a certificate.
+    ///
+    /// Refers to `http.sslCert`.
+    pub ssl_cert: Option<PathBuf>,
+    /// If set, use this path to point to a file with the private key of the client certificate in `ssl_cert`.
+    ///
+    /// Refers to `http.sslKey`.
+    pub ssl_key: Option<PathBuf>,
+    /// Certificates and keys provided in memory, which take precedence over `ssl_cert`, `ssl_key` and `ssl_ca_info` if set.
+    pub certificates: crate::client::connect::Certificates,
     /// The HTTP version to enforce. If unset, it is implementation defined.
     pub http_version: Option<HttpVersion>,
+    /// If `true`, authentication methods which don't need a username or password, like `Negotiate` with a Kerberos ticket
+    /// or `NTLM` with the credentials of the current user on Windows, will be attempted if the server asks for them.
+    ///
+    /// This is only supported by the `curl` backend, and only if `libcurl` was built with GSS-API or SSPI support, see the
+    /// `http-client-curl-spnego` and `http-client-curl-ntlm` features.
+    /// Refers to `http.emptyAuth`.
+    pub empty_auth: bool,
     /// Backend specific options, if available.
     pub backend: Option<Arc<Mutex<dyn Any + Send + Sync + 'static>>>,
 }
@@ -208,7 +226,11 @@ impl Default for Options {
             ssl_ca_info: None,
             ssl_version: None,
             ssl_verify: true,
+            ssl_cert: None,
+            ssl_key: None,
+            certificates: Default::default(),
             http_version: None,
+            empty_auth: false,
             backend: None,
         }
     }
@@ -231,6 +253,7 @@ pub struct Transport<H: Http> {
     service: Option<Service>,
     line_provider: Option<gix_packetline::StreamingPeekableIter<H::ResponseBody>>,
     identity: Option<gix_sec::identity::Account>,
+    certificates: crate::client::connect::Certificates,
     trace: bool,
 }
 
@@ -255,9 +278,26 @@ impl<H: Http> Transport<H> {
             http,
             line_provider: None,
             identity,
+            certificates: Default::default(),
             trace,
         }
     }
+
+    /// Use `certificates` for all connections, unless the [`Options`] passed to
+    /// [`configure()`](client::TransportWithoutIO::configure()) have their own [certificates](Options::certificates).
+    pub fn with_certificates(mut self, certificates: crate::client::connect::Certificates) -> Self {
+        if !certificates.is_empty() {
+            // Custom implementations may not support our options, and won't use the certificates either way.
+            self.http
+                .configure(&Options {
+                    certificates: certificates.clone(),
+                    ..Default::default()
+                })
+                .ok();
+        }
+        self.certificates = certificates;
+        self
+    }
 }
 
 impl<H: Http> Transport<H> {
@@ -388,7 +428,14 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
     }
 
     fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        self.http.configure(config)
+        match config.downcast_ref::<Options>() {
+            Some(options) if options.certificates.is_empty() && !self.certificates.is_empty() => {
+                let mut options = options.clone();
+                options.certificates = self.certificates.clone();
+                self.http.configure(&options)
+            }
+            _ => self.http.configure(config),
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 use std::{
     any::Any,
     io::{Read, Write},
+    path::PathBuf,
     str::FromStr,
     sync::{atomic, Arc},
 };
@@ -21,6 +22,8 @@ pub enum Error {
     ConfigureRequest(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error(transparent)]
     Redirect(#[from] redirect::Error),
+    #[error("Could not read certificate or key from '{}'", path.display())]
+    ReadCertificate { path: PathBuf, source: std::io::Error },
 }
 
 impl crate::IsSpuriousError for Error {
@@ -43,39 +46,7 @@ impl Default for Remote {
             let mut redirected_base_url = None::<String>;
             let allow_redirects = Arc::new(atomic::AtomicBool::new(false));
 
-            // We may error while configuring, which is expected as part of the internal protocol. The error will be
-            // received and the sender of the request might restart us.
-            let client = reqwest::blocking::ClientBuilder::new()
-                .connect_timeout(std::time::Duration::from_secs(20))
-                .http1_title_case_headers()
-                .redirect(reqwest::redirect::Policy::custom({
-                    let allow_redirects = allow_redirects.clone();
-                    move |attempt| {
-                        if allow_redirects.load(atomic::Ordering::Relaxed) {
-                            let curr_url = attempt.url();
-                            let prev_urls = attempt.previous();
-
-                            match prev_urls.first() {
-                                Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
-                                    // git does not want to be redirected to a different host.
-                                    attempt.stop()
-                                }
-                                _ => {
-                                    // emulate default git behaviour which relies on curl default behaviour apparently.
-                                    const CURL_DEFAULT_REDIRS: usize = 50;
-                                    if prev_urls.len() >= CURL_DEFAULT_REDIRS {
-                                        attempt.error("too many redirects")
-                                    } else {
-                                        attempt.follow()
-                                    }
-                                }
-                            }
-                        } else {
-                            attempt.stop()
-                        }
-                    }
-                }))
-                .build()?;
+            let mut client = None::<(Tls, reqwest::blocking::Client)>;
 
             for Request {
                 url,
@@ -85,6 +56,13 @@ impl Default for Remote {
                 config,
             } in req_recv
             {
+                // We may error while configuring, which is expected as part of the internal protocol. The error will be
+                // received and the sender of the request might restart us.
+                let tls = Tls::from_options(&config);
+                if client.as_ref().map_or(true, |(previous, _)| *previous != tls) {
+                    client = Some((tls.clone(), new_client(allow_redirects.clone(), &tls)?));
+                }
+                let client = &client.as_ref().expect("just set").1;
                 let effective_url = redirect::swap_tails(redirected_base_url.as_deref(), &base_url, url.clone());
                 let mut req_builder = if upload_body_kind.is_some() {
                     client.post(&effective_url)
@@ -202,6 +180,129 @@ impl Default for Remote {
     }
 }
 
+/// The configuration of a client that can't be changed per request, and thus requires a new client if it changes.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(
+        feature = "http-client-reqwest-rust-tls",
+        feature = "http-client-reqwest-rust-tls-trust-dns",
+        feature = "http-client-reqwest-native-tls"
+    )),
+    allow(dead_code)
+)]
+struct Tls {
+    ssl_verify: bool,
+    ssl_ca_info: Option<PathBuf>,
+    ssl_cert: Option<PathBuf>,
+    ssl_key: Option<PathBuf>,
+    certificates: crate::client::connect::Certificates,
+}
+
+impl Tls {
+    fn from_options(opts: &http::Options) -> Self {
+        Tls {
+            ssl_verify: opts.ssl_verify,
+            ssl_ca_info: opts.ssl_ca_info.clone(),
+            ssl_cert: opts.ssl_cert.clone(),
+            ssl_key: opts.ssl_key.clone(),
+            certificates: opts.certificates.clone(),
+        }
+    }
+
+    #[cfg(any(
+        feature = "http-client-reqwest-rust-tls",
+        feature = "http-client-reqwest-rust-tls-trust-dns",
+        feature = "http-client-reqwest-native-tls"
+    ))]
+    fn apply(&self, mut builder: reqwest::blocking::ClientBuilder) -> Result<reqwest::blocking::ClientBuilder, Error> {
+        use std::borrow::Cow;
+
+        fn read<'a>(in_memory: Option<&'a Vec<u8>>, path: Option<&PathBuf>) -> Result<Option<Cow<'a, [u8]>>, Error> {
+            Ok(match (in_memory, path) {
+                (Some(data), _) => Some(Cow::Borrowed(data.as_slice())),
+                (None, Some(path)) => Some(Cow::Owned(std::fs::read(path).map_err(|source| {
+                    Error::ReadCertificate {
+                        path: path.to_owned(),
+                        source,
+                    }
+                })?)),
+                (None, None) => None,
+            })
+        }
+
+        if !self.ssl_verify {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(ca_certificates) = read(self.certificates.ca_certificates.as_ref(), self.ssl_ca_info.as_ref())? {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in reqwest::Certificate::from_pem_bundle(&ca_certificates)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        let key = read(self.certificates.client_key.as_ref(), self.ssl_key.as_ref())?;
+        if let Some(certificate) = read(self.certificates.client_certificate.as_ref(), self.ssl_cert.as_ref())? {
+            #[cfg(feature = "http-client-reqwest-native-tls")]
+            let identity = reqwest::Identity::from_pkcs8_pem(&certificate, key.as_deref().unwrap_or(&certificate))?;
+            #[cfg(not(feature = "http-client-reqwest-native-tls"))]
+            let identity = {
+                let mut pem = certificate.into_owned();
+                if let Some(key) = key {
+                    pem.push(b'\n');
+                    pem.extend_from_slice(&key);
+                }
+                reqwest::Identity::from_pem(&pem)?
+            };
+            builder = builder.identity(identity);
+        }
+        Ok(builder)
+    }
+}
+
+fn new_client(allow_redirects: Arc<atomic::AtomicBool>, tls: &Tls) -> Result<reqwest::blocking::Client, Error> {
+    let builder = reqwest::blocking::ClientBuilder::new()
+        .connect_timeout(std::time::Duration::from_secs(20))
+        .http1_title_case_headers()
+        .redirect(reqwest::redirect::Policy::custom({
+            move |attempt| {
+                if allow_redirects.load(atomic::Ordering::Relaxed) {
+                    let curr_url = attempt.url();
+                    let prev_urls = attempt.previous();
+
+                    match prev_urls.first() {
+                        Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
+                            // git does not want to be redirected to a different host.
+                            attempt.stop()
+                        }
+                        _ => {
+                            // emulate default git behaviour which relies on curl default behaviour apparently.
+                            const CURL_DEFAULT_REDIRS: usize = 50;
+                            if prev_urls.len() >= CURL_DEFAULT_REDIRS {
+                                attempt.error("too many redirects")
+                            } else {
+                                attempt.follow()
+                            }
+                        }
+                    }
+                } else {
+                    attempt.stop()
+                }
+            }
+        }));
+    #[cfg(any(
+        feature = "http-client-reqwest-rust-tls",
+        feature = "http-client-reqwest-rust-tls-trust-dns",
+        feature = "http-client-reqwest-native-tls"
+    ))]
+    let builder = tls.apply(builder)?;
+    #[cfg(not(any(
+        feature = "http-client-reqwest-rust-tls",
+        feature = "http-client-reqwest-rust-tls-trust-dns",
+        feature = "http-client-reqwest-native-tls"
+    )))]
+    let _ = tls;
+    Ok(builder.build()?)
+}
+
 /// utilities
 impl Remote {
     fn restore_thread_after_failure(&mut self) -> http::Error {
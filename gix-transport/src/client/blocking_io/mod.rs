@@ -9,6 +9,10 @@ pub mod file;
 #[cfg(feature = "http-client")]
 pub mod http;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod pool;
+
 mod bufread_ext;
 pub use bufread_ext::{ExtendedBufRead, HandleProgress, ReadlineBufRead};
 
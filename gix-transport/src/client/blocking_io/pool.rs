@@ -0,0 +1,142 @@
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
+
+use bstr::BString;
+
+use crate::{
+    client::{connect, Transport},
+    Protocol,
+};
+
+/// A pool of transports to reuse connections across sequential operations against the same remote, like listing
+/// references, fetching and then pushing, instead of connecting again for each of them.
+///
+/// Only transports that don't keep state across requests, like the `http(s)` transport, can be reused as they perform
+/// a new handshake for each operation while keeping their connection alive. All other transports are dropped when
+/// [released](Pool::release()), which closes their connection. For `ssh`, consider
+/// [connection multiplexing](crate::client::ssh::connect::Multiplex) instead.
+///
+/// Released transports are kept until they remain unused for longer than [`max_idle_time`](Pool::max_idle_time),
+/// or until the pool is [cleared](Pool::clear()) or dropped.
+#[derive(Default)]
+pub struct Pool {
+    /// The amount of time a released transport may remain unused before it is closed, or `None` to keep it open
+    /// as long as the pool exists.
+    pub max_idle_time: Option<Duration>,
+    idle: Vec<Idle>,
+}
+
+struct Idle {
+    key: Key,
+    transport: Box<dyn Transport + Send>,
+    released_at: Instant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Key {
+    url: BString,
+    version: Protocol,
+}
+
+/// A transport obtained from a [`Pool`], which should be [released](Pool::release()) to it once an operation is done
+/// to allow its reuse.
+pub struct Pooled {
+    key: Key,
+    transport: Box<dyn Transport + Send>,
+}
+
+impl Pooled {
+    /// Take the transport out of the pool's control, so that it can't be [released](Pool::release()) anymore.
+    pub fn into_inner(self) -> Box<dyn Transport + Send> {
+        self.transport
+    }
+}
+
+impl Deref for Pooled {
+    type Target = Box<dyn Transport + Send>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.transport
+    }
+}
+
+impl DerefMut for Pooled {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.transport
+    }
+}
+
+/// Lifecycle
+impl Pool {
+    /// Create a new empty pool whose released transports are closed after `max_idle_time`, or never if `None`.
+    pub fn new(max_idle_time: Option<Duration>) -> Self {
+        Pool {
+            max_idle_time,
+            idle: Vec::new(),
+        }
+    }
+
+    /// Close all idle transports.
+    pub fn clear(&mut self) {
+        self.idle.clear();
+    }
+}
+
+/// Access
+impl Pool {
+    /// Return a previously [released](Pool::release()) transport for `url` that was created with the same protocol
+    /// version as specified in `options`, or [connect](crate::client::connect()) to `url` with `options` if there is none.
+    pub fn connect<Url, E>(&mut self, url: Url, options: connect::Options) -> Result<Pooled, connect::Error>
+    where
+        Url: TryInto<gix_url::Url, Error = E>,
+        gix_url::parse::Error: From<E>,
+    {
+        self.remove_expired();
+        let url = url.try_into().map_err(gix_url::parse::Error::from)?;
+        let key = Key {
+            url: url.to_bstring(),
+            version: options.version,
+        };
+        if let Some(pos) = self.idle.iter().position(|idle| idle.key == key) {
+            let transport = self.idle.remove(pos).transport;
+            return Ok(Pooled { key, transport });
+        }
+        let transport = crate::client::connect::<_, std::convert::Infallible>(url, options)?;
+        Ok(Pooled { key, transport })
+    }
+
+    /// Return `transport` to the pool once the operation it was used for is complete, i.e. all responses were read
+    /// entirely, so that it can be reused for the next operation against the same remote.
+    ///
+    /// Transports that can't be reused are dropped right away, and so should be transports that failed.
+    pub fn release(&mut self, transport: Pooled) {
+        self.remove_expired();
+        if transport.connection_persists_across_multiple_requests() {
+            return;
+        }
+        self.idle.push(Idle {
+            key: transport.key,
+            transport: transport.transport,
+            released_at: Instant::now(),
+        });
+    }
+
+    /// Return the amount of transports that were released and can still be reused.
+    pub fn num_idle(&self) -> usize {
+        self.idle
+            .iter()
+            .filter(|idle| !is_expired(idle, self.max_idle_time))
+            .count()
+    }
+
+    fn remove_expired(&mut self) {
+        let max_idle_time = self.max_idle_time;
+        self.idle.retain(|idle| !is_expired(idle, max_idle_time));
+    }
+}
+
+fn is_expired(idle: &Idle, max_idle_time: Option<Duration>) -> bool {
+    matches!(max_idle_time, Some(max) if idle.released_at.elapsed() >= max)
+}
@@ -61,10 +61,25 @@ pub mod invocation {
 ///
 #[allow(clippy::empty_docs)]
 pub mod connect {
-    use std::ffi::{OsStr, OsString};
+    use std::{
+        ffi::{OsStr, OsString},
+        path::PathBuf,
+        time::Duration,
+    };
 
     use crate::client::ssh::ProgramKind;
 
+    /// Configure OpenSSH connection multiplexing so that all `ssh` invocations for the same host share a single
+    /// connection, which avoids the cost of connecting and authenticating for each operation.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Multiplex {
+        /// The path to the control socket of the master connection, which may contain the tokens that `ssh` supports
+        /// for its `ControlPath` option, like `%C` for a hash of the connection parameters.
+        pub control_path: PathBuf,
+        /// How long the master connection is kept open after the last operation that used it finished.
+        pub persist: Duration,
+    }
+
     /// The options for use when [connecting][super::connect()] via the `ssh` protocol.
     #[derive(Debug, Clone, Default)]
     pub struct Options {
@@ -79,6 +94,10 @@ pub mod connect {
         /// when invoking the program.
         /// If unset, the `program` basename determines the variant, or an invocation of the `command` itself.
         pub kind: Option<ProgramKind>,
+        /// If set, reuse a single connection per host across `ssh` invocations.
+        ///
+        /// This is only supported by [`ProgramKind::Ssh`], and ignored for all other kinds.
+        pub multiplex: Option<Multiplex>,
     }
 
     impl Options {
@@ -143,7 +162,8 @@ pub fn connect(
         options.disallow_shell,
         desired_version,
         trace,
-    ))
+    )
+    .with_ssh_multiplex(options.multiplex))
 }
 
 #[cfg(test)]
@@ -1,4 +1,7 @@
-use std::{ffi::OsStr, io::ErrorKind};
+use std::{
+    ffi::{OsStr, OsString},
+    io::ErrorKind,
+};
 
 use bstr::{BString, ByteSlice, ByteVec};
 
@@ -28,6 +31,7 @@ impl ProgramKind {
         url: &gix_url::Url,
         desired_version: Protocol,
         disallow_shell: bool,
+        multiplex: Option<&ssh::connect::Multiplex>,
     ) -> Result<gix_command::Prepare, ssh::invocation::Error> {
         let mut prepare = gix_command::prepare(ssh_cmd).with_shell();
         if disallow_shell {
@@ -43,6 +47,17 @@ impl ProgramKind {
                 if let Some(port) = url.port {
                     prepare = prepare.arg(format!("-p{port}"));
                 }
+                if let Some(multiplex) = multiplex {
+                    let mut control_path = OsString::from("ControlPath=");
+                    control_path.push(&multiplex.control_path);
+                    prepare = prepare
+                        .args(["-o", "ControlMaster=auto", "-o"])
+                        .arg(control_path)
+                        .args([
+                            "-o".into(),
+                            format!("ControlPersist={}s", multiplex.persist.as_secs().max(1)),
+                        ]);
+                }
             }
             ProgramKind::Plink | ProgramKind::Putty | ProgramKind::TortoisePlink => {
                 if *self == ProgramKind::TortoisePlink {
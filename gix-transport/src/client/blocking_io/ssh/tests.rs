@@ -130,6 +130,42 @@ mod program_kind {
             }
         }
 
+        #[test]
+        fn ssh_with_multiplex() {
+            let url = gix_url::parse("ssh://user@host:42/p".into()).expect("valid url");
+            let multiplex = ssh::connect::Multiplex {
+                control_path: "/tmp/gix-%C".into(),
+                persist: std::time::Duration::from_secs(60),
+            };
+            let cmd = std::process::Command::from(
+                ProgramKind::Ssh
+                    .prepare_invocation(OsStr::new("ssh"), &url, Protocol::V1, false, Some(&multiplex))
+                    .expect("no error"),
+            );
+            assert_eq!(
+                cmd.get_args().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>(),
+                [
+                    "-p42",
+                    "-o",
+                    "ControlMaster=auto",
+                    "-o",
+                    "ControlPath=/tmp/gix-%C",
+                    "-o",
+                    "ControlPersist=60s",
+                    "user@host"
+                ]
+            );
+
+            for kind in [ProgramKind::Plink, ProgramKind::Simple] {
+                let url = gix_url::parse("ssh://user@host/p".into()).expect("valid url");
+                let cmd = std::process::Command::from(
+                    kind.prepare_invocation(OsStr::new("ssh"), &url, Protocol::V1, false, Some(&multiplex))
+                        .expect("no error"),
+                );
+                assert_eq!(cmd.get_args().count(), 1, "{kind:?} doesn't support multiplexing");
+            }
+        }
+
         #[test]
         fn tortoise_plink_has_batch_command() {
             assert_eq!(
@@ -234,12 +270,12 @@ mod program_kind {
 
             let disallow_shell = false;
             let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell, None)?;
             assert!(prepare.use_shell, "shells are used when needed");
 
             let disallow_shell = true;
             let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell, None)?;
             assert!(
                 !prepare.use_shell,
                 "but we can enforce it not to be used as well for historical reasons"
@@ -257,7 +293,7 @@ mod program_kind {
         ) -> std::result::Result<gix_command::Prepare, ssh::invocation::Error> {
             let ssh_cmd = kind.exe().unwrap_or_else(|| OsStr::new("simple"));
             let url = gix_url::parse(url.into()).expect("valid url");
-            kind.prepare_invocation(ssh_cmd, &url, version, false)
+            kind.prepare_invocation(ssh_cmd, &url, version, false, None)
         }
         fn call(kind: ProgramKind, url: &str, version: Protocol) -> gix_command::Prepare {
             try_call(kind, url, version).expect("no error")
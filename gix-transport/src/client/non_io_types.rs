@@ -42,6 +42,40 @@ pub(crate) mod connect {
         pub ssh: crate::client::ssh::connect::Options,
         /// If `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
         pub trace: bool,
+        #[cfg(feature = "http-client")]
+        /// Certificates to use if the scheme of the URL is `https`, which take precedence over the ones configured by path
+        /// in [`http::Options`](crate::client::http::Options).
+        pub certificates: Certificates,
+    }
+
+    /// Certificates and keys in `PEM` format to use for TLS connections, supplied programmatically instead of via paths to files.
+    ///
+    /// Note that the `client_key` is redacted when formatting instances with `Debug`.
+    #[derive(Default, Clone, PartialEq, Eq)]
+    pub struct Certificates {
+        /// The client certificate to present to servers that require clients to authenticate with a certificate.
+        pub client_certificate: Option<Vec<u8>>,
+        /// The private key belonging to the `client_certificate`.
+        pub client_key: Option<Vec<u8>>,
+        /// One or more certificates of the authorities to verify servers with, which are used instead of the default ones.
+        pub ca_certificates: Option<Vec<u8>>,
+    }
+
+    impl std::fmt::Debug for Certificates {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Certificates")
+                .field("client_certificate", &self.client_certificate)
+                .field("client_key", &self.client_key.as_ref().map(|_| "<redacted>"))
+                .field("ca_certificates", &self.ca_certificates)
+                .finish()
+        }
+    }
+
+    impl Certificates {
+        /// Return `true` if no certificate or key is set.
+        pub fn is_empty(&self) -> bool {
+            self.client_certificate.is_none() && self.client_key.is_none() && self.ca_certificates.is_none()
+        }
     }
 
     /// The error used in [`connect()`][crate::connect()].
@@ -94,16 +128,16 @@ mod error {
     use bstr::BString;
 
     use crate::client::capabilities;
-    #[cfg(feature = "http-client")]
+    #[cfg(any(feature = "http-client", feature = "async-http-client"))]
     use crate::client::http;
     #[cfg(feature = "blocking-client")]
     use crate::client::ssh;
 
-    #[cfg(feature = "http-client")]
+    #[cfg(any(feature = "http-client", feature = "async-http-client"))]
     type HttpError = http::Error;
     #[cfg(feature = "blocking-client")]
     type SshInvocationError = ssh::invocation::Error;
-    #[cfg(not(feature = "http-client"))]
+    #[cfg(not(any(feature = "http-client", feature = "async-http-client")))]
     type HttpError = std::convert::Infallible;
     #[cfg(not(feature = "blocking-client"))]
     type SshInvocationError = std::convert::Infallible;
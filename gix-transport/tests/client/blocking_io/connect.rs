@@ -0,0 +1,13 @@
+use gix_transport::client::connect::Certificates;
+
+#[test]
+fn certificates_debug_output_redacts_the_client_key() {
+    let certificates = Certificates {
+        client_certificate: Some(b"certificate".to_vec()),
+        client_key: Some(b"secret".to_vec()),
+        ca_certificates: None,
+    };
+    let debug = format!("{certificates:?}");
+    assert!(!debug.contains(&format!("{:?}", b"secret".to_vec())), "{debug}");
+    assert!(debug.contains("client_key: Some(\"<redacted>\")"), "{debug}");
+}
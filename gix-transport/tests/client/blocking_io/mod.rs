@@ -1,2 +1,5 @@
+#[cfg(feature = "http-client")]
+mod connect;
 #[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
 mod http;
+mod pool;
@@ -0,0 +1,54 @@
+use gix_transport::client::{connect, pool::Pool};
+
+#[test]
+fn stateful_transports_are_not_kept() -> crate::Result {
+    let mut pool = Pool::default();
+    let transport = pool.connect("file:///path/to/repo", connect::Options::default())?;
+    pool.release(transport);
+    assert_eq!(pool.num_idle(), 0, "the connection of stateful transports is closed");
+    Ok(())
+}
+
+#[test]
+#[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
+fn stateless_transports_are_reused_for_the_same_url_and_version() -> crate::Result {
+    let url = "https://example.com/repo";
+    let mut pool = Pool::default();
+    let transport = pool.connect(url, connect::Options::default())?;
+    let first = &**transport as *const _ as *const ();
+    pool.release(transport);
+    assert_eq!(pool.num_idle(), 1);
+
+    let transport = pool.connect(url, connect::Options::default())?;
+    assert_eq!(
+        &**transport as *const _ as *const (), first,
+        "the same transport is handed out"
+    );
+    assert_eq!(pool.num_idle(), 0);
+    pool.release(transport);
+
+    let transport = pool.connect(
+        url,
+        connect::Options {
+            version: gix_transport::Protocol::V1,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(pool.num_idle(), 1, "a different protocol version needs a new transport");
+    pool.release(transport);
+    assert_eq!(pool.num_idle(), 2);
+
+    pool.clear();
+    assert_eq!(pool.num_idle(), 0);
+    Ok(())
+}
+
+#[test]
+#[cfg(any(feature = "http-client-curl", feature = "http-client-reqwest"))]
+fn idle_transports_expire() -> crate::Result {
+    let mut pool = Pool::new(Some(std::time::Duration::ZERO));
+    let transport = pool.connect("https://example.com/repo", connect::Options::default())?;
+    pool.release(transport);
+    assert_eq!(pool.num_idle(), 0, "it's closed immediately");
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use bstr::ByteSlice;
+use futures_lite::{AsyncBufReadExt, AsyncReadExt, StreamExt};
+use gix_transport::{
+    client::{
+        http::{self, Method, Request, Response, ResponseFuture},
+        SetServiceResponse, Transport, TransportV2Ext, TransportWithoutIO,
+    },
+    Protocol, Service,
+};
+
+use crate::fixture_bytes;
+
+/// Answers requests with the given fixtures in order, and records all requests.
+#[derive(Default)]
+struct Mock {
+    responses: RefCell<VecDeque<Vec<u8>>>,
+    requests: Rc<RefCell<Vec<Request>>>,
+}
+
+impl Mock {
+    fn new(fixtures: &[&str]) -> Self {
+        Mock {
+            responses: RefCell::new(fixtures.iter().copied().map(fixture_bytes).collect()),
+            requests: Default::default(),
+        }
+    }
+}
+
+impl http::Http for Mock {
+    fn request(&self, request: Request) -> ResponseFuture {
+        self.requests.borrow_mut().push(request);
+        let response = self
+            .responses
+            .borrow_mut()
+            .pop_front()
+            .expect("a response for each request");
+        Box::pin(async move {
+            let (headers, body) = split_response(&response);
+            let status = headers.first().cloned().unwrap_or_default();
+            if !status.contains(" 200 ") {
+                return Err(http::Error::Detail { description: status });
+            }
+            Ok(Response {
+                headers,
+                body: Box::new(futures_lite::io::Cursor::new(body)),
+            })
+        })
+    }
+}
+
+fn split_response(response: &[u8]) -> (Vec<String>, Vec<u8>) {
+    let mut headers = Vec::new();
+    let mut rest = response;
+    while let Some(pos) = rest.find_byte(b'\n') {
+        let line = rest[..pos].trim_end_with(|c| c == '\r');
+        rest = &rest[pos + 1..];
+        if line.is_empty() {
+            break;
+        }
+        headers.push(line.to_str_lossy().into_owned());
+    }
+    (headers, rest.to_vec())
+}
+
+fn connect(mock: Mock) -> crate::Result<http::Transport<Mock>> {
+    Ok(http::connect_http(
+        mock,
+        gix_url::parse("https://example.com/repo.git".into())?,
+        Protocol::V2,
+        false,
+    ))
+}
+
+#[async_std::test]
+async fn handshake_and_lsrefs_and_fetch_v2() -> crate::Result {
+    let mock = Mock::new(&[
+        "v2/http-handshake-service-announced.response",
+        "v2/http-lsrefs.response",
+        "v2/http-fetch.response",
+    ]);
+    let requests = Rc::clone(&mock.requests);
+    let mut c = connect(mock)?;
+    assert!(
+        !c.connection_persists_across_multiple_requests(),
+        "http connections are never stateful"
+    );
+
+    let SetServiceResponse {
+        actual_protocol,
+        capabilities,
+        refs,
+    } = c.handshake(Service::UploadPack, &[("key", Some("value"))]).await?;
+    assert_eq!(actual_protocol, Protocol::V2);
+    assert!(refs.is_none(), "refs are only returned in V1");
+    assert_eq!(
+        capabilities
+            .iter()
+            .map(|c| c.name().to_str_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        ["agent", "ls-refs", "fetch", "server-option"]
+    );
+    {
+        let requests = requests.borrow();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(
+            request.url,
+            "https://example.com/repo.git/info/refs?service=git-upload-pack"
+        );
+        assert_eq!(request.base_url, "https://example.com/repo.git");
+        assert_eq!(
+            request.headers,
+            [
+                format!("User-Agent: git/oxide-{}", env!("CARGO_PKG_VERSION")),
+                "Git-Protocol: version=2:key=value".into()
+            ]
+        );
+    }
+    drop(refs);
+
+    let res = c
+        .invoke(
+            "ls-refs",
+            [("with-value", Some("value"))].iter().copied(),
+            Some(vec!["arg1".into()].into_iter()),
+            false,
+        )
+        .await?;
+    assert_eq!(
+        res.lines().try_collect::<_, _, Vec<_>>().await?,
+        [
+            "808e50d724f604f69ab93c6da2919c014667bedb HEAD symref-target:refs/heads/master",
+            "808e50d724f604f69ab93c6da2919c014667bedb refs/heads/master"
+        ]
+    );
+    {
+        let requests = requests.borrow();
+        let request = &requests[1];
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.url, "https://example.com/repo.git/git-upload-pack");
+        assert_eq!(
+            request.headers[1..],
+            [
+                "Content-Type: application/x-git-upload-pack-request",
+                "Accept: application/x-git-upload-pack-result",
+                "Git-Protocol: version=2"
+            ]
+        );
+        assert_eq!(
+            request.body.as_bstr(),
+            "0014command=ls-refs\n0015with-value=value\n00010009arg1\n0000",
+            "the request is sent once its body is complete"
+        );
+    }
+
+    let mut res = c
+        .invoke(
+            "fetch",
+            Vec::<(_, Option<&str>)>::new().into_iter(),
+            None::<std::vec::IntoIter<bstr::BString>>,
+            false,
+        )
+        .await?;
+    let mut line = String::new();
+    res.read_line(&mut line).await?;
+    assert_eq!(line, "packfile\n");
+    res.set_progress_handler(Some(Box::new(|_, _| gix_packetline::read::ProgressAction::Continue)));
+    let mut pack = Vec::new();
+    res.read_to_end(&mut pack).await?;
+    assert_eq!(pack.len(), 876);
+    Ok(())
+}
+
+#[async_std::test]
+async fn missing_smart_content_type_is_an_error() -> crate::Result {
+    let mut c = connect(Mock::new(&["v2/http-lsrefs.response"]))?;
+    let err = c
+        .handshake(Service::UploadPack, &[])
+        .await
+        .err()
+        .expect("the advertisement has the wrong content type");
+    assert!(err.to_string().contains("application/x-git-upload-pack-advertisement"));
+    Ok(())
+}
+
+#[async_std::test]
+async fn failed_requests_are_errors() -> crate::Result {
+    let mut c = connect(Mock::new(&["http-404.response"]))?;
+    let err = c
+        .handshake(Service::UploadPack, &[])
+        .await
+        .err()
+        .expect("404 is an error");
+    assert!(matches!(
+        err,
+        gix_transport::client::Error::Http(http::Error::Detail { description }) if description.contains("404")
+    ));
+    Ok(())
+}
@@ -2,3 +2,5 @@
 mod blocking_io;
 mod capabilities;
 mod git;
+#[cfg(feature = "async-http-client")]
+mod http;
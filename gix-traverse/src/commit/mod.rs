@@ -15,6 +15,7 @@ pub struct Simple<Find, Predicate> {
     state: simple::State,
     parents: Parents,
     sorting: simple::Sorting,
+    paths: Option<simple::PathFilter>,
 }
 
 /// Simple ancestors traversal, without the need to keep track of graph-state.
@@ -40,6 +41,9 @@ pub struct Topo<Find, Predicate> {
 
 pub mod topo;
 
+pub mod reachable;
+pub use reachable::function::reachable_from;
+
 /// Specify how to handle commit parents during traversal.
 #[derive(Default, Copy, Clone)]
 pub enum Parents {
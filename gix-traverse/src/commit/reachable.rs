@@ -0,0 +1,197 @@
+//! Find commits that are reachable from some commits, but not from others, similar to `git rev-list <tips> --not <haves>`.
+use gix_hash::{oid, ObjectId};
+use smallvec::SmallVec;
+
+use super::{find, Either};
+
+/// The error returned by [`reachable_from()`](super::reachable_from()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The commit {id} could not be found")]
+    NotFound { id: ObjectId },
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    ObjectDecode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    CommitGraphFile(#[from] gix_commitgraph::file::commit::Error),
+}
+
+bitflags::bitflags! {
+    /// The state of a commit during the walk.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    struct Flags: u8 {
+        /// The commit is reachable from one of the haves.
+        const UNINTERESTING = 1 << 0;
+        /// The parents of the commit were added to the queue.
+        const ADDED = 1 << 1;
+    }
+}
+
+/// The generation number and commit time, used to process the newest commits first.
+type GenAndCommitTime = (u32, i64);
+
+/// The parents of a commit along with their generation and commit time.
+type Parents = SmallVec<[(ObjectId, GenAndCommitTime); 1]>;
+
+pub(crate) mod function {
+    use gix_hash::ObjectId;
+    use gix_revwalk::{graph::IdMap, PriorityQueue};
+
+    use super::{parents, Error, Flags};
+
+    /// Return all commits that are reachable from `tips`, but not from `haves`, similar to `git rev-list <tips> --not <haves>`.
+    /// Commits are ordered by generation number if `cache` is set and by commit time otherwise, so newer commits come first.
+    ///
+    /// Commits are looked up in `objects`, or in `cache` if it is set, which speeds up the walk considerably.
+    /// It is an error if a commit reachable from `tips` can't be found, whereas commits that are only reachable from `haves`
+    /// may be missing, as it is the case in shallow repositories.
+    ///
+    /// ### Limitations
+    ///
+    /// * Commits are processed by generation number if a `cache` is available, and by commit time otherwise.
+    ///   Just like in `git`, commits that are reachable from `haves` may be returned in the latter case if commit times
+    ///   are skewed, i.e. if commits are older than their parents.
+    /// * Reachability bitmaps aren't used, as they aren't supported yet.
+    pub fn reachable_from(
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        haves: impl IntoIterator<Item = impl Into<ObjectId>>,
+        objects: impl gix_object::Find,
+        cache: Option<&gix_commitgraph::Graph>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        walk(tips, haves, &objects, cache, &mut |id, _child| {
+            Err(Error::NotFound { id })
+        })
+    }
+
+    /// Like [`reachable_from()`], but call `missing` with each commit reachable from `tips` that can't be found,
+    /// along with the commit that refers to it as parent, if there is one.
+    /// `missing` may return an error to abort the walk.
+    pub(crate) fn walk(
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        haves: impl IntoIterator<Item = impl Into<ObjectId>>,
+        objects: &impl gix_object::Find,
+        cache: Option<&gix_commitgraph::Graph>,
+        missing: &mut dyn FnMut(ObjectId, Option<ObjectId>) -> Result<(), Error>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut states = IdMap::<(Flags, Option<ObjectId>)>::default();
+        let mut queue = PriorityQueue::new();
+        let mut buf = Vec::new();
+        for (id, flags) in tips
+            .into_iter()
+            .map(|id| (id.into(), Flags::empty()))
+            .chain(haves.into_iter().map(|id| (id.into(), Flags::UNINTERESTING)))
+        {
+            let state = states.entry(id).or_insert((flags, None));
+            state.0 |= flags;
+            queue.insert(super::gen_and_commit_time(cache, objects, &id, &mut buf)?, id);
+        }
+
+        let mut commits = Vec::new();
+        while queue
+            .iter_unordered()
+            .any(|id| !states[id].0.contains(Flags::UNINTERESTING))
+        {
+            let id = queue.pop_value().expect("non-empty");
+            let (flags, child) = states[&id];
+            let uninteresting = flags.contains(Flags::UNINTERESTING);
+            if flags.contains(Flags::ADDED) && !uninteresting {
+                continue;
+            }
+            let Some(parents) = parents(cache, objects, &id, &mut buf)? else {
+                if !uninteresting {
+                    missing(id, child)?;
+                }
+                continue;
+            };
+            if !flags.contains(Flags::ADDED) {
+                states.get_mut(&id).expect("present").0 |= Flags::ADDED;
+                if !uninteresting {
+                    commits.push(id);
+                }
+            }
+            for (parent, key) in parents {
+                match states.get_mut(&parent) {
+                    Some((parent_flags, _)) => {
+                        if uninteresting && !parent_flags.contains(Flags::UNINTERESTING) {
+                            *parent_flags |= Flags::UNINTERESTING;
+                            // Commits that were already processed have to propagate their new state to their parents.
+                            if parent_flags.contains(Flags::ADDED) {
+                                queue.insert(key, parent);
+                            }
+                        }
+                    }
+                    None => {
+                        let flags = if uninteresting {
+                            Flags::UNINTERESTING
+                        } else {
+                            Flags::empty()
+                        };
+                        states.insert(parent, (flags, Some(id)));
+                        queue.insert(key, parent);
+                    }
+                }
+            }
+        }
+        commits.retain(|id| !states[id].0.contains(Flags::UNINTERESTING));
+        Ok(commits)
+    }
+}
+
+/// Return the parents of the commit `id` along with their generation and commit time, or `None` if it doesn't exist.
+fn parents(
+    cache: Option<&gix_commitgraph::Graph>,
+    objects: &impl gix_object::Find,
+    id: &oid,
+    buf: &mut Vec<u8>,
+) -> Result<Option<Parents>, Error> {
+    let mut parents = Parents::new();
+    match find(cache, objects, id, buf) {
+        Ok(Either::CommitRefIter(commit)) => {
+            for token in commit {
+                use gix_object::commit::ref_iter::Token as T;
+                match token {
+                    Ok(T::Tree { .. }) => continue,
+                    Ok(T::Parent { id }) => parents.push((id, (0, 0))),
+                    Ok(_past_parents) => break,
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            for (id, key) in parents.iter_mut() {
+                *key = gen_and_commit_time(cache, objects, id, buf)?;
+            }
+        }
+        Ok(Either::CachedCommit(commit)) => {
+            let cache = cache.expect("cache exists if CachedCommit was returned");
+            for pos in commit.iter_parents() {
+                let parent = cache.commit_at(pos?);
+                parents.push((
+                    parent.id().into(),
+                    (parent.generation(), parent.committer_timestamp() as i64),
+                ));
+            }
+        }
+        Err(gix_object::find::existing_iter::Error::NotFound { .. }) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+    Ok(Some(parents))
+}
+
+/// Return the generation and commit time of the commit `id`, or the lowest priority if it doesn't exist so it's processed last.
+fn gen_and_commit_time(
+    cache: Option<&gix_commitgraph::Graph>,
+    objects: &impl gix_object::Find,
+    id: &oid,
+    buf: &mut Vec<u8>,
+) -> Result<GenAndCommitTime, Error> {
+    Ok(match find(cache, objects, id, buf) {
+        Ok(Either::CommitRefIter(commit)) => (
+            gix_commitgraph::GENERATION_NUMBER_INFINITY,
+            commit.committer()?.time.seconds,
+        ),
+        Ok(Either::CachedCommit(commit)) => (commit.generation(), commit.committer_timestamp() as i64),
+        Err(gix_object::find::existing_iter::Error::NotFound { .. }) => (0, 0),
+        Err(err) => return Err(err.into()),
+    })
+}
@@ -1,6 +1,8 @@
+use gix_commitgraph::bloom;
 use gix_date::SecondsSinceUnixEpoch;
 use gix_hash::ObjectId;
 use gix_hashtable::HashSet;
+use gix_object::{bstr::BString, FindExt};
 use smallvec::SmallVec;
 use std::collections::VecDeque;
 
@@ -72,6 +74,15 @@ pub(super) struct State {
     parent_ids: SmallVec<[(ObjectId, SecondsSinceUnixEpoch); 2]>,
 }
 
+/// The state needed to only return commits that change one of the given paths.
+#[derive(Clone)]
+pub(super) struct PathFilter {
+    paths: Vec<BString>,
+    /// The keys of each path, along with the settings of the changed-path Bloom filters they were created for.
+    keys: Option<(bloom::Settings, Vec<Vec<bloom::Key>>)>,
+    buf: Vec<u8>,
+}
+
 ///
 #[allow(clippy::empty_docs)]
 mod init {
@@ -81,7 +92,7 @@ mod init {
 
     use super::{
         super::{simple::Sorting, Either, Info, ParentIds, Parents, Simple},
-        collect_parents, Error, State,
+        collect_parents, Error, PathFilter, State,
     };
 
     impl Default for State {
@@ -157,6 +168,23 @@ mod init {
             self
         }
 
+        /// Only return commits that change at least one of `paths` compared to their first parent, or that contain one of
+        /// them if they have no parent, while still traversing all of their ancestors.
+        /// `paths` are slash-separated and relative to the root of the repository, and a directory includes all paths within it.
+        /// If `paths` is empty, all commits are returned.
+        ///
+        /// If the [commit-graph](Self::commit_graph()) has changed-path Bloom filters, they are used to skip looking at the
+        /// trees of most commits which don't change any of `paths`.
+        pub fn paths(mut self, paths: impl IntoIterator<Item = impl Into<gix_object::bstr::BString>>) -> Self {
+            let paths: Vec<_> = paths.into_iter().map(Into::into).collect();
+            self.paths = (!paths.is_empty()).then(|| PathFilter {
+                paths,
+                keys: None,
+                buf: Vec::new(),
+            });
+            self
+        }
+
         fn queue_to_vecdeque(&mut self) {
             let state = &mut self.state;
             state.next.extend(
@@ -225,6 +253,7 @@ mod init {
                 state,
                 parents: Default::default(),
                 sorting: Default::default(),
+                paths: None,
             }
         }
     }
@@ -250,6 +279,30 @@ mod init {
         type Item = Result<Info, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                let info = match self.next_unfiltered()? {
+                    Ok(info) => info,
+                    Err(err) => return Some(Err(err)),
+                };
+                let Some(paths) = self.paths.as_mut() else {
+                    return Some(Ok(info));
+                };
+                match paths.is_changed_by(&info, self.cache.as_ref(), &self.objects) {
+                    Ok(true) => return Some(Ok(info)),
+                    Ok(false) => continue,
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+        }
+    }
+
+    /// Utilities
+    impl<Find, Predicate> Simple<Find, Predicate>
+    where
+        Find: gix_object::Find,
+        Predicate: FnMut(&oid) -> bool,
+    {
+        fn next_unfiltered(&mut self) -> Option<Result<Info, Error>> {
             if matches!(self.parents, Parents::First) {
                 self.next_by_topology()
             } else {
@@ -403,6 +456,85 @@ mod init {
     }
 }
 
+impl PathFilter {
+    /// Return `true` if the commit described by `info` changes any of our paths compared to its first parent.
+    fn is_changed_by(
+        &mut self,
+        info: &super::Info,
+        cache: Option<&gix_commitgraph::Graph>,
+        objects: &impl gix_object::Find,
+    ) -> Result<bool, Error> {
+        let cached = cache.and_then(|cache| cache.commit_by_id(info.id));
+        if let Some(filter) = cached.and_then(|commit| commit.bloom_filter()) {
+            let settings = *filter.settings();
+            let keys = match &self.keys {
+                Some((keys_settings, keys)) if *keys_settings == settings => keys,
+                _ => {
+                    let keys = self
+                        .paths
+                        .iter()
+                        .map(|path| bloom::Key::with_leading_dirs(path.as_ref(), &settings))
+                        .collect();
+                    &self.keys.insert((settings, keys)).1
+                }
+            };
+            if !keys.iter().any(|keys| filter.may_contain_all(keys)) {
+                return Ok(false);
+            }
+        }
+
+        let tree_id = match cached {
+            Some(commit) => commit.root_tree_id().to_owned(),
+            None => objects.find_commit_iter(&info.id, &mut self.buf)?.tree_id()?,
+        };
+        let parent_tree_id = match info.parent_ids.first() {
+            Some(parent_id) => Some(match cache.and_then(|cache| cache.commit_by_id(parent_id)) {
+                Some(parent) => parent.root_tree_id().to_owned(),
+                None => objects.find_commit_iter(parent_id, &mut self.buf)?.tree_id()?,
+            }),
+            None => None,
+        };
+        for path in &self.paths {
+            let entry = lookup_entry(objects, tree_id, path, &mut self.buf)?;
+            let parent_entry = match parent_tree_id {
+                Some(parent_tree_id) => lookup_entry(objects, parent_tree_id, path, &mut self.buf)?,
+                None => None,
+            };
+            if entry != parent_entry {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Return the id and mode of the entry at the slash-separated `path` in the tree with `tree_id`, if it exists.
+fn lookup_entry(
+    objects: &impl gix_object::Find,
+    tree_id: ObjectId,
+    path: &[u8],
+    buf: &mut Vec<u8>,
+) -> Result<Option<(ObjectId, gix_object::tree::EntryMode)>, Error> {
+    let mut id = tree_id;
+    let mut components = path.split(|b| *b == b'/').filter(|c| !c.is_empty()).peekable();
+    while let Some(component) = components.next() {
+        let mut entry = None;
+        for item in objects.find_tree_iter(&id, buf)? {
+            let item = item?;
+            if item.filename == component {
+                entry = Some((item.oid.to_owned(), item.mode));
+                break;
+            }
+        }
+        match entry {
+            Some((oid, mode)) if components.peek().is_none() => return Ok(Some((oid, mode))),
+            Some((oid, mode)) if mode.is_tree() => id = oid,
+            _ => return Ok(None),
+        }
+    }
+    Ok(Some((tree_id, gix_object::tree::EntryKind::Tree.into())))
+}
+
 fn collect_parents(
     dest: &mut SmallVec<[(gix_hash::ObjectId, gix_date::SecondsSinceUnixEpoch); 2]>,
     cache: Option<&gix_commitgraph::Graph>,
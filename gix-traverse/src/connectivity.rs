@@ -0,0 +1,148 @@
+//! Check that all objects referenced by commits exist, as needed when receiving packs, or to check repositories for consistency.
+use gix_hash::ObjectId;
+use gix_object::{FindExt, Kind};
+
+/// An object that is referenced, but doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Missing {
+    /// The id of the missing object.
+    pub id: ObjectId,
+    /// The kind of the missing object, as derived from the way it's referenced.
+    pub kind: Kind,
+    /// The commit or tree referring to the missing object, or `None` if it was one of the tips.
+    pub referenced_by: Option<ObjectId>,
+}
+
+/// The outcome of [`check()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of commits that were checked.
+    pub commits: usize,
+    /// The amount of distinct trees that were checked.
+    pub trees: usize,
+    /// The amount of distinct blobs whose existence was checked.
+    pub blobs: usize,
+    /// All objects that are referenced, but don't exist, in the order they were encountered.
+    pub missing: Vec<Missing>,
+}
+
+impl Outcome {
+    /// Return `true` if no object is missing.
+    pub fn is_connected(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// The error returned by [`check()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Walk(#[from] crate::commit::reachable::Error),
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    ObjectDecode(#[from] gix_object::decode::Error),
+}
+
+/// Check that all commits reachable from `tips` but not from `haves` exist, along with their trees and blobs, and collect
+/// all missing objects. Commits are found by walking the history [like `reachable_from()`](crate::commit::reachable_from()),
+/// and `cache` is used to accelerate the walk if set.
+///
+/// This is what `git` does after receiving a pack to assure the new references are connected to existing history,
+/// where `tips` are the new reference targets and `haves` are the existing ones.
+///
+/// Note that the trees and blobs of all checked commits are checked, even if they are also reachable from `haves`,
+/// but each tree and blob is only checked once. Submodule commits aren't checked.
+pub fn check(
+    tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+    haves: impl IntoIterator<Item = impl Into<ObjectId>>,
+    objects: &(impl gix_object::Find + gix_object::Exists),
+    cache: Option<&gix_commitgraph::Graph>,
+) -> Result<Outcome, Error> {
+    let mut out = Outcome::default();
+    let commits = crate::commit::reachable::function::walk(tips, haves, objects, cache, &mut |id, referenced_by| {
+        out.missing.push(Missing {
+            id,
+            kind: Kind::Commit,
+            referenced_by,
+        });
+        Ok(())
+    })?;
+    out.commits = commits.len();
+
+    let mut seen = gix_hashtable::HashSet::default();
+    let mut buf = Vec::new();
+    for commit in commits {
+        let tree = objects.find_commit_iter(&commit, &mut buf)?.tree_id()?;
+        check_trees(tree, Some(commit), objects, &mut seen, &mut buf, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Like [`check()`], but check that the tree `tree` exists along with all trees and blobs it references, as needed
+/// when references point to trees directly.
+pub fn check_tree(
+    tree: impl Into<ObjectId>,
+    objects: &(impl gix_object::Find + gix_object::Exists),
+) -> Result<Outcome, Error> {
+    let mut out = Outcome::default();
+    check_trees(
+        tree.into(),
+        None,
+        objects,
+        &mut gix_hashtable::HashSet::default(),
+        &mut Vec::new(),
+        &mut out,
+    )?;
+    Ok(out)
+}
+
+/// Check `tree`, which is referenced by `referenced_by`, and all trees and blobs it references unless they are in `seen`.
+fn check_trees(
+    tree: ObjectId,
+    referenced_by: Option<ObjectId>,
+    objects: &(impl gix_object::Find + gix_object::Exists),
+    seen: &mut gix_hashtable::HashSet<ObjectId>,
+    buf: &mut Vec<u8>,
+    out: &mut Outcome,
+) -> Result<(), Error> {
+    if !seen.insert(tree) {
+        return Ok(());
+    }
+    let mut trees = vec![(tree, referenced_by)];
+    while let Some((tree, parent)) = trees.pop() {
+        let entries = match objects.find_tree_iter(&tree, buf) {
+            Ok(entries) => entries,
+            Err(gix_object::find::existing_iter::Error::NotFound { .. }) => {
+                out.missing.push(Missing {
+                    id: tree,
+                    kind: Kind::Tree,
+                    referenced_by: parent,
+                });
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        out.trees += 1;
+        for entry in entries {
+            let entry = entry?;
+            if entry.mode.is_commit() || !seen.insert(entry.oid.to_owned()) {
+                continue;
+            }
+            if entry.mode.is_tree() {
+                trees.push((entry.oid.to_owned(), Some(tree)));
+            } else {
+                out.blobs += 1;
+                if !objects.exists(entry.oid) {
+                    out.missing.push(Missing {
+                        id: entry.oid.to_owned(),
+                        kind: Kind::Blob,
+                        referenced_by: Some(tree),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
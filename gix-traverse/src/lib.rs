@@ -4,5 +4,7 @@
 
 pub mod commit;
 
+pub mod connectivity;
+
 /// Tree traversal
 pub mod tree;
@@ -1,2 +1,3 @@
+pub(crate) mod reachable;
 mod simple;
 mod topo;
@@ -0,0 +1,88 @@
+use gix_hash::ObjectId;
+use gix_object::bstr::ByteSlice;
+use gix_traverse::commit::reachable_from;
+
+pub(crate) fn setup(repo_name: &str) -> crate::Result<(std::path::PathBuf, gix_odb::Handle)> {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_for_reachability.sh")?.join(repo_name);
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    Ok((dir, store))
+}
+
+pub(crate) fn id(dir: &std::path::Path, tag: &str) -> ObjectId {
+    let hex = std::fs::read(dir.join(".git").join("refs").join("tags").join(tag)).expect("tag exists");
+    crate::hex_to_id(hex.trim().to_str().expect("ascii"))
+}
+
+fn baseline(dir: &std::path::Path, name: &str) -> crate::Result<Vec<ObjectId>> {
+    let buf = std::fs::read(dir.join(format!("{name}.baseline")))?;
+    Ok(buf
+        .lines()
+        .map(|line| crate::hex_to_id(line.to_str().expect("ascii")))
+        .collect())
+}
+
+fn assert_reachable(tips: &[&str], haves: &[&str], baseline_name: &str) -> crate::Result {
+    for repo_name in ["base", "with-commit-graph"] {
+        let (dir, store) = setup(repo_name)?;
+        let cache = gix_commitgraph::at(store.store_ref().path().join("info")).ok();
+        assert_eq!(
+            cache.is_some(),
+            repo_name == "with-commit-graph",
+            "the commit-graph is only used where it exists"
+        );
+        let actual = reachable_from(
+            tips.iter().map(|tag| id(&dir, tag)),
+            haves.iter().map(|tag| id(&dir, tag)),
+            &store,
+            cache.as_ref(),
+        )?;
+        let mut expected = baseline(&dir, baseline_name)?;
+        assert_eq!(
+            actual.first(),
+            expected.first(),
+            "{repo_name}: the newest commit comes first"
+        );
+        let mut actual = actual;
+        actual.sort();
+        expected.sort();
+        assert_eq!(
+            actual, expected,
+            "{repo_name}: the same commits as `git rev-list` are returned"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn all_commits_without_haves() -> crate::Result {
+    assert_reachable(&["f"], &[], "all")
+}
+
+#[test]
+fn commits_not_reachable_from_haves() -> crate::Result {
+    assert_reachable(&["f"], &["d"], "main-not-feature")?;
+    assert_reachable(&["f", "d"], &["e"], "main-and-feature-not-e")
+}
+
+#[test]
+fn nothing_if_tips_are_reachable_from_haves() -> crate::Result {
+    assert_reachable(&["d"], &["f"], "feature-not-main")
+}
+
+#[test]
+fn missing_commits_are_an_error() -> crate::Result {
+    let (dir, store) = setup("missing-objects")?;
+    let err = reachable_from(Some(id(&dir, "f")), None::<ObjectId>, &store, None).unwrap_err();
+    assert!(
+        matches!(err, gix_traverse::commit::reachable::Error::NotFound { id: missing } if missing == id(&dir, "c")),
+        "{err:?}"
+    );
+
+    let commits = reachable_from(Some(id(&dir, "f")), Some(id(&dir, "merge")), &store, None)?;
+    assert_eq!(
+        commits,
+        [id(&dir, "f")],
+        "missing commits that are only reachable from haves are fine, just like in shallow repositories"
+    );
+    Ok(())
+}
@@ -420,3 +420,46 @@ mod adjusted_dates {
         .check()
     }
 }
+
+mod paths {
+    use gix_traverse::commit::Simple;
+
+    use crate::hex_to_id;
+
+    #[test]
+    fn only_commits_changing_the_paths_are_returned() -> crate::Result {
+        let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_for_path_filter.sh")?;
+        let store = gix_odb::at(dir.join(".git").join("objects"))?;
+        let head = {
+            let head = std::fs::read(dir.join(".git").join("refs").join("heads").join("main"))?;
+            hex_to_id(std::str::from_utf8(&head)?.trim())
+        };
+        for (paths, expected_file) in [
+            (&["top"][..], "top"),
+            (&["dir"], "dir"),
+            (&["dir/sub/file"], "dir-sub-file"),
+            (&["dir/other"], "dir-other"),
+            (&["does-not-exist"], "does-not-exist"),
+        ] {
+            let expected: Vec<_> = std::fs::read_to_string(dir.join(format!("expected-{expected_file}")))?
+                .lines()
+                .map(hex_to_id)
+                .collect();
+            for use_commitgraph in [false, true] {
+                let graph = use_commitgraph
+                    .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+                    .transpose()?;
+                let actual = Simple::new(Some(head), &store)
+                    .commit_graph(graph)
+                    .paths(paths.iter().copied())
+                    .map(|res| res.map(|info| info.id))
+                    .collect::<Result<Vec<_>, _>>()?;
+                assert_eq!(actual, expected, "{paths:?}, commitgraph: {use_commitgraph}");
+            }
+        }
+
+        let all = Simple::new(Some(head), &store).paths(["top", "dir/other"]).count();
+        assert_eq!(all, 5, "commits are returned if any of the paths changes");
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use gix_object::Kind;
+use gix_traverse::connectivity::{check, check_tree, Missing};
+
+use crate::commit::reachable::{id, setup};
+
+#[test]
+fn complete_history_is_connected() -> crate::Result {
+    for repo_name in ["base", "with-commit-graph"] {
+        let (dir, store) = setup(repo_name)?;
+        let cache = gix_commitgraph::at(store.store_ref().path().join("info")).ok();
+        let outcome = check(Some(id(&dir, "f")), None::<gix_hash::ObjectId>, &store, cache.as_ref())?;
+        assert!(outcome.is_connected());
+        assert_eq!(outcome.commits, 7);
+        assert_eq!(outcome.trees, 8, "one tree per commit, and one subtree");
+        assert_eq!(outcome.blobs, 6, "each blob is only checked once");
+
+        let outcome = check(Some(id(&dir, "f")), Some(id(&dir, "merge")), &store, cache.as_ref())?;
+        assert!(outcome.is_connected());
+        assert_eq!(outcome.commits, 1, "only new commits are checked");
+        assert_eq!(outcome.trees, 2);
+        assert_eq!(outcome.blobs, 6, "…but all of their objects");
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_objects_are_reported() -> crate::Result {
+    let (dir, store) = setup("missing-objects")?;
+    let outcome = check(Some(id(&dir, "f")), None::<gix_hash::ObjectId>, &store, None)?;
+    assert!(!outcome.is_connected());
+    let mut missing = outcome.missing;
+    missing.sort_by_key(|m| m.kind);
+    let tree_of = |tag: &str| -> crate::Result<gix_hash::ObjectId> {
+        let mut buf = Vec::new();
+        Ok(gix_object::FindExt::find_commit_iter(&store, &id(&dir, tag), &mut buf)?.tree_id()?)
+    };
+    assert_eq!(
+        missing,
+        [
+            Missing {
+                id: missing[0].id,
+                kind: Kind::Tree,
+                referenced_by: Some(tree_of("f")?),
+            },
+            Missing {
+                id: missing[1].id,
+                kind: Kind::Blob,
+                referenced_by: Some(tree_of("f")?),
+            },
+            Missing {
+                id: id(&dir, "c"),
+                kind: Kind::Commit,
+                referenced_by: Some(id(&dir, "d")),
+            },
+        ],
+        "objects are reported where they are first encountered"
+    );
+    Ok(())
+}
+
+#[test]
+fn trees_are_checked_with_everything_they_reference() -> crate::Result {
+    let mut buf = Vec::new();
+    let (dir, store) = setup("base")?;
+    let tree = gix_object::FindExt::find_commit_iter(&store, &id(&dir, "f"), &mut buf)?.tree_id()?;
+    let outcome = check_tree(tree, &store)?;
+    assert!(outcome.is_connected());
+    assert_eq!(outcome.commits, 0);
+    assert!(outcome.trees > 0 && outcome.blobs > 0);
+
+    let (dir, store) = setup("missing-objects")?;
+    let tree = gix_object::FindExt::find_commit_iter(&store, &id(&dir, "f"), &mut buf)?.tree_id()?;
+    let mut missing: Vec<_> = check_tree(tree, &store)?.missing.iter().map(|m| m.kind).collect();
+    missing.sort();
+    assert_eq!(
+        missing,
+        [Kind::Tree, Kind::Blob],
+        "missing trees and blobs are found even without commits"
+    );
+    Ok(())
+}
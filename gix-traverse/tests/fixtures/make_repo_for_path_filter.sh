@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+mkdir -p dir/sub
+echo 1 > top && echo 1 > dir/sub/file
+git add . && git commit -q -m "c1"
+
+echo 2 > top
+git commit -q -am "c2"
+
+echo 2 > dir/sub/file
+git commit -q -am "c3"
+
+echo 1 > dir/other
+git add . && git commit -q -m "c4"
+
+chmod +x top
+git commit -q -am "c5 (mode change)"
+
+git rm -q top
+git commit -q -m "c6 (removal)"
+
+for n in $(seq 5); do
+  echo "$n" > "unrelated$n"
+  git add . && git commit -q -m "unrelated $n"
+done
+
+git commit-graph write --no-progress --reachable --changed-paths
+
+for path in top dir dir/sub/file dir/other does-not-exist; do
+  git log --format=%H -- "$path" > "expected-$(echo "$path" | tr / -)"
+done
@@ -0,0 +1,60 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit () {
+  tick
+  echo "$1" > "$1"
+  git add "$1"
+  git commit -q -m "$1"
+  git tag "$1"
+}
+
+function path_of () {
+  local id
+  id=$(git rev-parse "$1")
+  echo ".git/objects/${id:0:2}/${id:2}"
+}
+
+git init -q base
+(cd base
+  git checkout -q -b main
+  commit a
+  commit b
+  git checkout -q -b feature
+  commit c
+  commit d
+  git checkout -q main
+  commit e
+  tick
+  git merge -q --no-ff -m merge feature
+  git tag merge
+  mkdir dir && echo f > dir/f && git add dir
+  commit f
+
+  git rev-list main -- > all.baseline
+  git rev-list main --not feature -- > main-not-feature.baseline
+  git rev-list main feature --not e -- > main-and-feature-not-e.baseline
+  git rev-list feature --not main -- > feature-not-main.baseline
+)
+
+cp -R base with-commit-graph
+(cd with-commit-graph
+  git commit-graph write --no-progress --reachable
+)
+
+cp -R base missing-objects
+(cd missing-objects
+  rm "$(path_of c)" "$(path_of f:dir)" "$(path_of d:d)"
+)
@@ -10,7 +10,7 @@
 
 use std::{borrow::Cow, path::PathBuf};
 
-use bstr::{BStr, BString};
+use bstr::{BStr, BString, ByteSlice};
 
 ///
 #[allow(clippy::empty_docs)]
@@ -113,6 +113,9 @@ pub struct Url {
     ///
     /// If this value is ever going to be passed to a command-line application, call [Self::path_argument_safe()] instead.
     pub path: BString,
+    /// If `true`, `path` was created from a file path and is percent-encoded only when serializing.
+    #[cfg_attr(feature = "serde", serde(default))]
+    percent_encode_path: bool,
 }
 
 /// Instantiation
@@ -136,6 +139,7 @@ impl Url {
                 port,
                 path,
                 serialize_alternative_form,
+                percent_encode_path: false,
             }
             .to_bstring()
             .as_ref(),
@@ -143,6 +147,51 @@ impl Url {
     }
 }
 
+/// Instantiation
+impl Url {
+    /// Create a file URL from the platform-specific `path`, which is the inverse of [`to_file_path()`](Self::to_file_path()).
+    ///
+    /// Absolute paths become `file://` URLs whose [`path`](Self::path) is kept as is, but `%` is percent-encoded when
+    /// [serializing](Self::to_bstring()) them, and UNC paths like `\\server\share\repo`
+    /// turn into `file://server/share/repo` on Windows. Drive-letter paths like `C:\repo` are stored with slashes as `C:/repo`.
+    /// Relative paths are kept as they are and serialize without a scheme, as they can't be represented as `file://` URL.
+    pub fn from_file_path(path: &std::path::Path) -> Self {
+        let is_absolute = path.is_absolute();
+        let path = gix_path::to_unix_separators_on_windows(gix_path::into_bstr(path));
+        let mut url = Url {
+            serialize_alternative_form: !is_absolute,
+            scheme: Scheme::File,
+            user: None,
+            password: None,
+            host: None,
+            port: None,
+            path: path.as_ref().to_owned(),
+            percent_encode_path: false,
+        };
+        if !is_absolute {
+            return url;
+        }
+
+        let mut path: &[u8] = path.as_ref();
+        if cfg!(windows) {
+            let unc = if let Some(verbatim) = path.strip_prefix(b"//?/") {
+                path = verbatim;
+                verbatim.strip_prefix(b"UNC/")
+            } else {
+                path.strip_prefix(b"//")
+            };
+            if let Some(unc) = unc {
+                let host_end = unc.find_byte(b'/').unwrap_or(unc.len());
+                url.host = Some(unc[..host_end].to_str_lossy().into_owned());
+                path = &unc[host_end..];
+            }
+        }
+        url.path = path.into();
+        url.percent_encode_path = true;
+        url
+    }
+}
+
 /// Modification
 impl Url {
     /// Set the given `user`, or unset it with `None`. Return the previous value.
@@ -299,6 +348,42 @@ fn looks_like_command_line_option(b: &[u8]) -> bool {
     b.first() == Some(&b'-')
 }
 
+/// Encode `%` so that decoding the result yields `path` again.
+fn percent_encode(path: &BStr) -> BString {
+    let mut out = BString::from(Vec::with_capacity(path.len()));
+    for b in path.iter() {
+        match b {
+            b'%' => out.extend_from_slice(b"%25"),
+            b => out.push(*b),
+        }
+    }
+    out
+}
+
+/// Decode all valid `%XX` sequences in `path`, and keep everything else as is.
+fn percent_decode(path: &BStr) -> BString {
+    let hex = |b: u8| char::from(b).to_digit(16).map(|d| d as u8);
+    let mut out = BString::from(Vec::with_capacity(path.len()));
+    let mut rest = path.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        match (
+            b,
+            tail.first().copied().and_then(hex),
+            tail.get(1).copied().and_then(hex),
+        ) {
+            (b'%', Some(high), Some(low)) => {
+                out.push(high << 4 | low);
+                rest = &tail[2..];
+            }
+            _ => {
+                out.push(b);
+                rest = tail;
+            }
+        }
+    }
+    out
+}
+
 /// Transformation
 impl Url {
     /// Turn a file URL like `file://relative` into `file:///root/relative`, hence it assures the URL's path component is absolute, using
@@ -310,6 +395,41 @@ impl Url {
     }
 }
 
+/// Conversion
+impl Url {
+    /// Convert this file URL into a path to be used on the current platform, or return `None` if this isn't a
+    /// [file URL](Scheme::File) or if its path can't be represented on this platform.
+    ///
+    /// The path of `file://` URLs is percent-decoded, whereas paths that were parsed as such are used verbatim.
+    /// On Windows, a host is turned into a UNC path like `\\server\share\repo`, a leading slash before a drive letter
+    /// like in `file:///C:/repo` is removed, and slashes are turned into backslashes. Elsewhere the host is ignored,
+    /// just like `git` does.
+    pub fn to_file_path(&self) -> Option<PathBuf> {
+        if self.scheme != Scheme::File {
+            return None;
+        }
+        let mut path = if self.serialize_alternative_form || self.percent_encode_path {
+            self.path.clone()
+        } else {
+            percent_decode(self.path.as_ref())
+        };
+        if cfg!(windows) {
+            if path.len() > 2 && path[0] == b'/' && path[1].is_ascii_alphabetic() && path[2] == b':' {
+                path.remove(0);
+            }
+            if let Some(host) = self.host().filter(|host| !host.is_empty()) {
+                let mut unc = BString::from(format!("//{host}"));
+                if path.first() != Some(&b'/') {
+                    unc.push(b'/');
+                }
+                unc.extend_from_slice(&path);
+                path = unc;
+            }
+        }
+        gix_path::try_from_bstring(gix_path::to_native_separators(path).into_owned()).ok()
+    }
+}
+
 /// Serialization
 impl Url {
     /// Write this URL losslessly to `out`, ready to be parsed again.
@@ -340,7 +460,11 @@ impl Url {
         if self.serialize_alternative_form && self.scheme == Scheme::Ssh {
             out.write_all(b":")?;
         }
-        out.write_all(&self.path)?;
+        if self.percent_encode_path {
+            out.write_all(&percent_encode(self.path.as_ref()))?;
+        } else {
+            out.write_all(&self.path)?;
+        }
         Ok(())
     }
 
@@ -401,6 +525,7 @@ pub mod testing {
                 port,
                 path,
                 serialize_alternative_form,
+                percent_encode_path: false,
             }
         }
     }
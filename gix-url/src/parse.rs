@@ -119,6 +119,7 @@ pub(crate) fn url(input: &BStr, protocol_end: usize) -> Result<crate::Url, Error
         host: url.host_str().map(Into::into),
         port: url.port(),
         path: url.path().into(),
+        percent_encode_path: false,
     })
 }
 
@@ -155,6 +156,7 @@ pub(crate) fn scp(input: &BStr, colon: usize) -> Result<crate::Url, Error> {
         host: url.host_str().map(Into::into),
         port: url.port(),
         path: path.into(),
+        percent_encode_path: false,
     })
 }
 
@@ -240,6 +242,7 @@ pub(crate) fn local(input: &BStr) -> Result<crate::Url, Error> {
         host: None,
         port: None,
         path: input.to_owned(),
+        percent_encode_path: false,
     })
 }
 
@@ -1,3 +1,97 @@
+mod file_path {
+    use std::path::Path;
+
+    #[test]
+    fn non_file_scheme_has_no_path() -> crate::Result {
+        let url = gix_url::parse("https://github.com/byron/gitoxide".into())?;
+        assert_eq!(url.to_file_path(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn relative_paths_are_kept_verbatim() -> crate::Result {
+        let url = gix_url::Url::from_file_path(Path::new("relative/100%"));
+        assert_eq!(url.to_bstring(), "relative/100%");
+        assert_eq!(url.to_file_path().as_deref(), Some(Path::new("relative/100%")));
+        assert_eq!(
+            gix_url::parse("relative/%20".into())?.to_file_path().as_deref(),
+            Some(Path::new("relative/%20")),
+            "paths without scheme are never decoded"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn absolute_paths_roundtrip_with_percent_encoding() -> crate::Result {
+        let path = Path::new("/dir with space/100%/repo");
+        let url = gix_url::Url::from_file_path(path);
+        assert_eq!(url.path, "/dir with space/100%/repo", "the path is kept as is…");
+        assert_eq!(
+            url.to_bstring(),
+            "file:///dir with space/100%25/repo",
+            "…and only encoded when serializing"
+        );
+        assert_eq!(url.to_file_path().as_deref(), Some(path));
+        assert_eq!(
+            gix_url::parse(url.to_bstring().as_ref())?.to_file_path().as_deref(),
+            Some(path)
+        );
+
+        let url = gix_url::parse("file:///dir%20with%20space/r%C3%A9po%zz".into())?;
+        assert_eq!(
+            url.to_file_path().as_deref(),
+            Some(Path::new("/dir with space/répo%zz")),
+            "invalid escapes are kept"
+        );
+
+        let url = gix_url::parse("file://server/share/repo".into())?;
+        assert_eq!(
+            url.to_file_path().as_deref(),
+            Some(Path::new("/share/repo")),
+            "like git, the host is ignored"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn drive_letter_paths_roundtrip() -> crate::Result {
+        let path = Path::new(r"C:\dir with space\100%\repo");
+        let url = gix_url::Url::from_file_path(path);
+        assert_eq!(url.path, "C:/dir with space/100%/repo");
+        assert_eq!(url.to_bstring(), "file://C:/dir with space/100%25/repo");
+        assert_eq!(url.to_file_path().as_deref(), Some(path));
+        assert_eq!(
+            gix_url::parse(url.to_bstring().as_ref())?.to_file_path().as_deref(),
+            Some(path)
+        );
+
+        for input in ["file:///C:/dir%20with%20space/repo", r"file://C:\dir with space\repo"] {
+            assert_eq!(
+                gix_url::parse(input.into())?.to_file_path().as_deref(),
+                Some(Path::new(r"C:\dir with space\repo")),
+                "{input}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn unc_paths_roundtrip() -> crate::Result {
+        for input in [r"\\server\share\repo", r"\\?\UNC\server\share\repo"] {
+            let url = gix_url::Url::from_file_path(Path::new(input));
+            assert_eq!(url.host(), Some("server"));
+            assert_eq!(url.to_bstring(), "file://server/share/repo");
+            assert_eq!(url.to_file_path().as_deref(), Some(Path::new(r"\\server\share\repo")));
+        }
+        let url = gix_url::parse("file://server/share/repo".into())?;
+        assert_eq!(url.to_file_path().as_deref(), Some(Path::new(r"\\server\share\repo")));
+        Ok(())
+    }
+}
+
 mod canonicalized {
     use std::borrow::Cow;
 
@@ -1,7 +1,12 @@
 use std::time::Duration;
 
 fn randomize(backoff_ms: usize) -> usize {
-    let new_value = (fastrand::usize(750..=1250) * backoff_ms) / 1000;
+    randomize_by_percent(backoff_ms, 25)
+}
+
+fn randomize_by_percent(backoff_ms: usize, percent: usize) -> usize {
+    let percent = percent.min(100);
+    let new_value = (fastrand::usize(1000 - percent * 10..=1000 + percent * 10) * backoff_ms) / 1000;
     if new_value == 0 {
         backoff_ms
     } else {
@@ -9,6 +14,14 @@ fn randomize(backoff_ms: usize) -> usize {
     }
 }
 
+/// Return a function suitable as transform for [`Exponential::new()`] which randomly deviates each step by up to
+/// `percent` in either direction, with `percent` being clamped to 100.
+///
+/// This avoids multiple contending parties to retry in lockstep.
+pub fn jitter(percent: u8) -> impl Fn(usize) -> usize + Copy + Send + Sync {
+    move |backoff_ms| randomize_by_percent(backoff_ms, percent.into())
+}
+
 /// A utility to calculate steps for exponential backoff similar to how it's done in `git`.
 pub struct Exponential<Fn> {
     multiplier: usize,
@@ -44,6 +57,17 @@ impl<Transform> Exponential<Transform>
 where
     Transform: Fn(usize) -> usize,
 {
+    /// Create a new exponential backoff iterator whose steps never exceed `max_step`, with each step in milliseconds being
+    /// passed through `transform` before being returned, for example to add [`jitter()`].
+    pub fn new(max_step: Duration, transform: Transform) -> Self {
+        Exponential {
+            multiplier: 1,
+            max_multiplier: usize::try_from(max_step.as_millis()).unwrap_or(usize::MAX).max(1),
+            exponent: 1,
+            transform,
+        }
+    }
+
     /// Return an iterator that yields `Duration` instances to sleep on until `time` is depleted.
     pub fn until_no_remaining(&mut self, time: Duration) -> impl Iterator<Item = Duration> + '_ {
         let mut elapsed = Duration::default();
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use gix_utils::backoff::Exponential;
+use gix_utils::backoff::{jitter, Exponential};
 
 const EXPECTED_TILL_SECOND: &[usize] = &[
     1usize, 4, 9, 16, 25, 36, 49, 64, 81, 100, 121, 144, 169, 196, 225, 256, 289, 324, 361, 400, 441, 484, 529, 576,
@@ -54,3 +54,24 @@ fn output_with_default_settings() {
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn custom_max_step_and_jitter() {
+    let max_step = Duration::from_millis(100);
+    let steps: Vec<_> = Exponential::new(max_step, jitter(0)).take(12).collect();
+    assert_eq!(
+        steps,
+        [1, 4, 9, 16, 25, 36, 49, 64, 81, 100, 100, 100]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>(),
+        "without jitter, steps are capped at the maximum step"
+    );
+
+    for step in Exponential::new(max_step, jitter(50)).skip(10).take(100) {
+        assert!(
+            (50..=150).contains(&step.as_millis()),
+            "{step:?} deviates no more than the configured percentage"
+        );
+    }
+}
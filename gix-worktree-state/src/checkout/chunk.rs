@@ -85,6 +85,7 @@ pub struct Options {
     pub overwrite_existing: bool,
     pub keep_going: bool,
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    pub large_file_threshold_bytes: u64,
 }
 
 impl From<&checkout::Options> for Options {
@@ -95,6 +96,7 @@ impl From<&checkout::Options> for Options {
             overwrite_existing: opts.overwrite_existing,
             keep_going: opts.keep_going,
             filter_process_delay: opts.filter_process_delay,
+            large_file_threshold_bytes: opts.large_file_threshold_bytes,
         }
     }
 }
@@ -107,7 +109,7 @@ pub fn process<'entry, Find>(
     ctx: &mut Context<Find>,
 ) -> Result<Outcome<'entry>, checkout::Error>
 where
-    Find: gix_object::Find + Clone,
+    Find: gix_object::FindObjectOrHeader + Clone,
 {
     let mut delayed_symlinks = Vec::new();
     let mut collisions = Vec::new();
@@ -162,7 +164,7 @@ pub fn process_delayed_filter_results<Find>(
     ctx: &mut Context<Find>,
 ) -> Result<(), checkout::Error>
 where
-    Find: gix_object::Find + Clone,
+    Find: gix_object::FindObjectOrHeader + Clone,
 {
     let Options {
         destination_is_initially_empty,
@@ -295,7 +297,7 @@ pub fn checkout_entry_handle_result<'entry, Find>(
     }: &mut Context<Find>,
 ) -> Result<entry::Outcome<'entry>, checkout::Error>
 where
-    Find: gix_object::Find + Clone,
+    Find: gix_object::FindObjectOrHeader + Clone,
 {
     let res = entry::checkout(
         entry,
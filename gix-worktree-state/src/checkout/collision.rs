@@ -0,0 +1,102 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+use crate::checkout::{CollidingEntry, CollisionPolicy, PathCollision};
+
+/// Find all paths in `entry_paths`, which are expected in index order, that would be checked out to the same location on
+/// disk as a previous path according to `fs`, and decide how to check them out according to `policy`.
+///
+/// A path collides if the path of its file, or one of its leading directories, is the same as the one of a previous path
+/// once case is ignored or unicode is normalized.
+pub(crate) fn detect<'a>(
+    entry_paths: impl IntoIterator<Item = &'a BStr>,
+    fs: gix_fs::Capabilities,
+    policy: CollisionPolicy,
+) -> Vec<PathCollision> {
+    let mut out = Vec::<PathCollision>::new();
+    if policy == CollisionPolicy::DetectOnWrite || !(fs.ignore_case || fs.precompose_unicode) {
+        return out;
+    }
+    // normalized path -> the path that claimed its location on disk
+    let mut claims = HashMap::<BString, BString>::new();
+    // colliding path -> (replacement, the path it collided with)
+    let mut renames = HashMap::<BString, (BString, BString)>::new();
+    let mut collision_by_path = HashMap::<BString, usize>::new();
+
+    for entry_path in entry_paths {
+        let mut path = BString::default();
+        let mut collided_with = None;
+        for component in entry_path.split_str("/") {
+            if !path.is_empty() {
+                path.push_byte(b'/');
+            }
+            path.push_str(component);
+            if let Some((replacement, claimant)) = renames.get(&path) {
+                collided_with.get_or_insert_with(|| claimant.clone());
+                path = replacement.clone();
+                continue;
+            }
+            let key = normalize(path.as_bstr(), fs).into_owned();
+            match claims.get(&key) {
+                None => {
+                    claims.insert(key, path.clone());
+                }
+                Some(claimant) if *claimant == path => {}
+                Some(claimant) => {
+                    let claimant = claimant.clone();
+                    collided_with.get_or_insert_with(|| claimant.clone());
+                    if policy != CollisionPolicy::RenameWithSuffix {
+                        break;
+                    }
+                    let replacement = (1..)
+                        .map(|n| {
+                            let mut candidate = path.clone();
+                            candidate.push_str(format!("~{n}"));
+                            candidate
+                        })
+                        .find(|candidate| !claims.contains_key(normalize(candidate.as_bstr(), fs).as_ref()))
+                        .expect("there are enough numbers");
+                    claims.insert(normalize(replacement.as_bstr(), fs).into_owned(), replacement.clone());
+                    renames.insert(
+                        std::mem::replace(&mut path, replacement.clone()),
+                        (replacement, claimant),
+                    );
+                }
+            }
+        }
+
+        if let Some(claimant) = collided_with {
+            let idx = *collision_by_path.entry(claimant.clone()).or_insert_with(|| {
+                out.push(PathCollision {
+                    path: claimant,
+                    colliding: Vec::new(),
+                });
+                out.len() - 1
+            });
+            out[idx].colliding.push(CollidingEntry {
+                path: entry_path.to_owned(),
+                checked_out_to: (policy == CollisionPolicy::RenameWithSuffix).then_some(path),
+            });
+        }
+    }
+    out
+}
+
+/// Return `path` in the form the filesystem described by `fs` would use to identify it, or `path` itself if it isn't valid UTF-8.
+fn normalize(path: &BStr, fs: gix_fs::Capabilities) -> Cow<'_, BStr> {
+    let Ok(path_str) = path.to_str() else {
+        return Cow::Borrowed(path);
+    };
+    let mut normalized = Cow::Borrowed(path_str);
+    if fs.precompose_unicode {
+        normalized = gix_utils::str::precompose(normalized);
+    }
+    if fs.ignore_case && normalized.chars().any(char::is_uppercase) {
+        normalized = Cow::Owned(normalized.to_lowercase());
+    }
+    match normalized {
+        Cow::Borrowed(_) => Cow::Borrowed(path),
+        Cow::Owned(normalized) => Cow::Owned(normalized.into()),
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     fs::OpenOptions,
-    io::Write,
+    io::{Read, Write},
     path::{Path, PathBuf},
 };
 
@@ -71,11 +71,12 @@ pub fn checkout<'entry, Find>(
         destination_is_initially_empty,
         overwrite_existing,
         filter_process_delay,
+        large_file_threshold_bytes,
         ..
     }: crate::checkout::chunk::Options,
 ) -> Result<Outcome<'entry>, crate::checkout::Error>
 where
-    Find: gix_object::Find,
+    Find: gix_object::FindObjectOrHeader,
 {
     let dest_relative = gix_path::try_from_bstr(entry_path).map_err(|_| crate::checkout::Error::IllformedUtf8 {
         path: entry_path.to_owned(),
@@ -85,15 +86,47 @@ where
 
     let object_size = match entry.mode {
         gix_index::entry::Mode::FILE | gix_index::entry::Mode::FILE_EXECUTABLE => {
-            let obj = (*objects)
+            let is_large = large_file_threshold_bytes != 0
+                && objects
+                    .try_header(&entry.id)
+                    .map_err(|err| crate::checkout::Error::Find {
+                        err: gix_object::find::existing_object::Error::Find(err),
+                        path: dest.to_path_buf(),
+                    })?
+                    .map_or(false, |header| header.size > large_file_threshold_bytes);
+            if is_large
+                && !filters
+                    .may_convert_to_worktree(entry_path, &mut |_, attrs| {
+                        path_cache.matching_attributes(attrs);
+                    })
+                    .map_err(gix_filter::pipeline::convert::to_worktree::Error::from)?
+            {
+                let mut stream =
+                    find_blob_stream(&*objects, &entry.id).map_err(|err| crate::checkout::Error::Find {
+                        err,
+                        path: dest.to_path_buf(),
+                    })?;
+                let (mut file, set_executable_after_creation) = open_file(
+                    dest,
+                    destination_is_initially_empty,
+                    overwrite_existing,
+                    executable_bit,
+                    entry.mode,
+                )?;
+                let num_bytes = std::io::copy(&mut stream, &mut file)? as usize;
+                finalize_entry(entry, file, set_executable_after_creation.then_some(dest))?;
+                return Ok(Outcome::Written { bytes: num_bytes });
+            }
+            let data = (*objects)
                 .find_blob(&entry.id, buf)
                 .map_err(|err| crate::checkout::Error::Find {
                     err,
                     path: dest.to_path_buf(),
-                })?;
+                })?
+                .data;
 
             let filtered = filters.convert_to_worktree(
-                obj.data,
+                data,
                 entry_path,
                 &mut |_, attrs| {
                     path_cache.matching_attributes(attrs);
@@ -148,11 +181,20 @@ where
             let symlink_destination = gix_path::try_from_byte_slice(obj.data)
                 .map_err(|_| crate::checkout::Error::IllformedUtf8 { path: obj.data.into() })?;
 
-            if symlink {
-                try_op_or_unlink(dest, overwrite_existing, |p| {
+            let created_symlink = symlink
+                && match try_op_or_unlink(dest, overwrite_existing, |p| {
                     gix_fs::symlink::create(symlink_destination, p)
-                })?;
-            } else {
+                }) {
+                    Ok(()) => true,
+                    Err(err) if gix_fs::symlink::is_unsupported_error(&err) => {
+                        gix_features::trace::debug!(
+                            "Could not create symlink at '{entry_path}', writing it as file instead: {err}"
+                        );
+                        false
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+            if !created_symlink {
                 let mut file = try_op_or_unlink(dest, overwrite_existing, |p| {
                     open_options(p, destination_is_initially_empty, overwrite_existing).open(dest)
                 })?;
@@ -182,6 +224,28 @@ where
     Ok(Outcome::Written { bytes: object_size })
 }
 
+/// Obtain a stream of the data of the blob at `id`, which is expected to be large.
+fn find_blob_stream<'a, Find>(
+    objects: &'a Find,
+    id: &gix_hash::oid,
+) -> Result<Box<dyn Read + 'a>, gix_object::find::existing_object::Error>
+where
+    Find: gix_object::Find,
+{
+    let (header, stream) = objects
+        .try_find_stream(id)
+        .map_err(gix_object::find::existing_object::Error::Find)?
+        .ok_or_else(|| gix_object::find::existing_object::Error::NotFound { oid: id.to_owned() })?;
+    if header.kind != gix_object::Kind::Blob {
+        return Err(gix_object::find::existing_object::Error::ObjectKind {
+            oid: id.to_owned(),
+            actual: header.kind,
+            expected: gix_object::Kind::Blob,
+        });
+    }
+    Ok(stream)
+}
+
 /// Note that this works only because we assume to not race ourselves when symlinks are involved, and we do this by
 /// delaying symlink creation to the end and will always do that sequentially.
 /// It's still possible to fall for a race if other actors create symlinks in our path, but that's nothing to defend against.
@@ -237,6 +301,7 @@ fn open_options(path: &Path, destination_is_initially_empty: bool, overwrite_exi
     options
         .create_new(destination_is_initially_empty && !overwrite_existing)
         .create(!destination_is_initially_empty || overwrite_existing)
+        .truncate(!destination_is_initially_empty || overwrite_existing)
         .write(true);
     options
 }
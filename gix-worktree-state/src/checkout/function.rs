@@ -1,9 +1,12 @@
 use std::sync::atomic::AtomicBool;
 
+use bstr::{BStr, BString, ByteSlice};
 use gix_features::{interrupt, parallel::in_parallel_with_finalize};
+use gix_filter::{driver::apply::MaybeDelayed, pipeline::convert::ToWorktreeOutcome};
+use gix_object::FindExt;
 use gix_worktree::{stack, Stack};
 
-use crate::checkout::chunk;
+use crate::checkout::{chunk, collision, entry, CollisionPolicy};
 
 /// Checkout the entire `index` into `dir`, and resolve objects found in index entries with `objects` to write their content to their
 /// respective path in `dir`.
@@ -26,18 +29,191 @@ pub fn checkout<Find>(
     options: crate::checkout::Options,
 ) -> Result<crate::checkout::Outcome, crate::checkout::Error>
 where
-    Find: gix_object::Find + Send + Clone,
+    Find: gix_object::FindObjectOrHeader + Send + Clone,
 {
+    let _span = gix_features::trace::coarse!("gix_worktree_state::checkout()");
     let paths = index.take_path_backing();
-    let res = checkout_inner(index, &paths, dir, objects, files, bytes, should_interrupt, options);
+    let num_entries = index.entries().len();
+    let res = checkout_inner(
+        index,
+        &paths,
+        &|_| true,
+        num_entries,
+        dir,
+        objects,
+        files,
+        bytes,
+        should_interrupt,
+        options,
+    );
     index.return_path_backing(paths);
     res
 }
 
+/// Like [`checkout()`], but only checkout the entries of `index` at `rela_paths`, or below them if they are directories,
+/// similar to `git checkout -- <path>…`. All other entries are left untouched, both in `index` and in `dir`.
+///
+/// It's an error if one of `rela_paths` doesn't match any entry, or if it matches an unmerged entry.
+/// As `dir` typically is an existing worktree, `options.destination_is_initially_empty` should be `false`, and
+/// `options.overwrite_existing` should be `true` to replace local modifications.
+#[allow(clippy::too_many_arguments)]
+pub fn checkout_paths<Find>(
+    index: &mut gix_index::State,
+    rela_paths: impl IntoIterator<Item = impl AsRef<BStr>>,
+    dir: impl Into<std::path::PathBuf>,
+    objects: Find,
+    files: &dyn gix_features::progress::Count,
+    bytes: &dyn gix_features::progress::Count,
+    should_interrupt: &AtomicBool,
+    options: crate::checkout::Options,
+) -> Result<crate::checkout::Outcome, crate::checkout::Error>
+where
+    Find: gix_object::FindObjectOrHeader + Send + Clone,
+{
+    let _span = gix_features::trace::coarse!("gix_worktree_state::checkout_paths()");
+    let rela_paths: Vec<BString> = rela_paths
+        .into_iter()
+        .map(|path| path.as_ref().trim_end_with(|c| c == '/').as_bstr().to_owned())
+        .collect();
+    let is_selected = |entry_path: &BStr| {
+        rela_paths
+            .iter()
+            .any(|rela_path| is_at_or_below(entry_path, rela_path.as_ref()))
+    };
+
+    let paths = index.take_path_backing();
+    let res = selected_entries(index, &paths, &rela_paths).and_then(|num_entries| {
+        checkout_inner(
+            index,
+            &paths,
+            &is_selected,
+            num_entries,
+            dir,
+            objects,
+            files,
+            bytes,
+            should_interrupt,
+            options,
+        )
+    });
+    index.return_path_backing(paths);
+    res
+}
+
+/// Write the blob with `id` that is stored at `rela_path` with `mode` to `out` as it would be checked out, which means
+/// it passes through `filters` for conversion to the worktree representation unless it's a symbolic link, similar
+/// to `git cat-file --filters`. `id` may be obtained from an index entry or a tree, and `objects` is used to find it.
+///
+/// `attributes` is used to configure the filters and should be setup for attribute queries only, as nothing
+/// is written to the worktree.
+///
+/// Return the amount of bytes written to `out`.
+pub fn write_blob_to<Find>(
+    id: &gix_hash::oid,
+    mode: gix_index::entry::Mode,
+    rela_path: &BStr,
+    attributes: &mut Stack,
+    filters: &mut gix_filter::Pipeline,
+    objects: &Find,
+    out: &mut dyn std::io::Write,
+) -> Result<u64, crate::checkout::Error>
+where
+    Find: gix_object::FindObjectOrHeader,
+{
+    let mut buf = Vec::new();
+    let find_blob = |buf| {
+        objects.find_blob(id, buf).map_err(|err| crate::checkout::Error::Find {
+            err,
+            path: gix_path::from_bstr(rela_path).into_owned(),
+        })
+    };
+    Ok(match mode {
+        gix_index::entry::Mode::FILE | gix_index::entry::Mode::FILE_EXECUTABLE => {
+            let blob = find_blob(&mut buf)?;
+            let platform = attributes.at_entry(rela_path, Some(mode), objects)?;
+            match filters.convert_to_worktree(
+                blob.data,
+                rela_path,
+                &mut |_, attrs| {
+                    platform.matching_attributes(attrs);
+                },
+                gix_filter::driver::apply::Delay::Forbid,
+            )? {
+                ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
+                    out.write_all(buf)?;
+                    buf.len() as u64
+                }
+                ToWorktreeOutcome::Process(MaybeDelayed::Immediate(mut filtered)) => std::io::copy(&mut filtered, out)?,
+                ToWorktreeOutcome::Process(MaybeDelayed::Delayed(_)) => {
+                    unreachable!("we forbid delaying the output of filter processes")
+                }
+            }
+        }
+        gix_index::entry::Mode::SYMLINK => {
+            let blob = find_blob(&mut buf)?;
+            out.write_all(blob.data)?;
+            blob.data.len() as u64
+        }
+        _ => {
+            return Err(crate::checkout::Error::NotABlob {
+                rela_path: rela_path.to_owned(),
+                mode,
+            })
+        }
+    })
+}
+
+/// Return the amount of entries in `index` that match `rela_paths`, assuring that each path matches at least one
+/// entry and that none of the matched entries is unmerged.
+fn selected_entries(
+    index: &gix_index::State,
+    paths: &gix_index::PathStorageRef,
+    rela_paths: &[BString],
+) -> Result<usize, crate::checkout::Error> {
+    let mut is_matched = vec![false; rela_paths.len()];
+    let mut num_entries = 0;
+    for entry in index.entries() {
+        let entry_path = entry.path_in(paths);
+        let mut is_selected = false;
+        for (rela_path, is_matched) in rela_paths.iter().zip(is_matched.iter_mut()) {
+            if is_at_or_below(entry_path, rela_path.as_ref()) {
+                *is_matched = true;
+                is_selected = true;
+            }
+        }
+        if !is_selected {
+            continue;
+        }
+        if entry.stage() != gix_index::entry::Stage::Unconflicted {
+            return Err(crate::checkout::Error::Unmerged {
+                rela_path: entry_path.to_owned(),
+            });
+        }
+        num_entries += 1;
+    }
+    match rela_paths.iter().zip(is_matched).find(|(_, is_matched)| !is_matched) {
+        Some((rela_path, _)) => Err(crate::checkout::Error::PathNotFound {
+            rela_path: rela_path.clone(),
+        }),
+        None => Ok(num_entries),
+    }
+}
+
+/// Return `true` if `entry_path` is `rela_path` or inside of it, if it is a directory, with the empty path matching everything.
+fn is_at_or_below(entry_path: &BStr, rela_path: &BStr) -> bool {
+    rela_path.is_empty()
+        || matches!(
+            entry_path.strip_prefix(rela_path.as_bytes()),
+            Some(rest) if rest.is_empty() || rest[0] == b'/'
+        )
+}
+
 #[allow(clippy::too_many_arguments)]
 fn checkout_inner<Find>(
     index: &mut gix_index::State,
     paths: &gix_index::PathStorage,
+    is_selected: &(dyn Fn(&BStr) -> bool + Sync),
+    num_entries: usize,
     dir: impl Into<std::path::PathBuf>,
     objects: Find,
     files: &dyn gix_features::progress::Count,
@@ -46,14 +222,48 @@ fn checkout_inner<Find>(
     mut options: crate::checkout::Options,
 ) -> Result<crate::checkout::Outcome, crate::checkout::Error>
 where
-    Find: gix_object::Find + Send + Clone,
+    Find: gix_object::FindObjectOrHeader + Send + Clone,
 {
     let num_files = files.counter();
     let num_bytes = bytes.counter();
     let dir = dir.into();
+
+    let path_collisions = collision::detect(
+        index
+            .entries()
+            .iter()
+            .filter(|entry| !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE))
+            .map(|entry| entry.path_in(paths))
+            .filter(|entry_path| is_selected(entry_path)),
+        options.fs,
+        options.collision_policy,
+    );
+    if options.collision_policy == CollisionPolicy::Error && !path_collisions.is_empty() {
+        return Err(crate::checkout::Error::PathCollisions {
+            collisions: path_collisions,
+        });
+    }
+    let mut colliding: Vec<_> = path_collisions
+        .iter()
+        .flat_map(|collision| collision.colliding.iter())
+        .map(|entry| {
+            (
+                entry.path.as_bstr(),
+                entry.checked_out_to.as_ref().map(|path| path.as_bstr()),
+            )
+        })
+        .collect();
+    colliding.sort_by(|a, b| a.0.cmp(b.0));
+    let is_colliding = |entry_path: &BStr| colliding.binary_search_by(|(path, _)| (*path).cmp(entry_path)).is_ok();
+    let is_selected = &|entry_path: &BStr| is_selected(entry_path) && !is_colliding(entry_path);
+    let num_skipped = colliding
+        .iter()
+        .filter(|(_, checked_out_to)| checked_out_to.is_none())
+        .count();
+    num_files.fetch_add(num_skipped, std::sync::atomic::Ordering::Relaxed);
     let (chunk_size, thread_limit, num_threads) = gix_features::parallel::optimize_chunk_size_and_thread_limit(
         100,
-        index.entries().len().into(),
+        num_entries.into(),
         options.thread_limit,
         None,
     );
@@ -85,7 +295,12 @@ where
         delayed_paths_unknown,
         delayed_paths_unprocessed,
     } = if num_threads == 1 {
-        let entries_with_paths = interrupt::Iter::new(index.entries_mut_with_paths_in(paths), should_interrupt);
+        let entries_with_paths = interrupt::Iter::new(
+            index
+                .entries_mut_with_paths_in(paths)
+                .filter(|(_, entry_path)| is_selected(entry_path)),
+            should_interrupt,
+        );
         let mut delayed_filter_results = Vec::new();
         let mut out = chunk::process(
             entries_with_paths,
@@ -97,7 +312,12 @@ where
         chunk::process_delayed_filter_results(delayed_filter_results, &num_files, &num_bytes, &mut out, &mut ctx)?;
         out
     } else {
-        let entries_with_paths = interrupt::Iter::new(index.entries_mut_with_paths_in(paths), should_interrupt);
+        let entries_with_paths = interrupt::Iter::new(
+            index
+                .entries_mut_with_paths_in(paths)
+                .filter(|(_, entry_path)| is_selected(entry_path)),
+            should_interrupt,
+        );
         in_parallel_with_finalize(
             gix_features::iter::Chunks {
                 inner: entries_with_paths,
@@ -143,9 +363,63 @@ where
             as u64;
     }
 
+    let mut out = chunk::Outcome {
+        collisions,
+        errors,
+        bytes_written,
+        files: files_updated,
+        ..Default::default()
+    };
+    if colliding.iter().any(|(_, checked_out_to)| checked_out_to.is_some()) {
+        let mut delayed_filter_results = Vec::new();
+        for (entry, entry_path) in index.entries_mut_with_paths_in(paths) {
+            let Some(checked_out_to) = colliding
+                .binary_search_by(|(path, _)| (*path).cmp(entry_path))
+                .ok()
+                .and_then(|idx| colliding[idx].1)
+            else {
+                continue;
+            };
+            match chunk::checkout_entry_handle_result(
+                entry,
+                checked_out_to,
+                &mut out.errors,
+                &mut out.collisions,
+                &num_files,
+                &num_bytes,
+                &mut ctx,
+            )? {
+                entry::Outcome::Written { bytes } => {
+                    out.bytes_written += bytes as u64;
+                    out.files += 1;
+                }
+                entry::Outcome::Delayed(delayed) => delayed_filter_results.push(delayed),
+            }
+        }
+        chunk::process_delayed_filter_results(delayed_filter_results, &num_files, &num_bytes, &mut out, &mut ctx)?;
+    }
+    let chunk::Outcome {
+        collisions,
+        errors,
+        bytes_written,
+        files: files_updated,
+        delayed_paths_unknown: renamed_delayed_paths_unknown,
+        delayed_paths_unprocessed: renamed_delayed_paths_unprocessed,
+        ..
+    } = out;
+    let delayed_paths_unknown = delayed_paths_unknown
+        .into_iter()
+        .chain(renamed_delayed_paths_unknown)
+        .collect();
+    let delayed_paths_unprocessed = delayed_paths_unprocessed
+        .into_iter()
+        .chain(renamed_delayed_paths_unprocessed)
+        .collect();
+
     Ok(crate::checkout::Outcome {
         files_updated,
         collisions,
+        path_collisions,
         errors,
         bytes_written,
         delayed_paths_unknown,
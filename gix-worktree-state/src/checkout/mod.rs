@@ -10,6 +10,44 @@ pub struct Collision {
     pub error_kind: std::io::ErrorKind,
 }
 
+/// Entries of the index whose paths refer to the same location on disk as the path of another entry, which can happen on
+/// filesystems that ignore case or unicode normalization.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PathCollision {
+    /// The path of the file or directory which was the first to claim its location on disk, and which is checked out.
+    pub path: BString,
+    /// All entries whose paths collide with `path`, in index order.
+    pub colliding: Vec<CollidingEntry>,
+}
+
+/// An entry of the index whose path collides with the path of another entry, as part of a [`PathCollision`].
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CollidingEntry {
+    /// The path of the entry as stored in the index.
+    pub path: BString,
+    /// The path the entry was checked out to instead if [`CollisionPolicy::RenameWithSuffix`] is used, or `None` if
+    /// it wasn't checked out at all.
+    pub checked_out_to: Option<BString>,
+}
+
+/// Determines what happens if multiple entries of the index would be checked out to the same location on disk, as
+/// the filesystem [ignores case](gix_fs::Capabilities::ignore_case) or [unicode normalization](gix_fs::Capabilities::precompose_unicode).
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CollisionPolicy {
+    /// Don't look for collisions ahead of time, but record them in [`Outcome::collisions`] if they are noticed while
+    /// writing files, which only works if [`Options::destination_is_initially_empty`] is `true`.
+    /// Otherwise, entries that are written later overwrite the ones written before them.
+    #[default]
+    DetectOnWrite,
+    /// Fail with [`Error::PathCollisions`] before anything is written.
+    Error,
+    /// Only check out the first of all colliding entries in index order, and skip all others.
+    FirstWins,
+    /// Check out the first of all colliding entries in index order, and all others with a `~<n>` suffix added to the
+    /// colliding file or directory name, like `File~1` if `file` was checked out already.
+    RenameWithSuffix,
+}
+
 /// A path that encountered an IO error.
 #[derive(Debug)]
 pub struct ErrorRecord {
@@ -28,6 +66,9 @@ pub struct Outcome {
     pub bytes_written: u64,
     /// The encountered collisions, which can happen on a case-insensitive filesystem.
     pub collisions: Vec<Collision>,
+    /// Entries which collided with other entries, as detected ahead of time if [`Options::collision_policy`] isn't
+    /// [`CollisionPolicy::DetectOnWrite`].
+    pub path_collisions: Vec<PathCollision>,
     /// Other errors that happened during checkout.
     pub errors: Vec<ErrorRecord>,
     /// Relative paths that the process listed as 'delayed' even though we never passed them.
@@ -56,6 +97,8 @@ pub struct Options {
     /// these will try to delete the existing entry.
     /// This is similar in behaviour as `git checkout --force`.
     pub overwrite_existing: bool,
+    /// Control what happens if multiple entries would be checked out to the same location on disk.
+    pub collision_policy: CollisionPolicy,
     /// If true, default false, try to checkout as much as possible and don't abort on first error which isn't
     /// due to a conflict.
     /// The checkout operation will never fail, but count the encountered errors instead along with their paths.
@@ -68,9 +111,13 @@ pub struct Options {
     pub filters: gix_filter::Pipeline,
     /// Control how long-running processes may use the 'delay' capability.
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    /// If not 0, blobs larger than this amount of bytes are streamed from the object database into the worktree
+    /// instead of being held in memory, as long as no filter would change them.
+    /// This is typically set from `core.bigFileThreshold`.
+    pub large_file_threshold_bytes: u64,
 }
 
-/// The error returned by the [checkout()][crate::checkout()] function.
+/// The error returned by the [checkout()][crate::checkout()] function and its relatives.
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -96,8 +143,20 @@ pub enum Error {
     FilterPathUnknown { rela_path: BString },
     #[error("The following paths were delayed and apparently forgotten to be processed by the filter driver: ")]
     FilterPathsUnprocessed { rela_paths: Vec<BString> },
+    #[error("The path '{rela_path}' did not match any entry in the index")]
+    PathNotFound { rela_path: BString },
+    #[error("{} entries would be checked out to the same location as other entries, like '{}' and '{}'", .collisions.iter().map(|c| c.colliding.len()).sum::<usize>(), .collisions[0].colliding[0].path, .collisions[0].path)]
+    PathCollisions { collisions: Vec<PathCollision> },
+    #[error("The entry at '{rela_path}' is unmerged and can't be checked out")]
+    Unmerged { rela_path: BString },
+    #[error("The entry at '{rela_path}' with mode {mode:?} isn't a blob")]
+    NotABlob {
+        rela_path: BString,
+        mode: gix_index::entry::Mode,
+    },
 }
 
 mod chunk;
+mod collision;
 mod entry;
 pub(crate) mod function;
@@ -10,7 +10,7 @@ use std::{
 use gix_features::progress;
 use gix_object::{bstr::ByteSlice, Data};
 use gix_testtools::tempfile::TempDir;
-use gix_worktree_state::checkout::Collision;
+use gix_worktree_state::checkout::{CollidingEntry, Collision, CollisionPolicy, PathCollision};
 use once_cell::sync::Lazy;
 
 use crate::fixture_path;
@@ -255,6 +255,29 @@ fn overwriting_files_and_lone_directories_works() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn overwriting_longer_files_truncates_them() -> crate::Result {
+    for destination_is_initially_empty in [false, true] {
+        let mut opts = opts_from_probe();
+        opts.overwrite_existing = true;
+        opts.destination_is_initially_empty = destination_is_initially_empty;
+        let (_source, destination, _index, outcome) = checkout_index_in_tmp_dir_opts(
+            opts,
+            "make_mixed",
+            None,
+            |_| true,
+            |d| std::fs::write(d.join("executable"), b"longer than the content"),
+        )?;
+        assert!(outcome.collisions.is_empty());
+        assert_eq!(
+            std::fs::read(destination.path().join("executable"))?.as_bstr(),
+            "content",
+            "the previous content must not shine through if it was longer"
+        );
+    }
+    Ok(())
+}
+
 #[test]
 fn symlinks_become_files_if_disabled() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -267,6 +290,38 @@ fn symlinks_become_files_if_disabled() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn large_files_are_streamed_unless_filters_apply() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.large_file_threshold_bytes = 1;
+    let (source_tree, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts.clone(), "make_mixed_without_submodules", None)?;
+    assert_equality(&source_tree, &destination, opts.fs.symlink)?;
+    assert!(outcome.collisions.is_empty());
+    assert!(outcome.errors.is_empty());
+
+    let mut opts = opts_from_probe();
+    opts.large_file_threshold_bytes = 1;
+    setup_filter_pipeline(opts.filters.options_mut());
+    let (_source, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts, "make_mixed_without_submodules_and_symlinks", None)?;
+    assert_eq!(outcome.errors.len(), 0);
+    assert_eq!(outcome.files_updated, 5);
+
+    let dest = destination.path();
+    assert_eq!(
+        std::fs::read(dest.join("executable"))?.as_bstr(),
+        "content",
+        "unfiltered, and streamed"
+    );
+    assert_eq!(
+        std::fs::read(dest.join("dir").join("content"))?.as_bstr(),
+        "➡other content\r\n",
+        "filters still apply to large files"
+    );
+    Ok(())
+}
+
 #[test]
 fn symlinks_to_directories_are_usable() -> crate::Result {
     let opts = opts_from_probe();
@@ -549,6 +604,86 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem_even_with_delayed_fi
     }
 }
 
+#[test]
+fn collisions_are_handled_ahead_of_time_according_to_policy() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.fs.ignore_case = true;
+    let expected_collisions = |checked_out_to: [Option<&str>; 5]| {
+        // Groups appear in the order of their first collision in the index.
+        let entry = |path: &str, idx: usize| CollidingEntry {
+            path: path.into(),
+            checked_out_to: checked_out_to[idx].map(Into::into),
+        };
+        vec![
+            PathCollision {
+                path: "FILE_X".into(),
+                colliding: vec![entry("FILE_x", 0), entry("file_X", 1), entry("file_x", 2)],
+            },
+            PathCollision {
+                path: "D".into(),
+                colliding: vec![entry("d", 3)],
+            },
+            PathCollision {
+                path: "X".into(),
+                colliding: vec![entry("x", 4)],
+            },
+        ]
+    };
+
+    opts.collision_policy = CollisionPolicy::Error;
+    let err = checkout_index_in_tmp_dir(opts.clone(), "make_ignorecase_collisions", None).unwrap_err();
+    let err = err
+        .downcast_ref::<gix_worktree_state::checkout::Error>()
+        .expect("checkout error");
+    assert!(
+        matches!(err, gix_worktree_state::checkout::Error::PathCollisions { collisions } if *collisions == expected_collisions([None; 5])),
+        "nothing is written if collisions are an error: {err:?}"
+    );
+
+    opts.collision_policy = CollisionPolicy::FirstWins;
+    let (_source_tree, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts.clone(), "make_ignorecase_collisions", None)?;
+    assert_eq!(outcome.path_collisions, expected_collisions([None; 5]));
+    assert!(outcome.collisions.is_empty(), "no collision happened while writing");
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["D/B", "D/C", "FILE_X", "X", "link-to-X"]),
+        "only the first of all colliding entries is checked out"
+    );
+
+    opts.collision_policy = CollisionPolicy::RenameWithSuffix;
+    let (_source_tree, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts, "make_ignorecase_collisions", None)?;
+    assert_eq!(
+        outcome.path_collisions,
+        expected_collisions([
+            Some("FILE_x~1"),
+            Some("file_X~2"),
+            Some("file_x~3"),
+            Some("d~1"),
+            Some("x~1")
+        ])
+    );
+    assert!(outcome.collisions.is_empty(), "no collision happened while writing");
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths([
+            "D/B",
+            "D/C",
+            "FILE_X",
+            "FILE_x~1",
+            "X",
+            "d~1",
+            "file_X~2",
+            "file_x~3",
+            "link-to-X",
+            "x~1"
+        ]),
+        "colliding entries are checked out next to the ones they collide with"
+    );
+    Ok(())
+}
+
 fn multi_threaded() -> bool {
     gix_features::parallel::num_threads(None) > 1
 }
@@ -645,6 +780,31 @@ fn checkout_index_in_tmp_dir_opts(
                 Ok(None)
             }
         }
+
+        fn try_find_stream(
+            &self,
+            id: &gix_hash::oid,
+        ) -> Result<Option<gix_object::find::Stream<'_>>, gix_object::find::Error> {
+            if (self.allow.borrow_mut())(id) {
+                self.objects.try_find_stream(id)
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    impl<Allow, Find> gix_object::FindHeader for MaybeFind<Allow, Find>
+    where
+        Allow: FnMut(&gix_hash::oid) -> bool + Send + Clone,
+        Find: gix_object::FindHeader + Send + Clone,
+    {
+        fn try_header(&self, id: &gix_hash::oid) -> Result<Option<gix_object::Header>, gix_object::find::Error> {
+            if (self.allow.borrow_mut())(id) {
+                self.objects.try_header(id)
+            } else {
+                Ok(None)
+            }
+        }
     }
 
     let db = MaybeFind {
@@ -701,3 +861,113 @@ fn setup_filter_pipeline(opts: &mut gix_filter::pipeline::Options) {
         required: true,
     }];
 }
+
+#[test]
+fn checkout_paths_only_writes_selected_entries() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.destination_is_initially_empty = false;
+    opts.overwrite_existing = true;
+    let (mut index, odb) = index_and_odb("make_mixed_without_submodules_and_symlinks")?;
+    let destination = gix_testtools::tempfile::tempdir_in(std::env::current_dir()?)?;
+    let dest = destination.path();
+    std::fs::write(dest.join("empty"), "local modification")?;
+
+    let outcome = gix_worktree_state::checkout_paths(
+        &mut index,
+        ["dir/sub-dir/", "empty"],
+        dest,
+        odb.clone(),
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        opts.clone(),
+    )?;
+    assert_eq!(outcome.files_updated, 2);
+    assert!(outcome.errors.is_empty());
+    assert_eq!(
+        stripped_prefix(dest, &dir_structure(dest)),
+        [Path::new("dir/sub-dir/file"), Path::new("empty")],
+        "only the selected entries are written, directories are matched recursively"
+    );
+    assert_eq!(
+        std::fs::read(dest.join("empty"))?,
+        b"",
+        "local modifications are overwritten"
+    );
+    let entry = index.entry_by_path("empty".into()).expect("present");
+    assert_ne!(entry.stat, Default::default(), "the stat information is updated");
+
+    let err = gix_worktree_state::checkout_paths(
+        &mut index,
+        ["dir/sub"],
+        dest,
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        opts,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(&err, gix_worktree_state::checkout::Error::PathNotFound { rela_path } if rela_path == "dir/sub"),
+        "paths have to match entries or directories exactly: {err:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_blob_to_applies_filters() -> crate::Result {
+    let mut filters = opts_from_probe().filters;
+    setup_filter_pipeline(filters.options_mut());
+    let source_tree = fixture_path("make_mixed_without_submodules_and_symlinks");
+    let (index, odb) = index_and_odb("make_mixed_without_submodules_and_symlinks")?;
+    let mut attributes = gix_worktree::Stack::from_state_and_ignore_case(
+        source_tree,
+        false,
+        gix_worktree::stack::State::AttributesStack(Default::default()),
+        &index,
+        index.path_backing(),
+    );
+
+    for (rela_path, expected) in [
+        ("dir/content", "➡other content\r\n"),
+        ("executable", "content"),
+        ("empty", ""),
+    ] {
+        let entry = index.entry_by_path(rela_path.into()).expect("present");
+        let mut out = Vec::new();
+        let num_bytes = gix_worktree_state::write_blob_to(
+            &entry.id,
+            entry.mode,
+            rela_path.into(),
+            &mut attributes,
+            &mut filters,
+            &odb,
+            &mut out,
+        )?;
+        assert_eq!(out.as_bstr(), expected, "{rela_path}");
+        assert_eq!(num_bytes as usize, out.len());
+    }
+
+    let err = gix_worktree_state::write_blob_to(
+        &gix_hash::Kind::Sha1.null(),
+        gix_index::entry::Mode::COMMIT,
+        "submodule".into(),
+        &mut attributes,
+        &mut filters,
+        &odb,
+        &mut Vec::new(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, gix_worktree_state::checkout::Error::NotABlob { .. }));
+    Ok(())
+}
+
+fn index_and_odb(
+    script_name: &str,
+) -> crate::Result<(gix_index::File, gix_odb::store::Handle<std::sync::Arc<gix_odb::Store>>)> {
+    let git_dir = fixture_path(script_name).join(".git");
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let odb = gix_odb::at(git_dir.join("objects"))?.into_inner().into_arc()?;
+    Ok((index, odb))
+}
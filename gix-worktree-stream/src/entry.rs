@@ -31,6 +31,26 @@ pub enum Error {
 impl Stream {
     /// Access the next entry of the stream or `None` if there is nothing more to read.
     pub fn next_entry(&mut self) -> Result<Option<Entry<'_>>, Error> {
+        if !self.own_entries_done {
+            match self.next_entry_info()? {
+                Some(info) => return Ok(Some(self.entry_from_info(info, None))),
+                None => self.own_entries_done = true,
+            }
+        }
+        loop {
+            let Some((_, stream)) = self.streams.front_mut() else {
+                return Ok(None);
+            };
+            if let Some(info) = stream.next_entry_info()? {
+                let (path, stream) = self.streams.front_mut().expect("still present");
+                return Ok(Some(stream.entry_from_info(info, Some(path.as_ref()))));
+            }
+            self.streams.pop_front();
+        }
+    }
+
+    /// Read the header of the next entry of our own byte stream, or `None` if there is no more entry.
+    fn next_entry_info(&mut self) -> Result<Option<EntryInfo>, Error> {
         assert!(
             self.path_buf.is_some(),
             "BUG: must consume and drop entry before getting the next one"
@@ -41,17 +61,11 @@ impl Stream {
             self.path_buf.as_mut().expect("set while producing an entry"),
         );
         match res {
-            Ok((remaining, mode, id)) => {
+            Ok(info) => {
                 if let Some(err) = self.err.lock().take() {
                     return Err(err);
                 }
-                Ok(Some(Entry {
-                    path_buf: self.path_buf.take(),
-                    parent: self,
-                    id,
-                    mode,
-                    remaining,
-                }))
+                Ok(Some(info))
             }
             Err(err) => {
                 if let Some(err) = self.err.lock().take() {
@@ -65,8 +79,27 @@ impl Stream {
             }
         }
     }
+
+    /// Create an entry for `info` as read by [`Self::next_entry_info()`], with its path placed into the directory at
+    /// `prefix` if set.
+    fn entry_from_info(&mut self, (remaining, mode, id): EntryInfo, prefix: Option<&BStr>) -> Entry<'_> {
+        let mut path_buf = self.path_buf.take();
+        if let (Some(path), Some(prefix)) = (path_buf.as_mut(), prefix) {
+            path.insert(0, b'/');
+            path.splice(0..0, prefix.iter().copied());
+        }
+        Entry {
+            path_buf,
+            parent: self,
+            id,
+            mode,
+            remaining,
+        }
+    }
 }
 
+type EntryInfo = (Option<usize>, gix_object::tree::EntryMode, gix_hash::ObjectId);
+
 /// The source of an additional entry
 pub enum Source {
     /// There is no content, typically the case with directories which are always considered empty.
@@ -1,6 +1,10 @@
 use std::io::Write;
 
-use gix_object::{bstr::BStr, FindExt};
+use gix_filter::{driver::apply::MaybeDelayed, pipeline::convert::ToWorktreeOutcome};
+use gix_object::{
+    bstr::{BStr, BString},
+    FindExt,
+};
 
 use crate::{entry, entry::Error, protocol, AdditionalEntry, SharedErrorSlot, Stream};
 
@@ -40,6 +44,28 @@ pub fn from_tree<Find, E>(
         + Send
         + 'static,
 ) -> Stream
+where
+    Find: gix_object::Find + Clone + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    from_tree_opts(tree, objects, pipeline, attributes, Options::default())
+}
+
+/// Like [`from_tree()`], but with `options` to filter or transform entries and to load blobs in parallel.
+///
+/// With a single thread, entries are streamed while the tree is traversed.
+/// With more than one thread, the tree is traversed first, and blobs are then loaded with at most as many blobs in
+/// flight as there are threads, while entries are still streamed in the order of the tree.
+/// Worktree conversions are always performed on a single thread.
+pub fn from_tree_opts<Find, E>(
+    tree: gix_hash::ObjectId,
+    objects: Find,
+    pipeline: gix_filter::Pipeline,
+    attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
+        + Send
+        + 'static,
+    options: Options,
+) -> Stream
 where
     Find: gix_object::Find + Clone + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
@@ -53,6 +79,7 @@ where
                 objects,
                 pipeline,
                 attributes,
+                options,
                 &mut write,
                 slot.clone(),
                 additional_entries,
@@ -75,6 +102,59 @@ where
     stream
 }
 
+/// A function to determine what to do with each blob or symlink of a tree, see [`Options::filter`].
+///
+/// It's called with the slash-separated path of the entry relative to the repository, its mode and its id.
+pub type Filter = Box<dyn FnMut(&BStr, gix_object::tree::EntryMode, &gix_hash::oid) -> Action + Send>;
+
+/// What to do with an entry of a tree, as determined by a [`Filter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Add the entry to the stream as is.
+    Keep,
+    /// Don't add the entry to the stream.
+    Skip,
+    /// Add the entry at the given slash-separated path relative to the repository instead.
+    ///
+    /// Note that its worktree conversion is still determined by the attributes of its original path.
+    Rename(BString),
+    /// Add the entry with the given content instead of the one of its blob, which isn't loaded then.
+    ///
+    /// The content is added verbatim, without worktree conversion.
+    Substitute(Vec<u8>),
+}
+
+/// Options for use in [`from_tree_opts()`].
+pub struct Options {
+    /// If set, called for each blob or symlink that isn't excluded by the `export-ignore` attribute to determine
+    /// if and how it should be added to the stream.
+    pub filter: Option<Filter>,
+    /// The amount of threads to use for loading blobs from the object database, or `None` to use all logical cores.
+    ///
+    /// Defaults to `Some(1)`, which loads blobs on the thread that traverses the tree. Note that more than one thread
+    /// is only used if the `parallel` feature of `gix-features` is enabled.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            filter: None,
+            thread_limit: Some(1),
+        }
+    }
+}
+
+impl std::fmt::Debug for Options {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Options")
+            .field("filter", &self.filter.as_ref().map(|_| "<fn>"))
+            .field("thread_limit", &self.thread_limit)
+            .finish()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run<Find, E>(
     tree: gix_hash::ObjectId,
     objects: Find,
@@ -82,12 +162,13 @@ fn run<Find, E>(
     mut attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
         + Send
         + 'static,
+    Options { filter, thread_limit }: Options,
     out: &mut gix_features::io::pipe::Writer,
     err: SharedErrorSlot,
     additional_entries: std::sync::mpsc::Receiver<AdditionalEntry>,
 ) -> Result<(), Error>
 where
-    Find: gix_object::Find + Clone,
+    Find: gix_object::Find + Clone + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
 {
     let mut buf = Vec::new();
@@ -98,21 +179,27 @@ where
 
     let mut attrs = gix_attributes::search::Outcome::default();
     attrs.initialize_with_selection(&Default::default(), Some("export-ignore"));
+    let is_parallel = gix_features::parallel::num_threads(thread_limit) > 1;
     let mut dlg = traverse::Delegate {
-        out,
         err,
-        pipeline,
         attrs,
-        objects: objects.clone(),
         fetch_attributes: move |a: &BStr, b: gix_object::tree::EntryMode, c: &mut gix_attributes::search::Outcome| {
             attributes(a, b, c).map_err(|err| Error::Attributes {
                 source: Box::new(err),
                 path: a.to_owned(),
             })
         },
+        filter,
         path_deque: Default::default(),
         path: Default::default(),
-        buf: Vec::with_capacity(1024),
+        jobs: Vec::new(),
+        write: (!is_parallel).then(|| traverse::WriteJobs {
+            objects: objects.clone(),
+            pipeline: &mut pipeline,
+            out: &mut *out,
+            buf: Vec::new(),
+            data: Vec::new(),
+        }),
     };
     gix_traverse::tree::breadthfirst(
         tree_iter,
@@ -121,6 +208,32 @@ where
         &mut dlg,
     )?;
 
+    let traverse::Delegate {
+        jobs,
+        mut fetch_attributes,
+        ..
+    } = dlg;
+    if is_parallel {
+        use gix_features::parallel;
+        let loaded = parallel::InOrderIter::from(parallel::reduce::Stepwise::new(
+            jobs.into_iter().enumerate(),
+            thread_limit,
+            move |_| objects.clone(),
+            |(seq, job): (parallel::SequenceId, traverse::Job), objects: &mut Find| {
+                let mut data = Vec::new();
+                if job.substitute.is_none() {
+                    objects.find(&job.id, &mut data)?;
+                }
+                Ok((seq, (job, data)))
+            },
+            parallel::reduce::IdentityWithResult::<(parallel::SequenceId, (traverse::Job, Vec<u8>)), Error>::default(),
+        ));
+        for res in loaded {
+            let (job, data) = res?;
+            write_job(job, &data, &mut pipeline, &mut fetch_attributes, out, &mut buf)?;
+        }
+    }
+
     for entry in additional_entries {
         protocol::write_entry_header_and_path(
             entry.relative_path.as_ref(),
@@ -143,4 +256,53 @@ where
     Ok(())
 }
 
+/// Write the entry of `job` whose blob contains `data` to `out`, after converting it to its worktree representation
+/// with `pipeline` unless its content is substituted.
+// Our pipe writer always writes the whole amount.
+#[allow(clippy::unused_io_amount)]
+fn write_job(
+    job: traverse::Job,
+    data: &[u8],
+    pipeline: &mut gix_filter::Pipeline,
+    fetch_attributes: &mut impl FnMut(
+        &BStr,
+        gix_object::tree::EntryMode,
+        &mut gix_attributes::search::Outcome,
+    ) -> Result<(), Error>,
+    out: &mut gix_features::io::pipe::Writer,
+    buf: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let path = job.path.as_ref().unwrap_or(&job.source_path);
+    if let Some(content) = &job.substitute {
+        protocol::write_entry_header_and_path(path.as_ref(), &job.id, job.mode, Some(content.len()), out)?;
+        out.write(content)?;
+        return Ok(());
+    }
+
+    pipeline.driver_context_mut().blob = Some(job.id);
+    let converted = pipeline.convert_to_worktree(
+        data,
+        job.source_path.as_ref(),
+        &mut |a, b| {
+            fetch_attributes(a, job.mode, b).ok();
+        },
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+
+    match converted {
+        ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
+            protocol::write_entry_header_and_path(path.as_ref(), &job.id, job.mode, Some(buf.len()), out)?;
+            out.write(buf)?;
+        }
+        ToWorktreeOutcome::Process(MaybeDelayed::Immediate(read)) => {
+            protocol::write_entry_header_and_path(path.as_ref(), &job.id, job.mode, None, out)?;
+            protocol::write_stream(buf, read, out)?;
+        }
+        ToWorktreeOutcome::Process(MaybeDelayed::Delayed(_)) => {
+            unreachable!("we forbade it")
+        }
+    }
+    Ok(())
+}
+
 mod traverse;
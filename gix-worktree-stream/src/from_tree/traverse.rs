@@ -1,27 +1,45 @@
-use std::{collections::VecDeque, io::Write};
+use std::collections::VecDeque;
 
-use gix_filter::{driver::apply::MaybeDelayed, pipeline::convert::ToWorktreeOutcome};
 use gix_object::{
     bstr::{BStr, BString, ByteSlice, ByteVec},
     tree, FindExt,
 };
 use gix_traverse::tree::{visit::Action, Visit};
 
-use crate::{entry::Error, protocol, SharedErrorSlot};
+use crate::{entry::Error, from_tree, SharedErrorSlot};
 
-pub struct Delegate<'a, AttributesFn, Find>
-where
-    Find: gix_object::Find,
-{
+/// A blob or symlink to add to the stream.
+pub(crate) struct Job {
+    /// The path of the entry in the tree.
+    pub(crate) source_path: BString,
+    /// The path to add the entry at if it differs from `source_path`.
+    pub(crate) path: Option<BString>,
+    pub(crate) mode: tree::EntryMode,
+    pub(crate) id: gix_hash::ObjectId,
+    /// The content to use instead of the one of the blob, without worktree conversion.
+    pub(crate) substitute: Option<Vec<u8>>,
+}
+
+/// What's needed to write jobs to the stream as soon as they are encountered.
+pub(crate) struct WriteJobs<'a, Find> {
+    pub(crate) objects: Find,
+    pub(crate) pipeline: &'a mut gix_filter::Pipeline,
     pub(crate) out: &'a mut gix_features::io::pipe::Writer,
+    pub(crate) buf: Vec<u8>,
+    pub(crate) data: Vec<u8>,
+}
+
+/// Write all blobs and symlinks of a tree that should be added to the stream, or collect them as jobs
+/// if `write` is `None`.
+pub struct Delegate<'a, AttributesFn, Find> {
     pub(crate) err: SharedErrorSlot,
     pub(crate) path_deque: VecDeque<BString>,
     pub(crate) path: BString,
-    pub(crate) pipeline: gix_filter::Pipeline,
     pub(crate) attrs: gix_attributes::search::Outcome,
     pub(crate) fetch_attributes: AttributesFn,
-    pub(crate) objects: Find,
-    pub(crate) buf: Vec<u8>,
+    pub(crate) filter: Option<from_tree::Filter>,
+    pub(crate) jobs: Vec<Job>,
+    pub(crate) write: Option<WriteJobs<'a, Find>>,
 }
 
 impl<AttributesFn, Find> Delegate<'_, AttributesFn, Find>
@@ -62,38 +80,38 @@ where
         if self.ignore_state().is_set() {
             return Ok(Action::Continue);
         }
-        self.objects.find(entry.oid, &mut self.buf)?;
-
-        self.pipeline.driver_context_mut().blob = Some(entry.oid.into());
-        let converted = self.pipeline.convert_to_worktree(
-            &self.buf,
-            self.path.as_ref(),
-            &mut |a, b| {
-                (self.fetch_attributes)(a, entry.mode, b).ok();
-            },
-            gix_filter::driver::apply::Delay::Forbid,
-        )?;
-
-        // Our pipe writer always writes the whole amount.
-        #[allow(clippy::unused_io_amount)]
-        match converted {
-            ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
-                protocol::write_entry_header_and_path(
-                    self.path.as_ref(),
-                    entry.oid,
-                    entry.mode,
-                    Some(buf.len()),
-                    self.out,
+        let (path, substitute) = match self
+            .filter
+            .as_mut()
+            .map(|filter| filter(self.path.as_ref(), entry.mode, entry.oid))
+        {
+            None | Some(from_tree::Action::Keep) => (None, None),
+            Some(from_tree::Action::Skip) => return Ok(Action::Continue),
+            Some(from_tree::Action::Rename(path)) => (Some(path), None),
+            Some(from_tree::Action::Substitute(content)) => (None, Some(content)),
+        };
+        let job = Job {
+            source_path: self.path.clone(),
+            path,
+            mode: entry.mode,
+            id: entry.oid.to_owned(),
+            substitute,
+        };
+        match self.write.as_mut() {
+            Some(write) => {
+                if job.substitute.is_none() {
+                    write.objects.find(&job.id, &mut write.data)?;
+                }
+                from_tree::write_job(
+                    job,
+                    &write.data,
+                    write.pipeline,
+                    &mut self.fetch_attributes,
+                    write.out,
+                    &mut write.buf,
                 )?;
-                self.out.write(buf)?;
-            }
-            ToWorktreeOutcome::Process(MaybeDelayed::Immediate(read)) => {
-                protocol::write_entry_header_and_path(self.path.as_ref(), entry.oid, entry.mode, None, self.out)?;
-                protocol::write_stream(&mut self.buf, read, self.out)?;
-            }
-            ToWorktreeOutcome::Process(MaybeDelayed::Delayed(_)) => {
-                unreachable!("we forbade it")
             }
+            None => self.jobs.push(job),
         }
         Ok(Action::Continue)
     }
@@ -23,6 +23,10 @@ pub struct Stream {
     pos: usize,
     /// The amount of bytes usable from `buf` (even though it always has a fixed size)
     filled: usize,
+    /// Streams whose entries are returned after our own, with their paths prefixed by the given directory.
+    streams: std::collections::VecDeque<(BString, Stream)>,
+    /// `true` if all entries of our own byte stream were returned.
+    own_entries_done: bool,
 }
 
 ///
@@ -30,8 +34,10 @@ pub struct Stream {
 pub mod entry;
 pub(crate) mod protocol;
 
-mod from_tree;
-pub use from_tree::from_tree;
+///
+#[allow(clippy::empty_docs)]
+pub mod from_tree;
+pub use from_tree::{from_tree, from_tree_opts};
 
 pub(crate) type SharedErrorSlot = Arc<parking_lot::Mutex<Option<entry::Error>>>;
 
@@ -102,6 +108,8 @@ impl Stream {
             buf: std::iter::repeat(0).take(u16::MAX as usize).collect(),
             pos: 0,
             filled: 0,
+            streams: Default::default(),
+            own_entries_done: false,
         }
     }
 }
@@ -122,6 +130,30 @@ impl Stream {
         self
     }
 
+    /// Add all entries of `stream` to this stream as if they were located in the directory at `path`, which is
+    /// slash-separated and relative to the repository, like the worktree of a submodule.
+    ///
+    /// They will be returned after all other entries of this stream, including [additional entries](Self::add_entry()),
+    /// and after the entries of previously added streams.
+    ///
+    /// Note that these entries are only returned by [`Self::next_entry()`], but aren't part of [`Self::into_read()`] or
+    /// [`Self::as_read_mut()`].
+    pub fn add_stream(&mut self, path: impl Into<BString>, mut stream: Stream) -> &mut Self {
+        let path = path.into();
+        let nested = std::mem::take(&mut stream.streams)
+            .into_iter()
+            .map(|(nested_path, nested)| {
+                let mut prefixed = path.clone();
+                prefixed.push(b'/');
+                prefixed.extend_from_slice(&nested_path);
+                (prefixed, nested)
+            })
+            .collect::<Vec<_>>();
+        self.streams.push_back((path, stream));
+        self.streams.extend(nested);
+        self
+    }
+
     /// Add the item at `path` as entry to this stream, which is expected to be under `root`.
     ///
     /// Note that the created entries will always have a null SHA1, and that we access this path
@@ -189,6 +221,8 @@ impl Stream {
                 buf: std::iter::repeat(0).take(u16::MAX as usize).collect(),
                 pos: 0,
                 filled: 0,
+                streams: Default::default(),
+                own_entries_done: false,
             },
             write,
             rx_entries,
@@ -216,6 +216,49 @@ mod from_tree {
         Ok(())
     }
 
+    #[test]
+    fn added_streams_are_returned_last_within_their_directory() -> gix_testtools::Result {
+        let (_dir, head_tree, odb, _cache) = basic()?;
+        let new_stream = || {
+            gix_worktree_stream::from_tree(head_tree, odb.clone(), mutating_pipeline(false), |_, _, _| {
+                Ok::<_, Infallible>(())
+            })
+        };
+        let mut stream = new_stream();
+        let mut nested = new_stream();
+        nested.add_stream("nested", new_stream());
+        stream.add_entry(gix_worktree_stream::AdditionalEntry {
+            id: gix_hash::Kind::Sha1.null(),
+            mode: EntryKind::Blob.into(),
+            relative_path: "VERSION".into(),
+            source: gix_worktree_stream::entry::Source::Memory(b"1.0".to_vec()),
+        });
+        stream.add_stream("sub", nested);
+
+        let mut paths = Vec::new();
+        let mut buf = Vec::new();
+        while let Some(mut entry) = stream.next_entry().expect("entry retrieval does not fail") {
+            buf.clear();
+            entry.read_to_end(&mut buf).expect("stream can always be read");
+            paths.push((entry.relative_path().to_owned(), buf.len()));
+        }
+        let paths: Vec<_> = paths
+            .into_iter()
+            .filter_map(|(path, len)| (path.ends_with_str("b") || path == "VERSION").then_some((path, len)))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                ("dir/b".into(), 4),
+                ("VERSION".into(), 3),
+                ("sub/dir/b".into(), 4),
+                ("sub/nested/dir/b".into(), 4)
+            ],
+            "nested streams of added streams are flattened, and their paths are adjusted"
+        );
+        Ok(())
+    }
+
     #[test]
     fn can_drop_entry_without_reading_it() -> gix_testtools::Result {
         let (_dir, head_tree, odb, mut cache) = basic()?;
@@ -235,6 +278,129 @@ mod from_tree {
         Ok(())
     }
 
+    #[test]
+    fn filter_can_skip_rename_and_substitute_entries() -> gix_testtools::Result {
+        use gix_worktree_stream::from_tree::{Action, Options};
+        let (_dir, head_tree, odb, _cache) = basic()?;
+        let stream = gix_worktree_stream::from_tree_opts(
+            head_tree,
+            odb,
+            mutating_pipeline(false),
+            |_, _, _| Ok::<_, Infallible>(()),
+            Options {
+                filter: Some(Box::new(|path, _mode, _id| match path.to_str().expect("valid UTF-8") {
+                    "a" => Action::Skip,
+                    "dir/b" => Action::Rename("b-renamed".into()),
+                    "bigfile" => Action::Substitute(b"small".to_vec()),
+                    _ => Action::Keep,
+                })),
+                ..Default::default()
+            },
+        );
+        let entries = paths_and_content(stream)?;
+        let paths: Vec<_> = entries.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                ".gitattributes",
+                "bigfile",
+                "file-ignored",
+                "symlink-to-a",
+                "dir-ignored/file-ignored-transitively",
+                "dir/.gitattributes",
+                "b-renamed",
+                "dir/subdir/exe",
+                "dir/subdir/streamed"
+            ],
+            "'a' is skipped, and renamed entries keep their position, while nothing is export-ignored without attributes"
+        );
+        assert_eq!(entries[1].1, "small", "substituted content is used verbatim");
+        Ok(())
+    }
+
+    #[test]
+    fn parallel_blob_loading_preserves_order() -> gix_testtools::Result {
+        let (_dir, head_tree, odb, _cache) = basic()?;
+        let new_stream = |thread_limit| {
+            gix_worktree_stream::from_tree_opts(
+                head_tree,
+                odb.clone(),
+                mutating_pipeline(false),
+                |_, _, _| Ok::<_, Infallible>(()),
+                gix_worktree_stream::from_tree::Options {
+                    thread_limit,
+                    ..Default::default()
+                },
+            )
+        };
+        let serial = paths_and_content(new_stream(Some(1)))?;
+        assert_eq!(serial.len(), 10);
+        assert_eq!(paths_and_content(new_stream(Some(4)))?, serial);
+        assert_eq!(paths_and_content(new_stream(None))?, serial);
+        Ok(())
+    }
+
+    #[test]
+    fn single_threaded_operation_streams_entries_while_traversing() -> gix_testtools::Result {
+        let (_dir, head_tree, odb, _cache) = basic()?;
+        let new_stream = |thread_limit| {
+            gix_worktree_stream::from_tree_opts(
+                head_tree,
+                odb.clone(),
+                mutating_pipeline(false),
+                |path, _, _| {
+                    if path == "dir/b" {
+                        Err(Error::new(ErrorKind::Other, "attribute retrieval failed"))
+                    } else {
+                        Ok(())
+                    }
+                },
+                gix_worktree_stream::from_tree::Options {
+                    thread_limit,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let mut stream = new_stream(Some(1));
+        let mut entry = stream
+            .next_entry()?
+            .expect("the first entry is streamed before the failing one is traversed");
+        assert_eq!(entry.relative_path(), ".gitattributes");
+        std::io::copy(&mut entry, &mut std::io::sink())?;
+        drop(entry);
+        let err = loop {
+            match stream.next_entry() {
+                Ok(Some(mut entry)) => {
+                    std::io::copy(&mut entry, &mut std::io::sink())?;
+                }
+                Ok(None) => unreachable!("the error is received eventually"),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.to_string(), "Could not query attributes for path \"dir/b\"");
+
+        if gix_features::parallel::num_threads(Some(4)) > 1 {
+            let err = new_stream(Some(4)).next_entry().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Could not query attributes for path \"dir/b\"",
+                "with multiple threads, the whole tree is traversed first"
+            );
+        }
+        Ok(())
+    }
+
+    fn paths_and_content(mut stream: gix_worktree_stream::Stream) -> gix_testtools::Result<Vec<(String, String)>> {
+        let mut out = Vec::new();
+        while let Some(mut entry) = stream.next_entry()? {
+            let mut buf = Vec::new();
+            entry.read_to_end(&mut buf)?;
+            out.push((entry.relative_path().to_string(), buf.as_bstr().to_string()));
+        }
+        Ok(out)
+    }
+
     fn basic() -> gix_testtools::Result<(PathBuf, gix_hash::ObjectId, gix_odb::HandleArc, gix_worktree::Stack)> {
         let dir = gix_testtools::scripted_fixture_read_only("basic.sh")?;
 
@@ -0,0 +1,216 @@
+//! An `async` façade for the most common high-level operations, suitable for use in servers running an async runtime.
+//!
+//! All operations are executed on a thread-pool dedicated to blocking work, which is why the futures returned here
+//! never block the executor they are polled on. This works with any async runtime, and doesn't require the
+//! `async-network-client` feature, which is why it can be used along with all transports of the `blocking-network-client`.
+#![allow(clippy::result_large_err)]
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use crate::{bstr::BString, ObjectDetached, ObjectId, Repository, ThreadSafeRepository};
+
+/// A repository handle whose methods return futures, to be used in async applications without blocking the executor.
+///
+/// It's cheap to clone and can be shared freely between tasks.
+#[derive(Clone)]
+pub struct AsyncRepository {
+    inner: ThreadSafeRepository,
+}
+
+impl std::fmt::Debug for AsyncRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncRepository")
+            .field("git_dir", &self.inner.git_dir())
+            .finish()
+    }
+}
+
+impl From<ThreadSafeRepository> for AsyncRepository {
+    fn from(inner: ThreadSafeRepository) -> Self {
+        AsyncRepository { inner }
+    }
+}
+
+impl From<AsyncRepository> for ThreadSafeRepository {
+    fn from(repo: AsyncRepository) -> Self {
+        repo.inner
+    }
+}
+
+/// Lifecycle
+impl AsyncRepository {
+    /// Open the repository at `directory` just like [`crate::open()`] would.
+    pub async fn open(directory: impl Into<PathBuf>) -> Result<Self, crate::open::Error> {
+        let directory = directory.into();
+        blocking::unblock(move || ThreadSafeRepository::open(directory))
+            .await
+            .map(Into::into)
+    }
+
+    /// Discover the repository at or above `directory` just like [`crate::discover()`] would.
+    pub async fn discover(directory: impl Into<PathBuf>) -> Result<Self, crate::discover::Error> {
+        let directory = directory.into();
+        blocking::unblock(move || ThreadSafeRepository::discover(directory))
+            .await
+            .map(Into::into)
+    }
+
+    /// Return the thread-safe repository this instance is wrapping.
+    pub fn thread_safe(&self) -> &ThreadSafeRepository {
+        &self.inner
+    }
+
+    /// Run `f` with a thread-local repository instance on the blocking thread-pool and return its result.
+    ///
+    /// Use this to perform all operations that don't have a dedicated method here.
+    pub async fn with_repo<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&Repository) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let repo = self.inner.clone();
+        blocking::unblock(move || f(&repo.to_thread_local())).await
+    }
+}
+
+/// Object access
+impl AsyncRepository {
+    /// Find the object with `id` in the object database and return it without ties to any repository.
+    ///
+    /// Note that none of the object database backends supports async IO natively, which is why the lookup is performed
+    /// on the blocking thread-pool.
+    pub async fn find_object(
+        &self,
+        id: impl Into<ObjectId>,
+    ) -> Result<ObjectDetached, crate::object::find::existing::Error> {
+        let id = id.into();
+        self.with_repo(move |repo| repo.find_object(id).map(crate::Object::detach))
+            .await
+    }
+
+    /// Return `true` if the object with `id` exists in the object database.
+    pub async fn has_object(&self, id: impl Into<ObjectId>) -> bool {
+        let id = id.into();
+        self.with_repo(move |repo| repo.has_object(id)).await
+    }
+}
+
+/// Remote operations
+impl AsyncRepository {
+    /// List the references of the remote identified by `name_or_url`, or of the default remote if `None`, similar to `git ls-remote`.
+    pub async fn ls_refs(
+        &self,
+        name_or_url: Option<BString>,
+    ) -> Result<Vec<gix_protocol::handshake::Ref>, ls_refs::Error> {
+        self.with_repo(move |repo| {
+            let remote = repo.find_fetch_remote(name_or_url.as_ref().map(AsRef::as_ref))?;
+            let map = remote.connect(crate::remote::Direction::Fetch)?.ref_map(
+                gix_features::progress::Discard,
+                crate::remote::ref_map::Options {
+                    prefix_from_spec_as_filter_on_remote: false,
+                    ..Default::default()
+                },
+            )?;
+            Ok(map.remote_refs)
+        })
+        .await
+    }
+
+    /// Fetch from the remote identified by `name_or_url`, or from the default remote if `None`, similar to `git fetch`.
+    ///
+    /// `should_interrupt` can be set at any time to abort the operation.
+    pub async fn fetch(
+        &self,
+        name_or_url: Option<BString>,
+        should_interrupt: Arc<AtomicBool>,
+    ) -> Result<crate::remote::fetch::Outcome, fetch::Error> {
+        self.with_repo(move |repo| {
+            let remote = repo.find_fetch_remote(name_or_url.as_ref().map(AsRef::as_ref))?;
+            let outcome = remote
+                .connect(crate::remote::Direction::Fetch)?
+                .prepare_fetch(gix_features::progress::Discard, Default::default())?
+                .receive(gix_features::progress::Discard, &should_interrupt)?;
+            Ok(outcome)
+        })
+        .await
+    }
+
+    /// Clone the repository at `url` into `path`, which must be empty or not exist, with `kind` determining if
+    /// a worktree is checked out.
+    ///
+    /// `should_interrupt` can be set at any time to abort the operation, which will also remove all data written so far.
+    pub async fn clone_repository(
+        url: gix_url::Url,
+        path: impl Into<PathBuf>,
+        kind: crate::create::Kind,
+        should_interrupt: Arc<AtomicBool>,
+    ) -> Result<(Self, crate::remote::fetch::Outcome), clone::Error> {
+        let path = path.into();
+        blocking::unblock(move || {
+            let mut prepare = crate::clone::PrepareFetch::new(url, path, kind, Default::default(), Default::default())?;
+            let (repo, outcome) = match kind {
+                crate::create::Kind::Bare => prepare.fetch_only(gix_features::progress::Discard, &should_interrupt)?,
+                crate::create::Kind::WithWorktree => {
+                    let (mut checkout, outcome) =
+                        prepare.fetch_then_checkout(gix_features::progress::Discard, &should_interrupt)?;
+                    let (repo, _) = checkout.main_worktree(gix_features::progress::Discard, &should_interrupt)?;
+                    (repo, outcome)
+                }
+            };
+            Ok((repo.into_sync().into(), outcome))
+        })
+        .await
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod ls_refs {
+    /// The error returned by [`AsyncRepository::ls_refs()`](super::AsyncRepository::ls_refs()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::for_fetch::Error),
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        RefMap(#[from] crate::remote::ref_map::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod fetch {
+    /// The error returned by [`AsyncRepository::fetch()`](super::AsyncRepository::fetch()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::for_fetch::Error),
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::remote::fetch::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod clone {
+    /// The error returned by [`AsyncRepository::clone_repository()`](super::AsyncRepository::clone_repository()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Prepare(#[from] crate::clone::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::clone::fetch::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::clone::checkout::main_worktree::Error),
+    }
+}
@@ -35,6 +35,71 @@ impl PrepareFetch {
         self
     }
 
+    /// If `mirror` is `true`, fetch all references of the remote into local references of the same name, like `git clone --mirror`.
+    ///
+    /// The remote will be configured as [mirror](crate::Remote::with_mirror()), so future fetches also delete local references
+    /// that don't exist on the remote anymore.
+    /// Note that the repository should be bare, as mirroring also overwrites local branches.
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// If `single_branch` is `true`, only fetch the branch to check out, which is the one set with
+    /// [`with_ref_name()`](Self::with_ref_name()) or the one the remote `HEAD` points to, like `git clone --single-branch`.
+    ///
+    /// The remote will be configured to only fetch this branch in future as well, and only tags pointing into its history
+    /// are fetched unless [configured otherwise](Self::with_fetch_tags()).
+    /// This has no effect on [mirrors](Self::with_mirror()).
+    ///
+    /// # Deviation
+    ///
+    /// Unlike `git`, [shallow clones](Self::with_shallow()) don't imply fetching only a single branch.
+    pub fn with_single_branch(mut self, single_branch: bool) -> Self {
+        self.single_branch = single_branch;
+        self
+    }
+
+    /// Fetch tags as described by `tags` and configure the remote to do so in future as well, so
+    /// [`Tags::None`](crate::remote::fetch::Tags::None) is similar to `git clone --no-tags`.
+    ///
+    /// This overrides changes to the tags to fetch done in [`configure_remote()`](Self::configure_remote()).
+    /// If not set, all tags are fetched during the clone, while the remote will only fetch tags pointing into the history
+    /// of fetched branches in future.
+    pub fn with_fetch_tags(mut self, tags: crate::remote::fetch::Tags) -> Self {
+        self.fetch_tags = Some(tags);
+        self
+    }
+
+    /// Determine how to obtain objects if the remote is a repository on the local filesystem, which is much faster than
+    /// receiving a pack of all of them.
+    ///
+    /// Nothing changes if the remote isn't local, or if the clone or the repository to clone from are [shallow](Self::with_shallow()).
+    pub fn with_local_objects(mut self, local_objects: crate::clone::LocalObjects) -> Self {
+        self.local_objects = local_objects;
+        self
+    }
+
+    /// Use the objects of the repository at `path` as alternate so that only the objects it doesn't have are received,
+    /// like `git clone --reference`.
+    ///
+    /// Note that the clone will be corrupted if objects it needs are removed from the repository at `path`.
+    pub fn with_reference_repository(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.reference_repositories.push(path.into());
+        self
+    }
+
+    /// If `dissociate` is `true`, copy all objects borrowed from [reference repositories](Self::with_reference_repository())
+    /// or a [shared](crate::clone::LocalObjects::Shared) local repository into the new repository once the fetch is done,
+    /// and stop using them as alternates, like `git clone --dissociate`.
+    ///
+    /// See [`Repository::dissociate()`](crate::Repository::dissociate()) for details.
+    #[cfg(feature = "repack")]
+    pub fn with_dissociate(mut self, dissociate: bool) -> Self {
+        self.dissociate = dissociate;
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
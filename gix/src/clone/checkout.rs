@@ -143,7 +143,7 @@ pub mod main_worktree {
             files.show_throughput(start);
             bytes.show_throughput(start);
 
-            index.write(Default::default())?;
+            index.write(repo.index_write_options())?;
             Ok((self.repo.take().expect("still present").clone(), outcome))
         }
     }
@@ -30,6 +30,18 @@ pub enum Error {
     SaveConfig(#[from] crate::remote::save::AsError),
     #[error("Failed to write repository configuration to disk")]
     SaveConfigIo(#[from] std::io::Error),
+    #[error("Could not open the local repository to obtain objects from")]
+    OpenLocalRepository(#[from] crate::open::Error),
+    #[error("Could not obtain objects from the local repository at '{}'", path.display())]
+    LocalObjects {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error(transparent)]
+    Alternates(#[from] gix_odb::alternate::Error),
+    #[cfg(feature = "repack")]
+    #[error("Could not copy borrowed objects into the new repository")]
+    Dissociate(#[from] crate::repack::Error),
     #[error("The remote HEAD points to a reference named {head_ref_name:?} which is invalid.")]
     InvalidHeadRef {
         source: gix_validate::reference::name::Error,
@@ -46,6 +58,31 @@ pub enum Error {
     },
 }
 
+/// Information about how [`PrepareFetch::fetch_only()`] configured the new repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Configured {
+    /// The name of the remote that was cloned from.
+    pub remote_name: BString,
+    /// The refspecs the remote is configured to fetch with in future.
+    pub fetch_specs: Vec<gix_refspec::RefSpec>,
+    /// How the remote is configured to fetch tags in future.
+    pub fetch_tags: crate::remote::fetch::Tags,
+    /// If `true`, the remote is configured as mirror.
+    pub mirror: bool,
+    /// The name of the reference `HEAD` points to, or `None` if it's detached.
+    pub head_name: Option<gix_ref::FullName>,
+    /// If `true`, the repository is shallow.
+    pub shallow: bool,
+}
+
+/// Access
+impl PrepareFetch {
+    /// Return information about how the new repository was configured, once [`fetch_only()`](Self::fetch_only()) succeeded.
+    pub fn configured(&self) -> Option<&Configured> {
+        self.configured.as_ref()
+    }
+}
+
 /// Modification
 impl PrepareFetch {
     /// Fetch a pack and update local branches according to refspecs, providing `progress` and checking `should_interrupt` to stop
@@ -82,6 +119,7 @@ impl PrepareFetch {
         should_interrupt: &std::sync::atomic::AtomicBool,
     ) -> Result<(crate::Repository, crate::remote::fetch::Outcome), Error> {
         use crate::{bstr::ByteVec, remote, remote::fetch::RefLogMessage};
+        let _span = gix_trace::coarse!("gix::clone::PrepareFetch::fetch_only()");
 
         let repo = self
             .repo
@@ -105,19 +143,31 @@ impl PrepareFetch {
                 .unwrap_or_else(|| "origin".into()),
         };
 
-        let mut remote = repo
-            .remote_at(self.url.clone())?
-            .with_refspecs(
-                Some(format!("+refs/heads/*:refs/remotes/{remote_name}/*").as_str()),
-                remote::Direction::Fetch,
-            )
-            .expect("valid static spec");
+        let mut remote = repo.remote_at(self.url.clone())?;
+        let single_branch = self.single_branch && !self.mirror;
+        remote = if self.mirror {
+            remote.with_mirror(true)
+        } else if single_branch {
+            // The refspec is known only once we know the branch to fetch.
+            remote
+        } else {
+            remote
+                .with_refspecs(
+                    Some(format!("+refs/heads/*:refs/remotes/{remote_name}/*").as_str()),
+                    remote::Direction::Fetch,
+                )
+                .expect("valid static spec")
+        };
         let mut clone_fetch_tags = None;
         if let Some(f) = self.configure_remote.as_mut() {
             remote = f(remote).map_err(Error::RemoteConfiguration)?;
-        } else {
+        } else if !single_branch {
             clone_fetch_tags = remote::fetch::Tags::All.into();
         }
+        if let Some(fetch_tags) = self.fetch_tags {
+            remote = remote.with_fetch_tags(fetch_tags);
+            clone_fetch_tags = None;
+        }
 
         let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
 
@@ -125,15 +175,35 @@ impl PrepareFetch {
         if let Some(fetch_tags) = clone_fetch_tags {
             remote = remote.with_fetch_tags(fetch_tags);
         }
+        if single_branch && remote.refspecs(remote::Direction::Fetch).is_empty() {
+            // `HEAD` and the reference to check out are fetched anyway, the refspec is just needed for a valid remote.
+            remote
+                .replace_refspecs(Some("HEAD"), remote::Direction::Fetch)
+                .expect("valid static spec");
+        }
 
         // Add HEAD after the remote was written to config, we need it to know what to check out later, and assure
         // the ref that HEAD points to is present no matter what.
+        // Mirrors don't have remote tracking branches, so HEAD is only listed.
         let head_refspec = gix_refspec::parse(
-            format!("HEAD:refs/remotes/{remote_name}/HEAD").as_str().into(),
+            if self.mirror {
+                "HEAD".into()
+            } else {
+                format!("HEAD:refs/remotes/{remote_name}/HEAD")
+            }
+            .as_str()
+            .into(),
             gix_refspec::parse::Operation::Fetch,
         )
         .expect("valid")
         .to_owned();
+        util::obtain_local_objects(
+            repo,
+            &self.url,
+            self.local_objects,
+            &self.reference_repositories,
+            &self.shallow,
+        )?;
         let pending_pack: remote::fetch::Prepare<'_, '_, _> = {
             let mut connection = remote.connect(remote::Direction::Fetch).await?;
             if let Some(f) = self.configure_connection.as_mut() {
@@ -171,6 +241,7 @@ impl PrepareFetch {
         };
         let outcome = pending_pack
             .with_write_packed_refs_only(true)
+            .with_write_fetch_head(false)
             .with_reflog_message(RefLogMessage::Override {
                 message: reflog_message.clone(),
             })
@@ -179,6 +250,15 @@ impl PrepareFetch {
             .await?;
 
         util::append_config_to_repo_config(repo, config);
+        if single_branch {
+            util::configure_single_branch(
+                repo,
+                &outcome.ref_map,
+                reflog_message.as_ref(),
+                remote_name.as_ref(),
+                self.ref_name.as_ref(),
+            )?;
+        }
         util::update_head(
             repo,
             &outcome.ref_map,
@@ -186,6 +266,22 @@ impl PrepareFetch {
             remote_name.as_ref(),
             self.ref_name.as_ref(),
         )?;
+        #[cfg(feature = "repack")]
+        if self.dissociate {
+            repo.dissociate(progress, should_interrupt)?;
+        }
+
+        let remote = repo
+            .find_remote(remote_name.as_bstr())
+            .expect("remote was just created and must be visible in config");
+        self.configured = Some(Configured {
+            fetch_specs: remote.fetch_specs.clone(),
+            fetch_tags: remote.fetch_tags(),
+            mirror: remote.mirror(),
+            head_name: repo.head_name().ok().flatten(),
+            shallow: repo.is_shallow(),
+            remote_name,
+        });
 
         Ok((self.repo.take().expect("still present"), outcome))
     }
@@ -1,4 +1,8 @@
-use std::{borrow::Cow, io::Write};
+use std::{
+    borrow::Cow,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use gix_ref::{
     transaction::{LogChange, RefLog},
@@ -72,27 +76,7 @@ pub fn update_head(
         transaction::{PreviousValue, RefEdit},
         Target,
     };
-    let head_info = match ref_name {
-        Some(ref_name) => Some(find_custom_refname(ref_map, ref_name)?),
-        None => ref_map.remote_refs.iter().find_map(|r| {
-            Some(match r {
-                gix_protocol::handshake::Ref::Symbolic {
-                    full_ref_name,
-                    target,
-                    tag: _,
-                    object,
-                } if full_ref_name == "HEAD" => (Some(object.as_ref()), Some(target.as_bstr())),
-                gix_protocol::handshake::Ref::Direct { full_ref_name, object } if full_ref_name == "HEAD" => {
-                    (Some(object.as_ref()), None)
-                }
-                gix_protocol::handshake::Ref::Unborn { full_ref_name, target } if full_ref_name == "HEAD" => {
-                    (None, Some(target.as_bstr()))
-                }
-                _ => return None,
-            })
-        }),
-    };
-    let Some((head_peeled_id, head_ref)) = head_info else {
+    let Some((head_peeled_id, head_ref)) = head_info(ref_map, ref_name)? else {
         return Ok(());
     };
 
@@ -183,10 +167,237 @@ pub fn update_head(
     Ok(())
 }
 
+/// Make the objects of all `reference_repositories` available in `repo` as alternates, along with the ones of the repository
+/// at `url` as described by `local_objects` if it is on the local filesystem, so they don't have to be received.
+///
+/// Objects aren't obtained from shallow repositories, nor for `shallow` clones.
+pub fn obtain_local_objects(
+    repo: &Repository,
+    url: &gix_url::Url,
+    local_objects: crate::clone::LocalObjects,
+    reference_repositories: &[PathBuf],
+    shallow: &crate::remote::fetch::Shallow,
+) -> Result<(), Error> {
+    use crate::clone::LocalObjects;
+
+    let mut alternates = Vec::new();
+    for path in reference_repositories {
+        let reference = crate::open_opts(path, crate::open::Options::isolated())?;
+        alternates.push(absolute(reference.objects.store_ref().path())?);
+    }
+
+    if url.scheme == gix_url::Scheme::File
+        && local_objects != LocalObjects::Fetch
+        && matches!(shallow, crate::remote::fetch::Shallow::NoChange)
+    {
+        let source = crate::open_opts(
+            gix_path::from_bstr(url.path.as_bstr()),
+            crate::open::Options::isolated(),
+        )?;
+        if !source.is_shallow() {
+            let objects_dir = source.objects.store_ref().path().to_owned();
+            if local_objects == LocalObjects::Shared {
+                alternates.push(absolute(&objects_dir)?);
+            } else {
+                // Objects in alternates aren't copied, so the alternates themselves are needed.
+                for alternate in
+                    gix_odb::alternate::resolve(objects_dir.clone(), source.options.current_dir_or_empty())?
+                {
+                    alternates.push(absolute(&alternate)?);
+                }
+                let mut dest = repo.objects.store_ref().path().to_owned();
+                copy_objects(
+                    &objects_dir,
+                    &objects_dir,
+                    &mut dest,
+                    local_objects == LocalObjects::Hardlink,
+                )?;
+            }
+        }
+    }
+
+    let alternates = without_transitive_alternates(alternates)?;
+    if alternates.is_empty() {
+        return Ok(());
+    }
+    let alternates_path = repo.objects.store_ref().path().join("info").join("alternates");
+    let mut buf = Vec::new();
+    for alternate in alternates {
+        buf.extend_from_slice(&gix_path::into_bstr(alternate));
+        buf.push(b'\n');
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&alternates_path)
+        .and_then(|mut file| file.write_all(&buf))
+        .map_err(|err| Error::LocalObjects {
+            source: err,
+            path: alternates_path,
+        })
+}
+
+/// Remove all `alternates` that are also alternates of another one, as repeated object databases are considered cycles.
+fn without_transitive_alternates(alternates: Vec<PathBuf>) -> Result<Vec<PathBuf>, Error> {
+    let realpath = |path: &Path| gix_path::realpath(path).map_err(gix_odb::alternate::Error::from);
+    let mut transitive = Vec::new();
+    for alternate in &alternates {
+        for path in gix_odb::alternate::resolve(alternate.clone(), Path::new(""))? {
+            transitive.push(realpath(&path)?);
+        }
+    }
+    let mut out: Vec<PathBuf> = Vec::with_capacity(alternates.len());
+    let mut seen = Vec::new();
+    for alternate in alternates {
+        let path = realpath(&alternate)?;
+        if !transitive.contains(&path) && !seen.contains(&path) {
+            seen.push(path);
+            out.push(alternate);
+        }
+    }
+    Ok(out)
+}
+
+/// Return `path` as absolute path, as relative alternates would be resolved relative to the new object database.
+fn absolute(path: &Path) -> Result<PathBuf, Error> {
+    let cwd = gix_fs::current_dir(false).map_err(|err| Error::LocalObjects {
+        source: err,
+        path: path.to_owned(),
+    })?;
+    let path = cwd.join(path);
+    Ok(gix_path::normalize(path.as_path().into(), &cwd).map_or_else(|| path.clone(), Cow::into_owned))
+}
+
+/// Copy or `hardlink` all files in `dir`, which is in the object database at `objects_dir`, into `dest` recursively,
+/// skipping files starting with a dot and the `info/alternates` file.
+fn copy_objects(objects_dir: &Path, dir: &Path, dest: &mut PathBuf, hardlink: bool) -> Result<(), Error> {
+    let err = |path: &Path| {
+        let path = path.to_owned();
+        move |err| Error::LocalObjects { source: err, path }
+    };
+    for entry in std::fs::read_dir(dir).map_err(err(dir))? {
+        let entry = entry.map_err(err(dir))?;
+        let path = entry.path();
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') || path == objects_dir.join("info").join("alternates") {
+            continue;
+        }
+        dest.push(name);
+        if entry.file_type().map_err(err(&path))?.is_dir() {
+            std::fs::create_dir_all(&dest).map_err(err(dest))?;
+            copy_objects(objects_dir, &path, dest, hardlink)?;
+        } else if !hardlink || std::fs::hard_link(&path, &dest).is_err() {
+            std::fs::copy(&path, &dest).map_err(err(&path))?;
+        }
+        dest.pop();
+    }
+    Ok(())
+}
+
+/// The id and name of a reference on the remote, if known.
+type RefInfo<'a> = (Option<&'a gix_hash::oid>, Option<&'a BStr>);
+
+/// Return the id and name of the reference to check out, which is `ref_name` if set or the remote `HEAD` otherwise, or `None`
+/// if the remote didn't have a `HEAD`.
+fn head_info<'a>(
+    ref_map: &'a crate::remote::fetch::RefMap,
+    ref_name: Option<&PartialName>,
+) -> Result<Option<RefInfo<'a>>, Error> {
+    Ok(match ref_name {
+        Some(ref_name) => Some(find_custom_refname(ref_map, ref_name)?),
+        None => ref_map.remote_refs.iter().find_map(|r| {
+            Some(match r {
+                gix_protocol::handshake::Ref::Symbolic {
+                    full_ref_name,
+                    target,
+                    tag: _,
+                    object,
+                } if full_ref_name == "HEAD" => (Some(object.as_ref()), Some(target.as_bstr())),
+                gix_protocol::handshake::Ref::Direct { full_ref_name, object } if full_ref_name == "HEAD" => {
+                    (Some(object.as_ref()), None)
+                }
+                gix_protocol::handshake::Ref::Unborn { full_ref_name, target } if full_ref_name == "HEAD" => {
+                    (None, Some(target.as_bstr()))
+                }
+                _ => return None,
+            })
+        }),
+    })
+}
+
+/// Configure the remote of `remote_name` to only fetch the reference to check out, which is `ref_name` or the one the remote `HEAD`
+/// points to, and create its remote tracking branch if it is a branch.
+/// Nothing is configured if the remote `HEAD` is detached.
+pub fn configure_single_branch(
+    repo: &mut Repository,
+    ref_map: &crate::remote::fetch::RefMap,
+    reflog_message: &BStr,
+    remote_name: &BStr,
+    ref_name: Option<&PartialName>,
+) -> Result<(), Error> {
+    let Some((id, Some(name))) = head_info(ref_map, ref_name)? else {
+        return Ok(());
+    };
+    let name: gix_ref::FullName = name.try_into().map_err(|err| Error::InvalidHeadRef {
+        head_ref_name: name.to_owned(),
+        source: err,
+    })?;
+    let (spec, tracking_branch) = match name.category_and_short_name() {
+        Some((gix_ref::Category::LocalBranch, short_name)) => {
+            let mut tracking_branch: BString = "refs/remotes/".into();
+            tracking_branch.extend_from_slice(remote_name);
+            tracking_branch.push(b'/');
+            tracking_branch.extend_from_slice(short_name);
+            let mut spec: BString = "+".into();
+            spec.extend_from_slice(name.as_bstr());
+            spec.push(b':');
+            spec.extend_from_slice(&tracking_branch);
+            (spec, Some(tracking_branch))
+        }
+        _ => {
+            let mut spec: BString = "+".into();
+            spec.extend_from_slice(name.as_bstr());
+            spec.push(b':');
+            spec.extend_from_slice(name.as_bstr());
+            (spec, None)
+        }
+    };
+
+    if let (Some(tracking_branch), Some(id)) = (tracking_branch, id) {
+        repo.edit_reference(gix_ref::transaction::RefEdit {
+            change: gix_ref::transaction::Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: reflog_message.to_owned(),
+                },
+                expected: gix_ref::transaction::PreviousValue::Any,
+                new: gix_ref::Target::Peeled(id.to_owned()),
+            },
+            name: tracking_branch.try_into().map_err(|err| Error::InvalidHeadRef {
+                head_ref_name: name.as_bstr().to_owned(),
+                source: err,
+            })?,
+            deref: false,
+        })?;
+    }
+
+    let mut config = repo.config_snapshot_mut();
+    config
+        .section_mut_or_create_new_filter("remote", Some(remote_name), &mut |meta| {
+            meta.source == gix_config::Source::Local
+        })
+        .expect("remote name was validated")
+        .push("fetch".try_into().expect("valid at compile time"), Some(spec.as_ref()));
+    write_to_local_config(&config, WriteMode::Overwrite)?;
+    config.commit().expect("configuration we set is valid");
+    Ok(())
+}
+
 pub(super) fn find_custom_refname<'a>(
     ref_map: &'a crate::remote::fetch::RefMap,
     ref_name: &PartialName,
-) -> Result<(Option<&'a gix_hash::oid>, Option<&'a BStr>), Error> {
+) -> Result<RefInfo<'a>, Error> {
     let group = gix_refspec::MatchGroup::from_fetch_specs(Some(
         gix_refspec::parse(ref_name.as_ref().as_bstr(), gix_refspec::parse::Operation::Fetch)
             .expect("partial names are valid refs"),
@@ -232,7 +443,7 @@ pub(super) fn find_custom_refname<'a>(
 }
 
 /// Set up the remote configuration for `branch` so that it points to itself, but on the remote, if and only if currently
-/// saved refspecs are able to match it.
+/// saved refspecs are able to match it and the remote isn't a mirror.
 /// For that we reload the remote of `remote_name` and use its `ref_specs` for match.
 fn setup_branch_config(
     repo: &mut Repository,
@@ -250,6 +461,9 @@ fn setup_branch_config(
     let remote = repo
         .find_remote(remote_name)
         .expect("remote was just created and must be visible in config");
+    if remote.mirror() {
+        return Ok(());
+    }
     let group = gix_refspec::MatchGroup::from_fetch_specs(remote.fetch_specs.iter().map(gix_refspec::RefSpec::to_ref));
     let null = gix_hash::ObjectId::null(repo.object_hash());
     let res = group.match_remotes(
@@ -37,6 +37,49 @@ pub struct PrepareFetch {
     /// The name of the reference to fetch. If `None`, the reference pointed to by `HEAD` will be checked out.
     #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
     ref_name: Option<gix_ref::PartialName>,
+    /// If `true`, all references of the remote will be mirrored into local references of the same name.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    mirror: bool,
+    /// If `true`, only the branch to check out will be fetched, now and in future.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    single_branch: bool,
+    /// How to fetch tags, now and in future, or `None` to fetch all tags once like `git` does.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    fetch_tags: Option<remote::fetch::Tags>,
+    /// How to obtain objects if the remote is a repository on the local filesystem.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    local_objects: LocalObjects,
+    /// Repositories whose objects are used as alternates to avoid receiving them.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    reference_repositories: Vec<std::path::PathBuf>,
+    /// If `true`, copy all objects from alternates into the new repository and stop using alternates once the fetch is done.
+    #[cfg(feature = "repack")]
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    dissociate: bool,
+    /// Information about how the repository was configured, available once the fetch succeeded.
+    #[cfg(any(feature = "async-network-client-async-std", feature = "blocking-network-client"))]
+    configured: Option<fetch::Configured>,
+}
+
+/// Determine how objects are obtained if the remote to clone from is a repository on the local filesystem,
+/// see [`PrepareFetch::with_local_objects()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalObjects {
+    /// Receive a pack with all objects as if the repository was on another machine, which is the default.
+    #[default]
+    Fetch,
+    /// Hardlink all files of the object database of the repository, or copy them if this isn't possible,
+    /// like `git clone --local`.
+    Hardlink,
+    /// Copy all files of the object database of the repository, like `git clone --local --no-hardlinks`.
+    ///
+    /// On filesystems that support it, copies may share their data with the original until either one is changed.
+    Copy,
+    /// Don't obtain any object, but use the object database of the repository as alternate, like `git clone --shared`.
+    ///
+    /// Note that the clone will be corrupted if objects it needs are removed from the repository, for instance
+    /// during garbage collection.
+    Shared,
 }
 
 /// The error returned by [`PrepareFetch::new()`].
@@ -96,7 +139,9 @@ impl PrepareFetch {
         open_opts: crate::open::Options,
     ) -> Result<Self, Error> {
         create_opts.destination_must_be_empty = true;
-        let mut repo = crate::ThreadSafeRepository::init_opts(path, kind, create_opts, open_opts)?.to_thread_local();
+        let mut repo =
+            crate::ThreadSafeRepository::init_opts(path, kind, create_opts, open_opts.for_other_repository())?
+                .to_thread_local();
         url.canonicalize(repo.options.current_dir_or_empty())
             .map_err(|err| Error::CanonicalizeUrl {
                 url: url.clone(),
@@ -126,6 +171,15 @@ impl PrepareFetch {
             configure_connection: None,
             shallow: remote::fetch::Shallow::NoChange,
             ref_name: None,
+            mirror: false,
+            single_branch: false,
+            fetch_tags: None,
+            local_objects: LocalObjects::default(),
+            reference_repositories: Vec::new(),
+            #[cfg(feature = "repack")]
+            dissociate: false,
+            #[cfg(any(feature = "async-network-client-async-std", feature = "blocking-network-client"))]
+            configured: None,
         })
     }
 }
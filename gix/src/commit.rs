@@ -20,6 +20,12 @@ pub enum Error {
     WriteObject(#[from] crate::object::write::Error),
     #[error(transparent)]
     ReferenceEdit(#[from] crate::reference::edit::Error),
+    #[cfg(feature = "attributes")]
+    #[error(transparent)]
+    CommitEncoding(#[from] crate::config::encoding::Error),
+    #[cfg(feature = "attributes")]
+    #[error("The commit message could not be converted to the encoding configured in i18n.commitEncoding")]
+    EncodeMessage(#[from] gix_filter::worktree::encode_to_worktree::Error),
 }
 
 ///
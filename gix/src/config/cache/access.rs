@@ -117,7 +117,7 @@ impl Cache {
             .copied()
     }
 
-    #[cfg(feature = "blob-diff")]
+    #[cfg(any(feature = "blob-diff", feature = "worktree-mutation"))]
     pub(crate) fn big_file_threshold(&self) -> Result<u64, config::unsigned_integer::Error> {
         Ok(self
             .resolved
@@ -178,6 +178,15 @@ impl Cache {
         })
     }
 
+    /// Return how to flush files of the kind selected by `component` to permanent storage when writing them,
+    /// or `None` if they shouldn't be flushed.
+    pub(crate) fn fsync(
+        &self,
+        component: impl FnOnce(&gix_fs::fsync::Components) -> bool,
+    ) -> Option<gix_fs::fsync::Method> {
+        component(&self.fsync_components).then_some(self.fsync_method)
+    }
+
     /// Returns (file-timeout, pack-refs timeout)
     pub(crate) fn lock_timeout(
         &self,
@@ -329,7 +338,9 @@ impl Cache {
             thread_limit,
             destination_is_initially_empty: false,
             overwrite_existing: false,
+            collision_policy: Default::default(),
             keep_going: false,
+            large_file_threshold_bytes: self.big_file_threshold()?,
             stat_options: self.stat_options().map_err(|err| match err {
                 config::stat_options::Error::ConfigCheckStat(err) => {
                     config::checkout_options::Error::ConfigCheckStat(err)
@@ -0,0 +1,72 @@
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Information about everything the configuration was loaded from, to be able to tell if it would be different if it was loaded again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Fingerprint {
+    /// All files that were or could have been loaded, along with their stamp at the time, or `None` if they didn't exist.
+    files: Vec<(PathBuf, Option<Stamp>)>,
+    /// A hash over all environment variables, as these may override configuration or change where it's loaded from.
+    environment: u64,
+    /// The name of the branch `HEAD` pointed to, for use in `includeIf.onbranch` conditions.
+    branch_name: Option<gix_ref::FullName>,
+}
+
+/// The modification time and size of a file, which are expected to change whenever the file is changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn at(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Stamp {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+impl Fingerprint {
+    /// Create a new instance from the `paths` of all configuration files that were or could have been loaded,
+    /// and the `branch_name` that `HEAD` pointed to when evaluating conditional includes.
+    pub(crate) fn new(paths: impl IntoIterator<Item = PathBuf>, branch_name: Option<&gix_ref::FullNameRef>) -> Self {
+        let mut paths: Vec<_> = paths.into_iter().collect();
+        paths.sort();
+        paths.dedup();
+        Fingerprint {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = Stamp::at(&path);
+                    (path, stamp)
+                })
+                .collect(),
+            environment: environment_hash(),
+            branch_name: branch_name.map(ToOwned::to_owned),
+        }
+    }
+
+    /// Return `true` if none of the inputs of the configuration changed, assuming `HEAD` now points to `branch_name`.
+    ///
+    /// Note that this is racy, just like any other check of the filesystem, and relies on modification times
+    /// to change with each write, unless the size of the file changes as well.
+    pub(crate) fn is_current(&self, branch_name: Option<&gix_ref::FullNameRef>) -> bool {
+        self.branch_name.as_ref().map(AsRef::as_ref) == branch_name
+            && self.environment == environment_hash()
+            && self.files.iter().all(|(path, stamp)| Stamp::at(path) == *stamp)
+    }
+}
+
+fn environment_hash() -> u64 {
+    let mut vars: Vec<_> = std::env::vars_os().collect();
+    vars.sort();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    vars.hash(&mut hasher);
+    hasher.finish()
+}
@@ -3,13 +3,13 @@ use std::{borrow::Cow, ffi::OsString};
 
 use gix_sec::Permission;
 
-use super::{interpolate_context, util, Error, StageOne};
+use super::{interpolate_context, util, Error, Fingerprint, StageOne};
 use crate::{
     bstr::BString,
     config,
     config::{
         cache::util::ApplyLeniency,
-        tree::{gitoxide, Core, Gitoxide, Http},
+        tree::{gitoxide, Core, Gitoxide, Http, Init},
         Cache,
     },
     open,
@@ -44,6 +44,7 @@ impl Cache {
             identity,
             objects,
         }: open::permissions::Environment,
+        local_repo_env: bool,
         attributes: open::permissions::Attributes,
         open::permissions::Config {
             git_binary: use_installation,
@@ -72,9 +73,11 @@ impl Cache {
             ..util::base_options(lossy, lenient_config)
         };
 
+        // Files that may affect the configuration even if they don't exist, to be able to detect their creation.
+        let mut candidate_paths = vec![git_dir.join("config")];
         let config = {
             let git_prefix = &git_prefix;
-            let mut metas = [
+            let metas = [
                 gix_config::source::Kind::GitInstallation,
                 gix_config::source::Kind::System,
                 gix_config::source::Kind::Global,
@@ -98,11 +101,13 @@ impl Cache {
                 source: *source,
                 level: 0,
                 trust: gix_sec::Trust::Full,
-            });
+            })
+            .collect::<Vec<_>>();
+            candidate_paths.extend(metas.iter().filter_map(|meta| meta.path.clone()));
 
             let err_on_nonexisting_paths = false;
             let mut globals = gix_config::File::from_paths_metadata_buf(
-                &mut metas,
+                &mut metas.into_iter(),
                 &mut buf,
                 err_on_nonexisting_paths,
                 gix_config::file::init::Options {
@@ -136,16 +141,33 @@ impl Cache {
                         source: gix_config::Source::Api,
                     })?;
             }
-            apply_environment_overrides(&mut globals, *git_prefix, http_transport, identity, objects)?;
+            apply_environment_overrides(
+                &mut globals,
+                *git_prefix,
+                http_transport,
+                identity,
+                objects,
+                local_repo_env,
+            )?;
             globals.set_meta(local_meta);
             globals
         };
+        candidate_paths.extend(config.include_paths(options.includes)?);
+        let fingerprint = Fingerprint::new(
+            candidate_paths.into_iter().chain(
+                config
+                    .sections()
+                    .filter_map(|section| section.meta().path.as_deref().map(ToOwned::to_owned)),
+            ),
+            branch_name,
+        );
 
         let hex_len = util::parse_core_abbrev(&config, object_hash).with_leniency(lenient_config)?;
 
         use util::config_bool;
         let reflog = util::query_refupdates(&config, lenient_config)?;
         let refs_namespace = util::query_refs_namespace(&config, lenient_config)?;
+        let (fsync_components, fsync_method) = util::query_fsync(&config, lenient_config)?;
         let ignore_case = config_bool(&config, &Core::IGNORE_CASE, "core.ignoreCase", false, lenient_config)?;
         let use_multi_pack_index = config_bool(
             &config,
@@ -158,6 +180,8 @@ impl Cache {
         let object_kind_hint = util::disambiguate_hint(&config, lenient_config)?;
         let (static_pack_cache_limit_bytes, pack_cache_bytes, object_cache_bytes) =
             util::parse_object_caches(&config, lenient_config, filter_config_section)?;
+        let (pack_cache_soft_limit, object_cache_soft_limit) =
+            util::parse_cache_soft_limits(&config, lenient_config, filter_config_section)?;
         // NOTE: When adding a new initial cache, consider adjusting `reread_values_and_clear_caches()` as well.
         Ok(Cache {
             resolved: config.into(),
@@ -168,8 +192,12 @@ impl Cache {
             static_pack_cache_limit_bytes,
             pack_cache_bytes,
             object_cache_bytes,
+            pack_cache_budget: gix_pack::cache::budget::Budget::new(pack_cache_soft_limit),
+            object_cache_budget: gix_pack::cache::budget::Budget::new(object_cache_soft_limit),
             reflog,
             refs_namespace,
+            fsync_components,
+            fsync_method,
             is_bare,
             ignore_case,
             hex_len,
@@ -186,6 +214,7 @@ impl Cache {
             url_scheme: Default::default(),
             #[cfg(feature = "blob-diff")]
             diff_algorithm: Default::default(),
+            fingerprint,
         })
     }
 
@@ -227,11 +256,14 @@ impl Cache {
         }
         let reflog = util::query_refupdates(config, self.lenient_config)?;
         let refs_namespace = util::query_refs_namespace(config, self.lenient_config)?;
+        let (fsync_components, fsync_method) = util::query_fsync(config, self.lenient_config)?;
 
         self.hex_len = hex_len;
         self.ignore_case = ignore_case;
         self.reflog = reflog;
         self.refs_namespace = refs_namespace;
+        self.fsync_components = fsync_components;
+        self.fsync_method = fsync_method;
 
         self.user_agent = Default::default();
         self.personas = Default::default();
@@ -246,6 +278,10 @@ impl Cache {
             self.pack_cache_bytes,
             self.object_cache_bytes,
         ) = util::parse_object_caches(config, self.lenient_config, self.filter_config_section)?;
+        let (pack_cache_soft_limit, object_cache_soft_limit) =
+            util::parse_cache_soft_limits(config, self.lenient_config, self.filter_config_section)?;
+        self.pack_cache_budget.set_soft_limit(pack_cache_soft_limit);
+        self.object_cache_budget.set_soft_limit(object_cache_soft_limit);
         #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
         {
             self.url_scheme = Default::default();
@@ -305,6 +341,7 @@ impl crate::Repository {
     fn apply_changed_values(&mut self) {
         self.refs.write_reflog = util::reflog_or_default(self.config.reflog, self.work_dir().is_some());
         self.refs.namespace.clone_from(&self.config.refs_namespace);
+        self.refs.fsync = self.config.fsync(|components| components.references);
     }
 }
 
@@ -314,10 +351,16 @@ fn apply_environment_overrides(
     http_transport: Permission,
     identity: Permission,
     objects: Permission,
+    local_repo_env: bool,
 ) -> Result<(), Error> {
     fn env(key: &'static dyn config::tree::Key) -> &'static str {
         key.the_environment_override()
     }
+    let local_repo_vars = [
+        env(&gitoxide::Core::INDEX_FILE),
+        env(&gitoxide::Objects::DIRECTORY),
+        env(&gitoxide::Objects::ALTERNATES),
+    ];
     fn var_as_bstring(var: &str, perm: Permission) -> Option<BString> {
         perm.check_opt(var)
             .and_then(std::env::var_os)
@@ -342,6 +385,14 @@ fn apply_environment_overrides(
                     let key = &Http::SSL_VERSION;
                     (env(key), key.name)
                 },
+                {
+                    let key = &Http::SSL_CERT;
+                    (env(key), key.name)
+                },
+                {
+                    let key = &Http::SSL_KEY;
+                    (env(key), key.name)
+                },
             ][..],
         ),
         (
@@ -353,6 +404,15 @@ fn apply_environment_overrides(
                 (env(key), key.name)
             }],
         ),
+        (
+            "init",
+            None,
+            git_prefix,
+            &[{
+                let key = &Init::TEMPLATE_DIR;
+                (env(key), key.name)
+            }],
+        ),
         (
             "gitoxide",
             Some(Cow::Borrowed("https".into())),
@@ -439,6 +499,10 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Core::SHALLOW_FILE;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Core::INDEX_FILE;
+                    (env(key), key.name)
+                },
                 {
                     let key = &gitoxide::Core::REFS_NAMESPACE;
                     (env(key), key.name)
@@ -507,6 +571,14 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Objects::CACHE_LIMIT;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Objects::DIRECTORY;
+                    (env(key), key.name)
+                },
+                {
+                    let key = &gitoxide::Objects::ALTERNATES;
+                    (env(key), key.name)
+                },
             ],
         ),
         (
@@ -565,6 +637,9 @@ fn apply_environment_overrides(
             .new_section(section_name, subsection_name)
             .expect("statically known valid section name");
         for (var, key) in data {
+            if !local_repo_env && local_repo_vars.contains(var) {
+                continue;
+            }
             if let Some(value) = var_as_bstring(var, permission) {
                 section.push_with_comment(
                     (*key).try_into().expect("statically known to be valid"),
@@ -5,6 +5,9 @@ pub(crate) use incubate::StageOne;
 
 mod init;
 
+mod fingerprint;
+pub(crate) use fingerprint::Fingerprint;
+
 impl std::fmt::Debug for Cache {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Cache").finish_non_exhaustive()
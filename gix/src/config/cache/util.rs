@@ -55,6 +55,34 @@ pub(crate) fn query_refupdates(
         .map_err(Into::into)
 }
 
+pub(crate) fn query_fsync(
+    config: &gix_config::File<'static>,
+    lenient_config: bool,
+) -> Result<(gix_fs::fsync::Components, gix_fs::fsync::Method), Error> {
+    let mut components = config
+        .string("core.fsync")
+        .map(|value| Core::FSYNC.try_into_components(value))
+        .transpose()
+        .with_leniency(lenient_config)?
+        .unwrap_or_default();
+    if config_bool(
+        config,
+        &Core::FSYNC_OBJECT_FILES,
+        "core.fsyncObjectFiles",
+        false,
+        lenient_config,
+    )? {
+        components.loose_objects = true;
+    }
+    let method = config
+        .string("core.fsyncMethod")
+        .map(|value| Core::FSYNC_METHOD.try_into_fsync_method(value))
+        .transpose()
+        .with_leniency(lenient_config)?
+        .unwrap_or_default();
+    Ok((components, method))
+}
+
 pub(crate) fn query_refs_namespace(
     config: &gix_config::File<'static>,
     lenient_config: bool,
@@ -103,6 +131,26 @@ pub(crate) fn parse_object_caches(
     Ok((static_pack_cache_limit, pack_cache_bytes, object_cache_bytes))
 }
 
+/// Return `(pack_cache_soft_limit, object_cache_soft_limit)` as parsed from gix-config, with `None` meaning there is no limit.
+pub(crate) fn parse_cache_soft_limits(
+    config: &gix_config::File<'static>,
+    lenient: bool,
+    mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
+) -> Result<(Option<usize>, Option<usize>), Error> {
+    let mut soft_limit = |key: &'static config::tree::keys::UnsignedInteger| {
+        config
+            .integer_filter(key, &mut filter_config_section)
+            .map(|res| key.try_into_usize(res))
+            .transpose()
+            .with_leniency(lenient)
+            .map(|limit| limit.filter(|limit| *limit != 0))
+    };
+    Ok((
+        soft_limit(&gitoxide::Objects::PACK_CACHE_SOFT_LIMIT)?,
+        soft_limit(&gitoxide::Objects::CACHE_SOFT_LIMIT)?,
+    ))
+}
+
 pub(crate) fn parse_core_abbrev(
     config: &gix_config::File<'static>,
     object_hash: gix_hash::Kind,
@@ -197,6 +197,8 @@ pub mod checkout_options {
         FilterPipelineOptions(#[from] crate::filter::pipeline::options::Error),
         #[error(transparent)]
         CommandContext(#[from] crate::config::command_context::Error),
+        #[error(transparent)]
+        BigFileThreshold(#[from] crate::config::unsigned_integer::Error),
     }
 }
 
@@ -384,7 +386,7 @@ pub mod key {
 pub mod encoding {
     use crate::bstr::BString;
 
-    /// The error produced when failing to parse the `core.checkRoundTripEncoding` key.
+    /// The error produced when failing to parse keys with encodings, like `core.checkRoundTripEncoding` or `i18n.commitEncoding`.
     #[derive(Debug, thiserror::Error)]
     #[error("The encoding named '{encoding}' seen in key '{key}={value}' is unsupported")]
     pub struct Error {
@@ -578,6 +580,8 @@ pub mod transport {
             InvalidHttpVersion(#[from] crate::config::key::GenericErrorWithValue),
             #[error("The follow redirects value 'initial', or boolean true or false")]
             InvalidFollowRedirects(#[source] crate::config::key::GenericErrorWithValue),
+            #[error("The empty auth value must be 'auto', or boolean true or false")]
+            InvalidEmptyAuth(#[source] crate::config::key::GenericErrorWithValue),
         }
     }
 }
@@ -601,6 +605,10 @@ pub(crate) struct Cache {
     pub reflog: Option<gix_ref::store::WriteReflog>,
     /// The representation of `gitoxide.core.refsNamespace`, or `None` if the variable wasn't set.
     pub refs_namespace: Option<gix_ref::Namespace>,
+    /// The kinds of files to flush to permanent storage when writing them, from `core.fsync` and `core.fsyncObjectFiles`.
+    pub(crate) fsync_components: gix_fs::fsync::Components,
+    /// How to flush files to permanent storage, from `core.fsyncMethod`.
+    pub(crate) fsync_method: gix_fs::fsync::Method,
     /// The configured user agent for presentation to servers.
     pub(crate) user_agent: OnceCell<String>,
     /// identities for later use, lazy initialization.
@@ -623,6 +631,10 @@ pub(crate) struct Cache {
     pub(crate) object_cache_bytes: usize,
     /// The amount of bytes we can hold in our static LRU cache. Otherwise, go with the defaults.
     pub(crate) static_pack_cache_limit_bytes: Option<usize>,
+    /// The tally of bytes held by all pack caches, shared with all clones of the owning repository.
+    pub(crate) pack_cache_budget: gix_pack::cache::budget::Budget,
+    /// The tally of bytes held by all object caches, shared with all clones of the owning repository.
+    pub(crate) object_cache_budget: gix_pack::cache::budget::Budget,
     /// The config section filter from the options used to initialize this instance. Keep these in sync!
     filter_config_section: fn(&gix_config::file::Metadata) -> bool,
     /// The object kind to pick if a prefix is ambiguous.
@@ -636,6 +648,8 @@ pub(crate) struct Cache {
     #[cfg_attr(not(feature = "worktree-mutation"), allow(dead_code))]
     attributes: crate::open::permissions::Attributes,
     environment: crate::open::permissions::Environment,
+    /// Information about the inputs of `resolved` to learn if it changed on disk.
+    pub(crate) fingerprint: cache::Fingerprint,
     // TODO: make core.precomposeUnicode available as well.
 }
 
@@ -48,7 +48,7 @@ impl Snapshot<'_> {
     }
 }
 
-pub(super) mod function {
+pub(crate) mod function {
     use crate::bstr::{ByteSlice, ByteVec};
     use crate::config::cache::util::ApplyLeniency;
     use crate::config::credential_helpers::Error;
@@ -215,7 +215,7 @@ pub(super) mod function {
         ))
     }
 
-    fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
+    pub(crate) fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
         match (pattern, host) {
             (Some(pattern), Some(host)) => {
                 let lfields = pattern.split('.');
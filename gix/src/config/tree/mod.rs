@@ -41,18 +41,29 @@ pub(crate) mod root {
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
         /// The `http` section.
         pub const HTTP: sections::Http = sections::Http;
+        /// The `i18n` section.
+        pub const I18N: sections::I18n = sections::I18n;
         /// The `index` section.
         pub const INDEX: sections::Index = sections::Index;
         /// The `init` section.
         pub const INIT: sections::Init = sections::Init;
         /// The `mailmap` section.
         pub const MAILMAP: sections::Mailmap = sections::Mailmap;
+        /// The `merge` section.
+        #[cfg(feature = "merge")]
+        pub const MERGE: sections::Merge = sections::Merge;
         /// The `pack` section.
         pub const PACK: sections::Pack = sections::Pack;
         /// The `protocol` section.
         pub const PROTOCOL: sections::Protocol = sections::Protocol;
+        /// The `pull` section.
+        #[cfg(feature = "merge")]
+        pub const PULL: sections::Pull = sections::Pull;
         /// The `push` section.
         pub const PUSH: sections::Push = sections::Push;
+        /// The `rebase` section.
+        #[cfg(feature = "rebase")]
+        pub const REBASE: sections::Rebase = sections::Rebase;
         /// The `remote` section.
         pub const REMOTE: sections::Remote = sections::Remote;
         /// The `safe` section.
@@ -83,12 +94,19 @@ pub(crate) mod root {
                 &Self::FETCH,
                 &Self::GITOXIDE,
                 &Self::HTTP,
+                &Self::I18N,
                 &Self::INDEX,
                 &Self::INIT,
                 &Self::MAILMAP,
+                #[cfg(feature = "merge")]
+                &Self::MERGE,
                 &Self::PACK,
                 &Self::PROTOCOL,
+                #[cfg(feature = "merge")]
+                &Self::PULL,
                 &Self::PUSH,
+                #[cfg(feature = "rebase")]
+                &Self::REBASE,
                 &Self::REMOTE,
                 &Self::SAFE,
                 &Self::SSH,
@@ -102,13 +120,17 @@ pub(crate) mod root {
 }
 
 mod sections;
+#[cfg(feature = "rebase")]
+pub use sections::Rebase;
 pub use sections::{
-    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, extensions, fetch, gitoxide, http, i18n, index, protocol, push, remote, ssh,
+    Author, Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, I18n, Index, Init,
+    Mailmap, Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
 };
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
+#[cfg(feature = "merge")]
+pub use sections::{merge, Merge, Pull};
 #[cfg(feature = "status")]
 pub use sections::{status, Status};
 
@@ -13,6 +13,11 @@ impl Branch {
     /// The `branch.<name>.remote` key.
     pub const REMOTE: keys::RemoteName = keys::RemoteName::new_remote_name("remote", &crate::config::Tree::BRANCH)
         .with_subsection_requirement(NAME_PARAMETER);
+    /// The `branch.<name>.rebase` key.
+    ///
+    /// Note that only boolean values are supported, while `merges` and `interactive` are not.
+    pub const REBASE: keys::Boolean =
+        keys::Boolean::new_boolean("rebase", &crate::config::Tree::BRANCH).with_subsection_requirement(NAME_PARAMETER);
 }
 
 impl Section for Branch {
@@ -21,7 +26,7 @@ impl Section for Branch {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::MERGE, &Self::PUSH_REMOTE, &Self::REMOTE]
+        &[&Self::MERGE, &Self::PUSH_REMOTE, &Self::REMOTE, &Self::REBASE]
     }
 }
 
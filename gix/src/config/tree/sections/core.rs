@@ -24,6 +24,15 @@ impl Core {
         Disambiguate::new_with_validate("disambiguate", &config::Tree::CORE, validate::Disambiguate);
     /// The `core.editor` key.
     pub const EDITOR: keys::Program = keys::Program::new_program("editor", &config::Tree::CORE);
+    /// The `core.fsync` key.
+    pub const FSYNC: Fsync = Fsync::new_with_validate("fsync", &config::Tree::CORE, validate::Fsync);
+    /// The `core.fsyncMethod` key.
+    pub const FSYNC_METHOD: FsyncMethod =
+        FsyncMethod::new_with_validate("fsyncMethod", &config::Tree::CORE, validate::FsyncMethod).with_deviation(
+            "'writeout-only' still waits for the storage device to persist the data, and 'batch' flushes each loose object when the batch is done",
+        );
+    /// The `core.fsyncObjectFiles` key, which is deprecated in favor of `core.fsync`.
+    pub const FSYNC_OBJECT_FILES: keys::Boolean = keys::Boolean::new_boolean("fsyncObjectFiles", &config::Tree::CORE);
     /// The `core.fileMode` key.
     pub const FILE_MODE: keys::Boolean = keys::Boolean::new_boolean("fileMode", &config::Tree::CORE);
     /// The `core.ignoreCase` key.
@@ -63,6 +72,8 @@ impl Core {
     pub const ASKPASS: keys::Executable = keys::Executable::new_executable("askPass", &config::Tree::CORE)
         .with_environment_override("GIT_ASKPASS")
         .with_note("fallback is 'SSH_ASKPASS'");
+    /// The `core.hooksPath` key.
+    pub const HOOKS_PATH: keys::Path = keys::Path::new_path("hooksPath", &config::Tree::CORE);
     /// The `core.excludesFile` key.
     pub const EXCLUDES_FILE: keys::Path = keys::Path::new_path("excludesFile", &config::Tree::CORE);
     /// The `core.attributesFile` key.
@@ -109,6 +120,9 @@ impl Section for Core {
             &Self::DELTA_BASE_CACHE_LIMIT,
             &Self::DISAMBIGUATE,
             &Self::EDITOR,
+            &Self::FSYNC,
+            &Self::FSYNC_METHOD,
+            &Self::FSYNC_OBJECT_FILES,
             &Self::FILE_MODE,
             &Self::IGNORE_CASE,
             &Self::FILES_REF_LOCK_TIMEOUT,
@@ -123,6 +137,7 @@ impl Section for Core {
             &Self::PROTECT_HFS,
             &Self::PROTECT_NTFS,
             &Self::ASKPASS,
+            &Self::HOOKS_PATH,
             &Self::EXCLUDES_FILE,
             &Self::ATTRIBUTES_FILE,
             &Self::SSH_COMMAND,
@@ -152,6 +167,12 @@ pub type LogAllRefUpdates = keys::Any<validate::LogAllRefUpdates>;
 /// The `core.disambiguate` key.
 pub type Disambiguate = keys::Any<validate::Disambiguate>;
 
+/// The `core.fsync` key.
+pub type Fsync = keys::Any<validate::Fsync>;
+
+/// The `core.fsyncMethod` key.
+pub type FsyncMethod = keys::Any<validate::FsyncMethod>;
+
 #[cfg(feature = "attributes")]
 mod filter {
     use super::validate;
@@ -184,9 +205,9 @@ mod filter {
             pub fn try_into_encodings(
                 &'static self,
                 value: Option<Cow<'_, BStr>>,
-            ) -> Result<Vec<&'static gix_filter::encoding::Encoding>, config::encoding::Error> {
+            ) -> Result<Vec<gix_filter::worktree::Encoding>, config::encoding::Error> {
                 Ok(match value {
-                    None => vec![gix_filter::encoding::SHIFT_JIS],
+                    None => vec![gix_filter::encoding::SHIFT_JIS.into()],
                     Some(value) => {
                         let mut out = Vec::new();
                         for encoding in value
@@ -194,15 +215,13 @@ mod filter {
                             .split(|b| *b == b',' || *b == b' ')
                             .filter(|e| !e.trim().is_empty())
                         {
-                            out.push(
-                                gix_filter::encoding::Encoding::for_label(encoding.trim()).ok_or_else(|| {
-                                    config::encoding::Error {
-                                        key: self.logical_name().into(),
-                                        value: value.as_ref().to_owned(),
-                                        encoding: encoding.into(),
-                                    }
-                                })?,
-                            );
+                            out.push(gix_filter::worktree::encoding::for_label(encoding.trim()).map_err(|_| {
+                                config::encoding::Error {
+                                    key: self.logical_name().into(),
+                                    value: value.as_ref().to_owned(),
+                                    encoding: encoding.into(),
+                                }
+                            })?);
                         }
                         out
                     }
@@ -357,6 +376,142 @@ mod log_all_ref_updates {
     }
 }
 
+mod fsync {
+    use std::borrow::Cow;
+
+    use gix_fs::fsync::{Components, Method};
+
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        config,
+        config::tree::core::{Fsync, FsyncMethod},
+    };
+
+    impl Fsync {
+        /// Parse the comma-separated list of components in `value`, each of which is added to the default components,
+        /// or removed from them if prefixed with `-`, while `none` removes all components.
+        ///
+        /// Like in `git`, the order of components doesn't matter as all removals are applied before all additions,
+        /// so `-pack,pack` and `pack,-pack` both flush packs.
+        pub fn try_into_components(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<Components, config::key::GenericErrorWithValue> {
+            let mut components = Components::default();
+            let (mut positive, mut negative) = (Components::none(), Components::none());
+            for token in value
+                .split_str(",")
+                .map(ByteSlice::trim)
+                .filter(|token| !token.is_empty())
+            {
+                let (token, negated) = match token.strip_prefix(b"-") {
+                    Some(token) => (token, true),
+                    None => (token, false),
+                };
+                let none = Components::none();
+                let selected = match token {
+                    b"none" if !negated => {
+                        components = none;
+                        continue;
+                    }
+                    b"loose-object" => Components {
+                        loose_objects: true,
+                        ..none
+                    },
+                    b"pack" => Components { packs: true, ..none },
+                    b"pack-metadata" => Components {
+                        pack_metadata: true,
+                        ..none
+                    },
+                    b"commit-graph" => Components {
+                        commit_graph: true,
+                        ..none
+                    },
+                    b"index" => Components { index: true, ..none },
+                    b"reference" => Components {
+                        references: true,
+                        ..none
+                    },
+                    b"objects" => Components {
+                        loose_objects: true,
+                        packs: true,
+                        ..none
+                    },
+                    b"derived-metadata" => Components {
+                        pack_metadata: true,
+                        commit_graph: true,
+                        ..none
+                    },
+                    b"committed" => Components {
+                        loose_objects: true,
+                        packs: true,
+                        references: true,
+                        ..none
+                    },
+                    b"added" => Components {
+                        loose_objects: true,
+                        packs: true,
+                        references: true,
+                        index: true,
+                        ..none
+                    },
+                    b"all" => Components::all(),
+                    _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+                };
+                let target = if negated { &mut negative } else { &mut positive };
+                for (component, is_selected) in [
+                    (&mut target.loose_objects, selected.loose_objects),
+                    (&mut target.packs, selected.packs),
+                    (&mut target.pack_metadata, selected.pack_metadata),
+                    (&mut target.commit_graph, selected.commit_graph),
+                    (&mut target.index, selected.index),
+                    (&mut target.references, selected.references),
+                ] {
+                    *component |= is_selected;
+                }
+            }
+            for (component, negative, positive) in [
+                (
+                    &mut components.loose_objects,
+                    negative.loose_objects,
+                    positive.loose_objects,
+                ),
+                (&mut components.packs, negative.packs, positive.packs),
+                (
+                    &mut components.pack_metadata,
+                    negative.pack_metadata,
+                    positive.pack_metadata,
+                ),
+                (
+                    &mut components.commit_graph,
+                    negative.commit_graph,
+                    positive.commit_graph,
+                ),
+                (&mut components.index, negative.index, positive.index),
+                (&mut components.references, negative.references, positive.references),
+            ] {
+                *component = (*component && !negative) || positive;
+            }
+            Ok(components)
+        }
+    }
+
+    impl FsyncMethod {
+        /// Convert `value` into the method to use for flushing files to permanent storage.
+        pub fn try_into_fsync_method(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<Method, config::key::GenericErrorWithValue> {
+            Ok(match value.as_ref().as_bytes() {
+                b"fsync" => Method::Fsync,
+                b"writeout-only" => Method::WriteoutOnly,
+                b"batch" => Method::Batch,
+                _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+            })
+        }
+    }
+}
+
 mod check_stat {
     use std::borrow::Cow;
 
@@ -472,6 +627,22 @@ mod validate {
         }
     }
 
+    pub struct Fsync;
+    impl keys::Validate for Fsync {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Core::FSYNC.try_into_components(value.into())?;
+            Ok(())
+        }
+    }
+
+    pub struct FsyncMethod;
+    impl keys::Validate for FsyncMethod {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Core::FSYNC_METHOD.try_into_fsync_method(value.into())?;
+            Ok(())
+        }
+    }
+
     pub struct CheckStat;
     impl keys::Validate for CheckStat {
         fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -10,10 +10,19 @@ impl Fetch {
         &config::Tree::FETCH,
         validate::NegotiationAlgorithm,
     );
+    /// The `fetch.parallel` key.
+    pub const PARALLEL: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("parallel", &config::Tree::FETCH);
+    /// The `fetch.prune` key.
+    pub const PRUNE: keys::Boolean = keys::Boolean::new_boolean("prune", &config::Tree::FETCH);
+    /// The `fetch.pruneTags` key.
+    pub const PRUNE_TAGS: keys::Boolean = keys::Boolean::new_boolean("pruneTags", &config::Tree::FETCH);
     /// The `fetch.recurseSubmodules` key.
     #[cfg(feature = "attributes")]
     pub const RECURSE_SUBMODULES: RecurseSubmodules =
         RecurseSubmodules::new_with_validate("recurseSubmodules", &config::Tree::FETCH, validate::RecurseSubmodules);
+    /// The `fetch.uriProtocols` key.
+    pub const URI_PROTOCOLS: keys::String = keys::String::new_string("uriProtocols", &config::Tree::FETCH);
 }
 
 impl Section for Fetch {
@@ -24,8 +33,12 @@ impl Section for Fetch {
     fn keys(&self) -> &[&dyn Key] {
         &[
             &Self::NEGOTIATION_ALGORITHM,
+            &Self::PARALLEL,
+            &Self::PRUNE,
+            &Self::PRUNE_TAGS,
             #[cfg(feature = "attributes")]
             &Self::RECURSE_SUBMODULES,
+            &Self::URI_PROTOCOLS,
         ]
     }
 }
@@ -114,6 +114,11 @@ mod subsections {
                 "relative file paths will always be made relative to the git-common-dir, whereas `git` keeps them as is.",
             );
 
+        /// The `gitoxide.core.indexFile` key.
+        pub const INDEX_FILE: keys::Path = keys::Path::new_path("indexFile", &Gitoxide::CORE)
+            .with_environment_override("GIT_INDEX_FILE")
+            .with_note("relative file paths are relative to the current working directory");
+
         /// The `gitoxide.core.filterProcessDelay` key (default `true`).
         ///
         /// It controls whether or not long running filter driver processes can use the 'delay' capability.
@@ -146,6 +151,7 @@ mod subsections {
                 &Self::USE_NSEC,
                 &Self::USE_STDEV,
                 &Self::SHALLOW_FILE,
+                &Self::INDEX_FILE,
                 &Self::PROTECT_WINDOWS,
                 &Self::FILTER_PROCESS_DELAY,
                 &Self::EXTERNAL_COMMAND_STDERR,
@@ -426,11 +432,29 @@ mod subsections {
             keys::UnsignedInteger::new_unsigned_integer("cacheLimit", &Gitoxide::OBJECTS)
                 .with_note("If unset or 0, there is no object cache")
                 .with_environment_override("GIX_OBJECT_CACHE_MEMORY");
+        /// The `gitoxide.objects.cacheSoftLimit` key.
+        pub const CACHE_SOFT_LIMIT: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("cacheSoftLimit", &Gitoxide::OBJECTS).with_note(
+                "The amount of bytes all object caches of a repository and its clones may hold together before they are cleared. If unset or 0, there is no limit",
+            );
+        /// The `gitoxide.objects.packCacheSoftLimit` key.
+        pub const PACK_CACHE_SOFT_LIMIT: keys::UnsignedInteger =
+            keys::UnsignedInteger::new_unsigned_integer("packCacheSoftLimit", &Gitoxide::OBJECTS).with_note(
+                "The amount of bytes all pack caches of a repository and its clones may hold together before they are cleared. If unset or 0, there is no limit",
+            );
         /// The `gitoxide.objects.noReplace` key.
         pub const NO_REPLACE: keys::Boolean = keys::Boolean::new_boolean("noReplace", &Gitoxide::OBJECTS);
         /// The `gitoxide.objects.replaceRefBase` key.
         pub const REPLACE_REF_BASE: keys::Any =
             keys::Any::new("replaceRefBase", &Gitoxide::OBJECTS).with_environment_override("GIT_REPLACE_REF_BASE");
+        /// The `gitoxide.objects.directory` key.
+        pub const DIRECTORY: keys::Path = keys::Path::new_path("directory", &Gitoxide::OBJECTS)
+            .with_environment_override("GIT_OBJECT_DIRECTORY")
+            .with_note("relative directories are relative to the current working directory");
+        /// The `gitoxide.objects.alternates` key.
+        pub const ALTERNATES: keys::Any = keys::Any::new("alternates", &Gitoxide::OBJECTS)
+            .with_environment_override("GIT_ALTERNATE_OBJECT_DIRECTORIES")
+            .with_note("a list of object directories separated like `PATH`, used in addition to the alternates of the object database");
     }
 
     impl Section for Objects {
@@ -439,7 +463,14 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::CACHE_LIMIT, &Self::REPLACE_REF_BASE]
+            &[
+                &Self::CACHE_LIMIT,
+                &Self::CACHE_SOFT_LIMIT,
+                &Self::PACK_CACHE_SOFT_LIMIT,
+                &Self::REPLACE_REF_BASE,
+                &Self::DIRECTORY,
+                &Self::ALTERNATES,
+            ]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
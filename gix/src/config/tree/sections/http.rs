@@ -33,6 +33,11 @@ impl Http {
     /// The `http.followRedirects` key.
     pub const FOLLOW_REDIRECTS: FollowRedirects =
         FollowRedirects::new_with_validate("followRedirects", &config::Tree::HTTP, validate::FollowRedirects);
+    /// The `http.emptyAuth` key.
+    pub const EMPTY_AUTH: EmptyAuth =
+        EmptyAuth::new_with_validate("emptyAuth", &config::Tree::HTTP, validate::EmptyAuth).with_deviation(
+            "'auto' is treated like 'false' as the authentication methods offered by the server aren't inspected",
+        );
     /// The `http.lowSpeedTime` key.
     pub const LOW_SPEED_TIME: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("lowSpeedTime", &config::Tree::HTTP)
@@ -51,6 +56,44 @@ impl Http {
     /// The `http.schannelCheckRevoke` key.
     pub const SCHANNEL_CHECK_REVOKE: keys::Boolean =
         keys::Boolean::new_boolean("schannelCheckRevoke", &config::Tree::HTTP);
+    /// The `http.sslCert` key.
+    pub const SSL_CERT: keys::Path =
+        keys::Path::new_path("sslCert", &config::Tree::HTTP).with_environment_override("GIT_SSL_CERT");
+    /// The `http.sslKey` key.
+    pub const SSL_KEY: keys::Path =
+        keys::Path::new_path("sslKey", &config::Tree::HTTP).with_environment_override("GIT_SSL_KEY");
+
+    /// The `http.<url>` subsection.
+    pub const URL_PARAMETER: UrlParameter = UrlParameter;
+}
+
+/// The `http.<url>` parameter section, whose keys override the ones in the `http` section if `<url>` matches the url
+/// to connect to.
+pub struct UrlParameter;
+
+impl UrlParameter {
+    /// The `http.<url>.sslVerify` key.
+    pub const SSL_VERIFY: keys::Boolean = keys::Boolean::new_boolean("sslVerify", &Http::URL_PARAMETER);
+    /// The `http.<url>.sslCAInfo` key.
+    pub const SSL_CA_INFO: keys::Path = keys::Path::new_path("sslCAInfo", &Http::URL_PARAMETER);
+    /// The `http.<url>.sslCert` key.
+    pub const SSL_CERT: keys::Path = keys::Path::new_path("sslCert", &Http::URL_PARAMETER);
+    /// The `http.<url>.sslKey` key.
+    pub const SSL_KEY: keys::Path = keys::Path::new_path("sslKey", &Http::URL_PARAMETER);
+}
+
+impl Section for UrlParameter {
+    fn name(&self) -> &str {
+        "<url>"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::SSL_VERIFY, &Self::SSL_CA_INFO, &Self::SSL_CERT, &Self::SSL_KEY]
+    }
+
+    fn parent(&self) -> Option<&dyn Section> {
+        Some(&config::Tree::HTTP)
+    }
 }
 
 impl Section for Http {
@@ -68,18 +111,28 @@ impl Section for Http {
             &Self::USER_AGENT,
             &Self::EXTRA_HEADER,
             &Self::FOLLOW_REDIRECTS,
+            &Self::EMPTY_AUTH,
             &Self::LOW_SPEED_TIME,
             &Self::LOW_SPEED_LIMIT,
             &Self::SCHANNEL_USE_SSL_CA_INFO,
             &Self::SSL_CA_INFO,
             &Self::SCHANNEL_CHECK_REVOKE,
+            &Self::SSL_CERT,
+            &Self::SSL_KEY,
         ]
     }
+
+    fn sub_sections(&self) -> &[&dyn Section] {
+        &[&Self::URL_PARAMETER]
+    }
 }
 
 /// The `http.followRedirects` key.
 pub type FollowRedirects = keys::Any<validate::FollowRedirects>;
 
+/// The `http.emptyAuth` key.
+pub type EmptyAuth = keys::Any<validate::EmptyAuth>;
+
 /// The `http.extraHeader` key.
 pub type ExtraHeader = keys::Any<validate::ExtraHeader>;
 
@@ -145,6 +198,30 @@ mod key_impls {
         }
     }
 
+    impl super::EmptyAuth {
+        /// Convert `value` into a flag to indicate whether or not authentication without credentials should be attempted,
+        /// or query the same value as `boolean` for additional possible input values.
+        ///
+        /// Note that `boolean` only queries the underlying key as boolean, which is a necessity to handle
+        /// empty booleans correctly, that is those without a value separator.
+        pub fn try_into_empty_auth(
+            &'static self,
+            value: std::borrow::Cow<'_, crate::bstr::BStr>,
+            boolean: impl FnOnce() -> Result<Option<bool>, gix_config::value::Error>,
+        ) -> Result<bool, crate::config::key::GenericErrorWithValue> {
+            use crate::bstr::ByteSlice;
+            Ok(if value.as_ref().as_bytes() == b"auto" {
+                false
+            } else {
+                boolean()
+                    .map_err(|err| {
+                        crate::config::key::GenericErrorWithValue::from_value(self, value.into_owned()).with_source(err)
+                    })?
+                    .unwrap_or_default()
+            })
+        }
+    }
+
     impl super::ExtraHeader {
         /// Convert a list of values into extra-headers, while failing entirely on illformed UTF-8.
         pub fn try_into_extra_header(
@@ -305,6 +382,16 @@ pub mod validate {
         }
     }
 
+    pub struct EmptyAuth;
+    impl Validate for EmptyAuth {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Http::EMPTY_AUTH.try_into_empty_auth(std::borrow::Cow::Borrowed(value), || {
+                gix_config::Boolean::try_from(value).map(|b| Some(b.0))
+            })?;
+            Ok(())
+        }
+    }
+
     pub struct FollowRedirects;
     impl Validate for FollowRedirects {
         fn validate(&self, _value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
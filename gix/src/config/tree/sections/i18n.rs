@@ -0,0 +1,62 @@
+use crate::{
+    config,
+    config::tree::{keys, I18n, Key, Section},
+};
+
+impl I18n {
+    /// The `i18n.commitEncoding` key.
+    pub const COMMIT_ENCODING: Encoding =
+        Encoding::new_with_validate("commitEncoding", &config::Tree::I18N, validate::Encoding);
+}
+
+impl Section for I18n {
+    fn name(&self) -> &str {
+        "i18n"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::COMMIT_ENCODING]
+    }
+}
+
+/// The `i18n.commitEncoding` key.
+pub type Encoding = keys::Any<validate::Encoding>;
+
+#[cfg(feature = "attributes")]
+mod encoding {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::BStr,
+        config,
+        config::tree::{i18n::Encoding, Key},
+    };
+
+    impl Encoding {
+        /// Convert `value` into the encoding it names, or fail if it is unknown.
+        pub fn try_into_encoding(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<gix_filter::worktree::Encoding, config::encoding::Error> {
+            gix_filter::worktree::encoding::for_label(value.as_ref()).map_err(|_| config::encoding::Error {
+                key: self.logical_name().into(),
+                value: value.as_ref().to_owned(),
+                encoding: value.as_ref().to_owned(),
+            })
+        }
+    }
+}
+
+mod validate {
+    use crate::{bstr::BStr, config::tree::keys};
+
+    pub struct Encoding;
+    impl keys::Validate for Encoding {
+        #[cfg_attr(not(feature = "attributes"), allow(unused_variables))]
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            #[cfg(feature = "attributes")]
+            super::super::I18n::COMMIT_ENCODING.try_into_encoding(value.into())?;
+            Ok(())
+        }
+    }
+}
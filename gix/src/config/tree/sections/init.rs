@@ -7,6 +7,10 @@ impl Init {
     /// The `init.defaultBranch` key.
     pub const DEFAULT_BRANCH: keys::Any = keys::Any::new("defaultBranch", &config::Tree::INIT)
         .with_deviation("If not set, we use `main` instead of `master`");
+    /// The `init.templateDir` key.
+    pub const TEMPLATE_DIR: keys::Path = keys::Path::new_path("templateDir", &config::Tree::INIT)
+        .with_environment_override("GIT_TEMPLATE_DIR")
+        .with_deviation("If not set, we use built-in templates instead of the ones of the `git` installation");
 }
 
 impl Section for Init {
@@ -15,6 +19,6 @@ impl Section for Init {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::DEFAULT_BRANCH]
+        &[&Self::DEFAULT_BRANCH, &Self::TEMPLATE_DIR]
     }
 }
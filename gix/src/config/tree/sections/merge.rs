@@ -0,0 +1,141 @@
+use crate::config;
+use crate::config::tree::sections::Merge;
+use crate::config::tree::{keys, Key, Section};
+
+impl Merge {
+    /// The `merge.conflictStyle` key
+    pub const CONFLICT_STYLE: ConflictStyle =
+        ConflictStyle::new_with_validate("conflictStyle", &config::Tree::MERGE, validate::ConflictStyle);
+    /// The `merge.ff` key
+    pub const FF: FastForward = FastForward::new_with_validate("ff", &config::Tree::MERGE, validate::FastForward);
+    /// The `merge.log` key
+    pub const LOG: Log = Log::new_with_validate("log", &config::Tree::MERGE, validate::Log);
+}
+
+/// The `merge.conflictStyle` key.
+pub type ConflictStyle = keys::Any<validate::ConflictStyle>;
+
+/// The `merge.ff` key.
+pub type FastForward = keys::Any<validate::FastForward>;
+
+/// The `merge.log` key.
+pub type Log = keys::Any<validate::Log>;
+
+mod conflict_style {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::merge::ConflictStyle};
+
+    impl ConflictStyle {
+        /// Obtain the conflict style to use when merging blobs.
+        ///
+        /// Note that `zdiff3` is treated like `diff3`, which only affects how much of the lines each side has in common
+        /// are shown as part of the conflict.
+        pub fn try_into_conflict_style(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<gix_merge::blob::ConflictStyle, config::key::GenericErrorWithValue> {
+            use crate::bstr::ByteSlice;
+            Ok(match value.as_ref().as_bytes() {
+                b"merge" => gix_merge::blob::ConflictStyle::Merge,
+                b"diff3" | b"zdiff3" => gix_merge::blob::ConflictStyle::Diff3,
+                _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+            })
+        }
+    }
+}
+
+mod fast_forward {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::merge::FastForward};
+
+    impl FastForward {
+        /// Obtain the policy for fast-forwarding when merging, with `only` meaning that only fast-forwards are allowed,
+        /// and `false` meaning that a merge commit is always created.
+        pub fn try_into_fast_forward(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<crate::merge::FastForward, config::key::GenericErrorWithValue> {
+            if value.as_ref() == "only" {
+                return Ok(crate::merge::FastForward::Only);
+            }
+            gix_config::Boolean::try_from(value.as_ref())
+                .map(|b| {
+                    if b.0 {
+                        crate::merge::FastForward::Allow
+                    } else {
+                        crate::merge::FastForward::Never
+                    }
+                })
+                .map_err(|_| config::key::GenericErrorWithValue::from_value(self, value.into_owned()))
+        }
+    }
+}
+
+mod log {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::BStr,
+        config::{key::GenericErrorWithValue, tree::merge::Log},
+    };
+
+    impl Log {
+        /// Parse `value` into the maximum amount of commits to list in the message of a merge commit, or `None` if
+        /// no commits should be listed. `true` is the same as `20`, just like in `git`.
+        pub fn try_into_shortlog_limit(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<Option<usize>, GenericErrorWithValue> {
+            gix_config::Integer::try_from(value.as_ref())
+                .ok()
+                .and_then(|i| i.to_decimal().and_then(|i| usize::try_from(i).ok()))
+                .map(|limit| (limit != 0).then_some(limit))
+                .or_else(|| {
+                    gix_config::Boolean::try_from(value.as_ref())
+                        .ok()
+                        .map(|b| b.0.then_some(20))
+                })
+                .ok_or_else(|| GenericErrorWithValue::from_value(self, value.into_owned()))
+        }
+    }
+}
+
+impl Section for Merge {
+    fn name(&self) -> &str {
+        "merge"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::CONFLICT_STYLE, &Self::FF, &Self::LOG]
+    }
+}
+
+pub(super) mod validate {
+    use crate::{bstr::BStr, config::tree::keys};
+
+    pub struct ConflictStyle;
+    impl keys::Validate for ConflictStyle {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Merge::CONFLICT_STYLE.try_into_conflict_style(value.into())?;
+            Ok(())
+        }
+    }
+
+    pub struct FastForward;
+    impl keys::Validate for FastForward {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Merge::FF.try_into_fast_forward(value.into())?;
+            Ok(())
+        }
+    }
+
+    pub struct Log;
+    impl keys::Validate for Log {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Merge::LOG.try_into_shortlog_limit(value.into())?;
+            Ok(())
+        }
+    }
+}
@@ -62,6 +62,11 @@ pub mod gitoxide;
 pub struct Http;
 pub mod http;
 
+/// The `i18n` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct I18n;
+pub mod i18n;
+
 /// The `index` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Index;
@@ -76,6 +81,13 @@ mod init;
 pub struct Mailmap;
 mod mailmap;
 
+/// The `merge` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "merge")]
+pub struct Merge;
+#[cfg(feature = "merge")]
+pub mod merge;
+
 /// The `pack` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Pack;
@@ -86,11 +98,25 @@ pub mod pack;
 pub struct Protocol;
 pub mod protocol;
 
+/// The `pull` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "merge")]
+pub struct Pull;
+#[cfg(feature = "merge")]
+mod pull;
+
 /// The `push` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Push;
 pub mod push;
 
+/// The `rebase` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "rebase")]
+pub struct Rebase;
+#[cfg(feature = "rebase")]
+mod rebase;
+
 /// The `remote` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Remote;
@@ -9,6 +9,13 @@ impl Pack {
         keys::UnsignedInteger::new_unsigned_integer("threads", &config::Tree::PACK)
             .with_deviation("Leaving this key unspecified uses all available cores, instead of 1");
 
+    /// The `pack.window` key.
+    pub const WINDOW: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("window", &config::Tree::PACK);
+
+    /// The `pack.depth` key.
+    pub const DEPTH: keys::UnsignedInteger = keys::UnsignedInteger::new_unsigned_integer("depth", &config::Tree::PACK);
+
     /// The `pack.indexVersion` key.
     pub const INDEX_VERSION: IndexVersion =
         IndexVersion::new_with_validate("indexVersion", &config::Tree::PACK, validate::IndexVersion);
@@ -42,7 +49,7 @@ impl Section for Pack {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::THREADS, &Self::INDEX_VERSION]
+        &[&Self::THREADS, &Self::WINDOW, &Self::DEPTH, &Self::INDEX_VERSION]
     }
 }
 
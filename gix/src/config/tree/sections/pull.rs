@@ -0,0 +1,24 @@
+use crate::{
+    config,
+    config::tree::{keys, merge, Key, Pull, Section},
+};
+
+impl Pull {
+    /// The `pull.ff` key.
+    pub const FF: merge::FastForward =
+        merge::FastForward::new_with_validate("ff", &config::Tree::PULL, super::merge::validate::FastForward);
+    /// The `pull.rebase` key.
+    ///
+    /// Note that only boolean values are supported, while `merges` and `interactive` are not.
+    pub const REBASE: keys::Boolean = keys::Boolean::new_boolean("rebase", &config::Tree::PULL);
+}
+
+impl Section for Pull {
+    fn name(&self) -> &str {
+        "pull"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::FF, &Self::REBASE]
+    }
+}
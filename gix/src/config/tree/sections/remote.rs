@@ -11,6 +11,19 @@ impl Remote {
     /// The `remote.<name>.tagOpt` key
     pub const TAG_OPT: TagOpt = TagOpt::new_with_validate("tagOpt", &config::Tree::REMOTE, validate::TagOpt)
         .with_subsection_requirement(Some(SubSectionRequirement::Parameter("name")));
+    /// The `remote.<name>.prune` key
+    pub const PRUNE: keys::Boolean =
+        keys::Boolean::new_boolean("prune", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.pruneTags` key
+    pub const PRUNE_TAGS: keys::Boolean =
+        keys::Boolean::new_boolean("pruneTags", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.mirror` key
+    pub const MIRROR: keys::Boolean = keys::Boolean::new_boolean("mirror", &config::Tree::REMOTE)
+        .with_subsection_requirement(NAME_PARAMETER)
+        .with_deviation("when fetching, mirrors also delete local references that don't exist on the remote anymore");
+    /// The `remote.<name>.skipFetchAll` key
+    pub const SKIP_FETCH_ALL: keys::Boolean =
+        keys::Boolean::new_boolean("skipFetchAll", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
     /// The `remote.<name>.url` key
     pub const URL: keys::Url =
         keys::Url::new_url("url", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
@@ -42,6 +55,10 @@ impl Section for Remote {
         &[
             &Self::PUSH_DEFAULT,
             &Self::TAG_OPT,
+            &Self::PRUNE,
+            &Self::PRUNE_TAGS,
+            &Self::MIRROR,
+            &Self::SKIP_FETCH_ALL,
             &Self::URL,
             &Self::PUSH_URL,
             &Self::FETCH,
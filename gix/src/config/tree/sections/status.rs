@@ -9,6 +9,11 @@ impl Status {
         &config::Tree::STATUS,
         validate::ShowUntrackedFiles,
     );
+    /// The `status.renames` key, which falls back to `diff.renames` if unset.
+    pub const RENAMES: super::diff::Renames = super::diff::Renames::new_renames("renames", &config::Tree::STATUS);
+    /// The `status.renameLimit` key, which falls back to `diff.renameLimit` if unset.
+    pub const RENAME_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("renameLimit", &config::Tree::STATUS);
 }
 
 /// The `status.showUntrackedFiles` key.
@@ -41,7 +46,7 @@ impl Section for Status {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::SHOW_UNTRACKED_FILES]
+        &[&Self::SHOW_UNTRACKED_FILES, &Self::RENAMES, &Self::RENAME_LIMIT]
     }
 }
 
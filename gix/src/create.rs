@@ -4,6 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::bstr::{BString, ByteSlice};
 use gix_config::parse::section;
 use gix_discover::DOT_GIT_DIR;
 use gix_macros::momo;
@@ -24,6 +25,13 @@ pub enum Error {
     DirectoryNotEmpty { path: PathBuf },
     #[error("Could not create directory at '{}'", .path.display())]
     CreateDirectory { source: std::io::Error, path: PathBuf },
+    #[error("Could not parse the configuration at '{}'", .path.display())]
+    ParseConfig {
+        source: gix_config::file::init::Error,
+        path: PathBuf,
+    },
+    #[error("A separate git directory can only be used for repositories with a worktree")]
+    SeparateGitDirInBareRepository,
 }
 
 /// The kind of repository to create.
@@ -55,7 +63,7 @@ struct PathCursor<'a>(&'a mut PathBuf);
 struct NewDir<'a>(&'a mut PathBuf);
 
 impl<'a> PathCursor<'a> {
-    fn at(&mut self, component: &str) -> &Path {
+    fn at(&mut self, component: impl AsRef<Path>) -> &Path {
         self.0.push(component);
         self.0.as_path()
     }
@@ -109,7 +117,7 @@ fn create_dir(p: &Path) -> Result<(), Error> {
 }
 
 /// Options for use in [`into()`];
-#[derive(Copy, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
     /// If true, and the kind of repository to create has a worktree, then the destination directory must be empty.
     ///
@@ -118,6 +126,18 @@ pub struct Options {
     /// If set, use these filesystem capabilities to populate the respective git-config fields.
     /// If `None`, the directory will be probed.
     pub fs_capabilities: Option<gix_fs::Capabilities>,
+    /// The directory whose files and directories are copied into the new repository, similar to `init.templateDir`.
+    ///
+    /// If `None`, the built-in templates with sample hooks and `info/exclude` are used, and if it's an empty path,
+    /// no templates are copied at all.
+    /// Just like in `git`, files and directories starting with `.` are skipped, and a `config` file is used as basis for the
+    /// configuration of the new repository.
+    pub template_dir: Option<PathBuf>,
+    /// If set, create the repository in this directory instead of the `.git` directory of the worktree, which will
+    /// be a `.git` file pointing to it instead, similar to `git init --separate-git-dir`.
+    ///
+    /// This is only valid for repositories with a worktree.
+    pub separate_git_dir: Option<PathBuf>,
 }
 
 /// Create a new `.git` repository of `kind` within the possibly non-existing `directory`
@@ -132,63 +152,38 @@ pub fn into(
     Options {
         fs_capabilities,
         destination_must_be_empty,
+        template_dir,
+        separate_git_dir,
     }: Options,
 ) -> Result<gix_discover::repository::Path, Error> {
     let mut dot_git = directory.into();
     let bare = matches!(kind, Kind::Bare);
+    if bare && separate_git_dir.is_some() {
+        return Err(Error::SeparateGitDirInBareRepository);
+    }
 
     if bare || destination_must_be_empty {
-        let num_entries_in_dot_git = fs::read_dir(&dot_git)
-            .or_else(|err| {
-                if err.kind() == std::io::ErrorKind::NotFound {
-                    fs::create_dir(&dot_git).and_then(|_| fs::read_dir(&dot_git))
-                } else {
-                    Err(err)
-                }
-            })
-            .map_err(|err| Error::IoOpen {
-                source: err,
-                path: dot_git.clone(),
-            })?
-            .count();
-        if num_entries_in_dot_git != 0 {
-            return Err(Error::DirectoryNotEmpty { path: dot_git });
-        }
+        assure_empty_dir(&dot_git)?;
     }
 
+    let mut git_file = None;
     if !bare {
         dot_git.push(DOT_GIT_DIR);
 
-        if dot_git.is_dir() {
+        if dot_git.is_dir() || (separate_git_dir.is_some() && dot_git.exists()) {
             return Err(Error::DirectoryExists { path: dot_git });
         }
+        if let Some(git_dir) = separate_git_dir {
+            let cwd = gix_fs::current_dir(false)?;
+            let git_dir = cwd.join(git_dir);
+            let git_dir = gix_path::normalize(git_dir.as_path().into(), &cwd)
+                .map_or_else(|| git_dir.clone(), std::borrow::Cow::into_owned);
+            assure_empty_dir(&git_dir)?;
+            git_file = Some(std::mem::replace(&mut dot_git, git_dir));
+        }
     };
     create_dir(&dot_git)?;
 
-    {
-        let mut cursor = NewDir(&mut dot_git).at("info")?;
-        write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"))?;
-    }
-
-    {
-        let mut cursor = NewDir(&mut dot_git).at("hooks")?;
-        for (tpl, filename) in &[
-            (TPL_HOOKS_DOCS_URL, "docs.url"),
-            (TPL_HOOKS_PREPARE_COMMIT_MSG, "prepare-commit-msg.sample"),
-            (TPL_HOOKS_PRE_REBASE, "pre-rebase.sample"),
-            (TPL_HOOKS_PRE_PUSH, "pre-push.sample"),
-            (TPL_HOOKS_PRE_COMMIT, "pre-commit.sample"),
-            (TPL_HOOKS_PRE_MERGE_COMMIT, "pre-merge-commit.sample"),
-            (TPL_HOOKS_PRE_APPLYPATCH, "pre-applypatch.sample"),
-            (TPL_HOOKS_POST_UPDATE, "post-update.sample"),
-            (TPL_HOOKS_FSMONITOR_WATCHMAN, "fsmonitor-watchman.sample"),
-            (TPL_HOOKS_COMMIT_MSG, "commit-msg.sample"),
-            (TPL_HOOKS_APPLYPATCH_MSG, "applypatch-msg.sample"),
-        ] {
-            write_file(tpl, PathCursor(cursor.as_mut()).at(filename))?;
-        }
-    }
-
     {
         let mut cursor = NewDir(&mut dot_git).at("objects")?;
         create_dir(PathCursor(cursor.as_mut()).at("info"))?;
@@ -201,9 +196,7 @@ pub fn into(
         create_dir(PathCursor(cursor.as_mut()).at("tags"))?;
     }
 
-    for (tpl, filename) in &[(TPL_HEAD, "HEAD"), (TPL_DESCRIPTION, "description")] {
-        write_file(tpl, PathCursor(&mut dot_git).at(filename))?;
-    }
+    write_file(TPL_HEAD, PathCursor(&mut dot_git).at("HEAD"))?;
 
     let caps = {
         let mut config = gix_config::File::default();
@@ -229,6 +222,22 @@ pub fn into(
         caps
     };
 
+    write_templates(&mut dot_git, template_dir.as_deref())?;
+
+    if let Some(git_file) = git_file {
+        let git_dir = gix_path::to_unix_separators_on_windows(gix_path::into_bstr(dot_git.as_path()));
+        let mut content = BString::from("gitdir: ");
+        content.extend_from_slice(&git_dir);
+        content.push(b'\n');
+        let work_dir = git_file.parent().expect("the .git file is in the worktree").to_owned();
+        create_dir(&work_dir)?;
+        write_file(&content, &git_file)?;
+        return Ok(gix_discover::repository::Path::LinkedWorkTree {
+            work_dir,
+            git_dir: dot_git,
+        });
+    }
+
     Ok(gix_discover::repository::Path::from_dot_git_dir(
         dot_git,
         if bare {
@@ -241,6 +250,150 @@ pub fn into(
     .expect("by now the `dot_git` dir is valid as we have accessed it"))
 }
 
+/// Copy the files and directories in `template_dir` into `git_dir` without overwriting existing files, or use the built-in
+/// templates if it is `None`. If `template_dir` is empty, nothing is copied.
+///
+/// Return `true` if the templates contained a `config` file, which is used as basis for the existing configuration of the
+/// repository.
+pub(crate) fn write_templates(git_dir: &mut PathBuf, template_dir: Option<&Path>) -> Result<bool, Error> {
+    let Some(template_dir) = template_dir else {
+        {
+            let mut cursor = NewDir(git_dir).at("info")?;
+            write_file(TPL_INFO_EXCLUDE, PathCursor(cursor.as_mut()).at("exclude"))?;
+        }
+
+        {
+            let mut cursor = NewDir(git_dir).at("hooks")?;
+            for (tpl, filename) in &[
+                (TPL_HOOKS_DOCS_URL, "docs.url"),
+                (TPL_HOOKS_PREPARE_COMMIT_MSG, "prepare-commit-msg.sample"),
+                (TPL_HOOKS_PRE_REBASE, "pre-rebase.sample"),
+                (TPL_HOOKS_PRE_PUSH, "pre-push.sample"),
+                (TPL_HOOKS_PRE_COMMIT, "pre-commit.sample"),
+                (TPL_HOOKS_PRE_MERGE_COMMIT, "pre-merge-commit.sample"),
+                (TPL_HOOKS_PRE_APPLYPATCH, "pre-applypatch.sample"),
+                (TPL_HOOKS_POST_UPDATE, "post-update.sample"),
+                (TPL_HOOKS_FSMONITOR_WATCHMAN, "fsmonitor-watchman.sample"),
+                (TPL_HOOKS_COMMIT_MSG, "commit-msg.sample"),
+                (TPL_HOOKS_APPLYPATCH_MSG, "applypatch-msg.sample"),
+            ] {
+                write_file(tpl, PathCursor(cursor.as_mut()).at(filename))?;
+            }
+        }
+
+        write_file(TPL_DESCRIPTION, PathCursor(git_dir).at("description"))?;
+        return Ok(false);
+    };
+    if template_dir.as_os_str().is_empty() {
+        return Ok(false);
+    }
+
+    copy_template_dir(template_dir, git_dir, true)?;
+    let template_config = template_dir.join("config");
+    if !template_config.is_file() {
+        return Ok(false);
+    }
+    let mut config = read_config(&template_config)?;
+    let config_path = PathCursor(git_dir).at("config").to_owned();
+    let repo_config = read_config(&config_path)?;
+    for section in repo_config.sections() {
+        let header = section.header();
+        let mut template_section = config
+            .section_mut_or_create_new(header.name().to_str_lossy(), header.subsection_name())
+            .expect("section names are valid as they were parsed");
+        for name in section.value_names() {
+            if let Some(value) = section.value(name.as_ref()) {
+                template_section.set(name.to_owned(), value.as_ref());
+            }
+        }
+    }
+    std::fs::write(&config_path, config.to_bstring()).map_err(|err| Error::IoWrite {
+        source: err,
+        path: config_path,
+    })?;
+    Ok(true)
+}
+
+/// Copy all entries in `template_dir` into `dir` recursively, skipping those starting with a dot and existing files,
+/// as well as the `config` file if `is_top_level` is `true`.
+fn copy_template_dir(template_dir: &Path, dir: &mut PathBuf, is_top_level: bool) -> Result<(), Error> {
+    let open_err = |err: std::io::Error| Error::IoOpen {
+        source: err,
+        path: template_dir.to_owned(),
+    };
+    let entries = match fs::read_dir(template_dir) {
+        Ok(entries) => entries,
+        Err(err) if is_top_level && err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(open_err(err)),
+    };
+    for entry in entries {
+        let entry = entry.map_err(open_err)?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') || (is_top_level && name == "config") {
+            continue;
+        }
+        let template_path = entry.path();
+        let file_type = entry.file_type().map_err(open_err)?;
+        let mut cursor = PathCursor(dir);
+        let path = cursor.at(&name);
+        if file_type.is_dir() {
+            create_dir(path)?;
+            copy_template_dir(&template_path, cursor.0, false)?;
+        } else if path.symlink_metadata().is_ok() {
+            continue;
+        } else if file_type.is_symlink() {
+            let original = fs::read_link(&template_path).map_err(|err| Error::IoOpen {
+                source: err,
+                path: template_path,
+            })?;
+            gix_fs::symlink::create(&original, path).map_err(|err| Error::IoWrite {
+                source: err,
+                path: path.to_owned(),
+            })?;
+        } else {
+            fs::copy(&template_path, path).map_err(|err| Error::IoWrite {
+                source: err,
+                path: path.to_owned(),
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn read_config(path: &Path) -> Result<gix_config::File<'static>, Error> {
+    let mut buf = fs::read(path).map_err(|err| Error::IoOpen {
+        source: err,
+        path: path.to_owned(),
+    })?;
+    gix_config::File::from_bytes_owned(&mut buf, gix_config::file::Metadata::api(), Default::default()).map_err(|err| {
+        Error::ParseConfig {
+            source: err,
+            path: path.to_owned(),
+        }
+    })
+}
+
+/// Fail unless `dir` is empty, and create it if it doesn't exist.
+fn assure_empty_dir(dir: &Path) -> Result<(), Error> {
+    let num_entries = fs::read_dir(dir)
+        .or_else(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                fs::create_dir(dir).and_then(|_| fs::read_dir(dir))
+            } else {
+                Err(err)
+            }
+        })
+        .map_err(|err| Error::IoOpen {
+            source: err,
+            path: dir.to_owned(),
+        })?
+        .count();
+    if num_entries != 0 {
+        return Err(Error::DirectoryNotEmpty { path: dir.to_owned() });
+    }
+    Ok(())
+}
+
 fn key(name: &'static str) -> section::ValueName<'static> {
     section::ValueName::try_from(name).expect("valid key name")
 }
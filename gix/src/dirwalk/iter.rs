@@ -14,17 +14,56 @@ pub struct Item {
     /// [gix_dir::walk::CollapsedEntriesEmissionMode::OnStatusMismatch]. It will also be `Some(dir_status)` if that option
     /// was [gix_dir::walk::CollapsedEntriesEmissionMode::All].
     pub collapsed_directory_status: Option<gix_dir::entry::Status>,
+    /// The state of `HEAD` of the nested repository at `entry`, if it has the
+    /// [`NestedRepository`](gix_dir::entry::Property::NestedRepository) property and if it could be opened.
+    ///
+    /// This is only the case if [`nested_repositories`](dirwalk::Options::nested_repositories()) was set to
+    /// [`Emit`](gix_dir::walk::NestedRepositoryMode::Emit).
+    pub nested_repository_head: Option<crate::head::Kind>,
 }
 
 impl Item {
-    fn new(entry: gix_dir::EntryRef<'_>, collapsed_directory_status: Option<gix_dir::entry::Status>) -> Self {
+    fn new(
+        entry: gix_dir::EntryRef<'_>,
+        collapsed_directory_status: Option<gix_dir::entry::Status>,
+        nested_repositories: Option<&NestedRepositories>,
+    ) -> Self {
+        let nested_repository_head = nested_repositories
+            .filter(|_| entry.property == Some(gix_dir::entry::Property::NestedRepository))
+            .and_then(|nested| nested.head(&entry.rela_path));
         Item {
             entry: entry.to_owned(),
             collapsed_directory_status,
+            nested_repository_head,
         }
     }
 }
 
+/// What's needed to open nested repositories to learn about their `HEAD`.
+struct NestedRepositories {
+    work_dir: PathBuf,
+    options: crate::open::Options,
+}
+
+impl NestedRepositories {
+    fn new(repo: &Repository, options: &dirwalk::Options) -> Option<Self> {
+        (options.nested_repositories == gix_dir::walk::NestedRepositoryMode::Emit)
+            .then(|| repo.work_dir())
+            .flatten()
+            .map(|work_dir| NestedRepositories {
+                work_dir: work_dir.to_owned(),
+                options: repo.options.clone(),
+            })
+    }
+
+    fn head(&self, rela_path: &crate::bstr::BStr) -> Option<crate::head::Kind> {
+        let path = self.work_dir.join(gix_path::from_bstr(rela_path));
+        let repo = crate::open_opts(path, self.options.clone().for_other_repository()).ok()?;
+        let head = repo.head().ok()?;
+        Some(head.kind)
+    }
+}
+
 /// The outcome of fully consumed [dirwalk iterator](Iter).
 pub struct Outcome {
     /// The index originally passed in to create the iterator.
@@ -64,6 +103,7 @@ impl Iter {
         should_interrupt: OwnedOrStaticAtomicBool,
         options: dirwalk::Options,
     ) -> Result<Iter, Error> {
+        let nested_repositories = NestedRepositories::new(repo, &options);
         #[cfg(feature = "parallel")]
         {
             let repo = repo.clone().into_sync();
@@ -74,7 +114,10 @@ impl Iter {
                     let should_interrupt = should_interrupt.clone();
                     move || -> Result<Outcome, dirwalk::Error> {
                         let repo: Repository = repo.into();
-                        let mut collect = Collect { tx };
+                        let mut collect = Collect {
+                            tx,
+                            nested_repositories,
+                        };
                         let out = repo.dirwalk(&index, patterns, &should_interrupt, options, &mut collect)?;
                         Ok(Outcome {
                             index,
@@ -99,7 +142,10 @@ impl Iter {
         }
         #[cfg(not(feature = "parallel"))]
         {
-            let mut collect = Collect { items: Vec::new() };
+            let mut collect = Collect {
+                items: Vec::new(),
+                nested_repositories,
+            };
             let out = repo.dirwalk(&index, patterns, &should_interrupt, options, &mut collect)?;
             let out = Outcome {
                 index,
@@ -169,6 +215,7 @@ struct Collect {
     tx: std::sync::mpsc::Sender<Item>,
     #[cfg(not(feature = "parallel"))]
     items: Vec<Item>,
+    nested_repositories: Option<NestedRepositories>,
 }
 
 impl gix_dir::walk::Delegate for Collect {
@@ -178,7 +225,7 @@ impl gix_dir::walk::Delegate for Collect {
         collapsed_directory_status: Option<gix_dir::entry::Status>,
     ) -> gix_dir::walk::Action {
         // NOTE: we assume that the receiver triggers interruption so the operation will stop if the receiver is down.
-        let item = Item::new(entry, collapsed_directory_status);
+        let item = Item::new(entry, collapsed_directory_status, self.nested_repositories.as_ref());
         #[cfg(feature = "parallel")]
         self.tx.send(item).ok();
         #[cfg(not(feature = "parallel"))]
@@ -1,4 +1,4 @@
-use gix_dir::walk::{CollapsedEntriesEmissionMode, EmissionMode, ForDeletionMode};
+use gix_dir::walk::{CollapsedEntriesEmissionMode, EmissionMode, ForDeletionMode, NestedRepositoryMode};
 
 use crate::{config, AttributeStack, Pathspec};
 use std::path::PathBuf;
@@ -77,7 +77,7 @@ pub struct Options {
     precompose_unicode: bool,
     ignore_case: bool,
 
-    recurse_repositories: bool,
+    nested_repositories: NestedRepositoryMode,
     emit_pruned: bool,
     emit_ignored: Option<EmissionMode>,
     for_deletion: Option<ForDeletionMode>,
@@ -87,5 +87,9 @@ pub struct Options {
     classify_untracked_bare_repositories: bool,
     emit_collapsed: Option<CollapsedEntriesEmissionMode>,
     symlinks_to_directories_are_ignored_like_directories: bool,
+    one_file_system: bool,
+    max_depth: Option<usize>,
+    max_path_len: Option<usize>,
+    skip_inaccessible: bool,
     pub(crate) empty_patterns_match_prefix: bool,
 }
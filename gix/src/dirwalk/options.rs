@@ -1,5 +1,5 @@
 use crate::dirwalk::Options;
-use gix_dir::walk::{CollapsedEntriesEmissionMode, EmissionMode, ForDeletionMode};
+use gix_dir::walk::{CollapsedEntriesEmissionMode, EmissionMode, ForDeletionMode, NestedRepositoryMode};
 
 /// Construction
 impl Options {
@@ -7,7 +7,7 @@ impl Options {
         Self {
             precompose_unicode: caps.precompose_unicode,
             ignore_case: caps.ignore_case,
-            recurse_repositories: false,
+            nested_repositories: NestedRepositoryMode::Untracked,
             emit_pruned: false,
             emit_ignored: None,
             for_deletion: None,
@@ -18,6 +18,10 @@ impl Options {
             emit_collapsed: None,
             empty_patterns_match_prefix: false,
             symlinks_to_directories_are_ignored_like_directories: false,
+            one_file_system: false,
+            max_depth: None,
+            max_path_len: None,
+            skip_inaccessible: false,
         }
     }
 }
@@ -27,7 +31,7 @@ impl From<Options> for gix_dir::walk::Options {
         gix_dir::walk::Options {
             precompose_unicode: v.precompose_unicode,
             ignore_case: v.ignore_case,
-            recurse_repositories: v.recurse_repositories,
+            nested_repositories: v.nested_repositories,
             emit_pruned: v.emit_pruned,
             emit_ignored: v.emit_ignored,
             for_deletion: v.for_deletion,
@@ -38,6 +42,10 @@ impl From<Options> for gix_dir::walk::Options {
             emit_collapsed: v.emit_collapsed,
             symlinks_to_directories_are_ignored_like_directories: v
                 .symlinks_to_directories_are_ignored_like_directories,
+            one_file_system: v.one_file_system,
+            max_depth: v.max_depth,
+            max_path_len: v.max_path_len,
+            skip_inaccessible: v.skip_inaccessible,
         }
     }
 }
@@ -59,13 +67,33 @@ impl Options {
     }
     /// If `toggle` is `true`, we will stop figuring out if any directory that is a candidate for recursion is also a nested repository,
     /// which saves time but leads to recurse into it. If `false`, nested repositories will not be traversed.
+    ///
+    /// This is a shortcut for setting [`nested_repositories()`](Self::nested_repositories) to
+    /// [`Recurse`](NestedRepositoryMode::Recurse) or [`Untracked`](NestedRepositoryMode::Untracked) respectively.
     pub fn recurse_repositories(mut self, toggle: bool) -> Self {
-        self.recurse_repositories = toggle;
+        self.set_recurse_repositories(toggle);
         self
     }
     /// Like [`recurse_repositories()`](Self::recurse_repositories), but only requires a mutably borrowed instance.
     pub fn set_recurse_repositories(&mut self, toggle: bool) -> &mut Self {
-        self.recurse_repositories = toggle;
+        self.nested_repositories = if toggle {
+            NestedRepositoryMode::Recurse
+        } else {
+            NestedRepositoryMode::Untracked
+        };
+        self
+    }
+    /// Control what to do when encountering nested repositories with `mode`.
+    ///
+    /// With [`NestedRepositoryMode::Emit`], the [dirwalk iterator](crate::dirwalk::Iter) will also provide
+    /// the `HEAD` of each nested repository.
+    pub fn nested_repositories(mut self, mode: NestedRepositoryMode) -> Self {
+        self.nested_repositories = mode;
+        self
+    }
+    /// Like [`nested_repositories()`](Self::nested_repositories), but only requires a mutably borrowed instance.
+    pub fn set_nested_repositories(&mut self, mode: NestedRepositoryMode) -> &mut Self {
+        self.nested_repositories = mode;
         self
     }
     /// If `toggle` is `true`, entries that are pruned and whose [Kind](gix_dir::entry::Kind) is known will be emitted.
@@ -183,4 +211,58 @@ impl Options {
         self.symlinks_to_directories_are_ignored_like_directories = value;
         self
     }
+
+    /// If `toggle` is `true`, directories on another filesystem than the one of the worktree root aren't entered,
+    /// and are passed to [`Delegate::skipped()`](gix_dir::walk::Delegate::skipped()) instead.
+    ///
+    /// This is only supported on Unix and has no effect on other platforms.
+    pub fn one_file_system(mut self, toggle: bool) -> Self {
+        self.one_file_system = toggle;
+        self
+    }
+
+    /// Like [`one_file_system()`](Self::one_file_system), but only requires a mutably borrowed instance.
+    pub fn set_one_file_system(&mut self, toggle: bool) -> &mut Self {
+        self.one_file_system = toggle;
+        self
+    }
+
+    /// If `value` is `Some(depth)`, directories whose worktree-relative path consists of `depth` or more components
+    /// aren't entered, and are passed to [`Delegate::skipped()`](gix_dir::walk::Delegate::skipped()) instead.
+    pub fn max_depth(mut self, value: Option<usize>) -> Self {
+        self.max_depth = value;
+        self
+    }
+
+    /// Like [`max_depth()`](Self::max_depth), but only requires a mutably borrowed instance.
+    pub fn set_max_depth(&mut self, value: Option<usize>) -> &mut Self {
+        self.max_depth = value;
+        self
+    }
+
+    /// If `value` is `Some(len)`, entries whose worktree-relative path is longer than `len` bytes are neither emitted
+    /// nor entered, and are passed to [`Delegate::skipped()`](gix_dir::walk::Delegate::skipped()) instead.
+    pub fn max_path_len(mut self, value: Option<usize>) -> Self {
+        self.max_path_len = value;
+        self
+    }
+
+    /// Like [`max_path_len()`](Self::max_path_len), but only requires a mutably borrowed instance.
+    pub fn set_max_path_len(&mut self, value: Option<usize>) -> &mut Self {
+        self.max_path_len = value;
+        self
+    }
+
+    /// If `toggle` is `true`, directories that can't be read and symlinks that are part of a loop are passed to
+    /// [`Delegate::skipped()`](gix_dir::walk::Delegate::skipped()) instead of failing the walk or being emitted.
+    pub fn skip_inaccessible(mut self, toggle: bool) -> Self {
+        self.skip_inaccessible = toggle;
+        self
+    }
+
+    /// Like [`skip_inaccessible()`](Self::skip_inaccessible), but only requires a mutably borrowed instance.
+    pub fn set_skip_inaccessible(&mut self, toggle: bool) -> &mut Self {
+        self.skip_inaccessible = toggle;
+        self
+    }
 }
@@ -11,6 +11,8 @@ impl RevSpecExt for gix_revision::Spec {
             path: None,
             first_ref: None,
             second_ref: None,
+            first_reflog_entry: None,
+            second_reflog_entry: None,
             repo,
         }
     }
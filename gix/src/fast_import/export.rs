@@ -0,0 +1,255 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_hash::ObjectId;
+
+use super::{flatten_tree, Command, Commit, Commitish, DataRef, FileChange, Files, Tag};
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// The error returned by [`Repository::fast_export()`](crate::Repository::fast_export()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    IterReferences(#[from] crate::reference::iter::Error),
+    #[error("Could not obtain reference during iteration")]
+    ObtainReference(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    IterReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] crate::object::conversion::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+impl From<super::FlattenTreeError> for Error {
+    fn from(err: super::FlattenTreeError) -> Self {
+        match err {
+            super::FlattenTreeError::FindObject(err) => Error::FindObject(err),
+            super::FlattenTreeError::Decode(err) => Error::Decode(err),
+        }
+    }
+}
+
+/// The result of [`Repository::fast_export()`](crate::Repository::fast_export()).
+#[derive(Debug, Default, Clone)]
+pub struct Outcome {
+    /// The marks assigned to each exported commit and blob.
+    pub marks: HashMap<ObjectId, u64>,
+    /// The amount of exported commits.
+    pub commits: usize,
+    /// The amount of exported blobs.
+    pub blobs: usize,
+    /// The amount of exported annotated tags.
+    pub tags: usize,
+}
+
+/// Export
+impl crate::Repository {
+    /// Write all commits reachable from `refs` along with the blobs they need as `git fast-import` stream to `out`,
+    /// or all branches and tags if `refs` is empty.
+    ///
+    /// Commits are written parents first, and each reference is set to its tip or turned into an annotated tag at
+    /// the end of the stream. Signatures of commits and tags are not exported, and references pointing to anything
+    /// but commits or annotated tags of commits are skipped.
+    ///
+    /// `should_interrupt` is checked after each commit, and if `true`, the export stops with an error.
+    pub fn fast_export(
+        &self,
+        refs: &[BString],
+        out: &mut dyn std::io::Write,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let mut references = Vec::new();
+        let platform = self.references()?;
+        if refs.is_empty() {
+            for reference in platform.local_branches()?.chain(platform.tags()?) {
+                references.push(reference?);
+            }
+        } else {
+            for name in refs {
+                references.push(self.find_reference(name.as_bstr())?);
+            }
+        }
+
+        let mut state = State {
+            repo: self,
+            out,
+            outcome: Outcome::default(),
+            next_mark: 1,
+        };
+
+        let mut tips = Vec::new();
+        let mut seen = HashSet::new();
+        for reference in references {
+            let Ok(id) = reference.clone().peel_to_id_in_place() else {
+                continue;
+            };
+            if self.find_header(id)?.kind() != gix_object::Kind::Commit {
+                continue;
+            }
+            tips.push((reference, id.detach()));
+        }
+
+        for (reference, tip) in &tips {
+            for commit in topological_order(self, *tip, &mut seen)? {
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+                state.write_commit(commit, reference.name().as_bstr())?;
+            }
+        }
+
+        for (reference, tip) in tips {
+            let from = Commitish::Mark(state.outcome.marks[&tip]);
+            let name = reference.name().as_bstr().to_owned();
+            let tag = match reference.target().try_id().map(ToOwned::to_owned) {
+                Some(id) if self.find_header(id)?.kind() == gix_object::Kind::Tag => {
+                    let tag = self.find_object(id)?;
+                    let tag = tag.try_to_tag_ref()?;
+                    Some(Tag {
+                        name: name.strip_prefix(b"refs/tags/").unwrap_or(&name).into(),
+                        mark: None,
+                        from: from.clone(),
+                        tagger: tag.tagger.map(|sig| sig.to_owned()),
+                        message: tag.message.to_owned(),
+                    })
+                }
+                _ => None,
+            };
+            match tag {
+                Some(tag) => {
+                    Command::Tag(tag).write_to(state.out)?;
+                    state.outcome.tags += 1;
+                }
+                None => Command::Reset {
+                    branch: name,
+                    from: Some(from),
+                }
+                .write_to(state.out)?,
+            }
+        }
+        Ok(state.outcome)
+    }
+}
+
+struct State<'repo, 'out> {
+    repo: &'repo crate::Repository,
+    out: &'out mut dyn std::io::Write,
+    outcome: Outcome,
+    next_mark: u64,
+}
+
+impl State<'_, '_> {
+    fn mark(&mut self, id: ObjectId) -> u64 {
+        let mark = self.next_mark;
+        self.next_mark += 1;
+        self.outcome.marks.insert(id, mark);
+        mark
+    }
+
+    fn write_commit(&mut self, id: ObjectId, branch: &BStr) -> Result<(), Error> {
+        let commit = self.repo.find_object(id)?;
+        let commit = commit.try_to_commit_ref()?;
+        let mut parent_files = Files::new();
+        if let Some(parent) = commit.parents().next() {
+            let parent_tree = self.repo.find_object(parent)?.try_to_commit_ref()?.tree();
+            flatten_tree(self.repo, parent_tree, "".into(), &mut parent_files)?;
+        }
+        let mut files = Files::new();
+        flatten_tree(self.repo, commit.tree(), "".into(), &mut files)?;
+
+        let mut changes = Vec::new();
+        for path in parent_files.keys().filter(|path| !files.contains_key(*path)) {
+            changes.push(FileChange::Delete { path: path.clone() });
+        }
+        for (path, (mode, blob)) in files {
+            if parent_files.get(&path) == Some(&(mode, blob)) {
+                continue;
+            }
+            let data = if mode.is_commit() {
+                DataRef::Id(blob)
+            } else {
+                DataRef::Mark(match self.outcome.marks.get(&blob) {
+                    Some(mark) => *mark,
+                    None => {
+                        let data = self.repo.find_object(blob)?.detach().data;
+                        let mark = self.mark(blob);
+                        Command::Blob {
+                            mark: Some(mark),
+                            data: data.into(),
+                        }
+                        .write_to(self.out)?;
+                        self.outcome.blobs += 1;
+                        mark
+                    }
+                })
+            };
+            changes.push(FileChange::Modify { mode, data, path });
+        }
+
+        let mut parents = commit.parents().map(|id| Commitish::Mark(self.outcome.marks[&id]));
+        let from = parents.next();
+        let merges = parents.collect();
+        if from.is_none() {
+            Command::Reset {
+                branch: branch.to_owned(),
+                from: None,
+            }
+            .write_to(self.out)?;
+        }
+        let mark = self.mark(id);
+        Command::Commit(Commit {
+            branch: branch.to_owned(),
+            mark: Some(mark),
+            author: Some(commit.author.to_owned()),
+            committer: commit.committer.to_owned(),
+            message: commit.message.to_owned(),
+            from,
+            merges,
+            changes,
+        })
+        .write_to(self.out)?;
+        self.outcome.commits += 1;
+        Ok(())
+    }
+}
+
+/// Return all commits reachable from `tip` that aren't in `seen` yet, parents first.
+fn topological_order(
+    repo: &crate::Repository,
+    tip: ObjectId,
+    seen: &mut HashSet<ObjectId>,
+) -> Result<Vec<ObjectId>, Error> {
+    let mut out = Vec::new();
+    let mut stack = vec![(tip, false)];
+    while let Some((id, parents_done)) = stack.pop() {
+        if parents_done {
+            out.push(id);
+            continue;
+        }
+        if !seen.insert(id) {
+            continue;
+        }
+        stack.push((id, true));
+        let commit = repo.find_object(id)?;
+        let commit = commit.try_to_commit_ref()?;
+        stack.extend(
+            commit
+                .parents()
+                .filter(|parent| !seen.contains(parent))
+                .map(|parent| (parent, false)),
+        );
+    }
+    Ok(out)
+}
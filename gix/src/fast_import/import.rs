@@ -0,0 +1,290 @@
+use std::collections::BTreeMap;
+
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName,
+};
+
+use super::{decode, flatten_tree, write_tree, Command, Commitish, DataRef, FileChange, Files};
+use crate::bstr::{BString, ByteSlice, ByteVec};
+
+/// The error returned by [`Repository::fast_import()`](crate::Repository::fast_import()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
+    #[error("Mark :{mark} was used before it was defined")]
+    UnknownMark { mark: u64 },
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error("Reference '{name}' could not be found")]
+    MissingReference { name: BString },
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeObject(#[from] crate::object::conversion::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    ReferenceName(#[from] gix_validate::reference::name::Error),
+    #[error(transparent)]
+    EditReferences(#[from] crate::reference::edit::Error),
+}
+
+impl From<super::FlattenTreeError> for Error {
+    fn from(err: super::FlattenTreeError) -> Self {
+        match err {
+            super::FlattenTreeError::FindObject(err) => Error::FindObject(err),
+            super::FlattenTreeError::Decode(err) => Error::DecodeObject(err),
+        }
+    }
+}
+
+/// The result of [`Repository::fast_import()`](crate::Repository::fast_import()).
+#[derive(Debug, Default, Clone)]
+pub struct Outcome {
+    /// All marks that were defined in the stream, along with the id of the object they refer to.
+    pub marks: BTreeMap<u64, ObjectId>,
+    /// The edits that were applied to references, after the stream was read entirely.
+    pub edits: Vec<RefEdit>,
+    /// The amount of commits that were created.
+    pub commits: usize,
+    /// The amount of blobs that were created.
+    pub blobs: usize,
+    /// The amount of annotated tags that were created.
+    pub tags: usize,
+}
+
+/// Import
+impl crate::Repository {
+    /// Create all objects described by `commands`, which typically are obtained from a [`Reader`](super::Reader),
+    /// and update all branches and tags once all commands were processed successfully.
+    ///
+    /// Branches that exist in the repository and are changed by the stream must still point to the same commit as
+    /// when they were first referred to, similar to `git fast-import --force`, even though branches
+    /// aren't required to be fast-forwarded.
+    pub fn fast_import(
+        &self,
+        commands: impl IntoIterator<Item = Result<Command, decode::Error>>,
+    ) -> Result<Outcome, Error> {
+        let mut state = State {
+            repo: self,
+            outcome: Outcome::default(),
+            branches: BTreeMap::new(),
+        };
+        for command in commands {
+            match command? {
+                Command::Blob { mark, data } => {
+                    let id = self.write_blob(&data)?.detach();
+                    state.set_mark(mark, id);
+                    state.outcome.blobs += 1;
+                }
+                Command::Commit(commit) => state.commit(commit)?,
+                Command::Tag(tag) => {
+                    let target = state.resolve(&tag.from)?;
+                    let target_kind = self.find_header(target)?.kind();
+                    let id = self
+                        .write_object(&gix_object::Tag {
+                            target,
+                            target_kind,
+                            name: tag.name.clone(),
+                            tagger: tag.tagger,
+                            message: tag.message,
+                            pgp_signature: None,
+                        })?
+                        .detach();
+                    state.set_mark(tag.mark, id);
+                    let mut name = BString::from("refs/tags/");
+                    name.push_str(&tag.name);
+                    state.set_branch(name, Some(id))?;
+                    state.outcome.tags += 1;
+                }
+                Command::Reset { branch, from } => {
+                    let target = from.map(|from| state.resolve(&from)).transpose()?;
+                    state.set_branch(branch, target)?;
+                }
+                Command::Done => break,
+                Command::Checkpoint | Command::Progress(_) | Command::Feature(_) | Command::Option(_) => {}
+            }
+        }
+
+        let mut edits = Vec::new();
+        for (name, branch) in state.branches {
+            let expected = match branch.original {
+                Some(id) => PreviousValue::MustExistAndMatch(id.into()),
+                None => PreviousValue::MustNotExist,
+            };
+            let change = match branch.tip {
+                Some(new) if Some(new) != branch.original => Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "fast-import".into(),
+                    },
+                    expected,
+                    new: new.into(),
+                },
+                Some(_) => continue,
+                None if branch.original.is_some() => Change::Delete {
+                    expected,
+                    log: RefLog::AndReference,
+                },
+                None => continue,
+            };
+            edits.push(RefEdit {
+                change,
+                name,
+                deref: false,
+            });
+        }
+        state.outcome.edits = self.edit_references(edits)?;
+        Ok(state.outcome)
+    }
+}
+
+struct Branch {
+    /// The value of the reference in the repository before the import.
+    original: Option<ObjectId>,
+    /// The value of the branch in the stream.
+    tip: Option<ObjectId>,
+}
+
+struct State<'repo> {
+    repo: &'repo crate::Repository,
+    outcome: Outcome,
+    branches: BTreeMap<FullName, Branch>,
+}
+
+impl State<'_> {
+    fn set_mark(&mut self, mark: Option<u64>, id: ObjectId) {
+        if let Some(mark) = mark {
+            self.outcome.marks.insert(mark, id);
+        }
+    }
+
+    fn mark(&self, mark: u64) -> Result<ObjectId, Error> {
+        self.outcome
+            .marks
+            .get(&mark)
+            .copied()
+            .ok_or(Error::UnknownMark { mark })
+    }
+
+    fn branch(&mut self, name: BString) -> Result<&mut Branch, Error> {
+        let name = FullName::try_from(name)?;
+        if !self.branches.contains_key(&name) {
+            let original = self
+                .repo
+                .try_find_reference(name.as_ref())?
+                .and_then(|r| r.target().try_id().map(ToOwned::to_owned));
+            self.branches.insert(
+                name.clone(),
+                Branch {
+                    original,
+                    tip: original,
+                },
+            );
+        }
+        Ok(self.branches.get_mut(&name).expect("just inserted"))
+    }
+
+    fn set_branch(&mut self, name: BString, tip: Option<ObjectId>) -> Result<(), Error> {
+        self.branch(name)?.tip = tip;
+        Ok(())
+    }
+
+    fn resolve(&mut self, commitish: &Commitish) -> Result<ObjectId, Error> {
+        Ok(match commitish {
+            Commitish::Mark(mark) => self.mark(*mark)?,
+            Commitish::Id(id) => *id,
+            Commitish::Name(name) => {
+                if let Ok(full_name) = FullName::try_from(name.clone()) {
+                    if let Some(tip) = self.branches.get(&full_name).and_then(|b| b.tip) {
+                        return Ok(tip);
+                    }
+                }
+                self.repo
+                    .try_find_reference(name.as_bstr())?
+                    .ok_or_else(|| Error::MissingReference { name: name.clone() })?
+                    .peel_to_id_in_place()?
+                    .detach()
+            }
+        })
+    }
+
+    fn commit(&mut self, commit: super::Commit) -> Result<(), Error> {
+        let first_parent = match &commit.from {
+            Some(from) => Some(self.resolve(from)?),
+            None => self.branch(commit.branch.clone())?.tip,
+        };
+        let mut parents: smallvec::SmallVec<[ObjectId; 1]> = first_parent.into_iter().collect();
+        for merge in &commit.merges {
+            parents.push(self.resolve(merge)?);
+        }
+
+        let mut files = Files::new();
+        if let Some(parent) = first_parent {
+            let tree = self.repo.find_object(parent)?.try_to_commit_ref()?.tree();
+            flatten_tree(self.repo, tree, "".into(), &mut files)?;
+        }
+        for change in commit.changes {
+            match change {
+                FileChange::DeleteAll => files.clear(),
+                FileChange::Delete { path } => remove_path(&mut files, path.as_ref()),
+                FileChange::Modify { mode, data, path } => {
+                    let id = match data {
+                        DataRef::Mark(mark) => self.mark(mark)?,
+                        DataRef::Id(id) => id,
+                        DataRef::Inline(data) => {
+                            self.outcome.blobs += 1;
+                            self.repo.write_blob(&data)?.detach()
+                        }
+                    };
+                    remove_path(&mut files, path.as_ref());
+                    if mode.is_tree() {
+                        flatten_tree(self.repo, id, path.as_ref(), &mut files)?;
+                    } else {
+                        files.insert(path, (mode, id));
+                    }
+                }
+            }
+        }
+
+        let tree = write_tree(self.repo, &files)?;
+        let id = self
+            .repo
+            .write_object(&gix_object::Commit {
+                tree,
+                parents,
+                author: commit.author.unwrap_or_else(|| commit.committer.clone()),
+                committer: commit.committer,
+                encoding: None,
+                message: commit.message,
+                extra_headers: Vec::new(),
+            })?
+            .detach();
+        self.set_mark(commit.mark, id);
+        self.set_branch(commit.branch, Some(id))?;
+        self.outcome.commits += 1;
+        Ok(())
+    }
+}
+
+/// Remove the file at `path`, or all files in the directory at `path`.
+fn remove_path(files: &mut Files, path: &crate::bstr::BStr) {
+    files.remove(path);
+    let mut dir = path.to_owned();
+    dir.push_byte(b'/');
+    let children: Vec<_> = files
+        .range(dir.clone()..)
+        .take_while(|(p, _)| p.starts_with(&dir))
+        .map(|(p, _)| p.clone())
+        .collect();
+    for child in children {
+        files.remove(&child);
+    }
+}
@@ -0,0 +1,127 @@
+//! Read and write the stream format of [`git fast-import`](https://git-scm.com/docs/git-fast-import), and use it to
+//! [export](crate::Repository::fast_export()) history from and [import](crate::Repository::fast_import()) history into
+//! a repository.
+//!
+//! This allows migration tools and bridges to other version control systems, which typically speak this format,
+//! to work with `gitoxide`.
+#![allow(clippy::result_large_err)]
+use std::collections::BTreeMap;
+
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+mod stream;
+pub use stream::{decode, Command, Commit, Commitish, DataRef, FileChange, Reader, Tag};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod export;
+///
+#[allow(clippy::empty_docs)]
+pub mod import;
+
+/// All files of a tree, by path.
+type Files = BTreeMap<BString, (EntryMode, ObjectId)>;
+
+/// The error returned by [`flatten_tree()`], which is converted into the errors of imports and exports.
+#[derive(Debug)]
+enum FlattenTreeError {
+    FindObject(crate::object::find::existing::Error),
+    Decode(crate::object::conversion::Error),
+}
+
+impl From<crate::object::find::existing::Error> for FlattenTreeError {
+    fn from(err: crate::object::find::existing::Error) -> Self {
+        FlattenTreeError::FindObject(err)
+    }
+}
+
+impl From<crate::object::conversion::Error> for FlattenTreeError {
+    fn from(err: crate::object::conversion::Error) -> Self {
+        FlattenTreeError::Decode(err)
+    }
+}
+
+/// Add all non-tree entries of `tree` and its subtrees to `out`, with their paths prefixed by `prefix`.
+fn flatten_tree(
+    repo: &crate::Repository,
+    tree: ObjectId,
+    prefix: &BStr,
+    out: &mut Files,
+) -> Result<(), FlattenTreeError> {
+    if tree.is_empty_tree() {
+        return Ok(());
+    }
+    let tree = repo.find_object(tree)?;
+    if tree.kind != gix_object::Kind::Tree {
+        return Err(crate::object::conversion::Error::UnexpectedType {
+            expected: gix_object::Kind::Tree,
+            actual: tree.kind,
+        }
+        .into());
+    }
+    for entry in gix_object::TreeRefIter::from_bytes(&tree.data) {
+        let entry = entry.map_err(crate::object::conversion::Error::from)?;
+        let mut path = prefix.to_owned();
+        if !path.is_empty() {
+            path.push_byte(b'/');
+        }
+        path.push_str(entry.filename);
+        if entry.mode.is_tree() {
+            flatten_tree(repo, entry.oid.to_owned(), path.as_ref(), out)?;
+        } else {
+            out.insert(path, (entry.mode, entry.oid.to_owned()));
+        }
+    }
+    Ok(())
+}
+
+/// Write all `files` as tree and return the id of the root tree.
+fn write_tree(repo: &crate::Repository, files: &Files) -> Result<ObjectId, crate::object::write::Error> {
+    let entries: Vec<_> = files
+        .iter()
+        .map(|(path, (mode, id))| (path.as_bstr(), *mode, *id))
+        .collect();
+    write_tree_inner(repo, &entries)
+}
+
+fn write_tree_inner(
+    repo: &crate::Repository,
+    entries: &[(&BStr, EntryMode, ObjectId)],
+) -> Result<ObjectId, crate::object::write::Error> {
+    let mut tree = gix_object::Tree::empty();
+    let mut remaining = entries;
+    while let Some((path, mode, id)) = remaining.first().copied() {
+        match path.find_byte(b'/') {
+            None => {
+                tree.entries.push(gix_object::tree::Entry {
+                    mode,
+                    filename: path.to_owned(),
+                    oid: id,
+                });
+                remaining = &remaining[1..];
+            }
+            Some(pos) => {
+                let dir = &path[..pos];
+                let len = remaining
+                    .iter()
+                    .take_while(|(path, _, _)| path.len() > pos && path[..pos] == *dir && path[pos] == b'/')
+                    .count();
+                let children: Vec<_> = remaining[..len]
+                    .iter()
+                    .map(|(path, mode, id)| (path[pos + 1..].as_bstr(), *mode, *id))
+                    .collect();
+                tree.entries.push(gix_object::tree::Entry {
+                    mode: gix_object::tree::EntryKind::Tree.into(),
+                    filename: dir.into(),
+                    oid: write_tree_inner(repo, &children)?,
+                });
+                remaining = &remaining[len..];
+            }
+        }
+    }
+    tree.entries.sort();
+    Ok(repo.write_object(&tree)?.detach())
+}
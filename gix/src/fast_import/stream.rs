@@ -0,0 +1,574 @@
+use std::io::{BufRead, Read};
+
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// A reference to a commit in a stream, as used by `from` and `merge`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Commitish {
+    /// A mark that was previously assigned to a commit in the stream, like `:1`.
+    Mark(u64),
+    /// The id of a commit, which typically exists in the repository already.
+    Id(ObjectId),
+    /// The name of a branch or other reference, like `refs/heads/main`.
+    Name(BString),
+}
+
+/// The way the content of a file is provided in a [file change](FileChange::Modify).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataRef {
+    /// A mark that was previously assigned to a blob in the stream.
+    Mark(u64),
+    /// The id of a blob, or of a commit in case of submodules.
+    Id(ObjectId),
+    /// The data itself.
+    Inline(BString),
+}
+
+/// A change to the tree of the parent commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// Add or change the file at `path`.
+    Modify {
+        /// The mode of the file.
+        mode: EntryMode,
+        /// Its contents.
+        data: DataRef,
+        /// The path to the file, relative to the root of the tree.
+        path: BString,
+    },
+    /// Delete the file or directory at `path`.
+    Delete {
+        /// The path to delete, relative to the root of the tree.
+        path: BString,
+    },
+    /// Remove all files, which is typically followed by [`Modify`](Self::Modify) changes to create the tree from scratch.
+    DeleteAll,
+}
+
+/// A commit to add to a branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The full name of the branch to add the commit to.
+    pub branch: BString,
+    /// The mark of the commit, if it should be referable later in the stream.
+    pub mark: Option<u64>,
+    /// The author of the commit, or `None` if it's the same as the committer.
+    pub author: Option<gix_actor::Signature>,
+    /// The committer of the commit.
+    pub committer: gix_actor::Signature,
+    /// The commit message.
+    pub message: BString,
+    /// The first parent of the commit, or `None` to use the current tip of `branch`, if there is one.
+    pub from: Option<Commitish>,
+    /// Additional parents of the commit.
+    pub merges: Vec<Commitish>,
+    /// The changes to apply to the tree of the first parent, or to an empty tree, in order.
+    pub changes: Vec<FileChange>,
+}
+
+/// An annotated tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The name of the tag, without the `refs/tags/` prefix.
+    pub name: BString,
+    /// The mark of the tag, if it should be referable later in the stream.
+    pub mark: Option<u64>,
+    /// The commit to tag.
+    pub from: Commitish,
+    /// The creator of the tag, if known.
+    pub tagger: Option<gix_actor::Signature>,
+    /// The tag message.
+    pub message: BString,
+}
+
+/// A command in a `git fast-import` stream.
+///
+/// Note that commands for copying or renaming paths, as well as notes, aren't supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// A blob to be referred to by its mark later.
+    Blob {
+        /// The mark of the blob, which is required to be able to use it.
+        mark: Option<u64>,
+        /// The contents of the blob.
+        data: BString,
+    },
+    /// Add a commit to a branch.
+    Commit(Commit),
+    /// Create an annotated tag.
+    Tag(Tag),
+    /// Set a branch to a commit, or reset it to be empty if `from` is `None`.
+    Reset {
+        /// The full name of the branch.
+        branch: BString,
+        /// The commit to set it to.
+        from: Option<Commitish>,
+    },
+    /// Ask the importer to write out all data so far, which is a no-op for us.
+    Checkpoint,
+    /// A message to display by the importer.
+    Progress(BString),
+    /// Require a feature from the importer, like `done`.
+    Feature(BString),
+    /// Set an importer specific option.
+    Option(BString),
+    /// Mark the end of the stream.
+    Done,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Reader`](super::Reader).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("Line {line_number}: {message}: {line:?}")]
+        Invalid {
+            line_number: usize,
+            message: &'static str,
+            line: BString,
+        },
+        #[error("Line {line_number}: unsupported command {line:?}")]
+        Unsupported { line_number: usize, line: BString },
+    }
+}
+
+/// An iterator over the [commands](Command) of a `git fast-import` stream.
+pub struct Reader<R> {
+    read: R,
+    line: BString,
+    line_number: usize,
+    peeked: bool,
+    done: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Create a new instance to read commands from `read`.
+    pub fn new(read: R) -> Self {
+        Reader {
+            read,
+            line: BString::default(),
+            line_number: 0,
+            peeked: false,
+            done: false,
+        }
+    }
+
+    /// Read the next line into our buffer, without trailing newline and skipping comments, and return `false` if there is no more input.
+    ///
+    /// Comments are only allowed where a command is expected, so this must not be used to read data.
+    fn next_line(&mut self) -> Result<bool, decode::Error> {
+        loop {
+            if !self.next_raw_line()? {
+                return Ok(false);
+            }
+            if !self.line.starts_with(b"#") {
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Read the next line into our buffer as is, without trailing newline, and return `false` if there is no more input.
+    fn next_raw_line(&mut self) -> Result<bool, decode::Error> {
+        if self.peeked {
+            self.peeked = false;
+            return Ok(true);
+        }
+        self.line.clear();
+        if self.read.read_until(b'\n', &mut self.line)? == 0 {
+            return Ok(false);
+        }
+        self.line_number += 1;
+        if self.line.last() == Some(&b'\n') {
+            self.line.pop();
+        }
+        Ok(true)
+    }
+
+    /// Like `next_line()`, but skips empty lines and returns the line as well.
+    fn next_command_line(&mut self) -> Result<Option<BString>, decode::Error> {
+        while self.next_line()? {
+            if !self.line.is_empty() {
+                return Ok(Some(self.line.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the value of the current line if it starts with `prefix`, or put the line back.
+    fn optional(&mut self, prefix: &str) -> Result<Option<BString>, decode::Error> {
+        if !self.next_line()? {
+            return Ok(None);
+        }
+        match self.line.strip_prefix(prefix.as_bytes()) {
+            Some(value) => Ok(Some(value.into())),
+            None => {
+                self.peeked = true;
+                Ok(None)
+            }
+        }
+    }
+
+    fn required(&mut self, prefix: &str, message: &'static str) -> Result<BString, decode::Error> {
+        self.optional(prefix)?.ok_or_else(|| self.invalid(message))
+    }
+
+    fn invalid(&self, message: &'static str) -> decode::Error {
+        decode::Error::Invalid {
+            line_number: self.line_number,
+            message,
+            line: self.line.clone(),
+        }
+    }
+
+    fn mark(&mut self) -> Result<Option<u64>, decode::Error> {
+        self.optional("mark :")?
+            .map(|mark| parse_number(mark.as_ref()).ok_or_else(|| self.invalid("invalid mark")))
+            .transpose()
+    }
+
+    fn skip_original_oid(&mut self) -> Result<(), decode::Error> {
+        self.optional("original-oid ")?;
+        Ok(())
+    }
+
+    fn signature(&mut self, prefix: &str) -> Result<Option<gix_actor::Signature>, decode::Error> {
+        self.optional(prefix)?
+            .map(|value| {
+                gix_actor::SignatureRef::from_bytes::<()>(value.as_ref())
+                    .map(|sig| sig.to_owned())
+                    .map_err(|_| self.invalid("invalid signature"))
+            })
+            .transpose()
+    }
+
+    fn data(&mut self) -> Result<BString, decode::Error> {
+        let spec = self.required("data ", "expected 'data' command")?;
+        let mut data = BString::default();
+        if let Some(delimiter) = spec.strip_prefix(b"<<") {
+            let delimiter = delimiter.to_owned();
+            loop {
+                if !self.next_raw_line()? {
+                    return Err(self.invalid("missing end of delimited data"));
+                }
+                if self.line == delimiter {
+                    break;
+                }
+                data.extend_from_slice(&self.line);
+                data.push(b'\n');
+            }
+        } else {
+            let len = parse_number(spec.as_ref()).ok_or_else(|| self.invalid("invalid data length"))?;
+            (&mut self.read).take(len).read_to_end(&mut data)?;
+            if data.len() as u64 != len {
+                return Err(self.invalid("unexpected end of data"));
+            }
+            if self.read.fill_buf()?.first() == Some(&b'\n') {
+                self.read.consume(1);
+            }
+        }
+        Ok(data)
+    }
+
+    fn commitish(&self, value: &BStr) -> Result<Commitish, decode::Error> {
+        Ok(if let Some(mark) = value.strip_prefix(b":") {
+            Commitish::Mark(parse_number(mark.as_bstr()).ok_or_else(|| self.invalid("invalid mark"))?)
+        } else if let Ok(id) = ObjectId::from_hex(value) {
+            Commitish::Id(id)
+        } else {
+            Commitish::Name(value.to_owned())
+        })
+    }
+
+    fn path(&self, value: &BStr) -> Result<BString, decode::Error> {
+        let (path, _consumed) = gix_quote::ansi_c::undo(value).map_err(|_| self.invalid("invalid quoted path"))?;
+        Ok(path.into_owned())
+    }
+
+    fn commit(&mut self, branch: BString) -> Result<Command, decode::Error> {
+        let mark = self.mark()?;
+        self.skip_original_oid()?;
+        let author = self.signature("author ")?;
+        let committer = self
+            .signature("committer ")?
+            .ok_or_else(|| self.invalid("expected 'committer'"))?;
+        self.optional("encoding ")?;
+        let message = self.data()?;
+        let from = self
+            .optional("from ")?
+            .map(|from| self.commitish(from.as_ref()))
+            .transpose()?;
+        let mut merges = Vec::new();
+        while let Some(merge) = self.optional("merge ")? {
+            merges.push(self.commitish(merge.as_ref())?);
+        }
+
+        let mut changes = Vec::new();
+        while self.next_line()? {
+            if self.line.is_empty() {
+                continue;
+            }
+            let line = self.line.clone();
+            if let Some(rest) = line.strip_prefix(b"M ") {
+                let mut tokens = rest.splitn_str(3, " ");
+                let (Some(mode), Some(dataref), Some(path)) = (tokens.next(), tokens.next(), tokens.next()) else {
+                    return Err(self.invalid("expected 'M <mode> <dataref> <path>'"));
+                };
+                let mode = parse_mode(mode.as_bstr()).ok_or_else(|| self.invalid("invalid mode"))?;
+                let path = self.path(path.as_bstr())?;
+                let data = if dataref == b"inline" {
+                    DataRef::Inline(self.data()?)
+                } else if let Some(mark) = dataref.strip_prefix(b":") {
+                    DataRef::Mark(parse_number(mark.as_bstr()).ok_or_else(|| self.invalid("invalid mark"))?)
+                } else {
+                    DataRef::Id(ObjectId::from_hex(dataref).map_err(|_| self.invalid("invalid object id"))?)
+                };
+                changes.push(FileChange::Modify { mode, data, path });
+            } else if let Some(path) = line.strip_prefix(b"D ") {
+                changes.push(FileChange::Delete {
+                    path: self.path(path.as_bstr())?,
+                });
+            } else if line == "deleteall" {
+                changes.push(FileChange::DeleteAll);
+            } else if [b"C ".as_slice(), b"R ", b"N "]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+            {
+                return Err(decode::Error::Unsupported {
+                    line_number: self.line_number,
+                    line,
+                });
+            } else {
+                self.peeked = true;
+                break;
+            }
+        }
+
+        Ok(Command::Commit(Commit {
+            branch,
+            mark,
+            author,
+            committer,
+            message,
+            from,
+            merges,
+            changes,
+        }))
+    }
+
+    fn tag(&mut self, name: BString) -> Result<Command, decode::Error> {
+        let mark = self.mark()?;
+        let from = self.required("from ", "expected 'from'")?;
+        let from = self.commitish(from.as_ref())?;
+        self.skip_original_oid()?;
+        let tagger = self.signature("tagger ")?;
+        let message = self.data()?;
+        Ok(Command::Tag(Tag {
+            name,
+            mark,
+            from,
+            tagger,
+            message,
+        }))
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Command, decode::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = (|| -> Result<Option<Command>, decode::Error> {
+            let Some(line) = self.next_command_line()? else {
+                return Ok(None);
+            };
+            Ok(Some(if line == "blob" {
+                let mark = self.mark()?;
+                self.skip_original_oid()?;
+                Command::Blob {
+                    mark,
+                    data: self.data()?,
+                }
+            } else if let Some(branch) = line.strip_prefix(b"commit ") {
+                self.commit(branch.into())?
+            } else if let Some(name) = line.strip_prefix(b"tag ") {
+                self.tag(name.into())?
+            } else if let Some(branch) = line.strip_prefix(b"reset ") {
+                let from = self
+                    .optional("from ")?
+                    .map(|from| self.commitish(from.as_ref()))
+                    .transpose()?;
+                Command::Reset {
+                    branch: branch.into(),
+                    from,
+                }
+            } else if line == "checkpoint" {
+                Command::Checkpoint
+            } else if line == "done" {
+                self.done = true;
+                Command::Done
+            } else if let Some(message) = line.strip_prefix(b"progress ") {
+                Command::Progress(message.into())
+            } else if let Some(feature) = line.strip_prefix(b"feature ") {
+                Command::Feature(feature.into())
+            } else if let Some(option) = line.strip_prefix(b"option ") {
+                Command::Option(option.into())
+            } else {
+                return Err(decode::Error::Unsupported {
+                    line_number: self.line_number,
+                    line,
+                });
+            }))
+        })();
+        match res {
+            Ok(Some(cmd)) => Some(Ok(cmd)),
+            Ok(None) => None,
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn parse_mode(input: &BStr) -> Option<EntryMode> {
+    let mode = match u32::from_str_radix(input.to_str().ok()?, 8).ok()? {
+        0o644 => 0o100644,
+        0o755 => 0o100755,
+        mode => mode,
+    };
+    EntryMode::try_from(mode).ok()
+}
+
+fn parse_number(input: &BStr) -> Option<u64> {
+    input.to_str().ok()?.parse().ok()
+}
+
+/// Serialization
+impl Command {
+    /// Write this command in the `git fast-import` format to `out`.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        match self {
+            Command::Blob { mark, data } => {
+                out.write_all(b"blob\n")?;
+                write_mark(*mark, out)?;
+                write_data(data.as_ref(), out)?;
+            }
+            Command::Commit(commit) => {
+                writeln!(out, "commit {}", commit.branch)?;
+                write_mark(commit.mark, out)?;
+                if let Some(author) = &commit.author {
+                    write_signature("author", author, out)?;
+                }
+                write_signature("committer", &commit.committer, out)?;
+                write_data(commit.message.as_ref(), out)?;
+                if let Some(from) = &commit.from {
+                    write_commitish("from", from, out)?;
+                }
+                for merge in &commit.merges {
+                    write_commitish("merge", merge, out)?;
+                }
+                for change in &commit.changes {
+                    match change {
+                        FileChange::Modify { mode, data, path } => {
+                            write!(out, "M {:o} ", mode.0)?;
+                            match data {
+                                DataRef::Mark(mark) => write!(out, ":{mark} ")?,
+                                DataRef::Id(id) => write!(out, "{id} ")?,
+                                DataRef::Inline(_) => out.write_all(b"inline ")?,
+                            }
+                            write_path(path.as_ref(), out)?;
+                            if let DataRef::Inline(data) = data {
+                                write_data(data.as_ref(), out)?;
+                            }
+                        }
+                        FileChange::Delete { path } => {
+                            out.write_all(b"D ")?;
+                            write_path(path.as_ref(), out)?;
+                        }
+                        FileChange::DeleteAll => out.write_all(b"deleteall\n")?,
+                    }
+                }
+                out.write_all(b"\n")?;
+            }
+            Command::Tag(tag) => {
+                writeln!(out, "tag {}", tag.name)?;
+                write_mark(tag.mark, out)?;
+                write_commitish("from", &tag.from, out)?;
+                if let Some(tagger) = &tag.tagger {
+                    write_signature("tagger", tagger, out)?;
+                }
+                write_data(tag.message.as_ref(), out)?;
+            }
+            Command::Reset { branch, from } => {
+                writeln!(out, "reset {branch}")?;
+                if let Some(from) = from {
+                    write_commitish("from", from, out)?;
+                }
+                out.write_all(b"\n")?;
+            }
+            Command::Checkpoint => out.write_all(b"checkpoint\n")?,
+            Command::Progress(message) => writeln!(out, "progress {message}")?,
+            Command::Feature(feature) => writeln!(out, "feature {feature}")?,
+            Command::Option(option) => writeln!(out, "option {option}")?,
+            Command::Done => out.write_all(b"done\n")?,
+        }
+        Ok(())
+    }
+}
+
+fn write_mark(mark: Option<u64>, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    match mark {
+        Some(mark) => writeln!(out, "mark :{mark}"),
+        None => Ok(()),
+    }
+}
+
+fn write_data(data: &BStr, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    writeln!(out, "data {}", data.len())?;
+    out.write_all(data)?;
+    out.write_all(b"\n")
+}
+
+fn write_signature(field: &str, signature: &gix_actor::Signature, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    write!(out, "{field} ")?;
+    signature.write_to(out)?;
+    out.write_all(b"\n")
+}
+
+fn write_commitish(field: &str, commitish: &Commitish, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    match commitish {
+        Commitish::Mark(mark) => writeln!(out, "{field} :{mark}"),
+        Commitish::Id(id) => writeln!(out, "{field} {id}"),
+        Commitish::Name(name) => writeln!(out, "{field} {name}"),
+    }
+}
+
+/// Write `path` followed by a newline, quoting it if it would otherwise be ambiguous.
+fn write_path(path: &BStr, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    if !path.starts_with(b"\"") && !path.contains(&b'\n') {
+        out.write_all(path)?;
+        return out.write_all(b"\n");
+    }
+    let mut quoted = BString::from("\"");
+    for b in path.iter().copied() {
+        match b {
+            b'"' => quoted.push_str("\\\""),
+            b'\\' => quoted.push_str("\\\\"),
+            b'\n' => quoted.push_str("\\n"),
+            b => quoted.push(b),
+        }
+    }
+    quoted.push_str("\"\n");
+    out.write_all(&quoted)
+}
@@ -1,5 +1,8 @@
 #![allow(clippy::result_large_err)]
-use std::{borrow::Cow, path::Path};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
 
 use gix_macros::momo;
 use gix_ref::{
@@ -34,6 +37,8 @@ pub enum Error {
     },
     #[error("Could not edit HEAD reference with new default name")]
     EditHeadForDefaultBranch(#[from] crate::reference::edit::Error),
+    #[error("Could not obtain the directory with templates for the new repository")]
+    TemplateDir(#[from] gix_config::path::interpolate::Error),
 }
 
 impl ThreadSafeRepository {
@@ -58,19 +63,40 @@ impl ThreadSafeRepository {
     ///
     /// Instead of naming the default branch `master`, we name it `main` unless configured explicitly using the `init.defaultBranch`
     /// configuration key.
+    ///
+    /// Unless [a template directory](crate::create::Options::template_dir) is set, it's obtained from the `init.templateDir`
+    /// configuration key or the `GIT_TEMPLATE_DIR` environment variable, and the built-in templates are used if neither is set.
+    /// Configuration provided by the templates is respected, including `init.defaultBranch`.
     #[momo]
     pub fn init_opts(
         directory: impl AsRef<Path>,
         kind: crate::create::Kind,
-        create_options: crate::create::Options,
+        mut create_options: crate::create::Options,
         mut open_options: crate::open::Options,
     ) -> Result<Self, Error> {
+        // The configuration to obtain the templates from is only available once the repository exists.
+        let template_dir_from_config = create_options.template_dir.is_none();
+        if template_dir_from_config {
+            create_options.template_dir = Some(PathBuf::new());
+        }
         let path = crate::create::into(directory.as_ref(), kind, create_options)?;
-        let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
+        let (mut git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
         open_options.git_dir_trust = Some(gix_sec::Trust::Full);
         // The repo will use `core.precomposeUnicode` to adjust the value as needed.
         open_options.current_dir = gix_fs::current_dir(false)?.into();
-        let repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+        let mut repo =
+            ThreadSafeRepository::open_from_paths(git_dir.clone(), worktree_dir.clone(), open_options.clone())?;
+        if template_dir_from_config {
+            let template_dir = match repo.config.resolved.string(Init::TEMPLATE_DIR) {
+                Some(dir) if dir.is_empty() => Some(Cow::Borrowed(Path::new(""))),
+                _ => repo
+                    .config
+                    .apply_leniency(repo.config.trusted_file_path(Init::TEMPLATE_DIR))?,
+            };
+            if crate::create::write_templates(&mut git_dir, template_dir.as_deref())? {
+                repo = ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, open_options)?;
+            }
+        }
 
         let branch_name = repo
             .config
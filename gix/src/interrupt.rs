@@ -1,9 +1,12 @@
-//! Process-global interrupt handling
+//! Process-global and per-operation interrupt handling
 //!
 //! This module contains facilities to globally request an interrupt, which will cause supporting computations to
 //! abort once it is observed.
 //! Such checks for interrupts are provided in custom implementations of various traits to transparently add interrupt
 //! support to methods who wouldn't otherwise by injecting it. see [`Read`].
+//!
+//! To cancel individual operations without affecting others in the same process, create a [`Token`] for each of them
+//! and pass it wherever a `should_interrupt` flag is expected.
 
 #[cfg(feature = "interrupt")]
 mod init {
@@ -145,12 +148,81 @@ mod init {
 }
 use std::{
     io,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 #[cfg(feature = "interrupt")]
 pub use init::{init_handler, Deregister};
 
+/// A cancellation token to interrupt a single operation, or a group of operations that share it, without affecting any other.
+///
+/// It dereferences to an [`AtomicBool`], so `&token` can be passed to all methods that take a `should_interrupt` flag,
+/// like fetching, checking out or walking directories, and [`shared_flag()`](Token::shared_flag()) returns a flag for
+/// use in places that have to own it, like [status](crate::status::Platform::should_interrupt_owned()) and
+/// [revision walks](crate::revision::walk::Platform::should_interrupt_owned()).
+///
+/// Cloned tokens share the same state, so cancelling one cancels all of them.
+///
+/// ### Relation to the global interrupt
+///
+/// Tokens are independent of the process-global flag that [`trigger()`] sets. To also cancel an operation on user interrupts,
+/// [`cancel()`](Token::cancel()) its token from the `interrupt` callback of [`init_handler()`], which is safe as
+/// cancellation only sets an atomic flag.
+#[derive(Debug, Default, Clone)]
+pub struct Token {
+    flag: Arc<AtomicBool>,
+}
+
+/// Lifecycle
+impl Token {
+    /// Create a new token which isn't cancelled.
+    pub fn new() -> Self {
+        Token::default()
+    }
+}
+
+/// Access
+impl Token {
+    /// Request all operations using this token to stop as soon as possible.
+    ///
+    /// This only sets a flag and is safe to call from signal handlers.
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+
+    /// Return `true` if [`cancel()`](Self::cancel()) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Allow operations using this token to proceed again, which is useful to reuse it after an operation was cancelled.
+    pub fn reset(&self) {
+        self.flag.store(false, Ordering::SeqCst);
+    }
+
+    /// Return the flag behind this token for use in places that need to own it.
+    pub fn shared_flag(&self) -> Arc<AtomicBool> {
+        self.flag.clone()
+    }
+}
+
+impl std::ops::Deref for Token {
+    type Target = AtomicBool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.flag
+    }
+}
+
+impl From<Arc<AtomicBool>> for Token {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        Token { flag }
+    }
+}
+
 /// A wrapper for an inner iterator which will check for interruptions on each iteration.
 pub struct Iter<I, EFN> {
     /// The actual iterator to yield elements from.
@@ -110,6 +110,7 @@ pub use gix_features::{
     progress::{Count, DynNestedProgress, NestedProgress, Progress},
     threading,
 };
+pub use gix_fetchhead as fetchhead;
 pub use gix_fs as fs;
 pub use gix_glob as glob;
 pub use gix_hash as hash;
@@ -178,21 +179,42 @@ pub use types::{Pathspec, PathspecDetached, Submodule};
 #[allow(clippy::empty_docs)]
 pub mod clone;
 pub mod commit;
+
+///
+#[cfg(feature = "async-facade")]
+pub mod async_repository;
+#[cfg(feature = "async-facade")]
+pub use async_repository::AsyncRepository;
 ///
 #[cfg(feature = "dirwalk")]
 #[allow(clippy::empty_docs)]
 pub mod dirwalk;
+#[cfg(feature = "fast-import")]
+pub mod fast_import;
 pub mod head;
 pub mod id;
+#[cfg(feature = "merge")]
+pub mod merge;
 pub mod object;
 #[cfg(feature = "attributes")]
 pub mod pathspec;
+#[cfg(all(feature = "rebase", feature = "blocking-network-client"))]
+pub mod pull;
+#[cfg(feature = "rebase")]
+pub mod rebase;
 pub mod reference;
+#[cfg(feature = "repack")]
+pub mod repack;
 pub mod repository;
+#[cfg(feature = "merge")]
+pub mod sandbox;
+#[cfg(feature = "merge")]
+pub use sandbox::Sandbox;
+#[cfg(feature = "serve")]
+pub mod serve;
 #[cfg(feature = "attributes")]
 pub mod submodule;
 pub mod tag;
-#[cfg(any(feature = "dirwalk", feature = "status"))]
 pub(crate) mod util;
 
 ///
@@ -0,0 +1,166 @@
+//! Merge trees and blobs without the need for a worktree, or merge branches into the current branch.
+//!
+//! Use [`Repository::merge_trees_to_index()`](crate::Repository::merge_trees_to_index()) to merge trees into an index,
+//! and [`Repository::merge()`](crate::Repository::merge()) to merge a branch into `HEAD` and the worktree, similar to `git merge`.
+use gix_hash::ObjectId;
+
+pub use gix_merge::{blob, tree};
+
+/// Determine if a [merge](crate::Repository::merge()) may fast-forward, as configured by `merge.ff`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FastForward {
+    /// Fast-forward if possible, and create a merge commit otherwise.
+    #[default]
+    Allow,
+    /// Only allow fast-forwards, and fail if a merge commit would have to be created.
+    Only,
+    /// Always create a merge commit, even if a fast-forward would be possible.
+    Never,
+}
+
+/// Options for use in [`Repository::merge()`](crate::Repository::merge()).
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+    /// Whether or not to fast-forward, or `None` to use the value configured by `merge.ff`.
+    pub fast_forward: Option<FastForward>,
+    /// The message of the merge commit, or `None` to generate it like `git` does, which also lists the merged commits
+    /// if configured by `merge.log`.
+    pub message: Option<String>,
+}
+
+/// The outcome of [`Repository::merge()`](crate::Repository::merge()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// All commits to merge were already contained in `HEAD`, so nothing was done.
+    UpToDate,
+    /// `HEAD` was fast-forwarded to the commit to merge, and the worktree was updated accordingly.
+    FastForward {
+        /// The commit `HEAD` pointed to before, or `None` if it was unborn.
+        previous: Option<ObjectId>,
+        /// The commit `HEAD` points to now.
+        new: ObjectId,
+    },
+    /// A merge commit was created, and the worktree was updated accordingly.
+    Merge {
+        /// The id of the newly created merge commit.
+        commit: ObjectId,
+    },
+    /// The merge couldn't be completed automatically, and no commit was created.
+    ///
+    /// The index contains the conflicting entries in their stages and the worktree contains files with conflict markers,
+    /// while `MERGE_HEAD` and `MERGE_MSG` were written so the merge can be concluded once all conflicts are resolved.
+    Conflict {
+        /// All paths that couldn't be merged automatically, sorted by path.
+        conflicts: Vec<tree::Conflict>,
+    },
+}
+
+/// The error returned by [`Repository::merge()`](crate::Repository::merge()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot merge in a bare repository as it has no worktree")]
+    BareRepository,
+    #[error("Could not find '{name}' to merge")]
+    NotFound { name: crate::bstr::BString },
+    #[error(transparent)]
+    FindExistingReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    RevParse(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelObject(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    ConfigValue(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    MergeBase(#[from] crate::repository::merge_base::Error),
+    #[error("Refusing to merge unrelated histories")]
+    UnrelatedHistories,
+    #[error("Not possible to fast-forward, and only fast-forwards are allowed")]
+    NotPossibleToFastForward,
+    #[error("A merge is already in progress, as MERGE_HEAD exists")]
+    MergeInProgress,
+    #[error("The worktree or index has local changes which would be affected by the merge")]
+    LocalChanges,
+    #[error("Untracked working tree files would be overwritten by the merge: {}", paths.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", "))]
+    UntrackedFilesWouldBeOverwritten { paths: Vec<crate::bstr::BString> },
+    #[error(transparent)]
+    IsDirty(#[from] crate::status::is_dirty::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    TreeMergeOptions(#[from] tree_options::Error),
+    #[error(transparent)]
+    MergeTrees(#[from] trees_to_index::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Commit(#[from] crate::commit::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    Traverse(#[from] crate::revision::walk::iter::Error),
+    #[error("Could not update the worktree or write merge state files")]
+    Io(#[from] std::io::Error),
+    #[error("The operation was interrupted")]
+    Interrupted,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree_options {
+    /// The error returned by [Repository::tree_merge_options()](crate::Repository::tree_merge_options()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ConflictStyle(#[from] crate::config::key::GenericErrorWithValue),
+        #[error("Couldn't obtain configuration for core.protect*")]
+        BooleanConfig(#[from] crate::config::boolean::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod trees_to_index {
+    /// The error returned by [Repository::merge_trees_to_index()](crate::Repository::merge_trees_to_index()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        MergeTrees(#[from] gix_merge::tree::Error),
+    }
+
+    /// The outcome of [Repository::merge_trees_to_index()](crate::Repository::merge_trees_to_index()).
+    #[derive(Clone)]
+    pub struct Outcome {
+        /// The merged index, with conflicting entries placed into stages 1, 2 and 3.
+        ///
+        /// It is located at the path of the repository's index, but lives only in memory until it's written.
+        pub index: gix_index::File,
+        /// All paths that couldn't be merged automatically, sorted by path.
+        pub conflicts: Vec<gix_merge::tree::Conflict>,
+    }
+
+    impl Outcome {
+        /// Return `true` if at least one path couldn't be merged automatically.
+        pub fn has_conflicts(&self) -> bool {
+            !self.conflicts.is_empty()
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Inspect many objects in one go, similar to `git cat-file --batch` and `git cat-file --batch-check`.
+use std::io::{BufRead, Write};
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// A single part of a [`Format`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Atom {
+    /// Literal text to print as is.
+    Literal(BString),
+    /// `%(objectname)`, the full hexadecimal id of the object.
+    ObjectName,
+    /// `%(objecttype)`, the kind of the object, like `blob`.
+    ObjectType,
+    /// `%(objectsize)`, the size of the object in bytes once decompressed.
+    ObjectSize,
+    /// `%(rest)`, the text following the first whitespace of the input line.
+    Rest,
+}
+
+/// A parsed format string like `%(objectname) %(objecttype) %(objectsize)`, which defines the header line printed
+/// for each object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    atoms: Vec<Atom>,
+}
+
+impl Default for Format {
+    /// The format used by `git cat-file --batch`.
+    fn default() -> Self {
+        Format {
+            atoms: vec![
+                Atom::ObjectName,
+                Atom::Literal(" ".into()),
+                Atom::ObjectType,
+                Atom::Literal(" ".into()),
+                Atom::ObjectSize,
+            ],
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod format {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Format::from_bytes()`](super::Format::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Unknown format atom %({name})")]
+        UnknownAtom { name: BString },
+        #[error("Format atom starting at byte {position} is not terminated by ')'")]
+        Unterminated { position: usize },
+    }
+}
+
+impl Format {
+    /// Parse `format` like `%(objectname) %(objecttype)`, where `%%` can be used to produce a literal `%`.
+    pub fn from_bytes(format: &[u8]) -> Result<Self, format::Error> {
+        let mut atoms = Vec::new();
+        let mut literal = BString::default();
+        let mut pos = 0;
+        while pos < format.len() {
+            match &format[pos..] {
+                [b'%', b'%', ..] => {
+                    literal.push(b'%');
+                    pos += 2;
+                }
+                [b'%', b'(', rest @ ..] => {
+                    let end = rest
+                        .find_byte(b')')
+                        .ok_or(format::Error::Unterminated { position: pos })?;
+                    let atom = match &rest[..end] {
+                        b"objectname" => Atom::ObjectName,
+                        b"objecttype" => Atom::ObjectType,
+                        b"objectsize" => Atom::ObjectSize,
+                        b"rest" => Atom::Rest,
+                        name => return Err(format::Error::UnknownAtom { name: name.into() }),
+                    };
+                    if !literal.is_empty() {
+                        atoms.push(Atom::Literal(std::mem::take(&mut literal)));
+                    }
+                    atoms.push(atom);
+                    pos += 2 + end + 1;
+                }
+                [byte, ..] => {
+                    literal.push(*byte);
+                    pos += 1;
+                }
+                [] => unreachable!("loop condition"),
+            }
+        }
+        if !literal.is_empty() {
+            atoms.push(Atom::Literal(literal));
+        }
+        Ok(Format { atoms })
+    }
+
+    /// Return all atoms of this format in order.
+    pub fn atoms(&self) -> &[Atom] {
+        &self.atoms
+    }
+
+    /// Return `true` if `%(rest)` is used, which causes input lines to be split at the first whitespace.
+    pub fn uses_rest(&self) -> bool {
+        self.atoms.iter().any(|atom| matches!(atom, Atom::Rest))
+    }
+
+    fn write_to(
+        &self,
+        out: &mut dyn Write,
+        id: &gix_hash::oid,
+        header: &gix_odb::find::Header,
+        rest: &BStr,
+    ) -> std::io::Result<()> {
+        for atom in &self.atoms {
+            match atom {
+                Atom::Literal(text) => out.write_all(text)?,
+                Atom::ObjectName => write!(out, "{id}")?,
+                Atom::ObjectType => write!(out, "{}", header.kind())?,
+                Atom::ObjectSize => write!(out, "{}", header.size())?,
+                Atom::Rest => out.write_all(rest)?,
+            }
+        }
+        out.write_all(b"\n")
+    }
+}
+
+/// Options for use in [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()).
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The format of the header line printed for each object.
+    pub format: Format,
+    /// If `true`, the object data is printed after the header, followed by a newline, like `git cat-file --batch`.
+    /// Otherwise only the header is printed, like `git cat-file --batch-check`.
+    pub contents: bool,
+    /// If `true`, output is only flushed once all input was processed, which is faster but prevents interactive use.
+    pub buffer: bool,
+}
+
+/// The error returned by [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    FindHeader(#[from] crate::object::find::Error),
+}
+
+/// Statistics about a [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()) invocation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of objects that were found and printed.
+    pub found: usize,
+    /// The amount of input lines that didn't name an existing object.
+    pub missing: usize,
+    /// The amount of input lines that named more than one object.
+    pub ambiguous: usize,
+}
+
+/// Batch access
+impl crate::Repository {
+    /// Read one object specification per line from `input`, like `HEAD:README.md` or a full hexadecimal id,
+    /// and print information about the object it names to `out` according to `options`, until `input` is depleted.
+    ///
+    /// Input lines that don't name an object are answered with `<spec> missing`, and those that name more than one
+    /// with `<spec> ambiguous`, just like `git cat-file --batch` does.
+    /// All lookups are performed with the same object database handle, making this suitable for scripts that
+    /// need to look at a lot of objects.
+    pub fn cat_file_batch(
+        &self,
+        input: impl BufRead,
+        out: &mut dyn Write,
+        options: &Options,
+    ) -> Result<Outcome, Error> {
+        let mut outcome = Outcome::default();
+        let uses_rest = options.format.uses_rest();
+        for line in input.split(b'\n') {
+            let mut line = line?;
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let line = line.as_bstr();
+            let (spec, rest) = if uses_rest {
+                match line.find_byteset(b" \t") {
+                    Some(pos) => (line[..pos].as_bstr(), line[pos + 1..].as_bstr()),
+                    None => (line, "".into()),
+                }
+            } else {
+                (line, "".into())
+            };
+
+            let id = match self.rev_parse_single(spec) {
+                Ok(id) => id.detach(),
+                Err(err) if is_ambiguous(&err) => {
+                    writeln!(out, "{spec} ambiguous")?;
+                    outcome.ambiguous += 1;
+                    continue;
+                }
+                Err(_) => {
+                    writeln!(out, "{spec} missing")?;
+                    outcome.missing += 1;
+                    continue;
+                }
+            };
+            let Some(header) = self.try_find_header(id)? else {
+                writeln!(out, "{spec} missing")?;
+                outcome.missing += 1;
+                continue;
+            };
+            options.format.write_to(out, &id, &header, rest)?;
+            if options.contents {
+                out.write_all(&self.find_object(id)?.data)?;
+                out.write_all(b"\n")?;
+            }
+            outcome.found += 1;
+            if !options.buffer {
+                out.flush()?;
+            }
+        }
+        out.flush()?;
+        Ok(outcome)
+    }
+}
+
+fn is_ambiguous(err: &crate::revision::spec::parse::single::Error) -> bool {
+    use crate::revision::spec::parse::{single, Error};
+    matches!(
+        err,
+        single::Error::Parse(Error::AmbiguousPrefix { .. } | Error::AmbiguousRefAndObject { .. })
+    )
+}
@@ -20,6 +20,23 @@ mod error {
 
 pub use error::Error;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "attributes")]
+pub mod message_utf8 {
+    /// The error returned by [`Commit::message_utf8()`](crate::Commit::message_utf8()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Decode(#[from] gix_object::decode::Error),
+        #[error("The encoding of the commit message is unknown")]
+        UnknownEncoding(#[from] gix_filter::worktree::encoding::for_label::Error),
+        #[error("The commit message could not be converted to UTF-8")]
+        ConvertToUtf8(#[from] gix_filter::worktree::encode_to_git::Error),
+    }
+}
+
 /// Remove Lifetime
 impl<'repo> Commit<'repo> {
     /// Create an owned instance of this object, copying our data in the process.
@@ -59,6 +76,30 @@ impl<'repo> Commit<'repo> {
     pub fn message_raw(&self) -> Result<&'_ BStr, gix_object::decode::Error> {
         gix_object::CommitRefIter::from_bytes(&self.data).message()
     }
+    /// Decode the commit object until the message and return it converted to UTF-8 from the encoding named
+    /// in its `encoding` header, similar to what `git log` does by default.
+    ///
+    /// The message is returned as is if there is no such header, or if it names `UTF-8` already.
+    /// Use [`message_raw()`](Self::message_raw()) instead to fall back to the unconverted message in case of errors.
+    #[cfg(feature = "attributes")]
+    pub fn message_utf8(&self) -> Result<std::borrow::Cow<'_, BStr>, message_utf8::Error> {
+        let commit = self.decode()?;
+        let Some(encoding) = commit.encoding else {
+            return Ok(commit.message.into());
+        };
+        let encoding = gix_filter::worktree::encoding::for_label(encoding)?;
+        if encoding.is_utf8() {
+            return Ok(commit.message.into());
+        }
+        let mut buf = Vec::new();
+        gix_filter::worktree::encode_to_git(
+            commit.message,
+            encoding,
+            &mut buf,
+            gix_filter::worktree::encode_to_git::RoundTripCheck::Skip,
+        )?;
+        Ok(bstr::BString::from(buf).into())
+    }
     /// Obtain the message by using intricate knowledge about the encoding, which is fastest and
     /// can't fail at the expense of error handling.
     pub fn message_raw_sloppy(&self) -> &BStr {
@@ -10,6 +10,8 @@ pub(crate) mod cache {
     pub use gix_pack::cache::object::MemoryCappedHashmap;
 }
 pub use errors::{conversion, find, write};
+#[cfg(feature = "revision")]
+pub mod batch;
 ///
 #[allow(clippy::empty_docs)]
 pub mod blob;
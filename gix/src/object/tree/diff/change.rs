@@ -83,6 +83,23 @@ impl<'a, 'old, 'new> super::Change<'a, 'old, 'new> {
     ) -> Result<crate::object::blob::diff::Platform<'b>, crate::object::blob::diff::init::Error> {
         crate::object::blob::diff::Platform::from_tree_change(self, resource_cache)
     }
+
+    /// Return `true` if this change is a rename that only changed the case of its location, like `Foo.txt` to `foo.txt`
+    /// or `Dir/a` to `dir/a`.
+    ///
+    /// On case-insensitive filesystems, both locations refer to the same file, so such a change can't be applied
+    /// by deleting the source and adding the destination.
+    /// Note that only ASCII characters are folded, just like `git` does with `core.ignoreCase`.
+    pub fn is_case_only_rename(&self) -> bool {
+        match self.event {
+            Event::Rewrite {
+                source_location,
+                copy: false,
+                ..
+            } => source_location != self.location && source_location.eq_ignore_ascii_case(self.location),
+            _ => false,
+        }
+    }
 }
 
 impl<'a, 'old, 'new> Event<'a, 'old, 'new> {
@@ -11,6 +11,10 @@ pub struct Permissions {
     pub config: permissions::Config,
     /// Permissions related to where `gitattributes` should be loaded from.
     pub attributes: permissions::Attributes,
+    /// Whether hooks may be run, like the `reference-transaction` hook whenever references are edited.
+    ///
+    /// It's disabled in all presets as hooks are arbitrary programs, and need to be enabled explicitly.
+    pub hooks: bool,
 }
 
 /// The options used in [`ThreadSafeRepository::open_opts()`][crate::ThreadSafeRepository::open_opts()].
@@ -35,6 +39,9 @@ pub struct Options {
     pub(crate) api_config_overrides: Vec<BString>,
     pub(crate) cli_config_overrides: Vec<BString>,
     pub(crate) open_path_as_is: bool,
+    /// If `false`, don't apply environment variables that are specific to the repository opened from the environment,
+    /// like `GIT_INDEX_FILE`, similar to how `git` clears its `local_repo_env` when operating on other repositories.
+    pub(crate) local_repo_env: bool,
     /// Internal to pass an already obtained CWD on to where it may also be used. This avoids the CWD being queried more than once per repo.
     pub(crate) current_dir: Option<PathBuf>,
 }
@@ -14,6 +14,7 @@ impl Default for Options {
             lenient_config: true,
             bail_if_untrusted: false,
             open_path_as_is: false,
+            local_repo_env: true,
             api_config_overrides: Vec::new(),
             cli_config_overrides: Vec::new(),
             current_dir: None,
@@ -150,6 +151,13 @@ impl Options {
 }
 
 impl Options {
+    /// Return options suitable for opening a repository other than the one opened with these options, like submodules,
+    /// which won't be affected by variables like `GIT_INDEX_FILE` that are meant for the original repository only.
+    pub(crate) fn for_other_repository(mut self) -> Self {
+        self.local_repo_env = false;
+        self
+    }
+
     pub(crate) fn current_dir_or_empty(&self) -> &std::path::Path {
         self.current_dir.as_deref().unwrap_or(std::path::Path::new(""))
     }
@@ -167,6 +175,7 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                local_repo_env: true,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
                 current_dir: None,
@@ -179,6 +188,7 @@ impl gix_sec::trust::DefaultForLevel for Options {
                 bail_if_untrusted: false,
                 lenient_config: true,
                 open_path_as_is: false,
+                local_repo_env: true,
                 lossy_config: None,
                 api_config_overrides: Vec::new(),
                 cli_config_overrides: Vec::new(),
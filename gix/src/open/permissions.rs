@@ -112,6 +112,16 @@ impl Default for Attributes {
 }
 
 /// Permissions related to the usage of environment variables
+///
+/// Most environment variables are applied as configuration values of the [`EnvOverride`](gix_config::Source::EnvOverride)
+/// source when the repository is opened, so they are visible in its configuration snapshot and take precedence over
+/// configuration files. Which key a variable sets can be seen with [`Key::environment_override()`](crate::config::tree::Key::environment_override()),
+/// for instance `GIT_INDEX_FILE` sets `gitoxide.core.indexFile`, `GIT_OBJECT_DIRECTORY` sets `gitoxide.objects.directory`
+/// and `GIT_ALTERNATE_OBJECT_DIRECTORIES` sets `gitoxide.objects.alternates`.
+/// Each variable is only read if the category it belongs to is allowed.
+///
+/// The latter three only apply to the repository that is opened directly, but not to its submodules, linked worktrees or clones,
+/// just like `git` doesn't pass them on when operating on other repositories.
 #[derive(Debug, Clone, Copy)]
 pub struct Environment {
     /// Control whether resources pointed to by `XDG_CONFIG_HOME` can be used when looking up common configuration values.
@@ -177,6 +187,7 @@ impl Permissions {
             env: Environment::all(),
             config: Config::all(),
             attributes: Attributes::all(),
+            hooks: false,
         }
     }
 
@@ -187,6 +198,7 @@ impl Permissions {
             env: Environment::all(),
             config: Config::all(),
             attributes: Attributes::all(),
+            hooks: false,
         }
     }
 
@@ -196,6 +208,7 @@ impl Permissions {
             config: Config::isolated(),
             attributes: Attributes::isolated(),
             env: Environment::isolated(),
+            hooks: false,
         }
     }
 }
@@ -165,11 +165,13 @@ impl ThreadSafeRepository {
             lenient_config,
             bail_if_untrusted,
             open_path_as_is: _,
+            local_repo_env,
             permissions:
                 Permissions {
                     ref env,
                     config,
                     attributes,
+                    hooks: _,
                 },
             ref api_config_overrides,
             ref cli_config_overrides,
@@ -242,6 +244,7 @@ impl ThreadSafeRepository {
             git_install_dir.as_deref(),
             home.as_deref(),
             *env,
+            local_repo_env,
             attributes,
             config,
             lenient_config,
@@ -299,6 +302,7 @@ impl ThreadSafeRepository {
 
         refs.write_reflog = config::cache::util::reflog_or_default(config.reflog, worktree_dir.is_some());
         refs.namespace.clone_from(&config.refs_namespace);
+        refs.fsync = config.fsync(|components| components.references);
         let replacements = replacement_objects_refs_prefix(&config.resolved, lenient_config, filter_config_section)?
             .and_then(|prefix| {
                 let _span = gix_trace::detail!("find replacement objects");
@@ -318,15 +322,40 @@ impl ThreadSafeRepository {
             })
             .unwrap_or_default();
 
+        let objects_dir = config
+            .resolved
+            .string_filter(
+                gitoxide::Objects::DIRECTORY.logical_name().as_str(),
+                &mut filter_config_section,
+            )
+            .map_or_else(
+                || common_dir_ref.join("objects"),
+                |dir| current_dir.join(gix_path::from_bstr(dir)),
+            );
+        let alternates = config
+            .resolved
+            .string_filter(
+                gitoxide::Objects::ALTERNATES.logical_name().as_str(),
+                &mut filter_config_section,
+            )
+            .and_then(|dirs| gix_path::try_from_bstr(dirs).ok().map(Cow::into_owned))
+            .map(|dirs| {
+                std::env::split_paths(&dirs)
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
         Ok(ThreadSafeRepository {
             objects: OwnShared::new(gix_odb::Store::at_opts(
-                common_dir_ref.join("objects"),
+                objects_dir,
                 &mut replacements.into_iter(),
                 gix_odb::store::init::Options {
                     slots: object_store_slots,
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
                     current_dir: current_dir.to_owned().into(),
+                    fsync_loose_objects: config.fsync(|components| components.loose_objects),
+                    alternates,
                 },
             )?),
             common_dir,
@@ -0,0 +1,70 @@
+//! Fetch from a remote and integrate the configured upstream branch into `HEAD`, similar to `git pull`.
+use crate::bstr::BString;
+
+/// Options for use in [`Repository::pull()`](crate::Repository::pull()).
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+    /// If `true`, rebase `HEAD` onto the fetched branch instead of merging it, or `None` to use the value configured by
+    /// `branch.<name>.rebase` or `pull.rebase`, which defaults to merging.
+    pub rebase: Option<bool>,
+    /// Control fast-forwards when merging, or `None` to use the value configured by `pull.ff` or `merge.ff`.
+    ///
+    /// It has no effect when rebasing.
+    pub fast_forward: Option<crate::merge::FastForward>,
+    /// Whether local changes should be stashed before rebasing, or `None` to use the value configured by `rebase.autoStash`.
+    ///
+    /// It has no effect when merging.
+    pub autostash: Option<bool>,
+}
+
+/// The outcome of [`Repository::pull()`](crate::Repository::pull()).
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The outcome of fetching from the remote.
+    pub fetch: crate::remote::fetch::Outcome,
+    /// The id of the fetched commit that was integrated into `HEAD`.
+    pub upstream: gix_hash::ObjectId,
+    /// How the fetched commit was integrated into `HEAD`.
+    pub integration: Integration,
+}
+
+/// The way the fetched commit was integrated into `HEAD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Integration {
+    /// The fetched commit was merged into `HEAD`.
+    Merge(crate::merge::Outcome),
+    /// `HEAD` was rebased onto the fetched commit.
+    Rebase(crate::rebase::Outcome),
+}
+
+/// The error returned by [`Repository::pull()`](crate::Repository::pull()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot pull as HEAD isn't pointing to a branch")]
+    DetachedHead,
+    #[error("The branch '{branch}' has no upstream branch configured in 'branch.<name>.merge'")]
+    NoUpstream { branch: BString },
+    #[error("The remote didn't advertise the upstream branch '{name}'")]
+    UpstreamNotFound { name: BString },
+    #[error(transparent)]
+    FindRemote(#[from] crate::remote::find::for_fetch::Error),
+    #[error(transparent)]
+    UpstreamName(#[from] crate::repository::branch_remote_ref_name::Error),
+    #[error(transparent)]
+    Connect(#[from] crate::remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+    #[error(transparent)]
+    Fetch(#[from] crate::remote::fetch::Error),
+    #[error(transparent)]
+    FindExistingReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    ConfigValue(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    Merge(#[from] crate::merge::Error),
+    #[error(transparent)]
+    Rebase(#[from] crate::rebase::Error),
+}
@@ -0,0 +1,118 @@
+//! Rebase commits onto another base, similar to `git rebase`, and resume it once conflicts are resolved.
+//!
+//! The state of a rebase in progress is kept in the `.git/rebase-merge` directory just like `git` does it, so it can be
+//! continued with `git rebase --continue` as well as with [`Repository::rebase_continue()`](crate::Repository::rebase_continue()).
+use gix_hash::ObjectId;
+
+/// Options for use in [`Repository::rebase()`](crate::Repository::rebase()).
+#[derive(Default, Debug, Clone)]
+pub struct Options {
+    /// The commit to put the rebased commits on top of, similar to `git rebase --onto`, or `None` to use the upstream commit.
+    pub onto: Option<ObjectId>,
+    /// If `true`, local changes are stashed before the rebase and applied again once it's done, or `None` to use
+    /// the value configured by `rebase.autoStash`.
+    ///
+    /// If local changes are present and this is `false`, the rebase fails.
+    pub autostash: Option<bool>,
+}
+
+/// The outcome of [`Repository::rebase()`](crate::Repository::rebase()) and [`Repository::rebase_continue()`](crate::Repository::rebase_continue()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// `HEAD` is already based on the commit to rebase onto, so nothing was done.
+    UpToDate,
+    /// All commits were rebased, and `HEAD` was updated to point to the last one of them.
+    Complete {
+        /// The commit `HEAD` pointed to before the rebase started.
+        previous: ObjectId,
+        /// The commit `HEAD` points to now.
+        new: ObjectId,
+        /// What happened to the local changes that were stashed before the rebase, if there were any.
+        autostash: Option<Autostash>,
+    },
+    /// A commit couldn't be applied without conflicts, which now have to be resolved in the index and worktree before
+    /// the rebase can [be continued](crate::Repository::rebase_continue()).
+    Conflict {
+        /// The commit that couldn't be applied.
+        commit: ObjectId,
+        /// All paths that couldn't be merged automatically, sorted by path.
+        conflicts: Vec<crate::merge::tree::Conflict>,
+    },
+}
+
+/// The outcome of applying the local changes that were stashed before a rebase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Autostash {
+    /// The local changes were applied to the worktree without conflicts.
+    Applied,
+    /// The local changes couldn't be applied without conflicts, and were stored in `refs/stash` instead, leaving
+    /// the worktree unchanged.
+    Conflict {
+        /// The id of the stash commit containing the local changes.
+        stash: ObjectId,
+    },
+}
+
+/// The error returned by [`Repository::rebase()`](crate::Repository::rebase()) and its sibling methods.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot rebase in a bare repository as it has no worktree")]
+    BareRepository,
+    #[error("Cannot rebase as HEAD doesn't point to a commit yet")]
+    UnbornHead,
+    #[error("Another operation is already in progress: {0:?}")]
+    OperationInProgress(crate::state::InProgress),
+    #[error("There is no rebase in progress")]
+    NoRebaseInProgress,
+    #[error("Refusing to rebase onto unrelated histories")]
+    UnrelatedHistories,
+    #[error("The index and worktree have local changes, and autostash is disabled")]
+    LocalChanges,
+    #[error("The index still has unresolved conflicts")]
+    UnresolvedConflicts,
+    #[error("Could not parse the rebase state file '{name}'")]
+    InvalidState { name: &'static str },
+    #[error("The command '{command}' in the todo-list isn't supported")]
+    UnsupportedTodoCommand { command: crate::bstr::BString },
+    #[error(transparent)]
+    Merge(#[from] crate::merge::Error),
+    #[error(transparent)]
+    MergeBase(#[from] crate::repository::merge_base::Error),
+    #[error(transparent)]
+    MergeTrees(#[from] crate::merge::trees_to_index::Error),
+    #[error(transparent)]
+    TreeMergeOptions(#[from] crate::merge::tree_options::Error),
+    #[error(transparent)]
+    FindExistingReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    HeadId(#[from] crate::reference::head_id::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Signature(#[from] crate::config::time::Error),
+    #[error("Author or committer are not configured")]
+    SignatureMissing,
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    Traverse(#[from] crate::revision::walk::iter::Error),
+    #[error("Could not read or write the rebase state or the worktree")]
+    Io(#[from] std::io::Error),
+}
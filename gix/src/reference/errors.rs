@@ -18,6 +18,10 @@ pub mod edit {
         LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
         #[error(transparent)]
         ParseCommitterTime(#[from] crate::config::time::Error),
+        #[error("Could not interpolate the path configured in core.hooksPath")]
+        HooksPath(#[from] gix_config::path::interpolate::Error),
+        #[error("Could not flush written objects to permanent storage before updating references")]
+        FlushObjects(#[source] std::io::Error),
     }
 }
 
@@ -1,5 +1,6 @@
 //!
 #![allow(clippy::empty_docs)]
+use gix_hash::ObjectId;
 use gix_object::commit::MessageRef;
 use gix_ref::file::ReferenceExt;
 
@@ -8,12 +9,104 @@ use crate::{
     Reference,
 };
 
+/// The error returned by [`Reference::log()`] and [`Repository::reflog()`](crate::Repository::reflog()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindExisting(#[from] crate::reference::find::existing::Error),
+    #[error("Could not open the reference log")]
+    Io(#[from] std::io::Error),
+    #[error("Could not read an entry of the reference log")]
+    Entry(#[from] gix_ref::file::log::iter::reverse::Error),
+}
+
+/// An entry of a reference [log](Log) along with its position in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The position of the entry with `0` being the most recent one, as used in `<ref>@{<index>}`.
+    pub index: usize,
+    /// The previous and new object id, the signature and the message of the entry.
+    pub line: gix_ref::log::Line,
+}
+
+/// All entries of a reference log, as returned by [`Reference::log()`] and [`Repository::reflog()`](crate::Repository::reflog()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Log {
+    /// The entries of the log, with the most recent one first.
+    pub entries: Vec<Entry>,
+}
+
+impl Log {
+    /// Return the entry at `index` with `0` being the most recent one, like `<ref>@{<index>}`.
+    pub fn entry(&self, index: usize) -> Option<&Entry> {
+        self.entries.get(index)
+    }
+
+    /// Return the id the reference pointed to at `time` along with the entry that recorded it, like `<ref>@{<date>}`,
+    /// or `None` if the log is empty.
+    ///
+    /// If `time` predates the log, its oldest entry is returned along with the id it changed, or with the id it set
+    /// if it created the reference.
+    pub fn entry_at(&self, time: gix_date::Time) -> Option<(ObjectId, &Entry)> {
+        entry_at(self.entries.iter().map(Ok::<_, std::convert::Infallible>), time)
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// Return all entries whose message matches the glob `pattern`, most recent first.
+    ///
+    /// Note that `*` also matches slashes, so `checkout: moving from * to main` matches all checkouts of `main`.
+    pub fn entries_matching<'a>(&'a self, pattern: &'a BStr) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries.iter().filter(move |entry| {
+            gix_glob::wildmatch(pattern, entry.line.message.as_ref(), gix_glob::wildmatch::Mode::empty())
+        })
+    }
+}
+
+/// Like [`Log::entry_at()`], but reads `entries`, which are ordered with the most recent one first, only until the
+/// entry is found.
+pub(crate) fn entry_at<T: std::borrow::Borrow<Entry>, E>(
+    entries: impl IntoIterator<Item = Result<T, E>>,
+    time: gix_date::Time,
+) -> Result<Option<(ObjectId, T)>, E> {
+    let mut oldest = None;
+    for entry in entries {
+        let entry = entry?;
+        if entry.borrow().line.signature.time.seconds <= time.seconds {
+            return Ok(Some((entry.borrow().line.new_oid, entry)));
+        }
+        oldest = Some(entry);
+    }
+    Ok(oldest.map(|oldest| {
+        let line = &oldest.borrow().line;
+        let id = if line.previous_oid.is_null() {
+            line.new_oid
+        } else {
+            line.previous_oid
+        };
+        (id, oldest)
+    }))
+}
+
 impl<'repo> Reference<'repo> {
     /// Return a platform for obtaining iterators over reference logs.
     pub fn log_iter(&self) -> gix_ref::file::log::iter::Platform<'_, '_> {
         self.inner.log_iter(&self.repo.refs)
     }
 
+    /// Read all entries of the reference log of this reference, or return `None` if there is no log.
+    pub fn log(&self) -> Result<Option<Log>, Error> {
+        let mut platform = self.log_iter();
+        let Some(lines) = platform.rev()? else {
+            return Ok(None);
+        };
+        let entries = lines
+            .enumerate()
+            .map(|(index, line)| line.map(|line| Entry { index, line }))
+            .collect::<Result<_, _>>()?;
+        Ok(Some(Log { entries }))
+    }
+
     /// Return true if a reflog is present for this reference.
     pub fn log_exists(&self) -> bool {
         self.inner.log_exists(&self.repo.refs)
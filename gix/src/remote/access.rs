@@ -27,6 +27,23 @@ impl<'repo> Remote<'repo> {
         self.fetch_tags
     }
 
+    /// Return `true` if local references without a counterpart on the remote are deleted when fetching.
+    pub fn prune(&self) -> bool {
+        self.prune
+    }
+
+    /// Return `true` if local tags without a counterpart on the remote are deleted when fetching, which only has an
+    /// effect if [`prune()`](Self::prune()) is `true` as well.
+    pub fn prune_tags(&self) -> bool {
+        self.prune_tags
+    }
+
+    /// Return `true` if this remote is a mirror, which fetches all references into local references of the same name and
+    /// deletes local references that don't exist on the remote anymore.
+    pub fn mirror(&self) -> bool {
+        self.mirror
+    }
+
     /// Return the url used for the given `direction` with rewrites from `url.<base>.insteadOf|pushInsteadOf`, unless the instance
     /// was created with one of the `_without_url_rewrite()` methods.
     /// For pushing, this is the `remote.<name>.pushUrl` or the `remote.<name>.url` used for fetching, and for fetching it's
@@ -33,6 +33,41 @@ impl Remote<'_> {
         self
     }
 
+    /// Configure whether local references that are the destination of a fetch ref-spec but whose source doesn't exist
+    /// on the remote anymore should be deleted when fetching, like `git fetch --prune`.
+    pub fn with_prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Configure whether local tags that don't exist on the remote anymore should be deleted when fetching with
+    /// [pruning](Self::with_prune()) enabled, like `git fetch --prune-tags`.
+    ///
+    /// This is equivalent to fetching with the additional ref-spec `refs/tags/*:refs/tags/*`.
+    pub fn with_prune_tags(mut self, prune_tags: bool) -> Self {
+        self.prune_tags = prune_tags;
+        self
+    }
+
+    /// Configure this remote to be a mirror of the remote repository if `mirror` is `true`, like `git clone --mirror` does.
+    ///
+    /// This replaces all fetch ref-specs with `+refs/*:refs/*` so that all references are fetched into local references
+    /// of the same name, and local references that don't exist on the remote anymore will be deleted when fetching.
+    /// If `mirror` is `false`, the ref-specs are left unchanged.
+    ///
+    /// Note that the mirror configuration is [saved](Self::save_to()) as `remote.<name>.mirror`.
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        if mirror {
+            self.fetch_specs = vec![
+                gix_refspec::parse("+refs/*:refs/*".into(), gix_refspec::parse::Operation::Fetch)
+                    .expect("valid static spec")
+                    .to_owned(),
+            ];
+        }
+        self.mirror = mirror;
+        self
+    }
+
     fn push_url_inner(
         mut self,
         push_url: gix_url::Url,
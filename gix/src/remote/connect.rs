@@ -95,6 +95,7 @@ impl<'repo> Remote<'repo> {
                     .transpose()?
                     .unwrap_or_default(),
                 trace: self.repo.config.trace_packet(),
+                ..Default::default()
             },
         )
         .await?;
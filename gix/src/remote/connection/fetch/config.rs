@@ -1,6 +1,9 @@
 use super::Error;
 use crate::{
-    config::{cache::util::ApplyLeniency, tree::Pack},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Fetch, Pack},
+    },
     Repository,
 };
 
@@ -24,3 +27,28 @@ pub fn pack_index_version(repo: &Repository) -> Result<gix_pack::index::Version,
         .with_leniency(repo.options.lenient_config)?
         .unwrap_or(gix_pack::index::Version::V2))
 }
+
+/// Return the protocols of `fetch.uriProtocols` that we can download packs with, as only those can be offered to the server.
+pub fn packfile_uri_protocols(repo: &Repository) -> Vec<String> {
+    const SUPPORTED: &[&str] = if cfg!(any(
+        feature = "blocking-http-transport-curl",
+        feature = "blocking-http-transport-reqwest"
+    )) {
+        &["http", "https"]
+    } else {
+        &[]
+    };
+    repo.config
+        .resolved
+        .string_filter(Fetch::URI_PROTOCOLS, &mut repo.filter_config_section())
+        .map(|protocols| {
+            protocols
+                .split(|b| *b == b',')
+                .filter_map(|protocol| std::str::from_utf8(protocol).ok())
+                .map(str::trim)
+                .filter(|protocol| SUPPORTED.contains(protocol))
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
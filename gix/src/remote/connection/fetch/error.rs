@@ -23,6 +23,8 @@ pub enum Error {
     WritePack(#[from] gix_pack::bundle::write::Error),
     #[error(transparent)]
     UpdateRefs(#[from] super::refs::update::Error),
+    #[error("Could not write FETCH_HEAD")]
+    WriteFetchHead(#[source] std::io::Error),
     #[error("Failed to remove .keep file at \"{}\"", path.display())]
     RemovePackKeepFile {
         path: std::path::PathBuf,
@@ -35,6 +37,8 @@ pub enum Error {
         feature: &'static str,
         description: &'static str,
     },
+    #[error(transparent)]
+    ShallowArguments(#[from] gix_protocol::fetch::arguments::Error),
     #[error("Could not write 'shallow' file to incorporate remote updates after fetching")]
     WriteShallowFile(#[from] crate::shallow::write::Error),
     #[error("'shallow' file could not be locked in preparation for writing changes")]
@@ -45,6 +49,17 @@ pub enum Error {
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error("Could not download the pack at '{uri}'")]
+    PackfileUriDownload {
+        uri: gix_protocol::transport::bstr::BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("The pack downloaded from '{uri}' has checksum {actual}, but the server announced {expected}")]
+    PackfileUriChecksumMismatch {
+        uri: gix_protocol::transport::bstr::BString,
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+    },
     #[error("Failed to read remaining bytes in stream")]
     ReadRemainingBytes(#[source] std::io::Error),
     #[error("None of the refspec(s) {} matched any of the {num_remote_refs} refs on the remote", refspecs.iter().map(|r| r.to_ref().instruction().to_bstring().to_string()).collect::<Vec<_>>().join(", "))]
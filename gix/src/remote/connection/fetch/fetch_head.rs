@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    remote,
+    remote::fetch::{Mapping, Source, SpecIndex},
+    Repository,
+};
+
+/// Return the entries to write into `FETCH_HEAD` after fetching `mappings` from `remote`, with entries for merging first.
+///
+/// Like `git`, an entry is marked for merging if it was fetched by a refspec that isn't a pattern, and that was passed
+/// explicitly instead of being configured for the remote. Otherwise, if `HEAD` is a branch that tracks `remote`, only the
+/// references configured in its `branch.<name>.merge` are for merging, or the one matched by the first configured refspec
+/// if it isn't a pattern and no such configuration exists.
+pub(crate) fn entries(
+    repo: &Repository,
+    remote: &crate::Remote<'_>,
+    mappings: &[Mapping],
+    refspecs: &[gix_refspec::RefSpec],
+    extra_refspecs: &[gix_refspec::RefSpec],
+) -> Vec<gix_fetchhead::Entry> {
+    let url = remote
+        .url(remote::Direction::Fetch)
+        .map(anonymized_url)
+        .unwrap_or_default();
+    let upstream_refs = upstream_refs(repo, remote);
+    let is_pattern = |spec: Option<&gix_refspec::RefSpec>| {
+        spec.and_then(|spec| spec.to_ref().source().map(|source| source.find_byte(b'*').is_some()))
+            .unwrap_or(true)
+    };
+
+    let mut out = Vec::<gix_fetchhead::Entry>::with_capacity(mappings.len());
+    let mut index_by_name = HashMap::<BString, usize>::new();
+    for mapping in mappings {
+        let Some(id) = mapping.remote.as_id() else {
+            continue;
+        };
+        let name: BString = match &mapping.remote {
+            Source::ObjectId(id) => id.to_hex().to_string().into(),
+            Source::Ref(_) => mapping.remote.as_name().expect("refs have names").to_owned(),
+        };
+        let for_merge = match mapping.spec_index {
+            SpecIndex::Implicit(_) => !is_pattern(mapping.spec_index.get(refspecs, extra_refspecs)),
+            SpecIndex::ExplicitInRemote(idx) => match &upstream_refs {
+                Some(upstream_refs) if !upstream_refs.is_empty() => upstream_refs.contains(&name),
+                Some(_) => idx == 0 && !is_pattern(refspecs.first()),
+                None => false,
+            },
+        };
+        match index_by_name.get(&name) {
+            Some(idx) => out[*idx].for_merge |= for_merge,
+            None => {
+                index_by_name.insert(name.clone(), out.len());
+                out.push(gix_fetchhead::Entry {
+                    id: id.to_owned(),
+                    for_merge,
+                    description: gix_fetchhead::description(name.as_ref(), url.as_ref()),
+                });
+            }
+        }
+    }
+    out.sort_by_key(|entry| !entry.for_merge);
+    out
+}
+
+/// Return `url` without any credentials, for use in `FETCH_HEAD` and merge messages.
+pub(crate) fn anonymized_url(url: &gix_url::Url) -> BString {
+    let mut url = url.clone();
+    url.set_user(None);
+    url.set_password(None);
+    url.to_bstring()
+}
+
+/// Return the names of the remote references configured to be merged into the branch `HEAD` points to,
+/// or `None` if `HEAD` isn't a branch that fetches from `remote`.
+fn upstream_refs(repo: &Repository, remote: &crate::Remote<'_>) -> Option<Vec<BString>> {
+    let head_name = repo.head_name().ok()??;
+    let short_name = head_name.shorten();
+    let branch_remote = repo.branch_remote_name(short_name, remote::Direction::Fetch)?;
+    if Some(branch_remote.as_bstr()) != remote.name().map(remote::Name::as_bstr) {
+        return None;
+    }
+    Some(
+        repo.config
+            .resolved
+            .strings_by("branch", Some(short_name), crate::config::tree::Branch::MERGE.name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(std::borrow::Cow::into_owned)
+            .collect(),
+    )
+}
@@ -62,6 +62,9 @@ pub enum Status {
         negotiate: outcome::Negotiate,
         /// Information collected while writing the pack and its index.
         write_pack_bundle: gix_pack::bundle::write::Outcome,
+        /// Information collected while writing the packs that the server offloaded to separate downloads, if
+        /// `fetch.uriProtocols` is configured and the server supports it.
+        write_packfile_uri_bundles: Vec<gix_pack::bundle::write::Outcome>,
         /// Information collected while updating references.
         update_refs: refs::update::Outcome,
     },
@@ -182,11 +185,21 @@ where
     pub async fn prepare_fetch(
         mut self,
         progress: impl Progress,
-        options: ref_map::Options,
+        mut options: ref_map::Options,
     ) -> Result<Prepare<'remote, 'repo, T>, prepare::Error> {
         if self.remote.refspecs(remote::Direction::Fetch).is_empty() {
             return Err(prepare::Error::MissingRefSpecs);
         }
+        if self.remote.prune && self.remote.prune_tags {
+            // Assure all tags are known so the ones that are gone on the remote can be pruned.
+            let tag_spec = remote::fetch::Tags::All
+                .to_refspec()
+                .map(|spec| spec.to_owned())
+                .expect("tag spec exists");
+            if !options.extra_refspecs.contains(&tag_spec) {
+                options.extra_refspecs.push(tag_spec);
+            }
+        }
         let ref_map = self.ref_map_inner(progress, options).await?;
         Ok(Prepare {
             con: Some(self),
@@ -194,6 +207,7 @@ where
             dry_run: DryRun::No,
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
+            write_fetch_head: true,
             shallow: Default::default(),
         })
     }
@@ -210,6 +224,7 @@ where
 }
 
 mod config;
+pub(crate) mod fetch_head;
 mod receive_pack;
 ///
 #[path = "update_refs/mod.rs"]
@@ -225,6 +240,7 @@ where
     dry_run: DryRun,
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
+    write_fetch_head: bool,
     shallow: remote::fetch::Shallow,
 }
 
@@ -254,6 +270,25 @@ where
         self
     }
 
+    /// Write ref updates exclusively to packed-refs like [`with_write_packed_refs_only()`](Self::with_write_packed_refs_only()),
+    /// but only if at least `threshold` references are updated at once, and to loose refs otherwise.
+    ///
+    /// This keeps fetches that update many references, like mirror fetches, from creating thousands of loose files,
+    /// while small updates remain cheap as they don't have to rewrite the `packed-refs` file.
+    pub fn with_write_packed_refs_threshold(mut self, threshold: usize) -> Self {
+        self.write_packed_refs = WritePackedRefs::IfAtLeast(threshold);
+        self
+    }
+
+    /// If enabled, the default, write all fetched references into `FETCH_HEAD` like `git fetch` does, which marks the ones
+    /// to merge by `git pull`.
+    ///
+    /// It's never written in dry-run mode.
+    pub fn with_write_fetch_head(mut self, enabled: bool) -> Self {
+        self.write_fetch_head = enabled;
+        self
+    }
+
     /// Set the reflog message to use when updating refs after fetching a pack.
     pub fn with_reflog_message(mut self, reflog_message: RefLogMessage) -> Self {
         self.reflog_message = reflog_message.into();
@@ -16,7 +16,10 @@ use crate::{
     },
     remote,
     remote::{
-        connection::fetch::config,
+        connection::{
+            fetch::config,
+            ref_map::{is_permission_denied, reject_credentials},
+        },
         fetch,
         fetch::{
             negotiate, negotiate::Algorithm, outcome, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage,
@@ -125,6 +128,10 @@ where
             }
             arguments.use_include_tag();
         }
+        let packfile_uri_protocols = config::packfile_uri_protocols(repo);
+        if !packfile_uri_protocols.is_empty() && arguments.can_use_packfile_uris() {
+            arguments.use_packfile_uris(packfile_uri_protocols.iter().map(String::as_str));
+        }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
 
         if self.ref_map.object_hash != repo.object_hash() {
@@ -165,12 +172,12 @@ where
             negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
         )?;
         let mut previous_response = None::<gix_protocol::fetch::Response>;
-        let (mut write_pack_bundle, negotiate) = match &action {
+        let (mut write_pack_bundle, mut write_packfile_uri_bundles, negotiate) = match &action {
             negotiate::Action::NoChange | negotiate::Action::SkipToRefUpdate => {
                 gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace)
                     .await
                     .ok();
-                (None, None)
+                (None, Vec::new(), None)
             }
             negotiate::Action::MustNegotiate {
                 remote_ref_target_known,
@@ -190,6 +197,12 @@ where
                 let mut seen_ack = false;
                 let mut in_vain = 0;
                 let mut common = is_stateless.then(Vec::new);
+                let mut rejected_credentials = self
+                    .ref_map
+                    .handshake
+                    .credentials
+                    .clone()
+                    .map(|credentials| (credentials, con.credentials_url()));
                 let mut reader = 'negotiation: loop {
                     let _round = gix_trace::detail!("negotiate round", round = rounds.len() + 1);
                     progress.step();
@@ -226,17 +239,45 @@ where
                             return Err(err.into());
                         }
                     };
-                    let mut reader = arguments.send(&mut con.transport, is_done).await?;
+                    let mut reader = match arguments.send(&mut con.transport, is_done).await {
+                        Ok(reader) => reader,
+                        Err(err) => {
+                            if let gix_protocol::transport::client::Error::Io(io_err) = &err {
+                                if let Some((credentials, url)) =
+                                    rejected_credentials.take().filter(|_| is_permission_denied(io_err))
+                                {
+                                    reject_credentials(con.authenticate.as_mut(), repo, url, credentials);
+                                }
+                            }
+                            return Err(err.into());
+                        }
+                    };
                     if sideband_all {
                         setup_remote_progress(progress, &mut reader, should_interrupt);
                     }
-                    let response = gix_protocol::fetch::Response::from_line_reader(
+                    let response = match gix_protocol::fetch::Response::from_line_reader(
                         protocol_version,
                         &mut reader,
                         is_done,
                         !is_done,
                     )
-                    .await?;
+                    .await
+                    {
+                        Ok(response) => response,
+                        Err(err) => {
+                            let is_rejected = match &err {
+                                gix_protocol::fetch::response::Error::Io(err) => is_permission_denied(err),
+                                gix_protocol::fetch::response::Error::Transport(
+                                    gix_protocol::transport::client::Error::Io(err),
+                                ) => is_permission_denied(err),
+                                _ => false,
+                            };
+                            if let Some((credentials, url)) = rejected_credentials.take().filter(|_| is_rejected) {
+                                reject_credentials(con.authenticate.as_mut(), repo, url, credentials);
+                            }
+                            return Err(err.into());
+                        }
+                    };
                     let has_pack = response.has_pack();
                     previous_response = Some(response);
                     if has_pack {
@@ -272,6 +313,8 @@ where
                     index_version: config::pack_index_version(repo)?,
                     iteration_mode: gix_pack::data::input::Mode::Verify,
                     object_hash: con.remote.repo.object_hash(),
+                    fsync_pack: repo.config.fsync(|components| components.packs),
+                    fsync_index: repo.config.fsync(|components| components.pack_metadata),
                 };
 
                 let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
@@ -288,7 +331,7 @@ where
                             let repo = repo.clone();
                             repo.objects
                         })),
-                        options,
+                        options.clone(),
                     )?;
                     // Assure the final flush packet is consumed.
                     #[cfg(feature = "async-network-client")]
@@ -319,16 +362,32 @@ where
                         .ok();
                 }
 
+                let write_packfile_uri_bundles = if write_pack_bundle.is_some() {
+                    write_packfile_uris(
+                        repo,
+                        previous_response.packfile_uris(),
+                        options,
+                        progress,
+                        should_interrupt,
+                    )?
+                } else {
+                    Vec::new()
+                };
+
                 if let Some(shallow_lock) = shallow_lock {
                     if !previous_response.shallow_updates().is_empty() {
                         crate::shallow::write(shallow_lock, shallow_commits, previous_response.shallow_updates())?;
                     }
                 }
-                (write_pack_bundle, Some(outcome::Negotiate { graph, rounds }))
+                (
+                    write_pack_bundle,
+                    write_packfile_uri_bundles,
+                    Some(outcome::Negotiate { graph, rounds }),
+                )
             }
         };
 
-        let update_refs = refs::update(
+        let mut update_refs = refs::update(
             repo,
             self.reflog_message
                 .take()
@@ -340,11 +399,30 @@ where
             self.dry_run,
             self.write_packed_refs,
         )?;
+        if con.remote.prune || con.remote.mirror {
+            let mut prunable_specs = con.remote.refspecs(remote::Direction::Fetch).to_vec();
+            if con.remote.prune_tags {
+                prunable_specs.extend(fetch::Tags::All.to_refspec().map(|spec| spec.to_owned()));
+            }
+            update_refs.pruned = refs::prune(repo, &self.ref_map.mappings, &prunable_specs, self.dry_run)?;
+        }
+        if self.write_fetch_head && matches!(self.dry_run, fetch::DryRun::No) {
+            let entries = super::fetch_head::entries(
+                repo,
+                con.remote,
+                &self.ref_map.mappings,
+                con.remote.refspecs(remote::Direction::Fetch),
+                &self.ref_map.extra_refspecs,
+            );
+            repo.write_fetch_head(&entries).map_err(Error::WriteFetchHead)?;
+        }
 
         if let Some(bundle) = write_pack_bundle.as_mut() {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
-                if let Some(path) = bundle.keep_path.take() {
-                    std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+                for bundle in std::iter::once(bundle).chain(write_packfile_uri_bundles.iter_mut()) {
+                    if let Some(path) = bundle.keep_path.take() {
+                        std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+                    }
                 }
             }
         }
@@ -354,6 +432,7 @@ where
             status: match write_pack_bundle {
                 Some(write_pack_bundle) => Status::Change {
                     write_pack_bundle,
+                    write_packfile_uri_bundles,
                     update_refs,
                     negotiate: negotiate.expect("if we have a pack, we always negotiated it"),
                 },
@@ -368,6 +447,86 @@ where
     }
 }
 
+/// Download the packs at `uris` that the server excluded from the pack it sent, and write them into the object database
+/// with `options`, assuring their checksum matches what the server announced.
+#[cfg(any(
+    feature = "blocking-http-transport-curl",
+    feature = "blocking-http-transport-reqwest"
+))]
+fn write_packfile_uris(
+    repo: &Repository,
+    uris: &[gix_protocol::fetch::response::PackfileUri],
+    options: gix_pack::bundle::write::Options,
+    progress: &mut dyn crate::DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<gix_pack::bundle::write::Outcome>, Error> {
+    use gix_protocol::transport::client::http::{self, Http};
+
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    let mut out = Vec::with_capacity(uris.len());
+    for packfile in uris {
+        let download_err = |source: Box<dyn std::error::Error + Send + Sync + 'static>| Error::PackfileUriDownload {
+            uri: packfile.uri.clone(),
+            source,
+        };
+        let uri = std::str::from_utf8(&packfile.uri).map_err(|err| download_err(err.into()))?;
+        let mut client = http::Impl::default();
+        if let Some(options) = repo
+            .transport_options(uri, None)
+            .map_err(|err| download_err(err.into()))?
+        {
+            client.configure(options.as_ref()).map_err(download_err)?;
+        }
+        let mut body = client
+            .get(uri, uri, None::<&str>)
+            .map_err(|err| download_err(err.into()))?
+            .body;
+        let bundle = gix_pack::Bundle::write_to_directory(
+            &mut body,
+            Some(&pack_dir),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            options.clone(),
+        )?;
+        if bundle.index.data_hash != packfile.hash {
+            for path in [&bundle.index_path, &bundle.data_path, &bundle.keep_path]
+                .into_iter()
+                .flatten()
+            {
+                std::fs::remove_file(path).ok();
+            }
+            return Err(Error::PackfileUriChecksumMismatch {
+                uri: packfile.uri.clone(),
+                expected: packfile.hash,
+                actual: bundle.index.data_hash,
+            });
+        }
+        out.push(bundle);
+    }
+    Ok(out)
+}
+
+#[cfg(not(any(
+    feature = "blocking-http-transport-curl",
+    feature = "blocking-http-transport-reqwest"
+)))]
+fn write_packfile_uris(
+    _repo: &Repository,
+    uris: &[gix_protocol::fetch::response::PackfileUri],
+    _options: gix_pack::bundle::write::Options,
+    _progress: &mut dyn crate::DynNestedProgress,
+    _should_interrupt: &AtomicBool,
+) -> Result<Vec<gix_pack::bundle::write::Outcome>, Error> {
+    match uris.first() {
+        None => Ok(Vec::new()),
+        Some(packfile) => Err(Error::PackfileUriDownload {
+            uri: packfile.uri.clone(),
+            source: "HTTP support is not compiled in".into(),
+        }),
+    }
+}
+
 fn acquire_shallow_lock(repo: &Repository) -> Result<gix_lock::File, Error> {
     gix_lock::File::acquire_to_update_resource(repo.shallow_file(), gix_lock::acquire::Fail::Immediately, None)
         .map_err(Into::into)
@@ -391,31 +550,32 @@ fn add_shallow_args(
     }
     if let Some(shallow_commits) = &shallow_commits {
         for commit in shallow_commits.iter() {
-            args.shallow(commit);
+            args.try_shallow(commit)?;
         }
     }
     match shallow {
         Shallow::NoChange => {}
-        Shallow::DepthAtRemote(commits) => args.deepen(commits.get() as usize),
+        Shallow::DepthAtRemote(commits) => args.try_deepen(commits.get() as usize)?,
         Shallow::Deepen(commits) => {
-            args.deepen(*commits as usize);
-            args.deepen_relative();
+            args.try_deepen(*commits as usize)?;
+            args.try_deepen_relative()?;
         }
         Shallow::Since { cutoff } => {
-            args.deepen_since(cutoff.seconds);
+            args.try_deepen_since(cutoff.seconds)?;
         }
         Shallow::Exclude {
             remote_refs,
             since_cutoff,
         } => {
             if let Some(cutoff) = since_cutoff {
-                args.deepen_since(cutoff.seconds);
+                args.try_deepen_since(cutoff.seconds)?;
             }
             for ref_ in remote_refs {
-                args.deepen_not(ref_.as_ref().as_bstr());
+                args.try_deepen_not(ref_.as_ref().as_bstr())?;
             }
         }
     }
+    args.validate()?;
     Ok((shallow_commits, shallow_lock))
 }
 
@@ -1,7 +1,10 @@
 #![allow(clippy::result_large_err)]
 use std::{collections::BTreeMap, path::PathBuf};
 
-use gix_object::Exists;
+use gix_object::{
+    bstr::{BStr, ByteSlice},
+    Exists,
+};
 use gix_ref::{
     transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
     Target, TargetRef,
@@ -321,12 +324,17 @@ pub(crate) fn update(
                 .config
                 .lock_timeout()
                 .map_err(crate::reference::edit::Error::from)?;
-            repo.refs
-                .transaction()
+            repo.reference_transaction()?
                 .packed_refs(
                     match write_packed_refs {
                         fetch::WritePackedRefs::Only => {
                             gix_ref::file::transaction::PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(Box::new(&repo.objects))},
+                        fetch::WritePackedRefs::IfAtLeast(threshold) => {
+                            gix_ref::file::transaction::PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReferenceIfAtLeast {
+                                threshold,
+                                objects: Box::new(&repo.objects),
+                            }
+                        }
                         fetch::WritePackedRefs::Never => gix_ref::file::transaction::PackedRefs::DeletionsOnly
                     }
                 )
@@ -338,7 +346,86 @@ pub(crate) fn update(
         fetch::DryRun::Yes => edits,
     };
 
-    Ok(update::Outcome { edits, updates })
+    Ok(update::Outcome {
+        edits,
+        updates,
+        pruned: Vec::new(),
+    })
+}
+
+/// Delete all references in `repo` that match the destination of one of `refspecs`, but aren't the destination of any of
+/// the `mappings`, which means that the reference they were tracking doesn't exist on the remote anymore.
+/// Symbolic references are never deleted.
+///
+/// If `dry_run` is true, the deletions are returned but not applied.
+pub(crate) fn prune(
+    repo: &Repository,
+    mappings: &[fetch::Mapping],
+    refspecs: &[gix_refspec::RefSpec],
+    dry_run: fetch::DryRun,
+) -> Result<Vec<RefEdit>, update::Error> {
+    let _span = gix_trace::detail!("prune_refs()", refspecs = refspecs.len());
+    let destinations: Vec<_> = refspecs
+        .iter()
+        .filter_map(|spec| spec.to_ref().local().map(ToOwned::to_owned))
+        .collect();
+    if destinations.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mapped: std::collections::BTreeSet<&BStr> = mappings
+        .iter()
+        .filter_map(|m| m.local.as_ref().map(AsRef::as_ref))
+        .collect();
+
+    let mut edits = Vec::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(update::Error::IterateReferences)?;
+        let name = reference.name().as_bstr();
+        if matches!(reference.target(), TargetRef::Symbolic(_))
+            || mapped.contains(name)
+            || !destinations
+                .iter()
+                .any(|destination| matches_destination(destination.as_ref(), name))
+        {
+            continue;
+        }
+        edits.push(RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(reference.target().into_owned()),
+                log: RefLog::AndReference,
+            },
+            name: reference.name().to_owned(),
+            deref: false,
+        });
+    }
+
+    let edits = match dry_run {
+        fetch::DryRun::No if !edits.is_empty() => {
+            let _span = gix_trace::detail!("apply", edits = edits.len());
+            let (file_lock_fail, packed_refs_lock_fail) = repo
+                .config
+                .lock_timeout()
+                .map_err(crate::reference::edit::Error::from)?;
+            repo.reference_transaction()?
+                .prepare(edits, file_lock_fail, packed_refs_lock_fail)
+                .map_err(crate::reference::edit::Error::from)?
+                .commit(None)
+                .map_err(crate::reference::edit::Error::from)?
+        }
+        _ => edits,
+    };
+    Ok(edits)
+}
+
+/// Return `true` if `destination`, the local side of a ref-spec which may contain a single glob, matches `name`.
+fn matches_destination(destination: &BStr, name: &BStr) -> bool {
+    match destination.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&destination[..pos], &destination[pos + 1..]);
+            name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => destination == name,
+    }
 }
 
 /// Figure out if target of `edit` points to a reference that doesn't exist in `repo` and won't exist as it's not in any of `edits`.
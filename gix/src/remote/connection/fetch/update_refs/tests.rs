@@ -904,6 +904,56 @@ mod update {
         }
     }
 
+    #[test]
+    fn updates_go_into_packed_refs_only_at_or_above_the_threshold() -> Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let (mappings, specs) = mapping_from_spec("refs/heads/*:refs/remotes/loose/*", &repo);
+        let out = fetch::refs::update(
+            &repo,
+            prefixed("action"),
+            &mappings,
+            &specs,
+            &[],
+            fetch::Tags::None,
+            fetch::DryRun::No,
+            fetch::WritePackedRefs::IfAtLeast(mappings.len() + 1),
+        )?;
+        assert_eq!(out.edits.len(), mappings.len());
+        assert!(
+            repo.git_dir().join("refs/remotes/loose").is_dir(),
+            "below the threshold, loose refs are written"
+        );
+
+        let (mappings, specs) = mapping_from_spec("refs/heads/*:refs/remotes/packed/*", &repo);
+        let out = fetch::refs::update(
+            &repo,
+            prefixed("action"),
+            &mappings,
+            &specs,
+            &[],
+            fetch::Tags::None,
+            fetch::DryRun::No,
+            fetch::WritePackedRefs::IfAtLeast(2),
+        )?;
+        assert_eq!(out.edits.len(), mappings.len());
+        let packed = repo.refs.cached_packed_buffer()?.expect("packed refs were written");
+        for edit in &out.edits {
+            let is_symbolic = matches!(edit.change.new_value(), Some(TargetRef::Symbolic(_)));
+            assert_eq!(
+                packed.try_find(edit.name.as_ref())?.is_some(),
+                !is_symbolic,
+                "at the threshold, {} is packed unless it's symbolic",
+                edit.name
+            );
+            assert_eq!(
+                repo.git_dir().join(gix::path::from_bstr(edit.name.as_bstr())).is_file(),
+                is_symbolic,
+                "only symbolic refs are written as loose refs"
+            );
+        }
+        Ok(())
+    }
+
     fn mapping_from_spec(
         spec: &str,
         remote_repo: &gix::Repository,
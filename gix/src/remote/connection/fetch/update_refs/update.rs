@@ -23,6 +23,12 @@ mod error {
         PeelToId(#[from] crate::reference::peel::Error),
         #[error("Failed to follow a symbolic reference to assure worktree isn't affected")]
         FollowSymref(#[from] gix_ref::file::find::existing::Error),
+        #[error(transparent)]
+        OpenReferences(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        InitReferenceIteration(#[from] crate::reference::iter::init::Error),
+        #[error("Failed to iterate references to find the ones to prune")]
+        IterateReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 
@@ -37,6 +43,9 @@ pub struct Outcome {
     /// Use [`iter_mapping_updates()`][Self::iter_mapping_updates()] to recombine the update information with ref-edits and their
     /// mapping.
     pub updates: Vec<super::Update>,
+    /// All edits that deleted local references as their counterpart doesn't exist on the remote anymore, if
+    /// [pruning](crate::Remote::with_prune()) was enabled.
+    pub pruned: Vec<gix_ref::transaction::RefEdit>,
 }
 
 /// Describe the way a ref was updated, with particular focus on how the (peeled) target commit was affected.
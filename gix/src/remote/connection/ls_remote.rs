@@ -0,0 +1,96 @@
+use gix_features::progress::Progress;
+use gix_protocol::{handshake, transport::client::Transport};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    remote::{ref_map, Connection},
+};
+
+/// The error returned by [`Repository::ls_remote()`](crate::Repository::ls_remote()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindRemote(#[from] crate::remote::find::for_fetch::Error),
+    #[error(transparent)]
+    Connect(#[from] crate::remote::connect::Error),
+    #[error(transparent)]
+    ListRefs(#[from] ref_map::Error),
+}
+
+/// The result of [`Connection::ls_remote()`].
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The references advertised by the remote that matched at least one of the patterns, in the order the server
+    /// sent them.
+    ///
+    /// Symbolic references carry their target, and annotated tags carry the object they peel to.
+    pub refs: Vec<handshake::Ref>,
+    /// Information about the handshake with the remote.
+    pub handshake: handshake::Outcome,
+}
+
+impl<'remote, 'repo, T> Connection<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// List all references advertised by the remote which match at least one of `patterns`, or all of them if there
+    /// are no patterns, similar to `git ls-remote`. `progress` is used to provide feedback while connecting.
+    ///
+    /// Like in `git`, a pattern matches if it matches the end of a full reference name after a slash, so `main`
+    /// matches `refs/heads/main` as well as `refs/remotes/origin/main`, and it may contain glob wildcards like
+    /// `refs/tags/v1.*`.
+    /// If all patterns start with `refs/`, the remote is asked to only send references with the respective prefix,
+    /// which saves traffic with the V2 protocol. Note that this doesn't send references which only match in their
+    /// tail, like `refs/remotes/origin/refs/heads/main` for the pattern `refs/heads/main`.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn ls_remote(mut self, progress: impl Progress, patterns: &[BString]) -> Result<Outcome, ref_map::Error> {
+        let _span = gix_trace::coarse!("remote::Connection::ls_remote()");
+        let res = self.fetch_refs(ref_prefixes(patterns), Vec::new(), progress).await;
+        gix_protocol::indicate_end_of_interaction(&mut self.transport, self.trace)
+            .await
+            .ok();
+        let mut remote = res?;
+        if !patterns.is_empty() {
+            remote.refs.retain(|r| {
+                let (name, _, _) = r.unpack();
+                patterns.iter().any(|pattern| matches(pattern.as_bstr(), name))
+            });
+        }
+        Ok(Outcome {
+            refs: remote.refs,
+            handshake: remote.outcome,
+        })
+    }
+}
+
+/// Return `true` if `pattern` matches the tail of `full_ref_name` at a component boundary, like `git ls-remote` does.
+fn matches(pattern: &BStr, full_ref_name: &BStr) -> bool {
+    let mut pattern_buf = BString::from("*/");
+    pattern_buf.push_str(pattern);
+    let mut name_buf = BString::from("/");
+    name_buf.push_str(full_ref_name);
+    gix_glob::wildmatch(
+        pattern_buf.as_bstr(),
+        name_buf.as_bstr(),
+        gix_glob::wildmatch::Mode::empty(),
+    )
+}
+
+/// Return the ref-prefixes to send for `patterns`, which can only be done if all of them are anchored at `refs/`.
+fn ref_prefixes(patterns: &[BString]) -> Vec<BString> {
+    if !patterns.iter().all(|pattern| pattern.starts_with(b"refs/")) {
+        return Vec::new();
+    }
+    let mut prefixes: Vec<BString> = patterns
+        .iter()
+        .map(|pattern| {
+            let literal_len = pattern.find_byteset(b"*?[\\").unwrap_or(pattern.len());
+            pattern[..literal_len].into()
+        })
+        .collect();
+    prefixes.sort();
+    prefixes.dedup();
+    prefixes
+}
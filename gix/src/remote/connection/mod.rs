@@ -26,6 +26,10 @@ mod access;
 #[allow(clippy::empty_docs)]
 pub mod ref_map;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod ls_remote;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod fetch;
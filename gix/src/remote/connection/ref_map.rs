@@ -6,7 +6,12 @@ use gix_protocol::transport::client::Transport;
 use crate::{
     bstr,
     bstr::{BString, ByteVec},
-    remote::{connection::HandshakeWithRefs, fetch, fetch::SpecIndex, Connection, Direction},
+    remote::{
+        connection::{AuthenticateFn, HandshakeWithRefs},
+        fetch,
+        fetch::SpecIndex,
+        Connection, Direction,
+    },
 };
 
 /// The error returned by [`Connection::ref_map()`].
@@ -125,14 +130,20 @@ where
             s.extend(extra_refspecs.clone());
             s
         };
-        let remote = self
-            .fetch_refs(
-                prefix_from_spec_as_filter_on_remote,
-                handshake_parameters,
-                &specs,
-                progress,
-            )
-            .await?;
+        let ref_prefixes = if prefix_from_spec_as_filter_on_remote {
+            let mut seen = HashSet::new();
+            let mut prefixes = Vec::new();
+            for spec in &specs {
+                let spec = spec.to_ref();
+                if seen.insert(spec.instruction()) {
+                    spec.expand_prefixes(&mut prefixes);
+                }
+            }
+            prefixes
+        } else {
+            Vec::new()
+        };
+        let remote = self.fetch_refs(ref_prefixes, handshake_parameters, progress).await?;
         let num_explicit_specs = self.remote.fetch_specs.len();
         let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref));
         let (res, fixes) = group
@@ -181,11 +192,10 @@ where
 
     #[allow(clippy::result_large_err)]
     #[gix_protocol::maybe_async::maybe_async]
-    async fn fetch_refs(
+    pub(crate) async fn fetch_refs(
         &mut self,
-        filter_by_prefix: bool,
+        ref_prefixes: Vec<BString>,
         extra_parameters: Vec<(String, Option<String>)>,
-        refspecs: &[gix_refspec::RefSpec],
         mut progress: impl Progress,
     ) -> Result<HandshakeWithRefs, Error> {
         let _span = gix_trace::coarse!("remote::Connection::fetch_refs()");
@@ -194,11 +204,7 @@ where
         let authenticate = match self.authenticate.as_mut() {
             Some(f) => f,
             None => {
-                let url = self.remote.url(Direction::Fetch).map_or_else(
-                    || gix_url::parse(url.as_ref()).expect("valid URL to be provided by transport"),
-                    ToOwned::to_owned,
-                );
-                credentials_storage = self.configured_credentials(url)?;
+                credentials_storage = self.configured_credentials(self.credentials_url())?;
                 &mut credentials_storage
             }
         };
@@ -222,35 +228,87 @@ where
             Some(refs) => refs,
             None => {
                 let agent_feature = self.remote.repo.config.user_agent_tuple();
-                gix_protocol::ls_refs(
+                let res = gix_protocol::ls_refs(
                     &mut self.transport,
                     &outcome.capabilities,
                     move |_capabilities, arguments, features| {
                         features.push(agent_feature);
-                        if filter_by_prefix {
-                            let mut seen = HashSet::new();
-                            for spec in refspecs {
-                                let spec = spec.to_ref();
-                                if seen.insert(spec.instruction()) {
-                                    let mut prefixes = Vec::with_capacity(1);
-                                    spec.expand_prefixes(&mut prefixes);
-                                    for mut prefix in prefixes {
-                                        prefix.insert_str(0, "ref-prefix ");
-                                        arguments.push(prefix);
-                                    }
-                                }
-                            }
+                        for mut prefix in ref_prefixes {
+                            prefix.insert_str(0, "ref-prefix ");
+                            arguments.push(prefix);
                         }
                         Ok(gix_protocol::ls_refs::Action::Continue)
                     },
                     &mut progress,
                     self.trace,
                 )
-                .await?
+                .await;
+                match res {
+                    Ok(refs) => refs,
+                    Err(err) => {
+                        let is_permission_denied = match &err {
+                            gix_protocol::ls_refs::Error::Io(err) => is_permission_denied(err),
+                            gix_protocol::ls_refs::Error::Transport(gix_protocol::transport::client::Error::Io(
+                                err,
+                            )) => is_permission_denied(err),
+                            _ => false,
+                        };
+                        if is_permission_denied {
+                            self.reject_credentials(outcome.credentials.take());
+                        }
+                        return Err(err.into());
+                    }
+                }
             }
         };
         Ok(HandshakeWithRefs { outcome, refs })
     }
+
+    /// Return the URL to obtain credentials for if no custom [authentication function](Self::with_credentials()) is set.
+    pub(crate) fn credentials_url(&self) -> gix_url::Url {
+        self.remote.url(Direction::Fetch).map_or_else(
+            || gix_url::parse(self.transport.to_url().as_ref()).expect("valid URL to be provided by transport"),
+            ToOwned::to_owned,
+        )
+    }
+
+    fn reject_credentials(&mut self, credentials: Option<gix_credentials::helper::NextAction>) {
+        if let Some(credentials) = credentials {
+            let url = self.credentials_url();
+            reject_credentials(self.authenticate.as_mut(), self.remote.repo, url, credentials);
+        }
+    }
+}
+
+/// Erase the `credentials` that were accepted during the handshake from all credential helpers as the remote rejected
+/// them later, so they aren't provided again the next time credentials are obtained.
+///
+/// `authenticate` is used if set, or the credential helpers configured in `repo` for `url` otherwise.
+/// Errors are ignored as the operation that was rejected fails anyway.
+pub(crate) fn reject_credentials(
+    authenticate: Option<&mut AuthenticateFn<'_>>,
+    repo: &crate::Repository,
+    url: gix_url::Url,
+    credentials: gix_credentials::helper::NextAction,
+) {
+    let action = credentials.erase();
+    match authenticate {
+        Some(authenticate) => {
+            authenticate(action).ok();
+        }
+        None => {
+            if let Ok((mut cascade, _action_with_normalized_url, prompt_options)) =
+                repo.config_snapshot().credential_helpers(url)
+            {
+                cascade.invoke(action, prompt_options).ok();
+            }
+        }
+    }
+}
+
+/// Return `true` if `err` indicates that the remote rejected our credentials.
+pub(crate) fn is_permission_denied(err: &std::io::Error) -> bool {
+    err.kind() == std::io::ErrorKind::PermissionDenied
 }
 
 /// Assume sha1 if server says nothing, otherwise configure anything beyond sha1 in the local repo configuration
@@ -12,6 +12,8 @@ pub mod negotiate {
     };
 }
 
+#[cfg(feature = "blocking-network-client")]
+pub(crate) use super::connection::fetch::fetch_head;
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use super::connection::fetch::{
     outcome, prepare, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Status,
@@ -35,6 +37,8 @@ pub(crate) enum WritePackedRefs {
     Never,
     /// Put ref updates straight into the `packed-refs` file, without creating loose refs first or dealing with them in any way.
     Only,
+    /// Behave like [`Only`](Self::Only) if there are at least the given amount of ref updates, or like [`Never`](Self::Never) otherwise.
+    IfAtLeast(usize),
 }
 
 /// Describe how to handle tags when fetching
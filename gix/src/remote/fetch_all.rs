@@ -0,0 +1,298 @@
+//! Fetch from multiple remotes at once, similar to `git fetch --all` or `git fetch --multiple`.
+//!
+//! Use [`Repository::fetch_all()`](crate::Repository::fetch_all()) to perform the operation.
+#![allow(clippy::result_large_err)]
+use std::sync::atomic::AtomicBool;
+
+use gix_features::progress::DynNestedProgress;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    config::tree::{Fetch, Key, Remote, Section},
+    remote,
+};
+
+/// Options for use in [`Repository::fetch_all()`](crate::Repository::fetch_all()).
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    /// The name of a group of remotes as configured with `remotes.<group>`, or the name of a single remote if there is no
+    /// such group.
+    ///
+    /// If `None`, all remotes are fetched except for the ones with `remote.<name>.skipFetchAll` set.
+    pub group: Option<BString>,
+    /// The maximum amount of remotes to fetch from at the same time, with `0` meaning as many as there are logical cores.
+    ///
+    /// If `None`, `fetch.parallel` is used, and if that isn't set, remotes are fetched one after another.
+    pub jobs: Option<usize>,
+    /// If `true`, don't change the repository, see [`Prepare::with_dry_run()`](crate::remote::fetch::Prepare::with_dry_run()).
+    pub dry_run: bool,
+}
+
+/// The error returned by [`Repository::fetch_all()`](crate::Repository::fetch_all()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Jobs(#[from] crate::config::unsigned_integer::Error),
+    #[error("No remote to fetch from could be found")]
+    NoRemote,
+    #[error("Could not write FETCH_HEAD")]
+    WriteFetchHead(#[source] std::io::Error),
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod single {
+    /// The error produced when fetching from one of the remotes.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindRemote(#[from] crate::remote::find::existing::Error),
+        #[error(transparent)]
+        Connect(#[from] crate::remote::connect::Error),
+        #[error(transparent)]
+        PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::remote::fetch::Error),
+    }
+}
+
+/// The result of fetching from a single remote as part of [`Repository::fetch_all()`](crate::Repository::fetch_all()).
+#[derive(Debug)]
+pub struct Fetched {
+    /// The name of the remote.
+    pub name: BString,
+    /// The outcome of fetching from the remote.
+    pub result: Result<remote::fetch::Outcome, single::Error>,
+}
+
+/// The combined outcome of [`Repository::fetch_all()`](crate::Repository::fetch_all()).
+#[derive(Debug)]
+pub struct Outcome {
+    /// One entry per remote that was fetched from, in the order in which the remotes were configured or listed in the group.
+    pub remotes: Vec<Fetched>,
+}
+
+impl Outcome {
+    /// Return `true` if fetching from all remotes succeeded.
+    pub fn is_success(&self) -> bool {
+        self.remotes.iter().all(|fetched| fetched.result.is_ok())
+    }
+}
+
+/// Fetching
+impl crate::Repository {
+    /// Fetch from all remotes, or from the remotes of a group, as configured in `options`, with up to the configured amount
+    /// of remotes in parallel. A failure to fetch from one remote doesn't affect the others, and is reported in the returned
+    /// outcome instead.
+    /// Without the `parallel` feature, remotes are always fetched one after another.
+    ///
+    /// `progress` receives a child for each remote, and `should_interrupt` can be set to stop all fetches early.
+    ///
+    /// ### Configuration
+    ///
+    /// - `remotes.<group>` lists the names of the remotes in a group, separated by whitespace, with each value adding to the group.
+    /// - `remote.<name>.skipFetchAll` excludes a remote if no group is given.
+    /// - `fetch.parallel` is the amount of remotes to fetch from at the same time if `options.jobs` isn't set.
+    ///
+    /// ### `FETCH_HEAD`
+    ///
+    /// Unless this is a dry-run, `FETCH_HEAD` is written once all remotes were fetched, with the entries of all successful fetches
+    /// in the order of the remotes.
+    pub fn fetch_all(
+        &self,
+        options: Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let names = match &options.group {
+            Some(group) => self.remote_group(group.as_ref()),
+            None => self
+                .configured_remote_names()
+                .into_iter()
+                .filter(|name| !self.skips_fetch_all(name.as_ref()))
+                .collect(),
+        };
+        if names.is_empty() {
+            return Err(Error::NoRemote);
+        }
+        let jobs = match options.jobs {
+            Some(jobs) => jobs,
+            None => self
+                .config
+                .resolved
+                .integer_filter(Fetch::PARALLEL, &mut self.filter_config_section())
+                .map(|value| Fetch::PARALLEL.try_into_usize(value))
+                .transpose()?
+                .unwrap_or(1),
+        };
+        let jobs = match jobs {
+            0 => std::thread::available_parallelism().map_or(1, Into::into),
+            jobs => jobs,
+        }
+        .min(names.len());
+
+        let work = names
+            .into_iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let progress = progress.add_child(name.to_str_lossy().into_owned());
+                (idx, name, progress)
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+        #[cfg(not(feature = "parallel"))]
+        let mut remotes: Vec<_> = {
+            let _ = jobs;
+            work.map(|(idx, name, mut progress)| {
+                let (result, entries) = split(fetch_one(
+                    self,
+                    name.as_ref(),
+                    options.dry_run,
+                    &mut progress,
+                    should_interrupt,
+                ));
+                (idx, Fetched { name, result }, entries)
+            })
+            .collect()
+        };
+        #[cfg(feature = "parallel")]
+        let work = std::sync::Mutex::new(work);
+        #[cfg(feature = "parallel")]
+        let repo = self.clone().into_sync();
+        #[cfg(feature = "parallel")]
+        let mut remotes = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..jobs)
+                .map(|_| {
+                    let (work, repo) = (&work, &repo);
+                    std::thread::Builder::new()
+                        .name("gix::fetch_all".into())
+                        .spawn_scoped(scope, move || {
+                            let repo = repo.to_thread_local();
+                            let mut out = Vec::new();
+                            loop {
+                                let next = work.lock().expect("no panic while holding the lock").next();
+                                let Some((idx, name, mut progress)) = next else {
+                                    break;
+                                };
+                                let (result, entries) = split(fetch_one(
+                                    &repo,
+                                    name.as_ref(),
+                                    options.dry_run,
+                                    &mut progress,
+                                    should_interrupt,
+                                ));
+                                out.push((idx, Fetched { name, result }, entries));
+                            }
+                            out
+                        })
+                        .expect("valid name")
+                })
+                .collect();
+            threads
+                .into_iter()
+                .flat_map(|thread| thread.join().expect("no panic"))
+                .collect::<Vec<_>>()
+        });
+        remotes.sort_by_key(|(idx, _, _)| *idx);
+        if !options.dry_run {
+            let entries: Vec<_> = remotes
+                .iter_mut()
+                .flat_map(|(_, _, entries)| entries.drain(..))
+                .collect();
+            self.write_fetch_head(&entries).map_err(Error::WriteFetchHead)?;
+        }
+        Ok(Outcome {
+            remotes: remotes.into_iter().map(|(_, fetched, _)| fetched).collect(),
+        })
+    }
+
+    /// Return the unique names of all trusted remotes in the order in which they appear in the configuration.
+    fn configured_remote_names(&self) -> Vec<BString> {
+        let mut names = Vec::<BString>::new();
+        let filter = self.filter_config_section();
+        for name in self
+            .config
+            .resolved
+            .sections_by_name(Remote.name())
+            .into_iter()
+            .flatten()
+            .filter(|section| filter(section.meta()))
+            .filter_map(|section| section.header().subsection_name())
+        {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_owned());
+            }
+        }
+        names
+    }
+
+    /// Return the names of the remotes in `group`, or `group` itself if there is no such group.
+    fn remote_group(&self, group: &BStr) -> Vec<BString> {
+        let mut names = Vec::new();
+        for value in self
+            .config
+            .resolved
+            .strings_filter(format!("remotes.{group}").as_str(), &mut self.filter_config_section())
+            .unwrap_or_default()
+        {
+            for name in value.fields_with(|b| b.is_ascii_whitespace()) {
+                let name = BString::from(name);
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        if names.is_empty() {
+            names.push(group.to_owned());
+        }
+        names
+    }
+
+    fn skips_fetch_all(&self, name: &BStr) -> bool {
+        self.config
+            .resolved
+            .boolean_filter(
+                format!("remote.{name}.{}", Remote::SKIP_FETCH_ALL.name()).as_str(),
+                &mut self.filter_config_section(),
+            )
+            .and_then(Result::ok)
+            .unwrap_or(false)
+    }
+}
+
+type FetchedWithEntries = Result<(remote::fetch::Outcome, Vec<gix_fetchhead::Entry>), single::Error>;
+
+fn split(fetched: FetchedWithEntries) -> (Result<remote::fetch::Outcome, single::Error>, Vec<gix_fetchhead::Entry>) {
+    match fetched {
+        Ok((outcome, entries)) => (Ok(outcome), entries),
+        Err(err) => (Err(err), Vec::new()),
+    }
+}
+
+/// Fetch from the remote `name` without writing `FETCH_HEAD`, as all remotes would race to write it,
+/// and return the entries it would have written instead.
+fn fetch_one(
+    repo: &crate::Repository,
+    name: &BStr,
+    dry_run: bool,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> FetchedWithEntries {
+    let remote = repo.find_remote(name)?;
+    let outcome = remote
+        .connect(remote::Direction::Fetch)?
+        .prepare_fetch(&mut *progress, Default::default())?
+        .with_dry_run(dry_run)
+        .with_write_fetch_head(false)
+        .receive_inner(progress, should_interrupt)?;
+    let entries = remote::fetch::fetch_head::entries(
+        repo,
+        &remote,
+        &outcome.ref_map.mappings,
+        remote.refspecs(remote::Direction::Fetch),
+        &outcome.ref_map.extra_refspecs,
+    );
+    Ok((outcome, entries))
+}
@@ -17,11 +17,16 @@ mod error {
             rewritten_url: BString,
             source: gix_url::parse::Error,
         },
+        #[error(transparent)]
+        Boolean(#[from] crate::config::boolean::Error),
     }
 }
 pub use error::Error;
 
-use crate::bstr::BString;
+use crate::{
+    bstr::{BStr, BString},
+    config::tree::{Fetch, Key},
+};
 
 /// Initialization
 impl<'repo> Remote<'repo> {
@@ -43,6 +48,20 @@ impl<'repo> Remote<'repo> {
         let (url_alias, push_url_alias) = should_rewrite_urls
             .then(|| rewrite_urls(&repo.config, url.as_ref(), push_url.as_ref()))
             .unwrap_or(Ok((None, None)))?;
+        let (prune, prune_tags) = prune_config(repo, name_or_url.as_ref().map(AsRef::as_ref))?;
+        let mirror = name_or_url
+            .as_ref()
+            .and_then(|name| {
+                repo.config
+                    .resolved
+                    .boolean_filter(
+                        format!("remote.{name}.{}", config::tree::Remote::MIRROR.name()).as_str(),
+                        &mut repo.filter_config_section(),
+                    )
+                    .map(|value| config::tree::Remote::MIRROR.enrich_error(value))
+            })
+            .transpose()?
+            .unwrap_or(false);
         Ok(Remote {
             name: name_or_url.map(Into::into),
             url,
@@ -52,6 +71,9 @@ impl<'repo> Remote<'repo> {
             fetch_specs,
             push_specs,
             fetch_tags,
+            prune,
+            prune_tags,
+            mirror,
             repo,
         })
     }
@@ -80,6 +102,7 @@ impl<'repo> Remote<'repo> {
         let (url_alias, _) = should_rewrite_urls
             .then(|| rewrite_urls(&repo.config, Some(&url), None))
             .unwrap_or(Ok((None, None)))?;
+        let (prune, prune_tags) = prune_config(repo, None)?;
         Ok(Remote {
             name: None,
             url: Some(url),
@@ -89,11 +112,39 @@ impl<'repo> Remote<'repo> {
             fetch_specs: Vec::new(),
             push_specs: Vec::new(),
             fetch_tags: Default::default(),
+            prune,
+            prune_tags,
+            mirror: false,
             repo,
         })
     }
 }
 
+/// Return whether refs and tags should be pruned when fetching from the remote named `name`, with `remote.<name>.prune[Tags]`
+/// taking precedence over `fetch.prune[Tags]`.
+fn prune_config(repo: &Repository, name: Option<&BStr>) -> Result<(bool, bool), config::boolean::Error> {
+    let config = &repo.config.resolved;
+    let mut filter = repo.filter_config_section();
+    let mut boolean = |remote_key: &'static config::tree::keys::Boolean,
+                       fetch_key: &'static config::tree::keys::Boolean| {
+        name.and_then(|name| {
+            config
+                .boolean_filter(format!("remote.{name}.{}", remote_key.name()).as_str(), &mut filter)
+                .map(|value| remote_key.enrich_error(value))
+        })
+        .or_else(|| {
+            config
+                .boolean_filter(fetch_key, &mut filter)
+                .map(|value| fetch_key.enrich_error(value))
+        })
+        .unwrap_or(Ok(false))
+    };
+    Ok((
+        boolean(&config::tree::Remote::PRUNE, &Fetch::PRUNE)?,
+        boolean(&config::tree::Remote::PRUNE_TAGS, &Fetch::PRUNE_TAGS)?,
+    ))
+}
+
 pub(crate) fn rewrite_url(
     config: &config::Cache,
     url: Option<&gix_url::Url>,
@@ -56,6 +56,7 @@ impl Remote<'_> {
                 config::tree::Remote::FETCH.name,
                 config::tree::Remote::PUSH.name,
                 config::tree::Remote::TAG_OPT.name,
+                config::tree::Remote::MIRROR.name,
             ];
             for id in section_ids {
                 let mut section = config.section_mut_by_id(id).expect("just queried");
@@ -94,6 +95,9 @@ impl Remote<'_> {
                 .into(),
             );
         }
+        if self.mirror {
+            section.push(as_key(config::tree::Remote::MIRROR.name), Some("true".into()));
+        }
         for (key, spec) in self
             .fetch_specs
             .iter()
@@ -0,0 +1,510 @@
+//! Consolidate the packs of the object database, similar to [`git repack`](https://git-scm.com/docs/git-repack).
+//!
+//! Use [`Repository::repack()`](crate::Repository::repack()) to perform the operation.
+#![allow(clippy::result_large_err)]
+use std::{
+    io::{BufReader, Write},
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_features::progress::{DynNestedProgress, Progress};
+use gix_hash::ObjectId;
+use gix_pack::data::output;
+
+/// Determine which packs to combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Combine the smallest packs and all loose objects into a new pack so that the amount of objects in each pack
+    /// is at least `factor` times larger than in the next smaller pack, like `git repack --geometric=<factor>`.
+    ///
+    /// This keeps the amount of packs logarithmic to the amount of objects while rewriting only a fraction of all
+    /// objects, which makes it well suited for repositories that receive many small packs.
+    Geometric {
+        /// The minimal ratio between the amount of objects of a pack and the next smaller pack, which must be at least 2.
+        factor: u32,
+    },
+}
+
+/// The error returned by [`Repository::repack()`](crate::Repository::repack()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The geometric factor must be at least 2, got {factor}")]
+    InvalidFactor { factor: u32 },
+    #[error("Could not list the packs in '{}'", path.display())]
+    ListPacks { path: PathBuf, source: std::io::Error },
+    #[error(transparent)]
+    OpenPackIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    IterateLooseObjects(#[from] gix_odb::loose::iter::Error),
+    #[error(transparent)]
+    LoadIndex(#[from] gix_odb::store::load_index::Error),
+    #[error(transparent)]
+    CountObjects(#[from] output::count::objects::Error),
+    #[error(transparent)]
+    CreateEntries(#[from] output::entry::iter_from_counts::Error),
+    #[error(transparent)]
+    WritePackData(#[from] output::bytes::Error<output::entry::iter_from_counts::Error>),
+    #[error(transparent)]
+    WriteBundle(#[from] gix_pack::bundle::write::Error),
+    #[error(transparent)]
+    WriteMultiIndex(#[from] gix_pack::multi_index::write::Error),
+    #[error(transparent)]
+    Lock(#[from] gix_lock::acquire::Error),
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error("Could not obtain the next reference")]
+    IterReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    InitReferenceIter(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    Traverse(#[from] crate::revision::walk::iter::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Information about a [`Repository::repack()`](crate::Repository::repack()) operation.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The paths to the indices of the packs that were combined into the new pack, and which were removed.
+    pub removed_packs: Vec<PathBuf>,
+    /// The amount of loose objects that were put into the new pack, and which were removed.
+    pub removed_loose_objects: usize,
+    /// The path to the index of the newly written pack, or `None` if there was nothing to combine.
+    pub new_pack: Option<PathBuf>,
+    /// The amount of objects in the new pack.
+    pub num_objects: u32,
+    /// The path to the multi-pack index that was written over all packs after the operation.
+    pub multi_index: Option<PathBuf>,
+    /// The paths to the indices of packs with a [`.keep` file](gix_pack::keep), which were left untouched.
+    pub kept_packs: Vec<PathBuf>,
+}
+
+/// A pack in the object database along with the amount of objects it contains.
+#[derive(Debug, Clone)]
+struct Pack {
+    index_path: PathBuf,
+    num_objects: u32,
+}
+
+/// Repacking
+impl crate::Repository {
+    /// Consolidate the packs and loose objects of this repository's object database according to `strategy`, and write a
+    /// multi-pack index over all packs afterwards.
+    ///
+    /// Objects of combined packs are copied into the new pack as is, including their deltas if the delta base is also
+    /// part of it, so this operation is mostly limited by I/O.
+    /// `progress` is used to report progress, and `should_interrupt` can be set to stop the operation early, which
+    /// leaves the object database unchanged if nothing was removed yet.
+    ///
+    /// Packs with a [`.keep` file](gix_pack::keep) are never combined or removed, but are part of the multi-pack index.
+    /// This includes packs which are written by concurrent fetches until references point to their objects.
+    ///
+    /// Note that alternate object databases are never altered.
+    pub fn repack(
+        &self,
+        strategy: Strategy,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let Strategy::Geometric { factor } = strategy;
+        if factor < 2 {
+            return Err(Error::InvalidFactor { factor });
+        }
+        let object_hash = self.object_hash();
+        let objects_dir = self.objects.store_ref().path().to_owned();
+        let pack_dir = objects_dir.join("pack");
+        let (kept, mut packs): (Vec<_>, Vec<_>) = list_packs(&pack_dir, object_hash)?
+            .into_iter()
+            .partition(|pack| gix_pack::keep::is_kept(&pack.index_path));
+        packs.sort_by_key(|pack| pack.num_objects);
+        let split = geometric_split(&packs, factor);
+
+        let loose = gix_odb::loose::Store::at(&objects_dir, object_hash);
+        let loose_ids = loose.iter().collect::<Result<Vec<_>, _>>()?;
+        let mut outcome = Outcome {
+            kept_packs: kept.iter().map(|pack| pack.index_path.clone()).collect(),
+            ..Default::default()
+        };
+        if split > 1 || !loose_ids.is_empty() {
+            let rolled_up: Vec<_> = packs.drain(..split).collect();
+            let mut ids = loose_ids.clone();
+            for pack in &rolled_up {
+                let index = gix_pack::index::File::at(&pack.index_path, object_hash)?;
+                ids.extend(index.iter().map(|entry| entry.oid));
+            }
+            ids.sort();
+            ids.dedup();
+
+            let written = self.write_pack(ids, &pack_dir, progress, should_interrupt)?;
+            let new_index_path = written.index_path.clone().expect("a directory was provided");
+            if let Some(keep_path) = &written.keep_path {
+                std::fs::remove_file(keep_path)?;
+            }
+            outcome.num_objects = written.index.num_objects;
+            packs.retain(|pack| pack.index_path != new_index_path);
+            packs.push(Pack {
+                index_path: new_index_path.clone(),
+                num_objects: written.index.num_objects,
+            });
+            outcome.multi_index = Some(write_multi_index(
+                &pack_dir,
+                packs.iter().chain(&kept),
+                object_hash,
+                self.config.fsync(|components| components.pack_metadata),
+                progress,
+                should_interrupt,
+            )?);
+
+            for pack in rolled_up.iter().filter(|pack| pack.index_path != new_index_path) {
+                if gix_pack::keep::is_kept(&pack.index_path) {
+                    // The pack was kept after it was combined, and its objects are merely duplicated now.
+                    outcome.kept_packs.push(pack.index_path.clone());
+                    continue;
+                }
+                remove_pack(&pack.index_path)?;
+                outcome.removed_packs.push(pack.index_path.clone());
+            }
+            for id in &loose_ids {
+                let path = loose.object_path(id);
+                std::fs::remove_file(&path)?;
+                if let Some(dir) = path.parent() {
+                    // This only works if the directory is empty.
+                    std::fs::remove_dir(dir).ok();
+                }
+            }
+            outcome.removed_loose_objects = loose_ids.len();
+            outcome.new_pack = Some(new_index_path);
+        } else if !packs.is_empty() || !kept.is_empty() {
+            outcome.multi_index = Some(write_multi_index(
+                &pack_dir,
+                packs.iter().chain(&kept),
+                object_hash,
+                self.config.fsync(|components| components.pack_metadata),
+                progress,
+                should_interrupt,
+            )?);
+        }
+        Ok(outcome)
+    }
+
+    /// Copy all objects reachable from references, `HEAD` and the index out of the alternate object databases this
+    /// repository borrows objects from into a new pack, along with all of its own objects, and stop using alternates,
+    /// similar to `git repack -a -d` followed by removing `objects/info/alternates`.
+    ///
+    /// This makes the repository independent of the repositories it borrowed objects from, for instance after cloning with
+    /// reference repositories, which is what `git clone --dissociate` does.
+    /// Nothing is done if the repository doesn't use alternates.
+    ///
+    /// Note that unlike `git`, objects that are only reachable from reference logs aren't copied.
+    /// Packs with a [`.keep` file](gix_pack::keep) are never removed, but their objects are copied as well.
+    pub fn dissociate(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let object_hash = self.object_hash();
+        let objects_dir = self.objects.store_ref().path().to_owned();
+        let alternates_path = objects_dir.join("info").join("alternates");
+        if !alternates_path.is_file() {
+            return Ok(Outcome::default());
+        }
+        let pack_dir = objects_dir.join("pack");
+        let (kept, packs): (Vec<_>, Vec<_>) = list_packs(&pack_dir, object_hash)?
+            .into_iter()
+            .partition(|pack| gix_pack::keep::is_kept(&pack.index_path));
+        let loose = gix_odb::loose::Store::at(&objects_dir, object_hash);
+        let loose_ids = loose.iter().collect::<Result<Vec<_>, _>>()?;
+
+        let mut ids = self.reachable_object_ids(progress, should_interrupt)?;
+        ids.extend(loose_ids.iter().copied());
+        for pack in packs.iter().chain(&kept) {
+            let index = gix_pack::index::File::at(&pack.index_path, object_hash)?;
+            ids.extend(index.iter().map(|entry| entry.oid));
+        }
+        ids.sort();
+        ids.dedup();
+        let written = self.write_pack(ids, &pack_dir, progress, should_interrupt)?;
+        let new_index_path = written.index_path.clone().expect("a directory was provided");
+        if let Some(keep_path) = &written.keep_path {
+            std::fs::remove_file(keep_path)?;
+        }
+        let new_pack = Pack {
+            index_path: new_index_path.clone(),
+            num_objects: written.index.num_objects,
+        };
+        let multi_index = write_multi_index(
+            &pack_dir,
+            std::iter::once(&new_pack).chain(&kept),
+            object_hash,
+            self.config.fsync(|components| components.pack_metadata),
+            progress,
+            should_interrupt,
+        )?;
+        std::fs::remove_file(&alternates_path)?;
+
+        let mut outcome = Outcome {
+            num_objects: written.index.num_objects,
+            multi_index: Some(multi_index),
+            kept_packs: kept.into_iter().map(|pack| pack.index_path).collect(),
+            ..Default::default()
+        };
+        for pack in packs.into_iter().filter(|pack| pack.index_path != new_index_path) {
+            remove_pack(&pack.index_path)?;
+            outcome.removed_packs.push(pack.index_path);
+        }
+        for id in &loose_ids {
+            let path = loose.object_path(id);
+            std::fs::remove_file(&path)?;
+            if let Some(dir) = path.parent() {
+                // This only works if the directory is empty.
+                std::fs::remove_dir(dir).ok();
+            }
+        }
+        outcome.removed_loose_objects = loose_ids.len();
+        outcome.new_pack = Some(new_index_path);
+        Ok(outcome)
+    }
+
+    /// Return the ids of all objects reachable from references, `HEAD` and the index, in no particular order.
+    fn reachable_object_ids(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut tips: Vec<_> = self.head_id().ok().map(crate::Id::detach).into_iter().collect();
+        for reference in self.references()?.all()? {
+            let reference = reference.map_err(Error::IterReferences)?;
+            if let Some(id) = reference.target().try_id() {
+                tips.push(id.to_owned());
+            }
+        }
+        let mut commits = Vec::new();
+        for tip in &tips {
+            let object = self.find_object(*tip)?.peel_tags_to_end()?;
+            if object.kind == gix_object::Kind::Commit {
+                commits.push(object.id);
+            }
+        }
+        let mut ids = tips;
+        for info in self.rev_walk(commits).all()? {
+            ids.push(info?.id);
+        }
+        if let Some(index) = self.try_index()? {
+            ids.extend(
+                index
+                    .entries()
+                    .iter()
+                    .filter(|entry| entry.mode != gix_index::entry::Mode::COMMIT)
+                    .map(|entry| entry.id),
+            );
+            fn valid_trees(tree: &gix_index::extension::Tree, out: &mut Vec<ObjectId>) {
+                if tree.num_entries.is_some() {
+                    out.push(tree.id);
+                }
+                for child in &tree.children {
+                    valid_trees(child, out);
+                }
+            }
+            if let Some(tree) = index.tree() {
+                valid_trees(tree, &mut ids);
+            }
+        }
+
+        let mut count_progress = progress.add_child("finding reachable objects".into());
+        count_progress.init(None, gix_features::progress::count("objects"));
+        let (counts, _) = output::count::objects_unthreaded(
+            &self.objects,
+            &mut ids.into_iter().map(Ok),
+            &count_progress,
+            should_interrupt,
+            output::count::objects::ObjectExpansion::TreeContents,
+        )?;
+        Ok(counts.into_iter().map(|count| count.id).collect())
+    }
+
+    /// Write a pack with all objects identified by `ids` into `pack_dir` and index it.
+    fn write_pack(
+        &self,
+        ids: Vec<ObjectId>,
+        pack_dir: &Path,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<gix_pack::bundle::write::Outcome, Error> {
+        let mut db = self.objects.clone().into_arc()?;
+        db.prevent_pack_unload();
+        db.ignore_replacements = true;
+
+        let (counts, _) = {
+            let mut count_progress = progress.add_child("counting".into());
+            count_progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+            output::count::objects_unthreaded(
+                &db,
+                &mut ids.into_iter().map(Ok),
+                &count_progress,
+                should_interrupt,
+                output::count::objects::ObjectExpansion::AsIs,
+            )?
+        };
+        let num_entries = counts.len() as u32;
+        let mut entries = gix_features::parallel::InOrderIter::from(output::entry::iter_from_counts(
+            counts,
+            db,
+            Box::new(progress.add_child("creating entries".into())),
+            output::entry::iter_from_counts::Options {
+                mode: output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                ..Default::default()
+            },
+        ));
+
+        let mut pack_file = gix_tempfile::new(
+            pack_dir,
+            gix_tempfile::ContainingDirectory::Exists,
+            gix_tempfile::AutoRemove::Tempfile,
+        )?;
+        {
+            let mut out = std::io::BufWriter::new(&mut pack_file);
+            for written in output::bytes::FromEntriesIter::new(
+                entries.by_ref(),
+                &mut out,
+                num_entries,
+                gix_pack::data::Version::V2,
+                self.object_hash(),
+            ) {
+                written?;
+                if should_interrupt.load(std::sync::atomic::Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+            }
+            out.flush()?;
+        }
+        gix_features::parallel::reduce::Finalize::finalize(entries.inner)?;
+
+        let pack_data = pack_file.with_mut(|tempfile| tempfile.reopen())??;
+        Ok(gix_pack::Bundle::write_to_directory(
+            &mut BufReader::new(pack_data),
+            Some(pack_dir),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            gix_pack::bundle::write::Options {
+                object_hash: self.object_hash(),
+                fsync_pack: self.config.fsync(|components| components.packs),
+                fsync_index: self.config.fsync(|components| components.pack_metadata),
+                ..Default::default()
+            },
+        )?)
+    }
+}
+
+/// Return the index paths of all packs in `pack_dir` along with the amount of objects they contain.
+fn list_packs(pack_dir: &Path, object_hash: gix_hash::Kind) -> Result<Vec<Pack>, Error> {
+    let entries = match std::fs::read_dir(pack_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(Error::ListPacks {
+                path: pack_dir.to_owned(),
+                source,
+            })
+        }
+    };
+    let mut out = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|source| Error::ListPacks {
+                path: pack_dir.to_owned(),
+                source,
+            })?
+            .path();
+        if path.extension().map_or(true, |ext| ext != "idx") || !path.with_extension("pack").is_file() {
+            continue;
+        }
+        let num_objects = gix_pack::index::File::at(&path, object_hash)?.num_objects();
+        out.push(Pack {
+            index_path: path,
+            num_objects,
+        });
+    }
+    Ok(out)
+}
+
+/// Given `packs` sorted by their amount of objects in ascending order, return the amount of packs from the beginning
+/// that have to be combined so that the remaining packs, along with the combined one, form a geometric progression
+/// with `factor`.
+///
+/// This is the same algorithm that `git repack --geometric` uses.
+fn geometric_split(packs: &[Pack], factor: u32) -> usize {
+    let weight = |pack: &Pack| u64::from(pack.num_objects);
+    let factor = u64::from(factor);
+    let mut split = packs.len().saturating_sub(1);
+    while split > 0 {
+        if weight(&packs[split]) < factor * weight(&packs[split - 1]) {
+            break;
+        }
+        split -= 1;
+    }
+    if split > 0 {
+        // The larger pack of the pair that broke the progression can't be part of it either.
+        split += 1;
+    }
+
+    // The combined pack may be so large that it breaks the progression of the larger packs, which then have to be
+    // combined as well.
+    let mut total: u64 = packs[..split].iter().map(weight).sum();
+    while split < packs.len() && weight(&packs[split]) < factor * total {
+        total += weight(&packs[split]);
+        split += 1;
+    }
+    split
+}
+
+fn write_multi_index<'a>(
+    pack_dir: &Path,
+    packs: impl IntoIterator<Item = &'a Pack>,
+    object_hash: gix_hash::Kind,
+    fsync: Option<gix_fs::fsync::Method>,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<PathBuf, Error> {
+    let path = pack_dir.join("multi-pack-index");
+    let mut out = std::io::BufWriter::new(gix_lock::File::acquire_to_update_resource(
+        &path,
+        gix_lock::acquire::Fail::Immediately,
+        None,
+    )?);
+    gix_pack::multi_index::File::write_from_index_paths(
+        packs.into_iter().map(|pack| pack.index_path.clone()).collect(),
+        &mut out,
+        progress,
+        should_interrupt,
+        gix_pack::multi_index::write::Options { object_hash },
+    )?;
+    let mut out = out.into_inner().map_err(std::io::IntoInnerError::into_error)?;
+    if let Some(method) = fsync {
+        out.with_mut(|file| gix_fs::fsync::file(file, method))?;
+    }
+    out.commit().map_err(|err| err.error)?;
+    Ok(path)
+}
+
+/// Remove all files belonging to the pack whose index is at `index_path`.
+fn remove_pack(index_path: &Path) -> std::io::Result<()> {
+    for extension in ["pack", "idx", "rev", "bitmap"] {
+        match std::fs::remove_file(index_path.with_extension(extension)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -12,9 +12,15 @@ impl crate::Repository {
         let bytes = bytes.into();
         match bytes {
             Some(0) => self.objects.unset_object_cache(),
-            Some(bytes) => self
-                .objects
-                .set_object_cache(move || Box::new(crate::object::cache::MemoryCappedHashmap::new(bytes))),
+            Some(bytes) => {
+                let budget = self.config.object_cache_budget.clone();
+                self.objects.set_object_cache(move || {
+                    Box::new(gix_pack::cache::budget::Tracked::new(
+                        crate::object::cache::MemoryCappedHashmap::new(bytes),
+                        budget.clone(),
+                    ))
+                })
+            }
             None => self.objects.unset_object_cache(),
         }
     }
@@ -27,4 +33,93 @@ impl crate::Repository {
             self.object_cache_size(bytes)
         }
     }
+
+    /// Return the approximate amount of memory used by the caches of this instance and all of its clones, along with
+    /// the amount of bytes of memory-mapped object database files.
+    ///
+    /// Use this to see how much memory is used while accessing objects, and to tune [`pack_cache_budget()`](Self::pack_cache_budget())
+    /// and [`object_cache_budget()`](Self::object_cache_budget()).
+    pub fn memory_usage(&self) -> crate::repository::MemoryUsage {
+        let (pack, object) = (&self.config.pack_cache_budget, &self.config.object_cache_budget);
+        crate::repository::MemoryUsage {
+            pack_caches: pack.used(),
+            pack_cache_soft_limit: pack.soft_limit(),
+            object_caches: object.used(),
+            object_cache_soft_limit: object.soft_limit(),
+            evictions: pack.evictions() + object.evictions(),
+            mapped_bytes: self.objects.store_ref().mapped_bytes(),
+        }
+    }
+
+    /// Return information about the object database, like the amount of packs and loose objects, its alternates,
+    /// and how effective the caches of this instance and all of its clones are.
+    ///
+    /// This is meant for monitoring, and note that counting loose objects requires reading all of their directories.
+    pub fn object_database_stats(
+        &self,
+    ) -> Result<crate::repository::ObjectDatabaseStats, crate::repository::object_database_stats::Error> {
+        use gix_odb::store::structure::Record;
+
+        let store = self.objects.store_ref();
+        let structure = store.structure()?;
+        let loose_objects = structure
+            .iter()
+            .find_map(|record| match record {
+                Record::LooseObjectDatabase { num_objects, .. } => Some(*num_objects),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let multi_pack_indices = structure
+            .iter()
+            .filter(|record| matches!(record, Record::MultiIndex { .. }))
+            .count();
+
+        let has_bitmaps = match std::fs::read_dir(store.path().join("pack")) {
+            Ok(entries) => {
+                let mut has_bitmaps = false;
+                for entry in entries {
+                    if entry?.path().extension() == Some("bitmap".as_ref()) {
+                        has_bitmaps = true;
+                        break;
+                    }
+                }
+                has_bitmaps
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+
+        let (pack, object) = (&self.config.pack_cache_budget, &self.config.object_cache_budget);
+        let metrics = store.metrics();
+        Ok(crate::repository::ObjectDatabaseStats {
+            packs: metrics.known_packs,
+            multi_pack_indices,
+            loose_objects,
+            alternates: store.alternate_db_paths()?,
+            has_bitmaps,
+            pack_cache: crate::repository::CacheStats {
+                hits: pack.hits(),
+                misses: pack.misses(),
+            },
+            object_cache: crate::repository::CacheStats {
+                hits: object.hits(),
+                misses: object.misses(),
+            },
+            metrics,
+        })
+    }
+
+    /// Return the budget shared by the pack caches of this instance and all of its clones.
+    ///
+    /// Use it to change the soft limit at runtime, which is initialized from `gitoxide.objects.packCacheSoftLimit`.
+    pub fn pack_cache_budget(&self) -> &gix_pack::cache::budget::Budget {
+        &self.config.pack_cache_budget
+    }
+
+    /// Return the budget shared by the object caches of this instance and all of its clones.
+    ///
+    /// Use it to change the soft limit at runtime, which is initialized from `gitoxide.objects.cacheSoftLimit`.
+    pub fn object_cache_budget(&self) -> &gix_pack::cache::budget::Budget {
+        &self.config.object_cache_budget
+    }
 }
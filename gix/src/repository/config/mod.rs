@@ -22,6 +22,70 @@ impl crate::Repository {
         }
     }
 
+    /// Return `true` if the configuration would be different if it was loaded again, as the files or environment variables
+    /// it was loaded from changed, or as `HEAD` points to another branch which may affect conditional includes.
+    ///
+    /// This check merely obtains the metadata of all files involved and is cheap enough to be called before each
+    /// use of the configuration in long-running processes.
+    ///
+    /// Note that changes made in memory, for instance with [`config_snapshot_mut()`](Self::config_snapshot_mut()),
+    /// aren't considered.
+    pub fn is_config_outdated(&self) -> bool {
+        let branch_name = self.head_name().ok().flatten();
+        !self
+            .config
+            .fingerprint
+            .is_current(branch_name.as_ref().map(AsRef::as_ref))
+    }
+
+    /// Load the configuration from disk again, similar to what happens when opening the repository, and replace
+    /// the current configuration with it.
+    ///
+    /// Note that changes made in memory, for instance with [`config_snapshot_mut()`](Self::config_snapshot_mut()),
+    /// are lost, and that the location of the worktree isn't updated even if `core.worktree` changed.
+    pub fn reload_config(&mut self) -> Result<(), config::Error> {
+        let options = &self.options;
+        let stage_one = config::cache::StageOne::new(
+            self.common_dir(),
+            self.git_dir(),
+            options.git_dir_trust.expect("trust is determined when opening"),
+            options.lossy_config,
+            options.lenient_config,
+        )?;
+        let env = options.permissions.env;
+        let git_install_dir = crate::path::install_dir().ok();
+        let home = gix_path::env::home_dir().and_then(|home| env.home.check_opt(home));
+        let branch_name = self.head_name().ok().flatten();
+        let cache = config::Cache::from_stage_one(
+            stage_one,
+            self.common_dir(),
+            branch_name.as_ref().map(AsRef::as_ref),
+            options.filter_config_section.unwrap_or(config::section::is_trusted),
+            git_install_dir.as_deref(),
+            home.as_deref(),
+            env,
+            options.local_repo_env,
+            options.permissions.attributes,
+            options.permissions.config,
+            options.lenient_config,
+            &options.api_config_overrides,
+            &options.cli_config_overrides,
+        )?;
+        self.reread_values_and_clear_caches_replacing_config(cache.resolved)?;
+        self.config.fingerprint = cache.fingerprint;
+        Ok(())
+    }
+
+    /// Like [`reload_config()`](Self::reload_config()), but only reload the configuration if
+    /// [it is outdated](Self::is_config_outdated()), and return `true` in that case.
+    pub fn reload_config_if_changed(&mut self) -> Result<bool, config::Error> {
+        if !self.is_config_outdated() {
+            return Ok(false);
+        }
+        self.reload_config()?;
+        Ok(true)
+    }
+
     /// Return filesystem options as retrieved from the repository configuration.
     ///
     /// Note that these values have not been [probed](gix_fs::Capabilities::probe()).
@@ -70,6 +134,7 @@ impl crate::Repository {
                 .and_then(|variant| Ssh::VARIANT.try_into_variant(variant).transpose())
                 .transpose()
                 .with_leniency(self.options.lenient_config)?,
+            multiplex: None,
         };
         Ok(opts)
     }
@@ -101,6 +166,7 @@ impl crate::Repository {
                     .into()
             },
             git_dir: self.git_dir().to_owned().into(),
+            index_file: None,
             worktree_dir: self.work_dir().map(ToOwned::to_owned),
             no_replace_objects: config::shared::is_replace_refs_enabled(
                 &self.config.resolved,
@@ -126,6 +192,7 @@ mod branch;
 mod remote;
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 mod transport;
+mod worktree;
 
 impl crate::Repository {
     pub(crate) fn filter_config_section(&self) -> fn(&gix_config::file::Metadata) -> bool {
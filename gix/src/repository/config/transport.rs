@@ -141,6 +141,55 @@ impl crate::Repository {
                             }))
                     }
 
+                    /// Return the value of `http.<url>.<name>` whose `<url>` matches `url` most specifically, or the value
+                    /// of `http.<name>` otherwise. Values set by environment variables override all of them.
+                    fn url_specific_value<'a>(
+                        config: &'a gix_config::File<'static>,
+                        name: &str,
+                        url: &gix_url::Url,
+                        filter: fn(&gix_config::file::Metadata) -> bool,
+                    ) -> Option<Cow<'a, BStr>> {
+                        let mut best = None;
+                        for section in config.sections_by_name("http")?.filter(|s| filter(s.meta())) {
+                            let specificity = if section.meta().source == gix_config::Source::EnvOverride {
+                                (usize::MAX, true)
+                            } else {
+                                match section.header().subsection_name() {
+                                    None => (0, false),
+                                    Some(pattern) => match url_pattern_specificity(pattern, url) {
+                                        Some(specificity) => specificity,
+                                        None => continue,
+                                    },
+                                }
+                            };
+                            if let Some(value) = section.value(name) {
+                                if best.as_ref().map_or(true, |(best, _)| specificity >= *best) {
+                                    best = Some((specificity, value));
+                                }
+                            }
+                        }
+                        best.map(|(_, value)| value)
+                    }
+
+                    /// Return how specific `pattern` matches `url` as the length of its path and whether it has a user,
+                    /// or `None` if it doesn't match, just like `git` does it for `http.<url>.*` sections.
+                    fn url_pattern_specificity(pattern: &BStr, url: &gix_url::Url) -> Option<(usize, bool)> {
+                        use crate::bstr::ByteSlice;
+                        let pattern = gix_url::parse(pattern).ok()?;
+                        if pattern.scheme != url.scheme
+                            || matches!(pattern.user(), Some(user) if Some(user) != url.user())
+                            || !crate::config::credential_helpers::function::host_matches(pattern.host(), url.host())
+                            || pattern.port_or_default() != url.port_or_default()
+                        {
+                            return None;
+                        }
+                        let pattern_path = pattern.path.trim_end_with(|c| c == '/');
+                        let url_path = url.path.trim_end_with(|c| c == '/');
+                        let is_prefix = url_path.starts_with(pattern_path)
+                            && url_path.get(pattern_path.len()).map_or(true, |b| *b == b'/');
+                        is_prefix.then_some((pattern_path.len() + 1, pattern.user().is_some()))
+                    }
+
                     let mut opts = http::Options::default();
                     let config = &self.config.resolved;
                     let mut trusted_only = self.filter_config_section();
@@ -175,6 +224,22 @@ impl crate::Repository {
                             .map_err(config::transport::http::Error::InvalidFollowRedirects)?
                     };
 
+                    opts.empty_auth = {
+                        let key = "http.emptyAuth";
+                        debug_assert_eq!(key, config::tree::Http::EMPTY_AUTH.logical_name());
+                        config
+                            .string_filter(key, &mut trusted_only)
+                            .map(|value| {
+                                config::tree::Http::EMPTY_AUTH.try_into_empty_auth(value, || {
+                                    config.boolean_filter(key, &mut trusted_only).transpose()
+                                })
+                            })
+                            .transpose()
+                            .map_err(config::transport::http::Error::InvalidEmptyAuth)
+                            .with_leniency(lenient)?
+                            .unwrap_or_default()
+                    };
+
                     opts.low_speed_time_seconds = config
                         .integer_filter("http.lowSpeedTime", &mut trusted_only)
                         .map(|value| config::tree::Http::LOW_SPEED_TIME.try_into_u64(value))
@@ -338,6 +403,26 @@ impl crate::Repository {
                             .unwrap_or_default();
                     }
 
+                    let ssl_path =
+                        |name: &str,
+                         key: &'static str,
+                         filter: fn(&gix_config::file::Metadata) -> bool|
+                         -> Result<Option<std::path::PathBuf>, config::transport::Error> {
+                            url_specific_value(config, name, &url, filter)
+                                .map(|p| {
+                                    use crate::config::cache::interpolate_context;
+                                    gix_config::Path::from(p)
+                                        .interpolate(interpolate_context(
+                                            self.install_dir().ok().as_deref(),
+                                            self.config.home_dir().as_deref(),
+                                        ))
+                                        .map(std::borrow::Cow::into_owned)
+                                })
+                                .transpose()
+                                .with_leniency(lenient)
+                                .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })
+                        };
+
                     let may_use_cainfo = {
                         let key = "http.schannelUseSSLCAInfo";
                         config
@@ -352,19 +437,15 @@ impl crate::Repository {
                     if may_use_cainfo {
                         let key = "http.sslCAInfo";
                         debug_assert_eq!(key, config::tree::Http::SSL_CA_INFO.logical_name());
-                        opts.ssl_ca_info = config
-                            .path_filter(key, &mut trusted_only)
-                            .map(|p| {
-                                use crate::config::cache::interpolate_context;
-                                p.interpolate(interpolate_context(
-                                    self.install_dir().ok().as_deref(),
-                                    self.config.home_dir().as_deref(),
-                                ))
-                                .map(std::borrow::Cow::into_owned)
-                            })
-                            .transpose()
-                            .with_leniency(lenient)
-                            .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })?;
+                        opts.ssl_ca_info = ssl_path(config::tree::Http::SSL_CA_INFO.name, key, trusted_only)?;
+                    }
+                    {
+                        let key = "http.sslCert";
+                        debug_assert_eq!(key, config::tree::Http::SSL_CERT.logical_name());
+                        opts.ssl_cert = ssl_path(config::tree::Http::SSL_CERT.name, key, trusted_only)?;
+                        let key = "http.sslKey";
+                        debug_assert_eq!(key, config::tree::Http::SSL_KEY.logical_name());
+                        opts.ssl_key = ssl_path(config::tree::Http::SSL_KEY.name, key, trusted_only)?;
                     }
 
                     {
@@ -417,13 +498,17 @@ impl crate::Repository {
                             opts.ssl_verify = false;
                         } else {
                             let key = "http.sslVerify";
-                            opts.ssl_verify = config
-                                .boolean_filter(key, &mut trusted_only)
-                                .map(|value| config::tree::Http::SSL_VERIFY.enrich_error(value))
-                                .transpose()
-                                .with_leniency(lenient)
-                                .map_err(config::transport::http::Error::from)?
-                                .unwrap_or(true);
+                            debug_assert_eq!(key, config::tree::Http::SSL_VERIFY.logical_name());
+                            opts.ssl_verify =
+                                url_specific_value(config, config::tree::Http::SSL_VERIFY.name, &url, trusted_only)
+                                    .map(|value| {
+                                        config::tree::Http::SSL_VERIFY
+                                            .enrich_error(gix_config::Boolean::try_from(value).map(|b| b.0))
+                                    })
+                                    .transpose()
+                                    .with_leniency(lenient)
+                                    .map_err(config::transport::http::Error::from)?
+                                    .unwrap_or(true);
                         }
                     }
 
@@ -0,0 +1,126 @@
+use gix_config::{file::Metadata, file::Transaction, Source};
+
+use crate::bstr::{BString, ByteSlice};
+use crate::config::tree::{Core, Extensions};
+use crate::repository::{edit_worktree_config, enable_worktree_config};
+
+/// The keys in the `core` section that are specific to a worktree, and which are moved from the common configuration
+/// to the configuration of the main worktree when [enabling worktree configuration](crate::Repository::enable_worktree_config()).
+const WORKTREE_SPECIFIC_CORE_KEYS: &[&str] = &["bare", "worktree", "sparseCheckout", "sparseCheckoutCone"];
+
+/// Edit configuration specific to worktrees, i.e. the `config.worktree` file.
+impl crate::Repository {
+    /// Enable `extensions.worktreeConfig` in the repository configuration so that each worktree may have its own
+    /// configuration in `config.worktree`, return `true` if it was enabled by this call, and reload the configuration.
+    ///
+    /// Just like `git` does, `core.repositoryFormatVersion` is raised to `1` if needed, and worktree-specific keys
+    /// like `core.worktree`, `core.bare` (if `true`), `core.sparseCheckout` and `core.sparseCheckoutCone` are moved from
+    /// the common configuration to the `config.worktree` file of the main worktree so they don't apply to
+    /// all linked worktrees anymore.
+    /// All files are locked while they are changed, and are either all changed or none of them.
+    pub fn enable_worktree_config(&mut self) -> Result<bool, enable_worktree_config::Error> {
+        let mut transaction = Transaction::prepare(
+            [
+                Metadata::from(Source::Local).at(self.common_dir().join("config")),
+                Metadata::from(Source::Worktree).at(self.common_dir().join("config.worktree")),
+            ],
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        let local = transaction.file_mut(Source::Local).expect("added");
+        if is_enabled(local)? {
+            return Ok(false);
+        }
+        let version = local
+            .integer(&Core::REPOSITORY_FORMAT_VERSION)
+            .map(|version| Core::REPOSITORY_FORMAT_VERSION.try_into_usize(version))
+            .transpose()?
+            .unwrap_or_default();
+        if version < 1 {
+            local.set_raw_value(&"core.repositoryFormatVersion", "1")?;
+        }
+        local.set_raw_value(&"extensions.worktreeConfig", "true")?;
+
+        let mut moved = Vec::new();
+        for name in WORKTREE_SPECIFIC_CORE_KEYS {
+            let is_bare_and_false = *name == "bare"
+                && local
+                    .boolean(&Core::BARE)
+                    .map(|value| Core::BARE.enrich_error(value))
+                    .transpose()?
+                    .map_or(true, |is_bare| !is_bare);
+            if is_bare_and_false {
+                continue;
+            }
+            if let Some(value) = take_core_value(local, name) {
+                moved.push((*name, value));
+            }
+        }
+        let worktree = transaction.file_mut(Source::Worktree).expect("added");
+        for (name, value) in &moved {
+            worktree.set_raw_value_by("core", None, *name, value.as_bstr())?;
+        }
+        transaction.commit()?;
+        self.reload_config()?;
+        Ok(true)
+    }
+
+    /// Call `edit` with the configuration file that is specific to the worktree of this repository, commit all changes
+    /// made to it and reload the configuration, returning the value returned by `edit`.
+    ///
+    /// If [worktree configuration](Self::enable_worktree_config()) isn't enabled, the repository configuration is edited
+    /// instead as long as there are no linked worktrees, just like `git config --worktree` does, and an error is returned
+    /// otherwise.
+    /// The files are locked while `edit` runs, and aren't changed if `edit` doesn't change them.
+    pub fn edit_worktree_config<T>(
+        &mut self,
+        edit: impl FnOnce(&mut gix_config::File<'static>) -> T,
+    ) -> Result<T, edit_worktree_config::Error> {
+        let mut transaction = Transaction::prepare(
+            [
+                Metadata::from(Source::Local).at(self.common_dir().join("config")),
+                Metadata::from(Source::Worktree).at(self.git_dir().join("config.worktree")),
+            ],
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        let source = if is_enabled(transaction.file(Source::Local).expect("added"))? {
+            Source::Worktree
+        } else if self.worktrees()?.is_empty() {
+            Source::Local
+        } else {
+            return Err(edit_worktree_config::Error::NotEnabled);
+        };
+        let res = edit(transaction.file_mut(source).expect("added"));
+        transaction.commit()?;
+        self.reload_config()?;
+        Ok(res)
+    }
+}
+
+fn is_enabled(local: &gix_config::File<'_>) -> Result<bool, crate::config::boolean::Error> {
+    Ok(local
+        .boolean(&Extensions::WORKTREE_CONFIG)
+        .map(|value| Extensions::WORKTREE_CONFIG.enrich_error(value))
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// Remove all values called `name` from all `core` sections in `config`, and return the value that was effective.
+fn take_core_value(config: &mut gix_config::File<'static>, name: &str) -> Option<BString> {
+    let ids: Vec<_> = config
+        .sections_and_ids_by_name("core")?
+        .filter(|(section, _)| section.header().subsection_name().is_none())
+        .map(|(_, id)| id)
+        .collect();
+    let mut effective = None;
+    for id in ids {
+        let mut section = config.section_mut_by_id(id).expect("id is valid");
+        let mut last_in_section = None;
+        while let Some(value) = section.remove(name) {
+            last_in_section.get_or_insert_with(|| value.into_owned());
+        }
+        if last_in_section.is_some() {
+            effective = last_in_section;
+        }
+    }
+    effective
+}
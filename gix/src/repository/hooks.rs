@@ -0,0 +1,142 @@
+#[cfg(feature = "command")]
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+#[cfg(feature = "command")]
+use gix_ref::{
+    file::transaction::State,
+    transaction::{Change, PreviousValue, RefEdit},
+    Target,
+};
+
+#[cfg(feature = "command")]
+use crate::config::tree::Core;
+use crate::reference;
+
+/// Hooks
+impl crate::Repository {
+    /// Return the directory containing the hooks as configured by `core.hooksPath`, or the `hooks` directory
+    /// in the common `.git` directory by default.
+    ///
+    /// Relative paths are relative to the worktree, or to the `.git` directory in bare repositories.
+    #[cfg(feature = "command")]
+    pub(crate) fn hooks_dir(&self) -> Result<PathBuf, gix_config::path::interpolate::Error> {
+        Ok(match self.config.trusted_file_path(Core::HOOKS_PATH).transpose()? {
+            Some(path) => self.work_dir().unwrap_or(self.git_dir()).join(path),
+            None => self.common_dir().join("hooks"),
+        })
+    }
+
+    /// Return the path to the executable hook called `name`, or `None` if it doesn't exist or if hooks aren't permitted.
+    #[cfg(feature = "command")]
+    fn hook(&self, name: &str) -> Result<Option<PathBuf>, gix_config::path::interpolate::Error> {
+        if !self.options.permissions.hooks {
+            return Ok(None);
+        }
+        let path = self.hooks_dir()?.join(name);
+        Ok(std::fs::metadata(&path)
+            .ok()
+            .filter(|meta| meta.is_file() && gix_fs::is_executable(meta))
+            .map(|_| path))
+    }
+
+    /// Start a transaction to edit references, which runs the `reference-transaction` hook if it exists and hooks are permitted.
+    /// Hooks are only run if the `command` feature is enabled.
+    ///
+    /// Loose objects written in batch mode are flushed first, so references never point to objects that could be lost.
+    pub(crate) fn reference_transaction(&self) -> Result<gix_ref::file::Transaction<'_, '_>, reference::edit::Error> {
+        self.objects
+            .store_ref()
+            .flush_batch()
+            .map_err(reference::edit::Error::FlushObjects)?;
+        self.with_reference_transaction_hook(self.refs.transaction())
+    }
+
+    #[cfg(feature = "command")]
+    fn with_reference_transaction_hook<'s, 'h>(
+        &self,
+        transaction: gix_ref::file::Transaction<'s, 'h>,
+    ) -> Result<gix_ref::file::Transaction<'s, 'h>, reference::edit::Error> {
+        let Some(hook) = self.hook("reference-transaction")? else {
+            return Ok(transaction);
+        };
+        let cwd = self.work_dir().unwrap_or(self.git_dir()).to_owned();
+        let context = gix_command::Context {
+            git_dir: self.git_dir().to_owned().into(),
+            worktree_dir: self.work_dir().map(ToOwned::to_owned),
+            index_file: self.work_dir().map(|_| self.index_path()),
+            ..Default::default()
+        };
+        let null = self.object_hash().null();
+        Ok(transaction.with_observer(Box::new(move |state, edits| {
+            if edits.is_empty() {
+                return Ok(());
+            }
+            run_reference_transaction_hook(&hook, &cwd, &context, state, edits, null)
+        })))
+    }
+
+    #[cfg(not(feature = "command"))]
+    #[allow(clippy::unnecessary_wraps)]
+    fn with_reference_transaction_hook<'s, 'h>(
+        &self,
+        transaction: gix_ref::file::Transaction<'s, 'h>,
+    ) -> Result<gix_ref::file::Transaction<'s, 'h>, reference::edit::Error> {
+        Ok(transaction)
+    }
+}
+
+#[cfg(feature = "command")]
+/// Run the `reference-transaction` hook at `hook` for `edits` in `state`, and fail if it doesn't succeed.
+fn run_reference_transaction_hook(
+    hook: &Path,
+    cwd: &Path,
+    context: &gix_command::Context,
+    state: State,
+    edits: &[RefEdit],
+    null: gix_hash::ObjectId,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let format = |target: Option<&Target>| match target {
+        Some(Target::Peeled(id)) => id.to_string(),
+        Some(Target::Symbolic(name)) => format!("ref:{}", name.as_bstr()),
+        None => null.to_string(),
+    };
+    let mut input = Vec::new();
+    for edit in edits {
+        let (expected, new) = match &edit.change {
+            Change::Update { expected, new, .. } => (expected, Some(new)),
+            Change::Delete { expected, .. } => (expected, None),
+        };
+        let previous = match expected {
+            PreviousValue::MustExistAndMatch(target) | PreviousValue::ExistingMustMatch(target) => Some(target),
+            PreviousValue::Any | PreviousValue::MustExist | PreviousValue::MustNotExist => None,
+        };
+        writeln!(input, "{} {} {}", format(previous), format(new), edit.name.as_bstr())?;
+    }
+
+    let mut cmd: std::process::Command = gix_command::prepare(hook)
+        .arg(state.as_str())
+        .with_environment(gix_command::Environment::GitClean)
+        .with_context(context.clone())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .into();
+    let mut child = cmd.current_dir(cwd).spawn()?;
+    let written = child.stdin.take().expect("configured").write_all(&input);
+    let status = child.wait()?;
+    match written {
+        Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => return Err(err.into()),
+        _ => {}
+    }
+    if !status.success() {
+        return Err(format!(
+            "The reference-transaction hook at '{}' failed with {status}",
+            hook.display()
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -3,7 +3,10 @@ use std::time::SystemTime;
 use crate::{
     bstr::BString,
     config,
-    config::tree::{gitoxide, keys, Author, Committer, Key, User},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{gitoxide, keys, Author, Committer, Key, User},
+    },
 };
 
 /// Identity handling.
@@ -70,6 +73,159 @@ impl crate::Repository {
         })
         .into()
     }
+
+    /// Resolve the identity of `role` exactly like `git` does, or return an error that explains what to configure.
+    ///
+    /// Unlike [`author()`](Self::author()) and [`committer()`](Self::committer()), this prefers the
+    /// `GIT_(AUTHOR|COMMITTER)_(NAME|EMAIL)` environment variables over the configuration, and unless `user.useConfigOnly`
+    /// is set, falls back to the `EMAIL` environment variable and the name of the user and host.
+    /// On Unix, the latter are obtained from the user database and the system, while elsewhere the `USER|USERNAME` and
+    /// `HOSTNAME|COMPUTERNAME` environment variables are used, which is also the case if the system can't provide them.
+    /// See [`gix_actor::resolve::identity()`] for details.
+    ///
+    /// Note that unlike `git`, the full name of the user is only read on Unix.
+    ///
+    /// Note that environment variables are only used if the [identity permission](crate::open::permissions::Environment::identity)
+    /// allows it.
+    pub fn resolve_identity(
+        &self,
+        role: gix_actor::resolve::Role,
+    ) -> Result<gix_actor::Identity, crate::repository::resolve_identity::Error> {
+        use gix_actor::resolve::Role;
+        let config = &self.config.resolved;
+        let string = |key: &keys::Any| config.string(key).map(std::borrow::Cow::into_owned);
+        let var = |name: &str| {
+            self.options
+                .permissions
+                .env
+                .identity
+                .check_opt(name)
+                .and_then(std::env::var_os)
+                .and_then(|value| gix_path::os_string_into_bstring(value).ok())
+        };
+        let (env_name, env_email, config_name, config_email) = match role {
+            Role::Author => (
+                &gitoxide::Author::NAME_FALLBACK,
+                &gitoxide::Author::EMAIL_FALLBACK,
+                &Author::NAME,
+                &Author::EMAIL,
+            ),
+            Role::Committer => (
+                &gitoxide::Committer::NAME_FALLBACK,
+                &gitoxide::Committer::EMAIL_FALLBACK,
+                &Committer::NAME,
+                &Committer::EMAIL,
+            ),
+        };
+        let use_config_only = config
+            .boolean(&User::USE_CONFIG_ONLY)
+            .map(|res| User::USE_CONFIG_ONLY.enrich_error(res))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or_default();
+        let system_user = (!use_config_only).then(system::user).flatten();
+        let sources = gix_actor::resolve::Sources {
+            env_name: string(env_name),
+            env_email: string(env_email),
+            config_name: string(config_name),
+            config_email: string(config_email),
+            user_name: string(&User::NAME),
+            user_email: string(&User::EMAIL),
+            use_config_only,
+            email: string(&gitoxide::User::EMAIL_FALLBACK),
+            full_name: system_user.as_ref().and_then(|(_, full_name)| full_name.clone()),
+            username: system_user
+                .map(|(login, _)| login)
+                .or_else(|| var("USER"))
+                .or_else(|| var("USERNAME")),
+            hostname: system::hostname()
+                .or_else(|| var("HOSTNAME"))
+                .or_else(|| var("COMPUTERNAME")),
+        };
+        Ok(gix_actor::resolve::identity(role, &sources)?)
+    }
+}
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+mod system {
+    use std::ffi::CStr;
+
+    use crate::bstr::{BString, ByteSlice};
+
+    /// Return the name of the host as reported by the system.
+    pub fn hostname() -> Option<BString> {
+        let mut buf = [0u8; 256];
+        // SAFETY: the buffer is valid for writes of its length.
+        let res = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+        if res != 0 {
+            return None;
+        }
+        // Without a null-byte, the name was truncated.
+        let len = buf.iter().position(|b| *b == 0)?;
+        (len != 0).then(|| buf[..len].into())
+    }
+
+    /// Return the login name of the current user along with their full name from the user database, like `git` does.
+    pub fn user() -> Option<(BString, Option<BString>)> {
+        let mut entry = std::mem::MaybeUninit::<libc::passwd>::uninit();
+        let mut buf = vec![0 as libc::c_char; 1024];
+        let mut result = std::ptr::null_mut();
+        loop {
+            // SAFETY: all pointers are valid, and `buf` is valid for writes of its length.
+            let res = unsafe {
+                libc::getpwuid_r(
+                    libc::getuid(),
+                    entry.as_mut_ptr(),
+                    buf.as_mut_ptr(),
+                    buf.len(),
+                    &mut result,
+                )
+            };
+            match res {
+                libc::ERANGE if buf.len() < 1024 * 1024 => buf.resize(buf.len() * 2, 0),
+                0 if !result.is_null() => break,
+                _ => return None,
+            }
+        }
+        // SAFETY: the entry was initialized as `result` points to it, and its strings point into `buf` which is still alive.
+        let entry = unsafe { entry.assume_init() };
+        let login: BString = unsafe { CStr::from_ptr(entry.pw_name) }.to_bytes().into();
+        let full_name = (!entry.pw_gecos.is_null())
+            .then(|| unsafe { CStr::from_ptr(entry.pw_gecos) }.to_bytes())
+            .map(|gecos| full_name_from_gecos(gecos, login.as_ref()))
+            .filter(|name| !name.is_empty());
+        Some((login, full_name))
+    }
+
+    /// Like `git`, use the first comma-separated field and replace `&` with the capitalized login name.
+    fn full_name_from_gecos(gecos: &[u8], login: &[u8]) -> BString {
+        let name = gecos.split_str(",").next().unwrap_or_default();
+        let mut out = BString::default();
+        for (idx, part) in name.split_str("&").enumerate() {
+            if idx > 0 {
+                if let Some((first, rest)) = login.split_first() {
+                    out.push(first.to_ascii_uppercase());
+                    out.extend_from_slice(rest);
+                }
+            }
+            out.extend_from_slice(part);
+        }
+        out
+    }
+}
+
+#[cfg(not(unix))]
+mod system {
+    use crate::bstr::BString;
+
+    pub fn hostname() -> Option<BString> {
+        None
+    }
+
+    pub fn user() -> Option<(BString, Option<BString>)> {
+        None
+    }
 }
 
 fn extract_time_or_default(
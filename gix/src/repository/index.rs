@@ -124,6 +124,14 @@ impl crate::Repository {
             self.git_dir().join("index"),
         ))
     }
+
+    /// Return the options to use when writing the index file, which flush it to permanent storage as configured by `core.fsync`.
+    pub(crate) fn index_write_options(&self) -> gix_index::write::Options {
+        gix_index::write::Options {
+            fsync: self.config.fsync(|components| components.index),
+            ..Default::default()
+        }
+    }
 }
 
 impl std::ops::Deref for IndexPersistedOrInMemory {
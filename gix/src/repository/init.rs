@@ -40,23 +40,42 @@ impl crate::Repository {
 pub(crate) fn setup_objects(objects: &mut crate::OdbHandle, config: &crate::config::Cache) {
     #[cfg(feature = "max-performance-safe")]
     {
+        use gix_pack::cache::budget::Tracked;
+        let budget = config.pack_cache_budget.clone();
         match config.pack_cache_bytes {
             None => match config.static_pack_cache_limit_bytes {
-                None => objects.set_pack_cache(|| Box::<gix_pack::cache::lru::StaticLinkedList<64>>::default()),
-                Some(limit) => {
-                    objects.set_pack_cache(move || Box::new(gix_pack::cache::lru::StaticLinkedList::<64>::new(limit)))
-                }
+                None => objects.set_pack_cache(move || {
+                    Box::new(Tracked::new(
+                        gix_pack::cache::lru::StaticLinkedList::<64>::default(),
+                        budget.clone(),
+                    ))
+                }),
+                Some(limit) => objects.set_pack_cache(move || {
+                    Box::new(Tracked::new(
+                        gix_pack::cache::lru::StaticLinkedList::<64>::new(limit),
+                        budget.clone(),
+                    ))
+                }),
             },
             Some(0) => objects.unset_pack_cache(),
             Some(bytes) => objects.set_pack_cache(move || -> Box<gix_odb::cache::PackCache> {
-                Box::new(gix_pack::cache::lru::MemoryCappedHashmap::new(bytes))
+                Box::new(Tracked::new(
+                    gix_pack::cache::lru::MemoryCappedHashmap::new(bytes),
+                    budget.clone(),
+                ))
             }),
         };
         if config.object_cache_bytes == 0 {
             objects.unset_object_cache();
         } else {
             let bytes = config.object_cache_bytes;
-            objects.set_object_cache(move || Box::new(gix_pack::cache::object::MemoryCappedHashmap::new(bytes)));
+            let budget = config.object_cache_budget.clone();
+            objects.set_object_cache(move || {
+                Box::new(Tracked::new(
+                    gix_pack::cache::object::MemoryCappedHashmap::new(bytes),
+                    budget.clone(),
+                ))
+            });
         }
     }
 }
@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use gix_path::realpath::MAX_SYMLINKS;
 
+use crate::config::tree::{gitoxide, Key};
+
 impl crate::Repository {
     /// Return the path to the repository itself, containing objects, references, configuration, and more.
     ///
@@ -32,8 +34,17 @@ impl crate::Repository {
     }
 
     /// Return the path to the worktree index file, which may or may not exist.
+    ///
+    /// It can be overridden with `gitoxide.core.indexFile`, which is set by `GIT_INDEX_FILE`, and which is
+    /// relative to the [current working directory](Self::current_dir()) unless it's absolute.
     pub fn index_path(&self) -> PathBuf {
-        self.git_dir().join("index")
+        match self.config.resolved.string_filter(
+            gitoxide::Core::INDEX_FILE.logical_name().as_str(),
+            &mut self.filter_config_section(),
+        ) {
+            Some(path) => self.current_dir().join(gix_path::from_bstr(path)),
+            None => self.git_dir().join("index"),
+        }
     }
 
     /// The path to the `.gitmodules` file in the worktree, if a worktree is available.
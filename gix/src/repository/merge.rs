@@ -0,0 +1,753 @@
+use std::{collections::HashSet, path::Path, sync::atomic::AtomicBool};
+
+use gix_features::progress::{DynNestedProgress, Progress};
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Category, FullName, FullNameRef, Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    config::cache::util::ApplyLeniencyDefault,
+    config::tree::Merge,
+    ext::ObjectIdExt,
+    merge,
+    merge::{blob, tree, tree_options, trees_to_index, FastForward},
+    repository::merge_base,
+    Id,
+};
+
+/// Merging
+impl crate::Repository {
+    /// Return options for use with [`merge_trees_to_index()`](Self::merge_trees_to_index()) as configured by `merge.conflictStyle`
+    /// and the `core.protect*` settings.
+    pub fn tree_merge_options(&self) -> Result<tree::Options, tree_options::Error> {
+        let conflict_style = self
+            .config
+            .resolved
+            .string(Merge::CONFLICT_STYLE)
+            .map(|value| {
+                Merge::CONFLICT_STYLE
+                    .try_into_conflict_style(value)
+                    .with_lenient_default(self.config.lenient_config)
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(tree::Options {
+            blob: blob::Options {
+                conflict_style,
+                ..Default::default()
+            },
+            validate: self.config.protect_options()?,
+        })
+    }
+
+    /// Merge the trees `ours` and `theirs` which have the tree `base` as common ancestor, and return the merged index
+    /// without touching the worktree, which is what servers typically do to test if branches can be merged.
+    ///
+    /// Entries that can't be merged automatically are placed into stages 1, 2 and 3 of the index for the common ancestor,
+    /// our and their version respectively, while everything else is merged into stage 0.
+    /// `labels` are written next to conflict markers into the blobs of files with conflicting content, and `options`
+    /// can be obtained with [`tree_merge_options()`](Self::tree_merge_options()).
+    ///
+    /// Merged blobs are written into the object database, but the index is only kept in memory. Write it to persist it,
+    /// or to replace the repository's index.
+    ///
+    /// `progress` is informed about the merged paths and blobs, and the merge stops with an error once `should_interrupt` is `true`.
+    pub fn merge_trees_to_index(
+        &self,
+        base: &gix_hash::oid,
+        ours: &gix_hash::oid,
+        theirs: &gix_hash::oid,
+        labels: blob::Labels<'_>,
+        options: tree::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<trees_to_index::Outcome, trees_to_index::Error> {
+        let tree::Outcome { index, conflicts } = tree::merge(
+            base,
+            ours,
+            theirs,
+            &self.objects,
+            labels,
+            options,
+            progress,
+            should_interrupt,
+        )?;
+        Ok(trees_to_index::Outcome {
+            index: gix_index::File::from_state(index, self.index_path()),
+            conflicts,
+        })
+    }
+
+    /// Return the best common ancestor of the commits `one` and `two`, or `None` if they don't share any history.
+    ///
+    /// If there are multiple merge-bases, which can happen with criss-cross merges, the one with the most recent
+    /// commit time is returned.
+    pub fn merge_base(
+        &self,
+        one: impl Into<ObjectId>,
+        two: impl Into<ObjectId>,
+    ) -> Result<Option<Id<'_>>, merge_base::Error> {
+        let mut graph = self.revision_graph();
+        let bases = gix_revision::merge_base(one.into(), &[two.into()], &mut graph)?;
+        Ok(bases
+            .and_then(|bases| bases.into_iter().next())
+            .map(|id| id.attach(self)))
+    }
+
+    /// Return the best common ancestor of all `commits`, or `None` if they don't share any history or if `commits` is empty,
+    /// similar to `git merge-base --octopus`.
+    pub fn merge_base_octopus(
+        &self,
+        commits: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Option<Id<'_>>, merge_base::Error> {
+        let commits: Vec<_> = commits.into_iter().map(Into::into).collect();
+        let mut graph = self.revision_graph();
+        let bases = gix_revision::merge_base::octopus(&commits, &mut graph)?;
+        Ok(bases
+            .and_then(|bases| bases.into_iter().next())
+            .map(|id| id.attach(self)))
+    }
+
+    /// Return `true` if the commit `ancestor` is reachable from the commit `descendant`, or if both are the same,
+    /// similar to `git merge-base --is-ancestor`.
+    ///
+    /// Generation numbers of the commit-graph are used to avoid walking commits that are older than `ancestor`, if available.
+    pub fn is_ancestor(
+        &self,
+        ancestor: impl Into<ObjectId>,
+        descendant: impl Into<ObjectId>,
+    ) -> Result<bool, merge_base::Error> {
+        let mut graph = self.revision_graph();
+        Ok(gix_revision::merge_base::is_ancestor(
+            ancestor.into(),
+            descendant.into(),
+            &mut graph,
+        )?)
+    }
+
+    /// Return those of `commits` that aren't reachable from any of the others, with the most recent commits first,
+    /// similar to `git merge-base --independent`.
+    pub fn independent(
+        &self,
+        commits: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<Id<'_>>, merge_base::Error> {
+        let commits: Vec<_> = commits.into_iter().map(Into::into).collect();
+        let mut graph = self.revision_graph();
+        Ok(gix_revision::merge_base::independent(&commits, &mut graph)?
+            .into_iter()
+            .map(|id| id.attach(self))
+            .collect())
+    }
+
+    /// Merge the commit `name` resolves to into `HEAD` and the worktree, similar to what `git merge <name>` does,
+    /// using `options` to control fast-forwards and the message of the merge commit.
+    ///
+    /// `name` is looked up as reference first, and parsed as revision specification otherwise.
+    /// If `HEAD` can be fast-forwarded and `options` allow it, `HEAD` and the worktree are updated to the commit to merge.
+    /// Otherwise the trees are merged using the best common ancestor, and if there were no conflicts, a merge commit
+    /// is created with `HEAD` as first parent. If there are conflicts, the index and worktree contain them to be resolved,
+    /// and `MERGE_HEAD` and `MERGE_MSG` are written so the merge can be concluded by committing.
+    ///
+    /// `progress` is informed about the merge and the checkout of changed files, and the operation stops with an error
+    /// once `should_interrupt` is `true`.
+    ///
+    /// ### Limitations
+    ///
+    /// * The index and the worktree must not have any changes, even if they wouldn't be affected by the merge.
+    /// * Renames are not detected, and only one merge-base is used even if there are multiple.
+    pub fn merge<'a>(
+        &self,
+        name: impl Into<&'a BStr>,
+        options: merge::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<merge::Outcome, merge::Error> {
+        let name = name.into();
+        let _span = gix_trace::coarse!("gix::Repository::merge()", name = ?name);
+        let (theirs, description) = self.resolve_commit_to_merge(name)?;
+        self.merge_commit(theirs, &description, name, options, progress, should_interrupt)
+    }
+}
+
+/// Utilities for merging
+impl crate::Repository {
+    /// Merge the commit `theirs` into `HEAD` like [`merge()`](Self::merge()), using `description` and `name` in
+    /// the message of the merge commit.
+    pub(crate) fn merge_commit(
+        &self,
+        theirs: ObjectId,
+        description: &str,
+        name: &BStr,
+        options: merge::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<merge::Outcome, merge::Error> {
+        let workdir = self.work_dir().ok_or(merge::Error::BareRepository)?;
+        if self.git_dir().join("MERGE_HEAD").is_file() {
+            return Err(merge::Error::MergeInProgress);
+        }
+        let head = self.head()?;
+        let head_branch = head.referent_name().map(ToOwned::to_owned);
+        let fast_forward = self.fast_forward_or_configured(options.fast_forward)?;
+
+        let Some(ours) = head.id().map(Id::detach) else {
+            self.fast_forward(workdir, None, theirs, name, progress, should_interrupt)?;
+            return Ok(merge::Outcome::FastForward {
+                previous: None,
+                new: theirs,
+            });
+        };
+        let base = self
+            .merge_base(ours, theirs)?
+            .ok_or(merge::Error::UnrelatedHistories)?
+            .detach();
+        if base == theirs {
+            return Ok(merge::Outcome::UpToDate);
+        } else if base == ours && fast_forward != FastForward::Never {
+            self.fast_forward(workdir, Some(ours), theirs, name, progress, should_interrupt)?;
+            return Ok(merge::Outcome::FastForward {
+                previous: Some(ours),
+                new: theirs,
+            });
+        } else if fast_forward == FastForward::Only {
+            return Err(merge::Error::NotPossibleToFastForward);
+        }
+
+        let [base_tree, ours_tree, theirs_tree] = [base, ours, theirs].map(|id| self.tree_id_of(id));
+        let (base_tree, ours_tree, theirs_tree) = (base_tree?, ours_tree?, theirs_tree?);
+        let previous_index = self.index_if_clean(Some(ours_tree))?;
+        let base_label = base.attach(self).shorten_or_id().to_string();
+        let mut merged = self.merge_trees_to_index(
+            &base_tree,
+            &ours_tree,
+            &theirs_tree,
+            blob::Labels {
+                ancestor: Some(base_label.as_str().into()),
+                current: Some("HEAD".into()),
+                other: Some(name),
+            },
+            self.tree_merge_options()?,
+            progress,
+            should_interrupt,
+        )?;
+        self.update_worktree(
+            workdir,
+            &previous_index,
+            &mut merged.index,
+            &merged.conflicts,
+            progress,
+            should_interrupt,
+        )?;
+
+        self.set_orig_head(ours)?;
+        let message = match options.message {
+            Some(message) => message,
+            None => self.merge_message(description, name, head_branch.as_ref().map(AsRef::as_ref), ours, theirs)?,
+        };
+        if merged.has_conflicts() {
+            let mut message = message;
+            message.push_str("\n# Conflicts:\n");
+            for conflict in &merged.conflicts {
+                message.push_str(&format!("#\t{}\n", conflict.path));
+            }
+            std::fs::write(self.git_dir().join("MERGE_HEAD"), format!("{theirs}\n"))?;
+            std::fs::write(self.git_dir().join("MERGE_MSG"), message)?;
+            std::fs::write(self.git_dir().join("MERGE_MODE"), "")?;
+            return Ok(merge::Outcome::Conflict {
+                conflicts: merged.conflicts,
+            });
+        }
+
+        let tree = write_tree(self, &merged.index)?;
+        let commit = self.commit("HEAD", message, tree, [ours, theirs])?.detach();
+        Ok(merge::Outcome::Merge { commit })
+    }
+
+    /// Return `fast_forward` if set, or the value configured by `merge.ff`.
+    pub(crate) fn fast_forward_or_configured(
+        &self,
+        fast_forward: Option<FastForward>,
+    ) -> Result<FastForward, crate::config::key::GenericErrorWithValue> {
+        Ok(match fast_forward {
+            Some(fast_forward) => fast_forward,
+            None => self
+                .config
+                .resolved
+                .string(Merge::FF)
+                .map(|value| {
+                    Merge::FF
+                        .try_into_fast_forward(value)
+                        .with_lenient_default(self.config.lenient_config)
+                })
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Return the id of the commit `name` points to, along with its description for use in merge messages.
+    pub(crate) fn resolve_commit_to_merge(&self, name: &BStr) -> Result<(ObjectId, String), merge::Error> {
+        let (id, description) = match self.try_find_reference(name) {
+            Ok(Some(mut reference)) => {
+                let id = reference.peel_to_id_in_place()?;
+                let description = match reference.name().category_and_short_name() {
+                    Some((Category::LocalBranch, short_name)) => format!("branch '{short_name}'"),
+                    Some((Category::RemoteBranch, short_name)) => format!("remote-tracking branch '{short_name}'"),
+                    Some((Category::Tag, short_name)) => format!("tag '{short_name}'"),
+                    _ => format!("commit '{name}'"),
+                };
+                (id, description)
+            }
+            Ok(None) | Err(_) => (self.rev_parse_single(name)?, format!("commit '{name}'")),
+        };
+        let id = id.object()?.peel_to_kind(gix_object::Kind::Commit)?.id;
+        Ok((id, description))
+    }
+
+    pub(crate) fn tree_id_of(&self, commit: ObjectId) -> Result<ObjectId, merge::Error> {
+        Ok(self.find_object(commit)?.peel_to_tree()?.id)
+    }
+
+    /// Return the current index if it matches the tree at `head_tree`, or is empty if there is no such tree,
+    /// and if the worktree doesn't have any changes compared to it.
+    pub(crate) fn index_if_clean(&self, head_tree: Option<ObjectId>) -> Result<gix_index::File, merge::Error> {
+        let (index, has_local_changes) = self.index_and_local_changes(head_tree)?;
+        if has_local_changes {
+            return Err(merge::Error::LocalChanges);
+        }
+        Ok(index)
+    }
+
+    /// Return the current index along with `true` if it doesn't match the tree at `head_tree`, or isn't empty if there is no
+    /// such tree, or if the worktree has changes compared to it.
+    pub(crate) fn index_and_local_changes(
+        &self,
+        head_tree: Option<ObjectId>,
+    ) -> Result<(gix_index::File, bool), merge::Error> {
+        let index = self.index_or_empty()?;
+        let head_index = match head_tree {
+            Some(tree) => self.index_from_tree(&tree)?,
+            None => gix_index::File::from_state(gix_index::State::new(self.object_hash()), self.index_path()),
+        };
+        let index_matches_head = index.entries().len() == head_index.entries().len()
+            && index.entries().iter().zip(head_index.entries()).all(|(a, b)| {
+                a.id == b.id
+                    && a.mode == b.mode
+                    && a.flags.stage() == b.flags.stage()
+                    && a.path(&index) == b.path(&head_index)
+            });
+        let has_local_changes = !index_matches_head || self.is_dirty()?;
+        Ok((gix_index::File::clone(&index), has_local_changes))
+    }
+
+    /// Point `HEAD` from `previous` to `new` and update the worktree accordingly.
+    fn fast_forward(
+        &self,
+        workdir: &Path,
+        previous: Option<ObjectId>,
+        new: ObjectId,
+        name: &BStr,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), merge::Error> {
+        let previous_tree = previous.map(|id| self.tree_id_of(id)).transpose()?;
+        let previous_index = self.index_if_clean(previous_tree)?;
+        let mut index = self.index_from_tree(&self.tree_id_of(new)?)?;
+        self.update_worktree(workdir, &previous_index, &mut index, &[], progress, should_interrupt)?;
+        if let Some(previous) = previous {
+            self.set_orig_head(previous)?;
+        }
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("merge {name}: Fast-forward").into(),
+                },
+                expected: match previous {
+                    Some(previous) => PreviousValue::MustExistAndMatch(Target::Peeled(previous)),
+                    None => PreviousValue::MustNotExist,
+                },
+                new: Target::Peeled(new),
+            },
+            name: FullName::try_from("HEAD").expect("valid"),
+            deref: true,
+        })?;
+        Ok(())
+    }
+
+    /// Change the worktree which is currently at the state of `previous` to match `new`, and write `new` as the repository's index.
+    ///
+    /// Files of conflicting paths are written with conflict markers, or with our version if these aren't available.
+    /// `progress` is informed about checked out files, and nothing is written after the checkout if `should_interrupt` is `true`.
+    pub(crate) fn update_worktree(
+        &self,
+        workdir: &Path,
+        previous: &gix_index::File,
+        new: &mut gix_index::File,
+        conflicts: &[tree::Conflict],
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), merge::Error> {
+        use gix_index::entry::{Flags, Mode, Stage, Stat};
+
+        let mut options = self.config.checkout_options(
+            self,
+            gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+        )?;
+        // Paths of `previous` that only differ in case from a path in `new`, along with the new path.
+        let mut case_only_renames = Vec::<(BString, BString)>::new();
+        let mut to_remove = Vec::new();
+        {
+            let new_icase = options.fs.ignore_case.then(|| new.prepare_icase_backing());
+            for entry in previous.entries() {
+                let path = entry.path(previous);
+                if new.entry_index_by_path(path).is_ok() {
+                    continue;
+                }
+                if let Some(new_entry) = new_icase
+                    .as_ref()
+                    .and_then(|lookup| new.entry_by_path_icase(path, true, lookup))
+                {
+                    let new_path = new_entry.path(new);
+                    if previous.entry_index_by_path(new_path).is_err() {
+                        case_only_renames.push((path.to_owned(), new_path.to_owned()));
+                        continue;
+                    }
+                }
+                to_remove.push(path);
+            }
+        }
+
+        let is_tracked = |path: &BStr| {
+            previous.entry_index_by_path(path).is_ok() || case_only_renames.iter().any(|(_, new_path)| new_path == path)
+        };
+        let conflicting_paths = conflicts
+            .iter()
+            .filter(|c| !matches!(c.kind, tree::conflict::Kind::FileDirectory { .. }))
+            .map(|c| c.path.as_bstr());
+        let mut untracked: Vec<BString> = new
+            .entries()
+            .iter()
+            .filter(|e| e.stage() == Stage::Unconflicted)
+            .map(|e| e.path(new))
+            .chain(conflicting_paths)
+            .filter(|path| !is_tracked(path))
+            .filter_map(|path| untracked_in_the_way(workdir, path, previous))
+            .collect();
+        if !untracked.is_empty() {
+            untracked.sort();
+            untracked.dedup();
+            return Err(merge::Error::UntrackedFilesWouldBeOverwritten { paths: untracked });
+        }
+
+        for path in to_remove {
+            remove_file_and_empty_parents(workdir, &workdir.join(gix_path::from_bstr(path)))?;
+        }
+        rename_case_only(workdir, &mut case_only_renames)?;
+
+        let previous_entry = |path: &BStr| {
+            previous.entry_by_path_and_stage(path, Stage::Unconflicted).or_else(|| {
+                case_only_renames
+                    .iter()
+                    .find(|(_, new_path)| new_path == path)
+                    .and_then(|(previous_path, _)| {
+                        previous.entry_by_path_and_stage(previous_path.as_bstr(), Stage::Unconflicted)
+                    })
+            })
+        };
+        let is_unchanged = |path: &BStr, id: &gix_hash::oid, mode: Mode| matches!(previous_entry(path), Some(e) if e.id == id && e.mode == mode);
+        let mut to_checkout = gix_index::State::new(self.object_hash());
+        for entry in new.entries().iter().filter(|e| e.stage() == Stage::Unconflicted) {
+            let path = entry.path(new);
+            if !is_unchanged(path, &entry.id, entry.mode) {
+                to_checkout.dangerously_push_entry(Stat::default(), entry.id, Flags::empty(), entry.mode, path);
+            }
+        }
+        for conflict in conflicts {
+            if matches!(conflict.kind, tree::conflict::Kind::FileDirectory { .. }) {
+                continue;
+            }
+            let path = conflict.path.as_bstr();
+            let version = new
+                .entry_by_path_and_stage(path, Stage::Ours)
+                .or_else(|| new.entry_by_path_and_stage(path, Stage::Theirs))
+                .map(|entry| (conflict.merged_blob.unwrap_or(entry.id), entry.mode));
+            if let Some((id, mode)) = version.filter(|(id, mode)| !is_unchanged(path, id, *mode)) {
+                to_checkout.dangerously_push_entry(Stat::default(), id, Flags::empty(), mode, path);
+            }
+        }
+        to_checkout.sort_entries();
+
+        // Tracked files are clean and will be rewritten, but symlinks can't be written in place.
+        for entry in to_checkout.entries() {
+            let path = entry.path(&to_checkout);
+            if let Some(previous_entry) = previous_entry(path) {
+                if entry.mode == Mode::SYMLINK || previous_entry.mode == Mode::SYMLINK {
+                    remove_file_and_empty_parents(workdir, &workdir.join(gix_path::from_bstr(path)))?;
+                }
+            }
+        }
+
+        options.destination_is_initially_empty = false;
+        options.overwrite_existing = false;
+        let mut files = progress.add_child("checkout".into());
+        let mut bytes = progress.add_child("writing".into());
+        files.init(Some(to_checkout.entries().len()), crate::progress::count("files"));
+        bytes.init(None, crate::progress::bytes());
+        let outcome = gix_worktree_state::checkout(
+            &mut to_checkout,
+            workdir,
+            self.objects.clone().into_arc()?,
+            &files,
+            &bytes,
+            should_interrupt,
+            options,
+        )?;
+        if should_interrupt.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(merge::Error::Interrupted);
+        }
+        if !outcome.collisions.is_empty() {
+            return Err(merge::Error::UntrackedFilesWouldBeOverwritten {
+                paths: outcome.collisions.into_iter().map(|c| c.path).collect(),
+            });
+        }
+
+        for (state, entries) in [(&**previous, previous.entries()), (&to_checkout, to_checkout.entries())] {
+            for entry in entries {
+                let path = entry.path(state);
+                let path = case_only_renames
+                    .iter()
+                    .find(|(previous_path, _)| std::ptr::eq(state, &**previous) && previous_path == path)
+                    .map_or(path, |(_, new_path)| new_path.as_bstr());
+                if let Some(new_entry) = new.entry_mut_by_path_and_stage(path, Stage::Unconflicted) {
+                    if new_entry.id == entry.id && new_entry.mode == entry.mode {
+                        new_entry.stat = entry.stat;
+                    }
+                }
+            }
+        }
+        new.write(Default::default())?;
+        Ok(())
+    }
+
+    /// Generate the message for merging `theirs` into `ours`, the commit `HEAD` points to, just like `git` would.
+    fn merge_message(
+        &self,
+        description: &str,
+        name: &BStr,
+        head_branch: Option<&FullNameRef>,
+        ours: ObjectId,
+        theirs: ObjectId,
+    ) -> Result<String, merge::Error> {
+        let mut message = format!("Merge {description}");
+        if let Some((Category::LocalBranch, branch)) = head_branch.and_then(FullNameRef::category_and_short_name) {
+            if branch != "main" && branch != "master" {
+                message.push_str(&format!(" into {branch}"));
+            }
+        }
+        message.push('\n');
+
+        let limit = self
+            .config
+            .resolved
+            .string(Merge::LOG)
+            .map(|value| {
+                Merge::LOG
+                    .try_into_shortlog_limit(value)
+                    .with_lenient_default(self.config.lenient_config)
+            })
+            .transpose()?
+            .flatten();
+        let Some(limit) = limit else {
+            return Ok(message);
+        };
+        let hidden = self
+            .rev_walk([ours])
+            .all()?
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<HashSet<_>, _>>()?;
+        let mut count = 0;
+        let mut subjects = Vec::new();
+        for info in self
+            .rev_walk([theirs])
+            .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+            .selected(move |id| !hidden.contains(id))?
+        {
+            let info = info?;
+            if info.parent_ids().count() > 1 {
+                continue;
+            }
+            count += 1;
+            if subjects.len() < limit {
+                subjects.push(info.object()?.message()?.summary().into_owned());
+            }
+        }
+        if count == 0 {
+            return Ok(message);
+        }
+        if count > limit {
+            message.push_str(&format!("\n* {name}: ({count} commits)\n"));
+        } else {
+            message.push_str(&format!("\n* {name}:\n"));
+        }
+        for subject in subjects {
+            message.push_str(&format!("  {subject}\n"));
+        }
+        if count > limit {
+            message.push_str("  ...\n");
+        }
+        Ok(message)
+    }
+}
+
+/// Write a tree from all entries of `index`, which must not have conflicts, and return its id.
+pub(crate) fn write_tree(repo: &crate::Repository, index: &gix_index::State) -> Result<ObjectId, merge::Error> {
+    Ok(write_tree_with(index, &mut |tree| {
+        Ok(repo.write_object(tree)?.detach())
+    })?)
+}
+
+/// Like [`write_tree()`], but use `write` to write each tree and obtain its id.
+pub(crate) fn write_tree_with(
+    index: &gix_index::State,
+    write: &mut dyn FnMut(&gix_object::Tree) -> Result<ObjectId, crate::object::write::Error>,
+) -> Result<ObjectId, crate::object::write::Error> {
+    let entries: Vec<_> = index
+        .entries()
+        .iter()
+        .map(|entry| {
+            (
+                entry.path(index),
+                entry
+                    .mode
+                    .to_tree_entry_mode()
+                    .expect("merged entries are valid tree entries"),
+                entry.id,
+            )
+        })
+        .collect();
+    write_tree_inner(write, &entries)
+}
+
+fn write_tree_inner(
+    write: &mut dyn FnMut(&gix_object::Tree) -> Result<ObjectId, crate::object::write::Error>,
+    entries: &[(&BStr, EntryMode, ObjectId)],
+) -> Result<ObjectId, crate::object::write::Error> {
+    let mut tree = gix_object::Tree::empty();
+    let mut remaining = entries;
+    while let Some((path, mode, id)) = remaining.first().copied() {
+        match path.find_byte(b'/') {
+            None => {
+                tree.entries.push(gix_object::tree::Entry {
+                    mode,
+                    filename: path.to_owned(),
+                    oid: id,
+                });
+                remaining = &remaining[1..];
+            }
+            Some(pos) => {
+                let dir = &path[..pos];
+                let len = remaining
+                    .iter()
+                    .take_while(|(path, _, _)| path.len() > pos && path[..pos] == *dir && path[pos] == b'/')
+                    .count();
+                let children: Vec<_> = remaining[..len]
+                    .iter()
+                    .map(|(path, mode, id)| (path[pos + 1..].as_bstr(), *mode, *id))
+                    .collect();
+                tree.entries.push(gix_object::tree::Entry {
+                    mode: gix_object::tree::EntryKind::Tree.into(),
+                    filename: dir.into(),
+                    oid: write_tree_inner(write, &children)?,
+                });
+                remaining = &remaining[len..];
+            }
+        }
+    }
+    tree.entries.sort();
+    write(&tree)
+}
+
+/// Remove `file` if it exists, along with all of its parent directories up to `workdir` that are empty afterwards.
+/// Return `path` or one of its leading directories if it's in the way of checking out `path` in `workdir`,
+/// without being tracked in `previous`.
+fn untracked_in_the_way(workdir: &Path, path: &BStr, previous: &gix_index::File) -> Option<BString> {
+    let is_tracked_dir = |dir: &[u8]| {
+        previous.entries().iter().any(|e| {
+            e.path(previous)
+                .strip_prefix(dir)
+                .map_or(false, |rest| rest.starts_with(b"/"))
+        })
+    };
+    let components = path.split(|b| *b == b'/').count();
+    for (idx, end) in path
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, b)| (*b == b'/').then_some(idx))
+        .chain(Some(path.len()))
+        .enumerate()
+    {
+        let candidate = &path[..end];
+        let Ok(meta) = std::fs::symlink_metadata(workdir.join(gix_path::from_bstr(candidate))) else {
+            return None;
+        };
+        let is_leaf = idx + 1 == components;
+        if meta.is_dir() {
+            if is_leaf && !is_tracked_dir(candidate) {
+                return Some(candidate.into());
+            }
+        } else if is_leaf || previous.entry_index_by_path(candidate.as_bstr()).is_err() {
+            return Some(candidate.into());
+        }
+    }
+    None
+}
+
+fn remove_file_and_empty_parents(workdir: &Path, file: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(file) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    for dir in file.ancestors().skip(1).take_while(|dir| *dir != workdir) {
+        if std::fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Apply `renames` of `(previous, new)` paths which only differ in case, on a case-insensitive filesystem.
+///
+/// As `previous` and `new` refer to the same file, all files are first moved to a temporary location, which
+/// also allows parent directories which changed case to be removed, before they are moved to their new location.
+/// Renames of files that don't exist are removed from `renames`.
+fn rename_case_only(workdir: &Path, renames: &mut Vec<(BString, BString)>) -> std::io::Result<()> {
+    let temporary_path = |idx: usize| workdir.join(format!(".gix-case-rename-{}-{idx}", std::process::id()));
+    let mut moved = Vec::with_capacity(renames.len());
+    for (previous, new) in renames.drain(..) {
+        match std::fs::rename(
+            workdir.join(gix_path::from_bstr(previous.as_bstr())),
+            temporary_path(moved.len()),
+        ) {
+            Ok(()) => moved.push((previous, new)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    *renames = moved;
+    for (previous, _) in renames.iter() {
+        remove_file_and_empty_parents(workdir, &workdir.join(gix_path::from_bstr(previous.as_bstr())))?;
+    }
+    for (idx, (_, new)) in renames.iter().enumerate() {
+        let new = workdir.join(gix_path::from_bstr(new.as_bstr()));
+        if let Some(dir) = new.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::rename(temporary_path(idx), new)?;
+    }
+    Ok(())
+}
@@ -17,6 +17,61 @@ pub enum Kind {
     },
 }
 
+/// The approximate amount of memory used by a repository and all of its clones, as returned by
+/// [`Repository::memory_usage()`](crate::Repository::memory_usage()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    /// The amount of bytes held by all pack caches, which keep decoded delta-bases to speed up object decoding.
+    pub pack_caches: usize,
+    /// The amount of bytes all pack caches may hold together before they are cleared, or `None` if there is no limit.
+    pub pack_cache_soft_limit: Option<usize>,
+    /// The amount of bytes held by all object caches, which keep entire decoded objects.
+    pub object_caches: usize,
+    /// The amount of bytes all object caches may hold together before they are cleared, or `None` if there is no limit.
+    pub object_cache_soft_limit: Option<usize>,
+    /// The amount of times a cache was cleared because its soft limit was exceeded.
+    pub evictions: usize,
+    /// The amount of bytes of all memory-mapped pack and index files of the object database, which is shared
+    /// by all repositories that use the same object database.
+    ///
+    /// Note that the operating system decides how much of it is actually resident in memory.
+    pub mapped_bytes: u64,
+}
+
+/// Information about the object database of a repository, as returned by
+/// [`Repository::object_database_stats()`](crate::Repository::object_database_stats()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectDatabaseStats {
+    /// The amount of packs known to the object database, including those of alternates and those listed in multi-pack indices.
+    pub packs: usize,
+    /// The amount of multi-pack indices known to the object database, including those of alternates.
+    pub multi_pack_indices: usize,
+    /// The amount of loose objects in the object database of the repository, without those of alternates.
+    pub loose_objects: usize,
+    /// The `objects` directories of all alternate object databases, in the order they are searched.
+    pub alternates: Vec<std::path::PathBuf>,
+    /// If `true`, there is at least one reachability bitmap for a pack or a multi-pack index in the object database
+    /// of the repository.
+    ///
+    /// Note that these are written and used by `git`, but not by `gitoxide`.
+    pub has_bitmaps: bool,
+    /// How often pack caches were able to provide decoded delta-bases.
+    pub pack_cache: CacheStats,
+    /// How often object caches were able to provide entire objects.
+    pub object_cache: CacheStats,
+    /// Metrics about the state of the object database, shared by all repositories that use it.
+    pub metrics: gix_odb::store::Metrics,
+}
+
+/// The amount of lookups in all caches of one kind used by a repository and all of its clones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// The amount of lookups that found an entry.
+    pub hits: usize,
+    /// The amount of lookups that didn't find an entry.
+    pub misses: usize,
+}
+
 /// Internal
 impl crate::Repository {
     #[inline]
@@ -53,6 +108,7 @@ mod dirwalk;
 #[cfg(feature = "attributes")]
 pub mod filter;
 mod graph;
+mod hooks;
 pub(crate) mod identity;
 mod impls;
 #[cfg(feature = "index")]
@@ -62,13 +118,21 @@ mod kind;
 mod location;
 #[cfg(feature = "mailmap")]
 mod mailmap;
+#[cfg(feature = "merge")]
+pub(crate) mod merge;
 mod object;
 #[cfg(feature = "attributes")]
 mod pathspec;
+#[cfg(all(feature = "rebase", feature = "blocking-network-client"))]
+mod pull;
+#[cfg(feature = "rebase")]
+mod rebase;
 mod reference;
 mod remote;
 #[cfg(feature = "revision")]
 mod revision;
+#[cfg(feature = "merge")]
+mod sandbox;
 mod shallow;
 mod state;
 #[cfg(feature = "attributes")]
@@ -94,6 +158,91 @@ pub mod index_from_tree {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "merge")]
+pub mod merge_base {
+    /// The error returned by [Repository::merge_base()](crate::Repository::merge_base()) and related methods.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod object_database_stats {
+    /// The error returned by [Repository::object_database_stats()](crate::Repository::object_database_stats()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        LoadIndex(#[from] gix_odb::store::load_index::Error),
+        #[error("Could not read the pack directory to find bitmaps")]
+        ReadPackDirectory(#[from] std::io::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod enable_worktree_config {
+    /// The error returned by [Repository::enable_worktree_config()](crate::Repository::enable_worktree_config()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Prepare(#[from] gix_config::file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_config::file::transaction::commit::Error),
+        #[error(transparent)]
+        ConfigBoolean(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        ConfigUnsignedInteger(#[from] crate::config::unsigned_integer::Error),
+        #[error(transparent)]
+        SetValue(#[from] gix_config::file::set_raw_value::Error),
+        #[error("Could not reload the configuration after enabling worktree configuration")]
+        Reload(#[from] crate::config::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod edit_worktree_config {
+    /// The error returned by [Repository::edit_worktree_config()](crate::Repository::edit_worktree_config()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Worktree configuration must be enabled with extensions.worktreeConfig if there are linked worktrees")]
+        NotEnabled,
+        #[error(transparent)]
+        Prepare(#[from] gix_config::file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_config::file::transaction::commit::Error),
+        #[error(transparent)]
+        ConfigBoolean(#[from] crate::config::boolean::Error),
+        #[error("Could not list linked worktrees")]
+        ListWorktrees(#[from] std::io::Error),
+        #[error("Could not reload the configuration after editing it")]
+        Reload(#[from] crate::config::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod fetch_head {
+    /// The error returned by [Repository::fetch_head()](crate::Repository::fetch_head()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read FETCH_HEAD")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Parse(#[from] gix_fetchhead::parse::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod branch_remote_ref_name {
@@ -142,6 +291,19 @@ pub mod pathspec_defaults_ignore_case {
     }
 }
 
+///
+pub mod resolve_identity {
+    /// The error returned by [`Repository::resolve_identity()`][crate::Repository::resolve_identity()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        UseConfigOnly(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        Resolve(#[from] gix_actor::resolve::Error),
+    }
+}
+
 ///
 #[cfg(feature = "index")]
 pub mod index_or_load_from_head {
@@ -163,6 +325,8 @@ pub mod index_or_load_from_head {
 ///
 #[cfg(feature = "worktree-stream")]
 pub mod worktree_stream {
+    use gix_object::bstr::BString;
+
     /// The error returned by [`Repository::worktree_stream()`][crate::Repository::worktree_stream()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
@@ -182,6 +346,28 @@ pub mod worktree_stream {
             id: gix_hash::ObjectId,
             actual: gix_object::Kind,
         },
+        #[error(transparent)]
+        Submodules(#[from] crate::submodule::modules::Error),
+        #[error("Could not determine if the submodule at '{path}' is active")]
+        SubmoduleIsActive {
+            path: BString,
+            source: crate::submodule::is_active::Error,
+        },
+        #[error("Could not open the repository of the submodule at '{path}'")]
+        SubmoduleOpen {
+            path: BString,
+            source: crate::submodule::open::Error,
+        },
+        #[error("The submodule at '{path}' is active but wasn't initialized")]
+        SubmoduleUninitialized { path: BString },
+        #[error("Could not obtain the tree of commit {id} in the submodule at '{path}'")]
+        SubmoduleTree {
+            path: BString,
+            id: gix_hash::ObjectId,
+            source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        },
+        #[error("Could not stream the worktree of the submodule at '{path}'")]
+        Submodule { path: BString, source: Box<Error> },
     }
 }
 
@@ -56,6 +56,22 @@ impl crate::Repository {
         self.objects.header(id)
     }
 
+    /// Return the size of the object with `id` in bytes as it would be after decompression, or fail if the object doesn't exist.
+    ///
+    /// Only the header of loose objects is decompressed, and for packed objects only the entry headers of a delta-chain
+    /// are read, so the object itself is never materialized. This makes it suitable for checking object sizes against limits.
+    #[momo]
+    pub fn object_size(&self, id: impl Into<ObjectId>) -> Result<u64, object::find::existing::Error> {
+        self.find_header(id).map(|header| header.size())
+    }
+
+    /// Like [`Self::object_size()`], but returns `None` if the object doesn't exist.
+    #[momo]
+    pub fn try_object_size(&self, id: impl Into<ObjectId>) -> Result<Option<u64>, object::find::Error> {
+        self.try_find_header(id)
+            .map(|header| header.map(|header| header.size()))
+    }
+
     /// Return `true` if `id` exists in the object database.
     ///
     /// # Performance
@@ -265,7 +281,8 @@ impl crate::Repository {
 
         // TODO: possibly use CommitRef to save a few allocations (but will have to allocate for object ids anyway.
         //       This can be made vastly more efficient though if we wanted to, so we lie in the API
-        let commit = gix_object::Commit {
+        #[cfg_attr(not(feature = "attributes"), allow(unused_mut))]
+        let mut commit = gix_object::Commit {
             message: message.into(),
             tree,
             author: author.into(),
@@ -274,6 +291,16 @@ impl crate::Repository {
             parents,
             extra_headers: Default::default(),
         };
+        #[cfg(feature = "attributes")]
+        if let Some(name) = self.config.resolved.string("i18n.commitEncoding") {
+            let encoding = crate::config::tree::I18n::COMMIT_ENCODING.try_into_encoding(name.as_ref().into())?;
+            if !encoding.is_utf8() {
+                let mut buf = Vec::new();
+                gix_filter::worktree::encode_to_worktree(message.as_bytes(), encoding, &mut buf)?;
+                commit.message = buf.into();
+                commit.encoding = Some(name.into_owned());
+            }
+        }
 
         let commit_id = self.write_object(&commit)?;
         self.edit_reference(RefEdit {
@@ -281,7 +308,7 @@ impl crate::Repository {
                 log: LogChange {
                     mode: RefLog::AndReference,
                     force_create_reflog: false,
-                    message: crate::reference::log::message("commit", commit.message.as_ref(), commit.parents.len()),
+                    message: crate::reference::log::message("commit", message.into(), commit.parents.len()),
                 },
                 expected: match commit.parents.first().map(|p| Target::Peeled(*p)) {
                     Some(previous) => {
@@ -302,7 +329,8 @@ impl crate::Repository {
     }
 
     /// Create a new commit object with `message` referring to `tree` with `parents`, and point `reference`
-    /// to it. The commit is written without message encoding field, which can be assumed to be UTF-8.
+    /// to it. The commit is written without message encoding field, which can be assumed to be UTF-8, unless `i18n.commitEncoding`
+    /// names another encoding. Then `message` is converted to that encoding, which is recorded in the `encoding` header of the commit.
     /// `author` and `committer` fields are pre-set from the configuration, which can be altered
     /// [temporarily][crate::Repository::config_snapshot_mut()] before the call if required.
     ///
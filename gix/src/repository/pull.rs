@@ -0,0 +1,127 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Branch, Pull},
+    },
+    merge, pull, rebase,
+    remote::{self, fetch::RefLogMessage},
+};
+
+/// Pulling
+impl crate::Repository {
+    /// Fetch from the remote named `remote`, or the one configured for the current branch if `None`, and integrate
+    /// the upstream branch of `HEAD` into it, similar to what `git pull` does.
+    ///
+    /// The upstream branch is the one configured in `branch.<name>.merge`, and it's fetched even if the refspecs of the remote
+    /// don't include it. `progress` and `should_interrupt` are used while fetching and integrating.
+    ///
+    /// Depending on `options`, `branch.<name>.rebase` and `pull.rebase`, `HEAD` is then [rebased](Self::rebase()) onto the
+    /// fetched commit, or the fetched commit is [merged](Self::merge()) into `HEAD` with the fast-forward policy configured
+    /// in `pull.ff` or `merge.ff`.
+    pub fn pull<P>(
+        &self,
+        remote: Option<&BStr>,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: pull::Options,
+    ) -> Result<pull::Outcome, pull::Error>
+    where
+        P: gix_features::progress::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_trace::coarse!("gix::Repository::pull()", remote = ?remote);
+        let head = self.head()?;
+        let branch = head.referent_name().ok_or(pull::Error::DetachedHead)?;
+        let upstream = self
+            .branch_remote_ref_name(branch, remote::Direction::Fetch)
+            .ok_or_else(|| pull::Error::NoUpstream {
+                branch: branch.shorten().to_owned(),
+            })??;
+        let remote = self.find_fetch_remote(remote)?;
+        let upstream_refspec = gix_refspec::parse(upstream.as_bstr(), gix_refspec::parse::Operation::Fetch)
+            .expect("full reference names are valid refspecs")
+            .to_owned();
+        let fetch = remote
+            .connect(remote::Direction::Fetch)?
+            .prepare_fetch(
+                &mut progress,
+                remote::ref_map::Options {
+                    extra_refspecs: vec![upstream_refspec],
+                    ..Default::default()
+                },
+            )?
+            .with_reflog_message(RefLogMessage::Prefixed { action: "pull".into() })
+            .receive(&mut progress, should_interrupt)?;
+        let upstream_id = fetch
+            .ref_map
+            .mappings
+            .iter()
+            .find(|mapping| mapping.remote.as_name() == Some(upstream.as_bstr()))
+            .and_then(|mapping| mapping.remote.peeled_id())
+            .ok_or_else(|| pull::Error::UpstreamNotFound {
+                name: upstream.as_bstr().to_owned(),
+            })?
+            .to_owned();
+
+        let short_branch = branch.shorten();
+        let config = &self.config.resolved;
+        let lenient = self.config.lenient_config;
+        let should_rebase = match options.rebase {
+            Some(rebase) => rebase,
+            None => config
+                .boolean_by("branch", Some(short_branch), Branch::REBASE.name)
+                .map(|res| Branch::REBASE.enrich_error(res))
+                .or_else(|| config.boolean(Pull::REBASE).map(|res| Pull::REBASE.enrich_error(res)))
+                .transpose()
+                .with_leniency(lenient)?
+                .unwrap_or_default(),
+        };
+        let integration = if should_rebase {
+            pull::Integration::Rebase(self.rebase(
+                upstream_id.to_hex().to_string().as_str(),
+                rebase::Options {
+                    onto: None,
+                    autostash: options.autostash,
+                },
+                &mut progress,
+                should_interrupt,
+            )?)
+        } else {
+            let fast_forward = match options.fast_forward {
+                Some(fast_forward) => Some(fast_forward),
+                None => config
+                    .string(Pull::FF)
+                    .map(|value| Pull::FF.try_into_fast_forward(value).map(Some).with_leniency(lenient))
+                    .transpose()?
+                    .flatten(),
+            };
+            let description = gix_fetchhead::description(
+                upstream.as_bstr(),
+                remote
+                    .url(remote::Direction::Fetch)
+                    .map(remote::fetch::fetch_head::anonymized_url)
+                    .unwrap_or_default()
+                    .as_ref(),
+            );
+            pull::Integration::Merge(self.merge_commit(
+                upstream_id,
+                &description.to_str_lossy(),
+                upstream.shorten(),
+                merge::Options {
+                    fast_forward,
+                    message: None,
+                },
+                &mut progress,
+                should_interrupt,
+            )?)
+        };
+        Ok(pull::Outcome {
+            fetch,
+            upstream: upstream_id,
+            integration,
+        })
+    }
+}
@@ -0,0 +1,729 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    io::Read,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_features::progress::DynNestedProgress;
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    config::{cache::util::ApplyLeniency, tree::Rebase},
+    ext::ObjectIdExt,
+    merge::blob,
+    rebase,
+    repository::merge::write_tree,
+};
+
+/// Rebasing
+impl crate::Repository {
+    /// Rebase all commits of `HEAD` that aren't contained in the commit `upstream` resolves to onto that commit, or onto
+    /// [`Options::onto`](rebase::Options::onto) if set, similar to what `git rebase <upstream>` does.
+    ///
+    /// `upstream` is looked up as reference first, and parsed as revision specification otherwise.
+    /// The commits are applied one by one in the order they were created, skipping merge commits as well as commits whose
+    /// changes are already present. If `HEAD` was pointing to a branch, it is updated to the last rebased commit at the end.
+    ///
+    /// If a commit can't be applied without conflicts, the rebase stops so the conflicts can be resolved in the index and
+    /// worktree, to be [continued](Self::rebase_continue()) or [aborted](Self::rebase_abort()) later.
+    /// Its state is kept in `.git/rebase-merge` just like `git` does it, so `git rebase --continue` can be used as well.
+    ///
+    /// If there are local changes, they are stashed and applied once the rebase is complete if `options` or `rebase.autoStash`
+    /// allow it, or the rebase fails otherwise.
+    ///
+    /// `progress` is informed about merges and checkouts, and the rebase stops with an error once `should_interrupt` is `true`,
+    /// leaving its state so it can be [aborted](Self::rebase_abort()).
+    ///
+    /// ### Limitations
+    ///
+    /// * Commits whose changes are already contained in `upstream` are only skipped once they turn out to have no changes
+    ///   after being applied, instead of detecting them upfront.
+    pub fn rebase<'a>(
+        &self,
+        upstream: impl Into<&'a BStr>,
+        options: rebase::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<rebase::Outcome, rebase::Error> {
+        let upstream_name = upstream.into();
+        let _span = gix_trace::coarse!("gix::Repository::rebase()", upstream = ?upstream_name);
+        let workdir = self.work_dir().ok_or(rebase::Error::BareRepository)?;
+        if let Some(in_progress) = self.state() {
+            return Err(rebase::Error::OperationInProgress(in_progress));
+        }
+        let head = self.head()?;
+        let orig_head = head.id().ok_or(rebase::Error::UnbornHead)?.detach();
+        let head_name = head.referent_name().map(ToOwned::to_owned);
+        let (upstream, _) = self.resolve_commit_to_merge(upstream_name)?;
+        let onto = options.onto.unwrap_or(upstream);
+        let base = self
+            .merge_base(orig_head, upstream)?
+            .ok_or(rebase::Error::UnrelatedHistories)?
+            .detach();
+        if base == onto {
+            return Ok(rebase::Outcome::UpToDate);
+        }
+
+        let autostash = match options.autostash {
+            Some(autostash) => autostash,
+            None => self
+                .config
+                .resolved
+                .boolean(Rebase::AUTO_STASH)
+                .map(|res| Rebase::AUTO_STASH.enrich_error(res))
+                .transpose()
+                .with_leniency(self.config.lenient_config)?
+                .unwrap_or_default(),
+        };
+        let (mut index, has_local_changes) = self.index_and_local_changes(Some(self.tree_id_of(orig_head)?))?;
+        let stash = if has_local_changes {
+            if !autostash {
+                return Err(rebase::Error::LocalChanges);
+            }
+            let (stash, index_to_reset) = self.create_autostash(workdir, orig_head, head_name.as_ref(), index)?;
+            index = index_to_reset;
+            Some(stash)
+        } else {
+            None
+        };
+
+        let state = State {
+            dir: self.git_dir().join("rebase-merge"),
+            head_name,
+            onto,
+            orig_head,
+            todo: self.commits_to_rebase(orig_head, [upstream, onto])?.into(),
+            done: Vec::new(),
+            autostash: stash,
+        };
+        state.write()?;
+        let mut onto_index = self.index_from_tree(&self.tree_id_of(onto)?)?;
+        self.update_worktree(workdir, &index, &mut onto_index, &[], progress, should_interrupt)?;
+        self.set_orig_head(orig_head)?;
+        self.set_head(
+            Target::Peeled(onto),
+            format!("rebase (start): checkout {upstream_name}"),
+        )?;
+        self.rebase_run(workdir, state, progress, should_interrupt)
+    }
+
+    /// Continue the rebase in progress after all conflicts were resolved and the resolution was added to the index,
+    /// similar to `git rebase --continue`.
+    ///
+    /// The commit that couldn't be applied is created from the index unless it doesn't contain any changes, in which case
+    /// the commit is skipped, before the remaining commits are applied, using `progress` and `should_interrupt`
+    /// like [`rebase()`](Self::rebase()).
+    pub fn rebase_continue(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<rebase::Outcome, rebase::Error> {
+        let _span = gix_trace::coarse!("gix::Repository::rebase_continue()");
+        let workdir = self.work_dir().ok_or(rebase::Error::BareRepository)?;
+        let state = self.read_rebase_state()?;
+        let index = self.open_index()?;
+        if index
+            .entries()
+            .iter()
+            .any(|entry| entry.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(rebase::Error::UnresolvedConflicts);
+        }
+        if self.is_dirty().map_err(crate::merge::Error::from)? {
+            return Err(rebase::Error::LocalChanges);
+        }
+
+        if state.dir.join("stopped-sha").is_file() {
+            let head = self.head_id()?.detach();
+            let tree = write_tree(self, &index)?;
+            if tree != self.tree_id_of(head)? {
+                let message = std::fs::read(state.dir.join("message"))?;
+                let author = parse_author_script(&std::fs::read(state.dir.join("author-script"))?)
+                    .ok_or(rebase::Error::InvalidState { name: "author-script" })?;
+                let summary = gix_object::commit::MessageRef::from_bytes(&message)
+                    .summary()
+                    .into_owned();
+                let commit = self.write_rebased_commit(author, message.into(), None, tree, head)?;
+                self.set_head(Target::Peeled(commit), format!("rebase (continue): {summary}"))?;
+            }
+            for name in ["stopped-sha", "message", "author-script"] {
+                remove_file_if_present(&state.dir.join(name))?;
+            }
+            self.remove_stop_files()?;
+        }
+        self.rebase_run(workdir, state, progress, should_interrupt)
+    }
+
+    /// Abort the rebase in progress and return to the commit and branch `HEAD` pointed to before it started,
+    /// similar to `git rebase --abort`, while resetting the index and worktree accordingly.
+    ///
+    /// Local changes that were stashed before the rebase are applied again, with the outcome being returned.
+    /// `progress` is informed about the checkout, which stops with an error once `should_interrupt` is `true`.
+    pub fn rebase_abort(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Option<rebase::Autostash>, rebase::Error> {
+        let _span = gix_trace::coarse!("gix::Repository::rebase_abort()");
+        let workdir = self.work_dir().ok_or(rebase::Error::BareRepository)?;
+        let state = self.read_rebase_state()?;
+        let index = self.open_index()?;
+        let mut orig_index = self.index_from_tree(&self.tree_id_of(state.orig_head)?)?;
+        self.update_worktree(workdir, &index, &mut orig_index, &[], progress, should_interrupt)?;
+        match &state.head_name {
+            Some(name) => self.set_head(
+                Target::Symbolic(name.clone()),
+                format!("rebase (abort): returning to {name}"),
+            )?,
+            None => self.set_head(
+                Target::Peeled(state.orig_head),
+                format!("rebase (abort): returning to {}", state.orig_head),
+            )?,
+        }
+        std::fs::remove_dir_all(&state.dir)?;
+        self.remove_stop_files()?;
+        state
+            .autostash
+            .map(|stash| self.apply_autostash(workdir, stash, progress, should_interrupt))
+            .transpose()
+    }
+}
+
+/// Utilities for rebasing
+impl crate::Repository {
+    /// Apply all remaining commits of `state` and finish the rebase, or stop at the first commit with conflicts.
+    fn rebase_run(
+        &self,
+        workdir: &Path,
+        mut state: State,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<rebase::Outcome, rebase::Error> {
+        let mut index = self.open_index()?;
+        let tree_merge_options = self.tree_merge_options()?;
+        while let Some(pick) = state.todo.pop_front() {
+            state.done.push(pick.clone());
+            state.write()?;
+
+            let commit = self
+                .find_object(pick.id)?
+                .try_into_commit()
+                .map_err(|_| rebase::Error::InvalidState {
+                    name: "git-rebase-todo",
+                })?;
+            let head = self.head_id()?.detach();
+            let head_tree = self.tree_id_of(head)?;
+            let parent_tree = match commit.parent_ids().next() {
+                Some(parent) => self.tree_id_of(parent.detach())?,
+                None => self.write_object(gix_object::Tree::empty())?.detach(),
+            };
+            let other = format!("{} ({})", pick.id.attach(self).shorten_or_id(), pick.subject);
+            let ancestor = format!("parent of {other}");
+            let mut merged = self.merge_trees_to_index(
+                &parent_tree,
+                &head_tree,
+                &commit.tree_id()?,
+                blob::Labels {
+                    ancestor: Some(ancestor.as_str().into()),
+                    current: Some("HEAD".into()),
+                    other: Some(other.as_str().into()),
+                },
+                tree_merge_options,
+                progress,
+                should_interrupt,
+            )?;
+            self.update_worktree(
+                workdir,
+                &index,
+                &mut merged.index,
+                &merged.conflicts,
+                progress,
+                should_interrupt,
+            )?;
+            index = merged.index;
+
+            let decoded = commit.decode()?;
+            if !merged.conflicts.is_empty() {
+                let mut message = decoded.message.to_owned();
+                std::fs::write(state.dir.join("message"), &message)?;
+                std::fs::write(state.dir.join("author-script"), author_script(decoded.author()))?;
+                std::fs::write(state.dir.join("stopped-sha"), format!("{}\n", pick.id))?;
+                std::fs::write(self.git_dir().join("REBASE_HEAD"), format!("{}\n", pick.id))?;
+                message.extend_from_slice(b"\n# Conflicts:\n");
+                for conflict in &merged.conflicts {
+                    message.extend_from_slice(format!("#\t{}\n", conflict.path).as_bytes());
+                }
+                std::fs::write(self.git_dir().join("MERGE_MSG"), message)?;
+                return Ok(rebase::Outcome::Conflict {
+                    commit: pick.id,
+                    conflicts: merged.conflicts,
+                });
+            }
+
+            let tree = write_tree(self, &index)?;
+            if tree == head_tree {
+                continue;
+            }
+            let new = self.write_rebased_commit(
+                decoded.author().to_owned(),
+                decoded.message.to_owned(),
+                decoded.encoding.map(ToOwned::to_owned),
+                tree,
+                head,
+            )?;
+            self.set_head(Target::Peeled(new), format!("rebase (pick): {}", pick.subject))?;
+        }
+
+        let new = self.head_id()?.detach();
+        if let Some(name) = &state.head_name {
+            self.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: format!("rebase (finish): {name} onto {}", state.onto).into(),
+                    },
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(state.orig_head)),
+                    new: Target::Peeled(new),
+                },
+                name: name.clone(),
+                deref: false,
+            })?;
+            self.set_head(
+                Target::Symbolic(name.clone()),
+                format!("rebase (finish): returning to {name}"),
+            )?;
+        }
+        std::fs::remove_dir_all(&state.dir)?;
+        let autostash = state
+            .autostash
+            .map(|stash| self.apply_autostash(workdir, stash, progress, should_interrupt))
+            .transpose()?;
+        Ok(rebase::Outcome::Complete {
+            previous: state.orig_head,
+            new,
+            autostash,
+        })
+    }
+
+    /// Return all commits that are reachable from `head` but not from any of `hidden_tips`, without merge commits, and with the
+    /// oldest commit first.
+    fn commits_to_rebase(
+        &self,
+        head: ObjectId,
+        hidden_tips: impl IntoIterator<Item = ObjectId>,
+    ) -> Result<Vec<Pick>, rebase::Error> {
+        let hidden = self
+            .rev_walk(hidden_tips)
+            .all()?
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<HashSet<_>, _>>()?;
+        let mut picks = Vec::new();
+        for info in self.rev_walk([head]).selected(move |id| !hidden.contains(id))? {
+            let info = info?;
+            if info.parent_ids().count() > 1 {
+                continue;
+            }
+            let subject = info.object()?.message()?.summary().into_owned();
+            picks.push(Pick { id: info.id, subject });
+        }
+        picks.reverse();
+        Ok(picks)
+    }
+
+    fn write_rebased_commit(
+        &self,
+        author: gix_actor::Signature,
+        message: BString,
+        encoding: Option<BString>,
+        tree: ObjectId,
+        parent: ObjectId,
+    ) -> Result<ObjectId, rebase::Error> {
+        let committer = self.committer().ok_or(rebase::Error::SignatureMissing)??;
+        let commit = gix_object::Commit {
+            tree,
+            parents: [parent].into(),
+            author,
+            committer: committer.into(),
+            encoding,
+            message,
+            extra_headers: Vec::new(),
+        };
+        Ok(self.write_object(&commit)?.detach())
+    }
+
+    /// Point `HEAD` itself to `target`, which detaches it if `target` is an object id.
+    fn set_head(&self, target: Target, message: String) -> Result<(), rebase::Error> {
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: target,
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(())
+    }
+
+    /// Remove the files that `git` places into the `.git` directory when a commit can't be applied.
+    fn remove_stop_files(&self) -> std::io::Result<()> {
+        for name in ["REBASE_HEAD", "MERGE_MSG"] {
+            remove_file_if_present(&self.git_dir().join(name))?;
+        }
+        Ok(())
+    }
+
+    /// Store the changes of `index` and the worktree compared to `head` in a stash commit, similar to `git stash create`,
+    /// and return its id, along with a version of `index` which causes all changed files to be overwritten
+    /// when used as previous state of the worktree.
+    fn create_autostash(
+        &self,
+        workdir: &Path,
+        head: ObjectId,
+        head_name: Option<&FullName>,
+        mut index: gix_index::File,
+    ) -> Result<(ObjectId, gix_index::File), rebase::Error> {
+        if index
+            .entries()
+            .iter()
+            .any(|entry| entry.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(rebase::Error::UnresolvedConflicts);
+        }
+        let index_tree = write_tree(self, &index)?;
+
+        let (mut pipeline, _) = self.filter_pipeline(None)?;
+        let mut worktree = gix_index::State::new(self.object_hash());
+        let mut changed = Vec::new();
+        let mut buf = Vec::new();
+        for (idx, entry) in index.entries().iter().enumerate() {
+            let rela_path = entry.path(&index);
+            let path = workdir.join(gix_path::from_bstr(rela_path));
+            let id = match std::fs::symlink_metadata(&path) {
+                _ if entry.mode == gix_index::entry::Mode::COMMIT => Some(entry.id),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(err) => return Err(err.into()),
+                Ok(metadata) if metadata.is_symlink() => {
+                    let target = gix_path::into_bstr(std::fs::read_link(&path)?).into_owned();
+                    Some(self.write_blob(&target)?.detach())
+                }
+                Ok(metadata) if metadata.is_dir() => None,
+                Ok(_) => {
+                    buf.clear();
+                    pipeline
+                        .convert_to_git(
+                            std::fs::File::open(&path)?,
+                            gix_path::from_bstr(rela_path).as_ref(),
+                            &index,
+                        )?
+                        .read_to_end(&mut buf)?;
+                    Some(self.write_blob(&buf)?.detach())
+                }
+            };
+            if id != Some(entry.id) {
+                changed.push(idx);
+            }
+            if let Some(id) = id {
+                worktree.dangerously_push_entry(Default::default(), id, entry.flags, entry.mode, rela_path);
+            }
+        }
+        let worktree_tree = write_tree(self, &worktree)?;
+
+        let branch = head_name.map_or_else(|| "(no branch)".into(), |name| name.shorten().to_owned());
+        let subject = self.find_object(head)?.into_commit().message()?.summary().into_owned();
+        let reference = format!("{branch}: {} {subject}", head.attach(self).shorten_or_id());
+        let committer: gix_actor::Signature = self.committer().ok_or(rebase::Error::SignatureMissing)??.into();
+        let index_commit = self.write_object(&gix_object::Commit {
+            tree: index_tree,
+            parents: [head].into(),
+            author: committer.clone(),
+            committer: committer.clone(),
+            encoding: None,
+            message: format!("index on {reference}\n").into(),
+            extra_headers: Vec::new(),
+        })?;
+        let stash = self.write_object(&gix_object::Commit {
+            tree: worktree_tree,
+            parents: [head, index_commit.detach()].into_iter().collect(),
+            author: committer.clone(),
+            committer,
+            encoding: None,
+            message: format!("On {branch}: autostash\n").into(),
+            extra_headers: Vec::new(),
+        })?;
+
+        let null = ObjectId::null(self.object_hash());
+        for idx in changed {
+            index.entries_mut()[idx].id = null;
+        }
+        Ok((stash.detach(), index))
+    }
+
+    /// Apply the changes of the `stash` commit to the worktree without staging them, or store the `stash` in `refs/stash`
+    /// if this isn't possible without conflicts.
+    fn apply_autostash(
+        &self,
+        workdir: &Path,
+        stash: ObjectId,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<rebase::Autostash, rebase::Error> {
+        let stash_commit = self.find_object(stash)?.into_commit();
+        let stash_base = stash_commit
+            .parent_ids()
+            .next()
+            .ok_or(rebase::Error::InvalidState { name: "autostash" })?;
+        let head_tree = self.tree_id_of(self.head_id()?.detach())?;
+        let mut merged = self.merge_trees_to_index(
+            &self.tree_id_of(stash_base.detach())?,
+            &head_tree,
+            &stash_commit.tree_id()?,
+            blob::Labels {
+                ancestor: None,
+                current: Some("Updated upstream".into()),
+                other: Some("Stashed changes".into()),
+            },
+            self.tree_merge_options()?,
+            progress,
+            should_interrupt,
+        )?;
+        if merged.has_conflicts() {
+            self.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: true,
+                        message: "autostash".into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(stash),
+                },
+                name: "refs/stash".try_into().expect("valid"),
+                deref: false,
+            })?;
+            return Ok(rebase::Autostash::Conflict { stash });
+        }
+
+        let index = self.open_index()?;
+        self.update_worktree(workdir, &index, &mut merged.index, &[], progress, should_interrupt)?;
+        let mut head_index = self.index_from_tree(&head_tree)?;
+        for entry in merged.index.entries() {
+            let path = entry.path(&merged.index);
+            if let Some(head_entry) =
+                head_index.entry_mut_by_path_and_stage(path, gix_index::entry::Stage::Unconflicted)
+            {
+                if head_entry.id == entry.id && head_entry.mode == entry.mode {
+                    head_entry.stat = entry.stat;
+                }
+            }
+        }
+        head_index
+            .write(Default::default())
+            .map_err(crate::merge::Error::from)?;
+        Ok(rebase::Autostash::Applied)
+    }
+
+    fn read_rebase_state(&self) -> Result<State, rebase::Error> {
+        let dir = self.git_dir().join("rebase-merge");
+        if !dir.is_dir() {
+            return Err(rebase::Error::NoRebaseInProgress);
+        }
+        let read = |name: &'static str| -> Result<Option<BString>, rebase::Error> {
+            match std::fs::read(dir.join(name)) {
+                Ok(content) => Ok(Some(content.trim_end().into())),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err.into()),
+            }
+        };
+        let read_id = |name: &'static str| -> Result<ObjectId, rebase::Error> {
+            read(name)?
+                .and_then(|hex| ObjectId::from_hex(&hex).ok())
+                .ok_or(rebase::Error::InvalidState { name })
+        };
+
+        let head_name = match read("head-name")? {
+            Some(name) if name == "detached HEAD" => None,
+            Some(name) => {
+                Some(FullName::try_from(name).map_err(|_| rebase::Error::InvalidState { name: "head-name" })?)
+            }
+            None => return Err(rebase::Error::InvalidState { name: "head-name" }),
+        };
+        let autostash = read("autostash")?
+            .map(|hex| ObjectId::from_hex(&hex).map_err(|_| rebase::Error::InvalidState { name: "autostash" }))
+            .transpose()?;
+        Ok(State {
+            head_name,
+            onto: read_id("onto")?,
+            orig_head: read_id("orig-head")?,
+            todo: self
+                .parse_todo(read("git-rebase-todo")?.unwrap_or_default().as_ref())?
+                .into(),
+            done: self.parse_todo(read("done")?.unwrap_or_default().as_ref())?,
+            autostash,
+            dir,
+        })
+    }
+
+    /// Parse `todo` as written by [`State::write()`] or `git`, and fail on all commands but `pick`.
+    fn parse_todo(&self, todo: &BStr) -> Result<Vec<Pick>, rebase::Error> {
+        let mut picks = Vec::new();
+        for line in todo
+            .lines()
+            .map(ByteSlice::trim)
+            .filter(|line| !line.is_empty() && line[0] != b'#')
+        {
+            let mut tokens = line.splitn_str(3, " ");
+            let command = tokens.next().unwrap_or_default();
+            if command != b"pick" && command != b"p" {
+                return Err(rebase::Error::UnsupportedTodoCommand {
+                    command: command.into(),
+                });
+            }
+            let id = tokens.next().unwrap_or_default().as_bstr();
+            let id = match ObjectId::from_hex(id) {
+                Ok(id) => id,
+                Err(_) => self
+                    .rev_parse_single(id)
+                    .map_err(|_| rebase::Error::InvalidState {
+                        name: "git-rebase-todo",
+                    })?
+                    .detach(),
+            };
+            picks.push(Pick {
+                id,
+                subject: tokens.next().unwrap_or_default().into(),
+            });
+        }
+        Ok(picks)
+    }
+}
+
+/// A commit to apply during a rebase.
+#[derive(Debug, Clone)]
+struct Pick {
+    id: ObjectId,
+    /// The first line of the commit message.
+    subject: BString,
+}
+
+/// The state of a rebase in progress, as stored in `.git/rebase-merge`.
+struct State {
+    /// The directory all state is stored in.
+    dir: PathBuf,
+    /// The name of the branch that is rebased, or `None` if `HEAD` was detached.
+    head_name: Option<FullName>,
+    /// The commit the rebased commits are placed on top of.
+    onto: ObjectId,
+    /// The commit `HEAD` pointed to before the rebase started.
+    orig_head: ObjectId,
+    /// The commits that are yet to be applied.
+    todo: VecDeque<Pick>,
+    /// The commits that were applied already, including the one that is currently being applied.
+    done: Vec<Pick>,
+    /// The stash commit with the local changes before the rebase started.
+    autostash: Option<ObjectId>,
+}
+
+impl State {
+    /// Write all state to disk in a format that is compatible with `git`.
+    fn write(&self) -> std::io::Result<()> {
+        fn todo_list<'a>(picks: impl IntoIterator<Item = &'a Pick>) -> BString {
+            let mut out = BString::default();
+            for pick in picks {
+                out.extend_from_slice(format!("pick {} {}\n", pick.id, pick.subject).as_bytes());
+            }
+            out
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let head_name = self
+            .head_name
+            .as_ref()
+            .map_or_else(|| "detached HEAD".into(), |name| name.as_bstr().to_owned());
+        for (name, content) in [
+            ("head-name", format!("{head_name}\n").into()),
+            ("onto", format!("{}\n", self.onto).into()),
+            ("orig-head", format!("{}\n", self.orig_head).into()),
+            ("git-rebase-todo", todo_list(&self.todo)),
+            ("done", todo_list(&self.done)),
+            ("msgnum", format!("{}\n", self.done.len()).into()),
+            ("end", format!("{}\n", self.done.len() + self.todo.len()).into()),
+        ] {
+            std::fs::write(self.dir.join(name), content)?;
+        }
+        if let Some(stash) = self.autostash {
+            std::fs::write(self.dir.join("autostash"), format!("{stash}\n"))?;
+        }
+        Ok(())
+    }
+}
+
+fn remove_file_if_present(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Produce the content of the `author-script` file with the name, email and date of `author`, quoted for use in a shell.
+fn author_script(author: gix_actor::SignatureRef<'_>) -> BString {
+    let quote = |value: &BStr| {
+        let mut out = BString::from("'");
+        out.extend_from_slice(&value.replace("'", "'\\''"));
+        out.push(b'\'');
+        out
+    };
+    let mut out = BString::default();
+    for (name, value) in [
+        ("GIT_AUTHOR_NAME", quote(author.name)),
+        ("GIT_AUTHOR_EMAIL", quote(author.email)),
+        (
+            "GIT_AUTHOR_DATE",
+            quote(format!("@{}", author.time.to_bstring()).as_str().into()),
+        ),
+    ] {
+        out.extend_from_slice(name.as_bytes());
+        out.push(b'=');
+        out.extend_from_slice(&value);
+        out.push(b'\n');
+    }
+    out
+}
+
+/// Parse an `author-script` file as written by [`author_script()`] or `git`.
+fn parse_author_script(script: &[u8]) -> Option<gix_actor::Signature> {
+    let unquote = |value: &[u8]| -> Option<BString> {
+        let value = value.strip_prefix(b"'")?.strip_suffix(b"'")?;
+        Some(value.replace("'\\''", "'").into())
+    };
+    let (mut name, mut email, mut time) = (None, None, None);
+    for line in script.lines() {
+        let (key, value) = line.split_once_str("=")?;
+        let value = unquote(value)?;
+        match key {
+            b"GIT_AUTHOR_NAME" => name = Some(value),
+            b"GIT_AUTHOR_EMAIL" => email = Some(value),
+            b"GIT_AUTHOR_DATE" => {
+                let value = value.to_str().ok()?;
+                time = Some(gix_date::parse(value.strip_prefix('@').unwrap_or(value), None).ok()?);
+            }
+            _ => {}
+        }
+    }
+    Some(gix_actor::Signature {
+        name: name?,
+        email: email?,
+        time: time?,
+    })
+}
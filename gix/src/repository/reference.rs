@@ -146,13 +146,88 @@ impl crate::Repository {
         edits: impl IntoIterator<Item = RefEdit>,
     ) -> Result<Vec<RefEdit>, reference::edit::Error> {
         let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
-        self.refs
-            .transaction()
+        self.reference_transaction()?
             .prepare(edits, file_lock_fail, packed_refs_lock_fail)?
             .commit(self.committer().transpose()?)
             .map_err(Into::into)
     }
 
+    /// Point `HEAD` directly to `commit`, detaching it from the branch it may have pointed to, and record `message`
+    /// in its reflog, similar to `git checkout --detach`.
+    ///
+    /// Note that only `HEAD` is changed, the index and the worktree are left untouched.
+    #[momo]
+    pub fn detach_head(
+        &self,
+        commit: impl Into<ObjectId>,
+        message: impl Into<BString>,
+    ) -> Result<(), reference::edit::Error> {
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(commit.into()),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(())
+    }
+
+    /// Point `ORIG_HEAD` to `id`, which is what `git` does before operations that move `HEAD` to a different history,
+    /// like merges or rebases, to make it easy to return to where it was before.
+    #[momo]
+    pub fn set_orig_head(&self, id: impl Into<ObjectId>) -> Result<(), reference::edit::Error> {
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: Default::default(),
+                expected: PreviousValue::Any,
+                new: Target::Peeled(id.into()),
+            },
+            name: "ORIG_HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(())
+    }
+
+    /// Return all entries of `FETCH_HEAD` in the order they were written by the most recent fetch,
+    /// or `None` if there is no such file.
+    ///
+    /// The entries marked [for merging](gix_fetchhead::Entry::for_merge) are what `git pull` integrates into the current branch.
+    pub fn fetch_head(&self) -> Result<Option<Vec<gix_fetchhead::Entry>>, crate::repository::fetch_head::Error> {
+        let data = match std::fs::read(self.git_dir().join("FETCH_HEAD")) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some(gix_fetchhead::parse(&data).collect::<Result<_, _>>()?))
+    }
+
+    /// Replace the contents of `FETCH_HEAD` with `entries`, in order, through a lock file so concurrent writers fail
+    /// instead of clobbering each other.
+    ///
+    /// Note that `git` expects entries for merging to come before all others.
+    pub fn write_fetch_head(&self, entries: &[gix_fetchhead::Entry]) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut file = gix_lock::File::acquire_to_update_resource(
+            self.git_dir().join("FETCH_HEAD"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        let mut buf = Vec::new();
+        for entry in entries {
+            entry.write_to(&mut buf)?;
+        }
+        file.write_all(&buf)?;
+        file.commit().map_err(|err| err.error)?;
+        Ok(())
+    }
+
     /// Return the repository head, an abstraction to help dealing with the `HEAD` reference.
     ///
     /// The `HEAD` reference can be in various states, for more information, the documentation of [`Head`][crate::Head].
@@ -231,6 +306,19 @@ impl crate::Repository {
             .ok_or(reference::find::existing::Error::NotFound)
     }
 
+    /// Read the reference log of the reference with the given partial or full `name`, like `HEAD` or `main`, or return `None`
+    /// if it has none.
+    ///
+    /// Its entries can be obtained [by index](reference::log::Log::entry()), [by date](reference::log::Log::entry_at())
+    /// or [by message](reference::log::Log::entries_matching()), just like revision specifications like `main@{1}` do.
+    pub fn reflog<'a, Name, E>(&self, name: Name) -> Result<Option<reference::log::Log>, reference::log::Error>
+    where
+        Name: TryInto<&'a PartialNameRef, Error = E>,
+        gix_ref::file::find::Error: From<E>,
+    {
+        self.find_reference(name)?.log()
+    }
+
     /// Return a platform for iterating references.
     ///
     /// Common kinds of iteration are [all][crate::reference::iter::Platform::all()] or [prefixed][crate::reference::iter::Platform::prefixed()]
@@ -93,6 +93,24 @@ impl crate::Repository {
         })
     }
 
+    /// List the references of the remote to fetch from, chosen by `name_or_url` like with [`find_fetch_remote()`][Self::find_fetch_remote()],
+    /// that match at least one of `patterns`, or all of them if there are no patterns, similar to `git ls-remote`.
+    ///
+    /// The returned references include the targets of symbolic references and the objects that annotated tags peel to.
+    /// See [`Connection::ls_remote()`](crate::remote::Connection::ls_remote()) for details on how patterns match, and
+    /// use it directly to control how the connection is made or to get progress information.
+    #[cfg(feature = "blocking-network-client")]
+    pub fn ls_remote(
+        &self,
+        name_or_url: Option<&BStr>,
+        patterns: &[crate::bstr::BString],
+    ) -> Result<remote::ls_remote::Outcome, remote::ls_remote::Error> {
+        Ok(self
+            .find_fetch_remote(name_or_url)?
+            .connect(remote::Direction::Fetch)?
+            .ls_remote(gix_features::progress::Discard, patterns)?)
+    }
+
     /// Similar to [`try_find_remote()`][Self::try_find_remote()], but removes a failure mode if rewritten URLs turn out to be invalid
     /// as it skips rewriting them.
     /// Use this in conjunction with [`Remote::rewrite_urls()`] to non-destructively apply the rules and keep the failed urls unchanged.
@@ -35,6 +35,18 @@ impl crate::Repository {
             .ok_or(revision::spec::parse::single::Error::RangedRev { spec: spec.into() })
     }
 
+    /// Create a platform to configure which references to use for finding names of commits relative to them,
+    /// like `main~2` or `tags/v1.0~1^2`, similar to `git name-rev`.
+    #[cfg(feature = "revision")]
+    pub fn name_rev(&self) -> revision::name_rev::Platform<'_> {
+        revision::name_rev::Platform {
+            repo: self,
+            tags_only: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
     /// Create the baseline for a revision walk by initializing it with the `tips` to start iterating on.
     ///
     /// It can be configured further before starting the actual walk.
@@ -0,0 +1,12 @@
+use crate::Sandbox;
+
+/// Sandboxes
+impl crate::Repository {
+    /// Return a sandbox to create objects, commits and merges and to update references in memory, without changing the repository.
+    ///
+    /// Drop the sandbox to discard all of its changes, or [persist](Sandbox::persist()) them into the repository.
+    /// This is useful on servers to find out if branches can be merged, or to prepare merge commits speculatively.
+    pub fn sandbox(&self) -> Sandbox<'_> {
+        Sandbox::new(self)
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_config::{file::Metadata, file::Transaction, Source};
+use gix_index::entry::{Flags, Mode, Stat};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    remote,
+    submodule::{add::Error, MODULES_FILE},
+    Repository,
+};
+
+/// Add submodules
+impl Repository {
+    /// Clone the repository at `url` into `path`, a path relative to the worktree with slashes as separators, and register
+    /// it as submodule named after `path`, similar to `git submodule add <url> <path>`. Return the repository of the submodule.
+    ///
+    /// `url` may be relative to the URL of the default remote, like `../other.git`, and is resolved against the worktree
+    /// if there is no such remote. It's written into `.gitmodules` as given, but resolved in the repository configuration,
+    /// which also marks the submodule as active.
+    /// The repository of the submodule is moved into `.git/modules/<name>` while its worktree refers to it with a `.git` file.
+    /// Finally, the submodule and `.gitmodules` are staged, leaving it to the caller to commit the change.
+    ///
+    /// Note that the repository of the submodule is kept even if registering it fails.
+    pub fn submodule_add<P>(
+        &mut self,
+        url: impl AsRef<BStr>,
+        path: impl AsRef<BStr>,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Repository, Error>
+    where
+        P: crate::NestedProgress,
+        P::SubProgress: crate::NestedProgress + 'static,
+    {
+        let url = url.as_ref();
+        let path = path.as_ref().trim_end_with(|c| c == '/').as_bstr();
+        let name = gix_validate::submodule::name(path)?;
+        let work_dir = gix_path::realpath(self.work_dir().ok_or(Error::BareRepository)?)?;
+        let protect = self.config.protect_options()?;
+        for component in path.split(|b| *b == b'/') {
+            if component == b"." || component == b".." || component.is_empty() {
+                return Err(Error::InvalidPath {
+                    path: path.to_owned(),
+                    source: None,
+                });
+            }
+            gix_validate::path::component(component.as_bstr(), None, protect).map_err(|err| Error::InvalidPath {
+                path: path.to_owned(),
+                source: Some(err),
+            })?;
+        }
+
+        let is_registered = self
+            .modules()?
+            .filter(|modules| modules.names().any(|existing| existing == name))
+            .is_some();
+        if is_registered || self.index_or_empty()?.entry_by_path(path).is_some() {
+            return Err(Error::AlreadyExists { name: name.to_owned() });
+        }
+        let sm_work_dir = work_dir.join(gix_path::from_bstr(path));
+        let is_empty_dir = matches!(
+            std::fs::read_dir(&sm_work_dir).map(|mut entries| entries.next().is_none()),
+            Ok(true)
+        );
+        if sm_work_dir.symlink_metadata().is_ok() && !is_empty_dir {
+            return Err(Error::PathExists { path: sm_work_dir });
+        }
+        let sm_git_dir = self.common_dir().join("modules").join(gix_path::from_bstr(name));
+        if sm_git_dir.exists() {
+            return Err(Error::GitDirExists { path: sm_git_dir });
+        }
+        let resolved_url = self.resolve_submodule_url(url, &work_dir)?;
+
+        let mut prepare = crate::clone::PrepareFetch::new(
+            gix_url::parse(resolved_url.as_ref())?,
+            &sm_work_dir,
+            crate::create::Kind::WithWorktree,
+            crate::create::Options::default(),
+            self.options.clone().for_other_repository(),
+        )?;
+        let (mut checkout, _) = prepare.fetch_then_checkout(&mut progress, should_interrupt)?;
+        let (sm_repo, _) = checkout.main_worktree(progress, should_interrupt)?;
+        let head_id = sm_repo
+            .head_id()
+            .map_err(|_| Error::Unborn { path: path.to_owned() })?
+            .detach();
+        drop(sm_repo);
+
+        std::fs::create_dir_all(sm_git_dir.parent().expect("below 'modules'"))?;
+        std::fs::rename(sm_work_dir.join(gix_discover::DOT_GIT_DIR), &sm_git_dir)?;
+        let sm_git_dir = gix_path::realpath(&sm_git_dir)?;
+        let mut gitfile = BString::from("gitdir: ");
+        gitfile.push_str(
+            gix_path::to_unix_separators_on_windows(gix_path::into_bstr(relative_path(&sm_work_dir, &sm_git_dir)))
+                .as_ref(),
+        );
+        gitfile.push_byte(b'\n');
+        std::fs::write(sm_work_dir.join(gix_discover::DOT_GIT_DIR), gitfile)?;
+        let mut transaction = Transaction::prepare(
+            Some(Metadata::from(Source::Local).at(sm_git_dir.join("config"))),
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        transaction.file_mut(Source::Local).expect("added").set_raw_value_by(
+            "core",
+            None,
+            "worktree",
+            gix_path::to_unix_separators_on_windows(gix_path::into_bstr(relative_path(&sm_git_dir, &sm_work_dir)))
+                .as_ref(),
+        )?;
+        transaction.commit()?;
+
+        let mut transaction = Transaction::prepare(
+            [
+                Metadata::from(Source::Local).at(self.common_dir().join("config")),
+                // `.gitmodules` isn't a configuration file git would read, so it's edited on behalf of the caller.
+                Metadata::from(Source::Api).at(work_dir.join(MODULES_FILE)),
+            ],
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        let local = transaction.file_mut(Source::Local).expect("added");
+        local.set_raw_value_by("submodule", Some(name), "url", resolved_url.as_bstr())?;
+        local.set_raw_value_by("submodule", Some(name), "active", "true")?;
+        let modules = transaction.file_mut(Source::Api).expect("added");
+        modules.set_raw_value_by("submodule", Some(name), "path", path)?;
+        modules.set_raw_value_by("submodule", Some(name), "url", url)?;
+        transaction.commit()?;
+        self.reload_config()?;
+
+        let mut index = gix_index::File::clone(&**self.index_or_empty()?);
+        index.dangerously_push_entry(Stat::default(), head_id, Flags::empty(), Mode::COMMIT, path);
+        index.sort_entries();
+        index.invalidate_tree_cache(path);
+        index.write(self.index_write_options())?;
+        self.worktree()
+            .expect("checked for worktree")
+            .stage_path(MODULES_FILE)?;
+
+        Ok(crate::open_opts(
+            sm_work_dir,
+            self.options.clone().for_other_repository(),
+        )?)
+    }
+
+    /// Resolve `url` against the URL of the default remote if it's relative, or against `work_dir` if there is no such remote.
+    fn resolve_submodule_url(&self, url: &BStr, work_dir: &Path) -> Result<BString, Error> {
+        if !(url.starts_with(b"./") || url.starts_with(b"../")) {
+            return Ok(url.to_owned());
+        }
+        let base: BString = self
+            .remote_default_name(remote::Direction::Fetch)
+            .and_then(|name| {
+                self.config
+                    .resolved
+                    .string_by("remote", Some(name.as_ref()), "url")
+                    .map(std::borrow::Cow::into_owned)
+            })
+            .unwrap_or_else(|| gix_path::to_unix_separators_on_windows(gix_path::into_bstr(work_dir)).into_owned());
+        let mut resolved = BString::from(base.trim_end_with(|c| c == '/'));
+        let mut rest = url;
+        loop {
+            if let Some(stripped) = rest.strip_prefix(b"./") {
+                rest = stripped.as_bstr();
+            } else if let Some(stripped) = rest.strip_prefix(b"../") {
+                let pos = resolved.rfind_byte(b'/').ok_or_else(|| Error::RelativeUrl {
+                    url: url.to_owned(),
+                    base: base.clone(),
+                })?;
+                resolved.truncate(pos);
+                rest = stripped.as_bstr();
+            } else {
+                break;
+            }
+        }
+        resolved.push_byte(b'/');
+        resolved.push_str(rest);
+        Ok(resolved)
+    }
+}
+
+/// Return the path that leads from the directory `from` to `to`, both of which are absolute and free of symlinks.
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let mut from = from.components().peekable();
+    let mut to = to.components().peekable();
+    while from.peek().is_some() && from.peek() == to.peek() {
+        from.next();
+        to.next();
+    }
+    from.map(|_| Component::ParentDir).chain(to).collect()
+}
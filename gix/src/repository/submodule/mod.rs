@@ -2,6 +2,11 @@ use std::rc::Rc;
 
 use crate::{submodule, Repository};
 
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+mod add;
+#[cfg(feature = "status")]
+mod remove;
+
 impl Repository {
     /// Open the `.gitmodules` file as present in the worktree, or return `None` if no such file is available.
     /// Note that git configuration is also contributing to the result based on the current snapshot.
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use gix_config::{file::Metadata, file::Transaction, Source};
+
+use crate::{
+    bstr::{BStr, BString},
+    submodule::{config::Ignore, deinit, remove, MODULES_FILE},
+    Repository,
+};
+
+/// Remove submodules
+impl Repository {
+    /// Remove the worktree of the submodule named `name` and its configuration from the repository configuration,
+    /// similar to `git submodule deinit <path>`, leaving an empty directory in its place.
+    ///
+    /// The submodule remains registered in `.gitmodules`, and its repository in `.git/modules/<name>` is kept so
+    /// it can be initialized again later.
+    /// Unless `force` is `true`, submodules with local modifications or untracked files are not touched.
+    /// Submodules whose repository is located in their worktree are never removed as their history would be lost.
+    pub fn submodule_deinit(&mut self, name: impl AsRef<BStr>, force: bool) -> Result<(), deinit::Error> {
+        self.submodule_deinit_inner(name.as_ref(), force).map(|_| ())
+    }
+
+    /// Deinitialize the submodule named `name` and return its path and worktree directory.
+    fn submodule_deinit_inner(&mut self, name: &BStr, force: bool) -> Result<(BString, PathBuf), deinit::Error> {
+        let (path, work_dir) = self.submodule_work_dir_for_removal(name, force)?;
+        if work_dir.is_dir() {
+            std::fs::remove_dir_all(&work_dir)?;
+            std::fs::create_dir(&work_dir)?;
+        }
+
+        let mut transaction = Transaction::prepare(
+            Some(Metadata::from(Source::Local).at(self.common_dir().join("config"))),
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        let local = transaction.file_mut(Source::Local).expect("added");
+        while local.remove_section("submodule", Some(name)).is_some() {}
+        transaction.commit()?;
+        self.reload_config()?;
+        Ok((path, work_dir))
+    }
+
+    /// Remove the submodule named `name` entirely, similar to `git rm <path>` followed by removing its configuration,
+    /// after [deinitializing](Self::submodule_deinit()) it, which respects `force` as well.
+    ///
+    /// This removes its worktree directory, its section in `.gitmodules` and its entry in the index, and stages
+    /// the changed `.gitmodules` file, leaving it to the caller to commit the change.
+    /// Just like with `git`, the repository of the submodule in `.git/modules/<name>` is kept.
+    pub fn submodule_remove(&mut self, name: impl AsRef<BStr>, force: bool) -> Result<(), remove::Error> {
+        let name = name.as_ref();
+        let (path, work_dir) = self.submodule_deinit_inner(name, force)?;
+        match std::fs::remove_dir(&work_dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let worktree = self.worktree().expect("submodules are only listed with a worktree");
+        let mut transaction = Transaction::prepare(
+            Some(Metadata::from(Source::Api).at(worktree.base().join(MODULES_FILE))),
+            gix_lock::acquire::Fail::Immediately,
+        )?;
+        let modules = transaction.file_mut(Source::Api).expect("added");
+        while modules.remove_section("submodule", Some(name)).is_some() {}
+        transaction.commit()?;
+
+        let mut index = gix_index::File::clone(&**self.index_or_empty()?);
+        index.remove_entries(|_, entry_path, _| entry_path == path);
+        index.invalidate_tree_cache(path.as_ref());
+        index.write(self.index_write_options())?;
+        worktree.stage_path(MODULES_FILE)?;
+        Ok(())
+    }
+
+    /// Return the path and the worktree directory of the submodule named `name` if it can be removed without losing data,
+    /// or if `force` is `true`.
+    fn submodule_work_dir_for_removal(&self, name: &BStr, force: bool) -> Result<(BString, PathBuf), deinit::Error> {
+        let submodule = self
+            .submodules()?
+            .into_iter()
+            .flatten()
+            .find(|submodule| submodule.name() == name)
+            .ok_or_else(|| deinit::Error::NotFound { name: name.to_owned() })?;
+        let path = submodule.path()?.into_owned();
+        let work_dir = submodule.work_dir()?;
+        if work_dir.join(gix_discover::DOT_GIT_DIR).is_dir() {
+            return Err(deinit::Error::OldForm { path });
+        }
+        if !force && submodule.state()?.worktree_checkout {
+            let status = submodule.status(Ignore::None, false)?;
+            if matches!(status.changes, Some(changes) if !changes.is_empty()) {
+                return Err(deinit::Error::Modified { path });
+            }
+        }
+        Ok((path, work_dir))
+    }
+}
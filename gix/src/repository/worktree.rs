@@ -31,7 +31,11 @@ impl crate::Repository {
     /// Also note that the main repo might be bare.
     #[allow(clippy::result_large_err)]
     pub fn main_repo(&self) -> Result<crate::Repository, crate::open::Error> {
-        crate::ThreadSafeRepository::open_opts(self.common_dir(), self.options.clone()).map(Into::into)
+        let mut options = self.options.clone();
+        if self.common_dir() != self.git_dir() {
+            options = options.for_other_repository();
+        }
+        crate::ThreadSafeRepository::open_opts(self.common_dir(), options).map(Into::into)
     }
 
     /// Return the currently set worktree if there is one, acting as platform providing a validated worktree base path.
@@ -61,6 +65,18 @@ impl crate::Repository {
     pub fn worktree_stream(
         &self,
         id: impl Into<gix_hash::ObjectId>,
+    ) -> Result<(gix_worktree_stream::Stream, gix_index::File), crate::repository::worktree_stream::Error> {
+        self.worktree_stream_opts(id, Default::default())
+    }
+
+    /// Like [`worktree_stream()`](Self::worktree_stream()), but with `options` to filter or transform entries
+    /// and to control how many threads are used to load blobs.
+    #[cfg(feature = "worktree-stream")]
+    #[gix_macros::momo]
+    pub fn worktree_stream_opts(
+        &self,
+        id: impl Into<gix_hash::ObjectId>,
+        options: gix_worktree_stream::from_tree::Options,
     ) -> Result<(gix_worktree_stream::Stream, gix_index::File), crate::repository::worktree_stream::Error> {
         use gix_odb::HeaderExt;
         let id = id.into();
@@ -81,7 +97,7 @@ impl crate::Repository {
             .detach();
         let pipeline = gix_filter::Pipeline::new(self.command_context()?, crate::filter::Pipeline::options(self)?);
         let objects = self.objects.clone().into_arc().expect("TBD error handling");
-        let stream = gix_worktree_stream::from_tree(
+        let stream = gix_worktree_stream::from_tree_opts(
             id,
             objects.clone(),
             pipeline,
@@ -90,10 +106,88 @@ impl crate::Repository {
                 entry.matching_attributes(attrs);
                 Ok(())
             },
+            options,
         );
         Ok((stream, index))
     }
 
+    /// Like [`worktree_stream()`](Self::worktree_stream()), but also add the worktrees of all active submodules at the commits
+    /// recorded in the tree at `id`, recursively, so they appear as if they were part of this repository.
+    ///
+    /// Submodules are looked up by path in the `.gitmodules` file of this repository, and gitlinks without a configured
+    /// submodule or with an inactive one are skipped, just like they would be when checking out the worktree.
+    /// It's an error if an active submodule wasn't initialized or if it doesn't contain the recorded commit.
+    ///
+    /// Note that the entries of submodules are only returned by [`Stream::next_entry()`](gix_worktree_stream::Stream::next_entry()),
+    /// and the returned index is the one of this repository only.
+    #[cfg(feature = "worktree-stream")]
+    #[gix_macros::momo]
+    pub fn worktree_stream_with_submodules(
+        &self,
+        id: impl Into<gix_hash::ObjectId>,
+    ) -> Result<(gix_worktree_stream::Stream, gix_index::File), crate::repository::worktree_stream::Error> {
+        use crate::repository::worktree_stream::Error;
+        let (mut stream, index) = self.worktree_stream(id)?;
+        let gitlinks: Vec<_> = index
+            .entries()
+            .iter()
+            .filter(|entry| entry.mode == gix_index::entry::Mode::COMMIT)
+            .map(|entry| (entry.path(&index).to_owned(), entry.id))
+            .collect();
+        if gitlinks.is_empty() {
+            return Ok((stream, index));
+        }
+
+        let submodules: Vec<_> = self.submodules()?.into_iter().flatten().collect();
+        for (path, commit_id) in gitlinks {
+            let submodule = submodules
+                .iter()
+                .find(|sm| matches!(sm.path(), Ok(sm_path) if *sm_path == *path));
+            let Some(submodule) = submodule else { continue };
+            let is_active = submodule.is_active().map_err(|err| Error::SubmoduleIsActive {
+                path: path.clone(),
+                source: err,
+            })?;
+            if !is_active {
+                continue;
+            }
+            let repo = submodule
+                .open()
+                .map_err(|err| Error::SubmoduleOpen {
+                    path: path.clone(),
+                    source: err,
+                })?
+                .ok_or_else(|| Error::SubmoduleUninitialized { path: path.clone() })?;
+            let tree_id = repo
+                .find_object(commit_id)
+                .map_err(|err| Error::SubmoduleTree {
+                    path: path.clone(),
+                    id: commit_id,
+                    source: err.into(),
+                })?
+                .try_into_commit()
+                .map_err(|err| Error::SubmoduleTree {
+                    path: path.clone(),
+                    id: commit_id,
+                    source: err.into(),
+                })?
+                .tree_id()
+                .map_err(|err| Error::SubmoduleTree {
+                    path: path.clone(),
+                    id: commit_id,
+                    source: err.into(),
+                })?;
+            let (sub_stream, _index) =
+                repo.worktree_stream_with_submodules(tree_id)
+                    .map_err(|err| Error::Submodule {
+                        path: path.clone(),
+                        source: Box::new(err),
+                    })?;
+            stream.add_stream(path, sub_stream);
+        }
+        Ok((stream, index))
+    }
+
     /// Produce an archive from the `stream` and write it to `out` according to `options`.
     /// Use `blob` to provide progress for each entry written to `out`, and note that it should already be initialized to the amount
     /// of expected entries, with `should_interrupt` being queried between each entry to abort if needed, and on each write to `out`.
@@ -10,6 +10,11 @@ pub use gix_revision as plumbing;
 pub mod walk;
 pub use walk::iter::Walk;
 
+///
+#[cfg(feature = "revision")]
+#[allow(clippy::empty_docs)]
+pub mod name_rev;
+
 ///
 #[cfg(feature = "revision")]
 #[allow(clippy::empty_docs)]
@@ -30,5 +35,9 @@ pub struct Spec<'repo> {
     pub(crate) first_ref: Option<gix_ref::Reference>,
     /// The second name of a reference as seen while parsing a `RevSpec`, for completeness.
     pub(crate) second_ref: Option<gix_ref::Reference>,
+    /// The reference log entry used to resolve the first revision, like in `@{1}`.
+    pub(crate) first_reflog_entry: Option<crate::reference::log::Entry>,
+    /// The reference log entry used to resolve the second revision, like in `..main@{yesterday}`.
+    pub(crate) second_reflog_entry: Option<crate::reference::log::Entry>,
     pub(crate) repo: &'repo crate::Repository,
 }
@@ -0,0 +1,208 @@
+use gix_hash::{oid, ObjectId};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    Repository,
+};
+
+/// The error returned by [`Platform::names()`] and [`Platform::all_names()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    RefIter(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    RefIterInit(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    ObtainReference(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FollowSymref(#[from] gix_ref::file::find::existing::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeTag(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    NameRev(#[from] gix_revision::name_rev::Error),
+}
+
+/// A support type to configure which references are used to name commits, similar to `git name-rev`.
+///
+/// It's created by [`Repository::name_rev()`].
+pub struct Platform<'repo> {
+    pub(crate) repo: &'repo Repository,
+    pub(crate) tags_only: bool,
+    pub(crate) include: Vec<BString>,
+    pub(crate) exclude: Vec<BString>,
+}
+
+impl<'repo> Platform<'repo> {
+    /// If `true`, only use tags to name commits, similar to `git name-rev --tags`.
+    pub fn tags_only(mut self, tags_only: bool) -> Self {
+        self.tags_only = tags_only;
+        self
+    }
+
+    /// Only use references matching the glob `pattern` to name commits, similar to `git name-rev --refs=<pattern>`.
+    ///
+    /// The pattern is matched against the full reference name as well as any of its suffixes following a `/`,
+    /// so `main` matches `refs/heads/main`. If called multiple times, references need to match one of the patterns.
+    pub fn include_refs(mut self, pattern: impl Into<BString>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Don't use references matching the glob `pattern` to name commits, similar to `git name-rev --exclude=<pattern>`.
+    ///
+    /// Patterns are matched like the ones passed to [`include_refs()`](Self::include_refs()), and take precedence over them.
+    pub fn exclude_refs(mut self, pattern: impl Into<BString>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Find names for `commits`, like `main~2` or `tags/v1.0~1^2`.
+    ///
+    /// Only the names of `commits` are guaranteed to be correct, as commits much older than the oldest of `commits`
+    /// aren't considered. Use [`all_names()`](Self::all_names()) to obtain names for all commits.
+    pub fn names(&self, commits: impl IntoIterator<Item = impl Into<ObjectId>>) -> Result<Names, Error> {
+        let commits: Vec<_> = commits.into_iter().map(Into::into).collect();
+        let mut graph = self.repo.revision_graph();
+        let names = gix_revision::name_rev(&commits, self.tips()?, &mut graph)?;
+        Ok(Names {
+            inner: names,
+            object_hash: self.repo.object_hash(),
+        })
+    }
+
+    /// Find names for all commits reachable from the selected references, which is useful to
+    /// [annotate](Names::annotate()) text that mentions arbitrary commits.
+    pub fn all_names(&self) -> Result<Names, Error> {
+        self.names(None::<ObjectId>)
+    }
+
+    fn tips(&self) -> Result<Vec<gix_revision::name_rev::Tip<'static>>, Error> {
+        let mut tips = Vec::new();
+        for reference in self.repo.references()?.all()? {
+            let mut reference = reference?;
+            let full_name = reference.name().as_bstr().to_owned();
+            if !self.is_selected(full_name.as_ref()) {
+                continue;
+            }
+            while let Some(next) = reference.follow() {
+                reference = next?;
+            }
+            let Some(mut id) = reference.try_id().map(crate::Id::detach) else {
+                continue;
+            };
+            let mut tagger_date = None;
+            let mut peeled = false;
+            loop {
+                let object = self.repo.find_object(id)?;
+                if object.kind != gix_object::Kind::Tag {
+                    break;
+                }
+                let tag = object.into_tag();
+                tagger_date = tag.tagger()?.map(|signature| signature.time.seconds);
+                id = tag.target_id()?.detach();
+                peeled = true;
+            }
+            let name = full_name
+                .strip_prefix(b"refs/heads/")
+                .or_else(|| full_name.strip_prefix(b"refs/"))
+                .unwrap_or(full_name.as_slice());
+            tips.push(gix_revision::name_rev::Tip {
+                name: name.as_bstr().to_owned().into(),
+                id,
+                from_tag: full_name.starts_with(b"refs/tags/"),
+                tagger_date,
+                peeled,
+            });
+        }
+        Ok(tips)
+    }
+
+    fn is_selected(&self, full_name: &BStr) -> bool {
+        if self.tags_only && !full_name.starts_with(b"refs/tags/") {
+            return false;
+        }
+        if self
+            .exclude
+            .iter()
+            .any(|pattern| subpath_matches(full_name, pattern.as_ref()))
+        {
+            return false;
+        }
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| subpath_matches(full_name, pattern.as_ref()))
+    }
+}
+
+/// Return `true` if `pattern` matches `name` or any of its suffixes following a `/`.
+fn subpath_matches(name: &BStr, pattern: &BStr) -> bool {
+    let mut subpath = name;
+    loop {
+        if gix_glob::wildmatch(pattern, subpath, gix_glob::wildmatch::Mode::empty()) {
+            return true;
+        }
+        match subpath.find_byte(b'/') {
+            Some(pos) => subpath = subpath[pos + 1..].as_bstr(),
+            None => return false,
+        }
+    }
+}
+
+/// The names of commits as obtained by [`Platform::names()`].
+#[derive(Debug, Clone)]
+pub struct Names {
+    inner: gix_revision::name_rev::Names,
+    object_hash: gix_hash::Kind,
+}
+
+impl Names {
+    /// Return the name of the commit `id`, if one was found.
+    pub fn get(&self, id: &oid) -> Option<&gix_revision::name_rev::Name> {
+        self.inner.get(id)
+    }
+
+    /// Return all names by the id of the commit they name.
+    pub fn detach(self) -> gix_revision::name_rev::Names {
+        self.inner
+    }
+
+    /// Find full hexadecimal commit ids in `text` and annotate them with their name as `<id> (<name>)`,
+    /// or replace them with their name if `name_only` is `true`, similar to `git name-rev --annotate-stdin`.
+    ///
+    /// Ids without a name are left as is.
+    pub fn annotate(&self, text: &BStr, name_only: bool) -> BString {
+        let hex_len = self.object_hash.len_in_hex();
+        let mut out = BString::default();
+        let mut rest = text.as_bytes();
+        while let Some(start) = rest.iter().position(u8::is_ascii_hexdigit) {
+            let len = rest[start..]
+                .iter()
+                .position(|b| !b.is_ascii_hexdigit())
+                .unwrap_or(rest.len() - start);
+            let end = start + len;
+            let name = (len == hex_len)
+                .then(|| ObjectId::from_hex(&rest[start..end]).ok())
+                .flatten()
+                .and_then(|id| self.inner.get(&id));
+            match name {
+                Some(name) if name_only => {
+                    out.extend_from_slice(&rest[..start]);
+                    out.extend_from_slice(name.to_string().as_bytes());
+                }
+                Some(name) => {
+                    out.extend_from_slice(&rest[..end]);
+                    out.extend_from_slice(format!(" ({name})").as_bytes());
+                }
+                None => out.extend_from_slice(&rest[..end]),
+            }
+            rest = &rest[end..];
+        }
+        out.extend_from_slice(rest);
+        out
+    }
+}
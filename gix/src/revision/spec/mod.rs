@@ -43,6 +43,8 @@ impl<'repo> Spec<'repo> {
             repo: id.repo,
             first_ref: None,
             second_ref: None,
+            first_reflog_entry: None,
+            second_reflog_entry: None,
         }
     }
 }
@@ -85,6 +87,18 @@ impl<'repo> Spec<'repo> {
         self.second_ref.as_ref()
     }
 
+    /// Return the reference log entry that was used to resolve the first revision, like in `@{1}` or `main@{yesterday}`,
+    /// or `None` if no reference log was involved.
+    pub fn first_reflog_entry(&self) -> Option<&crate::reference::log::Entry> {
+        self.first_reflog_entry.as_ref()
+    }
+
+    /// Return the reference log entry that was used to resolve the second revision, like in `..@{1}`,
+    /// or `None` if no reference log was involved or there was no second revision.
+    pub fn second_reflog_entry(&self) -> Option<&crate::reference::log::Entry> {
+        self.second_reflog_entry.as_ref()
+    }
+
     /// Return the single included object represented by this instance, or `None` if it is a range of any kind.
     pub fn single(&self) -> Option<Id<'repo>> {
         match self.inner {
@@ -19,6 +19,7 @@ impl<'repo> Delegate<'repo> {
             objs: Default::default(),
             paths: Default::default(),
             ambiguous_objects: Default::default(),
+            reflog_entries: Default::default(),
             idx: 0,
             kind: None,
             err: Vec::new(),
@@ -104,6 +105,8 @@ impl<'repo> Delegate<'repo> {
             path: self.paths[0].take().or(self.paths[1].take()),
             first_ref: self.refs[0].take(),
             second_ref: self.refs[1].take(),
+            first_reflog_entry: self.reflog_entries[0].take(),
+            second_reflog_entry: self.reflog_entries[1].take(),
             inner: kind_to_spec(self.kind, range)?,
             repo: self.repo,
         })
@@ -105,61 +105,75 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
 
     fn reflog(&mut self, query: ReflogLookup) -> Option<()> {
         self.unset_disambiguate_call();
-        match query {
-            ReflogLookup::Date(_date) => {
-                // TODO: actually do this - this should be possible now despite incomplete date parsing
-                self.err.push(Error::Planned {
-                    dependency: "remote handling and ref-specs are fleshed out more",
-                });
-                None
+        let r = match &mut self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => {
+                    *val = Some(r.clone().detach());
+                    r
+                }
+                Ok(None) => {
+                    self.err.push(Error::UnbornHeadsHaveNoRefLog);
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        };
+        let mut platform = r.log_iter();
+        let found = match platform.rev() {
+            Ok(Some(lines)) => {
+                // Only read as many entries as needed, so malformed entries beyond them don't matter.
+                let mut entries = lines
+                    .enumerate()
+                    .map(|(index, line)| line.map(|line| crate::reference::log::Entry { index, line }));
+                match query {
+                    ReflogLookup::Entry(no) => entries
+                        .nth(no)
+                        .transpose()
+                        .map(|entry| entry.map(|entry| (entry.line.new_oid, entry))),
+                    ReflogLookup::Date(date) => crate::reference::log::entry_at(entries, date),
+                }
             }
-            ReflogLookup::Entry(no) => {
-                let r = match &mut self.refs[self.idx] {
-                    Some(r) => r.clone().attach(self.repo),
-                    val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
-                        Ok(Some(r)) => {
-                            *val = Some(r.clone().detach());
-                            r
-                        }
-                        Ok(None) => {
-                            self.err.push(Error::UnbornHeadsHaveNoRefLog);
-                            return None;
-                        }
-                        Err(err) => {
-                            self.err.push(err.into());
-                            return None;
-                        }
+            Ok(None) => {
+                self.err.push(Error::MissingRefLog {
+                    reference: r.name().as_bstr().into(),
+                    action: match query {
+                        ReflogLookup::Entry(_) => "lookup entry",
+                        ReflogLookup::Date(_) => "lookup entry by date",
                     },
-                };
-                let mut platform = r.log_iter();
-                match platform.rev().ok().flatten() {
-                    Some(mut it) => match it.nth(no).and_then(Result::ok) {
-                        Some(line) => {
-                            self.objs[self.idx]
-                                .get_or_insert_with(HashSet::default)
-                                .insert(line.new_oid);
-                            Some(())
-                        }
-                        None => {
-                            let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
-                            self.err.push(Error::RefLogEntryOutOfRange {
-                                reference: r.detach(),
-                                desired: no,
-                                available,
-                            });
-                            None
-                        }
+                });
+                return None;
+            }
+            Err(err) => {
+                self.err.push(crate::reference::log::Error::from(err).into());
+                return None;
+            }
+        };
+        let (id, entry) = match found {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
+                self.err.push(Error::RefLogEntryOutOfRange {
+                    reference: r.detach(),
+                    desired: match query {
+                        ReflogLookup::Entry(no) => no,
+                        ReflogLookup::Date(_) => 0,
                     },
-                    None => {
-                        self.err.push(Error::MissingRefLog {
-                            reference: r.name().as_bstr().into(),
-                            action: "lookup entry",
-                        });
-                        None
-                    }
-                }
+                    available,
+                });
+                return None;
             }
-        }
+            Err(err) => {
+                self.err.push(crate::reference::log::Error::from(err).into());
+                return None;
+            }
+        };
+        self.objs[self.idx].get_or_insert_with(HashSet::default).insert(id);
+        self.reflog_entries[self.idx] = Some(entry);
+        Some(())
     }
 
     fn nth_checked_out_branch(&mut self, branch_no: usize) -> Option<()> {
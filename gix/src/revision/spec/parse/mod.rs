@@ -53,6 +53,8 @@ struct Delegate<'repo> {
     paths: [Option<(BString, gix_object::tree::EntryMode)>; 2],
     /// The originally encountered ambiguous objects for potential later use in errors.
     ambiguous_objects: [Option<HashSet<ObjectId>>; 2],
+    /// The reference log entries used in specs like `@{1}` or `@{yesterday}`.
+    reflog_entries: [Option<crate::reference::log::Entry>; 2],
     idx: usize,
     kind: Option<gix_revision::spec::Kind>,
 
@@ -1,7 +1,9 @@
+use std::sync::atomic::AtomicBool;
+
 use gix_hash::ObjectId;
 use gix_object::FindExt;
 
-use crate::{ext::ObjectIdExt, revision, Repository};
+use crate::{ext::ObjectIdExt, revision, util::OwnedOrStaticAtomicBool, Repository};
 
 /// The error returned by [`Platform::all()`].
 #[derive(Debug, thiserror::Error)]
@@ -95,6 +97,7 @@ pub struct Platform<'repo> {
     pub(crate) parents: gix_traverse::commit::Parents,
     pub(crate) use_commit_graph: Option<bool>,
     pub(crate) commit_graph: Option<gix_commitgraph::Graph>,
+    pub(crate) should_interrupt: Option<OwnedOrStaticAtomicBool>,
 }
 
 impl<'repo> Platform<'repo> {
@@ -106,6 +109,7 @@ impl<'repo> Platform<'repo> {
             parents: Default::default(),
             use_commit_graph: None,
             commit_graph: None,
+            should_interrupt: None,
         }
     }
 }
@@ -143,6 +147,24 @@ impl<'repo> Platform<'repo> {
         self.commit_graph = graph;
         self
     }
+
+    /// Set the interrupt flag to `should_interrupt`, which typically is an application-wide flag
+    /// that is ultimately controlled by user interrupts.
+    ///
+    /// If it is `true`, the iteration yields [`iter::Error::Interrupted`] once and stops.
+    pub fn should_interrupt_shared(mut self, should_interrupt: &'static AtomicBool) -> Self {
+        self.should_interrupt = Some(OwnedOrStaticAtomicBool::Shared(should_interrupt));
+        self
+    }
+
+    /// Set the interrupt flag to `should_interrupt`, as controlled by the caller, for instance with an
+    /// [interrupt token](crate::interrupt::Token::shared_flag()).
+    ///
+    /// If it is `true`, the iteration yields [`iter::Error::Interrupted`] once and stops.
+    pub fn should_interrupt_owned(mut self, should_interrupt: std::sync::Arc<AtomicBool>) -> Self {
+        self.should_interrupt = Some(should_interrupt.into());
+        self
+    }
 }
 
 /// Produce the iterator
@@ -162,9 +184,12 @@ impl<'repo> Platform<'repo> {
             parents,
             use_commit_graph,
             commit_graph,
+            should_interrupt,
         } = self;
         Ok(revision::Walk {
             repo,
+            should_interrupt,
+            is_interrupted: false,
             inner: Box::new(
                 gix_traverse::commit::Simple::filtered(tips, &repo.objects, {
                     // Note that specific shallow handling for commit-graphs isn't needed as these contain
@@ -217,21 +242,41 @@ impl<'repo> Platform<'repo> {
     }
 }
 
-pub(crate) mod iter {
+///
+pub mod iter {
+    /// The error returned by the [`Walk`] iterator.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        SimpleTraversal(#[from] gix_traverse::commit::simple::Error),
+        #[error("The walk was interrupted")]
+        Interrupted,
+    }
+
     /// The iterator returned by [`crate::revision::walk::Platform::all()`].
     pub struct Walk<'repo> {
         pub(crate) repo: &'repo crate::Repository,
         pub(crate) inner:
             Box<dyn Iterator<Item = Result<gix_traverse::commit::Info, gix_traverse::commit::simple::Error>> + 'repo>,
+        pub(crate) should_interrupt: Option<crate::util::OwnedOrStaticAtomicBool>,
+        pub(crate) is_interrupted: bool,
     }
 
     impl<'repo> Iterator for Walk<'repo> {
-        type Item = Result<super::Info<'repo>, gix_traverse::commit::simple::Error>;
+        type Item = Result<super::Info<'repo>, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
+            if self.is_interrupted {
+                return None;
+            }
+            if matches!(&self.should_interrupt, Some(flag) if flag.load(std::sync::atomic::Ordering::Relaxed)) {
+                self.is_interrupted = true;
+                return Some(Err(Error::Interrupted));
+            }
             self.inner
                 .next()
-                .map(|res| res.map(|info| super::Info::new(info, self.repo)))
+                .map(|res| res.map(|info| super::Info::new(info, self.repo)).map_err(Into::into))
         }
     }
 }
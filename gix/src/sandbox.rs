@@ -0,0 +1,473 @@
+//! Create objects and update references in memory, for instance to find out if branches can be merged without leaving
+//! any trace in the repository.
+//!
+//! Use [`Repository::sandbox()`](crate::Repository::sandbox()) to create a [`Sandbox`], and either drop it to discard
+//! everything that was done in it, or [persist](Sandbox::persist()) all objects and reference updates into the repository.
+use std::{collections::BTreeMap, sync::atomic::AtomicBool};
+
+use gix_features::progress::DynNestedProgress;
+use gix_hash::ObjectId;
+use gix_object::{FindExt, WriteTo};
+use gix_odb::Write;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Category, FullName, FullNameRef, Target,
+};
+
+use crate::{
+    bstr::BString,
+    merge::{blob, tree, trees_to_index, FastForward},
+    repository::merge::write_tree_with,
+    Repository,
+};
+
+/// Objects and reference updates that are kept in memory on top of a repository.
+///
+/// Objects and references of the repository are visible through the sandbox, but everything written to it stays in memory
+/// until it's [persisted](Sandbox::persist()), and is discarded when the sandbox is dropped otherwise.
+pub struct Sandbox<'repo> {
+    repo: &'repo Repository,
+    objects: gix_odb::memory::Proxy<crate::OdbHandle>,
+    refs: BTreeMap<FullName, Update>,
+}
+
+/// A change to a direct reference, as recorded in a sandbox.
+struct Update {
+    /// The value of the reference in the repository when it was first changed in the sandbox, or `None` if it didn't exist.
+    previous: Option<ObjectId>,
+    /// The value of the reference in the sandbox, or `None` if it was deleted.
+    new: Option<ObjectId>,
+    /// The message for the reflog.
+    message: BString,
+}
+
+/// Lifecycle
+impl<'repo> Sandbox<'repo> {
+    pub(crate) fn new(repo: &'repo Repository) -> Self {
+        Sandbox {
+            repo,
+            objects: gix_odb::memory::Proxy::new(repo.objects.clone(), repo.object_hash()),
+            refs: Default::default(),
+        }
+    }
+
+    /// Return the repository this sandbox is placed on top of.
+    pub fn repo(&self) -> &'repo Repository {
+        self.repo
+    }
+
+    /// Write all objects created in the sandbox into the object database of the repository, and apply all reference updates
+    /// in a single transaction, returning the performed edits.
+    ///
+    /// The transaction fails if any of the changed references was changed in the repository since it was first changed
+    /// in the sandbox, in which case no reference is changed at all. The objects remain in the object database either way,
+    /// which is harmless as they aren't reachable.
+    pub fn persist(self) -> Result<Vec<RefEdit>, persist::Error> {
+        for (kind, data) in self.objects.take_object_memory().into_values() {
+            self.repo
+                .objects
+                .write_buf(kind, &data)
+                .map_err(crate::object::write::Error::from)?;
+        }
+        let edits: Vec<_> = self
+            .refs
+            .into_iter()
+            .filter(|(_, update)| update.previous != update.new)
+            .map(|(name, Update { previous, new, message })| {
+                let expected = match previous {
+                    Some(id) => PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                    None => PreviousValue::MustNotExist,
+                };
+                RefEdit {
+                    change: match new {
+                        Some(new) => Change::Update {
+                            log: LogChange {
+                                mode: RefLog::AndReference,
+                                force_create_reflog: false,
+                                message,
+                            },
+                            expected,
+                            new: Target::Peeled(new),
+                        },
+                        None => Change::Delete {
+                            expected,
+                            log: RefLog::AndReference,
+                        },
+                    },
+                    name,
+                    deref: false,
+                }
+            })
+            .collect();
+        if edits.is_empty() {
+            return Ok(edits);
+        }
+        Ok(self.repo.edit_references(edits)?)
+    }
+}
+
+/// Objects
+impl Sandbox<'_> {
+    /// Return the object database of the sandbox, which keeps written objects in memory and reads objects from memory
+    /// and from the repository.
+    pub fn objects(&self) -> &gix_odb::memory::Proxy<crate::OdbHandle> {
+        &self.objects
+    }
+
+    /// Write `object` into memory and return its id, unless it already exists in the sandbox or the repository.
+    pub fn write_object(&self, object: impl WriteTo) -> Result<ObjectId, crate::object::write::Error> {
+        let mut buf = Vec::new();
+        object.write_to(&mut buf).expect("write to memory works");
+        self.write_buf(object.kind(), &buf)
+    }
+
+    /// Write `bytes` as blob into memory and return its id, unless it already exists in the sandbox or the repository.
+    pub fn write_blob(&self, bytes: impl AsRef<[u8]>) -> Result<ObjectId, crate::object::write::Error> {
+        self.write_buf(gix_object::Kind::Blob, bytes.as_ref())
+    }
+
+    fn write_buf(&self, kind: gix_object::Kind, buf: &[u8]) -> Result<ObjectId, crate::object::write::Error> {
+        let id = gix_object::compute_hash(self.repo.object_hash(), kind, buf);
+        if gix_object::Exists::exists(&self.objects, &id) {
+            return Ok(id);
+        }
+        Ok(self.objects.write_buf(kind, buf)?)
+    }
+
+    /// Write a tree from all entries of `index`, which must not have conflicts, and return its id.
+    pub fn write_tree(&self, index: &gix_index::State) -> Result<ObjectId, crate::object::write::Error> {
+        write_tree_with(index, &mut |tree| self.write_object(tree))
+    }
+}
+
+/// References
+impl Sandbox<'_> {
+    /// Return the id the reference `name` points to in the sandbox, or `None` if it doesn't exist.
+    ///
+    /// Symbolic references are followed in the repository, so references changed in the sandbox are seen through `HEAD` as well.
+    pub fn reference_id(&self, name: &FullNameRef) -> Result<Option<ObjectId>, crate::reference::find::Error> {
+        let (name, id) = self.resolve(name)?;
+        Ok(match self.refs.get(&name) {
+            Some(update) => update.new,
+            None => id,
+        })
+    }
+
+    /// Point the reference `name`, or the reference it points to if it's symbolic, to `id` and record `message` for the reflog.
+    pub fn set_reference(
+        &mut self,
+        name: &FullNameRef,
+        id: impl Into<ObjectId>,
+        message: impl Into<BString>,
+    ) -> Result<(), crate::reference::find::Error> {
+        self.update(name, Some(id.into()), message.into())
+    }
+
+    /// Delete the reference `name`, or the reference it points to if it's symbolic.
+    pub fn delete_reference(&mut self, name: &FullNameRef) -> Result<(), crate::reference::find::Error> {
+        self.update(name, None, BString::default())
+    }
+
+    fn update(
+        &mut self,
+        name: &FullNameRef,
+        new: Option<ObjectId>,
+        message: BString,
+    ) -> Result<(), crate::reference::find::Error> {
+        let (name, previous) = self.resolve(name)?;
+        let update = self.refs.entry(name).or_insert(Update {
+            previous,
+            new: None,
+            message: BString::default(),
+        });
+        update.new = new;
+        update.message = message;
+        Ok(())
+    }
+
+    /// Follow symbolic references in the repository starting at `name`, and return the name of the direct reference
+    /// along with its target in the repository, or `None` if it doesn't exist.
+    fn resolve(&self, name: &FullNameRef) -> Result<(FullName, Option<ObjectId>), crate::reference::find::Error> {
+        let mut name = name.to_owned();
+        // Like `git`, give up on chains of symbolic references that are too long, which also handles cycles.
+        for _ in 0..5 {
+            match self.repo.try_find_reference(name.as_ref())? {
+                Some(reference) => match reference.inner.target {
+                    Target::Symbolic(next) => name = next,
+                    Target::Peeled(id) => return Ok((name, Some(id))),
+                },
+                None => break,
+            }
+        }
+        Ok((name, None))
+    }
+}
+
+/// Commits and merges
+impl Sandbox<'_> {
+    /// Create a commit with `message`, `tree` and `parents`, with author and committer as configured in the repository,
+    /// and point `reference` to it, just like [`Repository::commit()`] but without persisting anything.
+    ///
+    /// The first parent is expected to be the current target of `reference` in the sandbox, and if there is no parent,
+    /// `reference` is expected not to exist.
+    pub fn commit(
+        &mut self,
+        reference: &FullNameRef,
+        message: impl AsRef<str>,
+        tree: impl Into<ObjectId>,
+        parents: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<ObjectId, commit::Error> {
+        let parents: Vec<ObjectId> = parents.into_iter().map(Into::into).collect();
+        let actual = self.reference_id(reference)?;
+        let expected = parents.first().copied();
+        if actual.is_some() && actual != expected {
+            return Err(commit::Error::ReferenceMismatch {
+                name: reference.to_owned(),
+                expected,
+                actual,
+            });
+        }
+
+        let committer = self.repo.committer().ok_or(commit::Error::CommitterMissing)??;
+        let author = self.repo.author().ok_or(commit::Error::AuthorMissing)??;
+        let commit = gix_object::Commit {
+            message: message.as_ref().into(),
+            tree: tree.into(),
+            author: author.into(),
+            committer: committer.into(),
+            encoding: None,
+            parents: parents.into(),
+            extra_headers: Default::default(),
+        };
+        let id = self.write_object(&commit)?;
+        let message = crate::reference::log::message("commit", commit.message.as_ref(), commit.parents.len());
+        self.set_reference(reference, id, message)?;
+        Ok(id)
+    }
+
+    /// Return the best common ancestor of the commits `one` and `two`, or `None` if they don't share any history,
+    /// just like [`Repository::merge_base()`], but with commits in the sandbox taken into account.
+    pub fn merge_base(
+        &self,
+        one: impl Into<ObjectId>,
+        two: impl Into<ObjectId>,
+    ) -> Result<Option<ObjectId>, gix_revision::merge_base::Error> {
+        let mut graph = gix_revwalk::Graph::new(&self.objects, None);
+        let bases = gix_revision::merge_base(one.into(), &[two.into()], &mut graph)?;
+        Ok(bases.and_then(|bases| bases.into_iter().next()))
+    }
+
+    /// Merge the trees `ours` and `theirs` which have the tree `base` as common ancestor, just like
+    /// [`Repository::merge_trees_to_index()`], but write merged blobs into the sandbox.
+    pub fn merge_trees_to_index(
+        &self,
+        base: &gix_hash::oid,
+        ours: &gix_hash::oid,
+        theirs: &gix_hash::oid,
+        labels: blob::Labels<'_>,
+        options: tree::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<trees_to_index::Outcome, trees_to_index::Error> {
+        let tree::Outcome { index, conflicts } = tree::merge(
+            base,
+            ours,
+            theirs,
+            &self.objects,
+            labels,
+            options,
+            progress,
+            should_interrupt,
+        )?;
+        Ok(trees_to_index::Outcome {
+            index: gix_index::File::from_state(index, self.repo.index_path()),
+            conflicts,
+        })
+    }
+
+    /// Merge the commit `theirs` into the commit `reference` points to, similar to what [`Repository::merge()`] does,
+    /// but without a worktree and without persisting anything.
+    ///
+    /// If `reference` can be fast-forwarded and `options` allow it, or if it doesn't exist, it's set to `theirs`.
+    /// Otherwise the trees are merged using the best common ancestor, and if there were no conflicts, a merge commit
+    /// is created with the previous target of `reference` as first parent, and `reference` is pointed to it.
+    /// If there are conflicts, nothing is changed.
+    ///
+    /// `progress` is informed about the tree merge, which stops with an error once `should_interrupt` is `true`.
+    ///
+    /// ### Limitations
+    ///
+    /// * Renames are not detected, and only one merge-base is used even if there are multiple.
+    /// * `merge.log` isn't respected when generating merge messages.
+    pub fn merge(
+        &mut self,
+        reference: &FullNameRef,
+        theirs: impl Into<ObjectId>,
+        options: crate::merge::Options,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<merge::Outcome, merge::Error> {
+        let theirs = theirs.into();
+        let fast_forward = self.repo.fast_forward_or_configured(options.fast_forward)?;
+        let Some(ours) = self.reference_id(reference)? else {
+            self.set_reference(reference, theirs, "merge: Fast-forward")?;
+            return Ok(merge::Outcome::FastForward {
+                previous: None,
+                new: theirs,
+            });
+        };
+        let base = self.merge_base(ours, theirs)?.ok_or(merge::Error::UnrelatedHistories)?;
+        if base == theirs {
+            return Ok(merge::Outcome::UpToDate);
+        } else if base == ours && fast_forward != FastForward::Never {
+            self.set_reference(reference, theirs, "merge: Fast-forward")?;
+            return Ok(merge::Outcome::FastForward {
+                previous: Some(ours),
+                new: theirs,
+            });
+        } else if fast_forward == FastForward::Only {
+            return Err(merge::Error::NotPossibleToFastForward);
+        }
+
+        let mut buf = Vec::new();
+        let mut tree_id_of = |id: &ObjectId| self.objects.find_commit(id, &mut buf).map(|commit| commit.tree());
+        let (base_tree, ours_tree, theirs_tree) = (tree_id_of(&base)?, tree_id_of(&ours)?, tree_id_of(&theirs)?);
+        let short_name = reference.shorten();
+        let (base_label, theirs_label) = (base.to_hex_with_len(7).to_string(), theirs.to_string());
+        let merged = self.merge_trees_to_index(
+            &base_tree,
+            &ours_tree,
+            &theirs_tree,
+            blob::Labels {
+                ancestor: Some(base_label.as_str().into()),
+                current: Some(short_name),
+                other: Some(theirs_label.as_str().into()),
+            },
+            self.repo.tree_merge_options()?,
+            progress,
+            should_interrupt,
+        )?;
+        if merged.has_conflicts() {
+            return Ok(merge::Outcome::Conflict {
+                conflicts: merged.conflicts,
+            });
+        }
+
+        let tree = self.write_tree(&merged.index)?;
+        let message = match options.message {
+            Some(message) => message,
+            None => {
+                let mut message = format!("Merge commit '{theirs}'");
+                if let Some((Category::LocalBranch, branch)) = reference.category_and_short_name() {
+                    if branch != "main" && branch != "master" {
+                        message.push_str(&format!(" into {branch}"));
+                    }
+                }
+                message.push('\n');
+                message
+            }
+        };
+        let commit = self.commit(reference, message, tree, [ours, theirs])?;
+        Ok(merge::Outcome::Merge { commit })
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod commit {
+    use gix_hash::ObjectId;
+
+    /// The error returned by [`Sandbox::commit()`](super::Sandbox::commit()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ParseTime(#[from] crate::config::time::Error),
+        #[error("Committer identity is not configured")]
+        CommitterMissing,
+        #[error("Author identity is not configured")]
+        AuthorMissing,
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+        #[error("Reference '{name}' was expected to point to {expected:?}, but it points to {actual:?}")]
+        ReferenceMismatch {
+            name: gix_ref::FullName,
+            expected: Option<ObjectId>,
+            actual: Option<ObjectId>,
+        },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge {
+    use gix_hash::ObjectId;
+
+    /// The outcome of [`Sandbox::merge()`](super::Sandbox::merge()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Outcome {
+        /// All commits to merge were already contained in the reference, so nothing was done.
+        UpToDate,
+        /// The reference was fast-forwarded to the commit to merge.
+        FastForward {
+            /// The commit the reference pointed to before, or `None` if it didn't exist.
+            previous: Option<ObjectId>,
+            /// The commit the reference points to now.
+            new: ObjectId,
+        },
+        /// A merge commit was created and the reference points to it.
+        Merge {
+            /// The id of the newly created merge commit.
+            commit: ObjectId,
+        },
+        /// The merge couldn't be completed automatically, and no commit was created.
+        ///
+        /// Use [`Sandbox::merge_trees_to_index()`](super::Sandbox::merge_trees_to_index()) to obtain the merged index.
+        Conflict {
+            /// All paths that couldn't be merged automatically, sorted by path.
+            conflicts: Vec<crate::merge::tree::Conflict>,
+        },
+    }
+
+    /// The error returned by [`Sandbox::merge()`](super::Sandbox::merge()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Refusing to merge unrelated histories")]
+        UnrelatedHistories,
+        #[error("Not possible to fast-forward, aborting")]
+        NotPossibleToFastForward,
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+        #[error(transparent)]
+        FindCommit(#[from] gix_object::find::existing_object::Error),
+        #[error(transparent)]
+        ConfigValue(#[from] crate::config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        TreeMergeOptions(#[from] crate::merge::tree_options::Error),
+        #[error(transparent)]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+        #[error(transparent)]
+        MergeTrees(#[from] crate::merge::trees_to_index::Error),
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        Commit(#[from] super::commit::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod persist {
+    /// The error returned by [`Sandbox::persist()`](super::Sandbox::persist()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        EditReferences(#[from] crate::reference::edit::Error),
+    }
+}
@@ -0,0 +1,344 @@
+//! Handlers for the [smart HTTP protocol](https://git-scm.com/docs/http-protocol), similar to
+//! [`git http-backend`](https://git-scm.com/docs/git-http-backend), which can be embedded into any web framework.
+//!
+//! Route all requests below a repository to [`handle()`], for instance by finding the repository and the [`Endpoint`] with
+//! [`split_path()`]. Then send the status and headers of the returned [`Response`] and stream its body with
+//! [`Response::write_body()`]. As the length of the body isn't known upfront, servers typically send it with
+//! chunked transfer encoding.
+//!
+//! All operations are blocking, so in async frameworks like `axum` or `hyper` they should run on a thread for blocking
+//! operations, writing the body into a channel that is streamed to the client.
+//! Only protocol V1 is supported, which is also used by clients which ask for protocol V2.
+use std::{
+    io::{Read, Write},
+    sync::atomic::AtomicBool,
+};
+
+use crate::serve::{advertise, receive_pack, upload_pack};
+
+/// The error returned by [`Response::write_body()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Advertise(#[from] advertise::Error),
+    #[error(transparent)]
+    UploadPack(#[from] upload_pack::Error),
+    #[error(transparent)]
+    ReceivePack(#[from] receive_pack::Error),
+}
+
+/// A service that can be requested by clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Service {
+    /// Send packs to clients that fetch, see [`upload_pack`].
+    UploadPack,
+    /// Receive packs from clients that push, see [`receive_pack`].
+    ReceivePack,
+}
+
+impl Service {
+    /// Return the name of the service as used in URLs and content types.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Service::UploadPack => "git-upload-pack",
+            Service::ReceivePack => "git-receive-pack",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "git-upload-pack" => Service::UploadPack,
+            "git-receive-pack" => Service::ReceivePack,
+            _ => return None,
+        })
+    }
+}
+
+/// The location below a repository that is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// `info/refs`, which advertises references for the service named in the query.
+    InfoRefs,
+    /// `git-upload-pack` or `git-receive-pack`, which runs the service.
+    Service(Service),
+}
+
+/// Split `path`, like `/user/repo.git/info/refs`, into the path of the repository, like `/user/repo.git`, and the
+/// endpoint of the smart HTTP protocol that is requested, or return `None` if it isn't such an endpoint.
+pub fn split_path(path: &str) -> Option<(&str, Endpoint)> {
+    [
+        ("/info/refs", Endpoint::InfoRefs),
+        ("/git-upload-pack", Endpoint::Service(Service::UploadPack)),
+        ("/git-receive-pack", Endpoint::Service(Service::ReceivePack)),
+    ]
+    .into_iter()
+    .find_map(|(suffix, endpoint)| path.strip_suffix(suffix).map(|repo_path| (repo_path, endpoint)))
+}
+
+/// Options for use in [`handle()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// If `true`, clients may fetch from the repository. This is the default.
+    pub upload_pack: bool,
+    /// If `true`, clients may push to the repository.
+    ///
+    /// It's disabled by default as clients should be authenticated by the caller first.
+    pub receive_pack: bool,
+    /// The maximum amount of bytes to read from the body of a request after decompressing it, or `None` to read it entirely.
+    ///
+    /// It's unlimited by default as pushed packs can be arbitrarily large, so callers that serve untrusted clients should
+    /// set it, or limit the size of request bodies themselves.
+    pub max_request_body_size: Option<u64>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            upload_pack: true,
+            receive_pack: false,
+            max_request_body_size: None,
+        }
+    }
+}
+
+impl Options {
+    fn is_enabled(&self, service: Service) -> bool {
+        match service {
+            Service::UploadPack => self.upload_pack,
+            Service::ReceivePack => self.receive_pack,
+        }
+    }
+}
+
+/// An HTTP request to an [`Endpoint`] of a repository.
+#[derive(Debug)]
+pub struct Request<'a, R> {
+    /// The HTTP method, like `GET` or `POST`.
+    pub method: &'a str,
+    /// The endpoint that is requested.
+    pub endpoint: Endpoint,
+    /// The query string of the URL without the leading `?`, like `service=git-upload-pack`.
+    pub query: Option<&'a str>,
+    /// The value of the `Content-Type` header.
+    pub content_type: Option<&'a str>,
+    /// The value of the `Content-Encoding` header, which may be `gzip` for compressed request bodies.
+    pub content_encoding: Option<&'a str>,
+    /// The body of the request.
+    pub body: R,
+}
+
+/// The response to a [`Request`], whose status and headers should be sent before [writing its body](Response::write_body()).
+pub struct Response<'repo, R> {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The HTTP headers as pairs of name and value.
+    pub headers: Vec<(&'static str, String)>,
+    repo: &'repo crate::Repository,
+    body: Body<R>,
+}
+
+enum Body<R> {
+    Message(String),
+    Advertisement(Service),
+    Service {
+        service: Service,
+        body: R,
+        gzip: bool,
+        max_size: Option<u64>,
+    },
+}
+
+/// Information about what was sent with [`Response::write_body()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// A message explaining why the request couldn't be served.
+    Message,
+    /// The references of the repository were advertised for the given service.
+    Advertisement(Service),
+    /// A fetch was served.
+    UploadPack(upload_pack::Outcome),
+    /// A push was served.
+    ReceivePack(receive_pack::Outcome),
+}
+
+/// Determine how to respond to `request` for `repo` according to `options`.
+pub fn handle<'repo, R: Read>(
+    repo: &'repo crate::Repository,
+    request: Request<'_, R>,
+    options: &Options,
+) -> Response<'repo, R> {
+    let message = |status: u16, message: String| Response {
+        status,
+        headers: with_no_cache_headers(vec![("Content-Type", "text/plain; charset=utf-8".into())]),
+        repo,
+        body: Body::Message(message + "\n"),
+    };
+    let expected_method = match request.endpoint {
+        Endpoint::InfoRefs => "GET",
+        Endpoint::Service(_) => "POST",
+    };
+    if request.method != expected_method {
+        let mut response = message(405, format!("Method not allowed: {}", request.method));
+        response.headers.push(("Allow", expected_method.into()));
+        return response;
+    }
+
+    match request.endpoint {
+        Endpoint::InfoRefs => {
+            let service = request
+                .query
+                .unwrap_or_default()
+                .split('&')
+                .find_map(|parameter| parameter.strip_prefix("service="));
+            let Some(service) = service else {
+                return message(403, "The dumb HTTP protocol isn't supported".into());
+            };
+            let Some(service) = Service::from_name(service).filter(|service| options.is_enabled(*service)) else {
+                return message(403, format!("Service not enabled: '{service}'"));
+            };
+            Response {
+                status: 200,
+                headers: with_no_cache_headers(vec![(
+                    "Content-Type",
+                    format!("application/x-{}-advertisement", service.as_str()),
+                )]),
+                repo,
+                body: Body::Advertisement(service),
+            }
+        }
+        Endpoint::Service(service) => {
+            if !options.is_enabled(service) {
+                return message(403, format!("Service not enabled: '{}'", service.as_str()));
+            }
+            let expected_content_type = format!("application/x-{}-request", service.as_str());
+            if request.content_type != Some(expected_content_type.as_str()) {
+                return message(
+                    415,
+                    format!(
+                        "Expected content type '{expected_content_type}', got {:?}",
+                        request.content_type
+                    ),
+                );
+            }
+            let gzip = match request.content_encoding {
+                None | Some("identity") => false,
+                Some("gzip" | "x-gzip") => true,
+                Some(encoding) => return message(415, format!("Unsupported content encoding: '{encoding}'")),
+            };
+            Response {
+                status: 200,
+                headers: with_no_cache_headers(vec![(
+                    "Content-Type",
+                    format!("application/x-{}-result", service.as_str()),
+                )]),
+                repo,
+                body: Body::Service {
+                    service,
+                    body: request.body,
+                    gzip,
+                    max_size: options.max_request_body_size,
+                },
+            }
+        }
+    }
+}
+
+fn with_no_cache_headers(mut headers: Vec<(&'static str, String)>) -> Vec<(&'static str, String)> {
+    headers.extend([
+        ("Cache-Control", "no-cache, max-age=0, must-revalidate".into()),
+        ("Expires", "Fri, 01 Jan 1980 00:00:00 GMT".into()),
+        ("Pragma", "no-cache".into()),
+    ]);
+    headers
+}
+
+impl<R: Read> Response<'_, R> {
+    /// Write the body of this response to `out`, which reads the body of the request if needed and may change the repository
+    /// if a push is served.
+    ///
+    /// `should_interrupt` can be set to stop sending or receiving packs.
+    pub fn write_body(self, mut out: impl Write, should_interrupt: &AtomicBool) -> Result<Outcome, Error> {
+        let outcome = match self.body {
+            Body::Message(message) => {
+                out.write_all(message.as_bytes())?;
+                Outcome::Message
+            }
+            Body::Advertisement(service) => {
+                use gix_protocol::transport::packetline::encode;
+                encode::text_to_write(format!("# service={}", service.as_str()).as_bytes(), &mut out)?;
+                encode::flush_to_write(&mut out)?;
+                match service {
+                    Service::UploadPack => {
+                        upload_pack::advertise_refs(self.repo, &mut out)?;
+                    }
+                    Service::ReceivePack => receive_pack::advertise_refs(self.repo, &mut out)?,
+                }
+                Outcome::Advertisement(service)
+            }
+            Body::Service {
+                service,
+                body,
+                gzip,
+                max_size,
+            } => {
+                let mut body: Box<dyn Read> = if gzip {
+                    Box::new(flate2::read::GzDecoder::new(body))
+                } else {
+                    Box::new(body)
+                };
+                if let Some(max_size) = max_size {
+                    body = Box::new(LimitedRead {
+                        inner: body,
+                        max_size,
+                        remaining: max_size,
+                    });
+                }
+                match service {
+                    Service::UploadPack => {
+                        // The references may have changed since they were advertised, in which case the client may not get
+                        // what it wants.
+                        let advertisement = upload_pack::advertise_refs(self.repo, std::io::sink())?;
+                        Outcome::UploadPack(upload_pack::run(
+                            self.repo,
+                            &advertisement,
+                            body,
+                            &mut out,
+                            upload_pack::Options { stateless: true },
+                            should_interrupt,
+                        )?)
+                    }
+                    Service::ReceivePack => {
+                        Outcome::ReceivePack(receive_pack::run(self.repo, body, &mut out, should_interrupt)?)
+                    }
+                }
+            }
+        };
+        out.flush()?;
+        Ok(outcome)
+    }
+}
+
+/// A reader that fails once more than `max_size` bytes were read from `inner`.
+struct LimitedRead<R> {
+    inner: R,
+    max_size: u64,
+    remaining: u64,
+}
+
+impl<R: Read> Read for LimitedRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let max = usize::try_from(self.remaining.saturating_add(1)).map_or(buf.len(), |max| max.min(buf.len()));
+        let num_read = self.inner.read(&mut buf[..max])?;
+        if num_read as u64 > self.remaining {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("The request body is larger than {} bytes", self.max_size),
+            ));
+        }
+        self.remaining -= num_read as u64;
+        Ok(num_read)
+    }
+}
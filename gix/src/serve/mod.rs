@@ -0,0 +1,148 @@
+//! Serve repositories to `git` clients, which is the server side of fetches and pushes.
+//!
+//! [`upload_pack`] sends packs to clients that fetch or clone, while [`receive_pack`] receives packs from clients that
+//! push and updates references accordingly. Both speak protocol V1 and work over any bidirectional stream, and
+//! [`http`] provides handlers for the smart HTTP protocol on top of them which can be embedded into web frameworks.
+#![allow(clippy::result_large_err)]
+use std::io::Write;
+
+use gix_hash::ObjectId;
+use gix_protocol::transport::packetline::{self, encode};
+
+use crate::bstr::{BStr, BString};
+
+pub mod http;
+pub mod receive_pack;
+pub mod upload_pack;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod advertise {
+    /// The error returned by [`upload_pack::advertise_refs()`](super::upload_pack::advertise_refs()) and
+    /// [`receive_pack::advertise_refs()`](super::receive_pack::advertise_refs()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        InitReferences(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        IterateReferences(#[from] crate::reference::iter::init::Error),
+        #[error(transparent)]
+        DecodeReference(Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+}
+
+/// A reference to advertise, along with the object it points to after peeling if it differs.
+struct Ref {
+    name: BString,
+    id: ObjectId,
+    peeled: Option<ObjectId>,
+}
+
+/// Return all references of `repo` which point to an object, preceded by `HEAD` if `include_head` is `true`.
+fn references(repo: &crate::Repository, include_head: bool) -> Result<Vec<Ref>, advertise::Error> {
+    let mut refs = Vec::new();
+    if include_head {
+        if let Some(head) = repo.head_ref()? {
+            refs.push((BString::from("HEAD"), head));
+        }
+    }
+    let platform = repo.references()?;
+    for reference in platform.all()? {
+        let reference = reference.map_err(advertise::Error::DecodeReference)?;
+        refs.push((reference.name().as_bstr().to_owned(), reference));
+    }
+
+    Ok(refs
+        .into_iter()
+        .filter_map(|(name, mut reference)| {
+            let id = follow_to_id(&reference)?;
+            let peeled = reference.peel_to_id_in_place().ok()?.detach();
+            Some(Ref {
+                name,
+                id,
+                peeled: (peeled != id).then_some(peeled),
+            })
+        })
+        .collect())
+}
+
+/// Return the id `reference` points to, after following all symbolic references.
+fn follow_to_id(reference: &crate::Reference<'_>) -> Option<ObjectId> {
+    let mut reference = reference.clone();
+    for _ in 0..5 {
+        if let Some(id) = reference.try_id() {
+            return Some(id.detach());
+        }
+        reference = reference.follow()?.ok()?;
+    }
+    None
+}
+
+/// Write a line advertising `name` to point to `id` to `out`, followed by `capabilities` if set.
+fn write_ref_line(
+    buf: &mut Vec<u8>,
+    id: &gix_hash::oid,
+    name: &BStr,
+    capabilities: Option<&str>,
+    out: &mut dyn Write,
+) -> std::io::Result<()> {
+    buf.clear();
+    buf.extend_from_slice(id.to_hex().to_string().as_bytes());
+    buf.push(b' ');
+    buf.extend_from_slice(name);
+    if let Some(capabilities) = capabilities {
+        buf.push(0);
+        buf.extend_from_slice(capabilities.as_bytes());
+    }
+    buf.push(b'\n');
+    encode::data_to_write(buf, out).map(|_| ())
+}
+
+/// Write the line that carries `capabilities` if there is no reference to advertise.
+fn write_capabilities_line(
+    buf: &mut Vec<u8>,
+    object_hash: gix_hash::Kind,
+    capabilities: &str,
+    out: &mut dyn Write,
+) -> std::io::Result<()> {
+    write_ref_line(
+        buf,
+        &object_hash.null(),
+        "capabilities^{}".into(),
+        Some(capabilities),
+        out,
+    )
+}
+
+/// The value of the `agent` capability.
+fn agent() -> String {
+    format!("agent=gix/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Write everything as packet lines on the data channel if `max_data_len` is set, or as is otherwise.
+struct Sideband<W> {
+    out: W,
+    max_data_len: Option<usize>,
+}
+
+impl<W: Write> Write for Sideband<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.max_data_len {
+            Some(max_data_len) => {
+                let len = buf.len().min(max_data_len);
+                encode::band_to_write(packetline::Channel::Data, &buf[..len], &mut self.out)?;
+                Ok(len)
+            }
+            None => self.out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
@@ -0,0 +1,266 @@
+//! Receive packs from clients that push and update references accordingly, similar to
+//! [`git receive-pack`](https://git-scm.com/docs/git-receive-pack).
+//!
+//! Only protocol V1 is supported, without hooks, push options or atomic pushes, and references are updated one by one.
+//! Just like `git` does by default, the branch that is checked out in a non-bare repository can't be updated.
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    sync::atomic::AtomicBool,
+};
+
+use gix_hash::ObjectId;
+use gix_protocol::transport::packetline::{encode, PacketLineRef, StreamingPeekableIter};
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    serve::advertise,
+};
+
+/// The error returned by [`run()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    DecodePacketLine(#[from] gix_protocol::transport::packetline::decode::Error),
+    #[error("Expected a command like '<old-id> <new-id> <ref>', got {line:?}")]
+    InvalidCommand { line: BString },
+    #[error(transparent)]
+    DecodeId(#[from] gix_hash::decode::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error(transparent)]
+    References(#[from] advertise::Error),
+}
+
+/// A reference update requested by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// The id the client expects the reference to point to, or the null id if it expects it not to exist.
+    pub previous: ObjectId,
+    /// The id the reference should point to, or the null id if it should be deleted.
+    pub new: ObjectId,
+    /// The full name of the reference to update.
+    pub name: BString,
+}
+
+/// Information about a [`run()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The commands that were performed successfully.
+    pub updated: Vec<Command>,
+    /// The commands that couldn't be performed, along with the reason.
+    pub rejected: Vec<(Command, String)>,
+    /// The amount of objects in the pack that was received, if there was one.
+    pub num_objects: Option<u32>,
+}
+
+/// Write all references of `repo` to `out`.
+pub fn advertise_refs(repo: &crate::Repository, mut out: impl Write) -> Result<(), advertise::Error> {
+    let capabilities = format!("report-status delete-refs ofs-delta {}", super::agent());
+    let mut buf = Vec::new();
+    let mut is_first = true;
+    for reference in super::references(repo, false)? {
+        super::write_ref_line(
+            &mut buf,
+            &reference.id,
+            reference.name.as_bstr(),
+            is_first.then_some(capabilities.as_str()),
+            &mut out,
+        )?;
+        is_first = false;
+    }
+    if is_first {
+        super::write_capabilities_line(&mut buf, repo.object_hash(), &capabilities, &mut out)?;
+    }
+    encode::flush_to_write(&mut out)?;
+    Ok(())
+}
+
+/// Read the reference updates the client requests from `input`, followed by the pack with the objects they need,
+/// write the pack to the object database of `repo` and update all references whose update is valid.
+/// Just like `git`, references are only updated if all objects reachable from their new value that aren't reachable
+/// from existing references are present.
+///
+/// If the client asks for it, the status of each update is written to `out`.
+/// `should_interrupt` can be set to stop receiving the pack.
+pub fn run(
+    repo: &crate::Repository,
+    input: impl Read,
+    mut out: impl Write,
+    should_interrupt: &AtomicBool,
+) -> Result<Outcome, Error> {
+    let mut reader = StreamingPeekableIter::new(input, &[PacketLineRef::Flush], false);
+    let mut commands = Vec::new();
+    let mut report_status = false;
+    while let Some(line) = reader.read_line() {
+        let line = line??;
+        let Some(line) = line.as_bstr() else {
+            return Err(Error::InvalidCommand {
+                line: "a special packet line".into(),
+            });
+        };
+        let line = line.trim_end_with(|c| c == '\n');
+        let (command, capabilities) = line.split_once_str("\0").unwrap_or((line, b""));
+        if commands.is_empty() {
+            report_status = capabilities.split_str(" ").any(|cap| cap == b"report-status");
+        }
+        commands.push(parse_command(command.as_bstr())?);
+    }
+    let mut outcome = Outcome::default();
+    if commands.is_empty() {
+        return Ok(outcome);
+    }
+
+    let mut keep_path = None;
+    if commands.iter().any(|command| !command.new.is_null()) {
+        let mut pack = BufReader::new(reader.into_inner());
+        if !pack.fill_buf()?.is_empty() {
+            let written = gix_pack::Bundle::write_to_directory(
+                &mut pack,
+                Some(&repo.objects.store_ref().path().join("pack")),
+                &mut gix_features::progress::Discard,
+                should_interrupt,
+                Some(repo.objects.clone()),
+                gix_pack::bundle::write::Options {
+                    object_hash: repo.object_hash(),
+                    fsync_pack: repo.config.fsync(|components| components.packs),
+                    fsync_index: repo.config.fsync(|components| components.pack_metadata),
+                    ..Default::default()
+                },
+            );
+            match written {
+                Ok(written) => {
+                    outcome.num_objects = Some(written.index.num_objects);
+                    keep_path = written.keep_path;
+                }
+                Err(err) => {
+                    if report_status {
+                        encode::text_to_write(format!("unpack {err}").as_bytes(), &mut out)?;
+                        for command in &commands {
+                            encode::text_to_write(format!("ng {} unpacker error", command.name).as_bytes(), &mut out)?;
+                        }
+                        encode::flush_to_write(&mut out)?;
+                        out.flush()?;
+                    }
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    let checked_out = if repo.is_bare() {
+        None
+    } else {
+        repo.head_name().ok().flatten()
+    };
+    let existing_commits: Vec<_> = super::references(repo, false)?
+        .into_iter()
+        .map(|reference| reference.peeled.unwrap_or(reference.id))
+        .filter(|id| matches!(repo.find_header(*id), Ok(header) if header.kind() == gix_object::Kind::Commit))
+        .collect();
+    let mut report = Vec::new();
+    encode::text_to_write(b"unpack ok", &mut report)?;
+    for command in commands {
+        match update_reference(repo, &command, checked_out.as_ref(), &existing_commits) {
+            Ok(()) => {
+                encode::text_to_write(format!("ok {}", command.name).as_bytes(), &mut report)?;
+                outcome.updated.push(command);
+            }
+            Err(reason) => {
+                encode::text_to_write(format!("ng {} {reason}", command.name).as_bytes(), &mut report)?;
+                outcome.rejected.push((command, reason));
+            }
+        }
+    }
+    encode::flush_to_write(&mut report)?;
+    if let Some(keep_path) = keep_path {
+        std::fs::remove_file(keep_path)?;
+    }
+
+    if report_status {
+        out.write_all(&report)?;
+    }
+    out.flush()?;
+    Ok(outcome)
+}
+
+fn parse_command(line: &BStr) -> Result<Command, Error> {
+    let mut tokens = line.splitn_str(3, " ");
+    let (Some(previous), Some(new), Some(name)) = (tokens.next(), tokens.next(), tokens.next()) else {
+        return Err(Error::InvalidCommand { line: line.into() });
+    };
+    Ok(Command {
+        previous: ObjectId::from_hex(previous)?,
+        new: ObjectId::from_hex(new)?,
+        name: name.into(),
+    })
+}
+
+/// Perform `command` in `repo` unless it would change the `checked_out` branch or the new object isn't connected to
+/// `existing_commits`, and return the reason for rejecting it otherwise.
+fn update_reference(
+    repo: &crate::Repository,
+    command: &Command,
+    checked_out: Option<&FullName>,
+    existing_commits: &[ObjectId],
+) -> Result<(), String> {
+    let name = FullName::try_from(command.name.clone())
+        .ok()
+        .filter(|name| name.as_bstr().starts_with(b"refs/"))
+        .ok_or_else(|| "funny refname".to_string())?;
+    if checked_out == Some(&name) {
+        return Err("branch is currently checked out".into());
+    }
+    if !command.new.is_null() && !is_connected(repo, command.new, existing_commits) {
+        return Err("missing necessary objects".into());
+    }
+    let expected = if command.previous.is_null() {
+        PreviousValue::MustNotExist
+    } else {
+        PreviousValue::MustExistAndMatch(Target::Peeled(command.previous))
+    };
+    let change = if command.new.is_null() {
+        Change::Delete {
+            expected,
+            log: RefLog::AndReference,
+        }
+    } else {
+        Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: "push".into(),
+            },
+            expected,
+            new: Target::Peeled(command.new),
+        }
+    };
+    repo.edit_reference(RefEdit {
+        change,
+        name,
+        deref: false,
+    })
+    .map(|_| ())
+    .map_err(|err| err.to_string())
+}
+
+/// Return `true` if `id` exists along with all objects reachable from it that aren't reachable from `existing_commits`.
+fn is_connected(repo: &crate::Repository, id: ObjectId, existing_commits: &[ObjectId]) -> bool {
+    let Ok(object) = repo.find_object(id).and_then(crate::Object::peel_tags_to_end) else {
+        return false;
+    };
+    let outcome = match object.kind {
+        gix_object::Kind::Commit => {
+            gix_traverse::connectivity::check([object.id], existing_commits.iter().copied(), &repo.objects, None)
+        }
+        gix_object::Kind::Tree => gix_traverse::connectivity::check_tree(object.id, &repo.objects),
+        gix_object::Kind::Blob | gix_object::Kind::Tag => return true,
+    };
+    outcome.map_or(false, |outcome| outcome.is_connected())
+}
@@ -0,0 +1,316 @@
+//! Send packs to clients that fetch or clone, similar to [`git upload-pack`](https://git-scm.com/docs/git-upload-pack).
+//!
+//! Only protocol V1 is supported, without shallow clones or filters and without telling clients when enough common
+//! commits were found, which is enough for all clients to clone and fetch.
+use std::{
+    collections::HashSet,
+    io::{BufWriter, Read, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_hash::ObjectId;
+use gix_pack::data::output;
+use gix_protocol::transport::packetline::{encode, PacketLineRef, StreamingPeekableIter};
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    serve::{advertise, Sideband},
+};
+
+/// The error returned by [`run()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    DecodePacketLine(#[from] gix_protocol::transport::packetline::decode::Error),
+    #[error("Expected {expected}, got {line:?}")]
+    UnexpectedLine { expected: &'static str, line: BString },
+    #[error("The client disconnected during negotiation")]
+    Disconnected,
+    #[error(transparent)]
+    DecodeId(#[from] gix_hash::decode::Error),
+    #[error("The client wanted {id} which wasn't advertised")]
+    NotAdvertised { id: ObjectId },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    FindHeader(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    TraverseCommits(#[from] gix_traverse::commit::reachable::Error),
+    #[error(transparent)]
+    CountObjects(#[from] output::count::objects::Error),
+    #[error(transparent)]
+    CreateEntries(#[from] output::entry::iter_from_counts::Error),
+    #[error(transparent)]
+    WritePackData(#[from] output::bytes::Error<output::entry::iter_from_counts::Error>),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// The objects that were advertised to the client.
+#[derive(Debug, Default, Clone)]
+pub struct Advertisement {
+    /// The ids of all advertised objects, which are the only ones the client may want.
+    pub ids: HashSet<ObjectId>,
+    /// The ids of annotated tags along with the id of the object they point to after peeling.
+    pub tags: Vec<(ObjectId, ObjectId)>,
+}
+
+/// Options for use in [`run()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// If `true`, the negotiation ends after the first round of `have` lines unless the client is done, as the client
+    /// sends each round in a separate request when using stateless protocols like HTTP.
+    pub stateless: bool,
+}
+
+/// Information about a [`run()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The objects the client wanted.
+    pub wants: Vec<ObjectId>,
+    /// The amount of objects in the pack that was sent, or `None` if no pack was sent as the client didn't want anything
+    /// or the negotiation isn't complete yet in [stateless](Options::stateless) mode.
+    pub num_objects: Option<u32>,
+}
+
+/// Write all references of `repo` to `out`, starting with `HEAD`, along with the objects annotated tags point to,
+/// and return what was advertised.
+pub fn advertise_refs(repo: &crate::Repository, mut out: impl Write) -> Result<Advertisement, advertise::Error> {
+    let mut capabilities = format!(
+        "multi_ack multi_ack_detailed side-band side-band-64k ofs-delta include-tag {}",
+        super::agent()
+    );
+    if let Some(head) = repo.head_name()? {
+        capabilities.push_str(&format!(" symref=HEAD:{}", head.as_bstr()));
+    }
+
+    let mut advertised = Advertisement::default();
+    let mut buf = Vec::new();
+    for reference in super::references(repo, true)? {
+        super::write_ref_line(
+            &mut buf,
+            &reference.id,
+            reference.name.as_bstr(),
+            advertised.ids.is_empty().then_some(capabilities.as_str()),
+            &mut out,
+        )?;
+        advertised.ids.insert(reference.id);
+        if let Some(peeled) = reference.peeled {
+            let mut peeled_name = reference.name;
+            peeled_name.extend_from_slice(b"^{}");
+            super::write_ref_line(&mut buf, &peeled, peeled_name.as_bstr(), None, &mut out)?;
+            advertised.ids.insert(peeled);
+            advertised.tags.push((reference.id, peeled));
+        }
+    }
+    if advertised.ids.is_empty() {
+        super::write_capabilities_line(&mut buf, repo.object_hash(), &capabilities, &mut out)?;
+    }
+    encode::flush_to_write(&mut out)?;
+    Ok(advertised)
+}
+
+/// Read the objects the client wants from `input` after it received `advertisement`, negotiate which objects it already has
+/// and write a pack with all objects it is missing to `out`.
+///
+/// `options` control how the negotiation works, and `should_interrupt` can be set to stop sending the pack.
+pub fn run(
+    repo: &crate::Repository,
+    advertisement: &Advertisement,
+    input: impl Read,
+    mut out: impl Write,
+    options: Options,
+    should_interrupt: &AtomicBool,
+) -> Result<Outcome, Error> {
+    let mut reader = StreamingPeekableIter::new(input, &[PacketLineRef::Flush], false);
+    let mut outcome = Outcome::default();
+    let mut capabilities = BString::default();
+    while let Some(line) = reader.read_line() {
+        let line = line??;
+        let line = line.as_bstr().map(|line| line.trim_end_with(|c| c == '\n').as_bstr());
+        let Some(want) = line.and_then(|line| line.strip_prefix(b"want ")) else {
+            return Err(unexpected_line("a 'want' line", line));
+        };
+        let (hex, caps) = want.split_once_str(" ").unwrap_or((want, b""));
+        if outcome.wants.is_empty() {
+            capabilities = caps.into();
+        }
+        let id = ObjectId::from_hex(hex)?;
+        if !advertisement.ids.contains(&id) {
+            encode::error_to_write(format!("upload-pack: not our ref {id}").as_bytes(), &mut out)?;
+            out.flush()?;
+            return Err(Error::NotAdvertised { id });
+        }
+        outcome.wants.push(id);
+    }
+    if outcome.wants.is_empty() {
+        return Ok(outcome);
+    }
+
+    let capabilities: Vec<_> = capabilities.split_str(" ").collect();
+    let has_capability = |name: &str| capabilities.contains(&name.as_bytes());
+    let multi_ack = if has_capability("multi_ack_detailed") {
+        Some(" common")
+    } else if has_capability("multi_ack") {
+        Some(" continue")
+    } else {
+        None
+    };
+    let mut common = Vec::new();
+    reader.reset();
+    loop {
+        let Some(line) = reader.read_line() else {
+            if reader.stopped_at().is_none() {
+                return Err(Error::Disconnected);
+            }
+            if common.is_empty() || multi_ack.is_some() {
+                encode::text_to_write(b"NAK", &mut out)?;
+            }
+            out.flush()?;
+            if options.stateless {
+                return Ok(outcome);
+            }
+            reader.reset();
+            continue;
+        };
+        let line = line??;
+        let line = line.as_bstr().map(|line| line.trim_end_with(|c| c == '\n').as_bstr());
+        if line == Some(b"done".as_bstr()) {
+            match common.last() {
+                Some(id) if multi_ack.is_some() => encode::text_to_write(format!("ACK {id}").as_bytes(), &mut out)?,
+                Some(_) => 0,
+                None => encode::text_to_write(b"NAK", &mut out)?,
+            };
+            break;
+        }
+        let Some(have) = line.and_then(|line| line.strip_prefix(b"have ")) else {
+            return Err(unexpected_line("a 'have' or 'done' line", line));
+        };
+        let id = ObjectId::from_hex(have)?;
+        // Only commits can be used to find what the client has in common with us.
+        if repo
+            .try_find_header(id)?
+            .map_or(false, |header| header.kind() == gix_object::Kind::Commit)
+        {
+            common.push(id);
+            match multi_ack {
+                Some(status) => encode::text_to_write(format!("ACK {id}{status}").as_bytes(), &mut out)?,
+                None if common.len() == 1 => encode::text_to_write(format!("ACK {id}").as_bytes(), &mut out)?,
+                None => 0,
+            };
+        }
+    }
+
+    let max_data_len = if has_capability("side-band-64k") {
+        Some(65515)
+    } else if has_capability("side-band") {
+        Some(999)
+    } else {
+        None
+    };
+    let include_tags = if has_capability("include-tag") {
+        advertisement.tags.as_slice()
+    } else {
+        &[]
+    };
+    {
+        let mut pack = BufWriter::with_capacity(
+            max_data_len.unwrap_or(64 * 1024),
+            Sideband {
+                out: &mut out,
+                max_data_len,
+            },
+        );
+        outcome.num_objects = Some(write_pack(
+            repo,
+            &outcome.wants,
+            common,
+            include_tags,
+            &mut pack,
+            should_interrupt,
+        )?);
+        pack.flush()?;
+    }
+    if max_data_len.is_some() {
+        encode::flush_to_write(&mut out)?;
+    }
+    out.flush()?;
+    Ok(outcome)
+}
+
+fn unexpected_line(expected: &'static str, line: Option<&crate::bstr::BStr>) -> Error {
+    Error::UnexpectedLine {
+        expected,
+        line: line.map_or_else(|| "a special packet line".into(), ToOwned::to_owned),
+    }
+}
+
+/// Write a pack with all objects reachable from `wants`, but not from `common`, to `out`, along with all annotated tags
+/// in `include_tags` whose target is sent as well, and return the amount of objects in it.
+fn write_pack(
+    repo: &crate::Repository,
+    wants: &[ObjectId],
+    common: Vec<ObjectId>,
+    include_tags: &[(ObjectId, ObjectId)],
+    out: &mut impl Write,
+    should_interrupt: &AtomicBool,
+) -> Result<u32, Error> {
+    let mut ids = Vec::new();
+    let mut tips = Vec::new();
+    for want in wants {
+        if repo.find_object(*want)?.kind == gix_object::Kind::Commit {
+            tips.push(*want);
+        } else {
+            ids.push(*want);
+        }
+    }
+    ids.extend(gix_traverse::commit::reachable_from(tips, common, &repo.objects, None)?);
+    if !include_tags.is_empty() {
+        let sent: HashSet<_> = ids.iter().copied().collect();
+        ids.extend(
+            include_tags
+                .iter()
+                .filter(|(tag, peeled)| sent.contains(peeled) && !sent.contains(tag))
+                .map(|(tag, _)| *tag),
+        );
+    }
+
+    let mut db = repo.objects.clone().into_arc()?;
+    db.prevent_pack_unload();
+    db.ignore_replacements = true;
+    let (counts, _) = output::count::objects_unthreaded(
+        &db,
+        &mut ids.into_iter().map(Ok),
+        &gix_features::progress::Discard,
+        should_interrupt,
+        output::count::objects::ObjectExpansion::TreeAdditionsComparedToAncestor,
+    )?;
+    let num_entries = counts.len() as u32;
+    let mut entries = gix_features::parallel::InOrderIter::from(output::entry::iter_from_counts(
+        counts,
+        db,
+        Box::new(gix_features::progress::Discard),
+        output::entry::iter_from_counts::Options {
+            mode: output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+            allow_thin_pack: false,
+            ..Default::default()
+        },
+    ));
+    for written in output::bytes::FromEntriesIter::new(
+        entries.by_ref(),
+        out,
+        num_entries,
+        gix_pack::data::Version::V2,
+        repo.object_hash(),
+    ) {
+        written?;
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+    }
+    gix_features::parallel::reduce::Finalize::finalize(entries.inner)?;
+    Ok(num_entries)
+}
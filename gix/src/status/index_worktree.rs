@@ -23,6 +23,8 @@ pub enum Error {
     StatOptions(#[from] config::stat_options::Error),
     #[error(transparent)]
     ResourceCache(#[from] crate::diff::resource_cache::Error),
+    #[error("Could not create the filter pipeline to hash files that were renamed by changing their case")]
+    FilterPipeline(#[from] crate::filter::pipeline::options::Error),
 }
 
 /// Options for use with [Repository::index_worktree_status()].
@@ -50,6 +52,9 @@ pub struct Options {
     /// index and the working tree.
     /// Note that there is no git-configuration specific to index-worktree rename tracking.
     /// When rewrite tracking is enabled, there will be a delay for some entries as they partake in the rename-analysis.
+    ///
+    /// If `core.ignoreCase` is set, tracked files whose name on disk only differs in case are reported as renames as well,
+    /// which is particularly useful on case-insensitive filesystems where they would otherwise appear unchanged.
     pub rewrites: Option<gix_diff::Rewrites>,
     /// If set, don't use more than this amount of threads for the tracked modification check.
     /// Otherwise, usually use as many threads as there are logical cores.
@@ -110,7 +115,7 @@ impl Repository {
             crate::worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
             None,
         )?;
-        let pathspec = crate::Pathspec::new(
+        let mut pathspec = crate::Pathspec::new(
             self,
             options
                 .dirwalk_options
@@ -125,6 +130,23 @@ impl Repository {
         let git_dir_realpath = crate::path::realpath_opts(self.git_dir(), cwd, crate::path::realpath::MAX_SYMLINKS)?;
         let fs_caps = self.filesystem_options()?;
         let accelerate_lookup = fs_caps.ignore_case.then(|| index.prepare_icase_backing());
+        let case_only_renames =
+            if fs_caps.ignore_case && options.rewrites.is_some() && options.dirwalk_options.is_some() {
+                case_only_renames::find(index, workdir, &mut pathspec)
+            } else {
+                Vec::new()
+            };
+        let mut delegate = case_only_renames::Delegate {
+            pipeline: (!case_only_renames.is_empty())
+                .then(|| crate::filter::Pipeline::new(self, attrs_and_excludes.inner.clone()))
+                .transpose()?,
+            inner: delegate,
+            index,
+            workdir,
+            object_hash: self.object_hash(),
+            sorted: options.sorting.is_some(),
+            pending: case_only_renames,
+        };
         let resource_cache = crate::diff::resource_cache(
             self,
             gix_diff::blob::pipeline::Mode::ToGit,
@@ -138,7 +160,7 @@ impl Repository {
         let out = gix_status::index_as_worktree_with_renames(
             index,
             workdir,
-            delegate,
+            &mut delegate,
             compare,
             submodule,
             self.objects.clone().into_arc().expect("arc conversion always works"),
@@ -165,10 +187,229 @@ impl Repository {
                 rewrites: options.rewrites,
             },
         )?;
+        delegate.emit_remaining();
         Ok(out)
     }
 }
 
+/// Detect tracked files whose name on disk only differs in case, and report them as renames.
+mod case_only_renames {
+    use std::{collections::HashMap, io::Read, path::Path};
+
+    use gix_status::{
+        index_as_worktree::{Change, EntryStatus},
+        index_as_worktree_with_renames::{Entry, RewriteSource, VisitEntry},
+    };
+
+    use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+    /// An index entry whose file exists on disk with a name that only differs in case.
+    pub(super) struct Pending {
+        entry_index: usize,
+        rela_path: BString,
+        pathspec_match: Option<gix_dir::entry::PathspecMatch>,
+    }
+
+    /// Find all files in the index that are named differently on disk, but only in case, sorted by their path on disk.
+    ///
+    /// Files whose path on disk is tracked as well, or which are excluded by `pathspec`, are ignored.
+    pub(super) fn find(index: &gix_index::State, workdir: &Path, pathspec: &mut crate::Pathspec<'_>) -> Vec<Pending> {
+        use gix_index::entry::{Flags, Mode};
+        let mut names_by_dir = HashMap::<&BStr, Vec<BString>>::new();
+        let mut out = Vec::new();
+        for (entry_index, entry) in index.entries().iter().enumerate() {
+            if entry.stage_raw() != 0
+                || !(entry.mode.contains(Mode::FILE) || entry.mode == Mode::SYMLINK)
+                || entry.flags.intersects(Flags::SKIP_WORKTREE | Flags::INTENT_TO_ADD)
+            {
+                continue;
+            }
+            let path = entry.path(index);
+            let (dir, name) = path.rfind_byte(b'/').map_or((BStr::new(""), path), |pos| {
+                (path[..pos].as_bstr(), path[pos + 1..].as_bstr())
+            });
+            let names = names_by_dir.entry(dir).or_insert_with(|| {
+                std::fs::read_dir(workdir.join(gix_path::from_bstr(dir)))
+                    .map(|entries| {
+                        entries
+                            .filter_map(|entry| gix_path::os_string_into_bstring(entry.ok()?.file_name()).ok())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            });
+            if names.iter().any(|disk_name| disk_name == name) {
+                continue;
+            }
+            let Some(disk_name) = names.iter().find(|disk_name| disk_name.eq_ignore_ascii_case(name)) else {
+                continue;
+            };
+            let mut rela_path = BString::from(&path[..path.len() - name.len()]);
+            rela_path.push_str(disk_name);
+            if index.entry_by_path(rela_path.as_bstr()).is_some() || !pathspec.is_included(path, Some(false)) {
+                continue;
+            }
+            let pathspec_match = pathspec
+                .pattern_matching_relative_path(rela_path.as_bstr(), Some(false))
+                .map(Into::into);
+            out.push(Pending {
+                entry_index,
+                rela_path,
+                pathspec_match,
+            });
+        }
+        out.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+        out
+    }
+
+    /// A delegate which turns modifications of files with [pending](Pending) case-only renames into rewrites,
+    /// and emits rewrites for all that weren't modified once the status is complete.
+    pub(super) struct Delegate<'a, 'index, 'repo, D> {
+        pub inner: &'a mut D,
+        pub index: &'index gix_index::State,
+        pub workdir: &'a Path,
+        pub object_hash: gix_hash::Kind,
+        pub pipeline: Option<crate::filter::Pipeline<'repo>>,
+        pub sorted: bool,
+        pub pending: Vec<Pending>,
+    }
+
+    impl<'index, D, T, U> Delegate<'_, 'index, '_, D>
+    where
+        D: VisitEntry<'index, ContentChange = T, SubmoduleStatus = U>,
+    {
+        /// Emit all rewrites that weren't emitted yet.
+        pub fn emit_remaining(&mut self) {
+            for pending in std::mem::take(&mut self.pending) {
+                self.emit(pending, None);
+            }
+        }
+
+        fn take_pending(&mut self, entry_index: usize) -> Option<Pending> {
+            let pos = self.pending.iter().position(|p| p.entry_index == entry_index)?;
+            Some(self.pending.remove(pos))
+        }
+
+        /// Emit `pending` as rewrite, and return `false` if this wasn't possible as the worktree file couldn't be hashed.
+        /// If `modification` is `None`, the file is unchanged and has the id of its index entry.
+        fn emit(&mut self, pending: Pending, modification: Option<&EntryStatus<T, U>>) -> bool {
+            let source_entry = &self.index.entries()[pending.entry_index];
+            let id = match modification {
+                None | Some(EntryStatus::NeedsUpdate(_)) => Some(source_entry.id),
+                Some(_) => self.worktree_id(pending.rela_path.as_bstr()),
+            };
+            let Some(id) = id else { return false };
+            let kind = if source_entry.mode == gix_index::entry::Mode::SYMLINK {
+                gix_dir::entry::Kind::Symlink
+            } else {
+                gix_dir::entry::Kind::File
+            };
+            self.inner.visit_entry(Entry::Rewrite {
+                source: RewriteSource::RewriteFromIndex {
+                    index_entries: self.index.entries(),
+                    source_entry,
+                    source_entry_index: pending.entry_index,
+                    source_rela_path: source_entry.path(self.index),
+                    source_status: EntryStatus::Change(Change::Removed),
+                },
+                dirwalk_entry: gix_dir::Entry {
+                    rela_path: pending.rela_path,
+                    status: gix_dir::entry::Status::Untracked,
+                    property: None,
+                    disk_kind: Some(kind),
+                    index_kind: None,
+                    pathspec_match: pending.pathspec_match,
+                },
+                dirwalk_entry_collapsed_directory_status: None,
+                dirwalk_entry_id: id,
+                diff: None,
+                copy: false,
+            });
+            true
+        }
+
+        /// Hash the file at `rela_path` as it would be stored in `git`, or return `None` if this failed.
+        fn worktree_id(&mut self, rela_path: &BStr) -> Option<gix_hash::ObjectId> {
+            use gix_filter::pipeline::convert::ToGitOutcome;
+            let path = self.workdir.join(gix_path::from_bstr(rela_path));
+            let mut buf = Vec::new();
+            if std::fs::symlink_metadata(&path).ok()?.is_symlink() {
+                buf.extend_from_slice(gix_path::into_bstr(std::fs::read_link(&path).ok()?).as_ref());
+            } else {
+                let pipeline = self.pipeline.as_mut()?;
+                let file = std::fs::File::open(&path).ok()?;
+                match pipeline
+                    .convert_to_git(file, gix_path::from_bstr(rela_path).as_ref(), self.index)
+                    .ok()?
+                {
+                    ToGitOutcome::Unchanged(mut file) => {
+                        file.read_to_end(&mut buf).ok()?;
+                    }
+                    ToGitOutcome::Process(mut stream) => {
+                        stream.read_to_end(&mut buf).ok()?;
+                    }
+                    ToGitOutcome::Buffer(converted) => buf.extend_from_slice(converted),
+                }
+            }
+            Some(gix_object::compute_hash(self.object_hash, gix_object::Kind::Blob, &buf))
+        }
+    }
+
+    impl<'index, D, T, U> VisitEntry<'index> for Delegate<'_, 'index, '_, D>
+    where
+        D: VisitEntry<'index, ContentChange = T, SubmoduleStatus = U>,
+    {
+        type ContentChange = T;
+        type SubmoduleStatus = U;
+
+        fn visit_entry(&mut self, entry: Entry<'index, Self::ContentChange, Self::SubmoduleStatus>) {
+            if self.pending.is_empty() {
+                self.inner.visit_entry(entry);
+                return;
+            }
+            if self.sorted {
+                while self
+                    .pending
+                    .first()
+                    .map_or(false, |p| p.rela_path.as_bstr() < entry.destination_rela_path())
+                {
+                    let pending = self.pending.remove(0);
+                    self.emit(pending, None);
+                }
+            }
+            match &entry {
+                Entry::Rewrite {
+                    source:
+                        RewriteSource::RewriteFromIndex {
+                            source_entry_index: entry_index,
+                            ..
+                        },
+                    ..
+                } => {
+                    self.take_pending(*entry_index);
+                }
+                Entry::Modification {
+                    entry_index, status, ..
+                } if !matches!(status, EntryStatus::Conflict(_) | EntryStatus::IntentToAdd) => {
+                    if let Some(pending) = self.take_pending(*entry_index) {
+                        if self.emit(pending, Some(status)) {
+                            return;
+                        }
+                    }
+                }
+                Entry::DirectoryContents {
+                    entry: dirwalk_entry, ..
+                } if dirwalk_entry.status == gix_dir::entry::Status::Untracked => {
+                    if let Some(pos) = self.pending.iter().position(|p| p.rela_path == dirwalk_entry.rela_path) {
+                        self.pending.remove(pos);
+                    }
+                }
+                _ => {}
+            }
+            self.inner.visit_entry(entry);
+        }
+    }
+}
+
 /// An implementation of a trait to use with [`Repository::index_worktree_status()`] to compute the submodule status
 /// using [Submodule::status()](crate::Submodule::status()).
 #[derive(Clone)]
@@ -274,7 +515,8 @@ mod submodule_status {
 /// ### Index Changes
 ///
 /// Changes to the index are collected and it's possible to write the index back using [iter::Outcome::write_changes()].
-/// Note that these changes are not observable, they will always be kept.
+/// Note that entries which are unchanged but need their stat information updated are not observable, while racily clean
+/// entries that turned out to be modified are returned as modification.
 ///
 /// ### Parallel Operation
 ///
@@ -302,7 +544,7 @@ pub struct Iter {
     /// The outcome of the operation, only available once the operation has ended.
     out: Option<iter::Outcome>,
     /// The set of `(entry_index, change)` we extracted in order to potentially write back the index with the changes applied.
-    changes: Vec<(usize, iter::ApplyChange)>,
+    changes: Vec<(usize, gix_status::index_as_worktree::StatUpdate)>,
 }
 
 ///
@@ -310,18 +552,13 @@ pub struct Iter {
 pub mod iter {
     use crate::bstr::{BStr, BString};
     use crate::config::cache::util::ApplyLeniencyDefault;
-    use crate::status::index_worktree::{iter, BuiltinSubmoduleStatus};
+    use crate::status::index_worktree::BuiltinSubmoduleStatus;
     use crate::status::{index_worktree, Platform};
     use crate::worktree::IndexPersistedOrInMemory;
-    use gix_status::index_as_worktree::{Change, EntryStatus};
+    use gix_status::index_as_worktree::{Change, EntryStatus, StatUpdate, StatUpdates};
 
     pub use gix_status::index_as_worktree_with_renames::Summary;
 
-    pub(super) enum ApplyChange {
-        SetSizeToZero,
-        NewStat(crate::index::entry::Stat),
-    }
-
     /// The data the thread sends over to the receiving iterator.
     pub struct Outcome {
         /// The outcome of the index-to-worktree comparison operation.
@@ -329,7 +566,10 @@ pub mod iter {
         /// The index that was used for the operation.
         pub index: crate::worktree::IndexPersistedOrInMemory,
         skip_hash: bool,
-        changes: Option<Vec<(usize, iter::ApplyChange)>>,
+        fsync: Option<gix_fs::fsync::Method>,
+        changes: Option<Vec<(usize, StatUpdate)>>,
+        /// If `true`, sparse directories were expanded for the operation and should be collapsed again before writing.
+        collapse_sparse_directories: bool,
     }
 
     impl Outcome {
@@ -351,22 +591,15 @@ pub mod iter {
                 IndexPersistedOrInMemory::InMemory(index) => index.clone(),
             };
 
-            let entries = index.entries_mut();
-            for (entry_index, change) in changes {
-                let entry = &mut entries[entry_index];
-                match change {
-                    ApplyChange::SetSizeToZero => {
-                        entry.stat.size = 0;
-                    }
-                    ApplyChange::NewStat(new_stat) => {
-                        entry.stat = new_stat;
-                    }
-                }
+            StatUpdates { updates: changes }.apply_to(&mut index);
+            if self.collapse_sparse_directories {
+                index.collapse_sparse_directories();
             }
 
             Some(index.write(crate::index::write::Options {
                 extensions: Default::default(),
                 skip_hash: self.skip_hash,
+                fsync: self.fsync,
             }))
         }
     }
@@ -450,9 +683,35 @@ pub mod iter {
         }
     }
 
+    /// One side of a [conflict](Item::Conflict), as stored in an index entry at the respective stage.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ConflictEntry {
+        /// The id of the object at this stage.
+        pub id: gix_hash::ObjectId,
+        /// The mode of the entry at this stage.
+        pub mode: gix_index::entry::Mode,
+        /// The index of the entry for lookup in [`gix_index::State::entries()`].
+        pub entry_index: usize,
+    }
+
     /// The item produced by the iterator
     #[derive(Clone, PartialEq, Debug)]
     pub enum Item {
+        /// An unmerged entry, which has up to three entries in the index, one for each stage.
+        ///
+        /// This is what `git status` shows as *unmerged paths*, and which has to be resolved before it can be committed.
+        Conflict {
+            /// The repository-relative path of the conflicting entry.
+            rela_path: BString,
+            /// A summary of how both sides changed the entry, derived from the stages that are present.
+            kind: gix_status::index_as_worktree::Conflict,
+            /// The entry at the base stage, or `None` if there is no common ancestor as both sides added it.
+            base: Option<ConflictEntry>,
+            /// The entry at the stage for our side, or `None` if we deleted it.
+            ours: Option<ConflictEntry>,
+            /// The entry at the stage for their side, or `None` if they deleted it.
+            theirs: Option<ConflictEntry>,
+        },
         /// A tracked file was modified, and index-specific information is passed.
         Modification {
             /// The entry with modifications.
@@ -513,6 +772,7 @@ pub mod iter {
         pub fn summary(&self) -> Option<Summary> {
             use gix_status::index_as_worktree_with_renames::Summary::*;
             Some(match self {
+                Item::Conflict { .. } => Conflict,
                 Item::Modification { status, .. } => match status {
                     EntryStatus::Conflict(_) => Conflict,
                     EntryStatus::Change(change) => match change {
@@ -543,7 +803,7 @@ pub mod iter {
         /// The repository-relative path of the entry contained in this item.
         pub fn rela_path(&self) -> &BStr {
             match self {
-                Item::Modification { rela_path, .. } => rela_path.as_ref(),
+                Item::Conflict { rela_path, .. } | Item::Modification { rela_path, .. } => rela_path.as_ref(),
                 Item::DirectoryContents { entry, .. } => entry.rela_path.as_ref(),
                 Item::Rewrite { dirwalk_entry, .. } => dirwalk_entry.rela_path.as_ref(),
             }
@@ -553,6 +813,42 @@ pub mod iter {
     impl<'index> From<gix_status::index_as_worktree_with_renames::Entry<'index, (), SubmoduleStatus>> for Item {
         fn from(value: gix_status::index_as_worktree_with_renames::Entry<'index, (), SubmoduleStatus>) -> Self {
             match value {
+                gix_status::index_as_worktree_with_renames::Entry::Modification {
+                    entries,
+                    entry: _,
+                    entry_index,
+                    rela_path,
+                    status: EntryStatus::Conflict(kind),
+                } => {
+                    use gix_status::index_as_worktree::Conflict::*;
+                    // Entries are sorted by path and stage, and the kind of conflict tells us which stages are present.
+                    let num_stages = match kind {
+                        BothDeleted | AddedByUs | AddedByThem => 1,
+                        DeletedByThem | DeletedByUs | BothAdded => 2,
+                        BothModified => 3,
+                    };
+                    let (mut base, mut ours, mut theirs) = (None, None, None);
+                    for (entry_index, entry) in entries.iter().enumerate().skip(entry_index).take(num_stages) {
+                        let side = Some(ConflictEntry {
+                            id: entry.id,
+                            mode: entry.mode,
+                            entry_index,
+                        });
+                        match entry.stage() {
+                            gix_index::entry::Stage::Base => base = side,
+                            gix_index::entry::Stage::Ours => ours = side,
+                            gix_index::entry::Stage::Theirs => theirs = side,
+                            gix_index::entry::Stage::Unconflicted => {}
+                        }
+                    }
+                    Item::Conflict {
+                        rela_path: rela_path.to_owned(),
+                        kind,
+                        base,
+                        ours,
+                        theirs,
+                    }
+                }
                 gix_status::index_as_worktree_with_renames::Entry::Modification {
                     entries: _,
                     entry,
@@ -609,6 +905,8 @@ pub mod iter {
         ConfigSkipHash(#[from] crate::config::boolean::Error),
         #[error(transparent)]
         PrepareSubmodules(#[from] crate::submodule::modules::Error),
+        #[error(transparent)]
+        ExpandSparseDirectories(#[from] gix_index::sparse::expand::Error),
     }
 
     /// Lifecycle
@@ -629,6 +927,15 @@ pub mod iter {
                 None => IndexPersistedOrInMemory::Persisted(self.repo.index_or_empty()?),
                 Some(index) => index,
             };
+            // Entries within sparse directories must be visible to find the tracked files they contain.
+            let collapse_sparse_directories = index.is_sparse();
+            let index = if collapse_sparse_directories {
+                let mut index = index.into_owned();
+                index.expand_all_sparse_directories(&self.repo.objects)?;
+                IndexPersistedOrInMemory::InMemory(index)
+            } else {
+                index
+            };
 
             let skip_hash = self
                 .repo
@@ -639,6 +946,7 @@ pub mod iter {
                 .transpose()
                 .with_lenient_default(self.repo.config.lenient_config)?
                 .unwrap_or_default();
+            let fsync = self.repo.config.fsync(|components| components.index);
             let should_interrupt = self.should_interrupt.clone().unwrap_or_default();
             let submodule = BuiltinSubmoduleStatus::new(self.repo.clone().into_sync(), self.submodules)?;
             #[cfg(feature = "parallel")]
@@ -670,6 +978,8 @@ pub mod iter {
                                 index,
                                 changes: None,
                                 skip_hash,
+                                fsync,
+                                collapse_sparse_directories,
                             })
                         }
                     })
@@ -705,6 +1015,8 @@ pub mod iter {
                     index,
                     changes: None,
                     skip_hash,
+                    fsync,
+                    collapse_sparse_directories,
                 };
                 let mut iter = super::Iter {
                     items: Vec::new().into_iter(),
@@ -772,28 +1084,22 @@ pub mod iter {
 
     impl super::Iter {
         fn maybe_keep_index_change(&mut self, item: Item) -> Option<Item> {
-            let change = match item {
-                Item::Modification {
-                    status: gix_status::index_as_worktree::EntryStatus::NeedsUpdate(stat),
-                    entry_index,
-                    ..
-                } => (entry_index, ApplyChange::NewStat(stat)),
-                Item::Modification {
-                    status:
-                        gix_status::index_as_worktree::EntryStatus::Change(
-                            gix_status::index_as_worktree::Change::Modification {
-                                set_entry_stat_size_zero,
-                                ..
-                            },
-                        ),
-                    entry_index,
-                    ..
-                } if set_entry_stat_size_zero => (entry_index, ApplyChange::SetSizeToZero),
-                _ => return Some(item),
+            let Item::Modification {
+                status, entry_index, ..
+            } = &item
+            else {
+                return Some(item);
             };
-
-            self.changes.push(change);
-            None
+            let Some(update) = StatUpdate::from_status(status) else {
+                return Some(item);
+            };
+            self.changes.push((*entry_index, update));
+            match update {
+                // Unchanged entries are only of interest for updating the index.
+                StatUpdate::NewStat(_) => None,
+                // Racily clean entries whose content changed are still modified.
+                StatUpdate::SetSizeToZero => Some(item),
+            }
         }
     }
 
@@ -55,6 +55,24 @@ pub enum UntrackedFiles {
     Files,
 }
 
+/// How ignored files should be handled.
+#[derive(Default, Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum IgnoredFiles {
+    /// Do not show any ignored files, similar to `git status --ignored=no`.
+    #[default]
+    None,
+    /// Show ignored files, but collapse them into their parent directory if all of its content is ignored.
+    ///
+    /// This is particularly useful for large ignored directories like `node_modules` or `target`, which would
+    /// otherwise produce a lot of entries.
+    Collapsed,
+    /// Show ignored files and directories exactly as they match an exclude pattern, like `git status --ignored=matching`.
+    ///
+    /// Note that ignored directories are still emitted as a whole if the directory itself matches a pattern,
+    /// as they are never entered.
+    Matching,
+}
+
 impl Default for Submodule {
     fn default() -> Self {
         Submodule::AsConfigured { check_dirty: false }
@@ -85,6 +103,7 @@ impl Repository {
     /// Note that `status.showUntrackedFiles` is respected, which leads to untracked files being
     /// collapsed by default. If that needs to be controlled,
     /// [configure the directory walk explicitly](Platform::dirwalk_options) or more [implicitly](Platform::untracked_files).
+    /// Ignored files can be included with [`ignored_files()`](Platform::ignored_files).
     ///
     /// Pass `progress` to receive progress information on file modifications on this repository.
     /// Use [`progress::Discard`](crate::progress::Discard) to discard all progress information.
@@ -1,4 +1,4 @@
-use crate::status::{index_worktree, OwnedOrStaticAtomicBool, Platform, Submodule, UntrackedFiles};
+use crate::status::{index_worktree, IgnoredFiles, OwnedOrStaticAtomicBool, Platform, Submodule, UntrackedFiles};
 use std::sync::atomic::AtomicBool;
 
 /// Builder
@@ -40,6 +40,19 @@ where
         self.dirwalk_options(|cb| cb.emit_untracked(mode))
     }
 
+    /// A simple way to explicitly set the desired way of listing `ignored_files`, which aren't listed by default.
+    ///
+    /// Note that this call has no effect if no dirwalk options are present, for instance after
+    /// [`untracked_files(UntrackedFiles::None)`](Self::untracked_files()) was called.
+    pub fn ignored_files(self, ignored_files: IgnoredFiles) -> Self {
+        let mode = match ignored_files {
+            IgnoredFiles::None => None,
+            IgnoredFiles::Collapsed => Some(gix_dir::walk::EmissionMode::CollapseDirectory),
+            IgnoredFiles::Matching => Some(gix_dir::walk::EmissionMode::Matching),
+        };
+        self.dirwalk_options(|cb| cb.emit_ignored(mode))
+    }
+
     /// Set the interrupt flag to `should_interrupt`, which typically is an application-wide flag
     /// that is ultimately controlled by user interrupts.
     ///
@@ -111,3 +111,125 @@ pub mod head_id {
         PathConfiguration(#[from] gix_submodule::config::path::Error),
     }
 }
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+pub mod add {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::submodule_add()](crate::Repository::submodule_add()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Submodules can only be added to repositories with a worktree")]
+        BareRepository,
+        #[error("The path '{path}' isn't a valid path relative to the worktree")]
+        InvalidPath {
+            path: BString,
+            source: Option<gix_validate::path::component::Error>,
+        },
+        #[error(transparent)]
+        InvalidName(#[from] gix_validate::submodule::name::Error),
+        #[error("A submodule named '{name}' is already configured, or '{name}' is already tracked in the index")]
+        AlreadyExists { name: BString },
+        #[error("The path '{path}' already exists in the worktree")]
+        PathExists { path: std::path::PathBuf },
+        #[error("The submodule repository at '{path}' already exists")]
+        GitDirExists { path: std::path::PathBuf },
+        #[error("The relative url '{url}' can't be resolved against '{base}'")]
+        RelativeUrl { url: BString, base: BString },
+        #[error(transparent)]
+        Url(#[from] gix_url::parse::Error),
+        #[error(transparent)]
+        Modules(#[from] crate::submodule::modules::Error),
+        #[error(transparent)]
+        Clone(#[from] crate::clone::Error),
+        #[error(transparent)]
+        Fetch(#[from] crate::clone::fetch::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::clone::checkout::main_worktree::Error),
+        #[error("The submodule at '{path}' doesn't have a commit checked out")]
+        Unborn { path: BString },
+        #[error(transparent)]
+        Realpath(#[from] gix_path::realpath::Error),
+        #[error("Could not move the repository of the submodule into place or write its '.git' file")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Open(#[from] crate::open::Error),
+        #[error(transparent)]
+        Prepare(#[from] gix_config::file::transaction::prepare::Error),
+        #[error(transparent)]
+        SetValue(#[from] gix_config::file::set_raw_value::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_config::file::transaction::commit::Error),
+        #[error("Could not reload the configuration after registering the submodule")]
+        Reload(#[from] crate::config::Error),
+        #[error(transparent)]
+        ConfigBoolean(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error(transparent)]
+        StageModulesFile(#[from] crate::worktree::stage_path::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "status")]
+pub mod deinit {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::submodule_deinit()](crate::Repository::submodule_deinit()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("There is no submodule named '{name}'")]
+        NotFound { name: BString },
+        #[error("The submodule at '{path}' has its repository in its worktree, which would be lost")]
+        OldForm { path: BString },
+        #[error("The submodule at '{path}' contains local modifications")]
+        Modified { path: BString },
+        #[error(transparent)]
+        Modules(#[from] crate::submodule::modules::Error),
+        #[error(transparent)]
+        PathConfiguration(#[from] gix_submodule::config::path::Error),
+        #[error(transparent)]
+        Status(#[from] crate::submodule::status::Error),
+        #[error("Could not remove the worktree of the submodule")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Prepare(#[from] gix_config::file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_config::file::transaction::commit::Error),
+        #[error("Could not reload the configuration after unregistering the submodule")]
+        Reload(#[from] crate::config::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "status")]
+pub mod remove {
+    /// The error returned by [Repository::submodule_remove()](crate::Repository::submodule_remove()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Deinit(#[from] crate::submodule::deinit::Error),
+        #[error("Could not remove the worktree of the submodule")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Prepare(#[from] gix_config::file::transaction::prepare::Error),
+        #[error(transparent)]
+        Commit(#[from] gix_config::file::transaction::commit::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error(transparent)]
+        StageModulesFile(#[from] crate::worktree::stage_path::Error),
+    }
+}
@@ -267,7 +267,10 @@ impl<'repo> Submodule<'repo> {
     /// The repository can also be used to learn about the submodule `HEAD`, i.e. where its working tree is at,
     /// which may differ compared to the superproject's index or `HEAD` commit.
     pub fn open(&self) -> Result<Option<Repository>, open::Error> {
-        match crate::open_opts(self.git_dir_try_old_form()?, self.state.repo.options.clone()) {
+        match crate::open_opts(
+            self.git_dir_try_old_form()?,
+            self.state.repo.options.clone().for_other_repository(),
+        ) {
             Ok(repo) => Ok(Some(repo)),
             Err(crate::open::Error::NotARepository { .. }) => Ok(None),
             Err(err) => Err(err.into()),
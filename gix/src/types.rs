@@ -215,10 +215,12 @@ pub struct Remote<'repo> {
     pub(crate) push_specs: Vec<gix_refspec::RefSpec>,
     /// Tell us what to do with tags when fetched.
     pub(crate) fetch_tags: remote::fetch::Tags,
-    // /// Delete local tracking branches that don't exist on the remote anymore.
-    // pub(crate) prune: bool,
-    // /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
-    // pub(crate) prune_tags: bool,
+    /// Delete local tracking branches that don't exist on the remote anymore.
+    pub(crate) prune: bool,
+    /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
+    pub(crate) prune_tags: bool,
+    /// Fetch all references into local references of the same name, and delete the ones that are gone on the remote.
+    pub(crate) mirror: bool,
     pub(crate) repo: &'repo Repository,
 }
 
@@ -6,7 +6,10 @@ use std::sync::Arc;
 pub enum OwnedOrStaticAtomicBool {
     Owned {
         flag: Arc<AtomicBool>,
-        #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+        #[cfg_attr(
+            not(all(feature = "parallel", any(feature = "dirwalk", feature = "status"))),
+            allow(dead_code)
+        )]
         private: bool,
     },
     Shared(&'static AtomicBool),
@@ -52,7 +55,7 @@ impl From<Arc<AtomicBool>> for OwnedOrStaticAtomicBool {
         OwnedOrStaticAtomicBool::Owned { flag, private: false }
     }
 }
-#[cfg(feature = "parallel")]
+#[cfg(all(feature = "parallel", any(feature = "dirwalk", feature = "status")))]
 pub fn parallel_iter_drop<T, U>(
     mut rx_and_join: Option<(std::sync::mpsc::Receiver<T>, std::thread::JoinHandle<U>)>,
     should_interrupt: &OwnedOrStaticAtomicBool,
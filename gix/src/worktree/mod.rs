@@ -281,3 +281,281 @@ pub mod pathspec {
         }
     }
 }
+
+///
+#[cfg(feature = "attributes")]
+pub mod stage_path {
+    use std::io::Read;
+
+    use gix_index::entry::{Flags, Mode, Stage, Stat};
+
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        Worktree,
+    };
+
+    /// The error returned by [`Worktree::stage_path()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The path '{path}' isn't a valid path relative to the worktree")]
+        InvalidPath {
+            path: BString,
+            source: Option<gix_validate::path::component::Error>,
+        },
+        #[error("The path '{path}' doesn't exist in the worktree, nor is it tracked in the index")]
+        NotFound { path: BString },
+        #[error("The path '{path}' is a directory, but only files and symbolic links can be staged")]
+        Directory { path: BString },
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        ExpandSparseDirectories(#[from] gix_index::sparse::expand::Error),
+        #[error(transparent)]
+        FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        WriteBlob(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        ConfigBoolean(#[from] crate::config::boolean::Error),
+        #[error("Could not obtain the modification time of the file to stage")]
+        Stat(#[from] std::time::SystemTimeError),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error("Could not read the file to stage")]
+        Io(#[from] std::io::Error),
+        #[error("Could not flush the written blob to permanent storage")]
+        FlushObjects(#[source] std::io::Error),
+    }
+
+    impl<'repo> Worktree<'repo> {
+        /// Stage the file at `rela_path`, relative to the worktree root and with slashes as separators, like `git add` does,
+        /// and return the id of the blob that was written for it, or `None` if the file doesn't exist anymore and its entry
+        /// was removed from the index instead.
+        ///
+        /// The file is passed through all clean filters that apply to it, and its entry is updated with the stat information
+        /// of the file, or added if the path isn't tracked yet. Conflicts at `rela_path` are considered resolved, and
+        /// entries that would clash with `rela_path`, like a file in place of one of its leading directories, are removed.
+        /// Finally, the index is written back to disk.
+        ///
+        /// Sparse directories leading to `rela_path` are expanded to find its entry, and collapsed again before writing
+        /// as far as their entries remain excluded from the worktree.
+        ///
+        /// Note that only a single file or symbolic link can be staged at a time, directories are rejected.
+        pub fn stage_path(&self, rela_path: impl AsRef<BStr>) -> Result<Option<gix_hash::ObjectId>, Error> {
+            let rela_path = rela_path.as_ref();
+            let _span = gix_trace::detail!("gix::Worktree::stage_path()", rela_path = ?rela_path);
+            let repo = self.parent;
+            let protect = repo.config.protect_options()?;
+            let invalid_path = |source| Error::InvalidPath {
+                path: rela_path.to_owned(),
+                source,
+            };
+            for component in rela_path.split(|b| *b == b'/') {
+                if component == b"." || component == b".." {
+                    return Err(invalid_path(None));
+                }
+                gix_validate::path::component(component.as_bstr(), None, protect)
+                    .map_err(|err| invalid_path(Some(err)))?;
+            }
+
+            let mut index = gix_index::File::clone(&**repo.index_or_empty()?);
+            let collapse_sparse_directories = index.is_sparse();
+            index.expand_sparse_directories_to_path(rela_path, &repo.objects)?;
+            let path = self.path.join(gix_path::from_bstr(rela_path));
+            let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    if index.entry_by_path(rela_path).is_none() {
+                        return Err(Error::NotFound {
+                            path: rela_path.to_owned(),
+                        });
+                    }
+                    index.remove_entries(|_, path, _| path == rela_path);
+                    index.invalidate_tree_cache(rela_path);
+                    if collapse_sparse_directories {
+                        index.collapse_sparse_directories();
+                    }
+                    index.write(repo.index_write_options())?;
+                    return Ok(None);
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if metadata.is_dir() {
+                return Err(Error::Directory {
+                    path: rela_path.to_owned(),
+                });
+            }
+
+            let capabilities = repo.config.fs_capabilities()?;
+            let previous_mode = index
+                .entry_by_path_and_stage(rela_path, Stage::Unconflicted)
+                .map(|entry| entry.mode);
+            let mut buf = Vec::new();
+            let mode = if metadata.is_symlink() {
+                buf.extend_from_slice(&gix_path::into_bstr(std::fs::read_link(&path)?));
+                Mode::SYMLINK
+            } else if !capabilities.symlink && previous_mode == Some(Mode::SYMLINK) {
+                std::fs::File::open(&path)?.read_to_end(&mut buf)?;
+                Mode::SYMLINK
+            } else {
+                let (mut pipeline, _) = repo.filter_pipeline(None)?;
+                pipeline
+                    .convert_to_git(
+                        std::fs::File::open(&path)?,
+                        gix_path::from_bstr(rela_path).as_ref(),
+                        &index,
+                    )?
+                    .read_to_end(&mut buf)?;
+                if capabilities.executable_bit {
+                    if metadata.is_executable() {
+                        Mode::FILE_EXECUTABLE
+                    } else {
+                        Mode::FILE
+                    }
+                } else {
+                    previous_mode
+                        .filter(|mode| *mode == Mode::FILE_EXECUTABLE)
+                        .unwrap_or(Mode::FILE)
+                }
+            };
+            let id = repo.write_blob(&buf)?.detach();
+            let stat = Stat::from_fs(&metadata)?;
+
+            let is_leading_directory = |path: &BStr| {
+                rela_path.len() > path.len() && rela_path.starts_with(path) && rela_path[path.len()] == b'/'
+            };
+            let is_below = |path: &BStr| {
+                path.len() > rela_path.len() && path.starts_with(rela_path) && path[rela_path.len()] == b'/'
+            };
+            let mut removed = Vec::new();
+            index.remove_entries(|_, path, entry| {
+                let remove = (path == rela_path && entry.stage() != Stage::Unconflicted)
+                    || is_leading_directory(path)
+                    || is_below(path);
+                if remove {
+                    removed.push(path.to_owned());
+                }
+                remove
+            });
+            for path in removed {
+                index.invalidate_tree_cache(path.as_ref());
+            }
+            match index.entry_mut_by_path_and_stage(rela_path, Stage::Unconflicted) {
+                Some(entry) => {
+                    entry.stat = stat;
+                    entry.id = id;
+                    entry.mode = mode;
+                    entry.flags.remove(Flags::INTENT_TO_ADD);
+                }
+                None => {
+                    index.dangerously_push_entry(stat, id, Flags::empty(), mode, rela_path);
+                    index.sort_entries();
+                }
+            }
+            index.invalidate_tree_cache(rela_path);
+            repo.objects.store_ref().flush_batch().map_err(Error::FlushObjects)?;
+            if collapse_sparse_directories {
+                index.collapse_sparse_directories();
+            }
+            index.write(repo.index_write_options())?;
+            Ok(Some(id))
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "attributes")]
+pub mod index_flag {
+    use gix_index::entry::{Flags, Stage};
+
+    use crate::{
+        bstr::{BStr, BString},
+        Worktree,
+    };
+
+    /// A flag of index entries that makes changes to the corresponding files in the worktree invisible to
+    /// [status](crate::Repository::status()) and diffs between the index and the worktree.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Flag {
+        /// The file is assumed to be unchanged, so it isn't checked for changes anymore, like
+        /// `git update-index --assume-unchanged` does.
+        AssumeUnchanged,
+        /// The file is excluded from the worktree, so it is neither checked for changes nor checked out, like
+        /// `git update-index --skip-worktree` does.
+        SkipWorktree,
+    }
+
+    impl Flag {
+        fn to_flags(self) -> Flags {
+            match self {
+                Flag::AssumeUnchanged => Flags::ASSUME_VALID,
+                Flag::SkipWorktree => Flags::SKIP_WORKTREE,
+            }
+        }
+    }
+
+    /// The error returned by [`Worktree::set_index_flag()`] and [`Worktree::paths_with_index_flag()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Pathspec(#[from] crate::worktree::pathspec::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+    }
+
+    impl<'repo> Worktree<'repo> {
+        /// Set `flag` on all unconflicted index entries matching `patterns` if `enable` is `true`, or clear it otherwise,
+        /// write the index back to disk if anything changed, and return the paths of all entries that changed.
+        ///
+        /// Note that the worktree itself isn't changed, so files aren't removed when [`Flag::SkipWorktree`] is set,
+        /// nor are they checked out when it is cleared.
+        pub fn set_index_flag(
+            &self,
+            patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+            flag: Flag,
+            enable: bool,
+        ) -> Result<Vec<BString>, Error> {
+            let mut pathspec = self.pathspec(patterns)?;
+            let mut index = self.parent.open_index()?;
+            let mut changed = Vec::new();
+            let (entries, path_backing) = index.entries_mut_and_pathbacking();
+            for entry in entries {
+                let path = entry.path_in(path_backing);
+                if entry.stage() != Stage::Unconflicted || !pathspec.is_included(path, Some(false)) {
+                    continue;
+                }
+                let previous_flags = entry.flags;
+                entry.flags.set(flag.to_flags(), enable);
+                entry.flags.set(
+                    Flags::EXTENDED,
+                    entry.flags.intersects(Flags::SKIP_WORKTREE | Flags::INTENT_TO_ADD),
+                );
+                if entry.flags != previous_flags {
+                    changed.push(path.to_owned());
+                }
+            }
+            if !changed.is_empty() {
+                index.write(self.parent.index_write_options())?;
+            }
+            Ok(changed)
+        }
+
+        /// Return the paths of all index entries that have `flag` set, to learn which files are hidden from
+        /// [status](crate::Repository::status()).
+        pub fn paths_with_index_flag(&self, flag: Flag) -> Result<Vec<BString>, Error> {
+            let index = self.parent.open_index()?;
+            Ok(index
+                .entries()
+                .iter()
+                .filter(|entry| entry.flags.contains(flag.to_flags()))
+                .map(|entry| entry.path(&index).to_owned())
+                .collect())
+        }
+    }
+}
@@ -82,7 +82,11 @@ impl<'repo> Proxy<'repo> {
     /// a lot of information if work tree access is avoided.
     pub fn into_repo_with_possibly_inaccessible_worktree(self) -> Result<Repository, crate::open::Error> {
         let base = self.base().ok();
-        let repo = ThreadSafeRepository::open_from_paths(self.git_dir, base, self.parent.options.clone())?;
+        let repo = ThreadSafeRepository::open_from_paths(
+            self.git_dir,
+            base,
+            self.parent.options.clone().for_other_repository(),
+        )?;
         Ok(repo.into())
     }
 
@@ -95,7 +99,11 @@ impl<'repo> Proxy<'repo> {
         if !base.is_dir() {
             return Err(into_repo::Error::MissingWorktree { base });
         }
-        let repo = ThreadSafeRepository::open_from_paths(self.git_dir, base.into(), self.parent.options.clone())?;
+        let repo = ThreadSafeRepository::open_from_paths(
+            self.git_dir,
+            base.into(),
+            self.parent.options.clone().for_other_repository(),
+        )?;
         Ok(repo.into())
     }
 }
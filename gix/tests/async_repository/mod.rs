@@ -0,0 +1,43 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+use gix::{remote::fetch::Status, AsyncRepository};
+
+use crate::remote;
+
+#[async_std::test]
+async fn clone_ls_refs_fetch_and_find_object() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let url = gix::Url::try_from(remote::repo_path("base"))?;
+    let (repo, outcome) = AsyncRepository::clone_repository(
+        url,
+        tmp.path(),
+        gix::create::Kind::Bare,
+        Arc::new(AtomicBool::default()),
+    )
+    .await?;
+    assert!(matches!(outcome.status, Status::Change { .. }), "a pack was received");
+
+    let refs = repo.ls_refs(None).await?;
+    assert_eq!(
+        refs.len(),
+        outcome.ref_map.remote_refs.len(),
+        "the same refs are listed as were seen during the clone"
+    );
+
+    let outcome = repo
+        .fetch(Some("origin".into()), Arc::new(AtomicBool::default()))
+        .await?;
+    assert!(
+        matches!(outcome.status, Status::NoPackReceived { .. }),
+        "nothing changed since the clone"
+    );
+
+    let head_id = repo.with_repo(|repo| repo.head_id().map(gix::Id::detach)).await?;
+    let object = repo.find_object(head_id).await?;
+    assert_eq!(object.kind, gix::object::Kind::Commit);
+    assert!(repo.has_object(head_id).await);
+
+    let reopened = AsyncRepository::open(tmp.path()).await?;
+    assert_eq!(reopened.thread_safe().git_dir(), repo.thread_safe().git_dir());
+    Ok(())
+}
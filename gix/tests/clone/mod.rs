@@ -699,6 +699,279 @@ mod blocking_io {
         }
         Ok(())
     }
+
+    #[test]
+    fn fetch_only_as_mirror() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let remote_repo = remote::repo("base");
+        let (repo, _out) = gix::clone::PrepareFetch::new(
+            remote_repo.path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_mirror(true)
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let refs = |repo: &gix::Repository| -> crate::Result<Vec<(BString, gix::ObjectId)>> {
+            Ok(repo
+                .references()?
+                .all()?
+                .filter_map(Result::ok)
+                .filter_map(|r| r.try_id().map(|id| (r.name().as_bstr().to_owned(), id.detach())))
+                .collect())
+        };
+        assert_eq!(
+            refs(&repo)?,
+            refs(&remote_repo)?,
+            "all references are mirrored as they are, without remote tracking branches"
+        );
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            remote_repo.head_name()?.expect("not detached").as_bstr(),
+            "HEAD points to the same branch as the one of the remote"
+        );
+
+        let remote = repo.find_remote("origin")?;
+        assert!(remote.mirror(), "mirror configuration is persisted");
+        assert_eq!(
+            remote
+                .refspecs(Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/*:refs/*"]
+        );
+        assert!(
+            repo.config_snapshot().string("branch.main.remote").is_none(),
+            "mirrors don't track branches"
+        );
+
+        let id = repo.head_id()?.detach();
+        repo.reference(
+            "refs/heads/gone-on-remote",
+            id,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            "create stale ref",
+        )?;
+        let out = remote
+            .connect(Direction::Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        let update_refs = match out.status {
+            gix::remote::fetch::Status::Change { update_refs, .. }
+            | gix::remote::fetch::Status::NoPackReceived { update_refs, .. } => update_refs,
+        };
+        assert_eq!(
+            update_refs
+                .pruned
+                .iter()
+                .map(|edit| edit.name.as_bstr().to_owned())
+                .collect::<Vec<_>>(),
+            ["refs/heads/gone-on-remote"],
+            "mirrors prune local references that don't exist on the remote"
+        );
+        assert_eq!(refs(&repo)?, refs(&remote_repo)?);
+        Ok(())
+    }
+
+    fn ref_names(repo: &gix::Repository) -> crate::Result<Vec<BString>> {
+        let mut names = Vec::new();
+        for reference in repo.references()?.all()? {
+            names.push(reference?.name().as_bstr().to_owned());
+        }
+        Ok(names)
+    }
+
+    #[test]
+    fn fetch_only_single_branch_with_depth_and_without_tags() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_ref_name(Some("a"))?
+        .with_single_branch(true)
+        .with_fetch_tags(gix::remote::fetch::Tags::None)
+        .with_shallow(Shallow::DepthAtRemote(1.try_into()?));
+        let (repo, _out) = prepare.fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        let configured = prepare.configured().expect("set after fetch");
+        assert_eq!(configured.remote_name, "origin");
+        assert_eq!(
+            configured
+                .fetch_specs
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/heads/a:refs/remotes/origin/a"],
+            "only the branch to check out is fetched in future"
+        );
+        assert_eq!(configured.fetch_tags, gix::remote::fetch::Tags::None);
+        assert!(!configured.mirror);
+        assert_eq!(
+            configured.head_name.as_ref().map(gix::refs::FullName::as_bstr),
+            Some("refs/heads/a".into())
+        );
+        assert!(configured.shallow);
+
+        assert_eq!(
+            ref_names(&repo)?,
+            ["refs/heads/a", "refs/remotes/origin/HEAD", "refs/remotes/origin/a"],
+            "neither tags nor other branches are fetched"
+        );
+        assert_eq!(repo.shallow_commits()?.expect("shallow").len(), 1);
+
+        let repo = gix::open_opts(repo.path(), restricted())?;
+        let remote = repo.find_remote("origin")?;
+        assert_eq!(
+            remote.fetch_tags(),
+            gix::remote::fetch::Tags::None,
+            "the configuration was persisted"
+        );
+        assert_eq!(remote.refspecs(Direction::Fetch).len(), 1);
+        assert_eq!(
+            repo.config_snapshot().string("branch.a.merge").expect("set").as_ref(),
+            "refs/heads/a",
+            "the branch tracks its remote counterpart"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_single_branch_of_remote_head() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_single_branch(true);
+        let (repo, _out) = prepare.fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        let configured = prepare.configured().expect("set after fetch");
+        assert_eq!(
+            configured
+                .fetch_specs
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/heads/main:refs/remotes/origin/main"]
+        );
+        assert_eq!(
+            configured.fetch_tags,
+            gix::remote::fetch::Tags::Included,
+            "only tags pointing into the history of the branch are fetched"
+        );
+        assert!(!configured.shallow);
+        assert!(ref_names(&repo)?
+            .iter()
+            .filter(|name| name.starts_with(b"refs/remotes/"))
+            .all(|name| name == "refs/remotes/origin/HEAD" || name == "refs/remotes/origin/main"));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_local_objects() -> crate::Result {
+        for local_objects in [gix::clone::LocalObjects::Hardlink, gix::clone::LocalObjects::Copy] {
+            let tmp = gix_testtools::tempfile::TempDir::new()?;
+            let (repo, out) = gix::clone::PrepareFetch::new(
+                remote::repo("base").path(),
+                tmp.path(),
+                gix::create::Kind::Bare,
+                Default::default(),
+                restricted(),
+            )?
+            .with_local_objects(local_objects)
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+            assert!(
+                matches!(out.status, gix::remote::fetch::Status::NoPackReceived { .. }),
+                "all objects are present already"
+            );
+            assert!(
+                !repo.objects.store_ref().path().join("info").join("alternates").exists(),
+                "all objects are available locally"
+            );
+            assert_eq!(
+                repo.find_reference("refs/remotes/origin/main")?.id(),
+                remote::repo("base").find_reference("refs/heads/main")?.id()
+            );
+            repo.head_commit()?.tree()?.traverse().breadthfirst.files()?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_shared_objects_and_reference_repository() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let reference = remote::repo("clone");
+        let (repo, out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_local_objects(gix::clone::LocalObjects::Shared)
+        .with_reference_repository(reference.path())
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(matches!(out.status, gix::remote::fetch::Status::NoPackReceived { .. }));
+        let alternates = std::fs::read(repo.objects.store_ref().path().join("info").join("alternates"))?;
+        assert_eq!(
+            alternates.lines().count(),
+            1,
+            "objects are borrowed from the reference repository, which borrows from the source already"
+        );
+        assert!(alternates.contains_str("clone"));
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?.count(),
+            0,
+            "no object is copied"
+        );
+        repo.head_commit()?.tree()?.traverse().breadthfirst.files()?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "repack")]
+    fn fetch_only_with_reference_repository_then_dissociate() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .with_reference_repository(remote::repo("base").path())
+        .with_dissociate(true)
+        .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert!(matches!(out.status, gix::remote::fetch::Status::NoPackReceived { .. }));
+        assert!(
+            !repo.objects.store_ref().path().join("info").join("alternates").exists(),
+            "alternates aren't used anymore"
+        );
+        let repo = gix::open_opts(repo.path(), restricted())?;
+        repo.head_commit()?.tree()?.traverse().breadthfirst.files()?;
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension() == Some("idx".as_ref()))
+                .count(),
+            1,
+            "all borrowed objects were copied into a single pack"
+        );
+        Ok(())
+    }
 }
 
 #[test]
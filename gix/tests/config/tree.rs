@@ -148,7 +148,7 @@ mod ssh {
 #[cfg(feature = "status")]
 mod status {
     use crate::config::tree::bcow;
-    use gix::config::tree::Status;
+    use gix::config::tree::{Key, Status};
     use gix::status::UntrackedFiles;
 
     #[test]
@@ -174,6 +174,100 @@ mod status {
         );
         Ok(())
     }
+
+    #[test]
+    fn renames() -> crate::Result {
+        use gix::diff::rename::Tracking;
+        assert_eq!(Status::RENAMES.try_into_renames(Ok(true))?, Tracking::Renames);
+        assert_eq!(Status::RENAMES.try_into_renames(Ok(false))?, Tracking::Disabled);
+        assert!(Status::RENAMES.validate("copies".into()).is_ok());
+        assert_eq!(
+            Status::RENAMES
+                .try_into_renames(Err(gix_config::value::Error::new("err", "foo")))
+                .unwrap_err()
+                .to_string(),
+            "The value of key \"status.renames=foo\" was invalid"
+        );
+        assert!(Status::RENAME_LIMIT.validate("100".into()).is_ok());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "merge")]
+mod merge {
+    use crate::config::tree::bcow;
+    use gix::config::tree::Merge;
+    use gix::merge::{blob::ConflictStyle, FastForward};
+
+    #[test]
+    fn conflict_style() -> crate::Result {
+        for (actual, expected) in [
+            ("merge", ConflictStyle::Merge),
+            ("diff3", ConflictStyle::Diff3),
+            ("zdiff3", ConflictStyle::Diff3),
+        ] {
+            assert_eq!(Merge::CONFLICT_STYLE.try_into_conflict_style(bcow(actual))?, expected);
+        }
+
+        assert_eq!(
+            Merge::CONFLICT_STYLE
+                .try_into_conflict_style(bcow("unknown"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"merge.conflictStyle=unknown\" was invalid",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ff() -> crate::Result {
+        for (actual, expected) in [
+            ("true", FastForward::Allow),
+            ("false", FastForward::Never),
+            ("only", FastForward::Only),
+        ] {
+            assert_eq!(Merge::FF.try_into_fast_forward(bcow(actual))?, expected);
+        }
+
+        assert_eq!(
+            Merge::FF.try_into_fast_forward(bcow("always")).unwrap_err().to_string(),
+            "The key \"merge.ff=always\" was invalid",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn log() -> crate::Result {
+        for (actual, expected) in [("true", Some(20)), ("false", None), ("0", None), ("5", Some(5))] {
+            assert_eq!(Merge::LOG.try_into_shortlog_limit(bcow(actual))?, expected);
+        }
+
+        assert_eq!(
+            Merge::LOG
+                .try_into_shortlog_limit(bcow("invalid"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"merge.log=invalid\" was invalid",
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "merge")]
+mod pull {
+    use crate::config::tree::bcow;
+    use gix::config::tree::Pull;
+    use gix::merge::FastForward;
+
+    #[test]
+    fn ff() -> crate::Result {
+        assert_eq!(Pull::FF.try_into_fast_forward(bcow("only"))?, FastForward::Only);
+        assert_eq!(
+            Pull::FF.try_into_fast_forward(bcow("always")).unwrap_err().to_string(),
+            "The key \"pull.ff=always\" was invalid",
+        );
+        Ok(())
+    }
 }
 
 mod push {
@@ -609,12 +703,21 @@ mod core {
                 Core::CHECK_ROUND_TRIP_ENCODING
                     .try_into_encodings(value.map(bcow))
                     .unwrap(),
-                expected
+                expected.iter().copied().map(Into::into).collect::<Vec<_>>()
             );
             if let Some(value) = value {
                 assert!(Core::CHECK_ROUND_TRIP_ENCODING.validate(value.into()).is_ok());
             }
         }
+        assert_eq!(
+            Core::CHECK_ROUND_TRIP_ENCODING
+                .try_into_encodings(Some(bcow("utf32")))
+                .unwrap(),
+            [gix_filter::worktree::Encoding::Utf32(
+                gix_filter::worktree::encoding::ByteOrder::Bom
+            )],
+            "UTF-32 is supported as well"
+        );
         assert_eq!(
             Core::CHECK_ROUND_TRIP_ENCODING
                 .try_into_encodings(Some(bcow("SOMETHING ELSE")))
@@ -624,6 +727,102 @@ mod core {
         );
         Ok(())
     }
+
+    #[test]
+    fn fsync() -> crate::Result {
+        use gix::fs::fsync::Components;
+        assert_eq!(
+            Core::FSYNC.try_into_components(bcow(""))?,
+            Components::default(),
+            "nothing changes the defaults"
+        );
+        assert_eq!(
+            Core::FSYNC.try_into_components(bcow("none,index"))?,
+            Components {
+                index: true,
+                ..Components::none()
+            }
+        );
+        assert_eq!(
+            Core::FSYNC.try_into_components(bcow("-pack,-commit-graph"))?,
+            Components {
+                packs: false,
+                commit_graph: false,
+                ..Components::default()
+            }
+        );
+        for value in ["pack,-pack", "-pack,pack"] {
+            assert_eq!(
+                Core::FSYNC.try_into_components(bcow(value))?,
+                Components::default(),
+                "{value}: additions win over removals, independently of their order, like in git"
+            );
+        }
+        assert_eq!(
+            Core::FSYNC.try_into_components(bcow("all,-pack"))?,
+            Components::all(),
+            "removals don't affect what was added"
+        );
+        assert_eq!(
+            Core::FSYNC.try_into_components(bcow("none, objects"))?,
+            Components {
+                loose_objects: true,
+                packs: true,
+                ..Components::none()
+            },
+            "aggregates are supported, and whitespace is ignored"
+        );
+        assert!(Core::FSYNC.validate("committed,-reference".into()).is_ok());
+        assert_eq!(
+            Core::FSYNC
+                .try_into_components(bcow("everything"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"core.fsync=everything\" was invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fsync_method() -> crate::Result {
+        use gix::fs::fsync::Method;
+        for (value, expected) in [
+            ("fsync", Method::Fsync),
+            ("writeout-only", Method::WriteoutOnly),
+            ("batch", Method::Batch),
+        ] {
+            assert_eq!(Core::FSYNC_METHOD.try_into_fsync_method(bcow(value))?, expected);
+            assert!(Core::FSYNC_METHOD.validate(value.into()).is_ok());
+        }
+        assert!(Core::FSYNC_METHOD.validate("sync".into()).is_err());
+        Ok(())
+    }
+}
+
+mod i18n {
+    use gix::config::tree::{I18n, Key};
+
+    #[test]
+    fn commit_encoding() {
+        assert!(I18n::COMMIT_ENCODING.validate("ISO-8859-1".into()).is_ok());
+        #[cfg(feature = "attributes")]
+        {
+            assert_eq!(
+                I18n::COMMIT_ENCODING
+                    .try_into_encoding(crate::config::tree::bcow("latin1"))
+                    .unwrap(),
+                gix_filter::encoding::WINDOWS_1252.into()
+            );
+            assert_eq!(
+                I18n::COMMIT_ENCODING
+                    .try_into_encoding(crate::config::tree::bcow("foo"))
+                    .unwrap_err()
+                    .to_string(),
+                "The encoding named 'foo' seen in key 'i18n.commitEncoding=foo' is unsupported"
+            );
+            assert!(I18n::COMMIT_ENCODING.validate("foo".into()).is_err());
+        }
+    }
 }
 
 mod index {
@@ -726,6 +925,17 @@ mod pack {
         assert!(Pack::INDEX_VERSION.validate("-1".into()).is_err());
         Ok(())
     }
+
+    #[test]
+    fn window_and_depth() -> crate::Result {
+        for key in [&Pack::WINDOW, &Pack::DEPTH] {
+            assert!(key.validate("0".into()).is_ok());
+            assert!(key.validate("250".into()).is_ok());
+            assert!(key.validate("-1".into()).is_err());
+            assert_eq!(key.try_into_usize(Ok(10))?, 10);
+        }
+        Ok(())
+    }
 }
 
 mod protocol {
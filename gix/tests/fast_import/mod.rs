@@ -0,0 +1,138 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{
+    bstr::ByteSlice,
+    fast_import::{Command, DataRef, FileChange, Reader},
+};
+
+use crate::util::named_repo;
+
+#[test]
+fn export_and_import_roundtrip_with_identical_ids() -> crate::Result {
+    for fixture in ["make_diff_repo.sh", "make_repo_with_fork_and_dates.sh"] {
+        let source = named_repo(fixture)?;
+        let mut stream = Vec::new();
+        let exported = source.fast_export(&[], &mut stream, &AtomicBool::default())?;
+        assert_ne!(exported.commits, 0, "{fixture}: there is something to export");
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let dest = gix::init_bare(tmp.path())?;
+        let imported = dest.fast_import(Reader::new(stream.as_slice()))?;
+        assert_eq!(imported.commits, exported.commits, "{fixture}");
+
+        let platform = source.references()?;
+        for reference in platform.local_branches()?.chain(platform.tags()?) {
+            let reference = reference?;
+            assert_eq!(
+                dest.find_reference(reference.name())?.id(),
+                reference.id(),
+                "{fixture}: {} has the same id after the roundtrip",
+                reference.name().as_bstr().to_str_lossy()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn decode_inline_and_delimited_data() -> crate::Result {
+    let input = b"commit refs/heads/main
+committer a <a@example.com> 0 +0000
+data <<END
+message
+END
+M 644 inline \"quoted path\"
+data 5
+hello
+D old
+";
+    let commands = Reader::new(&input[..]).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(commands.len(), 1);
+    let Command::Commit(commit) = &commands[0] else {
+        unreachable!("a single commit")
+    };
+    assert_eq!(commit.message, "message\n");
+    assert_eq!(commit.changes.len(), 2);
+    let FileChange::Modify { mode, data, path } = &commit.changes[0] else {
+        unreachable!("a modification comes first")
+    };
+    assert_eq!(path, "quoted path");
+    assert!(mode.is_blob() && !mode.is_executable());
+    assert!(matches!(data, DataRef::Inline(data) if data == "hello"));
+    assert!(matches!(&commit.changes[1], FileChange::Delete { path } if path == "old"));
+    Ok(())
+}
+
+#[test]
+fn delimited_data_keeps_lines_that_look_like_comments() -> crate::Result {
+    let input = b"# a comment where a command is expected
+blob
+mark :1
+data <<EOF
+# not a comment
+content
+EOF
+
+commit refs/heads/main
+# a comment between headers
+committer a <a@example.com> 0 +0000
+data <<END
+subject
+
+# not a comment either
+END
+M 644 :1 file
+";
+    let commands = Reader::new(&input[..]).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(commands.len(), 2);
+    let Command::Blob { data, .. } = &commands[0] else {
+        unreachable!("a blob comes first")
+    };
+    assert_eq!(data, "# not a comment\ncontent\n");
+    let Command::Commit(commit) = &commands[1] else {
+        unreachable!("a commit comes second")
+    };
+    assert_eq!(commit.message, "subject\n\n# not a comment either\n");
+
+    let mut stream = Vec::new();
+    for command in &commands {
+        command.write_to(&mut stream)?;
+    }
+    assert_eq!(
+        Reader::new(stream.as_slice()).collect::<Result<Vec<_>, _>>()?,
+        commands,
+        "serialization roundtrips"
+    );
+
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init_bare(tmp.path())?;
+    repo.fast_import(Reader::new(&input[..]))?;
+    let commit = repo.find_reference("refs/heads/main")?.id().object()?.into_commit();
+    assert_eq!(commit.message_raw()?, "subject\n\n# not a comment either\n");
+    let blob = commit.tree()?.find_entry("file").expect("present").object()?;
+    assert_eq!(blob.data.as_bstr(), "# not a comment\ncontent\n");
+    Ok(())
+}
+
+#[test]
+fn corrupt_trees_are_reported_when_imported() -> crate::Result {
+    use gix::odb::Write;
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init_bare(tmp.path())?;
+    let tree = repo
+        .objects
+        .write_buf(gix::object::Kind::Tree, b"100644 truncated-entry")?;
+    let input = format!(
+        "commit refs/heads/main
+committer a <a@example.com> 0 +0000
+data 0
+M 040000 {tree} dir
+"
+    );
+    let err = repo.fast_import(Reader::new(input.as_bytes())).unwrap_err();
+    assert!(
+        matches!(err, gix::fast_import::import::Error::DecodeObject(_)),
+        "the tree can't be decoded, which isn't ignored: {err:?}"
+    );
+    Ok(())
+}
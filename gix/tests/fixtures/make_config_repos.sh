@@ -8,6 +8,7 @@ git init http-config
   git config --add http.extraHeader "ExtraHeader: value2"
   git config --add http.extraHeader "ExtraHeader: value3"
   git config http.followRedirects true
+  git config http.emptyAuth true
   git config http.lowSpeedLimit 5k
   git config http.lowSpeedTime 10
   git config http.postBuffer 8k
@@ -56,6 +57,16 @@ git init http-disabled-cainfo
   git config http.schannelUseSSLCAInfo false
 )
 
+git init http-ssl-url
+(cd http-ssl-url
+  git config http.sslCert ./client.pem
+  git config http.sslKey ./client.key
+  git config http.https://example.com.sslVerify false
+  git config http.https://example.com/org.sslCert ./org.pem
+  git config http.https://example.com/org/repo.sslCert ./repo.pem
+  git config http.https://*.example.com.sslCAInfo ./wildcard-CA.pem
+)
+
 git init http-proxy-empty
 (cd http-proxy-empty
   git config http.followRedirects false
@@ -0,0 +1,47 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+seq 1 5 > file
+echo removed > removed
+mkdir dir && echo content > dir/removed
+git add . && git commit -q -m base
+git branch behind
+
+echo changed > removed
+git commit -q -am "change removed"
+
+git checkout -q -b diverged
+git rm -q -r removed dir
+echo new > new
+git add . && git commit -q -m "remove files"
+sed -i.bak 's/^5$/diverged/' file && rm file.bak
+git commit -q -am "change file"
+
+git checkout -q -b conflicting main
+sed -i.bak 's/^2$/conflicting/' file && rm file.bak
+git commit -q -am "conflicting change"
+
+git checkout -q main
+sed -i.bak 's/^2$/main/' file && rm file.bak
+git commit -q -am "change on main"
+
+git checkout -q -b ahead
+echo ahead > ahead
+git add . && git commit -q -m ahead
+
+git checkout -q -b merged-main diverged
+git merge -q --no-edit main
+echo after > after
+git add after && git commit -q -m "after merge"
+
+git checkout -q -b expected-merge main
+git merge -q --no-edit diverged
+
+git checkout -q -b case-renamed main
+git mv file File
+git mv dir Dir
+git commit -q -m "change case"
+git checkout -q main
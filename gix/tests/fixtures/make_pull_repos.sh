@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+(mkdir remote && cd remote
+  git init -q
+  git checkout -q -b main
+  seq 1 5 > file
+  git add file && git commit -q -m base
+)
+
+git clone -q remote behind
+
+git clone -q remote diverged
+(cd diverged
+  sed -i.bak 's/^5$/local-5/' file && rm file.bak
+  git commit -q -am "local change"
+)
+
+git clone -q remote no-upstream
+(cd no-upstream
+  git checkout -q -b topic
+)
+
+git clone -q remote narrow-refspec
+(cd narrow-refspec
+  git config remote.origin.fetch "+refs/heads/other:refs/remotes/origin/other"
+)
+
+(cd remote
+  sed -i.bak 's/^1$/remote-1/' file && rm file.bak
+  git commit -q -am "remote change"
+)
+
+git clone -q remote fetch-head
+(cd remote
+  git branch feature
+)
+(cd fetch-head
+  git fetch -q
+  mv .git/FETCH_HEAD .git/FETCH_HEAD.git
+)
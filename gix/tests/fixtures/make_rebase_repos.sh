@@ -0,0 +1,52 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function setup_base() {
+  git init -q
+  git checkout -q -b main
+  seq 1 5 > file
+  echo other > other
+  git add . && git commit -q -m base
+
+  git checkout -q -b feature
+  sed -i.bak 's/^5$/feature-5/' file && rm file.bak
+  git commit -q -am "change line 5"
+}
+
+function finish() {
+  echo new > new
+  git add new && git commit -q -m "add new"
+
+  git checkout -q main
+  sed -i.bak 's/^1$/main-1/' file && rm file.bak
+  git commit -q -am "change line 1"
+  git checkout -q feature
+}
+
+(mkdir clean && cd clean
+  setup_base
+  finish
+)
+
+(mkdir conflict && cd conflict
+  setup_base
+  sed -i.bak 's/^1$/feature-1/' file && rm file.bak
+  git commit -q -am "conflicting change"
+  finish
+)
+
+(mkdir merged-upstream && cd merged-upstream
+  setup_base
+  git checkout -q main
+  sed -i.bak 's/^1$/main-1/' file && rm file.bak
+  git commit -q -am "change line 1"
+  git checkout -q feature
+  git merge -q --no-edit main
+  echo new > new
+  git add new && git commit -q -m "add new"
+
+  git checkout -q main
+  sed -i.bak 's/^3$/main-3/' file && rm file.bak
+  git commit -q -am "change line 3"
+  git checkout -q feature
+)
@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+for n in $(seq 20); do
+  echo "$n" > "file$n"
+done
+git add . && git commit -qm "many files"
+git repack -qd
+
+for round in 1 2 3; do
+  echo "$round" > "small$round"
+  git add . && git commit -qm "small $round"
+  git repack -qd
+done
+
+echo loose > loose
+git add . && git commit -qm "loose objects"
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+touch a b
+mkdir -p c1/c2 c1/c3 d/c4
+touch c1/a c1/b c1/c2/a c1/c2/b c1/c3/a c1/c3/b d/a d/b d/c4/a d/c4/b d/c4/c5
+
+git add .
+git commit -q -m "init"
+
+git sparse-checkout set c1/c2 --sparse-index
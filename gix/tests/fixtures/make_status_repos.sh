@@ -13,3 +13,53 @@ git init -q untracked-only
   mkdir new
   touch new/untracked subdir/untracked
 )
+
+git init -q ignored-directories
+(cd ignored-directories
+  printf 'node_modules/\n*.log\n' > .gitignore
+  mkdir src
+  touch src/lib.rs
+  git add .
+  git commit -q -m init
+
+  mkdir -p node_modules/a/b logs
+  touch node_modules/a/b/index.js node_modules/c.js logs/1.log logs/2.log src/debug.log
+)
+
+git init -q conflicts
+(cd conflicts
+  echo base > both-modified
+  echo base > deleted-by-them
+  git add .
+  git commit -q -m base
+
+  git checkout -q -b theirs
+  echo theirs > both-modified
+  git rm -q deleted-by-them
+  echo theirs > both-added
+  git add .
+  git commit -q -m theirs
+
+  git checkout -q main 2>/dev/null || git checkout -q master
+  echo ours > both-modified
+  echo ours > deleted-by-them
+  echo ours > both-added
+  git add .
+  git commit -q -m ours
+
+  git merge theirs || :
+)
+
+git init -q case-only-renames
+(cd case-only-renames
+  git config core.ignoreCase true
+  echo unchanged > Unchanged
+  mkdir dir
+  seq 10 > dir/Changed
+  git add .
+  git commit -q -m init
+
+  mv Unchanged unchanged
+  mv dir/Changed dir/changed
+  echo 11 >> dir/changed
+)
@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b base
+
+seq 1 9 > clean
+seq 1 3 > conflict
+echo content > modify-delete
+echo content > exe
+echo unchanged > unchanged
+mkdir dir && echo a > dir/file
+git add . && git commit -q -m base
+
+git checkout -q -b ours
+sed -i.bak 's/^2$/ours/' clean conflict && rm *.bak
+echo modified > modify-delete
+chmod +x exe
+echo new > new-ours
+echo file > df
+git add . && git commit -q -m ours
+
+git checkout -q -b theirs base
+sed -i.bak 's/^8$/theirs/' clean && sed -i.bak 's/^2$/theirs/' conflict && rm *.bak
+git rm -q modify-delete
+echo b > dir/file
+mkdir df && echo file > df/file
+git add . && git commit -q -m theirs
@@ -44,6 +44,8 @@ mod with_overrides {
             .set("GIX_EXTERNAL_COMMAND_STDERR", "filter-stderr")
             .set("GIT_SSL_CAINFO", "./env.pem")
             .set("GIT_SSL_VERSION", "tlsv1.3")
+            .set("GIT_SSL_CERT", "./env-cert.pem")
+            .set("GIT_SSL_KEY", "./env-key.pem")
             .set("GIT_SSH_VARIANT", "ssh-variant-env")
             .set("GIT_SSH_COMMAND", "ssh-command-env")
             .set("GIT_SSH", "ssh-command-fallback-env")
@@ -53,6 +55,7 @@ mod with_overrides {
             .set("GIT_ICASE_PATHSPECS", "pathspecs-icase")
             .set("GIT_TERMINAL_PROMPT", "42")
             .set("GIT_SHALLOW_FILE", "shallow-file-env")
+            .set("GIT_INDEX_FILE", "index-file-env")
             .set("GIT_NAMESPACE", "namespace-env")
             .set("GIT_EXTERNAL_DIFF", "external-diff-env");
         let mut opts = gix::open::Options::isolated()
@@ -171,6 +174,8 @@ mod with_overrides {
                 cow_bstr("./env.pem")
             ]
         );
+        assert_eq!(config.string("http.sslCert").expect("set").as_ref(), "./env-cert.pem");
+        assert_eq!(config.string("http.sslKey").expect("set").as_ref(), "./env-key.pem");
         assert_eq!(
             config.strings("http.sslVersion").expect("at least one value"),
             [
@@ -240,6 +245,7 @@ mod with_overrides {
             ("gitoxide.credentials.terminalPrompt", "42"),
             ("gitoxide.credentials.helperStderr", "creds-stderr"),
             ("gitoxide.core.externalCommandStderr", "filter-stderr"),
+            ("gitoxide.core.indexFile", "index-file-env"),
         ] {
             assert_eq!(
                 config
@@ -253,6 +259,67 @@ mod with_overrides {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn index_file_and_object_directories_from_environment() -> gix_testtools::Result {
+        let source = gix_testtools::scripted_fixture_read_only("make_basic_repo.sh")?;
+        let objects_dir = gix_testtools::tempfile::tempdir()?;
+        let index_file = objects_dir.path().join("custom-index");
+        let _env = Env::new()
+            .set("GIT_INDEX_FILE", index_file.to_str().expect("valid UTF-8"))
+            .set(
+                "GIT_OBJECT_DIRECTORY",
+                objects_dir.path().to_str().expect("valid UTF-8"),
+            )
+            .set(
+                "GIT_ALTERNATE_OBJECT_DIRECTORIES",
+                source.join(".git/objects").to_str().expect("valid UTF-8"),
+            );
+        let mut opts = gix::open::Options::isolated();
+        opts.permissions.env.git_prefix = Permission::Allow;
+        opts.permissions.env.objects = Permission::Allow;
+        let repo = gix::open_opts(&source, opts.clone())?;
+
+        assert_eq!(repo.index_path(), index_file, "GIT_INDEX_FILE is used as is");
+        assert_eq!(repo.objects.store_ref().path(), objects_dir.path());
+        let head_id = repo.head_id()?;
+        assert!(
+            repo.has_object(head_id),
+            "objects are found in the alternate as the object directory is empty"
+        );
+
+        let repo = gix::open_opts(
+            named_subrepo_opts("make_submodules.sh", "with-submodules", gix::open::Options::isolated())?.path(),
+            opts,
+        )?;
+        assert_eq!(repo.index_path(), index_file);
+        let submodule = repo
+            .submodules()?
+            .expect("submodules present")
+            .next()
+            .expect("at least one")
+            .open()?
+            .expect("initialized");
+        assert_eq!(
+            submodule.index_path(),
+            submodule.git_dir().join("index"),
+            "submodules don't see variables meant for the repository opened from the environment"
+        );
+        assert_eq!(
+            submodule.objects.store_ref().path(),
+            submodule.git_dir().join("objects")
+        );
+
+        let repo = gix::open_opts(&source, gix::open::Options::isolated())?;
+        assert_eq!(
+            repo.index_path(),
+            repo.git_dir().join("index"),
+            "the environment is ignored without permission"
+        );
+        assert_eq!(repo.objects.store_ref().path(), repo.git_dir().join("objects"));
+        Ok(())
+    }
+
     fn cow_bstr(s: &str) -> Cow<BStr> {
         Cow::Borrowed(s.into())
     }
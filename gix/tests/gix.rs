@@ -2,20 +2,28 @@
 mod util;
 use util::*;
 
+#[cfg(feature = "async-facade")]
+mod async_repository;
 mod clone;
 mod commit;
 mod config;
 #[cfg(feature = "blob-diff")]
 mod diff;
+#[cfg(feature = "fast-import")]
+mod fast_import;
 mod head;
 mod id;
 mod init;
 mod object;
 mod reference;
 mod remote;
+#[cfg(feature = "repack")]
+mod repack;
 mod repository;
 #[cfg(feature = "revision")]
 mod revision;
+#[cfg(feature = "serve")]
+mod serve;
 #[cfg(feature = "status")]
 mod status;
 #[cfg(feature = "attributes")]
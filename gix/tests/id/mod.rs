@@ -114,6 +114,29 @@ mod ancestors {
         Ok(())
     }
 
+    #[test]
+    fn interrupted_by_token() -> crate::Result {
+        let repo = crate::repo("make_repo_with_fork_and_dates.sh")?.to_thread_local();
+        let head = repo.head()?.into_peeled_id()?;
+        let token = gix::interrupt::Token::new();
+        let mut walk = head.ancestors().should_interrupt_owned(token.shared_flag()).all()?;
+        assert!(
+            walk.next().transpose()?.is_some(),
+            "the walk proceeds while not cancelled"
+        );
+        token.cancel();
+        assert!(
+            matches!(walk.next(), Some(Err(gix::revision::walk::iter::Error::Interrupted))),
+            "a cancelled walk reports the interruption"
+        );
+        assert!(walk.next().is_none(), "and stops afterwards");
+        assert!(
+            !gix::interrupt::is_triggered(),
+            "tokens are independent of the global interrupt flag"
+        );
+        Ok(())
+    }
+
     #[test]
     fn pre_epoch() -> crate::Result {
         let repo = crate::repo("make_pre_epoch_repo.sh")?.to_thread_local();
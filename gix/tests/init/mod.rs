@@ -111,6 +111,148 @@ mod non_bare {
         Ok(())
     }
 
+    #[test]
+    fn init_with_template_dir_copies_templates_and_starts_with_their_configuration() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let templates = tmp.path().join("templates");
+        std::fs::create_dir_all(templates.join("hooks"))?;
+        std::fs::write(templates.join("hooks").join("pre-commit"), b"#!/bin/sh")?;
+        std::fs::write(templates.join(".hidden"), b"skipped")?;
+        std::fs::write(templates.join("description"), b"custom")?;
+        std::fs::write(
+            templates.join("config"),
+            b"[init]\n\tdefaultBranch = from-template\n[core]\n\tbare = true\n",
+        )?;
+
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path().join("repo"),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                template_dir: Some(templates),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .into();
+        let git_dir = repo.git_dir();
+        assert!(git_dir.join("hooks").join("pre-commit").is_file());
+        assert!(
+            !git_dir.join("hooks").join("pre-commit.sample").exists(),
+            "built-in templates aren't used"
+        );
+        assert!(
+            !git_dir.join(".hidden").exists(),
+            "entries starting with a dot are skipped"
+        );
+        assert_eq!(std::fs::read(git_dir.join("description"))?, b"custom");
+
+        let config = std::fs::read_to_string(git_dir.join("config"))?;
+        assert!(
+            config.starts_with("[init]\n\tdefaultBranch = from-template\n[core]\n\tbare = false\n"),
+            "the configuration of the template is the basis, and its values are adjusted: {config}"
+        );
+        assert_eq!(
+            repo.head()?.referent_name().expect("name").as_bstr(),
+            "refs/heads/from-template"
+        );
+        assert!(!repo.is_bare());
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_template_dir_from_configuration() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let templates = tmp.path().join("templates");
+        std::fs::create_dir_all(templates.join("info"))?;
+        std::fs::write(templates.join("info").join("exclude"), b"target/")?;
+
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path().join("repo"),
+            gix::create::Kind::Bare,
+            gix::create::Options::default(),
+            gix::open::Options::isolated().config_overrides([format!("init.templateDir={}", templates.display())]),
+        )?
+        .into();
+        assert_eq!(std::fs::read(repo.git_dir().join("info").join("exclude"))?, b"target/");
+        assert!(!repo.git_dir().join("hooks").exists());
+        assert_eq!(repo.head()?.referent_name().expect("name").as_bstr(), "refs/heads/main");
+        Ok(())
+    }
+
+    #[test]
+    fn init_without_templates() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                template_dir: Some(Default::default()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .into();
+        let mut entries: Vec<_> = std::fs::read_dir(repo.git_dir())?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<_, _>>()?;
+        entries.sort();
+        assert_eq!(entries, ["HEAD", "config", "objects", "refs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn init_with_separate_git_dir() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let work_dir = tmp.path().join("worktree");
+        let git_dir = tmp.path().join("store");
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            &work_dir,
+            gix::create::Kind::WithWorktree,
+            gix::create::Options {
+                separate_git_dir: Some(git_dir.clone()),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )?
+        .into();
+        assert_eq!(repo.git_dir(), git_dir);
+        assert_eq!(repo.work_dir(), Some(work_dir.as_path()));
+        assert_eq!(repo.kind(), gix::repository::Kind::WorkTree { is_linked: false });
+        assert_eq!(
+            std::fs::read_to_string(work_dir.join(".git"))?,
+            format!(
+                "gitdir: {}\n",
+                gix::path::to_unix_separators_on_windows(gix::path::into_bstr(git_dir.as_path()))
+            ),
+            "the .git file points to the repository with an absolute path"
+        );
+
+        let reopened = gix::open_opts(&work_dir, gix::open::Options::isolated())?;
+        assert_eq!(reopened.git_dir(), repo.git_dir());
+        assert_eq!(reopened.work_dir(), repo.work_dir());
+        assert!(!reopened.config_snapshot().boolean("core.bare").unwrap_or(true));
+        Ok(())
+    }
+
+    #[test]
+    fn init_bare_with_separate_git_dir_is_not_allowed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::Bare,
+            gix::create::Options {
+                separate_git_dir: Some(tmp.path().join("store")),
+                ..Default::default()
+            },
+            gix::open::Options::isolated(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "A separate git directory can only be used for repositories with a worktree"
+        );
+    }
+
     #[test]
     fn init_into_non_empty_directory_is_allowed_by_default() -> crate::Result {
         let tmp = tempfile::tempdir()?;
@@ -68,3 +68,29 @@ mod needs_feature {
         Ok(())
     }
 }
+
+mod token {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn clones_share_state_and_others_are_independent() {
+        let token = gix::interrupt::Token::new();
+        let other = gix::interrupt::Token::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+
+        clone.cancel();
+        assert!(token.is_cancelled(), "clones share their state");
+        assert!(!other.is_cancelled(), "other tokens are unaffected");
+
+        let flag: &AtomicBool = &token;
+        assert!(
+            flag.load(Ordering::Relaxed),
+            "tokens can be used where flags are expected"
+        );
+        assert!(token.shared_flag().load(Ordering::Relaxed));
+
+        token.reset();
+        assert!(!clone.is_cancelled(), "tokens can be reused after a reset");
+    }
+}
@@ -0,0 +1,71 @@
+use gix::{
+    bstr::ByteSlice,
+    object::batch::{Atom, Format, Options},
+};
+
+use crate::util::named_repo;
+
+#[test]
+fn format_parsing() -> crate::Result {
+    assert_eq!(
+        Format::from_bytes(b"%(objectname) %(objecttype) %(objectsize)")?,
+        Format::default(),
+        "the default matches the one of git"
+    );
+    assert_eq!(
+        Format::from_bytes(b"%%%(rest)!")?.atoms(),
+        &[Atom::Literal("%".into()), Atom::Rest, Atom::Literal("!".into())]
+    );
+    assert!(
+        Format::from_bytes(b"%(deltabase)").is_err(),
+        "unknown atoms are rejected"
+    );
+    assert!(Format::from_bytes(b"%(objectname").is_err());
+    Ok(())
+}
+
+#[test]
+fn headers_contents_and_missing_objects() -> crate::Result {
+    let repo = named_repo("make_basic_repo.sh")?;
+    let head = repo.head_id()?;
+    let input = format!(
+        "HEAD\nHEAD^{{tree}} with rest\ndoes-not-exist\n{}\n",
+        repo.object_hash().null()
+    );
+
+    let mut out = Vec::new();
+    let outcome = repo.cat_file_batch(
+        input.as_bytes(),
+        &mut out,
+        &Options {
+            format: Format::from_bytes(b"%(objecttype) %(objectname) [%(rest)]")?,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.found, 2);
+    assert_eq!(outcome.missing, 2);
+    let tree = head.object()?.peel_to_tree()?.id;
+    assert_eq!(
+        out.as_bstr(),
+        format!(
+            "commit {head} []\ntree {tree} [with rest]\ndoes-not-exist missing\n{} missing\n",
+            repo.object_hash().null()
+        )
+    );
+
+    let mut out = Vec::new();
+    repo.cat_file_batch(
+        "HEAD\n".as_bytes(),
+        &mut out,
+        &Options {
+            contents: true,
+            ..Default::default()
+        },
+    )?;
+    let commit = head.object()?;
+    let mut expected = format!("{head} commit {}\n", commit.data.len()).into_bytes();
+    expected.extend_from_slice(&commit.data);
+    expected.push(b'\n');
+    assert_eq!(out.as_bstr(), expected.as_bstr());
+    Ok(())
+}
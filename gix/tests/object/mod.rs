@@ -1,3 +1,5 @@
+#[cfg(feature = "revision")]
+mod batch;
 mod blob;
 mod commit;
 mod tree;
@@ -193,6 +193,29 @@ mod track_rewrites {
         Ok(())
     }
 
+    #[test]
+    fn case_only_renames() -> crate::Result {
+        let repo = named_repo("make_merge_branches_repo.sh")?;
+        let from = tree_named(&repo, "main");
+        let to = tree_named(&repo, "case-renamed");
+        let mut actual = Vec::new();
+        from.changes()?
+            .track_path()
+            .track_rewrites(Rewrites::default().into())
+            .for_each_to_obtain_tree(&to, |change| -> Result<_, Infallible> {
+                if !change.event.entry_mode().is_tree() {
+                    actual.push((change.location.to_owned(), change.is_case_only_rename()));
+                }
+                Ok(Default::default())
+            })?;
+        assert_eq!(
+            actual,
+            [("File".into(), true), ("Dir/removed".into(), true)],
+            "renames that only change the case of files or their directories are detected"
+        );
+        Ok(())
+    }
+
     #[test]
     fn rename_by_similarity() -> crate::Result {
         let repo = named_repo("make_diff_repo.sh")?;
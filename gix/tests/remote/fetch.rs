@@ -453,7 +453,8 @@ mod blocking_and_async_io {
                 .await?;
 
             match res.status {
-                gix::remote::fetch::Status::Change { write_pack_bundle, update_refs, negotiate } => {
+                gix::remote::fetch::Status::Change { write_pack_bundle, write_packfile_uri_bundles, update_refs, negotiate } => {
+                    assert!(write_packfile_uri_bundles.is_empty(), "packfile-uris aren't configured");
                     assert_eq!(negotiate.rounds.len(), 1);
                     assert_eq!(write_pack_bundle.index.data_hash, hex_to_id(expected_data_hash), );
                     assert_eq!(write_pack_bundle.index.num_objects, 3 + num_objects_offset, "{fetch_tags:?}");
@@ -534,6 +535,7 @@ mod blocking_and_async_io {
                         write_pack_bundle,
                         update_refs,
                         negotiate,
+                        ..
                     } => {
                         assert_eq!(negotiate.rounds.len(), 1);
                         assert_eq!(write_pack_bundle.pack_version, gix::odb::pack::data::Version::V2);
@@ -651,4 +653,154 @@ mod blocking_and_async_io {
         }
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_prune() -> crate::Result {
+        use gix::refs::transaction::PreviousValue;
+
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        let id = repo.head_id()?.detach();
+        for name in ["refs/remotes/origin/gone", "refs/tags/gone-tag"] {
+            repo.reference(name, id, PreviousValue::MustNotExist, "create stale ref")?;
+        }
+        let tags_before = repo.references()?.tags()?.count();
+
+        let remote = repo.find_remote("origin")?;
+        assert!(!remote.prune(), "pruning is off by default");
+        let fetch = |remote: gix::Remote<'_>, dry_run: bool| -> crate::Result<Vec<String>> {
+            let outcome = remote
+                .connect(Fetch)?
+                .prepare_fetch(progress::Discard, Default::default())?
+                .with_dry_run(dry_run)
+                .receive(progress::Discard, &AtomicBool::default())?;
+            let update_refs = match outcome.status {
+                fetch::Status::Change { update_refs, .. } | fetch::Status::NoPackReceived { update_refs, .. } => {
+                    update_refs
+                }
+            };
+            Ok(update_refs.pruned.iter().map(|edit| edit.name.to_string()).collect())
+        };
+
+        assert_eq!(
+            fetch(remote.clone().with_prune(true), true)?,
+            ["refs/remotes/origin/gone"],
+            "symbolic refs like origin/HEAD are never pruned, and neither are tags by default"
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/gone")?.is_some(),
+            "dry-run doesn't change anything"
+        );
+
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_subsection_value(&gix::config::tree::Remote::PRUNE, "origin", "true")?;
+            config.set_value(&gix::config::tree::Fetch::PRUNE_TAGS, "true")?;
+        }
+        let remote = repo.find_remote("origin")?;
+        assert!(remote.prune() && remote.prune_tags(), "configuration is picked up");
+        assert_eq!(
+            fetch(remote, false)?,
+            ["refs/remotes/origin/gone", "refs/tags/gone-tag"]
+        );
+        assert!(repo.try_find_reference("refs/remotes/origin/gone")?.is_none());
+        assert!(repo.try_find_reference("refs/remotes/origin/HEAD")?.is_some());
+        assert_eq!(
+            repo.references()?.tags()?.count(),
+            tags_before - 1,
+            "only the tag that doesn't exist on the remote is deleted"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_all() -> crate::Result {
+        use gix::remote::fetch_all::Options;
+
+        let (mut repo, _tmp) = repo_rw("two-origins");
+        let fetch_all = |repo: &gix::Repository, options: Options| -> crate::Result<Vec<(String, bool)>> {
+            let outcome = repo.fetch_all(options, &mut progress::Discard, &AtomicBool::default())?;
+            Ok(outcome
+                .remotes
+                .into_iter()
+                .map(|fetched| (fetched.name.to_string(), fetched.result.is_ok()))
+                .collect())
+        };
+
+        let options = Options {
+            jobs: Some(2),
+            dry_run: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            fetch_all(&repo, options.clone())?,
+            [("origin".into(), true), ("changes-on-top-of-origin".into(), true)],
+            "all remotes are fetched, in the order in which they are configured"
+        );
+
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_subsection_value(&gix::config::tree::Remote::SKIP_FETCH_ALL, "origin", "true")?;
+            config.set_value(&gix::config::tree::Fetch::PARALLEL, "0")?;
+            config.set_raw_value(&"remotes.group", "origin missing origin")?;
+        }
+        assert_eq!(
+            fetch_all(&repo, options.clone())?,
+            [("changes-on-top-of-origin".into(), true)],
+            "skipFetchAll excludes remotes"
+        );
+        assert_eq!(
+            fetch_all(
+                &repo,
+                Options {
+                    group: Some("group".into()),
+                    jobs: None,
+                    dry_run: false,
+                }
+            )?,
+            [("origin".into(), true), ("missing".into(), false)],
+            "groups are fetched in order even if some of their remotes fail, and skipFetchAll doesn't apply"
+        );
+        assert_eq!(
+            fetch_all(
+                &repo,
+                Options {
+                    group: Some("origin".into()),
+                    ..options
+                }
+            )?,
+            [("origin".into(), true)],
+            "a group that isn't configured is a remote name"
+        );
+
+        repo.config_snapshot_mut()
+            .set_raw_value(&"remotes.both", "origin changes-on-top-of-origin")?;
+        assert_eq!(
+            fetch_all(
+                &repo,
+                Options {
+                    group: Some("both".into()),
+                    jobs: Some(2),
+                    dry_run: false,
+                }
+            )?,
+            [("origin".into(), true), ("changes-on-top-of-origin".into(), true)]
+        );
+        let fetch_head = repo.fetch_head()?.expect("written");
+        for name in ["origin", "changes-on-top-of-origin"] {
+            let url = repo
+                .find_remote(name)?
+                .url(gix::remote::Direction::Fetch)
+                .expect("set")
+                .to_bstring();
+            assert!(
+                fetch_head
+                    .iter()
+                    .any(|entry| entry.description.ends_with(url.as_slice())),
+                "FETCH_HEAD has the entries of all remotes, instead of only the one that wrote it last"
+            );
+        }
+        Ok(())
+    }
 }
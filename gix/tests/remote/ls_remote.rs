@@ -0,0 +1,48 @@
+#[cfg(feature = "blocking-network-client")]
+mod blocking_io {
+    use gix::protocol::handshake::Ref;
+
+    use crate::remote;
+
+    fn names(refs: &[Ref]) -> Vec<String> {
+        refs.iter().map(|r| r.unpack().0.to_string()).collect()
+    }
+
+    #[test]
+    fn without_patterns_all_refs_are_listed_with_symrefs() -> crate::Result {
+        let repo = remote::repo("clone");
+        let out = repo.ls_remote(Some("origin".into()), &[])?;
+        assert_eq!(out.refs.len(), 18, "HEAD, 11 branches and 6 tags");
+        assert!(
+            matches!(&out.refs[0], Ref::Symbolic { full_ref_name, target, .. } if full_ref_name == "HEAD" && target == "refs/heads/main"),
+            "symbolic refs know their target"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn patterns_match_the_tail_of_ref_names() -> crate::Result {
+        let repo = remote::repo("clone");
+        let out = repo.ls_remote(Some("origin".into()), &["main".into(), "b-tag".into()])?;
+        assert_eq!(names(&out.refs), ["refs/heads/main", "refs/tags/b-tag"]);
+        assert!(
+            matches!(&out.refs[1], Ref::Peeled { object, tag, .. } if object != tag),
+            "annotated tags are peeled"
+        );
+
+        let out = repo.ls_remote(Some("origin".into()), &["refs/tags/*d-tag".into(), "HEAD".into()])?;
+        assert_eq!(
+            names(&out.refs),
+            ["HEAD", "refs/tags/annotated-detached-tag", "refs/tags/detached-tag"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn glob_patterns_anchored_at_refs() -> crate::Result {
+        let repo = remote::repo("clone");
+        let out = repo.ls_remote(Some("origin".into()), &["refs/heads/[ab]".into()])?;
+        assert_eq!(names(&out.refs), ["refs/heads/a", "refs/heads/b"]);
+        Ok(())
+    }
+}
@@ -66,6 +66,7 @@ pub(crate) fn into_daemon_remote_if_async<'repo, 'a>(
 
 mod connect;
 pub(crate) mod fetch;
+mod ls_remote;
 mod ref_map;
 mod save;
 mod name {
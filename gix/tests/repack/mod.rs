@@ -0,0 +1,169 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::repack::{Error, Strategy};
+
+use crate::util::repo_rw;
+
+#[test]
+fn geometric_rolls_up_small_packs_and_loose_objects() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_repo_for_geometric_repack.sh")?;
+    let objects_before = all_object_ids(&repo)?;
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    assert_eq!(num_indices(&pack_dir)?, 4, "one big pack and three small ones");
+
+    let outcome = repo.repack(
+        Strategy::Geometric { factor: 2 },
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(outcome.removed_packs.len(), 3, "all small packs were combined");
+    assert_eq!(outcome.removed_loose_objects, 3, "the last commit wasn't packed yet");
+    assert_eq!(outcome.num_objects, 2 * 3 + 3);
+    assert!(outcome.new_pack.expect("written").is_file());
+    assert!(outcome.multi_index.expect("written").is_file());
+    assert_eq!(num_indices(&pack_dir)?, 2, "the big pack stays untouched");
+
+    let repo = gix::open_opts(repo.path(), crate::restricted())?;
+    let loose = gix::odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+    assert_eq!(loose.iter().count(), 0, "all loose objects are gone");
+    for id in &objects_before {
+        assert!(repo.has_object(id), "{id} is still accessible");
+    }
+
+    let outcome = repo.repack(
+        Strategy::Geometric { factor: 2 },
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(outcome.new_pack, None, "the packs already form a geometric progression");
+    assert!(outcome.removed_packs.is_empty());
+    assert!(
+        outcome.multi_index.is_some(),
+        "the multi-pack index is always refreshed"
+    );
+    Ok(())
+}
+
+#[test]
+fn geometric_leaves_kept_packs_untouched() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_repo_for_geometric_repack.sh")?;
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    let kept_index = std::fs::read_dir(&pack_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|path| {
+            path.extension() == Some("idx".as_ref())
+                && matches!(gix::odb::pack::index::File::at(path, repo.object_hash()), Ok(index) if index.num_objects() == 2)
+        })
+        .expect("there are small packs");
+    gix::odb::pack::keep::create(&kept_index, b"")?.expect("not kept yet");
+
+    let outcome = repo.repack(
+        Strategy::Geometric { factor: 2 },
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(outcome.kept_packs, std::slice::from_ref(&kept_index));
+    assert_eq!(
+        outcome.removed_packs.len(),
+        2,
+        "only the unkept small packs were combined"
+    );
+    assert_eq!(
+        outcome.num_objects,
+        2 * 2 + 3,
+        "two small packs with a commit and a tree each, and loose objects"
+    );
+    assert!(kept_index.is_file());
+    assert_eq!(num_indices(&pack_dir)?, 3, "the big pack, the kept one and the new one");
+
+    let multi_index = gix::odb::pack::multi_index::File::at(outcome.multi_index.expect("written"))?;
+    assert_eq!(
+        multi_index.num_indices(),
+        3,
+        "kept packs are part of the multi-pack index"
+    );
+    Ok(())
+}
+
+#[test]
+fn factor_must_be_at_least_two() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_repo_for_geometric_repack.sh")?;
+    assert!(matches!(
+        repo.repack(
+            Strategy::Geometric { factor: 1 },
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        ),
+        Err(Error::InvalidFactor { factor: 1 })
+    ));
+    Ok(())
+}
+
+#[test]
+fn dissociate_without_alternates_does_nothing() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_repo_for_geometric_repack.sh")?;
+    let pack_dir = repo.objects.store_ref().path().join("pack");
+    let outcome = repo.dissociate(&mut gix::progress::Discard, &AtomicBool::default())?;
+    assert_eq!(outcome, Default::default());
+    assert_eq!(num_indices(&pack_dir)?, 4, "nothing was changed");
+    Ok(())
+}
+
+#[test]
+fn dissociate_copies_only_reachable_objects_of_alternates() -> crate::Result {
+    let (source, _source_tmp) = repo_rw("make_repo_for_geometric_repack.sh")?;
+    let reachable = all_object_ids(&source)?;
+    let unreachable = source.write_blob(b"not reachable from any reference")?.detach();
+
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let repo = gix::init_bare(tmp.path())?;
+    let objects_dir = repo.objects.store_ref().path().to_owned();
+    std::fs::write(
+        objects_dir.join("info").join("alternates"),
+        gix::path::into_bstr(source.objects.store_ref().path().canonicalize()?).as_ref(),
+    )?;
+    let repo = gix::open_opts(repo.path(), crate::restricted())?;
+    repo.reference(
+        "refs/heads/main",
+        source.head_id()?,
+        gix::refs::transaction::PreviousValue::Any,
+        "borrowed",
+    )?;
+    assert!(repo.has_object(unreachable), "everything is borrowed at first");
+
+    let outcome = repo.dissociate(&mut gix::progress::Discard, &AtomicBool::default())?;
+    assert!(outcome.new_pack.is_some());
+    assert!(!objects_dir.join("info").join("alternates").exists());
+
+    let repo = gix::open_opts(repo.path(), crate::restricted())?;
+    for id in &reachable {
+        assert!(repo.has_object(id), "{id} was copied");
+    }
+    assert!(
+        !repo.has_object(unreachable),
+        "objects that aren't reachable from references, HEAD or the index aren't copied"
+    );
+    Ok(())
+}
+
+fn all_object_ids(repo: &gix::Repository) -> crate::Result<Vec<gix::ObjectId>> {
+    let mut ids = Vec::new();
+    for info in repo.head_id()?.ancestors().all()? {
+        let commit = info?.id().object()?.into_commit();
+        ids.push(commit.id);
+        ids.push(commit.tree_id()?.detach());
+        for entry in commit.tree()?.iter() {
+            ids.push(entry?.oid().to_owned());
+        }
+    }
+    Ok(ids)
+}
+
+fn num_indices(pack_dir: &std::path::Path) -> std::io::Result<usize> {
+    Ok(std::fs::read_dir(pack_dir)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension() == Some("idx".as_ref()))
+        .count())
+}
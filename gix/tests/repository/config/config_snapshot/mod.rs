@@ -143,3 +143,118 @@ fn apply_cli_overrides() -> crate::Result {
 
     Ok(())
 }
+
+mod reload {
+    use std::io::Write;
+
+    use serial_test::serial;
+
+    use crate::{repo_rw, repo_rw_opts, restricted};
+
+    fn repo_rw_with_includes() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let mut opts = restricted();
+        opts.permissions.config.includes = true;
+        repo_rw_opts("make_basic_repo.sh", opts)
+    }
+
+    fn append(path: impl AsRef<std::path::Path>, content: &str) -> std::io::Result<()> {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?
+            .write_all(content.as_bytes())
+    }
+
+    #[test]
+    #[serial]
+    fn only_if_files_changed() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        assert!(!repo.is_config_outdated(), "nothing changed yet");
+        assert!(!repo.reload_config_if_changed()?);
+
+        append(repo.git_dir().join("config"), "[core]\n\tabbrev = 4\n")?;
+        assert!(repo.is_config_outdated());
+        assert!(repo.reload_config_if_changed()?);
+        assert_eq!(repo.config_snapshot().integer("core.abbrev"), Some(4));
+        assert_eq!(
+            repo.head_id()?.shorten()?.hex_len(),
+            4,
+            "cached values are updated as well"
+        );
+        assert!(!repo.is_config_outdated(), "the new state was recorded");
+        assert!(!repo.reload_config_if_changed()?);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn includes_and_files_that_do_not_exist_yet_are_tracked() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw_with_includes()?;
+        let include = repo.git_dir().join("include.config");
+        append(repo.git_dir().join("config"), "[include]\n\tpath = include.config\n")?;
+        assert!(repo.reload_config_if_changed()?);
+
+        append(&include, "[a]\n\tb = created\n")?;
+        assert!(repo.is_config_outdated(), "the creation of included files is detected");
+        repo.reload_config()?;
+        assert_eq!(
+            repo.config_snapshot().string("a.b").expect("present").as_ref(),
+            "created"
+        );
+
+        append(&include, "[a]\n\tb = changed\n")?;
+        assert!(
+            repo.reload_config_if_changed()?,
+            "changes to included files are detected"
+        );
+        assert_eq!(
+            repo.config_snapshot().string("a.b").expect("present").as_ref(),
+            "changed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn branch_changes_affect_conditional_includes() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw_with_includes()?;
+        append(
+            repo.git_dir().join("config"),
+            "[includeIf \"onbranch:other\"]\n\tpath = other.config\n",
+        )?;
+        append(repo.git_dir().join("other.config"), "[a]\n\tb = other\n")?;
+        assert!(repo.reload_config_if_changed()?);
+        assert_eq!(repo.config_snapshot().string("a.b"), None, "we are not on the branch");
+
+        std::fs::write(repo.git_dir().join("HEAD"), "ref: refs/heads/other\n")?;
+        assert!(repo.is_config_outdated());
+        assert!(repo.reload_config_if_changed()?);
+        assert_eq!(repo.config_snapshot().string("a.b").expect("present").as_ref(), "other");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn in_memory_changes_are_discarded() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        repo.config_snapshot_mut().set_raw_value(&"a.b", "in-memory")?;
+        assert!(!repo.is_config_outdated(), "changes in memory don't count");
+
+        repo.reload_config()?;
+        assert_eq!(repo.config_snapshot().string("a.b"), None);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn environment_changes_are_detected() -> crate::Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        assert!(!repo.is_config_outdated());
+        let _env = gix_testtools::Env::new().set("GIT_CONFIG_PARAMETERS", "'a.b'='c'");
+        assert!(
+            repo.is_config_outdated(),
+            "environment variables may override configuration"
+        );
+        Ok(())
+    }
+}
@@ -191,3 +191,88 @@ fn author_from_different_config_sections() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+#[serial]
+fn resolve_identity_like_git() -> crate::Result {
+    let _env = Env::new()
+        .set("GIT_AUTHOR_NAME", "env author")
+        .unset("GIT_AUTHOR_EMAIL")
+        .unset("GIT_COMMITTER_NAME")
+        .unset("GIT_COMMITTER_EMAIL")
+        .set("EMAIL", "email@env")
+        .set("USER", "login")
+        .set("HOSTNAME", "host.example.com");
+    let git_dir = gix_testtools::scripted_fixture_read_only("make_empty_repo.sh")?;
+    let open = |overrides: &[&str]| {
+        let mut opts = gix::open::Options::isolated().config_overrides(overrides.iter().copied());
+        opts.permissions.env.identity = Permission::Allow;
+        gix::open_opts(&git_dir, opts)
+    };
+    let id = |name: &str, email: &str| gix_actor::Identity {
+        name: name.into(),
+        email: email.into(),
+    };
+
+    let repo = open(&["author.name=config author", "committer.name=config committer"])?;
+    assert_eq!(
+        repo.resolve_identity(gix_actor::resolve::Role::Author)?,
+        id("env author", "email@env"),
+        "environment variables override the configuration, and EMAIL is auto-detected"
+    );
+    assert_eq!(
+        repo.resolve_identity(gix_actor::resolve::Role::Committer)?,
+        id("config committer", "email@env")
+    );
+
+    let repo = open(&["committer.name=config committer", "user.useConfigOnly=true"])?;
+    assert!(matches!(
+        repo.resolve_identity(gix_actor::resolve::Role::Committer),
+        Err(gix::repository::resolve_identity::Error::Resolve(
+            gix_actor::resolve::Error::EmailAutoDetectionDisabled { .. }
+        ))
+    ));
+
+    let repo = open(&[])?;
+    let identity = repo.resolve_identity(gix_actor::resolve::Role::Committer)?;
+    assert_eq!(identity.email, "email@env");
+    if cfg!(unix) {
+        assert_ne!(
+            identity.name, "login",
+            "on Unix, the user database is used instead of the environment"
+        );
+    } else {
+        assert_eq!(identity.name, "login", "the user name is the last resort");
+    }
+    Ok(())
+}
+
+#[test]
+#[serial]
+#[cfg(target_os = "linux")]
+fn resolve_identity_uses_the_hostname_of_the_system() -> crate::Result {
+    let _env = Env::new()
+        .unset("GIT_COMMITTER_NAME")
+        .unset("GIT_COMMITTER_EMAIL")
+        .unset("EMAIL")
+        .set("HOSTNAME", "host.example.com");
+    let git_dir = gix_testtools::scripted_fixture_read_only("make_empty_repo.sh")?;
+    let mut opts = gix::open::Options::isolated().config_overrides(["user.name=name"]);
+    opts.permissions.env.identity = Permission::Allow;
+    let repo = gix::open_opts(git_dir, opts)?;
+
+    let hostname = std::fs::read_to_string("/proc/sys/kernel/hostname")?;
+    let email = match repo.resolve_identity(gix_actor::resolve::Role::Committer) {
+        Ok(identity) => identity.email,
+        Err(gix::repository::resolve_identity::Error::Resolve(gix_actor::resolve::Error::BogusEmail {
+            email, ..
+        })) => email,
+        Err(err) => return Err(err.into()),
+    };
+    assert!(
+        email.ends_with(format!("@{}", hostname.trim()).as_bytes())
+            || email.ends_with(format!("@{}.(none)", hostname.trim()).as_bytes()),
+        "{email}: the hostname is obtained from the system, not from the environment"
+    );
+    Ok(())
+}
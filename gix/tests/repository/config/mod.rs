@@ -1,6 +1,7 @@
 mod config_snapshot;
 mod identity;
 mod remote;
+mod worktree;
 
 #[cfg(feature = "blocking-network-client")]
 mod ssh_options {
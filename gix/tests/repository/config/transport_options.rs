@@ -54,9 +54,13 @@ mod http {
             connect_timeout,
             verbose,
             ssl_ca_info,
+            ssl_cert,
+            ssl_key,
+            certificates,
             ssl_version,
             ssl_verify,
             http_version,
+            empty_auth,
             backend,
         } = http_options(&repo, None, "https://example.com/does/not/matter");
         assert_eq!(
@@ -78,6 +82,9 @@ mod http {
         assert_eq!(no_proxy, None);
         assert!(!verbose, "verbose is disabled by default");
         assert_eq!(ssl_ca_info.as_deref(), Some(std::path::Path::new("./CA.pem")));
+        assert_eq!(ssl_cert, None);
+        assert_eq!(ssl_key, None);
+        assert!(certificates.is_empty(), "certificates can only be set programmatically");
         #[cfg(feature = "blocking-http-transport-reqwest")]
         {
             assert!(
@@ -110,6 +117,22 @@ mod http {
 
         assert!(ssl_verify, "SSL verification is enabled by default if not configured");
         assert_eq!(http_version, Some(HttpVersion::V1_1));
+        assert!(empty_auth);
+    }
+
+    #[test]
+    fn http_empty_auth() {
+        let repo = repo("http-ssl-url");
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert!(!opts.empty_auth, "it's disabled by default");
+
+        let repo = repo_opts("http-config", |opts| opts.config_overrides(["http.emptyAuth=auto"]));
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert!(!opts.empty_auth, "'auto' is the same as `false` for now");
+
+        let repo = repo_opts("http-config", |opts| opts.config_overrides(["http.emptyAuth"]));
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert!(opts.empty_auth, "empty booleans are true");
     }
 
     #[test]
@@ -122,6 +145,65 @@ mod http {
         );
     }
 
+    #[test]
+    fn http_ssl_url_specific_configuration() {
+        let repo = repo("http-ssl-url");
+        let opts = http_options(&repo, None, "https://other.org/repo");
+        assert_eq!(opts.ssl_cert.as_deref(), Some(std::path::Path::new("./client.pem")));
+        assert_eq!(opts.ssl_key.as_deref(), Some(std::path::Path::new("./client.key")));
+        assert!(opts.ssl_verify, "url specific values don't apply to other hosts");
+        assert_eq!(opts.ssl_ca_info, None);
+
+        let opts = http_options(&repo, None, "https://example.com/other");
+        assert!(!opts.ssl_verify, "the host matches");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./client.pem")),
+            "the path doesn't match"
+        );
+
+        let opts = http_options(&repo, None, "https://example.com/organization");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./client.pem")),
+            "paths only match at component boundaries"
+        );
+
+        let opts = http_options(&repo, None, "https://example.com/org/other");
+        assert_eq!(opts.ssl_cert.as_deref(), Some(std::path::Path::new("./org.pem")));
+        assert_eq!(
+            opts.ssl_key.as_deref(),
+            Some(std::path::Path::new("./client.key")),
+            "unset url specific values fall back to the general ones"
+        );
+
+        let opts = http_options(&repo, None, "https://example.com/org/repo.git");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./org.pem")),
+            "the path must match entirely"
+        );
+        let opts = http_options(&repo, None, "https://example.com/org/repo/");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./repo.pem")),
+            "the longest matching path wins"
+        );
+
+        let opts = http_options(&repo, None, "http://example.com/org/repo");
+        assert!(opts.ssl_verify, "the scheme must match");
+        assert_eq!(opts.ssl_cert.as_deref(), Some(std::path::Path::new("./client.pem")));
+
+        let opts = http_options(&repo, None, "https://git.example.com/repo");
+        assert_eq!(
+            opts.ssl_ca_info.as_deref(),
+            Some(std::path::Path::new("./wildcard-CA.pem"))
+        );
+        assert!(opts.ssl_verify);
+        let opts = http_options(&repo, None, "https://example.com:8443/org");
+        assert!(opts.ssl_verify, "ports must match as well");
+    }
+
     #[test]
     fn http_ssl_version_min_max_overrides_ssl_version() {
         let repo = repo("http-ssl-version-min-max");
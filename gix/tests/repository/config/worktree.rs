@@ -0,0 +1,84 @@
+use gix::bstr::ByteSlice;
+
+fn repo_with_local_config(tmp: &gix_testtools::tempfile::TempDir, config: &str) -> crate::Result<gix::Repository> {
+    let repo = gix::init(tmp.path())?;
+    let config_path = repo.git_dir().join("config");
+    let mut buf = std::fs::read(&config_path)?;
+    buf.extend_from_slice(config.as_bytes());
+    std::fs::write(config_path, buf)?;
+    Ok(gix::open_opts(tmp.path(), gix::open::Options::isolated())?)
+}
+
+fn read(path: impl AsRef<std::path::Path>) -> crate::Result<String> {
+    Ok(std::fs::read(path)?.to_str_lossy().into_owned())
+}
+
+#[test]
+fn enabling_moves_worktree_specific_keys_to_the_main_worktree() -> crate::Result {
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let mut repo = repo_with_local_config(&tmp, "[core]\n\tsparseCheckout = true\n")?;
+    assert!(repo.enable_worktree_config()?, "it wasn't enabled before");
+    assert!(!repo.enable_worktree_config()?, "it's already enabled");
+
+    let local = read(repo.git_dir().join("config"))?;
+    assert!(local.contains("repositoryFormatVersion = 1"), "{local}");
+    assert!(local.contains("[extensions]\n\tworktreeConfig = true"), "{local}");
+    assert!(local.contains("bare = false"), "core.bare is only moved if true");
+    assert!(!local.contains("sparseCheckout"), "{local}");
+    assert_eq!(
+        read(repo.git_dir().join("config.worktree"))?,
+        "[core]\n\tsparseCheckout = true\n"
+    );
+
+    let config = repo.config_snapshot();
+    assert_eq!(
+        config.boolean("core.sparseCheckout"),
+        Some(true),
+        "the configuration was reloaded and includes config.worktree"
+    );
+    assert_eq!(config.boolean("extensions.worktreeConfig"), Some(true));
+    Ok(())
+}
+
+#[test]
+fn editing_writes_to_config_worktree_if_enabled() -> crate::Result {
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let mut repo = repo_with_local_config(&tmp, "")?;
+    repo.enable_worktree_config()?;
+    repo.edit_worktree_config(|config| config.set_raw_value(&"user.name", "worktree"))??;
+
+    assert_eq!(
+        read(repo.git_dir().join("config.worktree"))?,
+        "[user]\n\tname = worktree\n"
+    );
+    assert!(!read(repo.git_dir().join("config"))?.contains("worktree\n"));
+    assert_eq!(
+        repo.config_snapshot().string("user.name").expect("set").as_ref(),
+        "worktree"
+    );
+    Ok(())
+}
+
+#[test]
+fn editing_without_worktree_config_writes_to_the_local_config_if_there_are_no_linked_worktrees() -> crate::Result {
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let mut repo = repo_with_local_config(&tmp, "")?;
+    repo.edit_worktree_config(|config| config.set_raw_value(&"user.name", "local"))??;
+
+    assert!(!repo.git_dir().join("config.worktree").exists());
+    assert!(read(repo.git_dir().join("config"))?.contains("[user]\n\tname = local\n"));
+    Ok(())
+}
+
+#[test]
+fn editing_without_worktree_config_fails_if_there_are_linked_worktrees() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_worktree_repo.sh")?;
+    let mut repo = gix::open_opts(tmp.path().join("repo"), gix::open::Options::isolated())?;
+    let config_before = read(repo.git_dir().join("config"))?;
+    let err = repo
+        .edit_worktree_config(|config| config.set_raw_value(&"user.name", "local").map(|_| ()))
+        .unwrap_err();
+    assert!(matches!(err, gix::repository::edit_worktree_config::Error::NotEnabled));
+    assert_eq!(read(repo.git_dir().join("config"))?, config_before, "nothing changed");
+    Ok(())
+}
@@ -0,0 +1,578 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::merge::tree::{conflict, Conflict};
+
+fn tree_id(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(format!("{spec}^{{tree}}").as_str())?.detach())
+}
+
+#[test]
+fn trees_to_index() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_tree_merge_repo.sh")?;
+    let labels = gix::merge::blob::Labels {
+        ancestor: None,
+        current: Some("ours".into()),
+        other: Some("theirs".into()),
+    };
+    let outcome = repo.merge_trees_to_index(
+        &tree_id(&repo, "base")?,
+        &tree_id(&repo, "ours")?,
+        &tree_id(&repo, "theirs")?,
+        labels,
+        repo.tree_merge_options()?,
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert!(outcome.has_conflicts());
+    assert_eq!(
+        outcome.index.path(),
+        repo.index_path(),
+        "the index is placed as the repository index"
+    );
+
+    let entries: Vec<_> = outcome
+        .index
+        .entries()
+        .iter()
+        .map(|e| (e.path(&outcome.index).to_string(), e.stage() as u8, e.mode))
+        .collect();
+    let (file, exe) = (gix::index::entry::Mode::FILE, gix::index::entry::Mode::FILE_EXECUTABLE);
+    assert_eq!(
+        entries,
+        [
+            ("clean".into(), 0, file),
+            ("conflict".into(), 1, file),
+            ("conflict".into(), 2, file),
+            ("conflict".into(), 3, file),
+            ("df".into(), 2, file),
+            ("df/file".into(), 0, file),
+            ("dir/file".into(), 0, file),
+            ("exe".into(), 0, exe),
+            ("modify-delete".into(), 1, file),
+            ("modify-delete".into(), 2, file),
+            ("new-ours".into(), 0, file),
+            ("unchanged".into(), 0, file),
+        ],
+        "conflicts are placed in their stages, while everything else is merged"
+    );
+
+    let conflict_blob = outcome.conflicts[0]
+        .merged_blob
+        .expect("content conflicts have a merged blob");
+    assert_eq!(
+        outcome.conflicts,
+        [
+            Conflict {
+                path: "conflict".into(),
+                kind: conflict::Kind::Content,
+                merged_blob: Some(conflict_blob),
+            },
+            Conflict {
+                path: "df".into(),
+                kind: conflict::Kind::FileDirectory {
+                    file_by: conflict::Side::Ours
+                },
+                merged_blob: None,
+            },
+            Conflict {
+                path: "modify-delete".into(),
+                kind: conflict::Kind::ModifyDelete {
+                    deleted_by: conflict::Side::Theirs
+                },
+                merged_blob: None,
+            },
+        ]
+    );
+    assert_eq!(
+        repo.find_object(conflict_blob)?.data.as_slice(),
+        b"1\n<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\n3\n",
+        "the merged blob is written to the object database"
+    );
+    let clean = outcome.index.entry_by_path("clean".into()).expect("present");
+    assert_eq!(
+        repo.find_object(clean.id)?.data.as_slice(),
+        b"1\nours\n3\n4\n5\n6\n7\ntheirs\n9\n",
+        "changes to different lines are merged"
+    );
+
+    let mut index = outcome.index;
+    index.write(Default::default())?;
+    let index = repo.open_index()?;
+    assert_eq!(index.entries().len(), 12, "the index can be written to disk");
+    assert!(
+        index
+            .entries()
+            .iter()
+            .any(|e| e.stage() != gix::index::entry::Stage::Unconflicted),
+        "conflicts are written as well"
+    );
+    Ok(())
+}
+
+#[test]
+fn identical_trees_have_no_conflicts() -> crate::Result {
+    let repo = crate::named_repo("make_tree_merge_repo.sh")?;
+    let ours = tree_id(&repo, "ours")?;
+    let outcome = repo.merge_trees_to_index(
+        &tree_id(&repo, "base")?,
+        &ours,
+        &ours,
+        Default::default(),
+        repo.tree_merge_options()?,
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert!(!outcome.has_conflicts());
+    let expected = repo.index_from_tree(&ours)?;
+    assert_eq!(
+        outcome
+            .index
+            .entries()
+            .iter()
+            .map(|e| (e.id, e.mode))
+            .collect::<Vec<_>>(),
+        expected.entries().iter().map(|e| (e.id, e.mode)).collect::<Vec<_>>(),
+        "it's the same as our tree"
+    );
+    Ok(())
+}
+
+mod merge_base {
+    #[test]
+    fn octopus() -> crate::Result {
+        let repo = crate::named_repo("make_merge_branches_repo.sh")?;
+        let tips = [
+            crate::rev_parse_id(&repo, "main")?,
+            crate::rev_parse_id(&repo, "diverged")?,
+            crate::rev_parse_id(&repo, "conflicting")?,
+        ];
+        assert_eq!(
+            repo.merge_base_octopus(tips)?.map(gix::Id::detach),
+            Some(crate::rev_parse_id(&repo, "diverged~2")?),
+            "the commit all branches forked off from"
+        );
+        assert_eq!(
+            repo.merge_base_octopus([crate::rev_parse_id(&repo, "main")?])?
+                .map(gix::Id::detach),
+            Some(crate::rev_parse_id(&repo, "main")?),
+            "a single commit is its own merge-base"
+        );
+        assert_eq!(repo.merge_base_octopus(Vec::<gix::ObjectId>::new())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn is_ancestor() -> crate::Result {
+        let repo = crate::named_repo("make_merge_branches_repo.sh")?;
+        let (behind, main, diverged) = (
+            crate::rev_parse_id(&repo, "behind")?,
+            crate::rev_parse_id(&repo, "main")?,
+            crate::rev_parse_id(&repo, "diverged")?,
+        );
+        assert!(repo.is_ancestor(behind, main)?);
+        assert!(repo.is_ancestor(main, crate::rev_parse_id(&repo, "expected-merge")?)?);
+        assert!(repo.is_ancestor(main, main)?, "commits are their own ancestors");
+        assert!(!repo.is_ancestor(main, behind)?);
+        assert!(
+            !repo.is_ancestor(main, diverged)?,
+            "no ancestry across diverged histories"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn independent() -> crate::Result {
+        let repo = crate::named_repo("make_merge_branches_repo.sh")?;
+        let commits = [
+            crate::rev_parse_id(&repo, "behind")?,
+            crate::rev_parse_id(&repo, "main")?,
+            crate::rev_parse_id(&repo, "ahead")?,
+            crate::rev_parse_id(&repo, "diverged")?,
+            crate::rev_parse_id(&repo, "ahead")?,
+        ];
+        let mut actual: Vec<_> = repo.independent(commits)?.into_iter().map(gix::Id::detach).collect();
+        actual.sort();
+        let mut expected = vec![
+            crate::rev_parse_id(&repo, "ahead")?,
+            crate::rev_parse_id(&repo, "diverged")?,
+        ];
+        expected.sort();
+        assert_eq!(
+            actual, expected,
+            "commits reachable from others are removed, as are duplicates"
+        );
+        Ok(())
+    }
+}
+
+mod merge {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::merge::{FastForward, Options, Outcome};
+
+    #[test]
+    fn up_to_date() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let head = repo.head_id()?;
+        assert_eq!(
+            repo.merge(
+                "behind",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &AtomicBool::default()
+            )?,
+            Outcome::UpToDate
+        );
+        assert_eq!(repo.head_id()?, head, "nothing changed");
+        Ok(())
+    }
+
+    #[test]
+    fn fast_forward() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let previous = repo.head_id()?.detach();
+        let outcome = repo.merge(
+            "ahead",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?;
+        let new = crate::rev_parse_id(&repo, "ahead")?;
+        assert_eq!(
+            outcome,
+            Outcome::FastForward {
+                previous: Some(previous),
+                new
+            }
+        );
+        assert_eq!(repo.head_id()?, new);
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/main",
+            "the branch was moved"
+        );
+        assert_eq!(
+            crate::read_worktree_file(&repo, "ahead")?,
+            "ahead\n",
+            "the worktree was updated"
+        );
+        assert!(!repo.is_dirty()?, "the index was updated as well");
+        assert_eq!(
+            repo.head()?
+                .log_iter()
+                .rev()?
+                .expect("reflog")
+                .next()
+                .expect("one line")?
+                .message,
+            "merge ahead: Fast-forward"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fast_forward_applies_case_only_renames_if_case_is_ignored() -> crate::Result {
+        let (mut repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Core::IGNORE_CASE, "true")?;
+        let content = crate::read_worktree_file(&repo, "file")?;
+        repo.merge(
+            "case-renamed",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?;
+
+        let workdir = repo.work_dir().expect("non-bare");
+        let mut names: Vec<_> = std::fs::read_dir(workdir)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<_, _>>()?;
+        names.sort();
+        assert_eq!(
+            names,
+            [".git", "Dir", "File", "removed"],
+            "files and directories were renamed, even on case-insensitive filesystems"
+        );
+        assert_eq!(crate::read_worktree_file(&repo, "File")?, content);
+        assert_eq!(crate::read_worktree_file(&repo, "Dir/removed")?, "content\n");
+        assert!(!repo.is_dirty()?, "the index matches the worktree");
+        Ok(())
+    }
+
+    #[test]
+    fn no_fast_forward_creates_merge_commit() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let previous = repo.head_id()?.detach();
+        let outcome = repo.merge(
+            "ahead",
+            Options {
+                fast_forward: Some(FastForward::Never),
+                ..Default::default()
+            },
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?;
+        let Outcome::Merge { commit } = outcome else {
+            panic!("expected merge commit, got {outcome:?}")
+        };
+        let commit = repo.find_object(commit)?.into_commit();
+        assert_eq!(
+            commit.parent_ids().map(|id| id.detach()).collect::<Vec<_>>(),
+            [previous, crate::rev_parse_id(&repo, "ahead")?]
+        );
+        assert_eq!(commit.message_raw()?, "Merge branch 'ahead'\n");
+        assert_eq!(commit.tree_id()?, crate::rev_parse_id(&repo, "ahead^{tree}")?);
+        assert_eq!(repo.head_id()?, commit.id);
+        assert!(!repo.is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn interrupted_merges_and_fast_forwards_fail_without_changing_head() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let head = repo.head_id()?;
+        let should_interrupt = AtomicBool::new(true);
+        let err = repo
+            .merge(
+                "diverged",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &should_interrupt,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            gix::merge::Error::MergeTrees(gix::merge::trees_to_index::Error::MergeTrees(
+                gix::merge::tree::Error::Interrupted
+            ))
+        ));
+        let err = repo
+            .merge(
+                "ahead",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &should_interrupt,
+            )
+            .unwrap_err();
+        assert!(matches!(err, gix::merge::Error::Interrupted));
+        assert_eq!(repo.head_id()?, head, "nothing was committed or fast-forwarded");
+        Ok(())
+    }
+
+    #[test]
+    fn fast_forward_only_fails_if_histories_diverged() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let err = repo
+            .merge(
+                "diverged",
+                Options {
+                    fast_forward: Some(FastForward::Only),
+                    ..Default::default()
+                },
+                &mut gix::progress::Discard,
+                &AtomicBool::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, gix::merge::Error::NotPossibleToFastForward));
+        Ok(())
+    }
+
+    #[test]
+    fn fast_forward_policy_is_configurable() -> crate::Result {
+        let (mut repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Merge::FF, "only")?;
+        let err = repo
+            .merge(
+                "diverged",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &AtomicBool::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, gix::merge::Error::NotPossibleToFastForward));
+        Ok(())
+    }
+
+    #[test]
+    fn clean_merge() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let previous = repo.head_id()?.detach();
+        let Outcome::Merge { commit } = repo.merge(
+            "diverged",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?
+        else {
+            panic!("expected merge commit")
+        };
+        let commit = repo.find_object(commit)?.into_commit();
+        assert_eq!(
+            commit.tree_id()?,
+            crate::rev_parse_id(&repo, "expected-merge^{tree}")?,
+            "the tree is the same as the one produced by git"
+        );
+        assert_eq!(
+            commit.parent_ids().map(|id| id.detach()).collect::<Vec<_>>(),
+            [previous, crate::rev_parse_id(&repo, "diverged")?]
+        );
+        assert_eq!(commit.message_raw()?, "Merge branch 'diverged'\n");
+        assert_eq!(crate::read_worktree_file(&repo, "file")?, "1\nmain\n3\n4\ndiverged\n");
+        assert_eq!(crate::read_worktree_file(&repo, "new")?, "new\n");
+        let workdir = repo.work_dir().expect("non-bare");
+        assert!(!workdir.join("removed").exists(), "deleted files are removed");
+        assert!(!workdir.join("dir").exists(), "empty directories are removed as well");
+        assert!(!repo.is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_log_lists_merged_commits() -> crate::Result {
+        let (mut repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Merge::LOG, "1")?;
+        let Outcome::Merge { commit } = repo.merge(
+            "diverged",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?
+        else {
+            panic!("expected merge commit")
+        };
+        assert_eq!(
+            repo.find_object(commit)?.into_commit().message_raw()?,
+            "Merge branch 'diverged'\n\n* diverged: (2 commits)\n  change file\n  ...\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge_log_excludes_commits_reachable_from_head() -> crate::Result {
+        let (mut repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Merge::LOG, "true")?;
+        let Outcome::Merge { commit } = repo.merge(
+            "merged-main",
+            Options {
+                fast_forward: Some(FastForward::Never),
+                ..Default::default()
+            },
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?
+        else {
+            panic!("expected merge commit")
+        };
+        assert_eq!(
+            repo.find_object(commit)?.into_commit().message_raw()?,
+            "Merge branch 'merged-main'\n\n* merged-main:\n  after merge\n  change file\n  remove files\n",
+            "commits that were merged from `HEAD` before aren't listed, just like `git fmt-merge-msg` does it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn local_changes_prevent_merging() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        std::fs::write(repo.work_dir().expect("non-bare").join("file"), "changed")?;
+        let err = repo
+            .merge(
+                "diverged",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &AtomicBool::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, gix::merge::Error::LocalChanges));
+        Ok(())
+    }
+
+    #[test]
+    fn untracked_files_are_not_overwritten() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let head = repo.head_id()?;
+        std::fs::write(repo.work_dir().expect("non-bare").join("ahead"), "untracked")?;
+        let err = repo
+            .merge(
+                "ahead",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &AtomicBool::default(),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, gix::merge::Error::UntrackedFilesWouldBeOverwritten { paths } if paths == &["ahead"]),
+            "{err:?}"
+        );
+        assert_eq!(
+            crate::read_worktree_file(&repo, "ahead")?,
+            "untracked",
+            "the file was left untouched"
+        );
+        assert_eq!(repo.head_id()?, head, "HEAD didn't move");
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts_are_written_to_index_and_worktree() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+        let head = repo.head_id()?.detach();
+        let outcome = repo.merge(
+            "conflicting",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?;
+        let Outcome::Conflict { conflicts } = outcome else {
+            panic!("expected conflict, got {outcome:?}")
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, "file");
+        assert_eq!(repo.head_id()?, head, "no commit was created");
+
+        assert_eq!(
+            crate::read_worktree_file(&repo, "file")?,
+            "1\n<<<<<<< HEAD\nmain\n=======\nconflicting\n>>>>>>> conflicting\n3\n4\n5\n"
+        );
+        let index = repo.open_index()?;
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|e| (e.path(&index).to_string(), e.stage() as u8))
+                .collect::<Vec<_>>(),
+            [
+                ("dir/removed".to_string(), 0),
+                ("file".into(), 1),
+                ("file".into(), 2),
+                ("file".into(), 3),
+                ("removed".into(), 0),
+            ]
+        );
+
+        let git_dir = repo.git_dir();
+        assert_eq!(
+            std::fs::read_to_string(git_dir.join("MERGE_HEAD"))?,
+            format!("{}\n", crate::rev_parse_id(&repo, "conflicting")?)
+        );
+        assert_eq!(
+            std::fs::read_to_string(git_dir.join("MERGE_MSG"))?,
+            "Merge branch 'conflicting'\n\n# Conflicts:\n#\tfile\n"
+        );
+        assert_eq!(std::fs::read_to_string(git_dir.join("ORIG_HEAD"))?, format!("{head}\n"));
+        assert_eq!(repo.state(), Some(gix::state::InProgress::Merge));
+
+        let err = repo
+            .merge(
+                "diverged",
+                Options::default(),
+                &mut gix::progress::Discard,
+                &AtomicBool::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, gix::merge::Error::MergeInProgress));
+        Ok(())
+    }
+}
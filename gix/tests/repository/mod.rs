@@ -5,12 +5,20 @@ mod config;
 mod excludes;
 #[cfg(feature = "attributes")]
 mod filter;
+#[cfg(feature = "merge")]
+mod merge;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
 mod pathspec;
+#[cfg(all(feature = "rebase", feature = "blocking-network-client"))]
+mod pull;
+#[cfg(feature = "rebase")]
+mod rebase;
 mod reference;
 mod remote;
+#[cfg(feature = "merge")]
+mod sandbox;
 mod shallow;
 mod state;
 #[cfg(feature = "attributes")]
@@ -74,6 +82,70 @@ mod dirwalk {
         );
         Ok(())
     }
+
+    #[test]
+    fn skipped_entries_are_reported() -> crate::Result {
+        let repo = crate::named_repo("make_basic_repo.sh")?;
+        let options = repo
+            .dirwalk_options()?
+            .emit_untracked(EmissionMode::CollapseDirectory)
+            .max_depth(Some(3));
+        let mut collect = gix::dir::walk::delegate::Collect::default();
+        let index = repo.index()?;
+        repo.dirwalk(&index, None::<&str>, &AtomicBool::default(), options, &mut collect)?;
+        assert!(
+            collect
+                .skipped_entries
+                .iter()
+                .any(|(entry, _)| entry.rela_path == "some/very/deeply"),
+            "the options are passed on to the directory walk"
+        );
+        assert!(collect
+            .skipped_entries
+            .iter()
+            .all(|(_, reason)| *reason == gix_dir::walk::SkipReason::TooDeep));
+        Ok(())
+    }
+
+    #[test]
+    fn nested_repositories_with_head() -> crate::Result {
+        let repo = crate::named_repo("make_basic_repo.sh")?;
+        let options = repo
+            .dirwalk_options()?
+            .emit_untracked(EmissionMode::CollapseDirectory)
+            .nested_repositories(gix_dir::walk::NestedRepositoryMode::Emit);
+        let mut actual: Vec<_> = repo
+            .dirwalk_iter(repo.index()?, None::<&str>, Default::default(), options)?
+            .map(Result::unwrap)
+            .filter(|item| item.entry.disk_kind == Some(Repository))
+            .map(|item| {
+                assert_eq!(
+                    item.entry.property,
+                    Some(gix_dir::entry::Property::NestedRepository),
+                    "each repository is marked"
+                );
+                let head = match item
+                    .nested_repository_head
+                    .expect("each nested repository can be opened")
+                {
+                    gix::head::Kind::Symbolic(_) => "symbolic",
+                    gix::head::Kind::Unborn(_) => "unborn",
+                    gix::head::Kind::Detached { .. } => "detached",
+                };
+                (item.entry.rela_path.to_string(), head)
+            })
+            .collect();
+        actual.sort();
+        assert_eq!(
+            actual,
+            [
+                ("all-untracked".to_string(), "unborn"),
+                ("empty-core-excludes".into(), "unborn"),
+                ("non-bare-repo-without-index".into(), "symbolic"),
+            ]
+        );
+        Ok(())
+    }
 }
 
 #[test]
@@ -54,6 +54,48 @@ mod write_blob {
     }
 }
 
+#[test]
+fn object_database_stats() -> crate::Result {
+    let mut repo = crate::named_repo("make_packed_and_loose.sh")?;
+    repo.object_cache_size(128 * 1024);
+    let stats = repo.object_database_stats()?;
+    assert_eq!(stats.packs, 1, "`git gc` produced a single pack");
+    assert_eq!(stats.multi_pack_indices, 0);
+    assert_eq!(stats.loose_objects, 3, "the last commit wasn't packed");
+    assert!(stats.alternates.is_empty());
+    assert!(
+        !stats.has_bitmaps,
+        "bitmaps are only written by default in bare repositories"
+    );
+    assert_eq!(stats.object_cache, Default::default(), "nothing was looked up yet");
+    assert_eq!(stats.metrics.known_packs, stats.packs);
+
+    let head_id = repo.head_id()?.detach();
+    let before = repo.object_database_stats()?.object_cache;
+    assert_eq!(
+        before.misses, 1,
+        "peeling HEAD looks up its commit, which populates the cache"
+    );
+    for _ in 0..2 {
+        repo.find_object(head_id)?;
+    }
+    let stats = repo.object_database_stats()?;
+    assert_eq!(
+        stats.object_cache,
+        gix::repository::CacheStats {
+            hits: before.hits + 2,
+            misses: before.misses
+        },
+        "the commit is now found in the cache each time"
+    );
+    assert_eq!(
+        repo.clone().object_database_stats()?.object_cache,
+        stats.object_cache,
+        "clones share their cache statistics"
+    );
+    Ok(())
+}
+
 #[test]
 fn writes_avoid_io_using_duplicate_check() -> crate::Result {
     let repo = crate::named_repo("make_packed_and_loose.sh")?;
@@ -73,6 +115,12 @@ fn writes_avoid_io_using_duplicate_check() -> crate::Result {
         let header = repo.find_header(id)?;
         assert_eq!(obj.kind, header.kind(), "header and object agree");
         assert_eq!(repo.try_find_header(id)?, Some(header));
+        assert_eq!(
+            repo.object_size(id)?,
+            obj.data.len() as u64,
+            "sizes are obtained from headers, loose or packed"
+        );
+        assert_eq!(repo.try_object_size(id)?, Some(obj.data.len() as u64));
         use gix_object::Kind::*;
         match obj.kind {
             Commit => {
@@ -182,6 +230,8 @@ mod find {
             }),
             "empty tree is considered a loose object"
         );
+        assert_eq!(repo.object_size(empty_tree)?, 0);
+        assert_eq!(repo.try_object_size(empty_tree)?, Some(0));
 
         let mut buf = Vec::new();
         assert!(
@@ -397,6 +447,65 @@ mod commit {
     }
 }
 
+#[cfg(feature = "attributes")]
+mod commit_encoding {
+    use gix::bstr::ByteSlice;
+
+    #[test]
+    fn configured_encoding_is_used_for_writing_and_undone_when_reading() -> crate::Result {
+        let (mut repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
+        let parent = repo.head_id()?.detach();
+        let tree = repo.find_object(parent)?.into_commit().tree_id()?.detach();
+        repo.config_snapshot_mut()
+            .set_raw_value(&"i18n.commitEncoding", "ISO-8859-1")?;
+
+        let id = repo.commit("HEAD", "café\n", tree, Some(parent))?.detach();
+        {
+            let commit = repo.find_object(id)?.into_commit();
+            let decoded = commit.decode()?;
+            assert_eq!(decoded.encoding, Some("ISO-8859-1".into()), "the encoding is recorded");
+            assert_eq!(
+                decoded.message,
+                b"caf\xe9\n".as_bstr(),
+                "the message is stored as latin-1"
+            );
+            assert_eq!(commit.message_utf8()?.as_ref(), "café\n", "it can be converted back");
+        }
+        assert_eq!(
+            repo.head()?
+                .log_iter()
+                .rev()?
+                .expect("present")
+                .next()
+                .expect("line")?
+                .message,
+            "commit: café",
+            "the reflog is always UTF-8"
+        );
+
+        repo.config_snapshot_mut()
+            .set_raw_value(&"i18n.commitEncoding", "utf-8")?;
+        let id = repo.commit("HEAD", "café", tree, Some(id))?.detach();
+        {
+            let commit = repo.find_object(id)?.into_commit();
+            assert_eq!(
+                commit.decode()?.encoding,
+                None,
+                "UTF-8 is the default and isn't recorded"
+            );
+            assert_eq!(commit.message_utf8()?.as_ref(), "café");
+        }
+
+        repo.config_snapshot_mut()
+            .set_raw_value(&"i18n.commitEncoding", "unknown")?;
+        assert_eq!(
+            repo.commit("HEAD", "café", tree, Some(id)).unwrap_err().to_string(),
+            "The encoding named 'unknown' seen in key 'i18n.commitEncoding=unknown' is unsupported"
+        );
+        Ok(())
+    }
+}
+
 fn empty_bare_repo() -> crate::Result<(tempfile::TempDir, gix::Repository)> {
     let tmp = tempfile::tempdir()?;
     let repo = gix::ThreadSafeRepository::init_opts(
@@ -287,6 +287,51 @@ mod object_caches {
         assert!(!repo.objects.has_pack_cache());
         Ok(())
     }
+
+    #[test]
+    fn soft_limits_from_configuration() -> crate::Result {
+        let opts = gix::open::Options::isolated().config_overrides([
+            "gitoxide.objects.packCacheSoftLimit=2m",
+            "gitoxide.objects.cacheSoftLimit=0",
+        ]);
+        let repo = named_subrepo_opts("make_config_repos.sh", "object-caches", opts)?;
+        let usage = repo.memory_usage();
+        assert_eq!(usage.pack_cache_soft_limit, Some(2 * 1024 * 1024));
+        assert_eq!(usage.object_cache_soft_limit, None, "0 means there is no limit");
+        Ok(())
+    }
+
+    #[test]
+    fn memory_usage_is_shared_by_clones_and_limited() -> crate::Result {
+        let mut repo = crate::basic_repo()?;
+        repo.object_cache_size(128 * 1024);
+        let read_all_commits = |repo: &gix::Repository| -> crate::Result {
+            for commit in repo.head_id()?.ancestors().all()? {
+                commit?.id().object()?;
+            }
+            Ok(())
+        };
+        assert_eq!(repo.memory_usage().object_caches, 0, "nothing was cached yet");
+
+        read_all_commits(&repo)?;
+        let usage = repo.memory_usage();
+        assert!(usage.object_caches > 0, "objects are cached and accounted for");
+        assert_eq!(usage.evictions, 0, "there is no limit by default");
+        assert_eq!(repo.clone().memory_usage(), usage, "clones share the same budget");
+
+        repo.object_cache_budget().set_soft_limit(1);
+        repo.object_cache_size(128 * 1024);
+        assert_eq!(
+            repo.memory_usage().object_caches,
+            0,
+            "replaced caches release their memory"
+        );
+        read_all_commits(&repo)?;
+        let usage = repo.memory_usage();
+        assert_eq!(usage.object_caches, 0, "each cache is cleared as it exceeds the limit");
+        assert!(usage.evictions > 0);
+        Ok(())
+    }
 }
 
 mod worktree {
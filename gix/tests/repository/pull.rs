@@ -0,0 +1,180 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{
+    bstr::ByteSlice,
+    config::tree::{Branch, Pull},
+    pull::{Integration, Options},
+};
+
+fn pull(repo: &gix::Repository, options: Options) -> Result<gix::pull::Outcome, gix::pull::Error> {
+    repo.pull(None, gix::progress::Discard, &AtomicBool::default(), options)
+}
+
+#[test]
+fn fast_forward_by_default() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "behind")?;
+    let previous = repo.head_id()?.detach();
+    let outcome = pull(&repo, Options::default())?;
+    assert_eq!(
+        outcome.integration,
+        Integration::Merge(gix::merge::Outcome::FastForward {
+            previous: Some(previous),
+            new: outcome.upstream
+        })
+    );
+    assert_eq!(repo.head_id()?, outcome.upstream);
+    assert_eq!(
+        repo.find_reference("refs/remotes/origin/main")?.id(),
+        outcome.upstream,
+        "remote tracking branches are updated as well"
+    );
+    assert_eq!(
+        repo.fetch_head()?
+            .expect("written")
+            .into_iter()
+            .filter(|entry| entry.for_merge)
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>(),
+        [outcome.upstream],
+        "FETCH_HEAD marks what was merged"
+    );
+    assert_eq!(crate::read_worktree_file(&repo, "file")?, "remote-1\n2\n3\n4\n5\n");
+
+    let outcome = pull(&repo, Options::default())?;
+    assert_eq!(
+        outcome.integration,
+        Integration::Merge(gix::merge::Outcome::UpToDate),
+        "pulling again has nothing to do"
+    );
+    Ok(())
+}
+
+#[test]
+fn upstream_is_fetched_even_if_refspecs_dont_include_it() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "narrow-refspec")?;
+    let outcome = pull(&repo, Options::default())?;
+    assert!(matches!(
+        outcome.integration,
+        Integration::Merge(gix::merge::Outcome::FastForward { .. })
+    ));
+    assert_eq!(repo.head_id()?, outcome.upstream);
+    Ok(())
+}
+
+#[test]
+fn diverged_history_is_merged() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "diverged")?;
+    let previous = repo.head_id()?.detach();
+    let outcome = pull(&repo, Options::default())?;
+    let Integration::Merge(gix::merge::Outcome::Merge { commit }) = outcome.integration else {
+        panic!("expected merge commit, got {:?}", outcome.integration)
+    };
+    let commit = repo.find_object(commit)?.into_commit();
+    assert_eq!(
+        commit.parent_ids().map(|id| id.detach()).collect::<Vec<_>>(),
+        [previous, outcome.upstream]
+    );
+    let message = commit.message_raw()?.to_string();
+    assert!(
+        message.starts_with("Merge branch 'main' of ") && message.ends_with("remote\n"),
+        "the message mentions the remote just like git does: {message:?}"
+    );
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "remote-1\n2\n3\n4\nlocal-5\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn fast_forward_only_is_configurable() -> crate::Result {
+    let (mut repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "diverged")?;
+    repo.config_snapshot_mut().set_value(&Pull::FF, "only")?;
+    let err = pull(&repo, Options::default()).unwrap_err();
+    assert!(matches!(
+        err,
+        gix::pull::Error::Merge(gix::merge::Error::NotPossibleToFastForward)
+    ));
+    Ok(())
+}
+
+#[test]
+fn rebase_is_configurable_per_branch_and_globally() -> crate::Result {
+    let (mut repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "diverged")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Pull::REBASE, "true")?;
+        config.set_subsection_value(&Branch::REBASE, "main", "false")?;
+    }
+    let err = pull(
+        &repo,
+        Options {
+            fast_forward: Some(gix::merge::FastForward::Only),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, gix::pull::Error::Merge(_)),
+        "the branch configuration has precedence"
+    );
+
+    repo.config_snapshot_mut()
+        .set_subsection_value(&Branch::REBASE, "main", "true")?;
+    let outcome = pull(&repo, Options::default())?;
+    assert!(matches!(
+        outcome.integration,
+        Integration::Rebase(gix::rebase::Outcome::Complete { .. })
+    ));
+    assert_eq!(repo.rev_parse_single("HEAD~1")?, outcome.upstream);
+    assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/main");
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "remote-1\n2\n3\n4\nlocal-5\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn rebase_can_be_forced() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "diverged")?;
+    let outcome = pull(
+        &repo,
+        Options {
+            rebase: Some(true),
+            ..Default::default()
+        },
+    )?;
+    assert!(matches!(outcome.integration, Integration::Rebase(_)));
+    assert_eq!(repo.rev_parse_single("HEAD~1")?, outcome.upstream);
+    Ok(())
+}
+
+#[test]
+fn branch_without_upstream() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "no-upstream")?;
+    let err = pull(&repo, Options::default()).unwrap_err();
+    assert!(matches!(err, gix::pull::Error::NoUpstream { branch } if branch == "topic"));
+    Ok(())
+}
+
+#[test]
+fn fetch_writes_fetch_head_like_git() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_pull_repos.sh", "fetch-head")?;
+    repo.find_default_remote(gix::remote::Direction::Fetch)
+        .expect("configured")?
+        .connect(gix::remote::Direction::Fetch)?
+        .prepare_fetch(gix::progress::Discard, Default::default())?
+        .receive(gix::progress::Discard, &AtomicBool::default())?;
+    let expected = std::fs::read(repo.git_dir().join("FETCH_HEAD.git"))?;
+    let actual = std::fs::read(repo.git_dir().join("FETCH_HEAD"))?;
+    assert_eq!(actual.as_bstr(), expected.as_bstr());
+
+    let entries = repo.fetch_head()?.expect("written");
+    assert_eq!(
+        entries.iter().map(|entry| entry.for_merge).collect::<Vec<_>>(),
+        [true, false],
+        "the upstream of the current branch is merged, everything else isn't"
+    );
+    Ok(())
+}
@@ -0,0 +1,272 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::rebase::{Autostash, Options, Outcome};
+
+fn subjects(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    let mut out = Vec::new();
+    for info in repo.rev_walk([repo.head_id()?]).all()? {
+        let commit = info?.object()?;
+        out.push(commit.message()?.summary().to_string());
+    }
+    Ok(out)
+}
+
+#[test]
+fn up_to_date() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "clean")?;
+    let head = repo.head_id()?;
+    assert_eq!(
+        repo.rebase(
+            "HEAD~1",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default()
+        )?,
+        Outcome::UpToDate
+    );
+    assert_eq!(repo.head_id()?, head, "nothing changed");
+    Ok(())
+}
+
+#[test]
+fn linear_history() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "clean")?;
+    let previous = repo.head_id()?.detach();
+    let author_before = repo.head_commit()?.author()?.to_owned();
+    let outcome = repo.rebase(
+        "main",
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    let new = repo.head_id()?.detach();
+    assert_eq!(
+        outcome,
+        Outcome::Complete {
+            previous,
+            new,
+            autostash: None
+        }
+    );
+    assert_eq!(
+        repo.head_name()?.expect("not detached").as_bstr(),
+        "refs/heads/feature",
+        "HEAD points to the rebased branch again"
+    );
+    assert_eq!(
+        crate::rev_parse_id(&repo, "feature~2")?,
+        crate::rev_parse_id(&repo, "main")?
+    );
+    assert_eq!(subjects(&repo)?, ["add new", "change line 5", "change line 1", "base"]);
+    assert_eq!(
+        repo.head_commit()?.author()?.to_owned(),
+        author_before,
+        "the author is kept"
+    );
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "main-1\n2\n3\n4\nfeature-5\n"
+    );
+    assert_eq!(repo.state(), None, "the rebase is done");
+    assert!(!repo.is_dirty()?);
+    Ok(())
+}
+
+#[test]
+fn onto() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "clean")?;
+    repo.rebase(
+        "HEAD~1",
+        Options {
+            onto: Some(crate::rev_parse_id(&repo, "main")?),
+            ..Default::default()
+        },
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(
+        subjects(&repo)?,
+        ["add new", "change line 1", "base"],
+        "only the commits after upstream are placed onto the new base"
+    );
+    assert_eq!(crate::read_worktree_file(&repo, "file")?, "main-1\n2\n3\n4\n5\n");
+    Ok(())
+}
+
+#[test]
+fn merged_upstream_commits_are_not_picked_again() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "merged-upstream")?;
+    repo.rebase(
+        "main",
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(
+        subjects(&repo)?,
+        ["add new", "change line 5", "change line 3", "change line 1", "base"],
+        "the merge is dropped and commits reachable from upstream through it aren't rebased"
+    );
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "main-1\n2\nmain-3\n4\nfeature-5\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn local_changes_need_autostash() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "clean")?;
+    std::fs::write(repo.work_dir().expect("non-bare").join("other"), "changed\n")?;
+    let err = repo
+        .rebase(
+            "main",
+            Options {
+                autostash: Some(false),
+                ..Default::default()
+            },
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, gix::rebase::Error::LocalChanges));
+
+    let outcome = repo.rebase(
+        "main",
+        Options {
+            autostash: Some(true),
+            ..Default::default()
+        },
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert!(matches!(
+        outcome,
+        Outcome::Complete {
+            autostash: Some(Autostash::Applied),
+            ..
+        }
+    ));
+    assert_eq!(
+        crate::read_worktree_file(&repo, "other")?,
+        "changed\n",
+        "local changes are restored"
+    );
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "main-1\n2\n3\n4\nfeature-5\n"
+    );
+    assert!(repo.is_dirty()?, "the changes are still present, but not staged");
+    assert_eq!(repo.state(), None);
+    Ok(())
+}
+
+#[test]
+fn conflicts_can_be_resolved_and_continued() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "conflict")?;
+    let conflicting = crate::rev_parse_id(&repo, "feature~1")?;
+    let outcome = repo.rebase(
+        "main",
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    let Outcome::Conflict { commit, conflicts } = outcome else {
+        panic!("expected conflict, got {outcome:?}")
+    };
+    assert_eq!(commit, conflicting);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(repo.state(), Some(gix::state::InProgress::Rebase));
+    assert!(repo.head()?.is_detached(), "HEAD is detached during the rebase");
+
+    let state_dir = repo.git_dir().join("rebase-merge");
+    assert_eq!(
+        std::fs::read_to_string(state_dir.join("head-name"))?,
+        "refs/heads/feature\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(state_dir.join("git-rebase-todo"))?,
+        format!("pick {} add new\n", crate::rev_parse_id(&repo, "feature")?)
+    );
+    assert_eq!(std::fs::read_to_string(state_dir.join("msgnum"))?, "2\n");
+    assert_eq!(std::fs::read_to_string(state_dir.join("end"))?, "3\n");
+    assert_eq!(
+        std::fs::read_to_string(repo.git_dir().join("REBASE_HEAD"))?,
+        format!("{conflicting}\n")
+    );
+    let content = crate::read_worktree_file(&repo, "file")?;
+    assert!(content.starts_with("<<<<<<< HEAD\nmain-1\n=======\nfeature-1\n>>>>>>> "));
+
+    assert!(matches!(
+        repo.rebase_continue(&mut gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err(),
+        gix::rebase::Error::UnresolvedConflicts
+    ));
+
+    let resolved = "resolved\n2\n3\n4\nfeature-5\n";
+    std::fs::write(repo.work_dir().expect("non-bare").join("file"), resolved)?;
+    let blob = repo.write_blob(resolved)?.detach();
+    let mut index = repo.open_index()?;
+    index.remove_entries(|_, path, _| path == "file");
+    index.dangerously_push_entry(
+        Default::default(),
+        blob,
+        gix::index::entry::Flags::empty(),
+        gix::index::entry::Mode::FILE,
+        "file".into(),
+    );
+    index.sort_entries();
+    index.write(Default::default())?;
+
+    let outcome = repo.rebase_continue(&mut gix::progress::Discard, &AtomicBool::default())?;
+    assert!(matches!(outcome, Outcome::Complete { autostash: None, .. }));
+    assert_eq!(
+        subjects(&repo)?,
+        [
+            "add new",
+            "conflicting change",
+            "change line 5",
+            "change line 1",
+            "base"
+        ]
+    );
+    assert_eq!(crate::read_worktree_file(&repo, "file")?, resolved);
+    assert_eq!(repo.state(), None);
+    assert!(!repo.git_dir().join("REBASE_HEAD").exists());
+    assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/feature");
+    Ok(())
+}
+
+#[test]
+fn abort() -> crate::Result {
+    let (repo, _tmp) = crate::named_subrepo_rw("make_rebase_repos.sh", "conflict")?;
+    let previous = repo.head_id()?.detach();
+    assert!(matches!(
+        repo.rebase(
+            "main",
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default()
+        )?,
+        Outcome::Conflict { .. }
+    ));
+    assert_eq!(
+        repo.rebase_abort(&mut gix::progress::Discard, &AtomicBool::default())?,
+        None,
+        "nothing was stashed"
+    );
+    assert_eq!(repo.head_id()?, previous);
+    assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/feature");
+    assert_eq!(
+        crate::read_worktree_file(&repo, "file")?,
+        "feature-1\n2\n3\n4\nfeature-5\n"
+    );
+    assert_eq!(repo.state(), None);
+    assert!(!repo.is_dirty()?);
+    assert!(matches!(
+        repo.rebase_abort(&mut gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err(),
+        gix::rebase::Error::NoRebaseInProgress
+    ));
+    Ok(())
+}
@@ -223,4 +223,233 @@ mod head {
         assert!(head.referent_name().is_none());
         Ok(())
     }
+
+    #[test]
+    fn detach_head() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let branch = repo.head_ref()?.expect("born");
+        let id = hex_to_id("3189cd3cb0af8586c39a838aa3e54fd72a872a41");
+        repo.detach_head(id, "checkout: moving from main to 3189cd3")?;
+
+        let head = repo.head()?;
+        assert!(head.is_detached());
+        assert_eq!(head.id().expect("present"), id);
+        assert_eq!(
+            repo.find_reference(branch.name())?.id(),
+            branch.id(),
+            "the branch itself is untouched"
+        );
+        let head = repo.find_reference("HEAD")?;
+        let mut log = head.log_iter();
+        let last = log.rev()?.expect("reflog exists").next().expect("one entry")?;
+        assert_eq!(last.new_oid, id);
+        assert_eq!(last.message, "checkout: moving from main to 3189cd3");
+        Ok(())
+    }
+
+    #[test]
+    fn set_orig_head() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        assert!(repo.try_find_reference("ORIG_HEAD")?.is_none());
+        let id = hex_to_id("3189cd3cb0af8586c39a838aa3e54fd72a872a41");
+        repo.set_orig_head(id)?;
+        assert_eq!(repo.find_reference("ORIG_HEAD")?.id(), id);
+        assert!(
+            !repo.git_dir().join("logs").join("ORIG_HEAD").exists(),
+            "like git, there is no reflog for ORIG_HEAD"
+        );
+        Ok(())
+    }
+}
+
+mod reflog {
+    use crate::util::hex_to_id;
+
+    fn repo() -> crate::Result<gix::Repository> {
+        let base = gix_testtools::scripted_fixture_read_only("make_rev_spec_parse_repos.sh")?;
+        Ok(gix::open(base.join("complex_graph"))?)
+    }
+
+    #[test]
+    fn query_by_index_date_and_message() -> crate::Result {
+        let repo = repo()?;
+        let log = repo.reflog("main")?.expect("present");
+        assert_eq!(log.entries.len(), 4);
+        assert!(
+            log.entries.iter().enumerate().all(|(idx, entry)| entry.index == idx),
+            "entries know their position"
+        );
+
+        let entry = log.entry(0).expect("present");
+        assert_eq!(
+            entry.line.new_oid,
+            hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e")
+        );
+        assert_eq!(entry.line.message, "commit (merge): A", "most recent first");
+        assert!(log.entry(4).is_none());
+
+        let (id, entry) = log
+            .entry_at(gix_date::parse("2005-04-07 22:20:00 +0000", None)?)
+            .expect("present");
+        assert_eq!(id, hex_to_id("a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc"));
+        assert_eq!(entry.index, 2);
+        assert_eq!(entry.line.message, "commit (merge): D");
+
+        let (id, entry) = log.entry_at(gix_date::parse("2000-01-01", None)?).expect("present");
+        assert_eq!(
+            id,
+            hex_to_id("9f9eac6bd1cd4b4cc6a494f044b28c985a22972b"),
+            "dates before the log resolve to the id the oldest entry created the reference with"
+        );
+        assert_eq!(entry.index, 3);
+
+        let merges: Vec<_> = log
+            .entries_matching("commit (merge): *".into())
+            .map(|entry| entry.index)
+            .collect();
+        assert_eq!(merges, [0, 1, 2]);
+
+        assert!(
+            repo.reflog("refs/tags/b-tag")?.is_none(),
+            "references without log are no error"
+        );
+        Ok(())
+    }
+}
+
+mod fetch_head {
+    use crate::util::hex_to_id;
+
+    #[test]
+    fn write_and_read() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        assert_eq!(repo.fetch_head()?, None, "there was no fetch yet");
+
+        let entries = vec![
+            gix::fetchhead::Entry {
+                id: hex_to_id("3189cd3cb0af8586c39a838aa3e54fd72a872a41"),
+                for_merge: true,
+                description: "branch 'main' of https://example.com/repo".into(),
+            },
+            gix::fetchhead::Entry {
+                id: hex_to_id("efa596d621559707b2d221f10490959b2decbc6c"),
+                for_merge: false,
+                description: "tag 'v1' of https://example.com/repo".into(),
+            },
+        ];
+        repo.write_fetch_head(&entries)?;
+        assert_eq!(repo.fetch_head()?, Some(entries));
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, feature = "command"))]
+mod reference_transaction_hook {
+    use std::{os::unix::fs::PermissionsExt, path::Path};
+
+    use gix::refs::transaction::PreviousValue;
+
+    fn repo_rw(hooks: bool) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let mut opts = crate::restricted();
+        opts.permissions.hooks = hooks;
+        crate::repo_rw_opts("make_basic_repo.sh", opts)
+    }
+
+    fn write_hook(dir: &Path, script: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join("reference-transaction");
+        std::fs::write(&path, format!("#!/bin/sh\n{script}"))?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+    }
+
+    const RECORD: &str = "echo \"$1\" >> \"$GIT_DIR/hook.log\"\ncat >> \"$GIT_DIR/hook.log\"\n";
+
+    #[test]
+    fn runs_when_permitted_with_all_changed_references() -> crate::Result {
+        for hooks in [false, true] {
+            let (repo, _tmp) = repo_rw(hooks)?;
+            write_hook(&repo.git_dir().join("hooks"), RECORD)?;
+            let head = repo.head_id()?.detach();
+            repo.reference("refs/heads/new", head, PreviousValue::MustNotExist, "create")?;
+
+            let log = repo.git_dir().join("hook.log");
+            if !hooks {
+                assert!(!log.exists(), "hooks don't run unless they are permitted");
+                continue;
+            }
+            let null = repo.object_hash().null();
+            let change = format!("{null} {head} refs/heads/new\n");
+            assert_eq!(
+                std::fs::read_to_string(&log)?,
+                format!("prepared\n{change}committed\n{change}")
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn can_reject_edits_when_prepared() -> crate::Result {
+        let (repo, _tmp) = repo_rw(true)?;
+        write_hook(
+            &repo.git_dir().join("hooks"),
+            &format!("{RECORD}test \"$1\" != prepared\n"),
+        )?;
+        let head = repo.head_id()?.detach();
+        let err = repo
+            .reference("refs/heads/new", head, PreviousValue::MustNotExist, "create")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            gix::reference::edit::Error::FileTransactionPrepare(
+                gix::refs::file::transaction::prepare::Error::Rejected(_)
+            )
+        ));
+        assert!(
+            repo.try_find_reference("refs/heads/new")?.is_none(),
+            "nothing was changed"
+        );
+
+        let null = repo.object_hash().null();
+        let change = format!("{null} {head} refs/heads/new\n");
+        assert_eq!(
+            std::fs::read_to_string(repo.git_dir().join("hook.log"))?,
+            format!("prepared\n{change}aborted\n{change}")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hooks_path_is_relative_to_the_worktree() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw(true)?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Core::HOOKS_PATH, "custom-hooks")?;
+        write_hook(&repo.work_dir().expect("non-bare").join("custom-hooks"), RECORD)?;
+        let head = repo.head_id()?.detach();
+        repo.reference("refs/heads/new", head, PreviousValue::MustNotExist, "create")?;
+        assert!(repo.git_dir().join("hook.log").is_file());
+        Ok(())
+    }
+
+    #[test]
+    fn receives_the_worktree_and_index_file() -> crate::Result {
+        let (repo, _tmp) = repo_rw(true)?;
+        write_hook(
+            &repo.git_dir().join("hooks"),
+            "cat >/dev/null\necho \"$GIT_WORK_TREE\" \"$GIT_INDEX_FILE\" >> \"$GIT_DIR/hook.log\"\n",
+        )?;
+        let head = repo.head_id()?.detach();
+        repo.reference("refs/heads/new", head, PreviousValue::MustNotExist, "create")?;
+
+        let line = format!(
+            "{} {}\n",
+            repo.work_dir().expect("non-bare").display(),
+            repo.index_path().display()
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.git_dir().join("hook.log"))?,
+            line.repeat(2),
+            "the hook sees the same repository as `git` would show it"
+        );
+        Ok(())
+    }
 }
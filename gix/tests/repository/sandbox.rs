@@ -0,0 +1,166 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{merge::Options, refs::FullName, sandbox::merge::Outcome};
+
+fn name(name: &str) -> FullName {
+    name.try_into().expect("valid")
+}
+
+#[test]
+fn clean_merge_is_kept_in_memory_until_persisted() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+    let main = crate::rev_parse_id(&repo, "main")?;
+    let mut sandbox = repo.sandbox();
+    let Outcome::Merge { commit } = sandbox.merge(
+        name("HEAD").as_ref(),
+        crate::rev_parse_id(&repo, "diverged")?,
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?
+    else {
+        panic!("expected merge commit")
+    };
+    assert_eq!(
+        sandbox.reference_id(name("refs/heads/main").as_ref())?,
+        Some(commit),
+        "symbolic references are followed"
+    );
+    assert_eq!(crate::rev_parse_id(&repo, "main")?, main, "the repository is unchanged");
+    assert!(!repo.has_object(commit), "objects are only written into memory");
+
+    let mut buf = Vec::new();
+    let merged = gix::objs::FindExt::find_commit(sandbox.objects(), &commit, &mut buf)?;
+    assert_eq!(
+        merged.tree(),
+        crate::rev_parse_id(&repo, "expected-merge^{tree}")?,
+        "the tree is the same as the one produced by git"
+    );
+    assert_eq!(
+        merged.message,
+        format!("Merge commit '{}'\n", crate::rev_parse_id(&repo, "diverged")?)
+    );
+
+    let edits = sandbox.persist()?;
+    assert_eq!(edits.len(), 1);
+    assert_eq!(crate::rev_parse_id(&repo, "main")?, commit, "the reference was updated");
+    assert_eq!(
+        repo.find_object(commit)?.into_commit().tree_id()?,
+        crate::rev_parse_id(&repo, "expected-merge^{tree}")?,
+        "all objects were written"
+    );
+    Ok(())
+}
+
+#[test]
+fn dropping_the_sandbox_discards_everything() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+    let main = crate::rev_parse_id(&repo, "main")?;
+    let blob = {
+        let mut sandbox = repo.sandbox();
+        let blob = sandbox.write_blob("only in the sandbox")?;
+        assert!(gix::objs::Exists::exists(sandbox.objects(), &blob));
+        sandbox.delete_reference(name("refs/heads/main").as_ref())?;
+        assert_eq!(sandbox.reference_id(name("refs/heads/main").as_ref())?, None);
+        blob
+    };
+    assert!(!repo.has_object(blob));
+    assert_eq!(crate::rev_parse_id(&repo, "main")?, main);
+    Ok(())
+}
+
+#[test]
+fn fast_forward_and_up_to_date() -> crate::Result {
+    let repo = crate::named_repo("make_merge_branches_repo.sh")?;
+    let mut sandbox = repo.sandbox();
+    let behind = name("refs/heads/behind");
+    assert!(matches!(
+        sandbox.merge(
+            name("refs/heads/main").as_ref(),
+            crate::rev_parse_id(&repo, "behind")?,
+            Options::default(),
+            &mut gix::progress::Discard,
+            &AtomicBool::default(),
+        )?,
+        Outcome::UpToDate
+    ));
+
+    let main = crate::rev_parse_id(&repo, "main")?;
+    let Outcome::FastForward { previous, new } = sandbox.merge(
+        behind.as_ref(),
+        main,
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?
+    else {
+        panic!("expected fast-forward")
+    };
+    assert_eq!(previous, Some(crate::rev_parse_id(&repo, "behind")?));
+    assert_eq!(new, main);
+    assert_eq!(sandbox.reference_id(behind.as_ref())?, Some(main));
+    assert_eq!(crate::rev_parse_id(&repo, "behind")?, previous.expect("set"));
+    Ok(())
+}
+
+#[test]
+fn conflicts_change_nothing() -> crate::Result {
+    let repo = crate::named_repo("make_merge_branches_repo.sh")?;
+    let mut sandbox = repo.sandbox();
+    let main = name("refs/heads/main");
+    let outcome = sandbox.merge(
+        main.as_ref(),
+        crate::rev_parse_id(&repo, "conflicting")?,
+        Options::default(),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    let Outcome::Conflict { conflicts } = outcome else {
+        panic!("expected conflict, got {outcome:?}")
+    };
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path, "file");
+    assert_eq!(
+        sandbox.reference_id(main.as_ref())?,
+        Some(crate::rev_parse_id(&repo, "main")?)
+    );
+
+    let merged_blob = conflicts[0].merged_blob.expect("content conflicts have a merged blob");
+    assert!(
+        gix::objs::Exists::exists(sandbox.objects(), &merged_blob),
+        "the blob with conflict markers is available in the sandbox"
+    );
+    assert!(!repo.has_object(merged_blob));
+    Ok(())
+}
+
+#[test]
+fn persisting_fails_if_references_changed_in_the_meantime() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_merge_branches_repo.sh")?;
+    let mut sandbox = repo.sandbox();
+    let tree = crate::rev_parse_id(&repo, "main^{tree}")?;
+    let main = crate::rev_parse_id(&repo, "main")?;
+    let commit = sandbox.commit(name("refs/heads/main").as_ref(), "in sandbox", tree, [main])?;
+    assert_eq!(sandbox.reference_id(name("refs/heads/main").as_ref())?, Some(commit));
+    assert!(
+        sandbox
+            .commit(name("refs/heads/main").as_ref(), "stale parent", tree, [main])
+            .is_err(),
+        "the first parent must match the current value of the reference in the sandbox"
+    );
+
+    let other = crate::rev_parse_id(&repo, "ahead")?;
+    repo.reference(
+        "refs/heads/main",
+        other,
+        gix::refs::transaction::PreviousValue::Any,
+        "moved elsewhere",
+    )?;
+    assert!(sandbox.persist().is_err());
+    assert_eq!(
+        crate::rev_parse_id(&repo, "main")?,
+        other,
+        "the reference wasn't touched"
+    );
+    Ok(())
+}
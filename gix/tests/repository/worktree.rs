@@ -13,6 +13,46 @@ fn stream() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "worktree-stream")]
+fn stream_opts() -> crate::Result {
+    use gix_worktree_stream::from_tree::{Action, Options};
+    let repo = crate::named_repo("make_packed_and_loose.sh")?;
+    let (mut stream, _index) = repo.worktree_stream_opts(
+        repo.head_commit()?.tree_id()?,
+        Options {
+            filter: Some(Box::new(|_path, _mode, _id| Action::Skip)),
+            thread_limit: None,
+        },
+    )?;
+    assert!(stream.next_entry()?.is_none(), "all entries were skipped by the filter");
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "worktree-stream")]
+fn stream_with_submodules() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_submodules.sh", "with-submodules", gix::open::Options::isolated())?;
+    let (mut stream, _index) = repo.worktree_stream_with_submodules(repo.head_commit()?.tree_id()?)?;
+    let mut paths = Vec::new();
+    while let Some(mut entry) = stream.next_entry()? {
+        std::io::copy(&mut entry, &mut std::io::sink())?;
+        paths.push((entry.relative_path().to_owned(), entry.mode.kind()));
+    }
+    use gix::object::tree::EntryKind::*;
+    assert_eq!(
+        paths,
+        [
+            (".gitmodules".into(), Blob),
+            ("dir/file".into(), Blob),
+            ("m1/this".into(), Blob),
+            ("m1/subdir/that".into(), Blob),
+        ],
+        "the submodule at `dir/m1` isn't committed yet, but the one at `m1` is streamed at its recorded commit"
+    );
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "worktree-archive")]
 fn archive() -> crate::Result {
@@ -31,6 +71,178 @@ fn archive() -> crate::Result {
     Ok(())
 }
 
+#[cfg(feature = "attributes")]
+mod stage_path {
+    use gix::{bstr::ByteSlice, config::tree::Core};
+    use gix_index::entry::Mode;
+
+    fn entry(repo: &gix::Repository, path: &str) -> crate::Result<Option<gix_index::Entry>> {
+        let index = repo.open_index()?;
+        Ok(index.entry_by_path(path.into()).cloned())
+    }
+
+    #[test]
+    fn modified_files_are_updated_with_clean_filters_applied() -> crate::Result {
+        let (mut repo, _tmp) = crate::basic_rw_repo()?;
+        repo.config_snapshot_mut().set_value(&Core::AUTO_CRLF, "input")?;
+        let worktree = repo.worktree().expect("non-bare");
+        assert!(
+            repo.open_index()?.tree().and_then(|tree| tree.num_entries).is_some(),
+            "the tree cache is valid initially"
+        );
+
+        let path = worktree.base().join("this");
+        std::fs::write(&path, "hello\r\nworld\r\n")?;
+        let id = worktree.stage_path("this")?.expect("file exists");
+        assert_eq!(
+            repo.find_object(id)?.data.as_bstr(),
+            "hello\nworld\n",
+            "clean filters are applied"
+        );
+
+        let entry = entry(&repo, "this")?.expect("tracked");
+        assert_eq!(entry.id, id);
+        assert_eq!(entry.mode, Mode::FILE);
+        assert_eq!(
+            entry.stat.size as u64,
+            std::fs::metadata(&path)?.len(),
+            "stat data is updated"
+        );
+        let index = repo.open_index()?;
+        assert!(
+            index.tree().expect("still present").num_entries.is_none(),
+            "the tree cache is invalidated"
+        );
+        index.verify_entries()?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn new_files_are_added_with_their_executable_bit() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        let dir = worktree.base().join("some");
+        std::fs::write(dir.join("file"), "content")?;
+        std::fs::set_permissions(dir.join("file"), std::fs::Permissions::from_mode(0o755))?;
+
+        let id = worktree.stage_path("some/file")?.expect("file exists");
+        let entry = entry(&repo, "some/file")?.expect("added");
+        assert_eq!(entry.id, id);
+        assert_eq!(entry.mode, Mode::FILE_EXECUTABLE);
+        let index = repo.open_index()?;
+        assert_eq!(index.entries().len(), 2);
+        index.verify_entries()?;
+        Ok(())
+    }
+
+    #[test]
+    fn files_can_be_staged_without_an_index() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        std::fs::remove_file(repo.index_path())?;
+        let worktree = repo.worktree().expect("non-bare");
+
+        let id = worktree.stage_path("this")?.expect("file exists");
+        let index = repo.open_index()?;
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .map(|e| (e.path(&index), e.id))
+                .collect::<Vec<_>>(),
+            [("this".into(), id)],
+            "the index is created with the staged file"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn files_in_sparse_directories_can_be_staged() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_sparse_index_repo.sh")?;
+        let worktree = repo.worktree().expect("non-bare");
+        std::fs::create_dir_all(worktree.base().join("d/c4"))?;
+        std::fs::write(worktree.base().join("d/c4/c5"), "content")?;
+
+        let id = worktree.stage_path("d/c4/c5")?.expect("file exists");
+        let index = repo.open_index()?;
+        let entry = index
+            .entry_by_path("d/c4/c5".into())
+            .expect("expanded to update the entry");
+        assert_eq!(entry.id, id);
+        assert_eq!(
+            index
+                .entries()
+                .iter()
+                .filter(|e| e.path(&index).starts_with(b"d/"))
+                .count(),
+            5,
+            "'d' and 'd/c4' were expanded, but nothing was added"
+        );
+        assert!(
+            index
+                .entry_by_path("c1/c3/".into())
+                .map_or(false, |e| e.mode.is_sparse()),
+            "unrelated sparse directories stay collapsed"
+        );
+        assert!(index.is_sparse());
+        index.verify_entries()?;
+        Ok(())
+    }
+
+    #[test]
+    fn deleted_files_are_removed_from_the_index() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        std::fs::remove_file(worktree.base().join("this"))?;
+
+        assert_eq!(worktree.stage_path("this")?, None);
+        assert_eq!(entry(&repo, "this")?, None);
+
+        let err = worktree.stage_path("this").unwrap_err();
+        assert!(
+            matches!(err, gix::worktree::stage_path::Error::NotFound { .. }),
+            "the path is neither tracked nor in the worktree anymore"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn a_file_in_place_of_a_leading_directory_is_replaced() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        std::fs::remove_file(worktree.base().join("this"))?;
+        std::fs::create_dir(worktree.base().join("this"))?;
+        std::fs::write(worktree.base().join("this").join("file"), "content")?;
+
+        worktree.stage_path("this/file")?;
+        let index = repo.open_index()?;
+        assert_eq!(
+            index.entries().iter().map(|e| e.path(&index)).collect::<Vec<_>>(),
+            ["this/file"],
+            "the file in place of the leading directory was removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_paths_and_directories_are_rejected() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        for path in ["../outside", ".git/config", "some/./dir", ""] {
+            let err = worktree.stage_path(path).unwrap_err();
+            assert!(
+                matches!(err, gix::worktree::stage_path::Error::InvalidPath { .. }),
+                "{path:?}: {err:?}"
+            );
+        }
+        let err = worktree.stage_path("some").unwrap_err();
+        assert!(matches!(err, gix::worktree::stage_path::Error::Directory { .. }));
+        Ok(())
+    }
+}
+
 mod with_core_worktree_config {
     use std::io::BufRead;
 
@@ -336,3 +548,73 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         );
     }
 }
+
+#[cfg(feature = "attributes")]
+mod index_flag {
+    use gix::worktree::index_flag::Flag;
+    use gix_index::entry::Flags;
+
+    fn flags(repo: &gix::Repository, path: &str) -> crate::Result<Flags> {
+        let index = repo.open_index()?;
+        Ok(index.entry_by_path(path.into()).expect("tracked").flags)
+    }
+
+    #[test]
+    fn set_list_and_clear() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        assert!(worktree.paths_with_index_flag(Flag::AssumeUnchanged)?.is_empty());
+
+        assert_eq!(
+            worktree.set_index_flag(Some("this"), Flag::AssumeUnchanged, true)?,
+            ["this"]
+        );
+        assert!(flags(&repo, "this")?.contains(Flags::ASSUME_VALID));
+        assert_eq!(worktree.paths_with_index_flag(Flag::AssumeUnchanged)?, ["this"]);
+        assert!(
+            worktree
+                .set_index_flag(Some("this"), Flag::AssumeUnchanged, true)?
+                .is_empty(),
+            "nothing changes if the flag is already set"
+        );
+
+        assert_eq!(
+            worktree.set_index_flag(Some("th*"), Flag::SkipWorktree, true)?,
+            ["this"]
+        );
+        assert!(
+            flags(&repo, "this")?.contains(Flags::SKIP_WORKTREE | Flags::EXTENDED),
+            "extended flags are marked as such so they are persisted"
+        );
+        assert_eq!(worktree.paths_with_index_flag(Flag::SkipWorktree)?, ["this"]);
+
+        assert_eq!(
+            worktree.set_index_flag(None::<&str>, Flag::SkipWorktree, false)?,
+            ["this"]
+        );
+        assert_eq!(
+            worktree.set_index_flag(None::<&str>, Flag::AssumeUnchanged, false)?,
+            ["this"]
+        );
+        assert_eq!(flags(&repo, "this")?, Flags::empty(), "all flags were cleared");
+        repo.open_index()?.verify_entries()?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "status")]
+    fn flagged_files_are_hidden_from_status() -> crate::Result {
+        let (repo, _tmp) = crate::basic_rw_repo()?;
+        let worktree = repo.worktree().expect("non-bare");
+        std::fs::write(worktree.base().join("this"), "changed")?;
+        assert!(repo.is_dirty()?);
+
+        for flag in [Flag::AssumeUnchanged, Flag::SkipWorktree] {
+            worktree.set_index_flag(Some("this"), flag, true)?;
+            assert!(!repo.is_dirty()?, "{flag:?} hides the modification");
+            worktree.set_index_flag(Some("this"), flag, false)?;
+            assert!(repo.is_dirty()?, "the modification is visible again");
+        }
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "revision")]
+mod name_rev;
 mod spec;
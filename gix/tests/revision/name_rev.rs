@@ -0,0 +1,57 @@
+use gix::bstr::ByteSlice;
+
+use crate::named_repo;
+
+#[test]
+fn names_are_relative_to_selected_refs() -> crate::Result {
+    let repo = named_repo("make_merge_branches_repo.sh")?;
+    let commit = crate::rev_parse_id(&repo, "main~1")?;
+
+    let names = repo.name_rev().include_refs("main").names(Some(commit))?;
+    assert_eq!(names.get(&commit).expect("named").to_string(), "main~1");
+
+    let names = repo
+        .name_rev()
+        .include_refs("refs/heads/*")
+        .exclude_refs("main")
+        .exclude_refs("c*")
+        .names(Some(commit))?;
+    assert_eq!(
+        names.get(&commit).expect("named").to_string(),
+        "ahead~2",
+        "excluded refs aren't used, and among equally distant tips the first one wins"
+    );
+
+    let names = repo.name_rev().tags_only(true).names(Some(commit))?;
+    assert!(names.get(&commit).is_none(), "there are no tags");
+    Ok(())
+}
+
+#[test]
+fn annotate() -> crate::Result {
+    let repo = named_repo("make_merge_branches_repo.sh")?;
+    let names = repo.name_rev().include_refs("expected-merge").all_names()?;
+    let diverged = crate::rev_parse_id(&repo, "diverged")?;
+    let unknown = crate::rev_parse_id(&repo, "conflicting")?;
+    let text = format!(
+        "merged {diverged}, but not {unknown} or {}.\n",
+        &diverged.to_string()[..7]
+    );
+
+    assert_eq!(
+        names.annotate(text.as_bytes().as_bstr(), false),
+        format!(
+            "merged {diverged} (expected-merge^2), but not {unknown} or {}.\n",
+            &diverged.to_string()[..7]
+        ),
+        "only full ids of named commits are annotated"
+    );
+    assert_eq!(
+        names.annotate(text.as_bytes().as_bstr(), true),
+        format!(
+            "merged expected-merge^2, but not {unknown} or {}.\n",
+            &diverged.to_string()[..7]
+        ),
+    );
+    Ok(())
+}
@@ -55,6 +55,12 @@ fn by_index() {
             "it sets the reference name even if it is implied"
         );
         assert_eq!(spec.second_reference(), None);
+        let entry = spec.first_reflog_entry().expect("set for reflog lookups");
+        assert_eq!(entry.index, 0);
+        assert_eq!(
+            entry.line.new_oid,
+            hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e")
+        );
     }
 
     {
@@ -78,10 +84,58 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
+fn by_date() {
     let repo = repo("complex_graph").unwrap();
+    let spec = parse_spec_no_baseline("main@{2005-04-07 22:20:00 +0000}", &repo).unwrap();
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc").attach(&repo))
+    );
+    let entry = spec.first_reflog_entry().expect("set for reflog lookups");
+    assert_eq!(entry.index, 2);
+    assert_eq!(entry.line.message, "commit (merge): D");
+    assert_eq!(spec.second_reflog_entry(), None);
+
+    let spec = parse_spec_no_baseline("main@{2000-01-01}", &repo).unwrap();
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("9f9eac6bd1cd4b4cc6a494f044b28c985a22972b").attach(&repo)),
+        "dates before the log resolve to its oldest entry"
+    );
+
     assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
+        parse_spec_no_baseline("b-tag@{2000-01-01}", &repo).unwrap_err(),
+        Error::MissingRefLog { .. }
     ));
 }
+
+#[test]
+fn recent_entries_ignore_malformed_old_ones() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_rev_spec_parse_repos.sh")?;
+    let log_path = tmp.path().join("complex_graph/.git/logs/refs/heads/main");
+    let log = std::fs::read(&log_path)?;
+    std::fs::write(&log_path, [b"malformed\n".as_slice(), &log].concat())?;
+    let repo = gix::open(tmp.path().join("complex_graph"))?;
+
+    let spec = parse_spec_no_baseline("main@{0}", &repo)?;
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("55e825ebe8fd2ff78cad3826afb696b96b576a7e").attach(&repo)),
+        "only the entries up to the desired one are read"
+    );
+    let spec = parse_spec_no_baseline("main@{2005-04-07 22:20:00 +0000}", &repo)?;
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc").attach(&repo)),
+        "date lookups stop at the first entry that isn't newer"
+    );
+
+    assert!(
+        matches!(
+            parse_spec_no_baseline("main@{2000-01-01}", &repo).unwrap_err(),
+            Error::RefLog(_)
+        ),
+        "reaching the malformed entry fails the lookup"
+    );
+    Ok(())
+}
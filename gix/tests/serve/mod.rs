@@ -0,0 +1,404 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::{
+    bstr::ByteSlice,
+    serve::http::{self, Endpoint, Outcome, Request, Service},
+};
+
+use crate::util::{basic_repo, basic_rw_repo};
+
+fn request<'a>(method: &'a str, endpoint: Endpoint, body: &'a [u8]) -> Request<'a, &'a [u8]> {
+    let content_type = match endpoint {
+        Endpoint::InfoRefs => None,
+        Endpoint::Service(Service::UploadPack) => Some("application/x-git-upload-pack-request"),
+        Endpoint::Service(Service::ReceivePack) => Some("application/x-git-receive-pack-request"),
+    };
+    Request {
+        method,
+        endpoint,
+        query: None,
+        content_type,
+        content_encoding: None,
+        body,
+    }
+}
+
+fn header<'a, R>(response: &'a http::Response<'_, R>, name: &str) -> Option<&'a str> {
+    response
+        .headers
+        .iter()
+        .find_map(|(key, value)| (*key == name).then_some(value.as_str()))
+}
+
+fn body(response: http::Response<'_, &[u8]>) -> crate::Result<(Outcome, Vec<u8>)> {
+    let mut out = Vec::new();
+    let outcome = response.write_body(&mut out, &AtomicBool::default())?;
+    Ok((outcome, out))
+}
+
+fn pkt_lines(lines: &[&str]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for line in lines {
+        if line.is_empty() {
+            out.extend_from_slice(b"0000");
+        } else {
+            out.extend_from_slice(format!("{:04x}{line}", line.len() + 4).as_bytes());
+        }
+    }
+    out
+}
+
+#[test]
+fn split_path_finds_repository_and_endpoint() {
+    assert_eq!(
+        http::split_path("/user/repo.git/info/refs"),
+        Some(("/user/repo.git", Endpoint::InfoRefs))
+    );
+    assert_eq!(
+        http::split_path("/repo/git-receive-pack"),
+        Some(("/repo", Endpoint::Service(Service::ReceivePack)))
+    );
+    assert_eq!(http::split_path("/repo/HEAD"), None);
+}
+
+#[test]
+fn info_refs_advertises_references_of_service() -> crate::Result {
+    let repo = basic_repo()?;
+    let mut req = request("GET", Endpoint::InfoRefs, b"");
+    req.query = Some("service=git-upload-pack");
+    let response = http::handle(&repo, req, &Default::default());
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        header(&response, "Content-Type"),
+        Some("application/x-git-upload-pack-advertisement")
+    );
+    assert_eq!(
+        header(&response, "Cache-Control"),
+        Some("no-cache, max-age=0, must-revalidate")
+    );
+
+    let (outcome, out) = body(response)?;
+    assert_eq!(outcome, Outcome::Advertisement(Service::UploadPack));
+    let head = repo.head_id()?;
+    assert!(out.starts_with(b"001e# service=git-upload-pack\n0000"));
+    assert!(out.contains_str(format!("{head} HEAD\0")));
+    assert!(out.contains_str("symref=HEAD:refs/heads/main"));
+    assert!(out.contains_str(format!("{head} refs/heads/main\n")));
+    assert!(out.ends_with(b"0000"));
+    Ok(())
+}
+
+#[test]
+fn invalid_requests_are_rejected() -> crate::Result {
+    let repo = basic_repo()?;
+    let options = http::Options::default();
+
+    let response = http::handle(&repo, request("GET", Endpoint::InfoRefs, b""), &options);
+    assert_eq!(response.status, 403, "the dumb protocol isn't supported");
+
+    let mut req = request("GET", Endpoint::InfoRefs, b"");
+    req.query = Some("service=git-receive-pack");
+    let response = http::handle(&repo, req, &options);
+    assert_eq!(response.status, 403, "pushing is disabled by default");
+    let (outcome, out) = body(response)?;
+    assert_eq!(outcome, Outcome::Message);
+    assert_eq!(out.as_bstr(), "Service not enabled: 'git-receive-pack'\n");
+
+    let response = http::handle(
+        &repo,
+        request("GET", Endpoint::Service(Service::UploadPack), b""),
+        &options,
+    );
+    assert_eq!(response.status, 405);
+    assert_eq!(header(&response, "Allow"), Some("POST"));
+
+    let mut req = request("POST", Endpoint::Service(Service::UploadPack), b"");
+    req.content_type = Some("text/plain");
+    assert_eq!(http::handle(&repo, req, &options).status, 415);
+
+    let mut req = request("POST", Endpoint::Service(Service::UploadPack), b"");
+    req.content_encoding = Some("br");
+    assert_eq!(http::handle(&repo, req, &options).status, 415);
+    Ok(())
+}
+
+#[test]
+fn upload_pack_sends_pack_once_client_is_done() -> crate::Result {
+    let repo = basic_repo()?;
+    let head = repo.head_id()?;
+    let want = format!("want {head} multi_ack_detailed side-band-64k\n");
+    let have = format!("have {head}\n");
+
+    let negotiation = pkt_lines(&[&want, "", &have, ""]);
+    let response = http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::UploadPack), &negotiation),
+        &Default::default(),
+    );
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        header(&response, "Content-Type"),
+        Some("application/x-git-upload-pack-result")
+    );
+    let (outcome, out) = body(response)?;
+    assert_eq!(
+        outcome,
+        Outcome::UploadPack(gix::serve::upload_pack::Outcome {
+            wants: vec![head.detach()],
+            num_objects: None,
+        }),
+        "stateless negotiation ends after each round"
+    );
+    assert_eq!(out, pkt_lines(&[&format!("ACK {head} common\n"), "NAK\n"]));
+
+    let clone = pkt_lines(&[&want, "", "done\n"]);
+    let response = http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::UploadPack), &clone),
+        &Default::default(),
+    );
+    let (outcome, out) = body(response)?;
+    assert!(
+        matches!(outcome, Outcome::UploadPack(outcome) if outcome.num_objects == Some(6)),
+        "two commits with a tree and blob each"
+    );
+    assert!(out.starts_with(&pkt_lines(&["NAK\n"])));
+    assert_eq!(&out[12..17], b"\x01PACK", "the pack is sent on the data channel");
+    assert!(out.ends_with(b"0000"));
+    Ok(())
+}
+
+#[test]
+fn upload_pack_accepts_gzip_request_bodies() -> crate::Result {
+    use std::io::Write;
+
+    let repo = basic_repo()?;
+    let head = repo.head_id()?;
+    let mut compressed = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    compressed.write_all(&pkt_lines(&[&format!("want {head}\n"), "", "done\n"]))?;
+    let compressed = compressed.finish()?;
+
+    let mut req = request("POST", Endpoint::Service(Service::UploadPack), &compressed);
+    req.content_encoding = Some("gzip");
+    let (outcome, out) = body(http::handle(&repo, req, &Default::default()))?;
+    assert!(matches!(outcome, Outcome::UploadPack(outcome) if outcome.num_objects == Some(6)));
+    assert!(
+        out.starts_with(b"0008NAK\nPACK"),
+        "without side-band, the pack follows right away"
+    );
+    Ok(())
+}
+
+#[test]
+fn receive_pack_updates_references_and_reports_their_status() -> crate::Result {
+    let (repo, _tmp) = basic_rw_repo()?;
+    let head = repo.head_id()?.detach();
+    let null = repo.object_hash().null();
+    let options = http::Options {
+        receive_pack: true,
+        ..Default::default()
+    };
+
+    let mut req = request("GET", Endpoint::InfoRefs, b"");
+    req.query = Some("service=git-receive-pack");
+    let (_, out) = body(http::handle(&repo, req, &options))?;
+    assert!(out.starts_with(b"001f# service=git-receive-pack\n0000"));
+    assert!(out.contains_str(format!("{head} refs/heads/main\0report-status delete-refs")));
+    assert!(!out.contains_str(" HEAD"), "HEAD isn't advertised for pushes");
+
+    let commands = pkt_lines(&[
+        &format!("{null} {head} refs/heads/new\0report-status\n"),
+        &format!("{head} {null} refs/heads/main\n"),
+        &format!("{head} {head} refs/heads/missing\n"),
+        &format!("{null} {head} HEAD\n"),
+        "",
+    ]);
+    let (outcome, out) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::ReceivePack), &commands),
+        &options,
+    ))?;
+    let Outcome::ReceivePack(outcome) = outcome else {
+        panic!("a push was served")
+    };
+    assert_eq!(outcome.num_objects, None, "the objects are present already");
+    assert_eq!(outcome.updated.len(), 1);
+    let rejected: Vec<_> = outcome
+        .rejected
+        .iter()
+        .map(|(command, reason)| (command.name.to_string(), reason.as_str()))
+        .collect();
+    assert_eq!(
+        rejected[0],
+        ("refs/heads/main".into(), "branch is currently checked out")
+    );
+    assert_eq!(rejected[1].0, "refs/heads/missing", "the reference doesn't exist");
+    assert_eq!(rejected[2], ("HEAD".into(), "funny refname"));
+    assert!(out.starts_with(&pkt_lines(&[
+        "unpack ok\n",
+        "ok refs/heads/new\n",
+        "ng refs/heads/main branch is currently checked out\n"
+    ])));
+    assert!(out.ends_with(&pkt_lines(&["ng HEAD funny refname\n", ""])));
+
+    assert_eq!(repo.find_reference("refs/heads/new")?.id(), head);
+    assert!(repo.try_find_reference("refs/heads/missing")?.is_none());
+
+    let commands = pkt_lines(&[&format!("{head} {null} refs/heads/new\0report-status\n"), ""]);
+    let (_, out) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::ReceivePack), &commands),
+        &options,
+    ))?;
+    assert_eq!(out, pkt_lines(&["unpack ok\n", "ok refs/heads/new\n", ""]));
+    assert!(repo.try_find_reference("refs/heads/new")?.is_none(), "deleted");
+    Ok(())
+}
+
+/// Return the id of an object of `kind` with `data`, along with a pack that contains only that object.
+fn single_object_pack(
+    repo: &gix::Repository,
+    kind: gix::objs::Kind,
+    data: &[u8],
+) -> crate::Result<(gix::ObjectId, Vec<u8>)> {
+    use gix::odb::pack::data::{self, output};
+
+    let id = gix::objs::compute_hash(repo.object_hash(), kind, data);
+    let entry = output::Entry::from_data(&output::Count::from_data(id, None), &gix::objs::Data::new(kind, data))?;
+    let mut pack = Vec::new();
+    for written in output::bytes::FromEntriesIter::new(
+        std::iter::once(Ok::<_, output::entry::Error>(vec![entry])),
+        &mut pack,
+        1,
+        data::Version::V2,
+        repo.object_hash(),
+    ) {
+        written?;
+    }
+    Ok((id, pack))
+}
+
+#[test]
+fn receive_pack_rejects_updates_whose_objects_are_missing() -> crate::Result {
+    let (repo, _tmp) = basic_rw_repo()?;
+    let head = repo.head_id()?.detach();
+    let null = repo.object_hash().null();
+    let missing = gix::ObjectId::from_hex(b"1111111111111111111111111111111111111111")?;
+    let commit = format!(
+        "tree {missing}\nparent {head}\nauthor a <a@example.com> 0 +0000\ncommitter a <a@example.com> 0 +0000\n\nno tree\n"
+    );
+    let (commit_id, pack) = single_object_pack(&repo, gix::objs::Kind::Commit, commit.as_bytes())?;
+
+    let mut push = pkt_lines(&[&format!("{null} {commit_id} refs/heads/new\0report-status\n"), ""]);
+    push.extend_from_slice(&pack);
+    let options = http::Options {
+        receive_pack: true,
+        ..Default::default()
+    };
+    let (outcome, out) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::ReceivePack), &push),
+        &options,
+    ))?;
+    let Outcome::ReceivePack(outcome) = outcome else {
+        panic!("a push was served")
+    };
+    assert_eq!(outcome.num_objects, Some(1), "only the commit was sent");
+    assert!(repo.has_object(commit_id), "the commit itself was received");
+    assert_eq!(
+        out,
+        pkt_lines(&["unpack ok\n", "ng refs/heads/new missing necessary objects\n", ""])
+    );
+    assert!(
+        repo.try_find_reference("refs/heads/new")?.is_none(),
+        "references aren't updated to point to objects whose tree is missing"
+    );
+
+    let tree = gix::objs::Tree {
+        entries: vec![gix::objs::tree::Entry {
+            mode: gix::objs::tree::EntryKind::Blob.into(),
+            filename: "missing".into(),
+            oid: missing,
+        }],
+    };
+    let mut data = Vec::new();
+    gix::objs::WriteTo::write_to(&tree, &mut data)?;
+    let (tree_id, pack) = single_object_pack(&repo, gix::objs::Kind::Tree, &data)?;
+    let mut push = pkt_lines(&[&format!("{null} {tree_id} refs/tags/tree\0report-status\n"), ""]);
+    push.extend_from_slice(&pack);
+    let (_, out) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::ReceivePack), &push),
+        &options,
+    ))?;
+    assert_eq!(
+        out,
+        pkt_lines(&["unpack ok\n", "ng refs/tags/tree missing necessary objects\n", ""]),
+        "trees are checked for the objects they reference as well"
+    );
+    Ok(())
+}
+
+#[test]
+fn upload_pack_only_acknowledges_common_commits() -> crate::Result {
+    let repo = basic_repo()?;
+    let head = repo.head_id()?;
+    let tree = head.object()?.into_commit().tree_id()?;
+    let fetch = pkt_lines(&[
+        &format!("want {head} multi_ack_detailed\n"),
+        "",
+        &format!("have {tree}\n"),
+        "done\n",
+    ]);
+    let (outcome, out) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::UploadPack), &fetch),
+        &Default::default(),
+    ))?;
+    assert!(
+        matches!(outcome, Outcome::UploadPack(outcome) if outcome.num_objects == Some(6)),
+        "the tree can't be used to limit the pack, which contains everything"
+    );
+    assert!(
+        out.starts_with(b"0008NAK\nPACK"),
+        "trees the client has aren't acknowledged as common"
+    );
+    Ok(())
+}
+
+#[test]
+fn request_bodies_can_be_limited() -> crate::Result {
+    let repo = basic_repo()?;
+    let head = repo.head_id()?;
+    let fetch = pkt_lines(&[&format!("want {head}\n"), "", "done\n"]);
+    let options = http::Options {
+        max_request_body_size: Some(fetch.len() as u64 - 1),
+        ..Default::default()
+    };
+    let err = http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::UploadPack), &fetch),
+        &options,
+    )
+    .write_body(Vec::new(), &AtomicBool::default())
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("larger than"),
+        "reading more than the limit fails: {err}"
+    );
+
+    let options = http::Options {
+        max_request_body_size: Some(fetch.len() as u64),
+        ..Default::default()
+    };
+    let (outcome, _) = body(http::handle(
+        &repo,
+        request("POST", Endpoint::Service(Service::UploadPack), &fetch),
+        &options,
+    ))?;
+    assert!(
+        matches!(outcome, Outcome::UploadPack(_)),
+        "bodies within the limit are read"
+    );
+    Ok(())
+}
@@ -46,6 +46,78 @@ mod index_worktree {
             Ok(())
         }
 
+        #[test]
+        fn conflicts_are_reported_with_all_stages() -> crate::Result {
+            use gix::status::index_worktree::iter::ConflictEntry;
+            use gix::status::plumbing::index_as_worktree::Conflict;
+
+            let repo = repo("conflicts")?;
+            let index = repo.index()?;
+            let stage = |path: &str, stage: gix::index::entry::Stage| {
+                index
+                    .entry_index_by_path_and_stage(path.into(), stage)
+                    .map(|entry_index| {
+                        let entry = &index.entries()[entry_index];
+                        ConflictEntry {
+                            id: entry.id,
+                            mode: entry.mode,
+                            entry_index,
+                        }
+                    })
+            };
+            let conflict = |path: &str, kind| {
+                use gix::index::entry::Stage;
+                Item::Conflict {
+                    rela_path: path.into(),
+                    kind,
+                    base: stage(path, Stage::Base),
+                    ours: stage(path, Stage::Ours),
+                    theirs: stage(path, Stage::Theirs),
+                }
+            };
+
+            let status = repo
+                .status(gix::progress::Discard)?
+                .index_worktree_options_mut(|opts| {
+                    opts.sorting =
+                        Some(gix::status::plumbing::index_as_worktree_with_renames::Sorting::ByPathCaseSensitive)
+                })
+                .into_index_worktree_iter(Vec::new())?;
+            let items: Vec<_> = status.filter_map(Result::ok).collect();
+            assert_eq!(
+                items,
+                [
+                    conflict("both-added", Conflict::BothAdded),
+                    conflict("both-modified", Conflict::BothModified),
+                    conflict("deleted-by-them", Conflict::DeletedByThem),
+                ],
+                "each conflict is reported once, with all of its stages"
+            );
+            assert!(matches!(
+                &items[0],
+                Item::Conflict {
+                    base: None,
+                    ours: Some(_),
+                    theirs: Some(_),
+                    ..
+                }
+            ));
+            assert!(matches!(
+                &items[2],
+                Item::Conflict {
+                    base: Some(_),
+                    ours: Some(_),
+                    theirs: None,
+                    ..
+                }
+            ));
+            assert_eq!(
+                items.iter().map(Item::summary).collect::<Vec<_>>(),
+                [Some(gix::status::index_worktree::iter::Summary::Conflict); 3]
+            );
+            Ok(())
+        }
+
         #[test]
         fn untracked_files_collapse_by_default() -> crate::Result {
             let repo = repo("untracked-only")?;
@@ -112,6 +184,126 @@ mod index_worktree {
             Ok(())
         }
 
+        #[test]
+        fn ignored_files_collapsed_and_matching() -> crate::Result {
+            let repo = repo("ignored-directories")?;
+            let ignored_paths = |mode: gix::status::IgnoredFiles| -> crate::Result<Vec<String>> {
+                let status = repo
+                    .status(gix::progress::Discard)?
+                    .ignored_files(mode)
+                    .index_worktree_options_mut(|opts| {
+                        opts.sorting =
+                            Some(gix::status::plumbing::index_as_worktree_with_renames::Sorting::ByPathCaseSensitive)
+                    })
+                    .into_index_worktree_iter(Vec::new())?;
+                Ok(status
+                    .filter_map(Result::ok)
+                    .filter_map(|item| match item {
+                        Item::DirectoryContents { entry, .. }
+                            if matches!(entry.status, gix_dir::entry::Status::Ignored(_)) =>
+                        {
+                            Some(entry.rela_path.to_string())
+                        }
+                        _ => None,
+                    })
+                    .collect())
+            };
+
+            assert_eq!(
+                ignored_paths(gix::status::IgnoredFiles::None)?,
+                Vec::<String>::new(),
+                "ignored files aren't shown by default"
+            );
+            assert_eq!(
+                ignored_paths(gix::status::IgnoredFiles::Collapsed)?,
+                ["logs", "node_modules", "src/debug.log"],
+                "directories with only ignored files are reported as one entry"
+            );
+            assert_eq!(
+                ignored_paths(gix::status::IgnoredFiles::Matching)?,
+                ["logs/1.log", "logs/2.log", "node_modules", "src/debug.log"],
+                "ignored directories are never entered, but otherwise each match is shown"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn sparse_directories_are_expanded() -> crate::Result {
+            let (repo, _tmp) = crate::repo_rw("make_sparse_index_repo.sh")?;
+            assert!(repo.index()?.is_sparse(), "'c1/c3' and 'd' are collapsed");
+            let workdir = repo.work_dir().expect("non-bare");
+            std::fs::create_dir_all(workdir.join("d"))?;
+            std::fs::write(workdir.join("d/a"), "")?;
+
+            let mut status = repo
+                .status(gix::progress::Discard)?
+                .index_worktree_options_mut(|opts| {
+                    opts.sorting =
+                        Some(gix::status::plumbing::index_as_worktree_with_renames::Sorting::ByPathCaseSensitive)
+                })
+                .into_index_worktree_iter(Vec::new())?;
+            let items: Vec<_> = status.by_ref().filter_map(Result::ok).collect();
+            assert_eq!(
+                items,
+                [],
+                "files excluded from the worktree aren't reported, even if present"
+            );
+
+            let index = &status.outcome_mut().expect("iteration done").index;
+            assert!(
+                !index.is_sparse(),
+                "all sparse directories were expanded for the comparison"
+            );
+            assert!(
+                index
+                    .entry_by_path("d/c4/c5".into())
+                    .map_or(false, |e| e.flags.contains(gix::index::entry::Flags::SKIP_WORKTREE)),
+                "expanded entries remain excluded from the worktree"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn case_only_renames_are_detected_if_case_is_ignored() -> crate::Result {
+            let repo = repo("case-only-renames")?;
+            let status = repo
+                .status(gix::progress::Discard)?
+                .index_worktree_rewrites(gix::diff::Rewrites::default())
+                .index_worktree_options_mut(|opts| {
+                    opts.sorting =
+                        Some(gix::status::plumbing::index_as_worktree_with_renames::Sorting::ByPathCaseSensitive)
+                })
+                .into_index_worktree_iter(Vec::new())?;
+            let items: Vec<_> = status
+                .filter_map(Result::ok)
+                .map(|item| match item {
+                    Item::Rewrite {
+                        source,
+                        dirwalk_entry,
+                        dirwalk_entry_id,
+                        copy: false,
+                        ..
+                    } => (
+                        source.rela_path().to_string(),
+                        dirwalk_entry.rela_path.to_string(),
+                        dirwalk_entry_id,
+                    ),
+                    other => unreachable!("only renames are expected, got {other:?}"),
+                })
+                .collect();
+            let hash = |data: &[u8]| gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, data);
+            let changed: String = (1..=11).map(|n| format!("{n}\n")).collect();
+            assert_eq!(
+                items,
+                [
+                    ("Unchanged".into(), "unchanged".into(), hash(b"unchanged\n")),
+                    ("dir/Changed".into(), "dir/changed".into(), hash(changed.as_bytes())),
+                ],
+                "each rename is reported once, whether the filesystem ignores case or not"
+            );
+            Ok(())
+        }
+
         #[test]
         fn early_drop_for_is_dirty_emulation() -> crate::Result {
             let repo = submodule_repo("modified-untracked-and-submodule-head-changed-and-modified")?;
@@ -342,3 +342,197 @@ mod open {
         Ok(())
     }
 }
+
+fn writable_repo(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_submodules.sh")?;
+    let repo = gix::open_opts(tmp.path().join(name), crate::util::restricted())?;
+    Ok((repo, tmp))
+}
+
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+mod add {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::bstr::ByteSlice;
+
+    use crate::submodule::writable_repo;
+
+    #[test]
+    fn clone_register_and_stage() -> crate::Result {
+        let (mut repo, tmp) = writable_repo("with-submodules")?;
+        let sm_repo = repo.submodule_add("../module1", "m2/", gix::progress::Discard, &AtomicBool::default())?;
+
+        let module1 = gix::open_opts(tmp.path().join("module1"), crate::util::restricted())?;
+        assert_eq!(
+            sm_repo.head_id()?,
+            module1.head_id()?,
+            "the default branch is checked out"
+        );
+        assert_eq!(
+            sm_repo.kind(),
+            gix::repository::Kind::Submodule,
+            "its repository lives in the superproject"
+        );
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("worktree").join("m2/.git"))?.as_bstr(),
+            "gitdir: ../.git/modules/m2\n"
+        );
+        assert!(repo.work_dir().expect("worktree").join("m2/this").is_file());
+
+        let sm = repo
+            .submodules()?
+            .expect("modules present")
+            .find(|sm| sm.name() == "m2")
+            .expect("registered");
+        assert_eq!(sm.path()?.as_ref(), "m2");
+        assert_eq!(
+            sm.url()?.to_bstring(),
+            gix_path::into_bstr(gix_path::realpath(tmp.path().join("module1"))?).into_owned(),
+            "the url is resolved in the repository configuration, overriding the one in .gitmodules"
+        );
+        assert!(sm.is_active()?);
+        assert_eq!(
+            sm.state()?,
+            gix::submodule::State {
+                repository_exists: true,
+                is_old_form: false,
+                worktree_checkout: true,
+                superproject_configuration: true,
+            }
+        );
+        assert_eq!(
+            sm.index_id()?,
+            Some(module1.head_id()?.detach()),
+            "the submodule is staged"
+        );
+        let modules = std::fs::read(repo.work_dir().expect("worktree").join(".gitmodules"))?;
+        assert!(modules.contains_str("[submodule \"m2\"]\n\tpath = m2\n\turl = ../module1\n"));
+        assert_eq!(
+            repo.index()?.entry_by_path(".gitmodules".into()).expect("tracked").id,
+            gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &modules),
+            ".gitmodules is staged as well"
+        );
+
+        let err = repo
+            .submodule_add("../module1", "m2", gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(matches!(err, gix::submodule::add::Error::AlreadyExists { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_paths_are_rejected() -> crate::Result {
+        let (mut repo, _tmp) = writable_repo("with-submodules")?;
+        for path in ["../outside", "a/./b", ".git/m"] {
+            let err = repo
+                .submodule_add("../module1", path, gix::progress::Discard, &AtomicBool::default())
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    gix::submodule::add::Error::InvalidPath { .. } | gix::submodule::add::Error::InvalidName(_)
+                ),
+                "{path}: {err:?}"
+            );
+        }
+        let err = repo
+            .submodule_add("../module1", "dir", gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(matches!(err, gix::submodule::add::Error::PathExists { .. }));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "status")]
+mod remove {
+    use crate::submodule::writable_repo;
+
+    #[test]
+    fn deinit_keeps_registration_and_repository() -> crate::Result {
+        let (mut repo, _tmp) = writable_repo("with-submodules")?;
+        repo.submodule_deinit("m1", false)?;
+
+        let work_dir = repo.work_dir().expect("worktree").join("m1");
+        assert_eq!(std::fs::read_dir(&work_dir)?.count(), 0, "an empty directory remains");
+        let sm = repo
+            .submodules()?
+            .expect("modules present")
+            .find(|sm| sm.name() == "m1")
+            .expect("still registered");
+        assert_eq!(
+            sm.state()?,
+            gix::submodule::State {
+                repository_exists: true,
+                is_old_form: false,
+                worktree_checkout: false,
+                superproject_configuration: false,
+            }
+        );
+        assert!(repo.config_snapshot().string("submodule.m1.url").is_none());
+        assert!(
+            repo.config_snapshot().string("submodule.dir/m1.url").is_some(),
+            "other submodules are untouched"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deinit_refuses_modified_submodules_unless_forced() -> crate::Result {
+        let (mut repo, _tmp) = writable_repo("modified-and-untracked")?;
+        let err = repo.submodule_deinit("m1", false).unwrap_err();
+        assert!(matches!(err, gix::submodule::deinit::Error::Modified { .. }));
+        assert!(repo.work_dir().expect("worktree").join("m1/new").is_file());
+
+        repo.submodule_deinit("m1", true)?;
+        assert_eq!(
+            std::fs::read_dir(repo.work_dir().expect("worktree").join("m1"))?.count(),
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove_unregisters_and_unstages() -> crate::Result {
+        let (mut repo, _tmp) = writable_repo("with-submodules")?;
+        repo.submodule_remove("m1", false)?;
+
+        let work_dir = repo.work_dir().expect("worktree").to_owned();
+        assert!(!work_dir.join("m1").exists());
+        assert!(repo.git_dir().join("modules/m1").is_dir(), "the repository is kept");
+        let names: Vec<_> = repo
+            .submodules()?
+            .expect("modules present")
+            .map(|sm| sm.name().to_owned())
+            .collect();
+        assert_eq!(names, ["dir/m1"]);
+        let index = repo.index()?;
+        assert!(index.entry_by_path("m1".into()).is_none());
+        assert!(index.entry_by_path("dir/m1".into()).is_some());
+        assert_eq!(
+            index.entry_by_path(".gitmodules".into()).expect("tracked").id,
+            gix::objs::compute_hash(
+                repo.object_hash(),
+                gix::objs::Kind::Blob,
+                &std::fs::read(work_dir.join(".gitmodules"))?
+            )
+        );
+
+        let err = repo.submodule_remove("m1", false).unwrap_err();
+        assert!(matches!(
+            err,
+            gix::submodule::remove::Error::Deinit(gix::submodule::deinit::Error::NotFound { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn old_form_is_never_removed() -> crate::Result {
+        let (mut repo, _tmp) = writable_repo("old-form")?;
+        let err = repo.submodule_remove("old", true).unwrap_err();
+        assert!(matches!(
+            err,
+            gix::submodule::remove::Error::Deinit(gix::submodule::deinit::Error::OldForm { .. })
+        ));
+        Ok(())
+    }
+}
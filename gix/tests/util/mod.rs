@@ -73,6 +73,29 @@ pub fn repo_rw_opts(name: &str, opts: gix::open::Options) -> Result<(Repository,
     ))
 }
 
+/// Create a writable copy of the `name` repository within `fixture`, which is executed anew to keep its absolute paths valid.
+#[cfg(feature = "rebase")]
+pub fn named_subrepo_rw(fixture: &str, name: &str) -> Result<(Repository, tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable_with_args(
+        fixture,
+        None::<String>,
+        gix_testtools::Creation::ExecuteScript,
+    )?;
+    let repo = gix::open_opts(tmp.path().join(name), restricted())?;
+    Ok((repo, tmp))
+}
+
+/// Return the id of the object that `spec` resolves to in `repo`.
+pub fn rev_parse_id(repo: &Repository, spec: &str) -> Result<gix_hash::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+/// Read the file at `path` relative to the work tree of `repo`.
+#[cfg(feature = "merge")]
+pub fn read_worktree_file(repo: &Repository, path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(repo.work_dir().expect("non-bare").join(path))
+}
+
 pub fn basic_repo() -> Result<Repository> {
     repo("make_basic_repo.sh").map(|r| r.to_thread_local())
 }
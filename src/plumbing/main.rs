@@ -1,5 +1,5 @@
 use std::{
-    io::{stdin, BufReader},
+    io::{stdin, BufRead, BufReader},
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -12,12 +12,12 @@ use anyhow::{anyhow, Context, Result};
 use clap::{CommandFactory, Parser};
 use gitoxide_core as core;
 use gitoxide_core::{pack::verify, repository::PathsOrPatterns};
-use gix::bstr::{io::BufReadExt, BString};
+use gix::bstr::{io::BufReadExt, BString, ByteSlice};
 
 use crate::plumbing::{
     options::{
-        attributes, commit, commitgraph, config, credential, exclude, free, fsck, index, mailmap, odb, revision, tree,
-        Args, Subcommands,
+        attributes, cat, check_attr, check_ignore, commit, commitgraph, config, credential, diff, exclude, fast_export,
+        fast_import, free, fsck, index, mailmap, odb, revision, tree, Args, Subcommands,
     },
     show_progress,
 };
@@ -212,7 +212,10 @@ pub fn main() -> Result<()> {
             submodules,
             no_write,
             pathspec,
+            untracked_files,
             index_worktree_renames,
+            no_renames,
+            rename_limit,
         }) => prepare_and_run(
             "status",
             trace,
@@ -244,12 +247,24 @@ pub fn main() -> Result<()> {
                             crate::plumbing::options::status::Ignored::Collapsed => {
                                 core::repository::status::Ignored::Collapsed
                             }
+                            crate::plumbing::options::status::Ignored::Traditional => {
+                                core::repository::status::Ignored::Traditional
+                            }
+                        }),
+                        untracked_files: untracked_files.map(|untracked| match untracked.unwrap_or_default() {
+                            crate::plumbing::options::status::UntrackedFiles::No => gix::status::UntrackedFiles::None,
+                            crate::plumbing::options::status::UntrackedFiles::Normal => {
+                                gix::status::UntrackedFiles::Collapsed
+                            }
+                            crate::plumbing::options::status::UntrackedFiles::All => gix::status::UntrackedFiles::Files,
                         }),
                         output_format: format,
                         statistics,
                         thread_limit: thread_limit.or(cfg!(target_os = "macos").then_some(3)), // TODO: make this a configurable when in `gix`, this seems to be optimal on MacOS, linux scales though! MacOS also scales if reading a lot of files for refresh index
                         allow_write: !no_write,
                         index_worktree_renames: index_worktree_renames.map(|percentage| percentage.unwrap_or(0.5)),
+                        no_renames,
+                        rename_limit,
                         submodules: submodules.map(|submodules| match submodules {
                             Submodules::All => core::repository::status::Submodules::All,
                             Submodules::RefChange => core::repository::status::Submodules::RefChange,
@@ -288,6 +303,7 @@ pub fn main() -> Result<()> {
             compression_level,
             add_path,
             add_virtual_file,
+            recurse_submodules,
             output_file,
             treeish,
         }) => prepare_and_run(
@@ -312,6 +328,7 @@ pub fn main() -> Result<()> {
                     core::repository::archive::Options {
                         add_paths: add_path,
                         prefix,
+                        recurse_submodules,
                         files: add_virtual_file
                             .chunks_exact(2)
                             .map(|c| (c[0].clone(), c[1].clone()))
@@ -403,7 +420,14 @@ pub fn main() -> Result<()> {
         Subcommands::Clone(crate::plumbing::options::clone::Platform {
             handshake_info,
             bare,
+            mirror,
             no_tags,
+            single_branch,
+            local,
+            no_hardlinks,
+            shared,
+            reference,
+            dissociate,
             ref_name,
             remote,
             shallow,
@@ -412,8 +436,21 @@ pub fn main() -> Result<()> {
             let opts = core::repository::clone::Options {
                 format,
                 bare,
+                mirror,
                 handshake_info,
                 no_tags,
+                single_branch,
+                local_objects: if shared {
+                    gix::clone::LocalObjects::Shared
+                } else if no_hardlinks {
+                    gix::clone::LocalObjects::Copy
+                } else if local {
+                    gix::clone::LocalObjects::Hardlink
+                } else {
+                    gix::clone::LocalObjects::Fetch
+                },
+                reference_repositories: reference,
+                dissociate,
                 ref_name,
                 shallow: shallow.into(),
             };
@@ -434,17 +471,25 @@ pub fn main() -> Result<()> {
             negotiation_info,
             open_negotiation_graph,
             remote,
+            all,
+            jobs,
             shallow,
+            prune,
+            prune_tags,
             ref_spec,
         }) => {
             let opts = core::repository::fetch::Options {
                 format,
                 dry_run,
                 remote,
+                all,
+                jobs,
                 handshake_info,
                 negotiation_info,
                 open_negotiation_graph,
                 shallow: shallow.into(),
+                prune,
+                prune_tags,
                 ref_specs: ref_spec,
             };
             prepare_and_run(
@@ -459,6 +504,35 @@ pub fn main() -> Result<()> {
                 },
             )
         }
+        #[cfg(feature = "gitoxide-core-blocking-client")]
+        Subcommands::Daemon(crate::plumbing::options::daemon::Platform {
+            listen,
+            base_path,
+            export_all,
+            timeout,
+            max_connections,
+            directories,
+        }) => prepare_and_run(
+            "daemon",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, _out, err| {
+                core::daemon::serve(
+                    core::daemon::Options {
+                        listen,
+                        base_path,
+                        directories,
+                        export_all,
+                        timeout: timeout.map(std::time::Duration::from_secs),
+                        max_connections,
+                    },
+                    err,
+                )
+            },
+        ),
         Subcommands::ConfigTree => show_progress(),
         Subcommands::Credential(cmd) => core::repository::credential(
             repository(Mode::StrictWithGitInstallConfig)?,
@@ -476,9 +550,11 @@ pub fn main() -> Result<()> {
         }) => {
             use crate::plumbing::options::remote;
             match cmd {
-                remote::Subcommands::Refs | remote::Subcommands::RefMap { .. } => {
+                remote::Subcommands::Refs { .. } | remote::Subcommands::RefMap { .. } => {
                     let kind = match cmd {
-                        remote::Subcommands::Refs => core::repository::remote::refs::Kind::Remote,
+                        remote::Subcommands::Refs { patterns } => {
+                            core::repository::remote::refs::Kind::Remote { patterns }
+                        }
                         remote::Subcommands::RefMap {
                             ref_spec,
                             show_unmapped_remote_refs,
@@ -533,6 +609,57 @@ pub fn main() -> Result<()> {
                 }
             }
         }
+        #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
+        Subcommands::LsRemote(crate::plumbing::options::ls_remote::Platform {
+            handshake_info,
+            name,
+            patterns,
+        }) => {
+            let kind = core::repository::remote::refs::Kind::Remote { patterns };
+            let context = core::repository::remote::refs::Options {
+                name_or_url: name,
+                format,
+                handshake_info,
+            };
+            #[cfg(feature = "gitoxide-core-blocking-client")]
+            {
+                prepare_and_run(
+                    "ls-remote",
+                    trace,
+                    auto_verbose,
+                    progress,
+                    progress_keep_open,
+                    core::repository::remote::refs::PROGRESS_RANGE,
+                    move |progress, out, err| {
+                        core::repository::remote::refs(
+                            repository(Mode::LenientWithGitInstallConfig)?,
+                            kind,
+                            progress,
+                            out,
+                            err,
+                            context,
+                        )
+                    },
+                )
+            }
+            #[cfg(feature = "gitoxide-core-async-client")]
+            {
+                let (_handle, progress) = async_util::prepare(
+                    auto_verbose,
+                    trace,
+                    "ls-remote",
+                    Some(core::repository::remote::refs::PROGRESS_RANGE),
+                );
+                futures_lite::future::block_on(core::repository::remote::refs(
+                    repository(Mode::LenientWithGitInstallConfig)?,
+                    kind,
+                    progress,
+                    std::io::stdout(),
+                    std::io::stderr(),
+                    context,
+                ))
+            }
+        }
         Subcommands::Config(config::Platform { filter }) => prepare_and_run(
             "config-list",
             trace,
@@ -688,6 +815,10 @@ pub fn main() -> Result<()> {
                     pack_cache_size_mb,
                     counting_threads,
                     object_cache_size_mb,
+                    counts_memory_limit_mb,
+                    no_reuse_delta,
+                    window,
+                    depth,
                     output_directory,
                 } => {
                     let has_tips = !tips.is_empty();
@@ -707,6 +838,9 @@ pub fn main() -> Result<()> {
                                 nondeterministic_thread_count: nondeterministic_count.then_some(counting_threads),
                                 pack_cache_size_in_bytes: pack_cache_size_mb.unwrap_or(0) * 1_000_000,
                                 object_cache_size_in_bytes: object_cache_size_mb.unwrap_or(0) * 1_000_000,
+                                counts_memory_limit_in_bytes: counts_memory_limit_mb.map(|mb| mb * 1_000_000),
+                                delta_search: no_reuse_delta
+                                    .then_some(core::pack::create::DeltaSearch { window, depth }),
                                 statistics: if statistics { Some(format) } else { None },
                                 out,
                                 expansion: expansion.unwrap_or(if has_tips {
@@ -901,6 +1035,7 @@ pub fn main() -> Result<()> {
                     free::pack::index::Subcommands::Create {
                         iteration_mode,
                         pack_path,
+                        fix_thin,
                         directory,
                     } => prepare_and_run(
                         "pack-index-create",
@@ -932,6 +1067,7 @@ pub fn main() -> Result<()> {
                                     format,
                                     out,
                                     object_hash,
+                                    fix_thin,
                                     should_interrupt: &gix::interrupt::IS_INTERRUPTED,
                                 },
                             )
@@ -1111,6 +1247,35 @@ pub fn main() -> Result<()> {
                     )
                 },
             ),
+            commit::Subcommands::NameRev {
+                tags,
+                refs,
+                exclude,
+                name_only,
+                annotate_stdin,
+                rev_specs,
+            } => prepare_and_run(
+                "commit-name-rev",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    core::repository::commit::name_rev(
+                        repository(Mode::Strict)?,
+                        &rev_specs,
+                        annotate_stdin.then(stdin_or_bail).transpose()?,
+                        out,
+                        core::repository::commit::name_rev::Options {
+                            tags_only: tags,
+                            refs,
+                            exclude,
+                            name_only,
+                        },
+                    )
+                },
+            ),
         },
         Subcommands::Tree(cmd) => match cmd {
             tree::Subcommands::Entries {
@@ -1154,6 +1319,38 @@ pub fn main() -> Result<()> {
                 },
             ),
         },
+        Subcommands::Diff(cmd) => match cmd {
+            diff::Subcommands::Tree {
+                stat,
+                numstat,
+                shortstat,
+                old_treeish,
+                new_treeish,
+            } => prepare_and_run(
+                "diff-tree",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| {
+                    use core::repository::diff::Format;
+                    core::repository::diff::tree(
+                        repository(Mode::Lenient)?,
+                        out,
+                        old_treeish,
+                        new_treeish,
+                        match (stat, numstat, shortstat) {
+                            (true, _, _) => Format::Stat,
+                            (_, true, _) => Format::NumStat,
+                            (_, _, true) => Format::ShortStat,
+                            _ => Format::NameStatus,
+                        },
+                        format,
+                    )
+                },
+            ),
+        },
         Subcommands::Odb(cmd) => match cmd {
             odb::Subcommands::Stats { extra_header_lookup } => prepare_and_run(
                 "odb-stats",
@@ -1195,6 +1392,51 @@ pub fn main() -> Result<()> {
                 move |_progress, out, err| core::repository::odb::info(repository(Mode::Strict)?, format, out, err),
             ),
         },
+        Subcommands::Cat(cat::Platform {
+            batch,
+            batch_check,
+            buffer,
+        }) => prepare_and_run(
+            "cat",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                let contents = batch.is_some();
+                core::repository::cat::batch(
+                    repository(Mode::Strict)?,
+                    std::io::stdin().lock(),
+                    out,
+                    core::repository::cat::Options {
+                        format: batch.or(batch_check).flatten(),
+                        contents,
+                        buffer,
+                    },
+                )
+            },
+        ),
+        Subcommands::FastExport(fast_export::Platform { refs }) => prepare_and_run(
+            "fast-export",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| core::repository::fast_import::export(repository(Mode::Strict)?, refs, out),
+        ),
+        Subcommands::FastImport(fast_import::Platform { export_marks }) => prepare_and_run(
+            "fast-import",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::fast_import::import(repository(Mode::Strict)?, std::io::stdin(), export_marks, out)
+            },
+        ),
         Subcommands::Fsck(fsck::Platform { spec }) => prepare_and_run(
             "fsck",
             trace,
@@ -1316,6 +1558,79 @@ pub fn main() -> Result<()> {
                 },
             ),
         },
+        Subcommands::CheckAttr(check_attr::Platform {
+            all,
+            cached,
+            stdin,
+            nul_terminated,
+            mut args,
+            paths,
+        }) => prepare_and_run(
+            "check-attr",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                let (attributes, paths) = if all {
+                    args.extend(paths);
+                    (Vec::new(), args)
+                } else if stdin || !paths.is_empty() || args.is_empty() {
+                    (args, paths)
+                } else {
+                    let paths = args.split_off(1);
+                    (args, paths)
+                };
+                if stdin && !paths.is_empty() {
+                    anyhow::bail!("Paths can't be given on the command-line if they are read from stdin");
+                }
+                core::repository::check_attr(
+                    repository(Mode::Strict)?,
+                    paths_or_stdin(stdin, nul_terminated, paths)?,
+                    out,
+                    core::repository::check_attr::Options {
+                        format,
+                        all,
+                        attributes: attributes
+                            .into_iter()
+                            .map(|name| name.to_str().map(ToOwned::to_owned))
+                            .collect::<Result<_, _>>()?,
+                        cached,
+                        nul_terminated,
+                    },
+                )
+            },
+        ),
+        Subcommands::CheckIgnore(check_ignore::Platform {
+            verbose: print_patterns,
+            non_matching,
+            no_index,
+            stdin,
+            nul_terminated,
+            paths,
+        }) => prepare_and_run(
+            "check-ignore",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::check_ignore(
+                    repository(Mode::Strict)?,
+                    paths_or_stdin(stdin, nul_terminated, paths)?,
+                    out,
+                    core::repository::check_ignore::Options {
+                        format,
+                        verbose: print_patterns,
+                        non_matching,
+                        no_index,
+                        nul_terminated,
+                    },
+                )
+            },
+        ),
         Subcommands::Index(cmd) => match cmd {
             index::Subcommands::Entries {
                 format: entry_format,
@@ -1400,6 +1715,28 @@ pub fn main() -> Result<()> {
     Ok(())
 }
 
+/// Return an iterator over `paths`, or over the paths read from stdin if `stdin` is set, separated by NUL if `nul_terminated`
+/// or by newlines otherwise.
+fn paths_or_stdin(
+    stdin: bool,
+    nul_terminated: bool,
+    paths: Vec<BString>,
+) -> Result<Box<dyn Iterator<Item = std::io::Result<BString>>>> {
+    Ok(if stdin {
+        let separator = if nul_terminated { 0 } else { b'\n' };
+        Box::new(stdin_or_bail()?.split(separator).map(move |path| {
+            path.map(|mut path| {
+                if !nul_terminated && path.last() == Some(&b'\r') {
+                    path.pop();
+                }
+                BString::from(path)
+            })
+        }))
+    } else {
+        Box::new(paths.into_iter().map(Ok))
+    })
+}
+
 fn stdin_or_bail() -> Result<std::io::BufReader<std::io::Stdin>> {
     use is_terminal::IsTerminal;
     if std::io::stdin().is_terminal() {
@@ -161,6 +161,27 @@ pub mod pack {
             /// If unset, no cache will be used.
             object_cache_size_mb: Option<usize>,
 
+            #[clap(long)]
+            /// The amount of megabytes that counted objects may occupy in memory before they are written to disk.
+            ///
+            /// Use this to create packs of repositories with more objects than fit into memory.
+            /// If unset, all counts are kept in memory.
+            counts_memory_limit_mb: Option<usize>,
+
+            #[clap(long)]
+            /// Ignore deltas in existing packs and search for new delta bases instead, like `git repack -f`.
+            ///
+            /// This is much slower, but can produce smaller packs.
+            no_reuse_delta: bool,
+
+            #[clap(long, requires = "no_reuse_delta")]
+            /// The amount of objects to try as delta base for each object, overriding `pack.window`.
+            window: Option<usize>,
+
+            #[clap(long, requires = "no_reuse_delta")]
+            /// The maximum length of delta chains, overriding `pack.depth`.
+            depth: Option<usize>,
+
             #[clap(long)]
             /// if set, delta-objects whose base object wouldn't be in the pack will not be recompressed as base object, but instead
             /// refer to its base object using its object id.
@@ -358,6 +379,13 @@ pub mod pack {
                 #[clap(long, short = 'p')]
                 pack_path: Option<PathBuf>,
 
+                /// The repository to obtain base objects from if the pack is thin, i.e. contains deltas against objects
+                /// that aren't part of the pack.
+                ///
+                /// These base objects are appended to the pack to make it self-contained, similar to `git index-pack --fix-thin`.
+                #[clap(long, value_name = "repository")]
+                fix_thin: Option<PathBuf>,
+
                 /// The folder into which to place the pack and the generated index file
                 ///
                 /// If unset, only informational output will be provided to standard output.
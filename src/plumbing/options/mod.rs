@@ -59,7 +59,7 @@ pub struct Args {
     #[clap(long, conflicts_with("verbose"), requires("progress"))]
     pub progress_keep_open: bool,
 
-    /// Determine the format to use when outputting statistics.
+    /// Determine the format to use for output, with `json` producing documented structures for consumption by tools.
     #[clap(
         long,
         short = 'f',
@@ -91,12 +91,21 @@ pub enum Subcommands {
     Odb(odb::Subcommands),
     /// Check for missing objects.
     Fsck(fsck::Platform),
+    /// Print information about and the contents of objects named on stdin, like `git cat-file --batch`.
+    Cat(cat::Platform),
+    /// Write history as stream understood by `git fast-import`.
+    FastExport(fast_export::Platform),
+    /// Read a stream as produced by `git fast-export` from stdin and create the history it describes.
+    FastImport(fast_import::Platform),
     /// Interact with tree objects.
     #[clap(subcommand)]
     Tree(tree::Subcommands),
     /// Interact with commit objects.
     #[clap(subcommand)]
     Commit(commit::Subcommands),
+    /// Show the differences between trees.
+    #[clap(subcommand)]
+    Diff(diff::Subcommands),
     /// Verify the integrity of the entire repository
     Verify {
         #[clap(flatten)]
@@ -113,18 +122,28 @@ pub enum Subcommands {
     Fetch(fetch::Platform),
     #[cfg(feature = "gitoxide-core-blocking-client")]
     Clone(clone::Platform),
+    /// Serve repositories read-only via the `git://` protocol, like `git daemon`.
+    #[cfg(feature = "gitoxide-core-blocking-client")]
+    Daemon(daemon::Platform),
     /// Interact with the mailmap.
     #[clap(subcommand)]
     Mailmap(mailmap::Subcommands),
     /// Interact with the remote hosts.
     #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
     Remote(remote::Platform),
+    /// List references on a remote along with their symbolic ref targets and peeled objects, like `git ls-remote`.
+    #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
+    LsRemote(ls_remote::Platform),
     /// Interact with the attribute files like .gitattributes.
     #[clap(subcommand, visible_alias = "attrs")]
     Attributes(attributes::Subcommands),
     /// Interact with the exclude files like .gitignore.
     #[clap(subcommand)]
     Exclude(exclude::Subcommands),
+    /// Print the attributes of paths like `git check-attr`.
+    CheckAttr(check_attr::Platform),
+    /// Print which paths are ignored and why like `git check-ignore`, failing if none is.
+    CheckIgnore(check_ignore::Platform),
     #[clap(subcommand)]
     Index(index::Subcommands),
     /// Interact with submodules.
@@ -188,6 +207,9 @@ pub mod archive {
         /// Add the new file from a slash-separated path, which must happen in pairs of two, first the path, then the content.
         #[clap(long, short = 'v')]
         pub add_virtual_file: Vec<String>,
+        /// Also add the worktrees of all active submodules at the commits recorded in the tree, recursively.
+        #[clap(long)]
+        pub recurse_submodules: bool,
         /// The file to write the archive to.
         ///
         /// It's extension determines the archive format, unless `--format` is set.
@@ -226,9 +248,22 @@ pub mod status {
         ///
         /// Simplification will not happen in this mode.
         Matching,
-        // TODO: figure out how to implement traditional, which right now can't be done as it requires ignored folders
-        //       to be fully expanded. This should probably be implemented in `gix_dir` which then simply works by not
-        //       allowing to ignore directories, naturally traversing the entire content.
+        /// Like `git status --ignored=traditional`, show ignored files and directories collapsed, unless
+        /// `--untracked-files=all` is set, in which case exact matches are shown.
+        ///
+        /// Note that ignored directories are not fully expanded as Git would do in that case.
+        Traditional,
+    }
+
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+    pub enum UntrackedFiles {
+        /// Do not show untracked files at all.
+        No,
+        /// Show untracked files and directories, but collapse directories if all of their content is untracked.
+        Normal,
+        /// Show all individual untracked files, which is also what `-u` without a mode means, just like in `git`.
+        #[default]
+        All,
     }
 
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
@@ -249,6 +284,9 @@ pub mod status {
         /// If enabled, show ignored files and directories.
         #[clap(long)]
         pub ignored: Option<Option<Ignored>>,
+        /// Define how untracked files are shown. Defaults to `status.showUntrackedFiles` if unset, and to `all` if no mode is given.
+        #[clap(long, short = 'u')]
+        pub untracked_files: Option<Option<UntrackedFiles>>,
         /// Define how to display the submodule status. Defaults to git configuration if unset.
         #[clap(long)]
         pub submodules: Option<Submodules>,
@@ -259,8 +297,17 @@ pub mod status {
         #[clap(long)]
         pub no_write: bool,
         /// Enable rename tracking between the index and the working tree, preventing the collapse of folders as well.
-        #[clap(long, value_parser = ParseRenameFraction)]
+        #[clap(long, short = 'M', visible_alias = "find-renames", value_parser = ParseRenameFraction)]
         pub index_worktree_renames: Option<Option<f32>>,
+        /// Do not track renames between the index and the working tree, even if `status.renames` or `diff.renames` enable it.
+        #[clap(long, conflicts_with = "index_worktree_renames")]
+        pub no_renames: bool,
+        /// The maximum amount of rename candidates to consider, with 0 meaning no limit.
+        ///
+        /// Applies whenever renames are tracked, including if `status.renames` or `diff.renames` enable it.
+        /// Defaults to `status.renameLimit` or `diff.renameLimit` in that case, and to no limit with `--index-worktree-renames`.
+        #[clap(long)]
+        pub rename_limit: Option<usize>,
         /// The git path specifications to list attributes for, or unset to read from stdin one per line.
         #[clap(value_parser = CheckPathSpec)]
         pub pathspec: Vec<BString>,
@@ -352,12 +399,33 @@ pub mod fetch {
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
+        /// Delete remote-tracking references whose counterpart doesn't exist on the remote anymore.
+        #[clap(long, short = 'p')]
+        pub prune: bool,
+
+        /// Delete local tags that don't exist on the remote anymore, as if `refs/tags/*:refs/tags/*` was a ref-spec.
+        ///
+        /// Implies `--prune`.
+        #[clap(long, short = 'P')]
+        pub prune_tags: bool,
+
         /// The name of the remote to connect to, or the url of the remote to connect to directly.
         ///
         /// If unset, the current branch will determine the remote.
+        /// With `--all`, this is the name of a group of remotes as configured in `remotes.<group>`.
         #[clap(long, short = 'r')]
         pub remote: Option<String>,
 
+        /// Fetch from all remotes that don't have `remote.<name>.skipFetchAll` set, or from all remotes of the group named with `--remote`.
+        #[clap(long, short = 'a', conflicts_with = "ref_spec")]
+        pub all: bool,
+
+        /// The amount of remotes to fetch from in parallel with `--all`, with 0 meaning as many as there are cores.
+        ///
+        /// If unset, `fetch.parallel` is used.
+        #[clap(long, short = 'j', requires = "all")]
+        pub jobs: Option<usize>,
+
         /// Override the built-in and configured ref-specs with one or more of the given ones.
         #[clap(value_parser = crate::shared::AsBString)]
         pub ref_spec: Vec<gix::bstr::BString>,
@@ -408,6 +476,38 @@ pub mod fetch {
     }
 }
 
+#[cfg(feature = "gitoxide-core-blocking-client")]
+pub mod daemon {
+    use std::path::PathBuf;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The address to listen on.
+        #[clap(long, default_value = "0.0.0.0:9418")]
+        pub listen: std::net::SocketAddr,
+
+        /// Interpret all requested paths relative to this directory.
+        #[clap(long, value_name = "DIR")]
+        pub base_path: Option<PathBuf>,
+
+        /// Serve all repositories, not only those with the `git-daemon-export-ok` file in their git directory.
+        #[clap(long)]
+        pub export_all: bool,
+
+        /// Close connections that didn't send or receive anything for the given amount of seconds.
+        #[clap(long, value_name = "SECONDS")]
+        pub timeout: Option<u64>,
+
+        /// The maximum amount of connections to serve at the same time.
+        #[clap(long, default_value_t = 32)]
+        pub max_connections: usize,
+
+        /// If given, only serve repositories within these directories.
+        #[clap(value_name = "DIR")]
+        pub directories: Vec<PathBuf>,
+    }
+}
+
 #[cfg(feature = "gitoxide-core-blocking-client")]
 pub mod clone {
     use std::{ffi::OsString, num::NonZeroU32, path::PathBuf};
@@ -424,10 +524,39 @@ pub mod clone {
         #[clap(long)]
         pub bare: bool,
 
+        /// Mirror all references of the remote into local references of the same name, and delete the ones
+        /// that are gone on the remote when fetching. Implies `--bare`.
+        #[clap(long)]
+        pub mirror: bool,
+
         /// Do not clone any tags. Useful to reduce the size of the clone if only branches are needed.
         #[clap(long)]
         pub no_tags: bool,
 
+        /// Only fetch the branch to check out, and configure the remote to only fetch this branch in future.
+        #[clap(long)]
+        pub single_branch: bool,
+
+        /// If the remote is a local path, hardlink its objects instead of fetching them, or copy them if that isn't possible.
+        #[clap(long, conflicts_with_all = ["no_hardlinks", "shared"])]
+        pub local: bool,
+
+        /// If the remote is a local path, copy its objects instead of fetching them.
+        #[clap(long, conflicts_with = "shared")]
+        pub no_hardlinks: bool,
+
+        /// If the remote is a local path, borrow its objects as alternates instead of fetching them.
+        #[clap(long)]
+        pub shared: bool,
+
+        /// Borrow objects from the repository at the given path as alternates, so they don't have to be fetched.
+        #[clap(long, value_name = "repository")]
+        pub reference: Vec<std::path::PathBuf>,
+
+        /// Copy all objects borrowed from reference repositories or a shared local repository once the clone is done.
+        #[clap(long)]
+        pub dissociate: bool,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
@@ -498,7 +627,11 @@ pub mod remote {
     #[clap(visible_alias = "remotes")]
     pub enum Subcommands {
         /// Print all references available on the remote.
-        Refs,
+        Refs {
+            /// Only print references whose name ends with one of the given patterns, like `main` or `refs/tags/v1.*`.
+            #[clap(value_parser = crate::shared::AsBString)]
+            patterns: Vec<gix::bstr::BString>,
+        },
         /// Print all references available on the remote as filtered through ref-specs.
         RefMap {
             /// Also display remote references that were sent by the server, but filtered by the refspec locally.
@@ -511,6 +644,26 @@ pub mod remote {
     }
 }
 
+pub mod ls_remote {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Output additional typically information provided by the server as part of the connection handshake.
+        #[clap(long, short = 'H')]
+        pub handshake_info: bool,
+
+        /// The name of the remote to connect to, or the URL of the remote to connect to directly.
+        ///
+        /// If unset, the current branch will determine the remote.
+        pub name: Option<String>,
+
+        /// Only print references whose name ends with one of the given patterns, like `main` or `refs/tags/v1.*`.
+        #[clap(value_parser = crate::shared::AsBString)]
+        pub patterns: Vec<BString>,
+    }
+}
+
 pub mod mailmap {
     use gix::bstr::BString;
 
@@ -595,7 +748,8 @@ pub mod odb {
         Entries,
         /// Provide general information about the object database.
         Info,
-        /// Count and obtain information on all, possibly duplicate, objects in the database.
+        /// Count and obtain information on all, possibly duplicate, objects in the database, along with information
+        /// about the database itself, like its packs, alternates and how effective its caches were.
         #[clap(visible_alias = "statistics")]
         Stats {
             /// Lookup headers again, but without preloading indices.
@@ -613,6 +767,49 @@ pub mod fsck {
     }
 }
 
+pub mod cat {
+    #[derive(Debug, clap::Parser)]
+    #[clap(group(clap::ArgGroup::new("mode").required(true)))]
+    pub struct Platform {
+        /// Print a header and the contents of each object named on stdin, one per line.
+        ///
+        /// The header format defaults to `%(objectname) %(objecttype) %(objectsize)`, but may be set with `--batch=<format>`,
+        /// which also supports `%(rest)` for the text after the first whitespace of the input line.
+        #[clap(long, group = "mode", num_args = 0..=1, require_equals = true, value_name = "format")]
+        pub batch: Option<Option<String>>,
+        /// Like `--batch`, but only print the header of each object.
+        #[clap(long, group = "mode", num_args = 0..=1, require_equals = true, value_name = "format")]
+        pub batch_check: Option<Option<String>>,
+        /// Only flush the output once all input was processed, which is faster but prevents interactive use.
+        #[clap(long)]
+        pub buffer: bool,
+    }
+}
+
+pub mod fast_export {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The full names of the references to export, like `refs/heads/main`.
+        ///
+        /// If unset, all local branches and tags will be exported.
+        #[clap(value_parser = crate::shared::AsBString)]
+        pub refs: Vec<BString>,
+    }
+}
+
+pub mod fast_import {
+    use std::path::PathBuf;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Write all marks defined in the stream as `:<mark> <id>` lines to the given file.
+        #[clap(long)]
+        pub export_marks: Option<PathBuf>,
+    }
+}
+
 pub mod tree {
     #[derive(Debug, clap::Subcommand)]
     pub enum Subcommands {
@@ -640,6 +837,34 @@ pub mod tree {
     }
 }
 
+pub mod diff {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// Show the files that changed between two trees, like `git diff --name-status <old> <new>`.
+        Tree {
+            /// Show a histogram of inserted and deleted lines per file, followed by a summary.
+            #[clap(long, conflicts_with_all(["numstat", "shortstat"]))]
+            stat: bool,
+
+            /// Show the amount of inserted and deleted lines per file, with `-` for binary files.
+            #[clap(long, conflicts_with("shortstat"))]
+            numstat: bool,
+
+            /// Show only the summary of changed files, inserted and deleted lines.
+            #[clap(long)]
+            shortstat: bool,
+
+            /// A revspec pointing to the old tree, like `HEAD~1`.
+            old_treeish: BString,
+
+            /// A revspec pointing to the new tree, like `HEAD`.
+            new_treeish: BString,
+        },
+    }
+}
+
 pub mod commit {
     #[derive(Debug, clap::Subcommand)]
     pub enum Subcommands {
@@ -685,6 +910,32 @@ pub mod commit {
             /// A specification of the revision to use, or the current `HEAD` if unset.
             rev_spec: Option<String>,
         },
+        /// Name the given commits relative to the references they are reachable from, like `main~2` or `tags/v1.0~1^2`.
+        NameRev {
+            /// Use tags only to name commits.
+            #[clap(long)]
+            tags: bool,
+
+            /// Only use references matching the given glob pattern, which may be given multiple times.
+            #[clap(long = "refs", value_name = "PATTERN")]
+            refs: Vec<String>,
+
+            /// Don't use references matching the given glob pattern, which may be given multiple times.
+            #[clap(long, value_name = "PATTERN")]
+            exclude: Vec<String>,
+
+            /// Print only the names, without the revisions they name.
+            #[clap(long)]
+            name_only: bool,
+
+            /// Read text from stdin and annotate all full commit ids in it with their names.
+            #[clap(long, conflicts_with("rev_specs"))]
+            annotate_stdin: bool,
+
+            /// Specifications of the commits to name.
+            #[clap(required_unless_present("annotate_stdin"))]
+            rev_specs: Vec<String>,
+        },
     }
 }
 
@@ -855,6 +1106,61 @@ pub mod exclude {
     }
 }
 
+pub mod check_attr {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Print all attributes that are set, unset or have a value, instead of the given ones.
+        #[clap(long, short = 'a')]
+        pub all: bool,
+        /// Only read attributes from the index, ignoring `.gitattributes` files in the worktree.
+        #[clap(long)]
+        pub cached: bool,
+        /// Read paths from stdin, one per line, or separated by NUL if `-z` is given.
+        #[clap(long)]
+        pub stdin: bool,
+        /// Separate output fields with NUL, and input paths as well if `--stdin` is given.
+        #[clap(short = 'z')]
+        pub nul_terminated: bool,
+        /// The attributes to print followed by the worktree-relative paths to print them for, like `<attr>… -- <path>…`.
+        ///
+        /// Without `--`, the first argument is the attribute and all others are paths. With `--all`, all arguments
+        /// are paths, and with `--stdin` all of them are attributes.
+        #[clap(value_parser = crate::shared::AsBString)]
+        pub args: Vec<BString>,
+        /// The worktree-relative paths to print attributes for.
+        #[clap(last = true, value_parser = crate::shared::AsBString)]
+        pub paths: Vec<BString>,
+    }
+}
+
+pub mod check_ignore {
+    use gix::bstr::BString;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// Print the pattern that matched each path along with its source and line number, even if it is negated.
+        #[clap(long, short = 'v')]
+        pub verbose: bool,
+        /// Also print paths that didn't match any pattern, when used with `--verbose`.
+        #[clap(long, short = 'n', requires = "verbose")]
+        pub non_matching: bool,
+        /// Also check tracked paths, which are otherwise never considered to be ignored.
+        #[clap(long)]
+        pub no_index: bool,
+        /// Read paths from stdin, one per line, or separated by NUL if `-z` is given.
+        #[clap(long, conflicts_with = "paths")]
+        pub stdin: bool,
+        /// Separate output fields with NUL, and input paths as well if `--stdin` is given.
+        #[clap(short = 'z')]
+        pub nul_terminated: bool,
+        /// The worktree-relative paths to check.
+        #[clap(value_parser = crate::shared::AsBString, required_unless_present = "stdin")]
+        pub paths: Vec<BString>,
+    }
+}
+
 pub mod index {
     use std::path::PathBuf;
 
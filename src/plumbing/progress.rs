@@ -178,23 +178,11 @@ static GIT_CONFIG: &[Record] = &[
         config: "branch.autoSetupRebase",
         usage: Planned("for when we allow setting up upstream branches")
     },
-    Record {
-        config: "branch.<name>.rebase",
-        usage: Planned("for when we can merge, rebase should be supported")
-    },
     Record {
         config: "branch.<name>.description",
         usage: NotPlanned("no plan to implement format-patch or request-pull summary")
 
     },
-    Record {
-        config: "core.fsync",
-        usage: Planned("more safety for disk write operations is a good thing, definitely on the server")
-    },
-    Record {
-        config: "core.fsyncMethod",
-        usage: Planned("needed to support `core.fsync`")
-    },
     Record {
         config: "core.sharedRepository",
         usage: NotPlanned("on demand")
@@ -398,10 +386,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "http.<url>.*",
         usage: Planned("it's a vital part of git configuration. It's unclear how to get a baseline from git for this one.")
     },
-    Record {
-        config: "init.templateDir",
-        usage: NotPlanned("git expects this dir to be a valid git dir - I'd find additive template dirs more interesting, or changes done afterwards procedurally. Maybe this needs a 'init_or_open' semantic to be really useful")
-    },
     Record {
         config: "sparse.expectFilesOutsideOfPatterns",
         usage: NotPlanned("todo")
@@ -73,12 +73,44 @@ pub fn main() -> Result<()> {
                                 threads,
                             },
                         )?;
-                        match cmd {
-                            None => writeln!(err, "Choose a command for the query engine")?,
-                            Some(crate::porcelain::options::tools::query::Command::TracePath { path }) => {
-                                engine.run(query::Command::TracePath { spec: path }, out, progress)?;
+                        use crate::porcelain::options::tools::query::{Command, Interval, Window};
+                        let window = |Window { since, until }| query::Window { since, until };
+                        let cmd = match cmd {
+                            None => {
+                                writeln!(err, "Choose a command for the query engine")?;
+                                return Ok(());
                             }
-                        }
+                            Some(Command::TracePath { path }) => query::Command::TracePath { spec: path },
+                            Some(Command::FileChurn { window: w, limit }) => query::Command::FileChurn {
+                                window: window(w),
+                                limit,
+                            },
+                            Some(Command::AuthorActivity { window: w, interval }) => query::Command::AuthorActivity {
+                                window: window(w),
+                                interval: match interval {
+                                    Interval::Day => query::Interval::Day,
+                                    Interval::Week => query::Interval::Week,
+                                    Interval::Month => query::Interval::Month,
+                                    Interval::Year => query::Interval::Year,
+                                },
+                            },
+                            Some(Command::BusFactor { window: w, depth }) => query::Command::BusFactor {
+                                window: window(w),
+                                depth,
+                            },
+                            Some(Command::CoChange {
+                                window: w,
+                                min_shared_commits,
+                                max_files_per_commit,
+                                limit,
+                            }) => query::Command::CoChange {
+                                window: window(w),
+                                min_shared_commits,
+                                max_files_per_commit,
+                                limit,
+                            },
+                        };
+                        engine.run(cmd, out, progress)?;
                         Ok(())
                     },
                 )
@@ -120,6 +152,105 @@ pub fn main() -> Result<()> {
                     },
                 )
             }
+            crate::porcelain::options::ToolCommands::RepoSize(crate::porcelain::options::tools::RepoSize {
+                working_dir,
+                limit,
+                refs,
+                format,
+            }) => {
+                use gitoxide_core::repo_size;
+                prepare_and_run(
+                    "repo-size",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    crate::shared::STANDARD_RANGE,
+                    move |progress, out, _err| {
+                        repo_size::show(
+                            gix::discover(working_dir)?,
+                            progress,
+                            out,
+                            repo_size::Options {
+                                format,
+                                threads,
+                                limit,
+                                refs,
+                            },
+                        )
+                    },
+                )
+            }
+            crate::porcelain::options::ToolCommands::RewriteHistory(
+                crate::porcelain::options::tools::RewriteHistory {
+                    working_dir,
+                    paths,
+                    invert_paths,
+                    path_renames,
+                    subdirectory_filter,
+                    keep_empty_commits,
+                    commit_map,
+                    execute,
+                },
+            ) => {
+                use gitoxide_core::rewrite_history;
+                prepare_and_run(
+                    "rewrite-history",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    crate::shared::STANDARD_RANGE,
+                    move |progress, out, err| {
+                        use gix::bstr::ByteSlice;
+                        let rules = match subdirectory_filter {
+                            Some(dir) => rewrite_history::PathRules {
+                                paths: vec![dir.clone()],
+                                invert_paths: false,
+                                renames: vec![(dir, Default::default())],
+                            },
+                            None => rewrite_history::PathRules {
+                                paths,
+                                invert_paths,
+                                renames: path_renames
+                                    .into_iter()
+                                    .map(|rename| {
+                                        rename
+                                            .split_once_str(":")
+                                            .map(|(from, to)| (from.into(), to.into()))
+                                            .ok_or_else(|| anyhow!("Rename '{rename}' must have the form <old>:<new>"))
+                                    })
+                                    .collect::<Result<_>>()?,
+                            },
+                        };
+                        let repo = gix::discover(working_dir)?;
+                        let outcome = rewrite_history::rewrite(
+                            &repo,
+                            progress,
+                            rewrite_history::Options {
+                                rules,
+                                keep_empty_commits,
+                                update_refs: execute,
+                            },
+                        )?;
+                        if let Some(path) = commit_map {
+                            rewrite_history::write_commit_map(
+                                &outcome,
+                                repo.object_hash(),
+                                std::io::BufWriter::new(std::fs::File::create(path)?),
+                            )?;
+                        }
+                        rewrite_history::write_ref_map(&outcome, repo.object_hash(), out)?;
+                        if !execute {
+                            writeln!(
+                                err,
+                                "Rewritten objects were written, but references are only updated with --execute"
+                            )?;
+                        }
+                        Ok(())
+                    },
+                )
+            }
             crate::porcelain::options::ToolCommands::Find { root, debug } => {
                 use gitoxide_core::organize;
                 prepare_and_run(
@@ -92,6 +92,8 @@ pub enum ToolCommands {
     #[cfg(feature = "gitoxide-core-tools-query")]
     Query(tools::Query),
     EstimateHours(tools::EstimateHours),
+    RepoSize(tools::RepoSize),
+    RewriteHistory(tools::RewriteHistory),
 }
 
 #[cfg(feature = "gitoxide-core-tools")]
@@ -126,7 +128,7 @@ pub mod tools {
 
     #[cfg(feature = "gitoxide-core-tools-query")]
     pub mod query {
-        use crate::shared::AsPathSpec;
+        use crate::shared::{AsPathSpec, AsTime};
 
         #[derive(Debug, clap::Subcommand)]
         pub enum Command {
@@ -137,6 +139,68 @@ pub mod tools {
                 #[clap(value_parser = AsPathSpec)]
                 path: gix::pathspec::Pattern,
             },
+            /// List the files with the most added and removed lines, along with the amount of commits and authors.
+            FileChurn {
+                #[clap(flatten)]
+                window: Window,
+                /// The maximum amount of files to list.
+                #[clap(long, short = 'n', default_value_t = 20)]
+                limit: usize,
+            },
+            /// Show a histogram of the commits of each author over time.
+            AuthorActivity {
+                #[clap(flatten)]
+                window: Window,
+                /// The time span each bar of the histogram represents.
+                #[clap(long, short = 'i', value_enum, default_value_t = Interval::Month)]
+                interval: Interval,
+            },
+            /// Show the smallest amount of authors who together changed at least half of the lines in each directory.
+            ///
+            /// Directories with a low bus factor depend on the knowledge of only a few people.
+            BusFactor {
+                #[clap(flatten)]
+                window: Window,
+                /// The amount of leading path components that make up a directory.
+                #[clap(long, short = 'd', default_value_t = 1)]
+                depth: usize,
+            },
+            /// List pairs of files that are frequently changed in the same commit.
+            ///
+            /// The degree of coupling is the amount of shared commits relative to the average amount of commits of both files.
+            CoChange {
+                #[clap(flatten)]
+                window: Window,
+                /// The minimum amount of commits that changed both files.
+                #[clap(long, short = 's', default_value_t = 3)]
+                min_shared_commits: usize,
+                /// Ignore commits that change more files than this, as they tend to couple unrelated files.
+                #[clap(long, default_value_t = 30)]
+                max_files_per_commit: usize,
+                /// The maximum amount of pairs to list.
+                #[clap(long, short = 'n', default_value_t = 20)]
+                limit: usize,
+            },
+        }
+
+        /// The commits to consider based on the time they were authored.
+        #[derive(Debug, clap::Args)]
+        pub struct Window {
+            /// Ignore commits authored before the given date.
+            #[clap(long, value_parser = AsTime, value_name = "DATE")]
+            pub since: Option<gix::date::Time>,
+            /// Ignore commits authored after the given date.
+            #[clap(long, value_parser = AsTime, value_name = "DATE")]
+            pub until: Option<gix::date::Time>,
+        }
+
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+        pub enum Interval {
+            Day,
+            /// Weeks start on Monday.
+            Week,
+            Month,
+            Year,
         }
     }
 
@@ -175,6 +239,67 @@ pub mod tools {
         pub omit_unify_identities: bool,
     }
 
+    #[derive(Debug, clap::Parser)]
+    #[clap(
+        about = "Show which objects, paths, file extensions and references take up the most space",
+        long_about = "Similar to https://github.com/github/git-sizer, sizes are those of decompressed objects",
+        visible_alias = "size"
+    )]
+    pub struct RepoSize {
+        /// The directory containing a '.git/' folder.
+        #[clap(value_parser = validator::IsRepo)]
+        #[clap(default_value = ".")]
+        pub working_dir: PathBuf,
+        /// The amount of entries to show in each list of largest objects, paths and extensions.
+        #[clap(short = 'n', long, default_value_t = 10)]
+        pub limit: usize,
+        /// Determine how much data is only reachable from each reference, which traverses the history of each reference.
+        #[clap(short = 'r', long)]
+        pub refs: bool,
+        /// Determine the format to use when outputting the report.
+        #[clap(
+            long,
+            short = 'f',
+            default_value = "human",
+            value_parser = crate::shared::AsOutputFormat
+        )]
+        pub format: gitoxide_core::OutputFormat,
+    }
+
+    #[derive(Debug, clap::Parser)]
+    #[clap(
+        about = "Rewrite history to keep, drop or rename paths, like extracting a subdirectory into its own repository",
+        long_about = "Similar to https://github.com/newren/git-filter-repo. Prints '<old> <new> <ref>' for each changed reference.",
+        visible_alias = "filter-repo"
+    )]
+    pub struct RewriteHistory {
+        /// The directory containing a '.git/' folder.
+        #[clap(value_parser = validator::IsRepo)]
+        #[clap(default_value = ".")]
+        pub working_dir: PathBuf,
+        /// A file or directory to keep, or to drop if `--invert-paths` is set. Can be specified multiple times.
+        #[clap(long = "path", short = 'p', value_parser = crate::shared::AsBString)]
+        pub paths: Vec<BString>,
+        /// Drop the paths given with `--path` and keep everything else instead.
+        #[clap(long, requires = "paths")]
+        pub invert_paths: bool,
+        /// Rename a file or directory as `<old>:<new>`, with an empty `<new>` moving contents to the root.
+        #[clap(long = "path-rename", value_parser = crate::shared::AsBString)]
+        pub path_renames: Vec<BString>,
+        /// Keep only the given directory and make it the root of the tree, like `--path <dir> --path-rename <dir>:`.
+        #[clap(long, value_parser = crate::shared::AsBString, conflicts_with_all = ["paths", "path_renames"])]
+        pub subdirectory_filter: Option<BString>,
+        /// Keep commits which become empty after rewriting.
+        #[clap(long)]
+        pub keep_empty_commits: bool,
+        /// Write a mapping of all rewritten commits as '<old> <new>' lines to the given file.
+        #[clap(long)]
+        pub commit_map: Option<PathBuf>,
+        /// Update all references to point to the rewritten history. Otherwise, only new objects are written.
+        #[clap(long)]
+        pub execute: bool,
+    }
+
     mod validator {
         use std::{ffi::OsStr, path::PathBuf};
 
@@ -171,6 +171,217 @@ title "Porcelain ${kind}"
     )
   )
 
+  (when "using the 'tool' subcommand on a repository with known content"
+    snapshot="$snapshot/tool"
+    (sandbox
+      {
+        git init
+        git checkout -b main
+        git config commit.gpgsign false
+        git config tag.gpgsign false
+        mkdir src doc
+        echo "aaaaaaaaaa" > src/a.rs
+        echo "bbbbb" > doc/b.md
+        git add .
+        git commit -m "first"
+        echo "aaaaaaaaaa" >> src/a.rs
+        git commit -am "second"
+        git tag -m "tag message" v1
+        git checkout -b side
+        echo "x" > side.txt
+        git add side.txt
+        git commit -m "side"
+        git checkout main
+      } &>/dev/null
+
+      title "ein tool repo-size"
+      (when "running 'repo-size'"
+        snapshot="$snapshot/repo-size"
+        (with "no arguments"
+          it "succeeds and shows the totals of each kind along with the largest objects, paths and extensions" && {
+            WITH_SNAPSHOT="$snapshot/no-args-success" \
+            expect_run_sh $SUCCESSFULLY "$exe tool repo-size 2>/dev/null"
+          }
+        )
+        (with "--refs and --limit"
+          it "succeeds and attributes objects to references" && {
+            WITH_SNAPSHOT="$snapshot/refs-with-limit-success" \
+            expect_run_sh $SUCCESSFULLY "$exe tool repo-size --refs --limit 2 2>/dev/null"
+          }
+        )
+        (with "--format json"
+          it "succeeds and has the same totals" && {
+            WITH_SNAPSHOT="$snapshot/json-totals-success" \
+            expect_run_sh $SUCCESSFULLY "$exe tool repo-size --refs --limit 1 --format json 2>/dev/null | jq -c '.objects, .largest_blobs, .refs[]'"
+          }
+        )
+      )
+    )
+
+      title "ein tool rewrite-history"
+      (when "running 'rewrite-history'"
+        snapshot="$snapshot/rewrite-history"
+        function repo-with-lib-and-docs() {
+          {
+            git init
+            git checkout -b main
+            git config commit.gpgsign false
+            git config tag.gpgsign false
+            mkdir lib docs
+            echo "a" > lib/a.txt
+            echo "readme" > docs/readme.md
+            echo "root" > root.txt
+            git add .
+            git commit -m "first"
+            echo "more" >> docs/readme.md
+            git commit -am "docs only"
+            git branch docs-branch
+            echo "b" >> lib/a.txt
+            git commit -am "lib change"
+            git tag -m "release" v1
+          } &>/dev/null
+        }
+        (with "--path"
+          (sandbox
+            repo-with-lib-and-docs
+            it "succeeds and prints the mapping of references without changing them" && {
+              WITH_SNAPSHOT="$snapshot/path-dry-run-success" \
+              expect_run_sh $SUCCESSFULLY "$exe tool rewrite-history --path lib --commit-map ../commit-map 2>/dev/null && git log --format=%s main"
+            }
+            it "maps dropped commits to their rewritten ancestor" && {
+              expect_run_sh $SUCCESSFULLY "test \"\$(grep \$(git rev-parse docs-branch) ../commit-map | cut -d' ' -f2)\" = \"\$(grep \$(git rev-parse main~2) ../commit-map | cut -d' ' -f2)\""
+            }
+            (with "--execute"
+              it "succeeds and keeps only the given path in all commits" && {
+                WITH_SNAPSHOT="$snapshot/path-execute-success" \
+                expect_run_sh $SUCCESSFULLY "$exe tool rewrite-history --path lib --execute 2>/dev/null && git log --format=%s main && git ls-tree -r --name-only main"
+              }
+              it "points branches of dropped commits to their rewritten ancestor" && {
+                WITH_SNAPSHOT="$snapshot/path-execute-dropped-commit" \
+                expect_run_sh $SUCCESSFULLY "git log --format=%s docs-branch"
+              }
+              it "rewrites annotated tags to point to the rewritten commit" && {
+                WITH_SNAPSHOT="$snapshot/path-execute-tag" \
+                expect_run_sh $SUCCESSFULLY "git cat-file -t v1 && test \$(git rev-parse v1^{commit}) = \$(git rev-parse main) && git cat-file -p v1 | tail -n 3"
+              }
+              it "updates the index and the worktree of the checked-out branch" && {
+                WITH_SNAPSHOT="$snapshot/path-execute-worktree" \
+                expect_run_sh $SUCCESSFULLY "git status --porcelain && find . -path ./.git -prune -o -type f -print | sort"
+              }
+            )
+          )
+        )
+        (with "--subdirectory-filter"
+          (sandbox
+            repo-with-lib-and-docs
+            git checkout --detach main &>/dev/null
+            (with "--execute and a detached HEAD"
+              it "succeeds and moves the contents of the directory to the root" && {
+                WITH_SNAPSHOT="$snapshot/subdirectory-filter-execute-success" \
+                expect_run_sh $SUCCESSFULLY "$exe tool rewrite-history --subdirectory-filter lib --execute 2>/dev/null | cut -d' ' -f3 && git ls-tree -r --name-only main"
+              }
+              it "rewrites the detached HEAD and updates the worktree" && {
+                WITH_SNAPSHOT="$snapshot/subdirectory-filter-execute-worktree" \
+                expect_run_sh $SUCCESSFULLY "test \$(git rev-parse HEAD) = \$(git rev-parse main) && git status --porcelain && find . -path ./.git -prune -o -type f -print | sort"
+              }
+            )
+          )
+        )
+        (with "--path-rename of a file to the root"
+          (sandbox
+            repo-with-lib-and-docs
+            it "fails with a helpful message" && {
+              WITH_SNAPSHOT="$snapshot/path-rename-file-to-root-failure" \
+              expect_run_sh $WITH_FAILURE "$exe -q tool rewrite-history --path-rename root.txt:"
+            }
+          )
+        )
+        (with "--execute and local changes"
+          (sandbox
+            repo-with-lib-and-docs
+            echo "change" >> lib/a.txt
+            it "fails with a helpful message" && {
+              WITH_SNAPSHOT="$snapshot/execute-with-local-changes-failure" \
+              expect_run_sh $WITH_FAILURE "$exe -q tool rewrite-history --path lib --execute"
+            }
+            it "doesn't change any reference" && {
+              WITH_SNAPSHOT="$snapshot/execute-with-local-changes-unchanged" \
+              expect_run_sh $SUCCESSFULLY "git log --format=%s main"
+            }
+          )
+        )
+      )
+
+      title "ein tool query"
+      (when "running 'query' on a repository with known authors and changes"
+        snapshot="$snapshot/query"
+        (sandbox
+          {
+            function commit-as() {
+              local name="${1:?}" date="${2:?}" message="${3:?}"
+              GIT_AUTHOR_NAME="$name" GIT_AUTHOR_EMAIL="$name@example.com" GIT_AUTHOR_DATE="$date" \
+                git commit -q -m "$message"
+            }
+            git init
+            git config commit.gpgsign false
+            mkdir src doc
+            printf '1\n2\n3\n4\n' > src/a.rs
+            printf '1\n2\n' > src/b.rs
+            printf 'readme\n' > doc/readme.md
+            git add . && commit-as alice "2020-01-06 10:00:00 +0000" "first"
+            printf '1\n2\nthree\n4\n5\n' > src/a.rs
+            printf '1\ntwo\n' > src/b.rs
+            git add . && commit-as bob "2020-01-08 10:00:00 +0000" "change a and b"
+            printf 'one\n2\nthree\n4\n5\n' > src/a.rs
+            printf 'one\ntwo\n' > src/b.rs
+            git add . && commit-as alice "2020-02-03 10:00:00 +0000" "change a and b again"
+            printf 'one\n2\nthree\nfour\n5\n' > src/a.rs
+            printf 'one\ntwo\n3\n' > src/b.rs
+            git add . && commit-as carol "2020-02-04 10:00:00 +0000" "change a and b once more"
+            printf 'readme\nmore\n' > doc/readme.md
+            git add . && commit-as carol "2020-03-02 10:00:00 +0000" "docs"
+          } &>/dev/null
+
+          (with "file-churn"
+            it "lists added and removed lines, commits and authors per file" && {
+              WITH_SNAPSHOT="$snapshot/file-churn" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query file-churn 2>/dev/null"
+            }
+          )
+          (with "file-churn --since"
+            it "only considers commits authored after the given date" && {
+              WITH_SNAPSHOT="$snapshot/file-churn-since" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query file-churn --since 2020-02-01 2>/dev/null"
+            }
+          )
+          (with "author-activity"
+            it "shows the commits of each author per month" && {
+              WITH_SNAPSHOT="$snapshot/author-activity" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query author-activity 2>/dev/null"
+            }
+          )
+          (with "author-activity --interval week"
+            it "shows the commits of each author per week" && {
+              WITH_SNAPSHOT="$snapshot/author-activity-week" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query author-activity --interval week 2>/dev/null"
+            }
+          )
+          (with "bus-factor"
+            it "shows the smallest amount of authors owning half of the changed lines per directory" && {
+              WITH_SNAPSHOT="$snapshot/bus-factor" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query bus-factor 2>/dev/null"
+            }
+          )
+          (with "co-change"
+            it "lists files that are changed together" && {
+              WITH_SNAPSHOT="$snapshot/co-change" \
+              expect_run_sh $SUCCESSFULLY "$exe -q tool query co-change 2>/dev/null"
+            }
+          )
+        )
+      )
+  )
+
   title "ein init"
   (when "running 'init'"
     snapshot="$snapshot/init"
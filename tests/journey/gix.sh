@@ -167,6 +167,360 @@ title "gix attributes"
   )
 )
 
+title "gix status"
+(when "running 'status'"
+  snapshot="$snapshot/repository/status"
+  (sandbox
+    {
+      git init
+      git config commit.gpgsign false
+      echo "content that is long enough to be found as rename" > tracked
+      git add tracked
+      git commit -m "first"
+      mv tracked renamed
+      mkdir untracked
+      touch untracked/one untracked/two
+    } &>/dev/null
+
+    (with "no arguments"
+      it "collapses untracked directories and doesn't track renames" && {
+        WITH_SNAPSHOT="$snapshot/no-arguments" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write
+      }
+    )
+    (with "-u without a mode"
+      it "shows all untracked files like 'git status -u'" && {
+        WITH_SNAPSHOT="$snapshot/untracked-files-all" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write -u
+      }
+    )
+    (with "--untracked-files=normal"
+      it "collapses untracked directories" && {
+        WITH_SNAPSHOT="$snapshot/untracked-files-normal" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write --untracked-files=normal
+      }
+    )
+    (with "--untracked-files=no"
+      it "doesn't show untracked files" && {
+        WITH_SNAPSHOT="$snapshot/untracked-files-no" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write --untracked-files=no
+      }
+    )
+    (with "-M"
+      it "shows the rename" && {
+        WITH_SNAPSHOT="$snapshot/renames" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write -M
+      }
+      (with "--rename-limit"
+        it "shows the rename as there is only one candidate" && {
+          WITH_SNAPSHOT="$snapshot/renames" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose status --no-write -M --rename-limit 1
+        }
+      )
+      (with "--no-renames"
+        it "fails as both can't be used together" && {
+          expect_run $WITH_CLAP_FAILURE "$exe_plumbing" --no-verbose status --no-write -M --no-renames
+        }
+      )
+    )
+    (with "status.renames=true"
+      it "shows the rename" && {
+        WITH_SNAPSHOT="$snapshot/renames" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose -c status.renames=true status --no-write
+      }
+      (with "--no-renames"
+        it "doesn't track renames" && {
+          WITH_SNAPSHOT="$snapshot/no-arguments" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose -c status.renames=true status --no-write --no-renames
+        }
+      )
+    )
+  )
+  (sandbox
+    {
+      git init
+      git config commit.gpgsign false
+      seq 20 > a
+      seq 30 > b
+      git add a b
+      git commit -m "first"
+      mv a a-renamed
+      mv b b-renamed
+      echo 21 >> a-renamed
+      echo 31 >> b-renamed
+    } &>/dev/null
+
+    (with "status.renames=true and --rename-limit"
+      it "doesn't track renames as there are more candidates than allowed" && {
+        WITH_SNAPSHOT="$snapshot/rename-limit-exceeded" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose -c status.renames=true status --no-write --rename-limit 1
+      }
+    )
+  )
+)
+
+title "gix check-attr and check-ignore"
+(when "running 'check-attr' and 'check-ignore'"
+  snapshot="$snapshot/repository/check"
+  (sandbox
+    {
+      git init
+      printf '*.txt text diff=plain\n*.bin -text binary\ndoc/** export-ignore\n' > .gitattributes
+      printf '*.log\n!keep.log\nbuild/\n' > .gitignore
+      mkdir doc
+      printf '*.tmp\n' > doc/.gitignore
+    } &>/dev/null
+
+    (with "check-attr and attributes with paths"
+      it "prints each given attribute per path like 'git check-attr'" && {
+        WITH_SNAPSHOT="$snapshot/attr-paths" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose check-attr text diff -- a.txt b.bin doc/c.md
+      }
+    )
+    (with "check-attr --all"
+      it "prints all set attributes per path" && {
+        WITH_SNAPSHOT="$snapshot/attr-all" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose check-attr -a a.txt b.bin doc/c.md
+      }
+    )
+    (with "check-attr --stdin -z"
+      it "reads NUL-separated paths and separates all output fields with NUL" && {
+        WITH_SNAPSHOT="$snapshot/attr-stdin-z" \
+        expect_run_sh $SUCCESSFULLY "printf 'a.txt\0b.bin\0' | $exe_plumbing --no-verbose check-attr --stdin -z text | tr '\0' '|'"
+      }
+    )
+    (with "check-ignore and paths"
+      it "prints the ignored paths only like 'git check-ignore'" && {
+        WITH_SNAPSHOT="$snapshot/ignore-paths" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose check-ignore a.log keep.log build/x doc/y.tmp a.txt
+      }
+      (with "--verbose --non-matching"
+        it "prints the matching pattern with its source, and non-matching paths" && {
+          WITH_SNAPSHOT="$snapshot/ignore-verbose-non-matching" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose check-ignore -v -n a.log keep.log doc/y.tmp a.txt
+        }
+      )
+    )
+    (with "check-ignore --stdin -z --verbose"
+      it "reads NUL-separated paths and separates all output fields with NUL" && {
+        WITH_SNAPSHOT="$snapshot/ignore-stdin-z-verbose" \
+        expect_run_sh $SUCCESSFULLY "printf 'a.log\0keep.log\0' | $exe_plumbing --no-verbose check-ignore --stdin -z -v | tr '\0' '|'"
+      }
+    )
+    (with "check-ignore and no ignored path"
+      it "fails like 'git check-ignore'" && {
+        expect_run $WITH_FAILURE "$exe_plumbing" --no-verbose check-ignore a.txt
+      }
+    )
+  )
+)
+
+if test "$kind" = "max" || test "$kind" = "max-pure"; then
+title "gix --format json"
+(when "running commands with '--format json'"
+  snapshot="$snapshot/repository/json"
+  (with_program jq
+    (sandbox
+      {
+        git init
+        git config commit.gpgsign false
+        echo '*.txt text' > .gitattributes
+        echo '*.log' > .gitignore
+        echo 'A <a@example.com> <old@example.com>' > .mailmap
+        echo content > a.txt
+        mkdir dir && echo content > dir/b
+        git add . && git commit -m first
+        echo changed > dir/b
+        git commit -am second
+        git commit-graph write --reachable
+        echo changed > a.txt
+        echo new > untracked
+      } &>/dev/null
+
+      (with "status"
+        it "emits the changes as JSON array" && {
+          WITH_SNAPSHOT="$snapshot/status" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json status --no-write 2>/dev/null | jq -c '.[]'"
+        }
+      )
+      (with "is-changed"
+        it "emits whether the worktree is dirty" && {
+          WITH_SNAPSHOT="$snapshot/is-changed" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json is-changed | jq -c ."
+        }
+      )
+      (with "revision list"
+        it "emits each commit with its parents" && {
+          WITH_SNAPSHOT="$snapshot/revision-list" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json revision list HEAD | jq -c '.[] | [.id, .parent_ids]'"
+        }
+      )
+      (with "commit-graph list"
+        it "emits each commit with its generation" && {
+          WITH_SNAPSHOT="$snapshot/commit-graph-list" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json commit-graph list HEAD | jq -c '.[] | [.id, .num_parents, .graph.generation]'"
+        }
+      )
+      (with "tree entries"
+        it "emits each entry with its path" && {
+          WITH_SNAPSHOT="$snapshot/tree-entries" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json tree entries -r | jq -c '.[] | [.kind, .path]'"
+        }
+      )
+      (with "mailmap entries"
+        it "emits each mapping" && {
+          WITH_SNAPSHOT="$snapshot/mailmap-entries" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json mailmap entries | jq -c '.[]'"
+        }
+      )
+      (with "exclude query"
+        it "emits the matching pattern per path" && {
+          WITH_SNAPSHOT="$snapshot/exclude-query" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json exclude query a.log untracked | jq -c '.[] | [.path, .pattern.pattern]'"
+        }
+      )
+      (with "attributes query"
+        it "emits the matching assignments per path" && {
+          WITH_SNAPSHOT="$snapshot/attributes-query" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json attributes query a.txt | jq -c '.[] | [.path, .pattern, .assignment]'"
+        }
+      )
+      (with "check-attr"
+        it "emits the state of each attribute per path" && {
+          WITH_SNAPSHOT="$snapshot/check-attr" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json check-attr text -- a.txt dir/b | jq -c '.[]'"
+        }
+      )
+      (with "check-ignore --verbose --non-matching"
+        it "emits whether each path is ignored and why" && {
+          WITH_SNAPSHOT="$snapshot/check-ignore" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json check-ignore -v -n a.log a.txt | jq -c '.[]'"
+        }
+      )
+      (with "submodule list"
+        it "emits an empty array without submodules" && {
+          WITH_SNAPSHOT="$snapshot/submodule-list" \
+          expect_run_sh $SUCCESSFULLY "$exe_plumbing --no-verbose --format json submodule list | jq -c ."
+        }
+      )
+    )
+  )
+)
+fi
+
+title "gix diff tree"
+(when "running 'diff tree'"
+  snapshot="$snapshot/repository/diff/tree"
+  (sandbox
+    {
+      git init
+      git config commit.gpgsign false
+      mkdir dir
+      seq 1 20 > dir/long-file-name.txt
+      seq 1 5 > short
+      seq 1 20 > odd
+      printf 'a\0b' > bin
+      echo gone > removed
+      git add . && git commit -m first
+      seq 1 2 30 > odd
+      seq 1 40 > short
+      seq 1 300 > big
+      printf 'a\0bc' > bin
+      git rm removed
+      mkdir -p a/deep
+      git mv dir/long-file-name.txt a/deep/renamed-file-name.txt
+      git add . && git commit -m second
+    } &>/dev/null
+    unset COLUMNS
+
+    (with "--stat"
+      it "shows the same histogram as 'git diff --stat'" && {
+        WITH_SNAPSHOT="$snapshot/stat" \
+        expect_run $SUCCESSFULLY git diff --stat HEAD~1 HEAD
+        WITH_SNAPSHOT="$snapshot/stat" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose diff tree --stat HEAD~1 HEAD
+      }
+      (with "COLUMNS=40"
+        it "shortens paths and the histogram like 'git diff --stat'" && {
+          WITH_SNAPSHOT="$snapshot/stat-40-columns" \
+          expect_run_sh $SUCCESSFULLY "COLUMNS=40 git diff --stat HEAD~1 HEAD"
+          WITH_SNAPSHOT="$snapshot/stat-40-columns" \
+          expect_run_sh $SUCCESSFULLY "COLUMNS=40 $exe_plumbing --no-verbose diff tree --stat HEAD~1 HEAD"
+        }
+      )
+    )
+    (with "--numstat"
+      it "shows the same counts as 'git diff --numstat'" && {
+        WITH_SNAPSHOT="$snapshot/numstat" \
+        expect_run $SUCCESSFULLY git diff --numstat HEAD~1 HEAD
+        WITH_SNAPSHOT="$snapshot/numstat" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose diff tree --numstat HEAD~1 HEAD
+      }
+    )
+    (with "--shortstat"
+      it "shows the same summary as 'git diff --shortstat'" && {
+        WITH_SNAPSHOT="$snapshot/shortstat" \
+        expect_run $SUCCESSFULLY git diff --shortstat HEAD~1 HEAD
+        WITH_SNAPSHOT="$snapshot/shortstat" \
+        expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose diff tree --shortstat HEAD~1 HEAD
+      }
+    )
+  )
+)
+
+title "gix daemon"
+(when "running 'daemon'"
+  snapshot="$snapshot/daemon"
+  (sandbox
+    {
+      mkdir served
+      git init served/exported
+      (cd served/exported && git commit --allow-empty -m "exported" && touch .git/git-daemon-export-ok)
+      git init served/not-exported
+      (cd served/not-exported && git commit --allow-empty -m "not exported")
+      git init --bare served/bare.git
+      git -C served/bare.git fetch ../exported HEAD:refs/heads/main
+      git -C served/bare.git symbolic-ref HEAD refs/heads/main
+      touch served/bare.git/git-daemon-export-ok
+      git init outside
+      (cd outside && git commit --allow-empty -m "outside" && touch .git/git-daemon-export-ok)
+    } &>/dev/null
+
+    port=9428
+    "$exe_plumbing" daemon --listen "127.0.0.1:$port" --base-path "$PWD" "$PWD/served" &>/dev/null &
+    daemon_pid=$!
+    trap 'kill $daemon_pid' EXIT
+    while ! (exec 3<>"/dev/tcp/127.0.0.1/$port") 2>/dev/null; do
+      sleep 0.1
+    done
+
+    (with "an exported repository"
+      it "can be cloned" && {
+        WITH_SNAPSHOT="$snapshot/clone-exported-success" \
+        expect_run_sh $SUCCESSFULLY "git clone -q git://127.0.0.1:$port/served/exported clone && git -C clone log --format=%s"
+      }
+    )
+    (with "an exported bare repository named without its '.git' suffix"
+      it "can be cloned" && {
+        WITH_SNAPSHOT="$snapshot/clone-bare-success" \
+        expect_run_sh $SUCCESSFULLY "git clone -q git://127.0.0.1:$port/served/bare bare-clone && git -C bare-clone log --format=%s"
+      }
+    )
+    (with "a repository that isn't exported"
+      it "refuses to serve it" && {
+        WITH_SNAPSHOT="$snapshot/clone-not-exported-failure" \
+        expect_run_sh 128 "git clone -q git://127.0.0.1:$port/served/not-exported not-exported-clone 2>&1"
+      }
+    )
+    (with "an exported repository outside of the allowed directories"
+      it "refuses to serve it" && {
+        WITH_SNAPSHOT="$snapshot/clone-outside-failure" \
+        expect_run_sh 128 "git clone -q git://127.0.0.1:$port/outside outside-clone 2>&1"
+      }
+    )
+  )
+)
+
 title "gix commit-graph"
 (when "running 'commit-graph'"
   snapshot="$snapshot/commit-graph"